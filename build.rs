@@ -0,0 +1,49 @@
+//! Build script: records the versions of the font compiler crates
+//!
+//! The compile cache keys builds by the compiler that made them
+//! (`data::compile_cache`), so `BEZY_FONTC_VERSION` is set to the fontc,
+//! fontir and fontbe versions resolved in Cargo.lock, e.g.
+//! "fontc 0.3.2, fontir 0.3.1, fontbe 0.2.3". Updating any of them changes
+//! the key and the cached binaries are compiled again.
+
+use std::path::PathBuf;
+
+/// Crates whose output ends up in a compiled font
+const COMPILER_CRATES: [&str; 3] = ["fontc", "fontir", "fontbe"];
+
+fn main() {
+    let lock = lock_file();
+    if let Some(lock) = &lock {
+        println!("cargo:rerun-if-changed={}", lock.display());
+    }
+    let contents = lock
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .unwrap_or_default();
+    let versions: Vec<String> = COMPILER_CRATES
+        .iter()
+        .map(|name| {
+            let version = locked_version(&contents, name).unwrap_or("unknown");
+            format!("{name} {version}")
+        })
+        .collect();
+    println!("cargo:rustc-env=BEZY_FONTC_VERSION={}", versions.join(", "));
+}
+
+/// Cargo.lock of the package or the workspace it's in
+fn lock_file() -> Option<PathBuf> {
+    let manifest_dir = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR")?);
+    manifest_dir
+        .ancestors()
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|path| path.exists())
+}
+
+/// The version of a package in a Cargo.lock, the first if it's there more
+/// than once
+fn locked_version<'a>(lock: &'a str, name: &str) -> Option<&'a str> {
+    let name_line = format!("name = \"{name}\"");
+    let mut lines = lock.lines();
+    lines.find(|line| line.trim() == name_line)?;
+    let version = lines.next()?.trim().strip_prefix("version = \"")?;
+    version.strip_suffix('"')
+}
@@ -3,7 +3,7 @@
 //! This module provides the main app creation functions
 
 use super::plugins::{CorePluginGroup, EditorPluginGroup, RenderingPluginGroup};
-use crate::core::config::{BezySettings, CliArgs, DEFAULT_WINDOW_SIZE, WINDOW_TITLE};
+use crate::core::config::{BezySettings, CliArgs, ConfigFile, DEFAULT_WINDOW_SIZE, WINDOW_TITLE};
 use crate::core::state::{AppState, GlyphNavigation};
 use crate::systems::{
     center_camera_on_startup_layout, create_startup_layout, exit_on_esc, initialize_font_loading,
@@ -46,20 +46,28 @@ pub fn create_app(cli_args: CliArgs) -> Result<App> {
 fn configure_resources(app: &mut App, cli_args: CliArgs) {
     let glyph_navigation = GlyphNavigation::default();
     let mut settings = BezySettings::default();
+    if let Some(config) = ConfigFile::load() {
+        settings.apply_config(&config);
+    }
+
+    crate::i18n::set_language(&settings.language);
 
     // Set theme from CLI args (CLI overrides settings)
     let theme_variant = cli_args.get_theme_variant();
     settings.set_theme(theme_variant.clone());
 
     // Initialize current theme
-    let current_theme = CurrentTheme::new(theme_variant);
+    let mut current_theme = CurrentTheme::new(theme_variant);
+    current_theme.colorblind_safe = settings.accessibility.colorblind_safe;
     let background_color = current_theme.theme().background_color();
+    let ui_scale = UiScale(settings.accessibility.ui_scale);
 
     app.insert_resource(cli_args)
         .insert_resource(glyph_navigation)
         .insert_resource(settings)
         .insert_resource(current_theme)
-        .insert_resource(ClearColor(background_color));
+        .insert_resource(ClearColor(background_color))
+        .insert_resource(ui_scale);
 
     // Configure platform-specific window settings
     #[cfg(not(target_arch = "wasm32"))]
@@ -138,6 +146,10 @@ fn add_plugin_groups(app: &mut App) {
 
     app.add_plugins((RenderingPluginGroup, EditorPluginGroup, CorePluginGroup));
 
+    // Browsers have no file system, so fonts are opened from a folder picker
+    #[cfg(target_arch = "wasm32")]
+    app.add_plugins(crate::utils::web_file_access::WebFileAccessPlugin);
+
     debug!("All plugin groups added successfully");
 }
 
@@ -186,7 +198,13 @@ pub fn create_app_with_tui(
     app.add_systems(Update,
         handle_tui_messages.in_set(crate::editing::FontEditorSets::Input)
     );
-    app.add_systems(Update, send_initial_font_data_to_tui);
+    app.add_systems(
+        Update,
+        (
+            send_font_data_to_tui,
+            send_preferences_to_tui.after(crate::core::config::preferences::apply_preferences),
+        ),
+    );
 
     // Add deferred font loading system to load fonts after window is shown
     app.add_systems(Update, load_font_deferred);
@@ -205,6 +223,12 @@ fn handle_tui_messages(
     current_tool: Option<Res<crate::ui::edit_mode_toolbar::CurrentTool>>,
     text_placement_mode: Option<Res<crate::ui::edit_mode_toolbar::text::TextPlacementMode>>,
     app_state: Option<Res<AppState>>,
+    mut build_composites: EventWriter<crate::systems::commands::BuildCompositesEvent>,
+    mut load_glyph_set: EventWriter<crate::systems::commands::LoadGlyphSetEvent>,
+    mut add_placeholders: EventWriter<crate::systems::commands::AddGlyphSetPlaceholdersEvent>,
+    target_glyph_set: Res<crate::systems::commands::TargetGlyphSet>,
+    mut settings: ResMut<BezySettings>,
+    mut go_to_glyph: EventWriter<crate::editing::goto_glyph::GoToGlyph>,
 ) {
     while let Some(message) = tui_comm.try_recv() {
         match message {
@@ -241,12 +265,20 @@ fn handle_tui_messages(
                 }
             }
             TuiMessage::RequestGlyphList => {
-                info!("TUI requested glyph list - feature temporarily disabled during FontIR removal");
-                tui_comm.send_log("Glyph list feature temporarily disabled".to_string());
+                let glyphs = crate::tui::communication::generate_glyph_list(
+                    app_state.as_deref(),
+                    target_glyph_set.0.as_ref(),
+                );
+                tui_comm.send_glyph_list(glyphs);
             }
             TuiMessage::RequestFontInfo => {
-                info!("TUI requested font info - feature temporarily disabled during FontIR removal");
-                tui_comm.send_log("Font info feature temporarily disabled".to_string());
+                let info = crate::tui::communication::generate_font_info(
+                    app_state.as_deref(),
+                    target_glyph_set.0.as_ref(),
+                );
+                if let Some(info) = info {
+                    tui_comm.send_font_info(info);
+                }
             }
             TuiMessage::ChangeZoom(zoom) => {
                 info!("TUI requested zoom change: {}", zoom);
@@ -271,6 +303,38 @@ fn handle_tui_messages(
 
                 info!("Force redraw requested by TUI");
             }
+            TuiMessage::BuildComposites => {
+                info!("TUI requested composite glyph build");
+                build_composites.write(crate::systems::commands::BuildCompositesEvent::default());
+            }
+            TuiMessage::LoadGlyphSet(source) => {
+                load_glyph_set.write(crate::systems::commands::LoadGlyphSetEvent { source });
+            }
+            TuiMessage::AddGlyphSetPlaceholders => {
+                add_placeholders.write(crate::systems::commands::AddGlyphSetPlaceholdersEvent);
+            }
+            TuiMessage::RequestPreferences => {
+                tui_comm.send_preferences(&settings, None);
+            }
+            TuiMessage::StepPreference(preference, forward) => {
+                let profiles = app_state
+                    .as_deref()
+                    .map(|state| state.workspace.font.export_profiles.names())
+                    .unwrap_or_default();
+                preference.step(&mut settings, forward, &profiles);
+            }
+            TuiMessage::SetToolShortcut(tool_id, key) => {
+                // Only a shortcut that was set counts as a change, so the
+                // refusal isn't replaced by the update for the change
+                use crate::core::config::preferences::set_tool_shortcut;
+                match set_tool_shortcut(settings.bypass_change_detection(), tool_id, key) {
+                    Ok(()) => settings.set_changed(),
+                    Err(message) => tui_comm.send_preferences(&settings, Some(message)),
+                }
+            }
+            TuiMessage::JumpToGlyph(glyph_name) => {
+                go_to_glyph.write(crate::editing::goto_glyph::GoToGlyph(glyph_name));
+            }
             TuiMessage::QAReportReady(report) => {
                 info!("QA report ready: {:?}", report);
                 // TODO: Handle QA report
@@ -288,10 +352,35 @@ fn handle_tui_messages(
 }
 
 #[cfg(feature = "tui")]
-/// System to send initial font data to TUI on startup
-fn send_initial_font_data_to_tui(
-    _tui_comm: ResMut<crate::core::tui_communication::TuiCommunication>,
-    _app_state: Option<Res<AppState>>,
+/// System to keep the TUI's Preferences tab up to date
+fn send_preferences_to_tui(
+    tui_comm: Res<crate::core::tui_communication::TuiCommunication>,
+    settings: Res<BezySettings>,
 ) {
-    // Temporarily disabled during FontIR removal
+    if settings.is_changed() {
+        tui_comm.send_preferences(&settings, None);
+    }
+}
+
+#[cfg(feature = "tui")]
+/// System to send the glyph list and font info to the TUI when the font is
+/// loaded, its glyphs change or a glyph set is chosen
+fn send_font_data_to_tui(
+    tui_comm: Res<crate::core::tui_communication::TuiCommunication>,
+    app_state: Option<Res<AppState>>,
+    target_glyph_set: Res<crate::systems::commands::TargetGlyphSet>,
+    mut app_state_changed: EventReader<crate::editing::selection::events::AppStateChanged>,
+) {
+    let font_changed = app_state.as_ref().is_some_and(|state| state.is_added())
+        || app_state_changed.read().count() > 0;
+    if !font_changed && !target_glyph_set.is_changed() {
+        return;
+    }
+    let app_state = app_state.as_deref();
+    let glyph_set = target_glyph_set.0.as_ref();
+    let glyphs = crate::tui::communication::generate_glyph_list(app_state, glyph_set);
+    tui_comm.send_glyph_list(glyphs);
+    if let Some(info) = crate::tui::communication::generate_font_info(app_state, glyph_set) {
+        tui_comm.send_font_info(info);
+    }
 }
@@ -10,8 +10,22 @@ pub struct CorePluginGroup;
 
 impl PluginGroup for CorePluginGroup {
     fn build(self) -> PluginGroupBuilder {
-        use crate::editing::{FontEditorSystemSetsPlugin, SelectionPlugin, TextEditorPlugin};
-        use crate::io::{gamepad::GamepadPlugin, input::InputPlugin, pointer::PointerPlugin};
+        use crate::editing::{
+            AnchorSyncPlugin, AutoKerningPlugin, AutoSpacingPlugin, BraceLayersPlugin,
+            BuildInspectorPlugin, CanvasImagePlugin, CanvasMenuPlugin, ColorLayersPlugin,
+            CompileErrorsPlugin, ComponentGraphPlugin, CompositesPlugin, CurveFlavorPlugin,
+            DesignFramesPlugin, DesignspaceRulesPlugin, FindReplacePlugin, FontChangesPlugin,
+            FontEditorSystemSetsPlugin, GamepadEditingPlugin, GlyphAutocompletePlugin,
+            GlyphBlendPlugin, GlyphFiltersPlugin, GlyphGhostsPlugin, GlyphLayersPlugin,
+            GlyphNamesPlugin, GoToGlyphPlugin, HandleEntryPlugin, Os2ClassificationPlugin,
+            OutlineClipboardPlugin, OutlineLocksPlugin, SavedGlyphsPlugin, SelectionPlugin,
+            SmartPiecesPlugin, SpacingImportPlugin, StartPointsPlugin, StatPlugin, StemReportPlugin,
+            SubsetPlugin, TextBuffersPlugin, TextEditorPlugin, TextFieldFocusPlugin,
+            WorkspaceSessionPlugin,
+        };
+        use crate::io::{
+            gamepad::GamepadPlugin, input::InputPlugin, midi::MidiPlugin, pointer::PointerPlugin,
+        };
         use crate::systems::{
             BezySystems, CommandsPlugin, InputConsumerPlugin, TextShapingPlugin,
             UiInteractionPlugin,
@@ -21,14 +35,55 @@ impl PluginGroup for CorePluginGroup {
             .add(PointerPlugin)
             .add(InputPlugin)
             .add(GamepadPlugin)
+            .add(GamepadEditingPlugin)
+            .add(MidiPlugin)
             .add(InputConsumerPlugin)
             .add(FontEditorSystemSetsPlugin) // Must be added before other font editor plugins
+            .add(FontChangesPlugin)
             .add(TextEditorPlugin)
+            .add(TextFieldFocusPlugin)
             .add(TextShapingPlugin) // Unified text shaping for RTL support
             .add(SelectionPlugin)
+            .add(OutlineClipboardPlugin)
+            .add(ColorLayersPlugin)
+            .add(GlyphLayersPlugin)
+            .add(CompositesPlugin)
+            .add(GlyphGhostsPlugin)
+            .add(OutlineLocksPlugin)
+            .add(SmartPiecesPlugin)
+            .add(FindReplacePlugin)
+            .add(AutoSpacingPlugin)
+            .add(AutoKerningPlugin)
+            .add(GlyphBlendPlugin)
+            .add(StemReportPlugin)
+            .add(DesignFramesPlugin)
+            .add(GlyphFiltersPlugin)
+            .add(SavedGlyphsPlugin)
+            .add(DesignspaceRulesPlugin)
+            .add(BraceLayersPlugin)
+            .add(SpacingImportPlugin)
+            .add(SubsetPlugin)
+            .add(StatPlugin)
+            .add(GlyphNamesPlugin)
+            .add(Os2ClassificationPlugin)
+            .add(TextBuffersPlugin)
+            .add(WorkspaceSessionPlugin)
+            .add(CanvasImagePlugin)
+            .add(BuildInspectorPlugin)
+            .add(CompileErrorsPlugin)
+            .add(ComponentGraphPlugin)
+            .add(AnchorSyncPlugin)
+            .add(GlyphAutocompletePlugin)
+            .add(GoToGlyphPlugin)
+            .add(CanvasMenuPlugin)
+            .add(HandleEntryPlugin)
+            .add(StartPointsPlugin)
+            .add(CurveFlavorPlugin)
             .add(crate::editing::offcurve_insertion::OffCurveInsertionPlugin)
             .add(UiInteractionPlugin)
             .add(CommandsPlugin)
+            .add(crate::core::config::PreferencesPlugin)
+            .add(crate::systems::AutosavePlugin)
             .add(BezySystems)
     }
 }
@@ -42,8 +97,11 @@ impl PluginGroup for RenderingPluginGroup {
         use crate::rendering::{
             cameras::CameraPlugin, checkerboard::CheckerboardPlugin,
             sort_renderer::SortLabelRenderingPlugin, zoom_aware_scaling::CameraResponsivePlugin,
-            EntityPoolingPlugin, GlyphRenderingPlugin, MeshCachingPlugin, MetricsRenderingPlugin,
-            PostEditingRenderingPlugin, SortHandleRenderingPlugin,
+            BlueZonesPlugin, CurveFlavorRenderingPlugin, DesignFramesRenderingPlugin,
+            EntityPoolingPlugin, GlyphRenderingPlugin, MeasurementGridPlugin, MeshCachingPlugin,
+            MetricsRenderingPlugin, OpenCornersRenderingPlugin, PointRenderingPlugin,
+            PostEditingRenderingPlugin, SortHandleRenderingPlugin, StartPointsRenderingPlugin,
+            TemplateGlyphsPlugin,
         };
 
         PluginGroupBuilder::start::<Self>()
@@ -51,11 +109,16 @@ impl PluginGroup for RenderingPluginGroup {
             .add(CameraPlugin)
             .add(CameraResponsivePlugin)
             .add(CheckerboardPlugin)
+            .add(MeasurementGridPlugin)
+            .add(BlueZonesPlugin)
+            .add(DesignFramesRenderingPlugin)
+            .add(TemplateGlyphsPlugin)
+            .add(OpenCornersRenderingPlugin)
+            .add(StartPointsRenderingPlugin)
+            .add(CurveFlavorRenderingPlugin)
             .add(EntityPoolingPlugin)
             .add(MeshCachingPlugin)
-            // REMOVED: PointRenderingPlugin - Duplicate of GlyphRenderingPlugin's point rendering
-            // Caused z-fighting and broken drag behavior
-            // .add(PointRenderingPlugin)
+            .add(PointRenderingPlugin) // Draws the point and handle instances of edited sorts
             .add(MetricsRenderingPlugin)
             .add(SortHandleRenderingPlugin)
             .add(SortLabelRenderingPlugin) // Sort label rendering (text labels)
@@ -69,23 +132,106 @@ pub struct EditorPluginGroup;
 
 impl PluginGroup for EditorPluginGroup {
     fn build(self) -> PluginGroupBuilder {
+        use crate::ui::accessibility::AccessibilityPlugin;
         use crate::ui::edit_mode_toolbar::EditModeToolbarPlugin;
         use crate::ui::file_menu::FileMenuPlugin;
+        use crate::ui::localization::LocalizationPlugin;
+        use crate::ui::metrics_overlay::MetricsOverlayPlugin;
+        use crate::ui::panes::auto_kerning_pane::AutoKerningPanePlugin;
+        use crate::ui::panes::auto_spacing_pane::AutoSpacingPanePlugin;
+        use crate::ui::panes::brace_layers_pane::BraceLayersPanePlugin;
+        use crate::ui::panes::build_inspector_pane::BuildInspectorPanePlugin;
+        use crate::ui::panes::canvas_image_pane::CanvasImagePanePlugin;
+        use crate::ui::panes::color_layers_pane::ColorLayersPanePlugin;
+        use crate::ui::panes::compile_errors_pane::CompileErrorsPanePlugin;
+        use crate::ui::panes::component_graph_pane::ComponentGraphPanePlugin;
+        use crate::ui::panes::composites_pane::CompositesPanePlugin;
         use crate::ui::panes::coordinate_pane::CoordinatePanePlugin;
+        use crate::ui::panes::curve_flavor_pane::CurveFlavorPanePlugin;
+        use crate::ui::panes::design_frames_pane::DesignFramesPanePlugin;
+        use crate::ui::panes::designspace_rules_pane::DesignspaceRulesPanePlugin;
+        use crate::ui::panes::export_pane::ExportPanePlugin;
+        use crate::ui::panes::features_pane::FeaturesPanePlugin;
+        use crate::ui::panes::glyph_autocomplete_pane::GlyphAutocompletePanePlugin;
+        use crate::ui::panes::glyph_blend_pane::GlyphBlendPanePlugin;
+        use crate::ui::panes::glyph_filters_pane::GlyphFiltersPanePlugin;
+        use crate::ui::panes::glyph_ghosts_pane::GlyphGhostsPanePlugin;
+        use crate::ui::panes::glyph_names_pane::GlyphNamesPanePlugin;
         // use crate::ui::panes::file_pane::FilePanePlugin;  // Temporarily disabled
+        use crate::ui::panes::find_replace_pane::FindReplacePanePlugin;
+        use crate::ui::panes::glyph_layers_pane::GlyphLayersPanePlugin;
         use crate::ui::panes::glyph_pane::GlyphPanePlugin;
+        use crate::ui::panes::handle_entry_pane::HandleEntryPanePlugin;
+        use crate::ui::panes::midi_pane::MidiPanePlugin;
+        use crate::ui::panes::os2_pane::Os2PanePlugin;
+        use crate::ui::panes::preferences_pane::PreferencesPanePlugin;
+        use crate::ui::panes::smart_pieces_pane::SmartPiecesPanePlugin;
+        use crate::ui::panes::spacing_import_pane::SpacingImportPanePlugin;
+        use crate::ui::panes::stat_pane::StatPanePlugin;
+        use crate::ui::panes::stem_report_pane::StemReportPanePlugin;
+        use crate::ui::panes::subset_pane::SubsetPanePlugin;
+        use crate::ui::panes::text_buffers_pane::TextBuffersPanePlugin;
+        use crate::ui::panes::toolbar_pane::ToolbarPanePlugin;
+        use crate::ui::performance_overlay::PerformanceOverlayPlugin;
+        use crate::ui::reading_mode::ReadingModePlugin;
+        use crate::ui::rulers::RulersPlugin;
         use crate::ui::screen_flash::ScreenFlashPlugin;
+        use crate::ui::status_bar::StatusBarPlugin;
 
-        PluginGroupBuilder::start::<Self>()
+        let group = PluginGroupBuilder::start::<Self>()
             // .add(FilePanePlugin)  // Temporarily disabled - moving to TUI
             .add(GlyphPanePlugin)
             .add(CoordinatePanePlugin)
+            .add(FeaturesPanePlugin)
+            .add(AutoSpacingPanePlugin)
+            .add(AutoKerningPanePlugin)
+            .add(GlyphBlendPanePlugin)
+            .add(GlyphGhostsPanePlugin)
+            .add(StemReportPanePlugin)
+            .add(DesignFramesPanePlugin)
+            .add(GlyphFiltersPanePlugin)
+            .add(DesignspaceRulesPanePlugin)
+            .add(BraceLayersPanePlugin)
+            .add(SpacingImportPanePlugin)
+            .add(SubsetPanePlugin)
+            .add(StatPanePlugin)
+            .add(GlyphNamesPanePlugin)
+            .add(Os2PanePlugin)
+            .add(TextBuffersPanePlugin)
+            .add(CanvasImagePanePlugin)
+            .add(MidiPanePlugin)
+            .add(BuildInspectorPanePlugin)
+            .add(CompileErrorsPanePlugin)
+            .add(ComponentGraphPanePlugin)
+            .add(GlyphAutocompletePanePlugin)
+            .add(HandleEntryPanePlugin)
+            .add(CurveFlavorPanePlugin)
+            .add(PreferencesPanePlugin)
+            .add(ToolbarPanePlugin)
+            .add(ColorLayersPanePlugin)
+            .add(GlyphLayersPanePlugin)
+            .add(CompositesPanePlugin)
+            .add(SmartPiecesPanePlugin)
+            .add(FindReplacePanePlugin)
+            .add(RulersPlugin)
+            .add(StatusBarPlugin)
+            .add(MetricsOverlayPlugin)
             .add(crate::tools::ToolStatePlugin) // Unified tool state management
             .add(EditModeToolbarPlugin) // Handles all tools automatically
             .add(FileMenuPlugin)
             .add(ScreenFlashPlugin)
+            .add(PerformanceOverlayPlugin) // F3 frame and stage timings
+            .add(ReadingModePlugin) // Filled text on a clean background
+            .add(AccessibilityPlugin) // Screen reader names and keyboard focus
+            .add(LocalizationPlugin) // Relabels the UI when the language changes
             // Tool business logic plugins
             .add(crate::tools::PenToolPlugin)
-            .add(crate::tools::SelectToolPlugin)
+            .add(crate::tools::SelectToolPlugin);
+        // The web build can't compile fonts, so it has nothing to export
+        if cfg!(target_arch = "wasm32") {
+            group
+        } else {
+            group.add(ExportPanePlugin)
+        }
     }
 }
@@ -20,6 +20,14 @@ use std::path::PathBuf;
 ///   bezy --theme light                  # Use light theme
 ///   bezy --theme strawberry             # Use strawberry theme
 ///   bezy --no-default-buffer            # Start without default LTR buffer (for testing)
+///   bezy --edit my.designspace --export # Export with the first export profile
+///   bezy --edit my.ufo --export Web     # Export with the "Web" profile
+///   bezy --build-family MySans.bezyfamily # Export a family release
+///   bezy --edit my.ufo --glyph-set latin-core # Track coverage of a glyph set
+///   bezy --edit my.ufo --report glyphs.csv    # Write glyph metrics and exit
+///   bezy --edit my.ufo --subset Latin.ttf --keep U+0020-007E # Write a subset
+///   bezy --edit my.ufo --trial Trial.ttf --keep A,B,C         # Write a trial font
+///   bezy --dump-tables build/My.ttf --tables head,OS/2      # Dump and diff tables
 #[derive(Parser, Debug, Resource, Clone)]
 #[clap(
     name = "bezy",
@@ -43,13 +51,15 @@ pub struct CliArgs {
 
     /// Theme to use for the interface
     ///
-    /// Available themes: dark (default), light, strawberry, campfire.
+    /// Available themes: dark (default), light, strawberry, campfire, forest,
+    /// high-contrast.
     /// Custom themes can be added by creating new theme files.
     #[clap(
         long = "theme",
         short = 't',
         help = "Theme to use",
-        long_help = "Theme to use for the interface. Available themes: dark (default), light, strawberry, campfire"
+        long_help = "Theme to use for the interface. Available themes: dark (default), light, \
+                     strawberry, campfire, forest, high-contrast"
     )]
     pub theme: Option<String>,
 
@@ -90,6 +100,158 @@ pub struct CliArgs {
         long_help = "Disable the Terminal User Interface (TUI) that normally runs alongside the main editor. By default, Bezy shows a TUI in the terminal with tabs for codepoint browsing, font information, and real-time log viewing. Use this flag to run the GUI only."
     )]
     pub no_tui: bool,
+
+    /// Export the font source with an export profile and exit
+    ///
+    /// Profiles are stored with the font source (see the export dialog,
+    /// Cmd/Ctrl+E). Without a profile name the default export profile from
+    /// the preferences is used, or else the first one.
+    #[clap(
+        long = "export",
+        value_name = "PROFILE",
+        num_args = 0..=1,
+        requires = "font_source",
+        help = "Export with an export profile and exit",
+        long_help = "Export the font source given with --edit using one of its export profiles, then exit without opening the editor. Without a profile name the default export profile from the preferences is used, or else the project's first profile."
+    )]
+    pub export: Option<Option<String>>,
+
+    /// Build a family release from a family project and exit
+    ///
+    /// A `.bezyfamily` file lists the designspaces and UFOs of a family and
+    /// their export profiles; every font goes into one versioned release
+    /// folder with a manifest.
+    #[clap(
+        long = "build-family",
+        value_name = "PROJECT",
+        help = "Export every source of a .bezyfamily project into a release folder and exit",
+        long_help = "Export every designspace and UFO listed in a .bezyfamily family project \
+                     with its export profile into one release folder, <output_dir>/\
+                     <Family>-<version>, write a manifest.json listing the fonts there, then \
+                     exit without opening the editor. The version is the project's, or else \
+                     the first source's."
+    )]
+    pub build_family: Option<PathBuf>,
+
+    /// Glyph set the font is meant to cover
+    ///
+    /// Either a built-in set (latin-core) or a glyph set file: a `.nam`
+    /// codepoint list, a FontLab `.enc` encoding or a list of glyph names.
+    /// The TUI shows which of its glyphs are done, empty or missing.
+    #[clap(
+        long = "glyph-set",
+        value_name = "SET",
+        help = "Glyph set to track coverage of (latin-core or a .nam/.enc/.txt file)",
+        long_help = "Glyph set the font is meant to cover: the built-in latin-core set, or the \
+                     path of a .nam codepoint list, a FontLab .enc encoding or a glyph name \
+                     list. The TUI's Unicode and Font Info tabs show the font's coverage of \
+                     it, and its Glyph tab adds empty placeholders for the missing glyphs."
+    )]
+    pub glyph_set: Option<String>,
+
+    /// Write a glyph metrics report and exit
+    ///
+    /// CSV or JSON by the file's extension, with a row per glyph of the
+    /// UFO, or of every source of a designspace.
+    #[clap(
+        long = "report",
+        value_name = "FILE",
+        requires = "font_source",
+        help = "Write glyph metrics to a .csv or .json file and exit",
+        long_help = "Write the advance, sidebearings, bounding box, contour and point counts, \
+                     components and anchors of every glyph of the font source given with \
+                     --edit to a .csv or .json file, then exit without opening the editor. A \
+                     designspace gets a row per glyph of each of its sources."
+    )]
+    pub report: Option<PathBuf>,
+
+    /// Write a subset of the font and exit
+    ///
+    /// A .ufo or a .ttf compiled with fontc, holding the glyphs given with
+    /// `--keep` and the glyphs their components use.
+    #[clap(
+        long = "subset",
+        value_name = "FILE",
+        requires = "font_source",
+        help = "Write the glyphs given with --keep to a .ufo or .ttf file and exit",
+        long_help = "Write a subset of the font source given with --edit to a .ufo, or compile \
+                     it into a .ttf, then exit without opening the editor. The subset holds \
+                     the glyphs given with --keep, the base glyphs of their components and \
+                     .notdef, with kerning trimmed to them. A designspace is subset from its \
+                     first source."
+    )]
+    pub subset: Option<PathBuf>,
+
+    /// Write a trial version of the font and exit
+    ///
+    /// Glyphs outside the ones given with `--keep` become placeholders and
+    /// the family name starts with "Trial".
+    #[clap(
+        long = "trial",
+        value_name = "FILE",
+        requires = "font_source",
+        help = "Write a trial font drawing only the glyphs given with --keep and exit",
+        long_help = "Write a trial version of the font source given with --edit to a .ufo, or \
+                     compile it into a .ttf, then exit without opening the editor. Every glyph \
+                     is kept, but those outside the glyphs given with --keep and the glyphs \
+                     their components use are drawn as placeholder frames and left out of the \
+                     kerning, and the family name is prefixed with \"Trial\"."
+    )]
+    pub trial: Option<PathBuf>,
+
+    /// Glyphs a subset or trial font keeps
+    ///
+    /// Comma-separated codepoints or ranges (U+0041, U+0020-007E) and glyph
+    /// names.
+    #[clap(
+        long = "keep",
+        value_name = "GLYPHS",
+        value_delimiter = ',',
+        help = "Codepoints, ranges (U+0020-007E) and glyph names for --subset and --trial, \
+                comma-separated"
+    )]
+    pub keep: Vec<String>,
+
+    /// Dump tables of a compiled font to ttx-style XML and exit
+    ///
+    /// The dump is written next to the font as a `.ttx` file and diffed
+    /// against `--against`, or else the dump stored with the font's QA
+    /// reports last time.
+    #[clap(
+        long = "dump-tables",
+        value_name = "FONT",
+        help = "Dump tables of a compiled font to a .ttx file, report which changed, and exit",
+        long_help = "Dump the tables of a compiled .ttf or .otf font to ttx-style XML in a .ttx \
+                     file next to it, then exit without opening the editor. The dump is \
+                     compared with the same tables of the font given with --against, or else \
+                     with the last dump of this font, and the tables that changed are \
+                     reported, ignoring the checksum and timestamps in head. Dumps are kept \
+                     with the font's QA reports in the config folder."
+    )]
+    pub dump_tables: Option<PathBuf>,
+
+    /// Tables --dump-tables writes
+    ///
+    /// Comma-separated table tags (head, OS/2, GPOS) and GlyphOrder; every
+    /// table when not given.
+    #[clap(
+        long = "tables",
+        value_name = "TAGS",
+        value_delimiter = ',',
+        requires = "dump_tables",
+        help = "Tables for --dump-tables, comma-separated (all of them if not given)"
+    )]
+    pub tables: Vec<String>,
+
+    /// Earlier build for --dump-tables to compare with
+    #[clap(
+        long = "against",
+        value_name = "FONT",
+        requires = "dump_tables",
+        help = "Earlier build of the font for --dump-tables to compare with, instead of the \
+                last stored dump"
+    )]
+    pub against: Option<PathBuf>,
 }
 
 impl CliArgs {
@@ -147,6 +309,18 @@ impl CliArgs {
                 }
             }
 
+            for path in self.dump_tables.iter().chain(&self.against) {
+                if !path.is_file() {
+                    return Err(format!("Font file does not exist: {}", path.display()));
+                }
+            }
+
+            if let Some(path) = &self.build_family {
+                if !path.is_file() {
+                    return Err(format!("Family project does not exist: {}", path.display()));
+                }
+            }
+
             // Validate theme if provided
             if let Some(theme_name) = &self.theme {
                 if ThemeVariant::parse(theme_name).is_none() {
@@ -157,6 +331,12 @@ impl CliArgs {
                 }
             }
 
+            // Make sure the glyph set can be read
+            if let Some(glyph_set) = &self.glyph_set {
+                crate::data::glyph_sets::GlyphSet::load(glyph_set)
+                    .map_err(|error| format!("{error:#}"))?;
+            }
+
             Ok(())
         }
     }
@@ -171,6 +351,18 @@ impl CliArgs {
             font_source: None,        // Start with empty state for web builds
             theme: None,              // Use default theme for web builds
             no_default_buffer: false, // Enable default buffer for web builds
+            new_config: false,
+            no_tui: true, // There's no terminal in the browser
+            export: None,
+            build_family: None,
+            glyph_set: None,
+            report: None,
+            subset: None,
+            trial: None,
+            keep: Vec::new(),
+            dump_tables: None,
+            tables: Vec::new(),
+            against: None,
         }
     }
 
@@ -4,12 +4,15 @@
 //! - CLI arguments parsing
 //! - User configuration files
 //! - Application settings
+//! - Preferences editable from inside the app
 
 pub mod cli;
+pub mod preferences;
 pub mod settings;
 pub mod user_config;
 
 // Simple, clear re-exports
 pub use cli::CliArgs;
+pub use preferences::{Preference, PreferencesPlugin};
 pub use settings::{BezySettings, DEFAULT_WINDOW_SIZE, WINDOW_TITLE};
 pub use user_config::ConfigFile;
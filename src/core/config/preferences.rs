@@ -0,0 +1,391 @@
+//! Preferences editable from inside Bezy
+//!
+//! The preferences pane (Cmd/Ctrl+,) and the TUI's Preferences tab list the
+//! same preferences and change them the same way: each one steps forward or
+//! back through its values, and tool shortcuts are set to a key. Both edit
+//! `BezySettings`; `PreferencesPlugin` applies the change right away and
+//! writes the settings back to the user config file.
+
+use crate::core::config::{BezySettings, ConfigFile};
+use crate::i18n;
+use crate::logging::verbosity::{self, LogSubsystem, LogVerbosity, SubsystemVerbosity};
+use crate::ui::edit_mode_toolbar::ToolConfig;
+use crate::ui::themes::{CurrentTheme, ThemeVariant};
+use bevy::prelude::*;
+
+/// Grid sizes to step through, in font units
+const GRID_SIZES: [f32; 11] = [1.0, 2.0, 4.0, 5.0, 8.0, 10.0, 16.0, 20.0, 25.0, 50.0, 100.0];
+
+/// Measurement grid spacings to step through, in font units
+const MEASUREMENT_INTERVALS: [f32; 8] = [1.0, 2.0, 5.0, 10.0, 20.0, 25.0, 50.0, 100.0];
+
+/// Nudge distances to step through, in font units
+const NUDGE_DISTANCES: [f32; 14] = [
+    1.0, 2.0, 4.0, 5.0, 8.0, 10.0, 16.0, 20.0, 25.0, 32.0, 50.0, 64.0, 100.0, 128.0,
+];
+
+/// Click tolerances to step through, in logical pixels
+const HIT_TOLERANCES: [f32; 7] = [4.0, 6.0, 8.0, 12.0, 16.0, 24.0, 32.0];
+
+/// Autosave intervals to step through, in seconds; 0 is off
+const AUTOSAVE_INTERVALS: [u64; 8] = [0, 30, 60, 120, 300, 600, 900, 1800];
+
+/// Interface scales to step through
+const UI_SCALES: [f32; 7] = [0.75, 1.0, 1.25, 1.5, 1.75, 2.0, 2.5];
+
+/// One line of the preferences pane and the TUI Preferences tab
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Preference {
+    Theme,
+    Language,
+    ColorblindSafe,
+    UiScale,
+    GridSnap,
+    GridSize,
+    MeasurementGrid,
+    MeasurementInterval,
+    TemplateGlyphs,
+    Nudge,
+    ShiftNudge,
+    CmdNudge,
+    HitTolerance,
+    Autosave,
+    ExportProfile,
+    /// The key switching to a tool, by tool id
+    ToolShortcut(&'static str),
+    /// How much a subsystem logs
+    LogVerbosity(LogSubsystem),
+}
+
+impl Preference {
+    /// Every preference, in the order they're listed
+    pub fn all() -> Vec<Preference> {
+        let mut all = vec![
+            Preference::Theme,
+            Preference::Language,
+            Preference::ColorblindSafe,
+            Preference::UiScale,
+            Preference::GridSnap,
+            Preference::GridSize,
+            Preference::MeasurementGrid,
+            Preference::MeasurementInterval,
+            Preference::TemplateGlyphs,
+            Preference::Nudge,
+            Preference::ShiftNudge,
+            Preference::CmdNudge,
+            Preference::HitTolerance,
+            Preference::Autosave,
+            Preference::ExportProfile,
+        ];
+        all.extend(
+            ToolConfig::get_enabled_tools()
+                .into_iter()
+                .map(|tool| Preference::ToolShortcut(tool.id)),
+        );
+        all.extend(LogSubsystem::ALL.map(Preference::LogVerbosity));
+        all
+    }
+
+    /// The preference's name in the UI language
+    pub fn label(&self) -> String {
+        let id = match self {
+            Preference::Theme => "pref-theme",
+            Preference::Language => "pref-language",
+            Preference::ColorblindSafe => "pref-colorblind-safe",
+            Preference::UiScale => "pref-ui-scale",
+            Preference::GridSnap => "pref-grid-snap",
+            Preference::GridSize => "pref-grid-size",
+            Preference::MeasurementGrid => "pref-measurement-grid",
+            Preference::MeasurementInterval => "pref-measurement-interval",
+            Preference::TemplateGlyphs => "pref-template-glyphs",
+            Preference::Nudge => "pref-nudge",
+            Preference::ShiftNudge => "pref-shift-nudge",
+            Preference::CmdNudge => "pref-cmd-nudge",
+            Preference::HitTolerance => "pref-hit-tolerance",
+            Preference::Autosave => "pref-autosave",
+            Preference::ExportProfile => "pref-export-profile",
+            Preference::ToolShortcut(id) => {
+                let name = ToolConfig::get_tool(id).map_or(*id, |tool| tool.name);
+                let name = i18n::tool_name(id, name);
+                return i18n::tr_args("pref-tool-shortcut", &[("tool", &name)]);
+            }
+            Preference::LogVerbosity(subsystem) => {
+                let name = i18n::tr(&format!("pref-log-{}", subsystem.id()));
+                return i18n::tr_args("pref-log-verbosity", &[("subsystem", &name)]);
+            }
+        };
+        i18n::tr(id)
+    }
+
+    /// The preference's current value, for display
+    pub fn value(&self, settings: &BezySettings) -> String {
+        match self {
+            Preference::Theme => settings.theme.name().to_string(),
+            Preference::Language => i18n::LANGUAGES
+                .iter()
+                .find(|(id, _)| *id == settings.language)
+                .map_or_else(|| settings.language.clone(), |(_, name)| name.to_string()),
+            Preference::ColorblindSafe => on_off(settings.accessibility.colorblind_safe),
+            Preference::UiScale => format!("{:.0}%", settings.accessibility.ui_scale * 100.0),
+            Preference::GridSnap => on_off(settings.grid.enabled),
+            Preference::GridSize => format!("{}", settings.grid.unit_size),
+            Preference::MeasurementGrid => on_off(settings.grid.show_measurement_grid),
+            Preference::MeasurementInterval => format!("{}", settings.grid.measurement_interval),
+            Preference::TemplateGlyphs => on_off(settings.templates.show),
+            Preference::Nudge => format!("{}", settings.nudge.default),
+            Preference::ShiftNudge => format!("{}", settings.nudge.shift),
+            Preference::CmdNudge => format!("{}", settings.nudge.cmd),
+            Preference::HitTolerance => {
+                let pixels = settings.hit_test.tolerance.to_string();
+                i18n::tr_args("pref-pixels", &[("pixels", &pixels)])
+            }
+            Preference::Autosave => match settings.autosave.interval_secs {
+                0 => i18n::tr("pref-off"),
+                secs if secs % 60 == 0 => {
+                    i18n::tr_args("pref-every-minutes", &[("minutes", &(secs / 60).to_string())])
+                }
+                secs => i18n::tr_args("pref-every-seconds", &[("seconds", &secs.to_string())]),
+            },
+            Preference::ExportProfile => settings
+                .default_export_profile
+                .clone()
+                .unwrap_or_else(|| i18n::tr("pref-first-profile")),
+            Preference::ToolShortcut(id) => {
+                let default = ToolConfig::get_tool(id).and_then(|tool| tool.shortcut);
+                match settings.tool_shortcut(id, default) {
+                    Some(' ') => i18n::tr("pref-space"),
+                    Some(key) => key.to_string(),
+                    None => i18n::tr("pref-none"),
+                }
+            }
+            Preference::LogVerbosity(subsystem) => {
+                i18n::tr(&format!("pref-log-{}", settings.log_verbosity.get(*subsystem).id()))
+            }
+        }
+    }
+
+    /// Step to the next value, or the previous one when `forward` is false
+    ///
+    /// `export_profiles` are the names of the loaded font's export profiles.
+    /// Tool shortcuts don't step; they're set with `set_tool_shortcut`.
+    pub fn step(&self, settings: &mut BezySettings, forward: bool, export_profiles: &[&str]) {
+        match self {
+            Preference::Theme => {
+                let mut names = ThemeVariant::all_names();
+                names.sort();
+                let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                let next = cycle(&names, Some(settings.theme.name()), forward, false);
+                if let Some(variant) = next.and_then(ThemeVariant::parse) {
+                    settings.set_theme(variant);
+                }
+            }
+            Preference::Language => {
+                let names: Vec<&str> = i18n::LANGUAGES.iter().map(|(id, _)| *id).collect();
+                if let Some(language) = cycle(&names, Some(&settings.language), forward, false) {
+                    settings.language = language.to_string();
+                }
+            }
+            Preference::ColorblindSafe => {
+                settings.accessibility.colorblind_safe = !settings.accessibility.colorblind_safe
+            }
+            Preference::UiScale => {
+                let scale = &mut settings.accessibility.ui_scale;
+                *scale = step_value(&UI_SCALES, *scale, forward)
+            }
+            Preference::GridSnap => settings.grid.enabled = !settings.grid.enabled,
+            Preference::GridSize => {
+                settings.grid.unit_size = step_value(&GRID_SIZES, settings.grid.unit_size, forward)
+            }
+            Preference::MeasurementGrid => {
+                settings.grid.show_measurement_grid = !settings.grid.show_measurement_grid
+            }
+            Preference::MeasurementInterval => {
+                let interval = &mut settings.grid.measurement_interval;
+                *interval = step_value(&MEASUREMENT_INTERVALS, *interval, forward)
+            }
+            Preference::TemplateGlyphs => settings.templates.show = !settings.templates.show,
+            Preference::Nudge => {
+                settings.nudge.default =
+                    step_value(&NUDGE_DISTANCES, settings.nudge.default, forward)
+            }
+            Preference::ShiftNudge => {
+                settings.nudge.shift = step_value(&NUDGE_DISTANCES, settings.nudge.shift, forward)
+            }
+            Preference::CmdNudge => {
+                settings.nudge.cmd = step_value(&NUDGE_DISTANCES, settings.nudge.cmd, forward)
+            }
+            Preference::HitTolerance => {
+                let tolerance = &mut settings.hit_test.tolerance;
+                *tolerance = step_value(&HIT_TOLERANCES, *tolerance, forward)
+            }
+            Preference::Autosave => {
+                settings.autosave.interval_secs = step_value(
+                    &AUTOSAVE_INTERVALS,
+                    settings.autosave.interval_secs,
+                    forward,
+                )
+            }
+            Preference::ExportProfile => {
+                let current = settings.default_export_profile.as_deref();
+                settings.default_export_profile =
+                    cycle(export_profiles, current, forward, true).map(str::to_string);
+            }
+            Preference::ToolShortcut(_) => {}
+            Preference::LogVerbosity(subsystem) => {
+                let current = settings.log_verbosity.get(*subsystem);
+                let next = step_value(&LogVerbosity::ALL, current, forward);
+                settings.log_verbosity.set(*subsystem, next);
+            }
+        }
+    }
+}
+
+fn on_off(enabled: bool) -> String {
+    i18n::tr(if enabled { "pref-on" } else { "pref-off" })
+}
+
+/// The closest choice above `current`, or below it when going back; the
+/// ends of the list stay put
+pub(crate) fn step_value<T: PartialOrd + Copy>(choices: &[T], current: T, forward: bool) -> T {
+    let next = if forward {
+        choices.iter().find(|choice| **choice > current)
+    } else {
+        choices.iter().rev().find(|choice| **choice < current)
+    };
+    next.copied().unwrap_or(current)
+}
+
+/// The name after (or before) `current`, wrapping around; with
+/// `allow_none`, no name at all is one more stop on the way round
+fn cycle<'a>(
+    names: &[&'a str],
+    current: Option<&str>,
+    forward: bool,
+    allow_none: bool,
+) -> Option<&'a str> {
+    let mut stops: Vec<Option<&'a str>> = names.iter().copied().map(Some).collect();
+    if allow_none {
+        stops.insert(0, None);
+    }
+    if stops.is_empty() {
+        return None;
+    }
+    let position = stops.iter().position(|stop| *stop == current);
+    let next = match (position, forward) {
+        (Some(i), true) => (i + 1) % stops.len(),
+        (Some(i), false) => (i + stops.len() - 1) % stops.len(),
+        (None, _) => 0,
+    };
+    stops[next]
+}
+
+/// Set the key switching to a tool
+///
+/// Keys are the letters a to z; a key another tool already uses is refused
+/// with a message saying which.
+pub fn set_tool_shortcut(
+    settings: &mut BezySettings,
+    tool_id: &str,
+    key: char,
+) -> Result<(), String> {
+    let key = key.to_ascii_lowercase();
+    if !key.is_ascii_lowercase() {
+        let key = key.to_string();
+        return Err(i18n::tr_args("pref-key-not-a-letter", &[("key", &key)]));
+    }
+    let taken_by = ToolConfig::get_enabled_tools().into_iter().find(|tool| {
+        tool.id != tool_id && settings.tool_shortcut(tool.id, tool.shortcut) == Some(key)
+    });
+    if let Some(tool) = taken_by {
+        let (key, tool) = (key.to_string(), i18n::tool_name(tool.id, tool.name));
+        return Err(i18n::tr_args("pref-key-taken", &[("key", &key), ("tool", &tool)]));
+    }
+    let default = ToolConfig::get_tool(tool_id).and_then(|tool| tool.shortcut);
+    if default == Some(key) {
+        settings.tool_shortcuts.remove(tool_id);
+    } else {
+        settings.tool_shortcuts.insert(tool_id.to_string(), key);
+    }
+    Ok(())
+}
+
+pub struct PreferencesPlugin;
+
+impl Plugin for PreferencesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, apply_preferences);
+    }
+}
+
+/// Applies changed settings that need more than being read again (the
+/// theme, language, accessibility and log verbosity) and writes every
+/// setting back to the user config file
+pub(crate) fn apply_preferences(
+    settings: Res<BezySettings>,
+    mut theme: ResMut<CurrentTheme>,
+    mut clear_color: ResMut<ClearColor>,
+    mut ui_scale: ResMut<UiScale>,
+    mut log_verbosity: Local<Option<SubsystemVerbosity>>,
+) {
+    if !settings.is_changed() || settings.is_added() {
+        return;
+    }
+    if theme.variant != settings.theme {
+        theme.switch_to(settings.theme.clone());
+        clear_color.0 = theme.theme().background_color();
+    }
+    if i18n::language() != settings.language {
+        i18n::set_language(&settings.language);
+    }
+    if theme.colorblind_safe != settings.accessibility.colorblind_safe {
+        theme.colorblind_safe = settings.accessibility.colorblind_safe;
+    }
+    if ui_scale.0 != settings.accessibility.ui_scale {
+        ui_scale.0 = settings.accessibility.ui_scale;
+    }
+    // Reloading the filter resets every callsite's cached interest
+    if log_verbosity.as_ref() != Some(&settings.log_verbosity) {
+        verbosity::set_subsystem_verbosity(&settings.log_verbosity);
+        *log_verbosity = Some(settings.log_verbosity.clone());
+    }
+
+    let mut config = ConfigFile::load().unwrap_or_default();
+    settings.write_config(&mut config);
+    if let Err(e) = config.save() {
+        warn!("Failed to save preferences: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_value_stops_at_the_ends() {
+        assert_eq!(step_value(&NUDGE_DISTANCES, 2.0, true), 4.0);
+        assert_eq!(step_value(&NUDGE_DISTANCES, 3.0, false), 2.0);
+        assert_eq!(step_value(&NUDGE_DISTANCES, 128.0, true), 128.0);
+        assert_eq!(step_value(&AUTOSAVE_INTERVALS, 0, false), 0);
+    }
+
+    #[test]
+    fn test_cycle_wraps_through_no_name() {
+        let names = ["Web", "Desktop"];
+        assert_eq!(cycle(&names, None, true, true), Some("Web"));
+        assert_eq!(cycle(&names, Some("Desktop"), true, true), None);
+        assert_eq!(cycle(&names, None, false, true), Some("Desktop"));
+        assert_eq!(cycle(&names, Some("Web"), false, false), Some("Desktop"));
+        assert_eq!(cycle(&names, Some("Gone"), true, true), None);
+    }
+
+    #[test]
+    fn test_set_tool_shortcut_refuses_taken_keys() {
+        let mut settings = BezySettings::default();
+        assert!(set_tool_shortcut(&mut settings, "pen", 'v').is_err());
+        assert!(set_tool_shortcut(&mut settings, "pen", '1').is_err());
+        set_tool_shortcut(&mut settings, "pen", 'N').unwrap();
+        assert_eq!(settings.tool_shortcuts.get("pen"), Some(&'n'));
+        set_tool_shortcut(&mut settings, "pen", 'p').unwrap();
+        assert!(settings.tool_shortcuts.is_empty());
+    }
+}
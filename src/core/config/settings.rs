@@ -3,8 +3,16 @@
 //! This module contains all configurable settings for the Bezy font editor.
 //! For visual/UI settings, see ui/theme.rs
 
+use crate::core::config::ConfigFile;
+use crate::i18n::LANGUAGES;
+use crate::io::midi::{MidiBindings, MidiControl, MidiParameter};
+use crate::logging::verbosity::{LogSubsystem, LogVerbosity, SubsystemVerbosity};
+use crate::ui::edit_mode_toolbar::tool_options::{ToolOption, ToolOptions};
+use crate::ui::edit_mode_toolbar::toolbar_layout::ToolbarLayout;
 use crate::ui::themes::ThemeVariant;
 use bevy::prelude::*;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 pub const WINDOW_TITLE: &str = "Bezy";
 pub const DEFAULT_WINDOW_SIZE: (f32, f32) = (1280.0, 768.0);
@@ -17,6 +25,22 @@ pub struct GridSettings {
     pub unit_size: f32,
     /// Multiplier for sort placement grid (coarser than point grid)
     pub sort_multiplier: f32,
+    /// Whether the measurement grid is drawn over the canvas; while it is,
+    /// points snap to its lines instead
+    pub show_measurement_grid: bool,
+    /// Distance between measurement grid lines in font units
+    pub measurement_interval: f32,
+}
+
+impl GridSettings {
+    /// The distance points snap to: the measurement grid's while it's shown
+    pub fn snap_size(&self) -> f32 {
+        if self.show_measurement_grid {
+            self.measurement_interval
+        } else {
+            self.unit_size
+        }
+    }
 }
 
 impl Default for GridSettings {
@@ -25,6 +49,8 @@ impl Default for GridSettings {
             enabled: true,
             unit_size: 2.0,
             sort_multiplier: 8.0,
+            show_measurement_grid: false,
+            measurement_interval: 10.0,
         }
     }
 }
@@ -50,24 +76,137 @@ impl Default for NudgeSettings {
     }
 }
 
+/// Configuration for clicking on points and outlines
+#[derive(Debug, Clone, Copy)]
+pub struct HitTestSettings {
+    /// How far from a point a click still hits it, in logical pixels on
+    /// screen whatever the zoom
+    pub tolerance: f32,
+}
+
+impl Default for HitTestSettings {
+    fn default() -> Self {
+        Self { tolerance: 16.0 }
+    }
+}
+
+/// Configuration for outline copy/paste
+#[derive(Debug, Clone, Copy)]
+pub struct ClipboardSettings {
+    /// Mirror copied outlines to the system clipboard as SVG
+    pub use_system_clipboard: bool,
+    /// SVG user units per font unit when exchanging SVG with other apps
+    pub svg_scale: f64,
+}
+
+impl Default for ClipboardSettings {
+    fn default() -> Self {
+        Self {
+            use_system_clipboard: true,
+            svg_scale: 1.0,
+        }
+    }
+}
+
+/// Configuration for saving the font automatically
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AutosaveSettings {
+    /// Seconds between saves of unsaved edits, 0 when autosave is off
+    pub interval_secs: u64,
+}
+
+/// Configuration for making the editor easier to see
+#[derive(Debug, Clone, Copy)]
+pub struct AccessibilitySettings {
+    /// Draw points and QA severities in colorblind-safe colors
+    pub colorblind_safe: bool,
+    /// Scale of text, toolbars and points, 1.0 being the normal size
+    pub ui_scale: f32,
+}
+
+impl Default for AccessibilitySettings {
+    fn default() -> Self {
+        Self {
+            colorblind_safe: false,
+            ui_scale: 1.0,
+        }
+    }
+}
+
+/// Template outlines drawn in glyphs that aren't drawn yet
+#[derive(Debug, Clone)]
+pub struct TemplateSettings {
+    pub show: bool,
+    /// Font templates are taken from before the bundled reference fonts
+    pub font: Option<PathBuf>,
+}
+
+impl Default for TemplateSettings {
+    fn default() -> Self {
+        Self {
+            show: true,
+            font: None,
+        }
+    }
+}
+
 /// Main settings resource containing all configuration
 ///
 /// This is a Bevy resource that can be accessed from any system.
 /// Settings are loaded at startup and can be modified at runtime.
-#[derive(Resource, Debug, Clone, Default)]
+#[derive(Resource, Debug, Clone)]
 pub struct BezySettings {
     pub grid: GridSettings,
     pub nudge: NudgeSettings,
+    pub hit_test: HitTestSettings,
+    pub clipboard: ClipboardSettings,
+    pub autosave: AutosaveSettings,
+    pub accessibility: AccessibilitySettings,
+    pub templates: TemplateSettings,
     pub theme: ThemeVariant,
+    /// Language of the interface, an id from `i18n::LANGUAGES`
+    pub language: String,
+    /// Export profile the export dialog starts on, the first one when unset
+    pub default_export_profile: Option<String>,
+    /// Tool shortcuts replacing the toolbar's, by tool id
+    pub tool_shortcuts: BTreeMap<String, char>,
+    /// How much the chattiest subsystems log
+    pub log_verbosity: SubsystemVerbosity,
+    /// MIDI controls driving parameters
+    pub midi_bindings: MidiBindings,
+    /// Values of the tool options bar
+    pub tool_options: ToolOptions,
+    /// Order, hidden tools and groups of the edit-mode toolbar
+    pub toolbar: ToolbarLayout,
+}
+
+impl Default for BezySettings {
+    fn default() -> Self {
+        Self {
+            grid: GridSettings::default(),
+            nudge: NudgeSettings::default(),
+            hit_test: HitTestSettings::default(),
+            clipboard: ClipboardSettings::default(),
+            autosave: AutosaveSettings::default(),
+            accessibility: AccessibilitySettings::default(),
+            templates: TemplateSettings::default(),
+            theme: ThemeVariant::default(),
+            language: "en".to_string(),
+            default_export_profile: None,
+            tool_shortcuts: BTreeMap::new(),
+            log_verbosity: SubsystemVerbosity::default(),
+            midi_bindings: MidiBindings::default(),
+            tool_options: ToolOptions::default(),
+            toolbar: ToolbarLayout::default(),
+        }
+    }
 }
 
 impl BezySettings {
     pub fn apply_grid_snap(&self, position: Vec2) -> Vec2 {
         if self.grid.enabled {
-            Vec2::new(
-                (position.x / self.grid.unit_size).round() * self.grid.unit_size,
-                (position.y / self.grid.unit_size).round() * self.grid.unit_size,
-            )
+            let size = self.grid.snap_size();
+            (position / size).round() * size
         } else {
             position
         }
@@ -80,4 +219,200 @@ impl BezySettings {
     pub fn get_theme(&self) -> ThemeVariant {
         self.theme.clone()
     }
+
+    /// Take the settings the user config file has; the theme is left alone
+    /// since the CLI decides it (see `CliArgs::get_theme_variant`)
+    pub fn apply_config(&mut self, config: &ConfigFile) {
+        if let Some(enabled) = config.grid_enabled {
+            self.grid.enabled = enabled;
+        }
+        if let Some(size) = config.grid_unit_size.filter(|size| *size > 0.0) {
+            self.grid.unit_size = size;
+        }
+        if let Some(shown) = config.measurement_grid {
+            self.grid.show_measurement_grid = shown;
+        }
+        if let Some(interval) = config.measurement_grid_interval.filter(|size| *size > 0.0) {
+            self.grid.measurement_interval = interval;
+        }
+        let nudges = [
+            (config.nudge_default, &mut self.nudge.default),
+            (config.nudge_shift, &mut self.nudge.shift),
+            (config.nudge_cmd, &mut self.nudge.cmd),
+        ];
+        for (value, setting) in nudges {
+            if let Some(value) = value.filter(|value| *value > 0.0) {
+                *setting = value;
+            }
+        }
+        if let Some(tolerance) = config.hit_tolerance.filter(|tolerance| *tolerance > 0.0) {
+            self.hit_test.tolerance = tolerance;
+        }
+        if let Some(interval) = config.autosave_interval_secs {
+            self.autosave.interval_secs = interval;
+        }
+        if let Some(language) = config.language.as_deref().filter(|id| is_language(id)) {
+            self.language = language.to_string();
+        }
+        if let Some(colorblind_safe) = config.colorblind_safe {
+            self.accessibility.colorblind_safe = colorblind_safe;
+        }
+        if let Some(scale) = config.ui_scale.filter(|scale| *scale > 0.0) {
+            self.accessibility.ui_scale = scale;
+        }
+        if let Some(show) = config.template_glyphs {
+            self.templates.show = show;
+        }
+        self.templates.font = config.template_font.clone();
+        self.default_export_profile = config.default_export_profile.clone();
+        self.tool_shortcuts = config.tool_shortcuts.clone();
+        for (subsystem, verbosity) in &config.log_verbosity {
+            let subsystem = LogSubsystem::from_id(subsystem);
+            let verbosity = LogVerbosity::from_id(verbosity);
+            if let (Some(subsystem), Some(verbosity)) = (subsystem, verbosity) {
+                self.log_verbosity.set(subsystem, verbosity);
+            }
+        }
+        self.midi_bindings = MidiBindings(
+            config
+                .midi_bindings
+                .iter()
+                .filter(|(_, [channel, controller])| *channel < 16 && *controller < 120)
+                .filter_map(|(parameter, &[channel, controller])| {
+                    let control = MidiControl {
+                        channel,
+                        controller,
+                    };
+                    Some((MidiParameter::from_id(parameter)?, control))
+                })
+                .collect(),
+        );
+        for (tool_id, options) in &config.tool_options {
+            for (id, value) in options {
+                if let Some(option) = ToolOption::from_id(tool_id, id) {
+                    self.tool_options.set(option, value);
+                }
+            }
+        }
+        self.toolbar = ToolbarLayout {
+            order: config.toolbar_order.clone(),
+            hidden: config.toolbar_hidden.iter().cloned().collect(),
+            groups: config.toolbar_groups.clone(),
+        };
+    }
+
+    /// Write every setting the user config file has into `config`
+    pub fn write_config(&self, config: &mut ConfigFile) {
+        config.default_theme = Some(self.theme.name().to_string());
+        config.grid_enabled = Some(self.grid.enabled);
+        config.grid_unit_size = Some(self.grid.unit_size);
+        config.measurement_grid = Some(self.grid.show_measurement_grid);
+        config.measurement_grid_interval = Some(self.grid.measurement_interval);
+        config.nudge_default = Some(self.nudge.default);
+        config.nudge_shift = Some(self.nudge.shift);
+        config.nudge_cmd = Some(self.nudge.cmd);
+        config.hit_tolerance = Some(self.hit_test.tolerance);
+        config.autosave_interval_secs = Some(self.autosave.interval_secs);
+        config.language = Some(self.language.clone());
+        config.colorblind_safe = Some(self.accessibility.colorblind_safe);
+        config.ui_scale = Some(self.accessibility.ui_scale);
+        config.template_glyphs = Some(self.templates.show);
+        config.template_font = self.templates.font.clone();
+        config.default_export_profile = self.default_export_profile.clone();
+        config.tool_shortcuts = self.tool_shortcuts.clone();
+        config.log_verbosity = self
+            .log_verbosity
+            .0
+            .iter()
+            .map(|(subsystem, verbosity)| (subsystem.id().to_string(), verbosity.id().to_string()))
+            .collect();
+        config.midi_bindings = self
+            .midi_bindings
+            .0
+            .iter()
+            .map(|(parameter, control)| {
+                let control = [control.channel, control.controller];
+                (parameter.id().to_string(), control)
+            })
+            .collect();
+        config.tool_options.clear();
+        for option in ToolOption::ALL {
+            config
+                .tool_options
+                .entry(option.tool_id().to_string())
+                .or_default()
+                .insert(option.id().to_string(), self.tool_options.value(option));
+        }
+        config.toolbar_order = self.toolbar.order.clone();
+        config.toolbar_hidden = self.toolbar.hidden.iter().cloned().collect();
+        config.toolbar_groups = self.toolbar.groups.clone();
+    }
+
+    /// The key switching to a tool: the user's, or the toolbar's `default`
+    pub fn tool_shortcut(&self, tool_id: &str, default: Option<char>) -> Option<char> {
+        self.tool_shortcuts.get(tool_id).copied().or(default)
+    }
+}
+
+fn is_language(id: &str) -> bool {
+    LANGUAGES.iter().any(|(language, _)| *language == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_round_trip() {
+        let mut settings = BezySettings::default();
+        settings.grid.enabled = false;
+        settings.grid.show_measurement_grid = true;
+        settings.grid.measurement_interval = 50.0;
+        settings.nudge.shift = 10.0;
+        settings.hit_test.tolerance = 8.0;
+        settings.autosave.interval_secs = 120;
+        settings.accessibility.ui_scale = 1.5;
+        settings.templates.show = false;
+        settings.templates.font = Some(PathBuf::from("reference.otf"));
+        settings.language = "ar".to_string();
+        settings.default_export_profile = Some("Web".to_string());
+        settings.tool_shortcuts.insert("pen".to_string(), 'n');
+        settings
+            .log_verbosity
+            .set(LogSubsystem::Knife, LogVerbosity::Trace);
+        let fader = MidiControl {
+            channel: 1,
+            controller: 7,
+        };
+        settings.midi_bindings.bind(MidiParameter::Zoom, fader);
+        settings.tool_options.corner_radius = 40.0;
+        settings.tool_options.step(ToolOption::KnifeCut, true);
+        let toolbar = &mut settings.toolbar;
+        toolbar.order = vec!["pen".to_string(), "select".to_string()];
+        toolbar.hidden.insert("ai".to_string());
+        toolbar.groups.insert("knife".to_string(), vec!["measure".to_string()]);
+
+        let mut config = ConfigFile::default();
+        settings.write_config(&mut config);
+        let mut loaded = BezySettings::default();
+        loaded.apply_config(&config);
+
+        assert!(!loaded.grid.enabled);
+        assert!(loaded.grid.show_measurement_grid);
+        assert_eq!(loaded.grid.snap_size(), 50.0);
+        assert_eq!(loaded.nudge.shift, 10.0);
+        assert_eq!(loaded.hit_test.tolerance, 8.0);
+        assert_eq!(loaded.autosave.interval_secs, 120);
+        assert_eq!(loaded.accessibility.ui_scale, 1.5);
+        assert!(!loaded.templates.show);
+        assert_eq!(loaded.templates.font, settings.templates.font);
+        assert_eq!(loaded.language, "ar");
+        assert_eq!(loaded.default_export_profile.as_deref(), Some("Web"));
+        assert_eq!(loaded.tool_shortcut("pen", Some('p')), Some('n'));
+        assert_eq!(loaded.tool_shortcut("select", Some('v')), Some('v'));
+        assert_eq!(loaded.log_verbosity, settings.log_verbosity);
+        assert_eq!(loaded.midi_bindings, settings.midi_bindings);
+        assert_eq!(loaded.tool_options, settings.tool_options);
+        assert_eq!(loaded.toolbar, settings.toolbar);
+    }
 }
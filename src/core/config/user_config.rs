@@ -4,21 +4,83 @@
 
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
 /// User configuration from ~/.config/bezy/settings.json
 ///
-/// These settings override built-in defaults but are overridden by CLI arguments
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// These settings override built-in defaults but are overridden by CLI arguments.
+/// Missing entries keep their built-in defaults. The preferences pane and the
+/// TUI Preferences tab write every entry back when a preference changes.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct ConfigFile {
     /// Default theme to use (e.g., "dark", "light", "strawberry")
     pub default_theme: Option<String>,
-    // Additional settings can be added here in the future
-    // Examples could include:
-    // - default_font_directory: Option<PathBuf>
-    // - auto_save_interval: Option<u64>
-    // - default_grid_settings: Option<GridSettings>
+    /// Whether points snap to the grid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grid_enabled: Option<bool>,
+    /// Grid size in font units
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grid_unit_size: Option<f32>,
+    /// Whether the measurement grid is shown
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measurement_grid: Option<bool>,
+    /// Measurement grid spacing in font units
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measurement_grid_interval: Option<f32>,
+    /// Nudge distances for arrow keys alone, with Shift and with Cmd/Ctrl
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nudge_default: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nudge_shift: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nudge_cmd: Option<f32>,
+    /// How far from a point a click still hits it, in pixels on screen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hit_tolerance: Option<f32>,
+    /// Seconds between autosaves, 0 to turn autosave off
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub autosave_interval_secs: Option<u64>,
+    /// Whether empty glyphs show a template outline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_glyphs: Option<bool>,
+    /// Font templates are taken from before the bundled ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_font: Option<PathBuf>,
+    /// Export profile picked when the export dialog opens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_export_profile: Option<String>,
+    /// Language of the interface, such as "en" or "ar"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Draw points and QA severities in colorblind-safe colors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub colorblind_safe: Option<bool>,
+    /// Scale of text, toolbars and points, 1.0 being the normal size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ui_scale: Option<f32>,
+    /// Tool shortcuts replacing the toolbar defaults, by tool id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tool_shortcuts: BTreeMap<String, char>,
+    /// Log verbosity ("warn" to "trace") of subsystems such as "knife"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub log_verbosity: BTreeMap<String, String>,
+    /// MIDI controls driving parameters such as "zoom", as [channel, controller]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub midi_bindings: BTreeMap<String, [u8; 2]>,
+    /// Options bar values by tool id, then option, such as "corner_radius"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tool_options: BTreeMap<String, BTreeMap<String, String>>,
+    /// Tool ids in toolbar order; unlisted tools follow in their default order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub toolbar_order: Vec<String>,
+    /// Tools left off the toolbar
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub toolbar_hidden: Vec<String>,
+    /// Tool groups: the tools a long press on a toolbar tool reveals, by its id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub toolbar_groups: BTreeMap<String, Vec<String>>,
 }
 
 impl ConfigFile {
@@ -104,6 +166,7 @@ impl ConfigFile {
         if !settings_path.exists() {
             let example = ConfigFile {
                 default_theme: Some("forest".to_string()),
+                ..Default::default()
             };
             example.save()?;
             println!("Created settings file: {:?}", settings_path);
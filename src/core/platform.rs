@@ -5,9 +5,20 @@
 
 /// Initialize platform-specific panic handling.
 ///
-/// For WebAssembly builds, this sets up console error reporting
-/// so that Rust panics appear in the browser's developer console.
+/// - On native platforms: Panics are also written to the crash log, so
+///   they aren't lost when no console is attached (a GUI launch on Windows)
+///   or the TUI owns the terminal
+/// - On WebAssembly: Panics appear in the browser's developer console
 pub fn init_panic_handling() {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |panic_info| {
+            let backtrace = std::backtrace::Backtrace::capture();
+            crate::logging::write_crash_log(&format!("{panic_info}\n{backtrace}"));
+            default_hook(panic_info);
+        }));
+    }
     #[cfg(target_arch = "wasm32")]
     {
         console_error_panic_hook::set_once();
@@ -16,17 +27,22 @@ pub fn init_panic_handling() {
 
 /// Handle application errors with platform-appropriate logging.
 ///
-/// - On native platforms: Prints to stderr and exits with code 1
+/// - On native platforms: Prints to stderr, writes the crash log and exits
+///   with code 1
 /// - On WebAssembly: Logs to the browser console
 pub fn handle_error(error: anyhow::Error) {
     #[cfg(not(target_arch = "wasm32"))]
     {
+        let crash_log = crate::logging::write_crash_log(&format!("{error:?}"));
         eprintln!();
         eprintln!("Error starting Bezy:");
         eprintln!("{error}");
         eprintln!();
         eprintln!("Try running with --help for usage information.");
         eprintln!("Or visit: https://bezy.org");
+        if let Some(path) = crash_log {
+            eprintln!("Details were written to {}", path.display());
+        }
         std::process::exit(1);
     }
     #[cfg(target_arch = "wasm32")]
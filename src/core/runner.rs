@@ -3,8 +3,11 @@
 //! Handles the different ways to run the Bezy application
 
 use crate::core::config::{CliArgs, ConfigFile};
+use crate::core::state::{FontData, FontInfo};
 // use crate::logging;  // Not currently used
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
+use bevy::log::{info, warn};
+use std::path::Path;
 
 /// Create and run the application with the given CLI arguments.
 /// Handles special CLI flags and delegates to appropriate runners.
@@ -22,10 +25,46 @@ pub fn run_app(cli_args: CliArgs) -> Result<()> {
         }
     }
 
+    // Handle --export: write the fonts without opening the editor
+    #[cfg(not(target_arch = "wasm32"))]
+    if let (Some(profile), Some(source)) = (&cli_args.export, &cli_args.font_source) {
+        return run_export(source, profile.as_deref());
+    }
+
+    // Handle --build-family: write a family release without opening the editor
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(project) = &cli_args.build_family {
+        return run_build_family(project);
+    }
+
+    // Handle --report: write glyph metrics without opening the editor
+    #[cfg(not(target_arch = "wasm32"))]
+    if let (Some(report), Some(source)) = (&cli_args.report, &cli_args.font_source) {
+        return run_report(source, report);
+    }
+
+    // Handle --subset: write the subset without opening the editor
+    #[cfg(not(target_arch = "wasm32"))]
+    if let (Some(output), Some(source)) = (&cli_args.subset, &cli_args.font_source) {
+        return run_subset(source, &cli_args.keep, output, false);
+    }
+
+    // Handle --trial: write the trial font without opening the editor
+    #[cfg(not(target_arch = "wasm32"))]
+    if let (Some(output), Some(source)) = (&cli_args.trial, &cli_args.font_source) {
+        return run_subset(source, &cli_args.keep, output, true);
+    }
+
+    // Handle --dump-tables: dump and diff a compiled font's tables
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(font) = &cli_args.dump_tables {
+        return run_dump_tables(font, &cli_args.tables, cli_args.against.as_deref());
+    }
+
     // Run the main application
     if cli_args.no_tui {
-        // Only redirect logs when NOT using TUI (for debugging)
-        // When TUI is enabled, redirection happens AFTER TUI captures terminal
+        // Logs go to the console and the log file (see configure_logging);
+        // with the TUI they go to the log file only
         let mut app = crate::core::app::create_app(cli_args)?;
         app.run();
         Ok(())
@@ -41,3 +80,219 @@ pub fn run_app(cli_args: CliArgs) -> Result<()> {
         }
     }
 }
+
+/// Export a font source with one of its export profiles, logging to the
+/// console
+#[cfg(not(target_arch = "wasm32"))]
+fn run_export(source: &Path, profile_name: Option<&str>) -> Result<()> {
+    crate::logging::setup_console_logging()?;
+
+    // Profiles and artwork come from the designspace's first source
+    let ufo_path = crate::data::export::first_source_ufo(source)?;
+    let font = norad::Font::load(&ufo_path)
+        .with_context(|| format!("Failed to load {}", ufo_path.display()))?;
+    let font_data = FontData::from_norad_font(&font, Some(ufo_path));
+    let info = FontInfo::from_norad_font(&font);
+
+    let profiles = &font_data.export_profiles;
+    // Without a name, the default profile from the preferences if the
+    // project has one by that name, else the project's first
+    let default_profile = ConfigFile::load()
+        .and_then(|config| config.default_export_profile)
+        .filter(|name| profiles.get(Some(name.as_str())).is_some());
+    let profile_name = profile_name.or(default_profile.as_deref());
+    let profile = profiles.get(profile_name).ok_or_else(|| {
+        anyhow!(
+            "No export profile named '{}'\nAvailable profiles: {}",
+            profile_name.unwrap_or_default(),
+            profiles.names().join(", ")
+        )
+    })?;
+
+    info!("Exporting {} with profile '{}'", source.display(), profile.name);
+    let progress = crate::data::export::ExportProgress::default();
+    let report =
+        crate::data::export::export_project(source, profile, &font_data, &info, &progress)?;
+    for build in &report.builds {
+        info!("{}", build);
+    }
+    for warning in &report.warnings {
+        warn!("{}", warning);
+    }
+    for path in &report.written {
+        info!("Wrote {}", path.display());
+    }
+    for check in &report.checks {
+        if check.problems.is_empty() {
+            info!("{}", check.summary());
+        } else {
+            warn!("{}", check.summary());
+        }
+    }
+    if report.written.is_empty() {
+        return Err(anyhow!("No font files were exported"));
+    }
+    Ok(())
+}
+
+/// Export every source of a family project into its release folder,
+/// logging to the console
+#[cfg(not(target_arch = "wasm32"))]
+fn run_build_family(project: &Path) -> Result<()> {
+    crate::logging::setup_console_logging()?;
+
+    info!("Building family {}", project.display());
+    let progress = crate::data::export::ExportProgress::default();
+    let report = crate::data::family::build_family(project, &progress)?;
+    for build in &report.builds {
+        info!("{}", build);
+    }
+    for warning in &report.warnings {
+        warn!("{}", warning);
+    }
+    for path in &report.written {
+        info!("Wrote {}", path.display());
+    }
+    for check in &report.checks {
+        if check.problems.is_empty() {
+            info!("{}", check.summary());
+        } else {
+            warn!("{}", check.summary());
+        }
+    }
+    if report.written.is_empty() {
+        return Err(anyhow!("No font files were exported"));
+    }
+    Ok(())
+}
+
+/// Write a glyph metrics report of a font source, every source of a
+/// designspace included
+#[cfg(not(target_arch = "wasm32"))]
+fn run_report(source: &Path, report_path: &Path) -> Result<()> {
+    crate::logging::setup_console_logging()?;
+
+    let ufo_paths = if source.extension().and_then(|ext| ext.to_str()) == Some("designspace") {
+        let designspace = norad::designspace::DesignSpaceDocument::load(source)
+            .context("Failed to load designspace")?;
+        let folder = source.parent().unwrap_or(Path::new("."));
+        designspace
+            .sources
+            .iter()
+            .filter(|source| source.layer.is_none())
+            .map(|source| folder.join(&source.filename))
+            .collect()
+    } else {
+        vec![source.to_path_buf()]
+    };
+
+    let mut rows = Vec::new();
+    for ufo_path in ufo_paths {
+        let font = norad::Font::load(&ufo_path)
+            .with_context(|| format!("Failed to load {}", ufo_path.display()))?;
+        let font_data = FontData::from_norad_font(&font, Some(ufo_path.clone()));
+        let name = ufo_path.file_name().unwrap_or_default().to_string_lossy();
+        rows.extend(crate::data::glyph_report::glyph_report(&font_data, &name));
+    }
+    crate::data::glyph_report::write_glyph_report(&rows, report_path)?;
+    info!("Wrote {} glyph rows to {}", rows.len(), report_path.display());
+    Ok(())
+}
+
+/// Write a subset or a trial font of a font source, a designspace's first
+/// source for one
+#[cfg(not(target_arch = "wasm32"))]
+fn run_subset(source: &Path, keep: &[String], output: &Path, trial: bool) -> Result<()> {
+    crate::logging::setup_console_logging()?;
+
+    let ufo_path = crate::data::export::first_source_ufo(source)?;
+    let font = norad::Font::load(&ufo_path)
+        .with_context(|| format!("Failed to load {}", ufo_path.display()))?;
+    let font_data = FontData::from_norad_font(&font, Some(ufo_path));
+    let info = FontInfo::from_norad_font(&font);
+    let spec = crate::font_source::subset::SubsetSpec::parse(keep)?;
+    let write = if trial {
+        crate::data::subset::write_trial
+    } else {
+        crate::data::subset::write_subset
+    };
+    let report = write(&font_data, &info, &spec, output)?;
+    for warning in &report.warnings {
+        warn!("{}", warning);
+    }
+    info!(
+        "Wrote {} kept glyphs ({} for components) to {}",
+        report.resolved.glyphs.len(),
+        report.resolved.dependencies.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// Dump tables of a compiled font to a `.ttx` file next to it and report
+/// which changed since an earlier build, keeping the dump with the font's
+/// QA reports
+#[cfg(not(target_arch = "wasm32"))]
+fn run_dump_tables(font_path: &Path, tables: &[String], against: Option<&Path>) -> Result<()> {
+    use crate::data::table_dump::FontDump;
+    use crate::qa::storage::{ReportStorage, StoredTableDump};
+
+    crate::logging::setup_console_logging()?;
+
+    // Dumps are stored by path, so the same font is found from any folder
+    let font_path = font_path
+        .canonicalize()
+        .with_context(|| format!("Failed to find {}", font_path.display()))?;
+    let read_dump = |path: &Path| {
+        let data =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        FontDump::new(&data, tables).with_context(|| format!("Failed to dump {}", path.display()))
+    };
+    let dump = read_dump(&font_path)?;
+    let ttx_path = font_path.with_extension("ttx");
+    std::fs::write(&ttx_path, dump.to_ttx())
+        .with_context(|| format!("Failed to write {}", ttx_path.display()))?;
+    info!("Wrote {} tables to {}", dump.tables.len(), ttx_path.display());
+
+    let storage = ReportStorage::new()?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    let previous = match against {
+        Some(path) => Some((read_dump(path)?, path.display().to_string())),
+        None => runtime
+            .block_on(storage.load_latest_table_dump(&font_path))?
+            .map(|stored| {
+                let time = chrono::DateTime::<chrono::Local>::from(stored.storage_timestamp);
+                let label = format!("the dump of {}", time.format("%Y-%m-%d %H:%M"));
+                (stored.dump, label)
+            }),
+    };
+
+    let changes = match &previous {
+        Some((previous, label)) => {
+            let changes = dump.diff(previous);
+            for change in &changes {
+                warn!("{}", change);
+            }
+            info!(
+                "{} of {} tables changed since {}",
+                changes.len(),
+                dump.tables.len(),
+                label
+            );
+            changes
+        }
+        None => {
+            info!("No earlier dump of this font to compare with");
+            Vec::new()
+        }
+    };
+
+    let stored = StoredTableDump {
+        font_path,
+        dump,
+        changes,
+        storage_timestamp: std::time::SystemTime::now(),
+    };
+    runtime.block_on(storage.store_table_dump(&stored))?;
+    Ok(())
+}
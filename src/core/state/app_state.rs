@@ -11,6 +11,9 @@ use crate::core::errors::{validate_ufo_path, BezyContext, BezyResult};
 use crate::core::state::font_data::{FontData, PointData};
 use crate::core::state::font_metrics::FontInfo;
 
+/// Distance between text buffer lines when no font is loaded
+const FALLBACK_TEXT_LINE_HEIGHT: f32 = 1024.0;
+
 /// The main application state - thread-safe for Bevy
 #[derive(Resource, Default, Clone)]
 pub struct AppState {
@@ -29,6 +32,14 @@ pub struct Workspace {
     pub selected: Option<String>,
 }
 
+/// Distance between text buffer lines in the loaded font, for layout and
+/// cursor movement alike
+pub fn text_line_height(app_state: Option<&AppState>) -> f32 {
+    app_state.map_or(FALLBACK_TEXT_LINE_HEIGHT, |state| {
+        state.workspace.info.metrics.text_line_height()
+    })
+}
+
 impl AppState {
     /// Load a font from a UFO file path
     ///
@@ -102,8 +113,7 @@ impl AppState {
     ) -> Option<&mut PointData> {
         self.workspace
             .font
-            .glyphs
-            .get_mut(glyph_name)?
+            .editing_glyph_mut(glyph_name)?
             .outline
             .as_mut()?
             .contours
@@ -1,9 +1,11 @@
 //! Glyph navigation and codepoint utilities
 //!
 //! This module provides functionality for navigating between glyphs
-//! using Unicode codepoints and cycling through available glyphs.
+//! using Unicode codepoints and cycling through available glyphs, and
+//! stepping through the font's glyphs in encoding order.
 
 use crate::core::state::app_state::AppState;
+use crate::font_source::FontData;
 use bevy::prelude::*;
 
 /// Glyph navigation state
@@ -132,3 +134,76 @@ pub fn cycle_codepoint_in_list(
         codepoints.first().cloned()
     }
 }
+
+/// The font's glyphs in encoding order: by first codepoint, then the
+/// unencoded glyphs by name
+pub fn glyphs_in_encoding_order(font: &FontData) -> Vec<String> {
+    let mut glyphs: Vec<(Option<char>, &String)> = font
+        .glyphs
+        .iter()
+        .map(|(name, glyph)| (glyph.unicode_values.first().copied(), name))
+        .collect();
+    // Encoded glyphs first
+    glyphs.sort_by_key(|&(codepoint, name)| (codepoint.is_none(), codepoint, name));
+    glyphs.into_iter().map(|(_, name)| name.clone()).collect()
+}
+
+/// The glyph after or before `current` in `order`, wrapping around at the
+/// ends, or the first glyph when `current` isn't in it
+pub fn step_glyph<'a>(
+    order: &'a [String],
+    current: Option<&str>,
+    direction: CycleDirection,
+) -> Option<&'a str> {
+    let Some(index) = current.and_then(|name| order.iter().position(|glyph| glyph == name)) else {
+        return order.first().map(String::as_str);
+    };
+    let stepped = match direction {
+        CycleDirection::Next => (index + 1) % order.len(),
+        CycleDirection::Previous => (index + order.len() - 1) % order.len(),
+    };
+    order.get(stepped).map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::GlyphData;
+
+    #[test]
+    fn test_encoding_order() {
+        let mut font = FontData::default();
+        for (name, codepoint) in [
+            ("b", Some('b')),
+            ("a.sc", None),
+            ("A", Some('A')),
+            ("a", Some('a')),
+        ] {
+            let glyph = GlyphData {
+                unicode_values: codepoint.into_iter().collect(),
+                ..GlyphData::test(name, 500.0)
+            };
+            font.glyphs.insert(name.to_string(), glyph);
+        }
+
+        let order = glyphs_in_encoding_order(&font);
+        assert_eq!(order, ["A", "a", "b", "a.sc"]);
+        assert_eq!(
+            step_glyph(&order, Some("a"), CycleDirection::Next),
+            Some("b")
+        );
+        assert_eq!(
+            step_glyph(&order, Some("a.sc"), CycleDirection::Next),
+            Some("A")
+        );
+        assert_eq!(
+            step_glyph(&order, Some("A"), CycleDirection::Previous),
+            Some("a.sc")
+        );
+        assert_eq!(
+            step_glyph(&order, Some("x"), CycleDirection::Previous),
+            Some("A")
+        );
+        assert_eq!(step_glyph(&[], None, CycleDirection::Next), None);
+    }
+}
@@ -100,6 +100,9 @@ pub struct SortData {
     pub layout_mode: SortLayoutMode,
     /// Root position (used for text buffer roots); for freeform sorts, use freeform_position
     pub root_position: Vec2,
+    /// Shift from the sort's place in the text flow, e.g. a mark moved onto
+    /// its base by shaping; the cursor ignores it
+    pub placement_offset: Vec2,
     /// Cursor position within this buffer sequence (only for buffer roots)
     /// DEPRECATED: This field is deprecated. Use BufferCursor component on buffer entities instead.
     pub buffer_cursor_position: Option<usize>,
@@ -144,6 +147,7 @@ impl Default for SortData {
             is_active: false,
             layout_mode: SortLayoutMode::LTRText,
             root_position: Vec2::ZERO,
+            placement_offset: Vec2::ZERO,
             buffer_cursor_position: None,
             buffer_id: None, // Default to no buffer ID (freeform)
         }
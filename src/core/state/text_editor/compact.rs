@@ -0,0 +1,230 @@
+//! Compact copies of long texts
+//!
+//! A `SortData` carries a glyph name of its own, positions and buffer
+//! bookkeeping: over a hundred bytes a sort before the name's allocation.
+//! Laying out a text only needs each sort's glyph, codepoint and direction,
+//! so `CompactText` keeps those in twelve bytes a sort, with every distinct
+//! glyph stored once in a table and the rare placement offsets on the side.
+//! It holds no positions; layout expands one line at a time into `SortData`
+//! and computes them on demand.
+
+use super::buffer::{SortData, SortKind, SortLayoutMode};
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Index of a glyph in a `CompactText`'s glyph table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlyphId(u32);
+
+/// Layout mode and kind of a compact sort, packed into a byte
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SortFlags(u8);
+
+impl SortFlags {
+    pub const LINE_BREAK: u8 = 1;
+    pub const RTL: u8 = 1 << 1;
+    pub const FREEFORM: u8 = 1 << 2;
+
+    pub fn contains(self, flag: u8) -> bool {
+        self.0 & flag != 0
+    }
+
+    pub fn layout_mode(self) -> SortLayoutMode {
+        if self.contains(Self::FREEFORM) {
+            SortLayoutMode::Freeform
+        } else if self.contains(Self::RTL) {
+            SortLayoutMode::RTLText
+        } else {
+            SortLayoutMode::LTRText
+        }
+    }
+}
+
+/// A sort without its glyph name or positions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompactSort {
+    pub glyph: GlyphId,
+    pub codepoint: Option<char>,
+    pub flags: SortFlags,
+}
+
+/// A glyph in the table, with the advance its sorts were given
+#[derive(Debug, Clone, PartialEq)]
+struct GlyphEntry {
+    name: String,
+    advance_width: f32,
+}
+
+/// A sequence of sorts in compact form
+#[derive(Debug, Clone, Default)]
+pub struct CompactText {
+    sorts: Vec<CompactSort>,
+    glyphs: Vec<GlyphEntry>,
+    glyph_ids: HashMap<(String, u32), GlyphId>,
+    /// Placement offsets of the sorts that have one, by index
+    placement_offsets: HashMap<usize, Vec2>,
+}
+
+impl CompactText {
+    pub fn len(&self) -> usize {
+        self.sorts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sorts.is_empty()
+    }
+
+    /// Entries in the glyph table, one per distinct glyph and advance
+    pub fn glyph_count(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    pub fn push(&mut self, sort: &SortData) {
+        let mut flags = match sort.layout_mode {
+            SortLayoutMode::LTRText => 0,
+            SortLayoutMode::RTLText => SortFlags::RTL,
+            SortLayoutMode::Freeform => SortFlags::FREEFORM,
+        };
+        let (codepoint, name, advance_width) = match &sort.kind {
+            SortKind::Glyph {
+                codepoint,
+                glyph_name,
+                advance_width,
+            } => (*codepoint, glyph_name.as_str(), *advance_width),
+            SortKind::LineBreak => {
+                flags |= SortFlags::LINE_BREAK;
+                (None, "", 0.0)
+            }
+        };
+        let glyph = self.intern(name, advance_width);
+        if sort.placement_offset != Vec2::ZERO {
+            self.placement_offsets
+                .insert(self.sorts.len(), sort.placement_offset);
+        }
+        self.sorts.push(CompactSort {
+            glyph,
+            codepoint,
+            flags: SortFlags(flags),
+        });
+    }
+
+    /// Glyphs are told apart by name and advance, since spacing edits give
+    /// sorts of the same glyph different advances until they are refreshed
+    fn intern(&mut self, name: &str, advance_width: f32) -> GlyphId {
+        let key = (name.to_string(), advance_width.to_bits());
+        if let Some(&id) = self.glyph_ids.get(&key) {
+            return id;
+        }
+        let id = GlyphId(self.glyphs.len() as u32);
+        self.glyphs.push(GlyphEntry {
+            name: key.0.clone(),
+            advance_width,
+        });
+        self.glyph_ids.insert(key, id);
+        id
+    }
+
+    pub fn get(&self, index: usize) -> Option<CompactSort> {
+        self.sorts.get(index).copied()
+    }
+
+    pub fn glyph_name(&self, glyph: GlyphId) -> &str {
+        &self.glyphs[glyph.0 as usize].name
+    }
+
+    /// The sort at `index` expanded back into `SortData`, without the root
+    /// position or buffer bookkeeping the compact form drops
+    pub fn sort_data(&self, index: usize) -> Option<SortData> {
+        let sort = self.sorts.get(index)?;
+        let kind = if sort.flags.contains(SortFlags::LINE_BREAK) {
+            SortKind::LineBreak
+        } else {
+            let glyph = &self.glyphs[sort.glyph.0 as usize];
+            SortKind::Glyph {
+                codepoint: sort.codepoint,
+                glyph_name: glyph.name.clone(),
+                advance_width: glyph.advance_width,
+            }
+        };
+        Some(SortData {
+            kind,
+            layout_mode: sort.flags.layout_mode(),
+            placement_offset: self
+                .placement_offsets
+                .get(&index)
+                .copied()
+                .unwrap_or_default(),
+            ..Default::default()
+        })
+    }
+
+    /// Index ranges of the text's lines, each ending after its line break
+    pub fn hard_lines(&self) -> Vec<Range<usize>> {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for (index, sort) in self.sorts.iter().enumerate() {
+            if sort.flags.contains(SortFlags::LINE_BREAK) {
+                lines.push(start..index + 1);
+                start = index + 1;
+            }
+        }
+        lines.push(start..self.sorts.len());
+        lines
+    }
+}
+
+impl<'a> FromIterator<&'a SortData> for CompactText {
+    fn from_iter<I: IntoIterator<Item = &'a SortData>>(sorts: I) -> Self {
+        let mut text = CompactText::default();
+        for sort in sorts {
+            text.push(sort);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glyph(name: &str, codepoint: Option<char>) -> SortData {
+        SortData {
+            kind: SortKind::Glyph {
+                codepoint,
+                glyph_name: name.to_string(),
+                advance_width: 500.0,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_round_trip_shares_glyphs() {
+        let mut mark = glyph("acutecomb", None);
+        mark.placement_offset = Vec2::new(-250.0, 0.0);
+        let line_break = SortData {
+            kind: SortKind::LineBreak,
+            ..Default::default()
+        };
+        let sorts = vec![
+            glyph("a", Some('a')),
+            mark,
+            line_break,
+            glyph("a", Some('a')),
+        ];
+
+        let text: CompactText = sorts.iter().collect();
+        assert_eq!(text.len(), 4);
+        assert_eq!(text.glyph_count(), 3);
+        assert_eq!(text.get(0).unwrap().glyph, text.get(3).unwrap().glyph);
+        assert_eq!(text.hard_lines(), vec![0..3, 3..4]);
+
+        for (index, sort) in sorts.iter().enumerate() {
+            let expanded = text.sort_data(index).unwrap();
+            assert_eq!(expanded.kind, sort.kind);
+            assert_eq!(expanded.placement_offset, sort.placement_offset);
+        }
+        assert!(std::mem::size_of::<CompactSort>() <= 12);
+    }
+}
@@ -86,6 +86,7 @@ impl TextEditorState {
             is_active: true, // Automatically activate the new sort
             layout_mode: SortLayoutMode::Freeform,
             root_position: position,
+            placement_offset: Vec2::ZERO,
             buffer_cursor_position: None,
             buffer_id: None, // Freeform sorts have no buffer ID
         };
@@ -332,6 +333,7 @@ impl TextEditorState {
             is_active: true, // Automatically activate the new text root
             layout_mode: layout_mode.clone(),
             root_position: world_position,
+            placement_offset: Vec2::ZERO,
             // For LTR text, cursor goes after the glyph (position 1)
             // For RTL text, cursor goes before the glyph (position 0)
             buffer_cursor_position: Some(match &layout_mode {
@@ -653,6 +655,7 @@ impl TextEditorState {
                 is_active: false, // Don't make new sorts active by default
                 layout_mode: root_layout_mode.clone(),
                 root_position: Vec2::ZERO, // Will be calculated by flow
+                placement_offset: Vec2::ZERO,
                 buffer_cursor_position: None,
                 buffer_id: root_buffer_id, // CRITICAL: Inherit buffer ID from root for isolation
             };
@@ -1048,6 +1051,7 @@ impl TextEditorState {
             is_active: true,
             layout_mode: SortLayoutMode::LTRText,
             root_position: world_position,
+            placement_offset: Vec2::ZERO,
             buffer_cursor_position: Some(1), // Cursor is after the typed character.
             buffer_id: Some(buffer_id),      // Assign unique buffer ID
         };
@@ -1076,6 +1080,7 @@ impl TextEditorState {
                 is_active: false,
                 layout_mode: root_layout_mode,
                 root_position: Vec2::ZERO,
+                placement_offset: Vec2::ZERO,
                 buffer_cursor_position: None,
                 buffer_id: root_buffer_id, // Inherit buffer ID from root
             };
@@ -3,9 +3,11 @@
 //! This module provides text editing functionality for font editing operations.
 //! It's split into multiple files for better organization:
 //! - `buffer.rs`: Gap buffer implementation and data types
+//! - `compact.rs`: Compact copies of long texts for layout
 //! - `editor.rs`: Text editing operations and state management
 
 pub mod buffer;
+pub mod compact;
 pub mod editor;
 pub mod text_buffer;
 
@@ -14,6 +16,10 @@ pub use buffer::{
     ActiveSortEntity, GridConfig, SortBuffer, SortData, SortKind, SortLayoutMode, TextEditorState,
     TextModeConfig,
 };
+pub use compact::CompactText;
 
 // Re-export new buffer-level types
-pub use text_buffer::{ActiveTextBuffer, BufferCursor, BufferMember, BufferSystemSet, TextBuffer};
+pub use text_buffer::{
+    ActiveTextBuffer, BufferCursor, BufferMember, BufferSystemSet, TextAlignment, TextBuffer,
+    TextFrame,
+};
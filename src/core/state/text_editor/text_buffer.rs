@@ -7,18 +7,43 @@
 use crate::core::state::text_editor::buffer::BufferId;
 use crate::core::state::text_editor::SortLayoutMode;
 use bevy::prelude::*;
+use std::ops::Range;
 
 /// Component that marks an entity as a text buffer
 #[derive(Component, Debug, Clone)]
 pub struct TextBuffer {
     /// Unique buffer identifier
     pub id: BufferId,
+    /// Name shown in the text buffers pane
+    pub name: String,
     /// Text direction and layout mode
     pub layout_mode: SortLayoutMode,
     /// World position where this buffer starts (root position)
     pub root_position: Vec2,
     /// Whether this buffer is currently active for editing
     pub is_active: bool,
+    /// Width constraint for wrapped paragraph text (`None` for a single
+    /// unbounded line)
+    pub frame: Option<TextFrame>,
+}
+
+/// Horizontal alignment of lines inside a text frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// A fixed-width frame that wraps a buffer's text into lines
+///
+/// The buffer's root position is the frame's left edge on the first
+/// baseline, for both paragraph directions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextFrame {
+    pub width: f32,
+    pub alignment: TextAlignment,
 }
 
 /// Component that stores cursor position for a text buffer
@@ -26,6 +51,9 @@ pub struct TextBuffer {
 pub struct BufferCursor {
     /// Cursor position within the buffer (0 = before first character)
     pub position: usize,
+    /// Where the selection started, if text is selected; the selection runs
+    /// from here to `position`
+    pub anchor: Option<usize>,
 }
 
 /// Component that links a sort entity to its parent buffer entity
@@ -60,9 +88,28 @@ impl TextBuffer {
     pub fn new(id: BufferId, layout_mode: SortLayoutMode, root_position: Vec2) -> Self {
         Self {
             id,
+            name: format!("Text {}", id.0),
             layout_mode,
             root_position,
             is_active: false,
+            frame: None,
+        }
+    }
+}
+
+impl TextFrame {
+    /// Narrowest frame the resize handles allow, in font units
+    pub const MIN_WIDTH: f32 = 100.0;
+
+    /// A frame whose alignment follows the paragraph direction
+    pub fn new(width: f32, layout_mode: &SortLayoutMode) -> Self {
+        let alignment = match layout_mode {
+            SortLayoutMode::RTLText => TextAlignment::Right,
+            _ => TextAlignment::Left,
+        };
+        Self {
+            width: width.max(Self::MIN_WIDTH),
+            alignment,
         }
     }
 }
@@ -70,14 +117,31 @@ impl TextBuffer {
 impl BufferCursor {
     /// Create a new buffer cursor at the specified position
     pub fn new(position: usize) -> Self {
-        Self { position }
+        Self {
+            position,
+            anchor: None,
+        }
     }
 
     /// Create a cursor at the end of a buffer with the given length
     pub fn at_end(buffer_length: usize) -> Self {
-        Self {
-            position: buffer_length,
+        Self::new(buffer_length)
+    }
+
+    /// Move the cursor, extending the selection (shift) or dropping it
+    pub fn move_to(&mut self, position: usize, extend: bool) {
+        if !extend {
+            self.anchor = None;
+        } else if self.anchor.is_none() {
+            self.anchor = Some(self.position);
         }
+        self.position = position;
+    }
+
+    /// Buffer indices of the selected sorts, if any are selected
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor.filter(|&anchor| anchor != self.position)?;
+        Some(anchor.min(self.position)..anchor.max(self.position))
     }
 }
 
@@ -1,5 +1,9 @@
 #[cfg(feature = "tui")]
-use crate::tui::communication::{AppMessage, FontInfo, GlyphInfo, TuiMessage};
+use crate::core::config::{BezySettings, Preference};
+#[cfg(feature = "tui")]
+use crate::data::font_inspector::FontInspection;
+#[cfg(feature = "tui")]
+use crate::tui::communication::{AppMessage, FontInfo, GlyphInfo, PreferenceLine, TuiMessage};
 use bevy::prelude::*;
 #[cfg(feature = "tui")]
 use tokio::sync::mpsc;
@@ -44,6 +48,29 @@ impl TuiCommunication {
         let _ = self.send(AppMessage::LogLine(message));
     }
 
+    pub fn send_preferences(&self, settings: &BezySettings, message: Option<String>) {
+        let lines = Preference::all()
+            .into_iter()
+            .map(|preference| PreferenceLine {
+                label: preference.label(),
+                value: preference.value(settings),
+                preference,
+            })
+            .collect();
+        let _ = self.send(AppMessage::Preferences(lines, message));
+        let colorblind_safe = settings.accessibility.colorblind_safe;
+        let _ = self.send(AppMessage::ColorblindSafe(colorblind_safe));
+    }
+
+    pub fn send_build_inspections(&self, fonts: Vec<FontInspection>) {
+        let _ = self.send(AppMessage::BuildInspections(fonts));
+    }
+
+    /// Compile errors of the last failed build, as QA issues at their glyphs
+    pub fn send_compile_errors(&self, issues: Vec<crate::qa::QAIssue>) {
+        let _ = self.send(AppMessage::CompileErrors(issues));
+    }
+
     pub fn send_file_action(&self, action: String, path: Option<String>) {
         use chrono::Local;
         let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
@@ -0,0 +1,324 @@
+//! Checks on exported fonts
+//!
+//! Right after export each font is validated (required tables present,
+//! glyph counts agreeing between tables, checksums intact) and compared
+//! with the build it replaced on key metrics: glyph set, advance widths,
+//! units per em, line metrics and layout features. The result is a short
+//! list of human-readable findings, so accidental regressions show up at
+//! build time rather than in a type tester.
+
+use crate::data::sfnt::{self, checksum_errors};
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use write_fonts::read::{FontRef, TableProvider};
+use write_fonts::types::Tag;
+
+/// Tables every exported font needs, besides its outlines
+const REQUIRED_TABLES: [Tag; 8] = [
+    Tag::new(b"cmap"),
+    Tag::new(b"head"),
+    Tag::new(b"hhea"),
+    Tag::new(b"hmtx"),
+    Tag::new(b"maxp"),
+    Tag::new(b"name"),
+    Tag::new(b"OS/2"),
+    Tag::new(b"post"),
+];
+
+/// How many glyph names a finding lists before eliding the rest
+const NAMES_SHOWN: usize = 5;
+
+/// What was checked about one exported font
+#[derive(Debug, Default)]
+pub struct BuildCheck {
+    pub path: PathBuf,
+    /// Validation failures
+    pub problems: Vec<String>,
+    /// Differences from the previous build
+    pub changes: Vec<String>,
+    /// Whether there was a previous build to compare with
+    pub compared: bool,
+}
+
+/// Key metrics of a compiled font
+#[derive(Debug, Default, PartialEq)]
+pub struct BuildSummary {
+    pub glyph_count: usize,
+    pub units_per_em: u16,
+    /// Ascender, descender and line gap
+    pub line_metrics: (i16, i16, i16),
+    /// Glyph name (or "gid<n>" without names) -> advance width
+    pub advances: BTreeMap<String, u16>,
+    pub gsub_features: Vec<String>,
+    pub gpos_features: Vec<String>,
+}
+
+impl BuildSummary {
+    pub fn from_font(font: &FontRef) -> Result<Self> {
+        let widths = sfnt::advance_widths(font)?;
+        let names = sfnt::glyph_names(font)
+            .unwrap_or_else(|_| (0..widths.len()).map(|gid| format!("gid{gid}")).collect());
+        let hhea = font.hhea().context("Font has no 'hhea' table")?;
+        Ok(Self {
+            glyph_count: sfnt::num_glyphs(font)? as usize,
+            units_per_em: font
+                .head()
+                .context("Font has no 'head' table")?
+                .units_per_em(),
+            line_metrics: (
+                hhea.ascender().to_i16(),
+                hhea.descender().to_i16(),
+                hhea.line_gap().to_i16(),
+            ),
+            advances: names.into_iter().zip(widths).collect(),
+            gsub_features: sfnt::gsub_features(font)?,
+            gpos_features: sfnt::gpos_features(font)?,
+        })
+    }
+}
+
+/// Validate an exported font and compare it with the build it replaced
+pub fn check_build(path: PathBuf, data: &[u8], previous: Option<&[u8]>) -> BuildCheck {
+    let mut check = BuildCheck {
+        path,
+        ..Default::default()
+    };
+    let font = match sfnt::read_font(data) {
+        Ok(font) => font,
+        Err(e) => {
+            check.problems.push(format!("unreadable: {e:#}"));
+            return check;
+        }
+    };
+    check.problems = validate(&font);
+
+    let summary = match BuildSummary::from_font(&font) {
+        Ok(summary) => summary,
+        Err(e) => {
+            check.problems.push(format!("metrics unreadable: {e:#}"));
+            return check;
+        }
+    };
+    let previous = previous
+        .and_then(|data| sfnt::read_font(data).ok())
+        .and_then(|font| BuildSummary::from_font(&font).ok());
+    if let Some(previous) = previous {
+        check.changes = compare(&previous, &summary);
+        check.compared = true;
+    }
+    check
+}
+
+impl BuildCheck {
+    /// One line about the font, for logs
+    pub fn summary(&self) -> String {
+        let name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut findings: Vec<&str> = self.problems.iter().map(String::as_str).collect();
+        findings.extend(self.changes.iter().map(String::as_str));
+        if findings.is_empty() {
+            let status = if self.compared {
+                "valid, unchanged from the previous build"
+            } else {
+                "valid, no previous build to compare with"
+            };
+            return format!("{name}: {status}");
+        }
+        format!("{name}: {}", findings.join("; "))
+    }
+}
+
+/// Missing tables, disagreeing glyph counts and bad checksums
+fn validate(font: &FontRef) -> Vec<String> {
+    let has_table = |tag| font.table_data(tag).is_some();
+    let mut problems = Vec::new();
+    let missing: Vec<String> = REQUIRED_TABLES
+        .into_iter()
+        .filter(|tag| !has_table(*tag))
+        .map(|tag| tag.to_string())
+        .collect();
+    if !has_table(Tag::new(b"glyf")) && !has_table(Tag::new(b"CFF ")) {
+        problems.push("no outlines ('glyf' or 'CFF ' table)".to_string());
+    }
+    if !missing.is_empty() {
+        problems.push(format!("missing tables: {}", missing.join(", ")));
+    }
+
+    if let Ok(glyph_count) = sfnt::num_glyphs(font) {
+        if glyph_count == 0 {
+            problems.push("no glyphs".to_string());
+        }
+        if let Ok(names) = sfnt::glyph_names(font) {
+            if names.len() != glyph_count as usize {
+                problems.push(format!(
+                    "'maxp' has {} glyphs but 'post' names {}",
+                    glyph_count,
+                    names.len()
+                ));
+            }
+        }
+    }
+
+    match checksum_errors(font) {
+        Ok(errors) if !errors.is_empty() => {
+            problems.push(format!("bad checksums: {}", errors.join(", ")))
+        }
+        Ok(_) => {}
+        Err(e) => problems.push(format!("checksums unreadable: {e:#}")),
+    }
+    problems
+}
+
+/// A count and the first few names, e.g. "3 glyphs (a, b, c)"
+fn glyph_list<'a>(names: impl IntoIterator<Item = &'a String>) -> String {
+    let names: Vec<&String> = names.into_iter().collect();
+    let mut shown: Vec<&str> = names.iter().take(NAMES_SHOWN).map(|n| n.as_str()).collect();
+    if names.len() > NAMES_SHOWN {
+        shown.push("…");
+    }
+    let noun = if names.len() == 1 { "glyph" } else { "glyphs" };
+    format!("{} {} ({})", names.len(), noun, shown.join(", "))
+}
+
+/// Differences in key metrics between two builds
+pub fn compare(previous: &BuildSummary, current: &BuildSummary) -> Vec<String> {
+    let mut changes = Vec::new();
+    if previous.glyph_count != current.glyph_count {
+        changes.push(format!(
+            "glyph count {} → {}",
+            previous.glyph_count, current.glyph_count
+        ));
+    }
+    let removed: Vec<&String> = previous
+        .advances
+        .keys()
+        .filter(|name| !current.advances.contains_key(*name))
+        .collect();
+    if !removed.is_empty() {
+        changes.push(format!("{} removed", glyph_list(removed)));
+    }
+    let added: Vec<&String> = current
+        .advances
+        .keys()
+        .filter(|name| !previous.advances.contains_key(*name))
+        .collect();
+    if !added.is_empty() {
+        changes.push(format!("{} added", glyph_list(added)));
+    }
+    let changed: Vec<&String> = current
+        .advances
+        .iter()
+        .filter(|(name, width)| {
+            previous
+                .advances
+                .get(*name)
+                .is_some_and(|old| old != *width)
+        })
+        .map(|(name, _)| name)
+        .collect();
+    if !changed.is_empty() {
+        changes.push(format!("advance width of {} changed", glyph_list(changed)));
+    }
+
+    if previous.units_per_em != current.units_per_em {
+        changes.push(format!(
+            "units per em {} → {}",
+            previous.units_per_em, current.units_per_em
+        ));
+    }
+    let labels = ["ascender", "descender", "line gap"];
+    let (old, new) = (previous.line_metrics, current.line_metrics);
+    for (label, (old, new)) in labels
+        .iter()
+        .zip([(old.0, new.0), (old.1, new.1), (old.2, new.2)])
+    {
+        if old != new {
+            changes.push(format!("{label} {old} → {new}"));
+        }
+    }
+
+    for (table, old, new) in [
+        ("GSUB", &previous.gsub_features, &current.gsub_features),
+        ("GPOS", &previous.gpos_features, &current.gpos_features),
+    ] {
+        let lost: Vec<&str> = old
+            .iter()
+            .filter(|tag| !new.contains(tag))
+            .map(String::as_str)
+            .collect();
+        if !lost.is_empty() {
+            changes.push(format!("{table} features dropped: {}", lost.join(", ")));
+        }
+        let gained: Vec<&str> = new
+            .iter()
+            .filter(|tag| !old.contains(tag))
+            .map(String::as_str)
+            .collect();
+        if !gained.is_empty() {
+            changes.push(format!("{table} features added: {}", gained.join(", ")));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use write_fonts::FontBuilder;
+
+    fn summary(advances: &[(&str, u16)]) -> BuildSummary {
+        BuildSummary {
+            glyph_count: advances.len(),
+            units_per_em: 1000,
+            line_metrics: (800, -200, 0),
+            advances: advances
+                .iter()
+                .map(|(name, width)| (name.to_string(), *width))
+                .collect(),
+            gsub_features: vec!["liga".to_string()],
+            gpos_features: vec!["kern".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_compare_reports_glyph_and_metric_changes() {
+        let previous = summary(&[("a", 500), ("b", 520), ("c", 480)]);
+        let mut current = summary(&[("a", 510), ("b", 520), ("d", 600)]);
+        current.line_metrics.0 = 820;
+        current.gsub_features.clear();
+
+        assert_eq!(
+            compare(&previous, &current),
+            vec![
+                "1 glyph (c) removed",
+                "1 glyph (d) added",
+                "advance width of 1 glyph (a) changed",
+                "ascender 800 → 820",
+                "GSUB features dropped: liga",
+            ]
+        );
+        assert!(compare(&previous, &previous).is_empty());
+    }
+
+    #[test]
+    fn test_validation_of_an_incomplete_font() {
+        let bytes = FontBuilder::new()
+            .add_raw(Tag::new(b"head"), vec![0; 54])
+            .add_raw(Tag::new(b"maxp"), vec![0, 0, 0x50, 0, 0, 3])
+            .build();
+        let check = check_build(PathBuf::from("Test-Regular.ttf"), &bytes, None);
+        assert_eq!(
+            check.problems[..2],
+            [
+                "no outlines ('glyf' or 'CFF ' table)",
+                "missing tables: cmap, hhea, hmtx, name, OS/2, post",
+            ]
+        );
+        assert!(check.problems[2].starts_with("metrics unreadable"));
+        assert!(!check.compared);
+    }
+}
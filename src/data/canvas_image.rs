@@ -0,0 +1,280 @@
+//! Images of the canvas
+//!
+//! Draws filled glyph outlines, placed in design space, into a rectangle of
+//! the canvas as an SVG document or as coverage for a PNG, so progress can
+//! be shared without building the font. Outlines are filled with the
+//! nonzero rule like the editor's fills, and anything outside the rectangle
+//! is cut off.
+
+use crate::data::svg::font_to_svg_transform;
+use anyhow::{bail, Result};
+use kurbo::{Affine, BezPath, PathEl, Point, Rect};
+
+/// Widest and tallest image drawn, in pixels
+pub const MAX_IMAGE_SIZE: u32 = 16384;
+
+/// Scanlines sampled across each row of pixels
+const SUBSAMPLES: usize = 4;
+
+/// How far flattened curves may stray from the outline, in pixels
+const FLATTEN_TOLERANCE: f64 = 0.1;
+
+/// Size in pixels of `region` drawn at `pixels_per_unit`
+pub fn image_size(region: Rect, pixels_per_unit: f64) -> Result<(u32, u32)> {
+    let width = (region.width() * pixels_per_unit).ceil();
+    let height = (region.height() * pixels_per_unit).ceil();
+    if width.is_nan() || height.is_nan() || width < 1.0 || height < 1.0 {
+        bail!("The region to draw is empty");
+    }
+    let largest = MAX_IMAGE_SIZE as f64;
+    if width > largest || height > largest {
+        bail!(
+            "A {width}×{height} image is over the {MAX_IMAGE_SIZE} pixel limit; \
+             lower the scale"
+        );
+    }
+    Ok((width as u32, height as u32))
+}
+
+/// Design space → image transform: y flips and the region's top left
+/// corner goes to the origin
+fn region_transform(region: Rect, pixels_per_unit: f64) -> Affine {
+    Affine::translate((-region.x0 * pixels_per_unit, 0.0))
+        * font_to_svg_transform(pixels_per_unit, region.y1)
+}
+
+/// `#rrggbb` of an sRGB color
+fn hex(color: [u8; 4]) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+/// An SVG document of `paths` filled with `fill` inside `region`, sized to
+/// `pixels_per_unit` and on `background` if there is one
+pub fn canvas_svg(
+    paths: &[BezPath],
+    region: Rect,
+    pixels_per_unit: f64,
+    fill: [u8; 4],
+    background: Option<[u8; 4]>,
+) -> Result<String> {
+    let (width, height) = image_size(region, pixels_per_unit)?;
+    let transform = region_transform(region, 1.0);
+    let path_data = paths
+        .iter()
+        .map(|path| (transform * path.clone()).to_svg())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let (view_width, view_height) = (region.width(), region.height());
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {view_width} {view_height}\" \
+         width=\"{width}\" height=\"{height}\">\n"
+    );
+    if let Some(background) = background {
+        svg.push_str(&format!(
+            "  <rect width=\"{view_width}\" height=\"{view_height}\" fill=\"{}\"/>\n",
+            hex(background)
+        ));
+    }
+    svg.push_str(&format!(
+        "  <path d=\"{path_data}\" fill=\"{}\" fill-opacity=\"{:.3}\"/>\n</svg>\n",
+        hex(fill),
+        fill[3] as f64 / 255.0
+    ));
+    Ok(svg)
+}
+
+/// A straight piece of a flattened outline, in pixels, top to bottom
+struct Edge {
+    top: Point,
+    bottom_y: f64,
+    /// Change in x for each pixel down
+    slope: f64,
+    /// +1 or -1, for the direction the outline runs in
+    winding: i32,
+}
+
+impl Edge {
+    fn new(from: Point, to: Point) -> Option<Self> {
+        if from.y == to.y {
+            return None;
+        }
+        let (top, bottom, winding) = if from.y < to.y {
+            (from, to, 1)
+        } else {
+            (to, from, -1)
+        };
+        Some(Self {
+            top,
+            bottom_y: bottom.y,
+            slope: (bottom.x - top.x) / (bottom.y - top.y),
+            winding,
+        })
+    }
+
+    fn x_at(&self, y: f64) -> f64 {
+        self.top.x + (y - self.top.y) * self.slope
+    }
+}
+
+/// Adds the edge back to the start of a contour left open
+fn close_contour(start: &mut Option<Point>, last: Point, edges: &mut Vec<Edge>) {
+    if let Some(edge) = start.take().and_then(|start| Edge::new(last, start)) {
+        edges.push(edge);
+    }
+}
+
+/// Edges of the outlines in pixels, every contour closed
+fn edges(paths: &[BezPath], transform: Affine) -> Vec<Edge> {
+    let mut edges = Vec::new();
+    for path in paths {
+        let (mut start, mut last) = (None, Point::ZERO);
+        let path = transform * path.clone();
+        kurbo::flatten(path.iter(), FLATTEN_TOLERANCE, |element| match element {
+            PathEl::MoveTo(point) => {
+                close_contour(&mut start, last, &mut edges);
+                start = Some(point);
+                last = point;
+            }
+            PathEl::LineTo(point) => {
+                edges.extend(Edge::new(last, point));
+                last = point;
+            }
+            PathEl::ClosePath => close_contour(&mut start, last, &mut edges),
+            _ => {}
+        });
+        close_contour(&mut start, last, &mut edges);
+    }
+    edges.sort_by(|a, b| a.top.y.total_cmp(&b.top.y));
+    edges
+}
+
+/// Adds `weight` of coverage over `from..to` of a row of pixels, with
+/// partial coverage for the pixels at the ends
+fn add_span(row: &mut [f32], from: f64, to: f64, weight: f32) {
+    let width = row.len() as f64;
+    let (from, to) = (from.clamp(0.0, width), to.clamp(0.0, width));
+    if to <= from {
+        return;
+    }
+    let (first, last) = (from.floor() as usize, to.floor() as usize);
+    if first == last {
+        row[first] += (to - from) as f32 * weight;
+        return;
+    }
+    row[first] += (first as f64 + 1.0 - from) as f32 * weight;
+    for coverage in &mut row[first + 1..last] {
+        *coverage += weight;
+    }
+    if let Some(coverage) = row.get_mut(last) {
+        *coverage += (to - last as f64) as f32 * weight;
+    }
+}
+
+/// How much of each pixel of `region` the outlines cover, row by row from
+/// the top, drawn at `pixels_per_unit`
+pub fn rasterize(
+    paths: &[BezPath],
+    region: Rect,
+    pixels_per_unit: f64,
+) -> Result<(u32, u32, Vec<f32>)> {
+    let (width, height) = image_size(region, pixels_per_unit)?;
+    let edges = edges(paths, region_transform(region, pixels_per_unit));
+    let mut coverage = vec![0.0f32; width as usize * height as usize];
+    let weight = 1.0 / SUBSAMPLES as f32;
+
+    let mut next_edge = 0;
+    let mut active: Vec<&Edge> = Vec::new();
+    let mut crossings: Vec<(f64, i32)> = Vec::new();
+    for (index, row) in coverage.chunks_mut(width as usize).enumerate() {
+        for sample in 0..SUBSAMPLES {
+            let y = index as f64 + (sample as f64 + 0.5) / SUBSAMPLES as f64;
+            while next_edge < edges.len() && edges[next_edge].top.y <= y {
+                active.push(&edges[next_edge]);
+                next_edge += 1;
+            }
+            active.retain(|edge| edge.bottom_y > y);
+
+            crossings.clear();
+            crossings.extend(active.iter().map(|edge| (edge.x_at(y), edge.winding)));
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let mut winding = 0;
+            let mut span_start = 0.0;
+            for &(x, direction) in &crossings {
+                if winding == 0 {
+                    span_start = x;
+                }
+                winding += direction;
+                if winding == 0 {
+                    add_span(row, span_start, x, weight);
+                }
+            }
+        }
+    }
+    for value in &mut coverage {
+        *value = value.min(1.0);
+    }
+    Ok((width, height, coverage))
+}
+
+/// RGBA pixels of `coverage` filled with `fill` over `background`, or over
+/// nothing if there is no background
+pub fn composite(coverage: &[f32], fill: [u8; 4], background: Option<[u8; 4]>) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity(coverage.len() * 4);
+    let fill_alpha = fill[3] as f32 / 255.0;
+    for &covered in coverage {
+        let alpha = covered * fill_alpha;
+        match background {
+            Some(background) => {
+                for channel in 0..3 {
+                    let blended =
+                        fill[channel] as f32 * alpha + background[channel] as f32 * (1.0 - alpha);
+                    pixels.push(blended.round() as u8);
+                }
+                pixels.push(background[3]);
+            }
+            None => {
+                pixels.extend_from_slice(&fill[..3]);
+                pixels.push((alpha * 255.0).round() as u8);
+            }
+        }
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kurbo::Shape;
+
+    #[test]
+    fn test_rasterize_fills_inside_and_cuts_off_outside() {
+        // A square over the left half of a 4×2 unit region
+        let square = Rect::new(-10.0, 0.0, 2.0, 2.0).to_path(0.1);
+        let region = Rect::new(0.0, 0.0, 4.0, 2.0);
+        let (width, height, coverage) = rasterize(&[square], region, 2.0).unwrap();
+        assert_eq!((width, height), (8, 4));
+        for row in coverage.chunks(8) {
+            assert!(row[..4].iter().all(|&c| (c - 1.0).abs() < 1e-4));
+            assert!(row[4..].iter().all(|&c| c == 0.0));
+        }
+    }
+
+    #[test]
+    fn test_rasterize_antialiases_edges() {
+        let square = Rect::new(0.0, 0.0, 1.5, 1.0).to_path(0.1);
+        let (_, _, coverage) = rasterize(&[square], Rect::new(0.0, 0.0, 2.0, 1.0), 1.0).unwrap();
+        assert!((coverage[0] - 1.0).abs() < 1e-4);
+        assert!((coverage[1] - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_image_size_limit() {
+        assert!(image_size(Rect::new(0.0, 0.0, 1000.0, 1000.0), 100.0).is_err());
+        assert!(image_size(Rect::new(0.0, 0.0, 0.0, 10.0), 1.0).is_err());
+        assert_eq!(
+            image_size(Rect::new(0.0, 0.0, 100.0, 50.0), 1.5).unwrap(),
+            (150, 75)
+        );
+    }
+}
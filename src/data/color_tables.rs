@@ -0,0 +1,253 @@
+//! COLR and CPAL tables from a font's color layers
+//!
+//! Writes version 0 `COLR` and `CPAL` tables into a compiled font from the
+//! color layers and palettes of its source (see `font_source::color`),
+//! replacing any the compiler wrote, so color glyphs are exported whether
+//! or not it reads the ufo2ft lib keys. Glyphs are found by name in the
+//! `post` table; layers whose glyph isn't in the font are left out. The
+//! tables are built with `write-fonts`, and more glyphs, layers or colors
+//! than their 16-bit counts hold are an error rather than a corrupt font.
+
+use crate::data::sfnt;
+use crate::font_source::color::{ColorData, PaletteColor};
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::HashMap;
+use write_fonts::read::TopLevelTable;
+use write_fonts::tables::colr::{BaseGlyph, Colr, Layer};
+use write_fonts::tables::cpal::{ColorRecord, Cpal};
+use write_fonts::types::GlyphId16;
+
+/// Add `COLR` and `CPAL` tables to compiled font bytes
+pub fn apply_color_tables(font_bytes: &[u8], color: &ColorData) -> Result<Vec<u8>> {
+    let font = sfnt::read_font(font_bytes).context("Failed to read compiled font")?;
+    let glyph_ids: HashMap<String, u16> = sfnt::glyph_names(&font)?
+        .into_iter()
+        .enumerate()
+        .filter_map(|(glyph_id, name)| Some((name, u16::try_from(glyph_id).ok()?)))
+        .collect();
+
+    if let Some((base, layer)) = color.out_of_range_layers().first() {
+        bail!(
+            "Color layer '{}' of '{}' uses palette entry {}, but the palettes have {} entries",
+            layer.glyph_name,
+            base,
+            layer.palette_index,
+            color.palette_size()
+        );
+    }
+
+    let glyphs: Vec<(u16, Vec<(u16, u16)>)> = color
+        .layers
+        .iter()
+        .filter_map(|(base, layers)| {
+            let layers: Vec<(u16, u16)> = layers
+                .iter()
+                .filter_map(|layer| {
+                    let glyph_id = *glyph_ids.get(&layer.glyph_name)?;
+                    Some((glyph_id, layer.palette_index))
+                })
+                .collect();
+            Some((*glyph_ids.get(base)?, layers)).filter(|(_, layers)| !layers.is_empty())
+        })
+        .collect();
+    if glyphs.is_empty() {
+        bail!("None of the color glyphs are in the font");
+    }
+
+    let colr = build_colr_table(&glyphs)?;
+    let cpal = build_cpal_table(&color.palettes)?;
+    let tables = vec![
+        (Colr::TAG, write_table(&colr, "COLR")?),
+        (Cpal::TAG, write_table(&cpal, "CPAL")?),
+    ];
+    Ok(sfnt::with_tables(&font, tables))
+}
+
+/// A version 0 `COLR` table from each color glyph's ID and its layers'
+/// glyph IDs and palette indices, bottom layer first
+///
+/// Fails when the glyphs or layers don't fit the table's 16-bit counts.
+pub fn build_colr_table(glyphs: &[(u16, Vec<(u16, u16)>)]) -> Result<Colr> {
+    let mut glyphs: Vec<&(u16, Vec<(u16, u16)>)> = glyphs.iter().collect();
+    glyphs.sort_by_key(|(glyph_id, _)| *glyph_id);
+
+    let mut base_glyphs = Vec::new();
+    let mut layers = Vec::new();
+    for (glyph_id, glyph_layers) in glyphs {
+        let first_layer_index = count(layers.len(), "color layers")?;
+        let num_layers = count(glyph_layers.len(), "color layers of a glyph")?;
+        base_glyphs.push(BaseGlyph::new(
+            GlyphId16::new(*glyph_id),
+            first_layer_index,
+            num_layers,
+        ));
+        layers.extend(glyph_layers.iter().map(|(layer_glyph, palette_index)| {
+            Layer::new(GlyphId16::new(*layer_glyph), *palette_index)
+        }));
+    }
+    let num_layer_records = count(layers.len(), "color layers")?;
+    Ok(Colr::new(
+        count(base_glyphs.len(), "color glyphs")?,
+        Some(base_glyphs),
+        Some(layers),
+        num_layer_records,
+    ))
+}
+
+/// A version 0 `CPAL` table; a font without palettes gets one empty
+/// palette, which layers in the foreground color need. Palettes shorter
+/// than the longest are filled out with opaque black
+///
+/// Fails when the palettes don't fit the table's 16-bit counts.
+pub fn build_cpal_table(palettes: &[Vec<PaletteColor>]) -> Result<Cpal> {
+    let empty = [Vec::new()];
+    let palettes = if palettes.is_empty() {
+        &empty[..]
+    } else {
+        palettes
+    };
+    let entries = palettes.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut records = Vec::new();
+    let mut first_records = Vec::new();
+    for palette in palettes {
+        first_records.push(count(records.len(), "palette colors")?);
+        records.extend((0..entries).map(|index| {
+            let color = palette.get(index).copied().unwrap_or([0.0, 0.0, 0.0, 1.0]);
+            let [red, green, blue, alpha] =
+                color.map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8);
+            ColorRecord {
+                blue,
+                green,
+                red,
+                alpha,
+            }
+        }));
+    }
+    Ok(Cpal::new(
+        count(entries, "palette entries")?,
+        count(palettes.len(), "palettes")?,
+        count(records.len(), "palette colors")?,
+        Some(records),
+        first_records,
+    ))
+}
+
+/// A count or index stored in 16 bits, or an error naming what overflowed
+fn count(value: usize, what: &str) -> Result<u16> {
+    u16::try_from(value).map_err(|_| anyhow!("Too many {what} for a font ({value})"))
+}
+
+/// Serialize a table, naming it in the error
+fn write_table<T>(table: &T, tag: &str) -> Result<Vec<u8>>
+where
+    T: write_fonts::FontWrite + write_fonts::validate::Validate,
+{
+    write_fonts::dump_table(table).map_err(|e| anyhow!("Failed to write the '{tag}' table: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::ColorLayer;
+    use write_fonts::read::TableProvider;
+    use write_fonts::types::Tag;
+    use write_fonts::FontBuilder;
+
+    /// A format 2 `post` table naming glyphs with custom names
+    fn post_table(names: &[&str]) -> Vec<u8> {
+        let mut table = vec![0, 2, 0, 0];
+        table.resize(32, 0);
+        table.extend_from_slice(&(names.len() as u16).to_be_bytes());
+        for index in 0..names.len() {
+            table.extend_from_slice(&(258 + index as u16).to_be_bytes());
+        }
+        for name in names {
+            table.push(name.len() as u8);
+            table.extend_from_slice(name.as_bytes());
+        }
+        table
+    }
+
+    #[test]
+    fn test_color_layers_export_colr_and_cpal() {
+        let bytes = FontBuilder::new()
+            .add_raw(
+                Tag::new(b"post"),
+                post_table(&["notdef", "A", "A.color0", "A.color1"]),
+            )
+            .build();
+        let mut color = ColorData::default();
+        color.set_palette_color(0, 0, [1.0, 0.0, 0.0, 1.0]);
+        color.set_palette_color(1, 0, [0.0, 0.0, 1.0, 0.5]);
+        color.layers.insert(
+            "A".to_string(),
+            ["A.color0", "A.color1", "missing"]
+                .iter()
+                .map(|glyph_name| ColorLayer {
+                    glyph_name: glyph_name.to_string(),
+                    palette_index: 0,
+                })
+                .collect(),
+        );
+
+        let bytes = apply_color_tables(&bytes, &color).unwrap();
+        let font = sfnt::read_font(&bytes).unwrap();
+
+        // One base glyph with the two layers that are in the font
+        let colr = font.colr().unwrap();
+        assert_eq!(colr.num_base_glyph_records(), 1);
+        assert_eq!(colr.num_layer_records(), 2);
+        let base = colr.base_glyph_records().unwrap().unwrap();
+        assert_eq!(base[0].glyph_id(), GlyphId16::new(1));
+        assert_eq!((base[0].first_layer_index(), base[0].num_layers()), (0, 2));
+        let layers: Vec<(GlyphId16, u16)> = colr
+            .layer_records()
+            .unwrap()
+            .unwrap()
+            .iter()
+            .map(|layer| (layer.glyph_id(), layer.palette_index()))
+            .collect();
+        assert_eq!(layers, [(GlyphId16::new(2), 0), (GlyphId16::new(3), 0)]);
+
+        // Two palettes of one entry, red then half transparent blue
+        let cpal = font.cpal().unwrap();
+        assert_eq!(cpal.num_palette_entries(), 1);
+        assert_eq!(cpal.num_palettes(), 2);
+        let cpal = cpal.offset_data().as_bytes();
+        assert_eq!(&cpal[16..24], &[0, 0, 255, 255, 255, 0, 0, 128]);
+
+        let mut monochrome = ColorData::default();
+        monochrome.layers.insert("B".to_string(), Vec::new());
+        assert!(apply_color_tables(&bytes, &monochrome).is_err());
+
+        color.layers.get_mut("A").unwrap()[1].palette_index = 1;
+        let error = apply_color_tables(&bytes, &color).unwrap_err();
+        assert!(error.to_string().contains("'A.color1' of 'A'"));
+    }
+
+    #[test]
+    fn test_cpal_fills_out_short_palettes() {
+        let cpal = build_cpal_table(&[
+            vec![[1.0, 0.0, 0.0, 1.0]],
+            vec![[0.0, 1.0, 0.0, 1.0], [0.0, 0.0, 1.0, 1.0]],
+        ])
+        .unwrap();
+        // Two entries per palette, the first filled out with black
+        assert_eq!(cpal.num_palette_entries, 2);
+        assert_eq!(cpal.color_record_indices, [0, 2]);
+        let cpal = write_fonts::dump_table(&cpal).unwrap();
+        assert_eq!(&cpal[16..24], &[0, 0, 255, 255, 0, 0, 0, 255]);
+        assert_eq!(&cpal[24..32], &[0, 255, 0, 255, 255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_colr_refuses_more_layers_than_it_can_count() {
+        let layers = vec![(1, 0); u16::MAX as usize + 1];
+        let error = build_colr_table(&[(2, layers)]).unwrap_err();
+        assert!(error.to_string().contains("Too many color layers"));
+
+        let palettes = vec![vec![[0.0; 4]; 256]; 256];
+        assert!(build_cpal_table(&palettes).is_err());
+    }
+}
@@ -0,0 +1,444 @@
+//! Compile cache shared by QA and export
+//!
+//! QA-on-save and export both compile the saved sources with fontc, which
+//! takes a while on large fonts. The cache fingerprints a source (a UFO, or
+//! a designspace and its UFOs) by the contents of its files and keeps the
+//! binary built for each fingerprint on disk, so a source is only compiled
+//! again when its contents change: saving without edits, or exporting what
+//! QA just compiled, returns the stored binary without running fontc.
+//!
+//! Builds are whole fonts. fontc keeps no state between runs, so a source
+//! with any glyph changed is compiled in full. The fingerprint hashes each
+//! glyph separately to report which glyphs changed since the previous build
+//! of that source. Builds are also keyed by the fontc version and the flags
+//! it's run with, so an upgrade doesn't hand back a stale binary.
+//!
+//! Hashes are BLAKE3 over file contents or a value's JSON form, so keys
+//! and manifests stay valid across Rust versions and platforms.
+
+use anyhow::{anyhow, bail, Context, Result};
+use norad::designspace::DesignSpaceDocument;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Compiled fonts kept in the cache before the oldest are removed
+const MAX_CACHED_BUILDS: usize = 20;
+
+/// Versions of fontc, fontir and fontbe this build of Bezy compiles with
+const FONTC_VERSION: &str = env!("BEZY_FONTC_VERSION");
+
+/// How many changed names a summary lists before eliding the rest
+const NAMES_SHOWN: usize = 5;
+
+/// Content hashes of a source: one per glyph name, covering that glyph in
+/// every layer and master, and one per other file
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceFingerprint {
+    pub glyphs: BTreeMap<String, u64>,
+    pub files: BTreeMap<String, u64>,
+}
+
+impl SourceFingerprint {
+    /// Fingerprint a UFO, or a designspace and the UFOs it references
+    pub fn of_source(source: &Path) -> Result<Self> {
+        let mut entries = Vec::new();
+        if source.extension().and_then(|ext| ext.to_str()) == Some("designspace") {
+            let bytes = std::fs::read(source)
+                .with_context(|| format!("Failed to read {}", source.display()))?;
+            entries.push((file_name(source), bytes));
+            let document =
+                DesignSpaceDocument::load(source).context("Failed to load designspace")?;
+            let dir = source.parent().unwrap_or_else(|| Path::new("."));
+            let ufos: BTreeSet<&String> = document.sources.iter().map(|s| &s.filename).collect();
+            for ufo in ufos {
+                read_files(&dir.join(ufo), ufo, &mut entries)?;
+            }
+        } else {
+            read_files(source, &file_name(source), &mut entries)?;
+        }
+        Ok(Self::from_files(entries))
+    }
+
+    /// Fingerprint files given by their path relative to the source
+    pub fn from_files(entries: impl IntoIterator<Item = (String, Vec<u8>)>) -> Self {
+        let mut glyph_files: BTreeMap<String, Vec<(String, u64)>> = BTreeMap::new();
+        let mut files = BTreeMap::new();
+        for (path, bytes) in entries {
+            let hash = short_hash(blake3::hash(&bytes));
+            if path.ends_with(".glif") {
+                let name = glif_name(&bytes).unwrap_or_else(|| {
+                    let stem = path.rsplit('/').next().unwrap_or(&path);
+                    stem.trim_end_matches(".glif").to_string()
+                });
+                glyph_files.entry(name).or_default().push((path, hash));
+            } else {
+                files.insert(path, hash);
+            }
+        }
+        let glyphs = glyph_files
+            .into_iter()
+            .map(|(name, mut parts)| {
+                parts.sort();
+                (name, short_hash(stable_hash(&parts)))
+            })
+            .collect();
+        Self { glyphs, files }
+    }
+
+    /// Cache key of the build of this fingerprint with fontc `flags`
+    ///
+    /// Mixes in the versions of fontc and the crates it compiles with,
+    /// which the build script reads from Cargo.lock, and the flags, so
+    /// changing either compiles again.
+    pub fn key(&self, flags: fontc::Flags) -> String {
+        let compiler = (FONTC_VERSION, flags.bits());
+        stable_hash(&(self, compiler)).to_hex().to_string()
+    }
+
+    /// Glyphs added, removed or edited since `previous`, and other files
+    pub fn changes_since(&self, previous: &Self) -> (Vec<String>, Vec<String>) {
+        (
+            changed_keys(&previous.glyphs, &self.glyphs),
+            changed_keys(&previous.files, &self.files),
+        )
+    }
+}
+
+/// A compiled font from the cache
+#[derive(Debug)]
+pub struct CachedBuild {
+    /// Where the compiled font is stored; copy it before modifying it
+    pub path: PathBuf,
+    /// Whether the font came from the cache without compiling
+    pub reused: bool,
+    /// Glyphs changed since the previous build of the source, if there
+    /// was one
+    pub changed_glyphs: Option<Vec<String>>,
+    /// Other files changed since the previous build of the source
+    pub changed_files: Vec<String>,
+}
+
+impl CachedBuild {
+    /// One line about what was compiled, for logs
+    pub fn summary(&self) -> String {
+        if self.reused {
+            return "unchanged, reused the cached build".to_string();
+        }
+        let Some(glyphs) = &self.changed_glyphs else {
+            return "compiled, no previous build".to_string();
+        };
+        let mut changes = Vec::new();
+        if !glyphs.is_empty() {
+            let noun = if glyphs.len() == 1 { "glyph" } else { "glyphs" };
+            changes.push(format!("{} {} ({})", glyphs.len(), noun, name_list(glyphs)));
+        }
+        if !self.changed_files.is_empty() {
+            changes.push(name_list(&self.changed_files));
+        }
+        if changes.is_empty() {
+            return "compiled".to_string();
+        }
+        format!("compiled, changed: {}", changes.join(", "))
+    }
+}
+
+/// Compiled fonts on disk, keyed by the fingerprint of their source
+pub struct CompileCache {
+    dir: PathBuf,
+}
+
+impl Default for CompileCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CompileCache {
+    /// The cache shared by every QA run and export, in the user's cache
+    /// directory
+    pub fn new() -> Self {
+        let cache_dir = dirs::cache_dir().unwrap_or_else(std::env::temp_dir);
+        Self::in_dir(cache_dir.join("bezy").join("compiled"))
+    }
+
+    pub fn in_dir(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Compile a UFO or designspace, or return its cached build if its
+    /// contents haven't changed
+    pub fn compile(&self, source: &Path) -> Result<CachedBuild> {
+        self.compile_as(source, source)
+    }
+
+    /// Compile `source` as a build of `original`, the source it's a
+    /// temporary copy of, so changes are reported against the previous
+    /// build of `original`
+    pub fn compile_as(&self, source: &Path, original: &Path) -> Result<CachedBuild> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let fingerprint = SourceFingerprint::of_source(source)?;
+        let flags = fontc_flags();
+        let path = self.dir.join(format!("{}.ttf", fingerprint.key(flags)));
+        let manifest_path = self.manifest_path(original);
+        let previous: Option<SourceFingerprint> = std::fs::read(&manifest_path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok());
+        let (changed_glyphs, changed_files) = match &previous {
+            Some(previous) => {
+                let (glyphs, files) = fingerprint.changes_since(previous);
+                (Some(glyphs), files)
+            }
+            None => (None, Vec::new()),
+        };
+
+        let reused = path.exists();
+        if reused {
+            // Mark the build as recently used so pruning keeps it
+            if let Ok(file) = std::fs::File::options().append(true).open(&path) {
+                let _ = file.set_modified(std::time::SystemTime::now());
+            }
+        } else {
+            let bytes = compile_with_fontc(source, flags)?;
+            let partial = path.with_extension("partial");
+            std::fs::write(&partial, bytes)
+                .with_context(|| format!("Failed to write {}", partial.display()))?;
+            std::fs::rename(&partial, &path)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            self.prune(MAX_CACHED_BUILDS);
+        }
+        if previous.as_ref() != Some(&fingerprint) {
+            let json = serde_json::to_vec(&fingerprint)?;
+            std::fs::write(&manifest_path, json)
+                .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+        }
+
+        Ok(CachedBuild {
+            path,
+            reused,
+            changed_glyphs,
+            changed_files,
+        })
+    }
+
+    /// Remove all but the `max_builds` most recently used builds, and all
+    /// but as many of the most recently written manifests
+    pub fn prune(&self, max_builds: usize) {
+        for extension in ["ttf", "json"] {
+            self.prune_files(extension, max_builds);
+        }
+    }
+
+    /// Remove all but the `keep` most recently modified files with an
+    /// extension
+    fn prune_files(&self, extension: &str, keep: usize) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        let mut files: Vec<(PathBuf, std::time::SystemTime)> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some(extension))
+            .filter_map(|path| {
+                let modified = path.metadata().and_then(|meta| meta.modified()).ok()?;
+                Some((path, modified))
+            })
+            .collect();
+        files.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+        for (path, _) in files.into_iter().skip(keep) {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// File holding the fingerprint of the previous build of a source
+    fn manifest_path(&self, source: &Path) -> PathBuf {
+        let source = source
+            .canonicalize()
+            .unwrap_or_else(|_| source.to_path_buf());
+        let hash = short_hash(blake3::hash(source.as_os_str().as_encoded_bytes()));
+        self.dir.join(format!("{hash:016x}.json"))
+    }
+}
+
+/// Fail on targets where fontc can't run
+///
+/// fontc builds in a temporary directory, and `std::env::temp_dir` panics
+/// on wasm32, so the web build checks this before creating one.
+pub(crate) fn ensure_can_compile() -> Result<()> {
+    if cfg!(target_arch = "wasm32") {
+        bail!("Fonts can't be compiled in the web build");
+    }
+    Ok(())
+}
+
+/// The flags every build runs fontc with
+pub(crate) fn fontc_flags() -> fontc::Flags {
+    fontc::Flags::default()
+}
+
+/// Compile a source with fontc in a temporary build directory
+pub(crate) fn compile_with_fontc(source: &Path, flags: fontc::Flags) -> Result<Vec<u8>> {
+    ensure_can_compile()?;
+    let build_dir = tempfile::TempDir::new().context("Failed to create build directory")?;
+    let input = fontc::Input::new(source).map_err(|e| anyhow!("Invalid fontc input: {e}"))?;
+    fontc::generate_font(&input, build_dir.path(), None, flags, false)
+        .map_err(|e| anyhow!("fontc failed: {e}"))
+}
+
+/// Read every file under `dir`, named by `prefix` and its relative path
+fn read_files(dir: &Path, prefix: &str, entries: &mut Vec<(String, Vec<u8>)>) -> Result<()> {
+    let mut children: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    children.sort();
+    for child in children {
+        let name = file_name(&child);
+        if name.starts_with('.') {
+            continue;
+        }
+        let path = format!("{prefix}/{name}");
+        if child.is_dir() {
+            read_files(&child, &path, entries)?;
+        } else {
+            let bytes = std::fs::read(&child)
+                .with_context(|| format!("Failed to read {}", child.display()))?;
+            entries.push((path, bytes));
+        }
+    }
+    Ok(())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// BLAKE3 hash of a value's JSON form
+fn stable_hash(value: &impl Serialize) -> blake3::Hash {
+    let json = serde_json::to_vec(value).expect("cache keys serialize to JSON");
+    blake3::hash(&json)
+}
+
+/// The first 64 bits of a hash, enough to tell a file's versions apart
+fn short_hash(hash: blake3::Hash) -> u64 {
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&hash.as_bytes()[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+/// The glyph name in a .glif file's `<glyph name="...">` element
+fn glif_name(bytes: &[u8]) -> Option<String> {
+    let text = std::str::from_utf8(bytes).ok()?;
+    let element = &text[text.find("<glyph")?..];
+    let value = &element[element.find("name=")? + 5..];
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &value[1..];
+    Some(value[..value.find(quote)?].to_string())
+}
+
+/// Keys added, removed or with a different hash
+fn changed_keys(previous: &BTreeMap<String, u64>, current: &BTreeMap<String, u64>) -> Vec<String> {
+    let keys: BTreeSet<&String> = previous.keys().chain(current.keys()).collect();
+    keys.into_iter()
+        .filter(|key| previous.get(*key) != current.get(*key))
+        .cloned()
+        .collect()
+}
+
+/// The first few names, e.g. "a, b, c, …"
+fn name_list(names: &[String]) -> String {
+    let mut shown: Vec<&str> = names.iter().take(NAMES_SHOWN).map(String::as_str).collect();
+    if names.len() > NAMES_SHOWN {
+        shown.push("…");
+    }
+    shown.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glif(name: &str, width: u32) -> Vec<u8> {
+        format!(
+            "<?xml version=\"1.0\"?>\n<glyph name=\"{name}\" format=\"2\">\n  \
+             <advance width=\"{width}\"/>\n</glyph>\n"
+        )
+        .into_bytes()
+    }
+
+    fn fingerprint(a_width: u32, with_b: bool, fea: &str) -> SourceFingerprint {
+        let mut files = vec![
+            ("Font.ufo/glyphs/A_.glif".to_string(), glif("A", a_width)),
+            ("Font.ufo/glyphs.bg/A_.glif".to_string(), glif("A", 500)),
+            ("Font.ufo/features.fea".to_string(), fea.as_bytes().to_vec()),
+        ];
+        if with_b {
+            files.push(("Font.ufo/glyphs/B_.glif".to_string(), glif("B", 600)));
+        }
+        SourceFingerprint::from_files(files)
+    }
+
+    #[test]
+    fn test_fingerprint_keys_glyphs_by_name_across_layers() {
+        let before = fingerprint(500, true, "");
+        assert_eq!(before.glyphs.keys().collect::<Vec<_>>(), ["A", "B"]);
+        let flags = fontc_flags();
+        assert_eq!(before.key(flags), fingerprint(500, true, "").key(flags));
+        assert_ne!(
+            before.key(fontc::Flags::empty()),
+            before.key(fontc::Flags::all())
+        );
+
+        let after = fingerprint(520, false, "feature kern {} kern;");
+        assert_ne!(before.key(flags), after.key(flags));
+        assert_eq!(
+            after.changes_since(&before),
+            (
+                vec!["A".to_string(), "B".to_string()],
+                vec!["Font.ufo/features.fea".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn test_hashes_are_stable() {
+        // The start of BLAKE3's hash of no bytes, whatever the platform
+        let empty =
+            SourceFingerprint::from_files([("Font.ufo/features.fea".to_string(), Vec::new())]);
+        assert_eq!(empty.files["Font.ufo/features.fea"], 0xa6a1_f9f5_b949_13af);
+    }
+
+    #[test]
+    fn test_prune_removes_old_builds_and_manifests() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let start = std::time::SystemTime::UNIX_EPOCH;
+        for (index, name) in ["a", "b", "c"].iter().enumerate() {
+            for extension in ["ttf", "json"] {
+                let path = dir.path().join(format!("{name}.{extension}"));
+                let file = std::fs::File::create(&path).unwrap();
+                let age = std::time::Duration::from_secs(60 * (index as u64 + 1));
+                file.set_modified(start + age).unwrap();
+            }
+        }
+
+        CompileCache::in_dir(dir.path().to_path_buf()).prune(2);
+        let mut left: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .flatten()
+            .map(|entry| file_name(&entry.path()))
+            .collect();
+        left.sort();
+        assert_eq!(left, ["b.json", "b.ttf", "c.json", "c.ttf"]);
+    }
+
+    #[test]
+    fn test_glif_name() {
+        assert_eq!(glif_name(&glif("a.sc", 500)), Some("a.sc".to_string()));
+        assert_eq!(glif_name(b"<glyph name='x'/>"), Some("x".to_string()));
+        assert_eq!(glif_name(b"<glyph format=\"2\"/>"), None);
+    }
+}
@@ -0,0 +1,124 @@
+//! Glyph diagnostics from failed fontc builds
+//!
+//! fontc fails with one error for the whole build, listing the tasks that
+//! failed as `Tasks failed: [(Fe(Glyph("A")), "message"), …]`, or naming
+//! the glyph in quotes inside the message itself. The error is split into a
+//! diagnostic per failure, attached to the glyph of the font it names, so
+//! the editor can show it at the glyph rather than only in the log.
+
+/// One failure of a build, and the glyph it is about if it names one
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompileDiagnostic {
+    pub glyph: Option<String>,
+    pub message: String,
+}
+
+/// The diagnostics in a fontc error, with `is_glyph` telling which quoted
+/// names are glyphs of the font
+pub fn glyph_diagnostics(error: &str, is_glyph: impl Fn(&str) -> bool) -> Vec<CompileDiagnostic> {
+    let mut diagnostics: Vec<CompileDiagnostic> = Vec::new();
+    let mut pending_glyph = None;
+    for literal in string_literals(error) {
+        if is_glyph(&literal) {
+            pending_glyph = Some(literal);
+            continue;
+        }
+        let diagnostic = CompileDiagnostic {
+            glyph: pending_glyph
+                .take()
+                .or_else(|| quoted_glyph(&literal, &is_glyph)),
+            message: literal.trim().to_string(),
+        };
+        if !diagnostics.contains(&diagnostic) {
+            diagnostics.push(diagnostic);
+        }
+    }
+
+    if diagnostics.is_empty() {
+        diagnostics.push(CompileDiagnostic {
+            glyph: pending_glyph.or_else(|| quoted_glyph(error, &is_glyph)),
+            message: error.trim().to_string(),
+        });
+    }
+    diagnostics
+}
+
+/// Double-quoted strings outside any other, unescaped the way Rust's
+/// `Debug` escapes them
+fn string_literals(text: &str) -> Vec<String> {
+    let mut literals = Vec::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '"' {
+            continue;
+        }
+        let mut literal = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => break,
+                '\\' => match chars.next() {
+                    Some('n') => literal.push('\n'),
+                    Some('t') => literal.push('\t'),
+                    Some(escaped) => literal.push(escaped),
+                    None => {}
+                },
+                _ => literal.push(c),
+            }
+        }
+        literals.push(literal);
+    }
+    literals
+}
+
+/// The first glyph name quoted in `text` with ', " or `
+fn quoted_glyph(text: &str, is_glyph: &impl Fn(&str) -> bool) -> Option<String> {
+    text.char_indices()
+        .filter(|(_, c)| matches!(c, '\'' | '"' | '`'))
+        .find_map(|(start, quote)| {
+            let rest = &text[start + 1..];
+            let name = &rest[..rest.find(quote)?];
+            is_glyph(name).then(|| name.to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_glyph(name: &str) -> bool {
+        ["A", "Aacute", "acutecomb", "B"].contains(&name)
+    }
+
+    #[test]
+    fn test_failed_tasks_attach_to_their_glyphs() {
+        let error = "fontc failed: Tasks failed: [(Fe(Glyph(\"Aacute\")), \"Component cycle \
+                     through 'acutecomb'\"), (Be(Glyf(\"B\")), \"Contour \\\"0\\\" is open\")]";
+        assert_eq!(
+            glyph_diagnostics(error, is_glyph),
+            [
+                CompileDiagnostic {
+                    glyph: Some("Aacute".to_string()),
+                    message: "Component cycle through 'acutecomb'".to_string(),
+                },
+                CompileDiagnostic {
+                    glyph: Some("B".to_string()),
+                    message: "Contour \"0\" is open".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_glyph_named_in_message() {
+        let diagnostics = glyph_diagnostics("fontc failed: 'A' has no default master", is_glyph);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].glyph.as_deref(), Some("A"));
+
+        let diagnostics = glyph_diagnostics("fontc failed: No such file: 'Font.ufo'", is_glyph);
+        assert_eq!(diagnostics[0].glyph, None);
+        assert_eq!(
+            diagnostics[0].message,
+            "fontc failed: No such file: 'Font.ufo'"
+        );
+    }
+}
@@ -8,7 +8,9 @@ use crate::core::state::{
     ComponentData, ContourData, FontData, FontInfo, GlyphData, OutlineData, PointData,
     PointTypeData,
 };
-use kurbo::{BezPath, Point};
+use crate::font_source::layers::draws_on_layer;
+use crate::font_source::AnchorData;
+use kurbo::{BezPath, PathEl, Point};
 use norad::Font;
 use std::path::PathBuf;
 
@@ -28,6 +30,20 @@ impl GlyphData {
             .map(ComponentData::from_norad_component)
             .collect();
 
+        let anchors = norad_glyph
+            .anchors
+            .iter()
+            .map(|anchor| AnchorData {
+                name: anchor
+                    .name
+                    .as_ref()
+                    .map(|name| name.to_string())
+                    .unwrap_or_default(),
+                x: anchor.x,
+                y: anchor.y,
+            })
+            .collect();
+
         Self {
             name: norad_glyph.name().to_string(),
             advance_width: norad_glyph.width,
@@ -35,6 +51,7 @@ impl GlyphData {
             unicode_values: norad_glyph.codepoints.iter().collect(),
             outline,
             components,
+            anchors,
         }
     }
 
@@ -60,6 +77,15 @@ impl GlyphData {
             .map(ComponentData::to_norad_component)
             .collect();
 
+        glyph.anchors = self
+            .anchors
+            .iter()
+            .map(|anchor| {
+                let name = anchor.name.parse::<norad::Name>().ok();
+                norad::Anchor::new(anchor.x, anchor.y, name, None, None, None)
+            })
+            .collect();
+
         glyph
     }
 }
@@ -269,6 +295,99 @@ impl ContourData {
     }
 }
 
+impl ContourData {
+    /// Convert a kurbo path into UFO-style contours, one per subpath
+    ///
+    /// Closed subpaths start on the on-curve point that closes them, so the
+    /// result round-trips through `to_bezpath`. Open subpaths start with a
+    /// `Move` point.
+    pub fn from_bezpath(path: &BezPath) -> Vec<ContourData> {
+        let mut contours = Vec::new();
+        let mut points: Vec<PointData> = Vec::new();
+        let mut start: Option<Point> = None;
+
+        let on_curve = |pt: Point, point_type: PointTypeData| PointData {
+            x: pt.x,
+            y: pt.y,
+            point_type,
+        };
+        let off_curve = |pt: Point| PointData {
+            x: pt.x,
+            y: pt.y,
+            point_type: PointTypeData::OffCurve,
+        };
+
+        for element in path.elements() {
+            match *element {
+                PathEl::MoveTo(pt) => {
+                    if let Some(start_pt) = start.take() {
+                        contours.push(Self::finish_open_contour(start_pt, &mut points));
+                    }
+                    start = Some(pt);
+                }
+                PathEl::LineTo(pt) => points.push(on_curve(pt, PointTypeData::Line)),
+                PathEl::QuadTo(c, pt) => {
+                    points.push(off_curve(c));
+                    points.push(on_curve(pt, PointTypeData::QCurve));
+                }
+                PathEl::CurveTo(c1, c2, pt) => {
+                    points.push(off_curve(c1));
+                    points.push(off_curve(c2));
+                    points.push(on_curve(pt, PointTypeData::Curve));
+                }
+                PathEl::ClosePath => {
+                    if let Some(start_pt) = start.take() {
+                        contours.push(Self::finish_closed_contour(start_pt, &mut points));
+                    }
+                }
+            }
+        }
+
+        if let Some(start_pt) = start {
+            contours.push(Self::finish_open_contour(start_pt, &mut points));
+        }
+
+        contours.retain(|contour| !contour.points.is_empty());
+        contours
+    }
+
+    fn finish_open_contour(start: Point, points: &mut Vec<PointData>) -> ContourData {
+        let mut contour_points = vec![PointData {
+            x: start.x,
+            y: start.y,
+            point_type: PointTypeData::Move,
+        }];
+        contour_points.append(points);
+        ContourData {
+            points: contour_points,
+        }
+    }
+
+    fn finish_closed_contour(start: Point, points: &mut Vec<PointData>) -> ContourData {
+        let closes_on_start = points.last().is_some_and(|last| {
+            (last.x - start.x).abs() < 1e-6 && (last.y - start.y).abs() < 1e-6
+        });
+
+        let mut contour_points = Vec::with_capacity(points.len() + 1);
+        if closes_on_start {
+            // The closing segment's end point becomes the contour start
+            let closing = points.pop().expect("closes_on_start implies a last point");
+            contour_points.push(closing);
+        } else {
+            // Implicit closing line back to the start point
+            contour_points.push(PointData {
+                x: start.x,
+                y: start.y,
+                point_type: PointTypeData::Line,
+            });
+        }
+        contour_points.append(points);
+        ContourData {
+            points: contour_points,
+        }
+    }
+}
+
 impl PointData {
     pub fn from_norad_point(norad_point: &norad::ContourPoint) -> Self {
         Self {
@@ -327,7 +446,56 @@ impl FontData {
             glyphs.insert(glyph.name().to_string(), glyph_data);
         }
 
-        Self { glyphs, path }
+        let color = crate::font_source::ColorData::from_norad_lib(&font.lib);
+        for (base, layer) in color.out_of_range_layers() {
+            bevy::log::warn!(
+                "Color layer '{}' of '{}' uses palette entry {}, which no palette has",
+                layer.glyph_name,
+                base,
+                layer.palette_index
+            );
+        }
+        let smart_pieces = crate::font_source::SmartPieceData::from_norad_lib(&font.lib, &glyphs);
+        let kerning = crate::font_source::KerningData::from_norad_font(font);
+        let export_profiles = crate::font_source::ExportProfiles::from_norad_lib(&font.lib);
+        let stat = crate::font_source::StatConfig::from_norad_lib(&font.lib);
+        let design_frames = crate::font_source::DesignFrames::from_norad_lib(&font.lib);
+        let glyph_filters = crate::font_source::GlyphFilters::from_norad_layer(layer);
+        let anchor_sync = crate::font_source::AnchorSync::from_norad_layer(layer);
+        let glyph_order = crate::font_source::GlyphOrder::from_norad_lib(&font.lib);
+        let session = crate::font_source::WorkspaceSession::from_norad_lib(&font.lib);
+
+        // Keep every other layer (background, sketches) alongside the foreground
+        let default_layer_name = layer.name().to_string();
+        let layers = font
+            .layers
+            .iter()
+            .filter(|other| other.name().as_str() != default_layer_name)
+            .map(|other| crate::font_source::GlyphLayer {
+                name: other.name().to_string(),
+                glyphs: other
+                    .iter()
+                    .map(|glyph| (glyph.name().to_string(), GlyphData::from_norad_glyph(glyph)))
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            glyphs,
+            path,
+            color,
+            layers,
+            editing_layer: None,
+            smart_pieces,
+            kerning,
+            export_profiles,
+            stat,
+            design_frames,
+            glyph_filters,
+            anchor_sync,
+            glyph_order,
+            session,
+        }
     }
 
     /// Convert back to a complete norad Font
@@ -336,14 +504,53 @@ impl FontData {
 
         // Set font info using our conversion method
         font.font_info = info.to_norad_font_info();
+        self.color.write_to_norad_lib(&mut font.lib);
+        self.smart_pieces.write_to_norad_lib(&mut font.lib, &self.glyphs);
+        self.kerning.write_to_norad_font(&mut font);
+        self.export_profiles.write_to_norad_lib(&mut font.lib);
+        self.stat.write_to_norad_lib(&mut font.lib);
+        self.design_frames.write_to_norad_lib(&mut font.lib);
+        self.glyph_order.write_to_norad_lib(&mut font.lib);
+        if let Some(session) = &self.session {
+            session.write_to_norad_lib(&mut font.lib);
+        }
+
+        // Carry over the UFO's data and images folders (e.g. SVG artwork),
+        // since saving rewrites the whole package
+        if let Some(path) = self.path.as_ref().filter(|path| path.exists()) {
+            let request = norad::DataRequest::none().data(true).images(true);
+            if let Ok(existing) = Font::load_requested_data(path, request) {
+                font.data = existing.data;
+                font.images = existing.images;
+            }
+        }
 
         // Add glyphs to the default layer
         let layer = font.default_layer_mut();
         for glyph_data in self.glyphs.values() {
-            let glyph = glyph_data.to_norad_glyph();
+            let mut glyph = glyph_data.to_norad_glyph();
+            self.glyph_filters.write_to_norad_glyph(&mut glyph);
+            self.anchor_sync.write_to_norad_glyph(&mut glyph);
             layer.insert_glyph(glyph);
         }
 
+        for glyph_layer in &self.layers {
+            // The layer being edited has a glyph for every foreground
+            // glyph; leave out the ones it doesn't draw
+            let editing = self.editing_layer.as_deref() == Some(glyph_layer.name.as_str());
+            match font.layers.new_layer(&glyph_layer.name) {
+                Ok(layer) => {
+                    for (glyph_name, glyph_data) in &glyph_layer.glyphs {
+                        if editing && !draws_on_layer(glyph_data, self.glyphs.get(glyph_name)) {
+                            continue;
+                        }
+                        layer.insert_glyph(glyph_data.to_norad_glyph());
+                    }
+                }
+                Err(e) => bevy::log::warn!("Skipping layer '{}': {}", glyph_layer.name, e),
+            }
+        }
+
         font
     }
 }
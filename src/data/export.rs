@@ -0,0 +1,436 @@
+//! Font export with export profiles
+//!
+//! Compiles a project's saved sources with fontc, one variable font from a
+//! designspace and one static font per source UFO, and post-processes each
+//! as its profile asks. Open corners are closed and cubic curves made
+//! quadratic in a copy of the sources before compiling (see `export_copy`);
+//! when the copy can't be made, fontc gets the sources. Overlaps are only
+//! ever removed from the compiled fonts, not the copy (`export_copy` says
+//! why), so sources keep overlapping contours such as crossbars for
+//! editing. Overlap removal and subsetting use fontTools
+//! (`fonttools ttLib.removeOverlaps`, `fonttools subset`) and autohinting
+//! uses `ttfautohint`; these have to be on the PATH, and a font whose step
+//! fails is still written, with a warning in the report.
+//! Variable fonts have their overlapping glyphs flagged rather than merged
+//! (see `overlap_flags`) and get the family's STAT axis values when it has
+//! any (see `stat_table`). Fonts with color glyphs get COLR and CPAL tables
+//! from the default source's color layers (see `color_tables`). Every font
+//! written is then checked against the build it replaced (see
+//! `build_check`) and inspected for the build inspector (see
+//! `font_inspector`). Compilation goes through the compile cache shared
+//! with QA, so sources unchanged since the last QA run or export aren't
+//! compiled again.
+//!
+//! Exports run off the main thread; an `ExportProgress` shared with the
+//! caller reports which font is being built and lets the caller cancel.
+//! fontc can't be interrupted, so cancelling stops before the next font.
+
+use crate::core::state::{FontData, FontInfo};
+use crate::data::build_check::{check_build, BuildCheck};
+use crate::data::color_tables::apply_color_tables;
+use crate::data::compile_cache::{ensure_can_compile, CompileCache};
+use crate::data::export_copy::cleaned_copy;
+use crate::data::font_inspector::{inspect_font, FontInspection};
+use crate::data::overlap_flags::apply_overlap_flags;
+use crate::data::stat_table::apply_stat_table;
+use crate::data::svg_table::embed_svg_table;
+use crate::font_source::export_profiles::{ExportFormat, ExportProfile, FormatSettings};
+use anyhow::{anyhow, bail, Context, Result};
+use norad::designspace::DesignSpaceDocument;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// What an export wrote, which steps went wrong and how the fonts checked
+#[derive(Debug, Default)]
+pub struct ExportReport {
+    pub written: Vec<PathBuf>,
+    pub warnings: Vec<String>,
+    pub checks: Vec<BuildCheck>,
+    /// Tables, metrics and coverage of each font checked
+    pub inspections: Vec<FontInspection>,
+    /// What was compiled for each font, from the compile cache
+    pub builds: Vec<String>,
+    /// Whether the export was cancelled before every font was built
+    pub cancelled: bool,
+}
+
+/// Progress of a running export, shared between the export and its caller
+#[derive(Debug, Default)]
+pub struct ExportProgress {
+    total: AtomicUsize,
+    done: AtomicUsize,
+    current: Mutex<String>,
+    cancelled: AtomicBool,
+}
+
+impl ExportProgress {
+    /// Fonts built so far and fonts to build in total
+    pub fn counts(&self) -> (usize, usize) {
+        (
+            self.done.load(Ordering::Relaxed),
+            self.total.load(Ordering::Relaxed),
+        )
+    }
+
+    /// File name of the font being built
+    pub fn current(&self) -> String {
+        self.current
+            .lock()
+            .map(|name| name.clone())
+            .unwrap_or_default()
+    }
+
+    /// Stop the export before its next font
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    fn start(&self, path: &Path) {
+        if let Ok(mut current) = self.current.lock() {
+            *current = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+        }
+    }
+}
+
+/// A font to compile: a designspace or UFO, and the style it is named by
+struct ExportInput {
+    source: PathBuf,
+    style: String,
+}
+
+/// The UFO of a font source: itself, or a designspace's first source
+pub fn first_source_ufo(source: &Path) -> Result<PathBuf> {
+    if !is_designspace(source) {
+        return Ok(source.to_path_buf());
+    }
+    let designspace = DesignSpaceDocument::load(source).context("Failed to load designspace")?;
+    let first = designspace
+        .sources
+        .first()
+        .ok_or_else(|| anyhow!("{} has no sources", source.display()))?;
+    Ok(source.parent().unwrap_or(Path::new(".")).join(&first.filename))
+}
+
+/// Export a project (a designspace or UFO on disk) with a profile
+///
+/// `font` and `info` are the project's default source, used for the color
+/// tables and the SVG-in-OpenType variants.
+pub fn export_project(
+    source: &Path,
+    profile: &ExportProfile,
+    font: &FontData,
+    info: &FontInfo,
+    progress: &ExportProgress,
+) -> Result<ExportReport> {
+    let project_dir = source.parent().unwrap_or_else(|| Path::new("."));
+    let output_dir = project_dir.join(&profile.output_dir);
+    export_project_to(source, profile, &output_dir, font, info, progress)
+}
+
+/// Export a project with a profile into `output_dir` rather than the
+/// profile's own output directory
+///
+/// The fonts to build are added to the progress's total, so one progress
+/// can follow several projects.
+pub fn export_project_to(
+    source: &Path,
+    profile: &ExportProfile,
+    output_dir: &Path,
+    font: &FontData,
+    info: &FontInfo,
+    progress: &ExportProgress,
+) -> Result<ExportReport> {
+    ensure_can_compile()?;
+    let project_dir = source.parent().unwrap_or_else(|| Path::new("."));
+    let designspace = if is_designspace(source) {
+        Some(DesignSpaceDocument::load(source).context("Failed to load designspace")?)
+    } else {
+        None
+    };
+    let family = designspace
+        .as_ref()
+        .and_then(|ds| {
+            ds.sources
+                .iter()
+                .find_map(|s| s.familyname.clone())
+                .or_else(|| ds.instances.iter().find_map(|i| i.familyname.clone()))
+        })
+        .unwrap_or_else(|| info.family_name.clone());
+    let family = if family.is_empty() {
+        "Font".to_string()
+    } else {
+        family
+    };
+
+    let cache = CompileCache::new();
+    let mut report = ExportReport::default();
+    let mut jobs: Vec<(&FormatSettings, ExportInput)> = Vec::new();
+    for settings in &profile.formats {
+        let inputs = match (settings.format, &designspace) {
+            (ExportFormat::Variable, Some(_)) => vec![ExportInput {
+                source: source.to_path_buf(),
+                style: "Variable".to_string(),
+            }],
+            (ExportFormat::Variable, None) => {
+                report
+                    .warnings
+                    .push("A variable font needs a designspace, skipping it".to_string());
+                continue;
+            }
+            (ExportFormat::Static, Some(ds)) => ds
+                .sources
+                .iter()
+                .filter_map(|s| {
+                    let style = s.stylename.clone().unwrap_or_else(|| "Regular".to_string());
+                    profile.includes_style(&style).then(|| ExportInput {
+                        source: project_dir.join(&s.filename),
+                        style,
+                    })
+                })
+                .collect(),
+            (ExportFormat::Static, None) => vec![ExportInput {
+                source: source.to_path_buf(),
+                style: if info.style_name.is_empty() {
+                    "Regular".to_string()
+                } else {
+                    info.style_name.clone()
+                },
+            }],
+        };
+        jobs.extend(inputs.into_iter().map(|input| (settings, input)));
+    }
+
+    progress.total.fetch_add(jobs.len(), Ordering::Relaxed);
+    for (settings, input) in jobs {
+        if progress.is_cancelled() {
+            report.cancelled = true;
+            break;
+        }
+        let path = output_dir.join(profile.file_name(&family, &input.style));
+        progress.start(&path);
+        if let Err(e) = export_font(
+            &input,
+            &path,
+            settings,
+            &cache,
+            font,
+            info,
+            &mut report,
+        ) {
+            report
+                .warnings
+                .push(format!("{} was not exported: {:#}", path.display(), e));
+        }
+        progress.done.fetch_add(1, Ordering::Relaxed);
+    }
+    Ok(report)
+}
+
+fn is_designspace(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some("designspace")
+}
+
+/// Compile one font, post-process it and write it (and its SVG variant)
+fn export_font(
+    input: &ExportInput,
+    path: &Path,
+    settings: &FormatSettings,
+    cache: &CompileCache,
+    font: &FontData,
+    info: &FontInfo,
+    report: &mut ExportReport,
+) -> Result<()> {
+    if !input.source.exists() {
+        bail!("Source {} not found", input.source.display());
+    }
+    let name = path.display();
+    let copy_dir = tempfile::TempDir::new().context("Failed to create build directory")?;
+    let source = match cleaned_copy(&input.source, copy_dir.path(), settings.quadratic_max_error) {
+        Ok(copy) => copy.unwrap_or_else(|| input.source.clone()),
+        Err(e) => {
+            report.warnings.push(format!(
+                "Open corners kept and curves left to fontc in {name}: {e:#}"
+            ));
+            input.source.clone()
+        }
+    };
+    // Changes are reported against the previous export of the source, not
+    // of its throwaway copy
+    let build = cache.compile_as(&source, &input.source)?;
+    report.builds.push(format!("{name}: {}", build.summary()));
+    let previous = std::fs::read(path).ok();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::copy(&build.path, path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    if settings.remove_overlaps && settings.format == ExportFormat::Variable {
+        let flagged = std::fs::read(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|bytes| apply_overlap_flags(&bytes))
+            .and_then(|bytes| std::fs::write(path, bytes).map_err(Into::into));
+        if let Err(e) = flagged {
+            report
+                .warnings
+                .push(format!("Overlaps of {name} not flagged: {e:#}"));
+        }
+    } else if settings.remove_overlaps {
+        let args: [OsString; 2] = ["ttLib.removeOverlaps".into(), path.into()];
+        if let Err(e) = post_process(path, "fonttools", &args, None) {
+            report
+                .warnings
+                .push(format!("Overlaps kept in {name}: {e:#}"));
+        }
+    }
+    if !settings.subset.is_empty() {
+        let mut args: Vec<OsString> = vec!["subset".into(), path.into()];
+        args.extend(
+            subset_arguments(&settings.subset)
+                .into_iter()
+                .map(OsString::from),
+        );
+        if let Err(e) = post_process(path, "fonttools", &args, Some("--output-file=")) {
+            report
+                .warnings
+                .push(format!("{name} was not subset: {e:#}"));
+        }
+    }
+    if settings.autohint {
+        if settings.format == ExportFormat::Variable {
+            report.warnings.push(format!(
+                "{name} is unhinted: ttfautohint can't hint variable fonts"
+            ));
+        } else if let Err(e) = post_process(path, "ttfautohint", &[path.into()], None) {
+            report.warnings.push(format!("{name} is unhinted: {e:#}"));
+        }
+    }
+    if settings.format == ExportFormat::Variable && !font.stat.is_empty() {
+        let stat = std::fs::read(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|bytes| apply_stat_table(&bytes, &font.stat))
+            .and_then(|bytes| std::fs::write(path, bytes).map_err(Into::into));
+        if let Err(e) = stat {
+            report
+                .warnings
+                .push(format!("{name} keeps fontc's STAT table: {e:#}"));
+        }
+    }
+    if !font.color.is_empty() {
+        let colored = std::fs::read(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|bytes| apply_color_tables(&bytes, &font.color))
+            .and_then(|bytes| std::fs::write(path, bytes).map_err(Into::into));
+        if let Err(e) = colored {
+            report
+                .warnings
+                .push(format!("{name} has no color glyphs: {e:#}"));
+        }
+    }
+    report.written.push(path.to_path_buf());
+    let font_bytes = std::fs::read(path)?;
+    report.checks.push(check_build(
+        path.to_path_buf(),
+        &font_bytes,
+        previous.as_deref(),
+    ));
+    // Fonts too broken to inspect are already flagged by their check
+    if let Ok(inspection) = inspect_font(path.to_path_buf(), &font_bytes) {
+        report.inspections.push(inspection);
+    }
+
+    if settings.svg_variant {
+        match embed_svg_table(&font_bytes, font, info) {
+            Ok(Some(svg_font)) => {
+                let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                let svg_path = path.with_file_name(format!("{stem}-SVG.ttf"));
+                std::fs::write(&svg_path, svg_font)
+                    .with_context(|| format!("Failed to write {}", svg_path.display()))?;
+                report.written.push(svg_path);
+            }
+            Ok(None) => {}
+            Err(e) => report
+                .warnings
+                .push(format!("No SVG variant of {name}: {e:#}")),
+        }
+    }
+    Ok(())
+}
+
+/// Run a tool that reads the font at `path` and writes a new one, then
+/// replace the font with its output
+///
+/// The output path is the last argument, or follows `output_flag`.
+fn post_process(
+    path: &Path,
+    program: &str,
+    args: &[OsString],
+    output_flag: Option<&str>,
+) -> Result<()> {
+    let output = path.with_extension("tmp.ttf");
+    let mut command = Command::new(program);
+    command.args(args);
+    match output_flag {
+        Some(flag) => command.arg(format!("{flag}{}", output.display())),
+        None => command.arg(&output),
+    };
+    let result = command
+        .output()
+        .with_context(|| format!("Failed to run {program}, is it installed?"))?;
+    if !result.status.success() {
+        let _ = std::fs::remove_file(&output);
+        bail!(
+            "{program} failed: {}",
+            String::from_utf8_lossy(&result.stderr).trim()
+        );
+    }
+    std::fs::rename(&output, path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+/// `fonttools subset` arguments keeping the given codepoints, ranges and
+/// glyph names, with every layout feature, name and glyph name
+fn subset_arguments(subset: &[String]) -> Vec<String> {
+    let (unicodes, glyphs): (Vec<&str>, Vec<&str>) = subset
+        .iter()
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .partition(|entry| entry.starts_with("U+") || entry.starts_with("u+"));
+    let mut args = vec![
+        "--layout-features=*".to_string(),
+        "--name-IDs=*".to_string(),
+        "--glyph-names".to_string(),
+        "--notdef-outline".to_string(),
+    ];
+    if !unicodes.is_empty() {
+        args.push(format!("--unicodes={}", unicodes.join(",")));
+    }
+    if !glyphs.is_empty() {
+        args.push(format!("--glyphs={}", glyphs.join(",")));
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subset_arguments_split_codepoints_from_glyph_names() {
+        let subset = ["U+0020-007E", " fi ", "u+00A0", ""].map(String::from);
+        let args = subset_arguments(&subset);
+        assert!(args.contains(&"--unicodes=U+0020-007E,u+00A0".to_string()));
+        assert!(args.contains(&"--glyphs=fi".to_string()));
+        assert!(args.contains(&"--glyph-names".to_string()));
+    }
+}
@@ -0,0 +1,166 @@
+//! Cleaned copies of sources for export
+//!
+//! Some construction kept in a source for editing shouldn't reach the fonts
+//! built from it: open corners (see `font_source::open_corners`) are closed.
+//! Cubic curves are made quadratic for the TrueType outlines export writes,
+//! within the export profile's maximum error and compatibly across the
+//! masters (see `font_source::curve_flavor`), instead of leaving that to the
+//! compiler's defaults. Export compiles a cleaned copy of the source in a
+//! temporary directory, so the source on disk keeps its construction and its
+//! cubic curves. Sources with nothing to clean are compiled as they are.
+//!
+//! Overlaps are deliberately not removed here but from the compiled static
+//! fonts (see `data::export`): merging contours needs path booleans the app
+//! doesn't have, and fontTools' overlap removal is the one the usual font
+//! build tools use.
+
+use crate::font_source::curve_flavor::to_quadratic_compatible;
+use crate::font_source::open_corners::{close_open_corners, open_corners};
+use crate::font_source::ContourData;
+use anyhow::{bail, Context, Result};
+use norad::designspace::DesignSpaceDocument;
+use norad::Font;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Component, Path, PathBuf};
+
+/// Close the open corners of every glyph in every layer of a font, and
+/// return how many glyphs changed
+pub fn close_open_corners_in_font(font: &mut Font) -> usize {
+    let mut changed = 0;
+    for layer in font.layers.iter_mut() {
+        for glyph in layer.iter_mut() {
+            let mut glyph_changed = false;
+            for contour in glyph.contours.iter_mut() {
+                let data = ContourData::from_norad_contour(contour);
+                if !open_corners(&data).is_empty() {
+                    *contour = close_open_corners(&data).to_norad_contour();
+                    glyph_changed = true;
+                }
+            }
+            changed += usize::from(glyph_changed);
+        }
+    }
+    changed
+}
+
+/// Make the cubic curves of every glyph in every layer of the fonts
+/// quadratic, the drawings of a glyph compatibly, and return how many
+/// drawings changed. Fails on a glyph whose curves can't be made quadratic
+/// within `max_error`
+pub fn quadratic_outlines_in_fonts(fonts: &mut [Font], max_error: f64) -> Result<usize> {
+    // Every drawing of each glyph, in the order the fonts and layers are
+    // walked again below
+    let mut drawings: BTreeMap<String, Vec<Vec<ContourData>>> = BTreeMap::new();
+    for font in fonts.iter() {
+        for layer in font.layers.iter() {
+            for glyph in layer.iter() {
+                let contours = glyph
+                    .contours
+                    .iter()
+                    .map(ContourData::from_norad_contour)
+                    .collect();
+                drawings
+                    .entry(glyph.name().to_string())
+                    .or_default()
+                    .push(contours);
+            }
+        }
+    }
+    let mut converted: BTreeMap<String, std::vec::IntoIter<Vec<ContourData>>> = drawings
+        .iter()
+        .map(|(name, drawings)| {
+            let converted = to_quadratic_compatible(drawings, max_error)
+                .with_context(|| format!("Failed to make '{name}' quadratic"))?;
+            Ok((name.clone(), converted.into_iter()))
+        })
+        .collect::<Result<_>>()?;
+
+    let mut changed = 0;
+    for font in fonts.iter_mut() {
+        for layer in font.layers.iter_mut() {
+            for glyph in layer.iter_mut() {
+                let Some(contours) = converted
+                    .get_mut(glyph.name().as_str())
+                    .and_then(Iterator::next)
+                else {
+                    continue;
+                };
+                let before: Vec<ContourData> = glyph
+                    .contours
+                    .iter()
+                    .map(ContourData::from_norad_contour)
+                    .collect();
+                if before != contours {
+                    glyph.contours = contours.iter().map(ContourData::to_norad_contour).collect();
+                    changed += 1;
+                }
+            }
+        }
+    }
+    Ok(changed)
+}
+
+/// Write a cleaned copy of a UFO, or of a designspace and its UFOs, into
+/// `dir`, with cubic curves made quadratic within `max_error`, and return
+/// the copy's path; `None` when there is nothing to clean
+pub fn cleaned_copy(source: &Path, dir: &Path, max_error: f64) -> Result<Option<PathBuf>> {
+    let source_dir = source.parent().unwrap_or_else(|| Path::new("."));
+    let file_name = source
+        .file_name()
+        .with_context(|| format!("{} has no file name", source.display()))?;
+    let is_designspace = source.extension().and_then(|ext| ext.to_str()) == Some("designspace");
+    let ufos: Vec<PathBuf> = if is_designspace {
+        let document = DesignSpaceDocument::load(source).context("Failed to load designspace")?;
+        let filenames: BTreeSet<String> =
+            document.sources.into_iter().map(|s| s.filename).collect();
+        filenames.into_iter().map(PathBuf::from).collect()
+    } else {
+        vec![PathBuf::from(file_name)]
+    };
+
+    let (mut paths, mut fonts) = (Vec::new(), Vec::new());
+    let mut changed = 0;
+    for ufo in ufos {
+        // Sources outside the designspace's folder would be written there
+        let inside = ufo
+            .components()
+            .all(|part| matches!(part, Component::Normal(_) | Component::CurDir));
+        if !inside {
+            bail!(
+                "Can't copy {}, which is outside its designspace's folder",
+                ufo.display()
+            );
+        }
+        let path = if is_designspace {
+            source_dir.join(&ufo)
+        } else {
+            source.to_path_buf()
+        };
+        let mut font =
+            Font::load(&path).with_context(|| format!("Failed to load {}", path.display()))?;
+        changed += close_open_corners_in_font(&mut font);
+        paths.push(ufo);
+        fonts.push(font);
+    }
+    // After the corners are closed, so their segments convert as they're built
+    changed += quadratic_outlines_in_fonts(&mut fonts, max_error)?;
+    if changed == 0 {
+        return Ok(None);
+    }
+
+    for (ufo, font) in paths.iter().zip(&fonts) {
+        let path = dir.join(ufo);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        font.save(&path)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    let copy = dir.join(file_name);
+    if is_designspace {
+        std::fs::copy(source, &copy)
+            .with_context(|| format!("Failed to write {}", copy.display()))?;
+    }
+    Ok(Some(copy))
+}
@@ -0,0 +1,250 @@
+//! Family builds
+//!
+//! A family project (`<name>.bezyfamily`, JSON) groups the designspaces and
+//! UFOs of one family, such as its upright and italic, each exported with
+//! one of its own export profiles. Building the family exports every source
+//! into one release folder, `<output_dir>/<Family>-<version>`, instead of
+//! the profiles' own output directories, and writes a `manifest.json` there
+//! listing each font file with the source and profile it came from.
+//!
+//! The version is the project's, or else the first source's font info
+//! version, so a release folder is never overwritten by a newer version.
+
+use crate::core::state::{FontData, FontInfo};
+use crate::data::export::{export_project_to, first_source_ufo, ExportProgress, ExportReport};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// File extension of family projects
+pub const FAMILY_PROJECT_EXTENSION: &str = "bezyfamily";
+
+/// File listing a release's fonts, in the release folder
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// One designspace or UFO of a family
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FamilySource {
+    /// Path relative to the project file
+    pub path: String,
+    /// Export profile of the source; `None` uses its first
+    pub profile: Option<String>,
+}
+
+/// The sources of a family and where its releases go
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FamilyProject {
+    /// Family name of the release folder; empty uses the project file name
+    pub family: String,
+    /// Release version; empty reads it from the first source
+    pub version: String,
+    /// Folder of the releases, relative to the project file
+    pub output_dir: String,
+    pub sources: Vec<FamilySource>,
+}
+
+/// A font file of a release
+#[derive(Debug, Serialize)]
+pub struct ManifestFont {
+    /// Path relative to the release folder
+    pub file: String,
+    pub source: String,
+    pub profile: String,
+    pub bytes: u64,
+}
+
+/// What a release folder holds
+#[derive(Debug, Serialize)]
+pub struct ReleaseManifest {
+    pub family: String,
+    pub version: String,
+    /// When the release was built, in UTC
+    pub built: String,
+    pub fonts: Vec<ManifestFont>,
+}
+
+impl FamilyProject {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("{} is not a family project", path.display()))
+    }
+
+    /// Family name, or the project file's name without its extension
+    fn family_name(&self, path: &Path) -> String {
+        if self.family.is_empty() {
+            path.file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned()
+        } else {
+            self.family.clone()
+        }
+    }
+}
+
+/// A family project next to a font source, or in the folder above, that
+/// lists the source
+pub fn find_family_project(source: &Path) -> Option<PathBuf> {
+    let source = source.canonicalize().ok()?;
+    let folder = source.parent()?;
+    let project = [Some(folder), folder.parent()]
+        .into_iter()
+        .flatten()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension().and_then(|ext| ext.to_str()) == Some(FAMILY_PROJECT_EXTENSION)
+        })
+        .find(|path| {
+            let project_dir = path.parent().unwrap_or(Path::new("."));
+            FamilyProject::load(path).is_ok_and(|project| {
+                project.sources.iter().any(|family_source| {
+                    project_dir
+                        .join(&family_source.path)
+                        .canonicalize()
+                        .ok()
+                        .as_ref()
+                        == Some(&source)
+                })
+            })
+        });
+    project
+}
+
+/// Version of a UFO's font info as "1.002", if it has one
+fn ufo_version(font: &norad::Font) -> Option<String> {
+    let major = font.font_info.version_major?;
+    let minor = font.font_info.version_minor.unwrap_or(0);
+    Some(format!("{major}.{minor:03}"))
+}
+
+/// Export every source of a family project into its release folder, and
+/// write the release manifest
+pub fn build_family(project_path: &Path, progress: &ExportProgress) -> Result<ExportReport> {
+    let project = FamilyProject::load(project_path)?;
+    if project.sources.is_empty() {
+        bail!("{} lists no sources", project_path.display());
+    }
+    let project_dir = project_path.parent().unwrap_or(Path::new("."));
+
+    // Profiles and artwork come from each source's first UFO
+    let mut sources = Vec::new();
+    for family_source in &project.sources {
+        let source = project_dir.join(&family_source.path);
+        let ufo_path = first_source_ufo(&source)?;
+        let font = norad::Font::load(&ufo_path)
+            .with_context(|| format!("Failed to load {}", ufo_path.display()))?;
+        sources.push((family_source, source, font, ufo_path));
+    }
+
+    let version = if project.version.is_empty() {
+        ufo_version(&sources[0].2)
+            .ok_or_else(|| anyhow!("No version in the project or its first source"))?
+    } else {
+        project.version.clone()
+    };
+    let family = project.family_name(project_path);
+    let release_dir = project_dir
+        .join(&project.output_dir)
+        .join(format!("{}-{version}", family.replace(' ', "")));
+
+    let mut report = ExportReport::default();
+    let mut fonts = Vec::new();
+    for (family_source, source, font, ufo_path) in sources {
+        if progress.is_cancelled() {
+            report.cancelled = true;
+            break;
+        }
+        let font_data = FontData::from_norad_font(&font, Some(ufo_path));
+        let info = FontInfo::from_norad_font(&font);
+        let profiles = &font_data.export_profiles;
+        let Some(profile) = profiles.get(family_source.profile.as_deref()) else {
+            report.warnings.push(format!(
+                "{} was not exported: no export profile named '{}'",
+                family_source.path,
+                family_source.profile.as_deref().unwrap_or_default()
+            ));
+            continue;
+        };
+        let written = report.written.len();
+        let source_report =
+            export_project_to(&source, profile, &release_dir, &font_data, &info, progress)?;
+        report.warnings.extend(source_report.warnings);
+        report.checks.extend(source_report.checks);
+        report.inspections.extend(source_report.inspections);
+        report.builds.extend(source_report.builds);
+        report.written.extend(source_report.written);
+        report.cancelled |= source_report.cancelled;
+        for path in &report.written[written..] {
+            fonts.push(ManifestFont {
+                file: path
+                    .strip_prefix(&release_dir)
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .into_owned(),
+                source: family_source.path.clone(),
+                profile: profile.name.clone(),
+                bytes: std::fs::metadata(path).map_or(0, |metadata| metadata.len()),
+            });
+        }
+    }
+
+    // Sources of one family can name their fonts alike
+    let mut seen = BTreeSet::new();
+    for font in &fonts {
+        if !seen.insert(&font.file) {
+            report.warnings.push(format!(
+                "{} was written by more than one source; check the filename patterns",
+                font.file
+            ));
+        }
+    }
+    if fonts.is_empty() {
+        return Ok(report);
+    }
+
+    let manifest = ReleaseManifest {
+        family,
+        version,
+        built: chrono::Utc::now()
+            .format("%Y-%m-%d %H:%M:%S UTC")
+            .to_string(),
+        fonts,
+    };
+    let manifest_path = release_dir.join(MANIFEST_FILE);
+    let contents = serde_json::to_string_pretty(&manifest)?;
+    std::fs::write(&manifest_path, contents)
+        .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+    report.written.push(manifest_path);
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_family_project_defaults() {
+        let json = r#"{"sources": [{"path": "Upright.designspace"},
+            {"path": "Italic.designspace", "profile": "Web"}]}"#;
+        let project: FamilyProject = serde_json::from_str(json).unwrap();
+        assert_eq!(project.sources.len(), 2);
+        assert_eq!(project.sources[0].profile, None);
+        assert_eq!(project.sources[1].profile.as_deref(), Some("Web"));
+        assert_eq!(
+            project.family_name(Path::new("fam/My Sans.bezyfamily")),
+            "My Sans"
+        );
+
+        let mut font = norad::Font::new();
+        assert_eq!(ufo_version(&font), None);
+        font.font_info.version_major = Some(2);
+        font.font_info.version_minor = Some(7);
+        assert_eq!(ufo_version(&font).as_deref(), Some("2.007"));
+    }
+}
@@ -0,0 +1,250 @@
+//! Inspection of exported fonts
+//!
+//! Reads a compiled font with read-fonts (through skrifa) and sums up what
+//! went into it: the table directory with each table's size, key `head`,
+//! `hhea` and `OS/2` values, the Unicode blocks `cmap` covers and how much
+//! space the outlines take. Builds can be sanity-checked from the editor or
+//! the TUI this way, without dumping them with ttx.
+
+use crate::data::unicode_db::{self, UnicodeBlock};
+use anyhow::{anyhow, Result};
+use skrifa::raw::types::Tag;
+use skrifa::raw::{FontRef, ReadError, TableProvider};
+use skrifa::MetadataProvider;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// A titled list of name/value rows about one part of a font
+#[derive(Debug, Clone, PartialEq)]
+pub struct InspectionSection {
+    pub title: &'static str,
+    pub rows: Vec<(String, String)>,
+}
+
+/// What an exported font contains
+#[derive(Debug, Clone)]
+pub struct FontInspection {
+    pub path: PathBuf,
+    pub sections: Vec<InspectionSection>,
+}
+
+impl FontInspection {
+    /// File name of the font, for titles
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+}
+
+/// A size in bytes, as B, KB or MB
+pub fn format_size(bytes: u32) -> String {
+    match bytes {
+        0..=1023 => format!("{bytes} B"),
+        1024..=1_048_575 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.2} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+fn row(name: &str, value: impl ToString) -> (String, String) {
+    (name.to_string(), value.to_string())
+}
+
+/// Rows of a table that parsed, or one row saying why it didn't
+fn table_rows(table: Result<Vec<(String, String)>, ReadError>) -> Vec<(String, String)> {
+    table.unwrap_or_else(|error| vec![row("unreadable", error)])
+}
+
+/// What is inside `data`, the font written to `path`
+pub fn inspect_font(path: PathBuf, data: &[u8]) -> Result<FontInspection> {
+    let font = FontRef::new(data).map_err(|error| anyhow!("Not a font: {error}"))?;
+    let records = font.table_directory.table_records();
+    let table_size = |tag: &[u8; 4]| {
+        records
+            .iter()
+            .find(|record| record.tag() == Tag::new(tag))
+            .map(|r| r.length())
+    };
+
+    let tables = InspectionSection {
+        title: "Tables",
+        rows: records
+            .iter()
+            .map(|record| row(&record.tag().to_string(), format_size(record.length())))
+            .collect(),
+    };
+
+    let head = InspectionSection {
+        title: "head",
+        rows: table_rows(font.head().map(|head| {
+            let offsets = match head.index_to_loc_format() {
+                0 => "short",
+                _ => "long",
+            };
+            vec![
+                row("Units per em", head.units_per_em()),
+                row("Revision", format!("{:.3}", head.font_revision().to_f64())),
+                row(
+                    "Bounding box",
+                    format!(
+                        "{} {} {} {}",
+                        head.x_min(),
+                        head.y_min(),
+                        head.x_max(),
+                        head.y_max()
+                    ),
+                ),
+                row(
+                    "Smallest readable size",
+                    format!("{} ppem", head.lowest_rec_ppem()),
+                ),
+                row("Glyph offsets", offsets),
+            ]
+        })),
+    };
+
+    let hhea = InspectionSection {
+        title: "hhea",
+        rows: table_rows(font.hhea().map(|hhea| {
+            vec![
+                row("Ascender", hhea.ascender().to_i16()),
+                row("Descender", hhea.descender().to_i16()),
+                row("Line gap", hhea.line_gap().to_i16()),
+                row("Widest advance", hhea.advance_width_max().to_u16()),
+            ]
+        })),
+    };
+
+    let os2 = InspectionSection {
+        title: "OS/2",
+        rows: table_rows(font.os2().map(|os2| {
+            let optional = |value: Option<i16>| value.map_or("-".to_string(), |v| v.to_string());
+            vec![
+                row("Version", os2.version()),
+                row("Vendor", os2.ach_vend_id()),
+                row("Weight class", os2.us_weight_class()),
+                row("Width class", os2.us_width_class()),
+                row("Embedding (fsType)", format!("{:#06x}", os2.fs_type())),
+                row(
+                    "Typo ascender / descender / gap",
+                    format!(
+                        "{} / {} / {}",
+                        os2.s_typo_ascender(),
+                        os2.s_typo_descender(),
+                        os2.s_typo_line_gap()
+                    ),
+                ),
+                row(
+                    "Win ascent / descent",
+                    format!("{} / {}", os2.us_win_ascent(), os2.us_win_descent()),
+                ),
+                row("x-height", optional(os2.sx_height())),
+                row("Cap height", optional(os2.s_cap_height())),
+            ]
+        })),
+    };
+
+    let cmap = InspectionSection {
+        title: "cmap",
+        rows: cmap_rows(font.charmap().mappings().map(|(codepoint, _)| codepoint)),
+    };
+
+    let glyph_count = font.maxp().map(|maxp| maxp.num_glyphs()).unwrap_or(0);
+    let (format, outline_size) = match (
+        table_size(b"glyf"),
+        table_size(b"CFF "),
+        table_size(b"CFF2"),
+    ) {
+        (Some(glyf), _, _) => (
+            "TrueType (glyf + loca)",
+            glyf + table_size(b"loca").unwrap_or(0),
+        ),
+        (_, Some(cff), _) => ("CFF", cff),
+        (_, _, Some(cff2)) => ("CFF2", cff2),
+        _ => ("none", 0),
+    };
+    let mut outline_rows = vec![
+        row("Format", format),
+        row("Glyphs", glyph_count),
+        row("Outline data", format_size(outline_size)),
+        row(
+            "Per glyph",
+            format_size(outline_size / u32::from(glyph_count.max(1))),
+        ),
+    ];
+    if let Some(gvar) = table_size(b"gvar") {
+        outline_rows.push(row("Variations (gvar)", format_size(gvar)));
+    }
+    let outlines = InspectionSection {
+        title: "Outlines",
+        rows: outline_rows,
+    };
+
+    Ok(FontInspection {
+        path,
+        sections: vec![tables, head, hhea, os2, cmap, outlines],
+    })
+}
+
+/// How many codepoints are mapped, and how much of each Unicode block
+fn cmap_rows(codepoints: impl Iterator<Item = u32>) -> Vec<(String, String)> {
+    let mut total = 0;
+    let mut outside_blocks = 0;
+    let mut blocks: BTreeMap<u32, (&'static UnicodeBlock, usize)> = BTreeMap::new();
+    for c in codepoints.filter_map(char::from_u32) {
+        total += 1;
+        match unicode_db::block(c) {
+            Some(block) => blocks.entry(block.first).or_insert((block, 0)).1 += 1,
+            None => outside_blocks += 1,
+        }
+    }
+    let mut rows = vec![row("Codepoints", total)];
+    rows.extend(
+        blocks
+            .values()
+            .map(|(block, mapped)| row(block.name, format!("{mapped} of {}", block.chars().len()))),
+    );
+    if outside_blocks > 0 {
+        rows.push(row("Outside known blocks", outside_blocks));
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::embedded_assets::BEZY_GROTESK_BYTES;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(54), "54 B");
+        assert_eq!(format_size(21540), "21.0 KB");
+        assert_eq!(format_size(3 * 1_048_576), "3.00 MB");
+    }
+
+    #[test]
+    fn test_inspect_bundled_font() {
+        let inspection =
+            inspect_font(PathBuf::from("BezyGrotesk-Regular.ttf"), BEZY_GROTESK_BYTES).unwrap();
+        let section = |title| {
+            inspection
+                .sections
+                .iter()
+                .find(|section| section.title == title)
+                .unwrap()
+        };
+        let value = |title, name: &str| {
+            section(title)
+                .rows
+                .iter()
+                .find(|(row_name, _)| row_name == name)
+                .map(|(_, value)| value.clone())
+        };
+        assert_eq!(section("Tables").rows.len(), 13);
+        assert_eq!(value("Tables", "head").as_deref(), Some("54 B"));
+        assert_eq!(value("head", "Units per em").as_deref(), Some("1024"));
+        assert_eq!(value("Outlines", "Glyphs").as_deref(), Some("342"));
+        assert!(value("cmap", "Basic Latin").is_some());
+    }
+}
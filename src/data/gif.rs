@@ -0,0 +1,212 @@
+//! Animated GIF writing
+//!
+//! Encodes frames of palette indices as a looping GIF89a with one global
+//! palette of up to 256 colors, which is plenty for a glyph drawn in one
+//! color over a background at every level of antialiasing.
+
+/// Largest code the LZW table holds, plus one
+const MAX_CODES: u16 = 4096;
+
+/// Bits of the codes for 8-bit palette indices
+const MIN_CODE_SIZE: u8 = 8;
+
+/// Writes LZW codes as a little-endian bit stream, growing the code width
+/// as the table fills the way GIF decoders expect
+struct CodeWriter {
+    bytes: Vec<u8>,
+    accumulator: u32,
+    bits: u32,
+    code_size: u32,
+    next_code: u16,
+    cleared: bool,
+}
+
+impl CodeWriter {
+    fn clear_code() -> u16 {
+        1 << MIN_CODE_SIZE
+    }
+
+    fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            accumulator: 0,
+            bits: 0,
+            code_size: MIN_CODE_SIZE as u32 + 1,
+            next_code: Self::clear_code() + 2,
+            cleared: false,
+        }
+    }
+
+    fn write(&mut self, code: u16) {
+        self.accumulator |= (code as u32) << self.bits;
+        self.bits += self.code_size;
+        while self.bits >= 8 {
+            self.bytes.push(self.accumulator as u8);
+            self.accumulator >>= 8;
+            self.bits -= 8;
+        }
+        if self.cleared {
+            self.code_size = MIN_CODE_SIZE as u32 + 1;
+            self.cleared = false;
+        } else if self.code_size < 12 && self.next_code as u32 > (1 << self.code_size) - 1 {
+            self.code_size += 1;
+        }
+    }
+
+    /// Starts the table over, writing the clear code at the current width
+    fn clear(&mut self) {
+        self.next_code = Self::clear_code() + 2;
+        self.cleared = true;
+        self.write(Self::clear_code());
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.accumulator as u8);
+        }
+        self.bytes
+    }
+}
+
+/// LZW-compressed palette indices
+fn lzw(indices: &[u8]) -> Vec<u8> {
+    let mut writer = CodeWriter::new();
+    writer.write(CodeWriter::clear_code());
+    let mut table = std::collections::HashMap::new();
+    let Some((&first, rest)) = indices.split_first() else {
+        writer.write(CodeWriter::clear_code() + 1);
+        return writer.finish();
+    };
+    let mut prefix = first as u16;
+    for &index in rest {
+        if let Some(&code) = table.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+        writer.write(prefix);
+        if writer.next_code < MAX_CODES {
+            table.insert((prefix, index), writer.next_code);
+            writer.next_code += 1;
+        } else {
+            table.clear();
+            writer.clear();
+        }
+        prefix = index as u16;
+    }
+    writer.write(prefix);
+    writer.write(CodeWriter::clear_code() + 1);
+    writer.finish()
+}
+
+/// A looping animated GIF of `frames`, each `width`×`height` palette
+/// indices row by row from the top, shown for `delay` hundredths of a
+/// second each
+pub fn encode_gif(
+    width: u16,
+    height: u16,
+    palette: &[[u8; 3]],
+    frames: &[Vec<u8>],
+    delay: u16,
+) -> Vec<u8> {
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend(width.to_le_bytes());
+    gif.extend(height.to_le_bytes());
+    // Global palette of 256 colors, background color 0, square pixels
+    gif.extend([0xF7, 0, 0]);
+    for index in 0..256 {
+        gif.extend(palette.get(index).copied().unwrap_or_default());
+    }
+    // Loop forever
+    gif.extend([0x21, 0xFF, 0x0B]);
+    gif.extend(b"NETSCAPE2.0");
+    gif.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+
+    for frame in frames {
+        // Delay, no transparency
+        gif.extend([0x21, 0xF9, 0x04, 0x00]);
+        gif.extend(delay.to_le_bytes());
+        gif.extend([0x00, 0x00]);
+        // The whole image, no local palette
+        gif.push(0x2C);
+        gif.extend([0, 0, 0, 0]);
+        gif.extend(width.to_le_bytes());
+        gif.extend(height.to_le_bytes());
+        gif.push(0x00);
+        gif.push(MIN_CODE_SIZE);
+        for block in lzw(frame).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0x00);
+    }
+    gif.push(0x3B);
+    gif
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads codes back the way a GIF decoder does
+    fn unlzw(data: &[u8]) -> Vec<u8> {
+        let clear = 1u16 << MIN_CODE_SIZE;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            *table = (0..=255u8).map(|i| vec![i]).collect();
+            table.extend([Vec::new(), Vec::new()]);
+        };
+        reset(&mut table);
+        let (mut code_size, mut accumulator, mut bits) = (9u32, 0u32, 0u32);
+        let (mut bytes, mut output) = (data.iter(), Vec::new());
+        let mut previous: Option<Vec<u8>> = None;
+        loop {
+            while bits < code_size {
+                accumulator |= (*bytes.next().unwrap() as u32) << bits;
+                bits += 8;
+            }
+            let code = (accumulator & ((1 << code_size) - 1)) as u16;
+            accumulator >>= code_size;
+            bits -= code_size;
+            if code == clear {
+                reset(&mut table);
+                code_size = 9;
+                previous = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return output;
+            }
+            let entry = match (table.get(code as usize), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [previous.clone(), vec![previous[0]]].concat(),
+                (None, None) => panic!("bad code"),
+            };
+            if let Some(previous) = previous.filter(|_| table.len() < MAX_CODES as usize) {
+                table.push([previous, vec![entry[0]]].concat());
+                if table.len() as u32 == 1 << code_size && code_size < 12 {
+                    code_size += 1;
+                }
+            }
+            output.extend(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        let gradient: Vec<u8> = (0..200u32)
+            .flat_map(|y| (0..180u32).map(move |x| ((x * x + y * 7) % 251) as u8))
+            .collect();
+        for indices in [vec![], vec![5], vec![7; 10_000], gradient] {
+            assert_eq!(unlzw(&lzw(&indices)), indices);
+        }
+    }
+
+    #[test]
+    fn test_gif_layout() {
+        let gif = encode_gif(2, 1, &[[0, 0, 0], [255, 255, 255]], &vec![vec![0, 1]; 3], 4);
+        assert!(gif.starts_with(b"GIF89a\x02\x00\x01\x00"));
+        assert_eq!(gif.iter().filter(|&&b| b == 0x2C).count(), 3);
+        assert_eq!(gif.last(), Some(&0x3B));
+    }
+}
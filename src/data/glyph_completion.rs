@@ -0,0 +1,166 @@
+//! Glyph name completion
+//!
+//! Finds the glyphs a partly typed name most likely means, for every field
+//! that takes a glyph name. A glyph matches its exact name first, then the
+//! character it encodes (typed as itself, `U+00E9` or `uni00E9`), then names
+//! starting with the query, names containing it, and last names holding its
+//! letters in order ("agr" finds "agrave" and "Agrave.sc"). Case is ignored
+//! but breaks ties, and closer, shorter matches come first.
+
+/// A glyph matching a query
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GlyphCompletion {
+    pub name: String,
+    /// The glyph's first codepoint, if it has one
+    pub codepoint: Option<char>,
+}
+
+/// How a name matches a query, best first
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchKind {
+    Exact,
+    Codepoint,
+    Prefix,
+    Substring,
+    Fuzzy,
+}
+
+/// How well a name matches: its kind of match, how far off it is, its
+/// length and whether its case differs from the query's, lowest best
+type Rank = (MatchKind, usize, usize, bool);
+
+/// The glyphs matching `query`, best first, at most `limit` of them
+///
+/// Glyphs are given as names with their first codepoint. An empty query
+/// lists the glyphs in the order given.
+pub fn complete<'a>(
+    glyphs: impl IntoIterator<Item = (&'a str, Option<char>)>,
+    query: &str,
+    limit: usize,
+) -> Vec<GlyphCompletion> {
+    let query = query.trim();
+    let glyphs = glyphs.into_iter();
+    if query.is_empty() {
+        return glyphs
+            .take(limit)
+            .map(|(name, codepoint)| GlyphCompletion {
+                name: name.to_string(),
+                codepoint,
+            })
+            .collect();
+    }
+
+    let lowercase = query.to_lowercase();
+    let typed_codepoint = query_codepoint(query);
+    let mut matches: Vec<(Rank, &str, Option<char>)> = glyphs
+        .filter_map(|(name, codepoint)| {
+            let (kind, distance, case_differs) = if name == query {
+                (MatchKind::Exact, 0, false)
+            } else if typed_codepoint.is_some() && codepoint == typed_codepoint {
+                (MatchKind::Codepoint, 0, false)
+            } else {
+                name_match(name, query, &lowercase)?
+            };
+            Some(((kind, distance, name.len(), case_differs), name, codepoint))
+        })
+        .collect();
+    matches.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+    matches
+        .into_iter()
+        .take(limit)
+        .map(|(_, name, codepoint)| GlyphCompletion {
+            name: name.to_string(),
+            codepoint,
+        })
+        .collect()
+}
+
+/// The character a query stands for: itself, or a `U+XXXX`/`uniXXXX`
+/// codepoint
+fn query_codepoint(query: &str) -> Option<char> {
+    let mut chars = query.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(c);
+    }
+    let hex = ["U+", "u+", "uni"]
+        .iter()
+        .find_map(|prefix| query.strip_prefix(prefix))?;
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+/// How a name matches a query other than exactly, how far off it is (the
+/// offset of a substring, or the letters skipped between those of a fuzzy
+/// match) and whether the matching part's case differs
+fn name_match(name: &str, query: &str, lowercase_query: &str) -> Option<(MatchKind, usize, bool)> {
+    let lowercase = name.to_lowercase();
+    if lowercase.starts_with(lowercase_query) {
+        return Some((MatchKind::Prefix, 0, !name.starts_with(query)));
+    }
+    if let Some(offset) = lowercase.find(lowercase_query) {
+        let case_differs = !name
+            .get(offset..)
+            .is_some_and(|rest| rest.starts_with(query));
+        return Some((MatchKind::Substring, offset, case_differs));
+    }
+
+    let mut name_chars = lowercase.chars();
+    let mut skipped = 0;
+    let mut started = false;
+    for wanted in lowercase_query.chars() {
+        loop {
+            let c = name_chars.next()?;
+            if c == wanted {
+                started = true;
+                break;
+            }
+            if started {
+                skipped += 1;
+            }
+        }
+    }
+    Some((MatchKind::Fuzzy, skipped, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLYPHS: &[(&str, Option<char>)] = &[
+        ("A", Some('A')),
+        ("Agrave", Some('À')),
+        ("Agrave.sc", None),
+        ("a", Some('a')),
+        ("aacute", Some('á')),
+        ("agrave", Some('à')),
+        ("eacute", Some('é')),
+        ("uni0431", Some('б')),
+    ];
+
+    fn names(query: &str) -> Vec<String> {
+        complete(GLYPHS.iter().copied(), query, 10)
+            .into_iter()
+            .map(|completion| completion.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_ranking() {
+        assert_eq!(names("agrave"), ["agrave", "Agrave", "Agrave.sc"]);
+        assert_eq!(names("Agr"), ["Agrave", "agrave", "Agrave.sc"]);
+        assert_eq!(names("agr"), ["agrave", "Agrave", "Agrave.sc"]);
+        assert_eq!(names("cute"), ["aacute", "eacute"]);
+        assert_eq!(names("agsc"), ["Agrave.sc"]);
+        assert_eq!(names("a")[..3], ["a", "A", "aacute"]);
+        assert!(names("xyz").is_empty());
+    }
+
+    #[test]
+    fn test_codepoints() {
+        assert_eq!(names("é"), ["eacute"]);
+        assert_eq!(names("U+00E1"), ["aacute"]);
+        assert_eq!(names("uni0431")[0], "uni0431");
+        assert_eq!(names("uni00C0")[0], "Agrave");
+        assert_eq!(names("")[..2], ["A", "Agrave"]);
+        assert_eq!(complete(GLYPHS.iter().copied(), "", 3).len(), 3);
+    }
+}
@@ -0,0 +1,432 @@
+//! Glyph name and Unicode checks
+//!
+//! Compiled fonts store glyph names as production names, which may only use
+//! `A-Z a-z 0-9 . _`, can't start with a digit or a period and are at most
+//! 63 characters long. A glyph whose name breaks these rules gets a
+//! `uniXXXX` name when it's encoded and a cleaned-up one when it isn't, and
+//! two glyphs can end up with the same name that way. Besides the names,
+//! this checks that the font has a `.notdef` and how its characters fare
+//! under Unicode normalization: codepoints that normalization replaces
+//! before text reaches the font, precomposed characters without a glyph
+//! that shapers decompose into base and marks (`ccmp`), and soft-dotted
+//! letters that have no dotless form to swap in under top marks.
+//!
+//! The issues that can be fixed without guessing (renames to a free name,
+//! a placeholder `.notdef`, adding the canonical codepoint, building the
+//! missing composite) are fixed by `fix_issue`.
+
+use crate::core::state::{FontData, GlyphData};
+use crate::data::glyph_recipes::{build_composites, builtin_recipes};
+use crate::font_source::subset::placeholder_outline;
+use std::collections::{BTreeMap, HashSet};
+
+/// Name of the glyph shown for characters the font lacks
+pub const NOTDEF: &str = ".notdef";
+
+/// Longest production name the `post` table allows
+const MAX_PRODUCTION_NAME_LENGTH: usize = 63;
+
+/// Names that start with a period but are valid production names
+const DOTTED_NAMES: [&str; 2] = [NOTDEF, ".null"];
+
+/// Codepoints that canonical normalization replaces by another one:
+/// codepoint and its canonical equivalent
+const SINGLETON_DECOMPOSITIONS: [(char, char); 12] = [
+    ('\u{0340}', '\u{0300}'),
+    ('\u{0341}', '\u{0301}'),
+    ('\u{0343}', '\u{0313}'),
+    ('\u{0374}', '\u{02B9}'),
+    ('\u{037E}', '\u{003B}'),
+    ('\u{0387}', '\u{00B7}'),
+    ('\u{1FEF}', '\u{0060}'),
+    ('\u{2000}', '\u{2002}'),
+    ('\u{2001}', '\u{2003}'),
+    ('\u{2126}', '\u{03A9}'),
+    ('\u{212A}', '\u{004B}'),
+    ('\u{212B}', '\u{00C5}'),
+];
+
+/// Soft-dotted letters and their dotless forms
+const SOFT_DOTTED: [(char, char); 2] = [('i', '\u{0131}'), ('j', '\u{0237}')];
+
+/// A problem with the names or codepoints of a font's glyphs
+#[derive(Clone, Debug, PartialEq)]
+pub enum GlyphNameIssue {
+    /// A glyph name that is renamed in the compiled font; `fixable` when
+    /// no other glyph has or gets the new name
+    InvalidProductionName {
+        glyph: String,
+        production: String,
+        fixable: bool,
+    },
+    /// Glyphs that get the same name in the compiled font
+    DuplicateProductionName {
+        production: String,
+        glyphs: Vec<String>,
+    },
+    MissingNotdef,
+    /// A codepoint that normalization replaces before text reaches the
+    /// font; `fixable` when no glyph has the canonical codepoint yet
+    NormalizedCodepoint {
+        glyph: String,
+        codepoint: char,
+        canonical: char,
+        fixable: bool,
+    },
+    /// A precomposed character without a glyph, though its base and marks
+    /// are in the font
+    Decomposed {
+        recipe: String,
+        codepoint: char,
+    },
+    /// A soft-dotted letter that takes top marks but has no dotless form
+    MissingDotless {
+        glyph: String,
+        dotless: char,
+    },
+}
+
+impl GlyphNameIssue {
+    /// One-line description of the issue
+    pub fn message(&self) -> String {
+        match self {
+            Self::InvalidProductionName {
+                glyph, production, ..
+            } => format!("'{glyph}' is not a valid production name; it becomes '{production}'"),
+            Self::DuplicateProductionName { production, glyphs } => {
+                format!("{} all become '{production}'", glyphs.join(", "))
+            }
+            Self::MissingNotdef => format!("The font has no {NOTDEF} glyph"),
+            Self::NormalizedCodepoint {
+                glyph,
+                codepoint,
+                canonical,
+                ..
+            } => format!(
+                "'{glyph}' is mapped to U+{:04X}, which normalizes to U+{:04X}",
+                *codepoint as u32, *canonical as u32
+            ),
+            Self::Decomposed { recipe, codepoint } => format!(
+                "U+{:04X} has no glyph and is decomposed through ccmp; build '{recipe}'",
+                *codepoint as u32
+            ),
+            Self::MissingDotless { glyph, dotless } => format!(
+                "'{glyph}' keeps its dot under top marks: no glyph for U+{:04X}",
+                *dotless as u32
+            ),
+        }
+    }
+
+    /// Whether `fix_issue` can fix the issue
+    pub fn is_fixable(&self) -> bool {
+        match self {
+            Self::InvalidProductionName { fixable, .. }
+            | Self::NormalizedCodepoint { fixable, .. } => *fixable,
+            Self::MissingNotdef | Self::Decomposed { .. } => true,
+            Self::DuplicateProductionName { .. } | Self::MissingDotless { .. } => false,
+        }
+    }
+}
+
+/// Whether a name can go into the compiled font as is
+pub fn is_valid_production_name(name: &str) -> bool {
+    if DOTTED_NAMES.contains(&name) {
+        return true;
+    }
+    let Some(first) = name.chars().next() else {
+        return false;
+    };
+    name.len() <= MAX_PRODUCTION_NAME_LENGTH
+        && !first.is_ascii_digit()
+        && first != '.'
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_')
+}
+
+/// `uniXXXX` name of a codepoint, or `uXXXXX` outside the BMP
+fn unicode_name(codepoint: char) -> String {
+    if (codepoint as u32) <= 0xFFFF {
+        format!("uni{:04X}", codepoint as u32)
+    } else {
+        format!("u{:05X}", codepoint as u32)
+    }
+}
+
+/// A name with the characters a production name can't have replaced
+fn clean_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let cleaned = match cleaned.chars().next() {
+        Some(first) if first.is_ascii_digit() || first == '.' => format!("_{cleaned}"),
+        Some(_) => cleaned,
+        None => "_".to_string(),
+    };
+    cleaned.chars().take(MAX_PRODUCTION_NAME_LENGTH).collect()
+}
+
+/// Name of a glyph in the compiled font: its own name when valid; else
+/// for an encoded glyph the `uniXXXX` name of its first codepoint, keeping
+/// a valid suffix, and for others its name cleaned up
+pub fn production_name(glyph: &GlyphData) -> String {
+    if is_valid_production_name(&glyph.name) {
+        return glyph.name.clone();
+    }
+    let (base, suffix) = match glyph.name.split_once('.') {
+        Some((base, suffix)) if !base.is_empty() => (base, Some(suffix)),
+        _ => (glyph.name.as_str(), None),
+    };
+    match glyph.unicode_values.first() {
+        Some(&codepoint) if !is_valid_production_name(base) => {
+            let name = match suffix {
+                Some(suffix) => format!("{}.{}", unicode_name(codepoint), clean_name(suffix)),
+                None => unicode_name(codepoint),
+            };
+            clean_name(&name)
+        }
+        _ => clean_name(&glyph.name),
+    }
+}
+
+/// Check the glyph names and codepoints of a font
+pub fn check_glyph_names(font: &FontData) -> Vec<GlyphNameIssue> {
+    let mut glyphs: Vec<&GlyphData> = font.glyphs.values().collect();
+    glyphs.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut issues = Vec::new();
+
+    let mut productions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for glyph in &glyphs {
+        productions
+            .entry(production_name(glyph))
+            .or_default()
+            .push(glyph.name.clone());
+    }
+    for glyph in &glyphs {
+        let production = production_name(glyph);
+        if production != glyph.name {
+            let fixable = productions[&production].len() == 1;
+            issues.push(GlyphNameIssue::InvalidProductionName {
+                glyph: glyph.name.clone(),
+                production,
+                fixable,
+            });
+        }
+    }
+    for (production, names) in &productions {
+        if names.len() > 1 {
+            issues.push(GlyphNameIssue::DuplicateProductionName {
+                production: production.clone(),
+                glyphs: names.clone(),
+            });
+        }
+    }
+
+    if !font.glyphs.contains_key(NOTDEF) {
+        issues.push(GlyphNameIssue::MissingNotdef);
+    }
+
+    let encoded: HashSet<char> = glyphs
+        .iter()
+        .flat_map(|glyph| glyph.unicode_values.iter().copied())
+        .collect();
+    for glyph in &glyphs {
+        for &codepoint in &glyph.unicode_values {
+            let Some(&(_, canonical)) = SINGLETON_DECOMPOSITIONS
+                .iter()
+                .find(|(singleton, _)| *singleton == codepoint)
+            else {
+                continue;
+            };
+            issues.push(GlyphNameIssue::NormalizedCodepoint {
+                glyph: glyph.name.clone(),
+                codepoint,
+                canonical,
+                fixable: !encoded.contains(&canonical),
+            });
+        }
+    }
+
+    for recipe in builtin_recipes() {
+        let Some(codepoint) = recipe.codepoint else {
+            continue;
+        };
+        let parts_present = font.glyphs.contains_key(&recipe.base)
+            && recipe
+                .marks
+                .iter()
+                .all(|mark| font.glyphs.contains_key(&mark.glyph_name));
+        if parts_present && !encoded.contains(&codepoint) && !font.glyphs.contains_key(&recipe.name)
+        {
+            issues.push(GlyphNameIssue::Decomposed {
+                recipe: recipe.name,
+                codepoint,
+            });
+        }
+    }
+
+    let has_top_marks = glyphs
+        .iter()
+        .any(|glyph| glyph.anchors.iter().any(|anchor| anchor.name == "_top"));
+    if has_top_marks {
+        for (letter, dotless) in SOFT_DOTTED {
+            let glyph = glyphs
+                .iter()
+                .find(|glyph| glyph.unicode_values.contains(&letter));
+            if let Some(glyph) = glyph.filter(|_| !encoded.contains(&dotless)) {
+                issues.push(GlyphNameIssue::MissingDotless {
+                    glyph: glyph.name.clone(),
+                    dotless,
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// Fix an issue, if it can be fixed, returning the glyphs that changed; a
+/// missing `.notdef` gets a placeholder `width` wide and `height` tall
+pub fn fix_issue(
+    font: &mut FontData,
+    issue: &GlyphNameIssue,
+    width: f64,
+    height: f64,
+) -> Vec<String> {
+    if !issue.is_fixable() {
+        return Vec::new();
+    }
+    match issue {
+        GlyphNameIssue::InvalidProductionName {
+            glyph, production, ..
+        } => {
+            // Fails, changing nothing, when the production name is taken
+            font.rename_glyph(glyph, production).unwrap_or_default()
+        }
+        GlyphNameIssue::MissingNotdef => {
+            if font.glyphs.contains_key(NOTDEF) {
+                return Vec::new();
+            }
+            font.glyphs.insert(
+                NOTDEF.to_string(),
+                GlyphData {
+                    name: NOTDEF.to_string(),
+                    advance_width: width,
+                    advance_height: None,
+                    unicode_values: Vec::new(),
+                    outline: placeholder_outline(width, height),
+                    components: Vec::new(),
+                    anchors: Vec::new(),
+                },
+            );
+            vec![NOTDEF.to_string()]
+        }
+        GlyphNameIssue::NormalizedCodepoint {
+            glyph, canonical, ..
+        } => {
+            let encoded = font
+                .glyphs
+                .values()
+                .any(|glyph| glyph.unicode_values.contains(canonical));
+            match font.glyphs.get_mut(glyph) {
+                Some(data) if !encoded => {
+                    data.unicode_values.push(*canonical);
+                    vec![glyph.clone()]
+                }
+                _ => Vec::new(),
+            }
+        }
+        GlyphNameIssue::Decomposed { recipe, .. } => {
+            build_composites(font, &builtin_recipes(), Some(std::slice::from_ref(recipe))).built
+        }
+        GlyphNameIssue::DuplicateProductionName { .. } | GlyphNameIssue::MissingDotless { .. } => {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::AnchorData;
+
+    fn glyph(name: &str, unicodes: &[char]) -> GlyphData {
+        GlyphData {
+            unicode_values: unicodes.to_vec(),
+            ..GlyphData::test(name, 500.0)
+        }
+    }
+
+    #[test]
+    fn test_production_names() {
+        assert!(is_valid_production_name("a.ss01"));
+        assert!(is_valid_production_name(".notdef"));
+        assert!(!is_valid_production_name("1st"));
+        assert!(!is_valid_production_name(".alt"));
+        assert!(!is_valid_production_name(&"a".repeat(64)));
+        assert_eq!(production_name(&glyph("é", &['é'])), "uni00E9");
+        assert_eq!(production_name(&glyph("é.sc", &['é'])), "uni00E9.sc");
+        assert_eq!(production_name(&glyph("a-b", &[])), "a_b");
+        assert_eq!(production_name(&glyph("2x", &[])), "_2x");
+    }
+
+    #[test]
+    fn test_check_and_fix() {
+        let mut font = FontData::default();
+        let mut acutecomb = glyph("acutecomb", &['\u{0301}']);
+        acutecomb.anchors.push(AnchorData {
+            name: "_top".to_string(),
+            x: 0.0,
+            y: 0.0,
+        });
+        let mut a = glyph("A", &['A']);
+        a.anchors.push(AnchorData {
+            name: "top".to_string(),
+            x: 250.0,
+            y: 700.0,
+        });
+        for glyph in [
+            a,
+            glyph("i", &['i']),
+            glyph("a-b", &[]),
+            glyph("a_b", &[]),
+            glyph("ohm", &['\u{2126}']),
+            glyph("é", &['é']),
+            acutecomb,
+        ] {
+            font.glyphs.insert(glyph.name.clone(), glyph);
+        }
+
+        let issues = check_glyph_names(&font);
+        assert!(issues.contains(&GlyphNameIssue::MissingNotdef));
+        assert!(issues.contains(&GlyphNameIssue::DuplicateProductionName {
+            production: "a_b".to_string(),
+            glyphs: vec!["a-b".to_string(), "a_b".to_string()],
+        }));
+        assert!(issues.contains(&GlyphNameIssue::Decomposed {
+            recipe: "Aacute".to_string(),
+            codepoint: '\u{00C1}',
+        }));
+        assert!(issues.contains(&GlyphNameIssue::MissingDotless {
+            glyph: "i".to_string(),
+            dotless: '\u{0131}',
+        }));
+
+        for issue in issues.iter().filter(|issue| issue.is_fixable()) {
+            fix_issue(&mut font, issue, 500.0, 700.0);
+        }
+        assert!(font.glyphs.contains_key(NOTDEF));
+        assert!(font.glyphs.contains_key("uni00E9"));
+        assert!(font.glyphs.contains_key("a-b"));
+        assert!(font.glyphs["ohm"].unicode_values.contains(&'\u{03A9}'));
+        let remaining = check_glyph_names(&font);
+        assert!(remaining.iter().all(|issue| !issue.is_fixable()));
+    }
+}
@@ -0,0 +1,263 @@
+//! Composite glyph recipes
+//!
+//! Recipes describe accented glyphs as a base plus marks, e.g.
+//! `Aacute=A+acutecomb@top|00C1`. Building a recipe creates a glyph made of
+//! components, positioned by lining up each mark's `_anchor` with the
+//! matching anchor on the base glyph.
+
+use crate::core::state::{ComponentData, FontData, GlyphData};
+use crate::font_source::AnchorData;
+use std::collections::HashMap;
+
+/// Built-in recipes for Latin accented letters
+const BUILTIN_RECIPES: &str = include_str!("glyph_recipes.txt");
+
+/// A mark attached to an anchor
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecipeMark {
+    pub glyph_name: String,
+    pub anchor: String,
+}
+
+/// How to build one composite glyph
+#[derive(Clone, Debug, PartialEq)]
+pub struct GlyphRecipe {
+    pub name: String,
+    pub base: String,
+    pub marks: Vec<RecipeMark>,
+    pub codepoint: Option<char>,
+}
+
+/// Outcome of a build run
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CompositeBuildReport {
+    pub built: Vec<String>,
+    /// Glyph name and the reason it could not be built
+    pub skipped: Vec<(String, String)>,
+}
+
+/// Parse recipe lines, ignoring blank lines and `#` comments
+pub fn parse_recipes(text: &str) -> Vec<GlyphRecipe> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(parse_recipe)
+        .collect()
+}
+
+fn parse_recipe(line: &str) -> Option<GlyphRecipe> {
+    let (recipe, codepoint) = match line.split_once('|') {
+        Some((recipe, hex)) => (
+            recipe,
+            u32::from_str_radix(hex.trim(), 16).ok().and_then(char::from_u32),
+        ),
+        None => (line, None),
+    };
+    let (name, parts) = recipe.split_once('=')?;
+    let mut parts = parts.split('+').map(str::trim);
+    let base = parts.next()?.to_string();
+    let marks = parts
+        .map(|part| {
+            let (glyph_name, anchor) = part.split_once('@')?;
+            Some(RecipeMark {
+                glyph_name: glyph_name.to_string(),
+                anchor: anchor.to_string(),
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(GlyphRecipe {
+        name: name.trim().to_string(),
+        base,
+        marks,
+        codepoint,
+    })
+}
+
+/// The built-in recipe table
+pub fn builtin_recipes() -> Vec<GlyphRecipe> {
+    parse_recipes(BUILTIN_RECIPES)
+}
+
+/// Build missing composite glyphs
+///
+/// `targets` limits the run to specific glyph names; `None` builds every
+/// recipe whose glyph is not in the font yet. Existing glyphs are never
+/// overwritten. Composites are built from the foreground and added to it,
+/// whichever layer is being edited.
+pub fn build_composites(
+    font: &mut FontData,
+    recipes: &[GlyphRecipe],
+    targets: Option<&[String]>,
+) -> CompositeBuildReport {
+    let mut report = CompositeBuildReport::default();
+
+    for recipe in recipes {
+        if let Some(targets) = targets {
+            if !targets.contains(&recipe.name) {
+                continue;
+            }
+        }
+        if font.glyphs.contains_key(&recipe.name) {
+            if targets.is_some() {
+                report
+                    .skipped
+                    .push((recipe.name.clone(), "glyph already exists".to_string()));
+            }
+            continue;
+        }
+
+        match compose(font, recipe) {
+            Ok(glyph) => {
+                font.glyphs.insert(recipe.name.clone(), glyph);
+                report.built.push(recipe.name.clone());
+            }
+            Err(reason) => report.skipped.push((recipe.name.clone(), reason)),
+        }
+    }
+
+    if let Some(targets) = targets {
+        for target in targets {
+            if !recipes.iter().any(|recipe| &recipe.name == target) {
+                report.skipped.push((target.clone(), "no recipe".to_string()));
+            }
+        }
+    }
+
+    report
+}
+
+/// Assemble one composite from its recipe
+fn compose(font: &FontData, recipe: &GlyphRecipe) -> Result<GlyphData, String> {
+    let base = font
+        .get_glyph(&recipe.base)
+        .ok_or_else(|| format!("missing base glyph '{}'", recipe.base))?;
+
+    // Anchors available for attachment, updated as marks stack up
+    let mut anchors: HashMap<String, (f64, f64)> = base
+        .anchors
+        .iter()
+        .map(|anchor| (anchor.name.clone(), (anchor.x, anchor.y)))
+        .collect();
+
+    let mut components = vec![ComponentData {
+        base_glyph: recipe.base.clone(),
+        ..Default::default()
+    }];
+
+    let is_uppercase = recipe.base.chars().next().is_some_and(char::is_uppercase);
+
+    for mark in &recipe.marks {
+        // Prefer the cap-height variant of a mark on uppercase bases
+        let case_name = format!("{}.case", mark.glyph_name);
+        let mark_name = if is_uppercase && font.glyphs.contains_key(&case_name) {
+            case_name
+        } else {
+            mark.glyph_name.clone()
+        };
+        let mark_glyph = font
+            .get_glyph(&mark_name)
+            .ok_or_else(|| format!("missing mark glyph '{}'", mark_name))?;
+
+        let (base_x, base_y) = *anchors
+            .get(&mark.anchor)
+            .ok_or_else(|| format!("'{}' has no '{}' anchor", recipe.base, mark.anchor))?;
+        let mark_anchor_name = format!("_{}", mark.anchor);
+        let mark_anchor = mark_glyph
+            .anchor(&mark_anchor_name)
+            .ok_or_else(|| format!("'{}' has no '{}' anchor", mark_name, mark_anchor_name))?;
+
+        let dx = base_x - mark_anchor.x;
+        let dy = base_y - mark_anchor.y;
+        components.push(ComponentData {
+            base_glyph: mark_name.clone(),
+            transform: [1.0, 0.0, 0.0, 1.0, dx, dy],
+        });
+
+        // A mark's own anchors (e.g. "top" on a mark) let further marks stack
+        for anchor in mark_glyph.anchors.iter().filter(|a| !a.name.starts_with('_')) {
+            anchors.insert(anchor.name.clone(), (anchor.x + dx, anchor.y + dy));
+        }
+    }
+
+    let mut anchors: Vec<AnchorData> = anchors
+        .into_iter()
+        .map(|(name, (x, y))| AnchorData { name, x, y })
+        .collect();
+    anchors.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(GlyphData {
+        name: recipe.name.clone(),
+        advance_width: base.advance_width,
+        advance_height: base.advance_height,
+        unicode_values: recipe.codepoint.into_iter().collect(),
+        outline: None,
+        components,
+        anchors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::BACKGROUND_LAYER_NAME;
+
+    fn glyph(name: &str, anchors: &[(&str, f64, f64)]) -> GlyphData {
+        GlyphData {
+            anchors: anchors
+                .iter()
+                .map(|&(name, x, y)| AnchorData {
+                    name: name.to_string(),
+                    x,
+                    y,
+                })
+                .collect(),
+            ..GlyphData::test(name, 600.0)
+        }
+    }
+
+    #[test]
+    fn test_builtin_recipes_parse() {
+        let recipes = builtin_recipes();
+        let aacute = recipes.iter().find(|r| r.name == "Aacute").unwrap();
+        assert_eq!(aacute.base, "A");
+        assert_eq!(aacute.marks[0].anchor, "top");
+        assert_eq!(aacute.codepoint, Some('Á'));
+    }
+
+    #[test]
+    fn test_build_places_marks_on_anchors() {
+        let mut font = FontData::default();
+        font.glyphs.insert("A".to_string(), glyph("A", &[("top", 300.0, 700.0)]));
+        font.glyphs.insert(
+            "acutecomb".to_string(),
+            glyph("acutecomb", &[("_top", 100.0, 500.0)]),
+        );
+        let recipes = parse_recipes("Aacute=A+acutecomb@top|00C1\nAgrave=A+gravecomb@top");
+
+        let report = build_composites(&mut font, &recipes, None);
+
+        assert_eq!(report.built, vec!["Aacute".to_string()]);
+        assert_eq!(report.skipped.len(), 1);
+        let aacute = &font.glyphs["Aacute"];
+        assert_eq!(aacute.components[1].transform[4..], [200.0, 200.0]);
+    }
+
+    #[test]
+    fn test_build_uses_the_foreground_while_a_layer_is_edited() {
+        let mut font = FontData::default();
+        font.glyphs.insert("A".to_string(), glyph("A", &[("top", 300.0, 700.0)]));
+        font.glyphs.insert(
+            "acutecomb".to_string(),
+            glyph("acutecomb", &[("_top", 100.0, 500.0)]),
+        );
+        font.switch_editing_layer(Some(BACKGROUND_LAYER_NAME));
+        font.editing_glyph_mut("A").unwrap().advance_width = 900.0;
+        let recipes = parse_recipes("Aacute=A+acutecomb@top|00C1");
+
+        build_composites(&mut font, &recipes, None);
+
+        assert_eq!(font.glyphs["Aacute"].advance_width, 600.0);
+        assert_eq!(font.glyphs["Aacute"].components.len(), 2);
+    }
+}
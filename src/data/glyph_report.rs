@@ -0,0 +1,235 @@
+//! Glyph metrics reports
+//!
+//! Dumps a row per glyph — advance, sidebearings, bounding box, contour and
+//! point counts, components and anchors — as CSV for spreadsheets or JSON
+//! for scripts, to check a font's consistency outside the editor. Bounds and
+//! sidebearings include components. Reports of a designspace have a row per
+//! glyph of each source, told apart by the `source` column.
+
+use crate::font_source::FontData;
+use anyhow::{bail, Context, Result};
+use kurbo::Shape;
+use serde::Serialize;
+use std::path::Path;
+
+/// Columns of a CSV report, in the order of `GlyphReportRow`'s fields
+const CSV_HEADER: &str = "source,glyph,unicodes,advance,lsb,rsb,x_min,y_min,x_max,y_max,\
+                          contours,points,components,anchors";
+
+/// An anchor of a glyph
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ReportAnchor {
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// The metrics of one glyph
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct GlyphReportRow {
+    /// The source the glyph is from, by file name
+    pub source: String,
+    pub glyph: String,
+    /// Codepoints as `U+0041`
+    pub unicodes: Vec<String>,
+    pub advance: f64,
+    pub lsb: Option<f64>,
+    pub rsb: Option<f64>,
+    /// `[x_min, y_min, x_max, y_max]`, `None` for glyphs without outlines
+    pub bounds: Option<[f64; 4]>,
+    pub contours: usize,
+    pub points: usize,
+    pub components: Vec<String>,
+    pub anchors: Vec<ReportAnchor>,
+}
+
+/// A row for each glyph of a font's foreground, sorted by glyph name
+pub fn glyph_report(font: &FontData, source: &str) -> Vec<GlyphReportRow> {
+    let mut names: Vec<&String> = font.glyphs.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .map(|name| {
+            let glyph = &font.glyphs[name];
+            let bounds = font
+                .resolved_bezpaths(name)
+                .iter()
+                .map(|path| path.bounding_box())
+                .reduce(|a, b| a.union(b));
+            let contours = glyph
+                .outline
+                .as_ref()
+                .map_or(&[][..], |outline| outline.contours.as_slice());
+            GlyphReportRow {
+                source: source.to_string(),
+                glyph: name.clone(),
+                unicodes: glyph
+                    .unicode_values
+                    .iter()
+                    .map(|c| format!("U+{:04X}", u32::from(*c)))
+                    .collect(),
+                advance: glyph.advance_width,
+                lsb: bounds.map(|bounds| bounds.x0),
+                rsb: bounds.map(|bounds| glyph.advance_width - bounds.x1),
+                bounds: bounds.map(|bounds| [bounds.x0, bounds.y0, bounds.x1, bounds.y1]),
+                contours: contours.len(),
+                points: contours.iter().map(|contour| contour.points.len()).sum(),
+                components: glyph
+                    .components
+                    .iter()
+                    .map(|component| component.base_glyph.clone())
+                    .collect(),
+                anchors: glyph
+                    .anchors
+                    .iter()
+                    .map(|anchor| ReportAnchor {
+                        name: anchor.name.clone(),
+                        x: anchor.x,
+                        y: anchor.y,
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// A CSV field, quoted when it has to be
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// A report as CSV, lists joined with spaces and anchors as `name:x:y`
+pub fn report_to_csv(rows: &[GlyphReportRow]) -> String {
+    let optional = |value: Option<f64>| value.map(|value| value.to_string()).unwrap_or_default();
+    let mut csv = format!("{CSV_HEADER}\n");
+    for row in rows {
+        let bounds = row.bounds.map_or([None; 4], |bounds| bounds.map(Some));
+        let anchors: Vec<String> = row
+            .anchors
+            .iter()
+            .map(|anchor| format!("{}:{}:{}", anchor.name, anchor.x, anchor.y))
+            .collect();
+        let fields = [
+            row.source.clone(),
+            row.glyph.clone(),
+            row.unicodes.join(" "),
+            row.advance.to_string(),
+            optional(row.lsb),
+            optional(row.rsb),
+            optional(bounds[0]),
+            optional(bounds[1]),
+            optional(bounds[2]),
+            optional(bounds[3]),
+            row.contours.to_string(),
+            row.points.to_string(),
+            row.components.join(" "),
+            anchors.join(" "),
+        ];
+        let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Write a report as CSV or JSON, by the extension of `path`
+pub fn write_glyph_report(rows: &[GlyphReportRow], path: &Path) -> Result<()> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    let contents = match extension.as_deref() {
+        Some("csv") => report_to_csv(rows),
+        Some("json") => serde_json::to_string_pretty(rows)?,
+        _ => bail!(
+            "Glyph reports are .csv or .json files, not {}",
+            path.display()
+        ),
+    };
+    std::fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::{
+        AnchorData, ComponentData, ContourData, GlyphData, OutlineData, PointData, PointTypeData,
+        BACKGROUND_LAYER_NAME,
+    };
+
+    #[test]
+    fn test_glyph_report() {
+        let corners = [(50.0, 0.0), (450.0, 0.0), (450.0, 700.0), (50.0, 700.0)];
+        let square = GlyphData {
+            unicode_values: vec!['H'],
+            outline: Some(OutlineData {
+                contours: vec![ContourData {
+                    points: corners
+                        .iter()
+                        .map(|&(x, y)| PointData {
+                            x,
+                            y,
+                            point_type: PointTypeData::Line,
+                        })
+                        .collect(),
+                }],
+            }),
+            anchors: vec![AnchorData {
+                name: "top".to_string(),
+                x: 250.0,
+                y: 700.0,
+            }],
+            ..GlyphData::test("H", 500.0)
+        };
+        let composite = GlyphData {
+            name: "H.alt, wide".to_string(),
+            advance_width: 600.0,
+            unicode_values: Vec::new(),
+            outline: None,
+            components: vec![ComponentData {
+                base_glyph: "H".to_string(),
+                transform: [1.0, 0.0, 0.0, 1.0, 50.0, 0.0],
+            }],
+            anchors: Vec::new(),
+            ..square.clone()
+        };
+        let mut font = FontData::default();
+        font.glyphs.insert("H".to_string(), square);
+        font.glyphs.insert(composite.name.clone(), composite);
+
+        let rows = glyph_report(&font, "Font-Regular.ufo");
+        assert_eq!(rows[0].unicodes, ["U+0048"]);
+        assert_eq!((rows[0].lsb, rows[0].rsb), (Some(50.0), Some(50.0)));
+        assert_eq!(rows[0].points, 4);
+        // Components count towards the bounds but not the points
+        assert_eq!(rows[1].bounds, Some([100.0, 0.0, 500.0, 700.0]));
+        assert_eq!(rows[1].points, 0);
+
+        let csv = report_to_csv(&rows);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(
+            lines[1],
+            "Font-Regular.ufo,H,U+0048,500,50,50,50,0,450,700,1,4,,top:250:700"
+        );
+        assert!(lines[2].starts_with("Font-Regular.ufo,\"H.alt, wide\",,600,100,100,"));
+    }
+
+    #[test]
+    fn test_report_shows_the_foreground_while_a_layer_is_edited() {
+        let mut font = FontData::default();
+        font.glyphs
+            .insert("a".to_string(), GlyphData::test("a", 500.0));
+        font.glyphs.get_mut("a").unwrap().outline = Some(OutlineData {
+            contours: vec![ContourData { points: Vec::new() }],
+        });
+        font.switch_editing_layer(Some(BACKGROUND_LAYER_NAME));
+
+        let rows = glyph_report(&font, "Font-Regular.ufo");
+        assert_eq!(rows[0].contours, 1);
+    }
+}
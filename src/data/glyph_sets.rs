@@ -0,0 +1,318 @@
+//! Glyph sets
+//!
+//! A glyph set lists the characters and glyphs a font is meant to cover,
+//! like Google Fonts' Latin Core. Sets are read from:
+//! - `.nam` files: a `0xXXXX` codepoint per line, then a description
+//! - FontLab `.enc` files: a `%%FONTLAB` header, then a glyph name and its
+//!   code per line
+//! - glyph name lists: a glyph name per line, optionally followed by its
+//!   codepoint as `U+XXXX` or `0xXXXX`
+//!
+//! `#` starts a comment. Characters listed without a glyph name are named
+//! as in the built-in Latin Core set, or `uniXXXX`.
+
+use crate::core::state::{FontData, GlyphData};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Built-in Latin Core set
+const LATIN_CORE: &str = include_str!("glyph_set_latin_core.txt");
+
+/// Built-in glyph sets: id and name
+pub const BUILTIN_GLYPH_SETS: [(&str, &str); 1] = [("latin-core", "Latin Core")];
+
+/// A glyph the set asks for
+#[derive(Clone, Debug, PartialEq)]
+pub struct GlyphSetEntry {
+    pub name: String,
+    pub codepoint: Option<char>,
+}
+
+/// A target character set for a font
+#[derive(Clone, Debug, PartialEq)]
+pub struct GlyphSet {
+    pub name: String,
+    pub entries: Vec<GlyphSetEntry>,
+}
+
+/// How far along a glyph of the set is
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Coverage {
+    /// The glyph has contours or components
+    Done,
+    /// The glyph is in the font but has nothing drawn
+    Empty,
+    /// The font has no such glyph
+    Missing,
+}
+
+impl Coverage {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Coverage::Done => "done",
+            Coverage::Empty => "empty",
+            Coverage::Missing => "missing",
+        }
+    }
+}
+
+/// Counts of a font's coverage of a glyph set
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CoverageSummary {
+    pub set_name: String,
+    pub done: usize,
+    pub empty: usize,
+    pub missing: usize,
+}
+
+impl CoverageSummary {
+    /// Share of the set that is done, in percent
+    pub fn percent(&self) -> f64 {
+        let total = self.done + self.empty + self.missing;
+        if total == 0 {
+            return 100.0;
+        }
+        self.done as f64 * 100.0 / total as f64
+    }
+}
+
+impl GlyphSet {
+    /// A built-in glyph set by id
+    pub fn builtin(id: &str) -> Option<Self> {
+        let (_, name) = BUILTIN_GLYPH_SETS
+            .iter()
+            .find(|(set_id, _)| *set_id == id)?;
+        let text = match id {
+            "latin-core" => LATIN_CORE,
+            _ => return None,
+        };
+        Some(Self::parse(name, text))
+    }
+
+    /// A built-in glyph set id, or else the path of a glyph set file
+    pub fn load(source: &str) -> Result<Self> {
+        match Self::builtin(source) {
+            Some(set) => Ok(set),
+            None => Self::from_path(Path::new(source)),
+        }
+    }
+
+    /// Read a glyph set file, named after the file unless its header names it
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read glyph set {}", path.display()))?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Glyph set".to_string());
+        let set = Self::parse(&name, &text);
+        if set.entries.is_empty() {
+            anyhow::bail!("No glyphs found in glyph set {}", path.display());
+        }
+        Ok(set)
+    }
+
+    /// Read a glyph set in any of the supported formats
+    pub fn parse(name: &str, text: &str) -> Self {
+        let mut set = Self {
+            name: name.to_string(),
+            entries: Vec::new(),
+        };
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if let Some(header) = line.strip_prefix("%%") {
+                // %%FONTLAB ENCODING: 1; Name
+                if let Some((_, name)) = header.split_once(';') {
+                    set.name = name.trim().to_string();
+                }
+                continue;
+            }
+            let mut tokens = line.split_whitespace();
+            let Some(first) = tokens.next() else {
+                continue;
+            };
+            let entry = match parse_codepoint(first) {
+                Some(codepoint) => GlyphSetEntry {
+                    name: default_glyph_name(codepoint),
+                    codepoint: Some(codepoint),
+                },
+                None => GlyphSetEntry {
+                    name: first.to_string(),
+                    codepoint: tokens.next().and_then(parse_code),
+                },
+            };
+            if !set
+                .entries
+                .iter()
+                .any(|existing| existing.name == entry.name)
+            {
+                set.entries.push(entry);
+            }
+        }
+        set
+    }
+
+    /// Each glyph of the set with how far along it is in the font. Glyphs
+    /// are found by codepoint, or by name when the font has none for it.
+    pub fn coverage(&self, font: &FontData) -> Vec<(&GlyphSetEntry, Coverage)> {
+        let by_codepoint: HashMap<char, &GlyphData> = font
+            .glyphs
+            .values()
+            .flat_map(|glyph| glyph.unicode_values.iter().map(move |c| (*c, glyph)))
+            .collect();
+        self.entries
+            .iter()
+            .map(|entry| {
+                let glyph = entry
+                    .codepoint
+                    .and_then(|c| by_codepoint.get(&c).copied())
+                    .or_else(|| font.glyphs.get(&entry.name));
+                (entry, glyph.map_or(Coverage::Missing, glyph_coverage))
+            })
+            .collect()
+    }
+
+    /// How much of the set the font covers
+    pub fn summary(&self, font: &FontData) -> CoverageSummary {
+        let mut summary = CoverageSummary {
+            set_name: self.name.clone(),
+            ..Default::default()
+        };
+        for (_, coverage) in self.coverage(font) {
+            match coverage {
+                Coverage::Done => summary.done += 1,
+                Coverage::Empty => summary.empty += 1,
+                Coverage::Missing => summary.missing += 1,
+            }
+        }
+        summary
+    }
+
+    /// Add an empty glyph for every glyph of the set the font is missing,
+    /// returning their names
+    pub fn add_placeholders(&self, font: &mut FontData, advance_width: f64) -> Vec<String> {
+        let missing: Vec<GlyphSetEntry> = self
+            .coverage(font)
+            .into_iter()
+            .filter(|(_, coverage)| *coverage == Coverage::Missing)
+            .map(|(entry, _)| entry.clone())
+            .collect();
+        for entry in &missing {
+            let glyph = GlyphData {
+                name: entry.name.clone(),
+                advance_width,
+                advance_height: None,
+                unicode_values: entry.codepoint.into_iter().collect(),
+                outline: None,
+                components: Vec::new(),
+                anchors: Vec::new(),
+            };
+            font.glyphs.insert(entry.name.clone(), glyph);
+        }
+        missing.into_iter().map(|entry| entry.name).collect()
+    }
+}
+
+fn glyph_coverage(glyph: &GlyphData) -> Coverage {
+    let has_contours = glyph
+        .outline
+        .as_ref()
+        .is_some_and(|outline| !outline.contours.is_empty());
+    if has_contours || !glyph.components.is_empty() {
+        Coverage::Done
+    } else {
+        Coverage::Empty
+    }
+}
+
+/// A `U+XXXX` or `0xXXXX` codepoint
+fn parse_codepoint(token: &str) -> Option<char> {
+    let hex = token
+        .strip_prefix("U+")
+        .or_else(|| token.strip_prefix("u+"))
+        .or_else(|| token.strip_prefix("0x"))
+        .or_else(|| token.strip_prefix("0X"))?;
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+/// A codepoint, or an `.enc` file's decimal code
+fn parse_code(token: &str) -> Option<char> {
+    parse_codepoint(token).or_else(|| token.parse().ok().and_then(char::from_u32))
+}
+
+/// The glyph name for a character listed without one
+fn default_glyph_name(codepoint: char) -> String {
+    let known = LATIN_CORE.lines().find_map(|line| {
+        let mut tokens = line.split_whitespace();
+        let name = tokens.next()?;
+        (tokens.next().and_then(parse_codepoint) == Some(codepoint)).then_some(name)
+    });
+    match known {
+        Some(name) => name.to_string(),
+        None if (codepoint as u32) <= 0xFFFF => format!("uni{:04X}", codepoint as u32),
+        None => format!("u{:05X}", codepoint as u32),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_formats() {
+        let nam = GlyphSet::parse(
+            "nam",
+            "# comment\n0x0041  LATIN CAPITAL LETTER A\n0x0416 ZHE\n",
+        );
+        assert_eq!(nam.entries[0].name, "A");
+        assert_eq!(nam.entries[0].codepoint, Some('A'));
+        assert_eq!(nam.entries[1].name, "uni0416");
+
+        let enc = GlyphSet::parse(
+            "enc",
+            "%%FONTLAB ENCODING: 1; My Latin\nAacute 193\n.notdef 0\n",
+        );
+        assert_eq!(enc.name, "My Latin");
+        assert_eq!(enc.entries[0].codepoint, Some('Á'));
+
+        let list = GlyphSet::parse("list", "a\nf_f\nb U+0062\na\n");
+        assert_eq!(list.entries.len(), 3);
+        assert_eq!(list.entries[1].codepoint, None);
+        assert_eq!(list.entries[2].codepoint, Some('b'));
+    }
+
+    #[test]
+    fn test_builtin_latin_core() {
+        let set = GlyphSet::builtin("latin-core").unwrap();
+        assert_eq!(set.name, "Latin Core");
+        assert!(set.entries.iter().all(|entry| entry.codepoint.is_some()));
+        assert!(set.entries.iter().any(|entry| entry.name == "Euro"));
+    }
+
+    #[test]
+    fn test_coverage_and_placeholders() {
+        let empty = |name: &str, codepoint: char| GlyphData {
+            unicode_values: vec![codepoint],
+            ..GlyphData::test(name, 500.0)
+        };
+        let mut font = FontData::default();
+        font.glyphs.insert("A".to_string(), empty("A", 'A'));
+        let mut b = empty("b.alt", 'b');
+        b.components.push(Default::default());
+        font.glyphs.insert("b.alt".to_string(), b);
+        let set = GlyphSet::parse("test", "A U+0041\nb U+0062\nc U+0063\n");
+
+        let summary = set.summary(&font);
+        assert_eq!((summary.done, summary.empty, summary.missing), (1, 1, 1));
+        assert!((summary.percent() - 100.0 / 3.0).abs() < 1e-9);
+
+        assert_eq!(
+            set.add_placeholders(&mut font, 250.0),
+            vec!["c".to_string()]
+        );
+        assert_eq!(font.glyphs["c"].unicode_values, vec!['c']);
+        assert_eq!(set.summary(&font).missing, 0);
+    }
+}
@@ -3,6 +3,48 @@
 //! This module handles all font-related data operations:
 //! - UFO (Unified Font Object) file format support
 //! - UFO format conversions and serialization
+//! - SVG path interchange for outlines
+//! - Composite glyph recipes (accented glyph builder)
+//! - Glyph name and Unicode normalization checks, with safe fixes
+//! - Completion of partly typed glyph names
+//! - Glyph sets a font is meant to cover, and its coverage of them
+//! - The Unicode Character Database: names, categories, scripts, blocks
+//! - Post-processing of compiled fonts (SVG-in-OpenType, STAT, COLR and CPAL
+//!   tables, overlap flags)
+//! - Exporting fonts with export profiles, and checking what was built
+//! - Cleaned copies of sources for export, with open corners closed
+//! - Family releases built from several designspaces at once
+//! - Tables, metrics and coverage of exported fonts
+//! - ttx-style XML dumps of compiled tables, diffed between builds
+//! - Glyph metrics reports as CSV or JSON
+//! - Subsets of a font written as a UFO or TTF
+//! - A cache of whole builds shared by QA and export
+//! - Glyph diagnostics parsed from failed fontc builds
+//! - Images of the canvas as PNG or SVG
+//! - Animated GIFs, for sharing blend animations
 
+pub mod build_check;
+pub mod canvas_image;
+pub mod color_tables;
+pub mod compile_cache;
+pub mod compile_diagnostics;
 pub mod conversions;
+pub mod export;
+pub mod export_copy;
+pub mod family;
+pub mod font_inspector;
+pub mod gif;
+pub mod glyph_completion;
+pub mod glyph_names;
+pub mod glyph_recipes;
+pub mod glyph_report;
+pub mod glyph_sets;
+pub mod overlap_flags;
+pub mod sfnt;
+pub mod stat_table;
+pub mod subset;
+pub mod svg;
+pub mod svg_table;
+pub mod table_dump;
 pub mod ufo;
+pub mod unicode_db;
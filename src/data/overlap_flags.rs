@@ -0,0 +1,96 @@
+//! Overlap flags for variable fonts
+//!
+//! fontTools removes overlaps from a font's default outlines only, which
+//! would leave the deltas of a variable font pointing at points that are
+//! gone. A variable font keeps its overlaps instead and flags every glyph
+//! as overlapping: `OVERLAP_SIMPLE` on the first point of a simple glyph,
+//! `OVERLAP_COMPOUND` on the first component of a composite one. Some
+//! rasterizers, Apple's among them, fill overlapping contours badly unless
+//! the glyph says it has them.
+
+use crate::data::sfnt;
+use anyhow::{Context, Result};
+use write_fonts::read::tables::glyf::Glyf;
+use write_fonts::read::{FontData, TableProvider, TopLevelTable};
+
+/// Flag of a simple glyph's first point telling its contours overlap
+const OVERLAP_SIMPLE: u8 = 0x40;
+
+/// Flag of a composite glyph's first component telling its components
+/// overlap
+const OVERLAP_COMPOUND: u16 = 0x0400;
+
+/// Size of a glyph header: contour count and bounding box
+const GLYPH_HEADER_SIZE: usize = 10;
+
+/// Flag every glyph of compiled font bytes as overlapping
+pub fn apply_overlap_flags(font_bytes: &[u8]) -> Result<Vec<u8>> {
+    let font = sfnt::read_font(font_bytes).context("Failed to read compiled font")?;
+    let loca = font.loca(None).context("Font has no 'loca' table")?;
+    let offsets = (0..=sfnt::num_glyphs(&font)? as usize)
+        .map(|glyph| {
+            let offset = loca.get_raw(glyph).context("Truncated 'loca' table")?;
+            Ok(offset as usize)
+        })
+        .collect::<Result<Vec<usize>>>()?;
+    let mut glyf = font
+        .table_data(Glyf::TAG)
+        .context("Font has no 'glyf' table")?
+        .as_bytes()
+        .to_vec();
+    for range in offsets.windows(2) {
+        let glyph = glyf
+            .get_mut(range[0]..range[1])
+            .context("Glyph outside the 'glyf' table")?;
+        flag_glyph(glyph)?;
+    }
+    Ok(sfnt::with_tables(&font, vec![(Glyf::TAG, glyf)]))
+}
+
+/// Set the overlap flag of one glyph's data; empty glyphs have none
+fn flag_glyph(glyph: &mut [u8]) -> Result<()> {
+    if glyph.len() < GLYPH_HEADER_SIZE {
+        return Ok(());
+    }
+    let data = FontData::new(glyph);
+    let contours: i16 = data.read_at(0)?;
+    if contours < 0 {
+        let flags = data.read_at::<u16>(GLYPH_HEADER_SIZE)? | OVERLAP_COMPOUND;
+        glyph[GLYPH_HEADER_SIZE..GLYPH_HEADER_SIZE + 2].copy_from_slice(&flags.to_be_bytes());
+    } else if contours > 0 {
+        // The flags follow the contour ends and the instructions
+        let instructions = GLYPH_HEADER_SIZE + 2 * contours as usize;
+        let first_flag = instructions + 2 + data.read_at::<u16>(instructions)? as usize;
+        *glyph
+            .get_mut(first_flag)
+            .context("Glyph ends before its flags")? |= OVERLAP_SIMPLE;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_glyphs() {
+        // One contour of one point, after two bytes of instructions
+        let mut simple = vec![
+            0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0xb0, 0x00, 0x01, 0, 0,
+        ];
+        flag_glyph(&mut simple).unwrap();
+        assert_eq!(simple[16], 0x01 | OVERLAP_SIMPLE);
+
+        let mut composite = vec![0xff, 0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0x00, 0x23, 0, 1, 0, 0];
+        flag_glyph(&mut composite).unwrap();
+        assert_eq!(
+            &composite[10..12],
+            &(0x0023 | OVERLAP_COMPOUND).to_be_bytes()
+        );
+
+        let mut empty = Vec::new();
+        flag_glyph(&mut empty).unwrap();
+        let mut truncated = vec![0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert!(flag_glyph(&mut truncated).is_err());
+    }
+}
@@ -0,0 +1,215 @@
+//! Font-level access to compiled fonts
+//!
+//! Fonts that fontc compiled are read with `read-fonts` and written back
+//! with `write-fonts`' `FontBuilder`, which lays out the table directory and
+//! recomputes the table checksums and `head.checkSumAdjustment`. On top of
+//! that this module has what post-processing needs from a whole font:
+//! glyph names and advances in glyph ID order, the feature tags of GSUB and
+//! GPOS, adding or replacing tables, and checking the checksums of fonts
+//! read from disk.
+
+use anyhow::{bail, Context, Result};
+use write_fonts::read::tables::compute_checksum;
+use write_fonts::read::tables::head::Head;
+use write_fonts::read::tables::layout::FeatureList;
+use write_fonts::read::{FileRef, FontRef, ReadError, TableProvider, TopLevelTable};
+use write_fonts::types::{GlyphId, GlyphId16, Tag};
+use write_fonts::FontBuilder;
+
+/// What the checksum of a whole font adds up to when
+/// `head.checkSumAdjustment` is right
+const FONT_CHECKSUM: u32 = 0xB1B0_AFBA;
+
+/// Read a single (non-collection) font
+pub fn read_font(data: &[u8]) -> Result<FontRef<'_>> {
+    match FileRef::new(data)? {
+        FileRef::Font(font) => Ok(font),
+        FileRef::Collection(_) => bail!("Font collections are not supported"),
+    }
+}
+
+/// Glyph names in glyph ID order, from the `post` table
+///
+/// A version 2 table names as many glyphs as it has indices, which may
+/// differ from the glyph count in `maxp`.
+pub fn glyph_names(font: &FontRef) -> Result<Vec<String>> {
+    let post = font.post().context("Font has no 'post' table")?;
+    let num_glyphs = match post.num_glyphs() {
+        Some(num_glyphs) => num_glyphs,
+        None => num_glyphs(font)?,
+    };
+    (0..num_glyphs)
+        .map(|gid| {
+            post.glyph_name(GlyphId16::new(gid))
+                .map(str::to_string)
+                .with_context(|| format!("The 'post' table has no name for glyph {gid}"))
+        })
+        .collect()
+}
+
+/// Number of glyphs, from the `maxp` table
+pub fn num_glyphs(font: &FontRef) -> Result<u16> {
+    Ok(font
+        .maxp()
+        .context("Font has no 'maxp' table")?
+        .num_glyphs())
+}
+
+/// Advance widths in glyph ID order, from the `hmtx` table
+///
+/// Glyphs past the last full metric record share its advance.
+pub fn advance_widths(font: &FontRef) -> Result<Vec<u16>> {
+    let hmtx = font.hmtx().context("Font has no 'hmtx' table")?;
+    (0..num_glyphs(font)?)
+        .map(|gid| {
+            hmtx.advance(GlyphId::from(gid))
+                .context("Font has no horizontal metrics")
+        })
+        .collect()
+}
+
+/// Distinct feature tags of the `GSUB` table, in order of first
+/// appearance; empty if the font has none
+pub fn gsub_features(font: &FontRef) -> Result<Vec<String>> {
+    match font.gsub() {
+        Err(ReadError::TableIsMissing(_)) => Ok(Vec::new()),
+        gsub => feature_tags(gsub?.feature_list()),
+    }
+}
+
+/// Distinct feature tags of the `GPOS` table, in order of first
+/// appearance; empty if the font has none
+pub fn gpos_features(font: &FontRef) -> Result<Vec<String>> {
+    match font.gpos() {
+        Err(ReadError::TableIsMissing(_)) => Ok(Vec::new()),
+        gpos => feature_tags(gpos?.feature_list()),
+    }
+}
+
+fn feature_tags(feature_list: Result<FeatureList, ReadError>) -> Result<Vec<String>> {
+    let feature_list = match feature_list {
+        Err(ReadError::NullOffset) => return Ok(Vec::new()),
+        feature_list => feature_list?,
+    };
+    let mut tags = Vec::new();
+    for record in feature_list.feature_records() {
+        let tag = record.feature_tag().to_string();
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    Ok(tags)
+}
+
+/// The font with these tables added, replacing any with the same tag
+///
+/// `FontBuilder` always writes a TrueType sfnt version, which is what
+/// fontc compiles.
+pub fn with_tables(font: &FontRef, tables: Vec<(Tag, Vec<u8>)>) -> Vec<u8> {
+    let mut builder = FontBuilder::new();
+    for (tag, data) in tables {
+        builder.add_raw(tag, data);
+    }
+    builder.copy_missing_tables(font.clone()).build()
+}
+
+/// Tables whose stored checksum doesn't match their data, and "font" if
+/// `head.checkSumAdjustment` doesn't match the whole file
+pub fn checksum_errors(font: &FontRef) -> Result<Vec<String>> {
+    let mut errors = Vec::new();
+    for record in font.table_directory().table_records() {
+        let tag = record.tag();
+        let mut table = font
+            .table_data(tag)
+            .with_context(|| format!("Table '{tag}' is out of bounds"))?
+            .as_bytes()
+            .to_vec();
+        if tag == Head::TAG && table.len() >= 12 {
+            if compute_checksum(font.data().as_bytes()) != FONT_CHECKSUM {
+                errors.push("font".to_string());
+            }
+            // checkSumAdjustment must be zero while checksumming
+            table[8..12].fill(0);
+        }
+        if compute_checksum(&table) != record.checksum() {
+            errors.push(tag.to_string());
+        }
+    }
+    Ok(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A version 0.5 `maxp` table for three glyphs
+    const MAXP: [u8; 6] = [0, 0, 0x50, 0, 0, 3];
+
+    #[test]
+    fn test_with_tables_keeps_the_others() {
+        let bytes = FontBuilder::new()
+            .add_raw(Tag::new(b"maxp"), MAXP.to_vec())
+            .build();
+        let font = read_font(&bytes).unwrap();
+        let bytes = with_tables(&font, vec![(Tag::new(b"SVG "), vec![1, 2, 3])]);
+
+        let font = read_font(&bytes).unwrap();
+        let table = |tag| font.table_data(Tag::new(tag)).map(|data| data.as_bytes());
+        assert_eq!(table(b"SVG "), Some(&[1u8, 2, 3][..]));
+        assert_eq!(table(b"maxp"), Some(&MAXP[..]));
+    }
+
+    #[test]
+    fn test_checksum_errors_find_changed_tables() {
+        let mut bytes = FontBuilder::new()
+            .add_raw(Tag::new(b"head"), vec![0; 54])
+            .add_raw(Tag::new(b"maxp"), MAXP.to_vec())
+            .build();
+        assert!(checksum_errors(&read_font(&bytes).unwrap())
+            .unwrap()
+            .is_empty());
+
+        // The last bytes are the padded 'maxp' table
+        let maxp = bytes.len() - 3;
+        bytes[maxp] = 1;
+        assert_eq!(
+            checksum_errors(&read_font(&bytes).unwrap()).unwrap(),
+            vec!["font", "maxp"]
+        );
+    }
+
+    #[test]
+    fn test_feature_tags_are_deduplicated() {
+        // GSUB 1.0 header with the feature list at offset 10: three
+        // records, one per script/language system
+        let mut gsub = vec![0, 1, 0, 0, 0, 0, 0, 10, 0, 0, 0, 3];
+        for tag in [b"liga", b"ss01", b"liga"] {
+            gsub.extend_from_slice(tag);
+            gsub.extend_from_slice(&[0, 0]);
+        }
+        let bytes = FontBuilder::new().add_raw(Tag::new(b"GSUB"), gsub).build();
+        let font = read_font(&bytes).unwrap();
+
+        assert_eq!(gsub_features(&font).unwrap(), vec!["liga", "ss01"]);
+        assert!(gpos_features(&font).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_advance_widths_repeat_last_metric() {
+        let mut hhea = vec![0; 36];
+        hhea[..4].copy_from_slice(&[0, 1, 0, 0]);
+        hhea[35] = 2;
+        let bytes = FontBuilder::new()
+            .add_raw(Tag::new(b"maxp"), MAXP.to_vec())
+            .add_raw(Tag::new(b"hhea"), hhea)
+            // Two full records, then one left sidebearing
+            .add_raw(
+                Tag::new(b"hmtx"),
+                vec![0x01, 0xF4, 0, 0, 0x02, 0x58, 0, 10, 0, 20],
+            )
+            .build();
+
+        let font = read_font(&bytes).unwrap();
+        assert_eq!(advance_widths(&font).unwrap(), vec![500, 600, 600]);
+    }
+}
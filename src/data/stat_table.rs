@@ -0,0 +1,195 @@
+//! STAT tables from a family's axis values
+//!
+//! Replaces the STAT table of a compiled variable font with one built from
+//! the family's axis values (see `font_source::stat`), adding their names
+//! to the `name` table. The font's own axes come first, in `fvar` order;
+//! axes that only the values name follow, such as `ital` in the upright
+//! font of a family with a separate italic.
+
+use crate::data::sfnt;
+use crate::font_source::stat::{axis_name, StatAxisValue, StatConfig};
+use anyhow::{anyhow, Context, Result};
+use write_fonts::from_obj::ToOwnedTable;
+use write_fonts::read::{FontRef, ReadError, TableProvider, TopLevelTable};
+use write_fonts::tables::name::{Name, NameRecord};
+use write_fonts::tables::stat::{AxisRecord, AxisValue, AxisValueTableFlags, Stat};
+use write_fonts::types::{Fixed, NameId, Tag};
+use write_fonts::OffsetMarker;
+
+/// First name ID of a font's own names
+const FIRST_FONT_NAME_ID: u16 = 256;
+
+/// Name ID of the font's subfamily name, often the elided fallback name
+const SUBFAMILY_NAME_ID: u16 = 2;
+
+/// Platform, encoding and language of the names added: Windows, Unicode
+/// BMP, US English
+const WINDOWS_ENGLISH: (u16, u16, u16) = (3, 1, 0x409);
+
+/// ID of an English Windows name with this text, added to the `name` table
+/// if the font's own names don't have it yet
+fn name_id(names: &mut Name, text: &str) -> NameId {
+    let existing = names.name_record.iter().find(|record| {
+        (record.platform_id, record.encoding_id, record.language_id) == WINDOWS_ENGLISH
+            && matches!(
+                record.name_id.to_u16(),
+                SUBFAMILY_NAME_ID | FIRST_FONT_NAME_ID..
+            )
+            && record.string.as_str() == text
+    });
+    if let Some(record) = existing {
+        return record.name_id;
+    }
+    let name_id = names
+        .name_record
+        .iter()
+        .map(|record| record.name_id.to_u16().saturating_add(1))
+        .fold(FIRST_FONT_NAME_ID, u16::max);
+    let (platform, encoding, language) = WINDOWS_ENGLISH;
+    names.name_record.push(NameRecord::new(
+        platform,
+        encoding,
+        language,
+        NameId::new(name_id),
+        OffsetMarker::new(text.to_string()),
+    ));
+    NameId::new(name_id)
+}
+
+/// An axis tag as stored, padded with spaces
+fn axis_tag(axis: &str) -> Result<Tag> {
+    Tag::new_checked(axis.as_bytes()).map_err(|_| anyhow!("'{axis}' is not an axis tag"))
+}
+
+/// Tag and name ID of each axis in the `fvar` table, empty for static
+/// fonts
+fn variation_axes(font: &FontRef) -> Result<Vec<(Tag, NameId)>> {
+    let fvar = match font.fvar() {
+        Err(ReadError::TableIsMissing(_)) => return Ok(Vec::new()),
+        fvar => fvar?,
+    };
+    Ok(fvar
+        .axes()?
+        .iter()
+        .map(|axis| (axis.axis_tag(), axis.axis_name_id()))
+        .collect())
+}
+
+/// An axis value table: format 1, or format 3 when linked to another value
+fn axis_value(value: &StatAxisValue, axis_index: u16, name_id: NameId) -> AxisValue {
+    let flags = if value.elidable {
+        AxisValueTableFlags::ELIDABLE_AXIS_VALUE_NAME
+    } else {
+        AxisValueTableFlags::empty()
+    };
+    let fixed = Fixed::from_f64(value.value);
+    match value.linked_value {
+        Some(linked) => {
+            AxisValue::format_3(axis_index, flags, name_id, fixed, Fixed::from_f64(linked))
+        }
+        None => AxisValue::format_1(axis_index, flags, name_id, fixed),
+    }
+}
+
+/// Give a compiled font a STAT table of these axis values
+pub fn apply_stat_table(font_bytes: &[u8], stat: &StatConfig) -> Result<Vec<u8>> {
+    let font = sfnt::read_font(font_bytes)?;
+    let mut names: Name = font
+        .name()
+        .context("Font has no 'name' table")?
+        .to_owned_table();
+
+    let mut axes = variation_axes(&font)?;
+    let mut values = Vec::new();
+    for value in &stat.values {
+        let tag = axis_tag(&value.axis)?;
+        let axis_index = match axes.iter().position(|(axis, _)| *axis == tag) {
+            Some(index) => index,
+            None => {
+                axes.push((tag, name_id(&mut names, axis_name(&value.axis))));
+                axes.len() - 1
+            }
+        };
+        let axis_index = checked_u16(axis_index, "STAT axes")?;
+        let value_name = name_id(&mut names, &value.name);
+        values.push(axis_value(value, axis_index, value_name));
+    }
+    checked_u16(values.len(), "STAT axis values")?;
+    let elided_fallback = name_id(&mut names, &stat.elided_fallback_name);
+
+    let axes = axes
+        .into_iter()
+        .enumerate()
+        .map(|(ordering, (tag, name_id))| {
+            Ok(AxisRecord::new(
+                tag,
+                name_id,
+                checked_u16(ordering, "STAT axes")?,
+            ))
+        })
+        .collect::<Result<_>>()?;
+    let table = Stat::new(axes, values, elided_fallback);
+    names.name_record.sort();
+
+    let tables = vec![
+        (Stat::TAG, write_table(&table, "STAT")?),
+        (Name::TAG, write_table(&names, "name")?),
+    ];
+    Ok(sfnt::with_tables(&font, tables))
+}
+
+/// A count or index as the table stores it
+fn checked_u16(value: usize, what: &str) -> Result<u16> {
+    u16::try_from(value).map_err(|_| anyhow!("Too many {what} for a font ({value})"))
+}
+
+/// Serialize a table, naming it in the error
+fn write_table<T>(table: &T, tag: &str) -> Result<Vec<u8>>
+where
+    T: write_fonts::FontWrite + write_fonts::validate::Validate,
+{
+    write_fonts::dump_table(table).map_err(|e| anyhow!("Failed to write the '{tag}' table: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use write_fonts::read::tables::stat::AxisValue as ReadAxisValue;
+    use write_fonts::FontBuilder;
+
+    #[test]
+    fn test_stat_table_layout() {
+        let mut names = Name::new(Vec::new());
+        assert_eq!(name_id(&mut names, "Regular").to_u16(), 256);
+        assert_eq!(name_id(&mut names, "Bold").to_u16(), 257);
+        assert_eq!(name_id(&mut names, "Regular").to_u16(), 256);
+        let bytes = FontBuilder::new().add_table(&names).unwrap().build();
+
+        let mut stat = StatConfig::default();
+        stat.add_value("wght");
+        stat.toggle_link(0);
+        stat.add_value("ital");
+        let bytes = apply_stat_table(&bytes, &stat).unwrap();
+        let font = sfnt::read_font(&bytes).unwrap();
+        let table = font.stat().unwrap();
+
+        // Two axes added, and the first value linked to another
+        let axes = table.design_axes().unwrap();
+        assert_eq!(axes.len(), 2);
+        assert_eq!(axes[0].axis_tag(), Tag::new(b"wght"));
+        assert_eq!(axes[1].axis_tag(), Tag::new(b"ital"));
+        assert_eq!(
+            table.elided_fallback_name_id().map(|id| id.to_u16()),
+            Some(256)
+        );
+        let values = table.offset_to_axis_values().unwrap().unwrap();
+        let Ok(ReadAxisValue::Format3(first)) = values.axis_values().get(0) else {
+            panic!("The first axis value isn't linked");
+        };
+        assert_eq!(first.flags(), AxisValueTableFlags::ELIDABLE_AXIS_VALUE_NAME);
+        assert_eq!(first.linked_value(), Fixed::from_f64(700.0));
+
+        // Regular, Bold, and the Weight, Italic and Roman names
+        assert_eq!(font.name().unwrap().name_record().len(), 5);
+    }
+}
@@ -0,0 +1,225 @@
+//! Writing font subsets
+//!
+//! Writes the glyphs a `SubsetSpec` keeps (see `font_source::subset`) as a
+//! new UFO, or compiles them with fontc into a TTF, for webfont subsets and
+//! trial fonts. The source UFO's features come along unless they name
+//! glyphs the subset left out, which fontc couldn't compile; they are then
+//! left out with a warning.
+//!
+//! Trial fonts are written the same way, with every glyph the spec doesn't
+//! keep drawn as a placeholder and "Trial" before the family name. Their
+//! full, PostScript and unique names are set from the prefixed family name
+//! too, so none of them matches the full font's.
+
+use crate::core::state::{FontData, FontInfo};
+use crate::data::compile_cache::{compile_with_fontc, ensure_can_compile, fontc_flags};
+use crate::font_source::subset::{ResolvedSubset, SubsetSpec, TRIAL_PREFIX};
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Height of placeholders in fonts without a cap height, as a share of
+/// the em
+const PLACEHOLDER_HEIGHT: f64 = 0.7;
+
+/// What a subset kept and what went wrong on the way
+#[derive(Debug, Default)]
+pub struct SubsetReport {
+    pub resolved: ResolvedSubset,
+    pub warnings: Vec<String>,
+}
+
+/// Glyphs of the font that the features name but the subset doesn't keep
+fn dropped_glyphs_in_features(
+    features: &str,
+    font: &FontData,
+    keep: &BTreeSet<String>,
+) -> BTreeSet<String> {
+    let separators = |c: char| c.is_whitespace() || ";[]{}()<>',=".contains(c);
+    features
+        .split(separators)
+        .map(|word| word.trim_start_matches('\\'))
+        .filter(|word| font.glyphs.contains_key(*word) && !keep.contains(*word))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether a path is a `.ufo` rather than a `.ttf`, failing for others
+fn writes_ufo(output: &Path) -> Result<bool> {
+    let extension = output
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("ufo") => Ok(true),
+        Some("ttf") => Ok(false),
+        _ => bail!(
+            "Subsets are written as .ufo or .ttf, not {}",
+            output.display()
+        ),
+    }
+}
+
+/// The glyphs a spec keeps, failing when it keeps none
+fn resolve(font: &FontData, spec: &SubsetSpec, report: &mut SubsetReport) -> Result<()> {
+    if spec.is_empty() {
+        bail!("Nothing to keep: give codepoints, ranges or glyph names");
+    }
+    let resolved = font.resolve_subset(spec);
+    if resolved.glyphs.iter().all(|name| name == ".notdef") {
+        bail!("None of the font's glyphs are in the subset");
+    }
+    if !resolved.missing.is_empty() {
+        report
+            .warnings
+            .push(format!("Not in the font: {}", resolved.missing.join(" ")));
+    }
+    report.resolved = resolved;
+    Ok(())
+}
+
+/// The features of the font's UFO on disk, if it has been saved
+fn source_features(font: &FontData) -> Result<Option<String>> {
+    let Some(path) = font.path.as_ref().filter(|path| path.exists()) else {
+        return Ok(None);
+    };
+    let request = norad::DataRequest::none().features(true);
+    let source = norad::Font::load_requested_data(path, request)
+        .with_context(|| format!("Failed to read features from {}", path.display()))?;
+    Ok(Some(source.features))
+}
+
+/// Save a UFO, or compile it into a TTF
+fn save_or_compile(ufo: &norad::Font, output: &Path) -> Result<()> {
+    if writes_ufo(output)? {
+        return ufo
+            .save(output)
+            .with_context(|| format!("Failed to write {}", output.display()));
+    }
+    ensure_can_compile()?;
+    let build_dir = tempfile::TempDir::new().context("Failed to create build directory")?;
+    let ufo_path = build_dir.path().join("Subset.ufo");
+    ufo.save(&ufo_path)
+        .context("Failed to write the subset for compilation")?;
+    let bytes = compile_with_fontc(&ufo_path, fontc_flags())?;
+    std::fs::write(output, bytes).with_context(|| format!("Failed to write {}", output.display()))
+}
+
+/// Write the subset of a font to a `.ufo` or `.ttf` path
+pub fn write_subset(
+    font: &FontData,
+    info: &FontInfo,
+    spec: &SubsetSpec,
+    output: &Path,
+) -> Result<SubsetReport> {
+    writes_ufo(output)?;
+    let mut report = SubsetReport::default();
+    resolve(font, spec, &mut report)?;
+    let keep = &report.resolved.glyphs;
+
+    let mut ufo = font.subset(keep).to_norad_font(info);
+    if let Some(features) = source_features(font)? {
+        let dropped = dropped_glyphs_in_features(&features, font, keep);
+        if dropped.is_empty() {
+            ufo.features = features;
+        } else {
+            let names: Vec<&str> = dropped.iter().map(String::as_str).take(5).collect();
+            report.warnings.push(format!(
+                "Features left out: they use {} glyph(s) outside the subset, such as {}",
+                dropped.len(),
+                names.join(" ")
+            ));
+        }
+    }
+    save_or_compile(&ufo, output)?;
+    Ok(report)
+}
+
+/// Characters a PostScript name can't have, besides spaces and non-ASCII
+const POSTSCRIPT_NAME_EXCLUDED: &str = "[](){}<>/%";
+
+/// Set a font's full, PostScript and unique names from its family and
+/// style names
+fn derive_font_names(font_info: &mut norad::FontInfo) {
+    let family = font_info.family_name.clone().unwrap_or_default();
+    let style = font_info.style_name.as_deref().unwrap_or("Regular");
+    let postscript_name: String = format!("{family}-{style}")
+        .chars()
+        .filter(|c| c.is_ascii_graphic() && !POSTSCRIPT_NAME_EXCLUDED.contains(*c))
+        .collect();
+    font_info.postscript_full_name = Some(format!("{family} {style}"));
+    font_info.open_type_name_unique_id = Some(postscript_name.clone());
+    font_info.postscript_font_name = Some(postscript_name);
+}
+
+/// Write a trial version of a font to a `.ufo` or `.ttf` path: every glyph
+/// the spec doesn't keep is a placeholder, and the names are prefixed
+pub fn write_trial(
+    font: &FontData,
+    info: &FontInfo,
+    spec: &SubsetSpec,
+    output: &Path,
+) -> Result<SubsetReport> {
+    writes_ufo(output)?;
+    let mut report = SubsetReport::default();
+    resolve(font, spec, &mut report)?;
+
+    // Placeholders are as tall as capitals
+    let height = info
+        .cap_height
+        .unwrap_or(info.units_per_em * PLACEHOLDER_HEIGHT);
+    let mut info = info.clone();
+    info.family_name = format!("{TRIAL_PREFIX} {}", info.family_name)
+        .trim()
+        .to_string();
+    let mut ufo = font
+        .trial(&report.resolved.glyphs, height)
+        .to_norad_font(&info);
+    derive_font_names(&mut ufo.font_info);
+    // Every glyph is still there, so the features still compile
+    if let Some(features) = source_features(font)? {
+        ufo.features = features;
+    }
+    save_or_compile(&ufo, output)?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::GlyphData;
+
+    #[test]
+    fn test_trial_names() {
+        let mut font = FontData::default();
+        for name in [".notdef", "A", "B"] {
+            font.glyphs
+                .insert(name.to_string(), GlyphData::test(name, 500.0));
+        }
+        let info = FontInfo {
+            family_name: "Bezy Grotesk".to_string(),
+            style_name: "Bold".to_string(),
+            units_per_em: 1000.0,
+            ..Default::default()
+        };
+        let spec = SubsetSpec::parse(&["A".to_string()]).unwrap();
+        let dir = tempfile::TempDir::new().unwrap();
+        let output = dir.path().join("Trial.ufo");
+        write_trial(&font, &info, &spec, &output).unwrap();
+
+        let names = norad::Font::load(&output).unwrap().font_info;
+        assert_eq!(names.family_name.as_deref(), Some("Trial Bezy Grotesk"));
+        assert_eq!(
+            names.postscript_full_name.as_deref(),
+            Some("Trial Bezy Grotesk Bold")
+        );
+        assert_eq!(
+            names.postscript_font_name.as_deref(),
+            Some("TrialBezyGrotesk-Bold")
+        );
+        assert_eq!(
+            names.open_type_name_unique_id.as_deref(),
+            Some("TrialBezyGrotesk-Bold")
+        );
+    }
+}
@@ -0,0 +1,408 @@
+//! SVG path interchange
+//!
+//! Converts glyph contours to and from SVG so outlines can move between Bezy
+//! and vector apps. Font units are y-up while SVG is y-down, so every
+//! conversion goes through an affine transform that scales and flips.
+//!
+//! Documents are read with usvg, which resolves transforms, `<use>`,
+//! styles and basic shapes into paths and leaves out `<defs>`, so what's
+//! imported is what the document draws. Outlines can only hold filled
+//! shapes, so stroked paths, text, images, clip paths, masks and filters
+//! are refused with an error instead of being imported wrongly.
+
+use crate::core::state::{ContourData, FontInfo, GlyphData};
+use anyhow::{anyhow, bail, Result};
+use kurbo::{Affine, BezPath};
+use std::path::{Path, PathBuf};
+use usvg::tiny_skia_path::PathSegment;
+
+/// Build the font-units → SVG transform
+///
+/// `scale` converts font units to SVG user units, and `baseline` is the
+/// font-space y value that maps to SVG y = 0 (use the ascender to keep the
+/// whole glyph inside a viewBox that starts at zero).
+pub fn font_to_svg_transform(scale: f64, baseline: f64) -> Affine {
+    Affine::new([scale, 0.0, 0.0, -scale, 0.0, baseline * scale])
+}
+
+/// Convert contours into a single SVG path `d` attribute
+pub fn contours_to_svg_path(contours: &[ContourData], transform: Affine) -> String {
+    contours
+        .iter()
+        .map(|contour| (transform * contour.to_bezpath()).to_svg())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Wrap contours in a standalone SVG document
+pub fn contours_to_svg_document(
+    contours: &[ContourData],
+    transform: Affine,
+    width: f64,
+    height: f64,
+) -> String {
+    let path_data = contours_to_svg_path(contours, transform);
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {width} {height}\" \
+         width=\"{width}\" height=\"{height}\">\n  <path d=\"{path_data}\"/>\n</svg>\n"
+    )
+}
+
+/// Parse SVG text into contours in font units
+///
+/// Accepts either a full SVG document, whose filled shapes are read in the
+/// units of its canvas, or bare path data. `transform` is the font → SVG
+/// transform; its inverse is applied to the parsed geometry.
+pub fn svg_to_contours(svg: &str, transform: Affine) -> Result<Vec<ContourData>> {
+    let paths = if svg.contains('<') {
+        document_paths(&parse_document(svg)?)?
+    } else {
+        let path =
+            BezPath::from_svg(svg.trim()).map_err(|e| anyhow!("Invalid SVG path data: {e}"))?;
+        vec![path]
+    };
+    paths_to_contours(paths, transform)
+}
+
+/// Contours in font units of paths drawn in SVG units
+fn paths_to_contours(paths: Vec<BezPath>, transform: Affine) -> Result<Vec<ContourData>> {
+    let inverse = transform.inverse();
+    let contours: Vec<ContourData> = paths
+        .into_iter()
+        .flat_map(|path| ContourData::from_bezpath(&(inverse * path)))
+        .collect();
+    if contours.is_empty() {
+        bail!("SVG contained no drawable contours");
+    }
+    Ok(contours)
+}
+
+/// Parse an SVG document, refusing the elements outlines can't hold that
+/// usvg would leave out silently
+fn parse_document(svg: &str) -> Result<usvg::Tree> {
+    let document =
+        usvg::roxmltree::Document::parse(svg).map_err(|e| anyhow!("Invalid SVG: {e}"))?;
+    if document
+        .descendants()
+        .any(|node| node.tag_name().name() == "text")
+    {
+        bail!("SVG text can't be imported; convert it to outlines first");
+    }
+    usvg::Tree::from_str(svg, &usvg::Options::default()).map_err(|e| anyhow!("Invalid SVG: {e}"))
+}
+
+/// Every filled path a document draws, transformed into its canvas units
+fn document_paths(tree: &usvg::Tree) -> Result<Vec<BezPath>> {
+    let mut paths = Vec::new();
+    collect_paths(tree.root(), &mut paths)?;
+    if paths.is_empty() {
+        bail!("SVG has no filled shapes to import");
+    }
+    Ok(paths)
+}
+
+fn collect_paths(group: &usvg::Group, paths: &mut Vec<BezPath>) -> Result<()> {
+    for node in group.children() {
+        match node {
+            usvg::Node::Group(group) => {
+                if group.clip_path().is_some() || group.mask().is_some() {
+                    bail!("SVG clip paths and masks can't be imported");
+                }
+                if !group.filters().is_empty() {
+                    bail!("SVG filters can't be imported");
+                }
+                collect_paths(group, paths)?;
+            }
+            usvg::Node::Path(path) => {
+                if !path.is_visible() {
+                    continue;
+                }
+                if path.fill().is_none() {
+                    bail!("SVG strokes can't be imported; outline them first");
+                }
+                paths.push(to_bezpath(path.data(), path.abs_transform()));
+            }
+            usvg::Node::Image(_) => bail!("SVG images can't be imported"),
+            usvg::Node::Text(_) => {
+                bail!("SVG text can't be imported; convert it to outlines first")
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A usvg path as a kurbo path, with `transform` applied
+fn to_bezpath(data: &usvg::tiny_skia_path::Path, transform: usvg::Transform) -> BezPath {
+    let point = |p: usvg::tiny_skia_path::Point| kurbo::Point::new(p.x as f64, p.y as f64);
+    let mut path = BezPath::new();
+    for segment in data.segments() {
+        match segment {
+            PathSegment::MoveTo(p) => path.move_to(point(p)),
+            PathSegment::LineTo(p) => path.line_to(point(p)),
+            PathSegment::QuadTo(p1, p2) => path.quad_to(point(p1), point(p2)),
+            PathSegment::CubicTo(p1, p2, p3) => path.curve_to(point(p1), point(p2), point(p3)),
+            PathSegment::Close => path.close_path(),
+        }
+    }
+    let usvg::Transform {
+        sx,
+        ky,
+        kx,
+        sy,
+        tx,
+        ty,
+    } = transform;
+    Affine::new([sx, ky, kx, sy, tx, ty].map(f64::from)) * path
+}
+
+/// Vertical extent of the font as (top, bottom) in font units
+fn vertical_extent(info: &FontInfo) -> (f64, f64) {
+    let upm = if info.units_per_em > 0.0 {
+        info.units_per_em
+    } else {
+        1000.0
+    };
+    let top = info.ascender.unwrap_or(upm * 0.8);
+    let bottom = info.descender.unwrap_or(-(upm * 0.2));
+    (top, bottom)
+}
+
+/// Export a glyph as a standalone SVG document
+///
+/// The viewBox spans the advance width horizontally and ascender to
+/// descender vertically, so the file lines up with the glyph's em box.
+pub fn glyph_to_svg(glyph: &GlyphData, info: &FontInfo) -> String {
+    let (top, bottom) = vertical_extent(info);
+    let transform = font_to_svg_transform(1.0, top);
+    let contours = glyph
+        .outline
+        .as_ref()
+        .map(|outline| outline.contours.as_slice())
+        .unwrap_or_default();
+    contours_to_svg_document(contours, transform, glyph.advance_width, top - bottom)
+}
+
+/// Import an SVG file's outlines into font units
+///
+/// The height of the SVG's canvas is scaled to the font's
+/// ascender-descender span and its top edge is placed on the ascender,
+/// which is how icon sets are normally drawn. usvg fits the viewBox to the
+/// canvas, so when they have the same proportions the outlines line up
+/// with the artwork placed by [`artwork_transform`]. Bare path data is read
+/// as font-sized units measured down from the ascender.
+pub fn svg_to_glyph_contours(svg: &str, info: &FontInfo) -> Result<Vec<ContourData>> {
+    let (top, bottom) = vertical_extent(info);
+    if !svg.contains('<') {
+        return svg_to_contours(svg, font_to_svg_transform(1.0, top));
+    }
+    let tree = parse_document(svg)?;
+    let svg_units_per_font_unit = tree.size().height() as f64 / (top - bottom);
+    let transform = font_to_svg_transform(svg_units_per_font_unit, top);
+    paths_to_contours(document_paths(&tree)?, transform)
+}
+
+/// The font → SVG transform that places an SVG file's viewBox on the em
+///
+/// The viewBox height is scaled to the ascender-descender span and its top
+/// edge placed on the ascender, like [`svg_to_glyph_contours`] does with
+/// the canvas.
+pub fn artwork_transform(svg: &str, info: &FontInfo) -> Affine {
+    let (top, bottom) = vertical_extent(info);
+    match svg_view_box(svg) {
+        Some([min_x, min_y, _width, height]) if height > 0.0 => {
+            let svg_units_per_font_unit = height / (top - bottom);
+            Affine::translate((min_x, min_y))
+                * font_to_svg_transform(svg_units_per_font_unit, top)
+        }
+        _ => font_to_svg_transform(1.0, top),
+    }
+}
+
+/// Read the `viewBox` of the root `<svg>` element, falling back to its size
+fn svg_view_box(svg: &str) -> Option<[f64; 4]> {
+    let document = usvg::roxmltree::Document::parse(svg).ok()?;
+    let root = document.root_element();
+
+    if let Some(view_box) = root.attribute("viewBox") {
+        let values: Vec<f64> = view_box
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|part| !part.is_empty())
+            .filter_map(|part| part.parse().ok())
+            .collect();
+        if let [x, y, w, h] = values[..] {
+            return Some([x, y, w, h]);
+        }
+    }
+
+    // Strip unit suffixes such as "px" from width/height
+    let length = |name: &str| {
+        root.attribute(name)
+            .map(|value| value.trim_end_matches(|c: char| c.is_ascii_alphabetic()))
+            .and_then(|value| value.parse::<f64>().ok())
+    };
+    Some([0.0, 0.0, length("width")?, length("height")?])
+}
+
+/// File name for a glyph's SVG, following the UFO convention of marking
+/// uppercase letters with a trailing underscore so names stay unique on
+/// case-insensitive file systems
+pub fn glyph_svg_file_name(glyph_name: &str) -> String {
+    let mut stem = String::with_capacity(glyph_name.len() + 4);
+    for c in glyph_name.chars() {
+        match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => stem.push('_'),
+            c if c.is_control() => stem.push('_'),
+            c if c.is_ascii_uppercase() => {
+                stem.push(c);
+                stem.push('_');
+            }
+            c => stem.push(c),
+        }
+    }
+    if stem.starts_with('.') {
+        stem.replace_range(0..1, "_");
+    }
+    format!("{stem}.svg")
+}
+
+/// Write one SVG file per glyph into `dir`, returning the number written
+pub fn export_glyphs_to_dir<'a>(
+    glyphs: impl IntoIterator<Item = &'a GlyphData>,
+    info: &FontInfo,
+    dir: &Path,
+) -> Result<usize> {
+    std::fs::create_dir_all(dir)?;
+    let mut written = 0;
+    for glyph in glyphs {
+        let path: PathBuf = dir.join(glyph_svg_file_name(&glyph.name));
+        std::fs::write(&path, glyph_to_svg(glyph, info))?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg_round_trip() {
+        let transform = font_to_svg_transform(0.5, 800.0);
+        let square = ContourData::test_square(100.0);
+        let svg = contours_to_svg_document(&[square.clone()], transform, 500.0, 500.0);
+        let contours = svg_to_contours(&svg, transform).unwrap();
+
+        assert_eq!(contours.len(), 1);
+        assert_eq!(contours[0].points.len(), 4);
+        for (a, b) in contours[0].points.iter().zip(square.points.iter()) {
+            assert!((a.x - b.x).abs() < 1e-6 && (a.y - b.y).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_svg_import_reads_what_the_document_draws() {
+        let svg = "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 100 100\">\
+                   <defs><path id=\"hidden\" d=\"M0 0H5V5Z\"/></defs>\
+                   <g transform=\"translate(10 20)\"><rect width=\"30\" height=\"40\"/></g>\
+                   <circle cx=\"80\" cy=\"80\" r=\"10\"/>\
+                   </svg>";
+        let contours = svg_to_contours(svg, Affine::IDENTITY).unwrap();
+
+        // The rect and the circle, not the path in <defs>
+        assert_eq!(contours.len(), 2);
+        let rect = &contours[0].points;
+        let min_x = rect.iter().map(|p| p.x).fold(f64::MAX, f64::min);
+        let max_y = rect.iter().map(|p| p.y).fold(f64::MIN, f64::max);
+        assert!((min_x - 10.0).abs() < 1e-4);
+        assert!((max_y - 60.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_svg_import_refuses_what_outlines_cant_hold() {
+        let document = |content: &str| {
+            format!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 10 10\">{content}</svg>"
+            )
+        };
+        for content in [
+            "<path d=\"M0 0L10 10\" fill=\"none\" stroke=\"black\"/>",
+            "<text x=\"0\" y=\"10\">A</text>",
+        ] {
+            assert!(svg_to_contours(&document(content), Affine::IDENTITY).is_err());
+        }
+        assert!(svg_to_contours(&document(""), Affine::IDENTITY).is_err());
+    }
+
+    #[test]
+    fn test_svg_import_scales_view_box_to_em() {
+        let info = FontInfo {
+            units_per_em: 1000.0,
+            ascender: Some(800.0),
+            descender: Some(-200.0),
+            ..Default::default()
+        };
+        let svg = "<svg viewBox=\"0 0 24 24\"><path d=\"M0 0H24V24H0Z\"/></svg>";
+        let contours = svg_to_glyph_contours(svg, &info).unwrap();
+
+        let points = &contours[0].points;
+        let min_y = points.iter().map(|p| p.y).fold(f64::MAX, f64::min);
+        let max_x = points.iter().map(|p| p.x).fold(f64::MIN, f64::max);
+        assert!((min_y + 200.0).abs() < 1e-6);
+        assert!((max_x - 1000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_svg_import_applies_group_transforms() {
+        let info = FontInfo {
+            units_per_em: 1000.0,
+            ascender: Some(800.0),
+            descender: Some(-200.0),
+            ..Default::default()
+        };
+        // The square lands in the top right quarter of the em
+        let svg = "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\">\
+                   <g transform=\"translate(12 0)\"><g transform=\"scale(0.5)\">\
+                   <path d=\"M0 0H24V24H0Z\"/>\
+                   </g></g></svg>";
+        let contours = svg_to_glyph_contours(svg, &info).unwrap();
+
+        let points = &contours[0].points;
+        let min_x = points.iter().map(|p| p.x).fold(f64::MAX, f64::min);
+        let max_x = points.iter().map(|p| p.x).fold(f64::MIN, f64::max);
+        let min_y = points.iter().map(|p| p.y).fold(f64::MAX, f64::min);
+        let max_y = points.iter().map(|p| p.y).fold(f64::MIN, f64::max);
+        assert!((min_x - 500.0).abs() < 1e-3 && (max_x - 1000.0).abs() < 1e-3);
+        assert!((min_y - 300.0).abs() < 1e-3 && (max_y - 800.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_glyph_svg_file_name() {
+        assert_eq!(glyph_svg_file_name("A"), "A_.svg");
+        assert_eq!(glyph_svg_file_name("a.sc"), "a.sc.svg");
+        assert_eq!(glyph_svg_file_name(".notdef"), "_notdef.svg");
+    }
+
+    #[test]
+    fn test_export_while_a_layer_is_edited_writes_the_foreground() {
+        use crate::core::state::{FontData, OutlineData};
+        use crate::font_source::BACKGROUND_LAYER_NAME;
+
+        let mut font = FontData::default();
+        let foreground = GlyphData {
+            outline: Some(OutlineData {
+                contours: vec![ContourData::test_square(100.0)],
+            }),
+            ..GlyphData::test("a", 500.0)
+        };
+        font.glyphs.insert("a".to_string(), foreground.clone());
+        font.switch_editing_layer(Some(BACKGROUND_LAYER_NAME));
+        font.editing_glyph_mut("a").unwrap().outline = None;
+
+        let info = FontInfo::default();
+        let dir = tempfile::TempDir::new().unwrap();
+        let written = export_glyphs_to_dir(font.glyphs.values(), &info, dir.path()).unwrap();
+        assert_eq!(written, 1);
+        let svg = std::fs::read_to_string(dir.path().join(glyph_svg_file_name("a"))).unwrap();
+        assert_eq!(svg, glyph_to_svg(&foreground, &info));
+    }
+}
@@ -0,0 +1,191 @@
+//! SVG-in-OpenType export
+//!
+//! Builds an `SVG ` table for a compiled font so emoji and icon glyphs can
+//! ship full-color artwork while keeping their outlines as the monochrome
+//! fallback. Documents come from two places:
+//! - artwork kept in the UFO's data folder (saved when an SVG is imported)
+//! - color glyphs, whose COLR layers are flattened into filled paths
+
+use crate::core::state::{FontData, FontInfo};
+use crate::data::sfnt;
+use crate::data::svg;
+use anyhow::{anyhow, Context, Result};
+use kurbo::Affine;
+use std::path::{Path, PathBuf};
+use write_fonts::read::tables::svg::Svg;
+use write_fonts::read::TopLevelTable;
+
+/// UFO `data/` subfolder that stores original SVG artwork per glyph
+pub const ARTWORK_DATA_DIR: &str = "org.bezy.svgArtwork";
+
+/// Where a glyph's original SVG artwork is kept inside a UFO
+pub fn artwork_path(ufo_path: &Path, glyph_name: &str) -> PathBuf {
+    ufo_path
+        .join("data")
+        .join(ARTWORK_DATA_DIR)
+        .join(svg::glyph_svg_file_name(glyph_name))
+}
+
+/// Add an `SVG ` table to compiled font bytes
+///
+/// Returns `Ok(None)` when no glyph has artwork or color layers, so callers
+/// can skip writing a redundant file.
+pub fn embed_svg_table(
+    font_bytes: &[u8],
+    font: &FontData,
+    info: &FontInfo,
+) -> Result<Option<Vec<u8>>> {
+    let compiled = sfnt::read_font(font_bytes).context("Failed to read compiled font")?;
+    let glyph_names = sfnt::glyph_names(&compiled)?;
+
+    let mut documents = Vec::new();
+    for (glyph_id, name) in glyph_names.iter().enumerate() {
+        let Ok(glyph_id) = u16::try_from(glyph_id) else {
+            break;
+        };
+        let artwork = font
+            .path
+            .as_ref()
+            .and_then(|ufo| std::fs::read_to_string(artwork_path(ufo, name)).ok());
+
+        let document = match artwork {
+            Some(artwork) => artwork_document(glyph_id, &artwork, info),
+            None => color_layers_document(glyph_id, name, font),
+        };
+        if let Some(document) = document {
+            documents.push((glyph_id, document));
+        }
+    }
+
+    if documents.is_empty() {
+        return Ok(None);
+    }
+
+    let table = (Svg::TAG, build_svg_table(&documents)?);
+    Ok(Some(sfnt::with_tables(&compiled, vec![table])))
+}
+
+/// Serialize an `SVG ` table (version 0) with one document per glyph
+///
+/// `write-fonts` has no `SVG ` table, so it's laid out here; the table's
+/// 16-bit document count and 32-bit offsets and lengths are checked, and
+/// documents that don't fit them are an error.
+pub fn build_svg_table(documents: &[(u16, String)]) -> Result<Vec<u8>> {
+    let mut documents: Vec<&(u16, String)> = documents.iter().collect();
+    documents.sort_by_key(|(glyph_id, _)| *glyph_id);
+
+    const HEADER_LEN: u32 = 10;
+    const ENTRY_LEN: u32 = 12;
+
+    let count = u16::try_from(documents.len())
+        .map_err(|_| anyhow!("Too many SVG documents for a font ({})", documents.len()))?;
+    let mut table = Vec::new();
+    table.extend_from_slice(&0u16.to_be_bytes()); // version
+    table.extend_from_slice(&HEADER_LEN.to_be_bytes()); // svgDocumentListOffset
+    table.extend_from_slice(&0u32.to_be_bytes()); // reserved
+
+    // Document offsets are relative to the start of the document list
+    let mut doc_offset = 2 + ENTRY_LEN * u32::from(count);
+    table.extend_from_slice(&count.to_be_bytes());
+    for (glyph_id, document) in &documents {
+        let length = u32::try_from(document.len())
+            .with_context(|| format!("The SVG document of glyph {glyph_id} is too large"))?;
+        table.extend_from_slice(&glyph_id.to_be_bytes()); // startGlyphID
+        table.extend_from_slice(&glyph_id.to_be_bytes()); // endGlyphID
+        table.extend_from_slice(&doc_offset.to_be_bytes());
+        table.extend_from_slice(&length.to_be_bytes());
+        doc_offset = doc_offset
+            .checked_add(length)
+            .context("The SVG documents are too large for an 'SVG ' table")?;
+    }
+    for (_, document) in &documents {
+        table.extend_from_slice(document.as_bytes());
+    }
+
+    Ok(table)
+}
+
+/// SVG table documents use font units with y pointing down from the baseline
+fn table_transform() -> Affine {
+    svg::font_to_svg_transform(1.0, 0.0)
+}
+
+/// Flatten a color glyph's layers into an SVG document
+fn color_layers_document(glyph_id: u16, glyph_name: &str, font: &FontData) -> Option<String> {
+    let layers = font.color.layers_for(glyph_name)?;
+
+    let paths: Vec<String> = layers
+        .iter()
+        .filter_map(|layer| {
+            let outline = font.get_glyph(&layer.glyph_name)?.outline.as_ref()?;
+            let data = svg::contours_to_svg_path(&outline.contours, table_transform());
+            let fill = match font.color.resolve_color(layer.palette_index) {
+                Some([r, g, b, a]) => format!(
+                    "fill=\"#{:02x}{:02x}{:02x}\" fill-opacity=\"{}\"",
+                    (r * 255.0).round() as u8,
+                    (g * 255.0).round() as u8,
+                    (b * 255.0).round() as u8,
+                    a
+                ),
+                None => "fill=\"currentColor\"".to_string(),
+            };
+            Some(format!("<path {fill} d=\"{data}\"/>"))
+        })
+        .collect();
+
+    if paths.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\"><g id=\"glyph{glyph_id}\">{}</g></svg>",
+        paths.concat()
+    ))
+}
+
+/// Re-home imported artwork into the glyph's coordinate system
+///
+/// The artwork's root `<svg>` content is wrapped in a group whose transform
+/// maps the artwork's viewBox onto the em the same way SVG import does.
+fn artwork_document(glyph_id: u16, artwork: &str, info: &FontInfo) -> Option<String> {
+    let open = artwork.find("<svg")?;
+    let content_start = open + artwork[open..].find('>')? + 1;
+    let content_end = artwork.rfind("</svg>")?;
+    let content = artwork.get(content_start..content_end)?;
+
+    let to_font = svg::artwork_transform(artwork, info).inverse();
+    let [a, b, c, d, e, f] = (table_transform() * to_font).as_coeffs();
+
+    Some(format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+         xmlns:xlink=\"http://www.w3.org/1999/xlink\">\
+         <g id=\"glyph{glyph_id}\" transform=\"matrix({a} {b} {c} {d} {e} {f})\">{content}</g>\
+         </svg>"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg_table_layout() {
+        let table = build_svg_table(&[(3, "<svg/>".to_string())]).unwrap();
+
+        // Header (10) + count (2) + one entry (12) + document (6)
+        assert_eq!(table.len(), 30);
+        assert_eq!(u16::from_be_bytes([table[10], table[11]]), 1);
+        assert_eq!(u16::from_be_bytes([table[12], table[13]]), 3);
+        let doc_offset = u32::from_be_bytes([table[16], table[17], table[18], table[19]]);
+        assert_eq!(&table[10 + doc_offset as usize..], b"<svg/>");
+    }
+
+    #[test]
+    fn test_svg_table_refuses_more_documents_than_it_can_count() {
+        let documents: Vec<(u16, String)> = (0..=u16::MAX as usize)
+            .map(|index| (index as u16, String::new()))
+            .collect();
+        let error = build_svg_table(&documents).unwrap_err();
+        assert!(error.to_string().contains("Too many SVG documents"));
+    }
+}
@@ -0,0 +1,727 @@
+//! ttx-style dumps of compiled fonts
+//!
+//! Writes the tables of a compiled font as XML laid out the way fontTools'
+//! ttx does: `GlyphOrder`, the fields of `head`, `hhea`, `maxp`, `OS/2` and
+//! `post`, `hmtx` metrics by glyph name, `cmap` mappings and `name` records,
+//! and every other table as hex data. Two dumps of the same font can then
+//! be diffed table by table to see what a change to the source did to the
+//! build, ignoring the checksum and timestamps that change every time.
+
+use crate::data::sfnt;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use write_fonts::read::{FontRef, TableProvider};
+use write_fonts::types::Tag;
+
+/// Name of the pseudo-table listing the glyph names in glyph ID order
+const GLYPH_ORDER: &str = "GlyphOrder";
+
+/// Seconds from 1904-01-01, the epoch of `head` dates, to 1970-01-01
+const MAC_EPOCH_OFFSET: i64 = 2_082_844_800;
+
+/// Bytes of hex data on a line
+const HEX_LINE_BYTES: usize = 16;
+
+/// Fields that change with every build, left out of diffs
+const VOLATILE_FIELDS: [&str; 3] = ["<checkSumAdjustment ", "<created ", "<modified "];
+
+/// How a field of a table is stored and written
+#[derive(Clone, Copy)]
+enum Field {
+    U16,
+    I16,
+    U32,
+    /// 16.16 fixed point number
+    Fixed,
+    /// Version number or magic number, written in hex
+    Hex32,
+    /// Bit flags, written in binary a byte at a time
+    Flags16,
+    Flags32,
+    /// Seconds since 1904-01-01
+    DateTime,
+    Tag,
+    /// Bytes written as decimal numbers
+    Bytes(usize),
+}
+
+const HEAD_FIELDS: &[(&str, Field)] = &[
+    ("tableVersion", Field::Fixed),
+    ("fontRevision", Field::Fixed),
+    ("checkSumAdjustment", Field::Hex32),
+    ("magicNumber", Field::Hex32),
+    ("flags", Field::Flags16),
+    ("unitsPerEm", Field::U16),
+    ("created", Field::DateTime),
+    ("modified", Field::DateTime),
+    ("xMin", Field::I16),
+    ("yMin", Field::I16),
+    ("xMax", Field::I16),
+    ("yMax", Field::I16),
+    ("macStyle", Field::Flags16),
+    ("lowestRecPPEM", Field::U16),
+    ("fontDirectionHint", Field::I16),
+    ("indexToLocFormat", Field::I16),
+    ("glyphDataFormat", Field::I16),
+];
+
+const HHEA_FIELDS: &[(&str, Field)] = &[
+    ("tableVersion", Field::Hex32),
+    ("ascent", Field::I16),
+    ("descent", Field::I16),
+    ("lineGap", Field::I16),
+    ("advanceWidthMax", Field::U16),
+    ("minLeftSideBearing", Field::I16),
+    ("minRightSideBearing", Field::I16),
+    ("xMaxExtent", Field::I16),
+    ("caretSlopeRise", Field::I16),
+    ("caretSlopeRun", Field::I16),
+    ("caretOffset", Field::I16),
+    ("reserved0", Field::I16),
+    ("reserved1", Field::I16),
+    ("reserved2", Field::I16),
+    ("reserved3", Field::I16),
+    ("metricDataFormat", Field::I16),
+    ("numberOfHMetrics", Field::U16),
+];
+
+/// Fields of `maxp`; version 0.5 stops after `numGlyphs`
+const MAXP_FIELDS: &[(&str, Field)] = &[
+    ("tableVersion", Field::Hex32),
+    ("numGlyphs", Field::U16),
+    ("maxPoints", Field::U16),
+    ("maxContours", Field::U16),
+    ("maxCompositePoints", Field::U16),
+    ("maxCompositeContours", Field::U16),
+    ("maxZones", Field::U16),
+    ("maxTwilightPoints", Field::U16),
+    ("maxStorage", Field::U16),
+    ("maxFunctionDefs", Field::U16),
+    ("maxInstructionDefs", Field::U16),
+    ("maxStackElements", Field::U16),
+    ("maxSizeOfInstructions", Field::U16),
+    ("maxComponentElements", Field::U16),
+    ("maxComponentDepth", Field::U16),
+];
+
+/// Fields of `OS/2`; earlier versions stop where their table ends
+const OS2_FIELDS: &[(&str, Field)] = &[
+    ("version", Field::U16),
+    ("xAvgCharWidth", Field::I16),
+    ("usWeightClass", Field::U16),
+    ("usWidthClass", Field::U16),
+    ("fsType", Field::Flags16),
+    ("ySubscriptXSize", Field::I16),
+    ("ySubscriptYSize", Field::I16),
+    ("ySubscriptXOffset", Field::I16),
+    ("ySubscriptYOffset", Field::I16),
+    ("ySuperscriptXSize", Field::I16),
+    ("ySuperscriptYSize", Field::I16),
+    ("ySuperscriptXOffset", Field::I16),
+    ("ySuperscriptYOffset", Field::I16),
+    ("yStrikeoutSize", Field::I16),
+    ("yStrikeoutPosition", Field::I16),
+    ("sFamilyClass", Field::I16),
+    ("panose", Field::Bytes(10)),
+    ("ulUnicodeRange1", Field::Flags32),
+    ("ulUnicodeRange2", Field::Flags32),
+    ("ulUnicodeRange3", Field::Flags32),
+    ("ulUnicodeRange4", Field::Flags32),
+    ("achVendID", Field::Tag),
+    ("fsSelection", Field::Flags16),
+    ("usFirstCharIndex", Field::U16),
+    ("usLastCharIndex", Field::U16),
+    ("sTypoAscender", Field::I16),
+    ("sTypoDescender", Field::I16),
+    ("sTypoLineGap", Field::I16),
+    ("usWinAscent", Field::U16),
+    ("usWinDescent", Field::U16),
+    ("ulCodePageRange1", Field::Flags32),
+    ("ulCodePageRange2", Field::Flags32),
+    ("sxHeight", Field::I16),
+    ("sCapHeight", Field::I16),
+    ("usDefaultChar", Field::U16),
+    ("usBreakChar", Field::U16),
+    ("usMaxContext", Field::U16),
+    ("usLowerOpticalPointSize", Field::U16),
+    ("usUpperOpticalPointSize", Field::U16),
+];
+
+/// Header fields of `post`; glyph names are in `GlyphOrder`
+const POST_FIELDS: &[(&str, Field)] = &[
+    ("formatType", Field::Fixed),
+    ("italicAngle", Field::Fixed),
+    ("underlinePosition", Field::I16),
+    ("underlineThickness", Field::I16),
+    ("isFixedPitch", Field::U32),
+    ("minMemType42", Field::U32),
+    ("maxMemType42", Field::U32),
+    ("minMemType1", Field::U32),
+    ("maxMemType1", Field::U32),
+];
+
+/// One table of a dump, as an indented XML element
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableDump {
+    /// Tag of the table, or `GlyphOrder`
+    pub tag: String,
+    pub xml: String,
+}
+
+/// Tables of a compiled font dumped to XML
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FontDump {
+    pub sfnt_version: u32,
+    /// Whether every table was dumped, rather than a selection
+    pub complete: bool,
+    pub tables: Vec<TableDump>,
+}
+
+/// How a table differs from the same table in an earlier dump
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TableChangeKind {
+    Added,
+    Removed,
+    /// Number of XML lines only in one of the dumps
+    Changed {
+        lines: usize,
+    },
+}
+
+/// A table that differs from an earlier dump
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableChange {
+    pub tag: String,
+    pub kind: TableChangeKind,
+}
+
+impl std::fmt::Display for TableChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            TableChangeKind::Added => write!(f, "{}: added", self.tag),
+            TableChangeKind::Removed => write!(f, "{}: removed", self.tag),
+            TableChangeKind::Changed { lines } => {
+                write!(f, "{}: {lines} lines differ", self.tag)
+            }
+        }
+    }
+}
+
+impl FontDump {
+    /// Dumps the tables of a compiled font, every table if `tags` is empty
+    ///
+    /// Tags are matched with or without trailing spaces and also by their
+    /// ttx element name, so `OS/2`, `OS_2` and `CFF` all work.
+    pub fn new(data: &[u8], tags: &[String]) -> Result<Self> {
+        let font = sfnt::read_font(data)?;
+        let raw_tables = font
+            .table_directory()
+            .table_records()
+            .iter()
+            .map(|record| {
+                let tag = record.tag();
+                let table = font
+                    .table_data(tag)
+                    .with_context(|| format!("Table '{tag}' is out of bounds"))?;
+                Ok((tag, table.as_bytes()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut available = vec![GLYPH_ORDER.to_string()];
+        available.extend(raw_tables.iter().map(|(tag, _)| tag.to_string()));
+
+        let selected: Vec<String> = if tags.is_empty() {
+            available.clone()
+        } else {
+            let mut selected = Vec::new();
+            for tag in tags {
+                let Some(found) = available
+                    .iter()
+                    .find(|name| name.trim_end() == tag.trim_end() || element_name(name) == *tag)
+                else {
+                    bail!(
+                        "The font has no '{}' table\nAvailable tables: {}",
+                        tag,
+                        available.join(", ")
+                    );
+                };
+                if !selected.contains(found) {
+                    selected.push(found.clone());
+                }
+            }
+            selected
+        };
+
+        let glyph_names = glyph_order(&font);
+        let tables = selected
+            .iter()
+            .map(|tag| {
+                let xml = match raw_tables.iter().find(|(t, _)| t.to_string() == *tag) {
+                    Some((raw_tag, table)) => dump_table(&font, *raw_tag, table, &glyph_names),
+                    None => dump_glyph_order(&glyph_names),
+                };
+                TableDump {
+                    tag: tag.clone(),
+                    xml,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            sfnt_version: font.table_directory().sfnt_version(),
+            complete: tags.is_empty(),
+            tables,
+        })
+    }
+
+    /// The dump as a ttx-like XML document
+    pub fn to_ttx(&self) -> String {
+        let version = match &self.sfnt_version.to_be_bytes() {
+            b"OTTO" => "OTTO".to_string(),
+            bytes => bytes.iter().map(|b| format!("\\x{b:02x}")).collect(),
+        };
+        let mut ttx = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            ttx,
+            "<ttFont sfntVersion=\"{version}\" ttLibVersion=\"bezy\">"
+        );
+        for table in &self.tables {
+            ttx.push('\n');
+            ttx.push_str(&table.xml);
+        }
+        ttx.push_str("\n</ttFont>\n");
+        ttx
+    }
+
+    /// Tables that differ from the same font's dump in `previous`
+    ///
+    /// Tables left out of a selective dump are neither added nor removed.
+    pub fn diff(&self, previous: &FontDump) -> Vec<TableChange> {
+        let mut changes = Vec::new();
+        for table in &self.tables {
+            match previous.tables.iter().find(|t| t.tag == table.tag) {
+                Some(earlier) => {
+                    let lines = differing_lines(&earlier.xml, &table.xml);
+                    if lines > 0 {
+                        changes.push(TableChange {
+                            tag: table.tag.clone(),
+                            kind: TableChangeKind::Changed { lines },
+                        });
+                    }
+                }
+                None if previous.complete => changes.push(TableChange {
+                    tag: table.tag.clone(),
+                    kind: TableChangeKind::Added,
+                }),
+                None => {}
+            }
+        }
+        if self.complete {
+            for earlier in &previous.tables {
+                if !self.tables.iter().any(|t| t.tag == earlier.tag) {
+                    changes.push(TableChange {
+                        tag: earlier.tag.clone(),
+                        kind: TableChangeKind::Removed,
+                    });
+                }
+            }
+        }
+        changes
+    }
+}
+
+/// Number of lines in only one of two dumps of a table, in any order,
+/// leaving out the fields that change with every build
+fn differing_lines(earlier: &str, current: &str) -> usize {
+    let stable = |xml: &str| {
+        xml.lines()
+            .map(str::trim)
+            .filter(|line| !VOLATILE_FIELDS.iter().any(|field| line.starts_with(field)))
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+    let mut counts: BTreeMap<String, i64> = BTreeMap::new();
+    for line in stable(earlier) {
+        *counts.entry(line).or_default() -= 1;
+    }
+    for line in stable(current) {
+        *counts.entry(line).or_default() += 1;
+    }
+    counts
+        .values()
+        .map(|count| count.unsigned_abs() as usize)
+        .sum()
+}
+
+/// The XML element ttx writes a table as: `OS/2` as `OS_2`, `CFF ` as `CFF`
+fn element_name(tag: &str) -> String {
+    tag.trim_end().replace('/', "_")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Glyph names from `post`, or ttx's made-up names when it has none
+fn glyph_order(font: &FontRef) -> Vec<String> {
+    if let Ok(names) = sfnt::glyph_names(font) {
+        return names;
+    }
+    let count = sfnt::num_glyphs(font).unwrap_or(0);
+    (0..count)
+        .map(|gid| match gid {
+            0 => ".notdef".to_string(),
+            _ => format!("glyph{gid:05}"),
+        })
+        .collect()
+}
+
+fn glyph_name(names: &[String], gid: u16) -> String {
+    names
+        .get(gid as usize)
+        .cloned()
+        .unwrap_or_else(|| format!("glyph{gid:05}"))
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// A 16.16 fixed point number the way Python prints floats
+fn format_fixed(value: u32) -> String {
+    let value = value as i32 as f64 / 65536.0;
+    format!("{:?}", (value * 100_000.0).round() / 100_000.0)
+}
+
+/// Flags in binary, a space between bytes
+fn format_flags(value: u32, bytes: usize) -> String {
+    (0..bytes)
+        .rev()
+        .map(|byte| format!("{:08b}", (value >> (byte * 8)) & 0xFF))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A `head` date, like `Mon Jan  1 00:00:00 2024`
+fn format_date(seconds: i64) -> String {
+    chrono::DateTime::from_timestamp(seconds - MAC_EPOCH_OFFSET, 0)
+        .map(|date| date.format("%a %b %e %H:%M:%S %Y").to_string())
+        .unwrap_or_else(|| seconds.to_string())
+}
+
+/// `<field value="..."/>` lines for the fields the table is long enough for
+fn dump_fields(xml: &mut String, data: &[u8], fields: &[(&str, Field)]) {
+    let mut offset = 0;
+    for (name, field) in fields {
+        let (size, value) = match field {
+            Field::U16 => (2, u16_at(data, offset).map(|v| v.to_string())),
+            Field::I16 => (2, u16_at(data, offset).map(|v| (v as i16).to_string())),
+            Field::U32 => (4, u32_at(data, offset).map(|v| v.to_string())),
+            Field::Fixed => (4, u32_at(data, offset).map(format_fixed)),
+            Field::Hex32 => (4, u32_at(data, offset).map(|v| format!("{v:#010x}"))),
+            Field::Flags16 => (2, u16_at(data, offset).map(|v| format_flags(v as u32, 2))),
+            Field::Flags32 => (4, u32_at(data, offset).map(|v| format_flags(v, 4))),
+            Field::DateTime => (
+                8,
+                u32_at(data, offset)
+                    .zip(u32_at(data, offset + 4))
+                    .map(|(high, low)| format_date(((high as i64) << 32) | low as i64)),
+            ),
+            Field::Tag => (
+                4,
+                data.get(offset..offset + 4)
+                    .map(|tag| String::from_utf8_lossy(tag).into_owned()),
+            ),
+            Field::Bytes(count) => (
+                *count,
+                data.get(offset..offset + count).map(|bytes| {
+                    bytes
+                        .iter()
+                        .map(|b| b.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                }),
+            ),
+        };
+        let Some(value) = value else {
+            break;
+        };
+        let _ = writeln!(xml, "    <{name} value=\"{}\"/>", escape(&value));
+        offset += size;
+    }
+}
+
+fn dump_glyph_order(glyph_names: &[String]) -> String {
+    let mut xml = format!("  <{GLYPH_ORDER}>\n");
+    for (gid, name) in glyph_names.iter().enumerate() {
+        let _ = writeln!(xml, "    <GlyphID id=\"{gid}\" name=\"{}\"/>", escape(name));
+    }
+    let _ = writeln!(xml, "  </{GLYPH_ORDER}>");
+    xml
+}
+
+fn dump_table(font: &FontRef, tag: Tag, data: &[u8], glyph_names: &[String]) -> String {
+    let element = element_name(&tag.to_string());
+    let mut xml = format!("  <{element}>\n");
+    match &tag.to_be_bytes() {
+        b"head" => dump_fields(&mut xml, data, HEAD_FIELDS),
+        b"hhea" => dump_fields(&mut xml, data, HHEA_FIELDS),
+        b"maxp" => dump_fields(&mut xml, data, MAXP_FIELDS),
+        b"OS/2" => dump_fields(&mut xml, data, OS2_FIELDS),
+        b"post" => dump_fields(&mut xml, data, POST_FIELDS),
+        b"hmtx" => dump_hmtx(&mut xml, font, data, glyph_names),
+        b"cmap" => dump_cmap(&mut xml, data, glyph_names),
+        b"name" => dump_name(&mut xml, data),
+        _ => dump_hex(&mut xml, data, 2),
+    }
+    let _ = writeln!(xml, "  </{element}>");
+    xml
+}
+
+/// `<mtx>` lines in glyph ID order; glyphs past the last full metric
+/// record share its advance
+fn dump_hmtx(xml: &mut String, font: &FontRef, data: &[u8], glyph_names: &[String]) {
+    let num_metrics = font
+        .hhea()
+        .map(|hhea| hhea.number_of_h_metrics())
+        .unwrap_or(0) as usize;
+    if num_metrics == 0 {
+        dump_hex(xml, data, 2);
+        return;
+    }
+    for (gid, name) in glyph_names.iter().enumerate() {
+        let width = u16_at(data, gid.min(num_metrics - 1) * 4);
+        let lsb_offset = match gid.checked_sub(num_metrics) {
+            None => gid * 4 + 2,
+            Some(extra) => num_metrics * 4 + extra * 2,
+        };
+        let (Some(width), Some(lsb)) = (width, u16_at(data, lsb_offset)) else {
+            break;
+        };
+        let _ = writeln!(
+            xml,
+            "    <mtx name=\"{}\" width=\"{width}\" lsb=\"{}\"/>",
+            escape(name),
+            lsb as i16
+        );
+    }
+}
+
+/// Format 4 and 12 subtables as `<map>` lines, others as hex data
+fn dump_cmap(xml: &mut String, data: &[u8], glyph_names: &[String]) {
+    let _ = writeln!(xml, "    <tableVersion version=\"0\"/>");
+    let num_tables = u16_at(data, 2).unwrap_or(0) as usize;
+    for index in 0..num_tables {
+        let record = 4 + index * 8;
+        let (Some(platform), Some(encoding), Some(offset)) = (
+            u16_at(data, record),
+            u16_at(data, record + 2),
+            u32_at(data, record + 4),
+        ) else {
+            break;
+        };
+        let subtable = data.get(offset as usize..).unwrap_or_default();
+        let format = u16_at(subtable, 0).unwrap_or(0);
+        let mappings = match format {
+            4 => cmap_format_4(subtable),
+            12 => cmap_format_12(subtable),
+            _ => None,
+        };
+        let _ = writeln!(
+            xml,
+            "    <cmap_format_{format} platformID=\"{platform}\" platEncID=\"{encoding}\">"
+        );
+        match mappings {
+            Some(mappings) => {
+                for (code, gid) in mappings {
+                    let name = escape(&glyph_name(glyph_names, gid));
+                    let _ = writeln!(xml, "      <map code=\"{code:#x}\" name=\"{name}\"/>");
+                }
+            }
+            None => dump_hex(xml, subtable, 3),
+        }
+        let _ = writeln!(xml, "    </cmap_format_{format}>");
+    }
+}
+
+/// Codepoints and glyph IDs of a format 4 subtable
+fn cmap_format_4(data: &[u8]) -> Option<Vec<(u32, u16)>> {
+    let segments = u16_at(data, 6)? as usize / 2;
+    let end_codes = 14;
+    let start_codes = end_codes + segments * 2 + 2;
+    let deltas = start_codes + segments * 2;
+    let range_offsets = deltas + segments * 2;
+    let mut mappings = Vec::new();
+    for segment in 0..segments {
+        let end = u16_at(data, end_codes + segment * 2)?;
+        let start = u16_at(data, start_codes + segment * 2)?;
+        let delta = u16_at(data, deltas + segment * 2)?;
+        let range_offset_at = range_offsets + segment * 2;
+        let range_offset = u16_at(data, range_offset_at)? as usize;
+        if start == 0xFFFF {
+            continue;
+        }
+        for code in start..=end {
+            let gid = if range_offset == 0 {
+                code.wrapping_add(delta)
+            } else {
+                let at = range_offset_at + range_offset + (code - start) as usize * 2;
+                match u16_at(data, at)? {
+                    0 => 0,
+                    gid => gid.wrapping_add(delta),
+                }
+            };
+            if gid != 0 {
+                mappings.push((code as u32, gid));
+            }
+        }
+    }
+    Some(mappings)
+}
+
+/// Codepoints and glyph IDs of a format 12 subtable
+fn cmap_format_12(data: &[u8]) -> Option<Vec<(u32, u16)>> {
+    let groups = u32_at(data, 12)? as usize;
+    let mut mappings = Vec::new();
+    for group in 0..groups {
+        let record = 16 + group * 12;
+        let start = u32_at(data, record)?;
+        let end = u32_at(data, record + 4)?;
+        let first_gid = u32_at(data, record + 8)?;
+        for code in start..=end.min(0x10FFFF) {
+            mappings.push((code, (first_gid + code - start) as u16));
+        }
+    }
+    Some(mappings)
+}
+
+/// `<namerecord>` elements with the decoded strings
+fn dump_name(xml: &mut String, data: &[u8]) {
+    let count = u16_at(data, 2).unwrap_or(0) as usize;
+    let storage = u16_at(data, 4).unwrap_or(0) as usize;
+    for index in 0..count {
+        let record = 6 + index * 12;
+        let fields: Option<Vec<u16>> = (0..6).map(|i| u16_at(data, record + i * 2)).collect();
+        let Some([platform, encoding, language, name_id, length, offset]) =
+            fields.and_then(|fields| <[u16; 6]>::try_from(fields).ok())
+        else {
+            break;
+        };
+        let start = storage + offset as usize;
+        let bytes = data.get(start..start + length as usize).unwrap_or_default();
+        let text = match platform {
+            0 | 3 => {
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+                    .collect();
+                String::from_utf16_lossy(&units)
+            }
+            _ => bytes.iter().map(|&b| b as char).collect(),
+        };
+        let _ = writeln!(
+            xml,
+            "    <namerecord nameID=\"{name_id}\" platformID=\"{platform}\" \
+             platEncID=\"{encoding}\" langID=\"{language:#x}\">\n      {}\n    </namerecord>",
+            escape(&text)
+        );
+    }
+}
+
+/// `<hexdata>` ttx-style, `depth` levels in: words of four bytes, lines of
+/// sixteen
+fn dump_hex(xml: &mut String, data: &[u8], depth: usize) {
+    let indent = "  ".repeat(depth);
+    let _ = writeln!(xml, "{indent}<hexdata>");
+    for line in data.chunks(HEX_LINE_BYTES) {
+        let words: Vec<String> = line
+            .chunks(4)
+            .map(|word| word.iter().map(|b| format!("{b:02x}")).collect())
+            .collect();
+        let _ = writeln!(xml, "{indent}  {}", words.join(" "));
+    }
+    let _ = writeln!(xml, "{indent}</hexdata>");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::embedded_assets::BEZY_GROTESK_BYTES;
+
+    fn table<'a>(dump: &'a FontDump, tag: &str) -> &'a str {
+        &dump.tables.iter().find(|t| t.tag == tag).unwrap().xml
+    }
+
+    #[test]
+    fn test_field_formats() {
+        assert_eq!(format_fixed(0x0001_0000), "1.0");
+        assert_eq!(format_fixed(0xFFF4_0000), "-12.0");
+        assert_eq!(format_flags(0x000B, 2), "00000000 00001011");
+        assert_eq!(format_date(MAC_EPOCH_OFFSET), "Thu Jan  1 00:00:00 1970");
+        assert_eq!(element_name("OS/2"), "OS_2");
+        assert_eq!(element_name("CFF "), "CFF");
+    }
+
+    #[test]
+    fn test_dump_bundled_font() {
+        let dump = FontDump::new(BEZY_GROTESK_BYTES, &[]).unwrap();
+        assert!(dump.complete);
+        assert_eq!(dump.tables[0].tag, GLYPH_ORDER);
+        assert_eq!(dump.tables.len(), 14);
+        assert!(table(&dump, "head").contains("<unitsPerEm value=\"1024\"/>"));
+        assert!(table(&dump, "cmap").contains("<map code=\"0x41\" name=\"A\"/>"));
+        assert!(table(&dump, "hmtx").contains("<mtx name=\"A\" width="));
+        assert!(dump.to_ttx().starts_with("<?xml"));
+
+        let selected = FontDump::new(BEZY_GROTESK_BYTES, &["OS_2".into(), "head".into()]);
+        let selected = selected.unwrap();
+        assert!(!selected.complete);
+        let tags: Vec<_> = selected.tables.iter().map(|t| t.tag.as_str()).collect();
+        assert_eq!(tags, ["OS/2", "head"]);
+        assert!(FontDump::new(BEZY_GROTESK_BYTES, &["nope".into()]).is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let previous = FontDump::new(BEZY_GROTESK_BYTES, &[]).unwrap();
+        let mut current = previous.clone();
+        assert!(current.diff(&previous).is_empty());
+
+        let head = current.tables.iter_mut().find(|t| t.tag == "head").unwrap();
+        head.xml = head
+            .xml
+            .lines()
+            .map(|line| {
+                if line.trim_start().starts_with("<modified ") {
+                    "    <modified value=\"Mon Jan  1 00:00:00 2024\"/>"
+                } else {
+                    line
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(current.diff(&previous).is_empty());
+
+        current.tables.retain(|t| t.tag != "post");
+        current.tables[1].xml.push_str("    <extra/>\n");
+        let changes = current.diff(&previous);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].kind, TableChangeKind::Changed { lines: 1 });
+        assert_eq!(changes[1].tag, "post");
+        assert_eq!(changes[1].kind, TableChangeKind::Removed);
+
+        let selected = FontDump::new(BEZY_GROTESK_BYTES, &["head".into()]).unwrap();
+        assert!(selected.diff(&previous).is_empty());
+        assert!(previous.diff(&selected).is_empty());
+    }
+}
@@ -1,7 +1,7 @@
 //! UFO file I/O operations
 
-use anyhow::Result;
-use norad::Font;
+use anyhow::{Context, Result};
+use norad::{Font, Glyph};
 use std::path::Path;
 
 /// Load a UFO font file from disk
@@ -10,3 +10,99 @@ pub fn load_ufo_from_path(path: impl AsRef<Path>) -> Result<Font> {
     let font = Font::load(path)?;
     Ok(font)
 }
+
+/// Build a UFO from its files, each given by its path inside the UFO
+///
+/// This is for targets without a file system, like the web build, where
+/// the files come from the browser. Font info, lib, groups, kerning,
+/// features and the glyphs of every layer are read; data and images are
+/// not.
+pub fn load_ufo_from_files(files: &[(String, Vec<u8>)]) -> Result<Font> {
+    let file = |name: &str| {
+        files
+            .iter()
+            .find(|(path, _)| path == name)
+            .map(|(_, bytes)| bytes.as_slice())
+    };
+
+    let mut font = Font::new();
+    if let Some(bytes) = file("fontinfo.plist") {
+        font.font_info = plist::from_bytes(bytes).context("Invalid fontinfo.plist")?;
+    }
+    if let Some(bytes) = file("lib.plist") {
+        font.lib = plist::from_bytes(bytes).context("Invalid lib.plist")?;
+    }
+    if let Some(bytes) = file("groups.plist") {
+        font.groups = plist::from_bytes(bytes).context("Invalid groups.plist")?;
+    }
+    if let Some(bytes) = file("kerning.plist") {
+        font.kerning = plist::from_bytes(bytes).context("Invalid kerning.plist")?;
+    }
+    if let Some(bytes) = file("features.fea") {
+        font.features = String::from_utf8_lossy(bytes).into_owned();
+    }
+
+    // Layer name and directory pairs; the default layer is in "glyphs"
+    let layers: Vec<(String, String)> = match file("layercontents.plist") {
+        Some(bytes) => plist::from_bytes(bytes).context("Invalid layercontents.plist")?,
+        None => vec![("public.default".to_string(), "glyphs".to_string())],
+    };
+    for (name, dir) in &layers {
+        let layer = if dir == "glyphs" {
+            font.layers.default_layer_mut()
+        } else {
+            font.layers
+                .new_layer(name)
+                .with_context(|| format!("Invalid layer name '{name}'"))?
+        };
+        let prefix = format!("{dir}/");
+        for (path, bytes) in files {
+            let Some(file_name) = path.strip_prefix(&prefix) else {
+                continue;
+            };
+            if !file_name.ends_with(".glif") || file_name.contains('/') {
+                continue;
+            }
+            let glyph =
+                Glyph::parse_raw(bytes).with_context(|| format!("Invalid glyph file {path}"))?;
+            layer.insert_glyph(glyph);
+        }
+    }
+    Ok(font)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_ufo_from_files() {
+        let glif = br#"<?xml version="1.0" encoding="UTF-8"?>
+<glyph name="a" format="2">
+  <advance width="500"/>
+  <unicode hex="0061"/>
+</glyph>
+"#;
+        let fontinfo = br#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+  <key>familyName</key>
+  <string>Test Sans</string>
+  <key>unitsPerEm</key>
+  <integer>1000</integer>
+</dict>
+</plist>
+"#;
+        let files = vec![
+            ("fontinfo.plist".to_string(), fontinfo.to_vec()),
+            ("glyphs/a.glif".to_string(), glif.to_vec()),
+            ("glyphs/contents.plist".to_string(), Vec::new()),
+            ("images/a.png".to_string(), Vec::new()),
+        ];
+
+        let font = load_ufo_from_files(&files).unwrap();
+        assert_eq!(font.font_info.family_name.as_deref(), Some("Test Sans"));
+        let glyph = font.default_layer().get_glyph("a").unwrap();
+        assert_eq!(glyph.width, 500.0);
+    }
+}
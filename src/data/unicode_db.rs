@@ -0,0 +1,400 @@
+//! Unicode Character Database
+//!
+//! Names, general categories, scripts and blocks of every character, from
+//! trimmed-down UCD files compiled into the binary (`unicode_names.txt`,
+//! `unicode_scripts.txt` and `unicode_blocks.txt`) and read the first time
+//! they're asked for. The TUI's Unicode tab and the text tool's character
+//! picker browse and search it.
+
+use std::sync::OnceLock;
+
+const NAMES: &str = include_str!("unicode_names.txt");
+const SCRIPTS: &str = include_str!("unicode_scripts.txt");
+const BLOCKS: &str = include_str!("unicode_blocks.txt");
+
+/// Hangul syllable name parts, per the Unicode standard's chapter 3.12
+const HANGUL_FIRST: u32 = 0xAC00;
+const JAMO_L: [&str; 19] = [
+    "G", "GG", "N", "D", "DD", "R", "M", "B", "BB", "S", "SS", "", "J", "JJ", "C", "K", "T", "P",
+    "H",
+];
+const JAMO_V: [&str; 21] = [
+    "A", "AE", "YA", "YAE", "EO", "E", "YEO", "YE", "O", "WA", "WAE", "OE", "YO", "U", "WEO", "WE",
+    "WI", "YU", "EU", "YI", "I",
+];
+const JAMO_T: [&str; 28] = [
+    "", "G", "GG", "GS", "N", "NJ", "NH", "D", "L", "LG", "LM", "LB", "LS", "LT", "LP", "LH", "M",
+    "B", "BS", "S", "SS", "NG", "J", "C", "K", "T", "P", "H",
+];
+
+/// A named range of codepoints, like Basic Latin
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnicodeBlock {
+    pub first: u32,
+    pub last: u32,
+    pub name: &'static str,
+}
+
+impl UnicodeBlock {
+    pub fn contains(&self, c: char) -> bool {
+        (self.first..=self.last).contains(&(c as u32))
+    }
+
+    /// The assigned characters of the block
+    pub fn chars(&self) -> Vec<char> {
+        (self.first..=self.last)
+            .filter_map(char::from_u32)
+            .filter(|c| general_category(*c) != "Cn")
+            .collect()
+    }
+}
+
+/// What the database knows about a character
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CharInfo {
+    pub codepoint: char,
+    /// `None` for private use and unassigned characters
+    pub name: Option<String>,
+    /// Two-letter general category, like `Lu`
+    pub category: &'static str,
+    /// Script name, like `Latin`, or `Unknown`
+    pub script: &'static str,
+    pub block: Option<&'static str>,
+}
+
+impl CharInfo {
+    pub fn new(c: char) -> Self {
+        Self {
+            codepoint: c,
+            name: name(c),
+            category: general_category(c),
+            script: script(c),
+            block: block(c).map(|block| block.name),
+        }
+    }
+}
+
+/// A run of characters named by rule
+struct NamedRange {
+    first: u32,
+    last: u32,
+    category: &'static str,
+    prefix: &'static str,
+}
+
+impl NamedRange {
+    fn name(&self, code: u32) -> String {
+        if self.prefix == "HANGUL SYLLABLE" {
+            let index = (code - HANGUL_FIRST) as usize;
+            let (l, v, t) = (index / 588, index % 588 / 28, index % 28);
+            format!("{} {}{}{}", self.prefix, JAMO_L[l], JAMO_V[v], JAMO_T[t])
+        } else {
+            format!("{}-{:04X}", self.prefix, code)
+        }
+    }
+}
+
+struct Database {
+    /// Characters with a name of their own, by codepoint
+    named: Vec<(u32, &'static str, String)>,
+    ranges: Vec<NamedRange>,
+    scripts: Vec<(u32, u32, &'static str)>,
+    blocks: Vec<UnicodeBlock>,
+}
+
+fn database() -> &'static Database {
+    static DATABASE: OnceLock<Database> = OnceLock::new();
+    DATABASE.get_or_init(|| {
+        let (named, ranges) = parse_names(NAMES);
+        let scripts = parse_ranges(SCRIPTS).collect();
+        let blocks = parse_ranges(BLOCKS)
+            .map(|(first, last, name)| UnicodeBlock { first, last, name })
+            .collect();
+        Database {
+            named,
+            ranges,
+            scripts,
+            blocks,
+        }
+    })
+}
+
+/// Read the names file; see its header for the format
+fn parse_names(text: &'static str) -> (Vec<(u32, &'static str, String)>, Vec<NamedRange>) {
+    let mut named: Vec<(u32, &'static str, String)> = Vec::new();
+    let mut ranges = Vec::new();
+    let mut previous: Option<u32> = None;
+    for line in text.lines().filter(|line| !line.starts_with('#')) {
+        let mut fields = line.splitn(4, ';');
+        let (Some(code), Some(category), Some(third)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if let Some((first, last)) = parse_range(code) {
+            ranges.push(NamedRange {
+                first,
+                last,
+                category,
+                prefix: third,
+            });
+            previous = None;
+            continue;
+        }
+        let code = if code.is_empty() {
+            previous.map_or(0, |previous| previous + 1)
+        } else {
+            u32::from_str_radix(code, 16).unwrap_or_default()
+        };
+        let shared: usize = third.parse().unwrap_or_default();
+        let last_name = named.last().map_or("", |(_, _, name)| name.as_str());
+        let mut name = last_name.get(..shared).unwrap_or_default().to_string();
+        name.push_str(fields.next().unwrap_or_default());
+        named.push((code, category, name));
+        previous = Some(code);
+    }
+    (named, ranges)
+}
+
+/// Read `FIRST..LAST;Value` lines
+fn parse_ranges(text: &'static str) -> impl Iterator<Item = (u32, u32, &'static str)> {
+    text.lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let (range, value) = line.split_once(';')?;
+            let (first, last) = parse_range(range)?;
+            Some((first, last, value.trim()))
+        })
+}
+
+fn parse_range(range: &str) -> Option<(u32, u32)> {
+    let (first, last) = range.split_once("..")?;
+    Some((
+        u32::from_str_radix(first, 16).ok()?,
+        u32::from_str_radix(last, 16).ok()?,
+    ))
+}
+
+/// The character's Unicode name, e.g. `LATIN SMALL LETTER E WITH ACUTE`
+pub fn name(c: char) -> Option<String> {
+    let database = database();
+    let code = c as u32;
+    if let Ok(index) = database
+        .named
+        .binary_search_by_key(&code, |(code, _, _)| *code)
+    {
+        return Some(database.named[index].2.clone());
+    }
+    database
+        .ranges
+        .iter()
+        .find(|range| (range.first..=range.last).contains(&code))
+        .map(|range| range.name(code))
+}
+
+/// The character's two-letter general category: `Co` for private use and
+/// `Cn` for unassigned codepoints
+pub fn general_category(c: char) -> &'static str {
+    let database = database();
+    let code = c as u32;
+    if let Ok(index) = database
+        .named
+        .binary_search_by_key(&code, |(code, _, _)| *code)
+    {
+        return database.named[index].1;
+    }
+    if let Some(range) = database
+        .ranges
+        .iter()
+        .find(|range| (range.first..=range.last).contains(&code))
+    {
+        return range.category;
+    }
+    let private_use =
+        (0xE000..=0xF8FF).contains(&code) || (code >= 0xF0000 && code & 0xFFFF < 0xFFFE);
+    if private_use {
+        "Co"
+    } else {
+        "Cn"
+    }
+}
+
+/// Spelled-out general category, e.g. `Uppercase Letter` for `Lu`
+pub fn category_name(category: &str) -> &'static str {
+    match category {
+        "Lu" => "Uppercase Letter",
+        "Ll" => "Lowercase Letter",
+        "Lt" => "Titlecase Letter",
+        "Lm" => "Modifier Letter",
+        "Lo" => "Other Letter",
+        "Mn" => "Nonspacing Mark",
+        "Mc" => "Spacing Mark",
+        "Me" => "Enclosing Mark",
+        "Nd" => "Decimal Number",
+        "Nl" => "Letter Number",
+        "No" => "Other Number",
+        "Pc" => "Connector Punctuation",
+        "Pd" => "Dash Punctuation",
+        "Ps" => "Open Punctuation",
+        "Pe" => "Close Punctuation",
+        "Pi" => "Initial Punctuation",
+        "Pf" => "Final Punctuation",
+        "Po" => "Other Punctuation",
+        "Sm" => "Math Symbol",
+        "Sc" => "Currency Symbol",
+        "Sk" => "Modifier Symbol",
+        "So" => "Other Symbol",
+        "Zs" => "Space Separator",
+        "Zl" => "Line Separator",
+        "Zp" => "Paragraph Separator",
+        "Cc" => "Control",
+        "Cf" => "Format",
+        "Cs" => "Surrogate",
+        "Co" => "Private Use",
+        _ => "Unassigned",
+    }
+}
+
+/// The character's script, e.g. `Latin`, `Common` or `Unknown`
+pub fn script(c: char) -> &'static str {
+    let scripts = &database().scripts;
+    let code = c as u32;
+    let index = scripts.partition_point(|(first, _, _)| *first <= code);
+    match index.checked_sub(1).map(|index| scripts[index]) {
+        Some((_, last, script)) if code <= last => script,
+        _ => "Unknown",
+    }
+}
+
+/// Every Unicode block, in codepoint order
+pub fn blocks() -> &'static [UnicodeBlock] {
+    &database().blocks
+}
+
+/// The block the character is in
+pub fn block(c: char) -> Option<&'static UnicodeBlock> {
+    blocks().iter().find(|block| block.contains(c))
+}
+
+/// Characters matching a query: the character or `U+XXXX` codepoint typed,
+/// then every character whose name has words starting with each word of
+/// the query, the name typed out in full first and then shortest names
+pub fn search(query: &str, limit: usize) -> Vec<char> {
+    let query = query.trim();
+    let mut found = Vec::new();
+    let mut chars = query.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        found.push(c);
+    }
+    let hex = query
+        .strip_prefix("U+")
+        .or_else(|| query.strip_prefix("u+"))
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .and_then(char::from_u32);
+    found.extend(hex);
+
+    let words: Vec<String> = query.split_whitespace().map(str::to_uppercase).collect();
+    if words.is_empty() {
+        return found;
+    }
+    let matches = |name: &str| {
+        words.iter().all(|word| {
+            name.split([' ', '-'])
+                .any(|name_word| name_word.starts_with(word.as_str()))
+        })
+    };
+    // Exact names first, then shorter names
+    let whole_query = words.join(" ");
+    let rank = |name: &str| (name != whole_query, name.len());
+    let database = database();
+    let mut by_name: Vec<((bool, usize), u32)> = database
+        .named
+        .iter()
+        .filter(|(_, _, name)| matches(name))
+        .map(|(code, _, name)| (rank(name), *code))
+        .collect();
+    for range in &database.ranges {
+        // Only look at each name when the query could be about the range
+        let about_range = words.iter().any(|word| {
+            range
+                .prefix
+                .split(' ')
+                .any(|prefix_word| prefix_word.starts_with(word.as_str()))
+        });
+        if !about_range {
+            continue;
+        }
+        for code in range.first..=range.last {
+            let name = range.name(code);
+            if matches(&name) {
+                by_name.push((rank(&name), code));
+            }
+        }
+    }
+    by_name.sort_unstable();
+    for c in by_name
+        .into_iter()
+        .filter_map(|(_, code)| char::from_u32(code))
+    {
+        if found.len() >= limit {
+            break;
+        }
+        if !found.contains(&c) {
+            found.push(c);
+        }
+    }
+    found.truncate(limit);
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_and_properties() {
+        assert_eq!(name('A').as_deref(), Some("LATIN CAPITAL LETTER A"));
+        assert_eq!(
+            name('é').as_deref(),
+            Some("LATIN SMALL LETTER E WITH ACUTE")
+        );
+        assert_eq!(
+            name('\u{4E00}').as_deref(),
+            Some("CJK UNIFIED IDEOGRAPH-4E00")
+        );
+        assert_eq!(name('한').as_deref(), Some("HANGUL SYLLABLE HAN"));
+        assert_eq!(name('\u{E000}'), None);
+
+        let info = CharInfo::new('ش');
+        assert_eq!(info.name.as_deref(), Some("ARABIC LETTER SHEEN"));
+        assert_eq!(info.category, "Lo");
+        assert_eq!(info.script, "Arabic");
+        assert_eq!(info.block, Some("Arabic"));
+        assert_eq!(general_category('\u{E000}'), "Co");
+        assert_eq!(general_category('\u{0378}'), "Cn");
+        assert_eq!(script('\u{0378}'), "Unknown");
+        assert_eq!(category_name(general_category('1')), "Decimal Number");
+    }
+
+    #[test]
+    fn test_blocks() {
+        let basic_latin = &blocks()[0];
+        assert_eq!(basic_latin.name, "Basic Latin");
+        assert_eq!(basic_latin.chars().len(), 128);
+        assert_eq!(
+            block('ÿ').map(|block| block.name),
+            Some("Latin-1 Supplement")
+        );
+    }
+
+    #[test]
+    fn test_search() {
+        let found = search("latin small e acute", 10);
+        assert_eq!(found[0], 'é');
+        assert!(search("sheen", 50).contains(&'ش'));
+        assert_eq!(search("U+00E9", 5)[0], 'é');
+        assert_eq!(search("é", 5)[0], 'é');
+        assert_eq!(search("hangul syllable han", 5)[0], '한');
+        assert_eq!(search("letter", 7).len(), 7);
+    }
+}
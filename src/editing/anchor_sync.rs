@@ -0,0 +1,116 @@
+//! Composites following anchor moves
+//!
+//! However a glyph's anchors move, the marks sitting on them in composites
+//! move along (see `font_source::anchor_sync`), so accented glyphs stay in
+//! line with their bases. A composite can opt out from the component graph
+//! pane. The composites that moved are logged, and the last ones are listed
+//! in the component graph pane.
+
+use std::collections::HashMap;
+
+use crate::core::state::AppState;
+use crate::editing::font_changes::GlyphEdited;
+use crate::editing::selection::events::AppStateChanged;
+use crate::font_source::AnchorData;
+use bevy::prelude::*;
+
+/// The last glyph whose anchors moved composites, and those composites
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct AnchorSyncReport {
+    pub glyph_name: Option<String>,
+    pub composites: Vec<String>,
+}
+
+/// The anchors of every glyph when they were last looked at
+#[derive(Default)]
+struct AnchorSnapshot {
+    anchors: HashMap<String, Vec<AnchorData>>,
+}
+
+pub struct AnchorSyncPlugin;
+
+impl Plugin for AnchorSyncPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AnchorSyncReport>()
+            .add_systems(Update, sync_composites_to_anchors);
+    }
+}
+
+/// Moves the marks of composites after the anchors of edited glyphs moved
+fn sync_composites_to_anchors(
+    mut glyph_edits: ParamSet<(EventReader<GlyphEdited>, EventWriter<GlyphEdited>)>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut report: ResMut<AnchorSyncReport>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut snapshot: Local<AnchorSnapshot>,
+) {
+    let edits: Vec<Option<String>> = glyph_edits
+        .p0()
+        .read()
+        .map(|edit| edit.glyph_name.clone())
+        .collect();
+    let Some(state) = app_state.as_mut() else {
+        return;
+    };
+
+    // Composites are only kept in line on the default layer, which is all
+    // that `glyphs` holds
+    if state.is_added() {
+        snapshot.anchors = state
+            .workspace
+            .font
+            .glyphs
+            .iter()
+            .map(|(name, glyph)| (name.clone(), glyph.anchors.clone()))
+            .collect();
+        return;
+    }
+    if edits.is_empty() {
+        return;
+    }
+
+    let names: Vec<String> = if edits.iter().any(Option::is_none) {
+        state.workspace.font.glyphs.keys().cloned().collect()
+    } else {
+        edits.into_iter().flatten().collect()
+    };
+    let mut moved = Vec::new();
+    for name in names {
+        let Some(glyph) = state.workspace.font.glyphs.get(&name) else {
+            snapshot.anchors.remove(&name);
+            continue;
+        };
+        match snapshot.anchors.insert(name.clone(), glyph.anchors.clone()) {
+            Some(previous) if previous != glyph.anchors => moved.push((name, previous)),
+            _ => {}
+        }
+    }
+
+    for (glyph_name, previous) in moved {
+        let composites = state.workspace.font.follow_anchors(&glyph_name, &previous);
+        if composites.is_empty() {
+            continue;
+        }
+        for composite in &composites {
+            if let Some(glyph) = state.workspace.font.glyphs.get(composite) {
+                snapshot
+                    .anchors
+                    .insert(composite.clone(), glyph.anchors.clone());
+            }
+        }
+        info!(
+            "Moved the marks of {} composites with the anchors of '{}': {}",
+            composites.len(),
+            glyph_name,
+            composites.join(", ")
+        );
+        glyph_edits
+            .p1()
+            .write_batch(composites.iter().cloned().map(GlyphEdited::glyph));
+        app_state_changed.write(AppStateChanged);
+        *report = AnchorSyncReport {
+            glyph_name: Some(glyph_name),
+            composites,
+        };
+    }
+}
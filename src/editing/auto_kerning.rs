@@ -0,0 +1,200 @@
+//! Kerning assistant
+//!
+//! Cmd/Ctrl+Shift+J in the text tool (or `AutoKerningPreviewEvent`)
+//! measures every pair of the active buffer's foreground glyphs, whichever
+//! layer is being edited, and keeps the suggested values, one per pair of
+//! kerning classes, in `AutoKerning::suggestions` for review. Suggested
+//! values can be changed with `EditKernEvent` before `AcceptKernEvent`
+//! writes them to the font's kerning; `RejectKernEvent` drops them without
+//! touching the font. The glyph pairs of suggestions whose outlines collide
+//! can be proofed with a `KernProofRequest::Pairs` from
+//! `AutoKerning::collision_pairs`.
+
+use crate::core::state::text_editor::text_buffer::{ActiveTextBuffer, TextBuffer};
+use crate::core::state::{AppState, TextEditorState};
+use crate::editing::font_changes::KerningChanged;
+use crate::editing::selection::events::AppStateChanged;
+use crate::font_source::auto_kerning::{self, KernSuggestion, KerningParameters};
+use crate::io::shortcuts::Shortcut;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+/// Measuring parameters and the suggestions awaiting review
+#[derive(Resource, Default)]
+pub struct AutoKerning {
+    pub parameters: KerningParameters,
+    pub suggestions: Vec<KernSuggestion>,
+}
+
+impl AutoKerning {
+    /// Glyph pairs measured for the suggestions whose outlines collide, in
+    /// the order of the suggestions and without repeats
+    pub fn collision_pairs(&self) -> Vec<(String, String)> {
+        let mut seen = HashSet::new();
+        self.suggestions
+            .iter()
+            .filter(|suggestion| suggestion.collides)
+            .flat_map(|suggestion| &suggestion.pairs)
+            .filter(|pair| seen.insert(*pair))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Suggest kerning for every pair of these glyphs
+#[derive(Event, Clone)]
+pub struct AutoKerningPreviewEvent(pub Vec<String>);
+
+/// Write the suggestion for a pair of sides to the font, or all of them
+/// with `None`
+#[derive(Event, Clone)]
+pub struct AcceptKernEvent(pub Option<(String, String)>);
+
+/// Drop the suggestion for a pair of sides, or all of them with `None`
+#[derive(Event, Clone)]
+pub struct RejectKernEvent(pub Option<(String, String)>);
+
+/// Change the suggested value for a pair of sides by `delta` units
+#[derive(Event, Clone)]
+pub struct EditKernEvent {
+    pub first: String,
+    pub second: String,
+    pub delta: f64,
+}
+
+pub struct AutoKerningPlugin;
+
+impl Plugin for AutoKerningPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AutoKerning>()
+            .add_event::<AutoKerningPreviewEvent>()
+            .add_event::<AcceptKernEvent>()
+            .add_event::<RejectKernEvent>()
+            .add_event::<EditKernEvent>()
+            .add_systems(
+                Update,
+                (handle_auto_kerning_shortcut, handle_auto_kerning_events).chain(),
+            );
+    }
+}
+
+/// Whether a suggestion is for the given sides, or `None` for all
+fn matches_sides(suggestion: &KernSuggestion, sides: &Option<(String, String)>) -> bool {
+    sides
+        .as_ref()
+        .is_none_or(|(first, second)| suggestion.first == *first && suggestion.second == *second)
+}
+
+/// Measure the active buffer's glyphs with Cmd/Ctrl+Shift+J
+fn handle_auto_kerning_shortcut(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_tool: Res<CurrentTool>,
+    text_editor_state: Res<TextEditorState>,
+    active_buffer: Res<ActiveTextBuffer>,
+    buffer_query: Query<&TextBuffer>,
+    mut preview_events: EventWriter<AutoKerningPreviewEvent>,
+) {
+    if current_tool.get_current() != Some("text") {
+        return;
+    }
+    if !Shortcut::AutoKerning.just_pressed(&keyboard) {
+        return;
+    }
+    let Some(buffer) = active_buffer
+        .buffer_entity
+        .and_then(|entity| buffer_query.get(entity).ok())
+    else {
+        return;
+    };
+    let mut glyph_names: Vec<String> = Vec::new();
+    for sort in text_editor_state.buffer.iter() {
+        if sort.buffer_id != Some(buffer.id) || !sort.kind.is_glyph() {
+            continue;
+        }
+        let name = sort.kind.glyph_name();
+        if !glyph_names.iter().any(|existing| existing == name) {
+            glyph_names.push(name.to_string());
+        }
+    }
+    preview_events.write(AutoKerningPreviewEvent(glyph_names));
+}
+
+fn handle_auto_kerning_events(
+    mut preview_events: EventReader<AutoKerningPreviewEvent>,
+    mut edit_events: EventReader<EditKernEvent>,
+    mut accept_events: EventReader<AcceptKernEvent>,
+    mut reject_events: EventReader<RejectKernEvent>,
+    mut kerning: ResMut<AutoKerning>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut kerning_changes: EventWriter<KerningChanged>,
+) {
+    let Some(state) = app_state.as_mut() else {
+        preview_events.clear();
+        edit_events.clear();
+        accept_events.clear();
+        reject_events.clear();
+        return;
+    };
+
+    for AutoKerningPreviewEvent(glyph_names) in preview_events.read() {
+        let workspace = &state.workspace;
+        match auto_kerning::suggest(
+            &workspace.font,
+            &workspace.info.metrics,
+            &kerning.parameters,
+            glyph_names,
+        ) {
+            Ok(suggestions) => {
+                info!(
+                    "Kerning assistant suggests {} value(s), {} for collisions",
+                    suggestions.len(),
+                    suggestions.iter().filter(|s| s.collides).count()
+                );
+                kerning.suggestions = suggestions;
+            }
+            Err(e) => warn!("Kerning assistant: {:#}", e),
+        }
+    }
+
+    for edit in edit_events.read() {
+        let sides = Some((edit.first.clone(), edit.second.clone()));
+        for suggestion in kerning.suggestions.iter_mut() {
+            if matches_sides(suggestion, &sides) {
+                suggestion.suggested += edit.delta;
+            }
+        }
+    }
+
+    for RejectKernEvent(sides) in reject_events.read() {
+        kerning
+            .suggestions
+            .retain(|suggestion| !matches_sides(suggestion, sides));
+    }
+
+    let mut accepted = 0;
+    for AcceptKernEvent(sides) in accept_events.read() {
+        let (accept, keep): (Vec<_>, Vec<_>) = std::mem::take(&mut kerning.suggestions)
+            .into_iter()
+            .partition(|suggestion| matches_sides(suggestion, sides));
+        kerning.suggestions = keep;
+        for suggestion in accept {
+            debug!(
+                "Kerning {} {} = {}",
+                suggestion.first, suggestion.second, suggestion.suggested
+            );
+            state.workspace.font.kerning.set_value(
+                &suggestion.first,
+                &suggestion.second,
+                suggestion.suggested,
+            );
+            accepted += 1;
+        }
+    }
+    if accepted > 0 {
+        info!("Kerning assistant wrote {} value(s)", accepted);
+        app_state_changed.write(AppStateChanged);
+        kerning_changes.write(KerningChanged);
+    }
+}
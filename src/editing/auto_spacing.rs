@@ -0,0 +1,191 @@
+//! Auto-spacing assistant
+//!
+//! Cmd/Ctrl+Shift+P in the text tool (or `AutoSpacingPreviewEvent`)
+//! suggests sidebearings for the glyphs of the active buffer and keeps them
+//! in `AutoSpacing::preview` for review. `AcceptSpacingEvent` applies one
+//! suggestion or all of them, and `DiscardSpacingEvent` drops them from
+//! the preview without touching the font. Spacing is measured on and
+//! applied to the foreground, whichever layer is being edited.
+
+use crate::core::state::text_editor::text_buffer::{ActiveTextBuffer, TextBuffer};
+use crate::core::state::{AppState, SortKind, TextEditorState};
+use crate::editing::font_changes::GlyphEdited;
+use crate::editing::selection::events::AppStateChanged;
+use crate::font_source::auto_spacing::{self, AutoSpacingConfig, SpacingSuggestion};
+use crate::io::shortcuts::Shortcut;
+use crate::systems::sorts::{adjust_sidebearing, Sidebearing};
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use bevy::prelude::*;
+
+/// Spacing parameters and the suggestions awaiting review
+#[derive(Resource, Default)]
+pub struct AutoSpacing {
+    pub config: AutoSpacingConfig,
+    pub preview: Vec<SpacingSuggestion>,
+}
+
+/// Suggest sidebearings for these glyphs
+#[derive(Event, Clone)]
+pub struct AutoSpacingPreviewEvent(pub Vec<String>);
+
+/// Apply the previewed suggestion for a glyph, or all of them with `None`
+#[derive(Event, Clone)]
+pub struct AcceptSpacingEvent(pub Option<String>);
+
+/// Drop a glyph's suggestion from the preview, or all of them with `None`
+#[derive(Event, Clone)]
+pub struct DiscardSpacingEvent(pub Option<String>);
+
+pub struct AutoSpacingPlugin;
+
+impl Plugin for AutoSpacingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AutoSpacing>()
+            .add_event::<AutoSpacingPreviewEvent>()
+            .add_event::<AcceptSpacingEvent>()
+            .add_event::<DiscardSpacingEvent>()
+            .add_systems(
+                Update,
+                (handle_auto_spacing_shortcut, handle_auto_spacing_events).chain(),
+            );
+    }
+}
+
+/// Preview the active buffer's glyphs with Cmd/Ctrl+Shift+P
+fn handle_auto_spacing_shortcut(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_tool: Res<CurrentTool>,
+    text_editor_state: Res<TextEditorState>,
+    active_buffer: Res<ActiveTextBuffer>,
+    buffer_query: Query<&TextBuffer>,
+    mut preview_events: EventWriter<AutoSpacingPreviewEvent>,
+) {
+    if current_tool.get_current() != Some("text") {
+        return;
+    }
+    if !Shortcut::AutoSpacing.just_pressed(&keyboard) {
+        return;
+    }
+    let Some(buffer) = active_buffer
+        .buffer_entity
+        .and_then(|entity| buffer_query.get(entity).ok())
+    else {
+        return;
+    };
+    let mut glyph_names: Vec<String> = Vec::new();
+    for sort in text_editor_state.buffer.iter() {
+        if sort.buffer_id != Some(buffer.id) || !sort.kind.is_glyph() {
+            continue;
+        }
+        let name = sort.kind.glyph_name();
+        if !glyph_names.iter().any(|existing| existing == name) {
+            glyph_names.push(name.to_string());
+        }
+    }
+    preview_events.write(AutoSpacingPreviewEvent(glyph_names));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_auto_spacing_events(
+    mut preview_events: EventReader<AutoSpacingPreviewEvent>,
+    mut accept_events: EventReader<AcceptSpacingEvent>,
+    mut discard_events: EventReader<DiscardSpacingEvent>,
+    mut spacing: ResMut<AutoSpacing>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut text_editor_state: Option<ResMut<TextEditorState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut glyph_edits: EventWriter<GlyphEdited>,
+) {
+    let Some(state) = app_state.as_mut() else {
+        preview_events.clear();
+        accept_events.clear();
+        discard_events.clear();
+        return;
+    };
+
+    for AutoSpacingPreviewEvent(glyph_names) in preview_events.read() {
+        let workspace = &state.workspace;
+        let preview: Vec<SpacingSuggestion> = auto_spacing::suggest(
+            &workspace.font,
+            &workspace.info.metrics,
+            &spacing.config,
+            glyph_names,
+        )
+        .into_iter()
+        .filter(SpacingSuggestion::is_change)
+        .collect();
+        info!(
+            "Auto-spacing suggests changes to {} glyph(s)",
+            preview.len()
+        );
+        for suggestion in &preview {
+            debug!(
+                "  {}: {:.0}|{:.0} -> {:.0}|{:.0}",
+                suggestion.glyph_name,
+                suggestion.current.0,
+                suggestion.current.1,
+                suggestion.suggested.0,
+                suggestion.suggested.1
+            );
+        }
+        spacing.preview = preview;
+    }
+
+    for DiscardSpacingEvent(glyph_name) in discard_events.read() {
+        spacing.preview.retain(|suggestion| {
+            glyph_name
+                .as_ref()
+                .is_some_and(|name| *name != suggestion.glyph_name)
+        });
+    }
+
+    let mut advances: Vec<(String, f32)> = Vec::new();
+    for AcceptSpacingEvent(glyph_name) in accept_events.read() {
+        let (accepted, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut spacing.preview)
+            .into_iter()
+            .partition(|suggestion| {
+                glyph_name
+                    .as_ref()
+                    .is_none_or(|name| *name == suggestion.glyph_name)
+            });
+        spacing.preview = kept;
+        let font = &mut state.workspace.font;
+        for suggestion in accepted {
+            // Measure again in case the glyph was edited since the preview
+            let Some((left, right)) = font.sidebearings(&suggestion.glyph_name) else {
+                continue;
+            };
+            let Some(glyph) = font.glyphs.get_mut(&suggestion.glyph_name) else {
+                continue;
+            };
+            adjust_sidebearing(glyph, Sidebearing::Left, suggestion.suggested.0 - left);
+            adjust_sidebearing(glyph, Sidebearing::Right, suggestion.suggested.1 - right);
+            advances.push((suggestion.glyph_name, glyph.advance_width as f32));
+        }
+    }
+    if advances.is_empty() {
+        return;
+    }
+    info!("Auto-spacing applied to {} glyph(s)", advances.len());
+
+    // Reflow every sort of the respaced glyphs
+    if let Some(text_editor) = text_editor_state.as_mut() {
+        for index in 0..text_editor.buffer.len() {
+            if let Some(SortKind::Glyph {
+                glyph_name,
+                advance_width,
+                ..
+            }) = text_editor.buffer.get_mut(index).map(|sort| &mut sort.kind)
+            {
+                if let Some((_, advance)) = advances.iter().find(|(name, _)| name == glyph_name) {
+                    *advance_width = *advance;
+                }
+            }
+        }
+    }
+    app_state_changed.write(AppStateChanged);
+    let edited = advances
+        .into_iter()
+        .map(|(name, _)| GlyphEdited::glyph(name));
+    glyph_edits.write_batch(edited);
+}
@@ -0,0 +1,179 @@
+//! Brace layers of the active glyph
+//!
+//! Cmd/Ctrl+Alt+N shows the brace layers pane when the UFO being edited is
+//! a master of a designspace in its folder (see
+//! `font_source::brace_layers`). The pane steps a location along each axis
+//! and creates a brace layer there for the active sort's glyph, interpolated
+//! from the masters around it; the glyph's brace layers are then edited
+//! like any layer by switching to them. Saving the font writes them into
+//! the designspace as sparse sources.
+
+use crate::core::state::AppState;
+use crate::editing::glyph_layers::SwitchEditingLayerEvent;
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::font_source::brace_layers::{brace_layer_name, interpolate_at, BraceMasters};
+use crate::font_source::designspace_rules::Location;
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use crate::systems::commands::SaveFileEvent;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// The designspace's masters and the location a new brace layer goes to
+#[derive(Resource, Default)]
+pub struct BraceLayersState {
+    /// Masters of the font's designspace, `None` without one
+    pub masters: Option<BraceMasters>,
+    pub location: Location,
+    pub visible: bool,
+}
+
+/// Changes to brace layers, axes by index
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum BraceLayersEvent {
+    StepLocation(usize, bool),
+    /// Add a brace layer at the location for the active glyph
+    Create,
+    /// Edit a brace layer, or the foreground for `None`
+    Edit(Option<String>),
+}
+
+pub struct BraceLayersPlugin;
+
+impl Plugin for BraceLayersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BraceLayersState>()
+            .add_event::<BraceLayersEvent>()
+            .add_systems(
+                Update,
+                (
+                    load_brace_masters,
+                    toggle_brace_layers,
+                    handle_brace_layers_events,
+                    save_brace_sources,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Reads the masters whenever another font is opened
+fn load_brace_masters(
+    mut loaded_for: Local<Option<Option<PathBuf>>>,
+    app_state: Option<Res<AppState>>,
+    mut brace_state: ResMut<BraceLayersState>,
+) {
+    let font_path = app_state.and_then(|state| state.workspace.font.path.clone());
+    if loaded_for.as_ref() == Some(&font_path) {
+        return;
+    }
+    *loaded_for = Some(font_path.clone());
+
+    let masters = match font_path.as_deref().map(BraceMasters::find_for_ufo) {
+        Some(Ok(masters)) => masters,
+        Some(Err(error)) => {
+            warn!("Designspace masters not loaded: {error:#}");
+            None
+        }
+        None => None,
+    };
+    // Start halfway between the default and the top of each axis
+    brace_state.location = masters
+        .iter()
+        .flat_map(|masters| &masters.axes)
+        .map(|axis| {
+            let value = ((axis.default + axis.maximum) / 2.0).round();
+            (axis.name.clone(), value)
+        })
+        .collect();
+    brace_state.masters = masters;
+}
+
+/// Cmd/Ctrl+Alt+N shows or hides the brace layers pane
+fn toggle_brace_layers(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut brace_state: ResMut<BraceLayersState>,
+) {
+    if Shortcut::BraceLayers.just_pressed(&keyboard) {
+        brace_state.visible = !brace_state.visible;
+        debug!("Brace layers shown: {}", brace_state.visible);
+    }
+}
+
+fn handle_brace_layers_events(
+    mut events: EventReader<BraceLayersEvent>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut brace_state: ResMut<BraceLayersState>,
+    active_sorts: Query<&Sort, With<ActiveSort>>,
+    mut switch_events: EventWriter<SwitchEditingLayerEvent>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    let brace_state = brace_state.as_mut();
+    let (Some(state), Some(masters)) = (app_state.as_mut(), brace_state.masters.as_ref()) else {
+        events.clear();
+        return;
+    };
+    let font = &mut state.workspace.font;
+
+    for event in events.read() {
+        match event {
+            BraceLayersEvent::StepLocation(index, forward) => {
+                if let Some(axis) = masters.axes.get(*index) {
+                    let value = brace_state.location.entry(axis.name.clone()).or_default();
+                    *value = axis.step(*value, *forward);
+                }
+            }
+            BraceLayersEvent::Create => {
+                let Ok(sort) = active_sorts.single() else {
+                    warn!("Cannot add a brace layer: no active sort");
+                    continue;
+                };
+                let layer = brace_layer_name(&brace_state.location, &masters.axes);
+                if font
+                    .brace_layer_names(&sort.glyph_name, &masters.axes)
+                    .contains(&layer)
+                {
+                    warn!("'{}' already has the brace layer {layer}", sort.glyph_name);
+                    continue;
+                }
+                let glyph = masters
+                    .master_glyphs(font, &sort.glyph_name)
+                    .and_then(|glyphs| interpolate_at(&glyphs, &brace_state.location));
+                match glyph {
+                    Ok(mut glyph) => {
+                        glyph.name = sort.glyph_name.clone();
+                        font.insert_layer_glyph(&layer, glyph);
+                        info!("Added the brace layer {layer} to '{}'", sort.glyph_name);
+                        app_state_changed.write(AppStateChanged);
+                        visual_update_tracker.needs_update = true;
+                    }
+                    Err(error) => warn!("Cannot add a brace layer: {error:#}"),
+                }
+            }
+            BraceLayersEvent::Edit(layer) => {
+                switch_events.write(SwitchEditingLayerEvent(layer.clone()));
+            }
+        }
+    }
+}
+
+/// Writes the brace layers into the designspace along with the font
+fn save_brace_sources(
+    mut saves: EventReader<SaveFileEvent>,
+    app_state: Option<Res<AppState>>,
+    brace_state: Res<BraceLayersState>,
+) {
+    if saves.read().count() == 0 {
+        return;
+    }
+    let (Some(state), Some(masters)) = (app_state, brace_state.masters.as_ref()) else {
+        return;
+    };
+    match masters.save_sources(&state.workspace.font) {
+        Ok(true) => info!("Saved brace layer sources to {}", masters.path.display()),
+        Ok(false) => {}
+        Err(error) => error!("Saving brace layer sources failed: {error:#}"),
+    }
+}
@@ -0,0 +1,74 @@
+//! Inspection of the last build
+//!
+//! When an export finishes, the build inspector pane opens on the fonts it
+//! wrote: their tables and sizes, key `head`, `hhea` and `OS/2` values,
+//! the Unicode blocks `cmap` covers and how big the outlines are (see
+//! `data::font_inspector`), so a build can be sanity-checked without ttx.
+//! The export dialog opens it again for the last build.
+
+use crate::data::font_inspector::FontInspection;
+use bevy::prelude::*;
+
+/// The fonts of the last build and the one shown
+#[derive(Resource, Default)]
+pub struct BuildInspector {
+    pub visible: bool,
+    pub fonts: Vec<FontInspection>,
+    pub selected: usize,
+}
+
+impl BuildInspector {
+    /// Shows the fonts of a new build, starting from the first
+    pub fn inspect(&mut self, fonts: Vec<FontInspection>) {
+        self.visible = !fonts.is_empty();
+        self.fonts = fonts;
+        self.selected = 0;
+    }
+
+    pub fn selected_font(&self) -> Option<&FontInspection> {
+        self.fonts.get(self.selected)
+    }
+}
+
+/// Changes to the build inspector
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum BuildInspectorEvent {
+    /// Show the last build again
+    Show,
+    /// Show the font at an index of the build
+    Select(usize),
+    Hide,
+}
+
+pub struct BuildInspectorPlugin;
+
+impl Plugin for BuildInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BuildInspector>()
+            .add_event::<BuildInspectorEvent>()
+            .add_systems(Update, handle_build_inspector_events);
+    }
+}
+
+fn handle_build_inspector_events(
+    mut events: EventReader<BuildInspectorEvent>,
+    mut inspector: ResMut<BuildInspector>,
+) {
+    for event in events.read() {
+        match event {
+            BuildInspectorEvent::Show => {
+                if inspector.fonts.is_empty() {
+                    warn!("No build to inspect: export the font first");
+                    continue;
+                }
+                inspector.visible = true;
+            }
+            BuildInspectorEvent::Select(index) => {
+                if *index < inspector.fonts.len() {
+                    inspector.selected = *index;
+                }
+            }
+            BuildInspectorEvent::Hide => inspector.visible = false,
+        }
+    }
+}
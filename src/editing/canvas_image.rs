@@ -0,0 +1,300 @@
+//! Images of the canvas
+//!
+//! Cmd/Ctrl+Alt+Shift+X shows the canvas image pane, which saves the
+//! filled glyphs of the sorts on the canvas as a PNG or SVG next to the
+//! font, to share progress without building it. The image shows what the
+//! window shows, the text buffers, or the selected sorts, at a multiple of
+//! the screen's resolution, on the theme's background or a transparent one
+//! (see `data::canvas_image`).
+
+use crate::core::state::AppState;
+use crate::data::canvas_image::{canvas_svg, composite, rasterize};
+use crate::editing::selection::components::Selected;
+use crate::editing::sort::Sort;
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::cameras::DesignCamera;
+use crate::ui::themes::CurrentTheme;
+use anyhow::{Context, Result};
+use bevy::color::ColorToPacked;
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task};
+use bevy::window::PrimaryWindow;
+use kurbo::{Affine, Shape};
+use std::path::{Path, PathBuf};
+
+/// Multiples of the screen's resolution an image can be drawn at
+pub const CANVAS_IMAGE_SCALES: [f64; 4] = [1.0, 2.0, 4.0, 8.0];
+
+/// Space left around the text or selection, as a share of the em
+const MARGIN: f64 = 0.1;
+
+/// What an image is saved as
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CanvasImageFormat {
+    Png,
+    Svg,
+}
+
+impl CanvasImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CanvasImageFormat::Png => "png",
+            CanvasImageFormat::Svg => "svg",
+        }
+    }
+}
+
+/// The part of the canvas an image shows
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CanvasImageRegion {
+    /// What the window shows
+    Viewport,
+    /// Every sort on the canvas
+    Text,
+    /// The selected sorts
+    Selection,
+}
+
+/// How the next image is drawn, and the image being saved
+#[derive(Resource)]
+pub struct CanvasImageState {
+    pub visible: bool,
+    pub format: CanvasImageFormat,
+    pub region: CanvasImageRegion,
+    /// Multiple of the screen's resolution
+    pub scale: f64,
+    pub transparent: bool,
+    task: Option<Task<Result<PathBuf>>>,
+}
+
+impl Default for CanvasImageState {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            format: CanvasImageFormat::Png,
+            region: CanvasImageRegion::Viewport,
+            scale: 2.0,
+            transparent: false,
+            task: None,
+        }
+    }
+}
+
+impl CanvasImageState {
+    pub fn is_saving(&self) -> bool {
+        self.task.is_some()
+    }
+}
+
+/// Changes to the canvas image
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum CanvasImageEvent {
+    SetFormat(CanvasImageFormat),
+    SetRegion(CanvasImageRegion),
+    SetScale(f64),
+    ToggleTransparent,
+    /// Save the image next to the font
+    Save,
+}
+
+pub struct CanvasImagePlugin;
+
+impl Plugin for CanvasImagePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CanvasImageState>()
+            .add_event::<CanvasImageEvent>()
+            .add_systems(
+                Update,
+                (
+                    toggle_canvas_image,
+                    handle_canvas_image_events,
+                    finish_canvas_image,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Cmd/Ctrl+Alt+Shift+X shows or hides the canvas image pane
+fn toggle_canvas_image(keyboard: Res<ButtonInput<KeyCode>>, mut image: ResMut<CanvasImageState>) {
+    if Shortcut::CanvasImage.just_pressed(&keyboard) {
+        image.visible = !image.visible;
+        debug!("Canvas image shown: {}", image.visible);
+    }
+}
+
+/// The rectangle around the advance boxes of sorts, from descender to
+/// ascender, with a margin
+fn sorts_region<'a>(
+    sorts: impl Iterator<Item = (&'a Sort, Vec2)>,
+    state: &AppState,
+) -> Option<kurbo::Rect> {
+    let metrics = &state.workspace.info.metrics;
+    let upm = metrics.units_per_em;
+    let descender = metrics.descender.unwrap_or(-0.25 * upm);
+    let ascender = metrics.ascender.unwrap_or(0.75 * upm);
+    sorts
+        .map(|(sort, origin)| {
+            let advance = state
+                .workspace
+                .font
+                .editing()
+                .get_glyph(&sort.glyph_name)
+                .map_or(0.0, |glyph| glyph.advance_width);
+            let (x, y) = (origin.x as f64, origin.y as f64);
+            kurbo::Rect::new(x, y + descender, x + advance, y + ascender)
+        })
+        .reduce(|a, b| a.union(b))
+        .map(|region| region.inflate(MARGIN * upm, MARGIN * upm))
+}
+
+/// Writes RGBA pixels as a PNG
+fn write_png(path: &Path, width: u32, height: u32, pixels: Vec<u8>) -> Result<()> {
+    let image = Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        pixels,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD,
+    );
+    image
+        .try_into_dynamic()
+        .context("Cannot convert the image")?
+        .save(path)
+        .with_context(|| format!("Cannot write {}", path.display()))
+}
+
+fn handle_canvas_image_events(
+    mut events: EventReader<CanvasImageEvent>,
+    mut image: ResMut<CanvasImageState>,
+    app_state: Option<Res<AppState>>,
+    sort_query: Query<(&Sort, &Transform, Has<Selected>)>,
+    camera_query: Query<(&Transform, &Projection), With<DesignCamera>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    theme: Res<CurrentTheme>,
+) {
+    for event in events.read() {
+        match event {
+            CanvasImageEvent::SetFormat(format) => image.format = *format,
+            CanvasImageEvent::SetRegion(region) => image.region = *region,
+            CanvasImageEvent::SetScale(scale) => image.scale = *scale,
+            CanvasImageEvent::ToggleTransparent => image.transparent = !image.transparent,
+            CanvasImageEvent::Save => {
+                if image.is_saving() {
+                    warn!("A canvas image is already being saved");
+                    continue;
+                }
+                let Some(state) = app_state.as_ref() else {
+                    warn!("Cannot save a canvas image: no font loaded");
+                    continue;
+                };
+                let Some(font_path) = state.workspace.font.path.as_ref() else {
+                    warn!("Cannot save a canvas image: save the font first");
+                    continue;
+                };
+                let (Ok((camera, projection)), Ok(window)) =
+                    (camera_query.single(), window_query.single())
+                else {
+                    continue;
+                };
+                let zoom = match projection {
+                    Projection::Orthographic(orthographic) => orthographic.scale as f64,
+                    _ => 1.0,
+                };
+
+                // Screen pixels per design unit, times the chosen scale
+                let pixels_per_unit = window.scale_factor() as f64 / zoom * image.scale;
+                let sorts = || {
+                    sort_query.iter().map(|(sort, transform, selected)| {
+                        (sort, transform.translation.truncate(), selected)
+                    })
+                };
+                let region = match image.region {
+                    CanvasImageRegion::Viewport => {
+                        let center = camera.translation.truncate();
+                        let half_width = window.width() as f64 * zoom / 2.0;
+                        let half_height = window.height() as f64 * zoom / 2.0;
+                        Some(kurbo::Rect::from_center_size(
+                            (center.x as f64, center.y as f64),
+                            (half_width * 2.0, half_height * 2.0),
+                        ))
+                    }
+                    CanvasImageRegion::Text => {
+                        sorts_region(sorts().map(|(sort, origin, _)| (sort, origin)), state)
+                    }
+                    CanvasImageRegion::Selection => sorts_region(
+                        sorts()
+                            .filter(|(_, _, selected)| *selected)
+                            .map(|(sort, origin, _)| (sort, origin)),
+                        state,
+                    ),
+                };
+                let Some(region) = region else {
+                    warn!("Cannot save a canvas image: no sorts to show");
+                    continue;
+                };
+
+                // Outlines placed at their sorts, leaving out those off the image
+                let font = &state.workspace.font;
+                let paths: Vec<kurbo::BezPath> = sorts()
+                    .flat_map(|(sort, origin, _)| {
+                        let placement = Affine::translate((origin.x as f64, origin.y as f64));
+                        font.editing()
+                            .resolved_bezpaths(&sort.glyph_name)
+                            .into_iter()
+                            .map(move |path| placement * path)
+                    })
+                    .filter(|path| region.intersect(path.bounding_box()).area() > 0.0)
+                    .collect();
+
+                let fill = theme.theme().filled_glyph_color().to_srgba().to_u8_array();
+                let background = (!image.transparent)
+                    .then(|| theme.theme().background_color().to_srgba().to_u8_array());
+                let format = image.format;
+                let stem = font_path.file_stem().unwrap_or_default().to_string_lossy();
+                let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+                let path = font_path
+                    .with_file_name(format!("{stem}-Canvas-{timestamp}.{}", format.extension()));
+                info!("Saving the canvas image to {}", path.display());
+                image.task = Some(AsyncComputeTaskPool::get().spawn(async move {
+                    match format {
+                        CanvasImageFormat::Svg => {
+                            let svg =
+                                canvas_svg(&paths, region, pixels_per_unit, fill, background)?;
+                            std::fs::write(&path, svg)
+                                .with_context(|| format!("Cannot write {}", path.display()))?;
+                        }
+                        CanvasImageFormat::Png => {
+                            let (width, height, coverage) =
+                                rasterize(&paths, region, pixels_per_unit)?;
+                            let pixels = composite(&coverage, fill, background);
+                            write_png(&path, width, height, pixels)?;
+                        }
+                    }
+                    Ok(path)
+                }));
+            }
+        }
+    }
+}
+
+/// Picks up a saved image and reports where it went
+fn finish_canvas_image(mut image: ResMut<CanvasImageState>) {
+    let Some(task) = image.task.as_mut() else {
+        return;
+    };
+    let Some(result) = block_on(future::poll_once(task)) else {
+        return;
+    };
+    image.task = None;
+    match result {
+        Ok(path) => info!("Saved the canvas image to {}", path.display()),
+        Err(error) => error!("Saving the canvas image failed: {error:#}"),
+    }
+}
@@ -0,0 +1,766 @@
+//! Right-click menus on the canvas
+//!
+//! A right click opens a context menu (see `ui::context_menu`) for what's
+//! under the pointer, looked for in this order:
+//! - a point of the active sort: make the segment ending at it a line or a
+//!   curve, open or close the corner at it (see `font_source::open_corners`),
+//!   make it its contour's start point, round it to whole units, delete it; for handles, type their
+//!   length or angle (see `handle_entry`), make a smooth point's two handles
+//!   the same length, or pull a cubic segment's handles to a third of it
+//! - a contour of the active sort: reverse it, close it if it's open,
+//!   delete it
+//! - a sort handle: activate the sort, duplicate it, delete it, revert its
+//!   glyph to how it was saved, show its glyph's details
+//! - empty canvas: paste outlines, add a sort of the current glyph there
+//!
+//! Locked and hidden contours are passed over. Tools that use the right
+//! button themselves, like the pen, don't get menus.
+
+use crate::core::state::{
+    AppState, ContourData, GlyphNavigation, PointTypeData, SortLayoutMode, TextEditorState,
+};
+use crate::editing::clipboard::PasteOutlinesEvent;
+use crate::editing::handle_entry::HandleValue;
+use crate::editing::outline_locks::OutlineLocks;
+use crate::editing::saved_glyphs::{RevertGlyphEvent, SavedGlyphs};
+use crate::editing::selection::components::{GlyphPointReference, SelectionState};
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::sort::group::{deactivate_all_sorts, delete_buffer_sorts};
+use crate::editing::sort::manager::SortPointEntity;
+use crate::editing::sort::{ActiveSort, ActiveSortState, Sort, SortEvent};
+use crate::font_source::open_corners::{
+    close_open_corner, is_corner, open_corner, open_corners, OPEN_CORNER_EXTENSION,
+};
+use crate::io::pointer::PointerInfo;
+use crate::rendering::cameras::DesignCamera;
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use crate::rendering::sort_visuals::{sort_handle_center, SORT_HANDLE_SIZE};
+use crate::systems::sorts::sort_entities::{BufferSortIndex, BufferSortRespawnQueue};
+use crate::systems::ui_interaction::UiHoverState;
+use crate::ui::context_menu::{ContextMenuItem, ContextMenuPlugin, OpenContextMenu};
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use bevy::prelude::*;
+use kurbo::ParamCurveNearest;
+
+/// Tools that use the right button for themselves
+const RIGHT_BUTTON_TOOLS: [&str; 3] = ["pen", "hyper", "metaballs"];
+
+/// How close, in screen pixels, the pointer has to be to a point or a
+/// contour to get its menu
+const HIT_DISTANCE_PX: f32 = 8.0;
+
+/// A contour of a glyph
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContourTarget {
+    pub glyph_name: String,
+    pub contour_index: usize,
+}
+
+/// What the canvas menus do
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum CanvasAction {
+    /// Make the segment ending at an on-curve point a line or a curve
+    SetSegmentType(GlyphPointReference, PointTypeData),
+    RoundPoint(GlyphPointReference),
+    DeletePoint(GlyphPointReference),
+    /// Start a closed contour at an on-curve point
+    SetStartPoint(GlyphPointReference),
+    /// Extend the segments meeting at a corner past each other
+    OpenCorner(GlyphPointReference),
+    /// Cut the segments of an open corner back to where they cross
+    CloseCorner(GlyphPointReference),
+    /// Type a handle's length or angle
+    EditHandle(GlyphPointReference, HandleValue),
+    /// Make the two handles of an on-curve point, or of a handle's point,
+    /// the same length
+    EqualizeHandles(GlyphPointReference),
+    /// Make a cubic segment's handles a third of the segment long
+    HandlesToThirds(GlyphPointReference),
+    ReverseContour(ContourTarget),
+    CloseContour(ContourTarget),
+    DeleteContour(ContourTarget),
+    ActivateSort(Entity),
+    DuplicateSort(Entity),
+    DeleteSort(Entity),
+    ShowGlyphInfo(Entity),
+    /// Put a glyph back as it was last saved
+    RevertToSaved(String),
+    Paste,
+    AddSort {
+        glyph_name: String,
+        position: Vec2,
+    },
+}
+
+type CanvasMenuItem = ContextMenuItem<CanvasAction>;
+
+pub struct CanvasMenuPlugin;
+
+impl Plugin for CanvasMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ContextMenuPlugin::<CanvasAction>::default())
+            .add_systems(
+                Update,
+                (open_canvas_menu, apply_outline_actions, apply_sort_actions).chain(),
+            );
+    }
+}
+
+/// The contour with a segment within `reach` of `position`, nearest first
+fn contour_under(contours: &[ContourData], position: kurbo::Point, reach: f64) -> Option<usize> {
+    contours
+        .iter()
+        .enumerate()
+        .filter_map(|(index, contour)| {
+            let distance_sq = contour
+                .to_bezpath()
+                .segments()
+                .map(|segment| segment.nearest(position, 1e-3).distance_sq)
+                .reduce(f64::min)?;
+            (distance_sq <= reach * reach).then_some((distance_sq, index))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, index)| index)
+}
+
+fn point_items(reference: &GlyphPointReference, contour: &ContourData) -> Vec<CanvasMenuItem> {
+    let index = reference.point_index;
+    let point_type = contour.points[index].point_type;
+    let mut items = Vec::new();
+    if let Some((length, angle)) = contour.handle_polar(index) {
+        items.extend([
+            ContextMenuItem::info(format!("handle {length:.1} long at {angle:.1}°")),
+            ContextMenuItem::new(
+                "Set handle length…",
+                CanvasAction::EditHandle(reference.clone(), HandleValue::Length),
+            ),
+            ContextMenuItem::new(
+                "Set handle angle…",
+                CanvasAction::EditHandle(reference.clone(), HandleValue::Angle),
+            ),
+        ]);
+    }
+    let owner = contour.handle_owner(index).unwrap_or(index);
+    if contour.handle_pair(owner).is_some() {
+        items.push(ContextMenuItem::new(
+            "Equalize handles",
+            CanvasAction::EqualizeHandles(reference.clone()),
+        ));
+    }
+    if contour.with_handles_at_thirds(index) != *contour {
+        items.push(ContextMenuItem::new(
+            "Handles to ⅓ of segment",
+            CanvasAction::HandlesToThirds(reference.clone()),
+        ));
+    }
+    if !matches!(point_type, PointTypeData::Move | PointTypeData::OffCurve) {
+        if point_type != PointTypeData::Line {
+            items.push(ContextMenuItem::new(
+                "Make line segment",
+                CanvasAction::SetSegmentType(reference.clone(), PointTypeData::Line),
+            ));
+        }
+        if point_type != PointTypeData::Curve {
+            items.push(ContextMenuItem::new(
+                "Make curve segment",
+                CanvasAction::SetSegmentType(reference.clone(), PointTypeData::Curve),
+            ));
+        }
+    }
+    if index != 0 && !contour.is_open() && point_type != PointTypeData::OffCurve {
+        items.push(ContextMenuItem::new(
+            "Make start point",
+            CanvasAction::SetStartPoint(reference.clone()),
+        ));
+    }
+    let in_open_corner = open_corners(contour)
+        .iter()
+        .any(|corner| corner.arriving == index || corner.leaving == index);
+    if in_open_corner {
+        items.push(ContextMenuItem::new(
+            "Close corner",
+            CanvasAction::CloseCorner(reference.clone()),
+        ));
+    } else if is_corner(contour, index) {
+        items.push(ContextMenuItem::new(
+            "Open corner",
+            CanvasAction::OpenCorner(reference.clone()),
+        ));
+    }
+    items.push(ContextMenuItem::new(
+        "Round to whole units",
+        CanvasAction::RoundPoint(reference.clone()),
+    ));
+    items.push(ContextMenuItem::new(
+        "Delete point",
+        CanvasAction::DeletePoint(reference.clone()),
+    ));
+    items
+}
+
+fn contour_items(target: ContourTarget, is_open: bool) -> Vec<CanvasMenuItem> {
+    let mut items = vec![ContextMenuItem::new(
+        "Reverse direction",
+        CanvasAction::ReverseContour(target.clone()),
+    )];
+    if is_open {
+        items.push(ContextMenuItem::new(
+            "Close contour",
+            CanvasAction::CloseContour(target.clone()),
+        ));
+    }
+    items.push(ContextMenuItem::new(
+        "Delete contour",
+        CanvasAction::DeleteContour(target),
+    ));
+    items
+}
+
+/// Opens the menu for what's under the pointer on a right click
+#[allow(clippy::too_many_arguments)]
+fn open_canvas_menu(
+    mouse: Res<ButtonInput<MouseButton>>,
+    ui_hover_state: Res<UiHoverState>,
+    current_tool: Res<CurrentTool>,
+    pointer: Res<PointerInfo>,
+    app_state: Option<Res<AppState>>,
+    locks: Res<OutlineLocks>,
+    saved_glyphs: Res<SavedGlyphs>,
+    glyph_navigation: Option<Res<GlyphNavigation>>,
+    camera_query: Query<&Projection, With<DesignCamera>>,
+    points: Query<(&Transform, &GlyphPointReference), With<SortPointEntity>>,
+    sorts: Query<(Entity, &Transform, &Sort, Has<ActiveSort>)>,
+    mut menus: EventWriter<OpenContextMenu<CanvasAction>>,
+) {
+    if !mouse.just_pressed(MouseButton::Right) || ui_hover_state.is_hovering_ui {
+        return;
+    }
+    if current_tool
+        .get_current()
+        .is_some_and(|tool| RIGHT_BUTTON_TOOLS.contains(&tool))
+    {
+        return;
+    }
+    let Some(state) = app_state.as_deref() else {
+        return;
+    };
+    let font = &state.workspace.font;
+    let zoom_scale = match camera_query.single() {
+        Ok(Projection::Orthographic(ortho)) => ortho.scale,
+        _ => 1.0,
+    };
+    let reach = HIT_DISTANCE_PX * zoom_scale;
+    let position = pointer.world;
+
+    let point = points
+        .iter()
+        .filter(|(_, reference)| {
+            locks
+                .glyph(&reference.glyph_name)
+                .is_editable(reference.contour_index)
+        })
+        .map(|(transform, reference)| {
+            (
+                transform.translation.truncate().distance(position),
+                reference,
+            )
+        })
+        .filter(|(distance, _)| *distance <= reach)
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .and_then(|(_, reference)| {
+            let outline = font
+                .editing()
+                .get_glyph(&reference.glyph_name)?
+                .outline
+                .as_ref()?;
+            let contour = outline.contours.get(reference.contour_index)?;
+            contour.points.get(reference.point_index)?;
+            Some(point_items(reference, contour))
+        });
+
+    let contour = || {
+        let (_, transform, sort, _) = sorts.iter().find(|(.., active)| *active)?;
+        let outline = font
+            .editing()
+            .get_glyph(&sort.glyph_name)?
+            .outline
+            .as_ref()?;
+        let local = position - transform.translation.truncate();
+        let glyph_locks = locks.glyph(&sort.glyph_name);
+        let editable: Vec<ContourData> = outline
+            .contours
+            .iter()
+            .enumerate()
+            .map(|(index, contour)| {
+                if glyph_locks.is_editable(index) {
+                    contour.clone()
+                } else {
+                    ContourData { points: Vec::new() }
+                }
+            })
+            .collect();
+        let local = kurbo::Point::new(local.x as f64, local.y as f64);
+        let contour_index = contour_under(&editable, local, reach as f64)?;
+        let target = ContourTarget {
+            glyph_name: sort.glyph_name.clone(),
+            contour_index,
+        };
+        Some(contour_items(target, editable[contour_index].is_open()))
+    };
+
+    let sort_handle = || {
+        let descender = state.workspace.info.descender.unwrap_or(-200.0) as f32;
+        let (entity, _, sort, active) = sorts.iter().find(|(_, transform, ..)| {
+            let handle = sort_handle_center(transform.translation.truncate(), descender);
+            (position - handle).abs().max_element() <= SORT_HANDLE_SIZE / 2.0
+        })?;
+        let mut items = Vec::new();
+        if !active {
+            items.push(ContextMenuItem::new(
+                "Activate",
+                CanvasAction::ActivateSort(entity),
+            ));
+        }
+        items.extend([
+            ContextMenuItem::new("Duplicate", CanvasAction::DuplicateSort(entity)),
+            ContextMenuItem::new("Delete sort", CanvasAction::DeleteSort(entity)),
+        ]);
+        if saved_glyphs.is_modified(font, &sort.glyph_name) {
+            items.push(ContextMenuItem::new(
+                "Revert to saved",
+                CanvasAction::RevertToSaved(sort.glyph_name.clone()),
+            ));
+        }
+        items.push(ContextMenuItem::new(
+            "Glyph info",
+            CanvasAction::ShowGlyphInfo(entity),
+        ));
+        Some(items)
+    };
+
+    let canvas = || {
+        let mut items = vec![ContextMenuItem::new("Paste", CanvasAction::Paste)];
+        let current_glyph = glyph_navigation
+            .as_ref()
+            .and_then(|navigation| navigation.current_glyph.clone())
+            .or_else(|| {
+                sorts
+                    .iter()
+                    .find(|(.., active)| *active)
+                    .map(|(_, _, sort, _)| sort.glyph_name.clone())
+            })
+            .filter(|glyph_name| font.editing().get_glyph(glyph_name).is_some());
+        if let Some(glyph_name) = current_glyph {
+            items.push(ContextMenuItem::new(
+                format!("Add sort '{glyph_name}' here"),
+                CanvasAction::AddSort {
+                    glyph_name,
+                    position,
+                },
+            ));
+        }
+        items
+    };
+
+    let items = point
+        .or_else(contour)
+        .or_else(sort_handle)
+        .unwrap_or_else(canvas);
+    menus.write(OpenContextMenu {
+        position: pointer.screen,
+        items,
+    });
+}
+
+/// Replaces a contour with what `edit` makes of it, or removes it on `None`
+pub(crate) fn edit_contour(
+    state: &mut AppState,
+    glyph_name: &str,
+    contour_index: usize,
+    edit: impl FnOnce(&ContourData) -> Option<ContourData>,
+) -> bool {
+    let Some(contours) = state
+        .workspace
+        .font
+        .editing_glyph_mut(glyph_name)
+        .and_then(|glyph| glyph.outline.as_mut())
+        .map(|outline| &mut outline.contours)
+    else {
+        return false;
+    };
+    let Some(contour) = contours.get(contour_index) else {
+        return false;
+    };
+    match edit(contour) {
+        Some(edited) => contours[contour_index] = edited,
+        None => {
+            contours.remove(contour_index);
+        }
+    }
+    true
+}
+
+/// Edits the outlines of the active glyph
+fn apply_outline_actions(
+    mut actions: EventReader<CanvasAction>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    for action in actions.read() {
+        let Some(state) = app_state.as_deref_mut() else {
+            return;
+        };
+        let edited = match action {
+            CanvasAction::SetSegmentType(reference, point_type) => edit_contour(
+                state,
+                &reference.glyph_name,
+                reference.contour_index,
+                |contour| Some(contour.with_segment_type(reference.point_index, *point_type)),
+            ),
+            CanvasAction::RoundPoint(reference) => edit_contour(
+                state,
+                &reference.glyph_name,
+                reference.contour_index,
+                |contour| {
+                    let mut contour = contour.clone();
+                    if let Some(point) = contour.points.get_mut(reference.point_index) {
+                        point.x = point.x.round();
+                        point.y = point.y.round();
+                    }
+                    Some(contour)
+                },
+            ),
+            CanvasAction::DeletePoint(reference) => edit_contour(
+                state,
+                &reference.glyph_name,
+                reference.contour_index,
+                |contour| contour.without_point(reference.point_index),
+            ),
+            CanvasAction::SetStartPoint(reference) => edit_contour(
+                state,
+                &reference.glyph_name,
+                reference.contour_index,
+                |contour| Some(contour.with_start_point(reference.point_index)),
+            ),
+            CanvasAction::OpenCorner(reference) => edit_contour(
+                state,
+                &reference.glyph_name,
+                reference.contour_index,
+                |contour| {
+                    open_corner(contour, reference.point_index, OPEN_CORNER_EXTENSION)
+                        .or_else(|| Some(contour.clone()))
+                },
+            ),
+            CanvasAction::CloseCorner(reference) => edit_contour(
+                state,
+                &reference.glyph_name,
+                reference.contour_index,
+                |contour| {
+                    let index = reference.point_index;
+                    let closed = open_corners(contour)
+                        .iter()
+                        .find(|corner| corner.arriving == index || corner.leaving == index)
+                        .map(|corner| close_open_corner(contour, corner));
+                    Some(closed.unwrap_or_else(|| contour.clone()))
+                },
+            ),
+            CanvasAction::EqualizeHandles(reference) => edit_contour(
+                state,
+                &reference.glyph_name,
+                reference.contour_index,
+                |contour| {
+                    let index = reference.point_index;
+                    let owner = contour.handle_owner(index).unwrap_or(index);
+                    Some(contour.with_equal_handles(owner))
+                },
+            ),
+            CanvasAction::HandlesToThirds(reference) => edit_contour(
+                state,
+                &reference.glyph_name,
+                reference.contour_index,
+                |contour| Some(contour.with_handles_at_thirds(reference.point_index)),
+            ),
+            CanvasAction::ReverseContour(target) => {
+                edit_contour(state, &target.glyph_name, target.contour_index, |contour| {
+                    Some(contour.reversed())
+                })
+            }
+            CanvasAction::CloseContour(target) => {
+                edit_contour(state, &target.glyph_name, target.contour_index, |contour| {
+                    Some(contour.closed())
+                })
+            }
+            CanvasAction::DeleteContour(target) => {
+                edit_contour(state, &target.glyph_name, target.contour_index, |_| None)
+            }
+            _ => continue,
+        };
+        if edited {
+            debug!("Canvas menu edited an outline: {action:?}");
+            app_state_changed.write(AppStateChanged);
+            visual_update_tracker.needs_update = true;
+        }
+    }
+}
+
+/// What the glyph info menu lists about a glyph
+fn glyph_info_items(state: &AppState, glyph_name: &str) -> Vec<CanvasMenuItem> {
+    let font = &state.workspace.font;
+    let Some(glyph) = font.editing().get_glyph(glyph_name) else {
+        return vec![ContextMenuItem::info(format!("{glyph_name} (missing)"))];
+    };
+    let codepoints = if glyph.unicode_values.is_empty() {
+        "no code points".to_string()
+    } else {
+        glyph
+            .unicode_values
+            .iter()
+            .map(|ch| format!("U+{:04X}", *ch as u32))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let contours = glyph
+        .outline
+        .as_ref()
+        .map_or(0, |outline| outline.contours.len());
+    let mut items = vec![
+        ContextMenuItem::info(glyph_name),
+        ContextMenuItem::info(codepoints),
+        ContextMenuItem::info(format!("advance {}", glyph.advance_width.round())),
+        ContextMenuItem::info(format!(
+            "{contours} contours, {} components",
+            glyph.components.len()
+        )),
+    ];
+    if let Some((left, right)) = font.editing().sidebearings(glyph_name) {
+        items.push(ContextMenuItem::info(format!(
+            "sidebearings {} / {}",
+            left.round(),
+            right.round()
+        )));
+    }
+    items
+}
+
+/// Acts on sorts, and pastes and adds sorts on the canvas
+#[allow(clippy::too_many_arguments)]
+fn apply_sort_actions(
+    mut commands: Commands,
+    mut actions: EventReader<CanvasAction>,
+    app_state: Option<Res<AppState>>,
+    pointer: Res<PointerInfo>,
+    sorts: Query<(&Transform, &Sort, Option<&BufferSortIndex>)>,
+    point_entities: Query<Entity, With<SortPointEntity>>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+    mut active_sort_state: ResMut<ActiveSortState>,
+    mut selection_state: ResMut<SelectionState>,
+    mut sort_events: EventWriter<SortEvent>,
+    mut paste_events: EventWriter<PasteOutlinesEvent>,
+    mut revert_events: EventWriter<RevertGlyphEvent>,
+    mut menus: EventWriter<OpenContextMenu<CanvasAction>>,
+) {
+    for action in actions.read() {
+        match action {
+            CanvasAction::ActivateSort(entity) => {
+                if let Ok((_, _, Some(buffer_index))) = sorts.get(*entity) {
+                    text_editor_state.activate_sort(buffer_index.0);
+                }
+                sort_events.write(SortEvent::ActivateSort { entity: *entity });
+            }
+            CanvasAction::DuplicateSort(entity) => {
+                let Ok((transform, sort, buffer_index)) = sorts.get(*entity) else {
+                    continue;
+                };
+                if let Some(buffer_index) = buffer_index {
+                    // The copy follows the original in its text
+                    let Some(mut copy) = text_editor_state.buffer.get(buffer_index.0).cloned()
+                    else {
+                        continue;
+                    };
+                    copy.is_active = false;
+                    let index = buffer_index.0 + 1;
+                    text_editor_state.buffer.insert(index, copy);
+                    respawn_queue
+                        .indices
+                        .extend(index..text_editor_state.buffer.len());
+                    deactivate_all_sorts(
+                        &mut commands,
+                        &mut text_editor_state,
+                        &mut active_sort_state,
+                        &mut selection_state,
+                        &point_entities,
+                    );
+                } else {
+                    let advance = app_state
+                        .as_deref()
+                        .and_then(|state| {
+                            state.workspace.font.editing().get_glyph(&sort.glyph_name)
+                        })
+                        .map_or(0.0, |glyph| glyph.advance_width as f32);
+                    sort_events.write(SortEvent::CreateSort {
+                        glyph_name: sort.glyph_name.clone(),
+                        position: transform.translation.truncate() + Vec2::new(advance, 0.0),
+                        layout_mode: SortLayoutMode::Freeform,
+                    });
+                }
+            }
+            CanvasAction::DeleteSort(entity) => {
+                let Ok((_, _, buffer_index)) = sorts.get(*entity) else {
+                    continue;
+                };
+                if let Some(buffer_index) = buffer_index {
+                    delete_buffer_sorts(
+                        vec![buffer_index.0],
+                        &mut text_editor_state,
+                        &mut respawn_queue,
+                    );
+                    deactivate_all_sorts(
+                        &mut commands,
+                        &mut text_editor_state,
+                        &mut active_sort_state,
+                        &mut selection_state,
+                        &point_entities,
+                    );
+                } else {
+                    sort_events.write(SortEvent::DeleteSort { entity: *entity });
+                }
+            }
+            CanvasAction::ShowGlyphInfo(entity) => {
+                let (Some(state), Ok((_, sort, _))) = (app_state.as_deref(), sorts.get(*entity))
+                else {
+                    continue;
+                };
+                menus.write(OpenContextMenu {
+                    position: pointer.screen,
+                    items: glyph_info_items(state, &sort.glyph_name),
+                });
+            }
+            CanvasAction::RevertToSaved(glyph_name) => {
+                revert_events.write(RevertGlyphEvent {
+                    glyph_name: glyph_name.clone(),
+                });
+            }
+            CanvasAction::Paste => {
+                paste_events.write(PasteOutlinesEvent);
+            }
+            CanvasAction::AddSort {
+                glyph_name,
+                position,
+            } => {
+                sort_events.write(SortEvent::CreateSort {
+                    glyph_name: glyph_name.clone(),
+                    position: *position,
+                    layout_mode: SortLayoutMode::Freeform,
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::state::PointData;
+
+    #[test]
+    fn test_segment_types_and_point_deletion() {
+        // A line made a curve keeps its shape, and back again
+        let curved = ContourData::test_square(100.0).with_segment_type(2, PointTypeData::Curve);
+        assert_eq!(curved.points.len(), 6);
+        assert_eq!(curved.points[2].point_type, PointTypeData::OffCurve);
+        assert_eq!(curved.points[3].x, 100.0);
+        assert!((curved.points[3].y - 200.0 / 3.0).abs() < 1e-9);
+        assert_eq!(curved.points[4].point_type, PointTypeData::Curve);
+        assert_eq!(
+            curved.with_segment_type(4, PointTypeData::Line),
+            ContourData::test_square(100.0)
+        );
+
+        // The closing segment's handles go at the end
+        let closing = ContourData::test_square(100.0).with_segment_type(0, PointTypeData::Curve);
+        assert_eq!(closing.points[0].point_type, PointTypeData::Curve);
+        assert_eq!(closing.points[5].point_type, PointTypeData::OffCurve);
+
+        // Deleting a handle retracts its segment; deleting an on-curve point
+        // takes its handles along
+        assert_eq!(
+            curved.without_point(3),
+            Some(ContourData::test_square(100.0))
+        );
+        let triangle = curved.without_point(4).unwrap();
+        assert_eq!(triangle.points.len(), 3);
+        assert_eq!(closing.without_point(0).unwrap().points[0].x, 100.0);
+
+        // Too little left to be a contour
+        let line = ContourData {
+            points: vec![
+                PointData::test(0.0, 0.0, PointTypeData::Move),
+                PointData::test(100.0, 0.0, PointTypeData::Line),
+            ],
+        };
+        assert_eq!(line.without_point(0), None);
+        assert_eq!(line.closed().points[0].point_type, PointTypeData::Line);
+    }
+
+    #[test]
+    fn test_start_point() {
+        let curved = ContourData::test_square(100.0).with_segment_type(0, PointTypeData::Curve);
+        let started = curved.with_start_point(2);
+        assert_eq!((started.points[0].x, started.points[0].y), (100.0, 100.0));
+        // The curve's handles stay in front of its end point
+        assert_eq!(started.points[3].point_type, PointTypeData::OffCurve);
+        assert_eq!(started.points[4].point_type, PointTypeData::Curve);
+        assert_eq!(started.with_start_point(4), curved);
+        // Handles can't start a contour
+        assert_eq!(curved.with_start_point(4), curved);
+    }
+
+    #[test]
+    fn test_handle_edits() {
+        // The square's top made a curve, then its handles pulled apart
+        let mut curved = ContourData::test_square(100.0).with_segment_type(3, PointTypeData::Curve);
+        curved.points[3] = PointData::test(100.0, 160.0, PointTypeData::OffCurve);
+        curved.points[4] = PointData::test(0.0, 120.0, PointTypeData::OffCurve);
+
+        assert_eq!(curved.handle_owner(3), Some(2));
+        assert_eq!(curved.handle_owner(4), Some(5));
+        assert_eq!(curved.handle_owner(2), None);
+        let (length, angle) = curved.handle_polar(3).unwrap();
+        assert!((length - 60.0).abs() < 1e-9 && (angle - 90.0).abs() < 1e-9);
+
+        let turned = curved.with_handle_polar(4, 30.0, 0.0);
+        assert!((turned.points[4].x - 30.0).abs() < 1e-9);
+        assert!((turned.points[4].y - 100.0).abs() < 1e-9);
+
+        let thirds = curved.with_handles_at_thirds(4);
+        assert!((thirds.points[3].y - (100.0 + 100.0 / 3.0)).abs() < 1e-9);
+        assert!((thirds.points[4].y - (100.0 + 100.0 / 3.0)).abs() < 1e-9);
+        assert_eq!(thirds.points[4].x, 0.0);
+
+        // A point on the curve with a handle on either side
+        let mut smooth = curved.clone();
+        smooth
+            .points
+            .insert(2, PointData::test(100.0, 50.0, PointTypeData::OffCurve));
+        assert_eq!(smooth.handle_pair(3), Some((2, 4)));
+        assert_eq!(curved.handle_pair(2), None);
+        let equal = smooth.with_equal_handles(3);
+        assert!((equal.handle_polar(2).unwrap().0 - 55.0).abs() < 1e-9);
+        assert!((equal.handle_polar(4).unwrap().0 - 55.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_contour_under() {
+        let contours = [
+            ContourData::test_square(100.0),
+            ContourData { points: Vec::new() },
+        ];
+        let near_edge = kurbo::Point::new(50.0, 104.0);
+        assert_eq!(contour_under(&contours, near_edge, 8.0), Some(0));
+        let inside = kurbo::Point::new(50.0, 50.0);
+        assert_eq!(contour_under(&contours, inside, 8.0), None);
+    }
+}
@@ -0,0 +1,356 @@
+//! Outline clipboard
+//!
+//! Copies whole contours out of the active sort and pastes them into whatever
+//! glyph is active next. Copied outlines are kept internally for lossless
+//! glyph-to-glyph pastes and mirrored to the system clipboard as SVG, which
+//! lets other Bezy instances and vector apps exchange outlines with us.
+//! The system clipboard is read in the background, so a paste from it lands
+//! a few frames after the shortcut, in the glyph that was active then.
+//! Pastes land in the order they were asked for, and a paste asked for
+//! while a copy is still being written pastes the copy without reading the
+//! clipboard, which may still hold what was there before.
+
+use crate::core::config::BezySettings;
+use crate::core::state::{AppState, ContourData, OutlineData};
+use crate::data::svg;
+use crate::editing::selection::components::{GlyphPointReference, Selected};
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::sort::manager::SortPointEntity;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::io::shortcuts::Shortcut;
+use crate::utils::system_clipboard;
+use anyhow::Result;
+use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future, Task};
+use std::collections::{BTreeSet, VecDeque};
+
+/// Contours copied from a glyph, in font units relative to the sort origin
+#[derive(Resource, Default)]
+pub struct OutlineClipboard {
+    pub contours: Vec<ContourData>,
+    /// The SVG we last wrote to the system clipboard, used to tell our own
+    /// copies apart from SVG that came from another app
+    last_exported_svg: Option<String>,
+    /// The last copy being written to the system clipboard
+    pending_write: Option<Task<()>>,
+    /// Pastes waiting for the system clipboard to be read, oldest first
+    pending_pastes: VecDeque<PendingPaste>,
+}
+
+/// The glyph to paste into and what to paste
+struct PendingPaste {
+    glyph_name: String,
+    source: PasteSource,
+}
+
+enum PasteSource {
+    /// The system clipboard being read
+    SystemClipboard(Task<Result<String>>),
+    /// Our own copy, taken while it was still being written
+    Copied(Vec<ContourData>),
+}
+
+/// Copy the selected contours (or the whole glyph if nothing is selected)
+#[derive(Event)]
+pub struct CopyOutlinesEvent;
+
+/// Paste clipboard contours into the active sort's glyph
+#[derive(Event)]
+pub struct PasteOutlinesEvent;
+
+pub struct OutlineClipboardPlugin;
+
+impl Plugin for OutlineClipboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<OutlineClipboard>()
+            .add_event::<CopyOutlinesEvent>()
+            .add_event::<PasteOutlinesEvent>()
+            .add_systems(
+                Update,
+                (
+                    handle_clipboard_shortcuts,
+                    handle_copy_outlines,
+                    handle_paste_outlines,
+                    finish_outline_paste,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// System to turn Cmd/Ctrl+C and Cmd/Ctrl+V into clipboard events
+pub fn handle_clipboard_shortcuts(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    text_mode: Option<Res<crate::ui::edit_mode_toolbar::text::TextModeActive>>,
+    mut copy_events: EventWriter<CopyOutlinesEvent>,
+    mut paste_events: EventWriter<PasteOutlinesEvent>,
+) {
+    // The text tool uses these keys for its own buffer editing
+    if text_mode.is_some_and(|t| t.0) {
+        return;
+    }
+
+    if Shortcut::CopyOutlines.just_pressed(&keyboard) {
+        copy_events.write(CopyOutlinesEvent);
+    }
+    if Shortcut::PasteOutlines.just_pressed(&keyboard) {
+        paste_events.write(PasteOutlinesEvent);
+    }
+}
+
+fn handle_copy_outlines(
+    mut events: EventReader<CopyOutlinesEvent>,
+    mut clipboard: ResMut<OutlineClipboard>,
+    app_state: Option<Res<AppState>>,
+    settings: Res<BezySettings>,
+    active_sort_query: Query<(Entity, &Sort), With<ActiveSort>>,
+    selected_points: Query<(&GlyphPointReference, &SortPointEntity), With<Selected>>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+
+    let Some(state) = app_state.as_ref() else {
+        warn!("Copy requested but no font is loaded");
+        return;
+    };
+    let Ok((sort_entity, sort)) = active_sort_query.single() else {
+        debug!("Copy requested without an active sort");
+        return;
+    };
+    let Some(outline) = state
+        .workspace
+        .font
+        .editing()
+        .get_glyph(&sort.glyph_name)
+        .and_then(|glyph| glyph.outline.as_ref())
+    else {
+        debug!("Glyph '{}' has no outline to copy", sort.glyph_name);
+        return;
+    };
+
+    let selected_contours: BTreeSet<usize> = selected_points
+        .iter()
+        .filter(|(_, sort_point)| sort_point.sort_entity == sort_entity)
+        .map(|(point_ref, _)| point_ref.contour_index)
+        .collect();
+
+    clipboard.contours = outline
+        .contours
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| selected_contours.is_empty() || selected_contours.contains(index))
+        .map(|(_, contour)| contour.clone())
+        .collect();
+
+    info!(
+        "Copied {} contour(s) from glyph '{}'",
+        clipboard.contours.len(),
+        sort.glyph_name
+    );
+
+    if settings.clipboard.use_system_clipboard {
+        let upm = state.workspace.info.units_per_em;
+        let ascender = state.workspace.info.ascender.unwrap_or(upm * 0.8);
+        let scale = settings.clipboard.svg_scale;
+        let transform = svg::font_to_svg_transform(scale, ascender);
+        let size = upm * scale;
+        let document = svg::contours_to_svg_document(&clipboard.contours, transform, size, size);
+
+        // A failed write leaves other text on the clipboard, which then
+        // pastes as another app's
+        clipboard.pending_write =
+            Some(system_clipboard::write_text_in_background(document.clone()));
+        clipboard.last_exported_svg = Some(document);
+    }
+}
+
+fn handle_paste_outlines(
+    mut events: EventReader<PasteOutlinesEvent>,
+    mut clipboard: ResMut<OutlineClipboard>,
+    mut app_state: Option<ResMut<AppState>>,
+    settings: Res<BezySettings>,
+    active_sort_query: Query<&Sort, With<ActiveSort>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<crate::rendering::glyph_renderer::SortVisualUpdateTracker>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+
+    let Some(state) = app_state.as_mut() else {
+        warn!("Paste requested but no font is loaded");
+        return;
+    };
+    let Ok(sort) = active_sort_query.single() else {
+        debug!("Paste requested without an active sort");
+        return;
+    };
+
+    // Reading the clipboard before our copy is on it would paste whatever
+    // was there before
+    let writing = clipboard
+        .pending_write
+        .as_mut()
+        .is_some_and(|task| block_on(future::poll_once(task)).is_none());
+    if !writing {
+        clipboard.pending_write = None;
+    }
+    // Queued behind earlier pastes, so they land in order
+    if settings.clipboard.use_system_clipboard {
+        let source = if writing {
+            PasteSource::Copied(clipboard.contours.clone())
+        } else {
+            PasteSource::SystemClipboard(system_clipboard::read_text_in_background())
+        };
+        clipboard.pending_pastes.push_back(PendingPaste {
+            glyph_name: sort.glyph_name.clone(),
+            source,
+        });
+        return;
+    }
+
+    if paste_contours(state, &sort.glyph_name, clipboard.contours.clone()) {
+        app_state_changed.write(AppStateChanged);
+        visual_update_tracker.needs_update = true;
+    }
+}
+
+/// Pastes the queued pastes once the clipboard has been read for them, in
+/// the order they were asked for
+fn finish_outline_paste(
+    mut clipboard: ResMut<OutlineClipboard>,
+    mut app_state: Option<ResMut<AppState>>,
+    settings: Res<BezySettings>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<crate::rendering::glyph_renderer::SortVisualUpdateTracker>,
+) {
+    while let Some(pending) = clipboard.pending_pastes.front_mut() {
+        let text = match &mut pending.source {
+            PasteSource::SystemClipboard(task) => {
+                let Some(text) = block_on(future::poll_once(task)) else {
+                    return;
+                };
+                Some(text)
+            }
+            PasteSource::Copied(_) => None,
+        };
+        let Some(pending) = clipboard.pending_pastes.pop_front() else {
+            return;
+        };
+
+        let Some(state) = app_state.as_mut() else {
+            continue;
+        };
+        let contours = match (text, pending.source) {
+            (Some(text), _) => {
+                let svg_scale = settings.clipboard.svg_scale;
+                contours_from_system_clipboard(text, &clipboard, state, svg_scale)
+            }
+            (None, PasteSource::Copied(contours)) => contours,
+            (None, PasteSource::SystemClipboard(_)) => continue,
+        };
+        if paste_contours(state, &pending.glyph_name, contours) {
+            app_state_changed.write(AppStateChanged);
+            visual_update_tracker.needs_update = true;
+        }
+    }
+}
+
+/// Add contours to a glyph; false if there was nothing to paste into it
+fn paste_contours(state: &mut AppState, glyph_name: &str, contours: Vec<ContourData>) -> bool {
+    if contours.is_empty() {
+        debug!("Clipboard has no outlines to paste");
+        return false;
+    }
+
+    let Some(glyph) = state.workspace.font.editing_glyph_mut(glyph_name) else {
+        warn!("Cannot paste: glyph '{}' not found", glyph_name);
+        return false;
+    };
+
+    let pasted = contours.len();
+    glyph
+        .outline
+        .get_or_insert_with(|| OutlineData {
+            contours: Vec::new(),
+        })
+        .contours
+        .extend(contours);
+
+    info!("Pasted {} contour(s) into glyph '{}'", pasted, glyph_name);
+    true
+}
+
+/// Prefer SVG from another app, as a document or bare path data; fall back
+/// to our own lossless copy
+fn contours_from_system_clipboard(
+    text: Result<String>,
+    clipboard: &OutlineClipboard,
+    state: &AppState,
+    svg_scale: f64,
+) -> Vec<ContourData> {
+    let text = match text {
+        Ok(text) => text,
+        Err(e) => {
+            debug!("System clipboard unavailable, using internal clipboard: {}", e);
+            return clipboard.contours.clone();
+        }
+    };
+
+    let is_our_copy = clipboard.last_exported_svg.as_deref() == Some(text.as_str());
+    if is_our_copy {
+        return clipboard.contours.clone();
+    }
+
+    let upm = state.workspace.info.units_per_em;
+    let ascender = state.workspace.info.ascender.unwrap_or(upm * 0.8);
+    let transform = svg::font_to_svg_transform(svg_scale, ascender);
+    if !text.contains("<svg") {
+        // Bare path data, such as a copied `d` attribute; any other text
+        // leaves our own copy to paste
+        if !text.contains('<') {
+            if let Ok(contours) = svg::svg_to_contours(&text, transform) {
+                return contours;
+            }
+        }
+        return clipboard.contours.clone();
+    }
+    match svg::svg_to_contours(&text, transform) {
+        Ok(contours) => contours,
+        Err(e) => {
+            warn!("Could not read SVG from the system clipboard: {}", e);
+            clipboard.contours.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bare_path_data_pastes_from_the_system_clipboard() {
+        let state = AppState::default();
+        let clipboard = OutlineClipboard {
+            contours: vec![ContourData { points: Vec::new() }],
+            ..default()
+        };
+        let path_data = "M0 0 L100 0 L100 100 Z".to_string();
+        let contours = contours_from_system_clipboard(Ok(path_data), &clipboard, &state, 1.0);
+
+        assert_eq!(contours.len(), 1);
+        assert_eq!(contours[0].points.len(), 3);
+        let info = &state.workspace.info;
+        let ascender = info.ascender.unwrap_or(info.units_per_em * 0.8);
+        assert!(contours[0]
+            .points
+            .iter()
+            .any(|point| point.x == 100.0 && (point.y - (ascender - 100.0)).abs() < 1e-9));
+
+        // Other text leaves our own copy to paste
+        let contours =
+            contours_from_system_clipboard(Ok("a note".to_string()), &clipboard, &state, 1.0);
+        assert!(contours[0].points.is_empty());
+    }
+}
@@ -0,0 +1,383 @@
+//! Color layer editing
+//!
+//! Events for building COLRv0 color glyphs: attaching layer glyphs to a base
+//! glyph, choosing each layer's palette entry, and editing the CPAL palettes.
+//! The data itself lives in `FontData::color` and is saved to the UFO lib.
+//!
+//! Cmd/Ctrl+Alt+Shift+' shows the color layers pane, which sends these
+//! events for the active sort's glyph. A palette entry's color is typed as
+//! hex (`#rrggbb` or `#rrggbbaa`) until Enter sets it or Escape drops it.
+
+use crate::core::state::{AppState, GlyphData};
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::text_field_focus::{TextField, TextFieldFocus, TextFieldSystems};
+use crate::font_source::color::FOREGROUND_PALETTE_INDEX;
+use crate::font_source::{ColorLayer, PaletteColor};
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+
+/// Whether the color layers pane is shown, and the palette entry whose
+/// color is being typed
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct ColorLayersPanel {
+    pub visible: bool,
+    /// Entry of the active palette being typed, and the hex typed so far
+    pub editing: Option<(usize, String)>,
+}
+
+/// Add a layer to a color glyph
+///
+/// With `layer_glyph: None` a new glyph named `<base>.color<N>` is created
+/// from a copy of the base outline, which is the usual starting point when
+/// splitting a drawing into colored parts.
+#[derive(Event, Clone)]
+pub struct AddColorLayerEvent {
+    pub base_glyph: String,
+    pub layer_glyph: Option<String>,
+    pub palette_index: u16,
+}
+
+/// Remove a layer from a color glyph (the layer glyph itself is kept)
+#[derive(Event, Clone)]
+pub struct RemoveColorLayerEvent {
+    pub base_glyph: String,
+    pub layer_index: usize,
+}
+
+/// Change which palette entry a layer is filled with
+#[derive(Event, Clone)]
+pub struct SetLayerPaletteIndexEvent {
+    pub base_glyph: String,
+    pub layer_index: usize,
+    pub palette_index: u16,
+}
+
+/// Move a layer up (towards the top of the stack) or down
+#[derive(Event, Clone)]
+pub struct ReorderColorLayerEvent {
+    pub base_glyph: String,
+    pub layer_index: usize,
+    pub move_up: bool,
+}
+
+/// Set a palette entry, creating palettes/entries as needed
+#[derive(Event, Clone)]
+pub struct SetPaletteColorEvent {
+    pub palette: usize,
+    pub index: usize,
+    pub color: PaletteColor,
+}
+
+/// Switch the palette shown in the canvas
+#[derive(Event, Clone)]
+pub struct SelectPaletteEvent(pub usize);
+
+pub struct ColorLayersPlugin;
+
+impl Plugin for ColorLayersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ColorLayersPanel>()
+            .add_event::<AddColorLayerEvent>()
+            .add_event::<RemoveColorLayerEvent>()
+            .add_event::<SetLayerPaletteIndexEvent>()
+            .add_event::<ReorderColorLayerEvent>()
+            .add_event::<SetPaletteColorEvent>()
+            .add_event::<SelectPaletteEvent>()
+            .add_systems(
+                Update,
+                (
+                    toggle_color_layers_pane,
+                    color_entry_keys.in_set(TextFieldSystems),
+                    handle_color_layer_events,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// A palette color as hex, `#rrggbb`, with the alpha only when it isn't
+/// opaque
+pub fn hex_color(color: PaletteColor) -> String {
+    let [r, g, b, a] = color.map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8);
+    if a == 255 {
+        format!("#{r:02x}{g:02x}{b:02x}")
+    } else {
+        format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+    }
+}
+
+/// Parse `#rrggbb` or `#rrggbbaa`, the `#` being optional
+pub fn parse_hex_color(text: &str) -> Option<PaletteColor> {
+    let digits = text.trim().trim_start_matches('#');
+    if !matches!(digits.len(), 6 | 8) || !digits.is_ascii() {
+        return None;
+    }
+    let channel = |index: usize| {
+        let value = digits.get(index * 2..index * 2 + 2)?;
+        Some(u8::from_str_radix(value, 16).ok()? as f32 / 255.0)
+    };
+    let alpha = if digits.len() == 8 { channel(3)? } else { 1.0 };
+    Some([channel(0)?, channel(1)?, channel(2)?, alpha])
+}
+
+/// The palette index after or before `index`, going from the foreground
+/// color through every entry of a palette of `palette_size` and around
+pub fn step_palette_index(index: u16, palette_size: usize, forward: bool) -> u16 {
+    let last = palette_size.checked_sub(1).map(|last| last as u16);
+    match (forward, index == FOREGROUND_PALETTE_INDEX, last) {
+        (_, _, None) => FOREGROUND_PALETTE_INDEX,
+        (true, true, Some(_)) => 0,
+        (true, false, Some(last)) if index >= last => FOREGROUND_PALETTE_INDEX,
+        (true, false, Some(_)) => index + 1,
+        (false, true, Some(last)) => last,
+        (false, false, Some(_)) if index == 0 => FOREGROUND_PALETTE_INDEX,
+        (false, false, Some(last)) => (index - 1).min(last),
+    }
+}
+
+/// Cmd/Ctrl+Alt+Shift+' shows or hides the pane
+fn toggle_color_layers_pane(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut panel: ResMut<ColorLayersPanel>,
+) {
+    if Shortcut::ColorLayers.just_pressed(&keyboard) {
+        panel.visible = !panel.visible;
+        panel.editing = None;
+        debug!("Color layers pane shown: {}", panel.visible);
+    }
+}
+
+/// Types a palette entry's hex color; Enter sets it and Escape drops it
+fn color_entry_keys(
+    mut key_events: EventReader<KeyboardInput>,
+    mut focus: ResMut<TextFieldFocus>,
+    mut panel: ResMut<ColorLayersPanel>,
+    app_state: Option<Res<AppState>>,
+    mut palette_events: EventWriter<SetPaletteColorEvent>,
+) {
+    focus.set(TextField::ColorEntry, panel.editing.is_some());
+    let Some((index, typed)) = panel.editing.clone() else {
+        key_events.clear();
+        return;
+    };
+    let mut typed = typed;
+    let mut done = None;
+    for event in key_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(text) => {
+                typed.extend(text.chars().filter(|c| c.is_ascii_hexdigit() || *c == '#'))
+            }
+            Key::Backspace => {
+                typed.pop();
+            }
+            Key::Enter => done = Some(true),
+            Key::Escape => done = Some(false),
+            _ => {}
+        }
+    }
+
+    match done {
+        Some(true) => {
+            let palette = app_state
+                .as_deref()
+                .map_or(0, |state| state.workspace.font.color.active_palette);
+            match parse_hex_color(&typed) {
+                Some(color) => {
+                    palette_events.write(SetPaletteColorEvent {
+                        palette,
+                        index,
+                        color,
+                    });
+                }
+                None => warn!("'{}' is not a hex color", typed),
+            }
+            panel.editing = None;
+        }
+        Some(false) => panel.editing = None,
+        None => {
+            if panel.editing.as_ref().map(|(_, shown)| shown) != Some(&typed) {
+                panel.editing = Some((index, typed));
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_color_layer_events(
+    mut add_events: EventReader<AddColorLayerEvent>,
+    mut remove_events: EventReader<RemoveColorLayerEvent>,
+    mut index_events: EventReader<SetLayerPaletteIndexEvent>,
+    mut reorder_events: EventReader<ReorderColorLayerEvent>,
+    mut palette_events: EventReader<SetPaletteColorEvent>,
+    mut select_events: EventReader<SelectPaletteEvent>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    let Some(state) = app_state.as_mut() else {
+        add_events.clear();
+        remove_events.clear();
+        index_events.clear();
+        reorder_events.clear();
+        palette_events.clear();
+        select_events.clear();
+        return;
+    };
+
+    let mut changed = false;
+
+    for event in add_events.read() {
+        changed |= add_color_layer(state, event);
+    }
+
+    let font = &mut state.workspace.font;
+
+    for event in remove_events.read() {
+        let Some(layers) = font.color.layers.get_mut(&event.base_glyph) else {
+            continue;
+        };
+        if event.layer_index < layers.len() {
+            let removed = layers.remove(event.layer_index);
+            info!(
+                "Removed color layer '{}' from '{}'",
+                removed.glyph_name, event.base_glyph
+            );
+            if layers.is_empty() {
+                font.color.layers.remove(&event.base_glyph);
+            }
+            changed = true;
+        }
+    }
+
+    for event in index_events.read() {
+        let layer = font
+            .color
+            .layers
+            .get_mut(&event.base_glyph)
+            .and_then(|layers| layers.get_mut(event.layer_index));
+        if let Some(layer) = layer {
+            layer.palette_index = event.palette_index;
+            changed = true;
+        }
+    }
+
+    for event in reorder_events.read() {
+        let Some(layers) = font.color.layers.get_mut(&event.base_glyph) else {
+            continue;
+        };
+        let target = if event.move_up {
+            event.layer_index + 1
+        } else {
+            event.layer_index.wrapping_sub(1)
+        };
+        if event.layer_index < layers.len() && target < layers.len() {
+            layers.swap(event.layer_index, target);
+            changed = true;
+        }
+    }
+
+    for event in palette_events.read() {
+        font.color.set_palette_color(event.palette, event.index, event.color);
+        changed = true;
+    }
+
+    for SelectPaletteEvent(palette) in select_events.read() {
+        if *palette < font.color.palettes.len() {
+            font.color.active_palette = *palette;
+            changed = true;
+        } else {
+            warn!("Palette {} does not exist", palette);
+        }
+    }
+
+    if changed {
+        app_state_changed.write(AppStateChanged);
+        visual_update_tracker.needs_update = true;
+    }
+}
+
+fn add_color_layer(state: &mut AppState, event: &AddColorLayerEvent) -> bool {
+    let font = &mut state.workspace.font;
+    let Some(base) = font.glyphs.get(&event.base_glyph) else {
+        warn!("Cannot add color layer: glyph '{}' not found", event.base_glyph);
+        return false;
+    };
+
+    let layer_glyph = match &event.layer_glyph {
+        Some(name) if font.glyphs.contains_key(name) => name.clone(),
+        Some(name) => {
+            warn!("Cannot add color layer: layer glyph '{}' not found", name);
+            return false;
+        }
+        None => {
+            let name = (0..)
+                .map(|n| format!("{}.color{}", event.base_glyph, n))
+                .find(|name| !font.glyphs.contains_key(name))
+                .expect("an unused layer name always exists");
+            let layer = GlyphData {
+                name: name.clone(),
+                unicode_values: Vec::new(),
+                ..base.clone()
+            };
+            font.glyphs.insert(name.clone(), layer);
+            name
+        }
+    };
+
+    let is_palette_entry = event.palette_index != FOREGROUND_PALETTE_INDEX;
+    if is_palette_entry && font.color.palette_size() <= event.palette_index as usize {
+        // Grow the palettes so the new layer is visible straight away
+        let index = event.palette_index as usize;
+        font.color.set_palette_color(0, index, [0.0, 0.0, 0.0, 1.0]);
+    }
+
+    info!(
+        "Added color layer '{}' to '{}' (palette index {})",
+        layer_glyph, event.base_glyph, event.palette_index
+    );
+    font.color
+        .layers
+        .entry(event.base_glyph.clone())
+        .or_default()
+        .push(ColorLayer {
+            glyph_name: layer_glyph,
+            palette_index: event.palette_index,
+        });
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_colors_and_palette_steps() {
+        assert_eq!(hex_color([1.0, 0.0, 0.5, 1.0]), "#ff0080");
+        assert_eq!(hex_color([0.0, 0.0, 0.0, 0.5]), "#00000080");
+        assert_eq!(
+            parse_hex_color("#ff0080"),
+            Some([1.0, 0.0, 128.0 / 255.0, 1.0])
+        );
+        assert_eq!(parse_hex_color("00000000"), Some([0.0; 4]));
+        assert_eq!(parse_hex_color("#ff00"), None);
+        assert_eq!(parse_hex_color("#gg0000"), None);
+
+        let steps: Vec<u16> = (0..4)
+            .scan(FOREGROUND_PALETTE_INDEX, |index, _| {
+                *index = step_palette_index(*index, 2, true);
+                Some(*index)
+            })
+            .collect();
+        assert_eq!(steps, [0, 1, FOREGROUND_PALETTE_INDEX, 0]);
+        assert_eq!(step_palette_index(0, 2, false), FOREGROUND_PALETTE_INDEX);
+        assert_eq!(step_palette_index(FOREGROUND_PALETTE_INDEX, 2, false), 1);
+        assert_eq!(step_palette_index(5, 2, false), 1);
+        assert_eq!(step_palette_index(0, 0, true), FOREGROUND_PALETTE_INDEX);
+    }
+}
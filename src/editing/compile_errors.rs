@@ -0,0 +1,140 @@
+//! Compile errors attached to glyphs
+//!
+//! When fontc fails, compiling the font for shaping or exporting it, its
+//! error is split into a diagnostic per failure, attached to the glyph it
+//! names (see `data::compile_diagnostics`). The compile errors pane opens on
+//! them, and jumping to a glyph activates its sort, or shows the glyph in
+//! the active sort. The errors stay until that kind of build succeeds.
+
+use crate::core::state::{AppState, GlyphNavigation, TextEditorState};
+use crate::data::compile_diagnostics::{glyph_diagnostics, CompileDiagnostic};
+use crate::editing::glyph_jump::jump_to_glyph;
+use crate::systems::sorts::sort_entities::BufferSortRespawnQueue;
+use bevy::prelude::*;
+
+/// A build that compiles the font with fontc
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompileBuild {
+    /// The background build for shaping previews
+    Shaping,
+    Export,
+}
+
+impl CompileBuild {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CompileBuild::Shaping => "Shaping build",
+            CompileBuild::Export => "Export",
+        }
+    }
+}
+
+/// Diagnostics of the last failed build
+#[derive(Resource, Default)]
+pub struct CompileErrors {
+    pub visible: bool,
+    pub build: Option<CompileBuild>,
+    pub diagnostics: Vec<CompileDiagnostic>,
+}
+
+impl CompileErrors {
+    /// Shows the diagnostics of a build that fontc failed, attaching them
+    /// to the glyphs of `app_state`'s font, unless the same ones are showing
+    pub fn report(&mut self, build: CompileBuild, error: &str, app_state: Option<&AppState>) {
+        // Failures before or after compiling stay in the log
+        if !error.contains("fontc failed") {
+            return;
+        }
+        let diagnostics = glyph_diagnostics(error, |name| {
+            app_state.is_some_and(|state| state.workspace.font.glyphs.contains_key(name))
+        });
+        if self.build == Some(build) && self.diagnostics == diagnostics {
+            return;
+        }
+        for diagnostic in &diagnostics {
+            match &diagnostic.glyph {
+                Some(glyph) => warn!(
+                    "{} failed at '{}': {}",
+                    build.label(),
+                    glyph,
+                    diagnostic.message
+                ),
+                None => warn!("{} failed: {}", build.label(), diagnostic.message),
+            }
+        }
+        self.visible = true;
+        self.build = Some(build);
+        self.diagnostics = diagnostics;
+    }
+
+    /// Forgets the errors of a kind of build once it succeeds
+    pub fn clear(&mut self, build: CompileBuild) {
+        if self.build == Some(build) {
+            *self = Self::default();
+        }
+    }
+}
+
+/// Actions on the compile errors
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum CompileErrorsEvent {
+    /// Show a glyph on the canvas
+    JumpToGlyph(String),
+    Hide,
+}
+
+pub struct CompileErrorsPlugin;
+
+impl Plugin for CompileErrorsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CompileErrors>()
+            .add_event::<CompileErrorsEvent>()
+            .add_systems(Update, handle_compile_errors_events);
+        #[cfg(feature = "tui")]
+        app.add_systems(Update, send_compile_errors_to_tui);
+    }
+}
+
+fn handle_compile_errors_events(
+    mut events: EventReader<CompileErrorsEvent>,
+    mut errors: ResMut<CompileErrors>,
+    app_state: Option<Res<AppState>>,
+    mut text_editor_state: Option<ResMut<TextEditorState>>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+    mut glyph_navigation: ResMut<GlyphNavigation>,
+) {
+    for event in events.read() {
+        let glyph_name = match event {
+            CompileErrorsEvent::Hide => {
+                errors.visible = false;
+                continue;
+            }
+            CompileErrorsEvent::JumpToGlyph(glyph_name) => glyph_name,
+        };
+        let (Some(state), Some(text_editor)) = (app_state.as_ref(), text_editor_state.as_mut())
+        else {
+            continue;
+        };
+        jump_to_glyph(
+            state,
+            text_editor,
+            &mut respawn_queue,
+            &mut glyph_navigation,
+            glyph_name,
+        );
+    }
+}
+
+/// Keeps the TUI's QA tab listing the compile errors
+#[cfg(feature = "tui")]
+fn send_compile_errors_to_tui(
+    errors: Res<CompileErrors>,
+    tui_comm: Option<Res<crate::core::tui_communication::TuiCommunication>>,
+) {
+    if !errors.is_changed() {
+        return;
+    }
+    if let Some(tui) = tui_comm {
+        tui.send_compile_errors(crate::qa::compilation::compile_issues(&errors.diagnostics));
+    }
+}
@@ -0,0 +1,242 @@
+//! Component dependencies of the active glyph
+//!
+//! Cmd/Ctrl+Alt+Shift+Z shows the component graph pane for the active
+//! sort's glyph: the glyphs it is built from, the glyphs built from it, and
+//! any loops of component references in the font (see
+//! `font_source::components`). Clicking a glyph jumps to it, components are
+//! added by name (see `editing::glyph_autocomplete`), and the glyph can opt
+//! out of its components following the anchors they sit on (see
+//! `editing::anchor_sync`). The first edit of a glyph that many composites
+//! use warns that they all change with it.
+
+use std::collections::HashSet;
+
+use crate::core::state::{AppState, GlyphNavigation, TextEditorState};
+use crate::editing::font_changes::GlyphEdited;
+use crate::editing::glyph_autocomplete::{GlyphNamePicked, GlyphNameTarget, OpenGlyphAutocomplete};
+use crate::editing::glyph_jump::jump_to_glyph;
+use crate::editing::selection::events::AppStateChanged;
+use crate::font_source::components::{ComponentGraph, ComponentNode};
+use crate::font_source::ComponentData;
+use crate::io::shortcuts::Shortcut;
+use crate::systems::sorts::sort_entities::BufferSortRespawnQueue;
+use bevy::prelude::*;
+
+/// Composites using a glyph, at any depth, from which editing it warns
+pub const MANY_USERS: usize = 10;
+
+/// The component graph pane and the dependencies it shows
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct ComponentGraphPaneState {
+    pub visible: bool,
+    /// The glyph shown, the active sort's when the pane was last refreshed
+    pub glyph: Option<String>,
+    /// The glyphs the shown glyph is built from
+    pub components: Vec<ComponentNode>,
+    /// The glyphs built from the shown glyph
+    pub users: Vec<ComponentNode>,
+    /// How many glyphs change with the shown glyph
+    pub all_users: usize,
+    /// Whether the shown glyph's components follow the anchors they sit on
+    pub follows_anchors: bool,
+    /// Every loop of references in the font
+    pub cycles: Vec<Vec<String>>,
+}
+
+/// Glyphs whose first edit has been checked for many users
+#[derive(Resource, Default)]
+struct CheckedEdits(HashSet<String>);
+
+/// Actions on the component graph pane
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum ComponentGraphEvent {
+    /// Show a glyph on the canvas
+    JumpToGlyph(String),
+    /// Pick a glyph to add as a component of the named glyph
+    AddComponent(String),
+    /// Opt a glyph out of its components following anchors, or back in
+    ToggleFollowAnchors(String),
+    Hide,
+}
+
+pub struct ComponentGraphPlugin;
+
+impl Plugin for ComponentGraphPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ComponentGraphPaneState>()
+            .init_resource::<CheckedEdits>()
+            .add_event::<ComponentGraphEvent>()
+            .add_systems(
+                Update,
+                (
+                    toggle_component_graph_pane,
+                    handle_component_graph_events,
+                    add_picked_components,
+                    refresh_component_graph,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Cmd/Ctrl+Alt+Shift+Z shows or hides the component graph pane
+fn toggle_component_graph_pane(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut pane: ResMut<ComponentGraphPaneState>,
+) {
+    if Shortcut::ComponentGraph.just_pressed(&keyboard) {
+        pane.visible = !pane.visible;
+        debug!("Component graph pane shown: {}", pane.visible);
+    }
+}
+
+fn handle_component_graph_events(
+    mut events: EventReader<ComponentGraphEvent>,
+    mut pane: ResMut<ComponentGraphPaneState>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut text_editor_state: Option<ResMut<TextEditorState>>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+    mut glyph_navigation: ResMut<GlyphNavigation>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut autocomplete_events: EventWriter<OpenGlyphAutocomplete>,
+) {
+    for event in events.read() {
+        match event {
+            ComponentGraphEvent::Hide => pane.visible = false,
+            ComponentGraphEvent::AddComponent(glyph_name) => {
+                autocomplete_events.write(OpenGlyphAutocomplete(GlyphNameTarget::Component(
+                    glyph_name.clone(),
+                )));
+            }
+            ComponentGraphEvent::ToggleFollowAnchors(glyph_name) => {
+                let Some(state) = app_state.as_mut() else {
+                    continue;
+                };
+                let follows = state.workspace.font.anchor_sync.toggle(glyph_name);
+                info!("'{}' components follow anchors: {}", glyph_name, follows);
+                if pane.glyph.as_ref() == Some(glyph_name) {
+                    pane.follows_anchors = follows;
+                }
+                app_state_changed.write(AppStateChanged);
+            }
+            ComponentGraphEvent::JumpToGlyph(glyph_name) => {
+                let (Some(state), Some(text_editor)) =
+                    (app_state.as_ref(), text_editor_state.as_mut())
+                else {
+                    continue;
+                };
+                jump_to_glyph(
+                    state,
+                    text_editor,
+                    &mut respawn_queue,
+                    &mut glyph_navigation,
+                    glyph_name,
+                );
+            }
+        }
+    }
+}
+
+/// Adds glyphs picked as components, refusing those that would make the
+/// references loop
+fn add_picked_components(
+    mut picked_events: EventReader<GlyphNamePicked>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut glyph_edited: EventWriter<GlyphEdited>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+) {
+    for picked in picked_events.read() {
+        let (GlyphNameTarget::Component(glyph_name), Some(state)) =
+            (&picked.target, app_state.as_mut())
+        else {
+            continue;
+        };
+        let base = &picked.glyph_name;
+        let font = &mut state.workspace.font;
+        let users = ComponentGraph::new(font).all_users(glyph_name);
+        if base == glyph_name || users.contains(base) {
+            warn!(
+                "Adding '{}' as a component of '{}' would make their references loop",
+                base, glyph_name
+            );
+            continue;
+        }
+        let Some(glyph) = font.editing_glyph_mut(glyph_name) else {
+            continue;
+        };
+        glyph.components.push(ComponentData {
+            base_glyph: base.clone(),
+            ..default()
+        });
+        info!("Added '{}' as a component of '{}'", base, glyph_name);
+        glyph_edited.write(GlyphEdited::glyph(glyph_name.clone()));
+        app_state_changed.write(AppStateChanged);
+    }
+}
+
+/// Warns on the first edit of a glyph with many users, and shows the active
+/// glyph's dependencies again when it or the font changes
+fn refresh_component_graph(
+    mut glyph_edits: EventReader<GlyphEdited>,
+    mut pane: ResMut<ComponentGraphPaneState>,
+    mut checked: ResMut<CheckedEdits>,
+    app_state: Option<Res<AppState>>,
+    text_editor_state: Option<Res<TextEditorState>>,
+    mut was_visible: Local<bool>,
+) {
+    let shown = pane.visible && !*was_visible;
+    *was_visible = pane.visible;
+    let Some(state) = app_state else {
+        glyph_edits.clear();
+        return;
+    };
+    let active_glyph = text_editor_state
+        .as_ref()
+        .and_then(|text_editor| text_editor.get_active_sort())
+        .map(|(_, sort)| sort.kind.glyph_name().to_string())
+        .filter(|name| !name.is_empty());
+
+    let edits: Vec<Option<String>> = glyph_edits
+        .read()
+        .map(|edit| edit.glyph_name.clone())
+        .collect();
+    let font_changed = !edits.is_empty();
+    // Edits that don't name their glyph are taken to be of the active one,
+    // except for loading the font
+    let loaded = state.is_added();
+    let mut edited: Vec<String> = edits
+        .into_iter()
+        .filter_map(|name| name.or_else(|| active_glyph.clone().filter(|_| !loaded)))
+        .filter(|name| !checked.0.contains(name))
+        .collect();
+    edited.sort();
+    edited.dedup();
+
+    let refresh_pane = pane.visible && (shown || font_changed || pane.glyph != active_glyph);
+    if edited.is_empty() && !refresh_pane {
+        return;
+    }
+    let graph = ComponentGraph::new(&state.workspace.font);
+
+    for glyph_name in edited {
+        let users = graph.all_users(&glyph_name).len();
+        if users >= MANY_USERS {
+            warn!(
+                "'{}' is used as a component by {} glyphs: editing it changes them all",
+                glyph_name, users
+            );
+        }
+        checked.0.insert(glyph_name);
+    }
+
+    if !refresh_pane {
+        return;
+    }
+    let glyph_name = active_glyph.as_deref().unwrap_or_default();
+    pane.components = graph.component_tree(glyph_name);
+    pane.users = graph.user_tree(glyph_name);
+    pane.all_users = graph.all_users(glyph_name).len();
+    pane.follows_anchors = state.workspace.font.anchor_sync.follows(glyph_name);
+    pane.cycles = graph.cycles();
+    pane.glyph = active_glyph;
+}
@@ -0,0 +1,82 @@
+//! Building composites from recipes
+//!
+//! Cmd/Ctrl+Alt+` shows the composites pane, which lists the glyphs of the
+//! built-in recipes the font lacks. Glyphs are chosen there and built with
+//! a `BuildCompositesEvent`, or all missing ones are built at once; what
+//! couldn't be built, and why, is listed below.
+
+use crate::core::state::FontData;
+use crate::data::glyph_recipes::GlyphRecipe;
+use crate::io::shortcuts::Shortcut;
+use bevy::prelude::*;
+use std::collections::BTreeSet;
+
+/// Whether the composites pane is shown, the glyphs chosen to build and
+/// the page of missing glyphs shown
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct CompositesPanel {
+    pub visible: bool,
+    pub chosen: BTreeSet<String>,
+    pub page: usize,
+}
+
+impl CompositesPanel {
+    /// Choose a glyph, or drop it if it was chosen
+    pub fn toggle(&mut self, glyph_name: &str) {
+        if !self.chosen.remove(glyph_name) {
+            self.chosen.insert(glyph_name.to_string());
+        }
+    }
+}
+
+pub struct CompositesPlugin;
+
+impl Plugin for CompositesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CompositesPanel>()
+            .add_systems(Update, toggle_composites_pane);
+    }
+}
+
+/// Names of the recipes whose glyph isn't in the font, in recipe order
+pub fn missing_recipes(font: &FontData, recipes: &[GlyphRecipe]) -> Vec<String> {
+    recipes
+        .iter()
+        .filter(|recipe| !font.glyphs.contains_key(&recipe.name))
+        .map(|recipe| recipe.name.clone())
+        .collect()
+}
+
+/// Cmd/Ctrl+Alt+` shows or hides the pane
+fn toggle_composites_pane(keyboard: Res<ButtonInput<KeyCode>>, mut panel: ResMut<CompositesPanel>) {
+    if Shortcut::Composites.just_pressed(&keyboard) {
+        panel.visible = !panel.visible;
+        debug!("Composites pane shown: {}", panel.visible);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::state::GlyphData;
+    use crate::data::glyph_recipes::parse_recipes;
+
+    #[test]
+    fn test_missing_recipes_and_choices() {
+        let recipes = parse_recipes("Aacute=A+acutecomb@top|00C1\nAgrave=A+gravecomb@top|00C0");
+        let mut font = FontData::default();
+        assert_eq!(missing_recipes(&font, &recipes), ["Aacute", "Agrave"]);
+        let agrave = GlyphData {
+            unicode_values: vec!['\u{00C0}'],
+            ..GlyphData::test("Agrave", 600.0)
+        };
+        font.glyphs.insert(agrave.name.clone(), agrave);
+        assert_eq!(missing_recipes(&font, &recipes), ["Aacute"]);
+
+        let mut panel = CompositesPanel::default();
+        panel.toggle("Aacute");
+        panel.toggle("Agrave");
+        panel.toggle("Agrave");
+        assert_eq!(panel.chosen.iter().collect::<Vec<_>>(), ["Aacute"]);
+    }
+}
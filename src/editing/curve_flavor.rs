@@ -0,0 +1,198 @@
+//! Converting outlines between cubic and quadratic curves
+//!
+//! Cmd/Ctrl+Alt+; shows or hides the curve flavor pane. It tells which
+//! curves the active sort's glyph has and converts it, or every glyph of the
+//! layer being edited, to quadratic within a maximum error or to cubic (see
+//! `font_source::curve_flavor`). While the pane is shown, the points the
+//! active glyph would get are previewed on the canvas, and the pane counts
+//! how many points either conversion adds.
+//!
+//! Converting here changes the open source only. Export makes the curves of
+//! a copy quadratic by itself, every master compatibly, so the sources of a
+//! variable font can stay cubic.
+
+use crate::core::state::{AppState, ContourData, FontData};
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::font_source::curve_flavor::{
+    added_points, step_max_error, to_cubic, to_quadratic, CurveFlavor, DEFAULT_MAX_ERROR,
+};
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use bevy::prelude::*;
+
+/// Whether the curve flavor pane is shown, and how it converts
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct CurveFlavorPanel {
+    pub visible: bool,
+    /// Furthest quadratic curves may stray from cubic ones, in font units
+    pub max_error: f64,
+    /// Points converting every glyph would add, to quadratic and to cubic;
+    /// counted when the pane opens and after it changes something
+    pub font_added: Option<(isize, isize)>,
+}
+
+impl Default for CurveFlavorPanel {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            max_error: DEFAULT_MAX_ERROR,
+            font_added: None,
+        }
+    }
+}
+
+/// What the curve flavor pane does
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub enum CurveFlavorEvent {
+    /// Raise (`true`) or lower the maximum error by a step
+    StepMaxError(bool),
+    /// Make the active glyph, or every glyph, quadratic
+    ToQuadratic { whole_font: bool },
+    /// Make the active glyph, or every glyph, cubic
+    ToCubic { whole_font: bool },
+}
+
+pub struct CurveFlavorPlugin;
+
+impl Plugin for CurveFlavorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CurveFlavorPanel>()
+            .add_event::<CurveFlavorEvent>()
+            .add_systems(
+                Update,
+                (toggle_curve_flavor, handle_curve_flavor_events).chain(),
+            );
+    }
+}
+
+/// Cmd/Ctrl+Alt+; shows or hides the pane
+fn toggle_curve_flavor(keyboard: Res<ButtonInput<KeyCode>>, mut panel: ResMut<CurveFlavorPanel>) {
+    if Shortcut::CurveFlavor.just_pressed(&keyboard) {
+        panel.visible = !panel.visible;
+        // Counted again when it opens, as the font may have changed
+        panel.font_added = None;
+        debug!("Curve flavor pane shown: {}", panel.visible);
+    }
+}
+
+/// An outline as converting it to the other flavor would draw it: quadratic
+/// when it has cubic curves, cubic when it only has quadratic ones, and
+/// `None` when it only has lines or can't be made quadratic within
+/// `max_error`
+pub fn conversion_preview(contours: &[ContourData], max_error: f64) -> Option<Vec<ContourData>> {
+    match CurveFlavor::of(contours) {
+        CurveFlavor::Lines => None,
+        CurveFlavor::Cubic | CurveFlavor::Mixed => to_quadratic(contours, max_error).ok(),
+        CurveFlavor::Quadratic => Some(to_cubic(contours)),
+    }
+}
+
+/// Points converting every glyph would add, to quadratic and to cubic;
+/// glyphs that can't be made quadratic within `max_error` add none
+fn count_font_added(font: &FontData, max_error: f64) -> (isize, isize) {
+    font.editing()
+        .glyphs
+        .values()
+        .filter_map(|glyph| glyph.outline.as_ref())
+        .map(|outline| {
+            let contours = &outline.contours;
+            (
+                to_quadratic(contours, max_error)
+                    .map_or(0, |converted| added_points(contours, &converted)),
+                added_points(contours, &to_cubic(contours)),
+            )
+        })
+        .fold((0, 0), |(a, b), (c, d)| (a + c, b + d))
+}
+
+/// Convert the outlines of some glyphs, and return how many changed.
+/// Glyphs that fail to convert are left as they are
+fn convert_glyphs<'a>(
+    font: &mut FontData,
+    names: impl IntoIterator<Item = &'a String>,
+    convert: impl Fn(&[ContourData]) -> anyhow::Result<Vec<ContourData>>,
+) -> usize {
+    let mut changed = 0;
+    for name in names {
+        let Some(outline) = font
+            .editing_glyph_mut(name)
+            .and_then(|glyph| glyph.outline.as_mut())
+        else {
+            continue;
+        };
+        let converted = match convert(&outline.contours) {
+            Ok(converted) => converted,
+            Err(error) => {
+                warn!("Left '{}' as it is: {}", name, error);
+                continue;
+            }
+        };
+        if converted != outline.contours {
+            outline.contours = converted;
+            changed += 1;
+        }
+    }
+    changed
+}
+
+fn handle_curve_flavor_events(
+    mut events: EventReader<CurveFlavorEvent>,
+    mut panel: ResMut<CurveFlavorPanel>,
+    mut app_state: Option<ResMut<AppState>>,
+    active_sorts: Query<&Sort, With<ActiveSort>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    let Some(state) = app_state.as_mut() else {
+        events.clear();
+        return;
+    };
+    let font = &mut state.workspace.font;
+    let active_glyph = active_sorts
+        .single()
+        .ok()
+        .map(|sort| sort.glyph_name.clone());
+
+    for event in events.read() {
+        let max_error = panel.max_error;
+        let (whole_font, to_quadratic_flavor) = match *event {
+            CurveFlavorEvent::StepMaxError(forward) => {
+                panel.max_error = step_max_error(max_error, forward);
+                panel.font_added = None;
+                continue;
+            }
+            CurveFlavorEvent::ToQuadratic { whole_font } => (whole_font, true),
+            CurveFlavorEvent::ToCubic { whole_font } => (whole_font, false),
+        };
+        let names: Vec<String> = if whole_font {
+            font.glyphs.keys().cloned().collect()
+        } else {
+            active_glyph.iter().cloned().collect()
+        };
+        let changed = if to_quadratic_flavor {
+            convert_glyphs(font, &names, |contours| to_quadratic(contours, max_error))
+        } else {
+            convert_glyphs(font, &names, |contours| Ok(to_cubic(contours)))
+        };
+        if changed == 0 {
+            continue;
+        }
+        info!(
+            "Made {} glyphs {}",
+            changed,
+            if to_quadratic_flavor {
+                "quadratic"
+            } else {
+                "cubic"
+            }
+        );
+        panel.font_added = None;
+        app_state_changed.write(AppStateChanged);
+        visual_update_tracker.needs_update = true;
+    }
+
+    if panel.visible && panel.font_added.is_none() {
+        panel.font_added = Some(count_font_added(font, panel.max_error));
+    }
+}
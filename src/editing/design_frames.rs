@@ -0,0 +1,83 @@
+//! CJK design frames inside each sort's em box
+//!
+//! Cmd/Ctrl+Alt+F shows or hides the project's design frames (see
+//! `font_source::design_frames`) in every sort. While they are shown, the
+//! design frames pane turns the center lines and the third grid on and off
+//! and adds, resizes and removes inner frames. Changes are saved with the
+//! font.
+
+use crate::core::state::AppState;
+use crate::editing::selection::events::AppStateChanged;
+use crate::io::shortcuts::Shortcut;
+use bevy::prelude::*;
+
+/// Whether the design frames are shown
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct DesignFramesOverlay {
+    pub visible: bool,
+}
+
+/// Changes to the project's design frames, inner frames by index
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum DesignFramesEvent {
+    ToggleCenterLines,
+    ToggleThirdGrid,
+    /// Add an inner frame of this percentage of the em
+    AddInnerFrame(f64),
+    /// Grow or shrink an inner frame by this many percent
+    ResizeInnerFrame(usize, f64),
+    RemoveInnerFrame(usize),
+}
+
+pub struct DesignFramesPlugin;
+
+impl Plugin for DesignFramesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DesignFramesOverlay>()
+            .add_event::<DesignFramesEvent>()
+            .add_systems(
+                Update,
+                (toggle_design_frames, handle_design_frames_events).chain(),
+            );
+    }
+}
+
+/// Cmd/Ctrl+Alt+F shows or hides the frames
+fn toggle_design_frames(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<DesignFramesOverlay>,
+) {
+    if Shortcut::DesignFrames.just_pressed(&keyboard) {
+        overlay.visible = !overlay.visible;
+        debug!("Design frames shown: {}", overlay.visible);
+    }
+}
+
+fn handle_design_frames_events(
+    mut events: EventReader<DesignFramesEvent>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+) {
+    let Some(state) = app_state.as_mut() else {
+        events.clear();
+        return;
+    };
+
+    for event in events.read() {
+        let frames = &mut state.workspace.font.design_frames;
+        match *event {
+            DesignFramesEvent::ToggleCenterLines => frames.center_lines = !frames.center_lines,
+            DesignFramesEvent::ToggleThirdGrid => frames.third_grid = !frames.third_grid,
+            DesignFramesEvent::AddInnerFrame(percent) => frames.inner_frames.push(percent),
+            DesignFramesEvent::ResizeInnerFrame(index, delta) => {
+                frames.resize_inner_frame(index, delta)
+            }
+            DesignFramesEvent::RemoveInnerFrame(index) => {
+                if index < frames.inner_frames.len() {
+                    frames.inner_frames.remove(index);
+                }
+            }
+        }
+        app_state_changed.write(AppStateChanged);
+    }
+}
@@ -0,0 +1,206 @@
+//! Editing the designspace's substitution rules
+//!
+//! When the UFO being edited is a source of a designspace in its folder,
+//! Cmd/Ctrl+Alt+J shows the rules pane for the designspace's rules (see
+//! `font_source::designspace_rules`). With two sorts selected, a new rule
+//! replaces the left sort's glyph with the right one's in the upper half of
+//! the first axis; the bounds of its conditions are then stepped along
+//! their axes. The pane previews a location, stepped along each axis, to
+//! show where the rules kick in. Rules follow renamed glyphs, and are
+//! written to the designspace when the font is saved.
+
+use crate::core::state::AppState;
+use crate::editing::font_changes::GlyphsRenamed;
+use crate::editing::selection::components::Selected;
+use crate::editing::sort::Sort;
+use crate::font_source::designspace_rules::{DesignspaceRules, Location};
+use crate::io::shortcuts::Shortcut;
+use crate::systems::commands::SaveFileEvent;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// The designspace's rules and the location previewed in the rules pane
+#[derive(Resource, Default)]
+pub struct DesignspaceRulesState {
+    /// Rules of the font's designspace, `None` without one
+    pub rules: Option<DesignspaceRules>,
+    pub location: Location,
+    pub visible: bool,
+    /// Whether the rules changed since they were read or written
+    unsaved: bool,
+}
+
+/// Changes to the rules and the previewed location, rules and axes by index
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum DesignspaceRulesEvent {
+    /// Replace the left selected sort's glyph with the right one's
+    AddFromSelection,
+    RemoveRule(usize),
+    AddCondition(usize),
+    /// Move the minimum or maximum of a condition in a rule's first set
+    StepCondition {
+        rule: usize,
+        condition: usize,
+        maximum: bool,
+        forward: bool,
+    },
+    RemoveCondition(usize, usize),
+    StepLocation(usize, bool),
+}
+
+pub struct DesignspaceRulesPlugin;
+
+impl Plugin for DesignspaceRulesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DesignspaceRulesState>()
+            .add_event::<DesignspaceRulesEvent>()
+            .add_systems(
+                Update,
+                (
+                    load_designspace_rules,
+                    toggle_designspace_rules,
+                    handle_designspace_rules_events,
+                    follow_renamed_glyphs,
+                    save_designspace_rules,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Reads the rules whenever another font is opened
+fn load_designspace_rules(
+    mut loaded_for: Local<Option<Option<PathBuf>>>,
+    app_state: Option<Res<AppState>>,
+    mut rules_state: ResMut<DesignspaceRulesState>,
+) {
+    let font_path = app_state.and_then(|state| state.workspace.font.path.clone());
+    if loaded_for.as_ref() == Some(&font_path) {
+        return;
+    }
+    *loaded_for = Some(font_path.clone());
+
+    let rules = match font_path.as_deref().map(DesignspaceRules::find_for_ufo) {
+        Some(Ok(rules)) => rules,
+        Some(Err(error)) => {
+            warn!("Designspace rules not loaded: {error:#}");
+            None
+        }
+        None => None,
+    };
+    if let Some(rules) = &rules {
+        debug!(
+            "Loaded {} rules from {}",
+            rules.rules.len(),
+            rules.path.display()
+        );
+    }
+    rules_state.location = rules
+        .as_ref()
+        .map(DesignspaceRules::default_location)
+        .unwrap_or_default();
+    rules_state.rules = rules;
+    rules_state.unsaved = false;
+}
+
+/// Cmd/Ctrl+Alt+J shows or hides the rules pane
+fn toggle_designspace_rules(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut rules_state: ResMut<DesignspaceRulesState>,
+) {
+    if Shortcut::DesignspaceRules.just_pressed(&keyboard) {
+        rules_state.visible = !rules_state.visible;
+        debug!("Designspace rules shown: {}", rules_state.visible);
+    }
+}
+
+fn handle_designspace_rules_events(
+    mut events: EventReader<DesignspaceRulesEvent>,
+    selected_sorts: Query<(&Sort, &Transform), With<Selected>>,
+    mut rules_state: ResMut<DesignspaceRulesState>,
+) {
+    let state = rules_state.as_mut();
+    let Some(rules) = state.rules.as_mut() else {
+        events.clear();
+        return;
+    };
+
+    for event in events.read() {
+        match *event {
+            DesignspaceRulesEvent::AddFromSelection => {
+                let mut sorts: Vec<_> = selected_sorts.iter().collect();
+                if sorts.len() != 2 {
+                    warn!("Select two sorts to add a rule replacing one glyph with the other");
+                    continue;
+                }
+                sorts.sort_by(|(_, a), (_, b)| a.translation.x.total_cmp(&b.translation.x));
+                rules.add_rule(&sorts[0].0.glyph_name, &sorts[1].0.glyph_name);
+            }
+            DesignspaceRulesEvent::RemoveRule(index) => {
+                if index >= rules.rules.len() {
+                    continue;
+                }
+                rules.rules.remove(index);
+            }
+            DesignspaceRulesEvent::AddCondition(index) => rules.add_condition(index),
+            DesignspaceRulesEvent::StepCondition {
+                rule,
+                condition,
+                maximum,
+                forward,
+            } => rules.step_condition(rule, condition, maximum, forward),
+            DesignspaceRulesEvent::RemoveCondition(rule, condition) => {
+                let Some(set) = rules
+                    .rules
+                    .get_mut(rule)
+                    .and_then(|rule| rule.condition_sets.first_mut())
+                    .filter(|set| condition < set.len())
+                else {
+                    continue;
+                };
+                set.remove(condition);
+            }
+            DesignspaceRulesEvent::StepLocation(index, forward) => {
+                if let Some(axis) = rules.axes.get(index) {
+                    let value = state.location.entry(axis.name.clone()).or_default();
+                    *value = axis.step(*value, forward);
+                }
+                continue;
+            }
+        }
+        state.unsaved = true;
+    }
+}
+
+/// Renames glyphs in the substitutions as they are renamed in the font
+fn follow_renamed_glyphs(
+    mut renamed_glyphs: EventReader<GlyphsRenamed>,
+    mut rules_state: ResMut<DesignspaceRulesState>,
+) {
+    for GlyphsRenamed(renames) in renamed_glyphs.read() {
+        let state = rules_state.as_mut();
+        if let Some(rules) = state.rules.as_mut() {
+            state.unsaved |= rules.rename_glyphs(renames);
+        }
+    }
+}
+
+/// Writes changed rules to the designspace along with the font
+fn save_designspace_rules(
+    mut saves: EventReader<SaveFileEvent>,
+    mut rules_state: ResMut<DesignspaceRulesState>,
+) {
+    if saves.read().count() == 0 || !rules_state.unsaved {
+        return;
+    }
+    let Some(rules) = rules_state.rules.as_ref() else {
+        return;
+    };
+    match rules.save() {
+        Ok(()) => {
+            info!("Saved designspace rules to {}", rules.path.display());
+            rules_state.unsaved = false;
+        }
+        Err(error) => error!("Saving designspace rules failed: {error:#}"),
+    }
+}
@@ -0,0 +1,543 @@
+//! Find and replace across the font
+//!
+//! A replacement is a two-step flow: `FindReplacePreviewEvent` lists the
+//! glyphs a query would change (kept in `FindReplaceState::preview` for the
+//! UI), then `ApplyReplaceEvent` performs the previewed query. Every applied
+//! replacement is recorded so `UndoReplaceEvent` can revert it in one step.
+//!
+//! Cmd/Ctrl+F shows the find/replace pane, which builds the query from
+//! what's typed into its find and replace fields and sends these events.
+
+use crate::core::state::{AppState, SortKind, TextEditorState};
+use crate::editing::font_changes::GlyphsRenamed;
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::sort::Sort;
+use crate::editing::text_field_focus::{TextField, TextFieldFocus, TextFieldSystems};
+use crate::font_source::find_replace::{self, Axis, ReplaceMatch, ReplaceQuery, ReplaceUndo};
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Number of replacements kept for undo
+const MAX_UNDO_STEPS: usize = 32;
+
+/// How far from the coordinate found a point may be, in font units
+const DEFAULT_TOLERANCE: f64 = 0.5;
+
+/// Pending preview and undo history
+#[derive(Resource, Default)]
+pub struct FindReplaceState {
+    /// The query last previewed and the glyphs it would change
+    pub preview: Option<(ReplaceQuery, Vec<ReplaceMatch>)>,
+    history: Vec<ReplaceUndo>,
+}
+
+impl FindReplaceState {
+    pub fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+}
+
+/// What the find/replace pane looks for
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FindReplaceKind {
+    #[default]
+    GlyphName,
+    AnchorName,
+    ComponentReference,
+    Coordinate,
+}
+
+impl FindReplaceKind {
+    pub fn label(self) -> String {
+        let id = match self {
+            FindReplaceKind::GlyphName => "find-replace-glyph-names",
+            FindReplaceKind::AnchorName => "find-replace-anchor-names",
+            FindReplaceKind::ComponentReference => "find-replace-components",
+            FindReplaceKind::Coordinate => "find-replace-coordinates",
+        };
+        crate::i18n::tr(id)
+    }
+
+    /// The kind after this one, wrapping around
+    pub fn next(self) -> Self {
+        match self {
+            FindReplaceKind::GlyphName => FindReplaceKind::AnchorName,
+            FindReplaceKind::AnchorName => FindReplaceKind::ComponentReference,
+            FindReplaceKind::ComponentReference => FindReplaceKind::Coordinate,
+            FindReplaceKind::Coordinate => FindReplaceKind::GlyphName,
+        }
+    }
+}
+
+/// A text field of the find/replace pane
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FindReplaceField {
+    Find,
+    Replace,
+}
+
+/// What the find/replace pane shows and what's typed into it
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct FindReplacePanel {
+    pub visible: bool,
+    pub kind: FindReplaceKind,
+    pub find: String,
+    pub replace: String,
+    /// Axis and tolerance of coordinate queries
+    pub axis: Axis,
+    pub tolerance: f64,
+    /// Field being typed into, if any
+    pub typing: Option<FindReplaceField>,
+}
+
+impl Default for FindReplacePanel {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            kind: FindReplaceKind::default(),
+            find: String::new(),
+            replace: String::new(),
+            axis: Axis::Y,
+            tolerance: DEFAULT_TOLERANCE,
+            typing: None,
+        }
+    }
+}
+
+impl FindReplacePanel {
+    /// The query the fields describe; coordinates have to be numbers
+    pub fn query(&self) -> Option<ReplaceQuery> {
+        let find = self.find.clone();
+        let replace = self.replace.clone();
+        Some(match self.kind {
+            FindReplaceKind::GlyphName => ReplaceQuery::GlyphName { find, replace },
+            FindReplaceKind::AnchorName => ReplaceQuery::AnchorName { find, replace },
+            FindReplaceKind::ComponentReference => {
+                ReplaceQuery::ComponentReference { find, replace }
+            }
+            FindReplaceKind::Coordinate => ReplaceQuery::Coordinate {
+                axis: self.axis,
+                from: find.trim().parse().ok()?,
+                to: replace.trim().parse().ok()?,
+                tolerance: self.tolerance,
+            },
+        })
+    }
+
+    fn field_mut(&mut self, field: FindReplaceField) -> &mut String {
+        match field {
+            FindReplaceField::Find => &mut self.find,
+            FindReplaceField::Replace => &mut self.replace,
+        }
+    }
+}
+
+/// Work out what a query would change without applying it
+#[derive(Event, Clone)]
+pub struct FindReplacePreviewEvent(pub ReplaceQuery);
+
+/// Apply the previewed query
+#[derive(Event, Clone)]
+pub struct ApplyReplaceEvent;
+
+/// Revert the most recent replacement
+#[derive(Event, Clone)]
+pub struct UndoReplaceEvent;
+
+pub struct FindReplacePlugin;
+
+impl Plugin for FindReplacePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FindReplaceState>()
+            .init_resource::<FindReplacePanel>()
+            .add_event::<FindReplacePreviewEvent>()
+            .add_event::<ApplyReplaceEvent>()
+            .add_event::<UndoReplaceEvent>()
+            .add_systems(
+                Update,
+                (
+                    toggle_find_replace_pane,
+                    find_replace_keys.in_set(TextFieldSystems),
+                    handle_find_replace_events,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Cmd/Ctrl+F shows or hides the pane
+fn toggle_find_replace_pane(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut panel: ResMut<FindReplacePanel>,
+) {
+    if Shortcut::FindReplace.just_pressed(&keyboard) {
+        panel.visible = !panel.visible;
+        panel.typing = None;
+        debug!("Find/replace pane shown: {}", panel.visible);
+    }
+}
+
+/// Types into the field being edited; Tab swaps fields, Enter previews the
+/// query and Escape stops typing
+fn find_replace_keys(
+    mut key_events: EventReader<KeyboardInput>,
+    mut focus: ResMut<TextFieldFocus>,
+    mut panel: ResMut<FindReplacePanel>,
+    mut preview_events: EventWriter<FindReplacePreviewEvent>,
+) {
+    focus.set(TextField::FindReplace, panel.typing.is_some());
+    let Some(mut field) = panel.typing else {
+        key_events.clear();
+        return;
+    };
+    let mut edited = panel.clone();
+    let mut preview = false;
+    for event in key_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(text) => edited
+                .field_mut(field)
+                .extend(text.chars().filter(|c| !c.is_control())),
+            Key::Space => edited.field_mut(field).push(' '),
+            Key::Backspace => {
+                edited.field_mut(field).pop();
+            }
+            Key::Tab => {
+                field = match field {
+                    FindReplaceField::Find => FindReplaceField::Replace,
+                    FindReplaceField::Replace => FindReplaceField::Find,
+                };
+                edited.typing = Some(field);
+            }
+            Key::Enter => {
+                edited.typing = None;
+                preview = true;
+            }
+            Key::Escape => edited.typing = None,
+            _ => {}
+        }
+    }
+
+    if preview {
+        match edited.query() {
+            Some(query) => {
+                preview_events.write(FindReplacePreviewEvent(query));
+            }
+            None => warn!("Coordinates to find and replace have to be numbers"),
+        }
+    }
+    if *panel != edited {
+        *panel = edited;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_find_replace_events(
+    mut preview_events: EventReader<FindReplacePreviewEvent>,
+    mut apply_events: EventReader<ApplyReplaceEvent>,
+    mut undo_events: EventReader<UndoReplaceEvent>,
+    mut find_state: ResMut<FindReplaceState>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut text_editor_state: Option<ResMut<TextEditorState>>,
+    mut sort_query: Query<&mut Sort>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut glyphs_renamed: EventWriter<GlyphsRenamed>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    let Some(state) = app_state.as_mut() else {
+        preview_events.clear();
+        apply_events.clear();
+        undo_events.clear();
+        return;
+    };
+    let font = &mut state.workspace.font;
+    let mut renames: Vec<HashMap<String, String>> = Vec::new();
+    let mut changed = false;
+
+    for FindReplacePreviewEvent(query) in preview_events.read() {
+        let matches = find_replace::preview(font, query);
+        info!("Find/replace would change {} glyph(s)", matches.len());
+        for found in &matches {
+            debug!("  {}: {}", found.glyph_name, found.detail);
+        }
+        find_state.preview = Some((query.clone(), matches));
+    }
+
+    for _ in apply_events.read() {
+        let Some((query, matches)) = find_state.preview.take() else {
+            warn!("Nothing to replace: preview a query first");
+            continue;
+        };
+        if matches.is_empty() {
+            continue;
+        }
+        let undo = match find_replace::apply(font, &query) {
+            Ok(undo) => undo,
+            Err(error) => {
+                warn!("Nothing replaced: {error:#}");
+                continue;
+            }
+        };
+        info!("Replaced in {} glyph(s)", matches.len());
+        renames.push(undo.renames.clone());
+        find_state.history.push(undo);
+        if find_state.history.len() > MAX_UNDO_STEPS {
+            find_state.history.remove(0);
+        }
+        changed = true;
+    }
+
+    for _ in undo_events.read() {
+        let Some(undo) = find_state.history.pop() else {
+            debug!("No find/replace to undo");
+            continue;
+        };
+        let back: HashMap<String, String> = undo
+            .renames
+            .iter()
+            .map(|(old, new)| (new.clone(), old.clone()))
+            .collect();
+        if let Err(error) = find_replace::undo(font, undo) {
+            warn!("Could not undo the last find/replace: {error:#}");
+            continue;
+        }
+        renames.push(back);
+        info!("Undid last find/replace");
+        changed = true;
+    }
+
+    // Keep sorts pointing at renamed glyphs
+    for renames in renames.into_iter().filter(|renames| !renames.is_empty()) {
+        for mut sort in sort_query.iter_mut() {
+            if let Some(new) = renames.get(&sort.glyph_name) {
+                sort.glyph_name = new.clone();
+            }
+        }
+        if let Some(text_editor) = text_editor_state.as_mut() {
+            for index in 0..text_editor.buffer.len() {
+                if let Some(SortKind::Glyph { glyph_name, .. }) =
+                    text_editor.buffer.get_mut(index).map(|sort| &mut sort.kind)
+                {
+                    if let Some(new) = renames.get(glyph_name) {
+                        *glyph_name = new.clone();
+                    }
+                }
+            }
+        }
+        glyphs_renamed.write(GlyphsRenamed(renames));
+    }
+
+    if changed {
+        app_state_changed.write(AppStateChanged);
+        visual_update_tracker.needs_update = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::state::text_editor::buffer::BufferId;
+    use crate::core::state::text_editor::text_buffer::{
+        ActiveTextBuffer, BufferCursor, TextBuffer,
+    };
+    use crate::core::state::text_editor::{SortData, SortLayoutMode};
+    use crate::editing::selection::components::{Selected, SelectionState};
+    use crate::editing::sort::group::handle_sort_group_shortcuts;
+    use crate::editing::sort::{AlignSortsEvent, DeleteSelectedSortsEvent};
+    use crate::editing::text_field_focus::TextFieldFocusPlugin;
+    use crate::systems::sorts::{
+        handle_unicode_text_input, insert_sorts, AlternatesPicker, BufferSortRespawnQueue,
+        CharacterPicker, ImeComposition, SpacingMode,
+    };
+    use crate::ui::edit_mode_toolbar::text::TextPlacementMode;
+    use crate::ui::edit_mode_toolbar::CurrentTool;
+    use bevy::input::InputPlugin;
+
+    /// The find/replace keys and the text tool's typing, with "abcd" in the
+    /// active buffer and "bc" selected
+    fn typing_app(typing: Option<FindReplaceField>) -> App {
+        let buffer = TextBuffer::new(BufferId(1), SortLayoutMode::LTRText, Vec2::ZERO);
+        let sorts: Vec<SortData> = "abcd"
+            .chars()
+            .map(|c| SortData {
+                kind: SortKind::Glyph {
+                    codepoint: Some(c),
+                    glyph_name: c.to_string(),
+                    advance_width: 100.0,
+                },
+                ..Default::default()
+            })
+            .collect();
+        let mut text_editor_state = TextEditorState::default();
+        insert_sorts(&mut text_editor_state, &buffer, 0, &sorts);
+        let mut cursor = BufferCursor::new(1);
+        cursor.move_to(3, true);
+
+        let mut app = App::new();
+        app.add_plugins(TextFieldFocusPlugin)
+            .add_event::<KeyboardInput>()
+            .add_event::<FindReplacePreviewEvent>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .insert_resource(FindReplacePanel {
+                visible: true,
+                typing,
+                ..default()
+            })
+            .insert_resource(text_editor_state)
+            .insert_resource(CurrentTool {
+                current: Some("text"),
+                previous: None,
+            })
+            .init_resource::<TextPlacementMode>()
+            .init_resource::<BufferSortRespawnQueue>()
+            .init_resource::<ImeComposition>()
+            .init_resource::<AlternatesPicker>()
+            .init_resource::<CharacterPicker>()
+            .init_resource::<SpacingMode>()
+            .add_systems(
+                Update,
+                (
+                    find_replace_keys.in_set(TextFieldSystems),
+                    handle_unicode_text_input,
+                ),
+            );
+        let entity = app.world_mut().spawn((buffer, cursor)).id();
+        app.insert_resource(ActiveTextBuffer {
+            buffer_entity: Some(entity),
+        });
+        app
+    }
+
+    fn press(app: &mut App, key_code: KeyCode, logical_key: Key) {
+        app.world_mut().send_event(KeyboardInput {
+            key_code,
+            logical_key,
+            state: ButtonState::Pressed,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+
+    fn buffer_text(app: &App) -> String {
+        app.world()
+            .resource::<TextEditorState>()
+            .buffer
+            .iter()
+            .filter_map(|sort| sort.kind.codepoint())
+            .collect()
+    }
+
+    #[test]
+    fn test_typing_into_a_field_leaves_the_buffer_alone() {
+        let mut app = typing_app(Some(FindReplaceField::Find));
+        press(&mut app, KeyCode::KeyQ, Key::Character("q".into()));
+        press(&mut app, KeyCode::Backspace, Key::Backspace);
+        press(&mut app, KeyCode::KeyR, Key::Character("r".into()));
+        app.update();
+
+        assert_eq!(app.world().resource::<FindReplacePanel>().find, "r");
+        assert_eq!(buffer_text(&app), "abcd");
+        assert!(app.world().resource::<TextFieldFocus>().is_focused());
+    }
+
+    #[test]
+    fn test_typing_reaches_the_buffer_without_a_field() {
+        let mut app = typing_app(None);
+        press(&mut app, KeyCode::Backspace, Key::Backspace);
+        app.update();
+
+        assert_eq!(buffer_text(&app), "ad");
+        assert!(!app.world().resource::<TextFieldFocus>().is_focused());
+    }
+
+    /// Deletions of the selected sorts after Backspace is pressed with the
+    /// find field typed into, or with no field, and the select tool on
+    fn sort_deletions_after_backspace(typing: Option<FindReplaceField>) -> usize {
+        let mut app = App::new();
+        app.add_plugins((InputPlugin, TextFieldFocusPlugin))
+            .add_event::<FindReplacePreviewEvent>()
+            .add_event::<AlignSortsEvent>()
+            .add_event::<DeleteSelectedSortsEvent>()
+            .insert_resource(FindReplacePanel {
+                visible: true,
+                typing,
+                ..default()
+            })
+            .init_resource::<SelectionState>()
+            .insert_resource(CurrentTool {
+                current: Some("select"),
+                previous: None,
+            })
+            .add_systems(
+                Update,
+                (
+                    find_replace_keys.in_set(TextFieldSystems),
+                    handle_sort_group_shortcuts,
+                ),
+            );
+        app.world_mut().spawn((
+            Sort {
+                glyph_name: "a".to_string(),
+                layout_mode: SortLayoutMode::Freeform,
+            },
+            Selected,
+        ));
+        // The field is marked focused on the first frame
+        app.update();
+
+        press(&mut app, KeyCode::Backspace, Key::Backspace);
+        app.update();
+        app.world()
+            .resource::<Events<DeleteSelectedSortsEvent>>()
+            .len()
+    }
+
+    #[test]
+    fn test_backspace_in_a_field_keeps_the_selected_sorts() {
+        // Shortcuts reading the keyboard aren't ordered after the field,
+        // so the key has to be held off before any of them runs
+        assert_eq!(
+            sort_deletions_after_backspace(Some(FindReplaceField::Find)),
+            0
+        );
+        assert_eq!(sort_deletions_after_backspace(None), 1);
+    }
+
+    #[test]
+    fn test_panel_builds_queries() {
+        let mut panel = FindReplacePanel {
+            find: ".alt".to_string(),
+            replace: ".ss01".to_string(),
+            ..default()
+        };
+        assert_eq!(
+            panel.query(),
+            Some(ReplaceQuery::GlyphName {
+                find: ".alt".to_string(),
+                replace: ".ss01".to_string(),
+            })
+        );
+
+        panel.kind = FindReplaceKind::Coordinate;
+        assert_eq!(panel.query(), None);
+        panel.find = "700".to_string();
+        panel.replace = " 702.5".to_string();
+        assert_eq!(
+            panel.query(),
+            Some(ReplaceQuery::Coordinate {
+                axis: Axis::Y,
+                from: 700.0,
+                to: 702.5,
+                tolerance: DEFAULT_TOLERANCE,
+            })
+        );
+        assert_eq!(panel.kind.next(), FindReplaceKind::GlyphName);
+    }
+}
@@ -0,0 +1,90 @@
+//! Notifications of changes to the font
+//!
+//! Layout and rendering systems don't check the font every frame for
+//! edits. They run when one of these events says something they depend on
+//! changed:
+//! - `GlyphEdited` for a glyph's outline, components or advance
+//! - `MetricsChanged` for the font's vertical metrics
+//! - `KerningChanged` for kerning pairs and groups
+//! - `GlyphsRenamed` for renamed glyphs, so names kept outside the font
+//!   (designspace rules, saved glyphs) follow them
+//!
+//! Edits announced only with `AppStateChanged` or `EditEvent` are passed on
+//! as a `GlyphEdited` that may concern any glyph, and loading a font counts
+//! as a change to everything.
+
+use crate::core::state::AppState;
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::selection::nudge::EditEvent;
+use crate::editing::FontEditorSets;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// A glyph's outline, components or advance changed
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct GlyphEdited {
+    /// The edited glyph, or `None` when any glyph may have changed
+    pub glyph_name: Option<String>,
+}
+
+impl GlyphEdited {
+    pub fn glyph(glyph_name: impl Into<String>) -> Self {
+        Self {
+            glyph_name: Some(glyph_name.into()),
+        }
+    }
+
+    pub fn any() -> Self {
+        Self { glyph_name: None }
+    }
+}
+
+/// The font's vertical metrics changed
+#[derive(Event, Debug, Clone)]
+pub struct MetricsChanged;
+
+/// The font's kerning pairs or groups changed
+#[derive(Event, Debug, Clone)]
+pub struct KerningChanged;
+
+/// Glyphs were renamed, old name to new name
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct GlyphsRenamed(pub HashMap<String, String>);
+
+pub struct FontChangesPlugin;
+
+impl Plugin for FontChangesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<GlyphEdited>()
+            .add_event::<MetricsChanged>()
+            .add_event::<KerningChanged>()
+            .add_event::<GlyphsRenamed>()
+            .add_systems(
+                Update,
+                forward_untargeted_changes
+                    .after(FontEditorSets::Input)
+                    .before(FontEditorSets::TextBuffer),
+            );
+    }
+}
+
+/// Announce edits that didn't say what they changed, and newly loaded fonts
+fn forward_untargeted_changes(
+    app_state: Option<Res<AppState>>,
+    mut app_state_changes: EventReader<AppStateChanged>,
+    mut edit_events: EventReader<EditEvent>,
+    mut glyph_edits: EventWriter<GlyphEdited>,
+    mut metrics_changes: EventWriter<MetricsChanged>,
+    mut kerning_changes: EventWriter<KerningChanged>,
+) {
+    let loaded = app_state.is_some_and(|state| state.is_added());
+    let edits = app_state_changes.read().count() + edit_events.read().count();
+    if loaded || edits > 0 {
+        trace!(edits, loaded, "Forwarding font changes");
+        glyph_edits.write(GlyphEdited::any());
+    }
+    if loaded {
+        metrics_changes.write(MetricsChanged);
+        kerning_changes.write(KerningChanged);
+    }
+}
@@ -0,0 +1,272 @@
+//! Editing with a gamepad
+//!
+//! Start on the active gamepad switches its editing mode on and off, so
+//! points can be worked on from the couch. While it's on:
+//! - the left stick nudges the selected points, faster the further it's
+//!   pushed, and the right trigger is pressure: squeezing it scales the
+//!   nudge up to the Shift+arrow step
+//! - D-pad left and right select the previous and next point along the
+//!   selected point's contour; up and down jump to the next and previous
+//!   contour
+//! - the bumpers cycle backward and forward through the toolbar's tools
+
+use crate::core::config::BezySettings;
+use crate::editing::selection::components::{GlyphPointReference, Selected, SelectionState};
+use crate::editing::selection::NudgeSelectionEvent;
+use crate::editing::sort::manager::SortPointEntity;
+use crate::editing::sort::ActiveSort;
+use crate::io::gamepad::{get_gamepad_movement, GamepadManager};
+use crate::tools::{SwitchToolEvent, ToolId, ToolState};
+use crate::ui::edit_mode_toolbar::ToolConfig;
+use bevy::prelude::*;
+
+/// Nudge speed with the stick pushed all the way, in nudge steps a second
+const STICK_STEPS_PER_SECOND: f32 = 20.0;
+
+/// Whether the gamepad edits points
+#[derive(Resource, Debug, Default)]
+pub struct GamepadEditing {
+    pub active: bool,
+}
+
+/// A move of the selection through the points of the active glyph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointStep {
+    PreviousPoint,
+    NextPoint,
+    PreviousContour,
+    NextContour,
+}
+
+pub struct GamepadEditingPlugin;
+
+impl Plugin for GamepadEditingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GamepadEditing>().add_systems(
+            Update,
+            (
+                toggle_gamepad_editing,
+                (gamepad_nudge, gamepad_point_steps, gamepad_tool_cycling)
+                    .run_if(|editing: Res<GamepadEditing>| editing.active),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// The point `step` leads to from `from`, as (contour, point) indices, in
+/// contours of `contour_lengths` points; empty contours are skipped
+pub fn step_point(
+    contour_lengths: &[usize],
+    from: Option<(usize, usize)>,
+    step: PointStep,
+) -> Option<(usize, usize)> {
+    let first_point = |contour: usize| (contour_lengths[contour] > 0).then_some((contour, 0));
+    let count = contour_lengths.len();
+    let Some((contour, point)) = from.filter(|(contour, point)| {
+        contour_lengths
+            .get(*contour)
+            .is_some_and(|&length| *point < length)
+    }) else {
+        return (0..count).find_map(first_point);
+    };
+
+    let length = contour_lengths[contour];
+    match step {
+        PointStep::NextPoint => Some((contour, (point + 1) % length)),
+        PointStep::PreviousPoint => Some((contour, (point + length - 1) % length)),
+        PointStep::NextContour => {
+            (1..=count).find_map(|offset| first_point((contour + offset) % count))
+        }
+        PointStep::PreviousContour => {
+            (1..=count).find_map(|offset| first_point((contour + count - offset) % count))
+        }
+    }
+}
+
+fn active_gamepad<'a>(
+    manager: &GamepadManager,
+    gamepads: &'a Query<&Gamepad>,
+) -> Option<&'a Gamepad> {
+    gamepads.get(manager.active_gamepad.as_ref()?.entity).ok()
+}
+
+fn toggle_gamepad_editing(
+    manager: Res<GamepadManager>,
+    gamepads: Query<&Gamepad>,
+    mut editing: ResMut<GamepadEditing>,
+) {
+    let Some(gamepad) = active_gamepad(&manager, &gamepads) else {
+        if editing.active {
+            editing.active = false;
+        }
+        return;
+    };
+    if gamepad.just_pressed(GamepadButton::Start) {
+        editing.active = !editing.active;
+        info!(
+            "Gamepad editing {}",
+            if editing.active { "on" } else { "off" }
+        );
+    }
+}
+
+/// Turns the left stick into whole-unit nudges, keeping the fractions for
+/// the next frames
+fn gamepad_nudge(
+    time: Res<Time>,
+    manager: Res<GamepadManager>,
+    settings: Res<BezySettings>,
+    mut remainder: Local<Vec2>,
+    mut nudges: EventWriter<NudgeSelectionEvent>,
+) {
+    let stick = get_gamepad_movement(&manager);
+    if stick == Vec2::ZERO {
+        *remainder = Vec2::ZERO;
+        return;
+    }
+
+    // Pushing further speeds up more than linearly, for fine control near
+    // the center
+    let pressure = manager.right_trigger.clamp(0.0, 1.0);
+    let step = settings.nudge.default + (settings.nudge.shift - settings.nudge.default) * pressure;
+    let speed = stick * stick.length() * step * STICK_STEPS_PER_SECOND;
+    *remainder += speed * time.delta_secs();
+    let whole = remainder.trunc();
+    if whole != Vec2::ZERO {
+        *remainder -= whole;
+        nudges.write(NudgeSelectionEvent(whole));
+    }
+}
+
+/// Selects the next or previous point or contour on the D-pad
+fn gamepad_point_steps(
+    manager: Res<GamepadManager>,
+    gamepads: Query<&Gamepad>,
+    mut selection_state: ResMut<SelectionState>,
+    active_sort: Query<Entity, With<ActiveSort>>,
+    points: Query<(
+        Entity,
+        &GlyphPointReference,
+        &SortPointEntity,
+        Has<Selected>,
+    )>,
+) {
+    let Some(gamepad) = active_gamepad(&manager, &gamepads) else {
+        return;
+    };
+    let steps = [
+        (GamepadButton::DPadLeft, PointStep::PreviousPoint),
+        (GamepadButton::DPadRight, PointStep::NextPoint),
+        (GamepadButton::DPadUp, PointStep::NextContour),
+        (GamepadButton::DPadDown, PointStep::PreviousContour),
+    ];
+    let Some(step) = steps
+        .iter()
+        .find(|(button, _)| gamepad.just_pressed(*button))
+        .map(|(_, step)| *step)
+    else {
+        return;
+    };
+    let Ok(sort_entity) = active_sort.single() else {
+        return;
+    };
+
+    let sort_points: Vec<_> = points
+        .iter()
+        .filter(|(_, _, sort_point, _)| sort_point.sort_entity == sort_entity)
+        .collect();
+    let mut contour_lengths = Vec::new();
+    for (_, reference, ..) in &sort_points {
+        if contour_lengths.len() <= reference.contour_index {
+            contour_lengths.resize(reference.contour_index + 1, 0);
+        }
+        let length = &mut contour_lengths[reference.contour_index];
+        *length = (*length).max(reference.point_index + 1);
+    }
+    let from = sort_points
+        .iter()
+        .filter(|(.., selected)| *selected)
+        .map(|(_, reference, ..)| (reference.contour_index, reference.point_index))
+        .min();
+
+    let Some(target) = step_point(&contour_lengths, from, step) else {
+        return;
+    };
+    let Some((entity, ..)) = sort_points
+        .iter()
+        .find(|(_, reference, ..)| (reference.contour_index, reference.point_index) == target)
+    else {
+        return;
+    };
+    // sync_selected_components updates the Selected components to match
+    selection_state.selected = [*entity].into_iter().collect();
+}
+
+/// Switches to the previous or next toolbar tool on the bumpers
+fn gamepad_tool_cycling(
+    manager: Res<GamepadManager>,
+    gamepads: Query<&Gamepad>,
+    tool_state: Res<ToolState>,
+    mut switch_events: EventWriter<SwitchToolEvent>,
+) {
+    let Some(gamepad) = active_gamepad(&manager, &gamepads) else {
+        return;
+    };
+    let direction = if gamepad.just_pressed(GamepadButton::RightTrigger) {
+        1
+    } else if gamepad.just_pressed(GamepadButton::LeftTrigger) {
+        -1
+    } else {
+        return;
+    };
+
+    let tools: Vec<ToolId> = ToolConfig::get_enabled_tools()
+        .into_iter()
+        .filter_map(|tool| ToolId::from_str(tool.id))
+        .collect();
+    if tools.is_empty() {
+        return;
+    }
+    let current = tools
+        .iter()
+        .position(|tool| tool_state.is_active(*tool))
+        .unwrap_or(0);
+    let next = (current as isize + direction).rem_euclid(tools.len() as isize) as usize;
+    switch_events.write(SwitchToolEvent {
+        tool: tools[next],
+        temporary: false,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_point() {
+        let contours = [4, 0, 3];
+        assert_eq!(
+            step_point(&contours, None, PointStep::NextPoint),
+            Some((0, 0))
+        );
+        assert_eq!(
+            step_point(&contours, Some((0, 3)), PointStep::NextPoint),
+            Some((0, 0))
+        );
+        assert_eq!(
+            step_point(&contours, Some((2, 0)), PointStep::PreviousPoint),
+            Some((2, 2))
+        );
+        // The empty contour is skipped both ways
+        assert_eq!(
+            step_point(&contours, Some((0, 2)), PointStep::NextContour),
+            Some((2, 0))
+        );
+        assert_eq!(
+            step_point(&contours, Some((0, 2)), PointStep::PreviousContour),
+            Some((2, 0))
+        );
+        assert_eq!(step_point(&[], None, PointStep::NextContour), None);
+    }
+}
@@ -0,0 +1,328 @@
+//! Glyph name autocomplete
+//!
+//! A popup for entering a glyph name, shared by everything that needs one:
+//! Cmd/Ctrl+Alt+G goes to a glyph, Cmd/Ctrl+Alt+Shift+K picks the left and
+//! right glyphs of a kerning pair and proofs them (see
+//! `systems::sorts::kern_proof`), and the component graph pane adds a
+//! component to the active glyph. Typing completes names by prefix, then
+//! fuzzily, or by the character a glyph encodes (see
+//! `data::glyph_completion`), and the highlighted glyph is previewed.
+//! Up/Down move, Enter or a click picks, Escape closes.
+
+use crate::core::state::{AppState, FontData};
+use crate::data::glyph_completion::{complete, GlyphCompletion};
+use crate::editing::goto_glyph::GoToGlyph;
+use crate::editing::text_field_focus::{FieldKeys, TextField, TextFieldFocus, TextFieldSystems};
+use crate::i18n;
+use crate::io::shortcuts::Shortcut;
+use crate::systems::sorts::KernProofRequest;
+use crate::systems::ui_interaction::UiHoverState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+
+/// Most glyphs listed for a query
+const MAX_COMPLETIONS: usize = 50;
+
+/// Rows shown at once
+pub const VISIBLE_ROWS: usize = 10;
+
+/// What a picked glyph name is for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GlyphNameTarget {
+    /// Show the glyph on the canvas
+    GoToGlyph,
+    /// Add the glyph as a component of the named glyph
+    Component(String),
+    /// The left glyph of a kerning pair
+    KernLeft,
+    /// The right glyph of a kerning pair, after the named left glyph
+    KernRight(String),
+}
+
+impl GlyphNameTarget {
+    /// What the popup asks for
+    pub fn prompt(&self) -> String {
+        match self {
+            GlyphNameTarget::GoToGlyph => i18n::tr("autocomplete-go-to-glyph"),
+            GlyphNameTarget::Component(glyph) => {
+                i18n::tr_args("autocomplete-add-component", &[("glyph", glyph)])
+            }
+            GlyphNameTarget::KernLeft => i18n::tr("autocomplete-kern-left"),
+            GlyphNameTarget::KernRight(left) => {
+                i18n::tr_args("autocomplete-kern-right", &[("glyph", left)])
+            }
+        }
+    }
+}
+
+/// The open popup, its query and the glyphs it completes it to
+#[derive(Resource, Default, Debug)]
+pub struct GlyphAutocomplete {
+    /// What the name is for, while the popup is open
+    pub target: Option<GlyphNameTarget>,
+    pub query: String,
+    pub completions: Vec<GlyphCompletion>,
+    /// Highlighted completion
+    pub selected: usize,
+}
+
+impl GlyphAutocomplete {
+    pub fn is_open(&self) -> bool {
+        self.target.is_some()
+    }
+
+    /// Opens the popup for `target` with an empty query
+    pub fn open(&mut self, target: GlyphNameTarget, font: &FontData) {
+        self.target = Some(target);
+        self.query.clear();
+        self.refresh(font);
+    }
+
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Complete the query again
+    fn refresh(&mut self, font: &FontData) {
+        let glyphs = font
+            .glyphs
+            .iter()
+            .map(|(name, glyph)| (name.as_str(), glyph.unicode_values.first().copied()));
+        self.completions = complete(glyphs, &self.query, MAX_COMPLETIONS);
+        self.selected = 0;
+    }
+
+    /// Move the highlight, stopping at the ends of the list
+    fn step(&mut self, rows: isize) {
+        let last = self.completions.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(rows).min(last);
+    }
+
+    /// The first of the rows shown, keeping the highlight in view
+    pub fn first_visible(&self) -> usize {
+        let last_start = self.completions.len().saturating_sub(VISIBLE_ROWS);
+        self.selected
+            .saturating_sub(VISIBLE_ROWS / 2)
+            .min(last_start)
+    }
+
+    /// The highlighted glyph's name
+    pub fn selected_glyph(&self) -> Option<&str> {
+        self.completions
+            .get(self.selected)
+            .map(|completion| completion.name.as_str())
+    }
+
+    /// Picks the completion at `index` and closes the popup
+    pub fn pick(&mut self, index: usize) -> Option<GlyphNamePicked> {
+        let picked = match (&self.target, self.completions.get(index)) {
+            (Some(target), Some(completion)) => Some(GlyphNamePicked {
+                target: target.clone(),
+                glyph_name: completion.name.clone(),
+            }),
+            _ => None,
+        };
+        self.close();
+        picked
+    }
+}
+
+/// Opens the popup for a glyph name
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct OpenGlyphAutocomplete(pub GlyphNameTarget);
+
+/// A glyph name picked in the popup
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct GlyphNamePicked {
+    pub target: GlyphNameTarget,
+    pub glyph_name: String,
+}
+
+pub struct GlyphAutocompletePlugin;
+
+impl Plugin for GlyphAutocompletePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GlyphAutocomplete>()
+            .add_event::<OpenGlyphAutocomplete>()
+            .add_event::<GlyphNamePicked>()
+            .add_systems(
+                Update,
+                (
+                    handle_glyph_autocomplete_shortcuts,
+                    open_glyph_autocomplete,
+                    handle_glyph_autocomplete_keys.in_set(TextFieldSystems),
+                    handle_picked_glyph_names,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Cmd/Ctrl+Alt+G goes to a glyph, Cmd/Ctrl+Alt+Shift+K enters a kerning
+/// pair
+fn handle_glyph_autocomplete_shortcuts(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut open_events: EventWriter<OpenGlyphAutocomplete>,
+) {
+    if Shortcut::GoToGlyph.just_pressed(&keyboard) {
+        open_events.write(OpenGlyphAutocomplete(GlyphNameTarget::GoToGlyph));
+    }
+    if Shortcut::EnterKernPair.just_pressed(&keyboard) {
+        open_events.write(OpenGlyphAutocomplete(GlyphNameTarget::KernLeft));
+    }
+}
+
+fn open_glyph_autocomplete(
+    mut open_events: EventReader<OpenGlyphAutocomplete>,
+    mut autocomplete: ResMut<GlyphAutocomplete>,
+    app_state: Option<Res<AppState>>,
+) {
+    for OpenGlyphAutocomplete(target) in open_events.read() {
+        let Some(state) = app_state.as_ref() else {
+            warn!("No font to pick a glyph from");
+            continue;
+        };
+        autocomplete.open(target.clone(), &state.workspace.font);
+        debug!("Glyph autocomplete opened: {}", target.prompt());
+    }
+}
+
+/// Types into the query, moves the highlight and picks a glyph
+#[allow(clippy::too_many_arguments)]
+fn handle_glyph_autocomplete_keys(
+    keyboard: Res<FieldKeys>,
+    mut key_events: EventReader<KeyboardInput>,
+    mut focus: ResMut<TextFieldFocus>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    ui_hover_state: Res<UiHoverState>,
+    mut autocomplete: ResMut<GlyphAutocomplete>,
+    app_state: Option<Res<AppState>>,
+    mut picked_events: EventWriter<GlyphNamePicked>,
+) {
+    focus.set(TextField::GlyphAutocomplete, autocomplete.is_open());
+    let Some(state) = app_state.filter(|_| autocomplete.is_open()) else {
+        key_events.clear();
+        return;
+    };
+    if mouse_button_input.just_pressed(MouseButton::Left) && !ui_hover_state.is_hovering_ui {
+        autocomplete.close();
+        return;
+    }
+    let modifier = keyboard.any_pressed([
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+    ]);
+
+    let mut query_changed = false;
+    for event in key_events.read() {
+        if event.state != ButtonState::Pressed || modifier {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(text) => {
+                autocomplete
+                    .query
+                    .extend(text.chars().filter(|c| !c.is_control()));
+                query_changed = true;
+            }
+            Key::Backspace => {
+                query_changed |= autocomplete.query.pop().is_some();
+            }
+            _ => {}
+        }
+    }
+    if query_changed {
+        autocomplete.refresh(&state.workspace.font);
+    }
+
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        autocomplete.step(1);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        autocomplete.step(-1);
+    }
+    if keyboard.just_pressed(KeyCode::PageDown) {
+        autocomplete.step(VISIBLE_ROWS as isize);
+    }
+    if keyboard.just_pressed(KeyCode::PageUp) {
+        autocomplete.step(-(VISIBLE_ROWS as isize));
+    }
+    if keyboard.just_pressed(KeyCode::Enter) {
+        let selected = autocomplete.selected;
+        picked_events.write_batch(autocomplete.pick(selected));
+    }
+    if keyboard.just_pressed(KeyCode::Escape) {
+        autocomplete.close();
+    }
+}
+
+/// Goes to picked glyphs and proofs picked kerning pairs
+fn handle_picked_glyph_names(
+    mut picked_events: EventReader<GlyphNamePicked>,
+    mut autocomplete: ResMut<GlyphAutocomplete>,
+    app_state: Option<Res<AppState>>,
+    mut go_to_events: EventWriter<GoToGlyph>,
+    mut kern_proofs: EventWriter<KernProofRequest>,
+) {
+    for GlyphNamePicked { target, glyph_name } in picked_events.read() {
+        let Some(state) = app_state.as_ref() else {
+            continue;
+        };
+        match target {
+            GlyphNameTarget::GoToGlyph => {
+                go_to_events.write(GoToGlyph(glyph_name.clone()));
+            }
+            GlyphNameTarget::KernLeft => {
+                autocomplete.open(
+                    GlyphNameTarget::KernRight(glyph_name.clone()),
+                    &state.workspace.font,
+                );
+            }
+            GlyphNameTarget::KernRight(left) => {
+                kern_proofs.write(KernProofRequest::Pairs(vec![(
+                    left.clone(),
+                    glyph_name.clone(),
+                )]));
+            }
+            // Added by the component graph
+            GlyphNameTarget::Component(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::GlyphData;
+
+    #[test]
+    fn test_pick() {
+        let mut font = FontData::default();
+        for name in ["a", "aacute", "b"] {
+            let glyph = GlyphData::test(name, 500.0);
+            font.glyphs.insert(name.to_string(), glyph);
+        }
+
+        let mut autocomplete = GlyphAutocomplete::default();
+        autocomplete.open(GlyphNameTarget::KernLeft, &font);
+        assert_eq!(autocomplete.completions.len(), 3);
+        autocomplete.query = "a".to_string();
+        autocomplete.refresh(&font);
+        autocomplete.step(5);
+        assert_eq!(autocomplete.selected_glyph(), Some("aacute"));
+
+        let picked = autocomplete.pick(autocomplete.selected);
+        assert_eq!(
+            picked,
+            Some(GlyphNamePicked {
+                target: GlyphNameTarget::KernLeft,
+                glyph_name: "aacute".to_string(),
+            })
+        );
+        assert!(!autocomplete.is_open());
+        assert_eq!(autocomplete.pick(0), None);
+    }
+}
@@ -0,0 +1,519 @@
+//! Blending two glyphs into a new one
+//!
+//! With the select tool, Cmd/Ctrl+Alt+I starts a blend of the glyphs of the
+//! two selected sorts, say a light and a bold shape, to draw the weight in
+//! between or to recover a glyph lost from one master from two that
+//! survived. The blend is previewed after the right-hand sort and the blend
+//! pane's slider sets how far it goes from the left glyph to the right one.
+//! For judging weight and contrast, the pane can also blend y coordinates
+//! apart from x ones, each with its own slider.
+//! The pane's play button sweeps the blend back and forth between the two
+//! glyphs so wobbles and kinks show up in motion, and the same sweep can be
+//! saved next to the font as a looping GIF to share.
+//! Nothing changes in the font until `CommitGlyphBlendEvent` adds the blend
+//! as a new glyph, named after the left glyph with a `.blend` suffix.
+
+use crate::core::state::AppState;
+use crate::data::canvas_image::rasterize;
+use crate::data::gif::encode_gif;
+use crate::editing::font_changes::GlyphEdited;
+use crate::editing::selection::components::Selected;
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::sort::Sort;
+use crate::font_source::interpolate::{self, blend_glyphs_xy};
+use crate::font_source::{FontData, GlyphData};
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use crate::ui::themes::CurrentTheme;
+use anyhow::{Context, Result};
+use bevy::color::ColorToPacked;
+use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task};
+use kurbo::{BezPath, Shape};
+use std::path::PathBuf;
+
+/// Blend amount a new blend starts at
+const DEFAULT_AMOUNT: f64 = 0.5;
+
+/// Seconds to sweep from the left glyph to the right one and back
+const PLAYBACK_PERIOD: f64 = 2.0;
+
+/// Frames in one sweep of a saved animation
+const ANIMATION_FRAMES: usize = 50;
+
+/// Height of a saved animation in pixels
+const ANIMATION_HEIGHT: f64 = 240.0;
+
+/// Space left around the glyphs of a saved animation, as a share of the em
+const ANIMATION_MARGIN: f64 = 0.1;
+
+/// A blend being previewed
+pub struct BlendSession {
+    pub first: String,
+    pub second: String,
+    /// From 0.0 for `first` to 1.0 for `second`
+    pub amount: f64,
+    /// Amount for y coordinates when blended apart from x ones
+    pub amount_y: Option<f64>,
+    /// Sort the preview is drawn after
+    pub sort_entity: Entity,
+    /// Whether the amount sweeps back and forth on its own
+    pub playing: bool,
+    /// Where the sweep is, from 0.0 to 2.0 for there and back
+    phase: f64,
+    preview: GlyphData,
+    paths: Vec<BezPath>,
+    /// Distance from the sort's origin to the preview's
+    offset: f32,
+}
+
+impl BlendSession {
+    pub fn preview(&self) -> &GlyphData {
+        &self.preview
+    }
+
+    /// The preview's outline and components in glyph space, and where it is
+    /// drawn relative to `sort_entity`
+    pub fn preview_paths(&self) -> (&[BezPath], Vec2) {
+        (&self.paths, Vec2::new(self.offset, 0.0))
+    }
+}
+
+/// The blend being previewed, if any, and the animation being saved
+#[derive(Resource, Default)]
+pub struct GlyphBlend {
+    pub session: Option<BlendSession>,
+    animation_task: Option<Task<Result<PathBuf>>>,
+}
+
+impl GlyphBlend {
+    pub fn is_saving_animation(&self) -> bool {
+        self.animation_task.is_some()
+    }
+}
+
+/// Preview a blend of two glyphs after `sort_entity`
+#[derive(Event, Clone)]
+pub struct StartGlyphBlendEvent {
+    pub first: String,
+    pub second: String,
+    pub sort_entity: Entity,
+}
+
+/// Move the previewed blend to an amount between 0.0 and 1.0
+#[derive(Event, Clone, Copy)]
+pub struct SetBlendAmountEvent(pub f64);
+
+/// Blend y coordinates by their own amount, or by the same one as x
+/// coordinates for `None`
+#[derive(Event, Clone, Copy)]
+pub struct SetVerticalBlendAmountEvent(pub Option<f64>);
+
+/// Add the previewed blend to the font
+#[derive(Event, Clone)]
+pub struct CommitGlyphBlendEvent;
+
+/// Stop previewing without changing the font
+#[derive(Event, Clone)]
+pub struct CancelGlyphBlendEvent;
+
+/// Start or stop sweeping the blend back and forth
+#[derive(Event, Clone)]
+pub struct ToggleBlendPlaybackEvent;
+
+/// Save one sweep of the blend next to the font as a looping GIF
+#[derive(Event, Clone)]
+pub struct SaveBlendAnimationEvent;
+
+pub struct GlyphBlendPlugin;
+
+impl Plugin for GlyphBlendPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GlyphBlend>()
+            .add_event::<StartGlyphBlendEvent>()
+            .add_event::<SetBlendAmountEvent>()
+            .add_event::<SetVerticalBlendAmountEvent>()
+            .add_event::<CommitGlyphBlendEvent>()
+            .add_event::<CancelGlyphBlendEvent>()
+            .add_event::<ToggleBlendPlaybackEvent>()
+            .add_event::<SaveBlendAnimationEvent>()
+            .add_systems(
+                Update,
+                (
+                    handle_glyph_blend_shortcut,
+                    play_glyph_blend,
+                    handle_glyph_blend_events,
+                    save_blend_animation,
+                    finish_blend_animation,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Blend the glyphs of the two selected sorts with Cmd/Ctrl+Alt+I
+fn handle_glyph_blend_shortcut(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_tool: Res<CurrentTool>,
+    selected_sorts: Query<(Entity, &Sort, &Transform), With<Selected>>,
+    mut start_events: EventWriter<StartGlyphBlendEvent>,
+) {
+    if current_tool.get_current() != Some("select") {
+        return;
+    }
+    if !Shortcut::GlyphBlend.just_pressed(&keyboard) {
+        return;
+    }
+
+    let mut sorts: Vec<_> = selected_sorts.iter().collect();
+    if sorts.len() != 2 {
+        warn!("Select two sorts to blend their glyphs");
+        return;
+    }
+    sorts.sort_by(|(_, _, a), (_, _, b)| a.translation.x.total_cmp(&b.translation.x));
+    let [(_, first, _), (sort_entity, second, _)] = [sorts[0], sorts[1]];
+    start_events.write(StartGlyphBlendEvent {
+        first: first.glyph_name.clone(),
+        second: second.glyph_name.clone(),
+        sort_entity,
+    });
+}
+
+/// Glyph space paths of a glyph that isn't in the font, with its components
+/// resolved from the font
+fn glyph_paths(font: &FontData, glyph: &GlyphData) -> Vec<BezPath> {
+    let mut paths = glyph
+        .outline
+        .as_ref()
+        .map(|outline| outline.to_bezpaths())
+        .unwrap_or_default();
+    for component in &glyph.components {
+        let transform = kurbo::Affine::new(component.transform);
+        paths.extend(
+            font.resolved_bezpaths(&component.base_glyph)
+                .into_iter()
+                .map(|path| transform * path),
+        );
+    }
+    paths
+}
+
+/// Update the session's preview for its amount and the glyphs' current
+/// outlines
+fn update_preview(font: &FontData, session: &mut BlendSession) -> anyhow::Result<()> {
+    let (Some(first), Some(second)) = (
+        font.get_glyph(&session.first),
+        font.get_glyph(&session.second),
+    ) else {
+        anyhow::bail!(
+            "'{}' or '{}' is no longer in the font",
+            session.first,
+            session.second
+        );
+    };
+    let amount_y = session.amount_y.unwrap_or(session.amount);
+    session.preview = blend_glyphs_xy(first, second, session.amount, amount_y)?;
+    session.paths = glyph_paths(font, &session.preview);
+    session.offset = second.advance_width as f32;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_glyph_blend_events(
+    mut start_events: EventReader<StartGlyphBlendEvent>,
+    mut amount_events: EventReader<SetBlendAmountEvent>,
+    mut vertical_amount_events: EventReader<SetVerticalBlendAmountEvent>,
+    mut commit_events: EventReader<CommitGlyphBlendEvent>,
+    mut cancel_events: EventReader<CancelGlyphBlendEvent>,
+    mut glyph_edits: EventReader<GlyphEdited>,
+    mut blend: ResMut<GlyphBlend>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    let Some(state) = app_state.as_mut() else {
+        start_events.clear();
+        amount_events.clear();
+        vertical_amount_events.clear();
+        commit_events.clear();
+        cancel_events.clear();
+        glyph_edits.clear();
+        return;
+    };
+    let mut changed = false;
+
+    for event in start_events.read() {
+        let font = &state.workspace.font;
+        let (Some(first), Some(second)) =
+            (font.get_glyph(&event.first), font.get_glyph(&event.second))
+        else {
+            continue;
+        };
+        if let Err(error) = interpolate::check_compatible(first, second) {
+            warn!(
+                "Cannot blend '{}' and '{}': {}",
+                event.first, event.second, error
+            );
+            continue;
+        }
+        let mut session = BlendSession {
+            first: event.first.clone(),
+            second: event.second.clone(),
+            amount: DEFAULT_AMOUNT,
+            amount_y: None,
+            sort_entity: event.sort_entity,
+            playing: false,
+            phase: 0.0,
+            preview: first.clone(),
+            paths: Vec::new(),
+            offset: 0.0,
+        };
+        if update_preview(font, &mut session).is_ok() {
+            info!("Blending '{}' and '{}'", session.first, session.second);
+            blend.session = Some(session);
+            changed = true;
+        }
+    }
+
+    let edited = glyph_edits.read().count() > 0;
+    let amount = amount_events
+        .read()
+        .last()
+        .map(|event| event.0.clamp(0.0, 1.0));
+    let amount_y = vertical_amount_events
+        .read()
+        .last()
+        .map(|event| event.0.map(|amount| amount.clamp(0.0, 1.0)));
+    if let Some(session) = blend.session.as_mut() {
+        if amount.is_some() || amount_y.is_some() || edited {
+            session.amount = amount.unwrap_or(session.amount);
+            session.amount_y = amount_y.unwrap_or(session.amount_y);
+            if let Err(error) = update_preview(&state.workspace.font, session) {
+                warn!("Stopped blending: {}", error);
+                blend.session = None;
+            }
+            changed = true;
+        }
+    }
+
+    for _ in commit_events.read() {
+        let Some(session) = blend.session.take() else {
+            continue;
+        };
+        let font = &mut state.workspace.font;
+        let name = (0..)
+            .map(|n| match n {
+                0 => format!("{}.blend", session.first),
+                n => format!("{}.blend{}", session.first, n),
+            })
+            .find(|name| !font.glyphs.contains_key(name))
+            .expect("an unused glyph name always exists");
+        info!(
+            "Added '{}', {:.0}% of the way from '{}' to '{}'",
+            name,
+            session.amount * 100.0,
+            session.first,
+            session.second
+        );
+        let glyph = GlyphData {
+            name: name.clone(),
+            ..session.preview
+        };
+        font.glyphs.insert(name, glyph);
+        app_state_changed.write(AppStateChanged);
+        changed = true;
+    }
+
+    for _ in cancel_events.read() {
+        if blend.session.take().is_some() {
+            changed = true;
+        }
+    }
+
+    if changed {
+        visual_update_tracker.needs_update = true;
+    }
+}
+
+/// Amount at `phase` of a sweep, rising from 0.0 to 1.0 over the first
+/// half and falling back over the second
+fn sweep_amount(phase: f64) -> f64 {
+    let phase = phase.rem_euclid(2.0);
+    if phase <= 1.0 {
+        phase
+    } else {
+        2.0 - phase
+    }
+}
+
+/// Starts and stops playback, and moves a playing blend along its sweep
+fn play_glyph_blend(
+    mut toggle_events: EventReader<ToggleBlendPlaybackEvent>,
+    time: Res<Time>,
+    mut blend: ResMut<GlyphBlend>,
+    mut amount_events: EventWriter<SetBlendAmountEvent>,
+) {
+    let toggled = toggle_events.read().count() % 2 == 1;
+    let playing = blend
+        .session
+        .as_ref()
+        .is_some_and(|session| session.playing);
+    if !toggled && !playing {
+        return;
+    }
+    let Some(session) = blend.session.as_mut() else {
+        return;
+    };
+    if toggled {
+        session.playing = !session.playing;
+        // Pick up the sweep from the current amount, on its way up
+        session.phase = session.amount;
+        return;
+    }
+    session.phase = (session.phase + 2.0 * time.delta_secs_f64() / PLAYBACK_PERIOD) % 2.0;
+    amount_events.write(SetBlendAmountEvent(sweep_amount(session.phase)));
+}
+
+/// A file name part made of a glyph name, without path separators and other
+/// characters file systems reject
+fn file_name_part(glyph_name: &str) -> String {
+    glyph_name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect()
+}
+
+/// Blends one sweep of the session's glyphs on the main thread, and draws
+/// and writes the frames in the background
+fn save_blend_animation(
+    mut save_events: EventReader<SaveBlendAnimationEvent>,
+    mut blend: ResMut<GlyphBlend>,
+    app_state: Option<Res<AppState>>,
+    theme: Res<CurrentTheme>,
+) {
+    if save_events.read().count() == 0 {
+        return;
+    }
+    if blend.is_saving_animation() {
+        warn!("A blend animation is already being saved");
+        return;
+    }
+    let (Some(state), Some(session)) = (app_state.as_ref(), blend.session.as_ref()) else {
+        return;
+    };
+    let Some(font_path) = state.workspace.font.path.as_ref() else {
+        warn!("Cannot save the blend animation: save the font first");
+        return;
+    };
+    let font = &state.workspace.font;
+    let (Some(first), Some(second)) = (
+        font.get_glyph(&session.first),
+        font.get_glyph(&session.second),
+    ) else {
+        return;
+    };
+
+    // Frames of outlines, and an em box wide enough for the widest of them
+    let metrics = &state.workspace.info.metrics;
+    let upm = metrics.units_per_em;
+    let descender = metrics.descender.unwrap_or(-0.25 * upm);
+    let ascender = metrics.ascender.unwrap_or(0.75 * upm);
+    let mut region = kurbo::Rect::new(0.0, descender, 0.0, ascender);
+    let mut frames = Vec::with_capacity(ANIMATION_FRAMES);
+    for frame in 0..ANIMATION_FRAMES {
+        let amount = sweep_amount(2.0 * frame as f64 / ANIMATION_FRAMES as f64);
+        let amount_y = session.amount_y.unwrap_or(amount);
+        let glyph = match blend_glyphs_xy(first, second, amount, amount_y) {
+            Ok(glyph) => glyph,
+            Err(error) => {
+                warn!("Cannot save the blend animation: {}", error);
+                return;
+            }
+        };
+        let paths = glyph_paths(font, &glyph);
+        region = region.union(kurbo::Rect::new(0.0, 0.0, glyph.advance_width, 0.0));
+        for path in &paths {
+            region = region.union(path.bounding_box());
+        }
+        frames.push(paths);
+    }
+    let region = region.inflate(ANIMATION_MARGIN * upm, ANIMATION_MARGIN * upm);
+
+    let fill = theme.theme().filled_glyph_color().to_srgba().to_u8_array();
+    let background = theme.theme().background_color().to_srgba().to_u8_array();
+    let stem = font_path.file_stem().unwrap_or_default().to_string_lossy();
+    let path = font_path.with_file_name(format!(
+        "{stem}-Blend-{}-{}.gif",
+        file_name_part(&session.first),
+        file_name_part(&session.second)
+    ));
+    info!("Saving the blend animation to {}", path.display());
+    blend.animation_task = Some(AsyncComputeTaskPool::get().spawn(async move {
+        // Every level of coverage, from the background to the fill
+        let palette: Vec<[u8; 3]> = (0..256)
+            .map(|level| {
+                let alpha = level as f32 / 255.0 * fill[3] as f32 / 255.0;
+                std::array::from_fn(|channel| {
+                    let blended =
+                        fill[channel] as f32 * alpha + background[channel] as f32 * (1.0 - alpha);
+                    blended.round() as u8
+                })
+            })
+            .collect();
+        let pixels_per_unit = ANIMATION_HEIGHT / region.height();
+        let mut size = (0, 0);
+        let mut indices = Vec::with_capacity(frames.len());
+        for paths in &frames {
+            let (width, height, coverage) = rasterize(paths, region, pixels_per_unit)?;
+            size = (width, height);
+            indices.push(
+                coverage
+                    .iter()
+                    .map(|covered| (covered * 255.0).round() as u8)
+                    .collect(),
+            );
+        }
+        let (width, height) = (
+            u16::try_from(size.0).context("The animation is too wide")?,
+            u16::try_from(size.1).context("The animation is too tall")?,
+        );
+        // Hundredths of a second per frame
+        let delay = (PLAYBACK_PERIOD * 100.0 / ANIMATION_FRAMES as f64).round() as u16;
+        let gif = encode_gif(width, height, &palette, &indices, delay);
+        std::fs::write(&path, gif).with_context(|| format!("Cannot write {}", path.display()))?;
+        Ok(path)
+    }));
+}
+
+/// Picks up a saved animation and reports where it went
+fn finish_blend_animation(mut blend: ResMut<GlyphBlend>) {
+    let Some(task) = blend.animation_task.as_mut() else {
+        return;
+    };
+    let Some(result) = block_on(future::poll_once(task)) else {
+        return;
+    };
+    blend.animation_task = None;
+    match result {
+        Ok(path) => info!("Saved the blend animation to {}", path.display()),
+        Err(error) => error!("Saving the blend animation failed: {error:#}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep_goes_there_and_back() {
+        assert_eq!(sweep_amount(0.0), 0.0);
+        assert_eq!(sweep_amount(0.25), 0.25);
+        assert_eq!(sweep_amount(1.0), 1.0);
+        assert_eq!(sweep_amount(1.5), 0.5);
+        assert_eq!(sweep_amount(2.25), 0.25);
+    }
+}
@@ -0,0 +1,206 @@
+//! Non-destructive glyph filters on the active glyph
+//!
+//! Cmd/Ctrl+Alt+Y shows or hides the glyph filters pane, which edits the
+//! filter recipe of the active sort's glyph (see
+//! `font_source::glyph_filters`). Every change to the recipe draws the glyph
+//! again from its source outline, so a radius or an offset can be tweaked at
+//! any time. Applying redraws the glyph after the source layer was edited;
+//! baking keeps the drawing and forgets the recipe.
+
+use crate::core::state::{AppState, ContourData, FontData, OutlineData};
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::font_source::GlyphFilter;
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use crate::ui::edit_mode_toolbar::corners::{is_corner, round_corners};
+use crate::ui::edit_mode_toolbar::nib::offset_contour;
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+
+/// Whether the glyph filters pane is shown
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct GlyphFiltersPanel {
+    pub visible: bool,
+}
+
+/// Changes to the active glyph's recipe, filters by index
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum GlyphFiltersEvent {
+    Add(GlyphFilter),
+    /// Raise (`true`) or lower a filter's parameter by a step
+    Step(usize, bool),
+    Remove(usize),
+    /// Draw the glyph again from its source outline
+    Apply,
+    /// Keep the drawing and drop the recipe
+    Bake,
+}
+
+pub struct GlyphFiltersPlugin;
+
+impl Plugin for GlyphFiltersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GlyphFiltersPanel>()
+            .add_event::<GlyphFiltersEvent>()
+            .add_systems(
+                Update,
+                (toggle_glyph_filters, handle_glyph_filters_events).chain(),
+            );
+    }
+}
+
+/// Cmd/Ctrl+Alt+Y shows or hides the pane
+fn toggle_glyph_filters(keyboard: Res<ButtonInput<KeyCode>>, mut panel: ResMut<GlyphFiltersPanel>) {
+    if Shortcut::GlyphFilters.just_pressed(&keyboard) {
+        panel.visible = !panel.visible;
+        debug!("Glyph filters shown: {}", panel.visible);
+    }
+}
+
+fn handle_glyph_filters_events(
+    mut events: EventReader<GlyphFiltersEvent>,
+    mut app_state: Option<ResMut<AppState>>,
+    active_sorts: Query<&Sort, With<ActiveSort>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    let Some(state) = app_state.as_mut() else {
+        events.clear();
+        return;
+    };
+    let Ok(sort) = active_sorts.single() else {
+        events.clear();
+        return;
+    };
+    let font = &mut state.workspace.font;
+    let glyph_name = sort.glyph_name.as_str();
+
+    for event in events.read() {
+        match *event {
+            GlyphFiltersEvent::Add(filter) => {
+                if !font.add_glyph_filter(glyph_name, filter) {
+                    continue;
+                }
+            }
+            GlyphFiltersEvent::Step(index, forward) => {
+                let Some(filter) = font
+                    .glyph_filters_mut(glyph_name)
+                    .and_then(|filters| filters.get_mut(index))
+                else {
+                    continue;
+                };
+                filter.step(forward);
+            }
+            GlyphFiltersEvent::Remove(index) => {
+                let Some(filters) = font
+                    .glyph_filters_mut(glyph_name)
+                    .filter(|filters| index < filters.len())
+                else {
+                    continue;
+                };
+                filters.remove(index);
+            }
+            GlyphFiltersEvent::Apply => {}
+            GlyphFiltersEvent::Bake => {
+                if font.glyph_filters(glyph_name).is_empty() {
+                    continue;
+                }
+                font.drop_glyph_filters(glyph_name);
+                info!("Baked the filters of '{}'", glyph_name);
+                app_state_changed.write(AppStateChanged);
+                continue;
+            }
+        }
+        if !redraw_filtered_glyph(font, glyph_name) {
+            continue;
+        }
+        // Without filters the glyph is back to its source outline
+        if font.glyph_filters(glyph_name).is_empty() {
+            font.drop_glyph_filters(glyph_name);
+        }
+        app_state_changed.write(AppStateChanged);
+        visual_update_tracker.needs_update = true;
+    }
+}
+
+/// Draw a glyph again from its source outline; false if it has none
+fn redraw_filtered_glyph(font: &mut FontData, glyph_name: &str) -> bool {
+    let Some(source) = font.filter_source_contours(glyph_name) else {
+        return false;
+    };
+    let contours = apply_glyph_filters(&source, font.glyph_filters(glyph_name));
+    let Some(glyph) = font.foreground_glyph_mut(glyph_name) else {
+        return false;
+    };
+    glyph.outline = (!contours.is_empty()).then_some(OutlineData { contours });
+    true
+}
+
+/// Run a recipe on a set of contours, filter after filter
+pub fn apply_glyph_filters(contours: &[ContourData], filters: &[GlyphFilter]) -> Vec<ContourData> {
+    filters
+        .iter()
+        .fold(contours.to_vec(), |contours, filter| match *filter {
+            GlyphFilter::RoundCorners { radius } => contours
+                .iter()
+                .map(|contour| {
+                    let radii: BTreeMap<usize, f64> = (0..contour.points.len())
+                        .filter(|index| is_corner(contour, *index))
+                        .map(|index| (index, radius))
+                        .collect();
+                    round_corners(contour, &radii)
+                })
+                .collect(),
+            GlyphFilter::Offset { distance } => contours
+                .iter()
+                .map(|contour| offset_contour(contour, distance).unwrap_or_else(|| contour.clone()))
+                .collect(),
+            GlyphFilter::Slant { angle } => {
+                let shear = angle.to_radians().tan();
+                let mut contours = contours;
+                for point in contours.iter_mut().flat_map(|contour| &mut contour.points) {
+                    point.x += point.y * shear;
+                }
+                contours
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::state::{PointData, PointTypeData};
+
+    #[test]
+    fn test_apply_glyph_filters() {
+        let points = [(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)];
+        let square = ContourData {
+            points: points
+                .iter()
+                .map(|&(x, y)| PointData {
+                    x,
+                    y,
+                    point_type: PointTypeData::Line,
+                })
+                .collect(),
+        };
+        let contours = [square];
+        assert_eq!(apply_glyph_filters(&contours, &[]), contours);
+
+        let slanted = apply_glyph_filters(&contours, &[GlyphFilter::Slant { angle: 45.0 }]);
+        let xs: Vec<f64> = slanted[0]
+            .points
+            .iter()
+            .map(|point| point.x.round())
+            .collect();
+        assert_eq!(xs, [0.0, 100.0, 200.0, 100.0]);
+
+        // Filters run in order on what the previous one drew, and rounded
+        // corners have no corners left to round
+        let round = GlyphFilter::RoundCorners { radius: 10.0 };
+        let rounded = apply_glyph_filters(&contours, &[round]);
+        assert_eq!(rounded[0].points.len(), 16);
+        assert_eq!(apply_glyph_filters(&contours, &[round, round]), rounded);
+    }
+}
@@ -0,0 +1,172 @@
+//! Other glyphs ghosted behind the active sort
+//!
+//! Drawing an "h" is easier with the "n" it should match right behind it.
+//! Any glyph, or the same glyph from another layer, can be ghosted behind
+//! the active sort in a faint color, shifted by an offset of its own. Ghosts
+//! can't be selected or edited. With the select tool, Cmd/Ctrl+Alt+O ghosts
+//! the glyphs of the selected sorts other than the active one and
+//! Cmd/Ctrl+Alt+Shift+O removes every ghost; the ghost pane moves ghosts,
+//! changes their colors and removes them one by one.
+
+use crate::core::state::AppState;
+use crate::editing::selection::components::Selected;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use bevy::prelude::*;
+
+/// A glyph drawn behind the active sort for comparison
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlyphGhost {
+    pub glyph_name: String,
+    /// Layer the outline comes from, or `None` for the one being edited
+    pub layer: Option<String>,
+    /// Shift from the active sort's origin, in font units
+    pub offset: Vec2,
+    /// Index into the theme's ghost colors
+    pub color_index: usize,
+}
+
+impl GlyphGhost {
+    pub fn label(&self) -> String {
+        match &self.layer {
+            Some(layer) => format!("{}@{}", self.glyph_name, layer),
+            None => self.glyph_name.clone(),
+        }
+    }
+}
+
+/// Glyphs ghosted behind the active sort
+#[derive(Resource, Default)]
+pub struct GlyphGhosts {
+    pub ghosts: Vec<GlyphGhost>,
+}
+
+/// Changes to the ghosts, by index in `GlyphGhosts::ghosts`
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum GlyphGhostEvent {
+    Add {
+        glyph_name: String,
+        layer: Option<String>,
+    },
+    Move(usize, Vec2),
+    NextColor(usize),
+    Remove(usize),
+    Clear,
+}
+
+pub struct GlyphGhostsPlugin;
+
+impl Plugin for GlyphGhostsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GlyphGhosts>()
+            .add_event::<GlyphGhostEvent>()
+            .add_systems(
+                Update,
+                (handle_glyph_ghost_shortcuts, handle_glyph_ghost_events).chain(),
+            );
+    }
+}
+
+/// Ghost the selected sorts' glyphs with Cmd/Ctrl+Alt+O, or remove every
+/// ghost with Shift
+fn handle_glyph_ghost_shortcuts(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_tool: Res<CurrentTool>,
+    selected_sorts: Query<&Sort, (With<Selected>, Without<ActiveSort>)>,
+    mut ghost_events: EventWriter<GlyphGhostEvent>,
+) {
+    if current_tool.get_current() != Some("select") {
+        return;
+    }
+    if Shortcut::ClearGhosts.just_pressed(&keyboard) {
+        ghost_events.write(GlyphGhostEvent::Clear);
+        return;
+    }
+    if !Shortcut::GlyphGhosts.just_pressed(&keyboard) {
+        return;
+    }
+
+    let mut glyph_names: Vec<&str> = selected_sorts
+        .iter()
+        .map(|sort| sort.glyph_name.as_str())
+        .collect();
+    glyph_names.sort_unstable();
+    glyph_names.dedup();
+    if glyph_names.is_empty() {
+        warn!("Select the sorts to ghost behind the active sort");
+    }
+    for glyph_name in glyph_names {
+        ghost_events.write(GlyphGhostEvent::Add {
+            glyph_name: glyph_name.to_string(),
+            layer: None,
+        });
+    }
+}
+
+fn handle_glyph_ghost_events(
+    mut ghost_events: EventReader<GlyphGhostEvent>,
+    mut glyph_ghosts: ResMut<GlyphGhosts>,
+    app_state: Option<Res<AppState>>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    let mut changed = false;
+    for event in ghost_events.read() {
+        let ghosts = &mut glyph_ghosts.ghosts;
+        match event {
+            GlyphGhostEvent::Add { glyph_name, layer } => {
+                let exists = app_state.as_ref().is_some_and(|state| {
+                    let font = &state.workspace.font;
+                    match layer {
+                        Some(layer) => font.layer_glyph(layer, glyph_name).is_some(),
+                        None => font.get_glyph(glyph_name).is_some(),
+                    }
+                });
+                let ghosted = ghosts
+                    .iter()
+                    .any(|ghost| ghost.glyph_name == *glyph_name && ghost.layer == *layer);
+                if !exists || ghosted {
+                    continue;
+                }
+                let color_index = ghosts.last().map_or(0, |ghost| ghost.color_index + 1);
+                ghosts.push(GlyphGhost {
+                    glyph_name: glyph_name.clone(),
+                    layer: layer.clone(),
+                    offset: Vec2::ZERO,
+                    color_index,
+                });
+                info!("Ghosting '{}'", ghosts[ghosts.len() - 1].label());
+            }
+            GlyphGhostEvent::Move(index, delta) => {
+                let Some(ghost) = ghosts.get_mut(*index) else {
+                    continue;
+                };
+                ghost.offset += *delta;
+            }
+            GlyphGhostEvent::NextColor(index) => {
+                let Some(ghost) = ghosts.get_mut(*index) else {
+                    continue;
+                };
+                ghost.color_index += 1;
+            }
+            GlyphGhostEvent::Remove(index) => {
+                if *index >= ghosts.len() {
+                    continue;
+                }
+                ghosts.remove(*index);
+            }
+            GlyphGhostEvent::Clear => {
+                if ghosts.is_empty() {
+                    continue;
+                }
+                ghosts.clear();
+            }
+        }
+        changed = true;
+    }
+
+    if changed {
+        visual_update_tracker.needs_update = true;
+    }
+}
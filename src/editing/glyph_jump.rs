@@ -0,0 +1,64 @@
+//! Jumping to a glyph in the text
+//!
+//! Go to glyph, the compile errors pane and the component graph all show a
+//! glyph the same way: by activating a sort that shows it, or, when no sort
+//! does, by showing it in the active sort.
+
+use crate::core::state::{AppState, GlyphData, GlyphNavigation, SortKind, TextEditorState};
+use crate::systems::sorts::queue_respawn;
+use crate::systems::sorts::sort_entities::BufferSortRespawnQueue;
+use bevy::prelude::*;
+
+/// The first sort showing a glyph
+pub fn glyph_sort_index(text_editor: &TextEditorState, glyph_name: &str) -> Option<usize> {
+    (0..text_editor.buffer.len()).find(|&index| {
+        matches!(
+            text_editor.buffer.get(index).map(|sort| &sort.kind),
+            Some(SortKind::Glyph { glyph_name: name, .. }) if name == glyph_name
+        )
+    })
+}
+
+/// Shows a glyph in the sort at `index`, moving the sorts after it
+pub fn show_glyph_in_sort(
+    text_editor: &mut TextEditorState,
+    respawn_queue: &mut BufferSortRespawnQueue,
+    index: usize,
+    glyph_name: &str,
+    glyph: &GlyphData,
+) {
+    if let Some(sort) = text_editor.buffer.get_mut(index) {
+        sort.kind = SortKind::Glyph {
+            codepoint: glyph.unicode_values.first().copied(),
+            glyph_name: glyph_name.to_string(),
+            advance_width: glyph.advance_width as f32,
+        };
+    }
+    queue_respawn(text_editor, respawn_queue, index);
+}
+
+/// Activates a sort showing a glyph, or shows the glyph in the active sort
+/// when no sort does
+pub fn jump_to_glyph(
+    state: &AppState,
+    text_editor: &mut TextEditorState,
+    respawn_queue: &mut BufferSortRespawnQueue,
+    glyph_navigation: &mut GlyphNavigation,
+    glyph_name: &str,
+) {
+    let Some(glyph) = state.workspace.font.glyphs.get(glyph_name) else {
+        warn!("Glyph '{}' is not in the font", glyph_name);
+        return;
+    };
+
+    if let Some(index) = glyph_sort_index(text_editor, glyph_name) {
+        text_editor.activate_sort(index);
+    } else if let Some((index, _)) = text_editor.get_active_sort() {
+        show_glyph_in_sort(text_editor, respawn_queue, index, glyph_name, glyph);
+    } else {
+        warn!("No sort to show '{}' in: place a sort first", glyph_name);
+        return;
+    }
+    glyph_navigation.set_current_glyph(glyph_name.to_string());
+    info!("Jumped to glyph '{}'", glyph_name);
+}
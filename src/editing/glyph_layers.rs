@@ -0,0 +1,155 @@
+//! Glyph layer switching
+//!
+//! Lets the user edit background and other UFO layers with the normal tools.
+//! Cmd/Ctrl+B toggles between the foreground and `public.background`, and
+//! Cmd/Ctrl+Shift+B copies the active glyph's outline to the background.
+//! Cmd/Ctrl+Alt+B shows the layers pane, which lists every layer of the
+//! font and switches to any of them.
+
+use crate::core::state::AppState;
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::font_source::BACKGROUND_LAYER_NAME;
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use bevy::prelude::*;
+
+/// Whether the layers pane is shown
+#[derive(Resource, Default, Debug)]
+pub struct GlyphLayersPanel {
+    pub visible: bool,
+}
+
+/// Switch the layer being edited (`None` for the foreground)
+#[derive(Event, Clone)]
+pub struct SwitchEditingLayerEvent(pub Option<String>);
+
+/// Toggle between the foreground and the background layer
+#[derive(Event, Clone)]
+pub struct ToggleBackgroundLayerEvent;
+
+/// Copy the active glyph's current outline to another layer
+#[derive(Event, Clone)]
+pub struct CopyToLayerEvent {
+    pub layer: String,
+}
+
+pub struct GlyphLayersPlugin;
+
+impl Plugin for GlyphLayersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GlyphLayersPanel>()
+            .add_event::<SwitchEditingLayerEvent>()
+            .add_event::<ToggleBackgroundLayerEvent>()
+            .add_event::<CopyToLayerEvent>()
+            .add_systems(
+                Update,
+                (
+                    handle_layer_shortcuts,
+                    handle_layer_events,
+                    fill_editing_layer,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn handle_layer_shortcuts(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    text_mode: Option<Res<crate::ui::edit_mode_toolbar::text::TextModeActive>>,
+    mut panel: ResMut<GlyphLayersPanel>,
+    mut toggle_events: EventWriter<ToggleBackgroundLayerEvent>,
+    mut copy_events: EventWriter<CopyToLayerEvent>,
+) {
+    if Shortcut::LayersPane.just_pressed(&keyboard) {
+        panel.visible = !panel.visible;
+        debug!("Layers pane shown: {}", panel.visible);
+        return;
+    }
+    if text_mode.is_some_and(|t| t.0) {
+        return;
+    }
+
+    if Shortcut::CopyToBackgroundLayer.just_pressed(&keyboard) {
+        copy_events.write(CopyToLayerEvent {
+            layer: BACKGROUND_LAYER_NAME.to_string(),
+        });
+    }
+    if Shortcut::ToggleBackgroundLayer.just_pressed(&keyboard) {
+        toggle_events.write(ToggleBackgroundLayerEvent);
+    }
+}
+
+fn handle_layer_events(
+    mut switch_events: EventReader<SwitchEditingLayerEvent>,
+    mut toggle_events: EventReader<ToggleBackgroundLayerEvent>,
+    mut copy_events: EventReader<CopyToLayerEvent>,
+    mut app_state: Option<ResMut<AppState>>,
+    active_sort_query: Query<&Sort, With<ActiveSort>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    let Some(state) = app_state.as_mut() else {
+        switch_events.clear();
+        toggle_events.clear();
+        copy_events.clear();
+        return;
+    };
+    let font = &mut state.workspace.font;
+    let mut changed = false;
+
+    for SwitchEditingLayerEvent(layer) in switch_events.read() {
+        font.switch_editing_layer(layer.as_deref());
+        info!("Editing layer: {}", font.editing_layer_name());
+        changed = true;
+    }
+
+    for _ in toggle_events.read() {
+        let target = match font.editing_layer {
+            Some(_) => None,
+            None => Some(BACKGROUND_LAYER_NAME),
+        };
+        font.switch_editing_layer(target);
+        info!("Editing layer: {}", font.editing_layer_name());
+        changed = true;
+    }
+
+    for event in copy_events.read() {
+        let Ok(sort) = active_sort_query.single() else {
+            warn!("Cannot copy to layer: no active sort");
+            continue;
+        };
+        if font.copy_glyph_to_layer(&sort.glyph_name, &event.layer) {
+            info!(
+                "Copied '{}' from {} to {}",
+                sort.glyph_name,
+                font.editing_layer_name(),
+                event.layer
+            );
+            changed = true;
+        } else if event.layer == font.editing_layer_name() {
+            debug!("'{}' is already the layer being edited", event.layer);
+        }
+    }
+
+    if changed {
+        app_state_changed.write(AppStateChanged);
+        visual_update_tracker.needs_update = true;
+    }
+}
+
+/// Gives the layer being edited the glyphs added to the font since it was
+/// switched to, so they can be drawn on it too
+fn fill_editing_layer(app_state: Option<ResMut<AppState>>) {
+    let Some(mut state) = app_state else {
+        return;
+    };
+    if !state.is_changed() || state.workspace.font.editing_layer.is_none() {
+        return;
+    }
+    state
+        .bypass_change_detection()
+        .workspace
+        .font
+        .fill_editing_layer();
+}
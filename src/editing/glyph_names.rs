@@ -0,0 +1,142 @@
+//! Glyph name and Unicode checks
+//!
+//! Cmd/Ctrl+Alt+Q shows the glyph names pane, which lists the issues found
+//! by `data::glyph_names`: names that change or collide in the compiled
+//! font, a missing `.notdef`, codepoints lost to normalization, and
+//! characters shaped by decomposition. Issues with a safe fix get a button,
+//! and one button fixes them all. The list is checked again as the font
+//! changes while the pane is shown. Only the foreground is checked and
+//! fixed, whichever layer is being edited.
+
+use crate::core::state::AppState;
+use crate::data::glyph_names::{check_glyph_names, fix_issue, GlyphNameIssue};
+use crate::editing::font_changes::{GlyphEdited, GlyphsRenamed};
+use crate::editing::selection::events::AppStateChanged;
+use crate::io::shortcuts::Shortcut;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Width of a placeholder `.notdef`, in ems
+const NOTDEF_WIDTH: f64 = 0.5;
+
+/// Height of a placeholder `.notdef` in fonts without a cap height, in ems
+const NOTDEF_HEIGHT: f64 = 0.7;
+
+/// Whether the glyph names pane is shown, and the issues it lists
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct GlyphNamesPaneState {
+    pub visible: bool,
+    pub issues: Vec<GlyphNameIssue>,
+}
+
+/// Fixes of the listed issues, by index
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum GlyphNameEvent {
+    Fix(usize),
+    FixAll,
+}
+
+pub struct GlyphNamesPlugin;
+
+impl Plugin for GlyphNamesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GlyphNamesPaneState>()
+            .add_event::<GlyphNameEvent>()
+            .add_systems(
+                Update,
+                (
+                    toggle_glyph_names_pane,
+                    handle_glyph_name_events,
+                    refresh_glyph_name_issues,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Cmd/Ctrl+Alt+Q shows or hides the glyph names pane
+fn toggle_glyph_names_pane(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut pane: ResMut<GlyphNamesPaneState>,
+) {
+    if Shortcut::GlyphNames.just_pressed(&keyboard) {
+        pane.visible = !pane.visible;
+        debug!("Glyph names pane shown: {}", pane.visible);
+    }
+}
+
+fn handle_glyph_name_events(
+    mut events: EventReader<GlyphNameEvent>,
+    pane: Res<GlyphNamesPaneState>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut glyph_edits: EventWriter<GlyphEdited>,
+    mut glyphs_renamed: EventWriter<GlyphsRenamed>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+) {
+    let Some(state) = app_state.as_mut() else {
+        events.clear();
+        return;
+    };
+
+    for event in events.read() {
+        let issues: Vec<&GlyphNameIssue> = match *event {
+            GlyphNameEvent::Fix(index) => pane.issues.get(index).into_iter().collect(),
+            GlyphNameEvent::FixAll => pane.issues.iter().collect(),
+        };
+        let info = &state.workspace.info;
+        let width = info.units_per_em * NOTDEF_WIDTH;
+        let height = info.cap_height.unwrap_or(info.units_per_em * NOTDEF_HEIGHT);
+
+        let mut changed = Vec::new();
+        let mut renames = HashMap::new();
+        for issue in issues.into_iter().filter(|issue| issue.is_fixable()) {
+            let fixed = fix_issue(&mut state.workspace.font, issue, width, height);
+            if fixed.is_empty() {
+                warn!("Could not fix: {}", issue.message());
+                continue;
+            }
+            if let GlyphNameIssue::InvalidProductionName {
+                glyph, production, ..
+            } = issue
+            {
+                renames.insert(glyph.clone(), production.clone());
+            }
+            changed.extend(fixed);
+        }
+        if !renames.is_empty() {
+            glyphs_renamed.write(GlyphsRenamed(renames));
+        }
+        if changed.is_empty() {
+            continue;
+        }
+        info!(
+            "Fixed glyph names and codepoints in {} glyphs",
+            changed.len()
+        );
+        glyph_edits.write_batch(changed.into_iter().map(GlyphEdited::glyph));
+        app_state_changed.write(AppStateChanged);
+    }
+}
+
+/// Checks the font again when it changes, while the pane is shown
+fn refresh_glyph_name_issues(
+    mut pane: ResMut<GlyphNamesPaneState>,
+    app_state: Option<Res<AppState>>,
+) {
+    if !pane.visible {
+        return;
+    }
+    let Some(state) = app_state else {
+        if !pane.issues.is_empty() {
+            pane.issues.clear();
+        }
+        return;
+    };
+    if !state.is_changed() && !pane.is_changed() {
+        return;
+    }
+    let issues = check_glyph_names(&state.workspace.font);
+    if pane.issues != issues {
+        pane.issues = issues;
+    }
+}
@@ -0,0 +1,195 @@
+//! Go to a glyph
+//!
+//! Cmd/Ctrl+Alt+G asks for a glyph by name, by the character it encodes or
+//! by its `U+XXXX` codepoint (see `glyph_autocomplete`), and the TUI's Glyph
+//! tab does the same. Going to a glyph activates a sort showing it, or shows
+//! it in the active sort, or with no sort to show it in starts a text buffer
+//! with it in the middle of the view. The camera then centers on the sort.
+//!
+//! Cmd/Ctrl+Alt+. and Cmd/Ctrl+Alt+, show the next and previous glyph of
+//! the font in encoding order in the active sort, in whatever tool is
+//! current, to work through an alphabet glyph by glyph.
+
+use crate::core::state::text_editor::buffer::BufferId;
+use crate::core::state::{
+    glyphs_in_encoding_order, step_glyph, ActiveTextBuffer, AppState, CycleDirection, GlyphData,
+    GlyphNavigation, SortData, SortKind, SortLayoutMode, TextEditorState,
+};
+use crate::editing::glyph_jump::{glyph_sort_index, jump_to_glyph, show_glyph_in_sort};
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::cameras::DesignCamera;
+use crate::systems::sorts::queue_respawn;
+use crate::systems::sorts::sort_entities::BufferSortRespawnQueue;
+use crate::systems::sorts::BufferLayouts;
+use crate::systems::text_buffer_manager::create_text_buffer;
+use bevy::prelude::*;
+
+/// x-height when the font doesn't set one
+const FALLBACK_X_HEIGHT: f64 = 500.0;
+
+/// Makes the named glyph the active sort and centers the camera on it
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct GoToGlyph(pub String);
+
+pub struct GoToGlyphPlugin;
+
+impl Plugin for GoToGlyphPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<GoToGlyph>()
+            .add_systems(Update, (step_active_glyph, go_to_glyphs).chain());
+    }
+}
+
+/// Starts a text buffer at `root_position` holding a sort of the glyph,
+/// active, and returns the buffer's entity
+fn place_glyph_sort(
+    commands: &mut Commands,
+    text_editor: &mut TextEditorState,
+    glyph_name: &str,
+    glyph: &GlyphData,
+    root_position: Vec2,
+) -> Entity {
+    let buffer_id = BufferId::new();
+    let layout_mode = SortLayoutMode::LTRText;
+    // The cursor goes after the glyph, ready to type on
+    let buffer_entity =
+        create_text_buffer(commands, buffer_id, layout_mode.clone(), root_position, 1);
+
+    text_editor.clear_all_states();
+    let end = text_editor.buffer.len();
+    text_editor.buffer.insert(
+        end,
+        SortData {
+            kind: SortKind::Glyph {
+                codepoint: glyph.unicode_values.first().copied(),
+                glyph_name: glyph_name.to_string(),
+                advance_width: glyph.advance_width as f32,
+            },
+            is_active: true,
+            layout_mode,
+            root_position,
+            placement_offset: Vec2::ZERO,
+            buffer_cursor_position: None,
+            buffer_id: Some(buffer_id),
+        },
+    );
+    buffer_entity
+}
+
+#[allow(clippy::too_many_arguments)]
+fn go_to_glyphs(
+    mut commands: Commands,
+    mut events: EventReader<GoToGlyph>,
+    app_state: Option<Res<AppState>>,
+    mut text_editor_state: Option<ResMut<TextEditorState>>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+    mut glyph_navigation: ResMut<GlyphNavigation>,
+    layouts: Res<BufferLayouts>,
+    mut camera_query: Query<&mut Transform, With<DesignCamera>>,
+) {
+    for GoToGlyph(glyph_name) in events.read() {
+        let (Some(state), Some(text_editor)) = (app_state.as_ref(), text_editor_state.as_mut())
+        else {
+            continue;
+        };
+        let Some(glyph) = state.workspace.font.glyphs.get(glyph_name) else {
+            warn!("Glyph '{}' is not in the font", glyph_name);
+            continue;
+        };
+        let x_height = state
+            .workspace
+            .info
+            .metrics
+            .x_height
+            .unwrap_or(FALLBACK_X_HEIGHT) as f32;
+
+        let nowhere_to_show = text_editor.get_active_sort().is_none()
+            && glyph_sort_index(text_editor, glyph_name).is_none();
+        if nowhere_to_show {
+            // Center the new sort in the view, half an x-height below the
+            // middle
+            let view_center = camera_query
+                .iter()
+                .next()
+                .map_or(Vec2::ZERO, |transform| transform.translation.truncate());
+            let advance = glyph.advance_width as f32;
+            let root_position = view_center - Vec2::new(advance, x_height) / 2.0;
+            let buffer_entity =
+                place_glyph_sort(&mut commands, text_editor, glyph_name, glyph, root_position);
+            commands.insert_resource(ActiveTextBuffer {
+                buffer_entity: Some(buffer_entity),
+            });
+            let index = text_editor.buffer.len() - 1;
+            queue_respawn(text_editor, &mut respawn_queue, index);
+            glyph_navigation.set_current_glyph(glyph_name.clone());
+            info!("Placed a sort of glyph '{}'", glyph_name);
+        } else {
+            jump_to_glyph(
+                state,
+                text_editor,
+                &mut respawn_queue,
+                &mut glyph_navigation,
+                glyph_name,
+            );
+        }
+
+        // Center on the middle of the sort, half an x-height up
+        let Some((index, sort)) = text_editor.get_active_sort() else {
+            continue;
+        };
+        let advance = match &sort.kind {
+            SortKind::Glyph { advance_width, .. } => *advance_width,
+            SortKind::LineBreak => 0.0,
+        };
+        let origin = layouts.origin(index).unwrap_or(sort.root_position);
+        let center = origin + Vec2::new(advance, x_height) / 2.0;
+        for mut transform in camera_query.iter_mut() {
+            transform.translation.x = center.x;
+            transform.translation.y = center.y;
+        }
+    }
+}
+
+/// Shows the next or previous glyph in encoding order in the active sort,
+/// or with no active sort goes to the glyph after the last one shown
+fn step_active_glyph(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    app_state: Option<Res<AppState>>,
+    mut text_editor_state: Option<ResMut<TextEditorState>>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+    mut glyph_navigation: ResMut<GlyphNavigation>,
+    mut go_to_events: EventWriter<GoToGlyph>,
+) {
+    let direction = if Shortcut::NextGlyph.just_pressed(&keyboard) {
+        CycleDirection::Next
+    } else if Shortcut::PreviousGlyph.just_pressed(&keyboard) {
+        CycleDirection::Previous
+    } else {
+        return;
+    };
+    let (Some(state), Some(text_editor)) = (app_state.as_ref(), text_editor_state.as_mut()) else {
+        return;
+    };
+
+    let active = text_editor
+        .get_active_sort()
+        .map(|(index, sort)| (index, sort.kind.glyph_name().to_string()));
+    let current = match &active {
+        Some((_, glyph_name)) => Some(glyph_name.as_str()),
+        None => glyph_navigation.current_glyph.as_deref(),
+    };
+    let order = glyphs_in_encoding_order(&state.workspace.font);
+    let Some(glyph_name) = step_glyph(&order, current, direction) else {
+        return;
+    };
+    let Some((index, _)) = active else {
+        go_to_events.write(GoToGlyph(glyph_name.to_string()));
+        return;
+    };
+    let Some(glyph) = state.workspace.font.glyphs.get(glyph_name) else {
+        return;
+    };
+    show_glyph_in_sort(text_editor, &mut respawn_queue, index, glyph_name, glyph);
+    glyph_navigation.set_current_glyph(glyph_name.to_string());
+    debug!("Showing glyph '{}' in the active sort", glyph_name);
+}
@@ -0,0 +1,188 @@
+//! Typing a handle's length or angle
+//!
+//! "Set handle length…" and "Set handle angle…" in a handle's right-click
+//! menu (see `canvas_menu`) open a prompt filled with the current value.
+//! Typing goes into the prompt until Enter sets the value or Escape drops
+//! it. Angles are in degrees, counterclockwise from the x axis, as seen
+//! from the handle's on-curve point; lengths must be positive.
+
+use crate::core::state::AppState;
+use crate::editing::canvas_menu::{edit_contour, CanvasAction};
+use crate::editing::selection::components::GlyphPointReference;
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::text_field_focus::{TextField, TextFieldFocus, TextFieldSystems};
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+
+/// Which of a handle's values is typed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandleValue {
+    Length,
+    Angle,
+}
+
+impl HandleValue {
+    pub fn label(self) -> String {
+        let id = match self {
+            HandleValue::Length => "handle-entry-length",
+            HandleValue::Angle => "handle-entry-angle",
+        };
+        crate::i18n::tr(id)
+    }
+}
+
+/// The handle value being typed, if any
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct HandleEntry {
+    pub target: Option<(GlyphPointReference, HandleValue)>,
+    pub typed: String,
+}
+
+pub struct HandleEntryPlugin;
+
+impl Plugin for HandleEntryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HandleEntry>().add_systems(
+            Update,
+            (
+                open_handle_entry,
+                handle_entry_keys.in_set(TextFieldSystems),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Opens the prompt from the canvas menu, filled with the current value
+fn open_handle_entry(
+    mut actions: EventReader<CanvasAction>,
+    app_state: Option<Res<AppState>>,
+    mut entry: ResMut<HandleEntry>,
+) {
+    for action in actions.read() {
+        let CanvasAction::EditHandle(reference, value) = action else {
+            continue;
+        };
+        let Some((length, angle)) = app_state.as_deref().and_then(|state| {
+            state
+                .workspace
+                .font
+                .editing()
+                .get_glyph(&reference.glyph_name)?
+                .outline
+                .as_ref()?
+                .contours
+                .get(reference.contour_index)?
+                .handle_polar(reference.point_index)
+        }) else {
+            continue;
+        };
+        let current = match value {
+            HandleValue::Length => length,
+            HandleValue::Angle => angle,
+        };
+        entry.typed = format_value(current);
+        entry.target = Some((reference.clone(), *value));
+    }
+}
+
+/// A value as the prompt starts with it: one decimal, without a trailing
+/// `.0`
+fn format_value(value: f64) -> String {
+    let rounded = (value * 10.0).round() / 10.0;
+    if rounded.fract() == 0.0 {
+        format!("{rounded:.0}")
+    } else {
+        format!("{rounded:.1}")
+    }
+}
+
+/// Types into the prompt; Enter sets the value and Escape drops it
+fn handle_entry_keys(
+    mut key_events: EventReader<KeyboardInput>,
+    mut focus: ResMut<TextFieldFocus>,
+    mut entry: ResMut<HandleEntry>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    focus.set(TextField::HandleEntry, entry.target.is_some());
+    let Some((reference, value)) = entry.target.clone() else {
+        key_events.clear();
+        return;
+    };
+    let mut typed = entry.typed.clone();
+
+    let mut done = None;
+    for event in key_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(text) => typed.extend(
+                text.chars()
+                    .filter(|c| c.is_ascii_digit() || matches!(c, '.' | '-')),
+            ),
+            Key::Backspace => {
+                typed.pop();
+            }
+            Key::Enter => done = Some(true),
+            Key::Escape => done = Some(false),
+            _ => {}
+        }
+    }
+
+    match done {
+        Some(keep) => {
+            let number = typed
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite() && (value == HandleValue::Angle || *n > 0.0));
+            let edited = match (keep, number, app_state.as_deref_mut()) {
+                (true, Some(number), Some(state)) => edit_contour(
+                    state,
+                    &reference.glyph_name,
+                    reference.contour_index,
+                    |contour| {
+                        let index = reference.point_index;
+                        let Some((length, angle)) = contour.handle_polar(index) else {
+                            return Some(contour.clone());
+                        };
+                        Some(match value {
+                            HandleValue::Length => contour.with_handle_polar(index, number, angle),
+                            HandleValue::Angle => contour.with_handle_polar(index, length, number),
+                        })
+                    },
+                ),
+                _ => false,
+            };
+            if edited {
+                debug!("Set {} of {:?} to {}", value.label(), reference, typed);
+                app_state_changed.write(AppStateChanged);
+                visual_update_tracker.needs_update = true;
+            }
+            entry.target = None;
+            entry.typed.clear();
+        }
+        None => {
+            if entry.typed != typed {
+                entry.typed = typed;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value(120.0), "120");
+        assert_eq!(format_value(45.04), "45");
+        assert_eq!(format_value(-33.333), "-33.3");
+    }
+}
@@ -7,17 +7,94 @@
 //! - Sort system for movable type placement and editing
 
 
+pub mod anchor_sync;
+pub mod auto_kerning;
+pub mod auto_spacing;
+pub mod brace_layers;
+pub mod build_inspector;
+pub mod canvas_image;
+pub mod canvas_menu;
+pub mod clipboard;
+pub mod color_layers;
+pub mod compile_errors;
+pub mod component_graph;
+pub mod composites;
+pub mod curve_flavor;
+pub mod design_frames;
+pub mod designspace_rules;
 pub mod edit_session;
+pub mod find_replace;
+pub mod font_changes;
+pub mod gamepad_editing;
+pub mod glyph_autocomplete;
+pub mod glyph_blend;
+pub mod glyph_filters;
+pub mod glyph_ghosts;
+pub mod glyph_jump;
+pub mod glyph_layers;
+pub mod glyph_names;
+pub mod goto_glyph;
+pub mod handle_entry;
 pub mod offcurve_insertion;
+pub mod os2_classification;
+pub mod outline_locks;
+pub mod saved_glyphs;
 pub mod selection;
+pub mod smart_pieces;
 pub mod smooth_curves;
 pub mod sort;
+pub mod spacing_import;
+pub mod start_points;
+pub mod stat;
+pub mod stem_report;
+pub mod subset;
 pub mod system_sets;
+pub mod text_buffers;
 pub mod text_editor_plugin;
+pub mod text_field_focus;
+pub mod workspace_session;
 
 // Re-export commonly used items
+pub use anchor_sync::AnchorSyncPlugin;
+pub use auto_kerning::AutoKerningPlugin;
+pub use auto_spacing::AutoSpacingPlugin;
+pub use brace_layers::BraceLayersPlugin;
+pub use build_inspector::BuildInspectorPlugin;
+pub use canvas_image::CanvasImagePlugin;
+pub use canvas_menu::CanvasMenuPlugin;
+pub use clipboard::OutlineClipboardPlugin;
+pub use color_layers::ColorLayersPlugin;
+pub use compile_errors::CompileErrorsPlugin;
+pub use component_graph::ComponentGraphPlugin;
+pub use composites::CompositesPlugin;
+pub use curve_flavor::CurveFlavorPlugin;
+pub use design_frames::DesignFramesPlugin;
+pub use designspace_rules::DesignspaceRulesPlugin;
 pub use edit_session::EditSessionPlugin;
+pub use find_replace::FindReplacePlugin;
+pub use font_changes::FontChangesPlugin;
+pub use gamepad_editing::GamepadEditingPlugin;
+pub use glyph_autocomplete::GlyphAutocompletePlugin;
+pub use glyph_blend::GlyphBlendPlugin;
+pub use glyph_filters::GlyphFiltersPlugin;
+pub use glyph_ghosts::GlyphGhostsPlugin;
+pub use glyph_layers::GlyphLayersPlugin;
+pub use glyph_names::GlyphNamesPlugin;
+pub use goto_glyph::GoToGlyphPlugin;
+pub use handle_entry::HandleEntryPlugin;
+pub use os2_classification::Os2ClassificationPlugin;
+pub use outline_locks::OutlineLocksPlugin;
+pub use saved_glyphs::SavedGlyphsPlugin;
 pub use selection::SelectionPlugin;
+pub use smart_pieces::SmartPiecesPlugin;
 pub use sort::SortPlugin;
+pub use spacing_import::SpacingImportPlugin;
+pub use start_points::StartPointsPlugin;
+pub use stat::StatPlugin;
+pub use stem_report::StemReportPlugin;
+pub use subset::SubsetPlugin;
 pub use system_sets::{FontEditorSets, FontEditorSystemSetsPlugin};
+pub use text_buffers::TextBuffersPlugin;
 pub use text_editor_plugin::TextEditorPlugin;
+pub use text_field_focus::TextFieldFocusPlugin;
+pub use workspace_session::WorkspaceSessionPlugin;
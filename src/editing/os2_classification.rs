@@ -0,0 +1,100 @@
+//! OS/2 classification assistant
+//!
+//! Cmd/Ctrl+Alt+Shift+P shows the OS/2 pane, which suggests the weight and
+//! width classes, PANOSE digits and Unicode range and code page bits from
+//! the glyph set and metrics (see `font_source::os2`), lists where the font
+//! info differs, and writes the suggestions into the font info one value at
+//! a time or all at once. The suggestions follow the font while the pane is
+//! shown.
+
+use crate::core::state::AppState;
+use crate::editing::selection::events::AppStateChanged;
+use crate::font_source::os2::{Os2Classification, Os2Field, Os2Mismatch};
+use crate::io::shortcuts::Shortcut;
+use bevy::prelude::*;
+
+/// Whether the OS/2 pane is shown, the suggestions and where the font info
+/// differs from them
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct Os2PaneState {
+    pub visible: bool,
+    pub suggestion: Option<Os2Classification>,
+    pub mismatches: Vec<Os2Mismatch>,
+}
+
+/// Write suggested values into the font info
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum Os2Event {
+    Apply(Os2Field),
+    ApplyAll,
+}
+
+pub struct Os2ClassificationPlugin;
+
+impl Plugin for Os2ClassificationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Os2PaneState>()
+            .add_event::<Os2Event>()
+            .add_systems(
+                Update,
+                (toggle_os2_pane, handle_os2_events, refresh_os2_suggestion).chain(),
+            );
+    }
+}
+
+/// Cmd/Ctrl+Alt+Shift+P shows or hides the OS/2 pane
+fn toggle_os2_pane(keyboard: Res<ButtonInput<KeyCode>>, mut pane: ResMut<Os2PaneState>) {
+    if Shortcut::Os2Classification.just_pressed(&keyboard) {
+        pane.visible = !pane.visible;
+        debug!("OS/2 pane shown: {}", pane.visible);
+    }
+}
+
+fn handle_os2_events(
+    mut events: EventReader<Os2Event>,
+    pane: Res<Os2PaneState>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+) {
+    let (Some(state), Some(suggestion)) = (app_state.as_mut(), pane.suggestion.as_ref()) else {
+        events.clear();
+        return;
+    };
+
+    for event in events.read() {
+        let info = &mut state.workspace.info;
+        match *event {
+            Os2Event::Apply(field) => suggestion.apply(field, info),
+            Os2Event::ApplyAll => {
+                for mismatch in &pane.mismatches {
+                    suggestion.apply(mismatch.field, info);
+                }
+            }
+        }
+        app_state_changed.write(AppStateChanged);
+    }
+}
+
+/// Suggests the classification again when the font changes, while the
+/// pane is shown
+fn refresh_os2_suggestion(mut pane: ResMut<Os2PaneState>, app_state: Option<Res<AppState>>) {
+    if !pane.visible {
+        return;
+    }
+    let Some(state) = app_state else {
+        if pane.suggestion.is_some() {
+            pane.suggestion = None;
+            pane.mismatches.clear();
+        }
+        return;
+    };
+    if !state.is_changed() && !pane.is_changed() {
+        return;
+    }
+    let suggestion = Os2Classification::suggest(&state.workspace.font, &state.workspace.info);
+    let mismatches = suggestion.mismatches(&state.workspace.info);
+    if pane.suggestion.as_ref() != Some(&suggestion) || pane.mismatches != mismatches {
+        pane.suggestion = Some(suggestion);
+        pane.mismatches = mismatches;
+    }
+}
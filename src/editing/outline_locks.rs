@@ -0,0 +1,226 @@
+//! Locked and hidden contours
+//!
+//! Locking a contour keeps its points from being selected, so they can't be
+//! dragged or nudged by accident while working on the contours around it,
+//! like a counter inside the outer contour being adjusted. Locked contours
+//! are drawn in a fainter color without their points; hidden contours aren't
+//! drawn at all. With the select tool:
+//! - Cmd/Ctrl+L locks the contours with selected points
+//! - Cmd/Ctrl+Shift+L unlocks every contour of the active glyph
+//! - Cmd/Ctrl+Shift+H hides the contours with selected points, or shows the
+//!   hidden ones again when nothing is selected
+//!
+//! Locks belong to the glyph and last until the app quits. Components of
+//! mixed glyphs are drawn filled and can't be selected, so they are always
+//! locked in the editor.
+
+use crate::editing::selection::components::{GlyphPointReference, Selectable, SelectionState};
+use crate::editing::sort::manager::SortPointEntity;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::editing::FontEditorSets;
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use bevy::prelude::*;
+use std::collections::{BTreeSet, HashMap};
+
+/// Changes to the locked and hidden contours of the active glyph
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutlineLockEvent {
+    /// Lock the contours with selected points
+    LockSelected,
+    /// Hide the contours with selected points
+    HideSelected,
+    UnlockAll,
+    ShowAll,
+}
+
+/// Locked and hidden contours of one glyph, by contour index
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GlyphLocks {
+    pub locked: BTreeSet<usize>,
+    pub hidden: BTreeSet<usize>,
+}
+
+static NO_LOCKS: GlyphLocks = GlyphLocks {
+    locked: BTreeSet::new(),
+    hidden: BTreeSet::new(),
+};
+
+impl GlyphLocks {
+    pub fn is_locked(&self, contour_index: usize) -> bool {
+        self.locked.contains(&contour_index)
+    }
+
+    pub fn is_hidden(&self, contour_index: usize) -> bool {
+        self.hidden.contains(&contour_index)
+    }
+
+    /// Whether the contour's points can be selected
+    pub fn is_editable(&self, contour_index: usize) -> bool {
+        !self.is_locked(contour_index) && !self.is_hidden(contour_index)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.locked.is_empty() && self.hidden.is_empty()
+    }
+}
+
+/// Locked and hidden contours of each glyph this session
+#[derive(Resource, Default)]
+pub struct OutlineLocks {
+    glyphs: HashMap<String, GlyphLocks>,
+}
+
+impl OutlineLocks {
+    pub fn glyph(&self, glyph_name: &str) -> &GlyphLocks {
+        self.glyphs.get(glyph_name).unwrap_or(&NO_LOCKS)
+    }
+
+    fn apply(&mut self, glyph_name: &str, event: OutlineLockEvent, contours: &BTreeSet<usize>) {
+        let glyph = self.glyphs.entry(glyph_name.to_string()).or_default();
+        match event {
+            OutlineLockEvent::LockSelected => glyph.locked.extend(contours),
+            OutlineLockEvent::HideSelected => glyph.hidden.extend(contours),
+            OutlineLockEvent::UnlockAll => glyph.locked.clear(),
+            OutlineLockEvent::ShowAll => glyph.hidden.clear(),
+        }
+        if glyph.is_empty() {
+            self.glyphs.remove(glyph_name);
+        }
+    }
+}
+
+pub struct OutlineLocksPlugin;
+
+impl Plugin for OutlineLocksPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<OutlineLocks>()
+            .add_event::<OutlineLockEvent>()
+            .add_systems(
+                Update,
+                (
+                    handle_outline_lock_shortcuts,
+                    apply_outline_lock_events,
+                    sync_locked_points,
+                )
+                    .chain()
+                    .after(FontEditorSets::Input)
+                    .before(FontEditorSets::EntitySync),
+            );
+    }
+}
+
+/// Turns the select tool's lock and hide shortcuts into events
+pub fn handle_outline_lock_shortcuts(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_tool: Res<CurrentTool>,
+    selection_state: Res<SelectionState>,
+    mut lock_events: EventWriter<OutlineLockEvent>,
+) {
+    if current_tool.get_current() != Some("select") {
+        return;
+    }
+    if Shortcut::LockSelected.just_pressed(&keyboard) {
+        lock_events.write(OutlineLockEvent::LockSelected);
+    }
+    if Shortcut::UnlockAll.just_pressed(&keyboard) {
+        lock_events.write(OutlineLockEvent::UnlockAll);
+    }
+    if Shortcut::HideOrShow.just_pressed(&keyboard) {
+        lock_events.write(if selection_state.selected.is_empty() {
+            OutlineLockEvent::ShowAll
+        } else {
+            OutlineLockEvent::HideSelected
+        });
+    }
+}
+
+/// Lock, hide, unlock or show contours of the active glyph
+pub fn apply_outline_lock_events(
+    mut lock_events: EventReader<OutlineLockEvent>,
+    mut locks: ResMut<OutlineLocks>,
+    selection_state: Res<SelectionState>,
+    active_sort_query: Query<&Sort, With<ActiveSort>>,
+    point_query: Query<&GlyphPointReference>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    let Ok(sort) = active_sort_query.single() else {
+        lock_events.clear();
+        return;
+    };
+
+    for &event in lock_events.read() {
+        let contours: BTreeSet<usize> = selection_state
+            .selected
+            .iter()
+            .filter_map(|&entity| point_query.get(entity).ok())
+            .filter(|point_ref| point_ref.glyph_name == sort.glyph_name)
+            .map(|point_ref| point_ref.contour_index)
+            .collect();
+        let before = locks.glyph(&sort.glyph_name).clone();
+        locks.apply(&sort.glyph_name, event, &contours);
+        let after = locks.glyph(&sort.glyph_name);
+        if *after != before {
+            info!(
+                glyph = %sort.glyph_name,
+                ?event,
+                locked = ?after.locked,
+                hidden = ?after.hidden,
+                "Changed locked contours"
+            );
+            visual_update_tracker.needs_update = true;
+        }
+    }
+}
+
+/// Keep points of locked and hidden contours out of selections, including
+/// points spawned again after an edit
+pub fn sync_locked_points(
+    mut commands: Commands,
+    locks: Res<OutlineLocks>,
+    mut selection_state: ResMut<SelectionState>,
+    point_query: Query<(Entity, &GlyphPointReference, Has<Selectable>), With<SortPointEntity>>,
+    new_points: Query<(), Added<GlyphPointReference>>,
+) {
+    if !locks.is_changed() && new_points.is_empty() {
+        return;
+    }
+    for (entity, point_ref, selectable) in point_query.iter() {
+        let editable = locks
+            .glyph(&point_ref.glyph_name)
+            .is_editable(point_ref.contour_index);
+        if editable && !selectable {
+            commands.entity(entity).insert(Selectable);
+        } else if !editable && selectable {
+            commands.entity(entity).remove::<Selectable>();
+            if selection_state.selected.contains(&entity) {
+                // sync_selected_components removes the Selected component
+                selection_state.selected.remove(&entity);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_and_hide_contours() {
+        let mut locks = OutlineLocks::default();
+        let counter = BTreeSet::from([1]);
+        locks.apply("o", OutlineLockEvent::LockSelected, &counter);
+        locks.apply("o", OutlineLockEvent::HideSelected, &BTreeSet::from([2]));
+        assert!(locks.glyph("o").is_editable(0));
+        assert!(!locks.glyph("o").is_editable(1));
+        assert!(!locks.glyph("o").is_editable(2));
+        assert!(locks.glyph("p").is_editable(1));
+
+        locks.apply("o", OutlineLockEvent::UnlockAll, &BTreeSet::new());
+        assert!(locks.glyph("o").is_editable(1));
+        assert!(locks.glyph("o").is_hidden(2));
+        locks.apply("o", OutlineLockEvent::ShowAll, &BTreeSet::new());
+        assert!(locks.glyphs.is_empty());
+    }
+}
@@ -0,0 +1,276 @@
+//! Reverting one glyph to how it was saved
+//!
+//! The foreground of every glyph is remembered as it was when the font was
+//! loaded or last saved, so experimenting on a single glyph can be abandoned
+//! without reverting the whole font: "Revert to saved" in the menu of a sort
+//! handle puts the glyph's outline, components and advance back.
+//! Cmd/Ctrl+Alt+K ghosts the saved outline behind the active sort while the
+//! glyph differs from it, to compare before and after. Saved glyphs follow
+//! renames, so a renamed glyph can still be reverted. Nothing is kept after
+//! the app quits.
+
+use crate::core::state::{AppState, ComponentData, FontData, GlyphData, OutlineData};
+use crate::editing::font_changes::GlyphsRenamed;
+use crate::editing::selection::events::AppStateChanged;
+use crate::font_source::rename::rename_in_glyphs;
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use crate::systems::commands::{SaveFileAsEvent, SaveFileEvent};
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// The foreground of each glyph as it was last loaded or saved
+#[derive(Resource, Default)]
+pub struct SavedGlyphs {
+    /// Path of the font the glyphs were taken from, `None` before any was
+    font_path: Option<Option<PathBuf>>,
+    glyphs: HashMap<String, GlyphData>,
+    /// Whether the saved outline is ghosted behind the active sort
+    pub ghost_visible: bool,
+}
+
+/// Put a glyph back as it was saved
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct RevertGlyphEvent {
+    pub glyph_name: String,
+}
+
+/// The parts of a glyph a revert puts back
+fn drawing(glyph: Option<&GlyphData>) -> (Option<&OutlineData>, &[ComponentData]) {
+    match glyph {
+        Some(glyph) => (glyph.outline.as_ref(), glyph.components.as_slice()),
+        None => (None, &[]),
+    }
+}
+
+impl SavedGlyphs {
+    /// Remember the foreground of every glyph of `font`
+    pub fn remember(&mut self, font: &FontData) {
+        self.font_path = Some(font.path.clone());
+        self.glyphs = font.glyphs.clone();
+    }
+
+    /// Follow renamed glyphs, and the components referring to them
+    pub fn rename_glyphs(&mut self, renames: &HashMap<String, String>) {
+        rename_in_glyphs(&mut self.glyphs, renames);
+    }
+
+    /// Whether a glyph's foreground differs from how it was saved
+    pub fn is_modified(&self, font: &FontData, glyph_name: &str) -> bool {
+        let current = font.glyphs.get(glyph_name);
+        let saved = self.glyphs.get(glyph_name);
+        let advance_changed = current
+            .zip(saved)
+            .is_some_and(|(current, saved)| current.advance_width != saved.advance_width);
+        advance_changed || drawing(current) != drawing(saved)
+    }
+
+    /// The saved outline to ghost behind a glyph, if it's shown and the
+    /// glyph was changed since. Only the foreground is compared, so there is
+    /// no ghost while another layer is being edited
+    pub fn ghost_outline(&self, font: &FontData, glyph_name: &str) -> Option<&OutlineData> {
+        if !self.ghost_visible
+            || font.editing_layer.is_some()
+            || !self.is_modified(font, glyph_name)
+        {
+            return None;
+        }
+        self.glyphs.get(glyph_name)?.outline.as_ref()
+    }
+
+    /// Put a glyph's foreground back as it was saved; false for glyphs
+    /// added since
+    pub fn revert(&self, font: &mut FontData, glyph_name: &str) -> bool {
+        let Some(saved) = self.glyphs.get(glyph_name) else {
+            return false;
+        };
+        match font.glyphs.get_mut(glyph_name) {
+            Some(glyph) => {
+                glyph.outline = saved.outline.clone();
+                glyph.components = saved.components.clone();
+                glyph.advance_width = saved.advance_width;
+            }
+            None => {
+                font.glyphs.insert(glyph_name.to_string(), saved.clone());
+            }
+        }
+        true
+    }
+}
+
+pub struct SavedGlyphsPlugin;
+
+impl Plugin for SavedGlyphsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SavedGlyphs>()
+            .add_event::<RevertGlyphEvent>()
+            .add_systems(
+                Update,
+                (
+                    remember_saved_glyphs,
+                    follow_renamed_glyphs,
+                    toggle_saved_ghost,
+                    handle_revert_glyph_events,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Remembers the glyphs when a font is opened and whenever it's saved
+fn remember_saved_glyphs(
+    mut saves: EventReader<SaveFileEvent>,
+    mut saves_as: EventReader<SaveFileAsEvent>,
+    app_state: Option<Res<AppState>>,
+    mut saved_glyphs: ResMut<SavedGlyphs>,
+) {
+    let saved = saves.read().count() + saves_as.read().count() > 0;
+    let Some(state) = app_state else {
+        return;
+    };
+    let font = &state.workspace.font;
+    let opened = saved_glyphs.font_path.as_ref() != Some(&font.path);
+    // Fonts without a path can't be saved
+    if opened || (saved && font.path.is_some()) {
+        saved_glyphs.remember(font);
+        debug!("Remembered {} saved glyphs", saved_glyphs.glyphs.len());
+    }
+}
+
+/// Renames the saved glyphs as they are renamed in the font
+fn follow_renamed_glyphs(
+    mut renamed_glyphs: EventReader<GlyphsRenamed>,
+    mut saved_glyphs: ResMut<SavedGlyphs>,
+) {
+    for GlyphsRenamed(renames) in renamed_glyphs.read() {
+        saved_glyphs.rename_glyphs(renames);
+    }
+}
+
+/// Cmd/Ctrl+Alt+K shows or hides the saved outline
+fn toggle_saved_ghost(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut saved_glyphs: ResMut<SavedGlyphs>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    if Shortcut::SavedGhost.just_pressed(&keyboard) {
+        saved_glyphs.ghost_visible = !saved_glyphs.ghost_visible;
+        visual_update_tracker.needs_update = true;
+        debug!("Saved outline shown: {}", saved_glyphs.ghost_visible);
+    }
+}
+
+fn handle_revert_glyph_events(
+    mut events: EventReader<RevertGlyphEvent>,
+    mut app_state: Option<ResMut<AppState>>,
+    saved_glyphs: Res<SavedGlyphs>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    let Some(state) = app_state.as_mut() else {
+        events.clear();
+        return;
+    };
+
+    for event in events.read() {
+        let font = &mut state.workspace.font;
+        if !saved_glyphs.is_modified(font, &event.glyph_name) {
+            continue;
+        }
+        if !saved_glyphs.revert(font, &event.glyph_name) {
+            warn!("'{}' hasn't been saved yet", event.glyph_name);
+            continue;
+        }
+        info!("Reverted '{}' to saved", event.glyph_name);
+        app_state_changed.write(AppStateChanged);
+        visual_update_tracker.needs_update = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::state::{ContourData, PointData, PointTypeData};
+    use crate::font_source::BACKGROUND_LAYER_NAME;
+
+    fn glyph(name: &str, points: usize) -> GlyphData {
+        let point = PointData {
+            x: 0.0,
+            y: 0.0,
+            point_type: PointTypeData::Line,
+        };
+        GlyphData {
+            outline: Some(OutlineData {
+                contours: vec![ContourData {
+                    points: vec![point; points],
+                }],
+            }),
+            ..GlyphData::test(name, 500.0)
+        }
+    }
+
+    #[test]
+    fn test_revert_to_saved() {
+        let mut font = FontData::default();
+        font.glyphs.insert("a".to_string(), glyph("a", 3));
+        let mut saved = SavedGlyphs::default();
+        saved.remember(&font);
+        assert!(!saved.is_modified(&font, "a"));
+
+        font.glyphs.insert("a".to_string(), glyph("a", 4));
+        font.glyphs.insert("b".to_string(), glyph("b", 3));
+        assert!(saved.is_modified(&font, "a"));
+        saved.ghost_visible = true;
+        assert_eq!(
+            saved.ghost_outline(&font, "a"),
+            glyph("a", 3).outline.as_ref()
+        );
+
+        // Only the reverted glyph goes back; new glyphs have nothing to go
+        // back to
+        assert!(saved.revert(&mut font, "a"));
+        assert!(!saved.is_modified(&font, "a"));
+        assert!(!saved.revert(&mut font, "b"));
+        assert!(saved.is_modified(&font, "b"));
+    }
+
+    #[test]
+    fn test_revert_while_a_layer_is_edited_reverts_the_foreground() {
+        let mut font = FontData::default();
+        font.glyphs.insert("a".to_string(), glyph("a", 3));
+        font.glyphs
+            .insert("space".to_string(), GlyphData::test("space", 250.0));
+        font.switch_editing_layer(Some(BACKGROUND_LAYER_NAME));
+        let mut saved = SavedGlyphs::default();
+        saved.remember(&font);
+        assert_eq!(saved.glyphs["a"], glyph("a", 3));
+        assert!(saved.glyphs.contains_key("space"));
+
+        *font.editing_glyph_mut("a").unwrap() = glyph("a", 5);
+        font.glyphs.insert("a".to_string(), glyph("a", 4));
+        assert!(saved.is_modified(&font, "a"));
+        assert!(saved.revert(&mut font, "a"));
+
+        // The layer's drawing stays, the foreground is back as saved
+        assert_eq!(font.editing().get_glyph("a"), Some(&glyph("a", 5)));
+        assert_eq!(font.glyphs["a"], glyph("a", 3));
+    }
+
+    #[test]
+    fn test_saved_glyphs_follow_renames() {
+        let mut font = FontData::default();
+        font.glyphs.insert("a".to_string(), glyph("a", 3));
+        let mut saved = SavedGlyphs::default();
+        saved.remember(&font);
+
+        let renames = HashMap::from([("a".to_string(), "a.ss01".to_string())]);
+        font.rename_glyphs(&renames).unwrap();
+        saved.rename_glyphs(&renames);
+        assert!(!saved.is_modified(&font, "a.ss01"));
+
+        font.glyphs.insert("a.ss01".to_string(), glyph("a.ss01", 4));
+        assert!(saved.revert(&mut font, "a.ss01"));
+        assert_eq!(font.glyphs["a.ss01"], glyph("a.ss01", 3));
+    }
+}
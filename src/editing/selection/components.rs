@@ -74,6 +74,10 @@ impl Default for PointType {
 }
 
 /// Resource to track the global selection state
+///
+/// This is the point-level selection. Sorts selected by their handles are
+/// marked `Selected` without being listed here, so a selection is always
+/// either points of the active sort or whole sorts.
 #[derive(Resource, Debug, Default, Reflect)]
 #[reflect(Resource)]
 pub struct SelectionState {
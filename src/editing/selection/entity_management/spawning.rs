@@ -103,7 +103,7 @@ fn spawn_appstate_points(
     app_state: &AppState,
 ) {
     // Get glyph data for the active sort
-    if let Some(glyph_data) = app_state.workspace.font.get_glyph(glyph_name) {
+    if let Some(glyph_data) = app_state.workspace.font.editing().get_glyph(glyph_name) {
         if let Some(outline) = &glyph_data.outline {
             let mut point_count = 0;
 
@@ -11,5 +11,7 @@ pub struct AppStateChanged;
 #[derive(Resource)]
 pub struct ClickWorldPosition;
 
-/// Constants for selection
-pub const SELECTION_MARGIN: f32 = 16.0; // Distance in pixels for selection hit testing
+/// Distance for selection hit testing, in logical pixels: the window's scale
+/// factor keeps it the same size on monitors of any DPI. The select tool
+/// uses the click tolerance setting (`HitTestSettings`), which starts here
+pub const SELECTION_MARGIN: f32 = 16.0;
@@ -0,0 +1,272 @@
+//! Selection history
+//!
+//! Each glyph remembers the point selections made in it, so going back and
+//! forth between two groups of points, say the stems being spaced or the
+//! handles of a curve, takes a shortcut instead of a new marquee. With the
+//! select tool:
+//! - Cmd/Ctrl+Alt+[ selects the previous selection again and Cmd/Ctrl+Alt+]
+//!   the one after it, so stepping back can be undone
+//! - Cmd/Ctrl+Alt+Shift+1…9 saves the selection under that number and
+//!   Cmd/Ctrl+Alt+1…9 selects it again
+//!
+//! Other systems can send `SelectionHistoryEvent`s, and save selections
+//! under any name. Points are remembered by contour and point index, so
+//! selections outlive the point entities. Nothing is kept after the app
+//! quits.
+
+use crate::editing::selection::components::{GlyphPointReference, SelectionState};
+use crate::editing::selection::DragSelectionState;
+use crate::editing::sort::manager::SortPointEntity;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::editing::FontEditorSets;
+use crate::io::shortcuts::{Shortcut, DIGIT_KEYS};
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use bevy::prelude::*;
+use std::collections::{BTreeSet, HashMap};
+
+/// Selections kept to go back to, per glyph
+const HISTORY_LIMIT: usize = 50;
+
+/// Selected points of a glyph, by contour and point index
+pub type PointKeys = BTreeSet<(usize, usize)>;
+
+/// Changes to the active glyph's selection from its history
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum SelectionHistoryEvent {
+    Previous,
+    Next,
+    /// Remember the selection under a name, replacing what it named before
+    Save(String),
+    /// Select what was saved under a name
+    Recall(String),
+}
+
+/// The selections made in one glyph
+#[derive(Debug, Default)]
+struct GlyphSelections {
+    current: PointKeys,
+    back: Vec<PointKeys>,
+    forward: Vec<PointKeys>,
+    saved: HashMap<String, PointKeys>,
+}
+
+impl GlyphSelections {
+    /// Note a new selection, which ends any selections stepped back from
+    fn record(&mut self, keys: PointKeys) {
+        if keys == self.current {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.current, keys);
+        if !previous.is_empty() {
+            self.back.push(previous);
+            if self.back.len() > HISTORY_LIMIT {
+                self.back.remove(0);
+            }
+        }
+        self.forward.clear();
+    }
+
+    fn step_back(&mut self) -> Option<PointKeys> {
+        let previous = self.back.pop()?;
+        let current = std::mem::replace(&mut self.current, previous);
+        if !current.is_empty() {
+            self.forward.push(current);
+        }
+        Some(self.current.clone())
+    }
+
+    fn step_forward(&mut self) -> Option<PointKeys> {
+        let next = self.forward.pop()?;
+        let current = std::mem::replace(&mut self.current, next);
+        if !current.is_empty() {
+            self.back.push(current);
+        }
+        Some(self.current.clone())
+    }
+
+    /// A saved selection, which becomes the newest one in the history
+    fn recall(&mut self, name: &str) -> Option<PointKeys> {
+        let keys = self.saved.get(name)?.clone();
+        self.record(keys.clone());
+        Some(keys)
+    }
+}
+
+/// Point selections made in each glyph this session
+#[derive(Resource, Default)]
+pub struct SelectionHistory {
+    glyphs: HashMap<String, GlyphSelections>,
+}
+
+impl SelectionHistory {
+    fn glyph_mut(&mut self, glyph_name: &str) -> &mut GlyphSelections {
+        self.glyphs.entry(glyph_name.to_string()).or_default()
+    }
+}
+
+pub struct SelectionHistoryPlugin;
+
+impl Plugin for SelectionHistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectionHistory>()
+            .add_event::<SelectionHistoryEvent>()
+            .add_systems(
+                Update,
+                (
+                    handle_selection_history_shortcuts,
+                    record_selection_history,
+                    apply_selection_history,
+                )
+                    .chain()
+                    .after(FontEditorSets::Input)
+                    .before(FontEditorSets::EntitySync),
+            );
+    }
+}
+
+/// Turns the select tool's selection history shortcuts into events
+pub fn handle_selection_history_shortcuts(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_tool: Res<CurrentTool>,
+    mut history_events: EventWriter<SelectionHistoryEvent>,
+) {
+    if current_tool.get_current() != Some("select") {
+        return;
+    }
+    if Shortcut::PreviousSelection.just_pressed(&keyboard) {
+        history_events.write(SelectionHistoryEvent::Previous);
+    }
+    if Shortcut::NextSelection.just_pressed(&keyboard) {
+        history_events.write(SelectionHistoryEvent::Next);
+    }
+
+    // Slots are named after their digit
+    let slot = |key: KeyCode| {
+        (1..)
+            .zip(DIGIT_KEYS)
+            .find(|&(_, digit)| digit == key)
+            .map(|(number, _)| format!("{number}"))
+    };
+    if let Some(name) = Shortcut::SaveSelection
+        .pressed_key(&keyboard)
+        .and_then(slot)
+    {
+        history_events.write(SelectionHistoryEvent::Save(name));
+    }
+    if let Some(name) = Shortcut::RecallSelection
+        .pressed_key(&keyboard)
+        .and_then(slot)
+    {
+        history_events.write(SelectionHistoryEvent::Recall(name));
+    }
+}
+
+/// Note each new point selection in the active glyph's history, once a
+/// marquee is let go
+pub fn record_selection_history(
+    selection_state: Res<SelectionState>,
+    drag_state: Res<DragSelectionState>,
+    active_sort_query: Query<(Entity, &Sort), With<ActiveSort>>,
+    point_query: Query<(&GlyphPointReference, &SortPointEntity)>,
+    mut history: ResMut<SelectionHistory>,
+) {
+    let changed = selection_state.is_changed() || drag_state.is_changed();
+    if !changed || drag_state.is_dragging {
+        return;
+    }
+    let Ok((sort_entity, sort)) = active_sort_query.single() else {
+        return;
+    };
+
+    let keys: PointKeys = selection_state
+        .selected
+        .iter()
+        .filter_map(|&entity| point_query.get(entity).ok())
+        .filter(|(_, sort_point)| sort_point.sort_entity == sort_entity)
+        .map(|(point_ref, _)| (point_ref.contour_index, point_ref.point_index))
+        .collect();
+    history.glyph_mut(&sort.glyph_name).record(keys);
+}
+
+/// Select points of the active glyph from its selection history
+pub fn apply_selection_history(
+    mut history_events: EventReader<SelectionHistoryEvent>,
+    mut history: ResMut<SelectionHistory>,
+    mut selection_state: ResMut<SelectionState>,
+    active_sort_query: Query<(Entity, &Sort), With<ActiveSort>>,
+    point_query: Query<(Entity, &GlyphPointReference, &SortPointEntity)>,
+) {
+    if history_events.is_empty() {
+        return;
+    }
+    let Ok((sort_entity, sort)) = active_sort_query.single() else {
+        history_events.clear();
+        return;
+    };
+    let glyph = history.glyph_mut(&sort.glyph_name);
+
+    for event in history_events.read() {
+        let keys = match event {
+            SelectionHistoryEvent::Previous => glyph.step_back(),
+            SelectionHistoryEvent::Next => glyph.step_forward(),
+            SelectionHistoryEvent::Save(name) => {
+                debug!(name = %name, points = glyph.current.len(), "Saved selection");
+                glyph.saved.insert(name.clone(), glyph.current.clone());
+                None
+            }
+            SelectionHistoryEvent::Recall(name) => glyph.recall(name),
+        };
+        let Some(keys) = keys else {
+            continue;
+        };
+
+        // Points deleted since are left out; sync_selected_components
+        // updates the Selected components to match
+        selection_state.selected = point_query
+            .iter()
+            .filter(|(_, point_ref, sort_point)| {
+                sort_point.sort_entity == sort_entity
+                    && keys.contains(&(point_ref.contour_index, point_ref.point_index))
+            })
+            .map(|(entity, _, _)| entity)
+            .collect();
+        debug!(
+            ?event,
+            points = selection_state.selected.len(),
+            "Selected from history"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(points: &[(usize, usize)]) -> PointKeys {
+        points.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_step_between_selections() {
+        let stem = keys(&[(0, 0), (0, 1)]);
+        let bowl = keys(&[(1, 2)]);
+        let mut glyph = GlyphSelections::default();
+        glyph.record(stem.clone());
+        glyph.record(PointKeys::new());
+        glyph.record(bowl.clone());
+
+        // Empty selections aren't stepped back to
+        assert_eq!(glyph.step_back(), Some(stem.clone()));
+        assert_eq!(glyph.step_back(), None);
+        assert_eq!(glyph.step_forward(), Some(bowl.clone()));
+        assert_eq!(glyph.step_back(), Some(stem.clone()));
+
+        // A new selection drops the ones stepped back from
+        glyph.saved.insert("1".to_string(), bowl.clone());
+        glyph.record(keys(&[(2, 0)]));
+        assert_eq!(glyph.step_forward(), None);
+        assert_eq!(glyph.recall("1"), Some(bowl));
+        assert_eq!(glyph.step_back(), Some(keys(&[(2, 0)])));
+        assert_eq!(glyph.recall("2"), None);
+    }
+}
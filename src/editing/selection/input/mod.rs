@@ -2,6 +2,7 @@
 
 pub mod drag;
 pub mod mouse;
+pub mod segment_drag;
 pub mod shortcuts;
 
 // Note: These are re-exported at the parent module level
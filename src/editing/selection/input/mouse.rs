@@ -843,6 +843,8 @@ pub fn handle_selection_click(
 /// - Without Shift: Clear existing selection, select all points inside rectangle
 /// - With Shift: Keep existing selection, ADD all points inside rectangle
 /// - Rectangle uses min/max of start and current position
+/// - Only glyph points are marquee-selected here; sorts whose handles it
+///   takes in are picked when it is let go (`editing::sort::group`)
 #[allow(clippy::type_complexity)]
 #[allow(clippy::too_many_arguments)]
 pub fn handle_selection_drag(
@@ -945,10 +947,18 @@ pub fn handle_selection_drag(
             start_pos, current_pos
         );
 
+        // Sorts are selectable too, but at sort level. Collect point positions
+        // for debugging and coordinate system analysis
+        let entity_positions: Vec<(Entity, Vec2)> = selectable_query
+            .iter()
+            .filter(|(_, _, glyph_ref, _)| glyph_ref.is_some())
+            .map(|(entity, transform, _, _)| (entity, transform.translation().truncate()))
+            .collect();
+
         // In multi-select mode, start with previous selection
         if drag_state.is_multi_select {
             // Reset to previous selection
-            for (entity, _, _, _) in selectable_query.iter() {
+            for &(entity, _) in &entity_positions {
                 if !drag_state.previous_selection.contains(&entity) {
                     commands.entity(entity).remove::<Selected>();
                     selection_state.selected.remove(&entity);
@@ -963,7 +973,7 @@ pub fn handle_selection_drag(
             }
         } else {
             // Clear selection for non-multi-select
-            for (entity, _, _, _) in selectable_query.iter() {
+            for &(entity, _) in &entity_positions {
                 commands.entity(entity).remove::<Selected>();
             }
             selection_state.selected.clear();
@@ -974,16 +984,10 @@ pub fn handle_selection_drag(
         let mut points_selected = 0;
 
         debug!(
-            "Selection: Checking {} selectable entities for marquee selection",
-            selectable_query.iter().count()
+            "Selection: Checking {} points for marquee selection",
+            entity_positions.len()
         );
 
-        // Collect entity positions for debugging and coordinate system analysis
-        let entity_positions: Vec<(Entity, Vec2)> = selectable_query
-            .iter()
-            .map(|(entity, transform, _, _)| (entity, transform.translation().truncate()))
-            .collect();
-
         // Use centralized coordinate system for debugging
         let debug_info = SelectionCoordinateSystem::debug_coordinate_ranges(
             &entity_positions,
@@ -0,0 +1,272 @@
+//! Segment dragging for the select tool
+//!
+//! Pressing on a curve segment away from its points and dragging bends the
+//! curve through the pointer: the segment's off-curve points move so the
+//! spot that was grabbed follows the pointer, and its on-curve ends stay
+//! put. Near one end of a cubic segment, mostly that end's handle moves.
+//! Line segments aren't bent; dragging from them starts a marquee as before.
+
+use crate::core::config::BezySettings;
+use crate::core::state::{AppState, PointTypeData};
+use crate::editing::selection::components::GlyphPointReference;
+use crate::editing::selection::nudge::{EditEvent, PointCoordinates};
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::geometry::utilities::outline_point_positions;
+use crate::io::pointer::PointerInfo;
+use crate::rendering::sort_visuals::SortHandleDragState;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::systems::input_consumer::SelectionInputConsumer;
+use crate::systems::ui_interaction::UiHoverState;
+use crate::ui::edit_mode_toolbar::eraser::{contour_segments, segment_near, segment_path};
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use bevy::prelude::*;
+use kurbo::{ParamCurve, ParamCurveNearest, Point};
+
+/// How close to its ends a segment bends from; grabbing nearer would need
+/// ever larger handle moves for the same bend
+const MIN_BEND_T: f32 = 0.05;
+
+/// A curve segment being bent, in coordinates relative to its sort
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentDrag {
+    pub glyph_name: String,
+    pub contour_index: usize,
+    /// Indices of the segment's off-curve points in the contour
+    pub off_curves: Vec<usize>,
+    /// Positions of the off-curve points when the drag started
+    pub original_positions: Vec<Vec2>,
+    /// Where along the segment it was grabbed
+    pub t: f32,
+    /// The point of the curve at `t` when the drag started
+    pub grabbed: Vec2,
+    /// How far the pointer has taken that point so far
+    pub delta: Vec2,
+}
+
+/// Resource tracking the select tool's segment drag
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct SegmentDragState {
+    pub drag: Option<SegmentDrag>,
+}
+
+/// Starts a segment drag when the select tool presses on a curve segment
+/// away from any point, and keeps the selection from also starting a
+/// marquee while the drag goes on
+#[allow(clippy::too_many_arguments)]
+pub fn start_segment_drag(
+    mouse: Res<ButtonInput<MouseButton>>,
+    pointer_info: Res<PointerInfo>,
+    current_tool: Res<CurrentTool>,
+    ui_hover_state: Res<UiHoverState>,
+    sort_drag: Option<Res<SortHandleDragState>>,
+    active_sort_query: Query<(&Sort, &Transform), With<ActiveSort>>,
+    app_state: Option<Res<AppState>>,
+    settings: Res<BezySettings>,
+    camera_scale: Res<CameraResponsiveScale>,
+    mut selection_consumer: ResMut<SelectionInputConsumer>,
+    mut drag_state: ResMut<SegmentDragState>,
+) {
+    if drag_state.drag.is_some() {
+        // The pointer's moves and release are the segment drag's
+        selection_consumer.pending_events.clear();
+        return;
+    }
+    let starting = mouse.just_pressed(MouseButton::Left)
+        && current_tool.get_current() == Some("select")
+        && !ui_hover_state.is_hovering_ui
+        && !sort_drag.is_some_and(|drag| drag.dragging_sort.is_some());
+    if !starting {
+        return;
+    }
+    let (Ok((sort, sort_transform)), Some(state)) = (active_sort_query.single(), app_state) else {
+        return;
+    };
+    let Some(outline) = state
+        .workspace
+        .font
+        .editing()
+        .get_glyph(&sort.glyph_name)
+        .and_then(|glyph| glyph.outline.as_ref())
+    else {
+        return;
+    };
+
+    let position = pointer_info.design.to_raw() - sort_transform.translation.truncate();
+    let tolerance = camera_scale.hit_tolerance(&settings);
+    // Points win over the segments they sit on
+    if outline_point_positions(outline).any(|point| point.distance(position) <= tolerance) {
+        return;
+    }
+    let point = Point::new(position.x as f64, position.y as f64);
+    let Some((contour_index, segment_index)) =
+        segment_near(&outline.contours, point, tolerance as f64)
+    else {
+        return;
+    };
+    let contour = &outline.contours[contour_index];
+    let Some(indices) = contour_segments(contour).into_iter().nth(segment_index) else {
+        return;
+    };
+    let off_curves = indices[1..indices.len() - 1].to_vec();
+    let end_type = contour.points[indices[indices.len() - 1]].point_type;
+    let bendable = match off_curves.len() {
+        1 => true,
+        2 => end_type == PointTypeData::Curve,
+        _ => false,
+    };
+    if !bendable {
+        return;
+    }
+    let Some(curve) = segment_path(contour, &indices).segments().next() else {
+        return;
+    };
+    let t = curve.nearest(point, 1e-3).t;
+    let grabbed = curve.eval(t);
+
+    let original_positions = off_curves
+        .iter()
+        .map(|index| {
+            let point = &contour.points[*index];
+            Vec2::new(point.x as f32, point.y as f32)
+        })
+        .collect();
+    drag_state.drag = Some(SegmentDrag {
+        glyph_name: sort.glyph_name.clone(),
+        contour_index,
+        off_curves,
+        original_positions,
+        t: t as f32,
+        grabbed: Vec2::new(grabbed.x as f32, grabbed.y as f32),
+        delta: Vec2::ZERO,
+    });
+    debug!(
+        glyph = %sort.glyph_name,
+        contour = contour_index,
+        segment = segment_index,
+        t,
+        "Segment drag started"
+    );
+}
+
+/// Bends the grabbed segment through the pointer, and ends the drag when
+/// the button is released
+#[allow(clippy::type_complexity)]
+pub fn handle_segment_drag(
+    mouse: Res<ButtonInput<MouseButton>>,
+    pointer_info: Res<PointerInfo>,
+    active_sort_query: Query<&Transform, (With<ActiveSort>, Without<GlyphPointReference>)>,
+    mut point_query: Query<(&mut Transform, &mut PointCoordinates, &GlyphPointReference)>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut drag_state: ResMut<SegmentDragState>,
+    mut edit_events: EventWriter<EditEvent>,
+) {
+    if drag_state.drag.is_none() {
+        return;
+    }
+    if !mouse.pressed(MouseButton::Left) {
+        if let Some(drag) = drag_state.drag.take() {
+            debug!(glyph = %drag.glyph_name, "Segment drag ended");
+        }
+        return;
+    }
+    let Ok(sort_transform) = active_sort_query.single() else {
+        drag_state.drag = None;
+        return;
+    };
+    let sort_position = sort_transform.translation.truncate();
+    let Some(drag) = drag_state.drag.as_mut() else {
+        return;
+    };
+    let delta = pointer_info.design.to_raw() - sort_position - drag.grabbed;
+    if delta == drag.delta {
+        return;
+    }
+    drag.delta = delta;
+    let offsets = match drag.off_curves.len() {
+        1 => vec![bend_quadratic(drag.t, delta)],
+        _ => {
+            let (first, second) = bend_cubic(drag.t, delta);
+            vec![first, second]
+        }
+    };
+
+    for ((index, original), offset) in drag
+        .off_curves
+        .iter()
+        .zip(&drag.original_positions)
+        .zip(offsets)
+    {
+        let position = *original + offset;
+        if let Some(state) = app_state.as_deref_mut() {
+            state.set_point_position(
+                &drag.glyph_name,
+                drag.contour_index,
+                *index,
+                position.x as f64,
+                position.y as f64,
+            );
+        }
+        let world = sort_position + position;
+        for (mut transform, mut coordinates, point_ref) in &mut point_query {
+            let is_point = point_ref.glyph_name == drag.glyph_name
+                && point_ref.contour_index == drag.contour_index
+                && point_ref.point_index == *index;
+            if is_point && transform.translation.truncate() != world {
+                transform.translation.x = world.x;
+                transform.translation.y = world.y;
+                coordinates.x = world.x;
+                coordinates.y = world.y;
+            }
+        }
+    }
+    edit_events.write(EditEvent {});
+}
+
+/// How far to move a cubic segment's two handles for its point at `t` to
+/// move by `delta`
+///
+/// The handles share the move by how near `t` is to each end, the way
+/// Inkscape bends curves: up to a sixth of the way along only the first
+/// handle moves, past five sixths only the second.
+pub fn bend_cubic(t: f32, delta: Vec2) -> (Vec2, Vec2) {
+    let t = t.clamp(MIN_BEND_T, 1.0 - MIN_BEND_T);
+    let weight = if t <= 1.0 / 6.0 {
+        0.0
+    } else if t <= 0.5 {
+        ((6.0 * t - 1.0) / 2.0).powi(3) / 2.0
+    } else if t <= 5.0 / 6.0 {
+        (1.0 - ((6.0 * (1.0 - t) - 1.0) / 2.0).powi(3)) / 2.0 + 0.5
+    } else {
+        1.0
+    };
+    let first = delta * (1.0 - weight) / (3.0 * t * (1.0 - t) * (1.0 - t));
+    let second = delta * weight / (3.0 * t * t * (1.0 - t));
+    (first, second)
+}
+
+/// How far to move a quadratic segment's off-curve point for its point at
+/// `t` to move by `delta`
+pub fn bend_quadratic(t: f32, delta: Vec2) -> Vec2 {
+    let t = t.clamp(MIN_BEND_T, 1.0 - MIN_BEND_T);
+    delta / (2.0 * t * (1.0 - t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bent_curve_follows_the_pointer() {
+        let delta = Vec2::new(30.0, -12.0);
+        for t in [0.1, 0.3, 0.5, 0.7, 0.9] {
+            let (first, second) = bend_cubic(t, delta);
+            let moved = first * 3.0 * (1.0 - t) * (1.0 - t) * t + second * 3.0 * (1.0 - t) * t * t;
+            assert!((moved - delta).length() < 1e-3, "t = {t}: {moved:?}");
+
+            let moved = bend_quadratic(t, delta) * 2.0 * (1.0 - t) * t;
+            assert!((moved - delta).length() < 1e-3, "t = {t}: {moved:?}");
+        }
+        // Near the start only the first handle moves
+        assert_eq!(bend_cubic(0.1, delta).1, Vec2::ZERO);
+    }
+}
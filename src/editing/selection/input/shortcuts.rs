@@ -5,6 +5,7 @@ use crate::editing::selection::components::{
 };
 use crate::editing::selection::nudge::{EditEvent, NudgeState};
 use crate::io::input::ModifierState;
+use crate::io::shortcuts::Shortcut;
 use bevy::input::ButtonInput;
 use bevy::prelude::*;
 
@@ -54,12 +55,7 @@ pub fn handle_selection_shortcuts(
     }
 
     // Handle Ctrl+A (select all)
-    let ctrl_pressed = keyboard_input.pressed(KeyCode::ControlLeft)
-        || keyboard_input.pressed(KeyCode::ControlRight)
-        || keyboard_input.pressed(KeyCode::SuperLeft)
-        || keyboard_input.pressed(KeyCode::SuperRight);
-
-    if ctrl_pressed && keyboard_input.just_pressed(KeyCode::KeyA) {
+    if Shortcut::SelectAll.just_pressed(&keyboard_input) {
         debug!("Select all shortcut pressed");
 
         // Clear current selection
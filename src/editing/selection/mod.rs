@@ -1,3 +1,4 @@
+use crate::editing::sort::Sort;
 use crate::editing::FontEditorSets;
 use bevy::prelude::*;
 
@@ -6,6 +7,7 @@ pub mod coordinate_system;
 pub mod enhanced_point_component;
 pub mod entity_management;
 pub mod events;
+pub mod history;
 pub mod input;
 pub mod nudge;
 pub mod point_movement;
@@ -28,6 +30,8 @@ pub use entity_management::{
 };
 // Events
 pub use events::{AppStateChanged, ClickWorldPosition, SELECTION_MARGIN};
+// Selection history
+pub use history::{SelectionHistory, SelectionHistoryEvent, SelectionHistoryPlugin};
 // Input handling
 pub use input::mouse::{
     DoubleClickState, SelectionInputEvents, DOUBLE_CLICK_THRESHOLD_SECS,
@@ -41,8 +45,8 @@ pub use input::shortcuts::{
 };
 // Nudge functionality
 pub use nudge::{
-    EditEvent, NudgePlugin, NudgeState, PointCoordinates, handle_nudge_input, reset_nudge_state,
-    sync_nudged_points_on_completion,
+    EditEvent, NudgePlugin, NudgeSelectionEvent, NudgeState, PointCoordinates, handle_nudge_input,
+    reset_nudge_state, sync_nudged_points_on_completion,
 };
 // Utilities
 pub use utils::clear_selection_on_app_change;
@@ -111,7 +115,18 @@ impl Plugin for SelectionPlugin {
             // Selection systems now use FontEditorSets for better integration
             // NOTE: Input handling moved to SelectionInputConsumer in input_consumer.rs
             // to prevent event consumption conflicts
+            .init_resource::<input::segment_drag::SegmentDragState>()
             .add_systems(Update, input::drag::handle_point_drag)
+            .add_systems(
+                Update,
+                (
+                    input::segment_drag::start_segment_drag,
+                    input::segment_drag::handle_segment_drag,
+                )
+                    .chain()
+                    .after(crate::systems::input_consumer::process_input_events)
+                    .before(crate::systems::input_consumer::process_selection_events),
+            )
             // Processing systems
             .add_systems(
                 Update,
@@ -136,8 +151,8 @@ impl Plugin for SelectionPlugin {
                 )
                     .in_set(FontEditorSets::Rendering),
             )
-            // Add the nudge plugin
-            .add_plugins(NudgePlugin);
+            // Add the nudge and selection history plugins
+            .add_plugins((NudgePlugin, SelectionHistoryPlugin));
 
         // Register debug validation system only in debug builds
         #[cfg(debug_assertions)]
@@ -151,11 +166,14 @@ impl Plugin for SelectionPlugin {
 // SelectionSystemSet removed - now using FontEditorSets for better integration
 
 /// System to ensure Selected components are synchronized with SelectionState
+///
+/// Only point-level selection lives in `SelectionState`; sorts selected by
+/// their handles carry `Selected` on their own and are left alone here.
 pub fn sync_selected_components(
     mut commands: Commands,
     selection_state: Res<SelectionState>,
-    selected_entities: Query<Entity, With<Selected>>,
-    entities: Query<Entity>,
+    selected_entities: Query<Entity, (With<Selected>, Without<Sort>)>,
+    entities: Query<Entity, Without<Sort>>,
 ) {
     // Only log when there are changes to synchronize to avoid spam
     if !selection_state.selected.is_empty() || selected_entities.iter().count() > 0 {
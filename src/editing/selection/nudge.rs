@@ -53,6 +53,7 @@ pub fn handle_nudge_input(
     mut nudge_state: ResMut<NudgeState>,
     _active_sort_state: Res<ActiveSortState>, // Keep for potential future use
     settings: Res<BezySettings>,
+    mut nudge_events: EventReader<NudgeSelectionEvent>,
 ) {
     // Debug: Log that the system is being called
     debug!(
@@ -113,6 +114,9 @@ pub fn handle_nudge_input(
     if keyboard_input.just_pressed(KeyCode::ArrowDown) {
         nudge_direction.y -= nudge_amount;
     }
+    for NudgeSelectionEvent(offset) in nudge_events.read() {
+        nudge_direction += *offset;
+    }
 
     // If we have a nudge direction, apply it to all selected points
     if nudge_direction != Vec2::ZERO {
@@ -376,17 +380,19 @@ pub struct NudgePlugin;
 
 impl Plugin for NudgePlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(NudgeState::new()).add_systems(
-            Update,
-            (
-                sync_before_tool_switch.before(crate::tools::tool_state::handle_tool_switch),
-                handle_nudge_input,
-                reset_nudge_state,
-                sync_nudged_points_on_completion,
-            )
-                .chain()
-                .before(super::systems::update_glyph_data_from_selection),
-        );
+        app.insert_resource(NudgeState::new())
+            .add_event::<NudgeSelectionEvent>()
+            .add_systems(
+                Update,
+                (
+                    sync_before_tool_switch.before(crate::tools::tool_state::handle_tool_switch),
+                    handle_nudge_input,
+                    reset_nudge_state,
+                    sync_nudged_points_on_completion,
+                )
+                    .chain()
+                    .before(super::systems::update_glyph_data_from_selection),
+            );
     }
 }
 
@@ -394,6 +400,11 @@ impl Plugin for NudgePlugin {
 #[derive(Event)]
 pub struct EditEvent {}
 
+/// Nudge the selected points by an offset in font units, like the arrow
+/// keys do; sent by other input devices
+#[derive(Event, Debug, Clone, Copy)]
+pub struct NudgeSelectionEvent(pub Vec2);
+
 /// Point coordinates component
 #[derive(Component, Debug, Clone, Copy)]
 pub struct PointCoordinates {
@@ -16,6 +16,7 @@ use crate::geometry::point::{EditPoint, EntityId, EntityKind};
 #[allow(unused_imports)]
 use crate::geometry::world_space::DPoint;
 use crate::io::pointer::PointerInfo;
+use crate::io::shortcuts::Shortcut;
 use bevy::ecs::system::ParamSet;
 use bevy::input::mouse::MouseButton;
 use bevy::input::ButtonInput;
@@ -79,12 +80,7 @@ pub fn handle_selection_shortcuts(
     }
 
     // Handle Ctrl+A (select all)
-    let ctrl_pressed = keyboard_input.pressed(KeyCode::ControlLeft)
-        || keyboard_input.pressed(KeyCode::ControlRight)
-        || keyboard_input.pressed(KeyCode::SuperLeft)
-        || keyboard_input.pressed(KeyCode::SuperRight);
-
-    if ctrl_pressed && keyboard_input.just_pressed(KeyCode::KeyA) {
+    if Shortcut::SelectAll.just_pressed(&keyboard_input) {
         debug!("Select all shortcut pressed");
 
         // Clear current selection
@@ -135,11 +131,11 @@ pub fn update_glyph_data_from_selection(
         return;
     }
 
-    debug!(
-        "[update_glyph_data_from_selection] Processing {} moved points",
-        query.iter().count()
-    );
-
+    let _span = debug_span!(
+        "update_glyph_data_from_selection",
+        moved = query.iter().count()
+    )
+    .entered();
     let app_state = app_state.bypass_change_detection();
     let mut any_updates = false;
 
@@ -172,22 +168,18 @@ pub fn update_glyph_data_from_selection(
             relative_y,
         );
 
-        debug!(
-            "[update_glyph_data_from_selection] glyph='{}' contour={} point={} rel=({:.1}, {:.1}) updated={}",
-            point_ref.glyph_name,
-            point_ref.contour_index,
-            point_ref.point_index,
-            relative_x,
-            relative_y,
-            updated
+        trace!(
+            glyph = %point_ref.glyph_name,
+            contour = point_ref.contour_index,
+            point = point_ref.point_index,
+            x = relative_x,
+            y = relative_y,
+            updated,
+            "Moved point"
         );
 
         if updated {
             any_updates = true;
-            debug!(
-                "Updated UFO glyph data for point {} in contour {} of glyph {}",
-                point_ref.point_index, point_ref.contour_index, point_ref.glyph_name
-            );
         } else {
             warn!(
                 "Failed to update UFO glyph data for point {} in contour {} of glyph {} - invalid indices",
@@ -196,14 +188,8 @@ pub fn update_glyph_data_from_selection(
         }
     }
 
-    // Log the results
     if any_updates {
-        debug!(
-            "[update_glyph_data_from_selection] Successfully updated {} outline points",
-            query.iter().count()
-        );
-    } else {
-        debug!("[update_glyph_data_from_selection] No outline updates needed");
+        debug!("Updated the font's outline points");
     }
 }
 
@@ -231,7 +217,12 @@ pub fn spawn_active_sort_points(
                       sort.glyph_name, position);
 
                 // Get glyph data for the active sort
-                if let Some(glyph_data) = app_state.workspace.font.get_glyph(&sort.glyph_name) {
+                if let Some(glyph_data) = app_state
+                    .workspace
+                    .font
+                    .editing()
+                    .get_glyph(&sort.glyph_name)
+                {
                     if let Some(outline) = &glyph_data.outline {
                         let mut point_count = 0;
 
@@ -297,13 +288,13 @@ pub fn spawn_active_sort_points(
                     );
                 }
             } else {
-                debug!("[spawn_active_sort_points] Points already exist for active sort, skipping spawn");
+                trace!("Points already exist for the active sort");
             }
         } else {
             warn!("[spawn_active_sort_points] Active sort entity not found in sort query");
         }
     } else {
-        debug!("[spawn_active_sort_points] No active sort, skipping point spawn");
+        trace!("No active sort to spawn points for");
     }
 }
 
@@ -535,31 +526,24 @@ pub fn process_selection_input_events(
     text_editor_state: ResMut<TextEditorState>,
     app_state: Res<crate::core::state::AppState>,
 ) {
-    debug!("[process_selection_input_events] Called");
-
     // Check if select tool is active by checking InputMode
     if !crate::io::input::helpers::is_input_mode(&input_state, crate::io::input::InputMode::Select)
     {
-        debug!("[process_selection_input_events] Not in Select input mode, returning early");
         return;
     }
 
     // Only process if in select mode
     if let Some(select_mode) = select_mode {
         if !select_mode.0 {
-            debug!("[process_selection_input_events] Not in select mode, returning early");
             return;
         }
     }
     for event in input_events.read() {
-        debug!(
-            "[process_selection_input_events] Processing event: {:?}",
-            event
-        );
+        let _span = debug_span!("selection_input", ?event).entered();
 
         // Skip if UI is consuming input
         if crate::io::input::helpers::is_ui_consuming(&input_state) {
-            debug!("Selection: Skipping event - UI is consuming input");
+            trace!("UI is consuming input");
             continue;
         }
 
@@ -574,19 +558,14 @@ pub fn process_selection_input_events(
                     let world_position = position.to_raw();
                     let handle_tolerance = 50.0;
                     let font_metrics = &app_state.workspace.info.metrics;
-                    debug!(
-                        "[sort-handle-hit] Click at world position: ({:.1}, {:.1})",
-                        world_position.x, world_position.y
-                    );
-                    // Print all handle positions and distances
-                    for i in 0..text_editor_state.buffer.len() {
-                        if let Some(_sort) = text_editor_state.buffer.get(i) {
+                    // Every sort's handle distance, only worked out when traced
+                    if tracing::enabled!(tracing::Level::TRACE) {
+                        for i in 0..text_editor_state.buffer.len() {
                             if let Some(sort_pos) = text_editor_state.get_sort_visual_position(i) {
                                 let descender = font_metrics.descender.unwrap_or(-200.0) as f32;
-                                let handle_pos = sort_pos + Vec2::new(0.0, descender);
-                                let distance = world_position.distance(handle_pos);
-                                debug!("[sort-handle-hit] Sort {}: handle_pos=({:.1}, {:.1}), distance={:.1}, tolerance={:.1}",
-                                    i, handle_pos.x, handle_pos.y, distance, handle_tolerance);
+                                let handle = sort_pos + Vec2::new(0.0, descender);
+                                let distance = world_position.distance(handle);
+                                trace!(sort = i, ?handle, distance, "Sort handle distance");
                             }
                         }
                     }
@@ -597,24 +576,21 @@ pub fn process_selection_input_events(
                             Some(font_metrics),
                         )
                     {
-                        debug!(
-                            "[process_selection_input_events] Clicked near sort handle at index {}",
-                            clicked_sort_index
-                        );
+                        debug!(sort = clicked_sort_index, "Clicked near a sort handle");
                         let is_ctrl_held = modifiers.ctrl;
                         if is_ctrl_held {
                             // OLD: ECS-based selection: activate the clicked sort directly
                             // text_editor_state.activate_sort(clicked_sort_index);
-                            debug!("[process_selection_input_events] Ctrl: skipping activation (handled by selection system)");
+                            trace!("Ctrl: skipping activation (handled by selection system)");
                         } else {
                             // OLD: ECS-based selection: activate the clicked sort directly
                             // text_editor_state.activate_sort(clicked_sort_index);
-                            debug!("[process_selection_input_events] Regular click: skipping activation (handled by selection system)");
+                            trace!("Skipping activation (handled by selection system)");
                         }
                         // Early return: don't run the rest of the selection logic for this click
                         return;
                     } else {
-                        debug!("[sort-handle-hit] No sort handle hit detected");
+                        trace!("No sort handle hit");
 
                         // Fallback to general selection click handling
                         // Use a dummy entity if no active sort exists
@@ -645,21 +621,13 @@ pub fn process_selection_input_events(
                 modifiers,
             } => {
                 if *button == MouseButton::Left {
-                    debug!(
-                        "Selection: Processing mouse drag from {:?} to {:?} with modifiers {:?}",
-                        start_position, current_position, modifiers
-                    );
-                    debug!(
-                        "Selection: active_sort_entity={:?}",
-                        active_sort_state.active_sort_entity
-                    );
+                    trace!(active_sort = ?active_sort_state.active_sort_entity, "Drag");
 
                     // Always allow drag selection, regardless of active sort state
                     // Use a dummy entity if no active sort exists
                     let active_sort_entity = active_sort_state
                         .active_sort_entity
                         .unwrap_or(Entity::PLACEHOLDER);
-                    debug!("Selection: Calling handle_selection_drag...");
                     handle_selection_drag(
                         &mut commands,
                         start_position,
@@ -675,7 +643,6 @@ pub fn process_selection_input_events(
                         &sort_point_entities,
                         &selection_rect_query,
                     );
-                    debug!("Selection: handle_selection_drag completed");
                 }
             }
             crate::io::input::InputEvent::MouseRelease {
@@ -684,10 +651,6 @@ pub fn process_selection_input_events(
                 modifiers,
             } => {
                 if *button == MouseButton::Left {
-                    debug!(
-                        "Selection: Processing mouse release at {:?} with modifiers {:?}",
-                        position, modifiers
-                    );
                     // Always handle mouse release for selection, regardless of active sort state
                     handle_selection_release(
                         &mut commands,
@@ -706,10 +669,6 @@ pub fn process_selection_input_events(
                     key,
                     bevy::input::keyboard::KeyCode::KeyA | bevy::input::keyboard::KeyCode::Escape
                 ) {
-                    debug!(
-                        "Selection: Processing key press {:?} with modifiers {:?}",
-                        key, modifiers
-                    );
                     // Always handle key presses for selection, regardless of active sort state
                     // Use a dummy entity if no active sort exists
                     let active_sort_entity = active_sort_state
@@ -965,15 +924,12 @@ pub fn handle_selection_drag(
     _sort_point_entities: &Query<&crate::editing::sort::manager::SortPointEntity>,
     _selection_rect_query: &Query<Entity, With<SelectionRect>>,
 ) {
-    debug!(
-        "[handle_selection_drag] Called: start={:?}, current={:?}, delta={:?}, is_dragging={}",
-        start_position, current_position, delta, drag_state.is_dragging
-    );
+    trace!(?delta, dragging = drag_state.is_dragging, "Marquee drag");
     if !drag_state.is_dragging {
         debug!(
-            "[handle_selection_drag] Starting new drag selection at start={:?}, end={:?}",
-            start_position.to_raw(),
-            current_position.to_raw()
+            start = ?start_position.to_raw(),
+            end = ?current_position.to_raw(),
+            "Marquee selection started"
         );
 
         // Initialize drag state
@@ -995,17 +951,11 @@ pub fn handle_selection_drag(
             })
             .id();
         drag_state.selection_rect_entity = Some(rect_entity);
-        debug!(
-            "[handle_selection_drag] SelectionRect entity created with ID {:?}",
-            rect_entity
-        );
     } else {
         // Only update current_position during drag
-        debug!("Continuing existing drag selection...");
         drag_state.current_position = Some(*current_position);
         // Only update the entity if it exists
         if let Some(rect_entity) = drag_state.selection_rect_entity {
-            debug!("Updating SelectionRect entity {:?}", rect_entity);
             if let Ok(mut entity_commands) = commands.get_entity(rect_entity) {
                 entity_commands.insert(SelectionRect {
                     start: drag_state
@@ -1014,22 +964,11 @@ pub fn handle_selection_drag(
                         .to_raw(),
                     end: current_position.to_raw(),
                 });
-                debug!(
-                    "SelectionRect entity updated: start={:?}, end={:?}",
-                    drag_state
-                        .start_position
-                        .unwrap_or(*start_position)
-                        .to_raw(),
-                    current_position.to_raw()
-                );
             } else {
-                debug!(
-                    "ERROR: Could not get entity commands for SelectionRect entity {:?}",
-                    rect_entity
-                );
+                debug!(?rect_entity, "Marquee rectangle entity is gone");
             }
         } else {
-            debug!("ERROR: No SelectionRect entity found in drag_state!");
+            debug!("No marquee rectangle entity to update");
         }
     }
 
@@ -1037,11 +976,6 @@ pub fn handle_selection_drag(
     if let (Some(start_pos), Some(current_pos)) =
         (drag_state.start_position, drag_state.current_position)
     {
-        debug!(
-            "Selection: Marquee rect coordinates - start: {:?}, current: {:?}",
-            start_pos, current_pos
-        );
-
         // In multi-select mode, start with previous selection
         if drag_state.is_multi_select {
             // Reset to previous selection
@@ -1070,24 +1004,20 @@ pub fn handle_selection_drag(
         let mut points_in_rect = 0;
         let mut points_selected = 0;
 
-        debug!(
-            "Selection: Checking {} selectable entities for marquee selection",
-            selectable_query.iter().count()
-        );
-
-        // Collect entity positions for debugging and coordinate system analysis
         let entity_positions: Vec<(Entity, Vec2)> = selectable_query
             .iter()
             .map(|(entity, transform, _, _)| (entity, transform.translation().truncate()))
             .collect();
 
-        // Use centralized coordinate system for debugging
-        let debug_info = SelectionCoordinateSystem::debug_coordinate_ranges(
-            &entity_positions,
-            &start_pos,
-            &current_pos,
-        );
-        debug!("Selection: {}", debug_info);
+        // The coordinate ranges are only worked out when traced
+        if tracing::enabled!(tracing::Level::TRACE) {
+            let ranges = SelectionCoordinateSystem::debug_coordinate_ranges(
+                &entity_positions,
+                &start_pos,
+                &current_pos,
+            );
+            trace!(%ranges, "Marquee coordinates");
+        }
 
         for (entity, entity_pos) in &entity_positions {
             // Use centralized coordinate system to check if entity is inside the marquee rectangle
@@ -1097,24 +1027,20 @@ pub fn handle_selection_drag(
                 &current_pos,
             ) {
                 points_in_rect += 1;
-                debug!(
-                    "Selection: Entity {:?} is inside marquee rect! Position: {:?}",
-                    entity, entity_pos
-                );
                 if drag_state.is_multi_select && drag_state.previous_selection.contains(entity) {
                     // Toggle off if previously selected
                     selection_state.selected.remove(entity);
                     commands.entity(*entity).remove::<Selected>();
-                    debug!("Selection: Toggled off entity {:?}", entity);
+                    trace!(?entity, position = ?entity_pos, "Toggled off in marquee");
                 } else {
                     // Add to selection
                     selection_state.selected.insert(*entity);
                     commands.entity(*entity).insert(Selected);
                     points_selected += 1;
-                    debug!("Selection: Added entity {:?} to selection", entity);
+                    trace!(?entity, position = ?entity_pos, "Selected in marquee");
                 }
-            } else {
-                // Debug: Show why entity is not selected using centralized coordinate system
+            } else if tracing::enabled!(tracing::Level::TRACE) {
+                // How far outside the marquee the point is
                 let rect_entity_start =
                     SelectionCoordinateSystem::design_to_entity_coordinates(&start_pos);
                 let rect_entity_end =
@@ -1137,22 +1063,16 @@ pub fn handle_selection_drag(
                 };
 
                 if distance_x > 0.0 || distance_y > 0.0 {
-                    debug!(
-                        "Selection: Entity {:?} outside rect - X: {:.1} units, Y: {:.1} units",
-                        entity, distance_x, distance_y
-                    );
+                    trace!(?entity, distance_x, distance_y, "Outside the marquee");
                 }
             }
         }
 
         debug!(
-            "Marquee selection: {} points in rect, {} points selected",
-            points_in_rect, points_selected
-        );
-
-        debug!(
-            "Marquee selection updated: {} points selected",
-            selection_state.selected.len()
+            in_rect = points_in_rect,
+            newly_selected = points_selected,
+            selected = selection_state.selected.len(),
+            "Marquee selection updated"
         );
     }
 }
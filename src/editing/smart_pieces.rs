@@ -0,0 +1,305 @@
+//! Smart piece editing
+//!
+//! Events for turning selected contours into a reusable piece, stamping
+//! pieces into the active glyph, and unlinking stamped copies. A sync system
+//! watches the piece masters and redraws every use after a master is edited
+//! with the normal tools.
+//!
+//! Cmd/Ctrl+Alt+/ shows the smart pieces pane, which sends these events: a
+//! piece is defined from the selected contours under a typed name, and
+//! stamped with the scale, flips and offset set in the pane.
+
+use crate::core::state::AppState;
+use crate::editing::selection::components::{GlyphPointReference, Selected};
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::sort::manager::SortPointEntity;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::editing::text_field_focus::{TextField, TextFieldFocus, TextFieldSystems};
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use kurbo::Affine;
+use std::collections::BTreeSet;
+
+/// Whether the smart pieces pane is shown, the name being typed for a new
+/// piece, and how the next stamp is placed
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct SmartPiecesPanel {
+    pub visible: bool,
+    /// Name typed so far while defining a piece
+    pub naming: Option<String>,
+    pub scale: Vec2,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    pub offset: Vec2,
+}
+
+impl Default for SmartPiecesPanel {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            naming: None,
+            scale: Vec2::ONE,
+            flip_x: false,
+            flip_y: false,
+            offset: Vec2::ZERO,
+        }
+    }
+}
+
+impl SmartPiecesPanel {
+    /// A stamp of `piece` placed as the pane is set
+    pub fn stamp(&self, piece: &str) -> StampSmartPieceEvent {
+        StampSmartPieceEvent {
+            piece: piece.to_string(),
+            scale: self.scale,
+            flip_x: self.flip_x,
+            flip_y: self.flip_y,
+            offset: self.offset,
+        }
+    }
+}
+
+/// Save the selected contours of the active glyph as a piece master
+///
+/// Without a selection the whole outline is used. An existing piece with the
+/// same name is replaced and its uses are redrawn.
+#[derive(Event, Clone)]
+pub struct DefineSmartPieceEvent {
+    pub name: String,
+}
+
+/// Stamp a piece into the active glyph
+#[derive(Event, Clone)]
+pub struct StampSmartPieceEvent {
+    pub piece: String,
+    /// Per-axis scale applied to the master
+    pub scale: Vec2,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    /// Position of the master's origin in the glyph, in font units
+    pub offset: Vec2,
+}
+
+/// Unlink all stamped pieces in the active glyph
+#[derive(Event, Clone)]
+pub struct DetachSmartPiecesEvent;
+
+pub struct SmartPiecesPlugin;
+
+impl Plugin for SmartPiecesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SmartPiecesPanel>()
+            .add_event::<DefineSmartPieceEvent>()
+            .add_event::<StampSmartPieceEvent>()
+            .add_event::<DetachSmartPiecesEvent>()
+            .add_systems(
+                Update,
+                (
+                    toggle_smart_pieces_pane,
+                    piece_name_keys.in_set(TextFieldSystems),
+                    handle_smart_piece_events,
+                    sync_smart_pieces,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Whether a character can be part of a piece name, which becomes part of a
+/// glyph name
+pub fn is_piece_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')
+}
+
+/// Cmd/Ctrl+Alt+/ shows or hides the pane
+fn toggle_smart_pieces_pane(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut panel: ResMut<SmartPiecesPanel>,
+) {
+    if Shortcut::SmartPieces.just_pressed(&keyboard) {
+        panel.visible = !panel.visible;
+        panel.naming = None;
+        debug!("Smart pieces pane shown: {}", panel.visible);
+    }
+}
+
+/// Types the name of a new piece; Enter defines it from the selected
+/// contours and Escape drops it
+fn piece_name_keys(
+    mut key_events: EventReader<KeyboardInput>,
+    mut focus: ResMut<TextFieldFocus>,
+    mut panel: ResMut<SmartPiecesPanel>,
+    mut define_events: EventWriter<DefineSmartPieceEvent>,
+) {
+    focus.set(TextField::PieceName, panel.naming.is_some());
+    let Some(mut typed) = panel.naming.clone() else {
+        key_events.clear();
+        return;
+    };
+    let mut done = None;
+    for event in key_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(text) => typed.extend(text.chars().filter(|c| is_piece_name_char(*c))),
+            Key::Backspace => {
+                typed.pop();
+            }
+            Key::Enter => done = Some(true),
+            Key::Escape => done = Some(false),
+            _ => {}
+        }
+    }
+
+    match done {
+        Some(true) if typed.is_empty() => warn!("A piece needs a name"),
+        Some(true) => {
+            define_events.write(DefineSmartPieceEvent { name: typed });
+            panel.naming = None;
+        }
+        Some(false) => panel.naming = None,
+        None => {
+            if panel.naming.as_ref() != Some(&typed) {
+                panel.naming = Some(typed);
+            }
+        }
+    }
+}
+
+impl StampSmartPieceEvent {
+    /// Placement of the master: scale and flip about its origin, then move
+    pub fn transform(&self) -> Affine {
+        let sx = if self.flip_x { -self.scale.x } else { self.scale.x };
+        let sy = if self.flip_y { -self.scale.y } else { self.scale.y };
+        Affine::translate((self.offset.x as f64, self.offset.y as f64))
+            * Affine::scale_non_uniform(sx as f64, sy as f64)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_smart_piece_events(
+    mut define_events: EventReader<DefineSmartPieceEvent>,
+    mut stamp_events: EventReader<StampSmartPieceEvent>,
+    mut detach_events: EventReader<DetachSmartPiecesEvent>,
+    mut app_state: Option<ResMut<AppState>>,
+    active_sort_query: Query<(Entity, &Sort), With<ActiveSort>>,
+    selected_points: Query<(&GlyphPointReference, &SortPointEntity), With<Selected>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    let Some(state) = app_state.as_mut() else {
+        define_events.clear();
+        stamp_events.clear();
+        detach_events.clear();
+        return;
+    };
+    let font = &mut state.workspace.font;
+    let active = active_sort_query.single().ok();
+    let mut changed = false;
+
+    for event in define_events.read() {
+        let Some((sort_entity, sort)) = active else {
+            warn!("Cannot define piece '{}': no active sort", event.name);
+            continue;
+        };
+        let Some(outline) = font
+            .editing()
+            .get_glyph(&sort.glyph_name)
+            .and_then(|glyph| glyph.outline.as_ref())
+        else {
+            warn!("Cannot define piece: '{}' has no outline", sort.glyph_name);
+            continue;
+        };
+
+        let selected_contours: BTreeSet<usize> = selected_points
+            .iter()
+            .filter(|(_, sort_point)| sort_point.sort_entity == sort_entity)
+            .map(|(point_ref, _)| point_ref.contour_index)
+            .collect();
+        let contours = outline
+            .contours
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| selected_contours.is_empty() || selected_contours.contains(index))
+            .map(|(_, contour)| contour.clone())
+            .collect::<Vec<_>>();
+
+        info!(
+            "Defined piece '{}' from {} contour(s) of '{}'",
+            event.name,
+            contours.len(),
+            sort.glyph_name
+        );
+        font.define_smart_piece(&event.name, contours);
+        changed = true;
+    }
+
+    for event in stamp_events.read() {
+        let Some((_, sort)) = active else {
+            warn!("Cannot stamp piece '{}': no active sort", event.piece);
+            continue;
+        };
+        if font.editing_layer.is_some() {
+            warn!("Cannot stamp piece '{}': pieces go in the foreground", event.piece);
+            continue;
+        }
+        match font.stamp_smart_piece(&sort.glyph_name, &event.piece, event.transform()) {
+            Ok(()) => {
+                info!("Stamped piece '{}' into '{}'", event.piece, sort.glyph_name);
+                changed = true;
+            }
+            Err(reason) => warn!("Cannot stamp piece: {}", reason),
+        }
+    }
+
+    for _ in detach_events.read() {
+        let Some((_, sort)) = active else {
+            continue;
+        };
+        if font.editing_layer.is_some() {
+            continue;
+        }
+        let detached = font.detach_smart_pieces(&sort.glyph_name);
+        if detached > 0 {
+            info!("Detached {} piece(s) in '{}'", detached, sort.glyph_name);
+            changed = true;
+        }
+    }
+
+    if changed {
+        app_state_changed.write(AppStateChanged);
+        visual_update_tracker.needs_update = true;
+    }
+}
+
+/// Redraw piece uses after a master glyph changes
+fn sync_smart_pieces(
+    mut app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    let Some(state) = app_state.as_mut() else {
+        return;
+    };
+    if !state.is_changed() {
+        return;
+    }
+
+    let report = state.bypass_change_detection().workspace.font.sync_smart_pieces();
+    for (glyph_name, piece) in &report.detached {
+        warn!(
+            "Piece '{}' in '{}' was edited directly and is no longer linked",
+            piece, glyph_name
+        );
+    }
+    if !report.updated_glyphs.is_empty() {
+        debug!("Updated smart pieces in {:?}", report.updated_glyphs);
+        state.set_changed();
+        app_state_changed.write(AppStateChanged);
+        visual_update_tracker.needs_update = true;
+    }
+}
@@ -0,0 +1,399 @@
+//! Working with several sorts at once
+//!
+//! Sorts are selected at sort level by clicking their handles or by letting
+//! go of a marquee drawn over their handles; a marquee that takes in points
+//! of the active sort selects those points instead. With the select tool,
+//! the selected sorts line up with Cmd/Ctrl+Alt shortcuts and Delete or
+//! Backspace removes them all. Dragging the handle of a selected sort moves
+//! the whole selection (see `rendering::sort_visuals`).
+//!
+//! Sorts in a text buffer are laid out by the buffer, so moving one of them
+//! moves its whole buffer.
+
+use crate::core::state::text_editor::text_buffer::{BufferMember, TextBuffer};
+use crate::core::state::{AppState, TextEditorState};
+use crate::editing::selection::components::{Selected, SelectionState};
+use crate::editing::selection::coordinate_system::SelectionCoordinateSystem;
+use crate::editing::selection::DragSelectionState;
+use crate::editing::sort::manager::SortPointEntity;
+use crate::editing::sort::{ActiveSortState, Sort, SortEvent};
+use crate::geometry::world_space::DPoint;
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::sort_visuals::sort_handle_center;
+use crate::systems::sorts::sort_entities::{BufferSortIndex, BufferSortRespawnQueue};
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use bevy::prelude::*;
+
+/// How the selected sorts are lined up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortAlignment {
+    /// Left edges of the advance boxes on the leftmost one
+    Left,
+    /// Advance boxes centered on the middle of the selection
+    Center,
+    /// Right edges of the advance boxes on the rightmost one
+    Right,
+    /// Baselines on the highest one
+    Top,
+    /// Baselines on the lowest one
+    Bottom,
+    /// Equal gaps between the advance boxes, the outer sorts staying put
+    DistributeHorizontally,
+    /// Equal steps between the baselines, the outer sorts staying put
+    DistributeVertically,
+}
+
+/// Line up the selected sorts
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AlignSortsEvent(pub SortAlignment);
+
+/// Delete every selected sort
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DeleteSelectedSortsEvent;
+
+/// Where the origin of each sort goes, given its origin and advance width
+pub fn arranged_origins(sorts: &[(Vec2, f32)], alignment: SortAlignment) -> Vec<Vec2> {
+    let mut origins: Vec<Vec2> = sorts.iter().map(|(origin, _)| *origin).collect();
+    if sorts.len() < 2 {
+        return origins;
+    }
+    let left = sorts.iter().map(|(o, _)| o.x).fold(f32::INFINITY, f32::min);
+    let right = sorts
+        .iter()
+        .map(|(o, w)| o.x + w)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let top = sorts
+        .iter()
+        .map(|(o, _)| o.y)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let bottom = sorts.iter().map(|(o, _)| o.y).fold(f32::INFINITY, f32::min);
+
+    match alignment {
+        SortAlignment::Left => origins.iter_mut().for_each(|o| o.x = left),
+        SortAlignment::Right => {
+            for (origin, (_, width)) in origins.iter_mut().zip(sorts) {
+                origin.x = right - width;
+            }
+        }
+        SortAlignment::Center => {
+            let center = (left + right) / 2.0;
+            for (origin, (_, width)) in origins.iter_mut().zip(sorts) {
+                origin.x = center - width / 2.0;
+            }
+        }
+        SortAlignment::Top => origins.iter_mut().for_each(|o| o.y = top),
+        SortAlignment::Bottom => origins.iter_mut().for_each(|o| o.y = bottom),
+        SortAlignment::DistributeHorizontally => {
+            let mut order: Vec<usize> = (0..sorts.len()).collect();
+            order.sort_by(|a, b| sorts[*a].0.x.total_cmp(&sorts[*b].0.x));
+            let (first, last) = (sorts[order[0]], sorts[order[order.len() - 1]]);
+            let total_width: f32 = sorts.iter().map(|(_, width)| width).sum();
+            let span = last.0.x + last.1 - first.0.x;
+            let gap = (span - total_width) / (sorts.len() - 1) as f32;
+            let mut x = first.0.x;
+            for i in order {
+                origins[i].x = x;
+                x += sorts[i].1 + gap;
+            }
+        }
+        SortAlignment::DistributeVertically => {
+            let mut order: Vec<usize> = (0..sorts.len()).collect();
+            order.sort_by(|a, b| sorts[*a].0.y.total_cmp(&sorts[*b].0.y));
+            let step = (top - bottom) / (sorts.len() - 1) as f32;
+            for (n, i) in order.into_iter().enumerate() {
+                origins[i].y = bottom + step * n as f32;
+            }
+        }
+    }
+    origins
+}
+
+// SYSTEMS ---------------------------------------------------------------------
+
+/// Turns the select tool's sort shortcuts into events while sorts, not
+/// points, are selected
+pub fn handle_sort_group_shortcuts(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    current_tool: Res<CurrentTool>,
+    selection_state: Res<SelectionState>,
+    selected_sorts: Query<(), (With<Sort>, With<Selected>)>,
+    mut align_events: EventWriter<AlignSortsEvent>,
+    mut delete_events: EventWriter<DeleteSelectedSortsEvent>,
+) {
+    if current_tool.get_current() != Some("select")
+        || selected_sorts.is_empty()
+        || !selection_state.selected.is_empty()
+    {
+        return;
+    }
+
+    for key in [KeyCode::Delete, KeyCode::Backspace] {
+        if keyboard.just_pressed(key) {
+            keyboard.clear_just_pressed(key);
+            delete_events.write(DeleteSelectedSortsEvent);
+        }
+    }
+
+    if Shortcut::DistributeSortsVertically.just_pressed(&keyboard) {
+        align_events.write(AlignSortsEvent(SortAlignment::DistributeVertically));
+    }
+    let alignment = match Shortcut::AlignSorts.pressed_key(&keyboard) {
+        Some(KeyCode::KeyL) => SortAlignment::Left,
+        Some(KeyCode::KeyM) => SortAlignment::Center,
+        Some(KeyCode::KeyR) => SortAlignment::Right,
+        Some(KeyCode::KeyU) => SortAlignment::Top,
+        Some(KeyCode::KeyD) => SortAlignment::Bottom,
+        Some(KeyCode::KeyH) => SortAlignment::DistributeHorizontally,
+        _ => return,
+    };
+    align_events.write(AlignSortsEvent(alignment));
+}
+
+/// Moves the selected sorts into line
+#[allow(clippy::too_many_arguments)]
+pub fn align_selected_sorts(
+    mut events: EventReader<AlignSortsEvent>,
+    app_state: Option<Res<AppState>>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    mut sorts: Query<(Entity, &Sort, &mut Transform), With<Selected>>,
+    buffer_indices: Query<&BufferSortIndex>,
+    buffer_members: Query<&BufferMember>,
+    mut text_buffers: Query<&mut TextBuffer>,
+) {
+    let Some(app_state) = app_state else {
+        events.clear();
+        return;
+    };
+
+    for AlignSortsEvent(alignment) in events.read() {
+        let selected: Vec<(Entity, Vec2, f32)> = sorts
+            .iter()
+            .map(|(entity, sort, transform)| {
+                let advance = app_state
+                    .workspace
+                    .font
+                    .editing()
+                    .get_glyph(&sort.glyph_name)
+                    .map_or(0.0, |glyph| glyph.advance_width as f32);
+                (entity, transform.translation.truncate(), advance)
+            })
+            .collect();
+        if selected.len() < 2 {
+            continue;
+        }
+        let boxes: Vec<(Vec2, f32)> = selected.iter().map(|(_, o, w)| (*o, *w)).collect();
+        let targets = arranged_origins(&boxes, *alignment);
+
+        let mut moved_buffers = Vec::new();
+        for ((entity, origin, _), target) in selected.into_iter().zip(targets) {
+            if let Ok(member) = buffer_members.get(entity) {
+                // The first selected sort of a buffer places the buffer
+                if !moved_buffers.contains(&member.buffer_entity) {
+                    moved_buffers.push(member.buffer_entity);
+                    if let Ok(mut buffer) = text_buffers.get_mut(member.buffer_entity) {
+                        buffer.root_position += target - origin;
+                    }
+                }
+                continue;
+            }
+            if let Ok(index) = buffer_indices.get(entity) {
+                if let Some(sort) = text_editor_state.buffer.get_mut(index.0) {
+                    sort.root_position = target;
+                }
+            }
+            if let Ok((_, _, mut transform)) = sorts.get_mut(entity) {
+                transform.translation.x = target.x;
+                transform.translation.y = target.y;
+            }
+        }
+        // Buffer sorts are repositioned when the text state changes
+        text_editor_state.set_changed();
+        debug!("Arranged selected sorts: {:?}", alignment);
+    }
+}
+
+/// Deletes the selected sorts, from the text editor buffer when they are
+/// in it
+#[allow(clippy::too_many_arguments)]
+pub fn delete_selected_sorts(
+    mut commands: Commands,
+    mut events: EventReader<DeleteSelectedSortsEvent>,
+    selected_sorts: Query<(Entity, Option<&BufferSortIndex>), (With<Sort>, With<Selected>)>,
+    point_entities: Query<Entity, With<SortPointEntity>>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+    mut active_sort_state: ResMut<ActiveSortState>,
+    mut selection_state: ResMut<SelectionState>,
+    mut sort_events: EventWriter<SortEvent>,
+) {
+    if events.read().count() == 0 || selected_sorts.is_empty() {
+        return;
+    }
+
+    let mut buffer_indices = Vec::new();
+    for (entity, buffer_index) in selected_sorts.iter() {
+        match buffer_index {
+            Some(index) => buffer_indices.push(index.0),
+            None => {
+                sort_events.write(SortEvent::DeleteSort { entity });
+            }
+        }
+    }
+
+    delete_buffer_sorts(buffer_indices, &mut text_editor_state, &mut respawn_queue);
+
+    // Points only belong to the active sort, which may be gone or respawned
+    deactivate_all_sorts(
+        &mut commands,
+        &mut text_editor_state,
+        &mut active_sort_state,
+        &mut selection_state,
+        &point_entities,
+    );
+
+    info!("Deleted {} sorts", selected_sorts.iter().count());
+}
+
+/// Deletes sorts from the text editor buffer by index, then respawns every
+/// sort after the first gap with its new index
+pub fn delete_buffer_sorts(
+    mut buffer_indices: Vec<usize>,
+    text_editor_state: &mut TextEditorState,
+    respawn_queue: &mut BufferSortRespawnQueue,
+) {
+    // Delete from the back so the indices still to go stay put
+    buffer_indices.sort_unstable_by(|a, b| b.cmp(a));
+    for &index in &buffer_indices {
+        text_editor_state.buffer.delete(index);
+    }
+    if let Some(&first) = buffer_indices.last() {
+        respawn_queue
+            .indices
+            .extend(first..text_editor_state.buffer.len());
+    }
+}
+
+/// Leaves no sort active and removes the active sort's points, for when
+/// buffer sorts are respawned at new indices
+pub fn deactivate_all_sorts(
+    commands: &mut Commands,
+    text_editor_state: &mut TextEditorState,
+    active_sort_state: &mut ActiveSortState,
+    selection_state: &mut SelectionState,
+    point_entities: &Query<Entity, With<SortPointEntity>>,
+) {
+    for i in 0..text_editor_state.buffer.len() {
+        if let Some(sort) = text_editor_state.buffer.get_mut(i) {
+            sort.is_active = false;
+        }
+    }
+    active_sort_state.active_sort_entity = None;
+    selection_state.selected.clear();
+    for entity in point_entities.iter() {
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.despawn();
+        }
+    }
+}
+
+/// Selects the sorts whose handles a marquee took in once it is let go,
+/// unless it took in points, which keep the selection at point level
+pub fn select_sorts_in_marquee(
+    mut commands: Commands,
+    drag_state: Res<DragSelectionState>,
+    selection_state: Res<SelectionState>,
+    app_state: Option<Res<AppState>>,
+    sorts: Query<(Entity, &Transform, Has<Selected>), With<Sort>>,
+    mut marquee: Local<Option<(DPoint, DPoint, bool)>>,
+) {
+    if drag_state.is_dragging {
+        if let (Some(start), Some(end)) = (drag_state.start_position, drag_state.current_position) {
+            *marquee = Some((start, end, drag_state.is_multi_select));
+        }
+        return;
+    }
+    let Some((start, end, additive)) = marquee.take() else {
+        return;
+    };
+    let Some(app_state) = app_state else {
+        return;
+    };
+    let descender = app_state
+        .workspace
+        .info
+        .descender
+        .map(|v| v as f32)
+        .unwrap_or(-200.0);
+
+    let points_selected = !selection_state.selected.is_empty();
+    for (entity, transform, was_selected) in sorts.iter() {
+        let handle = sort_handle_center(transform.translation.truncate(), descender);
+        let inside = SelectionCoordinateSystem::is_point_in_rectangle(&handle, &start, &end);
+        let selected = if points_selected {
+            false
+        } else if additive {
+            was_selected != inside
+        } else {
+            inside
+        };
+        if selected && !was_selected {
+            commands.entity(entity).insert(Selected);
+        } else if !selected && was_selected {
+            commands.entity(entity).remove::<Selected>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_align_edges() {
+        let sorts = [
+            (Vec2::new(0.0, 0.0), 500.0),
+            (Vec2::new(800.0, -100.0), 300.0),
+        ];
+        let left = arranged_origins(&sorts, SortAlignment::Left);
+        assert_eq!(left, vec![Vec2::new(0.0, 0.0), Vec2::new(0.0, -100.0)]);
+        let right = arranged_origins(&sorts, SortAlignment::Right);
+        assert_eq!(right, vec![Vec2::new(600.0, 0.0), Vec2::new(800.0, -100.0)]);
+        let center = arranged_origins(&sorts, SortAlignment::Center);
+        assert_eq!(
+            center,
+            vec![Vec2::new(300.0, 0.0), Vec2::new(400.0, -100.0)]
+        );
+        let bottom = arranged_origins(&sorts, SortAlignment::Bottom);
+        assert_eq!(
+            bottom,
+            vec![Vec2::new(0.0, -100.0), Vec2::new(800.0, -100.0)]
+        );
+    }
+
+    #[test]
+    fn test_distribute() {
+        let sorts = [
+            (Vec2::new(1000.0, 0.0), 200.0),
+            (Vec2::new(0.0, 300.0), 200.0),
+            (Vec2::new(100.0, 100.0), 400.0),
+        ];
+        let across = arranged_origins(&sorts, SortAlignment::DistributeHorizontally);
+        assert_eq!(
+            across,
+            vec![
+                Vec2::new(1000.0, 0.0),
+                Vec2::new(0.0, 300.0),
+                Vec2::new(400.0, 100.0)
+            ]
+        );
+        let down = arranged_origins(&sorts, SortAlignment::DistributeVertically);
+        assert_eq!(
+            down,
+            vec![
+                Vec2::new(1000.0, 0.0),
+                Vec2::new(0.0, 300.0),
+                Vec2::new(100.0, 150.0)
+            ]
+        );
+    }
+}
@@ -61,7 +61,7 @@ pub fn handle_sort_events(
 
                 // Get advance width from the virtual font
                 let advance_width = if let Some(state) = app_state.as_ref() {
-                    if let Some(glyph_data) = state.workspace.font.get_glyph(glyph_name) {
+                    if let Some(glyph_data) = state.workspace.font.editing().get_glyph(glyph_name) {
                         glyph_data.advance_width as f32
                     } else {
                         600.0 // Default fallback
@@ -245,7 +245,12 @@ pub fn spawn_point_entities_for_sort(
     selection_state: &mut ResMut<SelectionState>,
 ) {
     // Get the glyph from the virtual font using our current architecture
-    if let Some(glyph_data) = app_state.workspace.font.get_glyph(&sort.glyph_name) {
+    if let Some(glyph_data) = app_state
+        .workspace
+        .font
+        .editing()
+        .get_glyph(&sort.glyph_name)
+    {
         if let Some(outline) = &glyph_data.outline {
             for (contour_idx, contour) in outline.contours.iter().enumerate() {
                 for (point_idx, point_data) in contour.points.iter().enumerate() {
@@ -4,12 +4,15 @@
 //! management systems, and plugin registration.
 
 pub mod components;
+pub mod group;
 pub mod manager;
 pub mod plugin;
 
 // Explicit re-exports for public API
 // Components
 pub use components::{ActiveSort, ActiveSortState, InactiveSort, Sort, SortBounds, SortEvent};
+// Working with several sorts
+pub use group::{AlignSortsEvent, DeleteSelectedSortsEvent, SortAlignment};
 // Manager functionality
 pub use manager::{
     NewlySpawnedCrosshair, SortCrosshair, SortPointEntity, auto_activate_first_sort,
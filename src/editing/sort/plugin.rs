@@ -2,6 +2,10 @@
 //!
 //! Bevy plugin that registers all sort-related systems, resources, and events.
 
+use super::group::{
+    align_selected_sorts, delete_selected_sorts, handle_sort_group_shortcuts,
+    select_sorts_in_marquee, AlignSortsEvent, DeleteSelectedSortsEvent,
+};
 use super::manager::{
     handle_sort_events, respawn_sort_points_on_glyph_change,
 };
@@ -27,6 +31,8 @@ impl Plugin for SortPlugin {
             // .add_plugins(SelectionPlugin)
             .init_resource::<ActiveSortState>()
             .add_event::<SortEvent>()
+            .add_event::<AlignSortsEvent>()
+            .add_event::<DeleteSelectedSortsEvent>()
             // Configure system sets to run in proper order
             .configure_sets(
                 Update,
@@ -42,6 +48,20 @@ impl Plugin for SortPlugin {
                 Update,
                 (handle_sort_events,).in_set(SortSystemSet::Management),
             )
+            // Selecting, lining up and deleting several sorts
+            .add_systems(
+                Update,
+                (
+                    (
+                        handle_sort_group_shortcuts,
+                        align_selected_sorts,
+                        delete_selected_sorts,
+                    )
+                        .chain()
+                        .before(handle_sort_events),
+                    select_sorts_in_marquee,
+                ),
+            )
             // Point spawning systems
             .add_systems(
                 Update,
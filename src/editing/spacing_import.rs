@@ -0,0 +1,149 @@
+//! Importing spacing done in other tools
+//!
+//! Dropping an `.afm`, kerning `.plist` or `.csv` file onto the window reads
+//! its advances and kerning (see `font_source::spacing_import`) and opens
+//! the spacing import pane, which shows what the file holds and how much of
+//! it the font already has. The import is then applied by replacing the
+//! font's kerning, merging into it or only adding what's missing, or
+//! cancelled.
+
+use crate::core::state::{AppState, SortKind, TextEditorState};
+use crate::editing::font_changes::{GlyphEdited, KerningChanged};
+use crate::editing::selection::events::AppStateChanged;
+use crate::font_source::spacing_import::{
+    read_spacing_file, ImportedSpacing, MergeMode, SpacingFormat,
+};
+use bevy::prelude::*;
+use bevy::window::FileDragAndDrop;
+use std::path::PathBuf;
+
+/// A file read for import, waiting for how to merge it
+#[derive(Clone, Debug)]
+pub struct PendingSpacingImport {
+    pub path: PathBuf,
+    pub spacing: ImportedSpacing,
+}
+
+/// The import awaiting a decision, shown in the pane while there is one
+#[derive(Resource, Default)]
+pub struct SpacingImport {
+    pub pending: Option<PendingSpacingImport>,
+}
+
+/// Steps of a spacing import
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum SpacingImportEvent {
+    /// Read a file and ask how to merge it
+    Open(PathBuf),
+    Apply(MergeMode),
+    Cancel,
+}
+
+pub struct SpacingImportPlugin;
+
+impl Plugin for SpacingImportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpacingImport>()
+            .add_event::<SpacingImportEvent>()
+            .add_systems(
+                Update,
+                (handle_dropped_spacing_files, handle_spacing_import_events).chain(),
+            );
+    }
+}
+
+/// Turns AFM, plist and CSV files dropped onto the window into imports
+fn handle_dropped_spacing_files(
+    mut drop_events: EventReader<FileDragAndDrop>,
+    mut import_events: EventWriter<SpacingImportEvent>,
+) {
+    for event in drop_events.read() {
+        if let FileDragAndDrop::DroppedFile { path_buf, .. } = event {
+            if SpacingFormat::from_path(path_buf).is_some() {
+                import_events.write(SpacingImportEvent::Open(path_buf.clone()));
+            }
+        }
+    }
+}
+
+fn handle_spacing_import_events(
+    mut events: EventReader<SpacingImportEvent>,
+    mut import: ResMut<SpacingImport>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut text_editor_state: Option<ResMut<TextEditorState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut glyph_edits: EventWriter<GlyphEdited>,
+    mut kerning_changes: EventWriter<KerningChanged>,
+) {
+    for event in events.read() {
+        match event {
+            SpacingImportEvent::Open(path) => match read_spacing_file(path) {
+                Ok(spacing) => {
+                    info!(
+                        "Read {} advance(s) and {} kerning pair(s) from {}",
+                        spacing.advances.len(),
+                        spacing
+                            .kerning
+                            .pairs
+                            .values()
+                            .map(|seconds| seconds.len())
+                            .sum::<usize>(),
+                        path.display()
+                    );
+                    import.pending = Some(PendingSpacingImport {
+                        path: path.clone(),
+                        spacing,
+                    });
+                }
+                Err(error) => warn!("Spacing not imported: {error:#}"),
+            },
+            SpacingImportEvent::Cancel => import.pending = None,
+            SpacingImportEvent::Apply(mode) => {
+                let Some(state) = app_state.as_mut() else {
+                    warn!("Cannot import spacing: no font loaded");
+                    continue;
+                };
+                let Some(pending) = import.pending.take() else {
+                    continue;
+                };
+                let summary = pending.spacing.apply(&mut state.workspace.font, *mode);
+                info!(
+                    "Imported {} advance(s), {} kerning pair(s) and {} group(s) from {}, \
+                     skipped {}",
+                    summary.advanced.len(),
+                    summary.pairs,
+                    summary.groups,
+                    pending.path.display(),
+                    summary.skipped
+                );
+                if !summary.unknown_glyphs.is_empty() {
+                    warn!("Not in the font: {}", summary.unknown_glyphs.join(" "));
+                }
+
+                // Reflow every sort of the glyphs with a new advance
+                if let Some(text_editor) = text_editor_state.as_mut() {
+                    for index in 0..text_editor.buffer.len() {
+                        if let Some(SortKind::Glyph {
+                            glyph_name,
+                            advance_width,
+                            ..
+                        }) = text_editor.buffer.get_mut(index).map(|sort| &mut sort.kind)
+                        {
+                            if !summary.advanced.contains(glyph_name) {
+                                continue;
+                            }
+                            if let Some(glyph) = font.glyphs.get(glyph_name) {
+                                *advance_width = glyph.advance_width as f32;
+                            }
+                        }
+                    }
+                }
+                app_state_changed.write(AppStateChanged);
+                if summary.pairs > 0 || summary.groups > 0 {
+                    kerning_changes.write(KerningChanged);
+                }
+                glyph_edits.write_batch(summary.advanced.into_iter().map(GlyphEdited::glyph));
+            }
+        }
+    }
+}
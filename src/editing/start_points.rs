@@ -0,0 +1,93 @@
+//! Contour start points and point order
+//!
+//! Interpolation needs every master to list a glyph's points in the same
+//! order, and TrueType conversion numbers points by it. Cmd/Ctrl+Alt+Shift+G
+//! makes each selected on-curve point the start point of its closed contour,
+//! as "Make start point" in a point's right-click menu does.
+//! Cmd/Ctrl+Alt+Shift+Q shows or hides the active sort's point numbers, with
+//! an arrow at each contour's start pointing the way the contour runs (see
+//! `rendering::start_points`).
+
+use crate::core::state::{AppState, PointTypeData};
+use crate::editing::canvas_menu::edit_contour;
+use crate::editing::selection::components::{GlyphPointReference, Selected};
+use crate::editing::selection::events::AppStateChanged;
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+
+/// Whether point numbers and start points are shown
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct StartPointsOverlay {
+    pub visible: bool,
+}
+
+pub struct StartPointsPlugin;
+
+impl Plugin for StartPointsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StartPointsOverlay>()
+            .add_systems(Update, (toggle_start_points, set_selected_start_points));
+    }
+}
+
+/// Cmd/Ctrl+Alt+Shift+Q shows or hides the point numbers
+fn toggle_start_points(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<StartPointsOverlay>,
+) {
+    if Shortcut::PointNumbers.just_pressed(&keyboard) {
+        overlay.visible = !overlay.visible;
+        debug!("Point numbers shown: {}", overlay.visible);
+    }
+}
+
+/// Cmd/Ctrl+Alt+Shift+G makes the selected on-curve points start points;
+/// of several selected in one contour, the first in its order wins
+fn set_selected_start_points(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    selected_points: Query<&GlyphPointReference, With<Selected>>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    if !Shortcut::SetStartPoints.just_pressed(&keyboard) {
+        return;
+    }
+    let Some(state) = app_state.as_deref_mut() else {
+        return;
+    };
+
+    let mut starts: BTreeMap<(String, usize), usize> = BTreeMap::new();
+    for reference in selected_points.iter() {
+        let on_curve = state
+            .workspace
+            .font
+            .editing()
+            .get_glyph(&reference.glyph_name)
+            .and_then(|glyph| glyph.outline.as_ref())
+            .and_then(|outline| outline.contours.get(reference.contour_index))
+            .and_then(|contour| contour.points.get(reference.point_index))
+            .is_some_and(|point| point.point_type != PointTypeData::OffCurve);
+        if on_curve {
+            let start = starts
+                .entry((reference.glyph_name.clone(), reference.contour_index))
+                .or_insert(reference.point_index);
+            *start = (*start).min(reference.point_index);
+        }
+    }
+
+    let mut changed = false;
+    for ((glyph_name, contour_index), index) in starts {
+        changed |= index != 0
+            && edit_contour(state, &glyph_name, contour_index, |contour| {
+                Some(contour.with_start_point(index))
+            });
+    }
+    if changed {
+        debug!("Moved contour start points to the selected points");
+        app_state_changed.write(AppStateChanged);
+        visual_update_tracker.needs_update = true;
+    }
+}
@@ -0,0 +1,74 @@
+//! Editing the family's STAT axis values
+//!
+//! Cmd/Ctrl+Alt+P shows the STAT pane, which adds named values on the
+//! registered axes, steps them along their axis, names them, links them,
+//! marks them elidable and picks the elided fallback name (see
+//! `font_source::stat`). Changes are saved with the font and written into
+//! its variable fonts on export.
+
+use crate::core::state::AppState;
+use crate::editing::selection::events::AppStateChanged;
+use crate::io::shortcuts::Shortcut;
+use bevy::prelude::*;
+
+/// Whether the STAT pane is shown
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct StatPaneState {
+    pub visible: bool,
+}
+
+/// Changes to the STAT axis values, values by index
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum StatEvent {
+    /// Add a value on the axis with this tag
+    AddValue(&'static str),
+    StepValue(usize, bool),
+    CycleName(usize),
+    ToggleLink(usize),
+    ToggleElidable(usize),
+    RemoveValue(usize),
+    CycleElidedFallbackName,
+}
+
+pub struct StatPlugin;
+
+impl Plugin for StatPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StatPaneState>()
+            .add_event::<StatEvent>()
+            .add_systems(Update, (toggle_stat_pane, handle_stat_events).chain());
+    }
+}
+
+/// Cmd/Ctrl+Alt+P shows or hides the STAT pane
+fn toggle_stat_pane(keyboard: Res<ButtonInput<KeyCode>>, mut pane: ResMut<StatPaneState>) {
+    if Shortcut::Stat.just_pressed(&keyboard) {
+        pane.visible = !pane.visible;
+        debug!("STAT pane shown: {}", pane.visible);
+    }
+}
+
+fn handle_stat_events(
+    mut events: EventReader<StatEvent>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+) {
+    let Some(state) = app_state.as_mut() else {
+        events.clear();
+        return;
+    };
+
+    for event in events.read() {
+        let stat = &mut state.workspace.font.stat;
+        match *event {
+            StatEvent::AddValue(axis) => stat.add_value(axis),
+            StatEvent::StepValue(index, forward) => stat.step_value(index, forward),
+            StatEvent::CycleName(index) => stat.cycle_name(index),
+            StatEvent::ToggleLink(index) => stat.toggle_link(index),
+            StatEvent::ToggleElidable(index) => stat.toggle_elidable(index),
+            StatEvent::RemoveValue(index) => stat.remove_value(index),
+            StatEvent::CycleElidedFallbackName => stat.cycle_elided_fallback_name(),
+        }
+        app_state_changed.write(AppStateChanged);
+    }
+}
@@ -0,0 +1,136 @@
+//! Stem width report for the active glyph
+//!
+//! With the select tool, Cmd/Ctrl+Alt+W measures the stems of the active
+//! sort's glyph and lists them in the stem report pane: vertical stems,
+//! horizontal bars and thin strokes, each with the font's nearest standard
+//! stem (the PostScript stem snap values in the font info) and a flag when
+//! it's off by more than the tolerance. The report follows edits to the
+//! measured glyph until it's closed.
+
+use crate::core::state::AppState;
+use crate::editing::font_changes::GlyphEdited;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::font_source::stems::{measure_stems, StemWidth};
+use crate::io::shortcuts::Shortcut;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use bevy::prelude::*;
+
+/// Stems measured in one glyph
+pub struct GlyphStems {
+    pub glyph_name: String,
+    pub stems: Vec<StemWidth>,
+}
+
+/// The stem report being shown, if any
+#[derive(Resource, Default)]
+pub struct StemReport {
+    pub report: Option<GlyphStems>,
+}
+
+/// Measure the stems of a glyph and show them
+#[derive(Event, Clone)]
+pub struct MeasureStemsEvent {
+    pub glyph_name: String,
+}
+
+/// Stop showing the stem report
+#[derive(Event, Clone)]
+pub struct CloseStemReportEvent;
+
+pub struct StemReportPlugin;
+
+impl Plugin for StemReportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StemReport>()
+            .add_event::<MeasureStemsEvent>()
+            .add_event::<CloseStemReportEvent>()
+            .add_systems(
+                Update,
+                (handle_stem_report_shortcut, handle_stem_report_events).chain(),
+            );
+    }
+}
+
+/// Measure the active sort's glyph with Cmd/Ctrl+Alt+W
+fn handle_stem_report_shortcut(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_tool: Res<CurrentTool>,
+    active_sorts: Query<&Sort, With<ActiveSort>>,
+    mut measure_events: EventWriter<MeasureStemsEvent>,
+) {
+    if current_tool.get_current() != Some("select") {
+        return;
+    }
+    if !Shortcut::StemReport.just_pressed(&keyboard) {
+        return;
+    }
+
+    let Ok(sort) = active_sorts.single() else {
+        warn!("Activate a sort to measure its stems");
+        return;
+    };
+    measure_events.write(MeasureStemsEvent {
+        glyph_name: sort.glyph_name.clone(),
+    });
+}
+
+fn measure(state: &AppState, glyph_name: &str) -> GlyphStems {
+    let paths = state.workspace.font.editing().resolved_bezpaths(glyph_name);
+    GlyphStems {
+        glyph_name: glyph_name.to_string(),
+        stems: measure_stems(&paths, &state.workspace.info),
+    }
+}
+
+fn handle_stem_report_events(
+    mut measure_events: EventReader<MeasureStemsEvent>,
+    mut close_events: EventReader<CloseStemReportEvent>,
+    mut glyph_edits: EventReader<GlyphEdited>,
+    mut stem_report: ResMut<StemReport>,
+    app_state: Option<Res<AppState>>,
+) {
+    let Some(state) = app_state else {
+        measure_events.clear();
+        close_events.clear();
+        glyph_edits.clear();
+        return;
+    };
+
+    for event in measure_events.read() {
+        if state
+            .workspace
+            .font
+            .editing()
+            .get_glyph(&event.glyph_name)
+            .is_none()
+        {
+            continue;
+        }
+        let report = measure(&state, &event.glyph_name);
+        let flagged = report.stems.iter().filter(|stem| stem.deviates).count();
+        info!(
+            "Measured {} stems in '{}', {} off the standard stems",
+            report.stems.len(),
+            report.glyph_name,
+            flagged
+        );
+        stem_report.report = Some(report);
+    }
+
+    let measured = stem_report
+        .report
+        .as_ref()
+        .map(|report| report.glyph_name.clone());
+    if let Some(glyph_name) = measured {
+        // Components change with their base glyphs, so any edit may matter
+        if glyph_edits.read().count() > 0 {
+            stem_report.report = Some(measure(&state, &glyph_name));
+        }
+    } else {
+        glyph_edits.clear();
+    }
+
+    if close_events.read().count() > 0 {
+        stem_report.report = None;
+    }
+}
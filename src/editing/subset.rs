@@ -0,0 +1,220 @@
+//! Subsets of the open font
+//!
+//! Cmd/Ctrl+Alt+X shows the subset pane, which picks what a subset keeps:
+//! the glyphs typed in the text buffers, the target glyph set and a few
+//! Unicode blocks. Writing a subset saves `<font>-Subset.ufo` or compiles
+//! `<font>-Subset.ttf` next to the font in the background, from the font
+//! as edited (see `data::subset`). A trial font (`<font>-Trial.ufo` or
+//! `.ttf`) keeps every glyph instead, drawing those outside the subset as
+//! placeholders.
+
+use crate::core::state::{AppState, TextEditorState};
+use crate::data::subset::{write_subset, write_trial, SubsetReport};
+use crate::font_source::subset::SubsetSpec;
+use crate::io::shortcuts::Shortcut;
+use crate::systems::commands::TargetGlyphSet;
+use anyhow::Result;
+use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// Unicode blocks a subset can keep: name and range
+pub const SUBSET_BLOCKS: [(&str, &str); 4] = [
+    ("Basic Latin", "U+0020-007E"),
+    ("Latin-1 Supplement", "U+00A0-00FF"),
+    ("Latin Extended-A", "U+0100-017F"),
+    ("General Punctuation", "U+2000-206F"),
+];
+
+/// Where the glyphs of a subset come from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SubsetSource {
+    /// Glyphs typed in the text buffers
+    Text,
+    /// The glyph set the font is meant to cover
+    GlyphSet,
+    /// One of `SUBSET_BLOCKS`, by index
+    Block(usize),
+}
+
+/// What a subset is written as
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubsetOutput {
+    Ufo,
+    Ttf,
+}
+
+impl SubsetOutput {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SubsetOutput::Ufo => "ufo",
+            SubsetOutput::Ttf => "ttf",
+        }
+    }
+}
+
+/// The sources picked for a subset, and the subset being written
+#[derive(Resource)]
+pub struct SubsetState {
+    pub visible: bool,
+    pub sources: BTreeSet<SubsetSource>,
+    task: Option<Task<Result<(PathBuf, SubsetReport)>>>,
+}
+
+impl Default for SubsetState {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            sources: BTreeSet::from([SubsetSource::Block(0)]),
+            task: None,
+        }
+    }
+}
+
+impl SubsetState {
+    pub fn is_writing(&self) -> bool {
+        self.task.is_some()
+    }
+
+    /// The glyph names, codepoints and ranges of the picked sources
+    pub fn entries(&self, text_editor: &TextEditorState, target: &TargetGlyphSet) -> Vec<String> {
+        let mut entries = Vec::new();
+        for source in &self.sources {
+            match source {
+                SubsetSource::Text => entries.extend(
+                    text_editor
+                        .buffer
+                        .iter()
+                        .filter(|sort| sort.kind.is_glyph())
+                        .map(|sort| sort.kind.glyph_name().to_string()),
+                ),
+                SubsetSource::GlyphSet => {
+                    let set_entries = target.0.iter().flat_map(|set| &set.entries);
+                    entries.extend(set_entries.map(|entry| match entry.codepoint {
+                        Some(c) => format!("U+{:04X}", u32::from(c)),
+                        None => entry.name.clone(),
+                    }));
+                }
+                SubsetSource::Block(index) => {
+                    if let Some((_, range)) = SUBSET_BLOCKS.get(*index) {
+                        entries.push(range.to_string());
+                    }
+                }
+            }
+        }
+        entries.sort();
+        entries.dedup();
+        entries
+    }
+}
+
+/// Changes to the subset
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum SubsetEvent {
+    Toggle(SubsetSource),
+    /// Write the subset, or a trial font of it
+    Write { output: SubsetOutput, trial: bool },
+}
+
+pub struct SubsetPlugin;
+
+impl Plugin for SubsetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SubsetState>()
+            .add_event::<SubsetEvent>()
+            .add_systems(
+                Update,
+                (toggle_subset, handle_subset_events, finish_subset).chain(),
+            );
+    }
+}
+
+/// Cmd/Ctrl+Alt+X shows or hides the subset pane
+fn toggle_subset(keyboard: Res<ButtonInput<KeyCode>>, mut subset: ResMut<SubsetState>) {
+    if Shortcut::Subset.just_pressed(&keyboard) {
+        subset.visible = !subset.visible;
+        debug!("Subset shown: {}", subset.visible);
+    }
+}
+
+fn handle_subset_events(
+    mut events: EventReader<SubsetEvent>,
+    mut subset: ResMut<SubsetState>,
+    app_state: Option<Res<AppState>>,
+    text_editor_state: Option<Res<TextEditorState>>,
+    target: Res<TargetGlyphSet>,
+) {
+    for event in events.read() {
+        match event {
+            SubsetEvent::Toggle(source) => {
+                if !subset.sources.remove(source) {
+                    subset.sources.insert(*source);
+                }
+            }
+            SubsetEvent::Write { output, trial } => {
+                if subset.is_writing() {
+                    warn!("A subset is already being written");
+                    continue;
+                }
+                let Some(state) = app_state.as_ref() else {
+                    warn!("Cannot write a subset: no font loaded");
+                    continue;
+                };
+                let workspace = &state.workspace;
+                let Some(font_path) = workspace.font.path.as_ref() else {
+                    warn!("Cannot write a subset: save the font first");
+                    continue;
+                };
+                let entries = text_editor_state
+                    .as_ref()
+                    .map(|text_editor| subset.entries(text_editor, &target))
+                    .unwrap_or_default();
+                let spec = match SubsetSpec::parse(&entries) {
+                    Ok(spec) => spec,
+                    Err(error) => {
+                        warn!("Cannot write a subset: {error:#}");
+                        continue;
+                    }
+                };
+                let stem = font_path.file_stem().unwrap_or_default().to_string_lossy();
+                let kind = if *trial { "Trial" } else { "Subset" };
+                let path =
+                    font_path.with_file_name(format!("{stem}-{kind}.{}", output.extension()));
+                let font = workspace.font.clone();
+                let info = workspace.info.clone();
+                let write = if *trial { write_trial } else { write_subset };
+                info!("Writing {} to {}", kind.to_lowercase(), path.display());
+                subset.task = Some(AsyncComputeTaskPool::get().spawn(async move {
+                    let report = write(&font, &info, &spec, &path)?;
+                    Ok((path, report))
+                }));
+            }
+        }
+    }
+}
+
+/// Picks up a finished subset and reports what it kept
+fn finish_subset(mut subset: ResMut<SubsetState>) {
+    let Some(task) = subset.task.as_mut() else {
+        return;
+    };
+    let Some(result) = block_on(future::poll_once(task)) else {
+        return;
+    };
+    subset.task = None;
+    match result {
+        Ok((path, report)) => {
+            for warning in &report.warnings {
+                warn!("{warning}");
+            }
+            info!(
+                "Wrote {} kept glyphs ({} for components) to {}",
+                report.resolved.glyphs.len(),
+                report.resolved.dependencies.len(),
+                path.display()
+            );
+        }
+        Err(error) => error!("Writing the subset failed: {error:#}"),
+    }
+}
@@ -0,0 +1,412 @@
+//! Text buffer manager
+//!
+//! Cmd/Ctrl+Alt+Shift+Y shows the text buffers pane, which lists every text
+//! buffer in the scene by name with the start of its text. From the pane a
+//! buffer can be made the one typed into, brought into view, renamed,
+//! duplicated below itself or deleted along with its sorts. While a buffer
+//! is renamed, typing goes into its name: Enter keeps it, Escape cancels.
+
+#![allow(clippy::too_many_arguments)]
+
+use crate::core::state::text_editor::buffer::BufferId;
+use crate::core::state::text_editor::text_buffer::{ActiveTextBuffer, BufferCursor, TextBuffer};
+use crate::core::state::text_editor::{BufferSystemSet, SortData, SortKind};
+use crate::core::state::{text_line_height, AppState, TextEditorState};
+use crate::editing::text_field_focus::{FieldKeys, TextField, TextFieldFocus, TextFieldSystems};
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::cameras::DesignCamera;
+use crate::systems::sorts::sort_entities::BufferSortRespawnQueue;
+use crate::systems::sorts::{copy_sorts, insert_sorts, queue_respawn, remove_sorts, BufferLayouts};
+use crate::systems::text_buffer_manager::create_text_buffer;
+use crate::ui::edit_mode_toolbar::text::TextPlacementMode;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+
+/// Characters of a buffer's text shown in the pane
+const PREVIEW_CHARS: usize = 24;
+
+/// A text buffer as the pane lists it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextBufferListing {
+    pub entity: Entity,
+    pub name: String,
+    /// Start of the buffer's text
+    pub preview: String,
+    pub sort_count: usize,
+    /// Whether typing goes into this buffer
+    pub active: bool,
+}
+
+/// Whether the text buffers pane is shown, the buffers it lists and the
+/// name being typed for a buffer
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct TextBuffersPaneState {
+    pub visible: bool,
+    /// Buffers in the order they were created
+    pub buffers: Vec<TextBufferListing>,
+    pub renaming: Option<(Entity, String)>,
+}
+
+impl TextBuffersPaneState {
+    /// Whether typing goes into a buffer's name
+    pub fn is_renaming(&self) -> bool {
+        self.renaming.is_some()
+    }
+}
+
+/// Operations on a text buffer from the pane
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextBufferEvent {
+    /// Type into the buffer
+    Activate(Entity),
+    /// Move the camera to the buffer
+    Focus(Entity),
+    /// Start typing a new name for the buffer
+    Rename(Entity),
+    Duplicate(Entity),
+    Delete(Entity),
+}
+
+impl TextBufferEvent {
+    /// The buffer entity the operation is on
+    fn entity(self) -> Entity {
+        match self {
+            Self::Activate(entity)
+            | Self::Focus(entity)
+            | Self::Rename(entity)
+            | Self::Duplicate(entity)
+            | Self::Delete(entity) => entity,
+        }
+    }
+}
+
+pub struct TextBuffersPlugin;
+
+impl Plugin for TextBuffersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TextBuffersPaneState>()
+            .add_event::<TextBufferEvent>()
+            .add_systems(
+                Update,
+                (
+                    toggle_text_buffers_pane,
+                    handle_rename_keys.in_set(TextFieldSystems),
+                    handle_text_buffer_events,
+                    refresh_text_buffer_listings,
+                )
+                    .chain()
+                    // New buffers exist before missing ones are created for
+                    // their sorts
+                    .before(BufferSystemSet::UpdateBuffers),
+            );
+    }
+}
+
+/// Start of a buffer's text; line breaks show as spaces and glyphs without
+/// a character as `/name`
+pub fn buffer_preview<'a>(sorts: impl IntoIterator<Item = &'a SortData>) -> String {
+    let mut preview = String::new();
+    for (index, sort) in sorts.into_iter().enumerate() {
+        if index == PREVIEW_CHARS {
+            preview.push('…');
+            break;
+        }
+        match &sort.kind {
+            SortKind::Glyph {
+                codepoint: Some(c), ..
+            } => preview.push(*c),
+            SortKind::Glyph { glyph_name, .. } => {
+                preview.push('/');
+                preview.push_str(glyph_name);
+            }
+            SortKind::LineBreak => preview.push(' '),
+        }
+    }
+    preview
+}
+
+/// Area covered by glyphs with the given origins and advances, on their
+/// baselines
+fn text_extent(glyphs: impl IntoIterator<Item = (Vec2, f32)>) -> Option<Rect> {
+    glyphs
+        .into_iter()
+        .map(|(origin, advance)| Rect::from_corners(origin, origin + Vec2::X * advance))
+        .reduce(|extent, glyph| extent.union(glyph))
+}
+
+/// Area covered by a buffer's sorts, where they are laid out
+fn buffer_extent(state: &TextEditorState, layouts: &BufferLayouts, buffer: &TextBuffer) -> Rect {
+    let glyphs = state
+        .buffer
+        .iter()
+        .enumerate()
+        .filter(|(_, sort)| sort.buffer_id == Some(buffer.id))
+        .map(|(index, sort)| {
+            let origin = layouts.origin(index).unwrap_or(buffer.root_position);
+            let advance = match &sort.kind {
+                SortKind::Glyph { advance_width, .. } => *advance_width,
+                SortKind::LineBreak => 0.0,
+            };
+            (origin, advance)
+        });
+    text_extent(glyphs).unwrap_or(Rect::from_center_size(buffer.root_position, Vec2::ZERO))
+}
+
+/// Cmd/Ctrl+Alt+Shift+Y shows or hides the text buffers pane
+fn toggle_text_buffers_pane(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut pane: ResMut<TextBuffersPaneState>,
+) {
+    if Shortcut::TextBuffers.just_pressed(&keyboard) {
+        pane.visible = !pane.visible;
+        if !pane.visible {
+            pane.renaming = None;
+        }
+        debug!("Text buffers pane shown: {}", pane.visible);
+    }
+}
+
+/// Types into the name of the buffer being renamed; Enter keeps the name
+/// and Escape drops it
+fn handle_rename_keys(
+    keyboard: Res<FieldKeys>,
+    mut key_events: EventReader<KeyboardInput>,
+    mut focus: ResMut<TextFieldFocus>,
+    mut pane: ResMut<TextBuffersPaneState>,
+    mut buffer_query: Query<&mut TextBuffer>,
+) {
+    focus.set(TextField::BufferName, pane.is_renaming());
+    let Some((entity, mut name)) = pane.renaming.clone() else {
+        key_events.clear();
+        return;
+    };
+    let modifier = keyboard.any_pressed([
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+    ]);
+
+    let mut done = None;
+    for event in key_events.read() {
+        if event.state != ButtonState::Pressed || modifier {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(text) => name.extend(text.chars().filter(|c| !c.is_control())),
+            Key::Space => name.push(' '),
+            Key::Backspace => {
+                name.pop();
+            }
+            Key::Enter => done = Some(true),
+            Key::Escape => done = Some(false),
+            _ => {}
+        }
+    }
+
+    match done {
+        Some(keep) => {
+            let name = name.trim();
+            if keep && !name.is_empty() {
+                if let Ok(mut buffer) = buffer_query.get_mut(entity) {
+                    buffer.name = name.to_string();
+                    debug!("Renamed text buffer {:?} to '{}'", entity, name);
+                }
+            }
+            pane.renaming = None;
+        }
+        None => {
+            if pane.renaming.as_ref().map(|(_, typed)| typed) != Some(&name) {
+                pane.renaming = Some((entity, name));
+            }
+        }
+    }
+}
+
+fn handle_text_buffer_events(
+    mut commands: Commands,
+    mut events: EventReader<TextBufferEvent>,
+    mut pane: ResMut<TextBuffersPaneState>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    buffer_query: Query<(&TextBuffer, &BufferCursor)>,
+    mut active_buffer: ResMut<ActiveTextBuffer>,
+    mut current_tool: ResMut<CurrentTool>,
+    mut placement_mode: ResMut<TextPlacementMode>,
+    layouts: Res<BufferLayouts>,
+    mut camera_query: Query<&mut Transform, With<DesignCamera>>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+    app_state: Option<Res<AppState>>,
+) {
+    for event in events.read() {
+        let entity = event.entity();
+        let Ok((buffer, cursor)) = buffer_query.get(entity) else {
+            continue;
+        };
+
+        match *event {
+            TextBufferEvent::Activate(_) => {
+                active_buffer.buffer_entity = Some(entity);
+                if current_tool.get_current() != Some("text") {
+                    current_tool.switch_to("text");
+                }
+                *placement_mode = TextPlacementMode::Insert;
+                text_editor_state.set_changed();
+                debug!("Typing into text buffer '{}'", buffer.name);
+            }
+            TextBufferEvent::Focus(_) => {
+                // Center on the middle of the text, half an x-height up
+                let extent = buffer_extent(&text_editor_state, &layouts, buffer);
+                let x_height = app_state
+                    .as_ref()
+                    .and_then(|state| state.workspace.info.metrics.x_height)
+                    .unwrap_or(500.0) as f32;
+                let center = extent.center() + Vec2::Y * x_height / 2.0;
+                for mut transform in camera_query.iter_mut() {
+                    transform.translation.x = center.x;
+                    transform.translation.y = center.y;
+                }
+                debug!("Moved the camera to text buffer '{}'", buffer.name);
+            }
+            TextBufferEvent::Rename(_) => {
+                pane.renaming = Some((entity, buffer.name.clone()));
+            }
+            TextBufferEvent::Duplicate(_) => {
+                let sorts = copy_sorts(&text_editor_state, buffer.id, 0..usize::MAX);
+                let line_height = text_line_height(app_state.as_deref());
+                // The copy starts a blank line below the last line
+                let lowest = buffer_extent(&text_editor_state, &layouts, buffer).min.y;
+                let copy = TextBuffer {
+                    id: BufferId::new(),
+                    name: format!("{} copy", buffer.name),
+                    root_position: Vec2::new(buffer.root_position.x, lowest - 2.0 * line_height),
+                    is_active: false,
+                    ..buffer.clone()
+                };
+                let copy_entity = create_text_buffer(
+                    &mut commands,
+                    copy.id,
+                    copy.layout_mode.clone(),
+                    copy.root_position,
+                    cursor.position.min(sorts.len()),
+                );
+                // Sorts of a new buffer go at the end, so no indices move
+                insert_sorts(&mut text_editor_state, &copy, 0, &sorts);
+                debug!(
+                    "Duplicated text buffer '{}' as '{}'",
+                    buffer.name, copy.name
+                );
+                commands.entity(copy_entity).insert(copy);
+            }
+            TextBufferEvent::Delete(_) => {
+                if let Some(first) = remove_sorts(&mut text_editor_state, buffer.id, 0..usize::MAX)
+                {
+                    queue_respawn(&text_editor_state, &mut respawn_queue, first);
+                }
+                if active_buffer.buffer_entity == Some(entity) {
+                    active_buffer.buffer_entity = None;
+                }
+                if pane
+                    .renaming
+                    .as_ref()
+                    .is_some_and(|(renamed, _)| *renamed == entity)
+                {
+                    pane.renaming = None;
+                }
+                commands.entity(entity).despawn();
+                debug!("Deleted text buffer '{}'", buffer.name);
+            }
+        }
+    }
+}
+
+/// Lists the buffers again when they change, while the pane is shown
+fn refresh_text_buffer_listings(
+    mut pane: ResMut<TextBuffersPaneState>,
+    text_editor_state: Res<TextEditorState>,
+    active_buffer: Res<ActiveTextBuffer>,
+    buffer_query: Query<(Entity, &TextBuffer)>,
+) {
+    if !pane.visible {
+        return;
+    }
+    let mut buffers: Vec<_> = buffer_query.iter().collect();
+    buffers.sort_by_key(|(_, buffer)| buffer.id.0);
+    let listings: Vec<_> = buffers
+        .into_iter()
+        .map(|(entity, buffer)| {
+            let sorts: Vec<_> = text_editor_state
+                .buffer
+                .iter()
+                .filter(|sort| sort.buffer_id == Some(buffer.id))
+                .collect();
+            TextBufferListing {
+                entity,
+                name: buffer.name.clone(),
+                preview: buffer_preview(sorts.iter().copied()),
+                sort_count: sorts.len(),
+                active: active_buffer.buffer_entity == Some(entity),
+            }
+        })
+        .collect();
+    if pane.buffers != listings {
+        pane.buffers = listings;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::state::text_editor::SortLayoutMode;
+
+    fn sort(kind: SortKind) -> SortData {
+        SortData {
+            kind,
+            is_active: false,
+            layout_mode: SortLayoutMode::LTRText,
+            root_position: Vec2::ZERO,
+            placement_offset: Vec2::ZERO,
+            buffer_cursor_position: None,
+            buffer_id: None,
+        }
+    }
+
+    fn glyph(name: &str, codepoint: Option<char>) -> SortData {
+        sort(SortKind::Glyph {
+            codepoint,
+            glyph_name: name.to_string(),
+            advance_width: 500.0,
+        })
+    }
+
+    #[test]
+    fn test_buffer_preview() {
+        let sorts = [
+            glyph("H", Some('H')),
+            glyph("i", Some('i')),
+            sort(SortKind::LineBreak),
+            glyph("f_i", None),
+        ];
+        assert_eq!(buffer_preview(&sorts), "Hi /f_i");
+
+        let long = vec![glyph("a", Some('a')); PREVIEW_CHARS + 1];
+        assert_eq!(
+            buffer_preview(&long),
+            format!("{}…", "a".repeat(PREVIEW_CHARS))
+        );
+    }
+
+    #[test]
+    fn test_text_extent() {
+        let extent = text_extent([
+            (Vec2::new(0.0, 0.0), 500.0),
+            (Vec2::new(500.0, 0.0), 300.0),
+            (Vec2::new(0.0, -1200.0), 400.0),
+        ])
+        .unwrap();
+        assert_eq!(extent.min, Vec2::new(0.0, -1200.0));
+        assert_eq!(extent.max, Vec2::new(800.0, 0.0));
+        assert!(text_extent(Vec::new()).is_none());
+    }
+}
@@ -7,10 +7,10 @@ use crate::systems::sorts::{
     despawn_inactive_sort_points_optimized, // NEW: Optimized instant point despawning
     despawn_missing_buffer_sort_entities,   // NEW: Despawn deleted buffer sorts
     detect_sort_glyph_changes, // NEW: Detect glyph changes and force point regeneration
-    handle_arabic_text_input,  // NEW: Arabic and Unicode text input
+    handle_escape_to_text_navigation,
+    handle_sort_double_click,
     handle_sort_placement_input,
     handle_unicode_text_input, // NEW: Unicode character input using Bevy events
-    initialize_rtl_shaping,    // NEW: Initialize RTL shaping resources
     initialize_text_editor_sorts,
     manage_sort_activation, // NEW: ECS-based sort activation management
     regenerate_points_on_fontir_change, // NEW: Regenerate points when FontIR data changes
@@ -31,23 +31,56 @@ impl Plugin for TextEditorPlugin {
             .init_resource::<crate::core::state::text_editor::TextEditorState>()
             .init_resource::<crate::rendering::CursorRenderingState>()
             .init_resource::<crate::core::state::text_editor::ActiveSortEntity>()
+            .init_resource::<crate::systems::sorts::ImeComposition>()
+            .init_resource::<crate::systems::sorts::AlternatesPicker>()
+            .init_resource::<crate::systems::sorts::CharacterPicker>()
+            .init_resource::<crate::systems::sorts::SpacingMode>()
+            .init_resource::<crate::systems::sorts::SortClipboard>()
+            .init_resource::<crate::systems::sorts::BufferLayouts>()
+            .init_resource::<crate::systems::sorts::SortViewport>()
+            .add_event::<crate::systems::sorts::KernProofRequest>()
+            .add_event::<crate::systems::sorts::SortClipboardEvent>()
             // Add buffer manager plugin
             .add_plugins(crate::systems::TextBufferManagerPlugin)
+            .add_plugins(crate::systems::TextFramesPlugin)
             // Initialize text editor state
-            .add_systems(
-                Startup,
-                (
-                    initialize_text_editor_sorts,
-                    initialize_rtl_shaping, // Initialize RTL shaping resources
-                ),
-            )
+            .add_systems(Startup, initialize_text_editor_sorts)
             // Input handling
             .add_systems(
                 Update,
                 (
+                    crate::systems::sorts::handle_ime_input.before(handle_unicode_text_input),
                     handle_unicode_text_input,
-                    handle_arabic_text_input, // Handle Arabic text input with shaping
                     handle_sort_placement_input,
+                    handle_sort_double_click,
+                    handle_escape_to_text_navigation,
+                    crate::systems::sorts::handle_alternates_picker_keys
+                        .after(handle_unicode_text_input)
+                        .before(crate::ui::edit_mode_toolbar::text::handle_text_tool_shortcuts),
+                    crate::systems::sorts::handle_alternate_option_clicks,
+                    crate::systems::sorts::handle_character_picker_keys
+                        .after(handle_unicode_text_input)
+                        .before(crate::ui::edit_mode_toolbar::text::handle_text_tool_shortcuts),
+                    crate::systems::sorts::handle_character_option_clicks,
+                    (
+                        crate::systems::sorts::handle_spacing_mode_toggle,
+                        crate::systems::sorts::handle_spacing_mode_keys,
+                    )
+                        .chain()
+                        .before(crate::ui::edit_mode_toolbar::text::handle_text_tool_shortcuts),
+                    (
+                        crate::systems::sorts::handle_kern_proof_shortcut,
+                        crate::systems::sorts::generate_kern_proofs,
+                    )
+                        .chain(),
+                    (
+                        crate::systems::sorts::handle_sort_clipboard_shortcuts,
+                        crate::systems::sorts::handle_sort_clipboard_events,
+                        crate::systems::sorts::finish_sort_paste,
+                    )
+                        .chain()
+                        .after(handle_unicode_text_input),
+                    crate::systems::sorts::handle_sort_drag,
                 )
                     .in_set(super::FontEditorSets::Input),
             )
@@ -55,9 +88,13 @@ impl Plugin for TextEditorPlugin {
             .add_systems(
                 Update,
                 (
-                    spawn_missing_sort_entities,
+                    crate::systems::sorts::update_sort_viewport,
+                    crate::systems::sorts::layout_text_buffers,
+                    crate::systems::sorts::apply_buffer_layouts,
+                    // Idle until the buffer, its layout or its entities change
+                    spawn_missing_sort_entities
+                        .run_if(crate::systems::sorts::sort_entities_out_of_date),
                     sync_buffer_sort_activation_state, // NEW: Sync activation state after spawning
-                    crate::systems::sorts::sort_entities::update_buffer_sort_positions,
                     crate::systems::sorts::sort_entities::auto_activate_selected_sorts,
                     manage_sort_activation,
                 )
@@ -80,13 +117,26 @@ impl Plugin for TextEditorPlugin {
             // Rendering systems
             .add_systems(
                 Update,
-                (crate::systems::sorts::cursor::render_text_editor_cursor,)
+                (
+                    (
+                        crate::systems::sorts::cursor::render_text_editor_cursor,
+                        crate::systems::sorts::cursor::blink_text_cursor,
+                    )
+                        .chain(),
+                    crate::systems::sorts::update_ime_window_state,
+                    crate::systems::sorts::render_ime_preedit,
+                    crate::systems::sorts::render_alternates_popup,
+                    crate::systems::sorts::render_character_picker,
+                    crate::systems::sorts::render_spacing_marker,
+                )
                     .in_set(super::FontEditorSets::Rendering),
             )
             // Cleanup systems (the old cleanup system is now replaced by component-relationship cleanup)
             .add_systems(
                 Update,
-                despawn_missing_buffer_sort_entities.in_set(super::FontEditorSets::Cleanup),
+                despawn_missing_buffer_sort_entities
+                    .run_if(crate::systems::sorts::sort_entities_out_of_date)
+                    .in_set(super::FontEditorSets::Cleanup),
             );
     }
 }
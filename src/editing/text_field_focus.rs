@@ -0,0 +1,97 @@
+//! Which of the editor's text fields has the keyboard
+//!
+//! Every `EventReader<KeyboardInput>` sees every key event, so a prompt
+//! reading what's typed doesn't keep the text tool from typing the same
+//! keys into the active buffer, or the tool shortcuts from switching tools.
+//! Instead, each prompt marks its field in `TextFieldFocus` while it's being
+//! typed into, and reads its keys in `TextFieldSystems`.
+//! `handle_unicode_text_input` and `handle_tool_keyboard_shortcuts` run
+//! after that set and ignore the keyboard while a field is focused.
+//!
+//! Shortcuts reading `ButtonInput<KeyCode>` are held off in `PreUpdate`,
+//! before any of them runs: while a field is focused the keyboard is
+//! cleared right after Bevy updates it, so Backspace typed into a field
+//! can't also delete the selected sorts, or an arrow nudge points. Prompts
+//! reading keys the same way read `FieldKeys`, the keyboard as it was
+//! before it was cleared.
+
+use crate::systems::sorts::handle_unicode_text_input;
+use crate::ui::edit_mode_toolbar::keyboard_shortcuts::handle_tool_keyboard_shortcuts;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+/// A text field that takes the keyboard while it's typed into
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextField {
+    BufferName,
+    ColorEntry,
+    FindReplace,
+    GlyphAutocomplete,
+    HandleEntry,
+    PieceName,
+}
+
+/// The text fields being typed into
+#[derive(Resource, Default, Debug)]
+pub struct TextFieldFocus {
+    focused: HashSet<TextField>,
+}
+
+impl TextFieldFocus {
+    /// Mark whether `field` is being typed into; its prompt calls this
+    /// every frame before reading keys
+    pub fn set(&mut self, field: TextField, focused: bool) {
+        if focused {
+            self.focused.insert(field);
+        } else {
+            self.focused.remove(&field);
+        }
+    }
+
+    /// Whether any field is being typed into
+    pub fn is_focused(&self) -> bool {
+        !self.focused.is_empty()
+    }
+}
+
+/// The keyboard before `hold_focused_keys` clears it, for prompts checking
+/// modifiers or arrow keys while their field is focused
+#[derive(Resource, Default, Debug, Deref)]
+pub struct FieldKeys(ButtonInput<KeyCode>);
+
+/// Systems of prompts reading keys into their text fields
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct TextFieldSystems;
+
+pub struct TextFieldFocusPlugin;
+
+impl Plugin for TextFieldFocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TextFieldFocus>()
+            .init_resource::<FieldKeys>()
+            .configure_sets(
+                Update,
+                TextFieldSystems
+                    .before(handle_unicode_text_input)
+                    .before(handle_tool_keyboard_shortcuts),
+            )
+            .add_systems(PreUpdate, hold_focused_keys.after(InputSystem));
+    }
+}
+
+/// Keeps the keys typed into a field from reaching the editor's shortcuts
+///
+/// Focus is what the prompts marked last frame; they keep it through the
+/// frame their field closes in, so the Enter or Escape closing it is held
+/// too.
+fn hold_focused_keys(
+    focus: Res<TextFieldFocus>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut field_keys: ResMut<FieldKeys>,
+) {
+    field_keys.0.clone_from(&keyboard);
+    if focus.is_focused() {
+        keyboard.clear();
+    }
+}
@@ -0,0 +1,327 @@
+//! Save and restore the editing session
+//!
+//! Every save records the text buffers, the camera, the glyph being viewed
+//! and the tool into the font (see `font_source::session`), and opening the
+//! font again puts them back in place of the startup layout, so work picks
+//! up where it stopped.
+
+#![allow(clippy::too_many_arguments)]
+
+use crate::core::state::text_editor::buffer::BufferId;
+use crate::core::state::text_editor::text_buffer::{
+    ActiveTextBuffer, BufferCursor, TextAlignment, TextBuffer, TextFrame,
+};
+use crate::core::state::text_editor::{SortData, SortKind, SortLayoutMode};
+use crate::core::state::{AppState, GlyphNavigation, TextEditorState};
+use crate::font_source::session::{
+    SessionAlignment, SessionBuffer, SessionCamera, SessionDirection, SessionSort, WorkspaceSession,
+};
+use crate::rendering::cameras::DesignCamera;
+use crate::systems::commands::{
+    handle_open_file, handle_save_file, handle_save_file_as, OpenFileEvent, SaveFileAsEvent,
+    SaveFileEvent,
+};
+use crate::systems::sorts::queue_respawn;
+use crate::systems::sorts::sort_entities::BufferSortRespawnQueue;
+use crate::systems::startup_layout::CenterCameraOnDefaultSort;
+use crate::systems::text_buffer_manager::create_text_buffer;
+use crate::ui::edit_mode_toolbar::{CurrentTool, ToolRegistry};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+pub struct WorkspaceSessionPlugin;
+
+impl Plugin for WorkspaceSessionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                capture_workspace_session
+                    .before(handle_save_file)
+                    .before(handle_save_file_as),
+                restore_workspace_session.after(handle_open_file),
+            ),
+        );
+    }
+}
+
+/// A text buffer as it is saved
+fn session_buffer<'a>(
+    buffer: &TextBuffer,
+    cursor: &BufferCursor,
+    sorts: impl IntoIterator<Item = &'a SortData>,
+) -> SessionBuffer {
+    SessionBuffer {
+        name: buffer.name.clone(),
+        direction: match buffer.layout_mode {
+            SortLayoutMode::LTRText => SessionDirection::Ltr,
+            SortLayoutMode::RTLText => SessionDirection::Rtl,
+            SortLayoutMode::Freeform => SessionDirection::Freeform,
+        },
+        position: (buffer.root_position.x as f64, buffer.root_position.y as f64),
+        frame_width: buffer.frame.as_ref().map(|frame| frame.width as f64),
+        alignment: match buffer.frame.as_ref().map(|frame| frame.alignment) {
+            Some(TextAlignment::Center) => SessionAlignment::Center,
+            Some(TextAlignment::Right) => SessionAlignment::Right,
+            _ => SessionAlignment::Left,
+        },
+        cursor: cursor.position,
+        sorts: sorts
+            .into_iter()
+            .map(|sort| {
+                let (glyph, codepoint, advance) = match &sort.kind {
+                    SortKind::Glyph {
+                        glyph_name,
+                        codepoint,
+                        advance_width,
+                    } => (Some(glyph_name.clone()), *codepoint, *advance_width as f64),
+                    SortKind::LineBreak => (None, None, 0.0),
+                };
+                SessionSort {
+                    glyph,
+                    codepoint,
+                    advance,
+                    active: sort.is_active,
+                    offset: (sort.placement_offset != Vec2::ZERO).then_some((
+                        sort.placement_offset.x as f64,
+                        sort.placement_offset.y as f64,
+                    )),
+                }
+            })
+            .collect(),
+    }
+}
+
+/// A saved text buffer and its sorts, as buffer `id`
+fn restored_buffer(saved: &SessionBuffer, id: BufferId) -> (TextBuffer, Vec<SortData>) {
+    let layout_mode = match saved.direction {
+        SessionDirection::Ltr => SortLayoutMode::LTRText,
+        SessionDirection::Rtl => SortLayoutMode::RTLText,
+        SessionDirection::Freeform => SortLayoutMode::Freeform,
+    };
+    let root_position = Vec2::new(saved.position.0 as f32, saved.position.1 as f32);
+    let mut buffer = TextBuffer::new(id, layout_mode.clone(), root_position);
+    buffer.name = saved.name.clone();
+    buffer.frame = saved.frame_width.map(|width| TextFrame {
+        width: (width as f32).max(TextFrame::MIN_WIDTH),
+        alignment: match saved.alignment {
+            SessionAlignment::Left => TextAlignment::Left,
+            SessionAlignment::Center => TextAlignment::Center,
+            SessionAlignment::Right => TextAlignment::Right,
+        },
+    });
+
+    let sorts = saved
+        .sorts
+        .iter()
+        .map(|sort| SortData {
+            kind: match &sort.glyph {
+                Some(glyph_name) => SortKind::Glyph {
+                    codepoint: sort.codepoint,
+                    glyph_name: glyph_name.clone(),
+                    advance_width: sort.advance as f32,
+                },
+                None => SortKind::LineBreak,
+            },
+            is_active: sort.active,
+            layout_mode: layout_mode.clone(),
+            root_position,
+            placement_offset: sort
+                .offset
+                .map_or(Vec2::ZERO, |(x, y)| Vec2::new(x as f32, y as f32)),
+            buffer_cursor_position: None,
+            buffer_id: Some(id),
+        })
+        .collect();
+    (buffer, sorts)
+}
+
+/// Records the session into the font before it is saved
+fn capture_workspace_session(
+    mut saves: EventReader<SaveFileEvent>,
+    mut saves_as: EventReader<SaveFileAsEvent>,
+    mut app_state: Option<ResMut<AppState>>,
+    text_editor_state: Res<TextEditorState>,
+    buffer_query: Query<(Entity, &TextBuffer, &BufferCursor)>,
+    active_buffer: Res<ActiveTextBuffer>,
+    camera_query: Query<(&Transform, &Projection), With<DesignCamera>>,
+    glyph_navigation: Res<GlyphNavigation>,
+    current_tool: Res<CurrentTool>,
+) {
+    let saving = saves.read().count() + saves_as.read().count() > 0;
+    let Some(state) = app_state.as_mut().filter(|_| saving) else {
+        return;
+    };
+
+    // Buffers go in the order of their sorts, then the empty ones
+    let mut sorts_by_buffer: HashMap<BufferId, Vec<&SortData>> = HashMap::new();
+    let mut order = Vec::new();
+    for sort in text_editor_state.buffer.iter() {
+        let Some(id) = sort.buffer_id else {
+            continue;
+        };
+        sorts_by_buffer
+            .entry(id)
+            .or_insert_with(|| {
+                order.push(id);
+                Vec::new()
+            })
+            .push(sort);
+    }
+    let mut buffers: Vec<_> = buffer_query.iter().collect();
+    buffers.sort_by_key(|(_, buffer, _)| {
+        let position = order.iter().position(|id| *id == buffer.id);
+        (position.unwrap_or(usize::MAX), buffer.id.0)
+    });
+
+    let mut session = WorkspaceSession::default();
+    for (entity, buffer, cursor) in buffers {
+        if active_buffer.buffer_entity == Some(entity) {
+            session.active_buffer = Some(session.buffers.len());
+        }
+        let sorts = sorts_by_buffer.remove(&buffer.id).unwrap_or_default();
+        session.buffers.push(session_buffer(buffer, cursor, sorts));
+    }
+    session.camera = camera_query.single().ok().map(|(transform, projection)| {
+        let scale = match projection {
+            Projection::Orthographic(orthographic) => orthographic.scale,
+            _ => 1.0,
+        };
+        SessionCamera {
+            x: transform.translation.x as f64,
+            y: transform.translation.y as f64,
+            scale: scale as f64,
+        }
+    });
+    session.active_glyph = glyph_navigation.current_glyph.clone();
+    session.tool = current_tool.get_current().map(str::to_string);
+
+    debug!(
+        "Recorded the session: {} text buffers, tool {:?}",
+        session.buffers.len(),
+        session.tool
+    );
+    state.workspace.font.session = Some(session);
+}
+
+/// Puts the saved session back when a font is opened
+fn restore_workspace_session(
+    mut commands: Commands,
+    mut opened: EventReader<OpenFileEvent>,
+    app_state: Option<Res<AppState>>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    buffer_query: Query<Entity, With<TextBuffer>>,
+    mut active_buffer: ResMut<ActiveTextBuffer>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<DesignCamera>>,
+    mut glyph_navigation: ResMut<GlyphNavigation>,
+    mut current_tool: ResMut<CurrentTool>,
+    tool_registry: Res<ToolRegistry>,
+) {
+    // Fonts opened at startup arrive as a new app state
+    let opened_now = opened.read().count() > 0;
+    let Some(state) = app_state.filter(|state| state.is_added() || opened_now) else {
+        return;
+    };
+    let Some(session) = state.workspace.font.session.as_ref() else {
+        return;
+    };
+
+    // The session's buffers replace the ones on screen
+    for entity in buffer_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    text_editor_state.buffer.clear();
+    active_buffer.buffer_entity = None;
+    for (index, saved) in session.buffers.iter().enumerate() {
+        let (buffer, sorts) = restored_buffer(saved, BufferId::new());
+        let entity = create_text_buffer(
+            &mut commands,
+            buffer.id,
+            buffer.layout_mode.clone(),
+            buffer.root_position,
+            saved.cursor.min(sorts.len()),
+        );
+        for sort in sorts {
+            let end = text_editor_state.buffer.len();
+            text_editor_state.buffer.insert(end, sort);
+        }
+        commands.entity(entity).insert(buffer);
+        if session.active_buffer == Some(index) {
+            active_buffer.buffer_entity = Some(entity);
+        }
+    }
+    queue_respawn(&text_editor_state, &mut respawn_queue, 0);
+
+    // Keep the startup layout from moving the camera afterwards
+    commands.remove_resource::<CenterCameraOnDefaultSort>();
+    if let (Some(camera), Ok((mut transform, mut projection))) =
+        (session.camera, camera_query.single_mut())
+    {
+        transform.translation.x = camera.x as f32;
+        transform.translation.y = camera.y as f32;
+        if let Projection::Orthographic(orthographic) = projection.as_mut() {
+            orthographic.scale = camera.scale as f32;
+        }
+    }
+    if session.active_glyph.is_some() {
+        glyph_navigation.current_glyph = session.active_glyph.clone();
+    }
+    let tool = tool_registry
+        .get_all_tool_ids()
+        .into_iter()
+        .find(|id| session.tool.as_deref() == Some(*id));
+    if let Some(tool) = tool {
+        current_tool.switch_to(tool);
+    }
+
+    info!(
+        "Restored the session: {} text buffers",
+        session.buffers.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_round_trip() {
+        let id = BufferId(7);
+        let mut buffer = TextBuffer::new(id, SortLayoutMode::RTLText, Vec2::new(100.0, -50.0));
+        buffer.name = "Arabic proof".to_string();
+        buffer.frame = Some(TextFrame::new(2000.0, &SortLayoutMode::RTLText));
+        let cursor = BufferCursor::new(1);
+        let (_, sorts) = restored_buffer(
+            &SessionBuffer {
+                direction: SessionDirection::Rtl,
+                position: (100.0, -50.0),
+                sorts: vec![
+                    SessionSort {
+                        glyph: Some("beh-ar".to_string()),
+                        codepoint: Some('\u{628}'),
+                        advance: 600.0,
+                        active: true,
+                        offset: None,
+                    },
+                    SessionSort::default(),
+                ],
+                ..default()
+            },
+            id,
+        );
+
+        let saved = session_buffer(&buffer, &cursor, &sorts);
+        assert_eq!(saved.name, "Arabic proof");
+        assert_eq!(saved.alignment, SessionAlignment::Right);
+        assert_eq!(saved.cursor, 1);
+        let (restored, restored_sorts) = restored_buffer(&saved, id);
+        assert_eq!(restored.name, buffer.name);
+        assert_eq!(restored.frame, buffer.frame);
+        assert_eq!(restored.root_position, buffer.root_position);
+        assert_eq!(restored_sorts.len(), 2);
+        assert!(restored_sorts[0].is_active);
+        assert_eq!(restored_sorts[1].kind, SortKind::LineBreak);
+        assert_eq!(session_buffer(&restored, &cursor, &restored_sorts), saved);
+    }
+}
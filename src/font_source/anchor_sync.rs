@@ -0,0 +1,332 @@
+//! Composites that follow their components' anchors
+//!
+//! Marks in composites sit on an anchor of a component before them: the
+//! `_top` anchor of `acutecomb` on the `top` anchor of `A` in `Aacute`.
+//! When a glyph's anchors move, the components attached by them in every
+//! composite using the glyph move along, with the marks stacked on those,
+//! and so do the composite's own anchors that came from them, which passes
+//! the move on to composites built from the composite. Components that
+//! don't sit on an anchor stay where they are, and so do all components of
+//! a glyph that opts out with a flag in its glyph lib.
+
+use std::collections::BTreeSet;
+
+use super::data::{AnchorData, FontData, GlyphData};
+
+/// Glyph lib key set on glyphs whose components don't follow anchors
+pub const KEEP_COMPONENTS_LIB_KEY: &str = "org.bezy.keepComponentPositions";
+
+/// How far apart two anchors can be, in font units, and still be attached
+const ATTACHED_TOLERANCE: f64 = 0.5;
+
+/// Glyphs that opted out of following anchors
+#[derive(Clone, Debug, Default)]
+pub struct AnchorSync {
+    pub opted_out: BTreeSet<String>,
+}
+
+impl AnchorSync {
+    /// Read the opted out glyphs from the glyph libs of a UFO layer
+    pub fn from_norad_layer(layer: &norad::Layer) -> Self {
+        let opted_out = layer
+            .iter()
+            .filter(|glyph| {
+                glyph
+                    .lib
+                    .get(KEEP_COMPONENTS_LIB_KEY)
+                    .and_then(|value| value.as_boolean())
+                    == Some(true)
+            })
+            .map(|glyph| glyph.name().to_string())
+            .collect();
+        Self { opted_out }
+    }
+
+    /// Flag a glyph in its glyph lib if it opted out
+    pub fn write_to_norad_glyph(&self, glyph: &mut norad::Glyph) {
+        glyph.lib.remove(KEEP_COMPONENTS_LIB_KEY);
+        if self.opted_out.contains(glyph.name().as_str()) {
+            glyph
+                .lib
+                .insert(KEEP_COMPONENTS_LIB_KEY.to_string(), true.into());
+        }
+    }
+
+    /// Whether a glyph's components follow the anchors they sit on
+    pub fn follows(&self, glyph_name: &str) -> bool {
+        !self.opted_out.contains(glyph_name)
+    }
+
+    /// Opt a glyph out or back in, returning whether it follows now
+    pub fn toggle(&mut self, glyph_name: &str) -> bool {
+        if !self.opted_out.remove(glyph_name) {
+            self.opted_out.insert(glyph_name.to_string());
+        }
+        self.follows(glyph_name)
+    }
+}
+
+/// A point through a component transform
+fn transformed(transform: &[f64; 6], anchor: &AnchorData) -> (f64, f64) {
+    (
+        transform[0] * anchor.x + transform[2] * anchor.y + transform[4],
+        transform[1] * anchor.x + transform[3] * anchor.y + transform[5],
+    )
+}
+
+fn attached(a: (f64, f64), b: (f64, f64)) -> bool {
+    (a.0 - b.0).hypot(a.1 - b.1) <= ATTACHED_TOLERANCE
+}
+
+impl FontData {
+    /// Moves what sits on `glyph_name`'s anchors in the composites using
+    /// it, given the anchors it had before, and returns the composites
+    /// that changed
+    pub fn follow_anchors(&mut self, glyph_name: &str, previous: &[AnchorData]) -> Vec<String> {
+        let mut adjusted = BTreeSet::new();
+        let mut pending = vec![(glyph_name.to_string(), previous.to_vec())];
+        while let Some((moved, previous)) = pending.pop() {
+            let mut users: Vec<String> = self
+                .glyphs
+                .values()
+                .filter(|glyph| glyph.components.iter().any(|c| c.base_glyph == moved))
+                .map(|glyph| glyph.name.clone())
+                .filter(|name| self.anchor_sync.follows(name) && *name != moved)
+                .collect();
+            users.sort();
+
+            for user in users {
+                let Some(composite) = self.glyphs.get(&user) else {
+                    continue;
+                };
+                let (offsets, anchors) = self.followed_anchors(composite, &moved, &previous);
+                let unmoved = offsets.iter().all(|&offset| offset == (0.0, 0.0));
+                if unmoved && anchors == composite.anchors {
+                    continue;
+                }
+                let Some(composite) = self.glyphs.get_mut(&user) else {
+                    continue;
+                };
+                for (component, (dx, dy)) in composite.components.iter_mut().zip(offsets) {
+                    component.transform[4] += dx;
+                    component.transform[5] += dy;
+                }
+                let own_anchors = std::mem::replace(&mut composite.anchors, anchors);
+                if own_anchors != composite.anchors && !adjusted.contains(&user) {
+                    pending.push((user.clone(), own_anchors));
+                }
+                adjusted.insert(user);
+            }
+        }
+        adjusted.remove(glyph_name);
+        adjusted.into_iter().collect()
+    }
+
+    /// How far each component of `composite` moves when `moved`'s anchors
+    /// change from `previous`, and where the composite's anchors end up
+    fn followed_anchors(
+        &self,
+        composite: &GlyphData,
+        moved: &str,
+        previous: &[AnchorData],
+    ) -> (Vec<(f64, f64)>, Vec<AnchorData>) {
+        let anchors_of = |glyph_name: &str, before: bool| -> &[AnchorData] {
+            if before && glyph_name == moved {
+                return previous;
+            }
+            self.glyphs
+                .get(glyph_name)
+                .map_or(&[], |glyph| glyph.anchors.as_slice())
+        };
+        // How far an anchor of a component moves with its glyph's anchors,
+        // or `None` if it's gone
+        let anchor_move = |index: usize, name: &str| {
+            let component = &composite.components[index];
+            let find = |before| {
+                anchors_of(&component.base_glyph, before)
+                    .iter()
+                    .find(|anchor| anchor.name == name)
+            };
+            let (before, after) = (find(true)?, find(false)?);
+            let (x0, y0) = transformed(&component.transform, before);
+            let (x1, y1) = transformed(&component.transform, after);
+            Some((x1 - x0, y1 - y0))
+        };
+
+        let mut offsets = vec![(0.0, 0.0); composite.components.len()];
+        for (index, component) in composite.components.iter().enumerate() {
+            // The first of the component's `_` anchors that sits on an
+            // anchor of a component before it, the nearest one first
+            let attachment = anchors_of(&component.base_glyph, true)
+                .iter()
+                .filter_map(|anchor| Some((anchor.name.strip_prefix('_')?, anchor)))
+                .find_map(|(name, mark_anchor)| {
+                    let at = transformed(&component.transform, mark_anchor);
+                    (0..index).rev().find_map(|earlier| {
+                        let base = &composite.components[earlier];
+                        let anchor = anchors_of(&base.base_glyph, true)
+                            .iter()
+                            .find(|anchor| anchor.name == name)?;
+                        attached(transformed(&base.transform, anchor), at)
+                            .then_some((earlier, name))
+                    })
+                });
+            let Some((earlier, name)) = attachment else {
+                continue;
+            };
+            let (Some(target), Some(own)) = (
+                anchor_move(earlier, name),
+                anchor_move(index, &format!("_{name}")),
+            ) else {
+                continue;
+            };
+            let (dx, dy) = offsets[earlier];
+            offsets[index] = (target.0 + dx - own.0, target.1 + dy - own.1);
+        }
+
+        // The composite's anchors that came from a component, the last one
+        // with the anchor first, as marks stack
+        let anchors = composite
+            .anchors
+            .iter()
+            .map(|anchor| {
+                let at = (anchor.x, anchor.y);
+                let source = (0..composite.components.len()).rev().find_map(|index| {
+                    let component = &composite.components[index];
+                    let before = anchors_of(&component.base_glyph, true)
+                        .iter()
+                        .find(|other| other.name == anchor.name)?;
+                    attached(transformed(&component.transform, before), at).then_some(index)
+                });
+                let (dx, dy) = source
+                    .and_then(|index| {
+                        let (dx, dy) = anchor_move(index, &anchor.name)?;
+                        Some((dx + offsets[index].0, dy + offsets[index].1))
+                    })
+                    .unwrap_or_default();
+                AnchorData {
+                    name: anchor.name.clone(),
+                    x: anchor.x + dx,
+                    y: anchor.y + dy,
+                }
+            })
+            .collect();
+        (offsets, anchors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::ComponentData;
+
+    fn anchor(name: &str, x: f64, y: f64) -> AnchorData {
+        AnchorData {
+            name: name.to_string(),
+            x,
+            y,
+        }
+    }
+
+    fn glyph(name: &str, components: &[(&str, f64, f64)], anchors: &[AnchorData]) -> GlyphData {
+        GlyphData {
+            components: components
+                .iter()
+                .map(|&(base, dx, dy)| ComponentData {
+                    base_glyph: base.to_string(),
+                    transform: [1.0, 0.0, 0.0, 1.0, dx, dy],
+                })
+                .collect(),
+            anchors: anchors.to_vec(),
+            ..GlyphData::test(name, 500.0)
+        }
+    }
+
+    fn offsets(font: &FontData, name: &str) -> Vec<(f64, f64)> {
+        font.glyphs[name]
+            .components
+            .iter()
+            .map(|component| (component.transform[4], component.transform[5]))
+            .collect()
+    }
+
+    /// `A` with its accents: `acutecomb` on its `top`, `gravecomb` stacked
+    /// on the acute, a dot built on `Aacute`'s `top` and a ring set by eye
+    fn font() -> FontData {
+        let mut font = FontData::default();
+        for glyph in [
+            glyph("A", &[], &[anchor("top", 250.0, 700.0)]),
+            glyph(
+                "acutecomb",
+                &[],
+                &[anchor("_top", 100.0, 500.0), anchor("top", 100.0, 800.0)],
+            ),
+            glyph("gravecomb", &[], &[anchor("_top", 50.0, 0.0)]),
+            glyph("dotcomb", &[], &[anchor("_top", 0.0, 0.0)]),
+            glyph("ringcomb", &[], &[anchor("_top", 0.0, 0.0)]),
+            glyph(
+                "Aacute",
+                &[("A", 0.0, 0.0), ("acutecomb", 150.0, 200.0)],
+                &[anchor("top", 250.0, 1000.0)],
+            ),
+            glyph(
+                "Aacutegrave",
+                &[
+                    ("A", 0.0, 0.0),
+                    ("acutecomb", 150.0, 200.0),
+                    ("gravecomb", 200.0, 1000.0),
+                ],
+                &[],
+            ),
+            glyph(
+                "Aacutedot",
+                &[("Aacute", 0.0, 0.0), ("dotcomb", 250.0, 1000.0)],
+                &[],
+            ),
+            glyph("Aring", &[("A", 0.0, 0.0), ("ringcomb", 230.0, 700.0)], &[]),
+        ] {
+            font.glyphs.insert(glyph.name.clone(), glyph);
+        }
+        font
+    }
+
+    #[test]
+    fn test_marks_follow_base_anchors() {
+        let mut font = font();
+        font.anchor_sync.toggle("Aacutegrave");
+        assert!(!font.anchor_sync.follows("Aacutegrave"));
+
+        let previous = font.glyphs["A"].anchors.clone();
+        font.glyphs.get_mut("A").unwrap().anchors[0] = anchor("top", 300.0, 720.0);
+        let adjusted = font.follow_anchors("A", &previous);
+
+        assert_eq!(adjusted, ["Aacute", "Aacutedot"]);
+        assert_eq!(offsets(&font, "Aacute"), [(0.0, 0.0), (200.0, 220.0)]);
+        assert_eq!(
+            font.glyphs["Aacute"].anchors,
+            [anchor("top", 300.0, 1020.0)]
+        );
+        assert_eq!(offsets(&font, "Aacutedot"), [(0.0, 0.0), (300.0, 1020.0)]);
+        // Opted out, and not sitting on the anchor
+        assert_eq!(offsets(&font, "Aacutegrave")[1], (150.0, 200.0));
+        assert_eq!(offsets(&font, "Aring")[1], (230.0, 700.0));
+    }
+
+    #[test]
+    fn test_marks_follow_their_own_anchors() {
+        let mut font = font();
+        let previous = font.glyphs["acutecomb"].anchors.clone();
+        font.glyphs.get_mut("acutecomb").unwrap().anchors[0] = anchor("_top", 110.0, 500.0);
+        let adjusted = font.follow_anchors("acutecomb", &previous);
+
+        assert_eq!(adjusted, ["Aacute", "Aacutedot", "Aacutegrave"]);
+        assert_eq!(offsets(&font, "Aacute")[1], (140.0, 200.0));
+        // Stacked marks and anchors from the moved mark move with it
+        assert_eq!(offsets(&font, "Aacutegrave")[2], (190.0, 1000.0));
+        assert_eq!(
+            font.glyphs["Aacute"].anchors,
+            [anchor("top", 240.0, 1000.0)]
+        );
+        assert_eq!(offsets(&font, "Aacutedot")[1], (240.0, 1000.0));
+    }
+}
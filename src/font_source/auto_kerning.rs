@@ -0,0 +1,253 @@
+//! Kerning suggestions from outline gaps and collisions
+//!
+//! Each glyph's outline is cut into horizontal slices from below the
+//! descender to above the ascender. Set side by side at their advance, two
+//! glyphs leave a gap on every slice: the white right of the first glyph
+//! plus the white left of the second, each counted only down to a fixed
+//! depth so open shapes don't read as bottomless. A pair is kerned so that
+//! its average gap matches a reference pair's (by default "nn"), and never
+//! so tight that the outlines come closer than a minimum clearance on the
+//! same height. Glyph pairs are gathered by kerning class, so one
+//! suggestion covers every glyph of a group.
+
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+
+use super::auto_spacing::margin_profile;
+use super::data::FontData;
+use super::metrics::FontMetrics;
+
+/// How far past the descender and ascender outlines are sampled, in
+/// units per em
+const ZONE_MARGIN_EM: f64 = 0.2;
+
+/// How pairs are measured and which suggestions are worth showing
+#[derive(Clone, Debug, PartialEq)]
+pub struct KerningParameters {
+    /// Pair whose average gap every other pair is kerned towards
+    pub reference: (String, String),
+    /// How far into open shapes white is counted, in percent of the em
+    pub depth: f64,
+    /// Closest two outlines may come, for a 1000 unit em
+    pub clearance: f64,
+    /// Smallest change worth suggesting, for a 1000 unit em
+    pub threshold: f64,
+    /// Suggested values are rounded to multiples of this
+    pub rounding: f64,
+}
+
+impl Default for KerningParameters {
+    fn default() -> Self {
+        Self {
+            reference: ("n".to_string(), "n".to_string()),
+            depth: 8.0,
+            clearance: 20.0,
+            threshold: 10.0,
+            rounding: 5.0,
+        }
+    }
+}
+
+/// A proposed kerning value for one pair of sides
+#[derive(Clone, Debug, PartialEq)]
+pub struct KernSuggestion {
+    /// First side, a `public.kern1.` group or a glyph
+    pub first: String,
+    /// Second side, a `public.kern2.` group or a glyph
+    pub second: String,
+    /// Glyph pairs measured for these sides
+    pub pairs: Vec<(String, String)>,
+    /// Value currently stored for these sides
+    pub current: f64,
+    pub suggested: f64,
+    /// Whether some measured pair overlaps or comes closer than the
+    /// clearance with the kerning it has now
+    pub collides: bool,
+}
+
+/// A glyph's outline edges on each slice of the sampled zone
+#[derive(Clone, Debug)]
+struct GlyphProfile {
+    advance: f64,
+    edges: Vec<Option<(f64, f64)>>,
+    left_sidebearing: f64,
+    right_sidebearing: f64,
+}
+
+impl GlyphProfile {
+    fn new(font: &FontData, glyph_name: &str, min_y: f64, max_y: f64) -> Option<Self> {
+        let glyph = font.get_glyph(glyph_name)?;
+        let edges = margin_profile(&font.resolved_bezpaths(glyph_name), min_y, max_y);
+        let left = edges.iter().flatten().map(|edge| edge.0).reduce(f64::min)?;
+        let right = edges.iter().flatten().map(|edge| edge.1).reduce(f64::max)?;
+        Some(Self {
+            advance: glyph.advance_width,
+            edges,
+            left_sidebearing: left,
+            right_sidebearing: glyph.advance_width - right,
+        })
+    }
+}
+
+/// Average gap of two glyphs set without kerning, with white counted down
+/// to `depth`, and the closest their outlines come on the same slice
+fn pair_gaps(first: &GlyphProfile, second: &GlyphProfile, depth: f64) -> Option<(f64, f64)> {
+    let first_limit = first.right_sidebearing + depth;
+    let second_limit = second.left_sidebearing + depth;
+    let mut total = 0.0;
+    let mut count = 0;
+    let mut closest = f64::MAX;
+    for (a, b) in first.edges.iter().zip(&second.edges) {
+        if a.is_none() && b.is_none() {
+            continue;
+        }
+        let right_white = a.map_or(first_limit, |(_, right)| {
+            (first.advance - right).min(first_limit)
+        });
+        let left_white = b.map_or(second_limit, |(left, _)| left.min(second_limit));
+        total += right_white + left_white;
+        count += 1;
+        if let (Some((_, right)), Some((left, _))) = (a, b) {
+            closest = closest.min(first.advance - right + left);
+        }
+    }
+    (count > 0).then(|| (total / count as f64, closest))
+}
+
+/// Suggest kerning for every ordered pair of the named glyphs
+///
+/// Suggestions that collide come first, then the largest changes.
+pub fn suggest(
+    font: &FontData,
+    metrics: &FontMetrics,
+    parameters: &KerningParameters,
+    glyph_names: &[String],
+) -> Result<Vec<KernSuggestion>> {
+    let upm = metrics.units_per_em;
+    let scale = upm / 1000.0;
+    let min_y = metrics.descender.unwrap_or(-0.2 * upm) - ZONE_MARGIN_EM * upm;
+    let max_y = metrics.ascender.unwrap_or(0.8 * upm) + ZONE_MARGIN_EM * upm;
+    let depth = upm * parameters.depth / 100.0;
+    let clearance = parameters.clearance * scale;
+    let rounding = parameters.rounding.max(1.0);
+
+    let (reference_first, reference_second) = &parameters.reference;
+    let reference_gap = GlyphProfile::new(font, reference_first, min_y, max_y)
+        .zip(GlyphProfile::new(font, reference_second, min_y, max_y))
+        .and_then(|(first, second)| pair_gaps(&first, &second, depth))
+        .map(|(gap, _)| gap)
+        .ok_or_else(|| {
+            anyhow!("Reference pair '{reference_first}' '{reference_second}' has no outlines")
+        })?;
+
+    let profiles: Vec<(&String, GlyphProfile)> = glyph_names
+        .iter()
+        .filter_map(|name| Some((name, GlyphProfile::new(font, name, min_y, max_y)?)))
+        .collect();
+
+    // Optical values and the least kerning that clears collisions, per class
+    struct ClassMeasure {
+        pairs: Vec<(String, String)>,
+        optical_total: f64,
+        minimum: f64,
+        collides: bool,
+    }
+    let mut classes: BTreeMap<(String, String), ClassMeasure> = BTreeMap::new();
+    for (first_name, first) in &profiles {
+        for (second_name, second) in &profiles {
+            let Some((gap, closest)) = pair_gaps(first, second, depth) else {
+                continue;
+            };
+            let current = font.kerning.pair_value(first_name, second_name);
+            let measure = classes
+                .entry(font.kerning.class_pair(first_name, second_name))
+                .or_insert(ClassMeasure {
+                    pairs: Vec::new(),
+                    optical_total: 0.0,
+                    minimum: f64::MIN,
+                    collides: false,
+                });
+            measure
+                .pairs
+                .push(((*first_name).clone(), (*second_name).clone()));
+            measure.optical_total += reference_gap - gap;
+            if closest < f64::MAX {
+                measure.minimum = measure.minimum.max(clearance - closest);
+                measure.collides |= closest + current < clearance;
+            }
+        }
+    }
+
+    let mut suggestions: Vec<KernSuggestion> = classes
+        .into_iter()
+        .filter_map(|((first, second), measure)| {
+            let optical = measure.optical_total / measure.pairs.len() as f64;
+            let suggested = (optical.max(measure.minimum) / rounding).round() * rounding;
+            let current = font.kerning.value(&first, &second).unwrap_or(0.0);
+            let worth_it = (suggested - current).abs() >= parameters.threshold * scale;
+            (worth_it || measure.collides).then_some(KernSuggestion {
+                first,
+                second,
+                pairs: measure.pairs,
+                current,
+                suggested,
+                collides: measure.collides,
+            })
+        })
+        .collect();
+    suggestions.sort_by(|a, b| {
+        b.collides.cmp(&a.collides).then(
+            (b.suggested - b.current)
+                .abs()
+                .total_cmp(&(a.suggested - a.current).abs()),
+        )
+    });
+    Ok(suggestions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kurbo::{Rect, Shape};
+
+    fn profile(rects: &[Rect], advance: f64) -> GlyphProfile {
+        let paths: Vec<_> = rects.iter().map(|rect| rect.to_path(0.1)).collect();
+        let edges = margin_profile(&paths, 0.0, 700.0);
+        let left = edges.iter().flatten().map(|edge| edge.0).reduce(f64::min);
+        let right = edges.iter().flatten().map(|edge| edge.1).reduce(f64::max);
+        GlyphProfile {
+            advance,
+            edges,
+            left_sidebearing: left.unwrap(),
+            right_sidebearing: advance - right.unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_stems_have_their_sidebearings_as_gap() {
+        let stem = profile(&[Rect::new(50.0, 0.0, 150.0, 500.0)], 200.0);
+        assert_eq!(pair_gaps(&stem, &stem, 80.0), Some((100.0, 100.0)));
+    }
+
+    #[test]
+    fn test_overhangs_open_the_gap_and_bring_outlines_closer() {
+        let stem = profile(&[Rect::new(50.0, 0.0, 150.0, 500.0)], 200.0);
+        // A "T" whose bar reaches past its advance on the right
+        let tee = profile(
+            &[
+                Rect::new(100.0, 0.0, 200.0, 600.0),
+                Rect::new(0.0, 600.0, 320.0, 700.0),
+            ],
+            300.0,
+        );
+        let (gap, closest) = pair_gaps(&tee, &stem, 80.0).unwrap();
+        assert!(
+            gap > 100.0,
+            "open space under the bar widens the gap, got {gap}"
+        );
+        assert_eq!(closest, 150.0);
+
+        let (_, closest) = pair_gaps(&stem, &tee, 80.0).unwrap();
+        assert_eq!(closest, 150.0);
+    }
+}
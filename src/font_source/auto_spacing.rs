@@ -0,0 +1,287 @@
+//! Sidebearing suggestions from glyph shapes
+//!
+//! The method follows HT Letterspacer: each side of a glyph is cut into
+//! horizontal slices across a reference zone (the x-height for lowercase,
+//! the cap height for capitals and figures), and the white between the
+//! outline and its outermost point is measured, counting open counters only
+//! down to a fixed depth and closing them at 45°. The suggested sidebearing
+//! is whatever brings that white up to a target area, so round and open
+//! shapes get tighter sidebearings than straight ones.
+
+use std::collections::HashMap;
+
+use kurbo::{BezPath, PathEl, Point};
+
+use super::data::FontData;
+use super::metrics::FontMetrics;
+
+/// Height of the horizontal slices, in font units
+const SLICE_HEIGHT: f64 = 5.0;
+
+/// Flattening tolerance for curves, in font units
+const FLATTEN_TOLERANCE: f64 = 0.5;
+
+/// How much white a script's glyphs get
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpacingParameters {
+    /// Target white area per side, scaled to a 1000 unit em
+    pub area: f64,
+    /// How far into open counters white is counted, in percent of the
+    /// reference height
+    pub depth: f64,
+    /// How far the zone reaches past the baseline and the reference height,
+    /// in percent of the reference height
+    pub overshoot: f64,
+}
+
+impl Default for SpacingParameters {
+    fn default() -> Self {
+        Self {
+            area: 400.0,
+            depth: 15.0,
+            overshoot: 0.0,
+        }
+    }
+}
+
+/// Spacing parameters, with overrides per script (e.g. "Latin", "Arabic")
+#[derive(Clone, Debug, Default)]
+pub struct AutoSpacingConfig {
+    pub default: SpacingParameters,
+    pub scripts: HashMap<String, SpacingParameters>,
+}
+
+impl AutoSpacingConfig {
+    pub fn parameters(&self, script: &str) -> SpacingParameters {
+        self.scripts.get(script).copied().unwrap_or(self.default)
+    }
+}
+
+/// Suggested sidebearings for a glyph next to its current ones
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpacingSuggestion {
+    pub glyph_name: String,
+    /// Current left and right sidebearings
+    pub current: (f64, f64),
+    /// Suggested left and right sidebearings
+    pub suggested: (f64, f64),
+}
+
+impl SpacingSuggestion {
+    /// Whether accepting the suggestion would change the glyph
+    pub fn is_change(&self) -> bool {
+        self.current.0.round() != self.suggested.0 || self.current.1.round() != self.suggested.1
+    }
+}
+
+/// Script of a letter, by Unicode block; "Common" for anything else
+pub fn script_of(c: char) -> &'static str {
+    if !c.is_alphabetic() {
+        return "Common";
+    }
+    match c as u32 {
+        0x0041..=0x024F | 0x1E00..=0x1EFF | 0xFB00..=0xFB06 => "Latin",
+        0x0370..=0x03FF | 0x1F00..=0x1FFF => "Greek",
+        0x0400..=0x052F => "Cyrillic",
+        0x0590..=0x05FF | 0xFB1D..=0xFB4F => "Hebrew",
+        0x0600..=0x06FF | 0x0750..=0x077F | 0xFB50..=0xFDFF | 0xFE70..=0xFEFF => "Arabic",
+        0x0900..=0x097F => "Devanagari",
+        0x0E00..=0x0E7F => "Thai",
+        _ => "Common",
+    }
+}
+
+/// The outlines flattened into straight segments, each contour closed
+pub fn outline_segments(paths: &[BezPath]) -> Vec<(Point, Point)> {
+    let mut segments: Vec<(Point, Point)> = Vec::new();
+    for path in paths {
+        let mut start = None;
+        let mut last = None;
+        kurbo::flatten(path.iter(), FLATTEN_TOLERANCE, |element| match element {
+            PathEl::MoveTo(point) => {
+                if let (Some(start), Some(last)) = (start, last) {
+                    segments.push((last, start));
+                }
+                start = Some(point);
+                last = Some(point);
+            }
+            PathEl::LineTo(point) => {
+                if let Some(last) = last {
+                    segments.push((last, point));
+                }
+                last = Some(point);
+            }
+            PathEl::ClosePath => {
+                if let (Some(start), Some(last)) = (start, last) {
+                    segments.push((last, start));
+                }
+                last = start;
+            }
+            _ => {}
+        });
+        // Contours are closed even without an explicit close
+        if let (Some(start), Some(last)) = (start, last) {
+            if start != last {
+                segments.push((last, start));
+            }
+        }
+    }
+
+    segments
+}
+
+/// Leftmost and rightmost outline x on each slice from `min_y` to `max_y`,
+/// `None` where a slice misses the outline
+pub fn margin_profile(paths: &[BezPath], min_y: f64, max_y: f64) -> Vec<Option<(f64, f64)>> {
+    let segments = outline_segments(paths);
+    let slices = ((max_y - min_y) / SLICE_HEIGHT).ceil().max(0.0) as usize;
+    (0..slices)
+        .map(|slice| {
+            let y = min_y + (slice as f64 + 0.5) * SLICE_HEIGHT;
+            segments
+                .iter()
+                .filter(|(a, b)| (a.y <= y && y < b.y) || (b.y <= y && y < a.y))
+                .map(|(a, b)| a.x + (y - a.y) * (b.x - a.x) / (b.y - a.y))
+                .fold(None, |margins: Option<(f64, f64)>, x| match margins {
+                    Some((left, right)) => Some((left.min(x), right.max(x))),
+                    None => Some((x, x)),
+                })
+        })
+        .collect()
+}
+
+/// White area between one side's profile and its outermost point
+///
+/// `insets` are each slice's distance from the outermost point. Counters
+/// count only down to `depth`, and are closed at 45° so narrow openings
+/// don't count as deep white.
+fn white_area(insets: &[Option<f64>], depth: f64) -> f64 {
+    let mut insets: Vec<f64> = insets
+        .iter()
+        .map(|inset| inset.map_or(depth, |inset| inset.min(depth)))
+        .collect();
+    for i in 1..insets.len() {
+        insets[i] = insets[i].min(insets[i - 1] + SLICE_HEIGHT);
+    }
+    for i in (0..insets.len().saturating_sub(1)).rev() {
+        insets[i] = insets[i].min(insets[i + 1] + SLICE_HEIGHT);
+    }
+    insets.iter().sum::<f64>() * SLICE_HEIGHT
+}
+
+/// Suggested left and right sidebearings for an outline, rounded to units,
+/// or `None` if nothing of it lies in the zone
+pub fn suggest_sidebearings(
+    paths: &[BezPath],
+    parameters: &SpacingParameters,
+    reference_height: f64,
+    x_height: f64,
+    units_per_em: f64,
+) -> Option<(f64, f64)> {
+    let overshoot = reference_height * parameters.overshoot / 100.0;
+    let (min_y, max_y) = (-overshoot, reference_height + overshoot);
+    let profile = margin_profile(paths, min_y, max_y);
+    let left = profile
+        .iter()
+        .flatten()
+        .map(|(left, _)| *left)
+        .reduce(f64::min)?;
+    let right = profile
+        .iter()
+        .flatten()
+        .map(|(_, right)| *right)
+        .reduce(f64::max)?;
+
+    let depth = reference_height * parameters.depth / 100.0;
+    let left_insets: Vec<Option<f64>> = profile.iter().map(|m| m.map(|m| m.0 - left)).collect();
+    let right_insets: Vec<Option<f64>> = profile.iter().map(|m| m.map(|m| right - m.1)).collect();
+    let zone_height = max_y - min_y;
+    let scale = units_per_em / 1000.0;
+    let target = parameters.area * scale * scale * 100.0 * zone_height / x_height;
+
+    let sidebearing = |white: f64| ((target - white) / zone_height).round();
+    Some((
+        sidebearing(white_area(&left_insets, depth)),
+        sidebearing(white_area(&right_insets, depth)),
+    ))
+}
+
+/// Suggestions for the named glyphs, skipping glyphs without outlines
+pub fn suggest(
+    font: &FontData,
+    metrics: &FontMetrics,
+    config: &AutoSpacingConfig,
+    glyph_names: &[String],
+) -> Vec<SpacingSuggestion> {
+    let upm = metrics.units_per_em;
+    let x_height = metrics.x_height.unwrap_or(upm * 0.5);
+    let cap_height = metrics.cap_height.unwrap_or(upm * 0.7);
+
+    glyph_names
+        .iter()
+        .filter_map(|name| {
+            let glyph = font.get_glyph(name)?;
+            let character = glyph.unicode_values.first().copied();
+            let parameters = config.parameters(character.map_or("Common", script_of));
+            let reference_height = match character {
+                Some(c) if c.is_uppercase() || c.is_numeric() => cap_height,
+                _ => x_height,
+            };
+            let paths = font.resolved_bezpaths(name);
+            Some(SpacingSuggestion {
+                glyph_name: name.clone(),
+                current: font.sidebearings(name)?,
+                suggested: suggest_sidebearings(
+                    &paths,
+                    &parameters,
+                    reference_height,
+                    x_height,
+                    upm,
+                )?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kurbo::{Rect, Shape};
+
+    #[test]
+    fn test_margin_profile_of_a_box() {
+        let stem = Rect::new(100.0, 0.0, 200.0, 500.0).to_path(0.1);
+        let profile = margin_profile(&[stem], -10.0, 510.0);
+        assert_eq!(profile.first(), Some(&None));
+        assert_eq!(profile[10], Some((100.0, 200.0)));
+        assert_eq!(profile.last(), Some(&None));
+    }
+
+    #[test]
+    fn test_open_shapes_get_less_space_than_straight_ones() {
+        let parameters = SpacingParameters::default();
+        let stem = Rect::new(100.0, 0.0, 200.0, 500.0).to_path(0.1);
+        let (left, right) = suggest_sidebearings(&[stem], &parameters, 500.0, 500.0, 1000.0)
+            .expect("stem is in the zone");
+        assert_eq!((left, right), (80.0, 80.0));
+
+        // The same stem with a bar at the top reaching out to the right
+        let bar = Rect::new(100.0, 450.0, 400.0, 500.0).to_path(0.1);
+        let stem = Rect::new(100.0, 0.0, 200.0, 500.0).to_path(0.1);
+        let (left, right) = suggest_sidebearings(&[stem, bar], &parameters, 500.0, 500.0, 1000.0)
+            .expect("shape is in the zone");
+        assert_eq!(left, 80.0);
+        assert!(
+            right < 80.0 - 50.0,
+            "open right side should be tighter, got {right}"
+        );
+    }
+
+    #[test]
+    fn test_script_of() {
+        assert_eq!(script_of('a'), "Latin");
+        assert_eq!(script_of('ж'), "Cyrillic");
+        assert_eq!(script_of('\u{0628}'), "Arabic");
+        assert_eq!(script_of('1'), "Common");
+    }
+}
@@ -0,0 +1,146 @@
+//! PostScript alignment zones
+//!
+//! The blue values in the font info pair up into alignment zones: the first
+//! pair is the baseline zone and the rest are top zones (x-height, cap
+//! height, ascender). The other blues are further bottom zones, like the
+//! descender's. A glyph's flat tops and bottoms should sit exactly on the
+//! flat edge of their zone, and its round ones at the overshoot edge; an
+//! extreme point anywhere in between hints inconsistently.
+
+use super::data::{GlyphData, PointTypeData};
+use super::metrics::FontInfo;
+
+/// How far an extreme may be from a zone edge and still count as on it, in
+/// font units
+const EDGE_TOLERANCE: f64 = 0.5;
+
+/// An alignment zone, from `bottom` to `top` in font units
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlueZone {
+    pub bottom: f64,
+    pub top: f64,
+}
+
+impl BlueZone {
+    /// Whether `y` is inside the zone but on neither edge
+    pub fn is_between_edges(&self, y: f64) -> bool {
+        y > self.bottom + EDGE_TOLERANCE && y < self.top - EDGE_TOLERANCE
+    }
+}
+
+/// The font's alignment zones; an odd value left over at the end is ignored
+pub fn blue_zones(info: &FontInfo) -> Vec<BlueZone> {
+    info.blue_values
+        .chunks_exact(2)
+        .chain(info.other_blues.chunks_exact(2))
+        .map(|pair| BlueZone {
+            bottom: pair[0].min(pair[1]),
+            top: pair[0].max(pair[1]),
+        })
+        .collect()
+}
+
+/// On-curve points of the glyph's own contours that are vertical extremes,
+/// with no neighbor beyond them
+pub fn vertical_extrema(glyph: &GlyphData) -> Vec<(f64, f64)> {
+    let Some(outline) = glyph.outline.as_ref() else {
+        return Vec::new();
+    };
+    let mut extrema = Vec::new();
+    for contour in &outline.contours {
+        let points = &contour.points;
+        if points.len() < 3 {
+            continue;
+        }
+        for (index, point) in points.iter().enumerate() {
+            if point.point_type == PointTypeData::OffCurve {
+                continue;
+            }
+            let previous = &points[(index + points.len() - 1) % points.len()];
+            let next = &points[(index + 1) % points.len()];
+            let top = previous.y <= point.y && next.y <= point.y;
+            let bottom = previous.y >= point.y && next.y >= point.y;
+            // The ends of a flat stretch are extremes, the points between aren't
+            if (top || bottom) && !(top && bottom) {
+                extrema.push((point.x, point.y));
+            }
+        }
+    }
+    extrema
+}
+
+/// Extremes of the glyph inside an alignment zone but on neither edge
+pub fn stray_extrema(glyph: &GlyphData, zones: &[BlueZone]) -> Vec<(f64, f64)> {
+    vertical_extrema(glyph)
+        .into_iter()
+        .filter(|&(_, y)| zones.iter().any(|zone| zone.is_between_edges(y)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::{ContourData, OutlineData, PointData};
+
+    fn glyph(points: &[(f64, f64, PointTypeData)]) -> GlyphData {
+        GlyphData {
+            outline: Some(OutlineData {
+                contours: vec![ContourData {
+                    points: points
+                        .iter()
+                        .map(|&(x, y, point_type)| PointData { x, y, point_type })
+                        .collect(),
+                }],
+            }),
+            ..GlyphData::test("o", 500.0)
+        }
+    }
+
+    #[test]
+    fn test_blue_zones_pair_values() {
+        let info = FontInfo {
+            blue_values: vec![-12.0, 0.0, 500.0, 512.0],
+            other_blues: vec![-210.0, -200.0, 7.0],
+            ..Default::default()
+        };
+        let zones = blue_zones(&info);
+        assert_eq!(zones.len(), 3);
+        assert_eq!(
+            zones[1],
+            BlueZone {
+                bottom: 500.0,
+                top: 512.0
+            }
+        );
+        assert_eq!(zones[2].bottom, -210.0);
+    }
+
+    #[test]
+    fn test_stray_extrema() {
+        use PointTypeData::{Curve, Line, OffCurve};
+        let zones = [
+            BlueZone {
+                bottom: -12.0,
+                top: 0.0,
+            },
+            BlueZone {
+                bottom: 500.0,
+                top: 512.0,
+            },
+        ];
+        // A diamond-ish shape topping out between the x-height zone's edges
+        // and bottoming out on the overshoot edge of the baseline zone
+        let shape = glyph(&[
+            (250.0, -12.0, Curve),
+            (400.0, -12.0, OffCurve),
+            (500.0, 100.0, OffCurve),
+            (500.0, 250.0, Curve),
+            (500.0, 400.0, OffCurve),
+            (400.0, 505.0, OffCurve),
+            (250.0, 505.0, Curve),
+            (0.0, 250.0, Line),
+        ]);
+        assert_eq!(vertical_extrema(&shape).len(), 2);
+        assert_eq!(stray_extrema(&shape, &zones), vec![(250.0, 505.0)]);
+    }
+}
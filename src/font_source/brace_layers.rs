@@ -0,0 +1,358 @@
+//! Brace layers: intermediate masters of single glyphs
+//!
+//! A glyph that doesn't interpolate well between the designspace's masters
+//! can get an extra master of its own somewhere in between. Like Glyphs, it
+//! is a layer named after its location in braces, `{600}` or `{600, 80}`
+//! with a value per axis in the designspace's order, holding only the glyphs
+//! that need it. A brace layer starts as the interpolation of the two
+//! masters on either side of it, and becomes a sparse source of the
+//! designspace (a `<source>` with a `layer`), so variable exports include it.
+
+use anyhow::{bail, Context, Result};
+use norad::designspace::{DesignSpaceDocument, Dimension, Source};
+use std::path::{Path, PathBuf};
+
+use super::data::{FontData, GlyphData};
+use super::designspace_rules::{designspace_axes, find_designspace_for_ufo, Location, RuleAxis};
+use super::interpolate::blend_glyphs;
+use super::layers::DEFAULT_LAYER_NAME;
+
+/// How close two locations on an axis count as the same
+const LOCATION_TOLERANCE: f64 = 1e-6;
+
+/// The name of the brace layer at `location`
+pub fn brace_layer_name(location: &Location, axes: &[RuleAxis]) -> String {
+    let values: Vec<String> = axes
+        .iter()
+        .map(|axis| {
+            location
+                .get(&axis.name)
+                .unwrap_or(&axis.default)
+                .to_string()
+        })
+        .collect();
+    format!("{{{}}}", values.join(", "))
+}
+
+/// The location of a brace layer, or `None` for other layers
+pub fn parse_brace_layer_name(name: &str, axes: &[RuleAxis]) -> Option<Location> {
+    let values = name.strip_prefix('{')?.strip_suffix('}')?.split(',');
+    let values: Vec<f64> = values
+        .map(|value| value.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    if values.len() != axes.len() {
+        return None;
+    }
+    Some(
+        axes.iter()
+            .map(|axis| axis.name.clone())
+            .zip(values)
+            .collect(),
+    )
+}
+
+/// `location` interpolated between the two closest masters on either side
+/// of it, as a glyph named after the first
+pub fn interpolate_at(masters: &[(Location, GlyphData)], location: &Location) -> Result<GlyphData> {
+    if masters.iter().any(|(at, _)| same_location(at, location)) {
+        bail!("There already is a master at {location:?}");
+    }
+    let mut best: Option<(f64, &GlyphData, &GlyphData, f64)> = None;
+    for (index, (from, first)) in masters.iter().enumerate() {
+        for (to, second) in &masters[index + 1..] {
+            let Some(amount) = amount_between(from, to, location) else {
+                continue;
+            };
+            let distance = distance(from, to);
+            if best.is_none_or(|(best_distance, ..)| distance < best_distance) {
+                best = Some((distance, first, second, amount));
+            }
+        }
+    }
+    let Some((_, first, second, amount)) = best else {
+        bail!("No two masters lie on either side of {location:?}");
+    };
+    blend_glyphs(first, second, amount)
+}
+
+fn same_location(a: &Location, b: &Location) -> bool {
+    a.len() == b.len()
+        && a.iter().all(|(axis, value)| {
+            b.get(axis)
+                .is_some_and(|other| (value - other).abs() < LOCATION_TOLERANCE)
+        })
+}
+
+fn distance(a: &Location, b: &Location) -> f64 {
+    a.iter()
+        .map(|(axis, value)| (b.get(axis).unwrap_or(value) - value).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// How far `location` is along the line from `from` to `to`, if it is on
+/// that line between them
+fn amount_between(from: &Location, to: &Location, location: &Location) -> Option<f64> {
+    let mut amount = None;
+    for (axis, value) in location {
+        let (start, end) = (*from.get(axis)?, *to.get(axis)?);
+        if (end - start).abs() < LOCATION_TOLERANCE {
+            if (value - start).abs() >= LOCATION_TOLERANCE {
+                return None;
+            }
+            continue;
+        }
+        let along = (value - start) / (end - start);
+        if amount.is_some_and(|amount: f64| (amount - along).abs() >= LOCATION_TOLERANCE) {
+            return None;
+        }
+        amount = Some(along);
+    }
+    amount.filter(|amount| (0.0..=1.0).contains(amount))
+}
+
+impl FontData {
+    /// Whether a layer has an outline or components for a glyph
+    fn draws_on_layer(&self, layer: &str, glyph_name: &str) -> bool {
+        self.layer_glyph(layer, glyph_name)
+            .is_some_and(|glyph| glyph.outline.is_some() || !glyph.components.is_empty())
+    }
+
+    /// Names of the brace layers drawing any glyph
+    pub fn brace_layers(&self, axes: &[RuleAxis]) -> Vec<String> {
+        self.layer_names()
+            .into_iter()
+            .filter(|layer| parse_brace_layer_name(layer, axes).is_some())
+            .filter(|layer| {
+                self.glyphs
+                    .keys()
+                    .any(|name| self.draws_on_layer(layer, name))
+            })
+            .collect()
+    }
+
+    /// Names of the brace layers drawing a glyph
+    pub fn brace_layer_names(&self, glyph_name: &str, axes: &[RuleAxis]) -> Vec<String> {
+        self.layer_names()
+            .into_iter()
+            .filter(|layer| parse_brace_layer_name(layer, axes).is_some())
+            .filter(|layer| self.draws_on_layer(layer, glyph_name))
+            .collect()
+    }
+
+    /// Names of the glyphs with at least one brace layer, sorted
+    pub fn glyphs_with_brace_layers(&self, axes: &[RuleAxis]) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .glyphs
+            .keys()
+            .filter(|name| !self.brace_layer_names(name, axes).is_empty())
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+/// The masters of the designspace listing a UFO
+#[derive(Clone, Debug, PartialEq)]
+pub struct BraceMasters {
+    /// The designspace file sparse sources are written to
+    pub path: PathBuf,
+    /// The UFO being edited
+    pub ufo_path: PathBuf,
+    pub axes: Vec<RuleAxis>,
+    /// Where each full master is and its UFO
+    pub masters: Vec<(Location, PathBuf)>,
+}
+
+impl BraceMasters {
+    /// The masters of the designspace next to a UFO that lists it as a
+    /// source, if there is one
+    pub fn find_for_ufo(ufo_path: &Path) -> Result<Option<Self>> {
+        let Some((path, document)) = find_designspace_for_ufo(ufo_path)? else {
+            return Ok(None);
+        };
+        let axes = designspace_axes(&document);
+        let folder = path.parent().unwrap_or(Path::new("."));
+        let masters = document
+            .sources
+            .iter()
+            .filter(|source| source.layer.is_none())
+            .map(|source| {
+                let location = axes
+                    .iter()
+                    .map(|axis| {
+                        let value = source
+                            .location
+                            .iter()
+                            .find(|dimension| dimension.name == axis.name)
+                            .and_then(|dimension| dimension.xvalue)
+                            .map_or(axis.default, f64::from);
+                        (axis.name.clone(), value)
+                    })
+                    .collect();
+                (location, folder.join(&source.filename))
+            })
+            .collect();
+        Ok(Some(Self {
+            path,
+            ufo_path: ufo_path.to_path_buf(),
+            axes,
+            masters,
+        }))
+    }
+
+    fn is_edited_ufo(&self, path: &Path) -> bool {
+        let canonical = |path: &Path| path.canonicalize().unwrap_or(path.to_path_buf());
+        canonical(path) == canonical(&self.ufo_path)
+    }
+
+    /// A glyph in every master that has it, the edited UFO's from `font`
+    /// and the others read from disk
+    pub fn master_glyphs(
+        &self,
+        font: &FontData,
+        glyph_name: &str,
+    ) -> Result<Vec<(Location, GlyphData)>> {
+        let mut glyphs = Vec::new();
+        for (location, path) in &self.masters {
+            let glyph = if self.is_edited_ufo(path) {
+                font.layer_glyph(DEFAULT_LAYER_NAME, glyph_name).cloned()
+            } else {
+                let master = norad::Font::load(path)
+                    .with_context(|| format!("Failed to load {}", path.display()))?;
+                master
+                    .default_layer()
+                    .get_glyph(glyph_name)
+                    .map(GlyphData::from_norad_glyph)
+            };
+            if let Some(glyph) = glyph {
+                glyphs.push((location.clone(), glyph));
+            }
+        }
+        Ok(glyphs)
+    }
+
+    /// Make the designspace list the font's brace layers as sparse
+    /// sources, and only those; true if it had to be written
+    pub fn save_sources(&self, font: &FontData) -> Result<bool> {
+        let mut document = DesignSpaceDocument::load(&self.path)
+            .with_context(|| format!("Failed to load {}", self.path.display()))?;
+        let folder = self.path.parent().unwrap_or(Path::new("."));
+        let is_brace_source = |source: &Source| {
+            self.is_edited_ufo(&folder.join(&source.filename))
+                && source
+                    .layer
+                    .as_deref()
+                    .is_some_and(|layer| parse_brace_layer_name(layer, &self.axes).is_some())
+        };
+
+        let listed: Vec<String> = document
+            .sources
+            .iter()
+            .filter(|source| is_brace_source(source))
+            .filter_map(|source| source.layer.clone())
+            .collect();
+        let layers = font.brace_layers(&self.axes);
+        if listed == layers {
+            return Ok(false);
+        }
+        let Some(main) = document
+            .sources
+            .iter()
+            .find(|source| {
+                source.layer.is_none() && self.is_edited_ufo(&folder.join(&source.filename))
+            })
+            .cloned()
+        else {
+            bail!("{} doesn't list the font as a master", self.path.display());
+        };
+
+        document.sources.retain(|source| !is_brace_source(source));
+        for layer in layers {
+            let Some(location) = parse_brace_layer_name(&layer, &self.axes) else {
+                continue;
+            };
+            let base_name = main.name.clone().unwrap_or_default();
+            document.sources.push(Source {
+                name: Some(format!("{base_name} {layer}").trim().to_string()),
+                layer: Some(layer),
+                location: location
+                    .into_iter()
+                    .map(|(name, value)| Dimension {
+                        name,
+                        uservalue: None,
+                        xvalue: Some(value as f32),
+                        yvalue: None,
+                    })
+                    .collect(),
+                ..main.clone()
+            });
+        }
+        document
+            .save(&self.path)
+            .with_context(|| format!("Failed to save {}", self.path.display()))?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::{ContourData, OutlineData, PointData, PointTypeData};
+
+    fn bar(width: f64) -> GlyphData {
+        let corners = [(0.0, 0.0), (width, 0.0), (width, 700.0), (0.0, 700.0)];
+        GlyphData {
+            outline: Some(OutlineData {
+                contours: vec![ContourData {
+                    points: corners
+                        .iter()
+                        .map(|&(x, y)| PointData {
+                            x,
+                            y,
+                            point_type: PointTypeData::Line,
+                        })
+                        .collect(),
+                }],
+            }),
+            ..GlyphData::test("l", width + 100.0)
+        }
+    }
+
+    fn weight(value: f64) -> Location {
+        Location::from([("Weight".to_string(), value)])
+    }
+
+    #[test]
+    fn test_brace_layers() {
+        let axes = [RuleAxis {
+            name: "Weight".to_string(),
+            minimum: 100.0,
+            default: 400.0,
+            maximum: 900.0,
+        }];
+        assert_eq!(brace_layer_name(&weight(600.0), &axes), "{600}");
+        assert_eq!(parse_brace_layer_name("{600}", &axes), Some(weight(600.0)));
+        assert_eq!(parse_brace_layer_name("{600, 80}", &axes), None);
+        assert_eq!(parse_brace_layer_name("public.background", &axes), None);
+
+        // The closest masters around the location are blended
+        let masters = [
+            (weight(100.0), bar(20.0)),
+            (weight(400.0), bar(80.0)),
+            (weight(900.0), bar(200.0)),
+        ];
+        let glyph = interpolate_at(&masters, &weight(600.0)).unwrap();
+        assert_eq!(glyph.advance_width, 228.0);
+        assert!(interpolate_at(&masters, &weight(400.0)).is_err());
+        assert!(interpolate_at(&masters, &weight(950.0)).is_err());
+
+        let mut font = FontData::default();
+        font.glyphs.insert("l".to_string(), bar(80.0));
+        font.insert_layer_glyph("{600}", glyph);
+        assert_eq!(font.brace_layer_names("l", &axes), ["{600}"]);
+        assert_eq!(font.glyphs_with_brace_layers(&axes), ["l"]);
+        assert_eq!(font.brace_layers(&axes), ["{600}"]);
+    }
+}
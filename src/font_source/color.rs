@@ -0,0 +1,245 @@
+//! Color font data (COLRv0 layers and CPAL palettes)
+//!
+//! A color glyph is a base glyph whose rendering is replaced by a stack of
+//! layer glyphs, each filled with a palette entry. The base glyph's own
+//! outline stays as the monochrome fallback.
+//!
+//! The data round-trips through the UFO font lib using the ufo2ft keys, so
+//! compilers that understand them build the COLR and CPAL tables directly.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Font lib key holding the CPAL palettes
+pub const COLOR_PALETTES_LIB_KEY: &str = "com.github.googlei18n.ufo2ft.colorPalettes";
+/// Font lib key holding the COLRv0 layer mapping
+pub const COLOR_LAYERS_LIB_KEY: &str = "com.github.googlei18n.ufo2ft.colorLayers";
+
+/// Palette index that means "use the text foreground color" in COLRv0
+pub const FOREGROUND_PALETTE_INDEX: u16 = 0xFFFF;
+
+/// An RGBA color with components in 0.0..=1.0, as stored in the UFO lib
+pub type PaletteColor = [f32; 4];
+
+/// Color of palette entries added to fill a palette out
+const FILL_COLOR: PaletteColor = [0.0, 0.0, 0.0, 1.0];
+
+/// One layer of a color glyph
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColorLayer {
+    /// Glyph whose outline is drawn for this layer
+    pub glyph_name: String,
+    /// Entry in the active palette used to fill the layer
+    pub palette_index: u16,
+}
+
+/// Color layers and palettes for the whole font
+#[derive(Clone, Debug, Default)]
+pub struct ColorData {
+    /// CPAL palettes; every palette has the same number of entries
+    pub palettes: Vec<Vec<PaletteColor>>,
+    /// Base glyph name -> layers, bottom layer first
+    pub layers: HashMap<String, Vec<ColorLayer>>,
+    /// Palette shown in the editor
+    pub active_palette: usize,
+}
+
+impl ColorData {
+    /// Whether the font has any color glyphs
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Layers of a color glyph, or `None` for monochrome glyphs
+    pub fn layers_for(&self, glyph_name: &str) -> Option<&[ColorLayer]> {
+        self.layers
+            .get(glyph_name)
+            .map(|layers| layers.as_slice())
+            .filter(|layers| !layers.is_empty())
+    }
+
+    /// Resolve a palette index against the active palette
+    ///
+    /// Returns `None` for the foreground index so callers can substitute
+    /// their own text color.
+    pub fn resolve_color(&self, palette_index: u16) -> Option<PaletteColor> {
+        if palette_index == FOREGROUND_PALETTE_INDEX {
+            return None;
+        }
+        self.palettes
+            .get(self.active_palette)
+            .or_else(|| self.palettes.first())
+            .and_then(|palette| palette.get(palette_index as usize))
+            .copied()
+    }
+
+    /// Number of entries per palette
+    pub fn palette_size(&self) -> usize {
+        self.palettes.first().map_or(0, |palette| palette.len())
+    }
+
+    /// Set a palette entry, growing every palette so they stay the same size
+    pub fn set_palette_color(&mut self, palette: usize, index: usize, color: PaletteColor) {
+        while self.palettes.len() <= palette {
+            let size = self.palette_size();
+            self.palettes.push(vec![FILL_COLOR; size]);
+        }
+        if index >= self.palette_size() {
+            for entries in &mut self.palettes {
+                entries.resize(index + 1, FILL_COLOR);
+            }
+        }
+        self.palettes[palette][index] = color;
+    }
+
+    /// Layers using a palette entry the palettes don't have, as base glyph
+    /// and layer, by base glyph name
+    pub fn out_of_range_layers(&self) -> Vec<(&str, &ColorLayer)> {
+        let size = self.palette_size();
+        let mut layers: Vec<(&str, &ColorLayer)> = self
+            .layers
+            .iter()
+            .flat_map(|(base, layers)| layers.iter().map(move |layer| (base.as_str(), layer)))
+            .filter(|(_, layer)| {
+                layer.palette_index != FOREGROUND_PALETTE_INDEX
+                    && layer.palette_index as usize >= size
+            })
+            .collect();
+        layers.sort_by(|a, b| a.0.cmp(b.0).then(a.1.glyph_name.cmp(&b.1.glyph_name)));
+        layers
+    }
+
+    /// Fill shorter palettes out to the size of the longest, so every
+    /// palette has an entry for every index
+    fn fill_palettes(&mut self) {
+        let size = self.palettes.iter().map(Vec::len).max().unwrap_or(0);
+        for entries in &mut self.palettes {
+            entries.resize(size, FILL_COLOR);
+        }
+    }
+
+    /// Read color data from a UFO font lib. Palettes shorter than the
+    /// longest are filled out with opaque black
+    pub fn from_norad_lib(lib: &norad::Plist) -> Self {
+        let palettes = lib
+            .get(COLOR_PALETTES_LIB_KEY)
+            .and_then(|value| serde_json::to_value(value).ok())
+            .and_then(|json| serde_json::from_value(json).ok())
+            .unwrap_or_default();
+
+        let raw_layers: HashMap<String, Vec<(String, u16)>> = lib
+            .get(COLOR_LAYERS_LIB_KEY)
+            .and_then(|value| serde_json::to_value(value).ok())
+            .and_then(|json| serde_json::from_value(json).ok())
+            .unwrap_or_default();
+
+        let layers = raw_layers
+            .into_iter()
+            .map(|(base, layers)| {
+                let layers = layers
+                    .into_iter()
+                    .map(|(glyph_name, palette_index)| ColorLayer {
+                        glyph_name,
+                        palette_index,
+                    })
+                    .collect();
+                (base, layers)
+            })
+            .collect();
+
+        let mut color = Self {
+            palettes,
+            layers,
+            active_palette: 0,
+        };
+        color.fill_palettes();
+        color
+    }
+
+    /// Write color data into a UFO font lib, removing stale keys if empty
+    pub fn write_to_norad_lib(&self, lib: &mut norad::Plist) {
+        lib.remove(COLOR_PALETTES_LIB_KEY);
+        lib.remove(COLOR_LAYERS_LIB_KEY);
+
+        if !self.palettes.is_empty() {
+            if let Ok(value) = serde_json::to_value(&self.palettes).and_then(serde_json::from_value)
+            {
+                lib.insert(COLOR_PALETTES_LIB_KEY.to_string(), value);
+            }
+        }
+
+        let layers: HashMap<&str, Vec<(&str, u16)>> = self
+            .layers
+            .iter()
+            .filter(|(_, layers)| !layers.is_empty())
+            .map(|(base, layers)| {
+                let pairs = layers
+                    .iter()
+                    .map(|layer| (layer.glyph_name.as_str(), layer.palette_index))
+                    .collect();
+                (base.as_str(), pairs)
+            })
+            .collect();
+
+        if !layers.is_empty() {
+            if let Ok(value) = serde_json::to_value(&layers).and_then(serde_json::from_value) {
+                lib.insert(COLOR_LAYERS_LIB_KEY.to_string(), value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lib_round_trip() {
+        let mut color = ColorData::default();
+        color.set_palette_color(0, 1, [1.0, 0.0, 0.0, 1.0]);
+        color.layers.insert(
+            "heart".to_string(),
+            vec![ColorLayer {
+                glyph_name: "heart.layer0".to_string(),
+                palette_index: 1,
+            }],
+        );
+
+        let mut lib = norad::Plist::new();
+        color.write_to_norad_lib(&mut lib);
+        let restored = ColorData::from_norad_lib(&lib);
+
+        assert_eq!(restored.palettes, color.palettes);
+        assert_eq!(restored.layers_for("heart"), color.layers_for("heart"));
+    }
+
+    #[test]
+    fn test_loading_fills_out_short_palettes() {
+        let mut color = ColorData {
+            palettes: vec![vec![[1.0, 0.0, 0.0, 1.0]], vec![[0.0, 1.0, 0.0, 1.0]; 3]],
+            ..Default::default()
+        };
+        color.layers.insert(
+            "a".to_string(),
+            [2, 3, FOREGROUND_PALETTE_INDEX]
+                .map(|palette_index| ColorLayer {
+                    glyph_name: format!("a.color{palette_index}"),
+                    palette_index,
+                })
+                .to_vec(),
+        );
+        let mut lib = norad::Plist::new();
+        color.write_to_norad_lib(&mut lib);
+        let restored = ColorData::from_norad_lib(&lib);
+
+        assert_eq!(restored.palette_size(), 3);
+        assert_eq!(restored.palettes[0][2], FILL_COLOR);
+        assert_eq!(restored.palettes[1], color.palettes[1]);
+        let out_of_range: Vec<&str> = restored
+            .out_of_range_layers()
+            .iter()
+            .map(|(_, layer)| layer.glyph_name.as_str())
+            .collect();
+        assert_eq!(out_of_range, ["a.color3"]);
+    }
+}
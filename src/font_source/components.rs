@@ -0,0 +1,233 @@
+//! Component dependencies between glyphs
+//!
+//! Composite glyphs are drawn from the base glyphs of their components,
+//! which may be composites themselves. The graph of these references tells
+//! which glyphs a glyph is built from, which glyphs are built from it and
+//! change with it, and where references loop back to a glyph, which no
+//! compiler can resolve.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::data::FontData;
+
+/// A glyph in a tree of component references
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComponentNode {
+    /// Steps from the glyph the tree is of, from 1
+    pub depth: usize,
+    pub glyph: String,
+    /// The glyph is referenced but not in the font
+    pub missing: bool,
+    /// The glyph is already on the way down to this node, so the
+    /// references loop and the tree stops here
+    pub cycle: bool,
+}
+
+/// Which glyphs use which others as components
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ComponentGraph {
+    /// Each glyph's base glyphs, once each, in component order
+    uses: BTreeMap<String, Vec<String>>,
+    /// Each base glyph's composites, in name order
+    used_by: BTreeMap<String, Vec<String>>,
+    glyphs: BTreeSet<String>,
+}
+
+impl ComponentGraph {
+    pub fn new(font: &FontData) -> Self {
+        let mut graph = Self::default();
+        for (name, glyph) in &font.glyphs {
+            graph.glyphs.insert(name.clone());
+            let mut bases: Vec<String> = Vec::new();
+            for component in &glyph.components {
+                if !bases.contains(&component.base_glyph) {
+                    bases.push(component.base_glyph.clone());
+                }
+            }
+            for base in &bases {
+                graph
+                    .used_by
+                    .entry(base.clone())
+                    .or_default()
+                    .push(name.clone());
+            }
+            if !bases.is_empty() {
+                graph.uses.insert(name.clone(), bases);
+            }
+        }
+        for users in graph.used_by.values_mut() {
+            users.sort();
+        }
+        graph
+    }
+
+    /// The base glyphs of a glyph's own components
+    pub fn components(&self, glyph: &str) -> &[String] {
+        self.uses.get(glyph).map_or(&[], Vec::as_slice)
+    }
+
+    /// The glyphs with a component of `glyph`
+    pub fn users(&self, glyph: &str) -> &[String] {
+        self.used_by.get(glyph).map_or(&[], Vec::as_slice)
+    }
+
+    /// The glyphs `glyph` is built from, depth first
+    pub fn component_tree(&self, glyph: &str) -> Vec<ComponentNode> {
+        self.tree(glyph, &self.uses)
+    }
+
+    /// The glyphs built from `glyph`, depth first
+    pub fn user_tree(&self, glyph: &str) -> Vec<ComponentNode> {
+        self.tree(glyph, &self.used_by)
+    }
+
+    /// Every glyph that changes when `glyph` does, at any depth
+    pub fn all_users(&self, glyph: &str) -> BTreeSet<String> {
+        let mut users = reachable(glyph, &self.used_by);
+        users.remove(glyph);
+        users
+    }
+
+    /// The glyphs on each loop of references, in name order
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut cycles: Vec<Vec<String>> = Vec::new();
+        for glyph in self.uses.keys() {
+            if cycles.iter().any(|cycle| cycle.contains(glyph)) {
+                continue;
+            }
+            let users = reachable(glyph, &self.used_by);
+            if !users.contains(glyph) {
+                continue;
+            }
+            let components = reachable(glyph, &self.uses);
+            cycles.push(users.intersection(&components).cloned().collect());
+        }
+        cycles
+    }
+
+    fn tree(&self, glyph: &str, edges: &BTreeMap<String, Vec<String>>) -> Vec<ComponentNode> {
+        let mut nodes = Vec::new();
+        self.collect_tree(glyph, edges, &mut vec![glyph.to_string()], &mut nodes);
+        nodes
+    }
+
+    fn collect_tree(
+        &self,
+        glyph: &str,
+        edges: &BTreeMap<String, Vec<String>>,
+        path: &mut Vec<String>,
+        nodes: &mut Vec<ComponentNode>,
+    ) {
+        for next in edges.get(glyph).into_iter().flatten() {
+            let cycle = path.contains(next);
+            nodes.push(ComponentNode {
+                depth: path.len(),
+                glyph: next.clone(),
+                missing: !self.glyphs.contains(next),
+                cycle,
+            });
+            if !cycle {
+                path.push(next.clone());
+                self.collect_tree(next, edges, path, nodes);
+                path.pop();
+            }
+        }
+    }
+}
+
+/// The glyphs reached from `start` along `edges`, which holds `start` only
+/// if a path leads back to it
+fn reachable(start: &str, edges: &BTreeMap<String, Vec<String>>) -> BTreeSet<String> {
+    let mut reached = BTreeSet::new();
+    let mut pending = vec![start.to_string()];
+    while let Some(glyph) = pending.pop() {
+        for next in edges.get(&glyph).into_iter().flatten() {
+            if reached.insert(next.clone()) {
+                pending.push(next.clone());
+            }
+        }
+    }
+    reached
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::{ComponentData, GlyphData};
+
+    fn font(glyphs: &[(&str, &[&str])]) -> FontData {
+        let mut font = FontData::default();
+        for (name, components) in glyphs {
+            let glyph = GlyphData {
+                components: components
+                    .iter()
+                    .map(|base| ComponentData {
+                        base_glyph: base.to_string(),
+                        transform: [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+                    })
+                    .collect(),
+                ..GlyphData::test(name, 500.0)
+            };
+            font.glyphs.insert(name.to_string(), glyph);
+        }
+        font
+    }
+
+    fn names(nodes: &[ComponentNode]) -> Vec<(usize, &str)> {
+        nodes
+            .iter()
+            .map(|node| (node.depth, node.glyph.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_components_and_users() {
+        let graph = ComponentGraph::new(&font(&[
+            ("A", &[]),
+            ("gravecomb", &[]),
+            ("Agrave", &["A", "gravecomb"]),
+            ("Agrave.sc", &["Agrave", "Agrave", "ringcomb"]),
+            ("Aring", &["A"]),
+        ]));
+
+        assert_eq!(graph.components("Agrave.sc"), ["Agrave", "ringcomb"]);
+        assert_eq!(graph.users("A"), ["Agrave", "Aring"]);
+        assert!(graph.users("Aring").is_empty());
+
+        let tree = graph.component_tree("Agrave.sc");
+        assert_eq!(
+            names(&tree),
+            [(1, "Agrave"), (2, "A"), (2, "gravecomb"), (1, "ringcomb")]
+        );
+        assert!(tree[3].missing && !tree[0].missing);
+        assert_eq!(
+            names(&graph.user_tree("A")),
+            [(1, "Agrave"), (2, "Agrave.sc"), (1, "Aring")]
+        );
+        assert_eq!(
+            graph.all_users("A").into_iter().collect::<Vec<_>>(),
+            ["Agrave", "Agrave.sc", "Aring"]
+        );
+        assert!(graph.cycles().is_empty());
+    }
+
+    #[test]
+    fn test_cycles() {
+        let graph = ComponentGraph::new(&font(&[
+            ("a", &["b"]),
+            ("b", &["c"]),
+            ("c", &["a"]),
+            ("d", &["a"]),
+            ("e", &["e"]),
+        ]));
+
+        assert_eq!(graph.cycles(), [vec!["a", "b", "c"], vec!["e"]]);
+        let tree = graph.component_tree("d");
+        assert_eq!(names(&tree), [(1, "a"), (2, "b"), (3, "c"), (4, "a")]);
+        assert!(tree[3].cycle && !tree[2].cycle);
+        assert_eq!(
+            graph.all_users("a").into_iter().collect::<Vec<_>>(),
+            ["b", "c", "d"]
+        );
+    }
+}
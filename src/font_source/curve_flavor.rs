@@ -0,0 +1,661 @@
+//! Cubic and quadratic outlines
+//!
+//! Sources are usually drawn with cubic curves, while TrueType `glyf`
+//! outlines only have quadratic ones. Converting to quadratic approximates
+//! each cubic segment with a quadratic spline of as few pieces as keep it
+//! within a maximum error, in font units; the pieces meet at on-curve points
+//! implied halfway between their off-curves, as TrueType allows. This is
+//! fontTools' cu2qu: each piece's control is where the tangents of its part
+//! of the cubic cross, and the piece, raised to a cubic, is compared with
+//! that part. A segment no spline of up to `MAX_PIECES` pieces keeps within
+//! the error fails the conversion rather than being drawn further off.
+//! Converting to cubic is exact: each quadratic piece is raised to a cubic
+//! one.
+//!
+//! The glyphs of the masters of a variable font have to stay compatible, so
+//! `to_quadratic_compatible` splits a segment into the same number of pieces
+//! in every master, the number the worst of them needs. Export converts the
+//! copy of the sources it compiles this way (see `data::export_copy`), with
+//! the maximum error of the export profile, since TrueType fonts are all it
+//! writes.
+
+use crate::font_source::data::{ContourData, PointData, PointTypeData};
+use anyhow::{anyhow, Result};
+use kurbo::{Point, Vec2};
+
+/// Furthest a quadratic segment may stray from its cubic, in font units
+pub const DEFAULT_MAX_ERROR: f64 = 1.0;
+
+/// Maximum errors to step through, in font units
+pub const MAX_ERROR_STEPS: [f64; 5] = [0.1, 0.25, 0.5, 1.0, 2.0];
+
+/// Most quadratic pieces a cubic segment is split into, as in cu2qu
+const MAX_PIECES: usize = 100;
+
+/// Which kinds of curves a glyph's outline has
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveFlavor {
+    /// Straight lines only, the same in both flavors
+    Lines,
+    Cubic,
+    Quadratic,
+    Mixed,
+}
+
+impl CurveFlavor {
+    /// The flavor of a set of contours
+    pub fn of(contours: &[ContourData]) -> Self {
+        let (mut cubic, mut quadratic) = (false, false);
+        for segment in contours.iter().flat_map(segments) {
+            cubic |= segment.is_cubic();
+            quadratic |= segment.is_quadratic();
+        }
+        match (cubic, quadratic) {
+            (false, false) => CurveFlavor::Lines,
+            (true, false) => CurveFlavor::Cubic,
+            (false, true) => CurveFlavor::Quadratic,
+            (true, true) => CurveFlavor::Mixed,
+        }
+    }
+
+    pub fn label(self) -> String {
+        let id = match self {
+            CurveFlavor::Lines => "curve-flavor-lines",
+            CurveFlavor::Cubic => "curve-flavor-cubic",
+            CurveFlavor::Quadratic => "curve-flavor-quadratic",
+            CurveFlavor::Mixed => "curve-flavor-mixed",
+        };
+        crate::i18n::tr(id)
+    }
+}
+
+/// The next larger (`forward`) or smaller maximum error step
+pub fn step_max_error(current: f64, forward: bool) -> f64 {
+    let next = if forward {
+        MAX_ERROR_STEPS.iter().find(|&&step| step > current)
+    } else {
+        MAX_ERROR_STEPS.iter().rev().find(|&&step| step < current)
+    };
+    next.copied().unwrap_or(current)
+}
+
+/// An on-curve point and the off-curve points before it
+struct Segment<'a> {
+    off_curves: Vec<&'a PointData>,
+    end: &'a PointData,
+}
+
+impl Segment<'_> {
+    fn is_cubic(&self) -> bool {
+        self.end.point_type == PointTypeData::Curve && self.off_curves.len() >= 2
+    }
+
+    fn is_quadratic(&self) -> bool {
+        match self.end.point_type {
+            PointTypeData::QCurve => !self.off_curves.is_empty(),
+            PointTypeData::Curve => self.off_curves.len() == 1,
+            _ => false,
+        }
+    }
+
+    /// The segment's points as they are
+    fn points(&self) -> Vec<PointData> {
+        let mut points: Vec<PointData> = self.off_curves.iter().copied().cloned().collect();
+        points.push(self.end.clone());
+        points
+    }
+}
+
+/// The index a closed contour's segments start from: its first on-curve
+/// point, which also ends the last segment
+fn first_on_curve(contour: &ContourData) -> Option<usize> {
+    contour.points.iter().position(PointData::is_on_curve)
+}
+
+/// A contour's segments in order, after its first point; empty for a
+/// contour without on-curve points
+fn segments(contour: &ContourData) -> Vec<Segment<'_>> {
+    let len = contour.points.len();
+    let Some(start) = first_on_curve(contour) else {
+        return Vec::new();
+    };
+    // An open contour's segments don't wrap around
+    let count = if contour.is_open() { len - 1 } else { len };
+    let mut segments = Vec::new();
+    let mut off_curves = Vec::new();
+    for step in 1..=count {
+        let point = &contour.points[(start + step) % len];
+        if point.is_on_curve() {
+            segments.push(Segment {
+                off_curves: std::mem::take(&mut off_curves),
+                end: point,
+            });
+        } else {
+            off_curves.push(point);
+        }
+    }
+    segments
+}
+
+/// Rebuild a contour from its first point and the points of each segment,
+/// every segment's points ending with its on-curve point
+fn rebuild(contour: &ContourData, pieces: Vec<Vec<PointData>>) -> ContourData {
+    let Some(start) = first_on_curve(contour) else {
+        return contour.clone();
+    };
+    let mut points = vec![contour.points[start].clone()];
+    let mut pieces = pieces.into_iter().peekable();
+    while let Some(mut piece) = pieces.next() {
+        // A closed contour's last segment ends at its first point
+        if pieces.peek().is_none() && !contour.is_open() {
+            if let Some(end) = piece.pop() {
+                points[0].point_type = end.point_type;
+            }
+        }
+        points.extend(piece);
+    }
+    ContourData { points }
+}
+
+/// A point on a cubic Bézier
+fn cubic_at(cubic: &[Point; 4], t: f64) -> Point {
+    let mt = 1.0 - t;
+    let [p0, p1, p2, p3] = cubic.map(|p| p.to_vec2());
+    (p0 * (mt * mt * mt) + p1 * (3.0 * mt * mt * t) + p2 * (3.0 * mt * t * t) + p3 * (t * t * t))
+        .to_point()
+}
+
+/// The part of a cubic between two parameters
+fn cubic_part(cubic: &[Point; 4], t0: f64, t1: f64) -> [Point; 4] {
+    let [p0, p1, p2, p3] = cubic.map(|p| p.to_vec2());
+    // Derivative of the cubic, scaled to the part's parameter range
+    let derivative = |t: f64| {
+        let mt = 1.0 - t;
+        ((p1 - p0) * (mt * mt) + (p2 - p1) * (2.0 * mt * t) + (p3 - p2) * (t * t)) * 3.0
+    };
+    let scale = (t1 - t0) / 3.0;
+    let start = cubic_at(cubic, t0);
+    let end = cubic_at(cubic, t1);
+    [
+        start,
+        start + derivative(t0) * scale,
+        end - derivative(t1) * scale,
+        end,
+    ]
+}
+
+/// Where the tangents at a cubic's ends cross
+fn tangent_crossing(cubic: &[Point; 4]) -> Option<Point> {
+    let [p0, p1, p2, p3] = *cubic;
+    let (a, b) = (p1 - p0, p3 - p2);
+    let denominator = a.cross(b);
+    if denominator.abs() < 1e-12 {
+        return None;
+    }
+    let along = (p3 - p0).cross(b) / denominator;
+    Some(p0 + a * along)
+}
+
+/// A quadratic raised to the cubic drawing the same curve
+fn as_cubic(start: Point, control: Point, end: Point) -> [Point; 4] {
+    [
+        start,
+        start + (control - start) * (2.0 / 3.0),
+        end + (control - end) * (2.0 / 3.0),
+        end,
+    ]
+}
+
+/// Whether a cubic, the difference between two cubics whose ends are
+/// already close enough, stays within `tolerance` of the origin (cu2qu's
+/// `cubic_farthest_fit_inside`)
+fn fits_inside(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2, tolerance: f64) -> bool {
+    if p1.hypot() <= tolerance && p2.hypot() <= tolerance {
+        return true;
+    }
+    let mid = (p0 + (p1 + p2) * 3.0 + p3) * 0.125;
+    if mid.hypot() > tolerance {
+        return false;
+    }
+    let derivative = (p3 + p2 - p1 - p0) * 0.125;
+    fits_inside(p0, (p0 + p1) * 0.5, mid - derivative, mid, tolerance)
+        && fits_inside(mid, mid + derivative, (p2 + p3) * 0.5, p3, tolerance)
+}
+
+/// Whether a quadratic follows a cubic within `max_error` all along
+fn follows(quadratic: [Point; 3], cubic: &[Point; 4], max_error: f64) -> bool {
+    let [q0, q1, q2, q3] = as_cubic(quadratic[0], quadratic[1], quadratic[2]);
+    let [c0, c1, c2, c3] = *cubic;
+    (q0 - c0).hypot() <= max_error
+        && (q3 - c3).hypot() <= max_error
+        && fits_inside(q0 - c0, q1 - c1, q2 - c2, q3 - c3, max_error)
+}
+
+/// The off-curve points of a quadratic spline of `pieces` pieces following
+/// a cubic within `max_error`, as TrueType draws it with implied on-curve
+/// points, or `None` when that many pieces can't
+fn quadratic_spline(cubic: &[Point; 4], pieces: usize, max_error: f64) -> Option<Vec<Point>> {
+    if pieces == 1 {
+        let control = tangent_crossing(cubic)?;
+        return follows([cubic[0], control, cubic[3]], cubic, max_error).then(|| vec![control]);
+    }
+    let parts: Vec<[Point; 4]> = (0..pieces)
+        .map(|index| {
+            let t0 = index as f64 / pieces as f64;
+            let t1 = (index + 1) as f64 / pieces as f64;
+            cubic_part(cubic, t0, t1)
+        })
+        .collect();
+    let controls: Vec<Point> = parts
+        .iter()
+        .enumerate()
+        .map(|(index, [p0, p1, p2, p3])| {
+            // Lean towards the control either end's tangent gives, so the
+            // first and last pieces keep the cubic's tangents
+            let from_start = *p0 + (*p1 - *p0) * 1.5;
+            let from_end = *p3 + (*p2 - *p3) * 1.5;
+            from_start.lerp(from_end, index as f64 / (pieces - 1) as f64)
+        })
+        .collect();
+    let implied = |index: usize| match index {
+        0 => cubic[0],
+        i if i == pieces => cubic[3],
+        i => controls[i - 1].midpoint(controls[i]),
+    };
+    let fits = parts.iter().enumerate().all(|(index, part)| {
+        let quadratic = [implied(index), controls[index], implied(index + 1)];
+        follows(quadratic, part, max_error)
+    });
+    fits.then_some(controls)
+}
+
+/// Splines of the fewest pieces that follow every cubic within
+/// `max_error`, as many pieces for each, or `None` when `MAX_PIECES` don't
+fn compatible_splines(cubics: &[[Point; 4]], max_error: f64) -> Option<Vec<Vec<Point>>> {
+    (1..=MAX_PIECES).find_map(|pieces| {
+        cubics
+            .iter()
+            .map(|cubic| quadratic_spline(cubic, pieces, max_error))
+            .collect()
+    })
+}
+
+/// A glyph's outline with every cubic segment made quadratic
+pub fn to_quadratic(contours: &[ContourData], max_error: f64) -> Result<Vec<ContourData>> {
+    Ok(convert_compatible(&[contours], max_error)?
+        .pop()
+        .unwrap_or_default())
+}
+
+/// Whether two outlines have the same contours and point types
+fn same_structure(a: &[ContourData], b: &[ContourData]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| {
+            a.points.len() == b.points.len()
+                && a.points
+                    .iter()
+                    .zip(&b.points)
+                    .all(|(a, b)| a.point_type == b.point_type)
+        })
+}
+
+/// Several masters of a glyph with every cubic segment made quadratic,
+/// each segment split into the same number of pieces in all masters that
+/// share their contours and point types
+///
+/// Masters that don't, like a background sketch, are converted along with
+/// those they do share them with, or on their own.
+pub fn to_quadratic_compatible(
+    masters: &[Vec<ContourData>],
+    max_error: f64,
+) -> Result<Vec<Vec<ContourData>>> {
+    let mut converted: Vec<Option<Vec<ContourData>>> = vec![None; masters.len()];
+    for first in 0..masters.len() {
+        if converted[first].is_some() {
+            continue;
+        }
+        let group: Vec<usize> = (first..masters.len())
+            .filter(|&index| {
+                converted[index].is_none() && same_structure(&masters[first], &masters[index])
+            })
+            .collect();
+        let drawings: Vec<&[ContourData]> = group
+            .iter()
+            .map(|&index| masters[index].as_slice())
+            .collect();
+        for (index, contours) in group
+            .into_iter()
+            .zip(convert_compatible(&drawings, max_error)?)
+        {
+            converted[index] = Some(contours);
+        }
+    }
+    Ok(converted.into_iter().flatten().collect())
+}
+
+/// Masters sharing their contours and point types, converted to quadratic
+fn convert_compatible(masters: &[&[ContourData]], max_error: f64) -> Result<Vec<Vec<ContourData>>> {
+    let Some(first) = masters.first() else {
+        return Ok(Vec::new());
+    };
+    let mut converted: Vec<Vec<ContourData>> = vec![Vec::new(); masters.len()];
+    for contour_index in 0..first.len() {
+        let master_segments: Vec<Vec<Segment>> = masters
+            .iter()
+            .map(|master| segments(&master[contour_index]))
+            .collect();
+        let mut starts: Vec<Point> = masters
+            .iter()
+            .map(|master| {
+                let contour = &master[contour_index];
+                first_on_curve(contour)
+                    .map(|start| contour.points[start].position())
+                    .unwrap_or_default()
+            })
+            .collect();
+        let mut pieces: Vec<Vec<Vec<PointData>>> = vec![Vec::new(); masters.len()];
+        for segment_index in 0..master_segments[0].len() {
+            let segment_of = |master: usize| &master_segments[master][segment_index];
+            // The segment as a cubic in every master, if it is one
+            let cubics: Option<Vec<[Point; 4]>> = starts
+                .iter()
+                .enumerate()
+                .map(|(master, &start)| {
+                    let segment = segment_of(master);
+                    match segment.off_curves[..] {
+                        [c1, c2] if segment.is_cubic() => {
+                            Some([start, c1.position(), c2.position(), segment.end.position()])
+                        }
+                        _ => None,
+                    }
+                })
+                .collect();
+            let splines = match cubics.as_deref() {
+                Some(cubics) => Some(compatible_splines(cubics, max_error).ok_or_else(|| {
+                    let end = cubics[0][3];
+                    anyhow!(
+                        "The curve to ({}, {}) can't be made quadratic within {max_error} units",
+                        end.x,
+                        end.y
+                    )
+                })?),
+                None => None,
+            };
+            for (master, (pieces, start)) in pieces.iter_mut().zip(&mut starts).enumerate() {
+                let segment = segment_of(master);
+                let mut points: Vec<PointData> = match &splines {
+                    Some(splines) => splines[master]
+                        .iter()
+                        .map(|&control| PointData::at(control, PointTypeData::OffCurve))
+                        .chain([PointData::at(segment.end.position(), PointTypeData::QCurve)])
+                        .collect(),
+                    None => segment.points(),
+                };
+                // A single handle already draws a quadratic
+                if segment.is_quadratic() {
+                    if let Some(end) = points.last_mut() {
+                        end.point_type = PointTypeData::QCurve;
+                    }
+                }
+                pieces.push(points);
+                *start = segment.end.position();
+            }
+        }
+        for (master, pieces) in pieces.into_iter().enumerate() {
+            converted[master].push(rebuild(&masters[master][contour_index], pieces));
+        }
+    }
+    Ok(converted)
+}
+
+/// A glyph's outline with every quadratic segment raised to cubic, and the
+/// on-curve points quadratic splines imply made explicit
+pub fn to_cubic(contours: &[ContourData]) -> Vec<ContourData> {
+    contours.iter().map(contour_to_cubic).collect()
+}
+
+/// The cubic drawing the same curve as a quadratic
+fn raise(start: Point, control: Point, end: Point) -> [PointData; 3] {
+    [
+        PointData::at(
+            start + (control - start) * (2.0 / 3.0),
+            PointTypeData::OffCurve,
+        ),
+        PointData::at(end + (control - end) * (2.0 / 3.0), PointTypeData::OffCurve),
+        PointData::at(end, PointTypeData::Curve),
+    ]
+}
+
+fn contour_to_cubic(contour: &ContourData) -> ContourData {
+    if contour.points.is_empty() {
+        return contour.clone();
+    }
+    let Some(start) = first_on_curve(contour) else {
+        // Only off-curve points: every on-curve point is implied
+        let controls: Vec<Point> = contour.points.iter().map(position).collect();
+        let len = controls.len();
+        let mut points: Vec<PointData> = (0..len)
+            .flat_map(|index| {
+                let previous = controls[(index + len - 1) % len];
+                let next = controls[(index + 1) % len];
+                let control = controls[index];
+                raise(previous.midpoint(control), control, control.midpoint(next))
+            })
+            .collect();
+        // Start on the on-curve point the last piece ends at
+        points.rotate_right(1);
+        return ContourData { points };
+    };
+
+    let mut from = contour.points[start].position();
+    let pieces = segments(contour)
+        .into_iter()
+        .map(|segment| {
+            let end = segment.end.position();
+            let points: Vec<PointData> = if segment.is_quadratic() {
+                let controls: Vec<Point> = segment
+                    .off_curves
+                    .iter()
+                    .map(|&point| point.position())
+                    .collect();
+                let mut piece_start = from;
+                controls
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(index, &control)| {
+                        let piece_end = controls
+                            .get(index + 1)
+                            .map_or(end, |&next| control.midpoint(next));
+                        let piece = raise(piece_start, control, piece_end);
+                        piece_start = piece_end;
+                        piece
+                    })
+                    .collect()
+            } else {
+                segment.points()
+            };
+            from = end;
+            points
+        })
+        .collect();
+    rebuild(contour, pieces)
+}
+
+/// How many points a conversion adds to a glyph, fewer when negative
+pub fn added_points(before: &[ContourData], after: &[ContourData]) -> isize {
+    let count = |contours: &[ContourData]| -> isize {
+        contours
+            .iter()
+            .map(|contour| contour.points.len() as isize)
+            .sum()
+    };
+    count(after) - count(before)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A circle of four cubic segments
+    fn circle(radius: f64) -> ContourData {
+        let k = radius * 0.5523;
+        let (r, off, curve) = (radius, PointTypeData::OffCurve, PointTypeData::Curve);
+        ContourData {
+            points: vec![
+                PointData::test(r, 0.0, curve),
+                PointData::test(r, k, off),
+                PointData::test(k, r, off),
+                PointData::test(0.0, r, curve),
+                PointData::test(-k, r, off),
+                PointData::test(-r, k, off),
+                PointData::test(-r, 0.0, curve),
+                PointData::test(-r, -k, off),
+                PointData::test(-k, -r, off),
+                PointData::test(0.0, -r, curve),
+                PointData::test(k, -r, off),
+                PointData::test(r, -k, off),
+            ],
+        }
+    }
+
+    /// How far an outline strays from the circle `circle` approximates
+    fn circle_error(contours: &[ContourData], radius: f64) -> f64 {
+        to_cubic(contours)
+            .iter()
+            .flat_map(|contour| {
+                let first = first_on_curve(contour).map(|start| &contour.points[start]);
+                let mut from = first.map(position).unwrap_or_default();
+                segments(contour)
+                    .into_iter()
+                    .map(move |segment| {
+                        let end = segment.end.position();
+                        let [c1, c2] = segment.off_curves[..] else {
+                            panic!("not a cubic segment");
+                        };
+                        let cubic = [from, c1.position(), c2.position(), end];
+                        from = end;
+                        cubic
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .flat_map(|cubic| (0..=20).map(move |t| cubic_at(&cubic, t as f64 / 20.0)))
+            .map(|point| (point.to_vec2().hypot() - radius).abs())
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_to_quadratic_and_back() {
+        let cubic = [circle(500.0)];
+        assert_eq!(CurveFlavor::of(&cubic), CurveFlavor::Cubic);
+
+        let coarse = to_quadratic(&cubic, 5.0).unwrap();
+        let fine = to_quadratic(&cubic, 0.1).unwrap();
+        assert_eq!(CurveFlavor::of(&coarse), CurveFlavor::Quadratic);
+        // A tighter error takes more points, each segment still ending on
+        // its on-curve point
+        assert!(added_points(&cubic, &fine) > added_points(&cubic, &coarse));
+        assert_eq!(
+            coarse[0].points[0],
+            PointData::test(500.0, 0.0, PointTypeData::QCurve)
+        );
+        // Within the error of the cubic, which is itself a little off
+        let cubic_error = circle_error(&cubic, 500.0);
+        assert!(circle_error(&coarse, 500.0) < cubic_error + 5.0);
+        assert!(circle_error(&fine, 500.0) < cubic_error + 0.1);
+
+        // Back to cubic, exactly, and lines stay lines
+        let raised = to_cubic(&fine);
+        assert_eq!(CurveFlavor::of(&raised), CurveFlavor::Cubic);
+        assert!((circle_error(&raised, 500.0) - circle_error(&fine, 500.0)).abs() < 1e-9);
+        // with the on-curve points between the pieces made explicit
+        let pieces = fine[0].points.iter().filter(|p| !p.is_on_curve()).count();
+        assert_eq!(raised[0].points.len(), pieces * 3);
+        let square = ContourData {
+            points: [(0.0, 0.0), (100.0, 0.0), (100.0, 100.0)]
+                .map(|(x, y)| PointData::test(x, y, PointTypeData::Line))
+                .to_vec(),
+        };
+        assert_eq!(
+            to_quadratic(&[square.clone()], 1.0).unwrap(),
+            [square.clone()]
+        );
+        assert_eq!(to_cubic(&[square.clone()]), [square]);
+    }
+
+    #[test]
+    fn test_to_quadratic_compatible() {
+        // The wide master needs more pieces, and the narrow one gets as many
+        // and a sketch that isn't compatible is converted on its own
+        let sketch = ContourData {
+            points: circle(50.0).points[..6].to_vec(),
+        };
+        let masters = vec![
+            vec![circle(50.0)],
+            vec![sketch.clone()],
+            vec![circle(2000.0)],
+        ];
+        let converted = to_quadratic_compatible(&masters, 1.0).unwrap();
+        assert_eq!(converted.len(), 3);
+        assert!(same_structure(&converted[0], &converted[2]));
+        assert_eq!(converted[1], to_quadratic(&[sketch], 1.0).unwrap());
+        assert!(added_points(&masters[2], &converted[2]) > 4);
+        assert!(
+            added_points(&masters[0], &to_quadratic(&masters[0], 1.0).unwrap())
+                < added_points(&masters[0], &converted[0])
+        );
+
+        assert_eq!(step_max_error(1.0, true), 2.0);
+        assert_eq!(step_max_error(0.3, false), 0.25);
+        assert_eq!(step_max_error(0.1, false), 0.1);
+    }
+
+    #[test]
+    fn test_quadratic_pieces_stay_within_max_error() {
+        let cubics = [
+            [(0.0, 0.0), (0.0, 276.0), (224.0, 500.0), (500.0, 500.0)],
+            // An S bend, which no single quadratic follows
+            [(0.0, 0.0), (600.0, 0.0), (-100.0, 400.0), (500.0, 400.0)],
+            // Nearly a cusp
+            [(0.0, 0.0), (800.0, 300.0), (-300.0, 300.0), (500.0, 0.0)],
+        ];
+        for cubic in cubics.map(|cubic| cubic.map(|(x, y)| Point::new(x, y))) {
+            let [p0, p1, p2, p3] = cubic;
+            let contour = ContourData {
+                points: vec![
+                    PointData::test(p0.x, p0.y, PointTypeData::Move),
+                    PointData::test(p1.x, p1.y, PointTypeData::OffCurve),
+                    PointData::test(p2.x, p2.y, PointTypeData::OffCurve),
+                    PointData::test(p3.x, p3.y, PointTypeData::Curve),
+                ],
+            };
+            for max_error in MAX_ERROR_STEPS {
+                let converted = to_quadratic(&[contour.clone()], max_error).unwrap();
+                let controls: Vec<Point> = converted[0].points[1..converted[0].points.len() - 1]
+                    .iter()
+                    .map(position)
+                    .collect();
+                let pieces = controls.len();
+                let implied = |index: usize| match index {
+                    0 => p0,
+                    i if i == pieces => p3,
+                    i => controls[i - 1].midpoint(controls[i]),
+                };
+                // Each piece, at every step along it, is close to the cubic
+                // at the same step along its part
+                for index in 0..pieces {
+                    let (start, control, end) =
+                        (implied(index), controls[index], implied(index + 1));
+                    for step in 0..=50 {
+                        let t = step as f64 / 50.0;
+                        let on_piece = start.lerp(control, t).lerp(control.lerp(end, t), t);
+                        let on_cubic = cubic_at(&cubic, (index as f64 + t) / pieces as f64);
+                        assert!((on_piece - on_cubic).hypot() <= max_error + 1e-9);
+                    }
+                }
+            }
+        }
+
+        // An error no spline of `MAX_PIECES` pieces keeps to fails
+        assert!(to_quadratic(&[circle(1000.0)], 1e-6).is_err());
+    }
+}
@@ -6,17 +6,56 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use kurbo::Point;
+
+use super::anchor_sync::AnchorSync;
+use super::color::ColorData;
+use super::design_frames::DesignFrames;
+use super::export_profiles::ExportProfiles;
+use super::glyph_filters::GlyphFilters;
+use super::glyph_order::GlyphOrder;
+use super::kerning::KerningData;
+use super::layers::GlyphLayer;
+use super::session::WorkspaceSession;
+use super::smart_pieces::SmartPieceData;
+use super::stat::StatConfig;
+
 /// Thread-safe font data structure
 #[derive(Clone, Default)]
 pub struct FontData {
-    /// All glyph data extracted from norad and stored thread-safely
+    /// Glyphs of the default (foreground) layer, extracted from norad and
+    /// stored thread-safely
     pub glyphs: HashMap<String, GlyphData>,
     /// Path to the UFO file (for saving)
     pub path: Option<PathBuf>,
+    /// Color layers and palettes (COLR/CPAL)
+    pub color: ColorData,
+    /// Non-default UFO layers (background, sketches, alternates)
+    pub layers: Vec<GlyphLayer>,
+    /// Layer the editor draws and edits (`None` means the default layer)
+    pub editing_layer: Option<String>,
+    /// Links between smart piece masters and the glyphs they are stamped in
+    pub smart_pieces: SmartPieceData,
+    /// Kerning groups and pairs
+    pub kerning: KerningData,
+    /// How the project is exported
+    pub export_profiles: ExportProfiles,
+    /// How the family's fonts group into styles (the STAT table)
+    pub stat: StatConfig,
+    /// Reference frames drawn inside the em box
+    pub design_frames: DesignFrames,
+    /// Filter recipes of glyphs drawn from a source outline
+    pub glyph_filters: GlyphFilters,
+    /// Glyphs whose components stay put when anchors they sit on move
+    pub anchor_sync: AnchorSync,
+    /// Build order and production names of the glyphs
+    pub glyph_order: GlyphOrder,
+    /// Where editing stopped when the project was last saved
+    pub session: Option<WorkspaceSession>,
 }
 
 /// Thread-safe glyph data
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct GlyphData {
     /// Glyph name
     pub name: String,
@@ -30,10 +69,21 @@ pub struct GlyphData {
     pub outline: Option<OutlineData>,
     /// Component references for composite glyphs
     pub components: Vec<ComponentData>,
+    /// Named attachment points used to position marks and components
+    pub anchors: Vec<AnchorData>,
+}
+
+/// Thread-safe anchor data
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnchorData {
+    /// Anchor name (e.g. "top", or "_top" on a mark)
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
 }
 
 /// Thread-safe component data for composite glyphs
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ComponentData {
     /// Name of the base glyph being referenced
     pub base_glyph: String,
@@ -52,21 +102,21 @@ impl Default for ComponentData {
 }
 
 /// Thread-safe outline data
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct OutlineData {
     /// Contour data
     pub contours: Vec<ContourData>,
 }
 
 /// Thread-safe contour data
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ContourData {
     /// Points in this contour
     pub points: Vec<PointData>,
 }
 
 /// Thread-safe point data
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct PointData {
     /// X coordinate
     pub x: f64,
@@ -116,9 +166,494 @@ impl GlyphData {
     }
 }
 
+impl PointData {
+    /// A point of this type at a position
+    pub fn at(point: Point, point_type: PointTypeData) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            point_type,
+        }
+    }
+
+    /// Where the point is
+    pub fn position(&self) -> Point {
+        Point::new(self.x, self.y)
+    }
+
+    /// Whether the point is on the curve rather than a handle
+    pub fn is_on_curve(&self) -> bool {
+        self.point_type != PointTypeData::OffCurve
+    }
+}
+
+impl ContourData {
+    /// Whether the contour is open (starts with a move point)
+    pub fn is_open(&self) -> bool {
+        self.points
+            .first()
+            .is_some_and(|point| point.point_type == PointTypeData::Move)
+    }
+
+    /// The same contour traced in the opposite direction
+    ///
+    /// On-curve point types describe the segment that ends at the point, so
+    /// each one takes the type of the next on-curve point in the original
+    /// order. Closed contours keep their start point.
+    pub fn reversed(&self) -> ContourData {
+        let len = self.points.len();
+        let is_on_curve = |i: usize| self.points[i].point_type != PointTypeData::OffCurve;
+        let next_on_curve_type = |i: usize| {
+            (1..=len)
+                .map(|step| (i + step) % len)
+                .find(|&j| is_on_curve(j))
+                .map(|j| self.points[j].point_type)
+                .unwrap_or(PointTypeData::Line)
+        };
+
+        let retyped: Vec<PointData> = (0..len)
+            .map(|i| {
+                let mut point = self.points[i].clone();
+                if is_on_curve(i) {
+                    point.point_type = match next_on_curve_type(i) {
+                        PointTypeData::Move => PointTypeData::Line,
+                        other => other,
+                    };
+                }
+                point
+            })
+            .collect();
+
+        let mut points: Vec<PointData> = if self.is_open() {
+            retyped.into_iter().rev().collect()
+        } else {
+            let mut points: Vec<PointData> = retyped.into_iter().rev().collect();
+            points.rotate_right(1);
+            points
+        };
+        if self.is_open() {
+            if let Some(first) = points.first_mut() {
+                first.point_type = PointTypeData::Move;
+            }
+        }
+
+        ContourData { points }
+    }
+
+    /// The contour with its ends joined: an open contour's move point
+    /// becomes a line point
+    pub fn closed(&self) -> ContourData {
+        let mut contour = self.clone();
+        if let Some(first) = contour.points.first_mut() {
+            if first.point_type == PointTypeData::Move {
+                first.point_type = PointTypeData::Line;
+            }
+        }
+        contour
+    }
+
+    /// The same closed contour starting at the on-curve point `index`
+    ///
+    /// Open contours start at their move point and off-curve points can't
+    /// start a contour, so those are left as they are.
+    pub fn with_start_point(&self, index: usize) -> ContourData {
+        let mut contour = self.clone();
+        let on_curve = self
+            .points
+            .get(index)
+            .is_some_and(|point| point.point_type != PointTypeData::OffCurve);
+        if on_curve && !self.is_open() {
+            contour.points.rotate_left(index);
+        }
+        contour
+    }
+
+    /// Indices of the off-curve points leading into the point at `index`,
+    /// nearest first
+    fn incoming_handles(&self, index: usize) -> Vec<usize> {
+        let len = self.points.len();
+        (1..len)
+            .map(|step| (index + len - step) % len)
+            .take_while(|&i| self.points[i].point_type == PointTypeData::OffCurve)
+            .collect()
+    }
+
+    /// Indices of the off-curve points leaving the point at `index`
+    fn outgoing_handles(&self, index: usize) -> Vec<usize> {
+        let len = self.points.len();
+        (1..len)
+            .map(|step| (index + step) % len)
+            .take_while(|&i| self.points[i].point_type == PointTypeData::OffCurve)
+            .collect()
+    }
+
+    /// The contour with the points at `indices` removed, starting on an
+    /// on-curve point again
+    fn without_indices(&self, indices: &[usize]) -> ContourData {
+        let mut points: Vec<PointData> = self
+            .points
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !indices.contains(i))
+            .map(|(_, point)| point.clone())
+            .collect();
+        if let Some(start) = points
+            .iter()
+            .position(|point| point.point_type != PointTypeData::OffCurve)
+        {
+            points.rotate_left(start);
+        }
+        ContourData { points }
+    }
+
+    /// The contour with the segment ending at the on-curve point `index`
+    /// made a line or a curve
+    ///
+    /// A line loses the segment's handles. A line made a curve gets handles
+    /// a third of the way from each end, so its shape doesn't change until
+    /// they're moved.
+    pub fn with_segment_type(&self, index: usize, point_type: PointTypeData) -> ContourData {
+        let Some(point) = self.points.get(index) else {
+            return self.clone();
+        };
+        if matches!(
+            point.point_type,
+            PointTypeData::Move | PointTypeData::OffCurve
+        ) || point.point_type == point_type
+        {
+            return self.clone();
+        }
+
+        let handles = self.incoming_handles(index);
+        match point_type {
+            PointTypeData::Line => {
+                let mut contour = self.clone();
+                contour.points[index].point_type = PointTypeData::Line;
+                contour.without_indices(&handles)
+            }
+            PointTypeData::Curve if handles.is_empty() => {
+                let len = self.points.len();
+                let start = &self.points[(index + len - 1) % len];
+                let handle = |t: f64| PointData {
+                    x: start.x + (point.x - start.x) * t,
+                    y: start.y + (point.y - start.y) * t,
+                    point_type: PointTypeData::OffCurve,
+                };
+                let mut contour = self.clone();
+                contour.points[index].point_type = PointTypeData::Curve;
+                // The closing segment's handles go after the last point
+                let at = if index == 0 { len } else { index };
+                contour
+                    .points
+                    .splice(at..at, [handle(1.0 / 3.0), handle(2.0 / 3.0)]);
+                contour
+            }
+            PointTypeData::Curve => {
+                let mut contour = self.clone();
+                contour.points[index].point_type = PointTypeData::Curve;
+                contour
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// The contour without the point at `index`, or `None` when fewer than
+    /// two on-curve points would be left
+    ///
+    /// An on-curve point goes with its incoming handles, so the segments on
+    /// either side merge into the outgoing one; an open contour's first point
+    /// takes its outgoing handles instead. A handle takes the other handles
+    /// of its segment with it, leaving a line.
+    pub fn without_point(&self, index: usize) -> Option<ContourData> {
+        let Some(point) = self.points.get(index) else {
+            return Some(self.clone());
+        };
+        let mut contour = self.clone();
+        let removed = match point.point_type {
+            PointTypeData::OffCurve => {
+                let mut removed = vec![index];
+                removed.extend(self.incoming_handles(index));
+                let outgoing = self.outgoing_handles(index);
+                let segment_end =
+                    outgoing.last().map_or(index + 1, |&last| last + 1) % self.points.len();
+                removed.extend(outgoing);
+                if contour.points[segment_end].point_type != PointTypeData::Move {
+                    contour.points[segment_end].point_type = PointTypeData::Line;
+                }
+                removed
+            }
+            PointTypeData::Move => {
+                let mut removed = vec![index];
+                let outgoing = self.outgoing_handles(index);
+                let next = outgoing.last().map_or(index + 1, |&last| last + 1);
+                removed.extend(outgoing);
+                if let Some(next) = contour.points.get_mut(next) {
+                    next.point_type = PointTypeData::Move;
+                }
+                removed
+            }
+            _ => {
+                let mut removed = vec![index];
+                removed.extend(self.incoming_handles(index));
+                removed
+            }
+        };
+
+        let contour = contour.without_indices(&removed);
+        let on_curve = contour
+            .points
+            .iter()
+            .filter(|point| point.point_type != PointTypeData::OffCurve)
+            .count();
+        (on_curve >= 2).then_some(contour)
+    }
+
+    /// The on-curve point a handle belongs to: the point before it for the
+    /// first handle of a segment, the point after it for the last one
+    pub fn handle_owner(&self, index: usize) -> Option<usize> {
+        if self.points.get(index)?.point_type != PointTypeData::OffCurve {
+            return None;
+        }
+        let len = self.points.len();
+        let is_on_curve = |i: usize| self.points[i].point_type != PointTypeData::OffCurve;
+        let previous = (index + len - 1) % len;
+        let next = (index + 1) % len;
+        if is_on_curve(previous) {
+            Some(previous)
+        } else if is_on_curve(next) {
+            Some(next)
+        } else {
+            None
+        }
+    }
+
+    /// The length of the handle at `index` and its angle in degrees,
+    /// counterclockwise from the x axis, seen from its on-curve point
+    pub fn handle_polar(&self, index: usize) -> Option<(f64, f64)> {
+        let owner = &self.points[self.handle_owner(index)?];
+        let handle = &self.points[index];
+        let (dx, dy) = (handle.x - owner.x, handle.y - owner.y);
+        Some((dx.hypot(dy), dy.atan2(dx).to_degrees()))
+    }
+
+    /// The contour with the handle at `index` moved to `length` units from
+    /// its on-curve point at `angle` degrees
+    pub fn with_handle_polar(&self, index: usize, length: f64, angle: f64) -> ContourData {
+        let mut contour = self.clone();
+        if let Some(owner) = self.handle_owner(index) {
+            let (sin, cos) = angle.to_radians().sin_cos();
+            let owner = &self.points[owner];
+            contour.points[index].x = owner.x + length * cos;
+            contour.points[index].y = owner.y + length * sin;
+        }
+        contour
+    }
+
+    /// The handles on either side of the on-curve point at `index`, when it
+    /// has both
+    pub fn handle_pair(&self, index: usize) -> Option<(usize, usize)> {
+        if self.points.get(index)?.point_type == PointTypeData::OffCurve {
+            return None;
+        }
+        let incoming = *self.incoming_handles(index).first()?;
+        let outgoing = *self.outgoing_handles(index).first()?;
+        (incoming != outgoing).then_some((incoming, outgoing))
+    }
+
+    /// The contour with both handles of the on-curve point at `index` as
+    /// long as their average, each keeping its direction
+    pub fn with_equal_handles(&self, index: usize) -> ContourData {
+        let Some((incoming, outgoing)) = self.handle_pair(index) else {
+            return self.clone();
+        };
+        let (Some((in_length, in_angle)), Some((out_length, out_angle))) =
+            (self.handle_polar(incoming), self.handle_polar(outgoing))
+        else {
+            return self.clone();
+        };
+        let length = (in_length + out_length) / 2.0;
+        self.with_handle_polar(incoming, length, in_angle)
+            .with_handle_polar(outgoing, length, out_angle)
+    }
+
+    /// The contour with both handles of the cubic segment the handle at
+    /// `index` is on a third of the distance between its ends long
+    ///
+    /// Handles keep their directions; a handle sitting on its point points
+    /// at the segment's other end.
+    pub fn with_handles_at_thirds(&self, index: usize) -> ContourData {
+        let Some(owner) = self.handle_owner(index) else {
+            return self.clone();
+        };
+        let start = if self.outgoing_handles(owner).contains(&index) {
+            owner
+        } else {
+            let handles = self.incoming_handles(owner);
+            let len = self.points.len();
+            (handles.last().copied().unwrap_or(owner) + len - 1) % len
+        };
+        let handles = self.outgoing_handles(start);
+        let [first, second] = handles[..] else {
+            return self.clone();
+        };
+        let end = (second + 1) % self.points.len();
+        let (from, to) = (&self.points[start], &self.points[end]);
+        let length = (to.x - from.x).hypot(to.y - from.y) / 3.0;
+
+        let mut contour = self.clone();
+        for (handle, owner, other) in [(first, from, to), (second, to, from)] {
+            let point = &self.points[handle];
+            let (mut dx, mut dy) = (point.x - owner.x, point.y - owner.y);
+            if dx == 0.0 && dy == 0.0 {
+                (dx, dy) = (other.x - owner.x, other.y - owner.y);
+            }
+            let scale = length / dx.hypot(dy).max(f64::EPSILON);
+            contour.points[handle].x = owner.x + dx * scale;
+            contour.points[handle].y = owner.y + dy * scale;
+        }
+        contour
+    }
+}
+
+impl GlyphData {
+    /// Find an anchor by name
+    pub fn anchor(&self, name: &str) -> Option<&AnchorData> {
+        self.anchors.iter().find(|anchor| anchor.name == name)
+    }
+}
+
+#[cfg(test)]
+impl GlyphData {
+    /// An empty glyph for tests; set the other fields with
+    /// `GlyphData { outline, ..GlyphData::test("a", 500.0) }`
+    pub fn test(name: &str, advance_width: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            advance_width,
+            advance_height: None,
+            unicode_values: Vec::new(),
+            outline: None,
+            components: Vec::new(),
+            anchors: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl PointData {
+    /// A point for tests
+    pub fn test(x: f64, y: f64, point_type: PointTypeData) -> Self {
+        Self { x, y, point_type }
+    }
+}
+
+#[cfg(test)]
+impl ContourData {
+    /// A closed square of lines for tests, counter-clockwise from the
+    /// origin
+    pub fn test_square(size: f64) -> Self {
+        let corners = [(0.0, 0.0), (size, 0.0), (size, size), (0.0, size)];
+        Self {
+            points: corners
+                .map(|(x, y)| PointData::test(x, y, PointTypeData::Line))
+                .to_vec(),
+        }
+    }
+}
+
 impl FontData {
-    /// Get a glyph by name
+    /// Get a foreground glyph by name
     pub fn get_glyph(&self, name: &str) -> Option<&GlyphData> {
         self.glyphs.get(name)
     }
+
+    /// A foreground glyph's own contours plus all component contours,
+    /// flattened into glyph space
+    pub fn resolved_bezpaths(&self, name: &str) -> Vec<kurbo::BezPath> {
+        let foreground = LayerGlyphs {
+            glyphs: &self.glyphs,
+        };
+        foreground.resolved_bezpaths(name)
+    }
+
+    /// Left and right sidebearings of a foreground glyph, components
+    /// included
+    pub fn sidebearings(&self, name: &str) -> Option<(f64, f64)> {
+        let foreground = LayerGlyphs {
+            glyphs: &self.glyphs,
+        };
+        foreground.sidebearings(name)
+    }
+}
+
+/// The glyphs of one layer; components resolve to glyphs of the same layer
+#[derive(Clone, Copy)]
+pub struct LayerGlyphs<'a> {
+    pub glyphs: &'a HashMap<String, GlyphData>,
+}
+
+impl<'a> LayerGlyphs<'a> {
+    /// Get a glyph by name
+    pub fn get_glyph(&self, name: &str) -> Option<&'a GlyphData> {
+        self.glyphs.get(name)
+    }
+
+    /// A glyph's own contours plus all component contours, flattened into
+    /// glyph space. Cyclic or missing component references are skipped.
+    pub fn resolved_bezpaths(&self, name: &str) -> Vec<kurbo::BezPath> {
+        let mut paths = Vec::new();
+        self.collect_bezpaths(name, kurbo::Affine::IDENTITY, &mut Vec::new(), &mut paths);
+        paths
+    }
+
+    /// Left and right sidebearings of a glyph, components included
+    pub fn sidebearings(&self, name: &str) -> Option<(f64, f64)> {
+        use kurbo::Shape;
+        let glyph = self.glyphs.get(name)?;
+        let bounds = self
+            .resolved_bezpaths(name)
+            .iter()
+            .map(|path| path.bounding_box())
+            .reduce(|a, b| a.union(b))?;
+        Some((bounds.x0, glyph.advance_width - bounds.x1))
+    }
+
+    /// Only the component contours of a glyph, flattened into glyph space
+    pub fn component_bezpaths(&self, name: &str) -> Vec<kurbo::BezPath> {
+        let mut paths = Vec::new();
+        if let Some(glyph) = self.glyphs.get(name) {
+            let mut visiting = vec![name.to_string()];
+            for component in &glyph.components {
+                let transform = kurbo::Affine::new(component.transform);
+                self.collect_bezpaths(&component.base_glyph, transform, &mut visiting, &mut paths);
+            }
+        }
+        paths
+    }
+
+    fn collect_bezpaths(
+        &self,
+        name: &str,
+        transform: kurbo::Affine,
+        visiting: &mut Vec<String>,
+        paths: &mut Vec<kurbo::BezPath>,
+    ) {
+        let Some(glyph) = self.glyphs.get(name) else {
+            return;
+        };
+        if visiting.iter().any(|visited| visited == name) {
+            return;
+        }
+        visiting.push(name.to_string());
+
+        if let Some(outline) = &glyph.outline {
+            paths.extend(outline.to_bezpaths().into_iter().map(|path| transform * path));
+        }
+        for component in &glyph.components {
+            let component_transform = transform * kurbo::Affine::new(component.transform);
+            self.collect_bezpaths(&component.base_glyph, component_transform, visiting, paths);
+        }
+
+        visiting.pop();
+    }
 }
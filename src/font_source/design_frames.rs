@@ -0,0 +1,139 @@
+//! CJK design frames
+//!
+//! Ideographs are drawn against reference frames inside the em box: the
+//! center lines, inner frames the strokes should reach (each a percentage of
+//! the em, centered in it) and a grid dividing the em into thirds. The
+//! frames of a project are stored in the font lib so they travel with it;
+//! nothing is stored while they are the default.
+
+use kurbo::{Point, Rect};
+use serde::{Deserialize, Serialize};
+
+/// Font lib key holding the project's design frames
+pub const DESIGN_FRAMES_LIB_KEY: &str = "org.bezy.designFrames";
+
+/// Smallest and largest inner frame, in percent of the em
+pub const INNER_FRAME_RANGE: (f64, f64) = (10.0, 100.0);
+
+/// Which reference frames are drawn inside each em box
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DesignFrames {
+    pub center_lines: bool,
+    /// Inner frames in percent of the em box
+    pub inner_frames: Vec<f64>,
+    pub third_grid: bool,
+}
+
+impl Default for DesignFrames {
+    fn default() -> Self {
+        Self {
+            center_lines: true,
+            inner_frames: vec![90.0],
+            third_grid: false,
+        }
+    }
+}
+
+impl DesignFrames {
+    /// Read frames from a UFO font lib, falling back to the default
+    pub fn from_norad_lib(lib: &norad::Plist) -> Self {
+        lib.get(DESIGN_FRAMES_LIB_KEY)
+            .and_then(|value| serde_json::to_value(value).ok())
+            .and_then(|json| serde_json::from_value(json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write frames into a UFO font lib, unless they are the default
+    pub fn write_to_norad_lib(&self, lib: &mut norad::Plist) {
+        lib.remove(DESIGN_FRAMES_LIB_KEY);
+        if *self == Self::default() {
+            return;
+        }
+        if let Ok(value) = serde_json::to_value(self).and_then(serde_json::from_value) {
+            lib.insert(DESIGN_FRAMES_LIB_KEY.to_string(), value);
+        }
+    }
+
+    /// Grows or shrinks an inner frame, keeping it within `INNER_FRAME_RANGE`
+    pub fn resize_inner_frame(&mut self, index: usize, delta: f64) {
+        if let Some(percent) = self.inner_frames.get_mut(index) {
+            let (min, max) = INNER_FRAME_RANGE;
+            *percent = (*percent + delta).clamp(min, max);
+        }
+    }
+
+    /// Line segments of the frames inside `em_box`, its outline included
+    pub fn frame_lines(&self, em_box: Rect) -> Vec<(Point, Point)> {
+        let mut lines = rect_lines(em_box);
+        let center = em_box.center();
+        if self.center_lines {
+            lines.push(((center.x, em_box.y0).into(), (center.x, em_box.y1).into()));
+            lines.push(((em_box.x0, center.y).into(), (em_box.x1, center.y).into()));
+        }
+        if self.third_grid {
+            for third in [1.0 / 3.0, 2.0 / 3.0] {
+                let x = em_box.x0 + em_box.width() * third;
+                let y = em_box.y0 + em_box.height() * third;
+                lines.push(((x, em_box.y0).into(), (x, em_box.y1).into()));
+                lines.push(((em_box.x0, y).into(), (em_box.x1, y).into()));
+            }
+        }
+        for percent in &self.inner_frames {
+            let scale = percent / 100.0;
+            let size = em_box.size() * scale;
+            lines.extend(rect_lines(Rect::from_center_size(center, size)));
+        }
+        lines
+    }
+}
+
+/// The four sides of a rectangle
+fn rect_lines(rect: Rect) -> Vec<(Point, Point)> {
+    let corners = [
+        Point::new(rect.x0, rect.y0),
+        Point::new(rect.x1, rect.y0),
+        Point::new(rect.x1, rect.y1),
+        Point::new(rect.x0, rect.y1),
+    ];
+    (0..4).map(|i| (corners[i], corners[(i + 1) % 4])).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_lines() {
+        let em_box = Rect::new(0.0, -120.0, 1000.0, 880.0);
+        let frames = DesignFrames {
+            center_lines: true,
+            inner_frames: vec![90.0],
+            third_grid: true,
+        };
+        let lines = frames.frame_lines(em_box);
+        // Em box, center lines, thirds and the inner frame
+        assert_eq!(lines.len(), 4 + 2 + 4 + 4);
+        assert!(lines.contains(&((500.0, -120.0).into(), (500.0, 880.0).into())));
+        assert!(lines.contains(&((50.0, -70.0).into(), (950.0, -70.0).into())));
+
+        let mut frames = DesignFrames::default();
+        frames.resize_inner_frame(0, 20.0);
+        assert_eq!(frames.inner_frames, vec![100.0]);
+    }
+
+    #[test]
+    fn test_lib_round_trip_keeps_default_out_of_the_lib() {
+        let mut lib = norad::Plist::new();
+        DesignFrames::default().write_to_norad_lib(&mut lib);
+        assert!(lib.is_empty());
+
+        let frames = DesignFrames {
+            center_lines: false,
+            inner_frames: vec![92.0, 80.0],
+            third_grid: true,
+        };
+        frames.write_to_norad_lib(&mut lib);
+        assert_eq!(DesignFrames::from_norad_lib(&lib), frames);
+    }
+}
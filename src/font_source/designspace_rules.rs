@@ -0,0 +1,433 @@
+//! Designspace substitution rules
+//!
+//! A variable font can swap glyphs in part of its designspace, say a dollar
+//! without the stroke through it above weight 700, with the designspace's
+//! rules (compiled into the `rvrn` feature). A rule has condition sets, each
+//! a range on one or more axes, and applies wherever every condition of one
+//! of its sets is met. Bezy edits the rules of the designspace in the UFO's
+//! folder that lists the UFO as a source; conditions are in design
+//! coordinates.
+
+use anyhow::{Context, Result};
+use norad::designspace::{Condition, ConditionSet, DesignSpaceDocument, Rule, Substitution};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use super::data::FontData;
+
+/// How many steps a condition or the preview location takes across an axis
+const AXIS_STEPS: f64 = 20.0;
+
+/// A value on each axis, by axis name
+pub type Location = BTreeMap<String, f64>;
+
+/// An axis of the designspace
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuleAxis {
+    pub name: String,
+    pub minimum: f64,
+    pub default: f64,
+    pub maximum: f64,
+}
+
+impl RuleAxis {
+    /// `value` moved a step up or down the axis, staying on it
+    pub fn step(&self, value: f64, forward: bool) -> f64 {
+        let step = (self.maximum - self.minimum) / AXIS_STEPS;
+        let value = if forward { value + step } else { value - step };
+        value.clamp(self.minimum, self.maximum)
+    }
+}
+
+/// A range on an axis, open on a side without a bound
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuleCondition {
+    pub axis: String,
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+}
+
+impl RuleCondition {
+    pub fn contains(&self, value: f64) -> bool {
+        self.minimum.is_none_or(|minimum| value >= minimum)
+            && self.maximum.is_none_or(|maximum| value <= maximum)
+    }
+
+    /// The condition as the rules pane shows it
+    pub fn label(&self) -> String {
+        match (self.minimum, self.maximum) {
+            (Some(minimum), Some(maximum)) => format!("{minimum}–{maximum} {}", self.axis),
+            (Some(minimum), None) => format!("{} ≥ {minimum}", self.axis),
+            (None, Some(maximum)) => format!("{} ≤ {maximum}", self.axis),
+            (None, None) => format!("any {}", self.axis),
+        }
+    }
+}
+
+/// A substitution rule
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubstitutionRule {
+    pub name: String,
+    pub condition_sets: Vec<Vec<RuleCondition>>,
+    /// Glyph replaced and the glyph replacing it
+    pub substitutions: Vec<(String, String)>,
+}
+
+impl SubstitutionRule {
+    /// Whether every condition of one of the rule's sets is met at
+    /// `location`
+    pub fn applies_at(&self, location: &Location) -> bool {
+        self.condition_sets.iter().any(|set| {
+            set.iter().all(|condition| {
+                location
+                    .get(&condition.axis)
+                    .is_some_and(|value| condition.contains(*value))
+            })
+        })
+    }
+
+    fn from_norad(rule: &Rule) -> Self {
+        Self {
+            name: rule.name.clone().unwrap_or_default(),
+            condition_sets: rule
+                .condition_sets
+                .iter()
+                .map(|set| {
+                    set.conditions
+                        .iter()
+                        .map(|condition| RuleCondition {
+                            axis: condition.name.clone(),
+                            minimum: condition.minimum.map(f64::from),
+                            maximum: condition.maximum.map(f64::from),
+                        })
+                        .collect()
+                })
+                .collect(),
+            substitutions: rule
+                .substitutions
+                .iter()
+                .map(|sub| (sub.name.to_string(), sub.with.to_string()))
+                .collect(),
+        }
+    }
+
+    fn to_norad(&self) -> Rule {
+        Rule {
+            name: (!self.name.is_empty()).then(|| self.name.clone()),
+            condition_sets: self
+                .condition_sets
+                .iter()
+                .map(|set| ConditionSet {
+                    conditions: set
+                        .iter()
+                        .map(|condition| Condition {
+                            name: condition.axis.clone(),
+                            minimum: condition.minimum.map(|minimum| minimum as _),
+                            maximum: condition.maximum.map(|maximum| maximum as _),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            substitutions: self
+                .substitutions
+                .iter()
+                .filter_map(|(name, with)| {
+                    Some(Substitution {
+                        name: name.parse().ok()?,
+                        with: with.parse().ok()?,
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The designspace in a UFO's folder that lists the UFO as a source, and
+/// its path
+pub fn find_designspace_for_ufo(ufo_path: &Path) -> Result<Option<(PathBuf, DesignSpaceDocument)>> {
+    let Some(folder) = ufo_path.parent() else {
+        return Ok(None);
+    };
+    let ufo_path = ufo_path.canonicalize().unwrap_or(ufo_path.to_path_buf());
+    let mut designspaces: Vec<PathBuf> = std::fs::read_dir(folder)
+        .with_context(|| format!("Failed to read {}", folder.display()))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "designspace"))
+        .collect();
+    designspaces.sort();
+
+    for path in designspaces {
+        let document = DesignSpaceDocument::load(&path)
+            .with_context(|| format!("Failed to load {}", path.display()))?;
+        let lists_ufo = document.sources.iter().any(|source| {
+            let source_path = folder.join(&source.filename);
+            source_path.canonicalize().unwrap_or(source_path) == ufo_path
+        });
+        if lists_ufo {
+            return Ok(Some((path, document)));
+        }
+    }
+    Ok(None)
+}
+
+/// The axes of a designspace, in design coordinates
+pub fn designspace_axes(document: &DesignSpaceDocument) -> Vec<RuleAxis> {
+    document
+        .axes
+        .iter()
+        .map(|axis| {
+            let default = f64::from(axis.default);
+            RuleAxis {
+                name: axis.name.clone(),
+                minimum: axis.minimum.map_or(default, f64::from),
+                default,
+                maximum: axis.maximum.map_or(default, f64::from),
+            }
+        })
+        .collect()
+}
+
+/// The axes and rules of a designspace
+#[derive(Clone, Debug, PartialEq)]
+pub struct DesignspaceRules {
+    /// The designspace file the rules are read from and written to
+    pub path: PathBuf,
+    pub axes: Vec<RuleAxis>,
+    pub rules: Vec<SubstitutionRule>,
+}
+
+impl DesignspaceRules {
+    /// The rules of the designspace next to a UFO that lists it as a
+    /// source, if there is one
+    pub fn find_for_ufo(ufo_path: &Path) -> Result<Option<Self>> {
+        let designspace = find_designspace_for_ufo(ufo_path)?;
+        Ok(designspace.map(|(path, document)| Self {
+            axes: designspace_axes(&document),
+            rules: document
+                .rules
+                .rules
+                .iter()
+                .map(SubstitutionRule::from_norad)
+                .collect(),
+            path,
+        }))
+    }
+
+    /// Write the rules into the designspace, keeping the rest of it
+    pub fn save(&self) -> Result<()> {
+        let mut document = DesignSpaceDocument::load(&self.path)
+            .with_context(|| format!("Failed to load {}", self.path.display()))?;
+        document.rules.rules = self.rules.iter().map(SubstitutionRule::to_norad).collect();
+        document
+            .save(&self.path)
+            .with_context(|| format!("Failed to save {}", self.path.display()))
+    }
+
+    pub fn axis(&self, name: &str) -> Option<&RuleAxis> {
+        self.axes.iter().find(|axis| axis.name == name)
+    }
+
+    /// The location of the designspace's default source
+    pub fn default_location(&self) -> Location {
+        self.axes
+            .iter()
+            .map(|axis| (axis.name.clone(), axis.default))
+            .collect()
+    }
+
+    /// The glyph shown for `glyph_name` at `location`, after every rule
+    /// that applies there
+    pub fn substitute<'a>(&'a self, glyph_name: &'a str, location: &Location) -> &'a str {
+        self.rules
+            .iter()
+            .filter(|rule| rule.applies_at(location))
+            .fold(glyph_name, |name, rule| {
+                rule.substitutions
+                    .iter()
+                    .find(|(from, _)| from == name)
+                    .map_or(name, |(_, to)| to.as_str())
+            })
+    }
+
+    /// Follow renamed glyphs in the substitutions, returning whether any
+    /// changed
+    pub fn rename_glyphs(&mut self, renames: &HashMap<String, String>) -> bool {
+        let mut changed = false;
+        let substitutions = self
+            .rules
+            .iter_mut()
+            .flat_map(|rule| &mut rule.substitutions);
+        for name in substitutions.flat_map(|(from, to)| [from, to]) {
+            if let Some(new) = renames.get(name.as_str()) {
+                *name = new.clone();
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Add a rule replacing `from` with `to` in the upper half of the first
+    /// axis, from its default up
+    pub fn add_rule(&mut self, from: &str, to: &str) {
+        let conditions = self
+            .axes
+            .first()
+            .map(|axis| RuleCondition {
+                axis: axis.name.clone(),
+                minimum: Some(((axis.default + axis.maximum) / 2.0).round()),
+                maximum: Some(axis.maximum),
+            })
+            .into_iter()
+            .collect();
+        self.rules.push(SubstitutionRule {
+            name: to.to_string(),
+            condition_sets: vec![conditions],
+            substitutions: vec![(from.to_string(), to.to_string())],
+        });
+    }
+
+    /// Add a condition to a rule's first set, on the first axis it doesn't
+    /// have one on yet
+    pub fn add_condition(&mut self, rule_index: usize) {
+        let Some(rule) = self.rules.get_mut(rule_index) else {
+            return;
+        };
+        if rule.condition_sets.is_empty() {
+            rule.condition_sets.push(Vec::new());
+        }
+        let set = &mut rule.condition_sets[0];
+        let Some(axis) = self
+            .axes
+            .iter()
+            .find(|axis| set.iter().all(|condition| condition.axis != axis.name))
+        else {
+            return;
+        };
+        set.push(RuleCondition {
+            axis: axis.name.clone(),
+            minimum: Some(axis.minimum),
+            maximum: Some(axis.maximum),
+        });
+    }
+
+    /// Move a bound of a condition in a rule's first set a step up or down
+    /// its axis
+    pub fn step_condition(
+        &mut self,
+        rule_index: usize,
+        condition_index: usize,
+        maximum: bool,
+        forward: bool,
+    ) {
+        let Some(condition) = self
+            .rules
+            .get_mut(rule_index)
+            .and_then(|rule| rule.condition_sets.first_mut())
+            .and_then(|set| set.get_mut(condition_index))
+        else {
+            return;
+        };
+        let Some(axis) = self.axes.iter().find(|axis| axis.name == condition.axis) else {
+            return;
+        };
+        let (bound, unbounded) = if maximum {
+            (&mut condition.maximum, axis.maximum)
+        } else {
+            (&mut condition.minimum, axis.minimum)
+        };
+        *bound = Some(axis.step(bound.unwrap_or(unbounded), forward));
+    }
+
+    /// What's wrong with a rule, for the rules pane
+    pub fn rule_problems(&self, rule: &SubstitutionRule, font: &FontData) -> Vec<String> {
+        let mut problems = Vec::new();
+        if rule.condition_sets.iter().all(Vec::is_empty) {
+            problems.push("no conditions, applies everywhere".to_string());
+        }
+        for condition in rule.condition_sets.iter().flatten() {
+            if self.axis(&condition.axis).is_none() {
+                problems.push(format!("no axis named '{}'", condition.axis));
+            }
+            if let (Some(minimum), Some(maximum)) = (condition.minimum, condition.maximum) {
+                if minimum > maximum {
+                    problems.push(format!("{}: minimum above maximum", condition.axis));
+                }
+            }
+        }
+        if rule.substitutions.is_empty() {
+            problems.push("no substitutions".to_string());
+        }
+        for (from, to) in &rule.substitutions {
+            for name in [from, to] {
+                if font.get_glyph(name).is_none() {
+                    problems.push(format!("'{name}' is not in the font"));
+                }
+            }
+        }
+        problems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_designspace_rules() {
+        let mut rules = DesignspaceRules {
+            path: PathBuf::from("Font.designspace"),
+            axes: vec![RuleAxis {
+                name: "Weight".to_string(),
+                minimum: 100.0,
+                default: 400.0,
+                maximum: 1000.0,
+            }],
+            rules: Vec::new(),
+        };
+        rules.add_rule("dollar", "dollar.nostroke");
+        assert_eq!(
+            rules.rules[0].condition_sets[0][0].label(),
+            "700–1000 Weight"
+        );
+
+        let mut location = rules.default_location();
+        assert_eq!(rules.substitute("dollar", &location), "dollar");
+        location.insert("Weight".to_string(), 700.0);
+        assert_eq!(rules.substitute("dollar", &location), "dollar.nostroke");
+        assert_eq!(rules.substitute("cent", &location), "cent");
+
+        // Bounds step across the axis and stay on it
+        rules.step_condition(0, 0, false, true);
+        assert!(!rules.rules[0].applies_at(&location));
+        rules.step_condition(0, 0, true, true);
+        assert_eq!(rules.rules[0].condition_sets[0][0].maximum, Some(1000.0));
+
+        let font = FontData::default();
+        let problems = rules.rule_problems(&rules.rules[0], &font);
+        assert_eq!(
+            problems,
+            [
+                "'dollar' is not in the font",
+                "'dollar.nostroke' is not in the font"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rules_follow_renamed_glyphs() {
+        let mut rules = DesignspaceRules {
+            path: PathBuf::from("Font.designspace"),
+            axes: Vec::new(),
+            rules: Vec::new(),
+        };
+        rules.add_rule("dollar", "dollar.nostroke");
+
+        let renames = HashMap::from([("dollar.nostroke".to_string(), "dollar.rvrn".to_string())]);
+        assert!(rules.rename_glyphs(&renames));
+        assert_eq!(
+            rules.rules[0].substitutions,
+            [("dollar".to_string(), "dollar.rvrn".to_string())]
+        );
+        assert!(!rules.rename_glyphs(&renames));
+    }
+}
@@ -0,0 +1,213 @@
+//! Export profiles
+//!
+//! A profile names the fonts an export writes (the variable font, static
+//! fonts per source), how each kind is built and post-processed (how closely
+//! quadratic outlines follow cubic sources, overlap removal, subsetting,
+//! autohinting, an SVG-in-OpenType variant), and where the files go.
+//! Profiles are stored in the font lib so they travel with the project; a
+//! font without any gets the default profile, which writes the variable
+//! font and the Regular and Bold statics next to the source.
+
+use serde::{Deserialize, Serialize};
+
+use super::curve_flavor::DEFAULT_MAX_ERROR;
+
+/// Font lib key holding the project's export profiles
+pub const EXPORT_PROFILES_LIB_KEY: &str = "org.bezy.exportProfiles";
+
+/// Kind of font an export writes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// One variable font compiled from the designspace
+    Variable,
+    /// One static font per source UFO
+    #[default]
+    Static,
+}
+
+impl ExportFormat {
+    /// The format's name in the UI language
+    pub fn label(&self) -> String {
+        let id = match self {
+            ExportFormat::Variable => "export-format-variable",
+            ExportFormat::Static => "export-format-static",
+        };
+        crate::i18n::tr(id)
+    }
+}
+
+/// How fonts of one format are built and post-processed
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FormatSettings {
+    pub format: ExportFormat,
+    /// Furthest the fonts' quadratic outlines may stray from cubic curves
+    /// in the sources, in font units
+    pub quadratic_max_error: f64,
+    pub autohint: bool,
+    /// Remove overlaps from the compiled fonts, or flag them in a variable
+    /// font; the sources keep theirs
+    pub remove_overlaps: bool,
+    /// Codepoints or ranges ("U+0020-007E") and glyph names to keep;
+    /// empty keeps the whole font
+    pub subset: Vec<String>,
+    /// Also write an SVG-in-OpenType variant when glyphs have artwork
+    pub svg_variant: bool,
+}
+
+impl Default for FormatSettings {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::default(),
+            quadratic_max_error: DEFAULT_MAX_ERROR,
+            autohint: false,
+            remove_overlaps: false,
+            subset: Vec::new(),
+            svg_variant: false,
+        }
+    }
+}
+
+/// A named set of export settings
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportProfile {
+    pub name: String,
+    pub formats: Vec<FormatSettings>,
+    /// Source styles written as statics; empty writes every source
+    pub styles: Vec<String>,
+    /// Output directory, relative to the source's directory
+    pub output_dir: String,
+    /// File name without extension; `{family}` and `{style}` are replaced
+    pub filename_pattern: String,
+}
+
+impl Default for ExportProfile {
+    fn default() -> Self {
+        Self {
+            name: "Default".to_string(),
+            formats: vec![
+                FormatSettings {
+                    format: ExportFormat::Variable,
+                    svg_variant: true,
+                    ..Default::default()
+                },
+                FormatSettings::default(),
+            ],
+            styles: vec!["Regular".to_string(), "Bold".to_string()],
+            output_dir: String::new(),
+            filename_pattern: "{family}-{style}".to_string(),
+        }
+    }
+}
+
+impl ExportProfile {
+    /// File name of a font of this family and style, spaces removed
+    pub fn file_name(&self, family: &str, style: &str) -> String {
+        let stem = self
+            .filename_pattern
+            .replace("{family}", &family.replace(' ', ""))
+            .replace("{style}", &style.replace(' ', ""));
+        format!("{stem}.ttf")
+    }
+
+    /// Whether statics are written for a source of this style
+    pub fn includes_style(&self, style: &str) -> bool {
+        self.styles.is_empty() || self.styles.iter().any(|name| name == style)
+    }
+}
+
+/// The export profiles of a project
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportProfiles {
+    pub profiles: Vec<ExportProfile>,
+}
+
+impl Default for ExportProfiles {
+    fn default() -> Self {
+        Self {
+            profiles: vec![ExportProfile::default()],
+        }
+    }
+}
+
+impl ExportProfiles {
+    /// Read profiles from a UFO font lib, falling back to the default
+    pub fn from_norad_lib(lib: &norad::Plist) -> Self {
+        let profiles: Vec<ExportProfile> = lib
+            .get(EXPORT_PROFILES_LIB_KEY)
+            .and_then(|value| serde_json::to_value(value).ok())
+            .and_then(|json| serde_json::from_value(json).ok())
+            .unwrap_or_default();
+        if profiles.is_empty() {
+            return Self::default();
+        }
+        Self { profiles }
+    }
+
+    /// Write profiles into a UFO font lib, unless they are the default
+    pub fn write_to_norad_lib(&self, lib: &mut norad::Plist) {
+        lib.remove(EXPORT_PROFILES_LIB_KEY);
+        if *self == Self::default() {
+            return;
+        }
+        if let Ok(value) = serde_json::to_value(&self.profiles).and_then(serde_json::from_value) {
+            lib.insert(EXPORT_PROFILES_LIB_KEY.to_string(), value);
+        }
+    }
+
+    /// Profile with this name, or the first one for `None`
+    pub fn get(&self, name: Option<&str>) -> Option<&ExportProfile> {
+        match name {
+            Some(name) => self.profiles.iter().find(|profile| profile.name == name),
+            None => self.profiles.first(),
+        }
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.profiles
+            .iter()
+            .map(|profile| profile.name.as_str())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_name_pattern() {
+        let profile = ExportProfile {
+            filename_pattern: "fonts/{family}_{style}-web".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            profile.file_name("My Sans", "Semi Bold"),
+            "fonts/MySans_SemiBold-web.ttf"
+        );
+        assert!(profile.includes_style("Bold"));
+        assert!(!profile.includes_style("Light"));
+    }
+
+    #[test]
+    fn test_lib_round_trip_keeps_default_out_of_the_lib() {
+        let mut lib = norad::Plist::new();
+        ExportProfiles::default().write_to_norad_lib(&mut lib);
+        assert!(lib.is_empty());
+
+        let mut web = ExportProfile {
+            name: "Web".to_string(),
+            styles: Vec::new(),
+            output_dir: "web".to_string(),
+            ..Default::default()
+        };
+        web.formats[1].subset = vec!["U+0020-007E".to_string(), "fi".to_string()];
+        let profiles = ExportProfiles {
+            profiles: vec![ExportProfile::default(), web],
+        };
+        profiles.write_to_norad_lib(&mut lib);
+        assert_eq!(ExportProfiles::from_norad_lib(&lib), profiles);
+    }
+}
@@ -0,0 +1,406 @@
+//! Search and replace across glyph data
+//!
+//! Queries match glyph names, anchor names, component references, or point
+//! coordinates. `preview` lists what a query would touch without changing
+//! anything; `apply` performs it and returns a snapshot of every glyph it
+//! changed, or the renames it made, so the whole operation can be undone in
+//! one step. Renames go through `FontData::rename_glyphs`, which follows
+//! every reference to a renamed glyph. Queries search and change the
+//! foreground, whichever layer is being edited.
+
+use anyhow::Result;
+use std::collections::HashMap;
+
+use super::data::{FontData, GlyphData};
+
+/// Coordinate axis for coordinate replacement
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+/// What to look for and what to put in its place
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReplaceQuery {
+    /// Substring replacement in glyph names; components, layers, kerning,
+    /// color layers and smart pieces follow the renamed glyphs
+    GlyphName { find: String, replace: String },
+    /// Substring replacement in anchor names (including `_` mark anchors)
+    AnchorName { find: String, replace: String },
+    /// Swap the base glyph of components that reference `find` exactly
+    ComponentReference { find: String, replace: String },
+    /// Move points and anchors lying at `from` on an axis to `to`
+    Coordinate {
+        axis: Axis,
+        from: f64,
+        to: f64,
+        tolerance: f64,
+    },
+}
+
+/// One glyph affected by a query
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplaceMatch {
+    pub glyph_name: String,
+    /// Number of names, references, or points that change
+    pub count: usize,
+    /// Short human-readable summary, e.g. "a.alt -> a.ss01"
+    pub detail: String,
+}
+
+/// Everything needed to revert one `apply`
+#[derive(Clone, Debug, Default)]
+pub struct ReplaceUndo {
+    /// Glyphs as they were before the replacement
+    pub originals: Vec<GlyphData>,
+    /// Old name -> new name for every renamed glyph, renamed back on undo
+    pub renames: HashMap<String, String>,
+}
+
+/// List the glyphs a query would change
+pub fn preview(font: &FontData, query: &ReplaceQuery) -> Vec<ReplaceMatch> {
+    let mut matches: Vec<ReplaceMatch> = match query {
+        ReplaceQuery::GlyphName { find, replace } => planned_renames(font, find, replace)
+            .into_iter()
+            .map(|(old, new)| ReplaceMatch {
+                detail: format!("{old} -> {new}"),
+                glyph_name: old,
+                count: 1,
+            })
+            .collect(),
+        _ => font.glyphs.values().filter_map(|glyph| match_glyph(glyph, query)).collect(),
+    };
+    matches.sort_by(|a, b| a.glyph_name.cmp(&b.glyph_name));
+    matches
+}
+
+/// Perform a query, returning the data needed to undo it
+pub fn apply(font: &mut FontData, query: &ReplaceQuery) -> Result<ReplaceUndo> {
+    let mut undo = ReplaceUndo::default();
+
+    if let ReplaceQuery::GlyphName { find, replace } = query {
+        undo.renames = planned_renames(font, find, replace);
+        font.rename_glyphs(&undo.renames)?;
+        return Ok(undo);
+    }
+
+    let touched: Vec<String> = preview(font, query)
+        .into_iter()
+        .map(|m| m.glyph_name)
+        .collect();
+    for name in touched {
+        let Some(glyph) = font.glyphs.get_mut(&name) else {
+            continue;
+        };
+        undo.originals.push(glyph.clone());
+        replace_in_glyph(glyph, query);
+    }
+    Ok(undo)
+}
+
+/// Revert an `apply`. Fails, changing nothing, when a glyph has taken a
+/// renamed glyph's old name since
+pub fn undo(font: &mut FontData, undo: ReplaceUndo) -> Result<()> {
+    // Renaming back restores every reference
+    let back: HashMap<String, String> = undo
+        .renames
+        .into_iter()
+        .map(|(old, new)| (new, old))
+        .collect();
+    font.rename_glyphs(&back)?;
+    for glyph in undo.originals {
+        font.glyphs.insert(glyph.name.clone(), glyph);
+    }
+    Ok(())
+}
+
+/// Renames a glyph-name query would make, skipping ones that would collide
+fn planned_renames(font: &FontData, find: &str, replace: &str) -> HashMap<String, String> {
+    if find.is_empty() {
+        return HashMap::new();
+    }
+    let mut renames: HashMap<String, String> = font
+        .glyphs
+        .keys()
+        .filter(|name| name.contains(find))
+        .map(|name| (name.clone(), name.replace(find, replace)))
+        .filter(|(old, new)| old != new && !new.is_empty())
+        .collect();
+
+    // A new name may not clash with a glyph that keeps its name, nor with
+    // another rename
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for new in renames.values() {
+        *seen.entry(new.clone()).or_default() += 1;
+    }
+    let renamed: Vec<String> = renames.keys().cloned().collect();
+    renames.retain(|_, new| {
+        let kept_elsewhere = font.is_glyph_name_taken(new) && !renamed.contains(new);
+        !kept_elsewhere && seen[new.as_str()] == 1
+    });
+    renames
+}
+
+fn matches_coordinate(value: f64, from: f64, tolerance: f64) -> bool {
+    (value - from).abs() <= tolerance
+}
+
+/// Per-glyph matches for every query except glyph renames
+fn match_glyph(glyph: &GlyphData, query: &ReplaceQuery) -> Option<ReplaceMatch> {
+    let (count, detail) = match query {
+        ReplaceQuery::GlyphName { .. } => return None,
+        ReplaceQuery::AnchorName { find, .. } => {
+            if find.is_empty() {
+                return None;
+            }
+            let names: Vec<&str> = glyph
+                .anchors
+                .iter()
+                .filter(|a| a.name.contains(find.as_str()))
+                .map(|a| a.name.as_str())
+                .collect();
+            (names.len(), format!("anchors {}", names.join(", ")))
+        }
+        ReplaceQuery::ComponentReference { find, .. } => {
+            let count = glyph
+                .components
+                .iter()
+                .filter(|c| &c.base_glyph == find)
+                .count();
+            (count, format!("{count} component(s)"))
+        }
+        ReplaceQuery::Coordinate {
+            axis,
+            from,
+            tolerance,
+            ..
+        } => {
+            let value = |x: f64, y: f64| if *axis == Axis::X { x } else { y };
+            let points = glyph
+                .outline
+                .iter()
+                .flat_map(|outline| &outline.contours)
+                .flat_map(|contour| &contour.points)
+                .filter(|p| matches_coordinate(value(p.x, p.y), *from, *tolerance))
+                .count();
+            let anchors = glyph
+                .anchors
+                .iter()
+                .filter(|a| matches_coordinate(value(a.x, a.y), *from, *tolerance))
+                .count();
+            (
+                points + anchors,
+                format!("{points} point(s), {anchors} anchor(s)"),
+            )
+        }
+    };
+
+    (count > 0).then(|| ReplaceMatch {
+        glyph_name: glyph.name.clone(),
+        count,
+        detail,
+    })
+}
+
+fn replace_in_glyph(glyph: &mut GlyphData, query: &ReplaceQuery) {
+    match query {
+        ReplaceQuery::GlyphName { .. } => {}
+        ReplaceQuery::AnchorName { find, replace } => {
+            for anchor in &mut glyph.anchors {
+                anchor.name = anchor.name.replace(find.as_str(), replace);
+            }
+        }
+        ReplaceQuery::ComponentReference { find, replace } => {
+            for component in &mut glyph.components {
+                if &component.base_glyph == find {
+                    component.base_glyph = replace.clone();
+                }
+            }
+        }
+        ReplaceQuery::Coordinate {
+            axis,
+            from,
+            to,
+            tolerance,
+        } => {
+            for point in glyph
+                .outline
+                .iter_mut()
+                .flat_map(|outline| &mut outline.contours)
+                .flat_map(|contour| &mut contour.points)
+            {
+                let value = if *axis == Axis::X { &mut point.x } else { &mut point.y };
+                if matches_coordinate(*value, *from, *tolerance) {
+                    *value = *to;
+                }
+            }
+            for anchor in &mut glyph.anchors {
+                let value = if *axis == Axis::X { &mut anchor.x } else { &mut anchor.y };
+                if matches_coordinate(*value, *from, *tolerance) {
+                    *value = *to;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::{
+        ColorLayer, ComponentData, ContourData, GlyphLayer, OutlineData, PointData, PointTypeData,
+        BACKGROUND_LAYER_NAME,
+    };
+    use std::collections::BTreeMap;
+
+    fn glyph(name: &str, ys: &[f64], components: &[&str]) -> GlyphData {
+        GlyphData {
+            outline: Some(OutlineData {
+                contours: vec![ContourData {
+                    points: ys
+                        .iter()
+                        .map(|&y| PointData {
+                            x: 0.0,
+                            y,
+                            point_type: PointTypeData::Line,
+                        })
+                        .collect(),
+                }],
+            }),
+            components: components
+                .iter()
+                .map(|base| ComponentData {
+                    base_glyph: base.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..GlyphData::test(name, 500.0)
+        }
+    }
+
+    fn font() -> FontData {
+        let mut font = FontData::default();
+        for glyph in [
+            glyph("a.alt", &[0.0, 700.0, 700.2], &[]),
+            glyph("b", &[700.0], &[]),
+            glyph("aacute.alt", &[], &["a.alt"]),
+        ] {
+            font.glyphs.insert(glyph.name.clone(), glyph);
+        }
+        font
+    }
+
+    #[test]
+    fn test_coordinate_replace_and_undo() {
+        let mut font = font();
+        let query = ReplaceQuery::Coordinate {
+            axis: Axis::Y,
+            from: 700.0,
+            to: 702.0,
+            tolerance: 0.5,
+        };
+
+        let matches = preview(&font, &query);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].count, 2);
+
+        let undo_data = apply(&mut font, &query).unwrap();
+        let points = &font.glyphs["a.alt"].outline.as_ref().unwrap().contours[0].points;
+        assert_eq!(points[2].y, 702.0);
+
+        undo(&mut font, undo_data).unwrap();
+        let points = &font.glyphs["a.alt"].outline.as_ref().unwrap().contours[0].points;
+        assert_eq!(points[2].y, 700.2);
+    }
+
+    #[test]
+    fn test_replace_changes_the_foreground_while_a_layer_is_edited() {
+        let mut font = font();
+        font.switch_editing_layer(Some(BACKGROUND_LAYER_NAME));
+        let query = ReplaceQuery::Coordinate {
+            axis: Axis::Y,
+            from: 700.0,
+            to: 702.0,
+            tolerance: 0.5,
+        };
+
+        assert_eq!(preview(&font, &query).len(), 2);
+        let undo_data = apply(&mut font, &query).unwrap();
+        assert_eq!(font.editing_layer_name(), BACKGROUND_LAYER_NAME);
+        let points = &font.glyphs["a.alt"].outline.as_ref().unwrap().contours[0].points;
+        assert_eq!(points[1].y, 702.0);
+        let layer_glyph = font.editing().get_glyph("a.alt").unwrap();
+        assert!(layer_glyph.outline.is_none());
+
+        undo(&mut font, undo_data).unwrap();
+        let points = &font.glyphs["a.alt"].outline.as_ref().unwrap().contours[0].points;
+        assert_eq!(points[1].y, 700.0);
+    }
+
+    #[test]
+    fn test_rename_updates_components_and_undoes() {
+        let mut font = font();
+        font.layers.push(GlyphLayer {
+            name: "public.background".to_string(),
+            glyphs: HashMap::from([(
+                "aacute.alt".to_string(),
+                glyph("aacute.alt", &[], &["a.alt"]),
+            )]),
+        });
+        font.kerning.groups.insert(
+            "public.kern1.a".to_string(),
+            vec!["a.alt".to_string(), "b".to_string()],
+        );
+        font.kerning.pairs.insert(
+            "b".to_string(),
+            BTreeMap::from([("a.alt".to_string(), -20.0)]),
+        );
+        font.color.layers.insert(
+            "aacute.alt".to_string(),
+            vec![ColorLayer {
+                glyph_name: "a.alt".to_string(),
+                palette_index: 0,
+            }],
+        );
+        let serif = font.glyphs["a.alt"].outline.clone().unwrap().contours;
+        font.define_smart_piece("serif", serif);
+        font.stamp_smart_piece("b", "serif", kurbo::Affine::IDENTITY)
+            .unwrap();
+        let query = ReplaceQuery::GlyphName {
+            find: ".alt".to_string(),
+            replace: ".ss01".to_string(),
+        };
+
+        let undo_data = apply(&mut font, &query).unwrap();
+        assert!(font.glyphs.contains_key("a.ss01"));
+        assert_eq!(
+            font.glyphs["aacute.ss01"].components[0].base_glyph,
+            "a.ss01"
+        );
+        let background = &font.layers[0].glyphs["aacute.ss01"];
+        assert_eq!(background.components[0].base_glyph, "a.ss01");
+        assert_eq!(font.kerning.groups["public.kern1.a"][0], "a.ss01");
+        assert!(font.kerning.pairs["b"].contains_key("a.ss01"));
+        assert_eq!(font.color.layers["aacute.ss01"][0].glyph_name, "a.ss01");
+
+        // Renaming a piece master keeps its uses linked
+        let piece_query = ReplaceQuery::GlyphName {
+            find: "serif".to_string(),
+            replace: "spur".to_string(),
+        };
+        let piece_undo = apply(&mut font, &piece_query).unwrap();
+        assert_eq!(font.smart_pieces.instances["b"][0].piece, "spur");
+        undo(&mut font, piece_undo).unwrap();
+        assert_eq!(font.smart_pieces.instances["b"][0].piece, "serif");
+
+        undo(&mut font, undo_data).unwrap();
+        assert!(font.glyphs.contains_key("a.alt"));
+        assert!(!font.glyphs.contains_key("a.ss01"));
+        assert_eq!(font.glyphs["aacute.alt"].components[0].base_glyph, "a.alt");
+        assert!(font.layers[0].glyphs.contains_key("aacute.alt"));
+        assert_eq!(font.kerning.groups["public.kern1.a"][0], "a.alt");
+        assert!(font.kerning.pairs["b"].contains_key("a.alt"));
+        assert_eq!(font.color.layers["aacute.alt"][0].glyph_name, "a.alt");
+    }
+}
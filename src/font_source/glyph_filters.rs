@@ -0,0 +1,187 @@
+//! Non-destructive glyph filters
+//!
+//! A glyph can carry a recipe of filters (rounding its corners, offsetting
+//! its outline, slanting it) stored in its glyph lib. The outline the recipe
+//! starts from is kept on the `org.bezy.filterSource` layer, so the filters
+//! are run again on it whenever a parameter changes instead of being baked
+//! into the drawing. Editing the source layer and applying the recipe again
+//! redraws the glyph; baking drops the recipe and keeps what it drew.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::data::{ContourData, FontData, GlyphData};
+use super::layers::DEFAULT_LAYER_NAME;
+
+/// Glyph lib key holding the glyph's filters
+pub const GLYPH_FILTERS_LIB_KEY: &str = "org.bezy.filters";
+/// Layer holding the outlines filtered glyphs are drawn from
+pub const FILTER_SOURCE_LAYER_NAME: &str = "org.bezy.filterSource";
+
+/// How much a step changes a corner radius or an offset, in font units
+const DISTANCE_STEP: f64 = 5.0;
+/// How much a step changes a slant, in degrees
+const ANGLE_STEP: f64 = 1.0;
+/// Steepest slant, in degrees either way
+const MAX_SLANT: f64 = 45.0;
+
+/// An operation in a glyph's recipe
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "filter", rename_all = "camelCase")]
+pub enum GlyphFilter {
+    /// Round every corner to this radius
+    RoundCorners { radius: f64 },
+    /// Move the outline outward by this distance, inward when negative
+    Offset { distance: f64 },
+    /// Lean the outline right by this angle in degrees, about the baseline
+    Slant { angle: f64 },
+}
+
+impl GlyphFilter {
+    /// The filter and its parameter, as the filters pane shows them
+    pub fn label(&self) -> String {
+        let (id, value) = match self {
+            GlyphFilter::RoundCorners { radius } => ("glyph-filters-round", radius),
+            GlyphFilter::Offset { distance } => ("glyph-filters-offset", distance),
+            GlyphFilter::Slant { angle } => ("glyph-filters-slant", angle),
+        };
+        crate::i18n::tr_args(id, &[("value", &value.to_string())])
+    }
+
+    /// Raise or lower the filter's parameter by a step
+    pub fn step(&mut self, forward: bool) {
+        let sign = if forward { 1.0 } else { -1.0 };
+        match self {
+            GlyphFilter::RoundCorners { radius } => {
+                *radius = (*radius + sign * DISTANCE_STEP).max(0.0);
+            }
+            GlyphFilter::Offset { distance } => *distance += sign * DISTANCE_STEP,
+            GlyphFilter::Slant { angle } => {
+                *angle = (*angle + sign * ANGLE_STEP).clamp(-MAX_SLANT, MAX_SLANT);
+            }
+        }
+    }
+}
+
+/// Filter recipes for the whole font
+#[derive(Clone, Debug, Default)]
+pub struct GlyphFilters {
+    /// Glyph name -> its filters, in the order they run
+    pub recipes: HashMap<String, Vec<GlyphFilter>>,
+}
+
+impl GlyphFilters {
+    /// Read the recipes from the glyph libs of a UFO layer
+    pub fn from_norad_layer(layer: &norad::Layer) -> Self {
+        let recipes = layer
+            .iter()
+            .filter_map(|glyph| {
+                let filters: Vec<GlyphFilter> = glyph
+                    .lib
+                    .get(GLYPH_FILTERS_LIB_KEY)
+                    .and_then(|value| serde_json::to_value(value).ok())
+                    .and_then(|json| serde_json::from_value(json).ok())?;
+                Some((glyph.name().to_string(), filters))
+            })
+            .filter(|(_, filters)| !filters.is_empty())
+            .collect();
+        Self { recipes }
+    }
+
+    /// Write a glyph's recipe into its glyph lib, if it has one
+    pub fn write_to_norad_glyph(&self, glyph: &mut norad::Glyph) {
+        glyph.lib.remove(GLYPH_FILTERS_LIB_KEY);
+        let Some(filters) = self
+            .recipes
+            .get(glyph.name().as_str())
+            .filter(|filters| !filters.is_empty())
+        else {
+            return;
+        };
+        if let Ok(value) = serde_json::to_value(filters).and_then(serde_json::from_value) {
+            glyph.lib.insert(GLYPH_FILTERS_LIB_KEY.to_string(), value);
+        }
+    }
+}
+
+impl FontData {
+    /// The filters of a glyph, in the order they run
+    pub fn glyph_filters(&self, glyph_name: &str) -> &[GlyphFilter] {
+        self.glyph_filters
+            .recipes
+            .get(glyph_name)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// The filters of a glyph, for changing their parameters
+    pub fn glyph_filters_mut(&mut self, glyph_name: &str) -> Option<&mut Vec<GlyphFilter>> {
+        self.glyph_filters.recipes.get_mut(glyph_name)
+    }
+
+    /// Add a filter to a glyph's recipe. The first one keeps the outline as
+    /// it is now on the source layer
+    pub fn add_glyph_filter(&mut self, glyph_name: &str, filter: GlyphFilter) -> bool {
+        if self.glyph_filters(glyph_name).is_empty() {
+            let Some(source) = self.layer_glyph(DEFAULT_LAYER_NAME, glyph_name).cloned() else {
+                return false;
+            };
+            self.insert_layer_glyph(FILTER_SOURCE_LAYER_NAME, source);
+        }
+        self.glyph_filters
+            .recipes
+            .entry(glyph_name.to_string())
+            .or_default()
+            .push(filter);
+        true
+    }
+
+    /// The contours a glyph's filters start from
+    pub fn filter_source_contours(&self, glyph_name: &str) -> Option<Vec<ContourData>> {
+        let source = self.layer_glyph(FILTER_SOURCE_LAYER_NAME, glyph_name)?;
+        Some(
+            source
+                .outline
+                .as_ref()
+                .map(|outline| outline.contours.clone())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// The foreground drawing of a glyph, whichever layer is being edited
+    pub fn foreground_glyph_mut(&mut self, glyph_name: &str) -> Option<&mut GlyphData> {
+        self.layer_glyph_mut(DEFAULT_LAYER_NAME, glyph_name)
+    }
+
+    /// Drop a glyph's recipe and its source outline, keeping the outline the
+    /// filters drew
+    pub fn drop_glyph_filters(&mut self, glyph_name: &str) {
+        self.glyph_filters.recipes.remove(glyph_name);
+        self.remove_layer_glyph(FILTER_SOURCE_LAYER_NAME, glyph_name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glyph_filter_recipes() {
+        let mut filter = GlyphFilter::RoundCorners { radius: 3.0 };
+        filter.step(false);
+        assert_eq!(filter, GlyphFilter::RoundCorners { radius: 0.0 });
+        let mut slant = GlyphFilter::Slant { angle: 45.0 };
+        slant.step(true);
+        assert_eq!(slant, GlyphFilter::Slant { angle: 45.0 });
+
+        // Recipes round-trip through the glyph lib
+        let mut filters = GlyphFilters::default();
+        let recipe = vec![filter, GlyphFilter::Offset { distance: -10.0 }, slant];
+        filters.recipes.insert("a".to_string(), recipe.clone());
+        let mut glyph = norad::Glyph::new("a");
+        filters.write_to_norad_glyph(&mut glyph);
+        let mut font = norad::Font::new();
+        font.default_layer_mut().insert_glyph(glyph);
+        let loaded = GlyphFilters::from_norad_layer(font.default_layer());
+        assert_eq!(loaded.recipes["a"], recipe);
+    }
+}
@@ -0,0 +1,96 @@
+//! Glyph order and production names
+//!
+//! The UFO's font lib can list the glyphs in the order they go into a built
+//! font (`public.glyphOrder`) and the names they get there
+//! (`public.postscriptNames`). Both are kept as read so saving writes them
+//! back, and renamed along with the glyphs they name.
+
+use std::collections::{BTreeMap, HashMap};
+
+/// Font lib key listing the glyphs in build order
+pub const GLYPH_ORDER_LIB_KEY: &str = "public.glyphOrder";
+
+/// Font lib key mapping glyph names to production names
+pub const POSTSCRIPT_NAMES_LIB_KEY: &str = "public.postscriptNames";
+
+/// The glyph order and production names of a font lib
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GlyphOrder {
+    pub order: Vec<String>,
+    /// Production name of each glyph that has one
+    pub postscript_names: BTreeMap<String, String>,
+}
+
+impl GlyphOrder {
+    /// Read the glyph order and production names from a UFO font lib
+    pub fn from_norad_lib(lib: &norad::Plist) -> Self {
+        let order = lib
+            .get(GLYPH_ORDER_LIB_KEY)
+            .and_then(|value| serde_json::to_value(value).ok())
+            .and_then(|json| serde_json::from_value(json).ok())
+            .unwrap_or_default();
+        let postscript_names = lib
+            .get(POSTSCRIPT_NAMES_LIB_KEY)
+            .and_then(|value| serde_json::to_value(value).ok())
+            .and_then(|json| serde_json::from_value(json).ok())
+            .unwrap_or_default();
+        Self {
+            order,
+            postscript_names,
+        }
+    }
+
+    /// Write the glyph order and production names into a UFO font lib,
+    /// leaving out the ones that are empty
+    pub fn write_to_norad_lib(&self, lib: &mut norad::Plist) {
+        lib.remove(GLYPH_ORDER_LIB_KEY);
+        lib.remove(POSTSCRIPT_NAMES_LIB_KEY);
+
+        if !self.order.is_empty() {
+            if let Ok(value) = serde_json::to_value(&self.order).and_then(serde_json::from_value) {
+                lib.insert(GLYPH_ORDER_LIB_KEY.to_string(), value);
+            }
+        }
+        if !self.postscript_names.is_empty() {
+            if let Ok(value) =
+                serde_json::to_value(&self.postscript_names).and_then(serde_json::from_value)
+            {
+                lib.insert(POSTSCRIPT_NAMES_LIB_KEY.to_string(), value);
+            }
+        }
+    }
+
+    /// Follow renamed glyphs, keeping their place in the order and their
+    /// production names
+    pub(crate) fn rename_glyphs(&mut self, renames: &HashMap<String, String>) {
+        for name in &mut self.order {
+            if let Some(new) = renames.get(name.as_str()) {
+                *name = new.clone();
+            }
+        }
+        self.postscript_names = std::mem::take(&mut self.postscript_names)
+            .into_iter()
+            .map(|(name, production)| (renames.get(&name).cloned().unwrap_or(name), production))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lib_round_trip_and_rename() {
+        let mut glyph_order = GlyphOrder {
+            order: vec![".notdef".to_string(), "a".to_string(), "b".to_string()],
+            postscript_names: BTreeMap::from([("a".to_string(), "uni0061".to_string())]),
+        };
+        let mut lib = norad::Plist::new();
+        glyph_order.write_to_norad_lib(&mut lib);
+        assert_eq!(GlyphOrder::from_norad_lib(&lib), glyph_order);
+
+        glyph_order.rename_glyphs(&HashMap::from([("a".to_string(), "a.ss01".to_string())]));
+        assert_eq!(glyph_order.order, [".notdef", "a.ss01", "b"]);
+        assert_eq!(glyph_order.postscript_names["a.ss01"], "uni0061");
+    }
+}
@@ -0,0 +1,211 @@
+//! Blends of two compatible glyphs
+//!
+//! Two glyphs are compatible when their contours match one for one, with
+//! the same number and types of points, and they use the same components in
+//! the same order. A blend moves every point, component offset and scale,
+//! shared anchor and the advance the same fraction of the way from the
+//! first glyph to the second. An anisotropic blend moves x and y
+//! coordinates by different fractions, to judge how weight and contrast
+//! behave between the two.
+
+use anyhow::{bail, Result};
+
+use super::data::{AnchorData, ComponentData, ContourData, GlyphData, OutlineData, PointData};
+
+fn lerp(a: f64, b: f64, amount: f64) -> f64 {
+    a + (b - a) * amount
+}
+
+fn contours(glyph: &GlyphData) -> &[ContourData] {
+    glyph
+        .outline
+        .as_ref()
+        .map_or(&[], |outline| outline.contours.as_slice())
+}
+
+/// Why two glyphs can't be blended, or `Ok` if they can
+pub fn check_compatible(first: &GlyphData, second: &GlyphData) -> Result<()> {
+    let (first_contours, second_contours) = (contours(first), contours(second));
+    if first_contours.len() != second_contours.len() {
+        bail!(
+            "'{}' has {} contours and '{}' has {}",
+            first.name,
+            first_contours.len(),
+            second.name,
+            second_contours.len()
+        );
+    }
+    for (index, (a, b)) in first_contours.iter().zip(second_contours).enumerate() {
+        if a.points.len() != b.points.len() {
+            bail!(
+                "Contour {index} has {} points in '{}' and {} in '{}'",
+                a.points.len(),
+                first.name,
+                b.points.len(),
+                second.name
+            );
+        }
+        let types_match = a
+            .points
+            .iter()
+            .zip(&b.points)
+            .all(|(a, b)| a.point_type == b.point_type);
+        if !types_match {
+            bail!(
+                "Contour {index} has different point types in '{}' and '{}'",
+                first.name,
+                second.name
+            );
+        }
+    }
+
+    let bases = |glyph: &GlyphData| -> Vec<String> {
+        glyph
+            .components
+            .iter()
+            .map(|component| component.base_glyph.clone())
+            .collect()
+    };
+    if bases(first) != bases(second) {
+        bail!(
+            "'{}' and '{}' use different components",
+            first.name,
+            second.name
+        );
+    }
+    Ok(())
+}
+
+/// `first` moved `amount` of the way to `second`, from 0.0 for `first` to
+/// 1.0 for `second`, with `first`'s name and no codepoints
+pub fn blend_glyphs(first: &GlyphData, second: &GlyphData, amount: f64) -> Result<GlyphData> {
+    blend_glyphs_xy(first, second, amount, amount)
+}
+
+/// Like `blend_glyphs`, with x coordinates and widths moved `amount_x` of
+/// the way and y coordinates and heights `amount_y`
+pub fn blend_glyphs_xy(
+    first: &GlyphData,
+    second: &GlyphData,
+    amount_x: f64,
+    amount_y: f64,
+) -> Result<GlyphData> {
+    check_compatible(first, second)?;
+
+    let contours: Vec<ContourData> = contours(first)
+        .iter()
+        .zip(contours(second))
+        .map(|(a, b)| ContourData {
+            points: a
+                .points
+                .iter()
+                .zip(&b.points)
+                .map(|(a, b)| PointData {
+                    x: lerp(a.x, b.x, amount_x),
+                    y: lerp(a.y, b.y, amount_y),
+                    point_type: a.point_type,
+                })
+                .collect(),
+        })
+        .collect();
+
+    let components = first
+        .components
+        .iter()
+        .zip(&second.components)
+        .map(|(a, b)| {
+            // The even coefficients make x, the odd ones y
+            let mut transform = a.transform;
+            for (index, (value, target)) in transform.iter_mut().zip(b.transform).enumerate() {
+                let amount = if index % 2 == 0 { amount_x } else { amount_y };
+                *value = lerp(*value, target, amount);
+            }
+            ComponentData {
+                base_glyph: a.base_glyph.clone(),
+                transform,
+            }
+        })
+        .collect();
+
+    // Anchors only one of the glyphs has are left out
+    let anchors = first
+        .anchors
+        .iter()
+        .filter_map(|a| {
+            let b = second.anchor(&a.name)?;
+            Some(AnchorData {
+                name: a.name.clone(),
+                x: lerp(a.x, b.x, amount_x),
+                y: lerp(a.y, b.y, amount_y),
+            })
+        })
+        .collect();
+
+    Ok(GlyphData {
+        name: first.name.clone(),
+        advance_width: lerp(first.advance_width, second.advance_width, amount_x),
+        advance_height: first
+            .advance_height
+            .zip(second.advance_height)
+            .map(|(a, b)| lerp(a, b, amount_y)),
+        unicode_values: Vec::new(),
+        outline: first.outline.as_ref().map(|_| OutlineData { contours }),
+        components,
+        anchors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::PointTypeData;
+
+    fn square(name: &str, size: f64) -> GlyphData {
+        GlyphData {
+            unicode_values: vec!['x'],
+            outline: Some(OutlineData {
+                contours: vec![ContourData::test_square(size)],
+            }),
+            anchors: vec![AnchorData {
+                name: "top".to_string(),
+                x: size / 2.0,
+                y: size,
+            }],
+            ..GlyphData::test(name, size + 100.0)
+        }
+    }
+
+    #[test]
+    fn test_blend_squares() {
+        let light = square("o.light", 400.0);
+        let bold = square("o.bold", 600.0);
+        let blend = blend_glyphs(&light, &bold, 0.25).unwrap();
+        assert_eq!(blend.name, "o.light");
+        assert_eq!(blend.advance_width, 550.0);
+        assert!(blend.unicode_values.is_empty());
+        assert_eq!(blend.outline.unwrap().contours[0].points[2].x, 450.0);
+        assert_eq!(blend.anchors[0].x, 225.0);
+    }
+
+    #[test]
+    fn test_anisotropic_blend() {
+        let light = square("o.light", 400.0);
+        let bold = square("o.bold", 600.0);
+        let blend = blend_glyphs_xy(&light, &bold, 1.0, 0.0).unwrap();
+        let corner = &blend.outline.unwrap().contours[0].points[2];
+        assert_eq!((corner.x, corner.y), (600.0, 400.0));
+        assert_eq!(blend.advance_width, 700.0);
+    }
+
+    #[test]
+    fn test_incompatible_glyphs() {
+        let light = square("o.light", 400.0);
+        let mut bold = square("o.bold", 600.0);
+        bold.outline.as_mut().unwrap().contours[0].points[1].point_type = PointTypeData::Curve;
+        let error = blend_glyphs(&light, &bold, 0.5).unwrap_err();
+        assert!(error.to_string().contains("point types"));
+
+        bold.outline.as_mut().unwrap().contours.clear();
+        assert!(check_compatible(&light, &bold).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+//! Kerning groups and pairs
+//!
+//! Kept as in the UFO: pairs are stored by side, where a side is either a
+//! glyph name or the name of a `public.kern1.` (first side) or
+//! `public.kern2.` (second side) group. Looking up a glyph pair follows the
+//! UFO precedence, so a glyph exception wins over its group's value.
+
+use std::collections::BTreeMap;
+
+/// Prefix of groups that kern on the first (left) side of a pair
+pub const FIRST_GROUP_PREFIX: &str = "public.kern1.";
+/// Prefix of groups that kern on the second (right) side of a pair
+pub const SECOND_GROUP_PREFIX: &str = "public.kern2.";
+
+/// Groups and kerning pairs for the whole font
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KerningData {
+    /// Group name -> member glyphs, including groups that don't kern
+    pub groups: BTreeMap<String, Vec<String>>,
+    /// First side -> second side -> value
+    pub pairs: BTreeMap<String, BTreeMap<String, f64>>,
+}
+
+impl KerningData {
+    pub fn from_norad_font(font: &norad::Font) -> Self {
+        let groups = font
+            .groups
+            .iter()
+            .map(|(name, members)| {
+                (
+                    name.to_string(),
+                    members.iter().map(|member| member.to_string()).collect(),
+                )
+            })
+            .collect();
+        let pairs = font
+            .kerning
+            .iter()
+            .map(|(first, seconds)| {
+                (
+                    first.to_string(),
+                    seconds
+                        .iter()
+                        .map(|(second, value)| (second.to_string(), *value))
+                        .collect(),
+                )
+            })
+            .collect();
+        Self { groups, pairs }
+    }
+
+    /// Write groups and pairs into a norad font, skipping invalid names
+    pub fn write_to_norad_font(&self, font: &mut norad::Font) {
+        font.groups = self
+            .groups
+            .iter()
+            .filter_map(|(name, members)| {
+                let members = members
+                    .iter()
+                    .filter_map(|member| member.parse::<norad::Name>().ok())
+                    .collect();
+                Some((name.parse::<norad::Name>().ok()?, members))
+            })
+            .collect();
+        font.kerning = self
+            .pairs
+            .iter()
+            .filter_map(|(first, seconds)| {
+                let seconds = seconds
+                    .iter()
+                    .filter_map(|(second, value)| {
+                        Some((second.parse::<norad::Name>().ok()?, *value))
+                    })
+                    .collect();
+                Some((first.parse::<norad::Name>().ok()?, seconds))
+            })
+            .collect();
+    }
+
+    /// The first-side kerning group a glyph belongs to
+    pub fn first_group(&self, glyph_name: &str) -> Option<&str> {
+        self.kerning_group(FIRST_GROUP_PREFIX, glyph_name)
+    }
+
+    /// The second-side kerning group a glyph belongs to
+    pub fn second_group(&self, glyph_name: &str) -> Option<&str> {
+        self.kerning_group(SECOND_GROUP_PREFIX, glyph_name)
+    }
+
+    fn kerning_group(&self, prefix: &str, glyph_name: &str) -> Option<&str> {
+        self.groups
+            .iter()
+            .find(|(name, members)| {
+                name.starts_with(prefix) && members.iter().any(|member| member == glyph_name)
+            })
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// The sides a glyph pair kerns as, groups where the glyphs have them
+    pub fn class_pair(&self, first: &str, second: &str) -> (String, String) {
+        (
+            self.first_group(first).unwrap_or(first).to_string(),
+            self.second_group(second).unwrap_or(second).to_string(),
+        )
+    }
+
+    /// Value stored for exactly these two sides
+    pub fn value(&self, first: &str, second: &str) -> Option<f64> {
+        self.pairs.get(first)?.get(second).copied()
+    }
+
+    /// Kerning between two glyphs: glyph pairs first, then glyph/group,
+    /// group/glyph and group/group pairs
+    pub fn pair_value(&self, first: &str, second: &str) -> f64 {
+        let first_group = self.first_group(first);
+        let second_group = self.second_group(second);
+        self.value(first, second)
+            .or_else(|| self.value(first, second_group?))
+            .or_else(|| self.value(first_group?, second))
+            .or_else(|| self.value(first_group?, second_group?))
+            .unwrap_or(0.0)
+    }
+
+    /// Store a value for two sides; zero removes the pair
+    pub fn set_value(&mut self, first: &str, second: &str, value: f64) {
+        if value == 0.0 {
+            if let Some(seconds) = self.pairs.get_mut(first) {
+                seconds.remove(second);
+                if seconds.is_empty() {
+                    self.pairs.remove(first);
+                }
+            }
+            return;
+        }
+        self.pairs
+            .entry(first.to_string())
+            .or_default()
+            .insert(second.to_string(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kerning() -> KerningData {
+        let mut kerning = KerningData::default();
+        kerning.groups.insert(
+            "public.kern1.T".to_string(),
+            vec!["T".to_string(), "Tcedilla".to_string()],
+        );
+        kerning.groups.insert(
+            "public.kern2.o".to_string(),
+            vec!["o".to_string(), "oacute".to_string()],
+        );
+        kerning.set_value("public.kern1.T", "public.kern2.o", -80.0);
+        kerning.set_value("T", "oacute", -40.0);
+        kerning
+    }
+
+    #[test]
+    fn test_glyph_exceptions_win_over_groups() {
+        let kerning = kerning();
+        assert_eq!(kerning.pair_value("Tcedilla", "o"), -80.0);
+        assert_eq!(kerning.pair_value("T", "oacute"), -40.0);
+        assert_eq!(kerning.pair_value("o", "T"), 0.0);
+        assert_eq!(
+            kerning.class_pair("Tcedilla", "e"),
+            ("public.kern1.T".to_string(), "e".to_string())
+        );
+    }
+
+    #[test]
+    fn test_setting_zero_removes_the_pair() {
+        let mut kerning = kerning();
+        kerning.set_value("T", "oacute", 0.0);
+        assert!(!kerning.pairs.contains_key("T"));
+        assert_eq!(kerning.pair_value("T", "oacute"), -80.0);
+    }
+
+    #[test]
+    fn test_norad_round_trip() {
+        let kerning = kerning();
+        let mut font = norad::Font::new();
+        kerning.write_to_norad_font(&mut font);
+        assert_eq!(KerningData::from_norad_font(&font), kerning);
+    }
+}
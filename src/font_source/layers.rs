@@ -0,0 +1,293 @@
+//! Glyph layers beyond the default (foreground) layer
+//!
+//! UFO fonts can carry extra layers such as `public.background` for sketches
+//! and alternates. `FontData::glyphs` always holds the foreground, which is
+//! what gets saved to the default layer, compiled, exported and reported on;
+//! the other layers live in `FontData::layers`.
+//!
+//! The editor draws and edits the layer chosen with
+//! `FontData::switch_editing_layer`, reading it through
+//! `FontData::editing` and changing it through
+//! `FontData::editing_glyph_mut`. While a layer is edited it holds a glyph
+//! for every foreground glyph, empty where the layer draws nothing, so that
+//! every glyph can be drawn on it; the empty ones are dropped again when
+//! editing moves to another layer and when the font is saved.
+
+use std::collections::HashMap;
+
+use super::data::{FontData, GlyphData, LayerGlyphs};
+
+/// Name of the UFO default layer
+pub const DEFAULT_LAYER_NAME: &str = "public.default";
+/// Conventional name of the background layer
+pub const BACKGROUND_LAYER_NAME: &str = "public.background";
+
+/// A non-default UFO layer
+#[derive(Clone, Debug, Default)]
+pub struct GlyphLayer {
+    pub name: String,
+    pub glyphs: HashMap<String, GlyphData>,
+}
+
+impl FontData {
+    /// Name of the layer the editor draws and edits
+    pub fn editing_layer_name(&self) -> &str {
+        self.editing_layer.as_deref().unwrap_or(DEFAULT_LAYER_NAME)
+    }
+
+    /// Names of all layers, foreground first
+    pub fn layer_names(&self) -> Vec<String> {
+        std::iter::once(DEFAULT_LAYER_NAME.to_string())
+            .chain(self.layers.iter().map(|layer| layer.name.clone()))
+            .collect()
+    }
+
+    /// Layers other than the one being edited, e.g. for ghosted display
+    pub fn inactive_layer_names(&self) -> Vec<String> {
+        let editing = self.editing_layer_name();
+        self.layer_names()
+            .into_iter()
+            .filter(|name| name != editing)
+            .collect()
+    }
+
+    /// Find or create a non-default layer
+    pub fn layer_mut_or_create(&mut self, name: &str) -> &mut GlyphLayer {
+        let index = match self.layers.iter().position(|layer| layer.name == name) {
+            Some(index) => index,
+            None => {
+                self.layers.push(GlyphLayer {
+                    name: name.to_string(),
+                    glyphs: HashMap::new(),
+                });
+                self.layers.len() - 1
+            }
+        };
+        &mut self.layers[index]
+    }
+
+    /// Make `layer` (or the foreground for `None`) the one the editor draws
+    /// and edits
+    pub fn switch_editing_layer(&mut self, layer: Option<&str>) {
+        let target = layer.filter(|name| *name != DEFAULT_LAYER_NAME);
+        if target == self.editing_layer.as_deref() {
+            return;
+        }
+
+        if let Some(current) = self.editing_layer.take() {
+            let Self { glyphs, layers, .. } = self;
+            if let Some(layer) = layers.iter_mut().find(|layer| layer.name == current) {
+                layer
+                    .glyphs
+                    .retain(|glyph_name, glyph| draws_on_layer(glyph, glyphs.get(glyph_name)));
+            }
+        }
+        if let Some(target) = target {
+            self.layer_mut_or_create(target);
+            self.editing_layer = Some(target.to_string());
+            self.fill_editing_layer();
+        }
+    }
+
+    /// Give the layer being edited an empty glyph for each foreground glyph
+    /// it doesn't have, e.g. glyphs added since it was switched to
+    pub fn fill_editing_layer(&mut self) {
+        let Some(editing) = self.editing_layer.as_deref() else {
+            return;
+        };
+        let Self { glyphs, layers, .. } = self;
+        let Some(layer) = layers.iter_mut().find(|layer| layer.name == editing) else {
+            return;
+        };
+        for (glyph_name, glyph) in glyphs.iter() {
+            if !layer.glyphs.contains_key(glyph_name) {
+                layer
+                    .glyphs
+                    .insert(glyph_name.clone(), empty_layer_glyph(glyph));
+            }
+        }
+    }
+
+    /// Glyphs of the layer the editor draws and edits
+    pub fn editing(&self) -> LayerGlyphs<'_> {
+        let glyphs = self
+            .editing_layer
+            .as_deref()
+            .and_then(|name| self.layers.iter().find(|layer| layer.name == name))
+            .map_or(&self.glyphs, |layer| &layer.glyphs);
+        LayerGlyphs { glyphs }
+    }
+
+    /// A glyph of the layer being edited, for changing it. A foreground
+    /// glyph the layer doesn't have yet is added to it empty
+    pub fn editing_glyph_mut(&mut self, glyph_name: &str) -> Option<&mut GlyphData> {
+        let Some(editing) = self.editing_layer.clone() else {
+            return self.glyphs.get_mut(glyph_name);
+        };
+        let Self { glyphs, layers, .. } = self;
+        let foreground = glyphs.get(glyph_name)?;
+        let layer = layers.iter_mut().find(|layer| layer.name == editing)?;
+        Some(
+            layer
+                .glyphs
+                .entry(glyph_name.to_string())
+                .or_insert_with(|| empty_layer_glyph(foreground)),
+        )
+    }
+
+    /// Outline data stored for a glyph on a layer
+    pub fn layer_glyph(&self, layer: &str, glyph_name: &str) -> Option<&GlyphData> {
+        if layer == DEFAULT_LAYER_NAME {
+            return self.glyphs.get(glyph_name);
+        }
+        self.layers
+            .iter()
+            .find(|l| l.name == layer)
+            .and_then(|l| l.glyphs.get(glyph_name))
+    }
+
+    /// Glyph data stored for a glyph on a layer, for changing it
+    pub fn layer_glyph_mut(&mut self, layer: &str, glyph_name: &str) -> Option<&mut GlyphData> {
+        if layer == DEFAULT_LAYER_NAME {
+            return self.glyphs.get_mut(glyph_name);
+        }
+        self.layers
+            .iter_mut()
+            .find(|l| l.name == layer)
+            .and_then(|l| l.glyphs.get_mut(glyph_name))
+    }
+
+    /// Store a glyph on a layer, creating the layer if needed
+    pub fn insert_layer_glyph(&mut self, layer: &str, glyph: GlyphData) {
+        if layer == DEFAULT_LAYER_NAME {
+            self.glyphs.insert(glyph.name.clone(), glyph);
+        } else {
+            self.layer_mut_or_create(layer)
+                .glyphs
+                .insert(glyph.name.clone(), glyph);
+        }
+    }
+
+    /// Remove a glyph's drawing from a layer. Foreground glyphs and glyphs
+    /// of the layer being edited are emptied instead, so they stay
+    pub fn remove_layer_glyph(&mut self, layer: &str, glyph_name: &str) {
+        if layer == DEFAULT_LAYER_NAME || layer == self.editing_layer_name() {
+            if let Some(glyph) = self.layer_glyph_mut(layer, glyph_name) {
+                glyph.outline = None;
+                glyph.components.clear();
+            }
+        } else if let Some(layer) = self.layers.iter_mut().find(|l| l.name == layer) {
+            layer.glyphs.remove(glyph_name);
+        }
+    }
+
+    /// Copy the outline being edited for a glyph onto another layer
+    pub fn copy_glyph_to_layer(&mut self, glyph_name: &str, layer: &str) -> bool {
+        if layer == self.editing_layer_name() {
+            return false;
+        }
+        let Some(source) = self.editing().get_glyph(glyph_name).cloned() else {
+            return false;
+        };
+        self.insert_layer_glyph(layer, source);
+        true
+    }
+}
+
+/// Whether a layer glyph is worth keeping: it draws something or changes
+/// the foreground glyph's advance. Untouched layers stay sparse this way
+pub(crate) fn draws_on_layer(glyph: &GlyphData, foreground: Option<&GlyphData>) -> bool {
+    glyph.outline.is_some()
+        || !glyph.components.is_empty()
+        || foreground.is_some_and(|fg| fg.advance_width != glyph.advance_width)
+}
+
+/// A layer glyph that draws nothing, with the foreground glyph's advance
+fn empty_layer_glyph(foreground: &GlyphData) -> GlyphData {
+    GlyphData {
+        outline: None,
+        components: Vec::new(),
+        ..foreground.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::{ContourData, OutlineData};
+
+    fn glyph(name: &str, contours: usize) -> GlyphData {
+        GlyphData {
+            outline: Some(OutlineData {
+                contours: vec![ContourData { points: Vec::new() }; contours],
+            }),
+            ..GlyphData::test(name, 500.0)
+        }
+    }
+
+    #[test]
+    fn test_editing_a_layer_leaves_the_foreground_in_place() {
+        let mut font = FontData::default();
+        font.glyphs.insert("a".to_string(), glyph("a", 2));
+        font.glyphs.insert("b".to_string(), glyph("b", 3));
+        font.layer_mut_or_create(BACKGROUND_LAYER_NAME)
+            .glyphs
+            .insert("a".to_string(), glyph("a", 1));
+
+        font.switch_editing_layer(Some(BACKGROUND_LAYER_NAME));
+        assert_eq!(font.editing_layer_name(), BACKGROUND_LAYER_NAME);
+        assert_eq!(font.glyphs["a"].outline.as_ref().unwrap().contours.len(), 2);
+        let editing = font.editing();
+        assert_eq!(
+            editing
+                .get_glyph("a")
+                .unwrap()
+                .outline
+                .as_ref()
+                .unwrap()
+                .contours
+                .len(),
+            1
+        );
+        // Glyphs the layer doesn't draw are there to draw on, empty
+        assert!(editing.get_glyph("b").unwrap().outline.is_none());
+
+        font.switch_editing_layer(None);
+        assert_eq!(font.editing_layer_name(), DEFAULT_LAYER_NAME);
+        assert_eq!(font.editing().get_glyph("a"), font.glyphs.get("a"));
+        let background = &font.layers[0].glyphs;
+        assert_eq!(background["a"].outline.as_ref().unwrap().contours.len(), 1);
+        assert!(!background.contains_key("b"));
+    }
+
+    #[test]
+    fn test_layer_advance_of_empty_glyph_leaves_foreground_alone() {
+        let mut font = FontData::default();
+        let mut space = glyph("space", 0);
+        space.outline = None;
+        space.advance_width = 250.0;
+        font.glyphs.insert("space".to_string(), space);
+
+        font.switch_editing_layer(Some(BACKGROUND_LAYER_NAME));
+        font.editing_glyph_mut("space").unwrap().advance_width = 300.0;
+        assert_eq!(font.glyphs["space"].advance_width, 250.0);
+
+        font.switch_editing_layer(None);
+        assert_eq!(font.glyphs["space"].advance_width, 250.0);
+        let layer = font.layer_glyph(BACKGROUND_LAYER_NAME, "space").unwrap();
+        assert_eq!(layer.advance_width, 300.0);
+    }
+
+    #[test]
+    fn test_glyphs_added_while_a_layer_is_edited_can_be_drawn_on_it() {
+        let mut font = FontData::default();
+        font.switch_editing_layer(Some(BACKGROUND_LAYER_NAME));
+        font.glyphs.insert("a".to_string(), glyph("a", 2));
+
+        let layer_glyph = font.editing_glyph_mut("a").unwrap();
+        assert!(layer_glyph.outline.is_none());
+        layer_glyph.outline = glyph("a", 1).outline;
+        assert_eq!(font.glyphs["a"].outline.as_ref().unwrap().contours.len(), 2);
+        assert!(font.editing_glyph_mut("b").is_none());
+    }
+}
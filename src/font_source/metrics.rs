@@ -17,6 +17,74 @@ pub struct FontInfo {
     pub descender: Option<f64>,
     pub x_height: Option<f64>,
     pub cap_height: Option<f64>,
+    /// Standard horizontal stem widths (bar thicknesses), most common first
+    pub stem_snap_h: Vec<f64>,
+    /// Standard vertical stem widths, most common first
+    pub stem_snap_v: Vec<f64>,
+    /// Alignment zone edges in pairs: the baseline zone, then top zones
+    pub blue_values: Vec<f64>,
+    /// Edges of further bottom zones in pairs
+    pub other_blues: Vec<f64>,
+    /// OS/2 weight class, 100 (thin) to 900 (black)
+    pub os2_weight_class: Option<u32>,
+    /// OS/2 width class, 1 (ultra-condensed) to 9 (ultra-expanded)
+    pub os2_width_class: Option<u32>,
+    /// The ten PANOSE classification digits
+    pub panose: Option<[u8; 10]>,
+    /// OS/2 Unicode range bits that are set
+    pub unicode_ranges: Vec<u8>,
+    /// OS/2 code page range bits that are set
+    pub code_page_ranges: Vec<u8>,
+}
+
+/// Number of an OS/2 width class, 1 to 9
+fn width_class_number(class: norad::fontinfo::Os2WidthClass) -> u32 {
+    use norad::fontinfo::Os2WidthClass::*;
+    match class {
+        UltraCondensed => 1,
+        ExtraCondensed => 2,
+        Condensed => 3,
+        SemiCondensed => 4,
+        Normal => 5,
+        SemiExpanded => 6,
+        Expanded => 7,
+        ExtraExpanded => 8,
+        UltraExpanded => 9,
+    }
+}
+
+/// OS/2 width class of a number, 1 to 9
+fn width_class_from_number(number: u32) -> Option<norad::fontinfo::Os2WidthClass> {
+    use norad::fontinfo::Os2WidthClass::*;
+    Some(match number {
+        1 => UltraCondensed,
+        2 => ExtraCondensed,
+        3 => Condensed,
+        4 => SemiCondensed,
+        5 => Normal,
+        6 => SemiExpanded,
+        7 => Expanded,
+        8 => ExtraExpanded,
+        9 => UltraExpanded,
+        _ => return None,
+    })
+}
+
+/// The ten digits of a UFO's PANOSE classification
+fn panose_digits(panose: &norad::fontinfo::Os2Panose) -> [u8; 10] {
+    [
+        panose.family_type,
+        panose.serif_style,
+        panose.weight,
+        panose.proportion,
+        panose.contrast,
+        panose.stroke_variation,
+        panose.arm_style,
+        panose.letterform,
+        panose.midline,
+        panose.x_height,
+    ]
+    .map(|digit| digit.min(u8::MAX as u32) as u8)
 }
 
 /// Font metrics for spacing and positioning
@@ -64,6 +132,19 @@ impl FontInfo {
             descender,
             x_height,
             cap_height,
+            stem_snap_h: font.font_info.postscript_stem_snap_h.clone().unwrap_or_default(),
+            stem_snap_v: font.font_info.postscript_stem_snap_v.clone().unwrap_or_default(),
+            blue_values: font.font_info.postscript_blue_values.clone().unwrap_or_default(),
+            other_blues: font.font_info.postscript_other_blues.clone().unwrap_or_default(),
+            os2_weight_class: font.font_info.open_type_os2_weight_class,
+            os2_width_class: font.font_info.open_type_os2_width_class.map(width_class_number),
+            panose: font.font_info.open_type_os2_panose.as_ref().map(panose_digits),
+            unicode_ranges: font.font_info.open_type_os2_unicode_ranges.clone().unwrap_or_default(),
+            code_page_ranges: font
+                .font_info
+                .open_type_os2_code_page_ranges
+                .clone()
+                .unwrap_or_default(),
         }
     }
 
@@ -115,6 +196,38 @@ impl FontInfo {
         info.descender = self.descender;
         info.x_height = self.x_height;
         info.cap_height = self.cap_height;
+        if !self.stem_snap_h.is_empty() {
+            info.postscript_stem_snap_h = Some(self.stem_snap_h.clone());
+        }
+        if !self.stem_snap_v.is_empty() {
+            info.postscript_stem_snap_v = Some(self.stem_snap_v.clone());
+        }
+        if !self.blue_values.is_empty() {
+            info.postscript_blue_values = Some(self.blue_values.clone());
+        }
+        if !self.other_blues.is_empty() {
+            info.postscript_other_blues = Some(self.other_blues.clone());
+        }
+        info.open_type_os2_weight_class = self.os2_weight_class;
+        info.open_type_os2_width_class = self.os2_width_class.and_then(width_class_from_number);
+        info.open_type_os2_panose = self.panose.map(|digits| norad::fontinfo::Os2Panose {
+            family_type: digits[0].into(),
+            serif_style: digits[1].into(),
+            weight: digits[2].into(),
+            proportion: digits[3].into(),
+            contrast: digits[4].into(),
+            stroke_variation: digits[5].into(),
+            arm_style: digits[6].into(),
+            letterform: digits[7].into(),
+            midline: digits[8].into(),
+            x_height: digits[9].into(),
+        });
+        if !self.unicode_ranges.is_empty() {
+            info.open_type_os2_unicode_ranges = Some(self.unicode_ranges.clone());
+        }
+        if !self.code_page_ranges.is_empty() {
+            info.open_type_os2_code_page_ranges = Some(self.code_page_ranges.clone());
+        }
         info
     }
 
@@ -184,6 +297,12 @@ impl AsF32 for f64 {
 }
 
 impl FontMetrics {
+    /// Distance between the baselines of lines in a text buffer: the em
+    /// plus the depth of the descender
+    pub fn text_line_height(&self) -> f32 {
+        (self.units_per_em - self.descender.unwrap_or(-256.0)) as f32
+    }
+
     /// Extract metrics from a UFO
     pub fn from_ufo(ufo: &Font) -> Self {
         let font_info = &ufo.font_info;
@@ -3,8 +3,35 @@
 //! This module contains everything related to the font files being edited
 //! (UFO, designspace, etc.), as opposed to UI fonts used by the editor.
 
+pub mod anchor_sync;
+pub mod auto_kerning;
+pub mod auto_spacing;
+pub mod blue_zones;
+pub mod brace_layers;
+pub mod color;
+pub mod components;
+pub mod curve_flavor;
 pub mod data;
+pub mod design_frames;
+pub mod designspace_rules;
+pub mod export_profiles;
+pub mod find_replace;
+pub mod glyph_filters;
+pub mod glyph_order;
+pub mod interpolate;
+pub mod kerning;
+pub mod layers;
 pub mod metrics;
+pub mod open_corners;
+pub mod os2;
+pub mod rename;
+pub mod session;
+pub mod smart_pieces;
+pub mod spacing_import;
+pub mod stat;
+pub mod stems;
+pub mod subset;
+pub mod template_glyphs;
 pub mod ufo_point;
 
 #[cfg(test)]
@@ -12,7 +39,21 @@ mod tests;
 
 // Explicit re-exports for public API
 // Data structures
-pub use data::{ComponentData, ContourData, FontData, GlyphData, OutlineData, PointData, PointTypeData};
+pub use anchor_sync::AnchorSync;
+pub use color::{ColorData, ColorLayer, PaletteColor};
+pub use design_frames::DesignFrames;
+pub use export_profiles::ExportProfiles;
+pub use glyph_filters::{GlyphFilter, GlyphFilters};
+pub use glyph_order::GlyphOrder;
+pub use kerning::KerningData;
+pub use layers::{GlyphLayer, BACKGROUND_LAYER_NAME, DEFAULT_LAYER_NAME};
+pub use smart_pieces::{PieceInstance, SmartPieceData};
+pub use session::WorkspaceSession;
+pub use stat::StatConfig;
+pub use data::{
+    AnchorData, ComponentData, ContourData, FontData, GlyphData, LayerGlyphs, OutlineData,
+    PointData, PointTypeData,
+};
 // Metrics
 pub use metrics::{FontInfo, FontMetrics};
 // UFO point types
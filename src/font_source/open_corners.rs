@@ -0,0 +1,245 @@
+//! Open corners
+//!
+//! Opening a corner extends the two segments that meet there past each
+//! other along their tangents and joins their new ends with a short line,
+//! so the outline crosses itself in a small loop at the corner. Curves near
+//! the corner can then be edited without the corner point getting in the
+//! way. Closing it cuts both segments back to where their tangents cross,
+//! which is how export cleans them up (see `data::export_copy`).
+//!
+//! An open corner is recognized by its shape: a line segment from one
+//! on-curve point back to the next, where the segment arriving at the first
+//! and the segment leaving the second cross their own extensions. A chamfer
+//! points the other way and is left alone.
+
+use crate::font_source::data::{ContourData, PointData, PointTypeData};
+use kurbo::{Point, Vec2};
+
+/// How far past the corner each segment is extended, in font units
+pub const OPEN_CORNER_EXTENSION: f64 = 20.0;
+
+/// Corners flatter than this, in degrees, aren't corners
+const MIN_CORNER_ANGLE: f64 = 1.0;
+
+/// An open corner of a contour
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpenCorner {
+    /// The extended end of the segment arriving at the corner
+    pub arriving: usize,
+    /// The extended start of the segment leaving it
+    pub leaving: usize,
+    /// Where the corner closes
+    pub corner: Point,
+}
+
+impl OpenCorner {
+    /// The loop the corner makes, as a triangle
+    pub fn triangle(&self, contour: &ContourData) -> [Point; 3] {
+        [
+            self.corner,
+            contour.points[self.arriving].position(),
+            contour.points[self.leaving].position(),
+        ]
+    }
+}
+
+/// The first point at another position than the point at `index`, going
+/// forward or backward around a closed contour or towards an open one's end
+fn distinct_neighbour(contour: &ContourData, index: usize, forward: bool) -> Option<Point> {
+    let len = contour.points.len();
+    let here = contour.points[index].position();
+    (1..len)
+        .map_while(|step| match (forward, contour.is_open()) {
+            (true, true) => contour.points.get(index + step),
+            (false, true) => contour.points.get(index.checked_sub(step)?),
+            (true, false) => contour.points.get((index + step) % len),
+            (false, false) => contour.points.get((index + len - step) % len),
+        })
+        .map(position)
+        .find(|point| point.distance(here) > 1e-9)
+}
+
+/// How far the on-curve point at `index` is from the next on-curve point
+/// forward or backward
+fn segment_chord(contour: &ContourData, index: usize, forward: bool) -> Option<f64> {
+    let len = contour.points.len();
+    let step = if forward { 1 } else { len - 1 };
+    let mut other = (index + step) % len;
+    while !contour.points[other].is_on_curve() && other != index {
+        other = (other + step) % len;
+    }
+    (other != index).then(|| {
+        contour.points[index]
+            .position()
+            .distance(contour.points[other].position())
+    })
+}
+
+/// Unit directions the contour arrives at and leaves the point at `index`
+fn directions(contour: &ContourData, index: usize) -> Option<(Vec2, Vec2)> {
+    // An open contour's ends have a segment on one side only
+    if contour.is_open() && (index == 0 || index + 1 >= contour.points.len()) {
+        return None;
+    }
+    let here = contour.points[index].position();
+    let before = distinct_neighbour(contour, index, false)?;
+    let after = distinct_neighbour(contour, index, true)?;
+    Some(((here - before).normalize(), (after - here).normalize()))
+}
+
+/// Whether the on-curve point at `index` is a corner that can be opened
+pub fn is_corner(contour: &ContourData, index: usize) -> bool {
+    let Some(point) = contour.points.get(index) else {
+        return false;
+    };
+    if !point.is_on_curve() {
+        return false;
+    }
+    directions(contour, index).is_some_and(|(arriving, leaving)| {
+        arriving.cross(leaving).atan2(arriving.dot(leaving)).abs() > MIN_CORNER_ANGLE.to_radians()
+    })
+}
+
+/// The contour with the corner at `index` opened by `extension` units, or
+/// `None` when the point isn't a corner
+///
+/// The corner point becomes the end of the arriving segment and a new line
+/// point after it the start of the leaving one; handles stay where they are,
+/// so both segments keep their tangents.
+pub fn open_corner(contour: &ContourData, index: usize, extension: f64) -> Option<ContourData> {
+    if !is_corner(contour, index) {
+        return None;
+    }
+    let (arriving, leaving) = directions(contour, index)?;
+    let corner = contour.points[index].position();
+    let end = corner + arriving * extension;
+    let start = corner - leaving * extension;
+
+    let mut opened = contour.clone();
+    opened.points[index].x = end.x;
+    opened.points[index].y = end.y;
+    opened.points.insert(
+        index + 1,
+        PointData {
+            x: start.x,
+            y: start.y,
+            point_type: PointTypeData::Line,
+        },
+    );
+    Some(opened)
+}
+
+/// Where two lines, each through a point along a direction, cross, as how
+/// far along each direction
+fn crossing(a: Point, a_direction: Vec2, b: Point, b_direction: Vec2) -> Option<(f64, f64)> {
+    let denominator = a_direction.cross(b_direction);
+    if denominator.abs() < 1e-12 {
+        return None;
+    }
+    let offset = b - a;
+    Some((
+        offset.cross(b_direction) / denominator,
+        offset.cross(a_direction) / denominator,
+    ))
+}
+
+/// The open corners of a contour
+pub fn open_corners(contour: &ContourData) -> Vec<OpenCorner> {
+    let len = contour.points.len();
+    let pairs = if contour.is_open() {
+        len.saturating_sub(1)
+    } else {
+        len
+    };
+    (0..pairs)
+        .filter_map(|arriving| {
+            let leaving = (arriving + 1) % len;
+            let (first, second) = (&contour.points[arriving], &contour.points[leaving]);
+            if !first.is_on_curve() || second.point_type != PointTypeData::Line {
+                return None;
+            }
+            let (arriving_direction, _) = directions(contour, arriving)?;
+            let (_, leaving_direction) = directions(contour, leaving)?;
+            let arriving_length = segment_chord(contour, arriving, false)?;
+            let leaving_length = segment_chord(contour, leaving, true)?;
+            let (end, start) = (first.position(), second.position());
+            let (along_arriving, along_leaving) =
+                crossing(end, arriving_direction, start, leaving_direction)?;
+            // Both segments run past the corner, by less than their length
+            let is_open = along_arriving < 0.0
+                && along_leaving > 0.0
+                && -along_arriving < arriving_length
+                && along_leaving < leaving_length;
+            is_open.then(|| OpenCorner {
+                arriving,
+                leaving,
+                corner: end + arriving_direction * along_arriving,
+            })
+        })
+        .collect()
+}
+
+/// The contour with an open corner closed again: the arriving segment ends
+/// at the corner and the leaving segment's start is removed
+pub fn close_open_corner(contour: &ContourData, corner: &OpenCorner) -> ContourData {
+    let mut closed = contour.clone();
+    closed.points[corner.arriving].x = corner.corner.x;
+    closed.points[corner.arriving].y = corner.corner.y;
+    closed.points.remove(corner.leaving);
+    if let Some(start) = closed.points.iter().position(PointData::is_on_curve) {
+        closed.points.rotate_left(start);
+    }
+    closed
+}
+
+/// The contour with all its open corners closed
+pub fn close_open_corners(contour: &ContourData) -> ContourData {
+    let mut closed = contour.clone();
+    while let Some(corner) = open_corners(&closed).first().copied() {
+        closed = close_open_corner(&closed, &corner);
+    }
+    closed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_and_close_corner() {
+        let opened = open_corner(&ContourData::test_square(100.0), 1, 20.0).unwrap();
+        assert_eq!(opened.points.len(), 5);
+        assert_eq!((opened.points[1].x, opened.points[1].y), (120.0, 0.0));
+        assert_eq!((opened.points[2].x, opened.points[2].y), (100.0, -20.0));
+
+        let corners = open_corners(&opened);
+        assert_eq!(corners.len(), 1);
+        assert_eq!(corners[0].corner, Point::new(100.0, 0.0));
+        assert_eq!(close_open_corners(&opened), ContourData::test_square(100.0));
+
+        // The start point too, and a corner after a curve
+        let curved = ContourData::test_square(100.0).with_segment_type(3, PointTypeData::Curve);
+        for index in [0, curved.points.len() - 1] {
+            let opened = open_corner(&curved, index, 10.0).unwrap();
+            assert_eq!(open_corners(&opened).len(), 1);
+            assert_eq!(close_open_corners(&opened), curved);
+        }
+
+        // Nothing to open or close on a straight run or a chamfer
+        let mut straight = ContourData::test_square(100.0);
+        straight
+            .points
+            .insert(1, PointData::test(50.0, 0.0, PointTypeData::Line));
+        assert!(open_corner(&straight, 1, 20.0).is_none());
+        let chamfer = ContourData {
+            points: vec![
+                PointData::test(0.0, 0.0, PointTypeData::Line),
+                PointData::test(90.0, 0.0, PointTypeData::Line),
+                PointData::test(100.0, 10.0, PointTypeData::Line),
+                PointData::test(100.0, 100.0, PointTypeData::Line),
+            ],
+        };
+        assert!(open_corners(&chamfer).is_empty());
+        assert!(open_corners(&ContourData::test_square(100.0)).is_empty());
+    }
+}
@@ -0,0 +1,619 @@
+//! OS/2 classification from the glyph set and metrics
+//!
+//! Apps pick fonts by the classification in the OS/2 table: the weight and
+//! width classes, the PANOSE digits, and the Unicode range and code page
+//! bits saying which scripts and legacy encodings the font covers. These
+//! are easy to leave at a template's values, so they're suggested here
+//! from the font itself:
+//!
+//! - the weight class from the vertical stem of "n" (or "l", "H", "I")
+//!   relative to the em, or the first standard stem
+//! - the width class from the advance of "H" relative to the cap height,
+//!   or of "n" relative to the x-height
+//! - the PANOSE family, weight, proportion and x-height digits, leaving the
+//!   digits that describe the design's details at 0 ("any")
+//! - the Unicode range bits of every block with an encoded glyph
+//! - the code page bits, by the characters that tell the code pages apart,
+//!   as ufo2ft computes them
+//!
+//! `Os2Classification::mismatches` lists where the font info disagrees.
+
+use std::collections::BTreeSet;
+
+use super::data::FontData;
+use super::metrics::FontInfo;
+use super::stems::{measure_stems, StemDirection};
+
+/// Glyphs whose vertical stem sets the weight, in order of preference
+const STEM_GLYPHS: [&str; 4] = ["n", "l", "H", "I"];
+
+/// Upper bounds of the stem width relative to the em for each weight
+/// class; heavier stems are Black
+const WEIGHT_STEMS: [(f64, u32); 8] = [
+    (0.03, 100),
+    (0.045, 200),
+    (0.065, 300),
+    (0.09, 400),
+    (0.11, 500),
+    (0.13, 600),
+    (0.155, 700),
+    (0.18, 800),
+];
+
+/// Heaviest weight class
+const BLACK: u32 = 900;
+
+/// Widths of the width classes 1 to 9, in percent of normal
+const WIDTH_PERCENTS: [f64; 9] = [50.0, 62.5, 75.0, 87.5, 100.0, 112.5, 125.0, 150.0, 200.0];
+
+/// PANOSE family kind of Latin text faces
+const PANOSE_LATIN_TEXT: u8 = 2;
+
+/// PANOSE proportion of monospaced faces
+const PANOSE_MONOSPACED: u8 = 9;
+
+/// Fewest glyphs with the same advance for a font to count as monospaced
+const MIN_MONOSPACED_GLYPHS: usize = 10;
+
+/// Unicode range bit of codepoints outside the Basic Multilingual Plane
+const NON_PLANE_0_BIT: u8 = 57;
+
+/// Unicode range bits and the blocks each one covers, first to last
+const UNICODE_RANGES: &[(u8, u32, u32)] = &[
+    (0, 0x0000, 0x007F),
+    (1, 0x0080, 0x00FF),
+    (2, 0x0100, 0x017F),
+    (3, 0x0180, 0x024F),
+    (4, 0x0250, 0x02AF),
+    (4, 0x1D00, 0x1DBF),
+    (5, 0x02B0, 0x02FF),
+    (5, 0xA700, 0xA71F),
+    (6, 0x0300, 0x036F),
+    (6, 0x1DC0, 0x1DFF),
+    (7, 0x0370, 0x03FF),
+    (8, 0x2C80, 0x2CFF),
+    (9, 0x0400, 0x052F),
+    (9, 0x2DE0, 0x2DFF),
+    (9, 0xA640, 0xA69F),
+    (10, 0x0530, 0x058F),
+    (11, 0x0590, 0x05FF),
+    (12, 0xA500, 0xA63F),
+    (13, 0x0600, 0x06FF),
+    (13, 0x0750, 0x077F),
+    (14, 0x07C0, 0x07FF),
+    (15, 0x0900, 0x097F),
+    (16, 0x0980, 0x09FF),
+    (17, 0x0A00, 0x0A7F),
+    (18, 0x0A80, 0x0AFF),
+    (19, 0x0B00, 0x0B7F),
+    (20, 0x0B80, 0x0BFF),
+    (21, 0x0C00, 0x0C7F),
+    (22, 0x0C80, 0x0CFF),
+    (23, 0x0D00, 0x0D7F),
+    (24, 0x0E00, 0x0E7F),
+    (25, 0x0E80, 0x0EFF),
+    (26, 0x10A0, 0x10FF),
+    (26, 0x2D00, 0x2D2F),
+    (27, 0x1B00, 0x1B7F),
+    (28, 0x1100, 0x11FF),
+    (29, 0x1E00, 0x1EFF),
+    (29, 0x2C60, 0x2C7F),
+    (29, 0xA720, 0xA7FF),
+    (30, 0x1F00, 0x1FFF),
+    (31, 0x2000, 0x206F),
+    (31, 0x2E00, 0x2E7F),
+    (32, 0x2070, 0x209F),
+    (33, 0x20A0, 0x20CF),
+    (34, 0x20D0, 0x20FF),
+    (35, 0x2100, 0x214F),
+    (36, 0x2150, 0x218F),
+    (37, 0x2190, 0x21FF),
+    (37, 0x27F0, 0x27FF),
+    (37, 0x2900, 0x297F),
+    (37, 0x2B00, 0x2BFF),
+    (38, 0x2200, 0x22FF),
+    (38, 0x27C0, 0x27EF),
+    (38, 0x2980, 0x2AFF),
+    (39, 0x2300, 0x23FF),
+    (40, 0x2400, 0x243F),
+    (41, 0x2440, 0x245F),
+    (42, 0x2460, 0x24FF),
+    (43, 0x2500, 0x257F),
+    (44, 0x2580, 0x259F),
+    (45, 0x25A0, 0x25FF),
+    (46, 0x2600, 0x26FF),
+    (47, 0x2700, 0x27BF),
+    (48, 0x3000, 0x303F),
+    (49, 0x3040, 0x309F),
+    (50, 0x30A0, 0x30FF),
+    (50, 0x31F0, 0x31FF),
+    (51, 0x3100, 0x312F),
+    (51, 0x31A0, 0x31BF),
+    (52, 0x3130, 0x318F),
+    (53, 0xA840, 0xA87F),
+    (54, 0x3200, 0x32FF),
+    (55, 0x3300, 0x33FF),
+    (56, 0xAC00, 0xD7AF),
+    (58, 0x10900, 0x1091F),
+    (59, 0x2E80, 0x2FFF),
+    (59, 0x3190, 0x319F),
+    (59, 0x3400, 0x4DBF),
+    (59, 0x4E00, 0x9FFF),
+    (59, 0x20000, 0x2A6DF),
+    (60, 0xE000, 0xF8FF),
+    (61, 0x31C0, 0x31EF),
+    (61, 0xF900, 0xFAFF),
+    (61, 0x2F800, 0x2FA1F),
+    (62, 0xFB00, 0xFB4F),
+    (63, 0xFB50, 0xFDFF),
+    (64, 0xFE20, 0xFE2F),
+    (65, 0xFE10, 0xFE1F),
+    (65, 0xFE30, 0xFE4F),
+    (66, 0xFE50, 0xFE6F),
+    (67, 0xFE70, 0xFEFF),
+    (68, 0xFF00, 0xFFEF),
+    (69, 0xFFF0, 0xFFFF),
+    (70, 0x0F00, 0x0FFF),
+    (71, 0x0700, 0x074F),
+    (72, 0x0780, 0x07BF),
+    (73, 0x0D80, 0x0DFF),
+    (74, 0x1000, 0x109F),
+    (75, 0x1200, 0x139F),
+    (75, 0x2D80, 0x2DDF),
+    (76, 0x13A0, 0x13FF),
+    (77, 0x1400, 0x167F),
+    (78, 0x1680, 0x169F),
+    (79, 0x16A0, 0x16FF),
+    (80, 0x1780, 0x17FF),
+    (80, 0x19E0, 0x19FF),
+    (81, 0x1800, 0x18AF),
+    (82, 0x2800, 0x28FF),
+    (83, 0xA000, 0xA4CF),
+    (84, 0x1700, 0x177F),
+    (85, 0x10300, 0x1032F),
+    (86, 0x10330, 0x1034F),
+    (87, 0x10400, 0x1044F),
+    (88, 0x1D000, 0x1D24F),
+    (89, 0x1D400, 0x1D7FF),
+    (90, 0xF0000, 0x10FFFD),
+    (91, 0xFE00, 0xFE0F),
+    (91, 0xE0100, 0xE01EF),
+    (92, 0xE0000, 0xE007F),
+    (93, 0x1900, 0x194F),
+    (94, 0x1950, 0x197F),
+    (95, 0x1980, 0x19DF),
+    (96, 0x1A00, 0x1A1F),
+    (97, 0x2C00, 0x2C5F),
+    (98, 0x2D30, 0x2D7F),
+    (99, 0x4DC0, 0x4DFF),
+    (100, 0xA800, 0xA82F),
+    (101, 0x10000, 0x1013F),
+    (102, 0x10140, 0x1018F),
+    (103, 0x10380, 0x1039F),
+    (104, 0x103A0, 0x103DF),
+    (105, 0x10450, 0x1047F),
+    (106, 0x10480, 0x104AF),
+    (107, 0x10800, 0x1083F),
+    (108, 0x10A00, 0x10A5F),
+    (109, 0x1D300, 0x1D35F),
+    (110, 0x12000, 0x1247F),
+    (111, 0x1D360, 0x1D37F),
+    (112, 0x1B80, 0x1BBF),
+    (113, 0x1C00, 0x1C4F),
+    (114, 0x1C50, 0x1C7F),
+    (115, 0xA880, 0xA8DF),
+    (116, 0xA900, 0xA92F),
+    (117, 0xA930, 0xA95F),
+    (118, 0xAA00, 0xAA5F),
+    (119, 0x10190, 0x101CF),
+    (120, 0x101D0, 0x101FF),
+    (121, 0x10280, 0x102DF),
+    (121, 0x10920, 0x1093F),
+    (122, 0x1F000, 0x1F09F),
+];
+
+/// Code page bits set by a single character: bit, character, and whether
+/// the font also needs all of ASCII
+const CODE_PAGE_CHARS: [(u8, char, bool); 16] = [
+    (0, 'Þ', true),
+    (1, 'Ľ', true),
+    (2, 'Б', false),
+    (3, 'Ά', false),
+    (4, 'İ', true),
+    (5, 'א', false),
+    (6, 'ر', false),
+    (7, 'ŗ', true),
+    (8, '₫', true),
+    (16, 'ๅ', false),
+    (17, 'エ', false),
+    (18, 'ㄅ', false),
+    (19, 'ㄱ', false),
+    (20, '央', false),
+    (21, '곴', false),
+    (30, '♥', true),
+];
+
+/// DOS code page bits that also need the box drawing character '┤': bit,
+/// the script's character, another character it needs, and whether the
+/// font also needs all of ASCII
+const DOS_CODE_PAGES: [(u8, char, Option<char>, bool); 13] = [
+    (58, 'Ľ', None, true),
+    (57, 'Б', Some('Ѕ'), false),
+    (49, 'Б', Some('╜'), false),
+    (48, 'Ά', Some('½'), false),
+    (60, 'Ά', Some('√'), false),
+    (56, 'İ', None, true),
+    (53, 'א', Some('√'), false),
+    (61, 'ر', None, false),
+    (59, 'ŗ', None, true),
+    (54, 'þ', None, true),
+    (50, 'Å', Some('√'), true),
+    (52, 'é', Some('√'), true),
+    (55, 'õ', Some('√'), true),
+];
+
+/// Code page bit of the Arabic DOS code page, which needs no box drawing
+const DOS_ARABIC: (u8, char, char) = (51, 'ر', '√');
+
+/// Code page bits of the Western European and US DOS code pages
+const DOS_WESTERN: [u8; 2] = [62, 63];
+
+/// Code page bit of the Mac Roman character set
+const MAC_ROMAN: u8 = 29;
+
+/// A value of the OS/2 classification
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Os2Field {
+    WeightClass,
+    WidthClass,
+    Panose,
+    UnicodeRanges,
+    CodePageRanges,
+}
+
+impl Os2Field {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::WeightClass => "weight class",
+            Self::WidthClass => "width class",
+            Self::Panose => "PANOSE",
+            Self::UnicodeRanges => "Unicode ranges",
+            Self::CodePageRanges => "code pages",
+        }
+    }
+}
+
+/// A value of the font info that differs from the suggested one
+#[derive(Clone, Debug, PartialEq)]
+pub struct Os2Mismatch {
+    pub field: Os2Field,
+    pub current: String,
+    pub suggested: String,
+}
+
+/// Suggested OS/2 classification of a font; `None` where the font has too
+/// little to go on
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Os2Classification {
+    pub weight_class: Option<u32>,
+    pub width_class: Option<u32>,
+    pub panose: [u8; 10],
+    pub unicode_ranges: Vec<u8>,
+    pub code_page_ranges: Vec<u8>,
+}
+
+/// Weight class of a stem this wide, relative to the em
+pub fn weight_class_for_stem(ratio: f64) -> u32 {
+    WEIGHT_STEMS
+        .iter()
+        .find(|(limit, _)| ratio < *limit)
+        .map_or(BLACK, |(_, class)| *class)
+}
+
+/// Width class, 1 to 9, nearest to a width in percent of normal
+pub fn width_class_for_percent(percent: f64) -> u32 {
+    let (index, _) = WIDTH_PERCENTS
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| (*a - percent).abs().total_cmp(&(*b - percent).abs()))
+        .unwrap_or((4, &100.0));
+    index as u32 + 1
+}
+
+/// Unicode range bits of these codepoints, in order
+pub fn unicode_range_bits(codepoints: &BTreeSet<char>) -> Vec<u8> {
+    let mut bits = BTreeSet::new();
+    for &codepoint in codepoints {
+        let value = codepoint as u32;
+        if value > 0xFFFF {
+            bits.insert(NON_PLANE_0_BIT);
+        }
+        for &(bit, first, last) in UNICODE_RANGES {
+            if (first..=last).contains(&value) {
+                bits.insert(bit);
+            }
+        }
+    }
+    bits.into_iter().collect()
+}
+
+/// Code page bits of these codepoints, in order; Latin 1 when no code page
+/// fits, so the font still works in Word
+pub fn code_page_bits(codepoints: &BTreeSet<char>) -> Vec<u8> {
+    let has = |c: char| codepoints.contains(&c);
+    let ascii = (0x20u8..0x7E).all(|byte| has(byte as char));
+    let lineart = has('┤');
+
+    let mut bits = BTreeSet::new();
+    for (bit, c, needs_ascii) in CODE_PAGE_CHARS {
+        if has(c) && (ascii || !needs_ascii) {
+            bits.insert(bit);
+        }
+    }
+    if lineart {
+        for (bit, c, other, needs_ascii) in DOS_CODE_PAGES {
+            if has(c) && other.is_none_or(has) && (ascii || !needs_ascii) {
+                bits.insert(bit);
+            }
+        }
+    }
+    let (bit, c, other) = DOS_ARABIC;
+    if has(c) && has(other) {
+        bits.insert(bit);
+    }
+    if ascii && has('╚') {
+        bits.extend(DOS_WESTERN);
+    }
+    if ascii && has('‰') && has('∑') {
+        bits.insert(MAC_ROMAN);
+    }
+    if bits.is_empty() {
+        bits.insert(0);
+    }
+    bits.into_iter().collect()
+}
+
+/// PANOSE digits from the weight and width classes, whether the font is
+/// monospaced and its x-height relative to its cap height
+fn panose_digits(
+    latin: bool,
+    weight_class: Option<u32>,
+    width_class: Option<u32>,
+    monospaced: bool,
+    x_ratio: Option<f64>,
+) -> [u8; 10] {
+    let mut panose = [0; 10];
+    if !latin {
+        return panose;
+    }
+    panose[0] = PANOSE_LATIN_TEXT;
+    // Very light to black
+    panose[2] = weight_class.map_or(0, |class| (class / 100 + 1).clamp(2, 10) as u8);
+    panose[3] = if monospaced {
+        PANOSE_MONOSPACED
+    } else {
+        match width_class {
+            Some(1..=2) => 8,
+            Some(3..=4) => 6,
+            Some(5) => 3,
+            Some(6..=7) => 5,
+            Some(_) => 7,
+            None => 0,
+        }
+    };
+    // Small, standard or large x-height
+    panose[9] = match x_ratio {
+        Some(ratio) if ratio < 0.6 => 2,
+        Some(ratio) if ratio < 0.7 => 3,
+        Some(_) => 4,
+        None => 0,
+    };
+    panose
+}
+
+/// Vertical stem of the first stem glyph that has one, in font units
+fn main_stem(font: &FontData, info: &FontInfo) -> Option<f64> {
+    STEM_GLYPHS
+        .iter()
+        .filter(|name| font.glyphs.contains_key(**name))
+        .find_map(|name| {
+            measure_stems(&font.resolved_bezpaths(name), info)
+                .into_iter()
+                .filter(|stem| stem.direction == StemDirection::Vertical && !stem.thin)
+                .max_by_key(|stem| stem.rays)
+                .map(|stem| stem.width)
+        })
+        .or_else(|| info.stem_snap_v.first().copied())
+}
+
+/// Width in percent of normal: "H" against the cap height, or "n" against
+/// the x-height
+fn width_percent(font: &FontData, info: &FontInfo) -> Option<f64> {
+    [("H", info.cap_height), ("n", info.x_height)]
+        .into_iter()
+        .find_map(|(name, height)| {
+            let glyph = font.glyphs.get(name)?;
+            let height = height.filter(|height| *height > 0.0)?;
+            Some(glyph.advance_width / height * 100.0)
+        })
+}
+
+/// Whether the font's encoded glyphs with width all share one advance
+fn is_monospaced(font: &FontData) -> bool {
+    let advances: Vec<f64> = font
+        .glyphs
+        .values()
+        .filter(|glyph| !glyph.unicode_values.is_empty() && glyph.advance_width > 0.0)
+        .map(|glyph| glyph.advance_width)
+        .collect();
+    advances.len() >= MIN_MONOSPACED_GLYPHS
+        && advances.iter().all(|advance| *advance == advances[0])
+}
+
+impl Os2Classification {
+    /// Suggest a classification from the font's glyphs and metrics
+    pub fn suggest(font: &FontData, info: &FontInfo) -> Self {
+        let codepoints: BTreeSet<char> = font
+            .glyphs
+            .values()
+            .flat_map(|glyph| glyph.unicode_values.iter().copied())
+            .collect();
+        let em = info.units_per_em;
+        let weight_class = main_stem(font, info)
+            .filter(|_| em > 0.0)
+            .map(|stem| weight_class_for_stem(stem / em));
+        let width_class = width_percent(font, info).map(width_class_for_percent);
+        let latin = ('a'..='z').all(|c| codepoints.contains(&c));
+        let x_ratio = info
+            .x_height
+            .zip(info.cap_height.filter(|height| *height > 0.0))
+            .map(|(x_height, cap_height)| x_height / cap_height);
+        Self {
+            weight_class,
+            width_class,
+            panose: panose_digits(
+                latin,
+                weight_class,
+                width_class,
+                is_monospaced(font),
+                x_ratio,
+            ),
+            unicode_ranges: unicode_range_bits(&codepoints),
+            code_page_ranges: code_page_bits(&codepoints),
+        }
+    }
+
+    /// PANOSE digits with the suggested ones in place of the current ones,
+    /// keeping the digits that aren't suggested
+    fn merged_panose(&self, current: Option<[u8; 10]>) -> [u8; 10] {
+        let mut panose = current.unwrap_or_default();
+        for (digit, suggested) in panose.iter_mut().zip(self.panose) {
+            if suggested != 0 {
+                *digit = suggested;
+            }
+        }
+        panose
+    }
+
+    /// Values of the font info that differ from the suggestions
+    pub fn mismatches(&self, info: &FontInfo) -> Vec<Os2Mismatch> {
+        let mut mismatches = Vec::new();
+        let mut compare = |field, current: String, suggested: String| {
+            if current != suggested {
+                mismatches.push(Os2Mismatch {
+                    field,
+                    current,
+                    suggested,
+                });
+            }
+        };
+        let class = |class: Option<u32>| class.map_or("unset".to_string(), |c| c.to_string());
+        if self.weight_class.is_some() {
+            let (current, suggested) = (info.os2_weight_class, self.weight_class);
+            compare(Os2Field::WeightClass, class(current), class(suggested));
+        }
+        if self.width_class.is_some() {
+            let (current, suggested) = (info.os2_width_class, self.width_class);
+            compare(Os2Field::WidthClass, class(current), class(suggested));
+        }
+        if self.panose != [0; 10] {
+            let digits = |panose: [u8; 10]| format!("{panose:?}");
+            let current = info.panose.map_or("unset".to_string(), digits);
+            compare(
+                Os2Field::Panose,
+                current,
+                digits(self.merged_panose(info.panose)),
+            );
+        }
+        let bits = |bits: &[u8]| format!("{bits:?}");
+        compare(
+            Os2Field::UnicodeRanges,
+            bits(&info.unicode_ranges),
+            bits(&self.unicode_ranges),
+        );
+        compare(
+            Os2Field::CodePageRanges,
+            bits(&info.code_page_ranges),
+            bits(&self.code_page_ranges),
+        );
+        mismatches
+    }
+
+    /// Write one suggested value into the font info
+    pub fn apply(&self, field: Os2Field, info: &mut FontInfo) {
+        match field {
+            Os2Field::WeightClass => {
+                if self.weight_class.is_some() {
+                    info.os2_weight_class = self.weight_class;
+                }
+            }
+            Os2Field::WidthClass => {
+                if self.width_class.is_some() {
+                    info.os2_width_class = self.width_class;
+                }
+            }
+            Os2Field::Panose => info.panose = Some(self.merged_panose(info.panose)),
+            Os2Field::UnicodeRanges => info.unicode_ranges = self.unicode_ranges.clone(),
+            Os2Field::CodePageRanges => info.code_page_ranges = self.code_page_ranges.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classes_and_bits() {
+        assert_eq!(weight_class_for_stem(0.085), 400);
+        assert_eq!(weight_class_for_stem(0.14), 700);
+        assert_eq!(weight_class_for_stem(0.25), 900);
+        assert_eq!(width_class_for_percent(101.0), 5);
+        assert_eq!(width_class_for_percent(70.0), 3);
+
+        let latin: BTreeSet<char> = (' '..='~').chain(['Þ', 'é', '‰', '∑']).collect();
+        assert_eq!(unicode_range_bits(&latin), [0, 1, 31, 38]);
+        assert_eq!(code_page_bits(&latin), [0, 29]);
+        let cyrillic: BTreeSet<char> = ['Б'].into_iter().collect();
+        assert_eq!(code_page_bits(&cyrillic), [2]);
+        assert_eq!(code_page_bits(&BTreeSet::new()), [0]);
+    }
+
+    #[test]
+    fn test_mismatches_and_apply() {
+        let suggested = Os2Classification {
+            weight_class: Some(700),
+            width_class: None,
+            panose: panose_digits(true, Some(700), Some(5), false, Some(0.72)),
+            unicode_ranges: vec![0, 1],
+            code_page_ranges: vec![0],
+        };
+        assert_eq!(suggested.panose, [2, 0, 8, 3, 0, 0, 0, 0, 0, 4]);
+
+        let mut info = FontInfo {
+            os2_weight_class: Some(400),
+            panose: Some([2, 11, 5, 3, 0, 0, 0, 0, 0, 4]),
+            unicode_ranges: vec![0, 1],
+            ..Default::default()
+        };
+        let fields: Vec<Os2Field> = suggested
+            .mismatches(&info)
+            .iter()
+            .map(|mismatch| mismatch.field)
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                Os2Field::WeightClass,
+                Os2Field::Panose,
+                Os2Field::CodePageRanges
+            ]
+        );
+        for field in fields {
+            suggested.apply(field, &mut info);
+        }
+        assert!(suggested.mismatches(&info).is_empty());
+        // The serif style digit was set by hand and is kept
+        assert_eq!(info.panose, Some([2, 11, 8, 3, 0, 0, 0, 0, 0, 4]));
+    }
+}
@@ -0,0 +1,241 @@
+//! Renaming glyphs
+//!
+//! The rest of the font refers to a glyph by name: components in every
+//! layer, brace layers included, kerning groups and pairs, color layers,
+//! smart pieces, filter recipes, anchor sync opt-outs, and the glyph order
+//! and production names of the lib. `FontData::rename_glyphs` renames any
+//! number of glyphs at once and follows each of those references, so
+//! nothing is left pointing at an old name, and refuses renames that would
+//! give two glyphs one name. Find/replace and the glyph name fixes both
+//! rename through it and announce their renames with `GlyphsRenamed`, for
+//! the designspace rules and saved glyphs kept outside the font.
+
+use anyhow::{bail, Result};
+use std::collections::{HashMap, HashSet};
+
+use super::data::{FontData, GlyphData};
+
+impl FontData {
+    /// Whether a glyph of this name is on any layer, so the name is taken
+    pub fn is_glyph_name_taken(&self, name: &str) -> bool {
+        self.glyphs.contains_key(name)
+            || self
+                .layers
+                .iter()
+                .any(|layer| layer.glyphs.contains_key(name))
+    }
+
+    /// Rename one glyph and every reference to it (see `rename_glyphs`)
+    pub fn rename_glyph(&mut self, old: &str, new: &str) -> Result<Vec<String>> {
+        self.rename_glyphs(&HashMap::from([(old.to_string(), new.to_string())]))
+    }
+
+    /// Rename glyphs, old name to new name, and every reference to them
+    ///
+    /// All renames happen at once, so swaps and chains (a -> b, c -> a)
+    /// don't overwrite each other. Returns the glyphs of the default layer
+    /// that changed, renamed ones under their new names, sorted. Fails,
+    /// renaming nothing, when a new name is taken by a glyph that keeps its
+    /// name on any layer or is given to two glyphs.
+    pub fn rename_glyphs(&mut self, renames: &HashMap<String, String>) -> Result<Vec<String>> {
+        if renames.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut new_names = HashSet::new();
+        for new in renames.values() {
+            if !new_names.insert(new) {
+                bail!("Two glyphs can't both be renamed to '{new}'");
+            }
+            if self.is_glyph_name_taken(new) && !renames.contains_key(new) {
+                bail!("A glyph named '{new}' already exists");
+            }
+        }
+        let rename = |name: String| renames.get(&name).cloned().unwrap_or(name);
+
+        let mut changed = rename_in_glyphs(&mut self.glyphs, renames);
+        for layer in &mut self.layers {
+            rename_in_glyphs(&mut layer.glyphs, renames);
+        }
+
+        let kerning = &mut self.kerning;
+        for member in kerning.groups.values_mut().flatten() {
+            if let Some(new) = renames.get(member.as_str()) {
+                *member = new.clone();
+            }
+        }
+        kerning.pairs = std::mem::take(&mut kerning.pairs)
+            .into_iter()
+            .map(|(first, seconds)| {
+                let seconds = seconds
+                    .into_iter()
+                    .map(|(second, value)| (rename(second), value))
+                    .collect();
+                (rename(first), seconds)
+            })
+            .collect();
+
+        self.color.layers = std::mem::take(&mut self.color.layers)
+            .into_iter()
+            .map(|(base, mut layers)| {
+                for layer in &mut layers {
+                    if let Some(new) = renames.get(&layer.glyph_name) {
+                        layer.glyph_name = new.clone();
+                    }
+                }
+                (rename(base), layers)
+            })
+            .collect();
+
+        self.smart_pieces.rename_glyphs(renames);
+
+        let recipes = &mut self.glyph_filters.recipes;
+        *recipes = std::mem::take(recipes)
+            .into_iter()
+            .map(|(glyph_name, filters)| (rename(glyph_name), filters))
+            .collect();
+        let opted_out = &mut self.anchor_sync.opted_out;
+        *opted_out = std::mem::take(opted_out).into_iter().map(rename).collect();
+        self.glyph_order.rename_glyphs(renames);
+
+        changed.sort();
+        changed.dedup();
+        Ok(changed)
+    }
+}
+
+/// Rename the glyphs of one layer and the components referring to them,
+/// returning the glyphs that changed. New names must not be taken
+pub(crate) fn rename_in_glyphs(
+    glyphs: &mut HashMap<String, GlyphData>,
+    renames: &HashMap<String, String>,
+) -> Vec<String> {
+    // Take every renamed glyph out before inserting any
+    let renamed: Vec<GlyphData> = renames
+        .iter()
+        .filter_map(|(old, new)| {
+            let mut glyph = glyphs.remove(old)?;
+            glyph.name = new.clone();
+            Some(glyph)
+        })
+        .collect();
+    let mut changed: Vec<String> = renamed.iter().map(|glyph| glyph.name.clone()).collect();
+    for glyph in renamed {
+        glyphs.insert(glyph.name.clone(), glyph);
+    }
+
+    for glyph in glyphs.values_mut() {
+        let mut references = false;
+        for component in &mut glyph.components {
+            if let Some(new) = renames.get(&component.base_glyph) {
+                component.base_glyph = new.clone();
+                references = true;
+            }
+        }
+        if references {
+            changed.push(glyph.name.clone());
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::{ComponentData, GlyphFilter, GlyphLayer};
+
+    fn glyph(name: &str, components: &[&str]) -> GlyphData {
+        GlyphData {
+            components: components
+                .iter()
+                .map(|base| ComponentData {
+                    base_glyph: base.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..GlyphData::test(name, 500.0)
+        }
+    }
+
+    #[test]
+    fn test_renames_follow_every_reference() {
+        let mut font = FontData::default();
+        for glyph in [glyph("a", &[]), glyph("b", &[]), glyph("aacute", &["a"])] {
+            font.glyphs.insert(glyph.name.clone(), glyph);
+        }
+        font.anchor_sync.opted_out.insert("aacute".to_string());
+        font.glyph_filters
+            .recipes
+            .insert("a".to_string(), vec![GlyphFilter::Slant { angle: 10.0 }]);
+
+        let changed = font.rename_glyph("aacute", "uni00E1").unwrap();
+        assert_eq!(changed, vec!["uni00E1".to_string()]);
+        assert!(font.anchor_sync.opted_out.contains("uni00E1"));
+
+        // Swapping two glyphs keeps both and follows them into components
+        let swap = HashMap::from([
+            ("a".to_string(), "b".to_string()),
+            ("b".to_string(), "a".to_string()),
+        ]);
+        let changed = font.rename_glyphs(&swap).unwrap();
+        assert_eq!(changed, vec!["a", "b", "uni00E1"]);
+        assert_eq!(font.glyphs.len(), 3);
+        assert_eq!(font.glyphs["uni00E1"].components[0].base_glyph, "b");
+        assert!(font.glyph_filters.recipes.contains_key("b"));
+        assert!(!font.glyph_filters.recipes.contains_key("a"));
+    }
+
+    #[test]
+    fn test_renames_to_taken_names_are_refused() {
+        let mut font = FontData::default();
+        for glyph in [glyph("a", &[]), glyph("b", &[]), glyph("aacute", &["a"])] {
+            font.glyphs.insert(glyph.name.clone(), glyph);
+        }
+        font.layers.push(GlyphLayer {
+            name: "public.background".to_string(),
+            glyphs: HashMap::from([("c".to_string(), glyph("c", &[]))]),
+        });
+
+        assert!(font.rename_glyph("a", "b").is_err());
+        // A glyph only drawn on another layer still has its name
+        assert!(font.rename_glyph("a", "c").is_err());
+        let both = HashMap::from([
+            ("a".to_string(), "d".to_string()),
+            ("b".to_string(), "d".to_string()),
+        ]);
+        assert!(font.rename_glyphs(&both).is_err());
+
+        assert_eq!(font.glyphs["a"].name, "a");
+        assert_eq!(font.glyphs["b"].name, "b");
+        assert_eq!(font.glyphs["aacute"].components[0].base_glyph, "a");
+    }
+
+    #[test]
+    fn test_renames_follow_brace_layers_and_the_glyph_order() {
+        let mut font = FontData::default();
+        for glyph in [glyph("dollar", &[]), glyph("dollar.bold", &["dollar"])] {
+            font.glyphs.insert(glyph.name.clone(), glyph);
+        }
+        font.layers.push(GlyphLayer {
+            name: "{600}".to_string(),
+            glyphs: HashMap::from([("dollar".to_string(), glyph("dollar", &[]))]),
+        });
+        font.glyph_order.order = vec!["dollar".to_string(), "dollar.bold".to_string()];
+        font.glyph_order
+            .postscript_names
+            .insert("dollar.bold".to_string(), "dollar.bold".to_string());
+
+        font.rename_glyph("dollar.bold", "dollar.rvrn").unwrap();
+        font.rename_glyph("dollar", "dollar.alt").unwrap();
+
+        assert!(font.layers[0].glyphs.contains_key("dollar.alt"));
+        assert_eq!(font.glyph_order.order, ["dollar.alt", "dollar.rvrn"]);
+        assert_eq!(
+            font.glyph_order.postscript_names["dollar.rvrn"],
+            "dollar.bold"
+        );
+        assert_eq!(
+            font.glyphs["dollar.rvrn"].components[0].base_glyph,
+            "dollar.alt"
+        );
+    }
+}
@@ -0,0 +1,153 @@
+//! Saved editing sessions
+//!
+//! A project remembers where work stopped: the text buffers with their
+//! sorts, names, positions and cursors, which buffer was typed into, the
+//! camera, the glyph being viewed and the tool. The session is written into
+//! the font lib on save and put back when the font is opened again. It is
+//! editor state rather than font data, so it is kept in plain numbers and
+//! names here; the editor converts it to and from its own types.
+
+use serde::{Deserialize, Serialize};
+
+/// Font lib key holding the saved editing session
+pub const SESSION_LIB_KEY: &str = "org.bezy.session";
+
+/// Where work stopped in a project
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceSession {
+    pub buffers: Vec<SessionBuffer>,
+    /// Index in `buffers` of the buffer being typed into
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_buffer: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera: Option<SessionCamera>,
+    /// Glyph being viewed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_glyph: Option<String>,
+    /// Id of the tool in use
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+}
+
+/// Direction a buffer's text runs in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionDirection {
+    #[default]
+    Ltr,
+    Rtl,
+    Freeform,
+}
+
+/// Alignment of lines in a buffer's frame
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// A text buffer and its sorts
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionBuffer {
+    pub name: String,
+    pub direction: SessionDirection,
+    /// Where the buffer starts, in design space
+    pub position: (f64, f64),
+    /// Width of the frame the text wraps in, if it wraps
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frame_width: Option<f64>,
+    pub alignment: SessionAlignment,
+    pub cursor: usize,
+    pub sorts: Vec<SessionSort>,
+}
+
+/// A sort of a text buffer
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionSort {
+    /// Glyph of the sort, or `None` for a line break
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub glyph: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codepoint: Option<char>,
+    pub advance: f64,
+    /// Whether the sort's glyph was being edited
+    pub active: bool,
+    /// Offset from the sort's place in the text, for freeform sorts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<(f64, f64)>,
+}
+
+/// Camera position and zoom
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionCamera {
+    pub x: f64,
+    pub y: f64,
+    /// Projection scale; larger is further out
+    pub scale: f64,
+}
+
+impl WorkspaceSession {
+    /// Read the session from a UFO font lib, if one was saved
+    pub fn from_norad_lib(lib: &norad::Plist) -> Option<Self> {
+        lib.get(SESSION_LIB_KEY)
+            .and_then(|value| serde_json::to_value(value).ok())
+            .and_then(|json| serde_json::from_value(json).ok())
+    }
+
+    /// Write the session into a UFO font lib; options left out are read
+    /// back as `None`, since a plist has no null
+    pub fn write_to_norad_lib(&self, lib: &mut norad::Plist) {
+        lib.remove(SESSION_LIB_KEY);
+        if let Ok(value) = serde_json::to_value(self).and_then(serde_json::from_value) {
+            lib.insert(SESSION_LIB_KEY.to_string(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lib_round_trip() {
+        let mut lib = norad::Plist::new();
+        assert_eq!(WorkspaceSession::from_norad_lib(&lib), None);
+
+        let session = WorkspaceSession {
+            buffers: vec![SessionBuffer {
+                name: "Proof".to_string(),
+                direction: SessionDirection::Rtl,
+                position: (120.0, -2400.0),
+                frame_width: Some(3000.0),
+                alignment: SessionAlignment::Right,
+                cursor: 1,
+                sorts: vec![
+                    SessionSort {
+                        glyph: Some("alef-ar".to_string()),
+                        codepoint: Some('\u{627}'),
+                        advance: 250.0,
+                        active: true,
+                        offset: None,
+                    },
+                    SessionSort::default(),
+                ],
+            }],
+            active_buffer: Some(0),
+            camera: Some(SessionCamera {
+                x: 400.0,
+                y: 300.0,
+                scale: 2.5,
+            }),
+            active_glyph: Some("alef-ar".to_string()),
+            tool: Some("text".to_string()),
+        };
+        session.write_to_norad_lib(&mut lib);
+        assert_eq!(WorkspaceSession::from_norad_lib(&lib), Some(session));
+    }
+}
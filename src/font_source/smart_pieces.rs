@@ -0,0 +1,431 @@
+//! Smart pieces: reusable path fragments shared between glyphs
+//!
+//! A piece (a serif, a terminal, a stroke ending) is drawn once in a master
+//! glyph named `_piece.<name>` and stamped into other glyphs as ordinary
+//! contours. Each stamp remembers a hash of each contour it produced and the
+//! scale/flip transform it was placed with, so editing the master redraws
+//! every use, wherever its contours have moved in the outline. Editing a
+//! stamped copy detaches it and leaves the copy as plain contours.
+//!
+//! Master glyphs are kept out of compiled fonts via `public.skipExportGlyphs`.
+
+use kurbo::{Affine, Point};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::data::{ContourData, FontData, GlyphData, OutlineData};
+
+/// Font lib key holding the stamped piece instances
+pub const SMART_PIECES_LIB_KEY: &str = "org.bezy.smartPieces";
+/// Standard font lib key listing glyphs that compilers should leave out
+pub const SKIP_EXPORT_LIB_KEY: &str = "public.skipExportGlyphs";
+/// Prefix of the glyphs that hold piece masters
+pub const PIECE_GLYPH_PREFIX: &str = "_piece.";
+
+/// Glyph name holding the master of a piece
+pub fn piece_glyph_name(piece: &str) -> String {
+    format!("{PIECE_GLYPH_PREFIX}{piece}")
+}
+
+/// Piece name if `glyph_name` is a piece master
+pub fn piece_name(glyph_name: &str) -> Option<&str> {
+    glyph_name.strip_prefix(PIECE_GLYPH_PREFIX)
+}
+
+/// One use of a piece inside a glyph
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PieceInstance {
+    pub piece: String,
+    /// Affine placement (xx, xy, yx, yy, x, y) applied to the master
+    pub transform: [f64; 6],
+    /// Index of the first contour this instance produced, as of the last
+    /// sync
+    pub first_contour: usize,
+    /// Hash of each produced contour, identifying them when other contours
+    /// are added or removed and spotting edited copies
+    pub contour_hashes: Vec<u32>,
+}
+
+/// Piece instances for the whole font
+#[derive(Clone, Debug, Default)]
+pub struct SmartPieceData {
+    /// Glyph name -> pieces stamped into it
+    pub instances: HashMap<String, Vec<PieceInstance>>,
+    /// Master contours as of the last sync, to detect master edits
+    synced_masters: HashMap<String, Vec<ContourData>>,
+}
+
+/// Outcome of propagating master edits
+#[derive(Debug, Default)]
+pub struct PieceSyncReport {
+    pub updated_glyphs: Vec<String>,
+    /// Glyph name and piece of instances that no longer match their master
+    pub detached: Vec<(String, String)>,
+}
+
+impl SmartPieceData {
+    /// Read piece instances from a UFO font lib
+    pub fn from_norad_lib(lib: &norad::Plist, glyphs: &HashMap<String, GlyphData>) -> Self {
+        let instances = lib
+            .get(SMART_PIECES_LIB_KEY)
+            .and_then(|value| serde_json::to_value(value).ok())
+            .and_then(|json| serde_json::from_value(json).ok())
+            .unwrap_or_default();
+
+        let synced_masters = glyphs
+            .iter()
+            .filter_map(|(name, glyph)| Some((piece_name(name)?.to_string(), contours_of(glyph))))
+            .collect();
+
+        Self {
+            instances,
+            synced_masters,
+        }
+    }
+
+    /// Write piece instances and the skip-export list into a UFO font lib
+    pub fn write_to_norad_lib(&self, lib: &mut norad::Plist, glyphs: &HashMap<String, GlyphData>) {
+        lib.remove(SMART_PIECES_LIB_KEY);
+        lib.remove(SKIP_EXPORT_LIB_KEY);
+
+        let instances: HashMap<&String, &Vec<PieceInstance>> = self
+            .instances
+            .iter()
+            .filter(|(_, instances)| !instances.is_empty())
+            .collect();
+        if !instances.is_empty() {
+            if let Ok(value) = serde_json::to_value(&instances).and_then(serde_json::from_value) {
+                lib.insert(SMART_PIECES_LIB_KEY.to_string(), value);
+            }
+        }
+
+        let mut masters: Vec<&String> =
+            glyphs.keys().filter(|name| piece_name(name).is_some()).collect();
+        masters.sort();
+        if !masters.is_empty() {
+            if let Ok(value) = serde_json::to_value(&masters).and_then(serde_json::from_value) {
+                lib.insert(SKIP_EXPORT_LIB_KEY.to_string(), value);
+            }
+        }
+    }
+}
+
+impl SmartPieceData {
+    /// Follow glyph renames: the glyphs pieces are stamped into, and pieces
+    /// whose master is renamed to another piece master
+    pub(crate) fn rename_glyphs(&mut self, renames: &HashMap<String, String>) {
+        let pieces: HashMap<&str, &str> = renames
+            .iter()
+            .filter_map(|(old, new)| Some((piece_name(old)?, piece_name(new)?)))
+            .collect();
+        let rename_piece = |piece: String| match pieces.get(piece.as_str()) {
+            Some(new) => new.to_string(),
+            None => piece,
+        };
+
+        self.instances = std::mem::take(&mut self.instances)
+            .into_iter()
+            .map(|(glyph_name, mut instances)| {
+                for instance in &mut instances {
+                    instance.piece = rename_piece(std::mem::take(&mut instance.piece));
+                }
+                let glyph_name = renames.get(&glyph_name).cloned().unwrap_or(glyph_name);
+                (glyph_name, instances)
+            })
+            .collect();
+        self.synced_masters = std::mem::take(&mut self.synced_masters)
+            .into_iter()
+            .map(|(piece, contours)| (rename_piece(piece), contours))
+            .collect();
+    }
+}
+
+impl FontData {
+    /// Names of all pieces, sorted
+    pub fn smart_piece_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .glyphs
+            .keys()
+            .filter_map(|name| piece_name(name).map(str::to_string))
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Create or replace a piece master from contours
+    ///
+    /// Glyphs using an existing piece pick up the change on the next
+    /// `sync_smart_pieces`.
+    pub fn define_smart_piece(&mut self, piece: &str, contours: Vec<ContourData>) {
+        let glyph_name = piece_glyph_name(piece);
+        let glyph = self
+            .glyphs
+            .entry(glyph_name.clone())
+            .or_insert_with(|| GlyphData {
+                name: glyph_name,
+                advance_width: 0.0,
+                advance_height: None,
+                unicode_values: Vec::new(),
+                outline: None,
+                components: Vec::new(),
+                anchors: Vec::new(),
+            });
+        glyph.outline = Some(OutlineData { contours });
+    }
+
+    /// Stamp a piece into a glyph's outline, linked to its master
+    pub fn stamp_smart_piece(
+        &mut self,
+        glyph_name: &str,
+        piece: &str,
+        transform: Affine,
+    ) -> Result<(), String> {
+        let master = self
+            .glyphs
+            .get(&piece_glyph_name(piece))
+            .map(contours_of)
+            .filter(|contours| !contours.is_empty())
+            .ok_or_else(|| format!("piece '{piece}' has no contours"))?;
+        if piece_name(glyph_name) == Some(piece) {
+            return Err(format!("cannot stamp '{piece}' into its own master"));
+        }
+        let glyph = self
+            .glyphs
+            .get_mut(glyph_name)
+            .ok_or_else(|| format!("glyph '{glyph_name}' not found"))?;
+
+        let stamped = place_contours(&master, transform);
+        let outline = glyph.outline.get_or_insert_with(|| OutlineData {
+            contours: Vec::new(),
+        });
+        let instance = PieceInstance {
+            piece: piece.to_string(),
+            transform: transform.as_coeffs(),
+            first_contour: outline.contours.len(),
+            contour_hashes: stamped.iter().map(contour_hash).collect(),
+        };
+        outline.contours.extend(stamped);
+
+        self.smart_pieces
+            .instances
+            .entry(glyph_name.to_string())
+            .or_default()
+            .push(instance);
+        self.smart_pieces
+            .synced_masters
+            .entry(piece.to_string())
+            .or_insert(master);
+        Ok(())
+    }
+
+    /// Unlink every piece in a glyph, keeping the contours as they are
+    pub fn detach_smart_pieces(&mut self, glyph_name: &str) -> usize {
+        self.smart_pieces
+            .instances
+            .remove(glyph_name)
+            .map_or(0, |instances| instances.len())
+    }
+
+    /// Redraw the uses of every piece whose master changed since last sync
+    pub fn sync_smart_pieces(&mut self) -> PieceSyncReport {
+        let mut report = PieceSyncReport::default();
+
+        let changed: HashMap<String, Vec<ContourData>> = self
+            .glyphs
+            .iter()
+            .filter_map(|(name, glyph)| {
+                let piece = piece_name(name)?;
+                let contours = contours_of(glyph);
+                let synced = self.smart_pieces.synced_masters.get(piece);
+                (synced != Some(&contours)).then(|| (piece.to_string(), contours))
+            })
+            .collect();
+        if changed.is_empty() {
+            return report;
+        }
+
+        for (glyph_name, instances) in self.smart_pieces.instances.iter_mut() {
+            if !instances.iter().any(|instance| changed.contains_key(&instance.piece)) {
+                continue;
+            }
+            let Some(outline) = self
+                .glyphs
+                .get_mut(glyph_name)
+                .and_then(|glyph| glyph.outline.as_mut())
+            else {
+                continue;
+            };
+
+            instances.sort_by_key(|instance| instance.first_contour);
+            let mut shift: isize = 0;
+            let mut updated = false;
+            instances.retain_mut(|instance| {
+                let expected = instance.first_contour.saturating_add_signed(shift);
+                let Some(start) = locate_instance(instance, &outline.contours, expected) else {
+                    report
+                        .detached
+                        .push((glyph_name.clone(), instance.piece.clone()));
+                    return false;
+                };
+                instance.first_contour = start;
+                let Some(master) = changed.get(&instance.piece) else {
+                    return true;
+                };
+
+                let stamped = place_contours(master, Affine::new(instance.transform));
+                let old_len = instance.contour_hashes.len();
+                shift += stamped.len() as isize - old_len as isize;
+                instance.contour_hashes = stamped.iter().map(contour_hash).collect();
+                outline.contours.splice(start..start + old_len, stamped);
+                updated = true;
+                true
+            });
+
+            if updated {
+                report.updated_glyphs.push(glyph_name.clone());
+            }
+        }
+
+        self.smart_pieces.instances.retain(|_, instances| !instances.is_empty());
+        self.smart_pieces.synced_masters.extend(changed);
+        report
+    }
+}
+
+fn contours_of(glyph: &GlyphData) -> Vec<ContourData> {
+    glyph
+        .outline
+        .as_ref()
+        .map(|outline| outline.contours.clone())
+        .unwrap_or_default()
+}
+
+/// Hash of a contour's points, rounded to hundredths of a unit so that it
+/// survives saving and reopening the font
+fn contour_hash(contour: &ContourData) -> u32 {
+    let mut hasher = blake3::Hasher::new();
+    for point in &contour.points {
+        for coordinate in [point.x, point.y] {
+            hasher.update(&((coordinate * 100.0).round() as i64).to_le_bytes());
+        }
+        hasher.update(&[point.point_type as u8]);
+    }
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(&hasher.finalize().as_bytes()[..4]);
+    u32::from_le_bytes(bytes)
+}
+
+/// Index of an instance's contours: the run of unedited copies nearest to
+/// where the instance expects them, or None if none is left
+fn locate_instance(
+    instance: &PieceInstance,
+    contours: &[ContourData],
+    expected: usize,
+) -> Option<usize> {
+    if instance.contour_hashes.is_empty() {
+        return None;
+    }
+    let hashes: Vec<u32> = contours.iter().map(contour_hash).collect();
+    hashes
+        .windows(instance.contour_hashes.len())
+        .enumerate()
+        .filter(|(_, run)| *run == instance.contour_hashes.as_slice())
+        .map(|(start, _)| start)
+        .min_by_key(|start| start.abs_diff(expected))
+}
+
+/// Transform master contours, reversing them if the placement mirrors them
+/// so the stamped outline keeps the master's winding direction
+fn place_contours(master: &[ContourData], transform: Affine) -> Vec<ContourData> {
+    let mirrored = transform.determinant() < 0.0;
+    master
+        .iter()
+        .map(|contour| {
+            let mut placed = contour.clone();
+            for point in &mut placed.points {
+                let moved = transform * Point::new(point.x, point.y);
+                point.x = moved.x;
+                point.y = moved.y;
+            }
+            if mirrored {
+                placed.reversed()
+            } else {
+                placed
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn font_with_glyph() -> FontData {
+        let mut font = FontData::default();
+        font.define_smart_piece("serif", vec![ContourData::test_square(10.0)]);
+        let glyph = GlyphData {
+            outline: Some(OutlineData {
+                contours: vec![ContourData::test_square(100.0)],
+            }),
+            ..GlyphData::test("I", 300.0)
+        };
+        font.glyphs.insert("I".to_string(), glyph);
+        font
+    }
+
+    #[test]
+    fn test_master_edits_update_stamped_copies() {
+        let mut font = font_with_glyph();
+        font.stamp_smart_piece("I", "serif", Affine::translate((50.0, 0.0)))
+            .unwrap();
+        font.stamp_smart_piece("I", "serif", Affine::FLIP_X).unwrap();
+
+        font.define_smart_piece(
+            "serif",
+            vec![ContourData::test_square(20.0), ContourData::test_square(5.0)],
+        );
+        let report = font.sync_smart_pieces();
+
+        assert_eq!(report.updated_glyphs, vec!["I".to_string()]);
+        let contours = &font.glyphs["I"].outline.as_ref().unwrap().contours;
+        assert_eq!(contours.len(), 5);
+        assert_eq!(contours[1].points[2].x, 70.0);
+        // The flipped copy now starts after the grown first copy
+        assert_eq!(font.smart_pieces.instances["I"][1].first_contour, 3);
+        assert_eq!(contours[3].points[2].x, -20.0);
+    }
+
+    #[test]
+    fn test_edited_copies_detach() {
+        let mut font = font_with_glyph();
+        font.stamp_smart_piece("I", "serif", Affine::IDENTITY).unwrap();
+        let outline = font.glyphs.get_mut("I").unwrap().outline.as_mut().unwrap();
+        outline.contours[1].points.pop();
+
+        font.define_smart_piece("serif", vec![ContourData::test_square(20.0)]);
+        let report = font.sync_smart_pieces();
+
+        assert_eq!(report.detached.len(), 1);
+        assert!(font.smart_pieces.instances.is_empty());
+    }
+
+    #[test]
+    fn test_copies_are_found_after_earlier_contours_are_deleted() {
+        let mut font = font_with_glyph();
+        font.stamp_smart_piece("I", "serif", Affine::IDENTITY).unwrap();
+        let outline = font.glyphs.get_mut("I").unwrap().outline.as_mut().unwrap();
+        outline.contours.push(ContourData::test_square(30.0));
+        // The copy moves down to index 0, and the user's square to index 1
+        outline.contours.remove(0);
+
+        font.define_smart_piece("serif", vec![ContourData::test_square(20.0)]);
+        let report = font.sync_smart_pieces();
+
+        assert!(report.detached.is_empty());
+        let contours = &font.glyphs["I"].outline.as_ref().unwrap().contours;
+        assert_eq!(contours.len(), 2);
+        assert_eq!(contours[0].points[2].x, 20.0);
+        assert_eq!(contours[1].points[2].x, 30.0);
+        assert_eq!(font.smart_pieces.instances["I"][0].first_contour, 0);
+    }
+}
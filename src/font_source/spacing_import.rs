@@ -0,0 +1,359 @@
+//! Metrics and kerning from other tools
+//!
+//! Reads advance widths and kerning from AFM files (`WX` of the character
+//! metrics, `KPX` pairs), FontTools-style kerning plists (a `kerning.plist`
+//! and the `groups.plist` next to it, if any) and CSV files. A CSV file with
+//! a header has `glyph` and `advance` columns for metrics, like the glyph
+//! reports, or `first`, `second` and `value` columns for kerning; without a
+//! header, two columns are metrics and three are kerning.
+//!
+//! Imports are merged into the font one of three ways, see `MergeMode`.
+
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::data::FontData;
+use super::kerning::{KerningData, FIRST_GROUP_PREFIX, SECOND_GROUP_PREFIX};
+
+/// The kinds of files spacing is imported from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpacingFormat {
+    Afm,
+    KerningPlist,
+    Csv,
+}
+
+impl SpacingFormat {
+    /// The format of a file, by its extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "afm" => Some(Self::Afm),
+            "plist" => Some(Self::KerningPlist),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// How imported values meet the font's own
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeMode {
+    /// The imported kerning replaces the font's, and imported advances are
+    /// set
+    Replace,
+    /// Imported pairs, groups and advances are set, the rest is kept
+    Merge,
+    /// Only pairs and groups the font lacks are added, and advances only
+    /// of glyphs without one
+    SkipExisting,
+}
+
+/// Advances and kerning read from a file
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportedSpacing {
+    pub advances: BTreeMap<String, f64>,
+    pub kerning: KerningData,
+}
+
+/// What applying an import changed
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportSummary {
+    /// Glyphs whose advance was set
+    pub advanced: Vec<String>,
+    pub pairs: usize,
+    pub groups: usize,
+    /// Values left alone because the font already had them
+    pub skipped: usize,
+    /// Glyphs with an imported advance that the font doesn't have
+    pub unknown_glyphs: Vec<String>,
+}
+
+/// Read the spacing of an AFM, kerning plist or CSV file
+pub fn read_spacing_file(path: &Path) -> Result<ImportedSpacing> {
+    let Some(format) = SpacingFormat::from_path(path) else {
+        bail!(
+            "Spacing is imported from .afm, .plist or .csv files, not {}",
+            path.display()
+        );
+    };
+    let bytes =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let text = || String::from_utf8_lossy(&bytes).into_owned();
+    let spacing = match format {
+        SpacingFormat::Afm => parse_afm(&text()),
+        SpacingFormat::Csv => parse_csv(&text()),
+        SpacingFormat::KerningPlist => {
+            let groups = path.with_file_name("groups.plist");
+            let groups = std::fs::read(&groups).ok();
+            parse_kerning_plist(&bytes, groups.as_deref())
+        }
+    };
+    spacing.with_context(|| format!("Failed to import {}", path.display()))
+}
+
+/// Advances and pairs of an AFM file
+pub fn parse_afm(text: &str) -> Result<ImportedSpacing> {
+    let mut spacing = ImportedSpacing::default();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with("C ") || line.starts_with("CH ") {
+            let mut advance = None;
+            let mut name = None;
+            for entry in line.split(';') {
+                let mut words = entry.split_whitespace();
+                match (words.next(), words.next()) {
+                    (Some("WX"), Some(value)) => advance = value.parse::<f64>().ok(),
+                    (Some("N"), Some(value)) => name = Some(value.to_string()),
+                    _ => {}
+                }
+            }
+            if let (Some(name), Some(advance)) = (name, advance) {
+                spacing.advances.insert(name, advance);
+            }
+        } else if let Some(pair) = line.strip_prefix("KPX ") {
+            let words: Vec<&str> = pair.split_whitespace().collect();
+            let [first, second, value] = words[..] else {
+                bail!("Malformed kerning pair: {line}");
+            };
+            let value = value
+                .parse::<f64>()
+                .with_context(|| format!("Malformed kerning pair: {line}"))?;
+            spacing.kerning.set_value(first, second, value);
+        }
+    }
+    if spacing.advances.is_empty() && spacing.kerning.pairs.is_empty() {
+        bail!("No character metrics or kerning pairs found");
+    }
+    Ok(spacing)
+}
+
+/// Pairs of a kerning plist, with the groups of a groups plist
+pub fn parse_kerning_plist(kerning: &[u8], groups: Option<&[u8]>) -> Result<ImportedSpacing> {
+    let mut spacing = ImportedSpacing::default();
+    let pairs: BTreeMap<String, BTreeMap<String, f64>> =
+        plist::from_bytes(kerning).context("Invalid kerning plist")?;
+    for (first, seconds) in pairs {
+        for (second, value) in seconds {
+            spacing.kerning.set_value(&first, &second, value);
+        }
+    }
+    if let Some(groups) = groups {
+        spacing.kerning.groups = plist::from_bytes(groups).context("Invalid groups plist")?;
+    }
+    Ok(spacing)
+}
+
+/// The fields of a CSV line, unquoted
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+        .iter()
+        .map(|field| field.trim().to_string())
+        .collect()
+}
+
+/// Advances or pairs of a CSV file
+pub fn parse_csv(text: &str) -> Result<ImportedSpacing> {
+    let mut lines = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .peekable();
+    let Some(first_line) = lines.peek() else {
+        bail!("The file is empty");
+    };
+    let first_fields = csv_fields(first_line);
+    let is_number = |field: &str| field.parse::<f64>().is_ok();
+    // Columns of glyph and advance, or of first, second and value
+    let (columns, has_header) = if first_fields.last().is_some_and(|field| is_number(field)) {
+        match first_fields.len() {
+            2 => (vec![0, 1], false),
+            3 => (vec![0, 1, 2], false),
+            _ => bail!("Expected two or three columns without a header"),
+        }
+    } else {
+        let column = |names: &[&str]| {
+            first_fields
+                .iter()
+                .position(|field| names.contains(&field.to_ascii_lowercase().as_str()))
+        };
+        let metrics = column(&["glyph", "name"]).zip(column(&["advance", "width"]));
+        let kerning = column(&["first", "left"])
+            .zip(column(&["second", "right"]))
+            .zip(column(&["value", "kerning"]));
+        match (metrics, kerning) {
+            (_, Some(((first, second), value))) => (vec![first, second, value], true),
+            (Some((glyph, advance)), None) => (vec![glyph, advance], true),
+            _ => bail!("Expected glyph and advance or first, second and value columns"),
+        }
+    };
+    if has_header {
+        lines.next();
+    }
+
+    let mut spacing = ImportedSpacing::default();
+    for (index, line) in lines.enumerate() {
+        let fields = csv_fields(line);
+        let field = |column: usize| fields.get(columns[column]).map(String::as_str);
+        let number = field(columns.len() - 1).and_then(|value| value.parse::<f64>().ok());
+        let line_number = index + 1 + usize::from(has_header);
+        let Some(number) = number else {
+            bail!("Line {line_number} has no number where one was expected");
+        };
+        match (field(0), columns.len()) {
+            (Some(glyph), 2) if !glyph.is_empty() => {
+                spacing.advances.insert(glyph.to_string(), number);
+            }
+            (Some(first), 3) if !first.is_empty() => {
+                let Some(second) = field(1).filter(|second| !second.is_empty()) else {
+                    bail!("Line {line_number} has no second side");
+                };
+                spacing.kerning.set_value(first, second, number);
+            }
+            _ => bail!("Line {line_number} has no glyph name"),
+        }
+    }
+    Ok(spacing)
+}
+
+fn is_kerning_group(name: &str) -> bool {
+    name.starts_with(FIRST_GROUP_PREFIX) || name.starts_with(SECOND_GROUP_PREFIX)
+}
+
+impl ImportedSpacing {
+    /// How many imported pairs the font already has a value for
+    pub fn existing_pairs(&self, kerning: &KerningData) -> usize {
+        self.kerning
+            .pairs
+            .iter()
+            .flat_map(|(first, seconds)| seconds.keys().map(move |second| (first, second)))
+            .filter(|(first, second)| kerning.value(first, second).is_some())
+            .count()
+    }
+
+    /// Merge the import into a font
+    pub fn apply(&self, font: &mut FontData, mode: MergeMode) -> ImportSummary {
+        let mut summary = ImportSummary::default();
+
+        for (name, advance) in &self.advances {
+            let Some(glyph) = font.glyphs.get_mut(name) else {
+                summary.unknown_glyphs.push(name.clone());
+                continue;
+            };
+            if mode == MergeMode::SkipExisting && glyph.advance_width != 0.0 {
+                summary.skipped += 1;
+                continue;
+            }
+            if glyph.advance_width != *advance {
+                glyph.advance_width = *advance;
+                summary.advanced.push(name.clone());
+            }
+        }
+
+        let kerning = &mut font.kerning;
+        if mode == MergeMode::Replace {
+            // Only what was imported is replaced: a file without groups
+            // keeps the font's groups, and one without pairs its pairs
+            if !self.kerning.groups.is_empty() {
+                kerning.groups.retain(|name, _| !is_kerning_group(name));
+            }
+            if !self.kerning.pairs.is_empty() {
+                kerning.pairs.clear();
+            }
+        }
+        for (name, members) in &self.kerning.groups {
+            if mode == MergeMode::SkipExisting && kerning.groups.contains_key(name) {
+                summary.skipped += 1;
+                continue;
+            }
+            kerning.groups.insert(name.clone(), members.clone());
+            summary.groups += 1;
+        }
+        for (first, seconds) in &self.kerning.pairs {
+            for (second, value) in seconds {
+                if mode == MergeMode::SkipExisting && kerning.value(first, second).is_some() {
+                    summary.skipped += 1;
+                    continue;
+                }
+                kerning.set_value(first, second, *value);
+                summary.pairs += 1;
+            }
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::GlyphData;
+
+    #[test]
+    fn test_spacing_import() {
+        let afm = "StartCharMetrics 2\n\
+                   C 65 ; WX 640 ; N A ; B 10 0 630 700 ;\n\
+                   C -1 ; WX 600 ; N A.alt ; B 10 0 590 700 ;\n\
+                   EndCharMetrics\n\
+                   KPX A V -80\n";
+        let spacing = parse_afm(afm).unwrap();
+        assert_eq!(spacing.advances["A"], 640.0);
+        assert_eq!(spacing.advances["A.alt"], 600.0);
+        assert_eq!(spacing.kerning.value("A", "V"), Some(-80.0));
+
+        // A glyph report reads back as metrics
+        let report = "source,glyph,unicodes,advance,lsb\nA.ufo,\"A, wide\",U+0041,700,10\n";
+        let spacing = parse_csv(report).unwrap();
+        assert_eq!(spacing.advances["A, wide"], 700.0);
+        let pairs = parse_csv("A,V,-80\nT,o,-40\n").unwrap();
+        assert_eq!(pairs.kerning.value("T", "o"), Some(-40.0));
+        assert!(parse_csv("A,V,W,X,-80\n").is_err());
+
+        let mut font = FontData::default();
+        for glyph in [GlyphData::test("A", 600.0), GlyphData::test("V", 0.0)] {
+            font.glyphs.insert(glyph.name.clone(), glyph);
+        }
+        font.kerning.set_value("A", "V", -60.0);
+        font.kerning.set_value("T", "o", -30.0);
+        let imported = ImportedSpacing {
+            advances: BTreeMap::from([
+                ("A".to_string(), 640.0),
+                ("V".to_string(), 620.0),
+                ("W".to_string(), 800.0),
+            ]),
+            kerning: parse_afm("KPX A V -80\nKPX V A -70\n").unwrap().kerning,
+        };
+        assert_eq!(imported.existing_pairs(&font.kerning), 1);
+
+        let mut skipped = font.clone();
+        let summary = imported.apply(&mut skipped, MergeMode::SkipExisting);
+        assert_eq!(summary.advanced, ["V"]);
+        assert_eq!((summary.pairs, summary.skipped), (1, 2));
+        assert_eq!(summary.unknown_glyphs, ["W"]);
+        assert_eq!(skipped.kerning.value("A", "V"), Some(-60.0));
+
+        let mut merged = font.clone();
+        imported.apply(&mut merged, MergeMode::Merge);
+        assert_eq!(merged.glyphs["A"].advance_width, 640.0);
+        assert_eq!(merged.kerning.value("A", "V"), Some(-80.0));
+        assert_eq!(merged.kerning.value("T", "o"), Some(-30.0));
+
+        let mut replaced = font;
+        imported.apply(&mut replaced, MergeMode::Replace);
+        assert_eq!(replaced.kerning.value("T", "o"), None);
+        assert_eq!(replaced.kerning.value("V", "A"), Some(-70.0));
+    }
+}
@@ -0,0 +1,270 @@
+//! STAT axis values
+//!
+//! The STAT table tells apps how the fonts of a family group into styles:
+//! the name of each value on an axis ("Bold" at weight 700, "Italic" at ital
+//! 1), which names are left out of full names ("Regular"), which style a
+//! value links to for style linking, and what a font with every name left
+//! out is called, the elided fallback name. A family sets these once and
+//! they are written into its variable fonts on export (see
+//! `data::stat_table`). They are stored in the font lib like export
+//! profiles; nothing is stored while there are none.
+
+use serde::{Deserialize, Serialize};
+
+/// Font lib key holding the project's STAT axis values
+pub const STAT_LIB_KEY: &str = "org.bezy.stat";
+
+/// Registered axes a STAT value can be on: tag and name
+pub const STAT_AXES: [(&str, &str); 5] = [
+    ("wght", "Weight"),
+    ("wdth", "Width"),
+    ("ital", "Italic"),
+    ("slnt", "Slant"),
+    ("opsz", "Optical size"),
+];
+
+/// Names the elided fallback name can be
+pub const ELIDED_FALLBACK_NAMES: [&str; 5] = ["Regular", "Roman", "Upright", "Normal", "Book"];
+
+/// Standard value names of the registered axes: tag, value and name
+const STANDARD_NAMES: [(&str, f64, &str); 22] = [
+    ("wght", 100.0, "Thin"),
+    ("wght", 200.0, "ExtraLight"),
+    ("wght", 300.0, "Light"),
+    ("wght", 400.0, "Regular"),
+    ("wght", 500.0, "Medium"),
+    ("wght", 600.0, "SemiBold"),
+    ("wght", 700.0, "Bold"),
+    ("wght", 800.0, "ExtraBold"),
+    ("wght", 900.0, "Black"),
+    ("wdth", 50.0, "UltraCondensed"),
+    ("wdth", 62.5, "ExtraCondensed"),
+    ("wdth", 75.0, "Condensed"),
+    ("wdth", 87.5, "SemiCondensed"),
+    ("wdth", 100.0, "Normal"),
+    ("wdth", 112.5, "SemiExpanded"),
+    ("wdth", 125.0, "Expanded"),
+    ("wdth", 150.0, "ExtraExpanded"),
+    ("wdth", 200.0, "UltraExpanded"),
+    ("ital", 0.0, "Roman"),
+    ("ital", 1.0, "Italic"),
+    ("slnt", 0.0, "Upright"),
+    ("opsz", 12.0, "Text"),
+];
+
+/// A named value on an axis, in user coordinates
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatAxisValue {
+    /// Axis tag, such as "wght"
+    pub axis: String,
+    pub value: f64,
+    pub name: String,
+    /// Value of the style this one links to, such as Bold for Regular
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub linked_value: Option<f64>,
+    /// Whether the name is left out of full names, as "Regular" is
+    pub elidable: bool,
+}
+
+/// The STAT axis values of a family and its elided fallback name
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatConfig {
+    pub values: Vec<StatAxisValue>,
+    pub elided_fallback_name: String,
+}
+
+impl Default for StatConfig {
+    fn default() -> Self {
+        Self {
+            values: Vec::new(),
+            elided_fallback_name: ELIDED_FALLBACK_NAMES[0].to_string(),
+        }
+    }
+}
+
+/// How far the value of an axis steps
+pub fn axis_step(axis: &str) -> f64 {
+    match axis {
+        "wght" => 100.0,
+        "wdth" => 12.5,
+        _ => 1.0,
+    }
+}
+
+/// Default value of a registered axis
+fn axis_default(axis: &str) -> f64 {
+    match axis {
+        "wght" => 400.0,
+        "wdth" => 100.0,
+        "opsz" => 12.0,
+        _ => 0.0,
+    }
+}
+
+/// Standard name of a value on a registered axis, such as "Bold"
+pub fn standard_name(axis: &str, value: f64) -> Option<&'static str> {
+    STANDARD_NAMES
+        .iter()
+        .find(|(tag, standard, _)| *tag == axis && *standard == value)
+        .map(|(_, _, name)| *name)
+}
+
+/// Name of a value: its standard name, or the value itself
+fn value_name(axis: &str, value: f64) -> String {
+    standard_name(axis, value).map_or_else(|| value.to_string(), str::to_string)
+}
+
+/// Name of a registered axis, or its tag
+pub fn axis_name(axis: &str) -> &str {
+    STAT_AXES
+        .iter()
+        .find(|(tag, _)| *tag == axis)
+        .map_or(axis, |(_, name)| *name)
+}
+
+impl StatConfig {
+    /// Read axis values from a UFO font lib, falling back to none
+    pub fn from_norad_lib(lib: &norad::Plist) -> Self {
+        lib.get(STAT_LIB_KEY)
+            .and_then(|value| serde_json::to_value(value).ok())
+            .and_then(|json| serde_json::from_value(json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write axis values into a UFO font lib, unless they are the default
+    pub fn write_to_norad_lib(&self, lib: &mut norad::Plist) {
+        lib.remove(STAT_LIB_KEY);
+        if *self == Self::default() {
+            return;
+        }
+        if let Ok(value) = serde_json::to_value(self).and_then(serde_json::from_value) {
+            lib.insert(STAT_LIB_KEY.to_string(), value);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Adds a value on an axis, at its default or a step above the axis's
+    /// highest value so far; the default value is elidable
+    pub fn add_value(&mut self, axis: &str) {
+        let highest = self
+            .values
+            .iter()
+            .filter(|value| value.axis == axis)
+            .map(|value| value.value)
+            .reduce(f64::max);
+        let value = highest.map_or(axis_default(axis), |highest| highest + axis_step(axis));
+        self.values.push(StatAxisValue {
+            axis: axis.to_string(),
+            value,
+            name: value_name(axis, value),
+            linked_value: None,
+            elidable: highest.is_none(),
+        });
+    }
+
+    /// Moves a value a step along its axis; a standard name follows it
+    pub fn step_value(&mut self, index: usize, forward: bool) {
+        let Some(value) = self.values.get_mut(index) else {
+            return;
+        };
+        let step = axis_step(&value.axis);
+        let renamed = value.name == value_name(&value.axis, value.value);
+        value.value += if forward { step } else { -step };
+        if renamed {
+            value.name = value_name(&value.axis, value.value);
+        }
+    }
+
+    /// Gives a value the next standard name of its axis
+    pub fn cycle_name(&mut self, index: usize) {
+        let Some(value) = self.values.get_mut(index) else {
+            return;
+        };
+        let names: Vec<&str> = STANDARD_NAMES
+            .iter()
+            .filter(|(tag, _, _)| *tag == value.axis)
+            .map(|(_, _, name)| *name)
+            .collect();
+        let next = names
+            .iter()
+            .position(|name| *name == value.name)
+            .map_or(0, |position| (position + 1) % names.len());
+        if let Some(name) = names.get(next) {
+            value.name = name.to_string();
+        }
+    }
+
+    /// Links a value to the style it pairs with, Regular to Bold three
+    /// steps up and Roman to Italic, or unlinks it
+    pub fn toggle_link(&mut self, index: usize) {
+        if let Some(value) = self.values.get_mut(index) {
+            value.linked_value = match value.linked_value {
+                Some(_) => None,
+                None if value.axis == "wght" => Some(value.value + 300.0),
+                None => Some(value.value + axis_step(&value.axis)),
+            };
+        }
+    }
+
+    pub fn toggle_elidable(&mut self, index: usize) {
+        if let Some(value) = self.values.get_mut(index) {
+            value.elidable = !value.elidable;
+        }
+    }
+
+    pub fn remove_value(&mut self, index: usize) {
+        if index < self.values.len() {
+            self.values.remove(index);
+        }
+    }
+
+    pub fn cycle_elided_fallback_name(&mut self) {
+        let next = ELIDED_FALLBACK_NAMES
+            .iter()
+            .position(|name| *name == self.elided_fallback_name)
+            .map_or(0, |position| (position + 1) % ELIDED_FALLBACK_NAMES.len());
+        self.elided_fallback_name = ELIDED_FALLBACK_NAMES[next].to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axis_values() {
+        let mut stat = StatConfig::default();
+        stat.add_value("wght");
+        stat.add_value("wght");
+        stat.add_value("ital");
+        assert_eq!(stat.values[0].name, "Regular");
+        assert!(stat.values[0].elidable);
+        assert_eq!(
+            (stat.values[1].value, stat.values[1].name.as_str()),
+            (500.0, "Medium")
+        );
+        assert!(!stat.values[1].elidable);
+        assert_eq!(stat.values[2].name, "Roman");
+
+        stat.step_value(1, true);
+        stat.step_value(1, true);
+        assert_eq!(stat.values[1].name, "Bold");
+        stat.toggle_link(0);
+        assert_eq!(stat.values[0].linked_value, Some(700.0));
+        stat.cycle_name(2);
+        assert_eq!(stat.values[2].name, "Italic");
+        stat.cycle_elided_fallback_name();
+        assert_eq!(stat.elided_fallback_name, "Roman");
+
+        let mut lib = norad::Plist::new();
+        StatConfig::default().write_to_norad_lib(&mut lib);
+        assert!(lib.is_empty());
+        stat.write_to_norad_lib(&mut lib);
+        assert_eq!(StatConfig::from_norad_lib(&lib), stat);
+    }
+}
@@ -0,0 +1,220 @@
+//! Stem widths measured across a glyph
+//!
+//! Horizontal rays cast across the glyph every few units measure the runs
+//! of ink they cross, so the vertical stems of an "H" show up as the same
+//! width on many rays; vertical rays do the same for horizontal bars.
+//! Widths within a unit of each other are grouped, and groups crossed by
+//! too few rays, like the changing thickness across a bowl, are dropped.
+//! Overlapping contours are measured as the ink they make together.
+
+use kurbo::{BezPath, Point};
+
+use super::auto_spacing::outline_segments;
+use super::metrics::FontInfo;
+
+/// Distance between rays, in em
+const RAY_SPACING: f64 = 0.005;
+
+/// Fewest rays that must cross a width for it to count as a stem
+const MIN_RAYS: usize = 4;
+
+/// Widths at most this far from the narrowest of a group join it, in font
+/// units
+const GROUP_TOLERANCE: f64 = 1.0;
+
+/// Runs wider than this are the length of a bar or the body of a shape
+/// rather than a stem, in em
+const MAX_STEM_WIDTH: f64 = 0.35;
+
+/// Stems narrower than this fraction of the most common stem in their
+/// direction are thin strokes
+const THIN_RATIO: f64 = 0.6;
+
+/// How far a stem may be from its standard before it's flagged, in em
+const STANDARD_TOLERANCE: f64 = 0.005;
+
+/// Which way a stem runs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StemDirection {
+    /// A vertical stem, measured across by horizontal rays
+    Vertical,
+    /// A horizontal bar, measured across by vertical rays
+    Horizontal,
+}
+
+/// A stem width found in a glyph
+#[derive(Clone, Debug, PartialEq)]
+pub struct StemWidth {
+    pub direction: StemDirection,
+    /// Average width of the group, in font units
+    pub width: f64,
+    /// Number of rays that crossed it
+    pub rays: usize,
+    /// Much narrower than the glyph's main stems in the same direction
+    pub thin: bool,
+    /// Nearest of the font's standard stems in the same direction; `None`
+    /// for thin strokes and fonts without standard stems
+    pub standard: Option<f64>,
+    /// Further from `standard` than the tolerance
+    pub deviates: bool,
+}
+
+/// Widths of the ink runs crossed by horizontal rays between `min_y` and
+/// `max_y`, counting ink by nonzero winding
+fn ink_runs(segments: &[(Point, Point)], min_y: f64, max_y: f64, spacing: f64) -> Vec<f64> {
+    let rays = ((max_y - min_y) / spacing).floor().max(0.0) as usize;
+    let mut widths = Vec::new();
+    let mut crossings: Vec<(f64, i32)> = Vec::new();
+    for ray in 0..rays {
+        let y = min_y + (ray as f64 + 0.5) * spacing;
+        crossings.clear();
+        for &(a, b) in segments {
+            if (a.y <= y) != (b.y <= y) {
+                let x = a.x + (y - a.y) / (b.y - a.y) * (b.x - a.x);
+                crossings.push((x, if b.y > a.y { 1 } else { -1 }));
+            }
+        }
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut winding = 0;
+        let mut start = 0.0;
+        for &(x, direction) in &crossings {
+            if winding == 0 {
+                start = x;
+            }
+            winding += direction;
+            if winding == 0 {
+                widths.push(x - start);
+            }
+        }
+    }
+    widths
+}
+
+/// Groups of nearly equal widths crossed by enough rays, as their average
+/// width and number of rays, narrowest first
+fn group_widths(mut widths: Vec<f64>) -> Vec<(f64, usize)> {
+    widths.sort_by(f64::total_cmp);
+    let mut groups: Vec<Vec<f64>> = Vec::new();
+    for width in widths {
+        match groups.last_mut() {
+            Some(group) if width - group[0] <= GROUP_TOLERANCE => group.push(width),
+            _ => groups.push(vec![width]),
+        }
+    }
+    groups
+        .into_iter()
+        .filter(|group| group.len() >= MIN_RAYS)
+        .map(|group| (group.iter().sum::<f64>() / group.len() as f64, group.len()))
+        .collect()
+}
+
+/// The stems of a glyph's outlines, vertical stems first, compared to the
+/// font's standard stems
+pub fn measure_stems(paths: &[BezPath], info: &FontInfo) -> Vec<StemWidth> {
+    let em = info.units_per_em;
+    let segments = outline_segments(paths);
+    // Vertical rays are horizontal rays across the outline mirrored diagonally
+    let mirrored: Vec<(Point, Point)> = segments
+        .iter()
+        .map(|(a, b)| (Point::new(a.y, a.x), Point::new(b.y, b.x)))
+        .collect();
+
+    let mut stems = Vec::new();
+    for (direction, segments, standards) in [
+        (StemDirection::Vertical, segments, &info.stem_snap_v),
+        (StemDirection::Horizontal, mirrored, &info.stem_snap_h),
+    ] {
+        if segments.is_empty() {
+            continue;
+        }
+        let min_y = segments
+            .iter()
+            .map(|(a, _)| a.y)
+            .fold(f64::INFINITY, f64::min);
+        let max_y = segments
+            .iter()
+            .map(|(a, _)| a.y)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let widths = ink_runs(&segments, min_y, max_y, em * RAY_SPACING)
+            .into_iter()
+            .filter(|&width| width <= em * MAX_STEM_WIDTH)
+            .collect();
+        let groups = group_widths(widths);
+        let main_width = groups
+            .iter()
+            .max_by_key(|(_, rays)| *rays)
+            .map_or(0.0, |(width, _)| *width);
+
+        for (width, rays) in groups {
+            let thin = width < main_width * THIN_RATIO;
+            let standard = standards
+                .iter()
+                .copied()
+                .filter(|_| !thin)
+                .min_by(|a, b| (a - width).abs().total_cmp(&(b - width).abs()));
+            stems.push(StemWidth {
+                direction,
+                width,
+                rays,
+                thin,
+                standard,
+                deviates: standard
+                    .is_some_and(|standard| (width - standard).abs() > em * STANDARD_TOLERANCE),
+            });
+        }
+    }
+    stems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kurbo::{Rect, Shape};
+
+    fn info(stem_snap_v: Vec<f64>, stem_snap_h: Vec<f64>) -> FontInfo {
+        FontInfo {
+            units_per_em: 1000.0,
+            stem_snap_v,
+            stem_snap_h,
+            ..Default::default()
+        }
+    }
+
+    fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> BezPath {
+        Rect::new(x0, y0, x1, y1).to_path(0.1)
+    }
+
+    #[test]
+    fn test_stems_of_overlapping_h() {
+        let paths = [
+            rect(0.0, 0.0, 80.0, 700.0),
+            rect(420.0, 0.0, 500.0, 700.0),
+            rect(0.0, 320.0, 500.0, 380.0),
+        ];
+        let stems = measure_stems(&paths, &info(vec![80.0], vec![70.0]));
+        assert_eq!(stems.len(), 2);
+
+        let stem = &stems[0];
+        assert_eq!(stem.direction, StemDirection::Vertical);
+        assert!((stem.width - 80.0).abs() < 1e-6);
+        assert_eq!(stem.standard, Some(80.0));
+        assert!(!stem.thin && !stem.deviates);
+
+        let bar = &stems[1];
+        assert_eq!(bar.direction, StemDirection::Horizontal);
+        assert!((bar.width - 60.0).abs() < 1e-6);
+        assert!(bar.deviates);
+    }
+
+    #[test]
+    fn test_thin_strokes_are_not_flagged() {
+        let paths = [rect(0.0, 0.0, 100.0, 700.0), rect(300.0, 0.0, 330.0, 400.0)];
+        let stems = measure_stems(&paths, &info(vec![100.0], Vec::new()));
+        assert_eq!(stems.len(), 2);
+        assert!(stems[0].thin);
+        assert_eq!(stems[0].standard, None);
+        assert!(!stems[0].deviates);
+        assert!(!stems[1].thin);
+    }
+}
@@ -0,0 +1,310 @@
+//! Subsets of a font
+//!
+//! A subset keeps the glyphs asked for, by name or by codepoint and range
+//! ("U+0020-007E", as in export profiles), plus whatever those need: the
+//! base glyphs of their components, recursively, and `.notdef`. Kerning is
+//! trimmed to the kept glyphs, and groups to their kept members, so pairs
+//! between kept glyphs keep their values through groups.
+//!
+//! Trial fonts keep every glyph but only draw the ones asked for: the rest
+//! become placeholder frames with their advance and codepoints, so text
+//! still lays out, and the family name is prefixed with "Trial".
+
+use anyhow::{bail, Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::RangeInclusive;
+
+use super::data::{ContourData, FontData, OutlineData, PointData, PointTypeData};
+use super::kerning::KerningData;
+
+/// Glyph that every subset keeps when the font has it
+const NOTDEF: &str = ".notdef";
+
+/// What the family name of a trial font starts with
+pub const TRIAL_PREFIX: &str = "Trial";
+
+/// Share of the advance left blank on each side of a placeholder
+const PLACEHOLDER_MARGIN: f64 = 0.1;
+
+/// Width of a placeholder's frame, as a share of the advance
+const PLACEHOLDER_STROKE: f64 = 0.08;
+
+/// The glyphs and codepoints a subset asks for
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SubsetSpec {
+    pub ranges: Vec<RangeInclusive<u32>>,
+    pub glyphs: Vec<String>,
+}
+
+/// A codepoint written as `U+0041`
+fn parse_codepoint(text: &str) -> Result<u32> {
+    let text = text.trim();
+    let hex = text
+        .strip_prefix("U+")
+        .or_else(|| text.strip_prefix("u+"))
+        .unwrap_or(text);
+    u32::from_str_radix(hex, 16).with_context(|| format!("Invalid codepoint: {text}"))
+}
+
+impl SubsetSpec {
+    /// Entries are codepoints or ranges ("U+0041", "U+0020-007E") and glyph
+    /// names; blank entries are ignored
+    pub fn parse(entries: &[String]) -> Result<Self> {
+        let mut spec = Self::default();
+        for entry in entries.iter().map(|entry| entry.trim()) {
+            if entry.is_empty() {
+                continue;
+            }
+            if !(entry.starts_with("U+") || entry.starts_with("u+")) {
+                spec.glyphs.push(entry.to_string());
+                continue;
+            }
+            let (start, end) = entry.split_once('-').unwrap_or((entry, entry));
+            let (start, end) = (parse_codepoint(start)?, parse_codepoint(end)?);
+            if end < start {
+                bail!("Range ends before it starts: {entry}");
+            }
+            spec.ranges.push(start..=end);
+        }
+        Ok(spec)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty() && self.glyphs.is_empty()
+    }
+
+    fn contains(&self, c: char) -> bool {
+        self.ranges
+            .iter()
+            .any(|range| range.contains(&u32::from(c)))
+    }
+}
+
+/// The glyphs a subset keeps
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResolvedSubset {
+    pub glyphs: BTreeSet<String>,
+    /// Glyphs kept only because kept glyphs use them as components
+    pub dependencies: Vec<String>,
+    /// Glyph names asked for that the font doesn't have
+    pub missing: Vec<String>,
+}
+
+impl FontData {
+    /// The glyphs a subset keeps, components resolved
+    pub fn resolve_subset(&self, spec: &SubsetSpec) -> ResolvedSubset {
+        let mut resolved = ResolvedSubset::default();
+        for name in &spec.glyphs {
+            if self.glyphs.contains_key(name) {
+                resolved.glyphs.insert(name.clone());
+            } else {
+                resolved.missing.push(name.clone());
+            }
+        }
+        for (name, glyph) in &self.glyphs {
+            if glyph.unicode_values.iter().any(|&c| spec.contains(c)) {
+                resolved.glyphs.insert(name.clone());
+            }
+        }
+        if self.glyphs.contains_key(NOTDEF) {
+            resolved.glyphs.insert(NOTDEF.to_string());
+        }
+
+        let mut pending: Vec<String> = resolved.glyphs.iter().cloned().collect();
+        while let Some(name) = pending.pop() {
+            let Some(glyph) = self.glyphs.get(&name) else {
+                continue;
+            };
+            for component in &glyph.components {
+                let base = &component.base_glyph;
+                if self.glyphs.contains_key(base) && resolved.glyphs.insert(base.clone()) {
+                    resolved.dependencies.push(base.clone());
+                    pending.push(base.clone());
+                }
+            }
+        }
+        resolved.dependencies.sort();
+        resolved
+    }
+
+    /// A copy of the font with only these glyphs, on every layer
+    pub fn subset(&self, keep: &BTreeSet<String>) -> FontData {
+        let mut subset = self.clone();
+        subset.switch_editing_layer(None);
+        subset.glyphs.retain(|name, _| keep.contains(name));
+        for layer in &mut subset.layers {
+            layer.glyphs.retain(|name, _| keep.contains(name));
+        }
+        subset.kerning = subset_kerning(&self.kerning, keep);
+        subset
+    }
+
+    /// A copy of the font where glyphs outside `keep` are placeholders
+    /// `height` units tall, gone from the other layers and the kerning
+    pub fn trial(&self, keep: &BTreeSet<String>, height: f64) -> FontData {
+        let mut trial = self.clone();
+        trial.switch_editing_layer(None);
+        for (name, glyph) in &mut trial.glyphs {
+            if keep.contains(name) {
+                continue;
+            }
+            glyph.outline = placeholder_outline(glyph.advance_width, height);
+            glyph.components.clear();
+            glyph.anchors.clear();
+        }
+        for layer in &mut trial.layers {
+            layer.glyphs.retain(|name, _| keep.contains(name));
+        }
+        trial.kerning = subset_kerning(&self.kerning, keep);
+        trial
+    }
+}
+
+/// A rectangular frame inside the advance, or `None` for glyphs without
+/// width
+pub fn placeholder_outline(advance: f64, height: f64) -> Option<OutlineData> {
+    if advance <= 0.0 || height <= 0.0 {
+        return None;
+    }
+    let rectangle = |inset: f64, clockwise: bool| {
+        let (left, right) = (
+            advance * PLACEHOLDER_MARGIN + inset,
+            advance * (1.0 - PLACEHOLDER_MARGIN) - inset,
+        );
+        let (bottom, top) = (inset, height - inset);
+        let mut corners = vec![(left, bottom), (right, bottom), (right, top), (left, top)];
+        if clockwise {
+            corners.reverse();
+        }
+        ContourData {
+            points: corners
+                .into_iter()
+                .map(|(x, y)| PointData {
+                    x,
+                    y,
+                    point_type: PointTypeData::Line,
+                })
+                .collect(),
+        }
+    };
+    let stroke = advance * PLACEHOLDER_STROKE;
+    Some(OutlineData {
+        contours: vec![rectangle(0.0, false), rectangle(stroke, true)],
+    })
+}
+
+/// Kerning between kept glyphs only: groups lose the other members, and
+/// pairs go when a side is a dropped glyph or an emptied group
+pub fn subset_kerning(kerning: &KerningData, keep: &BTreeSet<String>) -> KerningData {
+    let groups: BTreeMap<String, Vec<String>> = kerning
+        .groups
+        .iter()
+        .map(|(name, members)| {
+            let members: Vec<String> = members
+                .iter()
+                .filter(|member| keep.contains(*member))
+                .cloned()
+                .collect();
+            (name.clone(), members)
+        })
+        .filter(|(_, members)| !members.is_empty())
+        .collect();
+    let side_kept = |side: &String| keep.contains(side) || groups.contains_key(side);
+    let pairs = kerning
+        .pairs
+        .iter()
+        .filter(|(first, _)| side_kept(first))
+        .map(|(first, seconds)| {
+            let seconds: BTreeMap<String, f64> = seconds
+                .iter()
+                .filter(|(second, _)| side_kept(second))
+                .map(|(second, value)| (second.clone(), *value))
+                .collect();
+            (first.clone(), seconds)
+        })
+        .filter(|(_, seconds)| !seconds.is_empty())
+        .collect();
+    KerningData { groups, pairs }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::{ComponentData, GlyphData};
+
+    fn glyph(name: &str, unicode: Option<char>, components: &[&str]) -> GlyphData {
+        GlyphData {
+            unicode_values: unicode.into_iter().collect(),
+            components: components
+                .iter()
+                .map(|base| ComponentData {
+                    base_glyph: base.to_string(),
+                    transform: [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+                })
+                .collect(),
+            ..GlyphData::test(name, 500.0)
+        }
+    }
+
+    #[test]
+    fn test_subset() {
+        let entries = ["U+00C0-00C1", " fi ", "u+0020", "", "missing"].map(String::from);
+        let spec = SubsetSpec::parse(&entries).unwrap();
+        assert_eq!(spec.ranges, [0xC0..=0xC1, 0x20..=0x20]);
+        assert_eq!(spec.glyphs, ["fi", "missing"]);
+        assert!(SubsetSpec::parse(&["U+0041-0040".to_string()]).is_err());
+        assert!(SubsetSpec::parse(&["U+XYZ".to_string()]).is_err());
+
+        let mut font = FontData::default();
+        for glyph in [
+            glyph(".notdef", None, &[]),
+            glyph("space", Some(' '), &[]),
+            glyph("A", Some('A'), &[]),
+            glyph("B", Some('B'), &[]),
+            glyph("gravecomb", Some('\u{300}'), &[]),
+            glyph("Agrave", Some('À'), &["A", "gravecomb"]),
+            glyph("Agrave.alt", None, &["Agrave"]),
+            glyph("fi", None, &["f", "i"]),
+        ] {
+            font.glyphs.insert(glyph.name.clone(), glyph);
+        }
+        let spec = SubsetSpec::parse(&["U+00C0".to_string(), "Agrave.alt".to_string()]).unwrap();
+        let resolved = font.resolve_subset(&spec);
+        let kept: Vec<&str> = resolved.glyphs.iter().map(String::as_str).collect();
+        assert_eq!(kept, [".notdef", "A", "Agrave", "Agrave.alt", "gravecomb"]);
+        assert_eq!(resolved.dependencies, ["A", "gravecomb"]);
+
+        font.kerning.groups.insert(
+            "public.kern1.A".to_string(),
+            vec!["A".to_string(), "Agrave".to_string(), "B".to_string()],
+        );
+        font.kerning
+            .groups
+            .insert("public.kern2.B".to_string(), vec!["B".to_string()]);
+        font.kerning.set_value("public.kern1.A", "A", -10.0);
+        font.kerning
+            .set_value("public.kern1.A", "public.kern2.B", -20.0);
+        font.kerning.set_value("B", "A", -30.0);
+        let subset = font.subset(&resolved.glyphs);
+        assert_eq!(subset.glyphs.len(), 5);
+        assert_eq!(subset.kerning.groups["public.kern1.A"], ["A", "Agrave"]);
+        assert!(!subset.kerning.groups.contains_key("public.kern2.B"));
+        assert_eq!(subset.kerning.pair_value("Agrave", "A"), -10.0);
+        assert_eq!(subset.kerning.pairs.len(), 1);
+
+        // Trials keep every glyph, drawing the others as frames
+        let trial = font.trial(&resolved.glyphs, 700.0);
+        assert_eq!(trial.glyphs.len(), font.glyphs.len());
+        assert_eq!(trial.glyphs["Agrave"], font.glyphs["Agrave"]);
+        let placeholder = &trial.glyphs["fi"];
+        assert!(placeholder.components.is_empty());
+        let contours = &placeholder.outline.as_ref().unwrap().contours;
+        assert_eq!(contours.len(), 2);
+        assert_eq!(
+            (contours[0].points[0].x, contours[0].points[2].y),
+            (50.0, 700.0)
+        );
+        assert_eq!(trial.kerning, subset.kerning);
+        assert_eq!(placeholder_outline(0.0, 700.0), None);
+    }
+}
@@ -0,0 +1,166 @@
+//! Template outlines for glyphs that aren't drawn yet
+//!
+//! While a character set is incomplete, its empty glyphs are easier to space
+//! and proportion against a light template of the character. Templates come
+//! from compiled fonts: a user-selected one first, then the reference fonts
+//! bundled with Bezy. A glyph's template is the outline of its first Unicode
+//! value in the first font that has it, scaled to the edited font's em.
+
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use kurbo::{Affine, BezPath};
+use skrifa::instance::{LocationRef, Size};
+use skrifa::outline::{DrawSettings, OutlinePen};
+use skrifa::{FontRef, MetadataProvider};
+
+use super::data::GlyphData;
+
+/// A compiled font templates are taken from
+#[derive(Clone)]
+pub struct TemplateFont {
+    name: String,
+    bytes: Vec<u8>,
+}
+
+impl TemplateFont {
+    /// A template font from TrueType or OpenType bytes
+    pub fn from_bytes(name: impl Into<String>, bytes: Vec<u8>) -> Result<Self> {
+        let name = name.into();
+        FontRef::new(&bytes).map_err(|error| anyhow!("'{name}' is not a font: {error}"))?;
+        Ok(Self { name, bytes })
+    }
+
+    /// Load a template font from a .ttf or .otf file
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read template font {}", path.display()))?;
+        Self::from_bytes(path.display().to_string(), bytes)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Outline of `ch` scaled to `units_per_em`, if the font has it
+    pub fn outline(&self, ch: char, units_per_em: f64) -> Option<Vec<BezPath>> {
+        let font = FontRef::new(&self.bytes).ok()?;
+        let glyph_id = font.charmap().map(ch)?;
+        let glyph = font.outline_glyphs().get(glyph_id)?;
+        let mut pen = BezPathPen::default();
+        let settings = DrawSettings::unhinted(Size::unscaled(), LocationRef::default());
+        glyph.draw(settings, &mut pen).ok()?;
+        pen.finish();
+
+        let font_units_per_em = font
+            .metrics(Size::unscaled(), LocationRef::default())
+            .units_per_em;
+        let scale = Affine::scale(units_per_em / f64::from(font_units_per_em.max(1)));
+        let mut paths = pen.paths;
+        for path in &mut paths {
+            path.apply_affine(scale);
+        }
+        Some(paths)
+    }
+}
+
+/// Collects the contours a font draws, one path each
+#[derive(Default)]
+struct BezPathPen {
+    paths: Vec<BezPath>,
+    current: BezPath,
+}
+
+impl BezPathPen {
+    fn finish(&mut self) {
+        if !self.current.elements().is_empty() {
+            self.paths.push(std::mem::take(&mut self.current));
+        }
+    }
+}
+
+impl OutlinePen for BezPathPen {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.finish();
+        self.current.move_to((x as f64, y as f64));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.current.line_to((x as f64, y as f64));
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.current
+            .quad_to((cx0 as f64, cy0 as f64), (x as f64, y as f64));
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.current.curve_to(
+            (cx0 as f64, cy0 as f64),
+            (cx1 as f64, cy1 as f64),
+            (x as f64, y as f64),
+        );
+    }
+
+    fn close(&mut self) {
+        self.current.close_path();
+        self.finish();
+    }
+}
+
+/// Whether a glyph has nothing to draw: no contours and no components
+pub fn is_empty_glyph(glyph: &GlyphData) -> bool {
+    glyph.components.is_empty()
+        && glyph
+            .outline
+            .as_ref()
+            .is_none_or(|outline| outline.contours.is_empty())
+}
+
+/// Template outline of a glyph from the first of `fonts` having one of its
+/// characters, scaled to `units_per_em`
+pub fn template_outline(
+    glyph: &GlyphData,
+    fonts: &[TemplateFont],
+    units_per_em: f64,
+) -> Option<Vec<BezPath>> {
+    glyph.unicode_values.iter().find_map(|&ch| {
+        fonts
+            .iter()
+            .find_map(|font| font.outline(ch, units_per_em))
+            .filter(|paths| !paths.is_empty())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::embedded_assets::BEZY_GROTESK_BYTES;
+
+    fn glyph(unicode_values: Vec<char>) -> GlyphData {
+        GlyphData {
+            unicode_values,
+            ..GlyphData::test("A", 600.0)
+        }
+    }
+
+    #[test]
+    fn test_template_outline_from_bundled_font() {
+        let font = TemplateFont::from_bytes("Bezy Grotesk", BEZY_GROTESK_BYTES.to_vec()).unwrap();
+        let empty = glyph(vec!['A']);
+        assert!(is_empty_glyph(&empty));
+
+        let paths = template_outline(&empty, std::slice::from_ref(&font), 2000.0).unwrap();
+        let bounds = paths
+            .iter()
+            .map(kurbo::Shape::bounding_box)
+            .reduce(|a, b| a.union(b))
+            .unwrap();
+        // A capital sits on the baseline and stays within the em
+        assert!(bounds.y0.abs() < 50.0);
+        assert!(bounds.height() > 500.0 && bounds.height() < 2000.0);
+
+        assert!(template_outline(&glyph(vec!['\u{E000}']), &[font], 1000.0).is_none());
+        assert!(TemplateFont::from_bytes("junk", b"not a font".to_vec()).is_err());
+    }
+}
@@ -3,6 +3,7 @@
 //! Shared geometry functions to avoid code duplication across the codebase.
 //! Functions for coordinate conversion, position calculation, and transformations.
 
+use crate::core::state::OutlineData;
 use bevy::prelude::*;
 
 /// Lock a position to horizontal or vertical axis relative to another point
@@ -42,3 +43,41 @@ pub fn calculate_final_position_with_constraints(
         snapped_pos
     }
 }
+
+/// Positions of every point of an outline, on- and off-curve
+pub fn outline_point_positions(outline: &OutlineData) -> impl Iterator<Item = Vec2> + '_ {
+    outline
+        .contours
+        .iter()
+        .flat_map(|contour| &contour.points)
+        .map(|point| Vec2::new(point.x as f32, point.y as f32))
+}
+
+/// The nearest of `points` within `tolerance` of `pos`, or `pos` itself when
+/// none is that close (used to start and end tool lines exactly on points)
+pub fn snap_to_nearest_point(
+    pos: Vec2,
+    points: impl IntoIterator<Item = Vec2>,
+    tolerance: f32,
+) -> Vec2 {
+    points
+        .into_iter()
+        .map(|point| (point.distance(pos), point))
+        .filter(|(distance, _)| *distance <= tolerance)
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map_or(pos, |(_, point)| point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snap_to_nearest_point() {
+        let points = [Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0)];
+        let near = snap_to_nearest_point(Vec2::new(7.0, 1.0), points, 5.0);
+        assert_eq!(near, Vec2::new(10.0, 0.0));
+        let far = Vec2::new(5.0, 20.0);
+        assert_eq!(snap_to_nearest_point(far, points, 5.0), far);
+    }
+}
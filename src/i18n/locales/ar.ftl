@@ -0,0 +1,445 @@
+# نصوص بيزي بالعربية
+#
+# Bezy's Arabic strings. Messages missing here fall back to English.
+
+## Tools
+
+tool-select = تحديد
+tool-pan = تحريك
+tool-pen = قلم
+tool-shapes = أشكال
+tool-text = نص
+tool-knife = سكين
+tool-eraser = ممحاة
+tool-corners = زوايا
+tool-nib = ريشة
+tool-hyper = هايبر
+tool-measure = قياس
+tool-metaballs = كرات معدنية
+tool-ai = ذكاء اصطناعي
+hover-zoom = التكبير: { $percent }٪
+
+## Panes
+
+pane-loading = جارٍ التحميل...
+glyph-pane-glyph = الحرف:
+glyph-pane-unicode = يونيكود:
+glyph-pane-advance = العرض:
+glyph-pane-lsb = الهامش الأيسر:
+glyph-pane-rsb = الهامش الأيمن:
+glyph-pane-left-group = المجموعة اليسرى:
+glyph-pane-right-group = المجموعة اليمنى:
+export-title = تصدير
+preferences-title = التفضيلات
+button-close = إغلاق
+glyph-pane-none = لا شيء
+pane-no-designspace = لا يذكر أي فضاء تصميم هذا الخط
+pane-no-font = لم يُحمَّل أي خط
+pane-no-active-glyph = لا يوجد حرف نشط
+pane-edit = تحرير
+pane-edit-foreground = تحرير الواجهة
+pane-remove = إزالة
+pane-page = صفحة { $page } من { $count }
+
+## Export
+
+export-cancel = إلغاء
+export-export = تصدير
+export-build-family = بناء العائلة { $family }
+export-inspect = فحص آخر بناء
+export-no-font = لم يُحمَّل أي خط
+export-preparing = جارٍ التحضير للتصدير…
+export-cancelling = جارٍ الإلغاء بعد { $file }…
+export-progress = جارٍ تصدير { $done }/{ $total }: { $file }
+export-whole-font = الخط كاملًا
+export-all-sources = كل المصادر
+export-format-variable = متغير
+export-format-static = ثابت
+export-curve-error = خطأ المنحنى { $error }
+export-autohint = التلميح التلقائي { $state }
+export-flag-overlaps = تعليم التداخلات { $state }
+export-remove-overlaps = إزالة التداخلات { $state }
+export-svg = SVG { $state }
+export-subset = المجموعة الجزئية   { $subset }
+export-styles = الأنماط   { $styles }
+export-output = المخرجات   { $path }
+
+## Preferences
+
+pref-theme = السمة
+pref-language = اللغة
+pref-colorblind-safe = ألوان آمنة لعمى الألوان
+pref-ui-scale = حجم الواجهة
+pref-grid-snap = الالتقاط بالشبكة
+pref-grid-size = حجم الشبكة
+pref-measurement-grid = شبكة القياس
+pref-measurement-interval = تباعد شبكة القياس
+pref-template-glyphs = قوالب في الحروف الفارغة
+pref-nudge = الإزاحة
+pref-shift-nudge = الإزاحة مع Shift
+pref-cmd-nudge = الإزاحة مع Cmd
+pref-hit-tolerance = هامش النقر
+pref-autosave = الحفظ التلقائي
+pref-export-profile = ملف التصدير
+pref-tool-shortcut = أداة { $tool }
+pref-on = مفعّل
+pref-off = معطّل
+pref-none = لا شيء
+pref-space = مسافة
+pref-every-minutes = كل { $minutes } دقيقة
+pref-every-seconds = كل { $seconds } ثانية
+pref-pixels = { $pixels } بكسل
+pref-first-profile = أول ملف
+pref-press-a-letter = اضغط حرفًا…
+pref-reset-shortcuts = استعادة الاختصارات
+pref-key-not-a-letter = لا يمكن أن يكون '{ $key }' اختصارًا لأداة، استخدم حرفًا
+pref-key-taken = '{ $key }' ينتقل بالفعل إلى أداة { $tool }
+pref-log-verbosity = سجل { $subsystem }
+pref-log-knife = السكين
+pref-log-selection = التحديد
+pref-log-text-input = إدخال النص
+pref-log-warn = التحذيرات
+pref-log-info = المعلومات
+pref-log-debug = التصحيح
+pref-log-trace = التتبع
+pref-customize-toolbar = تخصيص شريط الأدوات
+
+## Toolbar
+
+toolbar-title = تخصيص شريط الأدوات
+toolbar-shown = ظاهرة
+toolbar-hidden = مخفية
+toolbar-no-group = بلا مجموعة
+toolbar-in-group = ضمن { $tool }
+toolbar-reset = استعادة شريط الأدوات
+toolbar-hide-tool = إخفاء { $tool }
+toolbar-customize = تخصيص شريط الأدوات…
+
+## QA
+
+qa-severity-error = خطأ
+qa-severity-warning = تحذير
+qa-severity-info = معلومة
+qa-category-outlines = الخطوط الخارجية
+qa-category-metadata = البيانات الوصفية
+qa-category-hinting = التلميح
+qa-category-kerning = التقنين
+qa-category-spacing = التباعد
+qa-category-unicode = يونيكود
+qa-label-check = الفحص:
+qa-label-severity = الخطورة:
+qa-label-category = الفئة:
+
+qa-check-outline_direction = اتجاه المسارات معكوس
+qa-check-license = الترخيص مفقود
+qa-check-family_naming_recommendations = يمكن أن يكون اسم العائلة أوضح
+qa-check-whitespace_glyphs = حرف مسافة يحتوي على حبر
+qa-check-kerning_for_non_ligated_sequences = أزواج حروف بلا تقنين
+qa-check-unicode_range_bits = نطاقات يونيكود في جدول OS/2
+qa-check-hinting_impact = التلميح يزيد حجم الخط
+qa-check-fontc_compile = يُبنى الحرف بـ fontc دون أخطاء
+
+## Ghosts
+
+ghosts-title = الأشباح
+ghosts-color = اللون
+ghosts-remove = إزالة
+ghosts-remove-all = إزالة الكل
+
+## Auto kerning
+
+auto-kerning-title = التقنين التلقائي
+auto-kerning-accept = قبول
+auto-kerning-reject = رفض
+auto-kerning-first = الأول
+auto-kerning-second = الثاني
+auto-kerning-now = الحالي
+auto-kerning-new = الجديد
+auto-kerning-all-pairs = كل الأزواج ({ $count })
+auto-kerning-proof-collisions = معاينة التصادمات
+
+## Auto spacing
+
+auto-spacing-title = التباعد التلقائي
+auto-spacing-accept = قبول
+auto-spacing-discard = تجاهل
+auto-spacing-all-glyphs = كل الحروف ({ $count })
+
+## Brace layers
+
+brace-layers-title = الطبقات المتوسطة
+brace-layers-add = إضافة { $layer } إلى { $glyph }
+brace-layers-none = لا يوجد حرف بطبقات متوسطة
+brace-layers-glyphs = بطبقات متوسطة: { $glyphs }
+
+## Build inspector
+
+build-inspector-title = فاحص البناء
+
+## Canvas image
+
+canvas-image-title = صورة اللوحة
+canvas-image-window = النافذة
+canvas-image-text = النص
+canvas-image-selection = التحديد
+canvas-image-transparent = خلفية شفافة
+canvas-image-saving = جارٍ حفظ الصورة...
+canvas-image-save = حفظ بجانب الخط
+
+## Color layers
+
+color-layers-title = الطبقات الملونة
+color-layers-foreground = اللون الأمامي
+color-layers-missing = مفقود
+color-layers-count = { $glyph }: { $count } طبقات
+color-layers-color-down = اللون -
+color-layers-color-up = اللون +
+color-layers-up = أعلى
+color-layers-down = أسفل
+color-layers-add-layer = + طبقة
+color-layers-palette = اللوحة { $palette } من { $count }
+color-layers-new-color = لون جديد
+color-layers-edit-hint = اكتب #rrggbb أو #rrggbbaa، Enter للتعيين، Escape للإلغاء
+
+## Compile errors
+
+compile-errors-title = أخطاء الترجمة
+compile-errors-shaping = بناء التشكيل
+compile-errors-export = التصدير
+compile-errors-build = البناء
+compile-errors-failed = فشل { $build } مع { $count } خطأ
+
+## Component graph
+
+component-graph-title = مخطط المكونات
+component-graph-not-in-font = ليس في الخط
+component-graph-loops-back = ⚠ يعود على نفسه
+component-graph-more = … { $count } أخرى
+component-graph-add-component = + مكوّن
+component-graph-built-from = مبني من
+component-graph-follow-anchors = المكونات تتبع نقاط الارتكاز: { $state }
+component-graph-used-by = مستخدم في ({ $count } حروف على أي عمق)
+component-graph-many-users = ⚠ تحرير هذا الحرف يغيّر { $count } حروف أخرى
+component-graph-moved = تحركت مع نقاط ارتكاز '{ $glyph }': { $composites }
+component-graph-no-loops = حلقات المكونات: لا شيء
+component-graph-loops = ⚠ حلقات المكونات: { $count }
+
+## Composites
+
+composites-title = الحروف المركبة
+composites-missing = { $missing } حروف وصفات مفقودة، { $chosen } مختارة
+composites-build-chosen = بناء المختارة
+composites-build-all = بناء كل المفقودة
+composites-last-build = آخر بناء: بُني { $built }، وتعذّر بناء { $skipped }
+composites-more = و{ $count } أخرى
+
+## Curve flavor
+
+curve-flavor-title = المنحنيات
+curve-flavor-lines = خطوط فقط
+curve-flavor-cubic = تكعيبي
+curve-flavor-quadratic = تربيعي
+curve-flavor-mixed = مختلط
+curve-flavor-error-down = الخطأ -
+curve-flavor-error-up = الخطأ +
+curve-flavor-glyph-to-quadratic = الحرف إلى تربيعي
+curve-flavor-glyph-to-cubic = الحرف إلى تكعيبي
+curve-flavor-font-to-quadratic = الخط إلى تربيعي
+curve-flavor-font-to-cubic = الخط إلى تكعيبي
+curve-flavor-points = { $count } نقاط
+curve-flavor-max-error = أقصى خطأ { $error } وحدات
+curve-flavor-not-within = ليس ضمن أقصى خطأ
+curve-flavor-glyph = الحرف: { $flavor }
+curve-flavor-added = إلى تربيعي { $quadratic }، إلى تكعيبي { $cubic }
+curve-flavor-no-glyph = الحرف: لا يوجد حرف نشط
+curve-flavor-font = الخط: إلى تربيعي { $quadratic }، إلى تكعيبي { $cubic }
+
+## Design frames
+
+design-frames-title = أطر التصميم
+design-frames-center-lines = خطوط المنتصف: { $state }
+design-frames-thirds = الأثلاث: { $state }
+design-frames-frame = إطار { $percent }%
+design-frames-add = إضافة إطار
+
+## Designspace rules
+
+designspace-rules-title = قواعد فضاء التصميم
+designspace-rules-add-condition = إضافة شرط
+designspace-rules-min-down = الأدنى -
+designspace-rules-min-up = الأدنى +
+designspace-rules-max-down = الأقصى -
+designspace-rules-max-up = الأقصى +
+designspace-rules-or = أو { $conditions }
+designspace-rules-add-rule = إضافة قاعدة من التحديد
+
+## Features
+
+features-title = الخصائص
+features-shaping = التشكيل
+features-marks-anchors = العلامات: نقاط الارتكاز
+features-marks-gpos = العلامات: gpos
+features-substitutions = الاستبدالات
+features-compile-failed = فشلت الترجمة
+features-compiling = جارٍ الترجمة…
+
+## Find replace
+
+find-replace-title = بحث واستبدال
+find-replace-glyph-names = أسماء الحروف
+find-replace-anchor-names = أسماء نقاط الارتكاز
+find-replace-components = المكونات
+find-replace-coordinates = الإحداثيات
+find-replace-kind = البحث في { $kind }
+find-replace-find = بحث
+find-replace-replace = استبدال
+find-replace-axis = المحور { $axis }
+find-replace-tolerance = التفاوت { $tolerance }
+find-replace-typing-hint = Tab يبدّل الحقول، Enter للمعاينة، Escape لإيقاف الكتابة
+find-replace-preview = معاينة
+find-replace-replace-all = استبدال الكل
+find-replace-undo = تراجع
+find-replace-would-change = سيتغير { $count } حرف
+find-replace-more = و{ $count } أخرى
+
+## Glyph filters
+
+glyph-filters-title = مرشحات الحروف
+glyph-filters-round = تدوير { $value }
+glyph-filters-offset = إزاحة { $value }
+glyph-filters-slant = إمالة { $value }°
+glyph-filters-add-round = إضافة تدوير
+glyph-filters-add-offset = إضافة إزاحة
+glyph-filters-add-slant = إضافة إمالة
+glyph-filters-apply = تطبيق
+glyph-filters-bake = تثبيت
+
+## Glyph layers
+
+glyph-layers-title = الطبقات
+
+## Glyph names
+
+glyph-names-title = أسماء الحروف
+glyph-names-no-issues = لم يُعثر على مشكلات
+glyph-names-fix = إصلاح
+glyph-names-more = و{ $count } أخرى
+glyph-names-fix-all = إصلاح الكل ({ $count })
+
+## OS/2
+
+os2-title = تصنيف OS/2
+os2-matches = معلومات الخط تطابق الاقتراحات
+os2-apply = تطبيق
+os2-apply-all = تطبيق الكل
+
+## Smart pieces
+
+smart-pieces-title = القطع الذكية
+smart-pieces-stamped = { $glyph }: { $count } قطع مطبوعة
+smart-pieces-new-piece = قطعة جديدة: { $name }_
+smart-pieces-naming-hint = Enter يعرّفها من المسارات المحددة، Escape للإلغاء
+smart-pieces-define = تعريف من المسارات المحددة
+smart-pieces-scale-x = تحجيم x { $value }
+smart-pieces-scale-y = تحجيم y { $value }
+smart-pieces-offset-x = إزاحة x { $value }
+smart-pieces-offset-y = إزاحة y { $value }
+smart-pieces-flip-x = قلب x: { $state }
+smart-pieces-flip-y = قلب y: { $state }
+smart-pieces-none = لا توجد قطع بعد
+smart-pieces-stamp = طبع
+smart-pieces-detach = فصل القطع
+
+## Spacing import
+
+spacing-import-title = استيراد التباعد
+spacing-import-advances = { $count } عرض
+spacing-import-pairs = { $count } زوج، { $existing } مقنّنة مسبقًا
+spacing-import-groups = { $count } مجموعة
+spacing-import-replace = استبدال
+spacing-import-merge = دمج
+spacing-import-skip-existing = تخطي الموجود
+spacing-import-cancel = إلغاء
+
+## STAT
+
+stat-title = STAT
+stat-link = ربط
+stat-link-to = ربط { $value }
+stat-elide = حذف
+stat-name = الاسم
+stat-add = إضافة { $tag }
+stat-elided-fallback = الاسم البديل عند الحذف: { $name }
+
+## Subset
+
+subset-title = مجموعة جزئية
+subset-text-buffers = مخازن النص
+subset-glyph-set = مجموعة الحروف { $name }
+subset-keeps = يحتفظ بـ{ $glyphs } حرف، و{ $dependencies } للمكونات
+subset-writing = جارٍ كتابة المجموعة الجزئية...
+subset-write-ufo = كتابة UFO
+subset-write-ttf = كتابة TTF
+subset-trial-ufo = UFO تجريبي
+subset-trial-ttf = TTF تجريبي
+
+## Text buffers
+
+text-buffers-title = مخازن النص
+text-buffers-renaming-hint = (Enter للحفظ، Esc للإلغاء)
+text-buffers-none = لا توجد مخازن نص؛ ضع نصًا بأداة النص
+text-buffers-type = كتابة
+text-buffers-go-to = انتقال
+text-buffers-rename = إعادة تسمية
+text-buffers-duplicate = تكرار
+text-buffers-delete = حذف
+
+## Glyph autocomplete
+
+autocomplete-go-to-glyph = الانتقال إلى حرف
+autocomplete-add-component = إضافة مكوّن إلى '{ $glyph }'
+autocomplete-kern-left = زوج التقنين: الحرف الأيسر
+autocomplete-kern-right = زوج التقنين: '{ $glyph }' و…
+autocomplete-no-glyphs = لم يُعثر على حروف
+
+## Glyph blend
+
+glyph-blend-title = المزج
+glyph-blend-session = مزج { $first } ← { $second }
+glyph-blend-add-glyph = إضافة حرف
+glyph-blend-cancel = إلغاء
+glyph-blend-apart = x/y منفصلين
+glyph-blend-play = تشغيل/إيقاف
+glyph-blend-save-gif = حفظ GIF
+
+## MIDI
+
+midi-blend = المزج
+midi-zoom = التكبير
+midi-nudge = الإزاحة
+midi-kerning = التقنين
+midi-no-input = لا يوجد إدخال
+midi-title = MIDI: { $device }
+midi-move-a-control = حرّك أداة تحكم
+midi-learn = تعلّم
+midi-clear = مسح
+
+## Stem report
+
+stem-report-title = الجذوع
+stem-report-glyph = الجذوع في { $glyph }
+stem-report-stem = جذع
+stem-report-bar = عارضة
+stem-report-thin-stem = جذع رفيع
+stem-report-thin-bar = عارضة رفيعة
+stem-report-none = لم يُعثر على جذوع
+stem-report-kind = النوع
+stem-report-width = العرض
+stem-report-rays = الأشعة
+stem-report-standard = القياسي
+
+## Handle entry
+
+handle-entry-length = طول المقبض
+handle-entry-angle = زاوية المقبض
+handle-entry-hint = (Enter للتعيين، Esc للإلغاء)
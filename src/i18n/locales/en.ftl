@@ -0,0 +1,449 @@
+# Bezy's English strings, and the fallback for every other language
+#
+# Each message is `id = text`; the code fills in `{ $variables }`. A new
+# language is a copy of this file with the text after each `=` translated,
+# added to `LANGUAGES` and `catalog_source` in `src/i18n/mod.rs`.
+
+## Tools
+
+tool-select = Select
+tool-pan = Pan
+tool-pen = Pen
+tool-shapes = Shapes
+tool-text = Text
+tool-knife = Knife
+tool-eraser = Eraser
+tool-corners = Corners
+tool-nib = Nib
+tool-hyper = Hyper
+tool-measure = Measure
+tool-metaballs = Metaballs
+tool-ai = AI
+hover-zoom = Zoom: { $percent }%
+
+## Panes
+
+pane-loading = Loading...
+glyph-pane-glyph = Glyph:
+glyph-pane-unicode = Unicode:
+glyph-pane-advance = Advance:
+glyph-pane-lsb = LSB:
+glyph-pane-rsb = RSB:
+glyph-pane-left-group = Left Group:
+glyph-pane-right-group = Right Group:
+export-title = Export
+preferences-title = Preferences
+button-close = close
+glyph-pane-none = None
+pane-no-designspace = No designspace lists this font
+pane-no-font = No font loaded
+pane-no-active-glyph = No active glyph
+pane-edit = edit
+pane-edit-foreground = edit foreground
+pane-remove = remove
+pane-page = page { $page } of { $count }
+
+## Export
+
+export-cancel = cancel
+export-export = export
+export-build-family = build family { $family }
+export-inspect = inspect last build
+export-no-font = No font loaded
+export-preparing = Preparing export…
+export-cancelling = Cancelling after { $file }…
+export-progress = Exporting { $done }/{ $total }: { $file }
+export-whole-font = whole font
+export-all-sources = all sources
+export-format-variable = variable
+export-format-static = static
+export-curve-error = curve error { $error }
+export-autohint = autohint { $state }
+export-flag-overlaps = flag overlaps { $state }
+export-remove-overlaps = remove overlaps { $state }
+export-svg = svg { $state }
+export-subset = subset   { $subset }
+export-styles = styles   { $styles }
+export-output = output   { $path }
+
+## Preferences
+
+pref-theme = Theme
+pref-language = Language
+pref-colorblind-safe = Colorblind-safe
+pref-ui-scale = UI scale
+pref-grid-snap = Grid snap
+pref-grid-size = Grid size
+pref-measurement-grid = Measurement grid
+pref-measurement-interval = Measurement grid spacing
+pref-template-glyphs = Templates in empty glyphs
+pref-nudge = Nudge
+pref-shift-nudge = Shift nudge
+pref-cmd-nudge = Cmd nudge
+pref-hit-tolerance = Click tolerance
+pref-autosave = Autosave
+pref-export-profile = Export profile
+pref-tool-shortcut = { $tool } tool
+pref-on = on
+pref-off = off
+pref-none = none
+pref-space = space
+pref-every-minutes = every { $minutes } min
+pref-every-seconds = every { $seconds } s
+pref-pixels = { $pixels } px
+pref-first-profile = first profile
+pref-press-a-letter = press a letter…
+pref-reset-shortcuts = reset shortcuts
+pref-key-not-a-letter = '{ $key }' can't be a tool shortcut, use a letter
+pref-key-taken = '{ $key }' already switches to the { $tool } tool
+pref-log-verbosity = { $subsystem } log
+pref-log-knife = Knife
+pref-log-selection = Selection
+pref-log-text-input = Text input
+pref-log-warn = warnings
+pref-log-info = info
+pref-log-debug = debug
+pref-log-trace = trace
+pref-customize-toolbar = customize toolbar
+
+## Toolbar
+
+toolbar-title = Customize toolbar
+toolbar-shown = shown
+toolbar-hidden = hidden
+toolbar-no-group = no group
+toolbar-in-group = in { $tool }
+toolbar-reset = reset toolbar
+toolbar-hide-tool = Hide { $tool }
+toolbar-customize = Customize toolbar…
+
+## QA
+
+qa-severity-error = ERROR
+qa-severity-warning = WARN
+qa-severity-info = INFO
+qa-category-outlines = Outlines
+qa-category-metadata = Metadata
+qa-category-hinting = Hinting
+qa-category-kerning = Kerning
+qa-category-spacing = Spacing
+qa-category-unicode = Unicode
+qa-label-check = Check:
+qa-label-severity = Severity:
+qa-label-category = Category:
+
+# What a check looks for, shown above Fontspector's own message, by the
+# last part of the check id
+qa-check-outline_direction = Contours run the wrong way
+qa-check-license = The license is missing
+qa-check-family_naming_recommendations = The family name could be clearer
+qa-check-whitespace_glyphs = A whitespace glyph has ink
+qa-check-kerning_for_non_ligated_sequences = Letter pairs lack kerning
+qa-check-unicode_range_bits = Unicode ranges in the OS/2 table
+qa-check-hinting_impact = Hinting makes the font bigger
+qa-check-fontc_compile = The glyph compiles with fontc
+
+## Ghosts
+
+ghosts-title = Ghosts
+ghosts-color = color
+ghosts-remove = remove
+ghosts-remove-all = remove all
+
+## Auto kerning
+
+auto-kerning-title = Auto-kerning
+auto-kerning-accept = accept
+auto-kerning-reject = reject
+auto-kerning-first = first
+auto-kerning-second = second
+auto-kerning-now = now
+auto-kerning-new = new
+auto-kerning-all-pairs = all { $count } pairs
+auto-kerning-proof-collisions = proof collisions
+
+## Auto spacing
+
+auto-spacing-title = Auto-spacing
+auto-spacing-accept = accept
+auto-spacing-discard = discard
+auto-spacing-all-glyphs = all { $count } glyphs
+
+## Brace layers
+
+brace-layers-title = Brace layers
+brace-layers-add = add { $layer } to { $glyph }
+brace-layers-none = No glyph has brace layers
+brace-layers-glyphs = With brace layers: { $glyphs }
+
+## Build inspector
+
+build-inspector-title = Build inspector
+
+## Canvas image
+
+canvas-image-title = Canvas image
+canvas-image-window = window
+canvas-image-text = text
+canvas-image-selection = selection
+canvas-image-transparent = transparent background
+canvas-image-saving = Saving the image...
+canvas-image-save = save next to the font
+
+## Color layers
+
+color-layers-title = Color layers
+color-layers-foreground = foreground
+color-layers-missing = missing
+color-layers-count = { $glyph }: { $count } layers
+color-layers-color-down = color -
+color-layers-color-up = color +
+color-layers-up = up
+color-layers-down = down
+color-layers-add-layer = + layer
+color-layers-palette = palette { $palette } of { $count }
+color-layers-new-color = new color
+color-layers-edit-hint = Type #rrggbb or #rrggbbaa, Enter sets, Escape cancels
+
+## Compile errors
+
+compile-errors-title = Compile errors
+compile-errors-shaping = Shaping build
+compile-errors-export = Export
+compile-errors-build = Build
+compile-errors-failed = { $build } failed with { $count } error(s)
+
+## Component graph
+
+component-graph-title = Component graph
+component-graph-not-in-font = not in the font
+component-graph-loops-back = ⚠ loops back
+component-graph-more = … { $count } more
+component-graph-add-component = + component
+component-graph-built-from = Built from
+component-graph-follow-anchors = Components follow anchors: { $state }
+component-graph-used-by = Used by ({ $count } glyphs at any depth)
+component-graph-many-users = ⚠ Editing this glyph changes { $count } others
+component-graph-moved = Moved with '{ $glyph }' anchors: { $composites }
+component-graph-no-loops = Component loops: none
+component-graph-loops = ⚠ Component loops: { $count }
+
+## Composites
+
+composites-title = Composites
+composites-missing = { $missing } recipe glyph(s) missing, { $chosen } chosen
+composites-build-chosen = build chosen
+composites-build-all = build all missing
+composites-last-build = Last build: { $built } built, { $skipped } couldn't be built
+composites-more = and { $count } more
+
+## Curve flavor
+
+curve-flavor-title = Curves
+curve-flavor-lines = lines only
+curve-flavor-cubic = cubic
+curve-flavor-quadratic = quadratic
+curve-flavor-mixed = mixed
+curve-flavor-error-down = error -
+curve-flavor-error-up = error +
+curve-flavor-glyph-to-quadratic = glyph to quadratic
+curve-flavor-glyph-to-cubic = glyph to cubic
+curve-flavor-font-to-quadratic = font to quadratic
+curve-flavor-font-to-cubic = font to cubic
+curve-flavor-points = { $count } points
+curve-flavor-max-error = max error { $error } units
+curve-flavor-not-within = not within the max error
+curve-flavor-glyph = glyph: { $flavor }
+curve-flavor-added = to quadratic { $quadratic }, to cubic { $cubic }
+curve-flavor-no-glyph = glyph: none active
+curve-flavor-font = font: to quadratic { $quadratic }, to cubic { $cubic }
+
+## Design frames
+
+design-frames-title = Design frames
+design-frames-center-lines = center lines: { $state }
+design-frames-thirds = thirds: { $state }
+design-frames-frame = frame { $percent }%
+design-frames-add = add frame
+
+## Designspace rules
+
+designspace-rules-title = Designspace rules
+designspace-rules-add-condition = add condition
+designspace-rules-min-down = min -
+designspace-rules-min-up = min +
+designspace-rules-max-down = max -
+designspace-rules-max-up = max +
+designspace-rules-or = or { $conditions }
+designspace-rules-add-rule = add rule from selection
+
+## Features
+
+features-title = Features
+features-shaping = shaping
+features-marks-anchors = marks: anchors
+features-marks-gpos = marks: gpos
+features-substitutions = substitutions
+features-compile-failed = compile failed
+features-compiling = compiling…
+
+## Find replace
+
+find-replace-title = Find and replace
+find-replace-glyph-names = glyph names
+find-replace-anchor-names = anchor names
+find-replace-components = components
+find-replace-coordinates = coordinates
+find-replace-kind = find { $kind }
+find-replace-find = find
+find-replace-replace = replace
+find-replace-axis = axis { $axis }
+find-replace-tolerance = tolerance { $tolerance }
+find-replace-typing-hint = Tab swaps fields, Enter previews, Escape stops typing
+find-replace-preview = preview
+find-replace-replace-all = replace all
+find-replace-undo = undo
+find-replace-would-change = { $count } glyph(s) would change
+find-replace-more = and { $count } more
+
+## Glyph filters
+
+glyph-filters-title = Glyph filters
+glyph-filters-round = round { $value }
+glyph-filters-offset = offset { $value }
+glyph-filters-slant = slant { $value }°
+glyph-filters-add-round = add round
+glyph-filters-add-offset = add offset
+glyph-filters-add-slant = add slant
+glyph-filters-apply = apply
+glyph-filters-bake = bake
+
+## Glyph layers
+
+glyph-layers-title = Layers
+
+## Glyph names
+
+glyph-names-title = Glyph names
+glyph-names-no-issues = No issues found
+glyph-names-fix = fix
+glyph-names-more = and { $count } more
+glyph-names-fix-all = fix all { $count }
+
+## OS/2
+
+os2-title = OS/2 classification
+os2-matches = The font info matches the suggestions
+os2-apply = apply
+os2-apply-all = apply all
+
+## Smart pieces
+
+smart-pieces-title = Smart pieces
+smart-pieces-stamped = { $glyph }: { $count } pieces stamped
+smart-pieces-new-piece = new piece: { $name }_
+smart-pieces-naming-hint = Enter defines it from the selected contours, Escape cancels
+smart-pieces-define = define from selected contours
+smart-pieces-scale-x = scale x { $value }
+smart-pieces-scale-y = scale y { $value }
+smart-pieces-offset-x = offset x { $value }
+smart-pieces-offset-y = offset y { $value }
+smart-pieces-flip-x = flip x: { $state }
+smart-pieces-flip-y = flip y: { $state }
+smart-pieces-none = No pieces yet
+smart-pieces-stamp = stamp
+smart-pieces-detach = detach pieces
+
+## Spacing import
+
+spacing-import-title = Import spacing
+spacing-import-advances = { $count } advance(s)
+spacing-import-pairs = { $count } pair(s), { $existing } already kerned
+spacing-import-groups = { $count } group(s)
+spacing-import-replace = replace
+spacing-import-merge = merge
+spacing-import-skip-existing = skip existing
+spacing-import-cancel = cancel
+
+## STAT
+
+stat-title = STAT
+stat-link = link
+stat-link-to = link { $value }
+stat-elide = elide
+stat-name = name
+stat-add = add { $tag }
+stat-elided-fallback = elided fallback: { $name }
+
+## Subset
+
+subset-title = Subset
+subset-text-buffers = text buffers
+subset-glyph-set = glyph set { $name }
+subset-keeps = Keeps { $glyphs } glyphs, { $dependencies } for components
+subset-writing = Writing the subset...
+subset-write-ufo = write UFO
+subset-write-ttf = write TTF
+subset-trial-ufo = trial UFO
+subset-trial-ttf = trial TTF
+
+## Text buffers
+
+text-buffers-title = Text buffers
+text-buffers-renaming-hint = (Enter to keep, Esc to cancel)
+text-buffers-none = No text buffers; place text with the text tool
+text-buffers-type = type
+text-buffers-go-to = go to
+text-buffers-rename = rename
+text-buffers-duplicate = duplicate
+text-buffers-delete = delete
+
+## Glyph autocomplete
+
+autocomplete-go-to-glyph = Go to glyph
+autocomplete-add-component = Add a component to '{ $glyph }'
+autocomplete-kern-left = Kerning pair: left glyph
+autocomplete-kern-right = Kerning pair: '{ $glyph }' and…
+autocomplete-no-glyphs = No glyphs found
+
+## Glyph blend
+
+glyph-blend-title = Blend
+glyph-blend-session = Blend { $first } → { $second }
+glyph-blend-add-glyph = add glyph
+glyph-blend-cancel = cancel
+glyph-blend-apart = x/y apart
+glyph-blend-play = play/pause
+glyph-blend-save-gif = save GIF
+
+## MIDI
+
+midi-blend = blend
+midi-zoom = zoom
+midi-nudge = nudge
+midi-kerning = kerning
+midi-no-input = no input
+midi-title = MIDI: { $device }
+midi-move-a-control = move a control
+midi-learn = learn
+midi-clear = clear
+
+## Stem report
+
+stem-report-title = Stems
+stem-report-glyph = Stems in { $glyph }
+stem-report-stem = stem
+stem-report-bar = bar
+stem-report-thin-stem = thin stem
+stem-report-thin-bar = thin bar
+stem-report-none = No stems found
+stem-report-kind = kind
+stem-report-width = width
+stem-report-rays = rays
+stem-report-standard = standard
+
+## Handle entry
+
+handle-entry-length = Handle length
+handle-entry-angle = Handle angle
+handle-entry-hint = (Enter to set, Esc to cancel)
@@ -0,0 +1,215 @@
+//! Localization
+//!
+//! UI strings live in Fluent catalogs (`locales/<language>.ftl`) compiled
+//! into the binary, one per language, and are formatted by `fluent-bundle`,
+//! so a translation can use everything Fluent has: terms, selectors,
+//! attributes and references to other messages. A message missing from a
+//! language falls back to English, and one missing from English too comes
+//! back as its id, so a typo shows on screen instead of an empty label.
+//!
+//! The language is process-wide like the theme registry, so `tr` works the
+//! same from systems, plain functions and the TUI. `set_language` switches
+//! it; `ui::localization` relabels the UI when the language setting changes.
+
+use bevy::log::warn;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use std::sync::{OnceLock, RwLock};
+use unic_langid::LanguageIdentifier;
+
+/// Built-in languages: id and the language's own name for it
+pub const LANGUAGES: [(&str, &str); 2] = [("en", "English"), ("ar", "العربية")];
+
+/// Languages written right to left
+const RTL_LANGUAGES: [&str; 4] = ["ar", "fa", "he", "ur"];
+
+const FALLBACK_LANGUAGE: &str = "en";
+
+fn catalog_source(language: &str) -> Option<&'static str> {
+    match language {
+        "en" => Some(include_str!("locales/en.ftl")),
+        "ar" => Some(include_str!("locales/ar.ftl")),
+        _ => None,
+    }
+}
+
+/// One language's catalog, ready to format
+type Bundle = FluentBundle<FluentResource>;
+
+/// Read a catalog; entries with errors are left out and logged
+fn bundle(language: &str, source: &str) -> Option<Bundle> {
+    let locale: LanguageIdentifier = language.parse().ok()?;
+    let resource =
+        FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, errors)| {
+            warn!("The '{language}' catalog has errors: {errors:?}");
+            resource
+        });
+    let mut bundle = Bundle::new_concurrent(vec![locale]);
+    // Bevy's text has no bidi isolation, the isolating marks would show up
+    // as missing glyphs
+    bundle.set_use_isolating(false);
+    if let Err(errors) = bundle.add_resource(resource) {
+        warn!("The '{language}' catalog has errors: {errors:?}");
+    }
+    Some(bundle)
+}
+
+/// Format a message of `bundle`, `None` when it has no such message
+fn format_message(bundle: &Bundle, id: &str, args: &FluentArgs) -> Option<String> {
+    let pattern = bundle.get_message(id)?.value()?;
+    // Unknown variables and messages show as their names in braces
+    let mut errors = Vec::new();
+    Some(
+        bundle
+            .format_pattern(pattern, Some(args), &mut errors)
+            .into_owned(),
+    )
+}
+
+struct Localizer {
+    language: String,
+    messages: Bundle,
+    fallback: Bundle,
+}
+
+impl Localizer {
+    fn new(language: &str) -> Option<Self> {
+        Some(Self {
+            language: language.to_string(),
+            messages: bundle(language, catalog_source(language)?)?,
+            fallback: bundle(FALLBACK_LANGUAGE, catalog_source(FALLBACK_LANGUAGE)?)?,
+        })
+    }
+
+    fn format(&self, id: &str, args: &[(&str, &str)]) -> Option<String> {
+        let args: FluentArgs = args.iter().copied().collect();
+        format_message(&self.messages, id, &args)
+            .or_else(|| format_message(&self.fallback, id, &args))
+    }
+}
+
+fn localizer() -> &'static RwLock<Localizer> {
+    static LOCALIZER: OnceLock<RwLock<Localizer>> = OnceLock::new();
+    LOCALIZER.get_or_init(|| {
+        let localizer = Localizer::new(FALLBACK_LANGUAGE).expect("English catalog is built in");
+        RwLock::new(localizer)
+    })
+}
+
+/// Switch the UI language; unknown languages are refused and leave the
+/// current one in place
+pub fn set_language(language: &str) -> bool {
+    let Some(new) = Localizer::new(language) else {
+        return false;
+    };
+    if let Ok(mut localizer) = localizer().write() {
+        *localizer = new;
+    }
+    true
+}
+
+/// The id of the current UI language
+pub fn language() -> String {
+    localizer()
+        .read()
+        .map(|localizer| localizer.language.clone())
+        .unwrap_or_else(|_| FALLBACK_LANGUAGE.to_string())
+}
+
+/// Whether the current UI language is written right to left
+pub fn is_rtl() -> bool {
+    RTL_LANGUAGES.contains(&language().as_str())
+}
+
+/// The message `id` in the current language
+pub fn tr(id: &str) -> String {
+    tr_args(id, &[])
+}
+
+/// The message `id` in the current language with its `{ $variables }`
+/// filled in from `args`. Arguments are text, so selectors match them by
+/// their exact value
+pub fn tr_args(id: &str, args: &[(&str, &str)]) -> String {
+    try_tr_args(id, args).unwrap_or_else(|| id.to_string())
+}
+
+/// The message `id` in the current language, or `None` when no catalog
+/// has it
+pub fn try_tr_args(id: &str, args: &[(&str, &str)]) -> Option<String> {
+    localizer().read().ok()?.format(id, args)
+}
+
+/// A tool's name in the current language, `default` when it has none
+pub fn tool_name(tool_id: &str, default: &str) -> String {
+    try_tr_args(&format!("tool-{tool_id}"), &[]).unwrap_or_else(|| default.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluent_syntax::ast::Entry;
+
+    #[test]
+    fn test_format_messages() {
+        let source = "\
+-brand = Bezy
+found = { -brand } found { $count ->
+    [one] one glyph
+   *[other] { $count } glyphs
+}
+missing = { $missing } and { other }
+";
+        let localizer = Localizer {
+            language: "en".to_string(),
+            messages: bundle("en", source).unwrap(),
+            fallback: bundle("en", "fallback = From English").unwrap(),
+        };
+        assert_eq!(
+            localizer.format("found", &[("count", "one")]).as_deref(),
+            Some("Bezy found one glyph")
+        );
+        assert_eq!(
+            localizer.format("found", &[("count", "3")]).as_deref(),
+            Some("Bezy found 3 glyphs")
+        );
+        assert_eq!(
+            localizer.format("missing", &[]).as_deref(),
+            Some("{$missing} and {other}")
+        );
+        assert_eq!(
+            localizer.format("fallback", &[]).as_deref(),
+            Some("From English")
+        );
+        assert_eq!(localizer.format("nowhere", &[]), None);
+    }
+
+    #[test]
+    fn test_catalogs_parse() {
+        for (language, _) in LANGUAGES {
+            let source = catalog_source(language).unwrap().to_string();
+            if let Err((_, errors)) = FluentResource::try_new(source) {
+                panic!("'{language}' has errors: {errors:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_catalogs_cover_english() {
+        let source = catalog_source("en").unwrap().to_string();
+        let english = FluentResource::try_new(source)
+            .map_err(|(_, errors)| errors)
+            .unwrap();
+        let ids: Vec<&str> = english
+            .entries()
+            .filter_map(|entry| match entry {
+                Entry::Message(message) => Some(message.id.name),
+                _ => None,
+            })
+            .collect();
+        for (language, _) in LANGUAGES {
+            let catalog = bundle(language, catalog_source(language).unwrap()).unwrap();
+            let missing: Vec<_> = ids.iter().filter(|id| !catalog.has_message(id)).collect();
+            assert!(missing.is_empty(), "'{language}' lacks {missing:?}");
+        }
+    }
+}
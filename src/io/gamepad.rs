@@ -1,9 +1,10 @@
 //! Gamepad Input Support Module
 //!
 //! This module provides basic gamepad support for Bezy.
-//! It manages gamepad connections and input state.
+//! It manages gamepad connections and input state; editing with the
+//! gamepad is in `editing::gamepad_editing`.
 
-use bevy::input::gamepad::GamepadConnectionEvent;
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent};
 use bevy::prelude::*;
 
 /// Information about a connected gamepad
@@ -41,25 +42,47 @@ fn gamepad_connection_system(
     for event in connection_events.read() {
         debug!("Gamepad connection event: {:?}", event);
 
-        // For now, just track the first connected gamepad
-        if gamepad_manager.active_gamepad.is_none() {
-            gamepad_manager.active_gamepad = Some(GamepadInfo {
-                entity: event.gamepad,
-                name: "Gamepad".to_string(),
-            });
+        match &event.connection {
+            // For now, just track the first connected gamepad
+            GamepadConnection::Connected { name, .. } => {
+                if gamepad_manager.active_gamepad.is_none() {
+                    info!("Gamepad connected: {}", name);
+                    gamepad_manager.active_gamepad = Some(GamepadInfo {
+                        entity: event.gamepad,
+                        name: name.clone(),
+                    });
+                }
+            }
+            GamepadConnection::Disconnected => {
+                let was_active = gamepad_manager
+                    .active_gamepad
+                    .as_ref()
+                    .is_some_and(|info| info.entity == event.gamepad);
+                if was_active {
+                    info!("Gamepad disconnected");
+                    *gamepad_manager = GamepadManager::default();
+                }
+            }
         }
     }
 }
 
 /// System to update gamepad input state
-fn gamepad_input_system(gamepad_manager: Res<GamepadManager>, gamepads: Query<&Gamepad>) {
-    if let Some(info) = &gamepad_manager.active_gamepad {
-        if let Ok(_gamepad) = gamepads.get(info.entity) {
-            // Placeholder for reading gamepad input
-            // In a full implementation, we would read axis values and button states here
-            debug!("Gamepad active: {:?}", info.entity);
-        }
-    }
+fn gamepad_input_system(mut gamepad_manager: ResMut<GamepadManager>, gamepads: Query<&Gamepad>) {
+    let Some(entity) = gamepad_manager
+        .active_gamepad
+        .as_ref()
+        .map(|info| info.entity)
+    else {
+        return;
+    };
+    let Ok(gamepad) = gamepads.get(entity) else {
+        return;
+    };
+    gamepad_manager.left_stick = gamepad.left_stick();
+    gamepad_manager.right_stick = gamepad.right_stick();
+    gamepad_manager.left_trigger = gamepad.get(GamepadButton::LeftTrigger2).unwrap_or(0.0);
+    gamepad_manager.right_trigger = gamepad.get(GamepadButton::RightTrigger2).unwrap_or(0.0);
 }
 
 /// Check if a gamepad is connected
@@ -172,6 +172,12 @@ pub enum InputMode {
     Pen,
     /// Knife tool mode
     Knife,
+    /// Eraser tool mode
+    Eraser,
+    /// Corners tool mode
+    Corners,
+    /// Nib tool mode
+    Nib,
     /// Shape tool mode
     Shape,
     /// Metaballs tool mode
@@ -0,0 +1,470 @@
+//! MIDI controllers
+//!
+//! Knobs and faders of a MIDI controller can drive continuous parameters:
+//! the glyph blend amount, the zoom, the arrow-key nudge distance and the
+//! kerning of the pair under the pointer. Controls are bound by learning:
+//! pick a parameter in the MIDI pane, then move the control that should
+//! drive it. Bindings are kept in the user settings.
+//!
+//! Reading devices needs the `midi` feature (`cargo run --features midi`).
+//! Without it bindings are still kept, but no control changes arrive.
+
+use std::collections::BTreeMap;
+
+use crate::core::config::BezySettings;
+use crate::core::state::AppState;
+use crate::editing::font_changes::KerningChanged;
+use crate::editing::glyph_blend::{GlyphBlend, SetBlendAmountEvent};
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::sort::Sort;
+use crate::font_source::KerningData;
+use crate::io::pointer::PointerInfo;
+use crate::rendering::cameras::DesignCamera;
+use crate::systems::sorts::sort_entities::BufferSortIndex;
+use crate::ui::metrics_overlay::VerticalMetric;
+use crate::ui::status_bar::sort_under;
+use crate::ui::theme::{MAX_ALLOWED_ZOOM_SCALE, MIN_ALLOWED_ZOOM_SCALE};
+use bevy::prelude::*;
+
+/// Highest value a control sends
+const MAX_VALUE: f32 = 127.0;
+
+/// Nudge distances the nudge control spans, in font units
+const MIN_NUDGE: f32 = 1.0;
+const MAX_NUDGE: f32 = 32.0;
+
+/// Kerning change per step of the kerning control, in font units
+const KERNING_STEP: f64 = 1.0;
+
+/// A parameter a control can be bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MidiParameter {
+    BlendAmount,
+    Zoom,
+    NudgeAmount,
+    /// Kerning of the pair ending at the sort under the pointer
+    Kerning,
+}
+
+impl MidiParameter {
+    pub const ALL: [MidiParameter; 4] = [
+        MidiParameter::BlendAmount,
+        MidiParameter::Zoom,
+        MidiParameter::NudgeAmount,
+        MidiParameter::Kerning,
+    ];
+
+    /// Name in the user config file
+    pub fn id(self) -> &'static str {
+        match self {
+            MidiParameter::BlendAmount => "blend_amount",
+            MidiParameter::Zoom => "zoom",
+            MidiParameter::NudgeAmount => "nudge_amount",
+            MidiParameter::Kerning => "kerning",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|parameter| parameter.id() == id)
+    }
+
+    pub fn label(self) -> String {
+        let id = match self {
+            MidiParameter::BlendAmount => "midi-blend",
+            MidiParameter::Zoom => "midi-zoom",
+            MidiParameter::NudgeAmount => "midi-nudge",
+            MidiParameter::Kerning => "midi-kerning",
+        };
+        crate::i18n::tr(id)
+    }
+}
+
+/// A knob, fader or other continuous control of a MIDI controller
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MidiControl {
+    /// Channel, 0 to 15
+    pub channel: u8,
+    /// Controller number, 0 to 119
+    pub controller: u8,
+}
+
+impl std::fmt::Display for MidiControl {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "ch {} cc {}", self.channel + 1, self.controller)
+    }
+}
+
+/// Controls bound to parameters; a control drives one parameter at most
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MidiBindings(pub BTreeMap<MidiParameter, MidiControl>);
+
+impl MidiBindings {
+    pub fn control(&self, parameter: MidiParameter) -> Option<MidiControl> {
+        self.0.get(&parameter).copied()
+    }
+
+    pub fn parameter(&self, control: MidiControl) -> Option<MidiParameter> {
+        self.0
+            .iter()
+            .find(|(_, bound)| **bound == control)
+            .map(|(parameter, _)| *parameter)
+    }
+
+    /// Bind `control` to `parameter`, unbinding it from any other
+    pub fn bind(&mut self, parameter: MidiParameter, control: MidiControl) {
+        self.0.retain(|_, bound| *bound != control);
+        self.0.insert(parameter, control);
+    }
+
+    pub fn clear(&mut self, parameter: MidiParameter) {
+        self.0.remove(&parameter);
+    }
+}
+
+/// A control moved to `value`, 0 to 127
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MidiControlChange {
+    pub control: MidiControl,
+    pub value: u8,
+}
+
+/// Change the bindings from the MIDI pane
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MidiBindingEvent {
+    /// Bind the next control that moves to the parameter
+    Learn(MidiParameter),
+    Clear(MidiParameter),
+}
+
+/// The parameter waiting for a control to move, while learning
+#[derive(Resource, Debug, Default)]
+pub struct MidiLearn {
+    pub parameter: Option<MidiParameter>,
+}
+
+/// The connected MIDI input, if any
+#[derive(Resource, Debug, Default)]
+pub struct MidiDevice {
+    pub port_name: Option<String>,
+}
+
+pub struct MidiPlugin;
+
+impl Plugin for MidiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MidiLearn>()
+            .init_resource::<MidiDevice>()
+            .add_event::<MidiControlChange>()
+            .add_event::<MidiBindingEvent>()
+            .add_systems(
+                Update,
+                (
+                    handle_binding_events,
+                    learn_midi_controls,
+                    (
+                        midi_blend_amount,
+                        midi_zoom,
+                        midi_nudge_amount,
+                        midi_kerning,
+                    ),
+                )
+                    .chain(),
+            );
+        #[cfg(feature = "midi")]
+        app.add_systems(Startup, device::connect_midi_input)
+            .add_systems(
+                Update,
+                device::read_midi_input.before(handle_binding_events),
+            );
+    }
+}
+
+/// The control change in a MIDI message, if it is one; channel mode
+/// messages (controllers 120 to 127) aren't controls
+pub fn parse_control_change(message: &[u8]) -> Option<MidiControlChange> {
+    let &[status, controller, value, ..] = message else {
+        return None;
+    };
+    let is_control_change = status & 0xF0 == 0xB0 && controller < 120 && value < 0x80;
+    is_control_change.then_some(MidiControlChange {
+        control: MidiControl {
+            channel: status & 0x0F,
+            controller,
+        },
+        value,
+    })
+}
+
+/// Zoom scale for a control value, from zoomed all the way out at 0 to
+/// all the way in at 127, evenly in powers
+pub fn zoom_scale(value: u8) -> f32 {
+    let t = value as f32 / MAX_VALUE;
+    let (min, max) = (MIN_ALLOWED_ZOOM_SCALE.ln(), MAX_ALLOWED_ZOOM_SCALE.ln());
+    (max + (min - max) * t).exp()
+}
+
+/// The sides to kern two glyphs on: their own pair if it has a value,
+/// otherwise their groups
+pub fn kerning_sides(kerning: &KerningData, first: &str, second: &str) -> (String, String) {
+    if kerning.value(first, second).is_some() {
+        (first.to_string(), second.to_string())
+    } else {
+        kerning.class_pair(first, second)
+    }
+}
+
+/// The last value the control bound to `parameter` moved to this frame
+fn latest_value(
+    changes: &mut EventReader<MidiControlChange>,
+    settings: &BezySettings,
+    parameter: MidiParameter,
+) -> Option<u8> {
+    let control = settings.midi_bindings.control(parameter);
+    changes
+        .read()
+        .filter(|change| Some(change.control) == control)
+        .last()
+        .map(|change| change.value)
+}
+
+fn handle_binding_events(
+    mut events: EventReader<MidiBindingEvent>,
+    mut learn: ResMut<MidiLearn>,
+    mut settings: ResMut<BezySettings>,
+) {
+    for event in events.read() {
+        match *event {
+            MidiBindingEvent::Learn(parameter) => {
+                // Learning the parameter being learned stops learning
+                learn.parameter = (learn.parameter != Some(parameter)).then_some(parameter);
+            }
+            MidiBindingEvent::Clear(parameter) => {
+                if learn.parameter == Some(parameter) {
+                    learn.parameter = None;
+                }
+                settings.midi_bindings.clear(parameter);
+            }
+        }
+    }
+}
+
+/// Binds the first control to move to the parameter being learned
+fn learn_midi_controls(
+    mut changes: EventReader<MidiControlChange>,
+    mut learn: ResMut<MidiLearn>,
+    mut settings: ResMut<BezySettings>,
+) {
+    let Some(parameter) = learn.parameter else {
+        changes.clear();
+        return;
+    };
+    if let Some(change) = changes.read().next() {
+        info!("MIDI {} bound to {}", change.control, parameter.id());
+        settings.midi_bindings.bind(parameter, change.control);
+        learn.parameter = None;
+    }
+    changes.clear();
+}
+
+fn midi_blend_amount(
+    mut changes: EventReader<MidiControlChange>,
+    settings: Res<BezySettings>,
+    blend: Res<GlyphBlend>,
+    mut amount_events: EventWriter<SetBlendAmountEvent>,
+) {
+    let value = latest_value(&mut changes, &settings, MidiParameter::BlendAmount);
+    if let (Some(value), Some(_)) = (value, &blend.session) {
+        amount_events.write(SetBlendAmountEvent(value as f64 / MAX_VALUE as f64));
+    }
+}
+
+fn midi_zoom(
+    mut changes: EventReader<MidiControlChange>,
+    settings: Res<BezySettings>,
+    mut camera_query: Query<&mut Projection, With<DesignCamera>>,
+) {
+    let Some(value) = latest_value(&mut changes, &settings, MidiParameter::Zoom) else {
+        return;
+    };
+    for mut projection in camera_query.iter_mut() {
+        if let Projection::Orthographic(ortho) = projection.as_mut() {
+            ortho.scale = zoom_scale(value);
+        }
+    }
+}
+
+fn midi_nudge_amount(
+    mut changes: EventReader<MidiControlChange>,
+    mut settings: ResMut<BezySettings>,
+) {
+    let Some(value) = latest_value(&mut changes, &settings, MidiParameter::NudgeAmount) else {
+        return;
+    };
+    let nudge = (MIN_NUDGE + (MAX_NUDGE - MIN_NUDGE) * value as f32 / MAX_VALUE).round();
+    // Only a new distance is written back to the settings file
+    if settings.nudge.default != nudge {
+        settings.nudge.default = nudge;
+    }
+}
+
+/// Kerns the pair ending at the buffer sort under the pointer by how far
+/// the control moved since its last value
+#[allow(clippy::too_many_arguments)]
+fn midi_kerning(
+    mut changes: EventReader<MidiControlChange>,
+    mut last_value: Local<Option<u8>>,
+    settings: Res<BezySettings>,
+    pointer: Res<PointerInfo>,
+    sorts: Query<(&Transform, &Sort, &BufferSortIndex)>,
+    app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut kerning_changes: EventWriter<KerningChanged>,
+) {
+    let Some(value) = latest_value(&mut changes, &settings, MidiParameter::Kerning) else {
+        return;
+    };
+    let Some(last) = last_value.replace(value) else {
+        return;
+    };
+    let Some(mut state) = app_state else {
+        return;
+    };
+
+    let font = &state.workspace.font;
+    let info = &state.workspace.info;
+    let placed = sorts.iter().filter_map(|(transform, sort, index)| {
+        let advance = font.editing().get_glyph(&sort.glyph_name)?.advance_width as f32;
+        Some((transform.translation.truncate(), advance, index.0))
+    });
+    let descender = VerticalMetric::Descender.value(info) as f32;
+    let ascender = VerticalMetric::Ascender.value(info) as f32;
+    let Some(index) = sort_under(pointer.world, placed, descender, ascender) else {
+        return;
+    };
+    // The sort before a line break has no entity, so lines don't kern
+    let glyph_at = |wanted: usize| {
+        sorts
+            .iter()
+            .find(|(_, _, index)| index.0 == wanted)
+            .map(|(_, sort, _)| sort.glyph_name.clone())
+    };
+    let (Some(first), Some(second)) = (index.checked_sub(1).and_then(glyph_at), glyph_at(index))
+    else {
+        return;
+    };
+
+    let kerning = &mut state.workspace.font.kerning;
+    let (first_side, second_side) = kerning_sides(kerning, &first, &second);
+    let delta = (value as f64 - last as f64) * KERNING_STEP;
+    let kern = kerning.value(&first_side, &second_side).unwrap_or(0.0) + delta;
+    debug!("MIDI kerning {} {} = {}", first_side, second_side, kern);
+    kerning.set_value(&first_side, &second_side, kern);
+    app_state_changed.write(AppStateChanged);
+    kerning_changes.write(KerningChanged);
+}
+
+/// Reading the first MIDI input port
+#[cfg(feature = "midi")]
+mod device {
+    use std::sync::mpsc::{self, Receiver};
+
+    use super::{parse_control_change, MidiControlChange, MidiDevice};
+    use bevy::prelude::*;
+    use midir::{MidiInput, MidiInputConnection};
+
+    /// The open port and the control changes it has sent; the connection
+    /// isn't `Send` on every platform, so it stays on the main thread
+    struct MidiConnection {
+        _connection: MidiInputConnection<()>,
+        receiver: Receiver<MidiControlChange>,
+    }
+
+    fn connect() -> anyhow::Result<Option<(String, MidiConnection)>> {
+        let input = MidiInput::new("Bezy")?;
+        let Some(port) = input.ports().into_iter().next() else {
+            return Ok(None);
+        };
+        let port_name = input.port_name(&port)?;
+        let (sender, receiver) = mpsc::channel();
+        let connection = input
+            .connect(
+                &port,
+                "bezy-input",
+                move |_timestamp, message, _| {
+                    if let Some(change) = parse_control_change(message) {
+                        // The receiver only goes away when the app exits
+                        let _ = sender.send(change);
+                    }
+                },
+                (),
+            )
+            .map_err(|e| anyhow::anyhow!("{}", e.kind()))?;
+        Ok(Some((
+            port_name,
+            MidiConnection {
+                _connection: connection,
+                receiver,
+            },
+        )))
+    }
+
+    pub(super) fn connect_midi_input(world: &mut World) {
+        match connect() {
+            Ok(Some((port_name, connection))) => {
+                info!("MIDI input: {}", port_name);
+                world.resource_mut::<MidiDevice>().port_name = Some(port_name);
+                world.insert_non_send_resource(connection);
+            }
+            Ok(None) => debug!("No MIDI input found"),
+            Err(e) => warn!("MIDI input not opened: {:#}", e),
+        }
+    }
+
+    pub(super) fn read_midi_input(
+        connection: Option<NonSend<MidiConnection>>,
+        mut changes: EventWriter<MidiControlChange>,
+    ) {
+        if let Some(connection) = connection {
+            changes.write_batch(connection.receiver.try_iter());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_control_change() {
+        let control = MidiControl {
+            channel: 2,
+            controller: 74,
+        };
+        assert_eq!(
+            parse_control_change(&[0xB2, 74, 100]),
+            Some(MidiControlChange {
+                control,
+                value: 100
+            })
+        );
+        // Note on, a channel mode message and a cut-off message
+        assert_eq!(parse_control_change(&[0x92, 60, 100]), None);
+        assert_eq!(parse_control_change(&[0xB0, 123, 0]), None);
+        assert_eq!(parse_control_change(&[0xB0, 74]), None);
+    }
+
+    #[test]
+    fn test_bindings_move_controls() {
+        let control = MidiControl {
+            channel: 0,
+            controller: 1,
+        };
+        let mut bindings = MidiBindings::default();
+        bindings.bind(MidiParameter::Zoom, control);
+        bindings.bind(MidiParameter::Kerning, control);
+        assert_eq!(bindings.control(MidiParameter::Zoom), None);
+        assert_eq!(bindings.parameter(control), Some(MidiParameter::Kerning));
+        assert!((zoom_scale(0) - MAX_ALLOWED_ZOOM_SCALE).abs() < 1e-3);
+        assert!((zoom_scale(127) - MIN_ALLOWED_ZOOM_SCALE).abs() < 1e-3);
+    }
+}
@@ -1,6 +1,8 @@
 pub mod gamepad;
 pub mod input;
+pub mod midi;
 pub mod pointer;
+pub mod shortcuts;
 
 // Explicit re-exports for public API
 // Gamepad functionality
@@ -0,0 +1,375 @@
+//! Cmd/Ctrl keyboard shortcuts
+//!
+//! Every command run with Cmd (macOS) or Ctrl plus a key is bound here, in
+//! `Shortcut::binding`, with the other modifiers it takes. A shortcut fires
+//! only with exactly its modifiers, so Cmd/Ctrl+Shift+P doesn't also fire on
+//! Cmd/Ctrl+Alt+Shift+P.
+//!
+//! Each binding also names the tools it's meant for. That scope isn't
+//! checked when a shortcut is read: the system handling it checks the tool
+//! itself. The scope tells the test below which bindings can fire together,
+//! so it catches two shortcuts bound to the same keys where both work.
+//!
+//! Cmd/Ctrl held with other input, like bigger arrow nudges or adding to the
+//! selection with a click, is checked where that input is handled.
+//! Single-key tool shortcuts are in `ui::edit_mode_toolbar::keyboard_shortcuts`.
+
+use bevy::input::keyboard::KeyCode;
+use bevy::input::ButtonInput;
+
+/// The tools a shortcut works with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutScope {
+    /// Whichever tool is active
+    Anywhere,
+    /// Any tool but the text tool
+    Outlines,
+    /// The select tool
+    SelectTool,
+    /// The text tool
+    TextTool,
+}
+
+impl ShortcutScope {
+    /// Whether a tool exists that both scopes work with
+    pub fn overlaps(self, other: Self) -> bool {
+        use ShortcutScope::*;
+        match (self, other) {
+            (Anywhere, _) | (_, Anywhere) => true,
+            (TextTool, TextTool) => true,
+            (TextTool, _) | (_, TextTool) => false,
+            _ => true,
+        }
+    }
+}
+
+/// The modifiers of a shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modifiers {
+    Command,
+    CommandAlt,
+    CommandShift,
+    CommandAltShift,
+}
+
+impl Modifiers {
+    /// Whether exactly these modifiers are held
+    pub fn held(self, keyboard: &ButtonInput<KeyCode>) -> bool {
+        let command = keyboard.any_pressed([
+            KeyCode::SuperLeft,
+            KeyCode::SuperRight,
+            KeyCode::ControlLeft,
+            KeyCode::ControlRight,
+        ]);
+        let alt = keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+        let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        let wanted = match self {
+            Modifiers::Command => (false, false),
+            Modifiers::CommandAlt => (true, false),
+            Modifiers::CommandShift => (false, true),
+            Modifiers::CommandAltShift => (true, true),
+        };
+        command && (alt, shift) == wanted
+    }
+}
+
+/// Keys of the selection history slots, 1 to 9
+pub const DIGIT_KEYS: [KeyCode; 9] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+/// Keys aligning sorts: left, center, right, top, bottom, and distributing
+/// them horizontally
+pub const ALIGN_SORT_KEYS: [KeyCode; 6] = [
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyR,
+    KeyCode::KeyU,
+    KeyCode::KeyD,
+    KeyCode::KeyH,
+];
+
+/// Keys aligning text frames: left, center and right
+pub const ALIGN_TEXT_KEYS: [KeyCode; 3] = [KeyCode::KeyL, KeyCode::KeyC, KeyCode::KeyR];
+
+/// A Cmd/Ctrl shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortcut {
+    Save,
+    OpenFolder,
+    ExportDialog,
+    ExportGlyphSvg,
+    ExportAllGlyphsSvg,
+    ExportGlyphReport,
+    Checkerboard,
+    MeasurementGrid,
+    MetricsOverlay,
+    Rulers,
+    Preferences,
+    ReadingMode,
+    FindReplace,
+    GoToGlyph,
+    EnterKernPair,
+    NextGlyph,
+    PreviousGlyph,
+    LayersPane,
+    BraceLayers,
+    ColorLayers,
+    CanvasImage,
+    Subset,
+    ComponentGraph,
+    BlueZones,
+    Composites,
+    CurveFlavor,
+    DesignFrames,
+    DesignspaceRules,
+    GlyphBlend,
+    GlyphFilters,
+    TextBuffers,
+    GlyphGhosts,
+    ClearGhosts,
+    GlyphNames,
+    SavedGhost,
+    SmartPieces,
+    StemReport,
+    Os2Classification,
+    Stat,
+    PointNumbers,
+    SetStartPoints,
+    ToggleBackgroundLayer,
+    CopyToBackgroundLayer,
+    CopyOutlines,
+    PasteOutlines,
+    ConvertMetaballs,
+    SelectAll,
+    LockSelected,
+    UnlockAll,
+    HideOrShow,
+    PreviousSelection,
+    NextSelection,
+    RecallSelection,
+    SaveSelection,
+    AlignSorts,
+    DistributeSortsVertically,
+    AutoSpacing,
+    AutoKerning,
+    KernProof,
+    SpacingMode,
+    CharacterPicker,
+    LogTextBuffer,
+    TextFrame,
+    AlignText,
+    CopySorts,
+    CutSorts,
+    PasteSorts,
+}
+
+impl Shortcut {
+    pub const ALL: [Shortcut; 67] = [
+        Shortcut::Save,
+        Shortcut::OpenFolder,
+        Shortcut::ExportDialog,
+        Shortcut::ExportGlyphSvg,
+        Shortcut::ExportAllGlyphsSvg,
+        Shortcut::ExportGlyphReport,
+        Shortcut::Checkerboard,
+        Shortcut::MeasurementGrid,
+        Shortcut::MetricsOverlay,
+        Shortcut::Rulers,
+        Shortcut::Preferences,
+        Shortcut::ReadingMode,
+        Shortcut::FindReplace,
+        Shortcut::GoToGlyph,
+        Shortcut::EnterKernPair,
+        Shortcut::NextGlyph,
+        Shortcut::PreviousGlyph,
+        Shortcut::LayersPane,
+        Shortcut::BraceLayers,
+        Shortcut::ColorLayers,
+        Shortcut::CanvasImage,
+        Shortcut::Subset,
+        Shortcut::ComponentGraph,
+        Shortcut::BlueZones,
+        Shortcut::Composites,
+        Shortcut::CurveFlavor,
+        Shortcut::DesignFrames,
+        Shortcut::DesignspaceRules,
+        Shortcut::GlyphBlend,
+        Shortcut::GlyphFilters,
+        Shortcut::TextBuffers,
+        Shortcut::GlyphGhosts,
+        Shortcut::ClearGhosts,
+        Shortcut::GlyphNames,
+        Shortcut::SavedGhost,
+        Shortcut::SmartPieces,
+        Shortcut::StemReport,
+        Shortcut::Os2Classification,
+        Shortcut::Stat,
+        Shortcut::PointNumbers,
+        Shortcut::SetStartPoints,
+        Shortcut::ToggleBackgroundLayer,
+        Shortcut::CopyToBackgroundLayer,
+        Shortcut::CopyOutlines,
+        Shortcut::PasteOutlines,
+        Shortcut::ConvertMetaballs,
+        Shortcut::SelectAll,
+        Shortcut::LockSelected,
+        Shortcut::UnlockAll,
+        Shortcut::HideOrShow,
+        Shortcut::PreviousSelection,
+        Shortcut::NextSelection,
+        Shortcut::RecallSelection,
+        Shortcut::SaveSelection,
+        Shortcut::AlignSorts,
+        Shortcut::DistributeSortsVertically,
+        Shortcut::AutoSpacing,
+        Shortcut::AutoKerning,
+        Shortcut::KernProof,
+        Shortcut::SpacingMode,
+        Shortcut::CharacterPicker,
+        Shortcut::LogTextBuffer,
+        Shortcut::TextFrame,
+        Shortcut::AlignText,
+        Shortcut::CopySorts,
+        Shortcut::CutSorts,
+        Shortcut::PasteSorts,
+    ];
+
+    /// Where the shortcut works, its modifiers, and the keys that fire it
+    pub fn binding(self) -> (ShortcutScope, Modifiers, &'static [KeyCode]) {
+        use Modifiers::*;
+        use ShortcutScope::*;
+        match self {
+            Shortcut::Save => (Anywhere, Command, &[KeyCode::KeyS]),
+            Shortcut::OpenFolder => (Anywhere, Command, &[KeyCode::KeyO]),
+            Shortcut::ExportDialog => (Anywhere, Command, &[KeyCode::KeyE]),
+            Shortcut::ExportGlyphSvg => (Anywhere, CommandShift, &[KeyCode::KeyE]),
+            Shortcut::ExportAllGlyphsSvg => (Anywhere, CommandAlt, &[KeyCode::KeyE]),
+            Shortcut::ExportGlyphReport => (Anywhere, CommandAltShift, &[KeyCode::KeyE]),
+            Shortcut::Checkerboard => (Anywhere, Command, &[KeyCode::KeyG]),
+            Shortcut::MeasurementGrid => (Anywhere, Command, &[KeyCode::Quote]),
+            Shortcut::MetricsOverlay => (Anywhere, CommandShift, &[KeyCode::Quote]),
+            Shortcut::Rulers => (Anywhere, Command, &[KeyCode::KeyR]),
+            Shortcut::Preferences => (Anywhere, Command, &[KeyCode::Comma]),
+            Shortcut::ReadingMode => (Anywhere, CommandAltShift, &[KeyCode::KeyN]),
+            Shortcut::FindReplace => (Anywhere, Command, &[KeyCode::KeyF]),
+            Shortcut::GoToGlyph => (Anywhere, CommandAlt, &[KeyCode::KeyG]),
+            Shortcut::EnterKernPair => (Anywhere, CommandAltShift, &[KeyCode::KeyK]),
+            Shortcut::NextGlyph => (Anywhere, CommandAlt, &[KeyCode::Period]),
+            Shortcut::PreviousGlyph => (Anywhere, CommandAlt, &[KeyCode::Comma]),
+            Shortcut::LayersPane => (Anywhere, CommandAlt, &[KeyCode::KeyB]),
+            Shortcut::BraceLayers => (Anywhere, CommandAlt, &[KeyCode::KeyN]),
+            Shortcut::ColorLayers => (Anywhere, CommandAltShift, &[KeyCode::Quote]),
+            Shortcut::CanvasImage => (Anywhere, CommandAltShift, &[KeyCode::KeyX]),
+            Shortcut::Subset => (Anywhere, CommandAlt, &[KeyCode::KeyX]),
+            Shortcut::ComponentGraph => (Anywhere, CommandAltShift, &[KeyCode::KeyZ]),
+            Shortcut::BlueZones => (Anywhere, CommandAlt, &[KeyCode::KeyZ]),
+            Shortcut::Composites => (Anywhere, CommandAlt, &[KeyCode::Backquote]),
+            Shortcut::CurveFlavor => (Anywhere, CommandAlt, &[KeyCode::Semicolon]),
+            Shortcut::DesignFrames => (Anywhere, CommandAlt, &[KeyCode::KeyF]),
+            Shortcut::DesignspaceRules => (Anywhere, CommandAlt, &[KeyCode::KeyJ]),
+            Shortcut::GlyphBlend => (SelectTool, CommandAlt, &[KeyCode::KeyI]),
+            Shortcut::GlyphFilters => (Anywhere, CommandAlt, &[KeyCode::KeyY]),
+            Shortcut::TextBuffers => (Anywhere, CommandAltShift, &[KeyCode::KeyY]),
+            Shortcut::GlyphGhosts => (SelectTool, CommandAlt, &[KeyCode::KeyO]),
+            Shortcut::ClearGhosts => (SelectTool, CommandAltShift, &[KeyCode::KeyO]),
+            Shortcut::GlyphNames => (Anywhere, CommandAlt, &[KeyCode::KeyQ]),
+            Shortcut::SavedGhost => (Anywhere, CommandAlt, &[KeyCode::KeyK]),
+            Shortcut::SmartPieces => (Anywhere, CommandAlt, &[KeyCode::Slash]),
+            Shortcut::StemReport => (SelectTool, CommandAlt, &[KeyCode::KeyW]),
+            Shortcut::Os2Classification => (Anywhere, CommandAltShift, &[KeyCode::KeyP]),
+            Shortcut::Stat => (Anywhere, CommandAlt, &[KeyCode::KeyP]),
+            Shortcut::PointNumbers => (Anywhere, CommandAltShift, &[KeyCode::KeyQ]),
+            Shortcut::SetStartPoints => (Anywhere, CommandAltShift, &[KeyCode::KeyG]),
+            Shortcut::ToggleBackgroundLayer => (Outlines, Command, &[KeyCode::KeyB]),
+            Shortcut::CopyToBackgroundLayer => (Outlines, CommandShift, &[KeyCode::KeyB]),
+            Shortcut::CopyOutlines => (Outlines, Command, &[KeyCode::KeyC]),
+            Shortcut::PasteOutlines => (Outlines, Command, &[KeyCode::KeyV]),
+            Shortcut::ConvertMetaballs => (Outlines, CommandShift, &[KeyCode::KeyC]),
+            Shortcut::SelectAll => (SelectTool, Command, &[KeyCode::KeyA]),
+            Shortcut::LockSelected => (SelectTool, Command, &[KeyCode::KeyL]),
+            Shortcut::UnlockAll => (SelectTool, CommandShift, &[KeyCode::KeyL]),
+            Shortcut::HideOrShow => (SelectTool, CommandShift, &[KeyCode::KeyH]),
+            Shortcut::PreviousSelection => (SelectTool, CommandAlt, &[KeyCode::BracketLeft]),
+            Shortcut::NextSelection => (SelectTool, CommandAlt, &[KeyCode::BracketRight]),
+            Shortcut::RecallSelection => (SelectTool, CommandAlt, &DIGIT_KEYS),
+            Shortcut::SaveSelection => (SelectTool, CommandAltShift, &DIGIT_KEYS),
+            Shortcut::AlignSorts => (SelectTool, CommandAlt, &ALIGN_SORT_KEYS),
+            Shortcut::DistributeSortsVertically => (SelectTool, CommandAltShift, &[KeyCode::KeyH]),
+            Shortcut::AutoSpacing => (TextTool, CommandShift, &[KeyCode::KeyP]),
+            Shortcut::AutoKerning => (TextTool, CommandShift, &[KeyCode::KeyJ]),
+            Shortcut::KernProof => (TextTool, CommandShift, &[KeyCode::KeyK]),
+            Shortcut::SpacingMode => (TextTool, CommandShift, &[KeyCode::KeyM]),
+            Shortcut::CharacterPicker => (TextTool, CommandShift, &[KeyCode::KeyU]),
+            Shortcut::LogTextBuffer => (TextTool, CommandAltShift, &[KeyCode::KeyS]),
+            Shortcut::TextFrame => (TextTool, CommandShift, &[KeyCode::KeyF]),
+            Shortcut::AlignText => (TextTool, CommandShift, &ALIGN_TEXT_KEYS),
+            Shortcut::CopySorts => (TextTool, Command, &[KeyCode::KeyC]),
+            Shortcut::CutSorts => (TextTool, Command, &[KeyCode::KeyX]),
+            Shortcut::PasteSorts => (TextTool, Command, &[KeyCode::KeyV]),
+        }
+    }
+
+    /// The shortcut's key just pressed with exactly its modifiers, if any
+    pub fn pressed_key(self, keyboard: &ButtonInput<KeyCode>) -> Option<KeyCode> {
+        let (_, modifiers, keys) = self.binding();
+        if !modifiers.held(keyboard) {
+            return None;
+        }
+        keys.iter().copied().find(|&key| keyboard.just_pressed(key))
+    }
+
+    /// Whether the shortcut was just pressed with exactly its modifiers
+    pub fn just_pressed(self, keyboard: &ButtonInput<KeyCode>) -> bool {
+        self.pressed_key(keyboard).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortcuts_dont_collide() {
+        for (index, a) in Shortcut::ALL.iter().enumerate() {
+            let (scope_a, modifiers_a, keys_a) = a.binding();
+            for b in &Shortcut::ALL[index + 1..] {
+                assert_ne!(a, b, "{a:?} is listed twice");
+                let (scope_b, modifiers_b, keys_b) = b.binding();
+                let collides = scope_a.overlaps(scope_b)
+                    && modifiers_a == modifiers_b
+                    && keys_a.iter().any(|key| keys_b.contains(key));
+                assert!(!collides, "{a:?} and {b:?} have the same keys");
+            }
+        }
+    }
+
+    #[test]
+    fn test_shortcuts_take_exactly_their_modifiers() {
+        let mut keyboard = ButtonInput::<KeyCode>::default();
+        keyboard.press(KeyCode::SuperLeft);
+        keyboard.press(KeyCode::ShiftLeft);
+        keyboard.press(KeyCode::KeyP);
+        assert!(Shortcut::AutoSpacing.just_pressed(&keyboard));
+        assert!(!Shortcut::Os2Classification.just_pressed(&keyboard));
+
+        keyboard.press(KeyCode::AltRight);
+        assert!(!Shortcut::AutoSpacing.just_pressed(&keyboard));
+        assert!(Shortcut::Os2Classification.just_pressed(&keyboard));
+
+        keyboard.press(KeyCode::Digit3);
+        assert_eq!(
+            Shortcut::SaveSelection.pressed_key(&keyboard),
+            Some(KeyCode::Digit3)
+        );
+        assert_eq!(Shortcut::RecallSelection.pressed_key(&keyboard), None);
+    }
+}
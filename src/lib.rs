@@ -4,8 +4,10 @@ pub mod data;
 pub mod editing;
 pub mod font_source;
 pub mod geometry;
+pub mod i18n;
 pub mod io;
 pub mod logging;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod qa;
 pub mod rendering;
 pub mod systems;
@@ -1,10 +1,17 @@
 //! Application logging functionality
 //!
-//! Handles log file management and output redirection
+//! Handles log file management. Logs go to a daily file in the logs
+//! directory through a tracing layer, which works the same on every
+//! native platform: alongside the console when running without the TUI,
+//! and instead of it when the TUI owns the terminal. Either way a runtime
+//! filter caps the chattiest subsystems at the verbosity picked in
+//! preferences (see `verbosity`).
 
+pub mod verbosity;
+
+use crate::core::config::{BezySettings, ConfigFile};
 use std::fs;
 use std::fs::OpenOptions;
-use std::os::unix::io::AsRawFd;
 use std::path::PathBuf;
 
 /// Get the path to the bezy config directory
@@ -19,12 +26,6 @@ pub fn logs_dir() -> PathBuf {
     config_dir().join("logs")
 }
 
-/// Get the path to the current log file
-pub fn current_log_file() -> PathBuf {
-    let timestamp = chrono::Utc::now().format("%Y-%m-%d");
-    logs_dir().join(format!("bezy-{}.log", timestamp))
-}
-
 /// Initialize the logs directory
 pub fn initialize_logs_directory() -> anyhow::Result<()> {
     let logs_dir = logs_dir();
@@ -32,76 +33,99 @@ pub fn initialize_logs_directory() -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Set up log redirection to ~/.config/bezy/logs/
-/// Used when running without TUI to capture logs to file
-pub fn setup_log_redirection() -> anyhow::Result<()> {
-    // Check if config directory exists - if not, we'll fail gracefully
-    let config_dir = config_dir();
-    if !config_dir.exists() {
-        // Config directory doesn't exist, so don't try to create logs
-        return Err(anyhow::anyhow!("Config directory doesn't exist"));
+#[cfg(not(target_arch = "wasm32"))]
+/// Log layers for the app's `LogPlugin`, used when running without the
+/// TUI: the runtime subsystem filter, and a file layer so logs are also
+/// kept in the logs directory
+///
+/// Logs only go to the console when the logs directory can't be created.
+pub fn app_log_layers(_app: &mut bevy::app::App) -> Option<bevy::log::BoxedLayer> {
+    use tracing_subscriber::Layer;
+
+    let filter = verbosity::runtime_filter_layer(&configured_verbosity());
+    if initialize_logs_directory().is_err() {
+        return Some(filter.boxed());
     }
+    Some(filter.and_then(file_layer()).boxed())
+}
 
-    // Initialize logs directory
-    initialize_logs_directory()?;
+#[cfg(not(target_arch = "wasm32"))]
+/// Subsystem verbosity from the user config file, read before the app's
+/// settings are
+fn configured_verbosity() -> verbosity::SubsystemVerbosity {
+    let mut settings = BezySettings::default();
+    if let Some(config) = ConfigFile::load() {
+        settings.apply_config(&config);
+    }
+    settings.log_verbosity
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Formatting layer writing to a daily log file in the logs directory
+fn file_layer<S>() -> impl tracing_subscriber::Layer<S> + Send + Sync + 'static
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    let file_appender = tracing_appender::rolling::daily(logs_dir(), "bezy.log");
+    tracing_subscriber::fmt::layer()
+        .with_writer(file_appender)
+        .with_ansi(false)
+        .with_target(true)
+        .with_thread_ids(false)
+        .with_line_number(true)
+}
 
-    // Get the log file path
-    let log_file_path = current_log_file();
+/// Append a message to today's crash log, for errors that happen before or
+/// outside logging, or where no console is attached (a GUI launch on
+/// Windows)
+pub fn write_crash_log(message: &str) -> Option<PathBuf> {
+    use std::io::Write;
 
-    // Create/open the log file - use truncate instead of append for single log file
-    let log_file = OpenOptions::new()
+    initialize_logs_directory().ok()?;
+    let timestamp = chrono::Utc::now().format("%Y-%m-%d");
+    let path = logs_dir().join(format!("crash-{}.log", timestamp));
+    let mut file = OpenOptions::new()
         .create(true)
-        .write(true)
-        .truncate(true)
-        .open(&log_file_path)?;
-
-    // Redirect stdout and stderr to the log file
-    unsafe {
-        libc::dup2(log_file.as_raw_fd(), libc::STDOUT_FILENO);
-        libc::dup2(log_file.as_raw_fd(), libc::STDERR_FILENO);
-    }
-
-    // Print initial log message to confirm redirection
-    println!(
-        "=== Bezy started at {} ===",
-        chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
-    );
-    println!("Logs redirected to: {:?}", log_file_path);
+        .append(true)
+        .open(&path)
+        .ok()?;
+    let time = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC");
+    writeln!(file, "=== {time} ===\n{message}\n").ok()?;
+    Some(path)
+}
 
-    Ok(())
+/// Log to the console, for command line runs that don't start the app
+pub fn setup_console_logging() -> anyhow::Result<()> {
+    use tracing_subscriber::{fmt, EnvFilter};
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("bezy=info"));
+    fmt()
+        .with_env_filter(filter)
+        .with_target(false)
+        .with_writer(std::io::stderr)
+        .try_init()
+        .map_err(|e| anyhow::anyhow!("Failed to set up logging: {e}"))
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 pub fn setup_file_logging_for_tui() -> anyhow::Result<()> {
-    use tracing_appender::rolling;
-    use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+    use tracing_subscriber::{prelude::*, EnvFilter};
 
     initialize_logs_directory()?;
 
-    let logs_dir = logs_dir();
-    let file_appender = rolling::daily(logs_dir, "bezy.log");
-
-    let file_layer = fmt::layer()
-        .with_writer(file_appender)
-        .with_ansi(false)
-        .with_target(true)
-        .with_thread_ids(false)
-        .with_line_number(true);
-
+    #[cfg(debug_assertions)]
+    let default_filter = "bezy=debug,bevy_render=warn,bevy_winit=warn,wgpu=warn,winit=warn,\
+                          bevy_ecs::error::handler=error";
+    #[cfg(not(debug_assertions))]
+    let default_filter =
+        "bezy=info,bevy=warn,wgpu=error,winit=error,bevy_ecs::error::handler=error";
     let filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| {
-            #[cfg(debug_assertions)]
-            {
-                EnvFilter::new("bezy=debug,bevy_render=warn,bevy_winit=warn,wgpu=warn,winit=warn,bevy_ecs::error::handler=error")
-            }
-            #[cfg(not(debug_assertions))]
-            {
-                EnvFilter::new("bezy=info,bevy=warn,wgpu=error,winit=error,bevy_ecs::error::handler=error")
-            }
-        });
+        .unwrap_or_else(|_| EnvFilter::new(verbosity::with_subsystem_targets(default_filter)));
 
     tracing_subscriber::registry()
+        .with(verbosity::runtime_filter_layer(&configured_verbosity()))
         .with(filter)
-        .with(file_layer)
+        .with(file_layer())
         .init();
 
     Ok(())
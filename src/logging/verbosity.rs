@@ -0,0 +1,193 @@
+//! Log verbosity per subsystem, changeable while the app runs
+//!
+//! The knife, selection and text input systems trace what they do in
+//! detail. The app's base filter lets their modules log at any level and a
+//! reloadable filter layer caps each subsystem at the verbosity picked in
+//! preferences, so turning one up takes effect without a restart. Events
+//! under a subsystem's verbosity are turned away at their callsite, before
+//! any of their fields are recorded.
+
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+/// A part of the editor whose logging can be turned up on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogSubsystem {
+    Knife,
+    Selection,
+    TextInput,
+}
+
+impl LogSubsystem {
+    pub const ALL: [LogSubsystem; 3] = [
+        LogSubsystem::Knife,
+        LogSubsystem::Selection,
+        LogSubsystem::TextInput,
+    ];
+
+    /// Name in the user config file
+    pub fn id(self) -> &'static str {
+        match self {
+            LogSubsystem::Knife => "knife",
+            LogSubsystem::Selection => "selection",
+            LogSubsystem::TextInput => "text-input",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|subsystem| subsystem.id() == id)
+    }
+
+    /// Modules the subsystem logs from
+    fn targets(self) -> &'static [&'static str] {
+        match self {
+            LogSubsystem::Knife => &["bezy::ui::edit_mode_toolbar::knife"],
+            LogSubsystem::Selection => &["bezy::editing::selection"],
+            LogSubsystem::TextInput => &[
+                "bezy::systems::sorts::unicode_input",
+                "bezy::ui::edit_mode_toolbar::text",
+                "bezy::core::state::text_editor",
+            ],
+        }
+    }
+}
+
+/// Most detailed level a subsystem logs at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogVerbosity {
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Default for LogVerbosity {
+    /// What the rest of the app logs at: info in debug builds and warnings
+    /// in release builds
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            LogVerbosity::Info
+        } else {
+            LogVerbosity::Warn
+        }
+    }
+}
+
+impl LogVerbosity {
+    pub const ALL: [LogVerbosity; 4] = [
+        LogVerbosity::Warn,
+        LogVerbosity::Info,
+        LogVerbosity::Debug,
+        LogVerbosity::Trace,
+    ];
+
+    /// Name in the user config file and in filter directives
+    pub fn id(self) -> &'static str {
+        match self {
+            LogVerbosity::Warn => "warn",
+            LogVerbosity::Info => "info",
+            LogVerbosity::Debug => "debug",
+            LogVerbosity::Trace => "trace",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|verbosity| verbosity.id() == id)
+    }
+}
+
+/// Verbosity of each subsystem; subsystems not listed log at the default
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SubsystemVerbosity(pub BTreeMap<LogSubsystem, LogVerbosity>);
+
+impl SubsystemVerbosity {
+    pub fn get(&self, subsystem: LogSubsystem) -> LogVerbosity {
+        self.0.get(&subsystem).copied().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, subsystem: LogSubsystem, verbosity: LogVerbosity) {
+        if verbosity == LogVerbosity::default() {
+            self.0.remove(&subsystem);
+        } else {
+            self.0.insert(subsystem, verbosity);
+        }
+    }
+
+    /// Directives capping each subsystem's modules at its verbosity and
+    /// letting everything else through to the base filter
+    fn filter_directives(&self) -> String {
+        let mut directives = vec!["trace".to_string()];
+        for subsystem in LogSubsystem::ALL {
+            let level = self.get(subsystem).id();
+            for target in subsystem.targets() {
+                directives.push(format!("{target}={level}"));
+            }
+        }
+        directives.join(",")
+    }
+}
+
+/// `filter` with every subsystem's modules let through at any level, for
+/// the runtime filter to cap
+pub fn with_subsystem_targets(filter: &str) -> String {
+    let mut directives = vec![filter.to_string()];
+    for subsystem in LogSubsystem::ALL {
+        for target in subsystem.targets() {
+            directives.push(format!("{target}=trace"));
+        }
+    }
+    directives.join(",")
+}
+
+/// Handle to the runtime filter, once logging has set one up
+static RUNTIME_FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Filter layer capping each subsystem at `verbosity`, changed later with
+/// `set_subsystem_verbosity`
+pub fn runtime_filter_layer(verbosity: &SubsystemVerbosity) -> reload::Layer<EnvFilter, Registry> {
+    let (layer, handle) = reload::Layer::new(EnvFilter::new(verbosity.filter_directives()));
+    // Only one subscriber is ever set up; a second handle would be unused
+    let _ = RUNTIME_FILTER.set(handle);
+    layer
+}
+
+/// Cap each subsystem at `verbosity` from now on
+pub fn set_subsystem_verbosity(verbosity: &SubsystemVerbosity) {
+    let Some(handle) = RUNTIME_FILTER.get() else {
+        return;
+    };
+    if let Err(e) = handle.reload(EnvFilter::new(verbosity.filter_directives())) {
+        warn!("Failed to change log verbosity: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_directives() {
+        let mut verbosity = SubsystemVerbosity::default();
+        verbosity.set(LogSubsystem::Knife, LogVerbosity::Trace);
+        verbosity.set(LogSubsystem::Selection, LogVerbosity::default());
+        assert_eq!(verbosity.0.len(), 1);
+
+        let directives = verbosity.filter_directives();
+        assert!(directives.starts_with("trace,"));
+        assert!(directives.contains("bezy::ui::edit_mode_toolbar::knife=trace"));
+        let default = LogVerbosity::default().id();
+        assert!(directives.contains(&format!("bezy::editing::selection={default}")));
+        assert!(EnvFilter::try_new(&directives).is_ok());
+
+        let base = with_subsystem_targets("bezy=info");
+        assert!(base.starts_with("bezy=info,"));
+        assert!(base.contains("bezy::systems::sorts::unicode_input=trace"));
+        assert_eq!(LogVerbosity::from_id("debug"), Some(LogVerbosity::Debug));
+        assert_eq!(
+            LogSubsystem::from_id("text-input"),
+            Some(LogSubsystem::TextInput)
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! OS/2 classification checks on the UFO source
+//!
+//! Fontspector only sees the compiled font, so it can't tell a weight class
+//! left at a template's value from a deliberate one. These warnings compare
+//! the font info with the classification suggested from the glyph set and
+//! metrics (see `font_source::os2`).
+
+use super::{Category, Location, QAIssue, Severity};
+use crate::core::state::{FontData, FontInfo};
+use crate::font_source::os2::{Os2Classification, Os2Field};
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Check ID of the warnings
+const CHECK_ID: &str = "org.bezy/check/os2_classification";
+
+/// Warnings where the font info differs from the suggested classification
+pub fn classification_issues(font: &FontData, info: &FontInfo) -> Vec<QAIssue> {
+    Os2Classification::suggest(font, info)
+        .mismatches(info)
+        .into_iter()
+        .map(|mismatch| {
+            let category = match mismatch.field {
+                Os2Field::UnicodeRanges | Os2Field::CodePageRanges => Category::Unicode,
+                _ => Category::Metadata,
+            };
+            QAIssue {
+                severity: Severity::Warning,
+                category,
+                check_id: CHECK_ID.to_string(),
+                message: format!(
+                    "OS/2 {} is {}; the glyph set and metrics suggest {}",
+                    mismatch.field.label(),
+                    mismatch.current,
+                    mismatch.suggested
+                ),
+                location: Some(Location {
+                    glyph_name: None,
+                    table_name: Some("OS/2".to_string()),
+                    position: None,
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Classification warnings of a UFO
+pub fn ufo_classification_issues(ufo_path: &Path) -> Result<Vec<QAIssue>> {
+    let font = norad::Font::load(ufo_path)
+        .with_context(|| format!("Failed to load {}", ufo_path.display()))?;
+    let info = FontInfo::from_norad_font(&font);
+    let font_data = FontData::from_norad_font(&font, Some(ufo_path.to_path_buf()));
+    Ok(classification_issues(&font_data, &info))
+}
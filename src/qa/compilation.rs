@@ -0,0 +1,55 @@
+//! Compile failures as QA issues
+//!
+//! A source fontc can't compile leaves Fontspector nothing to check, so the
+//! QA report of that save lists the compile errors instead, each located at
+//! the glyph it names (see `data::compile_diagnostics`) for the QA tab to
+//! jump to.
+
+use super::{Category, Location, QAIssue, QAReport, QASummary, Severity};
+use crate::data::compile_cache::SourceFingerprint;
+use crate::data::compile_diagnostics::{glyph_diagnostics, CompileDiagnostic};
+use std::path::Path;
+
+/// Check ID of the compile errors
+pub const CHECK_ID: &str = "org.bezy/check/fontc_compile";
+
+/// An error for each diagnostic, at its glyph
+pub fn compile_issues(diagnostics: &[CompileDiagnostic]) -> Vec<QAIssue> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| QAIssue {
+            severity: Severity::Error,
+            category: Category::Outlines,
+            check_id: CHECK_ID.to_string(),
+            message: diagnostic.message.clone(),
+            location: diagnostic.glyph.as_ref().map(|glyph| Location {
+                glyph_name: Some(glyph.clone()),
+                table_name: None,
+                position: None,
+            }),
+        })
+        .collect()
+}
+
+/// The report of a source that failed to compile
+pub fn compile_failure_report(source: &Path, error: &anyhow::Error) -> QAReport {
+    // The fingerprint is keyed by the glyph names of every source
+    let glyphs = SourceFingerprint::of_source(source)
+        .map(|fingerprint| fingerprint.glyphs)
+        .unwrap_or_default();
+    let diagnostics = glyph_diagnostics(&format!("{error:#}"), |name| glyphs.contains_key(name));
+    let issues = compile_issues(&diagnostics);
+    QAReport {
+        font_path: source.to_path_buf(),
+        timestamp: std::time::SystemTime::now(),
+        summary: QASummary {
+            total_checks: 1,
+            passed: 0,
+            failed: issues.len(),
+            warnings: 0,
+            info: 0,
+            skipped: 0,
+        },
+        issues,
+    }
+}
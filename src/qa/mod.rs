@@ -1,3 +1,5 @@
+pub mod classification;
+pub mod compilation;
 pub mod compiler;
 pub mod fontspector;
 pub mod storage;
@@ -26,13 +28,25 @@ impl QAEngine {
     }
 
     pub async fn run_qa_on_save(&mut self, ufo_path: &Path) -> Result<QAReport> {
-        // 1. Compile UFO to TTF/OTF
-        let compiled_font = self.compiler.compile_for_qa(ufo_path).await?;
+        // 1. Compile UFO to TTF/OTF, or report why it doesn't compile
+        let compiled_font = match self.compiler.compile_for_qa(ufo_path).await {
+            Ok(compiled_font) => compiled_font,
+            Err(e) => {
+                let report = compilation::compile_failure_report(ufo_path, &e);
+                self.storage.store_report(&report).await?;
+                return Ok(report);
+            }
+        };
 
         // 2. Run Fontspector analysis
-        let report = self.fontspector.analyze(&compiled_font).await?;
+        let mut report = self.fontspector.analyze(&compiled_font).await?;
 
-        // 3. Store report
+        // 3. Compare the source's OS/2 classification with its glyph set
+        let classification = classification::ufo_classification_issues(ufo_path)?;
+        report.summary.warnings += classification.len();
+        report.issues.extend(classification);
+
+        // 4. Store report
         self.storage.store_report(&report).await?;
 
         Ok(report)
@@ -59,6 +73,15 @@ pub struct QAIssue {
     pub location: Option<Location>,
 }
 
+impl QAIssue {
+    /// A one-line description of the check in the UI language, for the
+    /// checks the catalogs know
+    pub fn check_summary(&self) -> Option<String> {
+        let check = self.check_id.rsplit('/').next().unwrap_or(&self.check_id);
+        crate::i18n::try_tr_args(&format!("qa-check-{check}"), &[])
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Severity {
     Error,
@@ -75,12 +98,28 @@ impl Severity {
         }
     }
 
+    /// The severity's name in the UI language
+    pub fn label(&self) -> String {
+        let id = match self {
+            Severity::Error => "qa-severity-error",
+            Severity::Warning => "qa-severity-warning",
+            Severity::Info => "qa-severity-info",
+        };
+        crate::i18n::tr(id)
+    }
+
+    /// Terminal color of the severity; the colorblind-safe colors are the
+    /// Okabe-Ito vermillion, yellow and sky blue
     #[cfg(feature = "tui")]
-    pub fn color(&self) -> ratatui::style::Color {
-        match self {
-            Severity::Error => ratatui::style::Color::Red,
-            Severity::Warning => ratatui::style::Color::Yellow,
-            Severity::Info => ratatui::style::Color::Blue,
+    pub fn color(&self, colorblind_safe: bool) -> ratatui::style::Color {
+        use ratatui::style::Color;
+        match (self, colorblind_safe) {
+            (Severity::Error, false) => Color::Red,
+            (Severity::Warning, false) => Color::Yellow,
+            (Severity::Info, false) => Color::Blue,
+            (Severity::Error, true) => Color::Rgb(213, 94, 0),
+            (Severity::Warning, true) => Color::Rgb(240, 228, 66),
+            (Severity::Info, true) => Color::Rgb(86, 180, 233),
         }
     }
 }
@@ -108,6 +147,21 @@ impl Category {
             Category::Other(s) => s,
         }
     }
+
+    /// The category's name in the UI language; other categories keep the
+    /// name the checker gave them
+    pub fn label(&self) -> String {
+        let id = match self {
+            Category::Outlines => "qa-category-outlines",
+            Category::Metadata => "qa-category-metadata",
+            Category::Hinting => "qa-category-hinting",
+            Category::Kerning => "qa-category-kerning",
+            Category::Spacing => "qa-category-spacing",
+            Category::Unicode => "qa-category-unicode",
+            Category::Other(s) => return s.clone(),
+        };
+        crate::i18n::tr(id)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
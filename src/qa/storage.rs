@@ -1,3 +1,4 @@
+use crate::data::table_dump::{FontDump, TableChange};
 use crate::qa::QAReport;
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
@@ -18,6 +19,16 @@ pub struct StoredQAReport {
     pub storage_timestamp: std::time::SystemTime,
 }
 
+/// A table dump of a compiled font, kept next to its QA reports, and the
+/// tables that changed since the dump before it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredTableDump {
+    pub font_path: PathBuf,
+    pub dump: FontDump,
+    pub changes: Vec<TableChange>,
+    pub storage_timestamp: std::time::SystemTime,
+}
+
 impl ReportStorage {
     pub fn new() -> Result<Self> {
         let reports_dir = Self::get_reports_dir();
@@ -27,14 +38,9 @@ impl ReportStorage {
     }
 
     fn get_reports_dir() -> PathBuf {
-        if let Ok(home) = std::env::var("HOME") {
-            PathBuf::from(home)
-                .join(".config")
-                .join("bezy")
-                .join("qa")
-                .join("reports")
-        } else {
-            PathBuf::from("/tmp").join("bezy-qa-reports")
+        match dirs::config_dir() {
+            Some(config_dir) => config_dir.join("bezy").join("qa").join("reports"),
+            None => std::env::temp_dir().join("bezy-qa-reports"),
         }
     }
 
@@ -227,6 +233,44 @@ impl ReportStorage {
         Ok(reports)
     }
 
+    /// Folder of a font's table dumps, inside the folder of its QA reports
+    fn table_dumps_dir(&self, font_path: &Path) -> Result<PathBuf> {
+        let font_hash = self.calculate_font_hash(font_path)?;
+        Ok(self.reports_dir.join(font_hash).join("tables"))
+    }
+
+    pub async fn store_table_dump(&self, stored: &StoredTableDump) -> Result<()> {
+        let dumps_dir = self.table_dumps_dir(&stored.font_path)?;
+        fs::create_dir_all(&dumps_dir).await?;
+
+        let json = serde_json::to_string(stored)?;
+        fs::write(dumps_dir.join("latest.json"), &json).await?;
+        let timestamp = stored
+            .storage_timestamp
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|e| anyhow!("Time error: {}", e))?
+            .as_secs();
+        fs::write(dumps_dir.join(format!("{}.json", timestamp)), &json).await?;
+
+        self.cleanup_old_reports(&dumps_dir, 10).await?;
+        Ok(())
+    }
+
+    pub async fn load_latest_table_dump(
+        &self,
+        font_path: &Path,
+    ) -> Result<Option<StoredTableDump>> {
+        let latest_path = self.table_dumps_dir(font_path)?.join("latest.json");
+
+        if !latest_path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(latest_path).await?;
+        let stored: StoredTableDump = serde_json::from_str(&content)?;
+        Ok(Some(stored))
+    }
+
     fn calculate_font_hash(&self, font_path: &Path) -> Result<String> {
         let mut hasher = DefaultHasher::new();
         font_path.hash(&mut hasher);
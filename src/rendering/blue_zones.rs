@@ -0,0 +1,188 @@
+//! Alignment zone bands
+//!
+//! Cmd/Ctrl+Alt+Z shows the font's alignment zones, from the blue values in
+//! the font info (see `font_source::blue_zones`), as translucent bands
+//! across every line of text. The extremes of each sort's glyph that fall
+//! inside a zone but on neither of its edges are ringed, since hinting
+//! can't treat them like the overshoots and flat edges around them.
+//! Nothing is drawn for fonts without blue values.
+
+use std::collections::HashMap;
+
+use crate::core::state::AppState;
+use crate::editing::font_changes::GlyphEdited;
+use crate::editing::sort::Sort;
+use crate::font_source::blue_zones::{blue_zones, stray_extrema, BlueZone};
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::ui::edit_mode_toolbar::PresentationMode;
+use crate::ui::metrics_overlay::{text_lines, TextLine};
+use crate::ui::themes::CurrentTheme;
+use bevy::prelude::*;
+
+/// How far the bands reach past the first and last sort of a line, in em
+const OVERHANG: f32 = 0.25;
+
+/// Radius of the rings around stray extremes, before zoom scaling
+const MARKER_RADIUS: f32 = 8.0;
+
+/// Behind the glyphs and their metrics lines
+const BAND_Z: f32 = 0.4;
+
+/// Above unselected points, below selected ones
+const MARKER_Z: f32 = 14.0;
+
+/// Whether the zones are shown
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct BlueZonesOverlay {
+    pub visible: bool,
+}
+
+/// Stray extremes by glyph, for the zones they were found with
+#[derive(Default)]
+struct StrayExtremaCache {
+    zones: Vec<BlueZone>,
+    glyphs: HashMap<String, Vec<Vec2>>,
+}
+
+/// What the zones were last drawn for
+#[derive(Debug, Clone, PartialEq)]
+struct DrawnZones {
+    lines: Vec<TextLine>,
+    zones: Vec<BlueZone>,
+    markers: Vec<Vec2>,
+    overhang: f32,
+    marker_radius: f32,
+}
+
+/// Marker for the band and ring meshes
+#[derive(Component)]
+struct BlueZoneMesh;
+
+pub struct BlueZonesPlugin;
+
+impl Plugin for BlueZonesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BlueZonesOverlay>()
+            .add_systems(Update, (toggle_blue_zones, render_blue_zones).chain());
+    }
+}
+
+/// Cmd/Ctrl+Alt+Z shows or hides the zones
+fn toggle_blue_zones(keyboard: Res<ButtonInput<KeyCode>>, mut overlay: ResMut<BlueZonesOverlay>) {
+    if Shortcut::BlueZones.just_pressed(&keyboard) {
+        overlay.visible = !overlay.visible;
+        debug!("Blue zones shown: {}", overlay.visible);
+    }
+}
+
+/// Redraws the bands and rings when the text, the zones or the view change
+#[allow(clippy::too_many_arguments)]
+fn render_blue_zones(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut drawn: Local<Option<DrawnZones>>,
+    mut cache: Local<StrayExtremaCache>,
+    mut glyph_edits: EventReader<GlyphEdited>,
+    overlay: Res<BlueZonesOverlay>,
+    sorts: Query<(&Transform, &Sort)>,
+    app_state: Option<Res<AppState>>,
+    presentation_mode: Option<Res<PresentationMode>>,
+    camera_scale: Res<CameraResponsiveScale>,
+    theme: Res<CurrentTheme>,
+    existing: Query<Entity, With<BlueZoneMesh>>,
+) {
+    if glyph_edits.read().count() > 0 {
+        cache.glyphs.clear();
+    }
+    let presentation_active = presentation_mode.is_some_and(|pm| pm.active);
+    let zones = app_state
+        .as_ref()
+        .filter(|_| overlay.visible && !presentation_active)
+        .map(|state| blue_zones(&state.workspace.info))
+        .unwrap_or_default();
+    let (Some(state), false) = (app_state, zones.is_empty()) else {
+        if drawn.take().is_some() {
+            for entity in existing.iter() {
+                commands.entity(entity).despawn();
+            }
+        }
+        return;
+    };
+
+    let font = &state.workspace.font;
+    if cache.zones != zones {
+        cache.zones = zones.clone();
+        cache.glyphs.clear();
+    }
+    let mut markers = Vec::new();
+    let mut placed = Vec::new();
+    for (transform, sort) in sorts.iter() {
+        let Some(glyph) = font.editing().get_glyph(&sort.glyph_name) else {
+            continue;
+        };
+        let origin = transform.translation.truncate();
+        placed.push((origin, glyph.advance_width as f32));
+        let extrema = cache
+            .glyphs
+            .entry(sort.glyph_name.clone())
+            .or_insert_with(|| {
+                stray_extrema(glyph, &zones)
+                    .into_iter()
+                    .map(|(x, y)| Vec2::new(x as f32, y as f32))
+                    .collect()
+            });
+        markers.extend(extrema.iter().map(|&point| origin + point));
+    }
+
+    let zones_drawn = DrawnZones {
+        lines: text_lines(placed),
+        zones,
+        markers,
+        overhang: state.workspace.info.units_per_em as f32 * OVERHANG,
+        marker_radius: camera_scale.adjusted_size(MARKER_RADIUS),
+    };
+    if drawn.as_ref() == Some(&zones_drawn) && !theme.is_changed() {
+        return;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let band_material = materials.add(ColorMaterial::from_color(theme.theme().blue_zone_color()));
+    for line in &zones_drawn.lines {
+        let width = line.right - line.left + zones_drawn.overhang * 2.0;
+        let center_x = (line.left + line.right) / 2.0;
+        for zone in &zones_drawn.zones {
+            let height = (zone.top - zone.bottom) as f32;
+            let center_y = line.baseline + (zone.bottom + zone.top) as f32 / 2.0;
+            commands.spawn((
+                BlueZoneMesh,
+                Mesh2d(meshes.add(Rectangle::new(width, height))),
+                MeshMaterial2d(band_material.clone()),
+                Transform::from_xyz(center_x, center_y, BAND_Z),
+            ));
+        }
+    }
+
+    if !zones_drawn.markers.is_empty() {
+        let radius = zones_drawn.marker_radius;
+        let ring = meshes.add(Annulus::new(
+            radius - camera_scale.adjusted_line_width(),
+            radius,
+        ));
+        let marker_material = materials.add(ColorMaterial::from_color(
+            theme.theme().blue_zone_stray_extreme_color(),
+        ));
+        for &point in &zones_drawn.markers {
+            commands.spawn((
+                BlueZoneMesh,
+                Mesh2d(ring.clone()),
+                MeshMaterial2d(marker_material.clone()),
+                Transform::from_translation(point.extend(MARKER_Z)),
+            ));
+        }
+    }
+    *drawn = Some(zones_drawn);
+}
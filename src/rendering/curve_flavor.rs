@@ -0,0 +1,140 @@
+//! Curve conversion preview
+//!
+//! While the curve flavor pane is shown (see `editing::curve_flavor`), marks
+//! the points converting the active sort's glyph would add, with a small
+//! diamond each, so the cost of a maximum error can be seen before
+//! converting.
+
+use crate::core::state::{AppState, OutlineData};
+use crate::editing::curve_flavor::{conversion_preview, CurveFlavorPanel};
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::rendering::mesh_utils::create_triangles_mesh;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::ui::edit_mode_toolbar::PresentationMode;
+use crate::ui::themes::CurrentTheme;
+use bevy::prelude::*;
+
+/// Above the points of the active sort
+const PREVIEW_Z: f32 = 16.0;
+
+/// Half the width of a diamond, in screen pixels
+const DIAMOND_RADIUS_PX: f32 = 4.0;
+
+/// What the preview was last drawn for
+#[derive(Debug, Clone, PartialEq)]
+struct DrawnPreview {
+    outlines: Vec<(Vec2, OutlineData)>,
+    max_error: f64,
+    pixel: f32,
+}
+
+/// Marker for the diamonds
+#[derive(Component)]
+struct CurvePreviewMarker;
+
+pub struct CurveFlavorRenderingPlugin;
+
+impl Plugin for CurveFlavorRenderingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, render_curve_preview);
+    }
+}
+
+/// Positions of the points an outline gets from converting it, that it
+/// doesn't have already
+fn added_positions(outline: &OutlineData, max_error: f64) -> Vec<Vec2> {
+    let Some(converted) = conversion_preview(&outline.contours, max_error) else {
+        return Vec::new();
+    };
+    let position = |x: f64, y: f64| Vec2::new(x as f32, y as f32);
+    let existing: Vec<Vec2> = outline
+        .contours
+        .iter()
+        .flat_map(|contour| &contour.points)
+        .map(|point| position(point.x, point.y))
+        .collect();
+    converted
+        .iter()
+        .flat_map(|contour| &contour.points)
+        .map(|point| position(point.x, point.y))
+        .filter(|added| !existing.iter().any(|point| point.distance(*added) < 0.01))
+        .collect()
+}
+
+/// Redraws the diamonds when the active glyph, the error or the view change
+#[allow(clippy::too_many_arguments)]
+fn render_curve_preview(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut drawn: Local<Option<DrawnPreview>>,
+    panel: Res<CurveFlavorPanel>,
+    active_sorts: Query<(&Transform, &Sort), With<ActiveSort>>,
+    app_state: Option<Res<AppState>>,
+    presentation_mode: Option<Res<PresentationMode>>,
+    camera_scale: Res<CameraResponsiveScale>,
+    theme: Res<CurrentTheme>,
+    existing: Query<Entity, With<CurvePreviewMarker>>,
+) {
+    let presentation_active = presentation_mode.is_some_and(|pm| pm.active);
+    let Some(state) = app_state.filter(|_| panel.visible && !presentation_active) else {
+        if drawn.take().is_some() {
+            for entity in existing.iter() {
+                commands.entity(entity).despawn();
+            }
+        }
+        return;
+    };
+
+    let preview = DrawnPreview {
+        outlines: active_sorts
+            .iter()
+            .filter_map(|(transform, sort)| {
+                let outline = state
+                    .workspace
+                    .font
+                    .editing()
+                    .get_glyph(&sort.glyph_name)?
+                    .outline
+                    .clone()?;
+                Some((transform.translation.truncate(), outline))
+            })
+            .collect(),
+        max_error: panel.max_error,
+        pixel: camera_scale.pixels_to_world(1.0),
+    };
+    if drawn.as_ref() == Some(&preview) && !theme.is_changed() {
+        return;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let radius = DIAMOND_RADIUS_PX * preview.pixel;
+    let diamonds: Vec<[Vec2; 3]> = preview
+        .outlines
+        .iter()
+        .flat_map(|(origin, outline)| {
+            added_positions(outline, preview.max_error)
+                .into_iter()
+                .map(move |point| *origin + point)
+        })
+        .flat_map(|center| {
+            let (x, y) = (Vec2::X * radius, Vec2::Y * radius);
+            [
+                [center + x, center + y, center - x],
+                [center - x, center - y, center + x],
+            ]
+        })
+        .collect();
+    if !diamonds.is_empty() {
+        let color = theme.theme().curve_preview_color();
+        commands.spawn((
+            CurvePreviewMarker,
+            Mesh2d(meshes.add(create_triangles_mesh(&diamonds))),
+            MeshMaterial2d(materials.add(ColorMaterial::from_color(color))),
+            Transform::from_xyz(0.0, 0.0, PREVIEW_Z),
+        ));
+    }
+    *drawn = Some(preview);
+}
@@ -0,0 +1,125 @@
+//! CJK design frames
+//!
+//! Draws the project's design frames inside the em box of every sort while
+//! `DesignFramesOverlay` is visible: from the sort's origin to its advance,
+//! and from the descender up one em.
+
+use crate::core::state::AppState;
+use crate::editing::design_frames::DesignFramesOverlay;
+use crate::editing::sort::Sort;
+use crate::font_source::DesignFrames;
+use crate::rendering::mesh_utils::create_lines_mesh;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::ui::edit_mode_toolbar::PresentationMode;
+use crate::ui::metrics_overlay::VerticalMetric;
+use crate::ui::themes::CurrentTheme;
+use bevy::prelude::*;
+use kurbo::Rect;
+
+/// Above the alignment zones, behind the glyphs
+const FRAMES_Z: f32 = 0.45;
+
+/// What the frames were last drawn for
+#[derive(Debug, Clone, PartialEq)]
+struct DrawnFrames {
+    frames: DesignFrames,
+    /// Origin and advance width of each sort
+    sorts: Vec<(Vec2, f32)>,
+    descender: f32,
+    units_per_em: f32,
+    line_width: f32,
+}
+
+/// Marker for the frame meshes
+#[derive(Component)]
+struct DesignFramesMesh;
+
+pub struct DesignFramesRenderingPlugin;
+
+impl Plugin for DesignFramesRenderingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, render_design_frames);
+    }
+}
+
+/// Redraws the frames when the text, the frames or the view change
+#[allow(clippy::too_many_arguments)]
+fn render_design_frames(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut drawn: Local<Option<DrawnFrames>>,
+    overlay: Res<DesignFramesOverlay>,
+    sorts: Query<(&Transform, &Sort)>,
+    app_state: Option<Res<AppState>>,
+    presentation_mode: Option<Res<PresentationMode>>,
+    camera_scale: Res<CameraResponsiveScale>,
+    theme: Res<CurrentTheme>,
+    existing: Query<Entity, With<DesignFramesMesh>>,
+) {
+    let presentation_active = presentation_mode.is_some_and(|pm| pm.active);
+    let Some(state) = app_state.filter(|_| overlay.visible && !presentation_active) else {
+        if drawn.take().is_some() {
+            for entity in existing.iter() {
+                commands.entity(entity).despawn();
+            }
+        }
+        return;
+    };
+
+    let font = &state.workspace.font;
+    let placed = sorts
+        .iter()
+        .filter_map(|(transform, sort)| {
+            let glyph = font.editing().get_glyph(&sort.glyph_name)?;
+            Some((transform.translation.truncate(), glyph.advance_width as f32))
+        })
+        .collect();
+    let info = &state.workspace.info;
+    let frames_drawn = DrawnFrames {
+        frames: font.design_frames.clone(),
+        sorts: placed,
+        descender: VerticalMetric::Descender.value(info) as f32,
+        units_per_em: info.units_per_em as f32,
+        line_width: camera_scale.adjusted_line_width(),
+    };
+    if drawn.as_ref() == Some(&frames_drawn) && !theme.is_changed() {
+        return;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let mut lines = Vec::new();
+    for &(origin, advance) in &frames_drawn.sorts {
+        let bottom = frames_drawn.descender as f64;
+        let em_box = Rect::new(
+            0.0,
+            bottom,
+            advance as f64,
+            bottom + frames_drawn.units_per_em as f64,
+        );
+        lines.extend(
+            frames_drawn
+                .frames
+                .frame_lines(em_box)
+                .into_iter()
+                .map(|(start, end)| {
+                    (
+                        origin + Vec2::new(start.x as f32, start.y as f32),
+                        origin + Vec2::new(end.x as f32, end.y as f32),
+                    )
+                }),
+        );
+    }
+    if !lines.is_empty() {
+        let color = theme.theme().design_frame_color();
+        commands.spawn((
+            DesignFramesMesh,
+            Mesh2d(meshes.add(create_lines_mesh(&lines, frames_drawn.line_width))),
+            MeshMaterial2d(materials.add(ColorMaterial::from_color(color))),
+            Transform::from_xyz(0.0, 0.0, FRAMES_Z),
+        ));
+    }
+    *drawn = Some(frames_drawn);
+}
@@ -3,6 +3,12 @@
 //! This module provides entity pooling to eliminate the expensive despawn/spawn cycles
 //! that currently happen every frame in the text editor. Instead of destroying and
 //! recreating entities, we reuse existing entities by updating their components.
+//!
+//! The glyph renderer draws each sort's outline with entities from that sort's pool;
+//! points and handles are instances (see `rendering::points`). Redrawing a sort hides
+//! its entities and takes them again, so typing and switching the active sort update
+//! entities in place. When a sort is removed its entities become spares any other
+//! sort can take.
 
 use bevy::prelude::*;
 use bevy::render::mesh::Mesh2d;
@@ -10,6 +16,9 @@ use bevy::sprite::{ColorMaterial, MeshMaterial2d};
 use bevy::time::common_conditions::on_timer;
 use std::collections::HashMap;
 
+/// Most entities kept as spares once their sorts are gone
+const MAX_SPARE_ENTITIES: usize = 4096;
+
 /// Resource to manage entity pools for different types of rendering elements (internal)
 #[derive(Resource, Default)]
 pub(crate) struct EntityPools {
@@ -19,6 +28,8 @@ pub(crate) struct EntityPools {
     pub metrics_pools: HashMap<Entity, MetricsEntityPool>,
     /// Pool for cursor entities (shared pool)
     pub cursor_pool: CursorEntityPool,
+    /// Hidden outline entities of removed sorts
+    pub spare: Vec<Entity>,
 }
 
 /// Pool for outline entities associated with a specific sort (internal)
@@ -185,6 +196,92 @@ impl EntityPools {
         }
     }
 
+    /// Get an outline entity for a specific sort: one the sort drew with
+    /// before, else a spare, else a new one
+    pub fn get_sort_element_entity(
+        &mut self,
+        commands: &mut Commands,
+        sort_entity: Entity,
+        entity_type: PooledEntityType,
+    ) -> Entity {
+        let (available, in_use) = match entity_type {
+            PooledEntityType::Outline => {
+                let pool = self.outline_pools.entry(sort_entity).or_default();
+                (&mut pool.available, &mut pool.in_use)
+            }
+            PooledEntityType::Metrics => {
+                let pool = self.metrics_pools.entry(sort_entity).or_default();
+                (&mut pool.available, &mut pool.in_use)
+            }
+            PooledEntityType::Cursor => (
+                &mut self.cursor_pool.available,
+                &mut self.cursor_pool.in_use,
+            ),
+        };
+
+        // Pooled entities despawned elsewhere are dropped along the way
+        while let Some(entity) = available.pop().or_else(|| self.spare.pop()) {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.insert(PooledEntity { entity_type });
+                in_use.push(entity);
+                return entity;
+            }
+        }
+
+        let entity = commands
+            .spawn((
+                PooledEntity { entity_type },
+                // Basic transform - will be updated when entity is used
+                Transform::default(),
+                GlobalTransform::default(),
+                Visibility::Hidden,
+                InheritedVisibility::default(),
+                ViewVisibility::default(),
+            ))
+            .id();
+        in_use.push(entity);
+        entity
+    }
+
+    /// Return the outline entities of a specific sort to its pool before the
+    /// sort is drawn again
+    pub fn return_sort_element_entities(&mut self, commands: &mut Commands, sort_entity: Entity) {
+        self.return_outline_entities(commands, sort_entity);
+    }
+
+    /// Hide the outline entities of a removed sort and keep them as spares
+    /// for other sorts
+    pub fn release_sort(&mut self, commands: &mut Commands, sort_entity: Entity) {
+        let mut released = Vec::new();
+        if let Some(mut pool) = self.outline_pools.remove(&sort_entity) {
+            released.append(&mut pool.in_use);
+            released.append(&mut pool.available);
+        }
+        if released.is_empty() {
+            return;
+        }
+
+        let room = MAX_SPARE_ENTITIES.saturating_sub(self.spare.len());
+        let extra = released.split_off(room.min(released.len()));
+        for entity in extra {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.despawn();
+            }
+        }
+        hide_entities(commands, &released);
+        debug!(
+            "Released {} pooled entities of sort {:?} as spares",
+            released.len(),
+            sort_entity
+        );
+        self.spare.append(&mut released);
+    }
+
+    /// Cursor entities currently drawn
+    pub fn cursor_entities_in_use(&self) -> &[Entity] {
+        &self.cursor_pool.in_use
+    }
+
     /// Return cursor entities to the available pool (called at start of frame)
     pub fn return_cursor_entities(&mut self, commands: &mut Commands) {
         warn!(
@@ -325,6 +422,7 @@ impl EntityPools {
         PoolStats {
             outline_available,
             outline_in_use,
+            spare: self.spare.len(),
             metrics_available,
             metrics_in_use,
             cursor_available: self.cursor_pool.available.len(),
@@ -340,6 +438,7 @@ impl EntityPools {
 pub(crate) struct PoolStats {
     pub outline_available: usize,
     pub outline_in_use: usize,
+    pub spare: usize,
     pub metrics_available: usize,
     pub metrics_in_use: usize,
     pub cursor_available: usize,
@@ -348,6 +447,14 @@ pub(crate) struct PoolStats {
     pub metrics_pools_count: usize,
 }
 
+fn hide_entities(commands: &mut Commands, entities: &[Entity]) {
+    for &entity in entities {
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.insert(Visibility::Hidden);
+        }
+    }
+}
+
 /// Helper functions for updating pooled entities
 /// Update an outline entity with new mesh and material
 pub fn update_outline_entity(
@@ -449,8 +556,8 @@ impl Plugin for EntityPoolingPlugin {
 /// System to log pool statistics periodically
 fn log_pool_stats(pools: Res<EntityPools>) {
     let stats = pools.get_pool_stats();
-    debug!("Entity Pool Stats: Outline(avail:{}/use:{}), Metrics(avail:{}/use:{}), Cursor(avail:{}/use:{}), Pools(outline:{}, metrics:{})", 
-        stats.outline_available, stats.outline_in_use,
+    debug!("Entity Pool Stats: Outline(avail:{}/use:{}, spare:{}), Metrics(avail:{}/use:{}), Cursor(avail:{}/use:{}), Pools(outline:{}, metrics:{})", 
+        stats.outline_available, stats.outline_in_use, stats.spare,
         stats.metrics_available, stats.metrics_in_use,
         stats.cursor_available, stats.cursor_in_use,
         stats.outline_pools_count, stats.metrics_pools_count);
@@ -5,16 +5,27 @@
 
 #![allow(clippy::too_many_arguments)]
 
+use crate::editing::font_changes::GlyphEdited;
+use crate::editing::glyph_blend::GlyphBlend;
+use crate::editing::glyph_ghosts::GlyphGhosts;
+use crate::editing::outline_locks::{GlyphLocks, OutlineLocks};
+use crate::editing::saved_glyphs::SavedGlyphs;
 use crate::editing::selection::components::{GlyphPointReference, PointType, Selected};
 use crate::editing::selection::enhanced_point_component::EnhancedPointType;
 use crate::editing::sort::manager::SortPointEntity;
 use crate::editing::sort::{ActiveSort, Sort};
-use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::rendering::entity_pools::{
+    update_outline_entity, EntityPools, PooledEntity, PooledEntityType,
+};
+use crate::rendering::mesh_cache::GlyphMeshCache;
+use crate::rendering::points::{InstanceShape, PointInstance, PointInstances};
+use crate::rendering::zoom_aware_scaling::{CameraResponsiveScale, RenderDetail};
 use crate::ui::theme::*;
 use crate::ui::themes::CurrentTheme;
 use bevy::prelude::*;
 use bevy::render::mesh::Mesh2d;
 use bevy::sprite::{ColorMaterial, MeshMaterial2d};
+use bevy::tasks::AsyncComputeTaskPool;
 use std::collections::{HashMap, HashSet};
 
 /// Resource to collect rendering data and reduce system parameter count
@@ -67,6 +78,18 @@ const HANDLE_Z: f32 = 7.0; // Behind outlines
 const OUTLINE_Z: f32 = 8.0; // Above handles, behind points
 const POINT_Z: f32 = 10.0; // Unselected points
 const SELECTED_POINT_Z: f32 = 15.0; // Selected points - always above unselected
+const COLOR_LAYER_Z: f32 = 5.0; // Color layer composite behind the active sort's handles
+const GHOST_LAYER_Z: f32 = 4.0; // Other UFO layers, behind the color composite
+
+/// Opacity of the color layer composite while the sort is being edited
+const ACTIVE_COLOR_LAYER_ALPHA: f32 = 0.35;
+/// Opacity of ghosted background/sketch layers behind the active sort
+const GHOST_LAYER_ALPHA: f32 = 0.15;
+/// Opacity of a glyph's saved outline ghosted behind its changes
+const SAVED_OUTLINE_ALPHA: f32 = 0.25;
+/// Opacity of a glyph blend being previewed, and its name in the mesh cache
+const BLEND_PREVIEW_ALPHA: f32 = 0.5;
+const BLEND_PREVIEW_CACHE_KEY: &str = "#blend-preview";
 
 /// System to collect rendering data with fewer parameters
 pub(crate) fn collect_rendering_data(
@@ -142,10 +165,22 @@ pub(crate) fn render_glyphs(
     >,
     app_state: Option<Res<crate::core::state::AppState>>,
     text_editor_state: Option<Res<crate::core::state::TextEditorState>>,
-    existing_elements: Query<(Entity, &GlyphRenderElement)>,
+    existing_elements: Query<(Entity, &GlyphRenderElement, Has<PooledEntity>)>,
+    (mut entity_pools, mut mesh_cache, mut point_instances): (
+        ResMut<EntityPools>,
+        ResMut<GlyphMeshCache>,
+        ResMut<PointInstances>,
+    ),
     rendering_data: Res<GlyphRenderingData>,
     theme: Res<CurrentTheme>,
-    presentation_mode: Option<Res<crate::ui::edit_mode_toolbar::PresentationMode>>,
+    (presentation_mode, render_detail, outline_locks, glyph_blend, glyph_ghosts, saved_glyphs): (
+        Option<Res<crate::ui::edit_mode_toolbar::PresentationMode>>,
+        Res<RenderDetail>,
+        Res<OutlineLocks>,
+        Res<GlyphBlend>,
+        Res<GlyphGhosts>,
+        Res<SavedGlyphs>,
+    ),
 ) {
     // PERFORMANCE: Early exit if no sorts to render
     let active_count = active_sort_query.iter().count();
@@ -168,6 +203,16 @@ pub(crate) fn render_glyphs(
         update_tracker.needs_update = true;
     }
 
+    // Zoomed far out, active sorts are drawn filled like the rest and fills are coarser
+    let simplified = render_detail.is_simplified();
+    if render_detail.is_changed() {
+        mesh_cache.detail = *render_detail;
+        update_tracker.needs_update = true;
+    }
+    if outline_locks.is_changed() {
+        update_tracker.needs_update = true;
+    }
+
     // Only rebuild if we actually need to update (prevents flash)
     if !update_tracker.needs_update {
         return;
@@ -230,8 +275,12 @@ pub(crate) fn render_glyphs(
     let mut skipped_count = 0;
     let total_count = existing_elements.iter().count();
 
-    for (element_entity, glyph_element) in existing_elements.iter() {
-        // Only despawn elements that belong to sorts that need clearing
+    for (element_entity, glyph_element, pooled) in existing_elements.iter() {
+        // Only despawn elements that belong to sorts that need clearing; pooled
+        // outline entities go back to their sort's pool below
+        if pooled {
+            continue;
+        }
         if sorts_to_clear.contains(&glyph_element.sort_entity) {
             // Check if entity still exists before despawning
             if let Ok(mut entity_commands) = commands.get_entity(element_entity) {
@@ -246,6 +295,15 @@ pub(crate) fn render_glyphs(
     debug!("🧹 SELECTIVE CLEANUP: Cleared {}/{} elements, skipped {} (sorts_to_clear: {}, total_sorts: {})", 
           cleared_count, total_count, skipped_count, sorts_to_clear.len(), all_current_sorts.len());
 
+    for &sort_entity in &sorts_to_clear {
+        point_instances.remove_sort(sort_entity);
+        if all_current_sorts.contains(&sort_entity) {
+            entity_pools.return_sort_element_entities(&mut commands, sort_entity);
+        } else {
+            entity_pools.release_sort(&mut commands, sort_entity);
+        }
+    }
+
     // Only clear tracking for sorts that changed, not all sorts
     if sorts_to_clear.len() == all_current_sorts.len() {
         // If all sorts changed, clear everything (fallback to nuclear approach)
@@ -269,12 +327,15 @@ pub(crate) fn render_glyphs(
         let mut element_entities = Vec::new();
 
         // Check if this glyph has components - if so, render as filled even when active
-        let has_components = glyph_has_components(&sort.glyph_name);
+        let has_components = glyph_has_components(app_state.as_deref(), &sort.glyph_name);
 
-        // In presentation mode OR for component glyphs, skip all editing helpers and render as filled
-        if presentation_active || has_components {
+        // In presentation mode, zoomed far out, OR for component glyphs, skip all editing
+        // helpers and render as filled
+        if presentation_active || simplified || has_components {
             let render_reason = if presentation_active {
                 "presentation mode"
+            } else if simplified {
+                "zoomed out"
             } else {
                 "has components"
             };
@@ -286,6 +347,7 @@ pub(crate) fn render_glyphs(
                 &mut commands,
                 &mut meshes,
                 &mut materials,
+                &mut mesh_cache,
                 &mut element_entities,
                 sort_entity,
                 &sort.glyph_name,
@@ -329,53 +391,134 @@ pub(crate) fn render_glyphs(
 
         debug!("🔍 UNIFIED POINT COLLECTION: Sort '{}' found {}/{} matching points, sort_points.len()={}", sort.glyph_name, matching_points, checked_points, sort_points.len());
 
-        if !sort_points.is_empty() {
-            debug!(
-                "🎨 RENDERING COMPONENTS: {} points for sort '{}'",
-                sort_points.len(),
-                sort.glyph_name
-            );
-            // UNIFIED RENDERING: Render all components using the same live Transform data
+        // Color glyphs show their composited layers faintly behind the editable outline,
+        // and other UFO layers (background sketches) are ghosted behind that
+        if let Some(state) = app_state.as_deref() {
+            // Components of mixed glyphs can't be edited here, so draw them filled
+            let component_paths = state
+                .workspace
+                .font
+                .editing()
+                .component_bezpaths(&sort.glyph_name);
+            if !component_paths.is_empty() {
+                spawn_filled_paths(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    &mut mesh_cache,
+                    &mut element_entities,
+                    sort_entity,
+                    &format!("{}#components", sort.glyph_name),
+                    &component_paths,
+                    sort_position,
+                    theme.theme().filled_glyph_color(),
+                    COLOR_LAYER_Z,
+                );
+            }
 
-            // 1. Render outlines using live Transform positions
-            render_glyph_outline(
+            render_ghost_layers(
                 &mut commands,
                 &mut meshes,
                 &mut materials,
+                &mut mesh_cache,
                 &mut element_entities,
                 sort_entity,
-                &sort_points,
+                &sort.glyph_name,
                 sort_position,
-                app_state.as_deref(),
-                &camera_scale,
+                state,
                 &theme,
             );
-
-            // 2. Render handles using live Transform positions
-            render_glyph_handles(
+            render_glyph_ghosts(
                 &mut commands,
                 &mut meshes,
                 &mut materials,
+                &mut mesh_cache,
                 &mut element_entities,
                 sort_entity,
-                &sort_points,
-                &camera_scale,
+                &glyph_ghosts,
+                sort_position,
+                state,
                 &theme,
             );
+            if let Some(outline) =
+                saved_glyphs.ghost_outline(&state.workspace.font, &sort.glyph_name)
+            {
+                spawn_filled_paths(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    &mut mesh_cache,
+                    &mut element_entities,
+                    sort_entity,
+                    &format!("{}#saved", sort.glyph_name),
+                    &outline.to_bezpaths(),
+                    sort_position,
+                    theme
+                        .theme()
+                        .filled_glyph_color()
+                        .with_alpha(SAVED_OUTLINE_ALPHA),
+                    GHOST_LAYER_Z,
+                );
+            }
+            render_color_layers(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                &mut mesh_cache,
+                &mut element_entities,
+                sort_entity,
+                &sort.glyph_name,
+                sort_position,
+                state,
+                &theme,
+                ACTIVE_COLOR_LAYER_ALPHA,
+                COLOR_LAYER_Z,
+            );
+        }
 
-            // 3. Render points using live Transform positions
-            render_glyph_points(
+        let glyph_locks = outline_locks.glyph(&sort.glyph_name);
+        if !sort_points.is_empty() {
+            debug!(
+                "🎨 RENDERING COMPONENTS: {} points for sort '{}'",
+                sort_points.len(),
+                sort.glyph_name
+            );
+            // UNIFIED RENDERING: Render all components using the same live Transform data
+
+            // 1. Render outlines using live Transform positions
+            render_glyph_outline(
                 &mut commands,
                 &mut meshes,
                 &mut materials,
+                &mut entity_pools,
                 &mut element_entities,
                 sort_entity,
                 &sort_points,
+                sort_position,
+                app_state.as_deref(),
+                &camera_scale,
+                &theme,
+                glyph_locks,
+            );
+
+            // 2. and 3. Handles and points are drawn as instances, except
+            // those of locked and hidden contours
+            let editable_points: Vec<_> = sort_points
+                .iter()
+                .filter(|point| glyph_locks.is_editable(point.2.contour_index))
+                .copied()
+                .collect();
+            let mut instances = Vec::new();
+            render_glyph_handles(&mut instances, &editable_points, &camera_scale, &theme);
+            render_glyph_points(
+                &mut instances,
+                &editable_points,
                 &camera_scale,
                 &theme,
                 text_editor_state.as_deref(),
                 smooth_points,
             );
+            point_instances.set_sort(sort_entity, instances);
         } else {
             debug!(
                 "🚫 NO POINTS: Rendering static outline for sort '{}' (no points found)",
@@ -386,6 +529,7 @@ pub(crate) fn render_glyphs(
                 &mut commands,
                 &mut meshes,
                 &mut materials,
+                &mut entity_pools,
                 &mut element_entities,
                 sort_entity,
                 &sort.glyph_name,
@@ -393,6 +537,7 @@ pub(crate) fn render_glyphs(
                 app_state.as_deref(),
                 &camera_scale,
                 &theme,
+                glyph_locks,
             );
         }
 
@@ -416,6 +561,7 @@ pub(crate) fn render_glyphs(
             &mut commands,
             &mut meshes,
             &mut materials,
+            &mut mesh_cache,
             &mut element_entities,
             sort_entity,
             &sort.glyph_name,
@@ -429,6 +575,37 @@ pub(crate) fn render_glyphs(
             .elements
             .insert(sort_entity, element_entities);
     }
+
+    // A glyph blend being previewed is drawn after the sort it was started from
+    if let Some(session) = glyph_blend.session.as_ref() {
+        let sort_transform = active_sort_query
+            .get(session.sort_entity)
+            .or_else(|_| inactive_sort_query.get(session.sort_entity))
+            .map(|(_, _, transform)| transform);
+        if let Ok(sort_transform) = sort_transform {
+            let (paths, offset) = session.preview_paths();
+            let element_entities = glyph_entities
+                .elements
+                .entry(session.sort_entity)
+                .or_default();
+            spawn_filled_paths(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                &mut mesh_cache,
+                element_entities,
+                session.sort_entity,
+                BLEND_PREVIEW_CACHE_KEY,
+                paths,
+                sort_transform.translation.truncate() + offset,
+                theme
+                    .theme()
+                    .filled_glyph_color()
+                    .with_alpha(BLEND_PREVIEW_ALPHA),
+                COLOR_LAYER_Z,
+            );
+        }
+    }
 }
 
 /// Render filled shapes for inactive sorts using Lyon tessellation
@@ -436,6 +613,7 @@ fn render_filled_outline(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
+    mesh_cache: &mut GlyphMeshCache,
     element_entities: &mut Vec<Entity>,
     sort_entity: Entity,
     glyph_name: &str,
@@ -445,138 +623,494 @@ fn render_filled_outline(
     theme: &CurrentTheme,
 ) {
     if let Some(app_state) = app_state {
-        if let Some(glyph) = app_state.workspace.font.get_glyph(glyph_name) {
-            if let Some(outline) = &glyph.outline {
-                let paths = outline.to_bezpaths();
+        // Color glyphs are drawn as their composited layers instead of the
+        // monochrome fallback outline
+        if render_color_layers(
+            commands,
+            meshes,
+            materials,
+            mesh_cache,
+            element_entities,
+            sort_entity,
+            glyph_name,
+            position,
+            app_state,
+            theme,
+            1.0,
+            OUTLINE_Z,
+        ) {
+            return;
+        }
+
+        // A fill already tessellated or checked at this revision, for an
+        // earlier sort or ahead of rendering, needs no outline extracted
+        if let Some(mesh) = mesh_cache.current_filled_mesh(glyph_name) {
+            spawn_filled_mesh(
+                commands,
+                materials,
+                element_entities,
+                sort_entity,
+                mesh,
+                position,
+                theme.theme().filled_glyph_color(),
+                OUTLINE_Z,
+            );
+            return;
+        }
 
-                debug!(
-                    "🎨 Rendering filled outline for '{}' with {} paths",
+        // Include component contours so composite glyphs fill correctly
+        let paths = app_state.workspace.font.editing().resolved_bezpaths(glyph_name);
+
+        debug!(
+            "🎨 Rendering filled outline for '{}' with {} paths",
+            glyph_name,
+            paths.len()
+        );
+
+        let total_elements: usize = paths.iter().map(|p| p.elements().len()).sum();
+        if total_elements == 0 {
+            if !paths.is_empty() {
+                warn!(
+                    "⚠️ Glyph '{}' has {} paths but 0 total elements - skipping fill",
                     glyph_name,
                     paths.len()
                 );
+            }
+            return;
+        }
 
-                let total_elements: usize = paths.iter().map(|p| p.elements().len()).sum();
-                if total_elements == 0 {
-                    warn!(
-                        "⚠️ Glyph '{}' has {} paths but 0 total elements - skipping fill",
-                        glyph_name,
-                        paths.len()
-                    );
-                    return;
-                }
+        spawn_filled_paths(
+            commands,
+            meshes,
+            materials,
+            mesh_cache,
+            element_entities,
+            sort_entity,
+            glyph_name,
+            &paths,
+            position,
+            theme.theme().filled_glyph_color(),
+            OUTLINE_Z,
+        );
+    }
+}
 
-                let mut lyon_path_builder = Path::builder();
+/// Render the COLR layers of a color glyph, bottom layer first
+///
+/// Returns `false` if the glyph has no color layers. `alpha` scales every
+/// layer's opacity so the composite can sit behind an editable outline.
+fn render_color_layers(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    mesh_cache: &mut GlyphMeshCache,
+    element_entities: &mut Vec<Entity>,
+    sort_entity: Entity,
+    glyph_name: &str,
+    position: Vec2,
+    app_state: &crate::core::state::AppState,
+    theme: &CurrentTheme,
+    alpha: f32,
+    base_z: f32,
+) -> bool {
+    let font = &app_state.workspace.font;
+    let Some(layers) = font.color.layers_for(glyph_name) else {
+        return false;
+    };
+
+    for (layer_index, layer) in layers.iter().enumerate() {
+        let Some(outline) = font
+            .get_glyph(&layer.glyph_name)
+            .and_then(|glyph| glyph.outline.as_ref())
+        else {
+            continue;
+        };
 
-                for (path_idx, kurbo_path) in paths.iter().enumerate() {
-                    let elements_count = kurbo_path.elements().len();
-                    debug!(
-                        "🎨 Processing path {}/{}: {} elements",
-                        path_idx + 1,
-                        paths.len(),
-                        elements_count
-                    );
+        let color = font
+            .color
+            .resolve_color(layer.palette_index)
+            .map(|[r, g, b, a]| Color::srgba(r, g, b, a * alpha))
+            .unwrap_or_else(|| theme.theme().filled_glyph_color().with_alpha(alpha));
+
+        // Stack layers in tiny z steps so later layers paint over earlier ones
+        let z = base_z + layer_index as f32 * 0.01;
+        spawn_filled_paths(
+            commands,
+            meshes,
+            materials,
+            mesh_cache,
+            element_entities,
+            sort_entity,
+            &format!("{}#layer", layer.glyph_name),
+            &outline.to_bezpaths(),
+            position,
+            color,
+            z,
+        );
+    }
 
-                    for element in kurbo_path.elements().iter() {
-                        match element {
-                            kurbo::PathEl::MoveTo(pt) => {
-                                lyon_path_builder.begin(point(pt.x as f32, pt.y as f32));
-                            }
-                            kurbo::PathEl::LineTo(pt) => {
-                                lyon_path_builder.line_to(point(pt.x as f32, pt.y as f32));
-                            }
-                            kurbo::PathEl::CurveTo(c1, c2, pt) => {
-                                lyon_path_builder.cubic_bezier_to(
-                                    point(c1.x as f32, c1.y as f32),
-                                    point(c2.x as f32, c2.y as f32),
-                                    point(pt.x as f32, pt.y as f32),
-                                );
-                            }
-                            kurbo::PathEl::QuadTo(c, pt) => {
-                                lyon_path_builder.quadratic_bezier_to(
-                                    point(c.x as f32, c.y as f32),
-                                    point(pt.x as f32, pt.y as f32),
-                                );
-                            }
-                            kurbo::PathEl::ClosePath => {
-                                lyon_path_builder.close();
-                            }
-                        }
-                    }
-                }
+    true
+}
 
-                let lyon_path = lyon_path_builder.build();
+/// Render the active glyph's outline on every layer not being edited
+fn render_ghost_layers(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    mesh_cache: &mut GlyphMeshCache,
+    element_entities: &mut Vec<Entity>,
+    sort_entity: Entity,
+    glyph_name: &str,
+    position: Vec2,
+    app_state: &crate::core::state::AppState,
+    theme: &CurrentTheme,
+) {
+    let font = &app_state.workspace.font;
+    let color = theme
+        .theme()
+        .filled_glyph_color()
+        .with_alpha(GHOST_LAYER_ALPHA);
+
+    for layer in font.inactive_layer_names() {
+        let Some(outline) = font
+            .layer_glyph(&layer, glyph_name)
+            .and_then(|glyph| glyph.outline.as_ref())
+        else {
+            continue;
+        };
+        spawn_filled_paths(
+            commands,
+            meshes,
+            materials,
+            mesh_cache,
+            element_entities,
+            sort_entity,
+            &format!("{}@{}", glyph_name, layer),
+            &outline.to_bezpaths(),
+            position,
+            color,
+            GHOST_LAYER_Z,
+        );
+    }
+}
 
-                let mut tessellator = FillTessellator::new();
-                let mut geometry: VertexBuffers<[f32; 2], u32> = VertexBuffers::new();
+/// Draw the glyphs ghosted for comparison behind the active sort
+fn render_glyph_ghosts(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    mesh_cache: &mut GlyphMeshCache,
+    element_entities: &mut Vec<Entity>,
+    sort_entity: Entity,
+    glyph_ghosts: &GlyphGhosts,
+    position: Vec2,
+    app_state: &crate::core::state::AppState,
+    theme: &CurrentTheme,
+) {
+    let font = &app_state.workspace.font;
+    let colors = theme.theme().glyph_ghost_colors();
+
+    for ghost in &glyph_ghosts.ghosts {
+        // Ghosts from the layer being edited share the fill of their sorts
+        let (cache_key, paths) = match &ghost.layer {
+            Some(layer) => {
+                let Some(outline) = font
+                    .layer_glyph(layer, &ghost.glyph_name)
+                    .and_then(|glyph| glyph.outline.as_ref())
+                else {
+                    continue;
+                };
+                (ghost.label(), outline.to_bezpaths())
+            }
+            None => (
+                ghost.glyph_name.clone(),
+                font.editing().resolved_bezpaths(&ghost.glyph_name),
+            ),
+        };
+        spawn_filled_paths(
+            commands,
+            meshes,
+            materials,
+            mesh_cache,
+            element_entities,
+            sort_entity,
+            &cache_key,
+            &paths,
+            position + ghost.offset,
+            colors[ghost.color_index % colors.len()],
+            GHOST_LAYER_Z,
+        );
+    }
+}
 
-                let tessellation_result = tessellator.tessellate_path(
-                    &lyon_path,
-                    &FillOptions::default().with_fill_rule(FillRule::EvenOdd),
-                    &mut BuffersBuilder::new(&mut geometry, |vertex: FillVertex| {
-                        [vertex.position().x, vertex.position().y]
-                    }),
-                );
+/// Fill closed paths with a single mesh entity, sharing the tessellation with
+/// every other sort showing the same outline. `cache_key` names the outline in
+/// the mesh cache.
+fn spawn_filled_paths(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    mesh_cache: &mut GlyphMeshCache,
+    element_entities: &mut Vec<Entity>,
+    sort_entity: Entity,
+    cache_key: &str,
+    paths: &[kurbo::BezPath],
+    position: Vec2,
+    color: Color,
+    z: f32,
+) {
+    let mesh = match mesh_cache.get_filled_mesh(cache_key, paths) {
+        Some(mesh) => mesh,
+        None => {
+            let tolerance = mesh_cache.detail.fill_tolerance();
+            let Some(mesh) = tessellate_filled_paths(cache_key, paths, tolerance) else {
+                return;
+            };
+            let mesh = meshes.add(mesh);
+            mesh_cache.cache_filled_mesh(cache_key.to_string(), paths.to_vec(), mesh.clone());
+            mesh
+        }
+    };
+
+    spawn_filled_mesh(
+        commands,
+        materials,
+        element_entities,
+        sort_entity,
+        mesh,
+        position,
+        color,
+        z,
+    );
+}
 
-                if tessellation_result.is_ok() && !geometry.vertices.is_empty() {
-                    debug!(
-                        "🎨 Tessellation successful: {} vertices, {} indices for '{}'",
-                        geometry.vertices.len(),
-                        geometry.indices.len(),
-                        glyph_name
-                    );
+/// Spawn the entity drawing a glyph-space fill mesh at a sort's position
+fn spawn_filled_mesh(
+    commands: &mut Commands,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    element_entities: &mut Vec<Entity>,
+    sort_entity: Entity,
+    mesh: Handle<Mesh>,
+    position: Vec2,
+    color: Color,
+    z: f32,
+) {
+    let entity = commands
+        .spawn((
+            GlyphRenderElement {
+                element_type: GlyphElementType::OutlineSegment,
+                sort_entity,
+            },
+            Mesh2d(mesh),
+            MeshMaterial2d(materials.add(ColorMaterial::from_color(color))),
+            Transform::from_translation(position.extend(z)),
+            GlobalTransform::default(),
+            Visibility::Visible,
+            InheritedVisibility::default(),
+            ViewVisibility::default(),
+        ))
+        .id();
 
-                    let vertices: Vec<[f32; 3]> = geometry
-                        .vertices
-                        .iter()
-                        .map(|&[x, y]| [x + position.x, y + position.y, 0.0])
-                        .collect();
+    element_entities.push(entity);
+}
 
-                    let normals = vec![[0.0, 0.0, 1.0]; vertices.len()];
-                    let uvs = vec![[0.0, 0.0]; vertices.len()];
+/// Distinct glyphs needing fills before they are prepared on several threads
+const PARALLEL_FILL_MIN_GLYPHS: usize = 8;
 
-                    let mut mesh = Mesh::new(
-                        bevy::render::mesh::PrimitiveTopology::TriangleList,
-                        default(),
-                    );
-                    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
-                    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
-                    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
-                    mesh.insert_indices(bevy::render::mesh::Indices::U32(geometry.indices));
-
-                    let entity = commands
-                        .spawn((
-                            GlyphRenderElement {
-                                element_type: GlyphElementType::OutlineSegment,
-                                sort_entity,
-                            },
-                            Mesh2d(meshes.add(mesh)),
-                            MeshMaterial2d(materials.add(ColorMaterial::from_color(
-                                theme.theme().filled_glyph_color(),
-                            ))),
-                            Transform::from_translation(Vec3::new(0.0, 0.0, OUTLINE_Z)),
-                            GlobalTransform::default(),
-                            Visibility::Visible,
-                            InheritedVisibility::default(),
-                            ViewVisibility::default(),
-                        ))
-                        .id();
-
-                    element_entities.push(entity);
-                } else if tessellation_result.is_err() {
-                    warn!(
-                        "🎨 Tessellation FAILED for glyph '{}': {:?}",
-                        glyph_name,
-                        tessellation_result.err()
+/// A glyph's outline, extracted and tessellated off the main thread
+enum PreparedFill {
+    /// The cached fill still matches the outline
+    Unchanged(String, Vec<kurbo::BezPath>),
+    Tessellated(String, Vec<kurbo::BezPath>, Mesh),
+    /// Nothing to fill; left for `render_glyphs` to skip
+    Empty,
+}
+
+/// Extract and tessellate the outlines of inactive sorts whose fills are out
+/// of date on the async compute pool, ahead of `render_glyphs`
+///
+/// A long buffer in a large font, CJK especially, shows hundreds of distinct
+/// glyphs, and resolving their components and tessellating them one after
+/// another stalls the frame. The fills are cached at the current revision, so
+/// `render_glyphs` reuses them without touching the outlines again.
+fn prepare_filled_meshes(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mesh_cache: ResMut<GlyphMeshCache>,
+    update_tracker: Res<SortVisualUpdateTracker>,
+    render_detail: Res<RenderDetail>,
+    app_state: Option<Res<crate::core::state::AppState>>,
+    inactive_sort_query: Query<&Sort, With<crate::editing::sort::InactiveSort>>,
+) {
+    if !update_tracker.needs_update && !render_detail.is_changed() {
+        return;
+    }
+    let Some(app_state) = app_state else {
+        return;
+    };
+    if render_detail.is_changed() {
+        mesh_cache.detail = *render_detail;
+    }
+
+    // Color glyphs are filled layer by layer instead
+    let font = &app_state.workspace.font;
+    let mut glyph_names: Vec<&str> = inactive_sort_query
+        .iter()
+        .map(|sort| sort.glyph_name.as_str())
+        .filter(|name| {
+            font.color.layers_for(name).is_none() && !mesh_cache.is_filled_mesh_current(name)
+        })
+        .collect();
+    glyph_names.sort_unstable();
+    glyph_names.dedup();
+    if glyph_names.len() < PARALLEL_FILL_MIN_GLYPHS {
+        return;
+    }
+
+    let pool = AsyncComputeTaskPool::get();
+    let chunk_size = glyph_names.len().div_ceil(pool.thread_num().max(1));
+    let cache: &GlyphMeshCache = &mesh_cache;
+    let tolerance = cache.detail.fill_tolerance();
+    let prepared = pool.scope(|scope| {
+        for chunk in glyph_names.chunks(chunk_size) {
+            scope.spawn(async move {
+                chunk
+                    .iter()
+                    .map(|&glyph_name| {
+                        let paths = font.editing().resolved_bezpaths(glyph_name);
+                        let unchanged = cache.filled_meshes.get(glyph_name).is_some_and(|cached| {
+                            cached.detail == cache.detail && cached.paths == paths
+                        });
+                        if unchanged {
+                            return PreparedFill::Unchanged(glyph_name.to_string(), paths);
+                        }
+                        match tessellate_filled_paths(glyph_name, &paths, tolerance) {
+                            Some(mesh) => {
+                                PreparedFill::Tessellated(glyph_name.to_string(), paths, mesh)
+                            }
+                            None => PreparedFill::Empty,
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            });
+        }
+    });
+
+    debug!(glyphs = glyph_names.len(), "Prepared glyph fills in parallel");
+    for fill in prepared.into_iter().flatten() {
+        match fill {
+            PreparedFill::Unchanged(glyph_name, paths) => {
+                // Marks the cached fill current
+                mesh_cache.get_filled_mesh(&glyph_name, &paths);
+            }
+            PreparedFill::Tessellated(glyph_name, paths, mesh) => {
+                mesh_cache.stats.filled_misses += 1;
+                let mesh = meshes.add(mesh);
+                mesh_cache.cache_filled_mesh(glyph_name, paths, mesh);
+            }
+            PreparedFill::Empty => {}
+        }
+    }
+}
+
+/// Tessellate closed paths into a glyph-space mesh
+fn tessellate_filled_paths(
+    cache_key: &str,
+    paths: &[kurbo::BezPath],
+    tolerance: f32,
+) -> Option<Mesh> {
+    let mut lyon_path_builder = Path::builder();
+
+    for (path_idx, kurbo_path) in paths.iter().enumerate() {
+        let elements_count = kurbo_path.elements().len();
+        debug!(
+            "🎨 Processing path {}/{}: {} elements",
+            path_idx + 1,
+            paths.len(),
+            elements_count
+        );
+
+        for element in kurbo_path.elements().iter() {
+            match element {
+                kurbo::PathEl::MoveTo(pt) => {
+                    lyon_path_builder.begin(point(pt.x as f32, pt.y as f32));
+                }
+                kurbo::PathEl::LineTo(pt) => {
+                    lyon_path_builder.line_to(point(pt.x as f32, pt.y as f32));
+                }
+                kurbo::PathEl::CurveTo(c1, c2, pt) => {
+                    lyon_path_builder.cubic_bezier_to(
+                        point(c1.x as f32, c1.y as f32),
+                        point(c2.x as f32, c2.y as f32),
+                        point(pt.x as f32, pt.y as f32),
                     );
-                } else {
-                    warn!(
-                        "🎨 Tessellation produced EMPTY geometry for glyph '{}'",
-                        glyph_name
+                }
+                kurbo::PathEl::QuadTo(c, pt) => {
+                    lyon_path_builder.quadratic_bezier_to(
+                        point(c.x as f32, c.y as f32),
+                        point(pt.x as f32, pt.y as f32),
                     );
                 }
+                kurbo::PathEl::ClosePath => {
+                    lyon_path_builder.close();
+                }
             }
         }
     }
+
+    let lyon_path = lyon_path_builder.build();
+
+    let mut tessellator = FillTessellator::new();
+    let mut geometry: VertexBuffers<[f32; 2], u32> = VertexBuffers::new();
+
+    let tessellation_result = tessellator.tessellate_path(
+        &lyon_path,
+        &FillOptions::default()
+            .with_fill_rule(FillRule::EvenOdd)
+            .with_tolerance(tolerance),
+        &mut BuffersBuilder::new(&mut geometry, |vertex: FillVertex| {
+            [vertex.position().x, vertex.position().y]
+        }),
+    );
+
+    if tessellation_result.is_ok() && !geometry.vertices.is_empty() {
+        debug!(
+            "🎨 Tessellation successful: {} vertices, {} indices for '{}'",
+            geometry.vertices.len(),
+            geometry.indices.len(),
+            cache_key
+        );
+
+        let vertices: Vec<[f32; 3]> = geometry
+            .vertices
+            .iter()
+            .map(|&[x, y]| [x, y, 0.0])
+            .collect();
+
+        let normals = vec![[0.0, 0.0, 1.0]; vertices.len()];
+        let uvs = vec![[0.0, 0.0]; vertices.len()];
+
+        let mut mesh = Mesh::new(
+            bevy::render::mesh::PrimitiveTopology::TriangleList,
+            default(),
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh.insert_indices(bevy::render::mesh::Indices::U32(geometry.indices));
+        return Some(mesh);
+    } else if tessellation_result.is_err() {
+        warn!(
+            "🎨 Tessellation FAILED for glyph '{}': {:?}",
+            cache_key,
+            tessellation_result.err()
+        );
+    } else {
+        warn!(
+            "🎨 Tessellation produced EMPTY geometry for glyph '{}'",
+            cache_key
+        );
+    }
+    None
 }
 
 /// Render outline using live Transform positions from points
@@ -584,6 +1118,7 @@ fn render_glyph_outline(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
+    pools: &mut EntityPools,
     element_entities: &mut Vec<Entity>,
     sort_entity: Entity,
     sort_points: &[(Entity, Vec2, &GlyphPointReference, &PointType, bool)],
@@ -591,6 +1126,7 @@ fn render_glyph_outline(
     app_state: Option<&crate::core::state::AppState>,
     camera_scale: &CameraResponsiveScale,
     theme: &CurrentTheme,
+    locks: &GlyphLocks,
 ) {
     // Build position map from live Transform data
     let mut live_positions = HashMap::new();
@@ -606,7 +1142,7 @@ fn render_glyph_outline(
     if let Some(app_state) = app_state {
         let glyph_name = &sort_points[0].2.glyph_name;
         warn!("🎨 render_glyph_outline: Attempting to render outline for '{}'", glyph_name);
-        if let Some(glyph) = app_state.workspace.font.get_glyph(glyph_name) {
+        if let Some(glyph) = app_state.workspace.font.editing().get_glyph(glyph_name) {
             warn!("🎨 render_glyph_outline: Found glyph '{}'", glyph_name);
             if let Some(outline) = &glyph.outline {
                 let original_paths = outline.to_bezpaths();
@@ -615,6 +1151,7 @@ fn render_glyph_outline(
                     commands,
                     meshes,
                     materials,
+                    pools,
                     element_entities,
                     sort_entity,
                     &original_paths,
@@ -622,6 +1159,7 @@ fn render_glyph_outline(
                     sort_position,
                     camera_scale,
                     theme,
+                    locks,
                 );
             } else {
                 warn!("⚠️ render_glyph_outline: No outline found for '{}'", glyph_name);
@@ -634,13 +1172,9 @@ fn render_glyph_outline(
     }
 }
 
-/// Render handles using live Transform positions
+/// Add handle lines between on-curve and off-curve points
 fn render_glyph_handles(
-    commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<ColorMaterial>>,
-    element_entities: &mut Vec<Entity>,
-    sort_entity: Entity,
+    instances: &mut Vec<PointInstance>,
     sort_points: &[(Entity, Vec2, &GlyphPointReference, &PointType, bool)],
     camera_scale: &CameraResponsiveScale,
     theme: &CurrentTheme,
@@ -671,32 +1205,21 @@ fn render_glyph_handles(
 
             // Draw handle if one is on-curve and other is off-curve
             if current.3.is_on_curve != next.3.is_on_curve {
-                let entity = spawn_line_mesh(
-                    commands,
-                    meshes,
-                    materials,
+                instances.push(PointInstance::line(
                     current.1,
                     next.1,
-                    1.0, // 1px width
-                    theme.theme().handle_line_color(),
+                    camera_scale.adjusted_line_width(), // 1px width
                     HANDLE_Z,
-                    sort_entity,
-                    GlyphElementType::Handle,
-                    camera_scale,
-                );
-                element_entities.push(entity);
+                    theme.theme().handle_line_color(),
+                ));
             }
         }
     }
 }
 
-/// Render points using live Transform positions
+/// Add the layered point markers, using live Transform positions
 fn render_glyph_points(
-    commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<ColorMaterial>>,
-    element_entities: &mut Vec<Entity>,
-    sort_entity: Entity,
+    instances: &mut Vec<PointInstance>,
     sort_points: &[(Entity, Vec2, &GlyphPointReference, &PointType, bool)],
     camera_scale: &CameraResponsiveScale,
     theme: &CurrentTheme,
@@ -705,24 +1228,12 @@ fn render_glyph_points(
 ) {
     for (point_entity, position, _point_ref, point_type, is_selected) in sort_points {
         // Determine colors and z-depth for two-layer system
-        let (primary_color, secondary_color, base_z) = if *is_selected {
-            (
-                theme.theme().selected_primary_color(),
-                theme.theme().selected_secondary_color(),
-                SELECTED_POINT_Z,
-            )
+        let ((primary_color, secondary_color), base_z) = if *is_selected {
+            (theme.selected_point_colors(), SELECTED_POINT_Z)
         } else if point_type.is_on_curve {
-            (
-                theme.theme().on_curve_primary_color(),
-                theme.theme().on_curve_secondary_color(),
-                POINT_Z,
-            )
+            (theme.on_curve_point_colors(), POINT_Z)
         } else {
-            (
-                theme.theme().off_curve_primary_color(),
-                theme.theme().off_curve_secondary_color(),
-                POINT_Z,
-            )
+            (theme.off_curve_point_colors(), POINT_Z)
         };
 
         // Selected points are larger for visual feedback
@@ -742,79 +1253,34 @@ fn render_glyph_points(
             let size = camera_scale.adjusted_size(base_size);
 
             // Layer 1: Base shape (full width) - primary color
-            let entity = commands
-                .spawn((
-                    GlyphRenderElement {
-                        element_type: GlyphElementType::Point {
-                            point_entity: *point_entity,
-                            is_outer: true,
-                        },
-                        sort_entity,
-                    },
-                    Sprite {
-                        color: primary_color,
-                        custom_size: Some(Vec2::splat(size)),
-                        ..default()
-                    },
-                    Transform::from_translation(position.extend(base_z)),
-                    GlobalTransform::default(),
-                    Visibility::Visible,
-                    InheritedVisibility::default(),
-                    ViewVisibility::default(),
-                ))
-                .id();
-            element_entities.push(entity);
+            instances.push(PointInstance::new(
+                InstanceShape::Square,
+                *position,
+                Vec2::splat(size),
+                base_z,
+                primary_color,
+            ));
 
             // Layer 2: Slightly smaller shape - secondary color
             let secondary_size = size * 0.7;
-            let secondary_entity = commands
-                .spawn((
-                    GlyphRenderElement {
-                        element_type: GlyphElementType::Point {
-                            point_entity: *point_entity,
-                            is_outer: false,
-                        },
-                        sort_entity,
-                    },
-                    Sprite {
-                        color: secondary_color,
-                        custom_size: Some(Vec2::splat(secondary_size)),
-                        ..default()
-                    },
-                    Transform::from_translation(position.extend(base_z + 1.0)),
-                    GlobalTransform::default(),
-                    Visibility::Visible,
-                    InheritedVisibility::default(),
-                    ViewVisibility::default(),
-                ))
-                .id();
-            element_entities.push(secondary_entity);
+            instances.push(PointInstance::new(
+                InstanceShape::Square,
+                *position,
+                Vec2::splat(secondary_size),
+                base_z + 1.0,
+                secondary_color,
+            ));
 
             // Layer 3: Small center shape - primary color (only for non-selected points)
             if !*is_selected {
                 let center_size = size * theme.theme().on_curve_inner_circle_ratio();
-                let center_entity = commands
-                    .spawn((
-                        GlyphRenderElement {
-                            element_type: GlyphElementType::Point {
-                                point_entity: *point_entity,
-                                is_outer: false,
-                            },
-                            sort_entity,
-                        },
-                        Sprite {
-                            color: primary_color,
-                            custom_size: Some(Vec2::splat(center_size)),
-                            ..default()
-                        },
-                        Transform::from_translation(position.extend(base_z + 2.0)),
-                        GlobalTransform::default(),
-                        Visibility::Visible,
-                        InheritedVisibility::default(),
-                        ViewVisibility::default(),
-                    ))
-                    .id();
-                element_entities.push(center_entity);
+                instances.push(PointInstance::new(
+                    InstanceShape::Square,
+                    *position,
+                    Vec2::splat(center_size),
+                    base_z + 2.0,
+                    primary_color,
+                ));
             }
         } else {
             // Off-curve points and circular on-curve points: circle with three layers
@@ -826,47 +1292,23 @@ fn render_glyph_points(
             let radius = camera_scale.adjusted_size(base_radius);
 
             // Layer 1: Base circle (full size) - primary color
-            let entity = commands
-                .spawn((
-                    GlyphRenderElement {
-                        element_type: GlyphElementType::Point {
-                            point_entity: *point_entity,
-                            is_outer: true,
-                        },
-                        sort_entity,
-                    },
-                    Mesh2d(meshes.add(Circle::new(radius))),
-                    MeshMaterial2d(materials.add(ColorMaterial::from_color(primary_color))),
-                    Transform::from_translation(position.extend(base_z)),
-                    GlobalTransform::default(),
-                    Visibility::Visible,
-                    InheritedVisibility::default(),
-                    ViewVisibility::default(),
-                ))
-                .id();
-            element_entities.push(entity);
+            instances.push(PointInstance::new(
+                InstanceShape::Circle,
+                *position,
+                Vec2::splat(radius * 2.0),
+                base_z,
+                primary_color,
+            ));
 
             // Layer 2: Slightly smaller circle - secondary color
             let secondary_radius = radius * 0.7;
-            let secondary_entity = commands
-                .spawn((
-                    GlyphRenderElement {
-                        element_type: GlyphElementType::Point {
-                            point_entity: *point_entity,
-                            is_outer: false,
-                        },
-                        sort_entity,
-                    },
-                    Mesh2d(meshes.add(Circle::new(secondary_radius))),
-                    MeshMaterial2d(materials.add(ColorMaterial::from_color(secondary_color))),
-                    Transform::from_translation(position.extend(base_z + 1.0)),
-                    GlobalTransform::default(),
-                    Visibility::Visible,
-                    InheritedVisibility::default(),
-                    ViewVisibility::default(),
-                ))
-                .id();
-            element_entities.push(secondary_entity);
+            instances.push(PointInstance::new(
+                InstanceShape::Circle,
+                *position,
+                Vec2::splat(secondary_radius * 2.0),
+                base_z + 1.0,
+                secondary_color,
+            ));
 
             // Layer 3: Small center circle - primary color (only for non-selected points)
             if !*is_selected {
@@ -876,25 +1318,13 @@ fn render_glyph_points(
                     } else {
                         theme.theme().off_curve_inner_circle_ratio()
                     };
-                let center_entity = commands
-                    .spawn((
-                        GlyphRenderElement {
-                            element_type: GlyphElementType::Point {
-                                point_entity: *point_entity,
-                                is_outer: false,
-                            },
-                            sort_entity,
-                        },
-                        Mesh2d(meshes.add(Circle::new(center_radius))),
-                        MeshMaterial2d(materials.add(ColorMaterial::from_color(primary_color))),
-                        Transform::from_translation(position.extend(base_z + 2.0)),
-                        GlobalTransform::default(),
-                        Visibility::Visible,
-                        InheritedVisibility::default(),
-                        ViewVisibility::default(),
-                    ))
-                    .id();
-                element_entities.push(center_entity);
+                instances.push(PointInstance::new(
+                    InstanceShape::Circle,
+                    *position,
+                    Vec2::splat(center_radius * 2.0),
+                    base_z + 2.0,
+                    primary_color,
+                ));
             }
         }
 
@@ -912,52 +1342,22 @@ fn render_glyph_points(
             let crosshair_length = line_size * root_size_multiplier * 2.0;
 
             // Horizontal line - primary color only
-            let h_primary_entity = commands
-                .spawn((
-                    GlyphRenderElement {
-                        element_type: GlyphElementType::Point {
-                            point_entity: *point_entity,
-                            is_outer: false,
-                        },
-                        sort_entity,
-                    },
-                    Sprite {
-                        color: primary_color,
-                        custom_size: Some(Vec2::new(crosshair_length, line_width)),
-                        ..default()
-                    },
-                    Transform::from_translation(position.extend(base_z + 3.0)),
-                    GlobalTransform::default(),
-                    Visibility::Visible,
-                    InheritedVisibility::default(),
-                    ViewVisibility::default(),
-                ))
-                .id();
-            element_entities.push(h_primary_entity);
+            instances.push(PointInstance::new(
+                InstanceShape::Square,
+                *position,
+                Vec2::new(crosshair_length, line_width),
+                base_z + 3.0,
+                primary_color,
+            ));
 
             // Vertical line - primary color only
-            let v_primary_entity = commands
-                .spawn((
-                    GlyphRenderElement {
-                        element_type: GlyphElementType::Point {
-                            point_entity: *point_entity,
-                            is_outer: false,
-                        },
-                        sort_entity,
-                    },
-                    Sprite {
-                        color: primary_color,
-                        custom_size: Some(Vec2::new(line_width, crosshair_length)),
-                        ..default()
-                    },
-                    Transform::from_translation(position.extend(base_z + 3.0)),
-                    GlobalTransform::default(),
-                    Visibility::Visible,
-                    InheritedVisibility::default(),
-                    ViewVisibility::default(),
-                ))
-                .id();
-            element_entities.push(v_primary_entity);
+            instances.push(PointInstance::new(
+                InstanceShape::Square,
+                *position,
+                Vec2::new(line_width, crosshair_length),
+                base_z + 3.0,
+                primary_color,
+            ));
         }
     }
 }
@@ -967,6 +1367,7 @@ fn render_static_outline(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
+    pools: &mut EntityPools,
     element_entities: &mut Vec<Entity>,
     sort_entity: Entity,
     glyph_name: &str,
@@ -974,13 +1375,22 @@ fn render_static_outline(
     app_state: Option<&crate::core::state::AppState>,
     camera_scale: &CameraResponsiveScale,
     theme: &CurrentTheme,
+    locks: &GlyphLocks,
 ) {
     if let Some(app_state) = app_state {
-        if let Some(glyph) = app_state.workspace.font.get_glyph(glyph_name) {
+        if let Some(glyph) = app_state.workspace.font.editing().get_glyph(glyph_name) {
             if let Some(outline) = &glyph.outline {
                 let paths = outline.to_bezpaths();
             // Render static outline from FontIR working copy
-            for path in paths {
+            for (contour_idx, path) in paths.into_iter().enumerate() {
+                if locks.is_hidden(contour_idx) {
+                    continue;
+                }
+                let stroke_color = if locks.is_locked(contour_idx) {
+                    theme.theme().locked_outline_color()
+                } else {
+                    theme.theme().path_stroke_color()
+                };
                 let elements: Vec<_> = path.elements().iter().collect();
                 let mut current_pos = None;
 
@@ -996,10 +1406,11 @@ fn render_static_outline(
                                     commands,
                                     meshes,
                                     materials,
+                                    pools,
                                     start,
                                     end,
                                     theme.theme().path_line_width(),
-                                    theme.theme().path_stroke_color(),
+                                    stroke_color,
                                     OUTLINE_Z,
                                     sort_entity,
                                     GlyphElementType::OutlineSegment,
@@ -1043,10 +1454,11 @@ fn render_static_outline(
                                         commands,
                                         meshes,
                                         materials,
+                                        pools,
                                         last_pos,
                                         curve_pos,
                                         theme.theme().path_line_width(),
-                                        theme.theme().path_stroke_color(),
+                                        stroke_color,
                                         OUTLINE_Z,
                                         sort_entity,
                                         GlyphElementType::OutlineSegment,
@@ -1080,10 +1492,11 @@ fn render_static_outline(
                                         commands,
                                         meshes,
                                         materials,
+                                        pools,
                                         last_pos,
                                         curve_pos,
                                         theme.theme().path_line_width(),
-                                        theme.theme().path_stroke_color(),
+                                        stroke_color,
                                         OUTLINE_Z,
                                         sort_entity,
                                         GlyphElementType::OutlineSegment,
@@ -1111,6 +1524,7 @@ fn render_fontir_outline(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
+    pools: &mut EntityPools,
     element_entities: &mut Vec<Entity>,
     sort_entity: Entity,
     original_paths: &[kurbo::BezPath],
@@ -1118,11 +1532,20 @@ fn render_fontir_outline(
     sort_position: Vec2,
     camera_scale: &CameraResponsiveScale,
     theme: &CurrentTheme,
+    locks: &GlyphLocks,
 ) {
     warn!("🎨 render_fontir_outline: Called with {} paths, {} live positions", original_paths.len(), live_positions.len());
     let initial_entity_count = element_entities.len();
     // Process each contour with live positions
     for (contour_idx, original_path) in original_paths.iter().enumerate() {
+        if locks.is_hidden(contour_idx) {
+            continue;
+        }
+        let stroke_color = if locks.is_locked(contour_idx) {
+            theme.theme().locked_outline_color()
+        } else {
+            theme.theme().path_stroke_color()
+        };
         let element_count = original_path.elements().len();
         warn!("🎨 render_fontir_outline: Processing contour {} with {} elements", contour_idx, element_count);
         let elements: Vec<_> = original_path.elements().iter().collect();
@@ -1204,10 +1627,11 @@ fn render_fontir_outline(
                             commands,
                             meshes,
                             materials,
+                            pools,
                             start,
                             end,
                             theme.theme().path_line_width(),
-                            theme.theme().path_stroke_color(),
+                            stroke_color,
                             OUTLINE_Z,
                             sort_entity,
                             GlyphElementType::OutlineSegment,
@@ -1278,10 +1702,11 @@ fn render_fontir_outline(
                                 commands,
                                 meshes,
                                 materials,
+                                pools,
                                 last_pos,
                                 curve_pos,
                                 theme.theme().path_line_width(),
-                                theme.theme().path_stroke_color(),
+                                stroke_color,
                                 OUTLINE_Z,
                                 sort_entity,
                                 GlyphElementType::OutlineSegment,
@@ -1333,10 +1758,11 @@ fn render_fontir_outline(
                                 commands,
                                 meshes,
                                 materials,
+                                pools,
                                 last_pos,
                                 curve_pos,
                                 theme.theme().path_line_width(),
-                                theme.theme().path_stroke_color(),
+                                stroke_color,
                                 OUTLINE_Z,
                                 sort_entity,
                                 GlyphElementType::OutlineSegment,
@@ -1369,10 +1795,11 @@ fn render_fontir_outline(
                                     commands,
                                     meshes,
                                     materials,
+                                    pools,
                                     end,
                                     start,
                                     theme.theme().path_line_width(),
-                                    theme.theme().path_stroke_color(),
+                                    stroke_color,
                                     OUTLINE_Z,
                                     sort_entity,
                                     GlyphElementType::OutlineSegment,
@@ -1390,11 +1817,12 @@ fn render_fontir_outline(
     warn!("🎨 render_fontir_outline: Spawned {} outline entities", spawned_count);
 }
 
-/// Helper to spawn a line mesh entity for unified rendering
+/// Helper to draw a line mesh for unified rendering with a pooled entity
 fn spawn_line_mesh(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
+    pools: &mut EntityPools,
     start: Vec2,
     end: Vec2,
     width: f32,
@@ -1407,21 +1835,19 @@ fn spawn_line_mesh(
     let adjusted_width = camera_scale.adjusted_line_width() * width;
     let line_mesh = crate::rendering::mesh_utils::create_line_mesh(start, end, adjusted_width);
 
-    commands
-        .spawn((
-            GlyphRenderElement {
-                element_type,
-                sort_entity,
-            },
-            Mesh2d(meshes.add(line_mesh)),
-            MeshMaterial2d(materials.add(ColorMaterial::from_color(color))),
-            Transform::from_xyz((start.x + end.x) * 0.5, (start.y + end.y) * 0.5, z),
-            GlobalTransform::default(),
-            Visibility::Visible,
-            InheritedVisibility::default(),
-            ViewVisibility::default(),
-        ))
-        .id()
+    let entity = pools.get_sort_element_entity(commands, sort_entity, PooledEntityType::Outline);
+    update_outline_entity(
+        commands,
+        entity,
+        meshes.add(line_mesh),
+        materials.add(ColorMaterial::from_color(color)),
+        Transform::from_xyz((start.x + end.x) * 0.5, (start.y + end.y) * 0.5, z),
+        GlyphRenderElement {
+            element_type,
+            sort_entity,
+        },
+    );
+    entity
 }
 
 // Type aliases for complex query types
@@ -1458,6 +1884,9 @@ fn detect_sort_changes(
     selection_changes: Query<Entity, (With<SortPointEntity>, Changed<Selected>)>,
     removed_selected: RemovedComponents<Selected>,
     mut edit_events: EventReader<crate::editing::selection::nudge::EditEvent>,
+    mut glyph_edits: EventReader<GlyphEdited>,
+    mut mesh_cache: ResMut<GlyphMeshCache>,
+    theme: Res<CurrentTheme>,
 ) {
     let active_changed = !active_sort_query.is_empty();
     let inactive_changed = !inactive_sort_query.is_empty();
@@ -1467,6 +1896,12 @@ fn detect_sort_changes(
     let removed_selected_count = removed_selected.len();
     let edit_event_count = edit_events.read().count();
 
+    // Edited glyphs may have changed the outlines of cached fills
+    let glyph_edit_count = glyph_edits.read().count();
+    if glyph_edit_count > 0 {
+        mesh_cache.bump_revision();
+    }
+
     // Use EditEvent instead of Transform change detection
     // This is more precise: only fires when nudge/drag explicitly signals an edit
     // Transform changes happen too frequently (every frame during animations, etc.)
@@ -1476,7 +1911,9 @@ fn detect_sort_changes(
         || removed_inactive_count > 0
         || selection_changed
         || removed_selected_count > 0
-        || edit_event_count > 0;
+        || edit_event_count > 0
+        || glyph_edit_count > 0
+        || theme.is_changed();
 
     if needs_update {
         update_tracker.needs_update = true;
@@ -1574,7 +2011,10 @@ impl Plugin for GlyphRenderingPlugin {
             .add_systems(
                 Update,
                 (
-                    detect_sort_changes,
+                    detect_sort_changes.before(render_glyphs),
+                    prepare_filled_meshes
+                        .after(detect_sort_changes)
+                        .before(render_glyphs),
                     collect_rendering_data,
                     render_glyphs.after(collect_rendering_data),
                 )
@@ -1583,10 +2023,18 @@ impl Plugin for GlyphRenderingPlugin {
     }
 }
 
-/// Check if a glyph has components
+/// Check if a glyph is built only from components (nothing to edit directly)
 fn glyph_has_components(
-    _glyph_name: &str,
+    app_state: Option<&crate::core::state::AppState>,
+    glyph_name: &str,
 ) -> bool {
-    // Temporarily disabled during FontIR removal
-    false
+    app_state
+        .and_then(|state| state.workspace.font.editing().get_glyph(glyph_name))
+        .is_some_and(|glyph| {
+            !glyph.components.is_empty()
+                && glyph
+                    .outline
+                    .as_ref()
+                    .is_none_or(|outline| outline.contours.is_empty())
+        })
 }
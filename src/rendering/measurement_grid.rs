@@ -0,0 +1,244 @@
+//! Measurement grid
+//!
+//! A fine grid drawn over the checkerboard, with a line every
+//! `GridSettings::measurement_interval` font units and stronger lines every
+//! fifth and tenth (every 10, 50 and 100 units by default). The active
+//! sort's baseline and metrics heights are drawn across it, strongest of
+//! all. Zoomed out, the finer lines drop out before they crowd together.
+//!
+//! Cmd/Ctrl+' or the preferences show and hide the grid; while it's shown,
+//! points snap to its lines (see `GridSettings::snap_size`).
+
+use crate::core::config::BezySettings;
+use crate::core::state::AppState;
+use crate::editing::sort::ActiveSort;
+use crate::rendering::cameras::DesignCamera;
+use crate::rendering::mesh_utils::create_lines_mesh;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::ui::edit_mode_toolbar::PresentationMode;
+use crate::ui::themes::CurrentTheme;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// Lines closer together than this many screen pixels are left out
+const MIN_LINE_SPACING_PX: f32 = 4.0;
+
+/// Upper bound on the lines drawn along one axis
+const MAX_LINES: usize = 2000;
+
+/// Just above the checkerboard, behind everything else
+const GRID_Z: f32 = 0.2;
+
+/// The metrics heights are above the grid lines
+const METRICS_Z: f32 = 0.3;
+
+/// How strongly a grid line is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridLineWeight {
+    /// Every line
+    Minor,
+    /// Every fifth line
+    Medium,
+    /// Every tenth line
+    Major,
+}
+
+/// Marker for the measurement grid meshes
+#[derive(Component)]
+struct MeasurementGridMesh;
+
+/// What the grid was last drawn for
+#[derive(Debug, Clone, PartialEq)]
+struct DrawnGrid {
+    min: Vec2,
+    max: Vec2,
+    interval: f32,
+    line_width: f32,
+    metrics: Vec<f32>,
+}
+
+pub struct MeasurementGridPlugin;
+
+impl Plugin for MeasurementGridPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, render_measurement_grid);
+    }
+}
+
+/// The grid lines between `from` and `to` in font units, one every
+/// `interval`, as their position and weight. Lines closer than
+/// `MIN_LINE_SPACING_PX` at a zoom of `px_per_unit` are left out.
+pub fn measurement_grid_lines(
+    from: f32,
+    to: f32,
+    interval: f32,
+    px_per_unit: f32,
+) -> Vec<(f32, GridLineWeight)> {
+    if interval <= 0.0 || !px_per_unit.is_finite() || px_per_unit <= 0.0 {
+        return Vec::new();
+    }
+    let Some(every) = [1, 5, 10]
+        .into_iter()
+        .find(|every| interval * *every as f32 * px_per_unit >= MIN_LINE_SPACING_PX)
+    else {
+        return Vec::new();
+    };
+    let step = interval * every as f32;
+    let (low, high) = (from.min(to), from.max(to));
+    let first = (low / step).ceil() as i64;
+    let last = (high / step).floor() as i64;
+    (first..=last)
+        .take(MAX_LINES)
+        .map(|k| {
+            let line = k * every;
+            let weight = if line % 10 == 0 {
+                GridLineWeight::Major
+            } else if line % 5 == 0 {
+                GridLineWeight::Medium
+            } else {
+                GridLineWeight::Minor
+            };
+            (k as f32 * step, weight)
+        })
+        .collect()
+}
+
+/// Redraws the grid when the view, the settings or the metrics change
+#[allow(clippy::too_many_arguments)]
+fn render_measurement_grid(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut drawn: Local<Option<DrawnGrid>>,
+    settings: Res<BezySettings>,
+    theme: Res<CurrentTheme>,
+    camera_scale: Res<CameraResponsiveScale>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<DesignCamera>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    active_sort: Query<&Transform, With<ActiveSort>>,
+    app_state: Option<Res<AppState>>,
+    presentation_mode: Option<Res<PresentationMode>>,
+    existing: Query<Entity, With<MeasurementGridMesh>>,
+) {
+    let presentation_active = presentation_mode.is_some_and(|pm| pm.active);
+    if !settings.grid.show_measurement_grid || presentation_active {
+        if drawn.take().is_some() {
+            for entity in existing.iter() {
+                commands.entity(entity).despawn();
+            }
+        }
+        return;
+    }
+
+    let (Ok((camera, camera_transform)), Ok(window)) =
+        (camera_query.single(), window_query.single())
+    else {
+        return;
+    };
+    let (Ok(top_left), Ok(bottom_right)) = (
+        camera.viewport_to_world_2d(camera_transform, Vec2::ZERO),
+        camera.viewport_to_world_2d(camera_transform, window.size()),
+    ) else {
+        return;
+    };
+
+    // The baseline and metrics heights of the active sort, or of the origin
+    let baseline = active_sort
+        .single()
+        .map_or(0.0, |transform| transform.translation.y);
+    let mut metrics = vec![baseline];
+    if let Some(app_state) = app_state.as_ref() {
+        let info = &app_state.workspace.info;
+        let heights = [
+            info.descender,
+            info.x_height,
+            info.cap_height,
+            info.ascender,
+        ];
+        metrics.extend(heights.into_iter().flatten().map(|h| baseline + h as f32));
+    }
+
+    let grid = DrawnGrid {
+        min: top_left.min(bottom_right),
+        max: top_left.max(bottom_right),
+        interval: settings.grid.measurement_interval,
+        line_width: camera_scale.adjusted_line_width(),
+        metrics,
+    };
+    if drawn.as_ref() == Some(&grid) && !theme.is_changed() {
+        return;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let px_per_unit = window.width() / (grid.max.x - grid.min.x);
+    let mut lines: [Vec<(Vec2, Vec2)>; 3] = Default::default();
+    let weight_index = |weight| match weight {
+        GridLineWeight::Minor => 0,
+        GridLineWeight::Medium => 1,
+        GridLineWeight::Major => 2,
+    };
+    for (x, weight) in measurement_grid_lines(grid.min.x, grid.max.x, grid.interval, px_per_unit) {
+        let line = (Vec2::new(x, grid.min.y), Vec2::new(x, grid.max.y));
+        lines[weight_index(weight)].push(line);
+    }
+    for (y, weight) in measurement_grid_lines(grid.min.y, grid.max.y, grid.interval, px_per_unit) {
+        let line = (Vec2::new(grid.min.x, y), Vec2::new(grid.max.x, y));
+        lines[weight_index(weight)].push(line);
+    }
+    let colors = theme.theme().measurement_grid_colors();
+    for (lines, color) in lines.iter().zip(colors) {
+        if lines.is_empty() {
+            continue;
+        }
+        commands.spawn((
+            MeasurementGridMesh,
+            Mesh2d(meshes.add(create_lines_mesh(lines, grid.line_width))),
+            MeshMaterial2d(materials.add(ColorMaterial::from_color(color))),
+            Transform::from_xyz(0.0, 0.0, GRID_Z),
+        ));
+    }
+
+    let metrics_lines: Vec<(Vec2, Vec2)> = grid
+        .metrics
+        .iter()
+        .map(|&y| (Vec2::new(grid.min.x, y), Vec2::new(grid.max.x, y)))
+        .collect();
+    let metrics_color = theme.theme().measurement_grid_metrics_color();
+    commands.spawn((
+        MeasurementGridMesh,
+        Mesh2d(meshes.add(create_lines_mesh(&metrics_lines, grid.line_width * 2.0))),
+        MeshMaterial2d(materials.add(ColorMaterial::from_color(metrics_color))),
+        Transform::from_xyz(0.0, 0.0, METRICS_Z),
+    ));
+    *drawn = Some(grid);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_weighted_and_thinned_when_zoomed_out() {
+        let lines = measurement_grid_lines(-5.0, 100.0, 10.0, 1.0);
+        assert_eq!(lines.len(), 11);
+        assert_eq!(lines[0], (0.0, GridLineWeight::Major));
+        assert_eq!(lines[1], (10.0, GridLineWeight::Minor));
+        assert_eq!(lines[5], (50.0, GridLineWeight::Medium));
+        assert_eq!(lines[10], (100.0, GridLineWeight::Major));
+
+        let coarse = measurement_grid_lines(0.0, 200.0, 10.0, 0.2);
+        assert_eq!(
+            coarse,
+            vec![
+                (0.0, GridLineWeight::Major),
+                (50.0, GridLineWeight::Medium),
+                (100.0, GridLineWeight::Major),
+                (150.0, GridLineWeight::Medium),
+                (200.0, GridLineWeight::Major),
+            ]
+        );
+        assert!(measurement_grid_lines(0.0, 1000.0, 10.0, 0.01).is_empty());
+    }
+}
@@ -3,21 +3,44 @@
 //! This module provides caching for expensive mesh generation operations, particularly
 //! the tessellation of bezier curves to triangles for filled glyph rendering.
 //! By caching generated meshes per glyph, we avoid repeated tessellation overhead.
+//!
+//! Filled meshes are built in glyph space and shared by every sort showing the
+//! glyph, each sort placing it with its own transform. They are tagged with the
+//! working copy revision they were built at; once the font data changes, a
+//! cached mesh is reused only if its glyph's outline is still the same.
+//! Meshes are also tied to the [`RenderDetail`] they were tessellated for.
 
+use crate::rendering::zoom_aware_scaling::RenderDetail;
 use bevy::prelude::*;
+use kurbo::BezPath;
 use std::collections::HashMap;
 
+/// A filled glyph mesh shared by all sorts showing the glyph
+pub struct CachedFilledMesh {
+    /// Working copy revision the mesh was last known to match
+    pub revision: u64,
+    /// The outline that was tessellated
+    pub paths: Vec<BezPath>,
+    /// The detail level it was tessellated for
+    pub detail: RenderDetail,
+    pub mesh: Handle<Mesh>,
+}
+
 /// Resource for caching generated meshes to avoid expensive tessellation operations
 #[derive(Resource, Default)]
 pub struct GlyphMeshCache {
-    /// Cache for filled glyph meshes: glyph_name -> mesh handle
-    pub filled_meshes: HashMap<String, Handle<Mesh>>,
+    /// Cache for filled glyph meshes: glyph_name -> tessellated mesh
+    pub filled_meshes: HashMap<String, CachedFilledMesh>,
     /// Cache for outline meshes: glyph_name -> vec of mesh handles for segments
     pub outline_meshes: HashMap<String, Vec<Handle<Mesh>>>,
     /// Cache for metrics line meshes: glyph_name -> vec of mesh handles for metrics
     pub metrics_meshes: HashMap<String, Vec<Handle<Mesh>>>,
     /// Cache invalidation tracking: font generation counter for cache busting
     pub font_generation: u64,
+    /// Working copy revision, bumped whenever the font data changes
+    pub revision: u64,
+    /// Detail level filled meshes are tessellated for
+    pub detail: RenderDetail,
     /// Statistics for monitoring cache performance
     pub stats: MeshCacheStats,
 }
@@ -40,15 +63,29 @@ pub struct MeshCacheStats {
 }
 
 impl GlyphMeshCache {
-    /// Get a cached filled mesh for a glyph, or None if not cached
-    pub fn get_filled_mesh(&mut self, glyph_name: &str) -> Option<Handle<Mesh>> {
-        if let Some(mesh_handle) = self.filled_meshes.get(glyph_name) {
+    /// Note that the working copy changed, so cached meshes must be checked
+    /// against their glyph's outline before they are reused
+    pub fn bump_revision(&mut self) {
+        self.revision += 1;
+    }
+
+    /// Get the cached filled mesh for a glyph, or None if it isn't cached or
+    /// the glyph's outline changed since it was tessellated
+    pub fn get_filled_mesh(&mut self, glyph_name: &str, paths: &[BezPath]) -> Option<Handle<Mesh>> {
+        let revision = self.revision;
+        let detail = self.detail;
+        let cached = self.filled_meshes.get_mut(glyph_name).filter(|cached| {
+            cached.detail == detail
+                && (cached.revision == revision || cached.paths.as_slice() == paths)
+        });
+        if let Some(cached) = cached {
+            cached.revision = revision;
             self.stats.filled_hits += 1;
             debug!(
                 "Mesh cache HIT for filled glyph '{}' (hits: {})",
                 glyph_name, self.stats.filled_hits
             );
-            Some(mesh_handle.clone())
+            Some(cached.mesh.clone())
         } else {
             self.stats.filled_misses += 1;
             debug!(
@@ -59,10 +96,41 @@ impl GlyphMeshCache {
         }
     }
 
-    /// Cache a filled mesh for a glyph
-    pub fn cache_filled_mesh(&mut self, glyph_name: String, mesh_handle: Handle<Mesh>) {
+    /// Whether a glyph's filled mesh was tessellated or checked against its
+    /// outline at the current revision and detail level
+    pub fn is_filled_mesh_current(&self, glyph_name: &str) -> bool {
+        self.filled_meshes
+            .get(glyph_name)
+            .is_some_and(|cached| cached.revision == self.revision && cached.detail == self.detail)
+    }
+
+    /// Get the cached filled mesh for a glyph if it is current, without
+    /// needing the glyph's outline to check it
+    pub fn current_filled_mesh(&mut self, glyph_name: &str) -> Option<Handle<Mesh>> {
+        if !self.is_filled_mesh_current(glyph_name) {
+            return None;
+        }
+        self.stats.filled_hits += 1;
+        self.filled_meshes
+            .get(glyph_name)
+            .map(|cached| cached.mesh.clone())
+    }
+
+    /// Cache a filled mesh for a glyph at the current revision
+    pub fn cache_filled_mesh(
+        &mut self,
+        glyph_name: String,
+        paths: Vec<BezPath>,
+        mesh_handle: Handle<Mesh>,
+    ) {
         debug!("Caching filled mesh for glyph '{}'", glyph_name);
-        self.filled_meshes.insert(glyph_name, mesh_handle);
+        let cached = CachedFilledMesh {
+            revision: self.revision,
+            paths,
+            detail: self.detail,
+            mesh: mesh_handle,
+        };
+        self.filled_meshes.insert(glyph_name, cached);
     }
 
     /// Get cached outline meshes for a glyph, or None if not cached
@@ -206,3 +274,30 @@ fn log_cache_stats(cache: Res<GlyphMeshCache>) {
         cache.total_cached_count()
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filled_mesh_survives_unrelated_edits() {
+        let mut square = BezPath::new();
+        square.move_to((0.0, 0.0));
+        square.line_to((100.0, 0.0));
+        square.line_to((100.0, 100.0));
+        square.close_path();
+        let mut cache = GlyphMeshCache::default();
+        cache.cache_filled_mesh("o".to_string(), vec![square.clone()], Handle::default());
+        assert!(cache.get_filled_mesh("o", &[]).is_some());
+
+        // Another glyph was edited
+        cache.bump_revision();
+        assert!(cache.get_filled_mesh("o", &[square.clone()]).is_some());
+        assert!(cache.get_filled_mesh("o", &[]).is_some());
+
+        // This glyph was edited
+        cache.bump_revision();
+        square.line_to((0.0, 100.0));
+        assert!(cache.get_filled_mesh("o", &[square]).is_none());
+    }
+}
@@ -52,3 +52,61 @@ pub fn create_line_mesh(start: Vec2, end: Vec2, width: f32) -> Mesh {
     mesh.insert_indices(Indices::U32(indices));
     mesh
 }
+
+/// Create one mesh holding many lines of the same width, in the coordinates
+/// given rather than centered
+pub fn create_lines_mesh(lines: &[(Vec2, Vec2)], width: f32) -> Mesh {
+    let mut vertices = Vec::with_capacity(lines.len() * 4);
+    let mut indices = Vec::with_capacity(lines.len() * 6);
+    for &(start, end) in lines {
+        let direction = (end - start).normalize_or_zero();
+        let perpendicular = Vec2::new(-direction.y, direction.x) * width * 0.5;
+        let first = vertices.len() as u32;
+        for corner in [
+            start - perpendicular,
+            start + perpendicular,
+            end + perpendicular,
+            end - perpendicular,
+        ] {
+            vertices.push([corner.x, corner.y, 0.0]);
+        }
+        indices.extend([0, 1, 2, 0, 2, 3].map(|i| first + i));
+    }
+
+    let normals = vec![[0.0, 0.0, 1.0]; vertices.len()];
+    let uvs = vec![[0.0, 0.0]; vertices.len()];
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, default());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_indices(Indices::U32(indices));
+    mesh
+}
+
+/// Create one mesh filling many triangles, in the coordinates given and
+/// either winding
+pub fn create_triangles_mesh(triangles: &[[Vec2; 3]]) -> Mesh {
+    let vertices: Vec<[f32; 3]> = triangles
+        .iter()
+        .flat_map(|&[a, b, c]| {
+            // Counterclockwise, facing the camera
+            if (b - a).perp_dot(c - a) < 0.0 {
+                [a, c, b]
+            } else {
+                [a, b, c]
+            }
+        })
+        .map(|corner| [corner.x, corner.y, 0.0])
+        .collect();
+    let indices = (0..vertices.len() as u32).collect();
+    let normals = vec![[0.0, 0.0, 1.0]; vertices.len()];
+    let uvs = vec![[0.0, 0.0]; vertices.len()];
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, default());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_indices(Indices::U32(indices));
+    mesh
+}
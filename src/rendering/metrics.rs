@@ -6,8 +6,10 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::type_complexity)]
 
+use crate::editing::font_changes::{GlyphEdited, MetricsChanged};
+use crate::editing::sort::{ActiveSort, InactiveSort, Sort};
 use crate::rendering::entity_pools::{update_metrics_entity, EntityPools};
-use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::rendering::zoom_aware_scaling::{CameraResponsiveScale, RenderDetail};
 use bevy::prelude::*;
 use bevy::render::mesh::Mesh2d;
 use bevy::sprite::{ColorMaterial, MeshMaterial2d};
@@ -40,6 +42,8 @@ pub enum MetricsLineType {
 #[derive(Resource, Default)]
 pub struct MetricsLineEntities {
     pub lines: std::collections::HashMap<Entity, Vec<Entity>>, // sort_entity -> line entities
+    /// Whether sorts, the font or the view changed since the lines were drawn
+    pub needs_update: bool,
 }
 
 /// Simple font metrics for rendering
@@ -77,6 +81,7 @@ impl GlyphMetricsCache {
         let width = app_state
             .workspace
             .font
+            .editing()
             .get_glyph(glyph_name)
             .map(|g| g.advance_width as f32)
             .unwrap_or(0.0);
@@ -106,6 +111,16 @@ impl GlyphMetricsCache {
         self.font_metrics.as_ref().expect("font_metrics should be initialized")
     }
 
+    /// Forget the advance of an edited glyph, or of every glyph with `None`
+    pub fn forget_advance(&mut self, glyph_name: Option<&str>) {
+        match glyph_name {
+            Some(name) => {
+                self.advance_widths.remove(name);
+            }
+            None => self.advance_widths.clear(),
+        }
+    }
+
     /// Clear all cached data (useful for debugging)
     pub fn clear(&mut self) {
         self.advance_widths.clear();
@@ -230,6 +245,48 @@ fn get_or_update_metrics_line(
     entity
 }
 
+/// Mark the metrics lines out of date when sorts change, when the font's
+/// advances or metrics change, or when the view does
+fn detect_metrics_changes(
+    mut metrics_entities: ResMut<MetricsLineEntities>,
+    mut metrics_cache: ResMut<GlyphMetricsCache>,
+    mut glyph_edits: EventReader<GlyphEdited>,
+    mut metrics_changes: EventReader<MetricsChanged>,
+    changed_sorts: Query<
+        (),
+        (
+            With<Sort>,
+            Or<(
+                Changed<Transform>,
+                Changed<Sort>,
+                Added<ActiveSort>,
+                Added<InactiveSort>,
+            )>,
+        ),
+    >,
+    mut removed_active: RemovedComponents<ActiveSort>,
+    mut removed_inactive: RemovedComponents<InactiveSort>,
+    camera_scale: Res<CameraResponsiveScale>,
+    theme: Res<crate::ui::themes::CurrentTheme>,
+) {
+    let mut font_changed = false;
+    for edit in glyph_edits.read() {
+        metrics_cache.forget_advance(edit.glyph_name.as_deref());
+        font_changed = true;
+    }
+    if metrics_changes.read().count() > 0 {
+        metrics_cache.font_metrics = None;
+        font_changed = true;
+    }
+    let sorts_changed = !changed_sorts.is_empty()
+        || removed_active.read().count() > 0
+        || removed_inactive.read().count() > 0;
+
+    if font_changed || sorts_changed || camera_scale.is_changed() || theme.is_changed() {
+        metrics_entities.needs_update = true;
+    }
+}
+
 /// System to render mesh-based metrics lines for all active sorts (internal)
 pub(crate) fn render_mesh_metrics_lines(
     mut commands: Commands,
@@ -269,11 +326,14 @@ pub(crate) fn render_mesh_metrics_lines(
     app_state: Option<Res<crate::core::state::AppState>>,
     camera_scale: Res<CameraResponsiveScale>,
     presentation_mode: Option<Res<crate::ui::edit_mode_toolbar::PresentationMode>>,
+    render_detail: Res<RenderDetail>,
     theme: Res<crate::ui::themes::CurrentTheme>,
 ) {
-    // Check presentation mode state
-    let presentation_active = presentation_mode.as_ref().is_some_and(|pm| pm.active);
-    let presentation_changed = presentation_mode.as_ref().is_some_and(|pm| pm.is_changed());
+    // Check presentation mode state; zoomed far out, metrics are hidden the same way
+    let presentation_active =
+        presentation_mode.as_ref().is_some_and(|pm| pm.active) || render_detail.is_simplified();
+    let presentation_changed = presentation_mode.as_ref().is_some_and(|pm| pm.is_changed())
+        || render_detail.is_changed();
 
     // Hide metrics in presentation mode OR if presentation mode just activated
     if presentation_active || presentation_changed {
@@ -306,6 +366,12 @@ pub(crate) fn render_mesh_metrics_lines(
         }
     }
 
+    // Nothing to redraw until sorts, the font or the view change
+    if !metrics_entities.needs_update && !presentation_changed {
+        return;
+    }
+    metrics_entities.needs_update = false;
+
     // CHANGE DETECTION: Early return if no sorts have changed and presentation mode hasn't changed
     let sort_count = sort_query.iter().count();
     let active_buffer_count = active_buffer_sort_query.iter().count();
@@ -1173,7 +1239,8 @@ pub fn manage_preview_metrics(
     preview_entities.entities.push(left_entity);
 
     // Add glyph outline preview
-    if let Some(glyph) = app_state_res.workspace.font.get_glyph(&preview_state.glyph_name) {
+    let font = &app_state_res.workspace.font;
+    if let Some(glyph) = font.editing().get_glyph(&preview_state.glyph_name) {
         if let Some(outline) = &glyph.outline {
             let glyph_paths = outline.to_bezpaths();
         for path in &glyph_paths {
@@ -1500,6 +1567,9 @@ impl Plugin for MetricsRenderingPlugin {
             .add_systems(
                 Update,
                 (
+                    detect_metrics_changes
+                        .in_set(crate::editing::FontEditorSets::Rendering)
+                        .before(render_mesh_metrics_lines),
                     render_mesh_metrics_lines.in_set(crate::editing::FontEditorSets::Rendering),
                     manage_preview_metrics
                         .in_set(crate::editing::FontEditorSets::Rendering)
@@ -8,32 +8,47 @@
 //! - Selection visualization (marquee, selected points, handles)
 
 
+pub mod blue_zones;
 pub mod cameras;
 pub mod checkerboard;
+pub mod curve_flavor;
+pub mod design_frames;
 pub mod entity_pools;
 pub mod glyph_renderer;
+pub mod measurement_grid;
 pub mod mesh_cache;
 pub mod mesh_utils;
 pub mod metrics;
+pub mod open_corners;
 pub mod outline_elements;
 pub mod points;
 pub mod post_editing_systems;
 pub mod selection;
+pub mod smart_guides;
 pub mod sort_renderer;
 pub mod sort_visuals;
+pub mod start_points;
+pub mod template_glyphs;
 pub mod text_cursor;
 pub mod zoom_aware_scaling;
 
 // Re-export commonly used items
+pub use blue_zones::BlueZonesPlugin;
 pub use checkerboard::{CheckerboardEnabled, CheckerboardPlugin};
+pub use curve_flavor::CurveFlavorRenderingPlugin;
+pub use design_frames::DesignFramesRenderingPlugin;
 pub use entity_pools::EntityPoolingPlugin;
 pub use glyph_renderer::GlyphRenderingPlugin;
+pub use measurement_grid::MeasurementGridPlugin;
 pub use mesh_cache::MeshCachingPlugin;
 pub use metrics::MetricsRenderingPlugin;
+pub use open_corners::OpenCornersRenderingPlugin;
 pub use outline_elements::OutlineElementsPlugin;
 pub use points::PointRenderingPlugin;
 pub use post_editing_systems::{PostEditingRenderingPlugin, PostEditingRenderingSet};
 pub use selection::render_selection_marquee;
 pub use sort_visuals::SortHandleRenderingPlugin;
+pub use start_points::StartPointsRenderingPlugin;
+pub use template_glyphs::TemplateGlyphsPlugin;
 pub use text_cursor::{CursorRenderingState, TextEditorCursor};
 pub use zoom_aware_scaling::{CameraResponsivePlugin, CameraResponsiveScale};
@@ -0,0 +1,82 @@
+//! Open corner loops
+//!
+//! Fills the small loop each open corner of the active sort makes (see
+//! `font_source::open_corners`) with a faint tint, so the overlap reads as
+//! construction rather than as part of the outline.
+
+use crate::core::state::AppState;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::font_source::open_corners::open_corners;
+use crate::rendering::mesh_utils::create_triangles_mesh;
+use crate::ui::edit_mode_toolbar::PresentationMode;
+use crate::ui::themes::CurrentTheme;
+use bevy::prelude::*;
+
+/// Behind the handles and outlines of the active sort
+const LOOP_Z: f32 = 6.0;
+
+/// Marker for the loop mesh
+#[derive(Component)]
+struct OpenCornerMesh;
+
+pub struct OpenCornersRenderingPlugin;
+
+impl Plugin for OpenCornersRenderingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, render_open_corners);
+    }
+}
+
+/// Redraws the loops when the active sort's open corners change
+#[allow(clippy::too_many_arguments)]
+fn render_open_corners(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut drawn: Local<Vec<[Vec2; 3]>>,
+    active_sorts: Query<(&Transform, &Sort), With<ActiveSort>>,
+    app_state: Option<Res<AppState>>,
+    presentation_mode: Option<Res<PresentationMode>>,
+    theme: Res<CurrentTheme>,
+    existing: Query<Entity, With<OpenCornerMesh>>,
+) {
+    let presentation_active = presentation_mode.is_some_and(|pm| pm.active);
+    let mut triangles = Vec::new();
+    if let Some(state) = app_state.filter(|_| !presentation_active) {
+        for (transform, sort) in active_sorts.iter() {
+            let Some(outline) = state
+                .workspace
+                .font
+                .editing()
+                .get_glyph(&sort.glyph_name)
+                .and_then(|glyph| glyph.outline.as_ref())
+            else {
+                continue;
+            };
+            let origin = transform.translation.truncate();
+            for contour in &outline.contours {
+                triangles.extend(open_corners(contour).iter().map(|corner| {
+                    corner
+                        .triangle(contour)
+                        .map(|point| origin + Vec2::new(point.x as f32, point.y as f32))
+                }));
+            }
+        }
+    }
+    if *drawn == triangles && !theme.is_changed() {
+        return;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+    if !triangles.is_empty() {
+        let color = theme.theme().open_corner_color();
+        commands.spawn((
+            OpenCornerMesh,
+            Mesh2d(meshes.add(create_triangles_mesh(&triangles))),
+            MeshMaterial2d(materials.add(ColorMaterial::from_color(color))),
+            Transform::from_xyz(0.0, 0.0, LOOP_Z),
+        ));
+    }
+    *drawn = triangles;
+}
@@ -0,0 +1,57 @@
+// Instanced point and handle shapes, see points.rs
+
+#import bevy_sprite::mesh2d_view_bindings::view
+
+struct Instance {
+    // Center, depth and rotation
+    @location(0) transform: vec4<f32>,
+    // Half width, half height and shape (0 square, 1 circle)
+    @location(1) extents: vec4<f32>,
+    @location(2) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // Position within the shape, -1 to 1 on both axes
+    @location(0) local: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) @interpolate(flat) shape: f32,
+};
+
+@vertex
+fn vertex(@builtin(vertex_index) vertex_index: u32, instance: Instance) -> VertexOutput {
+    // Two triangles covering the shape's bounds
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+    let offset = corner * instance.extents.xy;
+    let c = cos(instance.transform.w);
+    let s = sin(instance.transform.w);
+    let rotated = vec2<f32>(offset.x * c - offset.y * s, offset.x * s + offset.y * c);
+    let world = vec4<f32>(instance.transform.xy + rotated, instance.transform.z, 1.0);
+
+    var out: VertexOutput;
+    out.clip_position = view.clip_from_world * world;
+    out.local = corner;
+    out.color = instance.color;
+    out.shape = instance.extents.z;
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    var alpha = in.color.a;
+    if in.shape > 0.5 {
+        // Circles fade out over about a pixel at their edge
+        let distance = length(in.local);
+        let edge = fwidth(distance);
+        alpha *= 1.0 - smoothstep(1.0 - edge, 1.0, distance);
+    }
+    return vec4<f32>(in.color.rgb, alpha);
+}
@@ -1,316 +1,438 @@
-//! Point rendering system
+//! Instanced point and handle rendering
 //!
-//! This module handles the mesh-based rendering of points (both on-curve and off-curve)
-//! replacing the previous gizmo-based point rendering.
+//! Points and handles of the sorts being edited are drawn as GPU instances
+//! instead of an entity per shape. Each layer of a point's marker, each
+//! crosshair bar and each handle line is one [`PointInstance`]: a rotated
+//! square or circle with its own position, size and color. The glyph renderer
+//! fills [`PointInstances`] per sort; the render world uploads them into one
+//! vertex buffer and draws every instance at the same depth with a single
+//! draw call, so dense glyphs and large selections cost a handful of draws.
 
-#![allow(clippy::too_many_arguments)]
-
-use crate::editing::selection::components::{PointType, Selected};
-use crate::editing::sort::manager::SortPointEntity;
-use crate::editing::sort::ActiveSort;
-use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
-use crate::ui::theme::*;
-use crate::ui::themes::CurrentTheme;
+use bevy::color::ColorToComponents;
+use bevy::core_pipeline::core_2d::{Transparent2d, CORE_2D_DEPTH_FORMAT};
+use bevy::ecs::system::lifetimeless::SRes;
+use bevy::ecs::system::SystemParamItem;
+use bevy::image::BevyDefault;
+use bevy::math::FloatOrd;
 use bevy::prelude::*;
-use bevy::render::mesh::Mesh2d;
-use bevy::render::view::Visibility;
-use bevy::sprite::{ColorMaterial, MeshMaterial2d};
-
-/// Component to mark entities as point visual meshes
-#[derive(Component)]
-pub struct PointMesh {
-    pub point_entity: Entity,
-    pub is_outer: bool, // true for outer shape, false for inner dot
+use bevy::render::extract_resource::{ExtractResource, ExtractResourcePlugin};
+use bevy::render::render_phase::{
+    AddRenderCommand, DrawFunctions, PhaseItemExtraIndex, RenderCommand, RenderCommandResult,
+    SetItemPipeline, TrackedRenderPass, ViewSortedRenderPhases,
+};
+use bevy::render::render_resource::{
+    BindGroupLayout, BlendState, BufferUsages, ColorTargetState, ColorWrites, CompareFunction,
+    DepthBiasState, DepthStencilState, FragmentState, MultisampleState, PipelineCache,
+    PrimitiveState, RawBufferVec, RenderPipelineDescriptor, SpecializedRenderPipeline,
+    SpecializedRenderPipelines, StencilState, TextureFormat, VertexAttribute, VertexBufferLayout,
+    VertexFormat, VertexState, VertexStepMode,
+};
+use bevy::render::renderer::{RenderDevice, RenderQueue};
+use bevy::render::sync_world::MainEntity;
+use bevy::render::view::{ExtractedView, ViewTarget};
+use bevy::render::{Render, RenderApp, RenderSet};
+use bevy::sprite::{Mesh2dPipeline, SetMesh2dViewBindGroup};
+use bytemuck::{Pod, Zeroable};
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+const POINT_INSTANCE_SHADER: &str = include_str!("point_instances.wgsl");
+
+/// Bytes per instance: three `vec4`s
+const INSTANCE_STRIDE: u64 = 48;
+
+/// The outline of an instance
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InstanceShape {
+    Square,
+    Circle,
 }
 
-/// System to render points using meshes instead of gizmos
-#[allow(clippy::type_complexity)]
-pub fn render_points_with_meshes(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    _point_entities: Query<
-        (Entity, &GlobalTransform, &PointType),
-        (With<SortPointEntity>, Without<Selected>),
-    >,
-    all_point_entities: Query<
-        (Entity, &GlobalTransform, &PointType, Option<&Selected>),
-        With<SortPointEntity>,
-    >,
-    active_sorts: Query<Entity, With<ActiveSort>>,
-    existing_point_meshes: Query<Entity, With<PointMesh>>,
-    theme: Res<CurrentTheme>,
-    camera_scale: Res<CameraResponsiveScale>,
-    current_tool: Res<crate::ui::edit_mode_toolbar::CurrentTool>,
-) {
-    let _all_point_count = all_point_entities.iter().count();
-    let _existing_mesh_count = existing_point_meshes.iter().count();
-    let active_sort_count = active_sorts.iter().count();
+/// One shape drawn by the instanced renderer
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct PointInstance {
+    /// Center in world space, depth, and rotation in radians
+    pub transform: Vec4,
+    /// Half width, half height, and the shape (0 square, 1 circle)
+    pub extents: Vec4,
+    /// Linear RGBA color
+    pub color: Vec4,
+}
 
-    info!("🎨 [render_points_with_meshes] CALLED - active_sorts={}, all_points={}", active_sort_count, _all_point_count);
+impl PointInstance {
+    /// A square or circle of the given full size, centered on `center`
+    pub fn new(shape: InstanceShape, center: Vec2, size: Vec2, z: f32, color: Color) -> Self {
+        let shape = match shape {
+            InstanceShape::Square => 0.0,
+            InstanceShape::Circle => 1.0,
+        };
+        Self {
+            transform: center.extend(z).extend(0.0),
+            extents: (size * 0.5).extend(shape).extend(0.0),
+            color: color.to_linear().to_vec4(),
+        }
+    }
 
-    // Hide points in pan/preview mode (standard font editor behavior)
-    if current_tool.get_current() == Some("pan") {
-        info!("🎨 [render_points_with_meshes] Pan mode active - hiding all points");
+    /// A straight line of the given width
+    pub fn line(start: Vec2, end: Vec2, width: f32, z: f32, color: Color) -> Self {
+        let delta = end - start;
+        let size = Vec2::new(delta.length(), width);
+        let mut instance = Self::new(InstanceShape::Square, (start + end) * 0.5, size, z, color);
+        instance.transform.w = delta.y.atan2(delta.x);
+        instance
+    }
 
-        // Clean up existing point meshes in pan mode
-        for entity in existing_point_meshes.iter() {
-            if let Ok(mut entity_commands) = commands.get_entity(entity) {
-                entity_commands.despawn();
-            }
+    /// Depth of the instance, which decides the draw call it goes in
+    pub fn z(&self) -> f32 {
+        self.transform.z
+    }
+}
+
+/// Point and handle instances of each sort being edited
+///
+/// Sorts are kept in entity order, so instances at the same depth draw in
+/// the same order every frame instead of flickering as they overlap.
+#[derive(Resource, Clone, Default, ExtractResource)]
+pub struct PointInstances {
+    by_sort: BTreeMap<Entity, Vec<PointInstance>>,
+}
+
+impl PointInstances {
+    /// Replace the instances drawn for a sort
+    pub fn set_sort(&mut self, sort_entity: Entity, instances: Vec<PointInstance>) {
+        if instances.is_empty() {
+            self.by_sort.remove(&sort_entity);
+        } else {
+            self.by_sort.insert(sort_entity, instances);
         }
-        return;
     }
 
-    // Also hide points if no active sort (no glyph being edited)
-    if active_sort_count == 0 {
-        info!("🎨 [render_points_with_meshes] No active sorts - hiding points");
+    /// Stop drawing a sort's points and handles
+    pub fn remove_sort(&mut self, sort_entity: Entity) {
+        self.by_sort.remove(&sort_entity);
+    }
+
+    /// Number of instances across all sorts
+    pub fn len(&self) -> usize {
+        self.by_sort.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_sort.is_empty()
+    }
+
+    /// All instances ordered by depth, then by sort and by their order in
+    /// the sort, with the range of each depth in turn
+    pub fn batches(&self) -> (Vec<PointInstance>, Vec<(f32, Range<u32>)>) {
+        let mut instances: Vec<PointInstance> = self.by_sort.values().flatten().copied().collect();
+        // A stable sort keeps the sort and instance order within a depth
+        instances.sort_by(|a, b| a.z().total_cmp(&b.z()));
 
-        // Clean up existing point meshes when no active sorts
-        for entity in existing_point_meshes.iter() {
-            if let Ok(mut entity_commands) = commands.get_entity(entity) {
-                entity_commands.despawn();
+        let mut batches: Vec<(f32, Range<u32>)> = Vec::new();
+        for (index, instance) in instances.iter().enumerate() {
+            let index = index as u32;
+            match batches.last_mut() {
+                Some((z, range)) if *z == instance.z() => range.end = index + 1,
+                _ => batches.push((instance.z(), index..index + 1)),
             }
         }
-        return;
+        (instances, batches)
     }
+}
 
-    // Clear existing point meshes
-    for entity in existing_point_meshes.iter() {
-        if let Ok(mut entity_commands) = commands.get_entity(entity) {
-            entity_commands.despawn();
-        }
+/// Plugin for instanced point and handle rendering
+pub struct PointRenderingPlugin;
+
+impl Plugin for PointRenderingPlugin {
+    fn build(&self, app: &mut App) {
+        let shader = app
+            .world_mut()
+            .resource_mut::<Assets<Shader>>()
+            .add(Shader::from_wgsl(
+                POINT_INSTANCE_SHADER,
+                "rendering/point_instances.wgsl",
+            ));
+        app.init_resource::<PointInstances>()
+            .insert_resource(PointInstanceShader(shader))
+            .add_plugins(ExtractResourcePlugin::<PointInstances>::default());
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .add_render_command::<Transparent2d, DrawPointInstances>()
+            .init_resource::<SpecializedRenderPipelines<PointInstancePipeline>>()
+            .init_resource::<PointInstanceBuffer>()
+            .add_systems(
+                Render,
+                (prepare_point_instances, queue_point_instances)
+                    .chain()
+                    .in_set(RenderSet::Queue),
+            );
     }
 
-    // Count selected vs unselected for debugging
-    let mut selected_count = 0;
-    let mut unselected_count = 0;
+    fn finish(&self, app: &mut App) {
+        let shader = app.world().resource::<PointInstanceShader>().clone();
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .insert_resource(shader)
+            .init_resource::<PointInstancePipeline>();
+    }
+}
 
-    // Render all points using meshes
-    for (point_entity, transform, point_type, selected) in all_point_entities.iter() {
-        let position = transform.translation().truncate();
+/// The instance shader, shared with the render world
+#[derive(Resource, Clone)]
+struct PointInstanceShader(Handle<Shader>);
 
-        if selected.is_some() {
-            selected_count += 1;
-        } else {
-            unselected_count += 1;
+/// GPU copy of the instances and the range drawn at each depth
+#[derive(Resource)]
+struct PointInstanceBuffer {
+    instances: RawBufferVec<PointInstance>,
+    batches: Vec<(f32, Range<u32>)>,
+}
+
+impl Default for PointInstanceBuffer {
+    fn default() -> Self {
+        Self {
+            instances: RawBufferVec::new(BufferUsages::VERTEX),
+            batches: Vec::new(),
         }
+    }
+}
 
-        // Determine colors for two-layer system
-        // Swap primary and secondary to make secondary the outline/center (darker) and primary the middle (lighter)
-        let (outline_color, middle_color) = if selected.is_some() {
-            (
-                theme.theme().selected_secondary_color(), // darker color for outline/center
-                theme.theme().selected_primary_color(),   // lighter color for middle ring
-            )
-        } else if point_type.is_on_curve {
-            (
-                theme.theme().on_curve_secondary_color(), // darker color for outline/center
-                theme.theme().on_curve_primary_color(),   // lighter color for middle ring
-            )
-        } else {
-            (
-                theme.theme().off_curve_secondary_color(), // darker color for outline/center
-                theme.theme().off_curve_primary_color(),   // lighter color for middle ring
-            )
-        };
+/// Upload the instances when the glyph renderer changed them
+fn prepare_point_instances(
+    instances: Option<Res<PointInstances>>,
+    mut buffer: ResMut<PointInstanceBuffer>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    let Some(instances) = instances else {
+        return;
+    };
+    if !instances.is_changed() {
+        return;
+    }
 
-        // Create the three-layer point shape
-        if point_type.is_on_curve && theme.theme().use_square_for_on_curve() {
-            // On-curve points: square with three layers
-            let base_size = theme.theme().on_curve_point_radius()
-                * theme.theme().on_curve_square_adjustment()
-                * 2.0;
-
-            // Layer 1: Base shape (full width) - outline color (darker)
-            commands.spawn((
-                PointMesh {
-                    point_entity,
-                    is_outer: true,
-                },
-                Sprite {
-                    color: outline_color,
-                    custom_size: Some(Vec2::splat(base_size)),
-                    ..default()
-                },
-                Transform::from_translation(position.extend(10.0)), // Above outlines
-                GlobalTransform::default(),
-                Visibility::Visible,
-                InheritedVisibility::default(),
-                ViewVisibility::default(),
-            ));
+    let (sorted, batches) = instances.batches();
+    buffer.instances.clear();
+    for instance in sorted {
+        buffer.instances.push(instance);
+    }
+    buffer.instances.write_buffer(&render_device, &render_queue);
+    buffer.batches = batches;
+}
 
-            // Layer 2: Slightly smaller shape - middle color (lighter)
-            let secondary_size = base_size * 0.7;
-            commands.spawn((
-                PointMesh {
-                    point_entity,
-                    is_outer: false,
-                },
-                Sprite {
-                    color: middle_color,
-                    custom_size: Some(Vec2::splat(secondary_size)),
-                    ..default()
-                },
-                Transform::from_translation(position.extend(11.0)), // Above base
-                GlobalTransform::default(),
-                Visibility::Visible,
-                InheritedVisibility::default(),
-                ViewVisibility::default(),
-            ));
+/// Add a draw per depth to each 2D view
+fn queue_point_instances(
+    draw_functions: Res<DrawFunctions<Transparent2d>>,
+    pipeline: Res<PointInstancePipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<PointInstancePipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    buffer: Res<PointInstanceBuffer>,
+    mut phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
+    views: Query<(&ExtractedView, &Msaa)>,
+) {
+    if buffer.batches.is_empty() {
+        return;
+    }
+    let draw_function = draw_functions.read().id::<DrawPointInstances>();
 
-            // Layer 3: Small center shape - outline color (darker, only for non-selected points)
-            if selected.is_none() {
-                let center_size = base_size * theme.theme().on_curve_inner_circle_ratio();
-                commands.spawn((
-                    PointMesh {
-                        point_entity,
-                        is_outer: false,
-                    },
-                    Sprite {
-                        color: outline_color,
-                        custom_size: Some(Vec2::splat(center_size)),
-                        ..default()
-                    },
-                    Transform::from_translation(position.extend(12.0)), // Above secondary
-                    GlobalTransform::default(),
-                    Visibility::Visible,
-                    InheritedVisibility::default(),
-                    ViewVisibility::default(),
-                ));
-            }
-        } else {
-            // Off-curve points and circular on-curve points: circle with three layers
-            let base_radius = if point_type.is_on_curve {
-                theme.theme().on_curve_point_radius()
-            } else {
-                theme.theme().off_curve_point_radius()
-            };
-
-            // Layer 1: Base circle (full size) - outline color (darker)
-            commands.spawn((
-                PointMesh {
-                    point_entity,
-                    is_outer: true,
-                },
-                Mesh2d(meshes.add(Circle::new(base_radius))),
-                MeshMaterial2d(materials.add(ColorMaterial::from_color(outline_color))),
-                Transform::from_translation(position.extend(10.0)), // Above outlines
-                GlobalTransform::default(),
-                Visibility::Visible,
-                InheritedVisibility::default(),
-                ViewVisibility::default(),
-            ));
+    for (view, msaa) in &views {
+        let Some(phase) = phases.get_mut(&view.retained_view_entity) else {
+            continue;
+        };
+        let key = PointInstancePipelineKey {
+            msaa_samples: msaa.samples(),
+            hdr: view.hdr,
+        };
+        let pipeline_id = pipelines.specialize(&pipeline_cache, &pipeline, key);
 
-            // Layer 2: Slightly smaller circle - middle color (lighter)
-            let secondary_radius = base_radius * 0.7;
-            commands.spawn((
-                PointMesh {
-                    point_entity,
-                    is_outer: false,
-                },
-                Mesh2d(meshes.add(Circle::new(secondary_radius))),
-                MeshMaterial2d(materials.add(ColorMaterial::from_color(middle_color))),
-                Transform::from_translation(position.extend(11.0)), // Above base
-                GlobalTransform::default(),
-                Visibility::Visible,
-                InheritedVisibility::default(),
-                ViewVisibility::default(),
-            ));
+        // The batches aren't tied to an entity; the draw finds its instances
+        // by the depth it sorts at
+        for (z, _) in &buffer.batches {
+            phase.add(Transparent2d {
+                sort_key: FloatOrd(*z),
+                entity: (Entity::PLACEHOLDER, MainEntity::from(Entity::PLACEHOLDER)),
+                pipeline: pipeline_id,
+                draw_function,
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::None,
+                extracted_index: usize::MAX,
+                indexed: false,
+            });
+        }
+    }
+}
 
-            // Layer 3: Small center circle - outline color (darker, only for non-selected points)
-            if selected.is_none() {
-                let center_radius = base_radius
-                    * if point_type.is_on_curve {
-                        theme.theme().on_curve_inner_circle_ratio()
-                    } else {
-                        theme.theme().off_curve_inner_circle_ratio()
-                    };
-                commands.spawn((
-                    PointMesh {
-                        point_entity,
-                        is_outer: false,
-                    },
-                    Mesh2d(meshes.add(Circle::new(center_radius))),
-                    MeshMaterial2d(materials.add(ColorMaterial::from_color(outline_color))),
-                    Transform::from_translation(position.extend(12.0)), // Above secondary
-                    GlobalTransform::default(),
-                    Visibility::Visible,
-                    InheritedVisibility::default(),
-                    ViewVisibility::default(),
-                ));
-            }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct PointInstancePipelineKey {
+    msaa_samples: u32,
+    hdr: bool,
+}
+
+#[derive(Resource)]
+struct PointInstancePipeline {
+    view_layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for PointInstancePipeline {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            view_layout: world.resource::<Mesh2dPipeline>().view_layout.clone(),
+            shader: world.resource::<PointInstanceShader>().0.clone(),
         }
+    }
+}
 
-        // Add crosshairs for selected points using outline color
-        if selected.is_some() {
-            let line_size = if point_type.is_on_curve {
-                theme.theme().on_curve_point_radius()
-            } else {
-                theme.theme().off_curve_point_radius()
-            };
-
-            // Use camera-responsive line width (1.0 base, same as outlines and handles)
-            let line_width = camera_scale.adjusted_line_width();
-
-            // Make crosshair lines slightly shorter to fit within point bounds
-            let crosshair_length = line_size * 1.6;
-
-            // Horizontal line - outline color (darker)
-            commands.spawn((
-                PointMesh {
-                    point_entity,
-                    is_outer: false,
-                },
-                Sprite {
-                    color: outline_color,
-                    custom_size: Some(Vec2::new(crosshair_length, line_width)),
-                    ..default()
-                },
-                Transform::from_translation(position.extend(13.0)), // Above everything
-                GlobalTransform::default(),
-                Visibility::Visible,
-                InheritedVisibility::default(),
-                ViewVisibility::default(),
-            ));
+impl SpecializedRenderPipeline for PointInstancePipeline {
+    type Key = PointInstancePipelineKey;
 
-            // Vertical line - outline color (darker)
-            commands.spawn((
-                PointMesh {
-                    point_entity,
-                    is_outer: false,
-                },
-                Sprite {
-                    color: outline_color,
-                    custom_size: Some(Vec2::new(line_width, crosshair_length)),
-                    ..default()
-                },
-                Transform::from_translation(position.extend(13.0)), // Above everything
-                GlobalTransform::default(),
-                Visibility::Visible,
-                InheritedVisibility::default(),
-                ViewVisibility::default(),
-            ));
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let attribute = |location: u32| VertexAttribute {
+            format: VertexFormat::Float32x4,
+            offset: u64::from(location) * 16,
+            shader_location: location,
+        };
+        let format = if key.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("point_instance_pipeline".into()),
+            layout: vec![self.view_layout.clone()],
+            push_constant_ranges: Vec::new(),
+            vertex: VertexState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "vertex".into(),
+                buffers: vec![VertexBufferLayout {
+                    array_stride: INSTANCE_STRIDE,
+                    step_mode: VertexStepMode::Instance,
+                    attributes: vec![attribute(0), attribute(1), attribute(2)],
+                }],
+            },
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            // Same depth setup as sprites and 2D meshes
+            depth_stencil: Some(DepthStencilState {
+                format: CORE_2D_DEPTH_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: CompareFunction::GreaterEqual,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            }),
+            multisample: MultisampleState {
+                count: key.msaa_samples,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            zero_initialize_workgroup_memory: false,
         }
     }
+}
+
+type DrawPointInstances = (
+    SetItemPipeline,
+    SetMesh2dViewBindGroup<0>,
+    DrawPointInstanceBatch,
+);
+
+/// Draws the instances at the item's depth as quads
+struct DrawPointInstanceBatch;
+
+impl RenderCommand<Transparent2d> for DrawPointInstanceBatch {
+    type Param = SRes<PointInstanceBuffer>;
+    type ViewQuery = ();
+    type ItemQuery = ();
+
+    fn render<'w>(
+        item: &Transparent2d,
+        _view: (),
+        _entity: Option<()>,
+        buffer: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let buffer = buffer.into_inner();
+        let Some(instances) = buffer.instances.buffer() else {
+            return RenderCommandResult::Skip;
+        };
+        let Some((_, range)) = buffer
+            .batches
+            .iter()
+            .find(|(z, _)| FloatOrd(*z) == item.sort_key)
+        else {
+            return RenderCommandResult::Skip;
+        };
 
-    // Log the counts
-    if selected_count > 0 || unselected_count > 0 {
-        info!("🎨 [render_points_with_meshes] Rendered {} selected points, {} unselected points", selected_count, unselected_count);
+        pass.set_vertex_buffer(0, instances.slice(..));
+        pass.draw(0..6, range.clone());
+        RenderCommandResult::Success
     }
 }
 
-/// Plugin for mesh-based point rendering
-pub struct PointRenderingPlugin;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl Plugin for PointRenderingPlugin {
-    fn build(&self, app: &mut App) {
-        // CRITICAL: Must use PostEditingRenderingSet, not individual .after() dependencies!
-        // This ensures points render AFTER point entities are spawned.
-        // Using .after() alone caused race conditions and 1-2 second rendering lag.
-        app.add_systems(
-            Update,
-            render_points_with_meshes.in_set(crate::rendering::PostEditingRenderingSet),
-        );
+    #[test]
+    fn test_batches_group_instances_by_depth() {
+        let color = Color::WHITE;
+        let point = |z| PointInstance::new(InstanceShape::Circle, Vec2::ZERO, Vec2::ONE, z, color);
+        let mut instances = PointInstances::default();
+        let sort_a = Entity::from_raw(1);
+        let sort_b = Entity::from_raw(2);
+        instances.set_sort(sort_a, vec![point(11.0), point(10.0)]);
+        instances.set_sort(sort_b, vec![point(10.0), point(7.0)]);
+
+        let (sorted, batches) = instances.batches();
+        assert_eq!(sorted.len(), 4);
+        assert_eq!(batches, vec![(7.0, 0..1), (10.0, 1..3), (11.0, 3..4)]);
+
+        instances.set_sort(sort_b, Vec::new());
+        assert_eq!(instances.len(), 2);
+    }
+
+    #[test]
+    fn test_batches_order_equal_depths_by_sort() {
+        let point = |x| {
+            PointInstance::new(
+                InstanceShape::Circle,
+                Vec2::new(x, 0.0),
+                Vec2::ONE,
+                10.0,
+                Color::WHITE,
+            )
+        };
+        let mut instances = PointInstances::default();
+        instances.set_sort(Entity::from_raw(2), vec![point(3.0), point(4.0)]);
+        instances.set_sort(Entity::from_raw(1), vec![point(1.0), point(2.0)]);
+
+        let (sorted, _) = instances.batches();
+        let xs: Vec<f32> = sorted.iter().map(|instance| instance.transform.x).collect();
+        assert_eq!(xs, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_line_instance_spans_its_ends() {
+        let line = PointInstance::line(Vec2::ZERO, Vec2::new(0.0, 10.0), 2.0, 7.0, Color::BLACK);
+        assert_eq!(line.transform.truncate(), Vec3::new(0.0, 5.0, 7.0));
+        assert_eq!(line.extents.truncate().truncate(), Vec2::new(5.0, 1.0));
+        assert!((line.transform.w - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
     }
 }
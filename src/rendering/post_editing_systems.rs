@@ -21,8 +21,7 @@ use bevy::prelude::*;
 /// Systems that MUST be in this set:
 /// - `detect_sort_changes` (change detection)
 /// - `collect_rendering_data` (data preparation)
-/// - `render_glyphs` (outline rendering)
-/// - `render_points_with_meshes` (point rendering)
+/// - `render_glyphs` (outline rendering, point and handle instances)
 /// - `update_handle_lines` (handle rendering)
 /// - Any future rendering systems that depend on point/sort data
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
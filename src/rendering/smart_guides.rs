@@ -0,0 +1,157 @@
+//! Smart guides between sorts
+//!
+//! While sorts are dragged by their handles, their origins and advance edges
+//! snap to those of the sorts around them, and their baselines to the other
+//! baselines, once within a few screen pixels. Each edge or baseline lined
+//! up is drawn as a guide across the sorts involved until the drag ends.
+//! Holding Alt moves the sorts freely.
+
+use crate::rendering::mesh_utils::create_line_mesh;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::ui::themes::CurrentTheme;
+use bevy::prelude::*;
+
+/// How close, in screen pixels, an edge or baseline has to come to snap
+pub const SNAP_DISTANCE_PX: f32 = 8.0;
+
+/// Guides draw just above the sort handles
+const GUIDE_Z: f32 = 16.0;
+
+/// Edges closer than this, in font units, count as lined up
+const LINED_UP_EPSILON: f32 = 0.01;
+
+/// The guides shown for the sort drag in progress, as line start and end
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct SortSmartGuides {
+    pub lines: Vec<(Vec2, Vec2)>,
+}
+
+/// Marker for guide line meshes
+#[derive(Component)]
+pub struct SmartGuideLine;
+
+/// Where the dragged sorts snap to and the guides that show it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SortSnap {
+    /// To add to the dragged sorts' positions
+    pub offset: Vec2,
+    pub guides: Vec<(Vec2, Vec2)>,
+}
+
+/// Snap the dragged sorts to the others. Sorts are given as origin and
+/// advance width; `descender` and `ascender` set how tall edge guides are.
+pub fn snap_sorts(
+    moving: &[(Vec2, f32)],
+    others: &[(Vec2, f32)],
+    threshold: f32,
+    descender: f32,
+    ascender: f32,
+) -> SortSnap {
+    let edges = |(origin, advance): &(Vec2, f32)| [origin.x, origin.x + advance];
+    let mut nearest_x: Option<f32> = None;
+    let mut nearest_y: Option<f32> = None;
+    for sort in moving {
+        for other in others {
+            for x in edges(sort) {
+                for edge in edges(other) {
+                    keep_nearest(&mut nearest_x, edge - x, threshold);
+                }
+            }
+            keep_nearest(&mut nearest_y, other.0.y - sort.0.y, threshold);
+        }
+    }
+    let offset = Vec2::new(nearest_x.unwrap_or(0.0), nearest_y.unwrap_or(0.0));
+
+    let mut guides = Vec::new();
+    for &(origin, advance) in moving {
+        let snapped = (origin + offset, advance);
+        for other in others {
+            for x in edges(&snapped) {
+                if edges(other)
+                    .iter()
+                    .any(|e| (e - x).abs() < LINED_UP_EPSILON)
+                {
+                    let low = snapped.0.y.min(other.0.y) + descender;
+                    let high = snapped.0.y.max(other.0.y) + ascender;
+                    guides.push((Vec2::new(x, low), Vec2::new(x, high)));
+                }
+            }
+            if (snapped.0.y - other.0.y).abs() < LINED_UP_EPSILON {
+                let left = snapped.0.x.min(other.0.x);
+                let right = (snapped.0.x + advance).max(other.0.x + other.1);
+                guides.push((Vec2::new(left, other.0.y), Vec2::new(right, other.0.y)));
+            }
+        }
+    }
+    guides.dedup();
+    SortSnap { offset, guides }
+}
+
+fn keep_nearest(nearest: &mut Option<f32>, distance: f32, threshold: f32) {
+    if distance.abs() <= threshold && nearest.is_none_or(|n| distance.abs() < n.abs()) {
+        *nearest = Some(distance);
+    }
+}
+
+/// Redraws the guide lines when they change
+pub fn render_sort_smart_guides(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    guides: Res<SortSmartGuides>,
+    existing: Query<Entity, With<SmartGuideLine>>,
+    camera_scale: Res<CameraResponsiveScale>,
+    theme: Res<CurrentTheme>,
+) {
+    if !guides.is_changed() && !camera_scale.is_changed() && !theme.is_changed() {
+        return;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+    if guides.lines.is_empty() {
+        return;
+    }
+
+    let material = materials.add(ColorMaterial::from_color(theme.theme().smart_guide_color()));
+    let line_width = camera_scale.adjusted_line_width();
+    for &(start, end) in &guides.lines {
+        if start.distance(end) < LINED_UP_EPSILON {
+            continue;
+        }
+        commands.spawn((
+            SmartGuideLine,
+            Mesh2d(meshes.add(create_line_mesh(start, end, line_width))),
+            MeshMaterial2d(material.clone()),
+            Transform::from_translation(((start + end) * 0.5).extend(GUIDE_Z)),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snaps_edge_to_neighbour() {
+        // An H dragged to just short of an O's left edge
+        let moving = [(Vec2::new(-603.0, 4.0), 600.0)];
+        let others = [(Vec2::new(0.0, 0.0), 700.0)];
+        let snap = snap_sorts(&moving, &others, 8.0, -200.0, 800.0);
+        assert_eq!(snap.offset, Vec2::new(3.0, -4.0));
+        assert!(snap
+            .guides
+            .contains(&(Vec2::new(0.0, -200.0), Vec2::new(0.0, 800.0))));
+        assert!(snap
+            .guides
+            .contains(&(Vec2::new(-600.0, 0.0), Vec2::new(700.0, 0.0))));
+    }
+
+    #[test]
+    fn test_far_sorts_move_freely() {
+        let moving = [(Vec2::new(-650.0, 40.0), 600.0)];
+        let others = [(Vec2::new(0.0, 0.0), 700.0)];
+        let snap = snap_sorts(&moving, &others, 8.0, -200.0, 800.0);
+        assert_eq!(snap, SortSnap::default());
+    }
+}
@@ -322,7 +322,7 @@ fn calculate_sort_info_transform(
 /// Get unicode value string for a glyph name
 fn get_unicode_for_glyph(glyph_name: &str, app_state: &AppState) -> Option<String> {
     // Check if glyph has unicode value
-    if let Some(glyph_data) = app_state.workspace.font.glyphs.get(glyph_name) {
+    if let Some(glyph_data) = app_state.workspace.font.editing().get_glyph(glyph_name) {
         if !glyph_data.unicode_values.is_empty() {
             // Format unicode values
             let unicode_strings: Vec<String> = glyph_data
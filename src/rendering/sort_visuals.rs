@@ -6,8 +6,12 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::uninlined_format_args)]
 
-use crate::editing::selection::components::Selected;
-use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::core::state::text_editor::text_buffer::{BufferMember, TextBuffer};
+use crate::editing::selection::components::{Selected, SelectionState};
+use crate::rendering::smart_guides::{
+    render_sort_smart_guides, snap_sorts, SortSmartGuides, SNAP_DISTANCE_PX,
+};
+use crate::rendering::zoom_aware_scaling::{CameraResponsiveScale, RenderDetail};
 use crate::ui::themes::CurrentTheme;
 use bevy::prelude::*;
 use bevy::render::mesh::Mesh2d;
@@ -47,6 +51,19 @@ pub struct SortHandleDragState {
     pub dragging_sort: Option<Entity>,
     pub drag_offset: Vec2,
     pub initial_position: Vec2,
+    /// Every sort moving with the grabbed one, the grabbed one included, and
+    /// where each started
+    pub group: Vec<(Entity, Vec2)>,
+}
+
+/// Width and height of the square handle drawn at a sort's lower left
+pub const SORT_HANDLE_SIZE: f32 = 32.0;
+
+/// Center of the handle of a sort whose origin is `sort_position`, sitting
+/// inside the lower left corner of its metrics box
+pub fn sort_handle_center(sort_position: Vec2, descender: f32) -> Vec2 {
+    let half_size = SORT_HANDLE_SIZE / 2.0;
+    sort_position + Vec2::new(half_size, descender + half_size)
 }
 
 /// Helper to spawn a box outline handle mesh
@@ -78,7 +95,6 @@ fn spawn_box_outline_handle(
             Visibility::Visible,
             InheritedVisibility::default(),
             ViewVisibility::default(),
-            crate::editing::selection::components::Selectable,
         ))
         .id();
     entities.push(top_entity);
@@ -98,7 +114,6 @@ fn spawn_box_outline_handle(
             Visibility::Visible,
             InheritedVisibility::default(),
             ViewVisibility::default(),
-            crate::editing::selection::components::Selectable,
         ))
         .id();
     entities.push(bottom_entity);
@@ -118,7 +133,6 @@ fn spawn_box_outline_handle(
             Visibility::Visible,
             InheritedVisibility::default(),
             ViewVisibility::default(),
-            crate::editing::selection::components::Selectable,
         ))
         .id();
     entities.push(left_entity);
@@ -138,7 +152,6 @@ fn spawn_box_outline_handle(
             Visibility::Visible,
             InheritedVisibility::default(),
             ViewVisibility::default(),
-            crate::editing::selection::components::Selectable,
         ))
         .id();
     entities.push(right_entity);
@@ -166,6 +179,7 @@ pub fn render_mesh_sort_handles(
     app_state: Option<Res<crate::core::state::AppState>>,
     camera_scale: Res<CameraResponsiveScale>,
     presentation_mode: Option<Res<crate::ui::edit_mode_toolbar::PresentationMode>>,
+    render_detail: Res<RenderDetail>,
     theme: Res<CurrentTheme>,
 ) {
     // Clear existing handles with entity existence checks
@@ -183,6 +197,11 @@ pub fn render_mesh_sort_handles(
         return;
     }
 
+    // And when zoomed out too far to grab them
+    if render_detail.is_simplified() {
+        return;
+    }
+
     if let Some(app_state_res) = app_state {
         let info = &app_state_res.workspace.info;
         let descender = info.descender.map(|v| v as f32).unwrap_or(-200.0);
@@ -191,10 +210,8 @@ pub fn render_mesh_sort_handles(
             let position = sort_transform.translation.truncate();
 
             // Position handle at lower left corner of the metrics box
-            // The handle should be at the bottom (descender) and left edge (x=0 relative to sort)
-            let handle_size = 32.0; // Fixed size for the handle box
-            let handle_position =
-                position + Vec2::new(handle_size / 2.0, descender + handle_size / 2.0);
+            let handle_size = SORT_HANDLE_SIZE;
+            let handle_position = sort_handle_center(position, descender);
 
             // Check if this sort is selected
             let is_selected = selected_query.iter().any(|e| e == sort_entity);
@@ -246,7 +263,6 @@ pub fn render_mesh_sort_handles(
                         Visibility::Visible,
                         InheritedVisibility::default(),
                         ViewVisibility::default(),
-                        crate::editing::selection::components::Selectable,
                     ))
                     .id();
                 handle_entities_list.push(center_circle);
@@ -260,12 +276,16 @@ pub fn render_mesh_sort_handles(
 }
 
 /// System to handle sort selection and dragging initiation through handles
+///
+/// Clicking a handle selects its sort at sort level, dropping any point
+/// selection. Shift or Cmd/Ctrl toggles the sort in a multi-sort selection,
+/// and grabbing the handle of a sort that is already selected drags the
+/// whole selection along.
 pub fn handle_sort_selection_and_drag_start(
     mut commands: Commands,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<crate::rendering::cameras::DesignCamera>>,
     windows: Query<&Window, With<bevy::window::PrimaryWindow>>,
-    handle_query: Query<(&Transform, &SortHandle), With<SortHandle>>,
     sort_query: Query<
         (
             Entity,
@@ -274,11 +294,13 @@ pub fn handle_sort_selection_and_drag_start(
         ),
         With<crate::editing::sort::Sort>,
     >,
-    _active_sorts: Query<Entity, With<crate::editing::sort::ActiveSort>>,
     selected_sorts: Query<Entity, (With<crate::editing::sort::Sort>, With<Selected>)>,
+    buffer_members: Query<(Entity, &BufferMember)>,
     ui_hover_state: Res<crate::systems::ui_interaction::UiHoverState>,
+    app_state: Option<Res<crate::core::state::AppState>>,
     mut drag_state: ResMut<SortHandleDragState>,
     mut active_sort_state: ResMut<crate::editing::sort::ActiveSortState>,
+    mut selection_state: ResMut<SelectionState>,
     mut text_editor_state: Option<ResMut<crate::core::state::text_editor::TextEditorState>>,
     buffer_index_query: Query<(
         Entity,
@@ -313,292 +335,278 @@ pub fn handle_sort_selection_and_drag_start(
         return;
     };
 
-    // Check if click is on any handle
-    let handle_size = 32.0; // Must match the size used in render_mesh_sort_handles
-    let half_size = handle_size / 2.0;
-
-    for (handle_transform, sort_handle) in handle_query.iter() {
-        let handle_pos = handle_transform.translation.truncate();
+    let Some(app_state) = app_state else {
+        return;
+    };
+    let descender = app_state
+        .workspace
+        .info
+        .descender
+        .map(|v| v as f32)
+        .unwrap_or(-200.0);
 
-        // Check if click is within the handle box
-        if world_position.x >= handle_pos.x - half_size
-            && world_position.x <= handle_pos.x + half_size
-            && world_position.y >= handle_pos.y - half_size
-            && world_position.y <= handle_pos.y + half_size
-        {
-            // Found a clicked handle
-            let sort_entity = sort_handle.sort_entity;
-
-            // Get the current active state of the clicked sort
-            let _is_currently_active = sort_query
-                .get(sort_entity)
-                .map(|(_, _, active)| active.is_some())
-                .unwrap_or(false);
-
-            // Check if shift or cmd/ctrl is held for multi-selection
-            let is_multi_select = keyboard_input.pressed(KeyCode::ShiftLeft)
-                || keyboard_input.pressed(KeyCode::ShiftRight)
-                || keyboard_input.pressed(KeyCode::SuperLeft)
-                || keyboard_input.pressed(KeyCode::SuperRight)
-                || keyboard_input.pressed(KeyCode::ControlLeft)
-                || keyboard_input.pressed(KeyCode::ControlRight);
-
-            // If not multi-selecting, clear selection from all other sorts
-            if !is_multi_select {
-                for selected_entity in selected_sorts.iter() {
-                    if selected_entity != sort_entity {
-                        if let Ok(mut entity_commands) = commands.get_entity(selected_entity) {
-                            entity_commands.remove::<Selected>();
-                        } else {
-                            debug!(
-                                "Skipping selection removal for non-existent entity {:?}",
-                                selected_entity
-                            );
-                        }
-                    }
-                }
-            }
+    // Check if click is on any handle
+    let half_size = SORT_HANDLE_SIZE / 2.0;
+    let clicked = sort_query.iter().find(|(_, transform, _)| {
+        let handle_pos = sort_handle_center(transform.translation.truncate(), descender);
+        (world_position - handle_pos).abs().max_element() <= half_size
+    });
+    let Some((sort_entity, _, _)) = clicked else {
+        return;
+    };
 
-            // Toggle or add selection based on current state
-            let was_selected = selected_sorts.iter().any(|e| e == sort_entity);
-            if is_multi_select && was_selected {
-                // In multi-select mode, clicking a selected item deselects it
-                if let Ok(mut entity_commands) = commands.get_entity(sort_entity) {
+    // Check if shift or cmd/ctrl is held for multi-selection
+    let is_multi_select = keyboard_input.pressed(KeyCode::ShiftLeft)
+        || keyboard_input.pressed(KeyCode::ShiftRight)
+        || keyboard_input.pressed(KeyCode::SuperLeft)
+        || keyboard_input.pressed(KeyCode::SuperRight)
+        || keyboard_input.pressed(KeyCode::ControlLeft)
+        || keyboard_input.pressed(KeyCode::ControlRight);
+    let was_selected = selected_sorts.contains(sort_entity);
+
+    // Selecting sorts replaces any point selection
+    selection_state.selected.clear();
+
+    // A plain click on an unselected sort selects only that sort; a plain
+    // click on a selected one keeps the selection so it can be dragged
+    if !is_multi_select && !was_selected {
+        for selected_entity in selected_sorts.iter() {
+            if selected_entity != sort_entity {
+                if let Ok(mut entity_commands) = commands.get_entity(selected_entity) {
                     entity_commands.remove::<Selected>();
-                    debug!(
-                        "Deselected sort {:?} via handle click (multi-select)",
-                        sort_entity
-                    );
-                } else {
-                    debug!(
-                        "Skipping deselection for non-existent entity {:?}",
-                        sort_entity
-                    );
-                }
-                // Don't proceed with activation/dragging if deselecting
-                continue;
-            } else {
-                // Select the clicked sort
-                if let Ok(mut entity_commands) = commands.get_entity(sort_entity) {
-                    entity_commands.insert(Selected);
-                    debug!("Selected sort {:?} via handle click", sort_entity);
-                } else {
-                    debug!(
-                        "Skipping selection for non-existent entity {:?}",
-                        sort_entity
-                    );
-                    continue; // Skip the rest if entity doesn't exist
                 }
             }
+        }
+    }
 
-            // CRITICAL FIX: Properly manage sort activation without affecting visual rendering
-
-            // First, deactivate all currently active sorts to ensure only one is active
-            for (entity, _, active_component) in sort_query.iter() {
-                if active_component.is_some() && entity != sort_entity {
-                    // Deactivate other sorts with entity existence check
-                    if let Ok(mut entity_commands) = commands.get_entity(entity) {
-                        entity_commands
-                            .remove::<crate::editing::sort::ActiveSort>()
-                            .insert(crate::editing::sort::InactiveSort);
-
-                        // Check if this is a buffer sort and specifically track root sort deactivation
-                        if let Ok(buffer_index) = buffer_index_query.get(entity) {
-                            warn!("HANDLE SYSTEM: Deactivated BUFFER sort {:?} (index {}) - should show filled rendering!", entity, buffer_index.0);
-                        } else {
-                            debug!("HANDLE DEBUG: Deactivated non-buffer sort {:?} via handle selection", entity);
-                        }
-
-                        // DEBUG: Check if this is a buffer sort (which should get filled rendering when inactive)
-                        if let Ok(_entity_ref) = commands.get_entity(entity) {
-                            // We can't directly query components from EntityCommands, so let's add a marker
-                            debug!("HANDLE DEBUG: Deactivated sort {:?} - checking if it's a buffer sort for filled rendering", entity);
-                        }
-                    } else {
-                        debug!("Skipping deactivation for non-existent entity {:?}", entity);
-                    }
-                }
-            }
+    if is_multi_select && was_selected {
+        // In multi-select mode, clicking a selected item deselects it
+        if let Ok(mut entity_commands) = commands.get_entity(sort_entity) {
+            entity_commands.remove::<Selected>();
+            debug!(
+                "Deselected sort {:?} via handle click (multi-select)",
+                sort_entity
+            );
+        }
+        // Don't proceed with activation/dragging if deselecting
+        return;
+    }
+
+    // Select the clicked sort
+    let Ok(mut entity_commands) = commands.get_entity(sort_entity) else {
+        return;
+    };
+    entity_commands.insert(Selected);
+    debug!("Selected sort {:?} via handle click", sort_entity);
 
-            // Activate the clicked sort with entity existence check
-            if let Ok(mut entity_commands) = commands.get_entity(sort_entity) {
+    // Deactivate all currently active sorts to ensure only one is active
+    for (entity, _, active_component) in sort_query.iter() {
+        if active_component.is_some() && entity != sort_entity {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
                 entity_commands
-                    .remove::<crate::editing::sort::InactiveSort>()
-                    .insert(crate::editing::sort::ActiveSort);
-                debug!("HANDLE DEBUG: Activated sort {:?} via handle selection (should get green metrics)", sort_entity);
-            } else {
-                debug!(
-                    "Skipping activation for non-existent entity {:?}",
-                    sort_entity
-                );
-                continue; // Skip the rest if entity doesn't exist
+                    .remove::<crate::editing::sort::ActiveSort>()
+                    .insert(crate::editing::sort::InactiveSort);
+                debug!("Deactivated sort {:?} via handle selection", entity);
             }
+        }
+    }
 
-            // Update the global active sort state
-            active_sort_state.active_sort_entity = Some(sort_entity);
-
-            // Update text editor state if this is a buffer sort
-            if let (Some(text_editor_state), Ok((_, buffer_index))) = (
-                text_editor_state.as_mut(),
-                buffer_index_query.get(sort_entity),
-            ) {
-                // Ensure all other buffer sorts are marked as inactive in text editor state
-                for i in 0..text_editor_state.buffer.len() {
-                    if i != buffer_index.0 {
-                        if let Some(sort_entry) = text_editor_state.buffer.get_mut(i) {
-                            sort_entry.is_active = false;
-                        }
-                    }
-                }
-
-                // Activate the selected sort in text editor state
-                if let Some(sort_entry) = text_editor_state.buffer.get_mut(buffer_index.0) {
-                    sort_entry.is_active = true;
-                    debug!("Activated buffer sort {} via handle click", buffer_index.0);
+    // Activate the clicked sort
+    commands
+        .entity(sort_entity)
+        .remove::<crate::editing::sort::InactiveSort>()
+        .insert(crate::editing::sort::ActiveSort);
+
+    // Update the global active sort state
+    active_sort_state.active_sort_entity = Some(sort_entity);
+
+    // Update text editor state if this is a buffer sort
+    if let (Some(text_editor_state), Ok((_, buffer_index))) = (
+        text_editor_state.as_mut(),
+        buffer_index_query.get(sort_entity),
+    ) {
+        // Ensure all other buffer sorts are marked as inactive in text editor state
+        for i in 0..text_editor_state.buffer.len() {
+            if i != buffer_index.0 {
+                if let Some(sort_entry) = text_editor_state.buffer.get_mut(i) {
+                    sort_entry.is_active = false;
                 }
             }
+        }
 
-            debug!("Activated sort {:?} via handle click", sort_entity);
-
-            // Start dragging
-            if let Ok((_, sort_transform, _)) = sort_query.get(sort_entity) {
-                let sort_position = sort_transform.translation.truncate();
-                drag_state.dragging_sort = Some(sort_entity);
-                drag_state.drag_offset = sort_position - world_position;
-                drag_state.initial_position = sort_position;
-                debug!(
-                    "Started dragging sort {:?} from position {:?}",
-                    sort_entity, sort_position
-                );
-            }
+        // Activate the selected sort in text editor state
+        if let Some(sort_entry) = text_editor_state.buffer.get_mut(buffer_index.0) {
+            sort_entry.is_active = true;
+            debug!("Activated buffer sort {} via handle click", buffer_index.0);
+        }
+    }
+
+    debug!("Activated sort {:?} via handle click", sort_entity);
 
-            // Stop checking other handles since we found one
-            break;
+    // The sorts to drag: the clicked one, the rest of the selection when the
+    // selection was kept, and the other sorts of their text buffers
+    let mut group: Vec<Entity> = vec![sort_entity];
+    if is_multi_select || was_selected {
+        group.extend(selected_sorts.iter().filter(|e| *e != sort_entity));
+    }
+    let buffers: Vec<Entity> = group
+        .iter()
+        .filter_map(|e| buffer_members.get(*e).ok())
+        .map(|(_, member)| member.buffer_entity)
+        .collect();
+    for (entity, member) in buffer_members.iter() {
+        if buffers.contains(&member.buffer_entity) && !group.contains(&entity) {
+            group.push(entity);
         }
     }
+
+    // Start dragging
+    if let Ok((_, sort_transform, _)) = sort_query.get(sort_entity) {
+        let sort_position = sort_transform.translation.truncate();
+        drag_state.dragging_sort = Some(sort_entity);
+        drag_state.drag_offset = sort_position - world_position;
+        drag_state.initial_position = sort_position;
+        drag_state.group = group
+            .into_iter()
+            .filter_map(|e| sort_query.get(e).ok())
+            .map(|(e, transform, _)| (e, transform.translation.truncate()))
+            .collect();
+        debug!(
+            "Started dragging {} sorts from position {:?}",
+            drag_state.group.len(),
+            sort_position
+        );
+    }
 }
 
 /// System to handle sort dragging updates
+///
+/// The sorts being dragged snap to the sorts around them unless Alt is held
+/// (see `rendering::smart_guides`).
 pub fn handle_sort_drag_update(
-    mut sort_query: Query<&mut Transform, With<crate::editing::sort::Sort>>,
+    mut sort_query: Query<(Entity, &crate::editing::sort::Sort, &mut Transform)>,
     camera_query: Query<(&Camera, &GlobalTransform), With<crate::rendering::cameras::DesignCamera>>,
     windows: Query<&Window, With<bevy::window::PrimaryWindow>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    app_state: Option<Res<crate::core::state::AppState>>,
     drag_state: Res<SortHandleDragState>,
-    text_editor_state: Option<Res<crate::core::state::text_editor::TextEditorState>>,
-    buffer_index_query: Query<(
-        Entity,
-        &crate::systems::sorts::sort_entities::BufferSortIndex,
-    )>,
+    mut guides: ResMut<SortSmartGuides>,
 ) {
-    if let Some(dragging_sort) = drag_state.dragging_sort {
-        // Get cursor position in world coordinates
-        let Ok((camera, camera_transform)) = camera_query.single() else {
-            return;
-        };
+    if drag_state.dragging_sort.is_none() {
+        return;
+    }
 
-        let Ok(window) = windows.single() else {
-            return;
-        };
+    // Get cursor position in world coordinates
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
 
-        let Some(cursor_position) = window.cursor_position() else {
-            return;
-        };
+    let Ok(window) = windows.single() else {
+        return;
+    };
+
+    let Some(cursor_position) = window.cursor_position() else {
+        return;
+    };
+
+    let Ok(world_position) = camera.viewport_to_world_2d(camera_transform, cursor_position) else {
+        return;
+    };
 
-        let Ok(world_position) = camera.viewport_to_world_2d(camera_transform, cursor_position)
-        else {
-            return;
+    // Move every sort in the group by as much as the grabbed one moved
+    let mut delta = world_position + drag_state.drag_offset - drag_state.initial_position;
+
+    let free_move = keyboard_input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+    let snap = app_state.filter(|_| !free_move).map(|app_state| {
+        let info = &app_state.workspace.info;
+        let advance = |sort: &crate::editing::sort::Sort| {
+            let glyph = app_state.workspace.font.editing().get_glyph(&sort.glyph_name);
+            glyph.map_or(0.0, |glyph| glyph.advance_width as f32)
         };
+        let mut moving = Vec::new();
+        let mut others = Vec::new();
+        for (entity, sort, transform) in sort_query.iter() {
+            match drag_state.group.iter().find(|(member, _)| *member == entity) {
+                Some(&(_, start)) => moving.push((start + delta, advance(sort))),
+                None => others.push((transform.translation.truncate(), advance(sort))),
+            }
+        }
+        let threshold = camera
+            .viewport_to_world_2d(camera_transform, cursor_position + Vec2::X * SNAP_DISTANCE_PX)
+            .map_or(0.0, |position| position.distance(world_position));
+        let descender = info.descender.map(|v| v as f32).unwrap_or(-200.0);
+        let ascender = info.ascender.map(|v| v as f32).unwrap_or(800.0);
+        snap_sorts(&moving, &others, threshold, descender, ascender)
+    });
+    let snap = snap.unwrap_or_default();
+    delta += snap.offset;
+    if guides.lines != snap.guides {
+        guides.lines = snap.guides;
+    }
 
-        // Update the sort's position with entity existence check
-        if let Ok(mut transform) = sort_query.get_mut(dragging_sort) {
-            let new_position = world_position + drag_state.drag_offset;
-            let delta = new_position - transform.translation.truncate();
+    for &(entity, start) in &drag_state.group {
+        if let Ok((_, _, mut transform)) = sort_query.get_mut(entity) {
+            let new_position = start + delta;
             transform.translation.x = new_position.x;
             transform.translation.y = new_position.y;
-
-            // If this is a text sort, move all other text sorts in the buffer
-            if let (Some(text_editor_state), Ok((_, buffer_index))) = (
-                text_editor_state.as_ref(),
-                buffer_index_query.get(dragging_sort),
-            ) {
-                if let Some(sort_entry) = text_editor_state.buffer.get(buffer_index.0) {
-                    // Check if this is a text sort (LTR or RTL)
-                    match sort_entry.layout_mode {
-                        crate::core::state::SortLayoutMode::LTRText
-                        | crate::core::state::SortLayoutMode::RTLText => {
-                            // Move all other text sorts by the same delta
-                            for (other_entity, other_buffer_index) in buffer_index_query.iter() {
-                                if other_entity != dragging_sort {
-                                    if let Some(other_sort) =
-                                        text_editor_state.buffer.get(other_buffer_index.0)
-                                    {
-                                        // Check if the other sort is also a text sort
-                                        match other_sort.layout_mode {
-                                            crate::core::state::SortLayoutMode::LTRText
-                                            | crate::core::state::SortLayoutMode::RTLText => {
-                                                // Check entity exists before updating transform
-                                                if let Ok(mut other_transform) =
-                                                    sort_query.get_mut(other_entity)
-                                                {
-                                                    other_transform.translation.x += delta.x;
-                                                    other_transform.translation.y += delta.y;
-                                                } else {
-                                                    debug!("Skipping transform update for non-existent entity {:?}", other_entity);
-                                                }
-                                            }
-                                            _ => {} // Skip freeform sorts
-                                        }
-                                    }
-                                }
-                            }
-                            debug!("Moved text sort group by delta: {:?}", delta);
-                        }
-                        crate::core::state::SortLayoutMode::Freeform => {
-                            // Freeform sorts move individually
-                        }
-                    }
-                }
-            }
         }
     }
 }
 
 /// System to handle sort drag release
+///
+/// Keeps the new positions where they are stored: freeform sorts in the
+/// text editor state, text sorts as their buffer's root position.
 pub fn handle_sort_drag_release(
     mouse_button_input: Res<ButtonInput<MouseButton>>,
     mut drag_state: ResMut<SortHandleDragState>,
+    mut guides: ResMut<SortSmartGuides>,
     mut text_editor_state: Option<ResMut<crate::core::state::text_editor::TextEditorState>>,
     sort_query: Query<&Transform, With<crate::editing::sort::Sort>>,
-    buffer_index_query: Query<(
-        Entity,
-        &crate::systems::sorts::sort_entities::BufferSortIndex,
-    )>,
+    buffer_index_query: Query<&crate::systems::sorts::sort_entities::BufferSortIndex>,
+    buffer_members: Query<&BufferMember>,
+    mut text_buffers: Query<&mut TextBuffer>,
 ) {
-    if drag_state.dragging_sort.is_some() && mouse_button_input.just_released(MouseButton::Left) {
-        if let Some(dragging_sort) = drag_state.dragging_sort.take() {
-            debug!("Stopped dragging sort {:?}", dragging_sort);
-
-            // Update the text editor state with the new position if applicable
-            if let (Some(text_editor_state), Ok((_, buffer_index)), Ok(transform)) = (
-                text_editor_state.as_mut(),
-                buffer_index_query.get(dragging_sort),
-                sort_query.get(dragging_sort),
-            ) {
-                if let Some(sort_entry) = text_editor_state.buffer.get_mut(buffer_index.0) {
-                    let new_position = transform.translation.truncate();
-                    sort_entry.root_position = new_position;
-                    debug!(
-                        "Updated buffer sort {} position to {:?}",
-                        buffer_index.0, new_position
-                    );
+    if drag_state.dragging_sort.is_none() || !mouse_button_input.just_released(MouseButton::Left)
+    {
+        return;
+    }
+    let Some(dragging_sort) = drag_state.dragging_sort.take() else {
+        return;
+    };
+    debug!("Stopped dragging sort {:?}", dragging_sort);
+
+    let delta = sort_query
+        .get(dragging_sort)
+        .map(|transform| transform.translation.truncate() - drag_state.initial_position)
+        .unwrap_or(Vec2::ZERO);
+    let mut moved_buffers = Vec::new();
+    for &(entity, _) in &drag_state.group {
+        let Ok(transform) = sort_query.get(entity) else {
+            continue;
+        };
+        if let (Some(text_editor_state), Ok(buffer_index)) =
+            (text_editor_state.as_mut(), buffer_index_query.get(entity))
+        {
+            if let Some(sort_entry) = text_editor_state.buffer.get_mut(buffer_index.0) {
+                sort_entry.root_position = transform.translation.truncate();
+            }
+        }
+        if let Ok(member) = buffer_members.get(entity) {
+            if !moved_buffers.contains(&member.buffer_entity) {
+                moved_buffers.push(member.buffer_entity);
+                if let Ok(mut buffer) = text_buffers.get_mut(member.buffer_entity) {
+                    buffer.root_position += delta;
                 }
             }
         }
-
-        // Clear drag state
-        drag_state.drag_offset = Vec2::ZERO;
-        drag_state.initial_position = Vec2::ZERO;
     }
+
+    // Clear drag state
+    drag_state.drag_offset = Vec2::ZERO;
+    drag_state.initial_position = Vec2::ZERO;
+    drag_state.group.clear();
+    guides.lines.clear();
 }
 
 pub struct SortHandleRenderingPlugin;
@@ -607,6 +615,7 @@ impl Plugin for SortHandleRenderingPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SortHandleEntities>()
             .init_resource::<SortHandleDragState>()
+            .init_resource::<SortSmartGuides>()
             .add_systems(
                 Update,
                 (
@@ -616,6 +625,7 @@ impl Plugin for SortHandleRenderingPlugin {
                         .before(crate::systems::sorts::sort_entities::auto_activate_selected_sorts),
                     handle_sort_drag_update,
                     handle_sort_drag_release,
+                    render_sort_smart_guides.after(handle_sort_drag_release),
                 ),
             );
     }
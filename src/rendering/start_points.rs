@@ -0,0 +1,167 @@
+//! Point numbers and contour start points
+//!
+//! While `StartPointsOverlay` is visible, labels every point of the active
+//! sort with its index in its contour and puts an arrow at each contour's
+//! start point, pointing the way the contour runs.
+
+use crate::core::state::{AppState, ContourData};
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::editing::start_points::StartPointsOverlay;
+use crate::rendering::mesh_utils::create_triangles_mesh;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::ui::edit_mode_toolbar::PresentationMode;
+use crate::ui::themes::CurrentTheme;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+/// Above the points and guides
+const LABEL_Z: f32 = 17.0;
+
+/// Size of the point numbers, in screen pixels
+const LABEL_SIZE_PX: f32 = 10.0;
+
+/// Gap between a point and its number, in screen pixels
+const LABEL_OFFSET_PX: f32 = 6.0;
+
+/// Length of the start point arrows, in screen pixels
+const ARROW_LENGTH_PX: f32 = 14.0;
+
+/// What the labels were last drawn for
+#[derive(Debug, Clone, PartialEq)]
+struct DrawnStartPoints {
+    /// Each point's position and index in its contour
+    labels: Vec<(Vec2, usize)>,
+    /// Each contour's start point and the way it runs from there
+    starts: Vec<(Vec2, Vec2)>,
+    pixel: f32,
+}
+
+/// Marker for the labels and arrows
+#[derive(Component)]
+struct StartPointsMarker;
+
+pub struct StartPointsRenderingPlugin;
+
+impl Plugin for StartPointsRenderingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, render_start_points);
+    }
+}
+
+/// The direction a contour runs from its start point
+fn start_direction(contour: &ContourData) -> Option<Vec2> {
+    let position = |index: usize| {
+        let point = &contour.points[index];
+        Vec2::new(point.x as f32, point.y as f32)
+    };
+    let start = position(0);
+    (1..contour.points.len())
+        .map(position)
+        .map(|point| point - start)
+        .find(|offset| offset.length() > f32::EPSILON)
+        .map(Vec2::normalize)
+}
+
+/// Redraws the labels when the active sort's points or the view change
+#[allow(clippy::too_many_arguments)]
+fn render_start_points(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut drawn: Local<Option<DrawnStartPoints>>,
+    overlay: Res<StartPointsOverlay>,
+    active_sorts: Query<(&Transform, &Sort), With<ActiveSort>>,
+    app_state: Option<Res<AppState>>,
+    presentation_mode: Option<Res<PresentationMode>>,
+    camera_scale: Res<CameraResponsiveScale>,
+    theme: Res<CurrentTheme>,
+    asset_server: Res<AssetServer>,
+    existing: Query<Entity, With<StartPointsMarker>>,
+) {
+    let presentation_active = presentation_mode.is_some_and(|pm| pm.active);
+    let Some(state) = app_state.filter(|_| overlay.visible && !presentation_active) else {
+        if drawn.take().is_some() {
+            for entity in existing.iter() {
+                commands.entity(entity).despawn();
+            }
+        }
+        return;
+    };
+
+    let mut start_points = DrawnStartPoints {
+        labels: Vec::new(),
+        starts: Vec::new(),
+        pixel: camera_scale.pixels_to_world(1.0),
+    };
+    for (transform, sort) in active_sorts.iter() {
+        let Some(outline) = state
+            .workspace
+            .font
+            .editing()
+            .get_glyph(&sort.glyph_name)
+            .and_then(|glyph| glyph.outline.as_ref())
+        else {
+            continue;
+        };
+        let origin = transform.translation.truncate();
+        for contour in &outline.contours {
+            start_points.labels.extend(
+                contour.points.iter().enumerate().map(|(index, point)| {
+                    (origin + Vec2::new(point.x as f32, point.y as f32), index)
+                }),
+            );
+            if let (Some(first), Some(direction)) =
+                (contour.points.first(), start_direction(contour))
+            {
+                let start = origin + Vec2::new(first.x as f32, first.y as f32);
+                start_points.starts.push((start, direction));
+            }
+        }
+    }
+    if drawn.as_ref() == Some(&start_points) && !theme.is_changed() {
+        return;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let pixel = start_points.pixel;
+    let font = asset_server.load(theme.theme().mono_font_path());
+    let label_color = theme.theme().point_number_color();
+    let offset = Vec2::splat(LABEL_OFFSET_PX * pixel);
+    for &(position, index) in &start_points.labels {
+        commands.spawn((
+            StartPointsMarker,
+            Text2d::new(index.to_string()),
+            TextFont {
+                font: font.clone(),
+                font_size: LABEL_SIZE_PX * pixel,
+                ..default()
+            },
+            TextColor(label_color),
+            Anchor::BottomLeft,
+            Transform::from_translation((position + offset).extend(LABEL_Z)),
+        ));
+    }
+
+    let length = ARROW_LENGTH_PX * pixel;
+    let arrows: Vec<[Vec2; 3]> = start_points
+        .starts
+        .iter()
+        .map(|&(start, direction)| {
+            let side = direction.perp() * length * 0.35;
+            [start + direction * length, start + side, start - side]
+        })
+        .collect();
+    if !arrows.is_empty() {
+        commands.spawn((
+            StartPointsMarker,
+            Mesh2d(meshes.add(create_triangles_mesh(&arrows))),
+            MeshMaterial2d(
+                materials.add(ColorMaterial::from_color(theme.theme().start_point_color())),
+            ),
+            Transform::from_xyz(0.0, 0.0, LABEL_Z),
+        ));
+    }
+    *drawn = Some(start_points);
+}
@@ -0,0 +1,210 @@
+//! Template outlines in empty glyphs
+//!
+//! Sorts of glyphs without contours or components show a light outline of
+//! their character from a template font (see `font_source::template_glyphs`),
+//! so spacing and proportions can be judged before the glyph is drawn. The
+//! template font set in the settings comes first, then the bundled ones; the
+//! templates preference hides them all.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::core::config::BezySettings;
+use crate::core::state::AppState;
+use crate::editing::font_changes::GlyphEdited;
+use crate::editing::sort::Sort;
+use crate::font_source::template_glyphs::{is_empty_glyph, template_outline, TemplateFont};
+use crate::rendering::mesh_utils::create_lines_mesh;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::ui::edit_mode_toolbar::PresentationMode;
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::{BEZY_GROTESK_BYTES, HASUBI_MONO_BYTES};
+use bevy::prelude::*;
+
+/// How closely the flattened outlines follow the curves, in font units
+const FLATTEN_TOLERANCE: f64 = 2.0;
+
+/// Behind ghosted glyphs and layers
+const TEMPLATE_Z: f32 = 3.0;
+
+/// Template fonts and the outlines taken from them, by glyph name
+#[derive(Default)]
+struct TemplateCache {
+    /// Settings font the fonts were loaded for, `None` before loading
+    loaded_for: Option<Option<PathBuf>>,
+    fonts: Vec<TemplateFont>,
+    units_per_em: f64,
+    /// Flattened template outline of each glyph, `None` without a template
+    glyphs: HashMap<String, Option<Vec<(Vec2, Vec2)>>>,
+}
+
+impl TemplateCache {
+    /// Reload the fonts when the settings font changes; true if they were
+    fn load_fonts(&mut self, font_path: &Option<PathBuf>) -> bool {
+        if self.loaded_for.as_ref() == Some(font_path) {
+            return false;
+        }
+        self.fonts.clear();
+        self.glyphs.clear();
+        if let Some(path) = font_path {
+            match TemplateFont::load(path) {
+                Ok(font) => self.fonts.push(font),
+                Err(error) => warn!("Template font not used: {error:#}"),
+            }
+        }
+        let bundled = [
+            ("Bezy Grotesk", BEZY_GROTESK_BYTES),
+            ("Hasubi Mono", HASUBI_MONO_BYTES),
+        ];
+        for (name, bytes) in bundled {
+            match TemplateFont::from_bytes(name, bytes.to_vec()) {
+                Ok(font) => self.fonts.push(font),
+                Err(error) => warn!("Bundled template font not used: {error:#}"),
+            }
+        }
+        debug!(
+            "Template fonts: {:?}",
+            self.fonts
+                .iter()
+                .map(TemplateFont::name)
+                .collect::<Vec<_>>()
+        );
+        self.loaded_for = Some(font_path.clone());
+        true
+    }
+}
+
+/// What the templates were last drawn for
+#[derive(Debug, Clone, PartialEq)]
+struct DrawnTemplates {
+    /// Origin and glyph name of each sort with a template
+    sorts: Vec<(Vec2, String)>,
+    line_width: f32,
+}
+
+/// Marker for the template meshes
+#[derive(Component)]
+struct TemplateGlyphMesh;
+
+pub struct TemplateGlyphsPlugin;
+
+impl Plugin for TemplateGlyphsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, render_template_glyphs);
+    }
+}
+
+/// Line segments of closed paths, curves flattened
+fn outline_lines(paths: &[kurbo::BezPath]) -> Vec<(Vec2, Vec2)> {
+    let to_vec2 = |point: kurbo::Point| Vec2::new(point.x as f32, point.y as f32);
+    let mut lines = Vec::new();
+    for path in paths {
+        let (mut start, mut last) = (Vec2::ZERO, Vec2::ZERO);
+        kurbo::flatten(path.iter(), FLATTEN_TOLERANCE, |element| match element {
+            kurbo::PathEl::MoveTo(point) => {
+                start = to_vec2(point);
+                last = start;
+            }
+            kurbo::PathEl::LineTo(point) => {
+                let point = to_vec2(point);
+                lines.push((last, point));
+                last = point;
+            }
+            kurbo::PathEl::ClosePath if last != start => {
+                lines.push((last, start));
+                last = start;
+            }
+            _ => {}
+        });
+    }
+    lines
+}
+
+/// Redraws the templates when the text, the glyphs or the view change
+#[allow(clippy::too_many_arguments)]
+fn render_template_glyphs(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut drawn: Local<Option<DrawnTemplates>>,
+    mut cache: Local<TemplateCache>,
+    mut glyph_edits: EventReader<GlyphEdited>,
+    settings: Res<BezySettings>,
+    sorts: Query<(&Transform, &Sort)>,
+    app_state: Option<Res<AppState>>,
+    presentation_mode: Option<Res<PresentationMode>>,
+    camera_scale: Res<CameraResponsiveScale>,
+    theme: Res<CurrentTheme>,
+    existing: Query<Entity, With<TemplateGlyphMesh>>,
+) {
+    // Glyphs get drawn, emptied or given other characters
+    let edited = glyph_edits.read().count() > 0;
+    let presentation_active = presentation_mode.is_some_and(|pm| pm.active);
+    let shown = settings.templates.show && !presentation_active;
+    let Some(state) = app_state.filter(|_| shown) else {
+        if drawn.take().is_some() {
+            for entity in existing.iter() {
+                commands.entity(entity).despawn();
+            }
+        }
+        return;
+    };
+
+    let reloaded = cache.load_fonts(&settings.templates.font);
+    let units_per_em = state.workspace.info.units_per_em;
+    let stale = edited || reloaded || cache.units_per_em != units_per_em;
+    if stale {
+        cache.units_per_em = units_per_em;
+        cache.glyphs.clear();
+    }
+
+    let font = &state.workspace.font;
+    let mut placed = Vec::new();
+    for (transform, sort) in sorts.iter() {
+        let Some(glyph) = font.editing().get_glyph(&sort.glyph_name) else {
+            continue;
+        };
+        if !is_empty_glyph(glyph) {
+            continue;
+        }
+        let TemplateCache { fonts, glyphs, .. } = &mut *cache;
+        let lines = glyphs.entry(sort.glyph_name.clone()).or_insert_with(|| {
+            template_outline(glyph, fonts, units_per_em).map(|paths| outline_lines(&paths))
+        });
+        if lines.is_some() {
+            placed.push((transform.translation.truncate(), sort.glyph_name.clone()));
+        }
+    }
+
+    let templates_drawn = DrawnTemplates {
+        sorts: placed,
+        line_width: camera_scale.adjusted_line_width(),
+    };
+    if drawn.as_ref() == Some(&templates_drawn) && !stale && !theme.is_changed() {
+        return;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let mut lines = Vec::new();
+    for (origin, glyph_name) in &templates_drawn.sorts {
+        if let Some(Some(template)) = cache.glyphs.get(glyph_name) {
+            lines.extend(
+                template
+                    .iter()
+                    .map(|&(start, end)| (*origin + start, *origin + end)),
+            );
+        }
+    }
+    if !lines.is_empty() {
+        let color = theme.theme().template_glyph_color();
+        commands.spawn((
+            TemplateGlyphMesh,
+            Mesh2d(meshes.add(create_lines_mesh(&lines, templates_drawn.line_width))),
+            MeshMaterial2d(materials.add(ColorMaterial::from_color(color))),
+            Transform::from_xyz(0.0, 0.0, TEMPLATE_Z),
+        ));
+    }
+    *drawn = Some(templates_drawn);
+}
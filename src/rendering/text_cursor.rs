@@ -1,17 +1,29 @@
 //! Text cursor rendering for the text editor
 //!
 //! This module handles the visual rendering of text cursors in Insert mode,
-//! including mesh generation and visual updates.
+//! including mesh generation and visual updates. The caret blinks, staying
+//! solid for a moment whenever it moves, and selected text is highlighted
+//! behind the glyphs.
 
 use crate::rendering::entity_pools::{update_cursor_entity, EntityPools, PooledEntityType};
 use bevy::prelude::*;
 use bevy::render::mesh::Mesh;
 use bevy::sprite::ColorMaterial;
 
+/// How long the blinking caret is shown, and then hidden, in seconds
+pub const CURSOR_BLINK_SECS: f32 = 0.53;
+
+/// Selection highlights draw behind the glyphs
+const SELECTION_Z: f32 = 0.5;
+
 /// Component to mark text editor cursor entities
 #[derive(Component)]
 pub struct TextEditorCursor;
 
+/// Marker for the highlight behind selected text
+#[derive(Component)]
+pub struct TextSelectionHighlight;
+
 /// Resource to track cursor state for change detection
 #[derive(Resource, Default)]
 pub struct CursorRenderingState {
@@ -20,6 +32,17 @@ pub struct CursorRenderingState {
     pub last_placement_mode: Option<crate::ui::edit_mode_toolbar::text::TextPlacementMode>,
     pub last_buffer_cursor_position: Option<usize>,
     pub last_camera_scale: Option<f32>,
+    /// World-space stretches of the highlighted selection (left end on the
+    /// baseline, width)
+    pub last_selection_spans: Vec<(Vec2, f32)>,
+    /// When the caret last moved, in seconds since startup; it blinks from
+    /// then on
+    pub moved_at: f32,
+}
+
+/// Whether the blinking caret shows this long after it last moved
+pub fn caret_visible(since_moved: f32) -> bool {
+    (since_moved.max(0.0) / CURSOR_BLINK_SECS) as u32 % 2 == 0
 }
 
 /// Render a text cursor at the specified world position (internal)
@@ -32,20 +55,13 @@ pub(crate) fn render_cursor_at_position(
     cursor_world_pos: Vec2,
     upm: f32,
     descender: f32,
+    cursor_color: Color,
     camera_scale: &crate::rendering::zoom_aware_scaling::CameraResponsiveScale,
 ) {
-    warn!(
-        "🎨 RENDERING CURSOR at world_pos=({:.1}, {:.1})",
-        cursor_world_pos.x, cursor_world_pos.y
-    );
-
     // Calculate cursor bounds based on font metrics
     let cursor_top = cursor_world_pos.y + upm; // UPM top
     let cursor_bottom = cursor_world_pos.y + descender; // Descender bottom
 
-    // Bright orange cursor color
-    let cursor_color = Color::srgb(1.0, 0.5, 0.0);
-
     // Create zoom-aware mesh-based cursor
     create_mesh_cursor(
         commands,
@@ -68,6 +84,33 @@ pub(crate) fn clear_cursor_entities(
     entity_pools.return_cursor_entities(commands);
 }
 
+/// Highlight selected text, one rectangle per stretch, from the descender
+/// to the top of the em like the caret (internal)
+pub(crate) fn render_selection_spans(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    spans: &[(Vec2, f32)],
+    upm: f32,
+    descender: f32,
+    color: Color,
+) {
+    if spans.is_empty() {
+        return;
+    }
+    let material = materials.add(ColorMaterial::from_color(color));
+    let height = upm - descender;
+    for &(left, width) in spans {
+        let center = Vec2::new(left.x + width * 0.5, left.y + (upm + descender) * 0.5);
+        commands.spawn((
+            TextSelectionHighlight,
+            Mesh2d(meshes.add(Rectangle::new(width, height))),
+            MeshMaterial2d(material.clone()),
+            Transform::from_translation(center.extend(SELECTION_Z)),
+        ));
+    }
+}
+
 /// Create a mesh-based cursor with triangular ends
 #[allow(clippy::too_many_arguments)]
 fn create_mesh_cursor(
@@ -4,9 +4,21 @@
 //! (points, lines, handles) to maintain visibility at all zoom levels.
 //! Similar to how professional font editors keep UI elements visible
 //! when zoomed out instead of letting them get teeny-tiny.
+//!
+//! The window's scale factor is followed too, changing when the window moves
+//! to a monitor of another DPI: line widths are rounded to whole device
+//! pixels so they stay crisp at fractional scale factors, and screen
+//! distances such as hit-test margins, given in logical pixels, are turned
+//! into design space distances at the current zoom.
+//!
+//! Zoomed far out, the editing chrome is dropped altogether: [`RenderDetail`]
+//! switches to simplified rendering past one zoom level and back below a
+//! lower one, so long buffers draw as coarse filled glyphs only.
 
+use crate::core::config::BezySettings;
 use crate::rendering::cameras::DesignCamera;
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 
 /// Zoom-responsive scaling behavior
 #[derive(Debug, Clone)]
@@ -44,6 +56,10 @@ pub struct ZoomRanges {
     pub default_zoom: f32,
     /// Camera scale at maximum zoom out
     pub max_zoom_out: f32,
+    /// Camera scale past which rendering is simplified
+    pub simplify_above: f32,
+    /// Camera scale below which full detail comes back
+    pub full_detail_below: f32,
 }
 
 impl Default for ZoomRanges {
@@ -52,6 +68,9 @@ impl Default for ZoomRanges {
             max_zoom_in: 0.2,   // Maximum zoom in
             default_zoom: 1.0,  // Default zoom level
             max_zoom_out: 16.0, // Maximum zoom out
+            // Apart, so zooming around one level doesn't flicker between them
+            simplify_above: 8.0,
+            full_detail_below: 6.0,
         }
     }
 }
@@ -63,6 +82,10 @@ pub struct CameraResponsiveScale {
     /// 1.0 = base size, >1.0 = bigger, <1.0 = smaller
     /// This field is private as it's automatically updated every frame
     scale_factor: f32,
+    /// Design space units per logical pixel, the camera's zoom
+    camera_scale: f32,
+    /// Device pixels per logical pixel of the window's monitor
+    window_scale: f32,
     /// Base line width in world units at normal zoom
     pub base_line_width: f32,
     /// Configuration for scale factors at different zoom levels
@@ -75,6 +98,8 @@ impl CameraResponsiveScale {
     pub fn new() -> Self {
         Self {
             scale_factor: 1.0,
+            camera_scale: 1.0,
+            window_scale: 1.0,
             base_line_width: 1.0,
             config: ZoomScaleConfig::default(),
             ranges: ZoomRanges::default(),
@@ -85,6 +110,8 @@ impl CameraResponsiveScale {
     pub fn with_config(config: ZoomScaleConfig, ranges: ZoomRanges) -> Self {
         Self {
             scale_factor: 1.0,
+            camera_scale: 1.0,
+            window_scale: 1.0,
             base_line_width: 1.0,
             config,
             ranges,
@@ -122,9 +149,30 @@ impl CameraResponsiveScale {
         }
     }
 
-    /// Get the adjusted line width based on camera zoom
+    /// Device pixels per logical pixel of the window's monitor
+    pub fn window_scale(&self) -> f32 {
+        self.window_scale
+    }
+
+    /// A distance in logical pixels on screen as a distance in the design
+    /// space at the current zoom
+    pub fn pixels_to_world(&self, pixels: f32) -> f32 {
+        pixels * self.camera_scale
+    }
+
+    /// How far from a point a click still hits it, in the design space at
+    /// the current zoom
+    pub fn hit_tolerance(&self, settings: &BezySettings) -> f32 {
+        self.pixels_to_world(settings.hit_test.tolerance)
+    }
+
+    /// Get the adjusted line width based on camera zoom, a whole number of
+    /// device pixels wide
     pub fn adjusted_line_width(&self) -> f32 {
-        self.base_line_width * self.scale_factor
+        snap_to_device_pixels(
+            self.base_line_width * self.scale_factor,
+            self.camera_scale / self.window_scale,
+        )
     }
 
     /// Get the adjusted size for any element
@@ -145,10 +193,58 @@ fn lerp_scale(
     factor_start * (1.0 - t) + factor_end * t
 }
 
-/// Updates the camera-responsive scale based on current zoom
+/// A width rounded to a whole number of device pixels, at least one
+fn snap_to_device_pixels(width: f32, units_per_device_pixel: f32) -> f32 {
+    if units_per_device_pixel <= 0.0 {
+        return width;
+    }
+    (width / units_per_device_pixel).round().max(1.0) * units_per_device_pixel
+}
+
+/// How much is drawn for each sort
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderDetail {
+    /// Points, handles, metrics and sort handles
+    #[default]
+    Full,
+    /// Coarsely tessellated filled glyphs only
+    Simplified,
+}
+
+impl RenderDetail {
+    /// The level for a camera scale, kept as it is between the two thresholds
+    pub fn for_camera_scale(self, camera_scale: f32, ranges: &ZoomRanges) -> Self {
+        match self {
+            RenderDetail::Full if camera_scale > ranges.simplify_above => RenderDetail::Simplified,
+            RenderDetail::Simplified if camera_scale < ranges.full_detail_below => {
+                RenderDetail::Full
+            }
+            detail => detail,
+        }
+    }
+
+    pub fn is_simplified(self) -> bool {
+        self == RenderDetail::Simplified
+    }
+
+    /// Largest distance, in font units, a filled glyph mesh may stray from
+    /// the outline
+    pub fn fill_tolerance(self) -> f32 {
+        match self {
+            RenderDetail::Full => 0.1,
+            RenderDetail::Simplified => 2.0,
+        }
+    }
+}
+
+/// Updates the camera-responsive scale based on current zoom, the window's
+/// scale factor and the user's UI scale
 pub fn update_camera_responsive_scale(
     mut scale_res: ResMut<CameraResponsiveScale>,
+    mut detail: ResMut<RenderDetail>,
     camera_q: Query<&Projection, With<DesignCamera>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<BezySettings>,
 ) {
     let Ok(projection) = camera_q.single() else {
         return;
@@ -159,7 +255,25 @@ pub fn update_camera_responsive_scale(
         _ => 1.0,
     };
 
-    scale_res.scale_factor = scale_res.calculate_scale_factor(camera_scale);
+    // Only a real change of scale redraws what depends on it
+    let scale_factor =
+        scale_res.calculate_scale_factor(camera_scale) * settings.accessibility.ui_scale;
+    if scale_res.scale_factor != scale_factor {
+        scale_res.scale_factor = scale_factor;
+    }
+    if scale_res.camera_scale != camera_scale {
+        scale_res.camera_scale = camera_scale;
+    }
+    // Changes when the window moves to a monitor of another DPI
+    if let Ok(window) = window_q.single() {
+        let window_scale = window.scale_factor();
+        if scale_res.window_scale != window_scale {
+            info!("Window scale factor is now {}", window_scale);
+            scale_res.window_scale = window_scale;
+        }
+    }
+    let next = detail.for_camera_scale(camera_scale, &scale_res.ranges);
+    detail.set_if_neq(next);
 }
 
 /// Types of visual elements that respond to camera zoom
@@ -225,6 +339,7 @@ pub struct CameraResponsivePlugin;
 impl Plugin for CameraResponsivePlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(CameraResponsiveScale::new())
+            .init_resource::<RenderDetail>()
             .add_systems(
                 Update,
                 (
@@ -235,3 +350,36 @@ impl Plugin for CameraResponsivePlugin {
             );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_detail_hysteresis() {
+        let ranges = ZoomRanges::default();
+        let detail = RenderDetail::Full.for_camera_scale(7.0, &ranges);
+        assert_eq!(detail, RenderDetail::Full);
+        let detail = detail.for_camera_scale(9.0, &ranges);
+        assert_eq!(detail, RenderDetail::Simplified);
+        // Zooming back in a little stays simplified until well past the threshold
+        let detail = detail.for_camera_scale(7.0, &ranges);
+        assert_eq!(detail, RenderDetail::Simplified);
+        assert_eq!(detail.for_camera_scale(5.0, &ranges), RenderDetail::Full);
+    }
+
+    #[test]
+    fn test_line_width_snapping() {
+        // 1.5 units at a 1.25 scale factor is 1.875 device pixels, drawn as 2
+        assert_eq!(snap_to_device_pixels(1.5, 0.8), 1.6);
+        assert_eq!(snap_to_device_pixels(1.5, 0.5), 1.5);
+        // Never thinner than a device pixel
+        assert_eq!(snap_to_device_pixels(0.1, 0.5), 0.5);
+
+        let mut scale = CameraResponsiveScale::new();
+        scale.camera_scale = 4.0;
+        scale.window_scale = 2.0;
+        assert_eq!(scale.pixels_to_world(16.0), 64.0);
+        assert_eq!(scale.adjusted_line_width(), 2.0);
+    }
+}
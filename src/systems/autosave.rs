@@ -0,0 +1,66 @@
+//! Autosave
+//!
+//! With an autosave interval set in the preferences, unsaved edits are
+//! saved once the interval has passed since the first of them. An edit is
+//! anything sending `AppStateChanged`; any save starts the wait over.
+//! Fonts that haven't been saved anywhere yet are left alone.
+
+use crate::core::config::BezySettings;
+use crate::core::state::AppState;
+use crate::editing::selection::events::AppStateChanged;
+use crate::systems::commands::SaveFileEvent;
+use bevy::prelude::*;
+
+/// When the oldest unsaved edit was made, in seconds since startup
+#[derive(Resource, Default)]
+struct UnsavedSince(Option<f64>);
+
+pub struct AutosavePlugin;
+
+impl Plugin for AutosavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UnsavedSince>()
+            .add_systems(Update, (forget_saved_edits, autosave).chain());
+    }
+}
+
+/// Starts the wait over whenever the font is saved
+fn forget_saved_edits(
+    mut saves: EventReader<SaveFileEvent>,
+    mut unsaved_since: ResMut<UnsavedSince>,
+) {
+    if saves.read().count() > 0 {
+        unsaved_since.0 = None;
+    }
+}
+
+/// Notes the first unsaved edit and saves once the interval has passed
+fn autosave(
+    time: Res<Time>,
+    settings: Res<BezySettings>,
+    app_state: Option<Res<AppState>>,
+    mut unsaved_since: ResMut<UnsavedSince>,
+    mut changes: EventReader<AppStateChanged>,
+    mut save_events: EventWriter<SaveFileEvent>,
+) {
+    let now = time.elapsed_secs_f64();
+    if changes.read().count() > 0 && unsaved_since.0.is_none() {
+        unsaved_since.0 = Some(now);
+    }
+
+    let interval = settings.autosave.interval_secs;
+    let Some(since) = unsaved_since.0 else {
+        return;
+    };
+    if interval == 0 || now - since < interval as f64 {
+        return;
+    }
+    let has_path = app_state
+        .as_deref()
+        .is_some_and(|state| state.workspace.font.path.is_some());
+    if has_path {
+        debug!("💾 Autosaving edits from {:.0}s ago", now - since);
+        save_events.write(SaveFileEvent);
+    }
+    unsaved_since.0 = None;
+}
@@ -11,8 +11,10 @@
 #![allow(deprecated)]
 #![allow(unused_mut)]
 
+use crate::core::config::BezySettings;
 use crate::core::state::{AppState, GlyphNavigation};
 use crate::editing::sort::{ActiveSort, Sort};
+use crate::io::shortcuts::Shortcut;
 use crate::rendering::checkerboard::CheckerboardEnabled;
 // BezyResult not used in current implementation
 use bevy::prelude::*;
@@ -62,6 +64,32 @@ pub enum CodepointDirection {
     Previous,
 }
 
+/// Build missing accented glyphs from the built-in composite recipes
+///
+/// `glyph_names: None` builds every recipe whose glyph is missing.
+#[derive(Event, Clone, Default)]
+pub struct BuildCompositesEvent {
+    pub glyph_names: Option<Vec<String>>,
+}
+
+/// What the last composite build made and couldn't make, for the UI
+#[derive(Resource, Default)]
+pub struct LastCompositeBuild(pub Option<crate::data::glyph_recipes::CompositeBuildReport>);
+
+/// Make a glyph set the font's target: a built-in set id or a file path
+#[derive(Event, Clone)]
+pub struct LoadGlyphSetEvent {
+    pub source: String,
+}
+
+/// Add empty glyphs for the glyphs of the target glyph set the font lacks
+#[derive(Event, Clone, Default)]
+pub struct AddGlyphSetPlaceholdersEvent;
+
+/// The glyph set the font is meant to cover, if one was chosen
+#[derive(Resource, Default)]
+pub struct TargetGlyphSet(pub Option<crate::data::glyph_sets::GlyphSet>);
+
 #[derive(Event)]
 pub struct CreateContourEvent {
     #[allow(dead_code)]
@@ -87,6 +115,12 @@ fn register_event_handlers(app: &mut App) {
         .add_event::<OpenGlyphEditorEvent>()
         .add_event::<CycleCodepointEvent>()
         .add_event::<CreateContourEvent>()
+        .add_event::<BuildCompositesEvent>()
+        .init_resource::<LastCompositeBuild>()
+        .add_event::<LoadGlyphSetEvent>()
+        .add_event::<AddGlyphSetPlaceholdersEvent>()
+        .init_resource::<TargetGlyphSet>()
+        .add_systems(Startup, load_cli_glyph_set)
         .add_systems(
             Update,
             (
@@ -102,11 +136,14 @@ fn register_event_handlers(app: &mut App) {
                 handle_codepoint_cycling,
                 handle_save_shortcuts,
                 handle_checkerboard_toggle,
+                handle_measurement_grid_toggle,
+                handle_build_composites,
+                (handle_load_glyph_set, handle_add_glyph_set_placeholders).chain(),
             ),
         );
 }
 
-fn handle_open_file(
+pub fn handle_open_file(
     mut events: EventReader<OpenFileEvent>,
     mut app_state: Option<ResMut<AppState>>,
 ) {
@@ -126,7 +163,7 @@ fn handle_open_file(
     }
 }
 
-fn handle_save_file(
+pub fn handle_save_file(
     mut events: EventReader<SaveFileEvent>,
     mut app_state: Option<ResMut<AppState>>,
 ) {
@@ -146,7 +183,7 @@ fn handle_save_file(
     }
 }
 
-fn handle_save_file_as(
+pub fn handle_save_file_as(
     mut events: EventReader<SaveFileAsEvent>,
     mut app_state: Option<ResMut<AppState>>,
 ) {
@@ -342,14 +379,7 @@ pub fn handle_save_shortcuts(
     keyboard: Res<ButtonInput<KeyCode>>,
     mut save_event: EventWriter<SaveFileEvent>,
 ) {
-    // Check for Command (macOS) or Control (Windows/Linux)
-    let modifier_pressed = keyboard.pressed(KeyCode::SuperLeft)
-        || keyboard.pressed(KeyCode::SuperRight)
-        || keyboard.pressed(KeyCode::ControlLeft)
-        || keyboard.pressed(KeyCode::ControlRight);
-
-    // If modifier is pressed and S is just pressed, trigger save
-    if modifier_pressed && keyboard.just_pressed(KeyCode::KeyS) {
+    if Shortcut::Save.just_pressed(&keyboard) {
         debug!("Detected Command+S / Ctrl+S key combination, saving font");
         save_event.write(SaveFileEvent);
     }
@@ -388,14 +418,7 @@ pub fn handle_checkerboard_toggle(
     keyboard: Res<ButtonInput<KeyCode>>,
     mut checkerboard_enabled: ResMut<CheckerboardEnabled>,
 ) {
-    // Check for Command (macOS) or Control (Windows/Linux)
-    let modifier_pressed = keyboard.pressed(KeyCode::SuperLeft)
-        || keyboard.pressed(KeyCode::SuperRight)
-        || keyboard.pressed(KeyCode::ControlLeft)
-        || keyboard.pressed(KeyCode::ControlRight);
-
-    // If modifier is pressed and G is just pressed, toggle checkerboard
-    if modifier_pressed && keyboard.just_pressed(KeyCode::KeyG) {
+    if Shortcut::Checkerboard.just_pressed(&keyboard) {
         checkerboard_enabled.enabled = !checkerboard_enabled.enabled;
         let status = if checkerboard_enabled.enabled {
             "enabled"
@@ -409,3 +432,153 @@ pub fn handle_checkerboard_toggle(
         );
     }
 }
+
+/// Cmd/Ctrl+' shows or hides the measurement grid; the change is saved with
+/// the other preferences
+pub fn handle_measurement_grid_toggle(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<BezySettings>,
+) {
+    if Shortcut::MeasurementGrid.just_pressed(&keyboard) {
+        let grid = &mut settings.grid;
+        grid.show_measurement_grid = !grid.show_measurement_grid;
+        debug!("Measurement grid shown: {}", grid.show_measurement_grid);
+    }
+}
+
+fn handle_build_composites(
+    mut events: EventReader<BuildCompositesEvent>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<crate::editing::selection::events::AppStateChanged>,
+    mut last_build: ResMut<LastCompositeBuild>,
+    #[cfg(feature = "tui")] tui_comm: Option<Res<crate::core::tui_communication::TuiCommunication>>,
+) {
+    for event in events.read() {
+        let Some(state) = app_state.as_mut() else {
+            warn!("Build composites requested but no font is loaded");
+            continue;
+        };
+
+        let recipes = crate::data::glyph_recipes::builtin_recipes();
+        let report = crate::data::glyph_recipes::build_composites(
+            &mut state.workspace.font,
+            &recipes,
+            event.glyph_names.as_deref(),
+        );
+
+        let summary = format!(
+            "Built {} composite glyph(s), {} could not be built",
+            report.built.len(),
+            report.skipped.len()
+        );
+        info!("{}", summary);
+        for (name, reason) in &report.skipped {
+            warn!("Could not build '{}': {}", name, reason);
+        }
+
+        #[cfg(feature = "tui")]
+        if let Some(tui) = &tui_comm {
+            tui.send_log(summary);
+            for (name, reason) in &report.skipped {
+                tui.send_log(format!("  {name}: {reason}"));
+            }
+        }
+
+        if !report.built.is_empty() {
+            app_state_changed.write(crate::editing::selection::events::AppStateChanged);
+        }
+        last_build.0 = Some(report);
+    }
+}
+
+/// Loads the glyph set given with --glyph-set
+fn load_cli_glyph_set(
+    cli_args: Option<Res<crate::core::config::CliArgs>>,
+    mut events: EventWriter<LoadGlyphSetEvent>,
+) {
+    if let Some(source) = cli_args.and_then(|args| args.glyph_set.clone()) {
+        events.write(LoadGlyphSetEvent { source });
+    }
+}
+
+fn handle_load_glyph_set(
+    mut events: EventReader<LoadGlyphSetEvent>,
+    mut target: ResMut<TargetGlyphSet>,
+    app_state: Option<Res<AppState>>,
+    #[cfg(feature = "tui")] tui_comm: Option<Res<crate::core::tui_communication::TuiCommunication>>,
+) {
+    for event in events.read() {
+        let set = match crate::data::glyph_sets::GlyphSet::load(&event.source) {
+            Ok(set) => set,
+            Err(error) => {
+                let message = format!("Could not load glyph set: {error:#}");
+                warn!("{}", message);
+                #[cfg(feature = "tui")]
+                if let Some(tui) = &tui_comm {
+                    tui.send_log(message);
+                }
+                continue;
+            }
+        };
+
+        let summary = match app_state.as_ref() {
+            Some(state) => {
+                let coverage = set.summary(&state.workspace.font);
+                format!(
+                    "Glyph set '{}': {:.0}% done, {} empty, {} missing",
+                    set.name,
+                    coverage.percent(),
+                    coverage.empty,
+                    coverage.missing
+                )
+            }
+            None => format!("Glyph set '{}': {} glyphs", set.name, set.entries.len()),
+        };
+        info!("{}", summary);
+
+        #[cfg(feature = "tui")]
+        if let Some(tui) = &tui_comm {
+            tui.send_log(summary);
+        }
+
+        target.0 = Some(set);
+    }
+}
+
+fn handle_add_glyph_set_placeholders(
+    mut events: EventReader<AddGlyphSetPlaceholdersEvent>,
+    target: Res<TargetGlyphSet>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<crate::editing::selection::events::AppStateChanged>,
+    #[cfg(feature = "tui")] tui_comm: Option<Res<crate::core::tui_communication::TuiCommunication>>,
+) {
+    for _ in events.read() {
+        let Some(set) = &target.0 else {
+            warn!("No glyph set to add placeholders for; start with --glyph-set");
+            continue;
+        };
+        let Some(state) = app_state.as_mut() else {
+            warn!("Adding glyph set placeholders requested but no font is loaded");
+            continue;
+        };
+
+        // Placeholders get half an em, like a typical lowercase advance
+        let advance_width = (state.workspace.info.units_per_em / 2.0).round();
+        let added = set.add_placeholders(&mut state.workspace.font, advance_width);
+        let summary = format!(
+            "Added {} empty placeholder glyph(s) for glyph set '{}'",
+            added.len(),
+            set.name
+        );
+        info!("{}", summary);
+
+        #[cfg(feature = "tui")]
+        if let Some(tui) = &tui_comm {
+            tui.send_log(summary);
+        }
+
+        if !added.is_empty() {
+            app_state_changed.write(crate::editing::selection::events::AppStateChanged);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! Compiled binary of the open font for shaping previews
+//!
+//! Real OpenType shaping needs GSUB/GPOS tables, which only exist once the
+//! UFO has been compiled. While shaping is switched on, the font is
+//! recompiled with fontc on a background thread shortly after glyphs,
+//! metrics or kerning stop changing, and the latest successful build is kept here together with its
+//! glyph names and feature tags.
+//!
+//! The web build can't run fontc (see `ensure_can_compile`), so it never
+//! compiles and its text buffers show each character's cmap glyph.
+
+use crate::core::state::{AppState, FontData, FontInfo};
+use crate::data::compile_cache::ensure_can_compile;
+use crate::data::sfnt;
+use crate::editing::compile_errors::{CompileBuild, CompileErrors};
+use crate::editing::font_changes::{GlyphEdited, KerningChanged, MetricsChanged};
+use crate::systems::text_shaping::ShapingSettings;
+use anyhow::{anyhow, Context, Result};
+use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task};
+use std::sync::Arc;
+
+/// Seconds without edits before the font is recompiled
+const RECOMPILE_DELAY_SECS: f32 = 1.0;
+
+/// One successful compilation
+pub struct CompiledFontData {
+    /// The compiled TrueType binary
+    pub bytes: Vec<u8>,
+    /// Source glyph names in glyph ID order
+    pub glyph_names: Vec<String>,
+    /// Compiled advance widths in glyph ID order
+    pub advances: Vec<u16>,
+    /// Feature tags with substitution lookups
+    pub gsub_features: Vec<String>,
+    /// Feature tags with positioning lookups
+    pub gpos_features: Vec<String>,
+}
+
+impl CompiledFontData {
+    /// Every feature tag in the font, sorted and without duplicates
+    pub fn features(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .gsub_features
+            .iter()
+            .chain(&self.gpos_features)
+            .cloned()
+            .collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+}
+
+/// The latest compiled binary and the state of the next compilation
+#[derive(Resource, Default)]
+pub struct CompiledFont {
+    /// Latest successful build, kept while a newer one compiles
+    pub data: Option<Arc<CompiledFontData>>,
+    /// Why the last compilation failed, cleared by the next success
+    pub error: Option<String>,
+    /// Time of the first edit not yet included in a build
+    stale_since: Option<f32>,
+    task: Option<Task<Result<CompiledFontData>>>,
+}
+
+impl CompiledFont {
+    pub fn is_compiling(&self) -> bool {
+        self.task.is_some()
+    }
+}
+
+pub struct CompiledFontPlugin;
+
+impl Plugin for CompiledFontPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CompiledFont>().add_systems(
+            Update,
+            (
+                mark_compiled_font_stale,
+                start_font_compilation,
+                finish_font_compilation,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Note edits that the compiled binary doesn't include yet
+fn mark_compiled_font_stale(
+    mut glyph_edits: EventReader<GlyphEdited>,
+    mut metrics_changes: EventReader<MetricsChanged>,
+    mut kerning_changes: EventReader<KerningChanged>,
+    app_state: Option<Res<AppState>>,
+    time: Res<Time>,
+    mut compiled: ResMut<CompiledFont>,
+) {
+    let edited = glyph_edits.read().count()
+        + metrics_changes.read().count()
+        + kerning_changes.read().count()
+        > 0;
+    if app_state.is_none() {
+        return;
+    }
+    let never_built = compiled.data.is_none() && compiled.error.is_none();
+    if (edited || never_built) && compiled.stale_since.is_none() {
+        compiled.stale_since = Some(time.elapsed_secs());
+    }
+}
+
+/// Start a background build once editing has paused
+fn start_font_compilation(
+    app_state: Option<Res<AppState>>,
+    settings: Res<ShapingSettings>,
+    time: Res<Time>,
+    mut compiled: ResMut<CompiledFont>,
+) {
+    let Some(app_state) = app_state else {
+        return;
+    };
+    let Some(stale_since) = compiled.stale_since else {
+        return;
+    };
+    if !settings.enabled
+        || cfg!(target_arch = "wasm32")
+        || compiled.is_compiling()
+        || time.elapsed_secs() - stale_since < RECOMPILE_DELAY_SECS
+        || app_state.workspace.font.glyphs.is_empty()
+    {
+        return;
+    }
+
+    let font = app_state.workspace.font.clone();
+    let info = app_state.workspace.info.clone();
+    debug!("Compiling font for shaping");
+    compiled.stale_since = None;
+    compiled.task =
+        Some(AsyncComputeTaskPool::get().spawn(async move { compile_font(&font, &info) }));
+}
+
+/// Pick up a finished background build, attaching its errors to glyphs if
+/// it failed
+fn finish_font_compilation(
+    mut compiled: ResMut<CompiledFont>,
+    mut compile_errors: ResMut<CompileErrors>,
+    app_state: Option<Res<AppState>>,
+) {
+    // Polled every frame, so only a finished build counts as a change
+    let Some(task) = compiled.bypass_change_detection().task.as_mut() else {
+        return;
+    };
+    let Some(result) = block_on(future::poll_once(task)) else {
+        return;
+    };
+    compiled.task = None;
+    match result {
+        Ok(data) => {
+            info!(
+                "Compiled font for shaping: {} glyphs, features {:?}",
+                data.glyph_names.len(),
+                data.features()
+            );
+            compiled.data = Some(Arc::new(data));
+            compiled.error = None;
+            compile_errors.clear(CompileBuild::Shaping);
+        }
+        Err(e) => {
+            let error = format!("{e:#}");
+            compile_errors.report(CompileBuild::Shaping, &error, app_state.as_deref());
+            compiled.error = Some(error);
+        }
+    }
+}
+
+/// Compile the in-memory font with fontc in a temporary directory
+fn compile_font(font: &FontData, info: &FontInfo) -> Result<CompiledFontData> {
+    ensure_can_compile()?;
+    let temp_dir = tempfile::TempDir::new().context("Failed to create build directory")?;
+    let ufo_path = temp_dir.path().join("Shaping.ufo");
+    let build_dir = temp_dir.path().join("build");
+    std::fs::create_dir_all(&build_dir).context("Failed to create build directory")?;
+
+    // Features aren't part of the edited font data, so take them from the
+    // source UFO
+    let mut ufo = font.to_norad_font(info);
+    if let Some(path) = font.path.as_ref().filter(|path| path.exists()) {
+        let request = norad::DataRequest::none().features(true);
+        let source = norad::Font::load_requested_data(path, request)
+            .with_context(|| format!("Failed to read features from {}", path.display()))?;
+        ufo.features = source.features;
+    }
+    ufo.save(&ufo_path).context("Failed to write UFO for compilation")?;
+
+    let input = fontc::Input::new(&ufo_path).map_err(|e| anyhow!("Invalid fontc input: {e}"))?;
+    let mut flags = fontc::Flags::default();
+    // Shaped glyph IDs are mapped back to sorts by name, so keep the
+    // source names instead of production names
+    flags.remove(fontc::Flags::PRODUCTION_NAMES);
+    let bytes = fontc::generate_font(&input, &build_dir, None, flags, false)
+        .map_err(|e| anyhow!("fontc failed: {e}"))?;
+
+    let font = sfnt::read_font(&bytes).context("Failed to read compiled font")?;
+    let glyph_names = sfnt::glyph_names(&font)?;
+    let advances = sfnt::advance_widths(&font)?;
+    let gsub_features = sfnt::gsub_features(&font)?;
+    let gpos_features = sfnt::gpos_features(&font)?;
+    Ok(CompiledFontData {
+        bytes,
+        glyph_names,
+        advances,
+        gsub_features,
+        gpos_features,
+    })
+}
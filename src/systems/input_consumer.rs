@@ -494,6 +494,9 @@ pub struct MeasureInputConsumer {
     pub shift_locked: bool,
     /// Intersection points for visualization
     pub intersections: Vec<Vec2>,
+    /// Whether the measurement stays on the canvas after the button is
+    /// released, until the next one starts
+    pub persistent: bool,
 }
 
 /// The state of the measure gesture
@@ -510,7 +513,9 @@ impl InputConsumer for MeasureInputConsumer {
     fn should_handle_input(&self, event: &InputEvent, input_state: &InputState) -> bool {
         let is_right_event = matches!(
             event,
-            InputEvent::MouseClick { .. } | InputEvent::MouseDrag { .. }
+            InputEvent::MouseClick { .. }
+                | InputEvent::MouseDrag { .. }
+                | InputEvent::MouseRelease { .. }
         );
         let is_measure_mode = helpers::is_input_mode(input_state, InputMode::Measure);
 
@@ -581,8 +586,10 @@ impl InputConsumer for MeasureInputConsumer {
                     }
 
                     // Reset state immediately after measurement
-                    self.gesture = MeasureGestureState::Ready;
-                    self.intersections.clear();
+                    if !self.persistent {
+                        self.gesture = MeasureGestureState::Ready;
+                        self.intersections.clear();
+                    }
                 }
             }
             _ => {}
@@ -715,29 +722,23 @@ pub fn process_selection_events(
     active_sort_state: Res<crate::editing::sort::ActiveSortState>,
     sort_point_entities: Query<&crate::editing::sort::manager::SortPointEntity>,
     mut selection_state: ResMut<SelectionState>,
-    camera_query: Query<&Projection, With<crate::rendering::cameras::DesignCamera>>,
+    camera_scale: Res<crate::rendering::zoom_aware_scaling::CameraResponsiveScale>,
+    settings: Res<crate::core::config::BezySettings>,
+    sort_drag: Option<Res<crate::rendering::sort_visuals::SortHandleDragState>>,
 ) {
     if selection_consumer.pending_events.is_empty() {
         return;
     }
 
-    // Get camera scale for zoom-aware selection margin
-    // TODO: This zoom-aware margin calculation is overly complex. Consider simpler approaches:
-    // - Use screen-space selection instead of world-space
-    // - Make SELECTION_MARGIN a configurable setting
-    // - Use a fixed screen-pixel threshold with ray casting
-    let camera_scale = camera_query
-        .get_single()
-        .ok()
-        .and_then(|proj| {
-            if let Projection::Orthographic(ortho) = proj {
-                Some(ortho.scale)
-            } else {
-                None
-            }
-        })
-        .unwrap_or(1.0);
-    let zoom_aware_margin = crate::editing::selection::events::SELECTION_MARGIN * camera_scale;
+    // A click on a sort handle selects and drags sorts, not points
+    if sort_drag.is_some_and(|drag| drag.dragging_sort.is_some()) {
+        selection_consumer.pending_events.clear();
+        return;
+    }
+
+    // The margin is in logical pixels, so it is as wide on screen at any
+    // zoom and on monitors of any DPI
+    let zoom_aware_margin = camera_scale.hit_tolerance(&settings);
 
     // Process all pending events
     let events = std::mem::take(&mut selection_consumer.pending_events);
@@ -855,7 +856,15 @@ impl Plugin for InputConsumerPlugin {
             .init_resource::<TextInputConsumer>()
             .init_resource::<CameraInputConsumer>()
             .init_resource::<MeasureInputConsumer>()
-            .add_systems(Update, (process_input_events, process_selection_events));
+            .add_systems(
+                Update,
+                (
+                    process_input_events,
+                    process_selection_events.after(
+                        crate::rendering::sort_visuals::handle_sort_selection_and_drag_start,
+                    ),
+                ),
+            );
 
         debug!("[INPUT CONSUMER] InputConsumerPlugin registration complete");
     }
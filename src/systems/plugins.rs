@@ -11,6 +11,9 @@ use crate::editing::sort::SortPlugin;
 use crate::ui::themes::CurrentTheme;
 
 /// Configure logging with performance optimization for release builds
+///
+/// Native builds also write the log to a file and cap the chattiest
+/// subsystems at their preferred verbosity (see `logging::app_log_layers`).
 pub fn configure_logging() -> LogPlugin {
     #[cfg(debug_assertions)]
     {
@@ -18,7 +21,12 @@ pub fn configure_logging() -> LogPlugin {
         // Silence entity despawn warnings as they're expected in ECS
         LogPlugin {
             level: Level::INFO,
-            filter: "bezy=info,bevy_render=warn,bevy_winit=warn,wgpu=warn,winit=warn,bevy_ecs::error::handler=error".to_string(),
+            filter: base_filter(
+                "bezy=info,bevy_render=warn,bevy_winit=warn,wgpu=warn,winit=warn,\
+                 bevy_ecs::error::handler=error",
+            ),
+            #[cfg(not(target_arch = "wasm32"))]
+            custom_layer: crate::logging::app_log_layers,
             ..default()
         }
     }
@@ -29,13 +37,26 @@ pub fn configure_logging() -> LogPlugin {
         // Silence entity despawn warnings as they're expected in ECS
         LogPlugin {
             level: Level::WARN,
-            filter: "bezy=warn,bevy=warn,wgpu=error,winit=error,bevy_ecs::error::handler=error"
-                .to_string(),
+            filter: base_filter(
+                "bezy=warn,bevy=warn,wgpu=error,winit=error,bevy_ecs::error::handler=error",
+            ),
+            #[cfg(not(target_arch = "wasm32"))]
+            custom_layer: crate::logging::app_log_layers,
             ..default()
         }
     }
 }
 
+/// `filter` letting the runtime-filtered subsystems through, on native
+/// builds where the runtime filter is set up
+fn base_filter(filter: &str) -> String {
+    if cfg!(target_arch = "wasm32") {
+        filter.to_string()
+    } else {
+        crate::logging::verbosity::with_subsystem_targets(filter)
+    }
+}
+
 /// Configure default Bevy plugins for the application
 #[allow(dead_code)]
 pub fn configure_default_plugins() -> bevy::app::PluginGroupBuilder {
@@ -0,0 +1,373 @@
+//! Glyph alternates picker at the text cursor
+//!
+//! Pressing Tab while typing opens a small popup listing the alternates of
+//! the glyph before the cursor: the glyphs sharing its base name with a
+//! suffix such as `.ss01`, `.alt` or `.fina`. Up/Down (or Tab/Shift+Tab)
+//! move through the list, Enter or a click swaps the sort, Escape closes.
+//!
+//! An alternate goes into the buffer as a glyph placed by name, so shaping
+//! leaves it alone; picking the base glyph again restores its codepoint.
+
+#![allow(clippy::too_many_arguments)]
+
+use crate::core::state::text_editor::text_buffer::{ActiveTextBuffer, BufferCursor, TextBuffer};
+use crate::core::state::text_editor::SortKind;
+use crate::core::state::{AppState, FontData, SortLayoutMode, TextEditorState};
+use crate::rendering::cameras::DesignCamera;
+use crate::systems::sorts::sort_entities::{BufferSortEntities, BufferSortRespawnQueue};
+use crate::systems::sorts::spacing_mode::SpacingMode;
+use crate::systems::ui_interaction::UiHoverState;
+use crate::ui::edit_mode_toolbar::text::TextPlacementMode;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use crate::ui::theme::*;
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::{AssetServerFontExt, EmbeddedFonts};
+use bevy::prelude::*;
+
+/// Padding inside each option row
+const OPTION_PADDING: f32 = 4.0;
+
+/// Open picker and its options
+#[derive(Resource, Default, Debug)]
+pub struct AlternatesPicker {
+    /// Buffer index of the sort being swapped, while the picker is open
+    pub target: Option<usize>,
+    /// Candidate glyph names, sorted, base glyph included
+    pub options: Vec<String>,
+    /// Highlighted option
+    pub selected: usize,
+}
+
+impl AlternatesPicker {
+    pub fn is_open(&self) -> bool {
+        self.target.is_some()
+    }
+
+    fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Move the highlight, wrapping around the list
+    fn step(&mut self, forwards: bool) {
+        let count = self.options.len().max(1);
+        self.selected = if forwards {
+            (self.selected + 1) % count
+        } else {
+            (self.selected + count - 1) % count
+        };
+    }
+}
+
+/// Marker for the popup node
+#[derive(Component, Default)]
+pub struct AlternatesPopup;
+
+/// One option row in the popup
+#[derive(Component)]
+pub struct AlternateOption(usize);
+
+/// The glyph and its suffixed alternates, or nothing if it has none
+///
+/// `a.ss01` and `a` both list `a`, `a.alt`, `a.ss01`..., so a swapped
+/// sort can go back to its base glyph.
+pub fn glyph_alternates(font: &FontData, glyph_name: &str) -> Vec<String> {
+    let base = glyph_name.split('.').next().unwrap_or_default();
+    if base.is_empty() {
+        return Vec::new();
+    }
+    let prefix = format!("{base}.");
+    let mut options: Vec<String> = font
+        .glyphs
+        .keys()
+        .filter(|name| name.as_str() == base || name.starts_with(&prefix))
+        .cloned()
+        .collect();
+    if options.len() < 2 {
+        return Vec::new();
+    }
+    options.sort();
+    options
+}
+
+/// Buffer index of the glyph sort just before the active buffer's cursor
+fn sort_before_cursor(
+    text_editor_state: &TextEditorState,
+    active_buffer: &ActiveTextBuffer,
+    buffer_query: &Query<(&TextBuffer, &BufferCursor)>,
+) -> Option<usize> {
+    let (buffer, cursor) = buffer_query.get(active_buffer.buffer_entity?).ok()?;
+    if buffer.layout_mode == SortLayoutMode::Freeform {
+        return None;
+    }
+    let indices: Vec<usize> = (0..text_editor_state.buffer.len())
+        .filter(|&index| {
+            text_editor_state
+                .buffer
+                .get(index)
+                .is_some_and(|sort| sort.buffer_id == Some(buffer.id))
+        })
+        .collect();
+    let index = *indices.get(cursor.position.checked_sub(1)?)?;
+    let sort = text_editor_state.buffer.get(index)?;
+    (!sort.kind.is_line_break()).then_some(index)
+}
+
+/// Replace a sort's glyph, taking the glyph's own codepoint if it has one
+fn swap_sort_glyph(
+    text_editor_state: &mut TextEditorState,
+    app_state: &AppState,
+    index: usize,
+    glyph_name: &str,
+    respawn_queue: &mut BufferSortRespawnQueue,
+) {
+    let Some(glyph) = app_state.workspace.font.get_glyph(glyph_name) else {
+        return;
+    };
+    let Some(sort) = text_editor_state.buffer.get_mut(index) else {
+        return;
+    };
+    sort.kind = SortKind::Glyph {
+        codepoint: glyph.unicode_values.first().copied(),
+        glyph_name: glyph_name.to_string(),
+        advance_width: glyph.advance_width as f32,
+    };
+    // Glyphs placed by name aren't shaped, so nothing positions them
+    sort.placement_offset = Vec2::ZERO;
+    respawn_queue.indices.push(index);
+    info!("Swapped sort {} to '{}'", index, glyph_name);
+}
+
+/// Open the picker with Tab, then navigate, apply or close it
+pub fn handle_alternates_picker_keys(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    ui_hover_state: Res<UiHoverState>,
+    current_tool: Res<CurrentTool>,
+    placement_mode: Res<TextPlacementMode>,
+    mut picker: ResMut<AlternatesPicker>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    app_state: Option<Res<AppState>>,
+    active_buffer: Option<Res<ActiveTextBuffer>>,
+    buffer_query: Query<(&TextBuffer, &BufferCursor)>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+    spacing_mode: Res<SpacingMode>,
+) {
+    let typing = current_tool.get_current() == Some("text")
+        && *placement_mode != TextPlacementMode::Freeform;
+    let Some(app_state) = app_state.filter(|_| typing) else {
+        if picker.is_open() {
+            picker.close();
+        }
+        return;
+    };
+
+    let Some(target) = picker.target else {
+        if !keyboard.just_pressed(KeyCode::Tab) || spacing_mode.active {
+            return;
+        }
+        keyboard.clear_just_pressed(KeyCode::Tab);
+        let Some(index) = active_buffer
+            .as_ref()
+            .and_then(|active| sort_before_cursor(&text_editor_state, active, &buffer_query))
+        else {
+            return;
+        };
+        let current = text_editor_state.buffer.get(index).map(|sort| sort.kind.glyph_name());
+        let options = glyph_alternates(&app_state.workspace.font, current.unwrap_or_default());
+        if options.is_empty() {
+            info!("'{}' has no alternates", current.unwrap_or_default());
+            return;
+        }
+        let selected = options
+            .iter()
+            .position(|name| Some(name.as_str()) == current)
+            .unwrap_or(0);
+        *picker = AlternatesPicker {
+            target: Some(index),
+            options,
+            selected,
+        };
+        return;
+    };
+
+    if mouse_button_input.just_pressed(MouseButton::Left) && !ui_hover_state.is_hovering_ui {
+        picker.close();
+        return;
+    }
+
+    let shift = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        picker.step(true);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        picker.step(false);
+    }
+    if keyboard.just_pressed(KeyCode::Tab) {
+        picker.step(!shift);
+    }
+    if keyboard.just_pressed(KeyCode::Enter) {
+        let name = picker.options[picker.selected].clone();
+        swap_sort_glyph(&mut text_editor_state, &app_state, target, &name, &mut respawn_queue);
+        picker.close();
+    }
+    if keyboard.just_pressed(KeyCode::Escape) {
+        picker.close();
+    }
+    // Keep these keys from moving the cursor or leaving the text tool
+    for key in [
+        KeyCode::ArrowDown,
+        KeyCode::ArrowUp,
+        KeyCode::Tab,
+        KeyCode::Enter,
+        KeyCode::Escape,
+    ] {
+        keyboard.clear_just_pressed(key);
+    }
+}
+
+/// Swap to an option when its row is clicked
+pub fn handle_alternate_option_clicks(
+    interaction_query: Query<(&Interaction, &AlternateOption), Changed<Interaction>>,
+    mut picker: ResMut<AlternatesPicker>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    app_state: Option<Res<AppState>>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+) {
+    let (Some(app_state), Some(target)) = (app_state, picker.target) else {
+        return;
+    };
+    for (interaction, option) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if let Some(name) = picker.options.get(option.0).cloned() {
+            swap_sort_glyph(&mut text_editor_state, &app_state, target, &name, &mut respawn_queue);
+        }
+        picker.close();
+        return;
+    }
+}
+
+/// Show the popup under the target sort
+pub fn render_alternates_popup(
+    mut commands: Commands,
+    picker: Res<AlternatesPicker>,
+    mut popup_query: Query<(Entity, &mut Node), With<AlternatesPopup>>,
+    buffer_entities: Res<BufferSortEntities>,
+    sort_transforms: Query<&GlobalTransform>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<DesignCamera>>,
+    app_state: Option<Res<AppState>>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    let rebuild = picker.is_changed() || popup_query.is_empty();
+    if picker.is_changed() {
+        for (entity, _) in popup_query.iter() {
+            commands.entity(entity).despawn();
+        }
+    }
+    let Some(target) = picker.target else {
+        return;
+    };
+
+    // Anchor the popup below the sort's baseline
+    let descender = app_state
+        .as_ref()
+        .and_then(|state| state.workspace.info.metrics.descender)
+        .unwrap_or(-256.0) as f32;
+    let screen = buffer_entities
+        .entities
+        .get(&target)
+        .and_then(|&entity| sort_transforms.get(entity).ok())
+        .zip(camera_query.single().ok())
+        .and_then(|(sort, (camera, camera_transform))| {
+            let anchor = sort.translation() + Vec3::new(0.0, descender, 0.0);
+            camera.world_to_viewport(camera_transform, anchor).ok()
+        });
+    let Some(screen) = screen else {
+        return;
+    };
+
+    if !rebuild {
+        for (_, mut node) in popup_query.iter_mut() {
+            node.left = Val::Px(screen.x);
+            node.top = Val::Px(screen.y);
+        }
+        return;
+    }
+
+    let position = UiRect {
+        left: Val::Px(screen.x),
+        top: Val::Px(screen.y),
+        right: Val::Auto,
+        bottom: Val::Auto,
+    };
+    commands
+        .spawn(create_widget_style(
+            &asset_server,
+            &theme,
+            PositionType::Absolute,
+            position,
+            AlternatesPopup,
+            "AlternatesPopup",
+        ))
+        .with_children(|parent| {
+            for (index, name) in picker.options.iter().enumerate() {
+                let is_selected = index == picker.selected;
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            padding: UiRect::all(Val::Px(OPTION_PADDING)),
+                            ..default()
+                        },
+                        BackgroundColor(if is_selected {
+                            theme.theme().button_pressed()
+                        } else {
+                            theme.theme().button_regular()
+                        }),
+                        AlternateOption(index),
+                    ))
+                    .with_children(|row| {
+                        row.spawn((
+                            Text::new(name.clone()),
+                            TextFont {
+                                font: asset_server.load_font_with_fallback(
+                                    theme.theme().mono_font_path(),
+                                    &embedded_fonts,
+                                ),
+                                font_size: WIDGET_TEXT_FONT_SIZE,
+                                ..default()
+                            },
+                            TextColor(theme.get_ui_text_primary()),
+                        ));
+                    });
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::state::GlyphData;
+
+    fn font(names: &[&str]) -> FontData {
+        let mut font = FontData::default();
+        for name in names {
+            let glyph = GlyphData::test(name, 500.0);
+            font.glyphs.insert(name.to_string(), glyph);
+        }
+        font
+    }
+
+    #[test]
+    fn test_alternates_share_base_name() {
+        let font = font(&["a", "a.ss01", "a.fina", "ae", "b"]);
+        let expected = vec!["a", "a.fina", "a.ss01"];
+        assert_eq!(glyph_alternates(&font, "a"), expected);
+        assert_eq!(glyph_alternates(&font, "a.ss01"), expected);
+        assert!(glyph_alternates(&font, "b").is_empty());
+        assert!(glyph_alternates(&font, ".notdef").is_empty());
+    }
+}
@@ -0,0 +1,253 @@
+//! Text buffer layout, in the background for long buffers
+//!
+//! Laying out a buffer wraps its lines and runs the bidi algorithm over all
+//! of its sorts. Buffers shorter than `BACKGROUND_LAYOUT_MIN_SORTS` are laid
+//! out as soon as their text changes. Longer ones are laid out on the async
+//! compute pool, one task per buffer, so typing into a paragraph doesn't
+//! stall the frame: their sorts move when the task finishes, usually the
+//! next frame, and sorts typed in the meantime are spawned then. Text that
+//! changes again while it is being laid out starts over and the outdated
+//! task is dropped.
+
+use super::sort_entities::{BufferSortEntities, BufferSortIndex};
+use super::text_flow_positioning::flow_layout;
+use crate::core::state::text_editor::buffer::BufferId;
+use crate::core::state::text_editor::{
+    CompactText, SortData, SortLayoutMode, TextBuffer, TextFrame,
+};
+use crate::core::state::{text_line_height, AppState, TextEditorState};
+use crate::editing::font_changes::MetricsChanged;
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task};
+use std::collections::HashMap;
+
+/// Sorts a buffer needs before it is laid out in the background
+pub const BACKGROUND_LAYOUT_MIN_SORTS: usize = 256;
+
+/// World origins of sorts, by index in the editor's buffer
+type Origins = Vec<(usize, Vec2)>;
+
+/// Latest origin of every laid out sort and the layouts still running
+#[derive(Resource, Default)]
+pub struct BufferLayouts {
+    origins: HashMap<usize, Vec2>,
+    tasks: HashMap<BufferId, Task<Origins>>,
+}
+
+impl BufferLayouts {
+    /// Where the sort at `index` goes, or `None` until its buffer is laid out
+    pub fn origin(&self, index: usize) -> Option<Vec2> {
+        self.origins.get(&index).copied()
+    }
+}
+
+/// A buffer's sorts and frame, copied so it can be laid out on any thread
+struct LayoutJob {
+    indices: Vec<usize>,
+    text: CompactText,
+    root: Vec2,
+    line_height: f32,
+    layout_mode: SortLayoutMode,
+    frame: Option<TextFrame>,
+}
+
+impl LayoutJob {
+    /// Origins of the buffer's text sorts; freeform sorts keep their own
+    ///
+    /// Lines are laid out one after another, each expanded from the compact
+    /// text only while it is laid out.
+    fn run(self) -> Origins {
+        let mut origins = Vec::with_capacity(self.indices.len());
+        let mut line_origin = self.root;
+        for line in self.text.hard_lines() {
+            let sorts: Vec<SortData> = line
+                .clone()
+                .filter_map(|index| self.text.sort_data(index))
+                .collect();
+            let refs: Vec<&SortData> = sorts.iter().collect();
+            let layout = flow_layout(
+                &refs,
+                self.line_height,
+                &self.layout_mode,
+                self.frame.as_ref(),
+            );
+            for ((index, sort), origin) in line.zip(&sorts).zip(&layout.sort_origins) {
+                if sort.layout_mode != SortLayoutMode::Freeform {
+                    origins.push((self.indices[index], line_origin + *origin));
+                }
+            }
+            // The line break ends the line, wrapped or not, and the cursor
+            // after it sits where the next line starts
+            if let Some(next_line) = layout.cursors.last() {
+                line_origin.y += next_line.y;
+            }
+        }
+        origins
+    }
+}
+
+/// One job per text buffer, with its sorts in buffer order
+fn layout_jobs(
+    text_editor_state: &TextEditorState,
+    buffer_query: &Query<&TextBuffer>,
+    line_height: f32,
+) -> Vec<(BufferId, LayoutJob)> {
+    let mut jobs: HashMap<BufferId, LayoutJob> = buffer_query
+        .iter()
+        .map(|buffer| {
+            let job = LayoutJob {
+                indices: Vec::new(),
+                text: CompactText::default(),
+                root: buffer.root_position,
+                line_height,
+                layout_mode: buffer.layout_mode.clone(),
+                frame: buffer.frame.clone(),
+            };
+            (buffer.id, job)
+        })
+        .collect();
+    for (index, sort) in text_editor_state.buffer.iter().enumerate() {
+        let Some(job) = sort.buffer_id.and_then(|id| jobs.get_mut(&id)) else {
+            continue;
+        };
+        job.indices.push(index);
+        job.text.push(sort);
+    }
+    jobs.into_iter().collect()
+}
+
+/// Lay out buffers once their text or the line metrics change, short ones
+/// right away and long ones in the background
+pub fn layout_text_buffers(
+    text_editor_state: Res<TextEditorState>,
+    app_state: Option<Res<AppState>>,
+    buffer_query: Query<&TextBuffer>,
+    mut metrics_changes: EventReader<MetricsChanged>,
+    mut layouts: ResMut<BufferLayouts>,
+) {
+    let metrics_changed = metrics_changes.read().count() > 0;
+    if !text_editor_state.is_changed() && !metrics_changed {
+        return;
+    }
+
+    let line_height = text_line_height(app_state.as_deref());
+
+    // An edit to one buffer shifts the indices of every later sort, so
+    // nothing laid out before is kept. Dropping a running task cancels it
+    let layouts = layouts.as_mut();
+    layouts.origins.clear();
+    layouts.tasks.clear();
+    for (index, sort) in text_editor_state.buffer.iter().enumerate() {
+        if sort.layout_mode == SortLayoutMode::Freeform {
+            layouts.origins.insert(index, sort.root_position);
+        }
+    }
+
+    for (id, job) in layout_jobs(&text_editor_state, &buffer_query, line_height) {
+        if job.text.len() < BACKGROUND_LAYOUT_MIN_SORTS {
+            layouts.origins.extend(job.run());
+        } else {
+            debug!(
+                buffer = id.0,
+                sorts = job.text.len(),
+                glyphs = job.text.glyph_count(),
+                "Laying out buffer in the background"
+            );
+            let task = AsyncComputeTaskPool::get().spawn(async move { job.run() });
+            layouts.tasks.insert(id, task);
+        }
+    }
+}
+
+/// Pick up finished background layouts and move sorts to their origins
+pub fn apply_buffer_layouts(
+    mut layouts: ResMut<BufferLayouts>,
+    buffer_entities: Res<BufferSortEntities>,
+    mut sort_query: Query<&mut Transform, With<BufferSortIndex>>,
+    mut update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    // Polled every frame, so only finished layouts count as a change
+    let mut finished = Vec::new();
+    layouts.bypass_change_detection().tasks.retain(|_, task| {
+        match block_on(future::poll_once(task)) {
+            Some(origins) => {
+                finished.push(origins);
+                false
+            }
+            None => true,
+        }
+    });
+    if !finished.is_empty() {
+        layouts.origins.extend(finished.into_iter().flatten());
+    }
+    if !layouts.is_changed() {
+        return;
+    }
+
+    let mut moved = 0;
+    for (&index, &entity) in &buffer_entities.entities {
+        let (Some(origin), Ok(mut transform)) = (layouts.origin(index), sort_query.get_mut(entity))
+        else {
+            continue;
+        };
+        let translation = origin.extend(transform.translation.z);
+        if transform.translation != translation {
+            transform.translation = translation;
+            moved += 1;
+        }
+    }
+    if moved > 0 {
+        debug!(moved, "Moved buffer sorts to their layout");
+        update_tracker.needs_update = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::state::text_editor::SortKind;
+
+    fn glyph(codepoint: char, layout_mode: SortLayoutMode) -> SortData {
+        SortData {
+            kind: SortKind::Glyph {
+                codepoint: Some(codepoint),
+                glyph_name: codepoint.to_string(),
+                advance_width: 100.0,
+            },
+            layout_mode,
+            root_position: Vec2::new(-5000.0, 0.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_job_places_text_sorts_from_the_root() {
+        let line_break = SortData {
+            kind: SortKind::LineBreak,
+            ..Default::default()
+        };
+        let sorts = [
+            glyph('a', SortLayoutMode::LTRText),
+            glyph('b', SortLayoutMode::Freeform),
+            line_break,
+            glyph('c', SortLayoutMode::LTRText),
+        ];
+        let job = LayoutJob {
+            indices: vec![3, 4, 7, 8],
+            text: sorts.iter().collect(),
+            root: Vec2::new(10.0, 20.0),
+            line_height: 1000.0,
+            layout_mode: SortLayoutMode::LTRText,
+            frame: None,
+        };
+        assert_eq!(
+            job.run(),
+            vec![
+                (3, Vec2::new(10.0, 20.0)),
+                (7, Vec2::new(210.0, 20.0)),
+                (8, Vec2::new(10.0, -980.0)),
+            ]
+        );
+    }
+}
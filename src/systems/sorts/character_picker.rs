@@ -0,0 +1,449 @@
+//! Character picker for the text tool
+//!
+//! Cmd/Ctrl+Shift+U while typing opens a panel for finding characters in
+//! the Unicode Character Database. Typing searches character names (or
+//! takes a `U+XXXX` codepoint); with no query the panel browses a Unicode
+//! block, Left/Right stepping through the blocks. Rows the font has a glyph
+//! for are marked, and the highlighted character's script, category and
+//! block are shown below. Up/Down move, Enter or a click types the character
+//! at the cursor, Escape closes.
+
+#![allow(clippy::too_many_arguments)]
+
+use crate::core::state::text_editor::text_buffer::{ActiveTextBuffer, BufferCursor, TextBuffer};
+use crate::core::state::{AppState, FontData, TextEditorState};
+use crate::data::unicode_db::{self, CharInfo};
+use crate::io::shortcuts::Shortcut;
+use crate::systems::sorts::sort_entities::BufferSortRespawnQueue;
+use crate::systems::sorts::unicode_input::insert_text_at_buffer_cursor;
+use crate::systems::ui_interaction::UiHoverState;
+use crate::ui::edit_mode_toolbar::text::TextPlacementMode;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use crate::ui::theme::*;
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::{AssetServerFontExt, EmbeddedFonts};
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+/// Most characters a name search lists
+const SEARCH_LIMIT: usize = 200;
+
+/// Rows shown at once
+const VISIBLE_ROWS: usize = 12;
+
+/// Padding inside each character row
+const OPTION_PADDING: f32 = 4.0;
+
+/// Distance of the panel from the left and top of the window
+const PANEL_LEFT: Val = Val::Percent(30.0);
+const PANEL_TOP: Val = Val::Percent(15.0);
+
+/// Open picker, its query and the characters it lists
+#[derive(Resource, Default, Debug)]
+pub struct CharacterPicker {
+    pub open: bool,
+    /// Name search; the block is browsed while it is empty
+    pub query: String,
+    /// Index of the browsed block in `unicode_db::blocks()`
+    pub block_index: usize,
+    /// Characters of the block, or found by the query
+    pub chars: Vec<char>,
+    /// Highlighted character
+    pub selected: usize,
+}
+
+impl CharacterPicker {
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn close(&mut self) {
+        self.open = false;
+        self.query.clear();
+    }
+
+    /// List the characters for the query, or for the block
+    fn refresh(&mut self) {
+        self.chars = if self.query.trim().is_empty() {
+            unicode_db::blocks()
+                .get(self.block_index)
+                .map(|block| block.chars())
+                .unwrap_or_default()
+        } else {
+            unicode_db::search(&self.query, SEARCH_LIMIT)
+        };
+        self.selected = 0;
+    }
+
+    /// Browse the next or previous block, wrapping around
+    fn step_block(&mut self, forwards: bool) {
+        let count = unicode_db::blocks().len().max(1);
+        self.block_index = if forwards {
+            (self.block_index + 1) % count
+        } else {
+            (self.block_index + count - 1) % count
+        };
+        self.refresh();
+    }
+
+    /// Move the highlight, stopping at the ends of the list
+    fn step(&mut self, rows: isize) {
+        let last = self.chars.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(rows).min(last);
+    }
+
+    /// The first of the rows shown, keeping the highlight in view
+    fn first_visible(&self) -> usize {
+        let last_start = self.chars.len().saturating_sub(VISIBLE_ROWS);
+        self.selected
+            .saturating_sub(VISIBLE_ROWS / 2)
+            .min(last_start)
+    }
+}
+
+/// Marker for the picker panel
+#[derive(Component, Default)]
+pub struct CharacterPickerPanel;
+
+/// One character row in the panel, by index in the picker's list
+#[derive(Component)]
+pub struct CharacterOption(usize);
+
+/// Codepoints the font has a glyph for
+fn font_coverage(font: &FontData) -> HashSet<char> {
+    font.glyphs
+        .values()
+        .flat_map(|glyph| glyph.unicode_values.iter().copied())
+        .collect()
+}
+
+/// Open the picker with Cmd/Ctrl+Shift+U, then search, browse and type
+/// characters
+pub fn handle_character_picker_keys(
+    mut commands: Commands,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut key_events: EventReader<KeyboardInput>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    ui_hover_state: Res<UiHoverState>,
+    current_tool: Res<CurrentTool>,
+    placement_mode: Res<TextPlacementMode>,
+    mut picker: ResMut<CharacterPicker>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    app_state: Option<Res<AppState>>,
+    active_buffer: Option<Res<ActiveTextBuffer>>,
+    mut buffer_query: Query<(&TextBuffer, &mut BufferCursor)>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+) {
+    let typing = current_tool.get_current() == Some("text")
+        && *placement_mode != TextPlacementMode::Freeform;
+    if !typing {
+        key_events.clear();
+        if picker.is_open() {
+            picker.close();
+        }
+        return;
+    }
+
+    let modifier = keyboard.any_pressed([
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+    ]);
+    if !picker.is_open() {
+        key_events.clear();
+        if Shortcut::CharacterPicker.just_pressed(&keyboard) {
+            keyboard.clear_just_pressed(KeyCode::KeyU);
+            picker.open = true;
+            picker.refresh();
+        }
+        return;
+    }
+
+    if mouse_button_input.just_pressed(MouseButton::Left) && !ui_hover_state.is_hovering_ui {
+        picker.close();
+        return;
+    }
+
+    // Typed text goes into the query
+    let mut query_changed = false;
+    for event in key_events.read() {
+        if event.state != ButtonState::Pressed || modifier {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(text) => {
+                picker
+                    .query
+                    .extend(text.chars().filter(|c| !c.is_control()));
+                query_changed = true;
+            }
+            Key::Space => {
+                picker.query.push(' ');
+                query_changed = true;
+            }
+            Key::Backspace => {
+                query_changed |= picker.query.pop().is_some();
+            }
+            _ => {}
+        }
+    }
+    if query_changed {
+        picker.refresh();
+    }
+
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        picker.step(1);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        picker.step(-1);
+    }
+    if keyboard.just_pressed(KeyCode::PageDown) {
+        picker.step(VISIBLE_ROWS as isize);
+    }
+    if keyboard.just_pressed(KeyCode::PageUp) {
+        picker.step(-(VISIBLE_ROWS as isize));
+    }
+    if picker.query.is_empty() {
+        if keyboard.just_pressed(KeyCode::ArrowRight) {
+            picker.step_block(true);
+        }
+        if keyboard.just_pressed(KeyCode::ArrowLeft) {
+            picker.step_block(false);
+        }
+    }
+    if keyboard.just_pressed(KeyCode::Enter) {
+        if let Some(c) = picker.chars.get(picker.selected) {
+            insert_text_at_buffer_cursor(
+                &c.to_string(),
+                &mut commands,
+                &mut text_editor_state,
+                &app_state,
+                &placement_mode,
+                &active_buffer,
+                &mut buffer_query,
+                &mut respawn_queue,
+            );
+        }
+        picker.close();
+    }
+    if keyboard.just_pressed(KeyCode::Escape) {
+        picker.close();
+    }
+    // Keep these keys from moving the cursor or leaving the text tool
+    for key in [
+        KeyCode::ArrowDown,
+        KeyCode::ArrowUp,
+        KeyCode::ArrowLeft,
+        KeyCode::ArrowRight,
+        KeyCode::PageDown,
+        KeyCode::PageUp,
+        KeyCode::Enter,
+        KeyCode::Escape,
+        KeyCode::Backspace,
+    ] {
+        keyboard.clear_just_pressed(key);
+    }
+}
+
+/// Type a character when its row is clicked
+pub fn handle_character_option_clicks(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &CharacterOption), Changed<Interaction>>,
+    mut picker: ResMut<CharacterPicker>,
+    placement_mode: Res<TextPlacementMode>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    app_state: Option<Res<AppState>>,
+    active_buffer: Option<Res<ActiveTextBuffer>>,
+    mut buffer_query: Query<(&TextBuffer, &mut BufferCursor)>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+) {
+    for (interaction, option) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if let Some(c) = picker.chars.get(option.0) {
+            insert_text_at_buffer_cursor(
+                &c.to_string(),
+                &mut commands,
+                &mut text_editor_state,
+                &app_state,
+                &placement_mode,
+                &active_buffer,
+                &mut buffer_query,
+                &mut respawn_queue,
+            );
+        }
+        picker.close();
+        return;
+    }
+}
+
+/// A row's text: whether the font has the character, its codepoint, the
+/// character (marks on a dotted circle) and its name
+fn option_label(c: char, in_font: bool) -> String {
+    let mark = if in_font { "●" } else { "○" };
+    let shown = match unicode_db::general_category(c) {
+        "Mn" | "Mc" | "Me" => format!("◌{c}"),
+        category if category.starts_with(['C', 'Z']) => String::new(),
+        _ => c.to_string(),
+    };
+    let name = unicode_db::name(c).unwrap_or_default();
+    format!("{mark} U+{:04X} {shown:<2} {name}", c as u32)
+}
+
+/// Rebuild the panel when the picker or the font changes
+pub fn render_character_picker(
+    mut commands: Commands,
+    picker: Res<CharacterPicker>,
+    panel_query: Query<Entity, With<CharacterPickerPanel>>,
+    app_state: Option<Res<AppState>>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    let font_changed = app_state.as_ref().is_some_and(|state| state.is_changed());
+    if !picker.is_changed() && !font_changed {
+        return;
+    }
+    for entity in panel_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    if !picker.is_open() {
+        return;
+    }
+
+    let covered = app_state
+        .as_ref()
+        .map(|state| font_coverage(&state.workspace.font))
+        .unwrap_or_default();
+    let heading = if picker.query.is_empty() {
+        let block = unicode_db::blocks().get(picker.block_index);
+        let in_font = block.map_or(0, |block| {
+            covered.iter().filter(|c| block.contains(**c)).count()
+        });
+        format!(
+            "‹ {} › {}/{} in font",
+            block.map_or("", |block| block.name),
+            in_font,
+            picker.chars.len()
+        )
+    } else {
+        format!("Search: {}", picker.query)
+    };
+    let details = picker.chars.get(picker.selected).map(|c| {
+        let info = CharInfo::new(*c);
+        format!(
+            "{} · {} {} · {}",
+            info.script.replace('_', " "),
+            info.category,
+            unicode_db::category_name(info.category),
+            info.block.unwrap_or("No block")
+        )
+    });
+
+    let position = UiRect {
+        left: PANEL_LEFT,
+        top: PANEL_TOP,
+        right: Val::Auto,
+        bottom: Val::Auto,
+    };
+    let text_font = TextFont {
+        font: asset_server.load_font_with_fallback(theme.theme().mono_font_path(), &embedded_fonts),
+        font_size: WIDGET_TEXT_FONT_SIZE,
+        ..default()
+    };
+    commands
+        .spawn(create_widget_style(
+            &asset_server,
+            &theme,
+            PositionType::Absolute,
+            position,
+            CharacterPickerPanel,
+            "CharacterPickerPanel",
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(heading),
+                text_font.clone(),
+                TextColor(theme.get_ui_text_primary()),
+            ));
+            let first = picker.first_visible();
+            for (index, c) in picker
+                .chars
+                .iter()
+                .enumerate()
+                .skip(first)
+                .take(VISIBLE_ROWS)
+            {
+                let in_font = covered.contains(c);
+                let is_selected = index == picker.selected;
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            padding: UiRect::all(Val::Px(OPTION_PADDING)),
+                            ..default()
+                        },
+                        BackgroundColor(if is_selected {
+                            theme.theme().button_pressed()
+                        } else {
+                            theme.theme().button_regular()
+                        }),
+                        CharacterOption(index),
+                    ))
+                    .with_children(|row| {
+                        row.spawn((
+                            Text::new(option_label(*c, in_font)),
+                            text_font.clone(),
+                            TextColor(if in_font {
+                                theme.get_ui_text_primary()
+                            } else {
+                                theme.get_ui_text_secondary()
+                            }),
+                        ));
+                    });
+            }
+            if picker.chars.is_empty() {
+                parent.spawn((
+                    Text::new("No characters found"),
+                    text_font.clone(),
+                    TextColor(theme.get_ui_text_secondary()),
+                ));
+            }
+            if let Some(details) = details {
+                parent.spawn((
+                    Text::new(details),
+                    text_font.clone(),
+                    TextColor(theme.get_ui_text_secondary()),
+                ));
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_browse_and_search() {
+        let mut picker = CharacterPicker::default();
+        picker.refresh();
+        assert_eq!(picker.chars.first(), Some(&'\0'));
+        picker.step(200);
+        assert_eq!(picker.selected, 127);
+        assert_eq!(picker.first_visible(), 128 - VISIBLE_ROWS);
+
+        picker.step_block(false);
+        assert_eq!(
+            unicode_db::blocks()[picker.block_index].name,
+            "Supplementary Private Use Area-B"
+        );
+        picker.query = "sheen".to_string();
+        picker.refresh();
+        assert!(picker.chars.contains(&'ش'));
+        assert_eq!(picker.selected, 0);
+    }
+}
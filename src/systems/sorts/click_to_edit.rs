@@ -0,0 +1,156 @@
+//! Modal flow between typing text and editing glyph outlines
+//!
+//! - Text tool: clicking inside an existing text buffer moves that buffer's
+//!   cursor to the nearest position instead of starting a new buffer
+//! - Select tool: double-clicking a sort activates it for outline editing
+//! - Escape while editing a sort that belongs to a text buffer returns to
+//!   the text tool, with the cursor placed after that sort
+
+#![allow(clippy::too_many_arguments)]
+
+use crate::core::state::text_editor::text_buffer::{
+    ActiveTextBuffer, BufferCursor, BufferMember, TextBuffer,
+};
+use crate::core::state::text_editor::{SortData, SortLayoutMode, TextEditorState};
+use crate::core::state::{AppState, FontMetrics};
+use crate::editing::selection::components::Selected;
+use crate::editing::selection::input::mouse::DOUBLE_CLICK_THRESHOLD_SECS;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::systems::sorts::text_flow_positioning::{flow_layout, nearest_cursor_index};
+use crate::ui::edit_mode_toolbar::text::TextPlacementMode;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use bevy::prelude::*;
+
+/// How far past the end of a line (in em fractions) a click still counts
+/// as being inside the buffer
+const LINE_END_MARGIN_EM: f32 = 0.25;
+
+/// Buffer and cursor index under a world position, if any
+pub fn find_buffer_cursor_at<'a>(
+    text_editor_state: &TextEditorState,
+    buffers: impl Iterator<Item = (Entity, &'a TextBuffer)>,
+    metrics: &FontMetrics,
+    world_position: Vec2,
+) -> Option<(Entity, usize)> {
+    let upm = metrics.units_per_em as f32;
+    let descender = metrics.descender.unwrap_or(-256.0) as f32;
+    let ascender = metrics.ascender.map_or(upm * 0.8, |a| a as f32);
+    let line_height = metrics.text_line_height();
+
+    buffers
+        .filter(|(_, buffer)| buffer.layout_mode != SortLayoutMode::Freeform)
+        .find_map(|(entity, buffer)| {
+            let sorts: Vec<&SortData> = text_editor_state
+                .buffer
+                .iter()
+                .filter(|sort| sort.buffer_id == Some(buffer.id))
+                .collect();
+            let offsets =
+                flow_layout(&sorts, line_height, &buffer.layout_mode, buffer.frame.as_ref())
+                    .cursors;
+            let local = world_position - buffer.root_position;
+            let margin = upm * LINE_END_MARGIN_EM;
+            nearest_cursor_index(&offsets, local, ascender, descender, margin)
+                .map(|index| (entity, index))
+        })
+}
+
+/// Activate a sort when it is double-clicked with the select tool
+pub fn handle_sort_double_click(
+    mut commands: Commands,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    current_tool: Res<CurrentTool>,
+    ui_hover_state: Res<crate::systems::ui_interaction::UiHoverState>,
+    pointer_info: Res<crate::io::pointer::PointerInfo>,
+    time: Res<Time>,
+    app_state: Option<Res<AppState>>,
+    sort_query: Query<(Entity, &Transform, &Sort, Has<ActiveSort>)>,
+    mut last_click: Local<Option<(Entity, f32)>>,
+) {
+    if current_tool.get_current() != Some("select")
+        || ui_hover_state.is_hovering_ui
+        || !mouse_button_input.just_pressed(MouseButton::Left)
+    {
+        return;
+    }
+    let Some(app_state) = app_state.as_ref() else {
+        return;
+    };
+
+    let metrics = &app_state.workspace.info.metrics;
+    let upm = metrics.units_per_em as f32;
+    let descender = metrics.descender.unwrap_or(-256.0) as f32;
+    let ascender = metrics.ascender.map_or(upm * 0.8, |a| a as f32);
+    let click = pointer_info.design.to_raw();
+
+    // The sort whose advance box contains the click
+    let hit = sort_query.iter().find(|(_, transform, sort, _)| {
+        let origin = transform.translation.truncate();
+        let advance = app_state
+            .workspace
+            .font
+            .editing()
+            .get_glyph(&sort.glyph_name)
+            .map_or(0.0, |glyph| glyph.advance_width as f32);
+        let local = click - origin;
+        local.x >= 0.0 && local.x <= advance && local.y >= descender && local.y <= ascender
+    });
+    let Some((entity, _, sort, is_active)) = hit else {
+        *last_click = None;
+        return;
+    };
+
+    let now = time.elapsed_secs();
+    let is_double_click = last_click
+        .is_some_and(|(last, at)| last == entity && now - at <= DOUBLE_CLICK_THRESHOLD_SECS);
+    *last_click = Some((entity, now));
+
+    // Double-clicks on the active sort belong to point editing
+    if !is_double_click || is_active {
+        return;
+    }
+
+    // Selecting exactly this sort lets `auto_activate_selected_sorts` make it
+    // the active sort, just like clicking its handle
+    for (other, _, _, _) in sort_query.iter() {
+        if other != entity {
+            commands.entity(other).remove::<Selected>();
+        }
+    }
+    commands.entity(entity).insert(Selected);
+    *last_click = None;
+    info!("Editing '{}' (double-click)", sort.glyph_name);
+}
+
+/// Escape from outline editing back to typing in the sort's text buffer
+pub fn handle_escape_to_text_navigation(
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    mut current_tool: ResMut<CurrentTool>,
+    mut placement_mode: ResMut<TextPlacementMode>,
+    mut active_buffer: ResMut<ActiveTextBuffer>,
+    active_sort_query: Query<&BufferMember, With<ActiveSort>>,
+    mut buffer_query: Query<(&TextBuffer, &mut BufferCursor)>,
+) {
+    if current_tool.get_current() != Some("select")
+        || !keyboard_input.just_pressed(KeyCode::Escape)
+    {
+        return;
+    }
+    let Ok(member) = active_sort_query.single() else {
+        return;
+    };
+    let Ok((buffer, mut cursor)) = buffer_query.get_mut(member.buffer_entity) else {
+        return;
+    };
+    if buffer.layout_mode == SortLayoutMode::Freeform {
+        return;
+    }
+
+    cursor.move_to(member.buffer_index + 1, false);
+    active_buffer.buffer_entity = Some(member.buffer_entity);
+    *placement_mode = TextPlacementMode::Insert;
+    current_tool.switch_to("text");
+    // Don't let the text tool's own Escape handling exit it again
+    keyboard_input.clear_just_pressed(KeyCode::Escape);
+    debug!("Returned to text navigation at cursor {}", cursor.position);
+}
@@ -1,17 +1,24 @@
 //! Text editor cursor management
 //!
 //! This module provides cursor management for the text editor, handling both
-//! cursor position calculation and coordination with visual rendering.
+//! cursor position calculation and coordination with visual rendering: the
+//! caret sits at its index in the shaped, bidi-ordered layout of the active
+//! buffer, and the selection is highlighted stretch by stretch.
 
 #![allow(clippy::too_many_arguments)]
 
-use crate::core::state::text_editor::{SortData, SortKind, SortLayoutMode, TextEditorState};
-use crate::core::state::{AppState, TextEditorState as CoreTextEditorState};
+use crate::core::state::text_editor::{SortData, TextEditorState};
+use crate::core::state::{text_line_height, AppState, TextEditorState as CoreTextEditorState};
 use crate::rendering::entity_pools::EntityPools;
-use crate::rendering::text_cursor::{self, CursorRenderingState};
+use crate::rendering::text_cursor::{
+    self, CursorRenderingState, TextEditorCursor, TextSelectionHighlight,
+};
+use crate::systems::sorts::text_flow_positioning::{flow_layout, selection_spans};
 use crate::ui::edit_mode_toolbar::text::TextPlacementMode;
+use crate::ui::themes::CurrentTheme;
 use bevy::prelude::*;
 use bevy::sprite::ColorMaterial;
+use std::ops::Range;
 
 // ============================================================================
 // CURSOR POSITION CALCULATION
@@ -20,9 +27,11 @@ use bevy::sprite::ColorMaterial;
 #[derive(Debug)]
 struct BufferInfo {
     cursor_position: usize,
+    selection: Option<Range<usize>>,
     root_position: Vec2,
     layout_mode: crate::core::state::text_editor::SortLayoutMode,
     buffer_id: crate::core::state::text_editor::buffer::BufferId,
+    frame: Option<crate::core::state::text_editor::TextFrame>,
 }
 
 /// Extract active buffer information from ECS queries
@@ -37,100 +46,69 @@ fn get_active_buffer_info(
     let buffer_entity = active_buffer_res.buffer_entity?;
     let (text_buffer, buffer_cursor) = buffer_query.get(buffer_entity).ok()?;
 
-    warn!(
-        "🎯 CURSOR: Using buffer entity {:?}, cursor: {}, layout: {:?}",
-        buffer_entity, buffer_cursor.position, text_buffer.layout_mode
-    );
-
     Some(BufferInfo {
         cursor_position: buffer_cursor.position,
+        selection: buffer_cursor.selection(),
         root_position: text_buffer.root_position,
         layout_mode: text_buffer.layout_mode.clone(),
         buffer_id: text_buffer.id,
+        frame: text_buffer.frame.clone(),
     })
 }
 
-/// Get line height from font metrics
-fn get_line_height(
-    app_state: &Option<Res<AppState>>,
-) -> f32 {
-    if let Some(app_state) = app_state.as_ref() {
-        let font_metrics = &app_state.workspace.info.metrics;
-        let upm = font_metrics.units_per_em as f32;
-        let descender = font_metrics.descender.unwrap_or(-256.0) as f32;
-        upm - descender
-    } else {
-        1024.0 // Reasonable fallback
-    }
-}
-
 /// Collect all sorts that belong to the specific buffer
 fn collect_buffer_sorts(
     text_editor_state: &TextEditorState,
     buffer_id: crate::core::state::text_editor::buffer::BufferId,
 ) -> Vec<&SortData> {
-    let sorts: Vec<&SortData> = text_editor_state
+    text_editor_state
         .buffer
         .iter()
         .filter(|sort| sort.buffer_id == Some(buffer_id))
-        .collect();
-
-    warn!(
-        "🔍 COLLECT BUFFER SORTS: Found {} sorts for buffer_id {:?}",
-        sorts.len(),
-        buffer_id.0
-    );
-
-    if sorts.is_empty() {
-        warn!("  ⚠️ BUFFER IS EMPTY - no sorts to display");
-    }
-
-    for (i, sort) in sorts.iter().enumerate() {
-        warn!("  🔄 LOOP ITERATION {}: Examining sort...", i);
-        match &sort.kind {
-            SortKind::Glyph { glyph_name, advance_width, .. } => {
-                warn!(
-                    "  [{}] GLYPH='{}', advance_width={:.1}, root_pos=({:.1},{:.1})",
-                    i, glyph_name, advance_width, sort.root_position.x, sort.root_position.y
-                );
-            }
-            SortKind::LineBreak => {
-                warn!("  [{}] LINE BREAK, root_pos=({:.1},{:.1})", i, sort.root_position.x, sort.root_position.y);
-            }
-        }
-    }
-
-    sorts
+        .collect()
 }
 
-/// Calculate cursor offset based on layout mode and cursor position
+/// Caret position and selection stretches of the active buffer, in world
+/// coordinates
 ///
-/// This is a thin wrapper around the unified text flow positioning system.
-/// The cursor position is where new text will be inserted, so we calculate
-/// the offset for that index in the buffer.
-fn calculate_cursor_offset(
-    buffer_sorts: &[&SortData],
-    cursor_position: usize,
-    layout_mode: &SortLayoutMode,
-    line_height: f32,
-) -> Vec2 {
-    // Use the shared positioning function - single source of truth
-    let offset = crate::systems::sorts::text_flow_positioning::calculate_text_flow_offset(
-        buffer_sorts,
-        cursor_position,
+/// Both come from the buffer's flow layout, the same one its sorts are
+/// drawn with, so they follow shaped advances, wrapping and bidi runs.
+fn calculate_cursor_layout(
+    text_editor_state: &TextEditorState,
+    app_state: &Option<Res<AppState>>,
+    buffer_query: &Query<(
+        &crate::core::state::text_editor::text_buffer::TextBuffer,
+        &crate::core::state::text_editor::text_buffer::BufferCursor,
+    )>,
+    active_buffer: &Option<Res<crate::core::state::text_editor::text_buffer::ActiveTextBuffer>>,
+) -> Option<(Vec2, Vec<(Vec2, f32)>)> {
+    let buffer_info = get_active_buffer_info(active_buffer, buffer_query)?;
+    let line_height = text_line_height(app_state.as_deref());
+    let buffer_sorts = collect_buffer_sorts(text_editor_state, buffer_info.buffer_id);
+    let layout = flow_layout(
+        &buffer_sorts,
         line_height,
-        layout_mode,
-    );
-
-    warn!(
-        "🎯 CURSOR OFFSET: cursor_position={}, layout={:?}, offset=({:.1}, {:.1})",
-        cursor_position, layout_mode, offset.x, offset.y
+        &buffer_info.layout_mode,
+        buffer_info.frame.as_ref(),
     );
 
-    offset
+    let root = buffer_info.root_position;
+    let caret = layout
+        .cursors
+        .get(buffer_info.cursor_position)
+        .copied()
+        .unwrap_or_default();
+    let selection = buffer_info
+        .selection
+        .map(|range| range.start.min(buffer_sorts.len())..range.end.min(buffer_sorts.len()))
+        .map(|range| selection_spans(&buffer_sorts, &layout, range))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(left, width)| (root + left, width))
+        .collect();
+    Some((root + caret, selection))
 }
 
-
 /// Calculate cursor position using buffer entity system with full feature support
 pub fn calculate_cursor_position(
     text_editor_state: &TextEditorState,
@@ -141,27 +119,8 @@ pub fn calculate_cursor_position(
     )>,
     active_buffer: &Option<Res<crate::core::state::text_editor::text_buffer::ActiveTextBuffer>>,
 ) -> Option<Vec2> {
-    let buffer_info = get_active_buffer_info(active_buffer, buffer_query)?;
-    let line_height = get_line_height(app_state);
-    let buffer_sorts = collect_buffer_sorts(text_editor_state, buffer_info.buffer_id);
-
-    let offset = calculate_cursor_offset(
-        &buffer_sorts,
-        buffer_info.cursor_position,
-        &buffer_info.layout_mode,
-        line_height,
-    );
-
-    let final_position = buffer_info.root_position + offset;
-
-    warn!(
-        "🎯 FINAL CURSOR POSITION: root=({:.1}, {:.1}) + offset=({:.1}, {:.1}) = ({:.1}, {:.1})",
-        buffer_info.root_position.x, buffer_info.root_position.y,
-        offset.x, offset.y,
-        final_position.x, final_position.y
-    );
-
-    Some(final_position)
+    calculate_cursor_layout(text_editor_state, app_state, buffer_query, active_buffer)
+        .map(|(caret, _)| caret)
 }
 
 // ============================================================================
@@ -185,13 +144,25 @@ pub(crate) fn render_text_editor_cursor(
         &crate::core::state::text_editor::BufferCursor,
     )>,
     active_buffer: Option<Res<crate::core::state::text_editor::ActiveTextBuffer>>,
+    highlight_query: Query<Entity, With<TextSelectionHighlight>>,
+    theme: Res<CurrentTheme>,
+    time: Res<Time>,
+    presentation_mode: Option<Res<crate::ui::edit_mode_toolbar::PresentationMode>>,
 ) {
-    // Only render cursor when Text tool is active AND in Insert mode
+    // Only render cursor when Text tool is active AND in Insert mode, outside reading mode
     let should_show_cursor = current_tool.get_current() == Some("text")
-        && matches!(*current_placement_mode, TextPlacementMode::Insert);
+        && matches!(*current_placement_mode, TextPlacementMode::Insert)
+        && !presentation_mode.is_some_and(|pm| pm.reading);
 
     if !should_show_cursor {
-        text_cursor::clear_cursor_entities(&mut commands, &mut entity_pools);
+        // Forget what was drawn so the cursor comes back when typing resumes
+        if cursor_state.last_tool.is_some() {
+            text_cursor::clear_cursor_entities(&mut commands, &mut entity_pools);
+            for entity in highlight_query.iter() {
+                commands.entity(entity).despawn();
+            }
+            *cursor_state = CursorRenderingState::default();
+        }
         return;
     }
 
@@ -211,15 +182,12 @@ pub(crate) fn render_text_editor_cursor(
                 .map(|(_, cursor)| cursor.position)
         });
 
-    // Calculate current cursor position using business logic
-    let current_cursor_position = text_editor_state.as_ref().and_then(|state| {
-        calculate_cursor_position(
-            state,
-            &app_state,
-            &buffer_query,
-            &active_buffer,
-        )
+    // Calculate current cursor position and selection using business logic
+    let cursor_layout = text_editor_state.as_ref().and_then(|state| {
+        calculate_cursor_layout(state, &app_state, &buffer_query, &active_buffer)
     });
+    let current_cursor_position = cursor_layout.as_ref().map(|(caret, _)| *caret);
+    let current_selection_spans = cursor_layout.map(|(_, spans)| spans).unwrap_or_default();
 
     // Check if anything changed (change detection optimization)
     let tool_changed = cursor_state.last_tool.as_deref() != current_tool_name;
@@ -229,65 +197,87 @@ pub(crate) fn render_text_editor_cursor(
         cursor_state.last_buffer_cursor_position != current_buffer_cursor_position;
     let cursor_position_changed = cursor_state.last_cursor_position != current_cursor_position;
     let camera_scale_changed = cursor_state.last_camera_scale != Some(current_camera_scale);
+    let selection_changed = cursor_state.last_selection_spans != current_selection_spans;
 
     if !tool_changed
         && !placement_mode_changed
         && !buffer_cursor_changed
         && !cursor_position_changed
         && !camera_scale_changed
+        && !selection_changed
     {
-        warn!("🔒 CURSOR RENDERING: No changes detected, skipping render");
         return; // No changes, skip rendering
     }
 
-    warn!(
-        "🔄 CURSOR RENDERING: Changes detected - tool:{} placement:{} buffer_cursor:{} cursor_pos:{} camera:{}",
-        tool_changed, placement_mode_changed, buffer_cursor_changed, cursor_position_changed, camera_scale_changed
-    );
-    warn!(
-        "   Last cursor pos: {:?}, Current cursor pos: {:?}",
-        cursor_state.last_cursor_position, current_cursor_position
-    );
-    warn!(
-        "   Last buffer cursor: {:?}, Current buffer cursor: {:?}",
-        cursor_state.last_buffer_cursor_position, current_buffer_cursor_position
-    );
-
     // Clear existing cursor entities before re-rendering
     text_cursor::clear_cursor_entities(&mut commands, &mut entity_pools);
+    for entity in highlight_query.iter() {
+        commands.entity(entity).despawn();
+    }
 
-    // Update state tracking
+    // Update state tracking; the caret shows solid again after it moves
+    if buffer_cursor_changed || cursor_position_changed || tool_changed {
+        cursor_state.moved_at = time.elapsed_secs();
+    }
     cursor_state.last_tool = current_tool_name.map(|s| s.to_string());
     cursor_state.last_placement_mode = Some(current_placement_mode_value);
     cursor_state.last_buffer_cursor_position = current_buffer_cursor_position;
     cursor_state.last_cursor_position = current_cursor_position;
     cursor_state.last_camera_scale = Some(current_camera_scale);
+    cursor_state.last_selection_spans = current_selection_spans;
+
+    // Get font metrics for proper cursor height
+    let Some(app_state) = app_state.as_ref() else {
+        return; // No font metrics available
+    };
+    let font_metrics = &app_state.workspace.info.metrics;
+    let upm = font_metrics.units_per_em as f32;
+    let descender = font_metrics.descender.unwrap_or(-256.0) as f32;
+
+    text_cursor::render_selection_spans(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        &cursor_state.last_selection_spans,
+        upm,
+        descender,
+        theme.theme().text_selection_color(),
+    );
 
     // Render cursor if we have a valid position
     if let Some(cursor_world_pos) = current_cursor_position {
-        if text_editor_state.is_some() {
-            // Get font metrics for proper cursor height
-            let (upm, descender) = if let Some(app_state) = app_state.as_ref() {
-                let font_metrics = &app_state.workspace.info.metrics;
-                (
-                    font_metrics.units_per_em as f32,
-                    font_metrics.descender.unwrap_or(-256.0) as f32,
-                )
-            } else {
-                return; // No font metrics available
-            };
+        text_cursor::render_cursor_at_position(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            &mut entity_pools,
+            cursor_world_pos,
+            upm,
+            descender,
+            theme.theme().text_cursor_color(),
+            &camera_scale,
+        );
+    }
+}
 
-            // Render the cursor using the rendering module
-            text_cursor::render_cursor_at_position(
-                &mut commands,
-                &mut meshes,
-                &mut materials,
-                &mut entity_pools,
-                cursor_world_pos,
-                upm,
-                descender,
-                &camera_scale,
-            );
+/// Blink the caret, keeping it solid for a moment after it moves
+pub(crate) fn blink_text_cursor(
+    time: Res<Time>,
+    cursor_state: Res<CursorRenderingState>,
+    entity_pools: Res<EntityPools>,
+    mut cursor_query: Query<&mut Visibility, With<TextEditorCursor>>,
+) {
+    let since_moved = time.elapsed_secs() - cursor_state.moved_at;
+    let target = if text_cursor::caret_visible(since_moved) {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    for &entity in entity_pools.cursor_entities_in_use() {
+        if let Ok(mut visibility) = cursor_query.get_mut(entity) {
+            if *visibility != target {
+                *visibility = target;
+            }
         }
     }
 }
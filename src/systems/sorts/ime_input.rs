@@ -0,0 +1,195 @@
+//! Input method (IME) composition for the text tool
+//!
+//! Chinese, Japanese and Korean input methods build characters over several
+//! key presses. While composing, the uncommitted "preedit" text is shown at
+//! the text cursor; once the user confirms, the committed string is inserted
+//! into the active buffer like typed text.
+
+#![allow(clippy::too_many_arguments)]
+
+use crate::core::state::text_editor::text_buffer::{ActiveTextBuffer, BufferCursor, TextBuffer};
+use crate::core::state::{AppState, SortLayoutMode, TextEditorState};
+use crate::rendering::cameras::DesignCamera;
+use crate::systems::sorts::cursor::calculate_cursor_position;
+use crate::systems::sorts::sort_entities::BufferSortRespawnQueue;
+use crate::systems::sorts::unicode_input::insert_text_at_buffer_cursor;
+use crate::ui::edit_mode_toolbar::text::TextPlacementMode;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use crate::ui::themes::CurrentTheme;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::window::{Ime, PrimaryWindow};
+
+/// Preedit text size relative to the font's units per em
+const PREEDIT_SIZE_EM: f32 = 0.5;
+/// Preedit text draws above sorts
+const PREEDIT_Z: f32 = 20.0;
+
+/// Current input method composition
+#[derive(Resource, Default, Debug)]
+pub struct ImeComposition {
+    /// Uncommitted text, empty when not composing
+    pub preedit: String,
+    /// Byte range of the IME's own cursor within `preedit`
+    pub cursor: Option<(usize, usize)>,
+}
+
+impl ImeComposition {
+    pub fn is_composing(&self) -> bool {
+        !self.preedit.is_empty()
+    }
+}
+
+/// Marker for the preedit text entity
+#[derive(Component)]
+pub struct ImePreeditText;
+
+fn is_typing(current_tool: &CurrentTool, placement_mode: &TextPlacementMode) -> bool {
+    current_tool.get_current() == Some("text") && *placement_mode != TextPlacementMode::Freeform
+}
+
+/// Turn the platform IME on while typing, and keep its candidate window
+/// next to the text cursor
+pub fn update_ime_window_state(
+    current_tool: Res<CurrentTool>,
+    placement_mode: Res<TextPlacementMode>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<DesignCamera>>,
+    text_editor_state: Res<TextEditorState>,
+    app_state: Option<Res<AppState>>,
+    buffer_query: Query<(&TextBuffer, &BufferCursor)>,
+    active_buffer: Option<Res<ActiveTextBuffer>>,
+    mut composition: ResMut<ImeComposition>,
+) {
+    let Ok(mut window) = windows.single_mut() else {
+        return;
+    };
+    let typing = is_typing(&current_tool, &placement_mode);
+    if window.ime_enabled != typing {
+        window.ime_enabled = typing;
+        debug!("IME {}", if typing { "enabled" } else { "disabled" });
+    }
+    if !typing {
+        if composition.is_composing() {
+            *composition = ImeComposition::default();
+        }
+        return;
+    }
+
+    let Some(cursor) =
+        calculate_cursor_position(&text_editor_state, &app_state, &buffer_query, &active_buffer)
+    else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+    // Candidate windows open below the given point, so aim at the descender
+    let descender = app_state
+        .as_ref()
+        .and_then(|state| state.workspace.info.metrics.descender)
+        .unwrap_or(-256.0) as f32;
+    let anchor = cursor + Vec2::new(0.0, descender);
+    if let Ok(screen) = camera.world_to_viewport(camera_transform, anchor.extend(0.0)) {
+        if window.ime_position != screen {
+            window.ime_position = screen;
+        }
+    }
+}
+
+/// Track preedit text and insert committed text at the buffer cursor
+pub fn handle_ime_input(
+    mut commands: Commands,
+    mut ime_events: EventReader<Ime>,
+    mut composition: ResMut<ImeComposition>,
+    current_tool: Res<CurrentTool>,
+    placement_mode: Res<TextPlacementMode>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    app_state: Option<Res<AppState>>,
+    active_buffer: Option<Res<ActiveTextBuffer>>,
+    mut buffer_query: Query<(&TextBuffer, &mut BufferCursor)>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+) {
+    if !is_typing(&current_tool, &placement_mode) {
+        ime_events.clear();
+        return;
+    }
+
+    for event in ime_events.read() {
+        match event {
+            Ime::Preedit { value, cursor, .. } => {
+                if composition.preedit != *value || composition.cursor != *cursor {
+                    composition.preedit = value.clone();
+                    composition.cursor = *cursor;
+                }
+            }
+            Ime::Commit { value, .. } => {
+                debug!("IME commit: '{}'", value);
+                *composition = ImeComposition::default();
+                insert_text_at_buffer_cursor(
+                    value,
+                    &mut commands,
+                    &mut text_editor_state,
+                    &app_state,
+                    &placement_mode,
+                    &active_buffer,
+                    &mut buffer_query,
+                    &mut respawn_queue,
+                );
+            }
+            Ime::Enabled { .. } => debug!("IME ready"),
+            Ime::Disabled { .. } => *composition = ImeComposition::default(),
+        }
+    }
+}
+
+/// Show the uncommitted preedit text at the text cursor
+pub fn render_ime_preedit(
+    mut commands: Commands,
+    composition: Res<ImeComposition>,
+    existing: Query<Entity, With<ImePreeditText>>,
+    text_editor_state: Res<TextEditorState>,
+    app_state: Option<Res<AppState>>,
+    buffer_query: Query<(&TextBuffer, &BufferCursor)>,
+    active_buffer: Option<Res<ActiveTextBuffer>>,
+    theme: Res<CurrentTheme>,
+    asset_server: Res<AssetServer>,
+) {
+    if !composition.is_changed() && !text_editor_state.is_changed() {
+        return;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+    if !composition.is_composing() {
+        return;
+    }
+    let Some(cursor) =
+        calculate_cursor_position(&text_editor_state, &app_state, &buffer_query, &active_buffer)
+    else {
+        return;
+    };
+
+    let upm = app_state
+        .as_ref()
+        .map_or(1000.0, |state| state.workspace.info.metrics.units_per_em as f32);
+    // RTL text grows leftwards from the cursor
+    let is_rtl = active_buffer
+        .as_ref()
+        .and_then(|active| active.buffer_entity)
+        .and_then(|entity| buffer_query.get(entity).ok())
+        .is_some_and(|(buffer, _)| buffer.layout_mode == SortLayoutMode::RTLText);
+
+    commands.spawn((
+        ImePreeditText,
+        Text2d::new(composition.preedit.clone()),
+        TextFont {
+            font: asset_server.load(theme.theme().mono_font_path()),
+            font_size: upm * PREEDIT_SIZE_EM,
+            ..default()
+        },
+        TextColor(theme.theme().ime_preedit_color()),
+        if is_rtl { Anchor::BottomRight } else { Anchor::BottomLeft },
+        Transform::from_translation(cursor.extend(PREEDIT_Z)),
+    ));
+}
@@ -138,55 +138,6 @@ pub fn unicode_to_glyph_name_fontir(
     unicode_char_to_standard_glyph_name(unicode_char)
 }
 
-/// Check if a character is Arabic
-fn is_arabic_character(ch: char) -> bool {
-    let code = ch as u32;
-    // Arabic block: U+0600-U+06FF
-    (0x0600..=0x06FF).contains(&code) ||
-    // Arabic Supplement: U+0750-U+077F  
-    (0x0750..=0x077F).contains(&code) ||
-    // Arabic Extended-A: U+08A0-U+08FF
-    (0x08A0..=0x08FF).contains(&code)
-}
-
-/// Try different Arabic glyph naming conventions
-fn try_arabic_glyph_naming(unicode_char: char, available_glyphs: &[String]) -> Option<String> {
-    let codepoint = unicode_char as u32;
-
-    // Try various naming conventions for Arabic
-    let string_attempts = vec![
-        format!("uni{:04X}", codepoint),    // uni0627 for Arabic Alef
-        format!("u{:04X}", codepoint),      // u0627
-        format!("arab{:04X}", codepoint),   // arab0627
-        format!("arabic{:04X}", codepoint), // arabic0627
-        format!("U+{:04X}", codepoint),     // U+0627
-    ];
-
-    // Check string-based naming attempts
-    for name in string_attempts {
-        if available_glyphs.contains(&name) {
-            debug!(
-                "Found Arabic glyph '{}' for character '{}' (U+{:04X})",
-                name, unicode_char, codepoint
-            );
-            return Some(name);
-        }
-    }
-
-    // Try Arabic character name
-    if let Some(arabic_name) = arabic_character_name(unicode_char) {
-        if available_glyphs.contains(&arabic_name) {
-            debug!(
-                "Found Arabic glyph '{}' for character '{}' (U+{:04X})",
-                arabic_name, unicode_char, codepoint
-            );
-            return Some(arabic_name);
-        }
-    }
-
-    None
-}
-
 /// Get standard glyph name for common Unicode characters
 fn unicode_char_to_standard_glyph_name(unicode_char: char) -> Option<String> {
     match unicode_char {
@@ -206,39 +157,3 @@ fn unicode_char_to_standard_glyph_name(unicode_char: char) -> Option<String> {
         _ => None,
     }
 }
-
-/// Get Arabic character name for common Arabic letters
-fn arabic_character_name(unicode_char: char) -> Option<String> {
-    // Using the Bezy Grotesk naming convention: {letter}-ar
-    match unicode_char as u32 {
-        0x0627 => Some("alef-ar".to_string()),
-        0x0628 => Some("beh-ar".to_string()),
-        0x062A => Some("teh-ar".to_string()),
-        0x062B => Some("theh-ar".to_string()),
-        0x062C => Some("jeem-ar".to_string()),
-        0x062D => Some("hah-ar".to_string()),
-        0x062E => Some("khah-ar".to_string()),
-        0x062F => Some("dal-ar".to_string()),
-        0x0630 => Some("thal-ar".to_string()),
-        0x0631 => Some("reh-ar".to_string()),
-        0x0632 => Some("zain-ar".to_string()),
-        0x0633 => Some("seen-ar".to_string()),
-        0x0634 => Some("sheen-ar".to_string()),
-        0x0635 => Some("sad-ar".to_string()),
-        0x0636 => Some("dad-ar".to_string()),
-        0x0637 => Some("tah-ar".to_string()),
-        0x0638 => Some("zah-ar".to_string()),
-        0x0639 => Some("ain-ar".to_string()),
-        0x063A => Some("ghain-ar".to_string()),
-        0x0641 => Some("feh-ar".to_string()),
-        0x0642 => Some("qaf-ar".to_string()),
-        0x0643 => Some("kaf-ar".to_string()),
-        0x0644 => Some("lam-ar".to_string()),
-        0x0645 => Some("meem-ar".to_string()),
-        0x0646 => Some("noon-ar".to_string()),
-        0x0647 => Some("heh-ar".to_string()),
-        0x0648 => Some("waw-ar".to_string()),
-        0x064A => Some("yeh-ar".to_string()),
-        _ => None,
-    }
-}
@@ -0,0 +1,328 @@
+//! Kerning proof generator
+//!
+//! Cmd/Ctrl+Shift+K in the text tool sets the glyph before the cursor
+//! against every glyph it could kern with ("ToTaTeTo…"), one glyph per
+//! combination of kerning groups, and adds the result as new text buffers
+//! laid out in a grid below the existing text. Other systems can send
+//! `KernProofRequest::Pairs` to proof a list of pairs the same way, e.g.
+//! pairs flagged by QA or a pair entered by name with Cmd/Ctrl+Alt+Shift+K.
+
+use crate::core::state::text_editor::buffer::BufferId;
+use crate::core::state::text_editor::text_buffer::{ActiveTextBuffer, BufferCursor, TextBuffer};
+use crate::core::state::text_editor::{SortData, SortKind};
+use crate::core::state::{AppState, FontData, SortLayoutMode, TextEditorState};
+use crate::font_source::KerningData;
+use crate::io::shortcuts::Shortcut;
+use crate::systems::sorts::text_flow_positioning::flow_layout;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use bevy::prelude::*;
+use std::collections::HashSet;
+use unicode_bidi::{bidi_class, BidiClass};
+
+/// Pairs per proof line
+const PAIRS_PER_LINE: usize = 12;
+/// Proof lines per column of the grid
+const LINES_PER_COLUMN: usize = 8;
+/// Gap between grid columns, in units per em
+const COLUMN_GAP_EM: f32 = 1.0;
+
+/// Proof buffers to generate
+#[derive(Event, Debug, Clone)]
+pub enum KernProofRequest {
+    /// A glyph against every glyph it could kern with
+    Glyph(String),
+    /// Specific left/right glyph pairs
+    Pairs(Vec<(String, String)>),
+}
+
+/// Partners that kern differently, keeping the first glyph of each
+/// combination of left and right groups
+pub fn kerning_partners(candidates: &[String], kerning: &KerningData) -> Vec<String> {
+    let mut seen = HashSet::new();
+    candidates
+        .iter()
+        .filter(|glyph| seen.insert(kerning.class_pair(glyph, glyph)))
+        .cloned()
+        .collect()
+}
+
+/// `glyph` between each of its partners, wrapped every `per_line` pairs
+pub fn glyph_proof_lines(glyph: &str, partners: &[String], per_line: usize) -> Vec<Vec<String>> {
+    partners
+        .chunks(per_line.max(1))
+        .map(|chunk| {
+            let mut line = vec![glyph.to_string()];
+            for partner in chunk {
+                line.push(partner.clone());
+                line.push(glyph.to_string());
+            }
+            line
+        })
+        .collect()
+}
+
+/// Each pair on its own, optionally separated, wrapped every `per_line`
+/// pairs
+pub fn pair_proof_lines(
+    pairs: &[(String, String)],
+    separator: Option<&str>,
+    per_line: usize,
+) -> Vec<Vec<String>> {
+    pairs
+        .chunks(per_line.max(1))
+        .map(|chunk| {
+            let mut line = Vec::new();
+            for (index, (left, right)) in chunk.iter().enumerate() {
+                if index > 0 {
+                    line.extend(separator.map(str::to_string));
+                }
+                line.push(left.clone());
+                line.push(right.clone());
+            }
+            line
+        })
+        .collect()
+}
+
+/// Whether a character belongs to right-to-left text
+fn is_rtl(c: char) -> bool {
+    matches!(bidi_class(c), BidiClass::R | BidiClass::AL)
+}
+
+/// Encoded letters, figures and punctuation of the same direction as
+/// `glyph`, in codepoint order
+fn partner_candidates(font: &FontData, glyph: &str) -> Vec<String> {
+    let rtl = font
+        .glyphs
+        .get(glyph)
+        .and_then(|data| data.unicode_values.first())
+        .is_some_and(|c| is_rtl(*c));
+    let mut candidates: Vec<(char, &String)> = font
+        .glyphs
+        .iter()
+        .filter_map(|(name, data)| Some((*data.unicode_values.first()?, name)))
+        .filter(|(c, _)| c.is_alphanumeric() || c.is_ascii_punctuation())
+        .filter(|(c, _)| !c.is_alphabetic() || is_rtl(*c) == rtl)
+        .collect();
+    candidates.sort();
+    candidates
+        .into_iter()
+        .map(|(_, name)| name.clone())
+        .collect()
+}
+
+/// Glyph before the cursor of the active buffer
+fn glyph_before_cursor(
+    text_editor_state: &TextEditorState,
+    active_buffer: &ActiveTextBuffer,
+    buffer_query: &Query<(&TextBuffer, &BufferCursor)>,
+) -> Option<String> {
+    let (buffer, cursor) = buffer_query.get(active_buffer.buffer_entity?).ok()?;
+    let sorts: Vec<&SortData> = text_editor_state
+        .buffer
+        .iter()
+        .filter(|sort| sort.buffer_id == Some(buffer.id))
+        .collect();
+    sorts[..cursor.position.min(sorts.len())]
+        .iter()
+        .rev()
+        .find(|sort| sort.kind.is_glyph())
+        .map(|sort| sort.kind.glyph_name().to_string())
+}
+
+/// Request a proof of the glyph before the cursor with Cmd/Ctrl+Shift+K
+pub fn handle_kern_proof_shortcut(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_tool: Res<CurrentTool>,
+    text_editor_state: Res<TextEditorState>,
+    active_buffer: Res<ActiveTextBuffer>,
+    buffer_query: Query<(&TextBuffer, &BufferCursor)>,
+    mut requests: EventWriter<KernProofRequest>,
+) {
+    if current_tool.get_current() != Some("text") {
+        return;
+    }
+    if !Shortcut::KernProof.just_pressed(&keyboard) {
+        return;
+    }
+    match glyph_before_cursor(&text_editor_state, &active_buffer, &buffer_query) {
+        Some(glyph) => {
+            requests.write(KernProofRequest::Glyph(glyph));
+        }
+        None => warn!("Kerning proof: no glyph before the cursor"),
+    }
+}
+
+/// Add the requested proof lines as text buffers in a grid below the
+/// existing text
+pub fn generate_kern_proofs(
+    mut requests: EventReader<KernProofRequest>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    app_state: Option<Res<AppState>>,
+    buffer_query: Query<&TextBuffer>,
+) {
+    let Some(app_state) = app_state else {
+        requests.clear();
+        return;
+    };
+    let font = &app_state.workspace.font;
+    let metrics = &app_state.workspace.info.metrics;
+    let upm = metrics.units_per_em as f32;
+    let line_height = metrics.text_line_height();
+
+    for request in requests.read() {
+        let lines = match request {
+            KernProofRequest::Glyph(glyph) => {
+                let partners = kerning_partners(&partner_candidates(font, glyph), &font.kerning);
+                info!(
+                    "Kerning proof of '{}' with {} partners",
+                    glyph,
+                    partners.len()
+                );
+                glyph_proof_lines(glyph, &partners, PAIRS_PER_LINE)
+            }
+            KernProofRequest::Pairs(pairs) => {
+                let separator = font.glyphs.contains_key("space").then_some("space");
+                info!("Kerning proof of {} pairs", pairs.len());
+                pair_proof_lines(pairs, separator, PAIRS_PER_LINE)
+            }
+        };
+        let lines: Vec<Vec<SortData>> = lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .filter_map(|name| proof_sort(font, name))
+                    .collect()
+            })
+            .filter(|line: &Vec<SortData>| !line.is_empty())
+            .collect();
+        if lines.is_empty() {
+            continue;
+        }
+
+        // Start a line below the lowest text, at the leftmost buffer
+        let mut left = f32::MAX;
+        let mut bottom = line_height;
+        for buffer in buffer_query.iter() {
+            let sorts: Vec<&SortData> = text_editor_state
+                .buffer
+                .iter()
+                .filter(|sort| sort.buffer_id == Some(buffer.id))
+                .collect();
+            let layout = flow_layout(
+                &sorts,
+                line_height,
+                &buffer.layout_mode,
+                buffer.frame.as_ref(),
+            );
+            let lowest = layout
+                .cursors
+                .iter()
+                .map(|cursor| cursor.y)
+                .fold(0.0, f32::min);
+            left = left.min(buffer.root_position.x);
+            bottom = bottom.min(buffer.root_position.y + lowest);
+        }
+        let origin = Vec2::new(
+            if left == f32::MAX { 0.0 } else { left },
+            bottom - 2.0 * line_height,
+        );
+        let column_width = lines
+            .iter()
+            .map(|line| line.iter().map(sort_advance).sum::<f32>())
+            .fold(0.0, f32::max)
+            + upm * COLUMN_GAP_EM;
+
+        for (index, line) in lines.into_iter().enumerate() {
+            let column = (index / LINES_PER_COLUMN) as f32;
+            let row = (index % LINES_PER_COLUMN) as f32;
+            let root_position = origin + Vec2::new(column * column_width, -row * line_height);
+            let buffer_id = BufferId::new();
+            // Lines take the direction of their first strong glyph
+            let layout_mode = line
+                .iter()
+                .find(|sort| sort.layout_mode == SortLayoutMode::RTLText)
+                .map_or(SortLayoutMode::LTRText, |sort| sort.layout_mode.clone());
+            for sort in line {
+                let insert_index = text_editor_state.buffer.len();
+                text_editor_state.buffer.insert(
+                    insert_index,
+                    SortData {
+                        layout_mode: layout_mode.clone(),
+                        root_position,
+                        buffer_id: Some(buffer_id),
+                        ..sort
+                    },
+                );
+            }
+        }
+    }
+}
+
+fn sort_advance(sort: &SortData) -> f32 {
+    match &sort.kind {
+        SortKind::Glyph { advance_width, .. } => *advance_width,
+        SortKind::LineBreak => 0.0,
+    }
+}
+
+/// An inactive sort of a glyph, RTL if the glyph is, or `None` if the font
+/// lacks it
+fn proof_sort(font: &FontData, glyph_name: &str) -> Option<SortData> {
+    let glyph = font.glyphs.get(glyph_name)?;
+    let rtl = glyph.unicode_values.first().is_some_and(|c| is_rtl(*c));
+    Some(SortData {
+        kind: SortKind::Glyph {
+            codepoint: glyph.unicode_values.first().copied(),
+            glyph_name: glyph_name.to_string(),
+            advance_width: glyph.advance_width as f32,
+        },
+        is_active: false,
+        layout_mode: if rtl {
+            SortLayoutMode::RTLText
+        } else {
+            SortLayoutMode::LTRText
+        },
+        root_position: Vec2::ZERO,
+        placement_offset: Vec2::ZERO,
+        buffer_cursor_position: None,
+        buffer_id: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_partners_skip_glyphs_in_the_same_groups() {
+        let mut kerning = KerningData::default();
+        kerning.groups.extend([
+            ("public.kern1.o".to_string(), names(&["o", "oacute"])),
+            ("public.kern2.o".to_string(), names(&["o", "oacute", "e"])),
+            ("public.kern1.e".to_string(), names(&["e"])),
+        ]);
+        let partners = kerning_partners(&names(&["a", "e", "o", "oacute"]), &kerning);
+        assert_eq!(partners, names(&["a", "e", "o"]));
+    }
+
+    #[test]
+    fn test_proof_lines() {
+        let lines = glyph_proof_lines("T", &names(&["o", "a", "e"]), 2);
+        assert_eq!(
+            lines,
+            vec![names(&["T", "o", "T", "a", "T"]), names(&["T", "e", "T"])]
+        );
+
+        let pairs = [
+            ("A".to_string(), "V".to_string()),
+            ("T".to_string(), "o".to_string()),
+        ];
+        let lines = pair_proof_lines(&pairs, Some("space"), 12);
+        assert_eq!(lines, vec![names(&["A", "V", "space", "T", "o"])]);
+    }
+}
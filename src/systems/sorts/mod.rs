@@ -3,13 +3,20 @@
 //! This module manages text sorts in the font editor, handling their placement,
 //! rendering, input processing, and entity lifecycle management.
 
+pub mod alternates_picker;
+pub mod buffer_layout;
+pub mod character_picker;
+pub mod click_to_edit;
 pub mod cursor;
+pub mod ime_input;
 pub mod input_utilities;
-pub mod keyboard_input;
+pub mod kern_proof;
 pub mod point_entities;
-pub mod rtl_shaping;
+pub mod sort_clipboard;
 pub mod sort_entities;
 pub mod sort_placement;
+pub mod sort_viewport;
+pub mod spacing_mode;
 pub mod text_flow_positioning;
 pub mod unicode_input;
 
@@ -17,12 +24,19 @@ pub mod unicode_input;
 // TODO: Phase 3 - These modules have mixed pub/pub(crate) visibility that needs careful refactoring
 // Many of these are tightly coupled internal systems that shouldn't be public
 // For now, keeping wildcards to avoid breaking the text editor plugin
+pub use alternates_picker::*;
+pub use buffer_layout::*;
+pub use character_picker::*;
+pub use click_to_edit::*;
 pub use cursor::*;
+pub use ime_input::*;
 pub use input_utilities::*;
-pub use keyboard_input::*;
+pub use kern_proof::*;
 pub use point_entities::*;
-pub use rtl_shaping::*;
+pub use sort_clipboard::*;
 pub use sort_entities::*;
 pub use sort_placement::*;
+pub use sort_viewport::*;
+pub use spacing_mode::*;
 pub use text_flow_positioning::*;
 pub use unicode_input::*;
@@ -105,7 +105,7 @@ pub(crate) fn spawn_active_sort_points_optimized(
 
         if let Some(state) = app_state.as_ref() {
             // Fallback to UFO AppState logic
-            if let Some(glyph_data) = state.workspace.font.get_glyph(&sort.glyph_name) {
+            if let Some(glyph_data) = state.workspace.font.editing().get_glyph(&sort.glyph_name) {
                 if let Some(outline) = &glyph_data.outline {
                     let mut point_count = 0;
 
@@ -0,0 +1,510 @@
+//! Cut, copy, paste and drag-and-drop of sorts in text buffers
+//!
+//! Cmd/Ctrl+C, X and V in the text tool copy, cut and paste the selected
+//! sorts of the active buffer. Copied sorts are kept internally, so
+//! alternates and ligatures paste back as the same glyphs into any buffer,
+//! and their characters are mirrored to the system clipboard; text copied
+//! in another app pastes as typed characters. The system clipboard is read
+//! in the background, so a paste lands a few frames after the shortcut.
+//! Dragging a selected run to another place in its buffer moves it there.
+//!
+//! Every edit queues the sorts from the first changed one onwards for
+//! respawning, so their entities get `BufferMember` indices that match the
+//! new order.
+
+#![allow(clippy::too_many_arguments)]
+
+use crate::core::config::BezySettings;
+use crate::core::state::text_editor::buffer::BufferId;
+use crate::core::state::text_editor::text_buffer::{ActiveTextBuffer, BufferCursor, TextBuffer};
+use crate::core::state::text_editor::{SortData, SortKind};
+use crate::core::state::{AppState, TextEditorState};
+use crate::io::shortcuts::Shortcut;
+use crate::systems::sorts::click_to_edit::find_buffer_cursor_at;
+use crate::systems::sorts::sort_entities::BufferSortRespawnQueue;
+use crate::systems::sorts::unicode_input::insert_text_at_buffer_cursor;
+use crate::ui::edit_mode_toolbar::text::TextPlacementMode;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use crate::utils::system_clipboard;
+use anyhow::Result;
+use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future, Task};
+use std::ops::Range;
+
+/// Sorts copied from a text buffer, in buffer order
+#[derive(Resource, Default)]
+pub struct SortClipboard {
+    pub sorts: Vec<SortData>,
+    /// The text we last wrote to the system clipboard, used to tell our own
+    /// copies apart from text that came from another app
+    last_exported_text: Option<String>,
+    /// The buffer to paste into and the system clipboard being read for it
+    pending_paste: Option<(Entity, Task<Result<String>>)>,
+}
+
+/// Clipboard operations on the active buffer's selection
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortClipboardEvent {
+    Copy,
+    Cut,
+    Paste,
+}
+
+/// A selected run being dragged with the mouse
+#[derive(Debug, Clone)]
+pub struct SortDrag {
+    buffer_entity: Entity,
+    range: Range<usize>,
+}
+
+/// Index in the editor's buffer of each sort of a text buffer, in order
+fn buffer_sort_indices(state: &TextEditorState, buffer_id: BufferId) -> Vec<usize> {
+    state
+        .buffer
+        .iter()
+        .enumerate()
+        .filter(|(_, sort)| sort.buffer_id == Some(buffer_id))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Copies of the sorts of a text buffer in a range of cursor positions
+pub fn copy_sorts(
+    state: &TextEditorState,
+    buffer_id: BufferId,
+    range: Range<usize>,
+) -> Vec<SortData> {
+    let indices = buffer_sort_indices(state, buffer_id);
+    let range = range.start.min(indices.len())..range.end.min(indices.len());
+    indices[range]
+        .iter()
+        .filter_map(|&index| state.buffer.get(index).cloned())
+        .collect()
+}
+
+/// Remove the sorts of a text buffer in a range of cursor positions
+///
+/// Returns the index in the editor's buffer of the first sort that moved,
+/// if any were removed.
+pub fn remove_sorts(
+    state: &mut TextEditorState,
+    buffer_id: BufferId,
+    range: Range<usize>,
+) -> Option<usize> {
+    let indices = buffer_sort_indices(state, buffer_id);
+    let range = range.start.min(indices.len())..range.end.min(indices.len());
+    let removed = &indices[range];
+    for &index in removed.iter().rev() {
+        state.buffer.delete(index);
+    }
+    removed.first().copied()
+}
+
+/// Insert sorts into a text buffer at a cursor position
+///
+/// The sorts take the buffer's direction and root, wherever they were
+/// copied from. Returns the index in the editor's buffer of the first
+/// inserted sort, if there were any.
+pub fn insert_sorts(
+    state: &mut TextEditorState,
+    buffer: &TextBuffer,
+    position: usize,
+    sorts: &[SortData],
+) -> Option<usize> {
+    if sorts.is_empty() {
+        return None;
+    }
+    let indices = buffer_sort_indices(state, buffer.id);
+    let first = match indices.get(position) {
+        Some(&index) => index,
+        None => indices.last().map_or(state.buffer.len(), |last| last + 1),
+    };
+    for (offset, sort) in sorts.iter().enumerate() {
+        let sort = SortData {
+            is_active: false,
+            layout_mode: buffer.layout_mode.clone(),
+            root_position: buffer.root_position,
+            placement_offset: Vec2::ZERO,
+            buffer_cursor_position: None,
+            buffer_id: Some(buffer.id),
+            ..sort.clone()
+        };
+        state.buffer.insert(first + offset, sort);
+    }
+    Some(first)
+}
+
+/// Move a run of sorts so it starts at cursor position `to`, counted
+/// before the move
+///
+/// Returns the run's new range and the index in the editor's buffer of the
+/// first sort that moved, or `None` when `to` is inside the run.
+pub fn move_sorts(
+    state: &mut TextEditorState,
+    buffer: &TextBuffer,
+    range: Range<usize>,
+    to: usize,
+) -> Option<(Range<usize>, usize)> {
+    if range.is_empty() || (range.start..=range.end).contains(&to) {
+        return None;
+    }
+    let sorts = copy_sorts(state, buffer.id, range.clone());
+    let removed = remove_sorts(state, buffer.id, range.clone())?;
+    let start = if to > range.end { to - sorts.len() } else { to };
+    let inserted = insert_sorts(state, buffer, start, &sorts)?;
+    Some((start..start + sorts.len(), removed.min(inserted)))
+}
+
+/// Characters of copied sorts, for other apps; glyphs without a codepoint
+/// are left out
+fn sorts_to_text(sorts: &[SortData]) -> String {
+    sorts
+        .iter()
+        .filter_map(|sort| match &sort.kind {
+            SortKind::Glyph { codepoint, .. } => *codepoint,
+            SortKind::LineBreak => Some('\n'),
+        })
+        .collect()
+}
+
+/// Respawn the sorts from `first` on, whose indices changed
+pub fn queue_respawn(
+    state: &TextEditorState,
+    respawn_queue: &mut BufferSortRespawnQueue,
+    first: usize,
+) {
+    respawn_queue.indices.extend(first..state.buffer.len());
+}
+
+/// Turn Cmd/Ctrl+C, X and V in the text tool into clipboard events
+pub fn handle_sort_clipboard_shortcuts(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_tool: Res<CurrentTool>,
+    placement_mode: Res<TextPlacementMode>,
+    mut events: EventWriter<SortClipboardEvent>,
+) {
+    if current_tool.get_current() != Some("text") || *placement_mode != TextPlacementMode::Insert {
+        return;
+    }
+    for (shortcut, event) in [
+        (Shortcut::CopySorts, SortClipboardEvent::Copy),
+        (Shortcut::CutSorts, SortClipboardEvent::Cut),
+        (Shortcut::PasteSorts, SortClipboardEvent::Paste),
+    ] {
+        if shortcut.just_pressed(&keyboard) {
+            events.write(event);
+        }
+    }
+}
+
+/// Copy, cut and paste the active buffer's selection
+pub fn handle_sort_clipboard_events(
+    mut commands: Commands,
+    mut events: EventReader<SortClipboardEvent>,
+    mut clipboard: ResMut<SortClipboard>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    app_state: Option<Res<AppState>>,
+    settings: Res<BezySettings>,
+    placement_mode: Res<TextPlacementMode>,
+    active_buffer: Option<Res<ActiveTextBuffer>>,
+    mut buffer_query: Query<(&TextBuffer, &mut BufferCursor)>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+) {
+    for &event in events.read() {
+        let Some(buffer_entity) = active_buffer
+            .as_ref()
+            .and_then(|active| active.buffer_entity)
+        else {
+            debug!("No active buffer for {:?}", event);
+            continue;
+        };
+        let Ok((buffer, mut cursor)) = buffer_query.get_mut(buffer_entity) else {
+            continue;
+        };
+        let buffer = buffer.clone();
+        let selection = cursor.selection();
+
+        // Copy and cut keep the selected sorts
+        if event != SortClipboardEvent::Paste {
+            let Some(range) = selection.clone() else {
+                debug!("Nothing selected to {:?}", event);
+                continue;
+            };
+            clipboard.sorts = copy_sorts(&text_editor_state, buffer.id, range);
+            info!("Copied {} sort(s)", clipboard.sorts.len());
+            if settings.clipboard.use_system_clipboard {
+                // A failed write leaves other text on the clipboard, which
+                // then pastes as another app's
+                let text = sorts_to_text(&clipboard.sorts);
+                system_clipboard::write_text_in_background(text.clone()).detach();
+                clipboard.last_exported_text = Some(text);
+            }
+        }
+        if event == SortClipboardEvent::Copy {
+            continue;
+        }
+
+        // Cut and paste replace the selection
+        if let Some(range) = selection {
+            if let Some(first) = remove_sorts(&mut text_editor_state, buffer.id, range.clone()) {
+                queue_respawn(&text_editor_state, &mut respawn_queue, first);
+            }
+            cursor.move_to(range.start, false);
+            text_editor_state.set_changed();
+        }
+        if event == SortClipboardEvent::Cut {
+            continue;
+        }
+
+        if settings.clipboard.use_system_clipboard {
+            let task = system_clipboard::read_text_in_background();
+            clipboard.pending_paste = Some((buffer_entity, task));
+            continue;
+        }
+        paste_sorts(
+            &clipboard.sorts,
+            buffer_entity,
+            &mut text_editor_state,
+            &mut buffer_query,
+            &mut respawn_queue,
+        );
+    }
+}
+
+/// Pastes what was read from the system clipboard once it's there: text
+/// from another app as typed characters, our own copy as the copied sorts
+pub fn finish_sort_paste(
+    mut commands: Commands,
+    mut clipboard: ResMut<SortClipboard>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    app_state: Option<Res<AppState>>,
+    placement_mode: Res<TextPlacementMode>,
+    active_buffer: Option<Res<ActiveTextBuffer>>,
+    mut buffer_query: Query<(&TextBuffer, &mut BufferCursor)>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+) {
+    let Some((buffer_entity, task)) = clipboard.pending_paste.as_mut() else {
+        return;
+    };
+    let Some(text) = block_on(future::poll_once(task)) else {
+        return;
+    };
+    let buffer_entity = *buffer_entity;
+    clipboard.pending_paste = None;
+
+    let foreign_text = text
+        .ok()
+        .filter(|text| !text.is_empty())
+        .filter(|text| clipboard.last_exported_text.as_deref() != Some(text.as_str()));
+    // Typed characters go to the active buffer's cursor
+    let is_active = active_buffer
+        .as_ref()
+        .is_some_and(|active| active.buffer_entity == Some(buffer_entity));
+    match foreign_text {
+        Some(text) if is_active => {
+            insert_text_at_buffer_cursor(
+                &text,
+                &mut commands,
+                &mut text_editor_state,
+                &app_state,
+                &placement_mode,
+                &active_buffer,
+                &mut buffer_query,
+                &mut respawn_queue,
+            );
+            info!("Pasted {} character(s)", text.chars().count());
+        }
+        Some(_) => debug!("The buffer to paste into is no longer active"),
+        None => paste_sorts(
+            &clipboard.sorts,
+            buffer_entity,
+            &mut text_editor_state,
+            &mut buffer_query,
+            &mut respawn_queue,
+        ),
+    }
+}
+
+/// Insert copied sorts at a buffer's cursor
+fn paste_sorts(
+    sorts: &[SortData],
+    buffer_entity: Entity,
+    text_editor_state: &mut ResMut<TextEditorState>,
+    buffer_query: &mut Query<(&TextBuffer, &mut BufferCursor)>,
+    respawn_queue: &mut BufferSortRespawnQueue,
+) {
+    let Ok((buffer, mut cursor)) = buffer_query.get_mut(buffer_entity) else {
+        return;
+    };
+    let position = cursor.position;
+    if let Some(first) = insert_sorts(text_editor_state, buffer, position, sorts) {
+        queue_respawn(text_editor_state, respawn_queue, first);
+        cursor.move_to(position + sorts.len(), false);
+        text_editor_state.set_changed();
+        info!("Pasted {} sort(s)", sorts.len());
+    }
+}
+
+/// Whether a press at a cursor position lands on the selection, which
+/// starts dragging it instead of placing the cursor
+pub fn is_on_selection(cursor: &BufferCursor, index: usize) -> bool {
+    cursor
+        .selection()
+        .is_some_and(|range| (range.start..=range.end).contains(&index))
+}
+
+/// Drag a selected run to another place in its buffer
+///
+/// A press on the selection picks it up; releasing elsewhere in the same
+/// buffer moves it there and keeps it selected, while releasing on the
+/// selection itself just places the cursor, like a click.
+pub fn handle_sort_drag(
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_tool: Res<CurrentTool>,
+    placement_mode: Res<TextPlacementMode>,
+    ui_hover_state: Res<crate::systems::ui_interaction::UiHoverState>,
+    pointer_info: Res<crate::io::pointer::PointerInfo>,
+    app_state: Option<Res<AppState>>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    active_buffer: Option<Res<ActiveTextBuffer>>,
+    mut buffer_query: Query<(Entity, &TextBuffer, &mut BufferCursor)>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+    mut drag: Local<Option<SortDrag>>,
+) {
+    if current_tool.get_current() != Some("text") || *placement_mode != TextPlacementMode::Insert {
+        *drag = None;
+        return;
+    }
+    let Some(app_state) = app_state.as_ref() else {
+        return;
+    };
+    let hit = || {
+        find_buffer_cursor_at(
+            &text_editor_state,
+            buffer_query
+                .iter()
+                .map(|(entity, buffer, _)| (entity, buffer)),
+            &app_state.workspace.info.metrics,
+            pointer_info.design.to_raw(),
+        )
+    };
+
+    if mouse_button_input.just_pressed(MouseButton::Left) {
+        let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        let active = active_buffer
+            .as_ref()
+            .and_then(|active| active.buffer_entity);
+        *drag = hit()
+            .filter(|_| !shift && !ui_hover_state.is_hovering_ui)
+            .filter(|(buffer_entity, _)| Some(*buffer_entity) == active)
+            .and_then(|(buffer_entity, index)| {
+                let (_, _, cursor) = buffer_query.get(buffer_entity).ok()?;
+                let range = cursor
+                    .selection()
+                    .filter(|_| is_on_selection(cursor, index))?;
+                Some(SortDrag {
+                    buffer_entity,
+                    range,
+                })
+            });
+        return;
+    }
+
+    if !mouse_button_input.just_released(MouseButton::Left) {
+        return;
+    }
+    let Some(SortDrag {
+        buffer_entity,
+        range,
+    }) = drag.take()
+    else {
+        return;
+    };
+    let drop = hit().filter(|(entity, _)| *entity == buffer_entity);
+    let Ok((_, buffer, mut cursor)) = buffer_query.get_mut(buffer_entity) else {
+        return;
+    };
+    let Some((_, to)) = drop else {
+        let position = cursor.position;
+        cursor.move_to(position, false);
+        return;
+    };
+
+    let buffer = buffer.clone();
+    match move_sorts(&mut text_editor_state, &buffer, range, to) {
+        Some((moved, first)) => {
+            queue_respawn(&text_editor_state, &mut respawn_queue, first);
+            cursor.move_to(moved.start, false);
+            cursor.move_to(moved.end, true);
+            debug!("Moved sorts to {:?}", moved);
+        }
+        // Released on the selection: a click
+        None => cursor.move_to(to, false),
+    }
+    text_editor_state.set_changed();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::state::text_editor::SortLayoutMode;
+
+    fn text(state: &TextEditorState, buffer_id: BufferId) -> String {
+        buffer_sort_indices(state, buffer_id)
+            .into_iter()
+            .filter_map(|index| state.buffer.get(index)?.kind.codepoint())
+            .collect()
+    }
+
+    fn push_text(state: &mut TextEditorState, buffer: &TextBuffer, text: &str) {
+        let sorts: Vec<SortData> = text
+            .chars()
+            .map(|c| SortData {
+                kind: SortKind::Glyph {
+                    codepoint: Some(c),
+                    glyph_name: c.to_string(),
+                    advance_width: 100.0,
+                },
+                ..Default::default()
+            })
+            .collect();
+        let end = buffer_sort_indices(state, buffer.id).len();
+        insert_sorts(state, buffer, end, &sorts);
+    }
+
+    #[test]
+    fn test_copy_cut_and_paste_between_buffers() {
+        let mut state = TextEditorState::default();
+        let first = TextBuffer::new(BufferId(1), SortLayoutMode::LTRText, Vec2::ZERO);
+        let second = TextBuffer::new(BufferId(2), SortLayoutMode::RTLText, Vec2::ONE);
+        push_text(&mut state, &first, "abcd");
+        push_text(&mut state, &second, "xy");
+
+        let copied = copy_sorts(&state, first.id, 1..3);
+        assert_eq!(sorts_to_text(&copied), "bc");
+        assert_eq!(remove_sorts(&mut state, first.id, 1..3), Some(1));
+        assert_eq!(text(&state, first.id), "ad");
+
+        // Pasted sorts belong to, and flow like, the buffer they land in
+        assert_eq!(insert_sorts(&mut state, &second, 1, &copied), Some(3));
+        assert_eq!(text(&state, second.id), "xbcy");
+        let pasted = state.buffer.get(3).unwrap();
+        assert_eq!(pasted.buffer_id, Some(second.id));
+        assert_eq!(pasted.layout_mode, SortLayoutMode::RTLText);
+    }
+
+    #[test]
+    fn test_move_sorts_both_ways() {
+        let mut state = TextEditorState::default();
+        let buffer = TextBuffer::new(BufferId(1), SortLayoutMode::LTRText, Vec2::ZERO);
+        push_text(&mut state, &buffer, "abcdef");
+
+        assert_eq!(move_sorts(&mut state, &buffer, 1..3, 5), Some((3..5, 1)));
+        assert_eq!(text(&state, buffer.id), "adebcf");
+        assert_eq!(move_sorts(&mut state, &buffer, 3..5, 0), Some((0..2, 0)));
+        assert_eq!(text(&state, buffer.id), "bcadef");
+        // Dropping a run onto itself leaves it
+        assert_eq!(move_sorts(&mut state, &buffer, 0..2, 1), None);
+    }
+}
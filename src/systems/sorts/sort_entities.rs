@@ -1,9 +1,8 @@
 //! Sort entity management for text editor sorts
 
+use super::buffer_layout::BufferLayouts;
+use super::sort_viewport::SortViewport;
 use crate::core::state::text_editor::TextEditorState;
-use crate::core::state::text_editor::TextBuffer;
-use crate::core::state::AppState;
-use crate::core::state::FontMetrics;
 use crate::editing::sort::{ActiveSort, InactiveSort, Sort};
 use bevy::prelude::*;
 use std::collections::HashMap;
@@ -131,121 +130,42 @@ pub fn manage_sort_activation(
     }
 }
 
-/// Modern ECS-based positioning for text sorts using buffer entities
-///
-/// This uses the SHARED text flow positioning system to ensure perfect alignment
-/// between sort visuals and cursor position.
-fn calculate_buffer_local_position(
-    global_buffer_index: usize,
-    text_editor_state: &TextEditorState,
-    font_metrics: &FontMetrics,
-    buffer_id_to_entity: &std::collections::HashMap<
-        crate::core::state::text_editor::buffer::BufferId,
-        Entity,
-    >,
-    buffer_entity_query: &Query<(Entity, &TextBuffer)>,
-) -> Option<Vec2> {
-    // Get the sort entry we're positioning
-    let sort_entry = text_editor_state.buffer.get(global_buffer_index)?;
-
-    // Get the buffer ID for this sort
-    let buffer_id = sort_entry.buffer_id?;
-
-    // Find the buffer entity
-    let buffer_entity = buffer_id_to_entity.get(&buffer_id)?;
-
-    // Get buffer information
-    let (_entity, text_buffer) = buffer_entity_query
-        .iter()
-        .find(|(entity, _)| entity == buffer_entity)?;
-
-    let root_position = text_buffer.root_position;
-    let layout_mode = &text_buffer.layout_mode;
-
-    // Find all sorts that belong to this specific buffer, in order
-    let mut buffer_sorts = Vec::new();
-    for (idx, sort) in text_editor_state.buffer.iter().enumerate() {
-        if sort.buffer_id == Some(buffer_id) {
-            buffer_sorts.push((idx, sort));
-        }
-    }
-
-    // Find our position within this buffer
-    let buffer_local_index = buffer_sorts
-        .iter()
-        .position(|(idx, _)| *idx == global_buffer_index)?;
-
-    debug!(
-        "🔍 ECS POSITIONING: Sort at global[{}] is buffer-local[{}] in buffer {:?} with {} total sorts",
-        global_buffer_index, buffer_local_index, buffer_id.0, buffer_sorts.len()
-    );
-
-    // Use font metrics to calculate line height
-    let upm = font_metrics.units_per_em as f32;
-    let descender = font_metrics.descender.unwrap_or(-256.0) as f32;
-    let line_height = upm - descender;
-
-    // Convert buffer_sorts to just references to SortData for the shared function
-    let sort_refs: Vec<&crate::core::state::text_editor::SortData> =
-        buffer_sorts.iter().map(|(_, sort)| *sort).collect();
-
-    // Use the SHARED positioning function - single source of truth
-    let offset = crate::systems::sorts::text_flow_positioning::calculate_text_flow_offset(
-        &sort_refs,
-        buffer_local_index,
-        line_height,
-        layout_mode,
-    );
-
-    let final_position = root_position + offset;
-
-    debug!(
-        "🎯 ECS POSITIONING: buffer-local[{}] '{}' at ({:.1}, {:.1}) (offset={:.1},{:.1})",
-        buffer_local_index,
-        sort_entry.kind.glyph_name(),
-        final_position.x,
-        final_position.y,
-        offset.x,
-        offset.y
-    );
-
-    Some(final_position)
+/// Whether the buffer, its layout, the area sorts get entities in or the
+/// entities changed, or sorts wait to be respawned
+pub fn sort_entities_out_of_date(
+    text_editor_state: Res<TextEditorState>,
+    layouts: Res<BufferLayouts>,
+    viewport: Res<SortViewport>,
+    buffer_entities: Res<BufferSortEntities>,
+    respawn_queue: Res<BufferSortRespawnQueue>,
+) -> bool {
+    text_editor_state.is_changed()
+        || layouts.is_changed()
+        || viewport.is_changed()
+        || buffer_entities.is_changed()
+        || !respawn_queue.indices.is_empty()
 }
 
 /// Spawn missing sort entities for sorts in the text editor buffer
 #[allow(clippy::too_many_arguments)]
 pub fn spawn_missing_sort_entities(
     mut commands: Commands,
-    text_editor_state: ResMut<TextEditorState>,
+    text_editor_state: Res<TextEditorState>,
     mut buffer_entities: ResMut<BufferSortEntities>,
     mut respawn_queue: ResMut<BufferSortRespawnQueue>,
-    app_state: Option<Res<AppState>>,
+    layouts: Res<BufferLayouts>,
+    viewport: Res<SortViewport>,
     _existing_active_sorts: Query<Entity, With<crate::editing::sort::ActiveSort>>,
     buffer_entity_query: Query<(
         Entity,
         &crate::core::state::text_editor::text_buffer::TextBuffer,
     )>,
 ) {
-    // Debug: Log buffer state (only when buffer has content to reduce spam)
-    if !text_editor_state.buffer.is_empty() {
-        warn!(
-            "🔍 SPAWN ENTITIES: spawn_missing_sort_entities called - buffer has {} sorts",
-            text_editor_state.buffer.len()
-        );
-        for i in 0..text_editor_state.buffer.len() {
-            if let Some(sort) = text_editor_state.buffer.get(i) {
-                warn!(
-                    "  📋 Buffer[{}]: glyph='{}', buffer_id={:?}, is_active={}, layout_mode={:?}",
-                    i,
-                    sort.kind.glyph_name(),
-                    sort.buffer_id.map(|id| id.0),
-                    sort.is_active,
-                    sort.layout_mode
-                );
-            }
-        }
-    }
-    // Silently skip logging when buffer is empty to reduce spam
+    trace!(
+        sorts = text_editor_state.buffer.len(),
+        respawns = respawn_queue.indices.len(),
+        "Syncing buffer sort entities"
+    );
 
     // Build a mapping from BufferId to buffer Entity
     let mut buffer_id_to_entity = std::collections::HashMap::new();
@@ -271,8 +191,21 @@ pub fn spawn_missing_sort_entities(
     }
     respawn_queue.indices.clear(); // Clear the queue after processing
 
+    // Sorts seen so far in each buffer, for buffer-local indices
+    let mut buffer_sort_counts: HashMap<_, usize> = HashMap::new();
+
     // Iterate through all sorts in the buffer
     for i in 0..text_editor_state.buffer.len() {
+        let buffer_local_index = text_editor_state
+            .buffer
+            .get(i)
+            .and_then(|sort| sort.buffer_id)
+            .map(|buffer_id| {
+                let count = buffer_sort_counts.entry(buffer_id).or_default();
+                *count += 1;
+                *count - 1
+            });
+
         // Skip if we already have an entity for this buffer index
         if buffer_entities.entities.contains_key(&i) {
             debug!("🔄 spawn_missing_sort_entities: Entity already exists for buffer index {} - SKIPPING", i);
@@ -288,42 +221,12 @@ pub fn spawn_missing_sort_entities(
                 continue;
             }
 
-            // Get font metrics from AppState (FontIR removed)
-            let font_metrics = if let Some(state) = app_state.as_ref() {
-                state.workspace.info.metrics.clone()
-            } else {
-                // Fallback metrics
-                crate::core::state::FontMetrics {
-                    units_per_em: 1024.0,
-                    ascender: Some(832.0),
-                    descender: Some(-256.0),
-                    line_height: 0.0,
-                    x_height: None,
-                    cap_height: None,
-                    italic_angle: None,
-                }
-            };
-
-            // Get the visual position for this sort using correct font metrics
-            let position = match sort_entry.layout_mode {
-                crate::core::state::SortLayoutMode::LTRText
-                | crate::core::state::SortLayoutMode::RTLText => {
-                    // Use modern ECS-based positioning to prevent buffer cross-contamination
-                    let calculated_pos = calculate_buffer_local_position(
-                        i,
-                        &text_editor_state,
-                        &font_metrics,
-                        &buffer_id_to_entity,
-                        &buffer_entity_query,
-                    );
-                    debug!(
-                        "🔍 ECS ENTITY POSITIONING: buffer[{}] calculated at {:?}",
-                        i, calculated_pos
-                    );
-                    calculated_pos
-                }
-                crate::core::state::SortLayoutMode::Freeform => Some(sort_entry.root_position),
-            };
+            // Sorts in long buffers wait for their background layout, and
+            // in very long texts only the active sort and sorts around the
+            // view are spawned
+            let position = layouts
+                .origin(i)
+                .filter(|&origin| sort_entry.is_active || viewport.contains(origin));
 
             if let Some(position) = position {
                 debug!("🎯 spawn_missing_sort_entities: Got position ({:.1}, {:.1}) for buffer index {}", position.x, position.y, i);
@@ -364,18 +267,10 @@ pub fn spawn_missing_sort_entities(
                 let entity = entity_commands.id();
 
                 // Add BufferMember component to link this sort to its buffer entity
-                if let Some(buffer_id) = sort_entry.buffer_id {
+                if let (Some(buffer_id), Some(buffer_local_index)) =
+                    (sort_entry.buffer_id, buffer_local_index)
+                {
                     if let Some(&buffer_entity) = buffer_id_to_entity.get(&buffer_id) {
-                        // Calculate buffer-local index by counting sorts with the same buffer_id up to this point
-                        let mut buffer_local_index = 0;
-                        for idx in 0..i {
-                            if let Some(sort) = text_editor_state.buffer.get(idx) {
-                                if sort.buffer_id == Some(buffer_id) {
-                                    buffer_local_index += 1;
-                                }
-                            }
-                        }
-
                         entity_commands.insert(
                             crate::core::state::text_editor::text_buffer::BufferMember::new(
                                 buffer_entity,
@@ -404,86 +299,7 @@ pub fn spawn_missing_sort_entities(
                     position.y
                 );
             } else {
-                debug!("❌ spawn_missing_sort_entities: Failed to get position for buffer sort index {} - SKIPPING ENTITY SPAWN", i);
-            }
-        }
-    }
-}
-
-/// Update positions of existing buffer sort entities to match text flow
-pub fn update_buffer_sort_positions(
-    text_editor_state: Res<TextEditorState>,
-    app_state: Option<Res<AppState>>,
-    buffer_entities: Res<BufferSortEntities>,
-    mut sort_query: Query<&mut Transform, With<BufferSortIndex>>,
-    buffer_entity_query: Query<(
-        Entity,
-        &crate::core::state::text_editor::text_buffer::TextBuffer,
-    )>,
-) {
-    // CRITICAL PERFORMANCE FIX: Early return if TextEditorState hasn't changed
-    // Prevents O(N²) position calculations every frame
-    if !text_editor_state.is_changed() {
-        return;
-    }
-
-    debug!("Buffer position update triggered - TextEditorState changed");
-
-    // Build a mapping from BufferId to buffer Entity for ECS positioning
-    let mut buffer_id_to_entity = std::collections::HashMap::new();
-    for (buffer_entity, text_buffer) in buffer_entity_query.iter() {
-        buffer_id_to_entity.insert(text_buffer.id, buffer_entity);
-    }
-
-    // Get font metrics from AppState (FontIR removed)
-    let font_metrics = if let Some(app_state) = app_state.as_ref() {
-        app_state.workspace.info.metrics.clone()
-    } else {
-        debug!("Buffer sort position updates skipped - no font data available");
-        return;
-    };
-
-    // Update Transform positions for all existing buffer sorts
-    for (&buffer_index, &entity) in buffer_entities.entities.iter() {
-        if let Ok(mut transform) = sort_query.get_mut(entity) {
-            // Calculate correct position using the font metrics from app state
-            if let Some(sort) = text_editor_state.buffer.get(buffer_index) {
-                // Skip line breaks - they shouldn't have entities
-                if sort.kind.is_line_break() {
-                    warn!(
-                        "Unexpected entity for line break at buffer index {}",
-                        buffer_index
-                    );
-                    continue;
-                }
-                let new_position = match sort.layout_mode {
-                    crate::core::state::SortLayoutMode::LTRText
-                    | crate::core::state::SortLayoutMode::RTLText => {
-                        // Use modern ECS-based positioning to prevent buffer cross-contamination
-                        let calculated_pos = calculate_buffer_local_position(
-                            buffer_index,
-                            &text_editor_state,
-                            &font_metrics,
-                            &buffer_id_to_entity,
-                            &buffer_entity_query,
-                        );
-                        debug!(
-                            "🔍 ECS POSITION UPDATE: buffer[{}] calculated at {:?}",
-                            buffer_index, calculated_pos
-                        );
-                        calculated_pos
-                    }
-                    crate::core::state::SortLayoutMode::Freeform => Some(sort.root_position),
-                };
-
-                if let Some(new_pos) = new_position {
-                    let new_pos_3d = new_pos.extend(transform.translation.z);
-                    transform.translation = new_pos_3d;
-                    debug!(
-                        "Sort {} positioned at ({:.1}, {:.1})",
-                        buffer_index, new_pos.x, new_pos.y
-                    );
-                }
+                trace!(index = i, "Sort not laid out yet or out of view");
             }
         }
     }
@@ -629,19 +445,27 @@ pub fn sync_buffer_sort_activation_state(
     }
 }
 
-/// Despawn missing buffer sort entities
+/// Despawn the entities of deleted buffer sorts, and of sorts that left the
+/// area sorts get entities in unless they are active or selected
 #[allow(clippy::too_many_arguments)]
-pub fn despawn_missing_buffer_sort_entities(
+pub(crate) fn despawn_missing_buffer_sort_entities(
     mut commands: Commands,
     text_editor_state: Res<TextEditorState>,
+    (layouts, viewport): (Res<BufferLayouts>, Res<SortViewport>),
     mut buffer_entities: ResMut<BufferSortEntities>,
     mut glyph_entities: ResMut<crate::rendering::glyph_renderer::GlyphRenderEntities>,
     mut metrics_entities: ResMut<crate::rendering::metrics::MetricsLineEntities>,
     mut handle_entities: ResMut<crate::rendering::sort_visuals::SortHandleEntities>,
-    sort_query: Query<Entity, With<BufferSortIndex>>,
+    mut entity_pools: ResMut<crate::rendering::entity_pools::EntityPools>,
+    mut point_instances: ResMut<crate::rendering::points::PointInstances>,
+    sort_query: Query<
+        (Has<ActiveSort>, Has<crate::editing::selection::components::Selected>),
+        With<BufferSortIndex>,
+    >,
     glyph_element_query: Query<(
         Entity,
         &crate::rendering::glyph_renderer::GlyphRenderElement,
+        Has<crate::rendering::entity_pools::PooledEntity>,
     )>,
     point_query: Query<Entity, With<crate::editing::sort::manager::SortPointEntity>>,
     sort_point_query: Query<&crate::editing::sort::manager::SortPointEntity>,
@@ -678,32 +502,34 @@ pub fn despawn_missing_buffer_sort_entities(
             break;
         }
         // Check if this buffer index still exists in the actual buffer
-        if buffer_index >= text_editor_state.buffer.len() {
-            // Buffer index no longer exists, despawn entity
+        let deleted = buffer_index >= text_editor_state.buffer.len();
+        let out_of_view = !deleted
+            && layouts
+                .origin(buffer_index)
+                .is_some_and(|origin| !viewport.contains(origin))
+            && sort_query
+                .get(entity)
+                .is_ok_and(|(active, selected)| !active && !selected);
+        if deleted || out_of_view {
+            // Buffer index no longer exists or is out of view, despawn entity
             if sort_query.get(entity).is_ok() {
                 debug!(
-                    "🗑️ Despawning sort entity for deleted buffer index {} (buffer len: {})",
+                    "🗑️ Despawning sort entity {} (buffer len: {}, deleted: {})",
                     buffer_index,
-                    text_editor_state.buffer.len()
+                    text_editor_state.buffer.len(),
+                    deleted
                 );
 
                 // First, despawn all glyph render elements associated with this sort
                 let mut glyph_count = 0;
 
-                // Despawn all glyph elements (points, outlines, handles)
-                if let Some(element_entities) = glyph_entities.elements.get(&entity) {
-                    for &element_entity in element_entities.iter() {
-                        if let Ok(mut entity_commands) = commands.get_entity(element_entity) {
-                            entity_commands.despawn();
-                            glyph_count += 1;
-                            despawn_operations += 1;
-                        }
-                    }
-                }
+                // Pooled points, outlines and handles are kept as spares
+                entity_pools.release_sort(&mut commands, entity);
+                point_instances.remove_sort(entity);
 
-                // Also despawn any loose glyph elements that might not be tracked
-                for (glyph_entity, glyph_element) in glyph_element_query.iter() {
-                    if glyph_element.sort_entity == entity {
+                // Despawn the remaining glyph elements, tracked or not
+                for (glyph_entity, glyph_element, pooled) in glyph_element_query.iter() {
+                    if glyph_element.sort_entity == entity && !pooled {
                         if let Ok(mut entity_commands) = commands.get_entity(glyph_entity) {
                             entity_commands.despawn();
                             glyph_count += 1;
@@ -4,7 +4,10 @@
 
 #![allow(clippy::too_many_arguments)]
 
+use crate::core::state::text_editor::text_buffer::{ActiveTextBuffer, BufferCursor, TextBuffer};
 use crate::rendering::checkerboard::calculate_dynamic_grid_size;
+use crate::systems::sorts::click_to_edit::find_buffer_cursor_at;
+use crate::systems::sorts::sort_clipboard::is_on_selection;
 use crate::ui::themes::CurrentTheme;
 use bevy::prelude::*;
 
@@ -23,6 +26,10 @@ pub fn handle_sort_placement_input(
     ui_hover_state: Res<crate::systems::ui_interaction::UiHoverState>,
     theme: Res<CurrentTheme>,
     app_state: Option<Res<crate::core::state::AppState>>,
+    pointer_info: Res<crate::io::pointer::PointerInfo>,
+    mut buffer_query: Query<(Entity, &TextBuffer, &mut BufferCursor)>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    active_buffer: Option<Res<ActiveTextBuffer>>,
 ) {
     use crate::ui::edit_mode_toolbar::text::TextPlacementMode;
 
@@ -32,6 +39,46 @@ pub fn handle_sort_placement_input(
         return;
     }
 
+    // Clicking inside an existing text buffer moves its cursor rather than
+    // starting a new buffer; shift-clicking in the active buffer selects up
+    // to the click
+    if mouse_button_input.just_pressed(MouseButton::Left)
+        && !ui_hover_state.is_hovering_ui
+        && *current_placement_mode != TextPlacementMode::Freeform
+    {
+        if let Some(app_state) = app_state.as_ref() {
+            let hit = find_buffer_cursor_at(
+                &text_editor_state,
+                buffer_query.iter().map(|(entity, buffer, _)| (entity, buffer)),
+                &app_state.workspace.info.metrics,
+                pointer_info.design.to_raw(),
+            );
+            if let Some((buffer_entity, index)) = hit {
+                let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+                let is_active = active_buffer
+                    .as_ref()
+                    .is_some_and(|active| active.buffer_entity == Some(buffer_entity));
+                let extend =
+                    shift && is_active && *current_placement_mode == TextPlacementMode::Insert;
+                if let Ok((_, _, mut cursor)) = buffer_query.get_mut(buffer_entity) {
+                    // A press on the selection may start dragging it; the
+                    // cursor is placed when the button is released
+                    if is_active && !shift && is_on_selection(&cursor, index) {
+                        return;
+                    }
+                    cursor.move_to(index, extend);
+                }
+                commands.insert_resource(ActiveTextBuffer {
+                    buffer_entity: Some(buffer_entity),
+                });
+                *current_placement_mode = TextPlacementMode::Insert;
+                text_editor_state.set_changed();
+                debug!("Placed cursor at {} in buffer {:?}", index, buffer_entity);
+                return;
+            }
+        }
+    }
+
     debug!("🖱️ SORT PLACEMENT: ✅ Text tool is active, checking other conditions...");
 
     // Only handle text placement modes, not insert mode
@@ -261,6 +308,7 @@ fn create_independent_sort_with_fontir(
         layout_mode: layout_mode.clone(),
         is_active: true, // Make this sort active for immediate editing
         root_position: world_position,
+        placement_offset: Vec2::ZERO,
         buffer_cursor_position: None, // Deprecated field - cursor stored in buffer entity now
         buffer_id: Some(buffer_id),   // For compatibility, though deprecated
     };
@@ -0,0 +1,98 @@
+//! Sort entities for only the visible part of very long texts
+//!
+//! Every sort normally gets an entity, with fill meshes, labels and metrics
+//! lines of its own. A proofing document of tens of thousands of characters
+//! would need as many, so once the editor holds `VIRTUALIZE_MIN_SORTS` sorts
+//! only those laid out around the view get entities. The area kept reaches a
+//! view's size past each edge and only moves once the view comes within half
+//! a view of its edge, so panning doesn't spawn and despawn sorts every
+//! frame. The active sort and selected sorts keep their entities anywhere.
+
+use crate::core::state::TextEditorState;
+use crate::rendering::cameras::DesignCamera;
+use bevy::prelude::*;
+
+/// Sorts in the editor before only those around the view get entities
+pub const VIRTUALIZE_MIN_SORTS: usize = 2048;
+
+/// Distance glyphs may reach past their origin, in font units
+const ORIGIN_MARGIN: f32 = 2048.0;
+
+/// Where sorts get entities
+#[derive(Resource, Debug, Default, PartialEq)]
+pub struct SortViewport {
+    /// World area of the origins of sorts with entities, or `None` for all
+    area: Option<Rect>,
+}
+
+impl SortViewport {
+    /// Whether the sort with its origin at `origin` gets an entity
+    pub fn contains(&self, origin: Vec2) -> bool {
+        self.area.is_none_or(|area| area.contains(origin))
+    }
+}
+
+/// The area to keep entities in while `view` is shown, which stays `area`
+/// until the view comes within half a view of its edge
+fn area_around(view: Rect, area: Option<Rect>) -> Rect {
+    let size = view.size();
+    let needed = Rect::from_center_size(view.center(), size * 2.0).inflate(ORIGIN_MARGIN);
+    if let Some(area) = area {
+        if area.contains(needed.min) && area.contains(needed.max) {
+            return area;
+        }
+    }
+    Rect::from_center_size(view.center(), size * 3.0).inflate(ORIGIN_MARGIN)
+}
+
+/// Follow the design camera with the area sorts get entities in
+pub fn update_sort_viewport(
+    text_editor_state: Res<TextEditorState>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<DesignCamera>>,
+    mut viewport: ResMut<SortViewport>,
+) {
+    let area = if text_editor_state.buffer.len() < VIRTUALIZE_MIN_SORTS {
+        None
+    } else {
+        let Ok((camera, camera_transform)) = camera_query.single() else {
+            return;
+        };
+        let Some(size) = camera.logical_viewport_size() else {
+            return;
+        };
+        let (Ok(corner), Ok(opposite)) = (
+            camera.viewport_to_world_2d(camera_transform, Vec2::ZERO),
+            camera.viewport_to_world_2d(camera_transform, size),
+        ) else {
+            return;
+        };
+        Some(area_around(
+            Rect::from_corners(corner, opposite),
+            viewport.area,
+        ))
+    };
+    if viewport.set_if_neq(SortViewport { area }) {
+        debug!(area = ?viewport.area, "Moved the area sorts get entities in");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_area_moves_only_near_its_edge() {
+        let view = Rect::from_center_size(Vec2::ZERO, Vec2::new(1000.0, 500.0));
+        let area = area_around(view, None);
+        assert!(area.contains(Vec2::new(1400.0 + ORIGIN_MARGIN, 0.0)));
+        assert!(!area.contains(Vec2::new(1600.0 + ORIGIN_MARGIN, 0.0)));
+
+        // Half a view to the right is still covered
+        let panned = Rect::from_center_size(Vec2::new(500.0, 0.0), view.size());
+        assert_eq!(area_around(panned, Some(area)), area);
+
+        let further = Rect::from_center_size(Vec2::new(600.0, 0.0), view.size());
+        let moved = area_around(further, Some(area));
+        assert_eq!(moved.center(), further.center());
+    }
+}
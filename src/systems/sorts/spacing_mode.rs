@@ -0,0 +1,398 @@
+//! Spacing mode: sidebearing editing from the keyboard
+//!
+//! Cmd/Ctrl+Shift+M in the text tool turns the active buffer into a
+//! spacing proof, in the spirit of Metrics Machine:
+//! - Up/Down selects the previous/next glyph in the buffer
+//! - Left/Right narrows/widens the selected glyph's right sidebearing, or
+//!   its left sidebearing with Alt held; Shift makes the step coarser
+//! - Escape (or the shortcut again) goes back to typing after that glyph
+//!
+//! Edits change the glyph in the font, so every sort of it reflows at once.
+
+#![allow(clippy::too_many_arguments)]
+
+use crate::core::state::text_editor::text_buffer::{ActiveTextBuffer, BufferCursor, TextBuffer};
+use crate::core::state::text_editor::SortKind;
+use crate::core::state::{AppState, SortLayoutMode, TextEditorState};
+use crate::editing::font_changes::GlyphEdited;
+use crate::editing::selection::events::AppStateChanged;
+use crate::font_source::GlyphData;
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::mesh_utils::create_line_mesh;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::systems::sorts::sort_entities::{BufferSortEntities, BufferSortIndex};
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use crate::ui::themes::CurrentTheme;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+/// Units per arrow press
+const SPACING_STEP: f64 = 1.0;
+/// Units per arrow press with Shift held
+const SPACING_STEP_LARGE: f64 = 10.0;
+/// Sidebearing label size relative to the font's units per em
+const LABEL_SIZE_EM: f32 = 0.1;
+/// The selection marker draws above sorts
+const SPACING_MARKER_Z: f32 = 16.0;
+
+/// Whether spacing mode is on, and the glyph sort it is editing
+#[derive(Resource, Default, Debug)]
+pub struct SpacingMode {
+    pub active: bool,
+    /// Buffer index of the selected sort
+    pub selected: Option<usize>,
+}
+
+/// Which side of a glyph an edit changes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sidebearing {
+    Left,
+    Right,
+}
+
+/// Marker for the selection underline and sidebearing label
+#[derive(Component)]
+pub struct SpacingMarker;
+
+/// Change one sidebearing of a glyph by `delta` units
+///
+/// The left side moves the outline, components and anchors along with the
+/// advance; the right side only moves the advance. The advance never goes
+/// below zero.
+pub fn adjust_sidebearing(glyph: &mut GlyphData, side: Sidebearing, delta: f64) {
+    let delta = delta.max(-glyph.advance_width);
+    glyph.advance_width += delta;
+    if side == Sidebearing::Right {
+        return;
+    }
+    if let Some(outline) = glyph.outline.as_mut() {
+        for point in outline.contours.iter_mut().flat_map(|c| c.points.iter_mut()) {
+            point.x += delta;
+        }
+    }
+    for component in &mut glyph.components {
+        component.transform[4] += delta;
+    }
+    for anchor in &mut glyph.anchors {
+        anchor.x += delta;
+    }
+}
+
+/// The active buffer and the buffer indices of its sorts
+fn active_buffer_sorts(
+    text_editor_state: &TextEditorState,
+    active_buffer: &ActiveTextBuffer,
+    buffer_query: &Query<(&TextBuffer, &mut BufferCursor)>,
+) -> Option<(Entity, Vec<usize>)> {
+    let entity = active_buffer.buffer_entity?;
+    let (buffer, _) = buffer_query.get(entity).ok()?;
+    if buffer.layout_mode == SortLayoutMode::Freeform {
+        return None;
+    }
+    let indices = (0..text_editor_state.buffer.len())
+        .filter(|&index| {
+            text_editor_state
+                .buffer
+                .get(index)
+                .is_some_and(|sort| sort.buffer_id == Some(buffer.id))
+        })
+        .collect();
+    Some((entity, indices))
+}
+
+/// Switch spacing mode with Cmd/Ctrl+Shift+M, and leave it with Escape
+pub fn handle_spacing_mode_toggle(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    current_tool: Res<CurrentTool>,
+    mut spacing: ResMut<SpacingMode>,
+    text_editor_state: Res<TextEditorState>,
+    active_buffer: Res<ActiveTextBuffer>,
+    mut buffer_query: Query<(&TextBuffer, &mut BufferCursor)>,
+) {
+    if current_tool.get_current() != Some("text") {
+        if spacing.active {
+            *spacing = SpacingMode::default();
+        }
+        return;
+    }
+    let toggled = Shortcut::SpacingMode.just_pressed(&keyboard);
+    let escaped = spacing.active && keyboard.just_pressed(KeyCode::Escape);
+    if !toggled && !escaped {
+        return;
+    }
+    // Leaving spacing mode shouldn't leave the text tool too
+    keyboard.clear_just_pressed(KeyCode::Escape);
+
+    let Some((entity, indices)) =
+        active_buffer_sorts(&text_editor_state, &active_buffer, &buffer_query)
+    else {
+        *spacing = SpacingMode::default();
+        return;
+    };
+    let Ok((_, mut cursor)) = buffer_query.get_mut(entity) else {
+        return;
+    };
+
+    if spacing.active {
+        // Back to typing just after the glyph that was being spaced
+        if let Some(position) = spacing
+            .selected
+            .and_then(|selected| indices.iter().position(|&index| index == selected))
+        {
+            cursor.move_to(position + 1, false);
+        }
+        *spacing = SpacingMode::default();
+        info!("Spacing mode off");
+        return;
+    }
+
+    let is_glyph = |index: &usize| {
+        text_editor_state
+            .buffer
+            .get(*index)
+            .is_some_and(|sort| sort.kind.is_glyph())
+    };
+    // Start on the glyph before the cursor, or the first glyph
+    let selected = indices[..cursor.position.min(indices.len())]
+        .iter()
+        .rev()
+        .find(|index| is_glyph(index))
+        .or_else(|| indices.iter().find(|index| is_glyph(index)))
+        .copied();
+    *spacing = SpacingMode {
+        active: true,
+        selected,
+    };
+    info!("Spacing mode on");
+}
+
+/// Select sorts with Up/Down and change sidebearings with Left/Right
+pub fn handle_spacing_mode_keys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut spacing: ResMut<SpacingMode>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    mut app_state: Option<ResMut<AppState>>,
+    active_buffer: Res<ActiveTextBuffer>,
+    buffer_query: Query<(&TextBuffer, &mut BufferCursor)>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut glyph_edits: EventWriter<GlyphEdited>,
+) {
+    if !spacing.active {
+        return;
+    }
+    let Some((_, indices)) =
+        active_buffer_sorts(&text_editor_state, &active_buffer, &buffer_query)
+    else {
+        return;
+    };
+    let glyph_indices: Vec<usize> = indices
+        .into_iter()
+        .filter(|&index| {
+            text_editor_state
+                .buffer
+                .get(index)
+                .is_some_and(|sort| sort.kind.is_glyph())
+        })
+        .collect();
+    let current = spacing
+        .selected
+        .and_then(|selected| glyph_indices.iter().position(|&index| index == selected));
+
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        let position = current.map_or(0, |position| position.saturating_sub(1));
+        spacing.selected = glyph_indices.get(position).copied();
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        let position = current.map_or(0, |position| {
+            (position + 1).min(glyph_indices.len().saturating_sub(1))
+        });
+        spacing.selected = glyph_indices.get(position).copied();
+    }
+
+    let direction = if keyboard.just_pressed(KeyCode::ArrowRight) {
+        1.0
+    } else if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        -1.0
+    } else {
+        return;
+    };
+    let Some(app_state) = app_state.as_mut() else {
+        return;
+    };
+    let Some(glyph_name) = spacing
+        .selected
+        .and_then(|index| text_editor_state.buffer.get(index))
+        .map(|sort| sort.kind.glyph_name().to_string())
+    else {
+        return;
+    };
+    let Some(glyph) = app_state.workspace.font.editing_glyph_mut(&glyph_name) else {
+        return;
+    };
+
+    let side = if keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]) {
+        Sidebearing::Left
+    } else {
+        Sidebearing::Right
+    };
+    let step = if keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        SPACING_STEP_LARGE
+    } else {
+        SPACING_STEP
+    };
+    adjust_sidebearing(glyph, side, direction * step);
+    let advance = glyph.advance_width as f32;
+    debug!("{:?} sidebearing of '{}' by {}", side, glyph_name, direction * step);
+
+    // Reflow every sort of this glyph
+    for index in 0..text_editor_state.buffer.len() {
+        if let Some(SortKind::Glyph {
+            glyph_name: name,
+            advance_width,
+            ..
+        }) = text_editor_state.buffer.get_mut(index).map(|sort| &mut sort.kind)
+        {
+            if *name == glyph_name {
+                *advance_width = advance;
+            }
+        }
+    }
+    app_state_changed.write(AppStateChanged);
+    glyph_edits.write(GlyphEdited::glyph(glyph_name));
+}
+
+/// Underline the selected sort and show its sidebearings
+pub fn render_spacing_marker(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    spacing: Res<SpacingMode>,
+    text_editor_state: Res<TextEditorState>,
+    buffer_entities: Res<BufferSortEntities>,
+    sort_query: Query<&Transform, With<BufferSortIndex>>,
+    moved_sorts: Query<(), (Changed<Transform>, With<BufferSortIndex>)>,
+    existing: Query<Entity, With<SpacingMarker>>,
+    camera_scale: Res<CameraResponsiveScale>,
+    theme: Res<CurrentTheme>,
+    asset_server: Res<AssetServer>,
+    app_state: Option<Res<AppState>>,
+) {
+    let app_state_changed = app_state.as_ref().is_some_and(|state| state.is_changed());
+    let needs_redraw = spacing.is_changed()
+        || app_state_changed
+        || camera_scale.is_changed()
+        || !moved_sorts.is_empty();
+    if !needs_redraw {
+        return;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+    let Some(app_state) = app_state.as_ref() else {
+        return;
+    };
+    let Some(index) = spacing.selected.filter(|_| spacing.active) else {
+        return;
+    };
+    let Some(sort) = text_editor_state.buffer.get(index) else {
+        return;
+    };
+    let SortKind::Glyph {
+        glyph_name,
+        advance_width,
+        ..
+    } = &sort.kind
+    else {
+        return;
+    };
+    let Some(transform) = buffer_entities
+        .entities
+        .get(&index)
+        .and_then(|entity| sort_query.get(*entity).ok())
+    else {
+        return;
+    };
+
+    let metrics = &app_state.workspace.info.metrics;
+    let upm = metrics.units_per_em as f32;
+    let descender = metrics.descender.unwrap_or(-256.0) as f32;
+    let origin = transform.translation.truncate() - sort.placement_offset;
+    let color = theme.theme().spacing_mode_color();
+    let line_width = camera_scale.adjusted_line_width() * 2.0;
+
+    let start = Vec2::new(origin.x, origin.y + descender);
+    let end = start + Vec2::new(*advance_width, 0.0);
+    commands.spawn((
+        SpacingMarker,
+        Mesh2d(meshes.add(create_line_mesh(start, end, line_width))),
+        MeshMaterial2d(materials.add(ColorMaterial::from_color(color))),
+        Transform::from_translation(((start + end) * 0.5).extend(SPACING_MARKER_Z)),
+    ));
+
+    if let Some((left, right)) = app_state.workspace.font.editing().sidebearings(glyph_name) {
+        commands.spawn((
+            SpacingMarker,
+            Text2d::new(format!("{}   {}", left.round(), right.round())),
+            TextFont {
+                font: asset_server.load(theme.theme().mono_font_path()),
+                font_size: upm * LABEL_SIZE_EM,
+                ..default()
+            },
+            TextColor(color),
+            Anchor::TopCenter,
+            Transform::from_translation(((start + end) * 0.5).extend(SPACING_MARKER_Z)),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_source::{
+        AnchorData, ComponentData, ContourData, OutlineData, PointData, PointTypeData,
+    };
+
+    fn glyph() -> GlyphData {
+        let point = |x: f64| PointData {
+            x,
+            y: 0.0,
+            point_type: PointTypeData::Line,
+        };
+        GlyphData {
+            unicode_values: vec!['a'],
+            outline: Some(OutlineData {
+                contours: vec![ContourData {
+                    points: vec![point(50.0), point(450.0)],
+                }],
+            }),
+            components: vec![ComponentData::default()],
+            anchors: vec![AnchorData {
+                name: "top".to_string(),
+                x: 250.0,
+                y: 500.0,
+            }],
+            ..GlyphData::test("a", 500.0)
+        }
+    }
+
+    #[test]
+    fn test_left_sidebearing_moves_everything() {
+        let mut glyph = glyph();
+        adjust_sidebearing(&mut glyph, Sidebearing::Left, 10.0);
+        assert_eq!(glyph.advance_width, 510.0);
+        let outline = glyph.outline.as_ref().unwrap();
+        assert_eq!(outline.contours[0].points[0].x, 60.0);
+        assert_eq!(glyph.components[0].transform[4], 10.0);
+        assert_eq!(glyph.anchors[0].x, 260.0);
+    }
+
+    #[test]
+    fn test_right_sidebearing_only_moves_advance() {
+        let mut glyph = glyph();
+        adjust_sidebearing(&mut glyph, Sidebearing::Right, -600.0);
+        assert_eq!(glyph.advance_width, 0.0);
+        let outline = glyph.outline.as_ref().unwrap();
+        assert_eq!(outline.contours[0].points[0].x, 50.0);
+        assert_eq!(glyph.anchors[0].x, 250.0);
+    }
+}
@@ -1,104 +1,457 @@
 use crate::core::state::text_editor::{SortData, SortKind, SortLayoutMode};
+use crate::core::state::text_editor::{TextAlignment, TextFrame};
 use bevy::math::Vec2;
+use std::ops::Range;
+use unicode_bidi::{bidi_class, BidiClass, BidiInfo, Level};
 
-/// Calculate text flow offset for a position within a buffer
+/// Where a buffer's sorts and cursor positions are drawn
 ///
-/// This is the SINGLE SOURCE OF TRUTH for text positioning in both LTR and RTL modes.
-/// Used by both:
-/// - Sort entity visual positioning
-/// - Cursor positioning
+/// This is the SINGLE SOURCE OF TRUTH for text positioning. Used by:
+/// - Sort entity visual positioning (`sort_origins`)
+/// - Cursor rendering, hit-testing and arrow keys (`cursors`)
 ///
-/// This ensures cursor and text are always perfectly aligned.
-pub fn calculate_text_flow_offset(
+/// Offsets are relative to the buffer root. Buffers hold text in logical
+/// (typing) order; each line is reordered for display with the Unicode
+/// Bidirectional Algorithm, so Arabic or Hebrew runs inside Latin text (and
+/// the other way round) read correctly.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlowLayout {
+    /// Left edge, on the baseline, of each sort, including its placement
+    /// offset
+    pub sort_origins: Vec<Vec2>,
+    /// Position of each cursor index (0..=len)
+    pub cursors: Vec<Vec2>,
+}
+
+/// Lay out a whole buffer
+///
+/// Unframed LTR lines start at the root and RTL lines end at it; framed
+/// buffers wrap into lines placed according to the frame's alignment.
+pub fn flow_layout(
     buffer_sorts: &[&SortData],
-    target_index: usize,
     line_height: f32,
     layout_mode: &SortLayoutMode,
-) -> Vec2 {
-    match layout_mode {
-        SortLayoutMode::RTLText => {
-            calculate_rtl_offset(buffer_sorts, target_index, line_height)
+    frame: Option<&TextFrame>,
+) -> FlowLayout {
+    let count = buffer_sorts.len();
+    let base_rtl = *layout_mode == SortLayoutMode::RTLText;
+    let lines = match frame {
+        Some(frame) => wrap_lines(buffer_sorts, frame.width),
+        None => wrap_lines(buffer_sorts, f32::INFINITY),
+    };
+
+    let mut sort_origins = vec![Vec2::ZERO; count];
+    let mut is_rtl = vec![base_rtl; count];
+    let mut line_of = vec![0; count];
+    // Where an empty line's cursor goes
+    let mut line_starts = Vec::with_capacity(lines.len());
+
+    for (line_index, line) in lines.iter().enumerate() {
+        let y = -(line_index as f32) * line_height;
+        let sorts = &buffer_sorts[line.clone()];
+        let (visual_order, levels) = bidi_line(sorts, base_rtl);
+        let total: f32 = sorts.iter().map(|sort| glyph_advance(sort)).sum();
+
+        let x_start = match frame {
+            Some(frame) => {
+                let visible = visible_line_width(sorts);
+                let slack = frame.width - visible;
+                let aligned = match frame.alignment {
+                    TextAlignment::Left => 0.0,
+                    TextAlignment::Center => slack * 0.5,
+                    TextAlignment::Right => slack,
+                };
+                // Trailing spaces hang past the line end, which is on the
+                // left in RTL paragraphs
+                if base_rtl {
+                    aligned - (total - visible)
+                } else {
+                    aligned
+                }
+            }
+            None if base_rtl => -total,
+            None => 0.0,
+        };
+
+        let mut x = x_start;
+        for local in visual_order {
+            sort_origins[line.start + local] = Vec2::new(x, y);
+            x += glyph_advance(sorts[local]);
+        }
+        for (local, sort) in sorts.iter().enumerate() {
+            let index = line.start + local;
+            line_of[index] = line_index;
+            is_rtl[index] = levels[local];
+            if sort.kind.is_line_break() {
+                // Hard breaks sit at the end of their line
+                let end = if base_rtl { x_start } else { x_start + total };
+                sort_origins[index] = Vec2::new(end, y);
+            }
         }
-        _ => calculate_ltr_offset(buffer_sorts, target_index, line_height),
+        let empty_start = if base_rtl { x_start + total } else { x_start };
+        line_starts.push(Vec2::new(empty_start, y));
+    }
+
+    let leading_edge = |i: usize| {
+        let advance = if is_rtl[i] { glyph_advance(buffer_sorts[i]) } else { 0.0 };
+        sort_origins[i] + Vec2::new(advance, 0.0)
+    };
+    let trailing_edge = |i: usize| {
+        let advance = if is_rtl[i] { 0.0 } else { glyph_advance(buffer_sorts[i]) };
+        sort_origins[i] + Vec2::new(advance, 0.0)
+    };
+    let is_glyph = |i: usize| !buffer_sorts[i].kind.is_line_break();
+
+    // Where two runs meet, the cursor sticks to the embedded (opposite
+    // direction) run so every index gets its own spot on screen
+    let cursors = (0..=count)
+        .map(|i| {
+            let after_embedded = i > 0 && is_glyph(i - 1) && is_rtl[i - 1] != base_rtl;
+            if after_embedded {
+                trailing_edge(i - 1)
+            } else if i < count && is_glyph(i) {
+                leading_edge(i)
+            } else if i > 0 && is_glyph(i - 1) {
+                trailing_edge(i - 1)
+            } else {
+                let line = if i < count { line_of[i] } else { lines.len() - 1 };
+                line_starts[line]
+            }
+        })
+        .collect();
+
+    // Placement offsets (attached marks) move sorts but not the cursor
+    for (origin, sort) in sort_origins.iter_mut().zip(buffer_sorts) {
+        *origin += sort.placement_offset;
+    }
+
+    FlowLayout {
+        sort_origins,
+        cursors,
     }
 }
 
-/// Calculate LTR text flow offset
+/// Cursor index one step to the left or right on screen
 ///
-/// LTR POSITIONING LOGIC:
-/// - Start at (0, 0) relative to buffer root
-/// - Move RIGHT by adding advance widths for each glyph before target
-/// - Line breaks reset to x=0 and move down by line_height
-fn calculate_ltr_offset(
-    buffer_sorts: &[&SortData],
-    target_index: usize,
-    line_height: f32,
-) -> Vec2 {
-    use bevy::prelude::warn;
+/// Moves to the nearest cursor position on the same line in the requested
+/// direction, so arrows follow the display across direction changes. At the
+/// visual end of a line it continues in logical order onto the next (or
+/// previous) line.
+pub fn visual_cursor_step(
+    layout: &FlowLayout,
+    from: usize,
+    rightwards: bool,
+    base_rtl: bool,
+) -> Option<usize> {
+    const EPSILON: f32 = 0.01;
+    let current = *layout.cursors.get(from)?;
+    let on_line = layout
+        .cursors
+        .iter()
+        .enumerate()
+        .filter(|(_, cursor)| (cursor.y - current.y).abs() < EPSILON)
+        .filter(|(_, cursor)| {
+            let dx = cursor.x - current.x;
+            if rightwards { dx > EPSILON } else { dx < -EPSILON }
+        });
+    let nearest = on_line.min_by(|(ia, a), (ib, b)| {
+        let da = (a.x - current.x).abs();
+        let db = (b.x - current.x).abs();
+        da.total_cmp(&db).then(ia.abs_diff(from).cmp(&ib.abs_diff(from)))
+    });
+    if let Some((index, _)) = nearest {
+        return Some(index);
+    }
 
-    let mut x_offset = 0.0;
-    let mut y_offset = 0.0;
+    // Off the end of the line: carry on in reading order
+    if rightwards != base_rtl {
+        (from + 1 < layout.cursors.len()).then_some(from + 1)
+    } else {
+        from.checked_sub(1)
+    }
+}
 
-    warn!("📐 CALCULATE_LTR_OFFSET: buffer_sorts.len()={}, target_index={}", buffer_sorts.len(), target_index);
+/// Highlighted stretches of a selected range of sorts
+///
+/// Returns the left end, on the baseline, and the width of each stretch. A
+/// range in buffer order shows as several stretches where it wraps onto
+/// another line or where part of it lies in a run of the other direction;
+/// sorts that meet on screen share one stretch.
+pub fn selection_spans(
+    buffer_sorts: &[&SortData],
+    layout: &FlowLayout,
+    range: Range<usize>,
+) -> Vec<(Vec2, f32)> {
+    const EPSILON: f32 = 0.01;
+    let mut boxes: Vec<(Vec2, f32)> = range
+        .filter_map(|i| {
+            let sort = buffer_sorts.get(i)?;
+            let origin = *layout.sort_origins.get(i)? - sort.placement_offset;
+            let advance = glyph_advance(sort);
+            (advance > 0.0).then_some((origin, advance))
+        })
+        .collect();
+    // Top line first, left to right
+    boxes.sort_by(|(a, _), (b, _)| b.y.total_cmp(&a.y).then(a.x.total_cmp(&b.x)));
 
-    // Accumulate advance widths for all sorts BEFORE the target index
-    for (i, sort) in buffer_sorts.iter().enumerate() {
-        warn!("  📊 LTR LOOP: i={}, target_index={}, checking i >= target_index...", i, target_index);
-        if i >= target_index {
-            warn!("  ⛔ BREAKING: i={} >= target_index={}", i, target_index);
-            break;
+    let mut spans: Vec<(Vec2, f32)> = Vec::new();
+    for (left, width) in boxes {
+        match spans.last_mut() {
+            Some((start, span_width))
+                if (start.y - left.y).abs() < EPSILON
+                    && left.x <= start.x + *span_width + EPSILON =>
+            {
+                *span_width = span_width.max(left.x + width - start.x);
+            }
+            _ => spans.push((left, width)),
         }
+    }
+    spans
+}
 
-        match &sort.kind {
-            SortKind::Glyph { advance_width, glyph_name, .. } => {
-                warn!("  ✅ PROCESSING GLYPH '{}' at index {}: advance_width={:.1}, accumulating to x_offset", glyph_name, i, advance_width);
-                x_offset += advance_width;
-                warn!("     → x_offset is now: {:.1}", x_offset);
-            }
-            SortKind::LineBreak => {
-                warn!("  📏 LINE BREAK at index {}: resetting x_offset to 0, moving down by {:.1}", i, line_height);
-                x_offset = 0.0;
-                y_offset -= line_height;
-            }
+/// Visual order and direction of one line's sorts
+///
+/// Returns the line-local indices of its glyphs from left to right, and for
+/// every sort whether it resolved to a right-to-left level.
+fn bidi_line(line: &[&SortData], base_rtl: bool) -> (Vec<usize>, Vec<bool>) {
+    let glyphs: Vec<usize> = (0..line.len())
+        .filter(|&i| !line[i].kind.is_line_break())
+        .collect();
+    let mut text = String::new();
+    let mut byte_to_local = Vec::new();
+    for &i in &glyphs {
+        let character = bidi_character(line[i]);
+        text.push(character);
+        byte_to_local.resize(text.len(), i);
+    }
+
+    let mut is_rtl = vec![base_rtl; line.len()];
+    let base_level = if base_rtl { Level::rtl() } else { Level::ltr() };
+    let info = BidiInfo::new(&text, Some(base_level));
+    let Some(paragraph) = info.paragraphs.first() else {
+        return (glyphs, is_rtl);
+    };
+
+    let (levels, runs) = info.visual_runs(paragraph, paragraph.range.clone());
+    let mut visual_order = Vec::with_capacity(glyphs.len());
+    for run in runs {
+        let run_rtl = levels[run.start].is_rtl();
+        let mut run_sorts: Vec<usize> = text[run.clone()]
+            .char_indices()
+            .map(|(offset, _)| byte_to_local[run.start + offset])
+            .collect();
+        if run_rtl {
+            run_sorts.reverse();
         }
+        for &local in &run_sorts {
+            is_rtl[local] = run_rtl;
+        }
+        visual_order.extend(run_sorts);
     }
+    (visual_order, is_rtl)
+}
 
-    warn!("📐 CALCULATE_LTR_OFFSET RESULT: offset=({:.1}, {:.1})", x_offset, y_offset);
-    Vec2::new(x_offset, y_offset)
+/// Character standing in for a sort in the bidi algorithm
+///
+/// Glyphs without a codepoint (alternates, ligatures) are neutral and take
+/// their direction from the surrounding text.
+fn bidi_character(sort: &SortData) -> char {
+    const NEUTRAL: char = '\u{FFFC}';
+    match sort.kind.codepoint() {
+        // Paragraph separators would split the line into paragraphs
+        Some(c) if bidi_class(c) != BidiClass::B => c,
+        _ => NEUTRAL,
+    }
 }
 
-/// Calculate RTL text flow offset
+/// Cursor index nearest to a point given in buffer-local coordinates
 ///
-/// RTL POSITIONING LOGIC:
-/// - Start at (0, 0) relative to buffer root (which is the RIGHT edge)
-/// - Move LEFT by subtracting advance widths for glyphs AT OR AFTER target
-/// - Line breaks reset to x=0 and move down by line_height
-fn calculate_rtl_offset(
-    buffer_sorts: &[&SortData],
-    target_index: usize,
-    line_height: f32,
-) -> Vec2 {
-    let mut x_offset = 0.0;
-    let mut y_offset = 0.0;
+/// Returns `None` when the point is outside the buffer's text: above or
+/// below every line (using the font's ascender and descender), or further
+/// than `margin` beyond either end of the line it falls on.
+pub fn nearest_cursor_index(
+    offsets: &[Vec2],
+    local_point: Vec2,
+    ascender: f32,
+    descender: f32,
+    margin: f32,
+) -> Option<usize> {
+    let line: Vec<(usize, Vec2)> = offsets
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, offset)| {
+            local_point.y >= offset.y + descender && local_point.y <= offset.y + ascender
+        })
+        .collect();
+
+    let min_x = line.iter().map(|(_, o)| o.x).reduce(f32::min)?;
+    let max_x = line.iter().map(|(_, o)| o.x).reduce(f32::max)?;
+    if local_point.x < min_x - margin || local_point.x > max_x + margin {
+        return None;
+    }
+
+    line.into_iter()
+        .min_by(|(_, a), (_, b)| {
+            let da = (a.x - local_point.x).abs();
+            let db = (b.x - local_point.x).abs();
+            da.total_cmp(&db)
+        })
+        .map(|(index, _)| index)
+}
+
+/// Number of lines framed text occupies
+pub fn frame_line_count(buffer_sorts: &[&SortData], frame: &TextFrame) -> usize {
+    wrap_lines(buffer_sorts, frame.width).len()
+}
+
+fn glyph_advance(sort: &SortData) -> f32 {
+    match &sort.kind {
+        SortKind::Glyph { advance_width, .. } => *advance_width,
+        SortKind::LineBreak => 0.0,
+    }
+}
+
+fn is_space(sort: &SortData) -> bool {
+    let is_space_char = matches!(&sort.kind, SortKind::Glyph { codepoint: Some(' '), .. });
+    is_space_char || sort.kind.glyph_name() == "space"
+}
+
+/// Width of a line without its trailing spaces and line break
+fn visible_line_width(line: &[&SortData]) -> f32 {
+    let end = line
+        .iter()
+        .rposition(|sort| !sort.kind.is_line_break() && !is_space(sort))
+        .map_or(0, |last| last + 1);
+    line[..end].iter().map(|sort| glyph_advance(sort)).sum()
+}
+
+/// Split sorts into lines no wider than `width`
+///
+/// Every line includes its trailing spaces and any hard line break, so the
+/// ranges cover the buffer without gaps. There is always at least one line.
+fn wrap_lines(buffer_sorts: &[&SortData], width: f32) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut x = 0.0;
+    // Position just after the most recent space on the current line
+    let mut break_after_space: Option<usize> = None;
 
-    // RTL: Process sorts AT OR AFTER target to move cursor leftward
     for (i, sort) in buffer_sorts.iter().enumerate() {
-        if i < target_index {
+        if sort.kind.is_line_break() {
+            lines.push(start..i + 1);
+            start = i + 1;
+            x = 0.0;
+            break_after_space = None;
             continue;
         }
 
-        match &sort.kind {
-            SortKind::LineBreak => {
-                if i == target_index {
-                    y_offset -= line_height;
-                    break;
-                }
-            }
-            SortKind::Glyph { advance_width, .. } => {
-                x_offset -= advance_width;
-            }
+        let advance = glyph_advance(sort);
+        if is_space(sort) {
+            // Spaces may hang past the frame edge
+            x += advance;
+            break_after_space = Some(i + 1);
+            continue;
         }
+
+        if x + advance > width && i > start {
+            let break_at = break_after_space.filter(|&b| b > start).unwrap_or(i);
+            lines.push(start..break_at);
+            start = break_at;
+            x = buffer_sorts[break_at..i].iter().map(|s| glyph_advance(s)).sum();
+            break_after_space = None;
+        }
+        x += advance;
+    }
+
+    lines.push(start..buffer_sorts.len());
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glyph(codepoint: char) -> SortData {
+        SortData {
+            kind: SortKind::Glyph {
+                codepoint: Some(codepoint),
+                glyph_name: codepoint.to_string(),
+                advance_width: 100.0,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_frame_wraps_after_spaces_and_aligns() {
+        let sorts: Vec<SortData> = "ab cd ef".chars().map(glyph).collect();
+        let refs: Vec<&SortData> = sorts.iter().collect();
+        let frame = TextFrame {
+            width: 550.0,
+            alignment: TextAlignment::Right,
+        };
+
+        assert_eq!(wrap_lines(&refs, frame.width), vec![0..6, 6..8]);
+
+        let layout = flow_layout(&refs, 1000.0, &SortLayoutMode::LTRText, Some(&frame));
+        assert_eq!(layout.cursors.len(), sorts.len() + 1);
+        // "ab cd" is 500 wide once the trailing space is ignored
+        assert_eq!(layout.sort_origins[0], Vec2::new(50.0, 0.0));
+        assert_eq!(layout.cursors[6], Vec2::new(350.0, -1000.0));
+        assert_eq!(layout.cursors[8], Vec2::new(550.0, -1000.0));
+    }
+
+    #[test]
+    fn test_bidi_reorders_mixed_runs() {
+        // Logical "ab" + Hebrew alef, bet + "c" in an LTR buffer
+        let sorts: Vec<SortData> = "ab\u{05D0}\u{05D1}c".chars().map(glyph).collect();
+        let refs: Vec<&SortData> = sorts.iter().collect();
+        let layout = flow_layout(&refs, 1000.0, &SortLayoutMode::LTRText, None);
+
+        // Displayed as "a b bet alef c"
+        let xs: Vec<f32> = layout.sort_origins.iter().map(|o| o.x).collect();
+        assert_eq!(xs, vec![0.0, 100.0, 300.0, 200.0, 400.0]);
+        // Every cursor index has its own spot at the run boundaries
+        let cursor_xs: Vec<f32> = layout.cursors.iter().map(|c| c.x).collect();
+        assert_eq!(cursor_xs, vec![0.0, 100.0, 400.0, 300.0, 200.0, 500.0]);
+
+        // Right arrow after "b" steps into the Hebrew run from its left end
+        assert_eq!(visual_cursor_step(&layout, 1, true, false), Some(4));
+        assert_eq!(visual_cursor_step(&layout, 0, false, false), None);
+
+        // In an RTL buffer the line reads "c bet alef a b" and ends at the root
+        let rtl = flow_layout(&refs, 1000.0, &SortLayoutMode::RTLText, None);
+        assert_eq!(rtl.sort_origins[4].x, -500.0);
+        assert_eq!(rtl.sort_origins[2].x, -300.0);
+        assert_eq!(rtl.cursors[2].x, 0.0);
     }
 
-    Vec2::new(x_offset, y_offset)
+    #[test]
+    fn test_selection_spans_split_across_runs() {
+        let sorts: Vec<SortData> = "ab\u{05D0}\u{05D1}c".chars().map(glyph).collect();
+        let refs: Vec<&SortData> = sorts.iter().collect();
+        let layout = flow_layout(&refs, 1000.0, &SortLayoutMode::LTRText, None);
+
+        // "b" and alef are apart on screen, with bet between them
+        assert_eq!(
+            selection_spans(&refs, &layout, 1..3),
+            vec![
+                (Vec2::new(100.0, 0.0), 100.0),
+                (Vec2::new(300.0, 0.0), 100.0)
+            ]
+        );
+        // Adding bet joins them into one stretch
+        assert_eq!(
+            selection_spans(&refs, &layout, 1..4),
+            vec![(Vec2::new(100.0, 0.0), 300.0)]
+        );
+
+        // Clicking the left half of bet puts the cursor after it, on screen
+        // just left of it
+        let click = Vec2::new(220.0, 0.0);
+        let index = nearest_cursor_index(&layout.cursors, click, 800.0, -200.0, 0.0);
+        assert_eq!(index, Some(4));
+        assert_eq!(layout.cursors[4].x, 200.0);
+    }
 }
@@ -4,8 +4,12 @@
 //! enabling input of any Unicode character including Latin, Arabic, Hebrew,
 //! Chinese, Japanese, Korean, and other global scripts.
 
-use crate::core::state::{AppState, TextEditorState};
+use crate::core::state::text_editor::buffer::BufferId;
+use crate::core::state::text_editor::text_buffer::BufferCursor;
+use crate::core::state::{text_line_height, AppState, TextEditorState};
 use crate::systems::sorts::input_utilities::unicode_to_glyph_name;
+use crate::systems::sorts::sort_clipboard::{queue_respawn, remove_sorts};
+use crate::systems::sorts::sort_entities::BufferSortRespawnQueue;
 use crate::ui::edit_mode_toolbar::text::TextPlacementMode;
 use crate::ui::edit_mode_toolbar::CurrentTool;
 use bevy::input::keyboard::{Key, KeyboardInput};
@@ -29,22 +33,29 @@ pub fn handle_unicode_text_input(
         &mut crate::core::state::text_editor::text_buffer::BufferCursor,
     )>,
     mut respawn_queue: ResMut<crate::systems::sorts::sort_entities::BufferSortRespawnQueue>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    ime_composition: Res<crate::systems::sorts::ime_input::ImeComposition>,
+    alternates_picker: Res<crate::systems::sorts::alternates_picker::AlternatesPicker>,
+    character_picker: Res<crate::systems::sorts::character_picker::CharacterPicker>,
+    spacing_mode: Res<crate::systems::sorts::spacing_mode::SpacingMode>,
+    text_field_focus: Res<crate::editing::text_field_focus::TextFieldFocus>,
 ) {
     // EARLY RETURN: Skip all expensive work if no keyboard events
     if key_evr.is_empty() {
-        debug!("Unicode input skipped - no keyboard events");
         return;
     }
 
-    // DEBUG: Log system entry for any keyboard input
-    let key_count = key_evr.len();
-    debug!("Unicode input: {} keyboard events detected", key_count);
-    debug!("Current tool: {:?}", current_tool.get_current());
-    debug!("Current placement mode: {:?}", *current_placement_mode);
+    let _span = debug_span!(
+        "unicode_input",
+        events = key_evr.len(),
+        tool = ?current_tool.get_current(),
+        mode = ?*current_placement_mode
+    )
+    .entered();
 
     // Only handle input when text tool is active
     if current_tool.get_current() != Some("text") {
-        debug!("Unicode input blocked: Text tool not active");
+        trace!("Text tool not active");
         return;
     }
 
@@ -53,60 +64,66 @@ pub fn handle_unicode_text_input(
         *current_placement_mode,
         TextPlacementMode::Insert | TextPlacementMode::RTLText | TextPlacementMode::LTRText
     ) {
-        debug!(
-            "Unicode input blocked: Not in a text input mode (current: {:?})",
-            *current_placement_mode
-        );
+        trace!("Not in a text input mode");
         return;
     }
 
-    if key_count > 0 {
-        debug!(
-            "Unicode input: Processing {} keyboard events in text input mode ({:?})",
-            key_count, *current_placement_mode
-        );
+    // While an input method is composing, keys belong to the IME; the
+    // composed text arrives as an `Ime::Commit` instead
+    if ime_composition.is_composing() {
+        key_evr.clear();
+        return;
     }
 
-    debug!("Unicode input: Processing in Insert mode");
+    // The alternates picker takes arrows, Enter and Escape while it's open,
+    // the character picker takes typing for its search, spacing mode takes
+    // the arrows instead of the cursor, and a prompt's text field takes
+    // typing while it's focused
+    if alternates_picker.is_open()
+        || character_picker.is_open()
+        || spacing_mode.active
+        || text_field_focus.is_focused()
+    {
+        key_evr.clear();
+        return;
+    }
 
-    // Handle keyboard input events
-    let event_count = key_evr.len();
-    debug!("Unicode input: Processing {} keyboard events", event_count);
+    // Cmd/Ctrl+key combinations are shortcuts, not text
+    let shortcut_modifier = keyboard.any_pressed([
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+    ]);
+    // Shift+arrows extend the selection
+    let extend_selection = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
 
+    // Handle keyboard input events
     for ev in key_evr.read() {
-        debug!(
-            "Unicode input: Keyboard event - key: {:?}, state: {:?}",
-            ev.logical_key, ev.state
-        );
+        trace!(key = ?ev.logical_key, state = ?ev.state, "Keyboard event");
 
         // Only process pressed keys
-        let is_pressed = matches!(ev.state, ButtonState::Pressed);
-        debug!(
-            "Unicode input: Key state - is_pressed: {}, raw state: {:?}",
-            is_pressed, ev.state
-        );
-
-        if !is_pressed {
-            debug!("Unicode input: Skipping non-pressed key event");
+        if !matches!(ev.state, ButtonState::Pressed) {
             continue;
         }
 
         match &ev.logical_key {
             // Handle Unicode character input
+            Key::Character(_) if shortcut_modifier => {
+                trace!("Skipping shortcut key combination");
+            }
             Key::Character(character_string) => {
-                debug!(
-                    "Unicode input: Character key pressed: '{}'",
-                    character_string
-                );
                 // Process each character in the string (usually just one)
                 for character in character_string.chars() {
-                    debug!(
-                        "Unicode input: Processing character '{}' (U+{:04X})",
-                        character, character as u32
-                    );
+                    let _span = trace_span!(
+                        "character",
+                        %character,
+                        codepoint = %format_args!("U+{:04X}", character as u32)
+                    )
+                    .entered();
                     // Skip control characters (except newline)
                     if character.is_control() && character != '\n' {
-                        debug!("Unicode input: Skipping control character");
+                        trace!("Skipping control character");
                         continue;
                     }
 
@@ -127,12 +144,10 @@ pub fn handle_unicode_text_input(
                     // Skip newline character - handled by Key::Enter instead
                     // to avoid duplicate line break insertion
                     if character == '\n' {
-                        debug!("Unicode input: Skipping '\\n' character - handled by Key::Enter");
                         continue;
                     }
 
                     // Handle regular Unicode character
-                    debug!("Unicode input: Handling character '{}'", character);
                     handle_unicode_character(
                         character,
                         &mut commands,
@@ -143,7 +158,6 @@ pub fn handle_unicode_text_input(
                         &mut buffer_query,
                         &mut respawn_queue,
                     );
-                    debug!("Unicode input: Completed character '{}'", character);
                 }
             }
             // Handle special keys
@@ -186,10 +200,22 @@ pub fn handle_unicode_text_input(
                 );
             }
             Key::ArrowLeft => {
-                handle_arrow_left(&mut text_editor_state, &active_buffer, &mut buffer_query);
+                handle_arrow_left(
+                    &mut text_editor_state,
+                    &active_buffer,
+                    &mut buffer_query,
+                    &app_state,
+                    extend_selection,
+                );
             }
             Key::ArrowRight => {
-                handle_arrow_right(&mut text_editor_state, &active_buffer, &mut buffer_query);
+                handle_arrow_right(
+                    &mut text_editor_state,
+                    &active_buffer,
+                    &mut buffer_query,
+                    &app_state,
+                    extend_selection,
+                );
             }
             Key::ArrowUp => {
                 handle_arrow_up(
@@ -197,6 +223,7 @@ pub fn handle_unicode_text_input(
                     &active_buffer,
                     &mut buffer_query,
                     &app_state,
+                    extend_selection,
                 );
             }
             Key::ArrowDown => {
@@ -205,6 +232,7 @@ pub fn handle_unicode_text_input(
                     &active_buffer,
                     &mut buffer_query,
                     &app_state,
+                    extend_selection,
                 );
             }
             _ => {
@@ -214,6 +242,54 @@ pub fn handle_unicode_text_input(
     }
 }
 
+/// Insert text that arrived in one piece (e.g. an IME commit) at the cursor
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn insert_text_at_buffer_cursor(
+    text: &str,
+    commands: &mut Commands,
+    text_editor_state: &mut ResMut<TextEditorState>,
+    app_state: &Option<Res<AppState>>,
+    current_placement_mode: &TextPlacementMode,
+    active_buffer: &Option<Res<crate::core::state::text_editor::text_buffer::ActiveTextBuffer>>,
+    buffer_query: &mut Query<(
+        &crate::core::state::text_editor::text_buffer::TextBuffer,
+        &mut crate::core::state::text_editor::text_buffer::BufferCursor,
+    )>,
+    respawn_queue: &mut ResMut<crate::systems::sorts::sort_entities::BufferSortRespawnQueue>,
+) {
+    for character in text.chars() {
+        match character {
+            ' ' => handle_space_character(
+                commands,
+                text_editor_state,
+                app_state,
+                current_placement_mode,
+                active_buffer,
+                buffer_query,
+                respawn_queue,
+            ),
+            '\n' => handle_newline_character(
+                text_editor_state,
+                current_placement_mode,
+                active_buffer,
+                buffer_query,
+                respawn_queue,
+            ),
+            c if c.is_control() => {}
+            c => handle_unicode_character(
+                c,
+                commands,
+                text_editor_state,
+                app_state,
+                current_placement_mode,
+                active_buffer,
+                buffer_query,
+                respawn_queue,
+            ),
+        }
+    }
+}
+
 /// Handle a single Unicode character input
 #[allow(clippy::too_many_arguments)]
 fn handle_unicode_character(
@@ -484,6 +560,13 @@ fn insert_line_break_at_buffer_cursor(
         return false;
     };
 
+    // A line break replaces the selection
+    delete_selection(
+        text_editor_state,
+        text_buffer.id,
+        &mut buffer_cursor,
+        respawn_queue,
+    );
     let cursor_position = buffer_cursor.position;
     let buffer_id = text_buffer.id;
     let layout_mode = text_buffer.layout_mode.clone();
@@ -498,6 +581,7 @@ fn insert_line_break_at_buffer_cursor(
         is_active: false,
         layout_mode,
         root_position: bevy::prelude::Vec2::ZERO,
+        placement_offset: Vec2::ZERO,
         buffer_cursor_position: None,
         buffer_id: Some(buffer_id),
     };
@@ -519,7 +603,7 @@ fn insert_line_break_at_buffer_cursor(
     }
 
     // Update the cursor position in the buffer entity (advance by 1 to position after line break)
-    buffer_cursor.position = cursor_position + 1;
+    buffer_cursor.move_to(cursor_position + 1, false);
 
     // Mark text editor state as changed for rendering updates
     text_editor_state.set_changed();
@@ -637,7 +721,7 @@ fn delete_character_at_buffer_cursor(
         warn!("⚠️ DELETE: Active buffer has no entity");
         return false;
     };
-    let Ok((_text_buffer, mut buffer_cursor)) = buffer_query.get_mut(buffer_entity) else {
+    let Ok((text_buffer, mut buffer_cursor)) = buffer_query.get_mut(buffer_entity) else {
         warn!(
             "⚠️ DELETE: Could not access buffer cursor for entity {:?}",
             buffer_entity
@@ -645,6 +729,19 @@ fn delete_character_at_buffer_cursor(
         return false;
     };
 
+    // Backspace and Delete remove the selection, if there is one, instead
+    // of a character
+    if delete_selection(
+        text_editor_state,
+        text_buffer.id,
+        &mut buffer_cursor,
+        respawn_queue,
+    ) {
+        text_editor_state.set_changed();
+        debug!("🗑️ DELETE: Deleted the selection");
+        return true;
+    }
+
     let cursor_position = buffer_cursor.position;
 
     // Calculate which buffer index to delete based on direction
@@ -714,14 +811,14 @@ fn delete_character_at_buffer_cursor(
     // Update cursor position based on deletion direction
     if delete_to_left {
         // Backspace: cursor moves left by 1
-        buffer_cursor.position = cursor_position - 1;
+        buffer_cursor.move_to(cursor_position - 1, false);
         debug!(
             "⬅️ DELETE: Cursor moved left to position {}",
             buffer_cursor.position
         );
     } else {
         // Delete key: cursor stays in same position (but content shifted left)
-        // No cursor position change needed
+        buffer_cursor.move_to(cursor_position, false);
         debug!("➡️ DELETE: Cursor remains at position {}", cursor_position);
     }
 
@@ -736,13 +833,32 @@ fn delete_character_at_buffer_cursor(
     true
 }
 
+/// Delete the sorts in the cursor's selection, as cut does, leaving the
+/// cursor where the selection started; returns whether anything was
+/// selected
+fn delete_selection(
+    text_editor_state: &mut TextEditorState,
+    buffer_id: BufferId,
+    buffer_cursor: &mut BufferCursor,
+    respawn_queue: &mut BufferSortRespawnQueue,
+) -> bool {
+    let Some(range) = buffer_cursor.selection() else {
+        return false;
+    };
+    if let Some(first) = remove_sorts(text_editor_state, buffer_id, range.clone()) {
+        queue_respawn(text_editor_state, respawn_queue, first);
+    }
+    buffer_cursor.move_to(range.start, false);
+    true
+}
+
 /// Get advance width for a glyph from AppState
 fn get_glyph_advance_width(
     glyph_name: &str,
     app_state: &Option<Res<AppState>>,
 ) -> f32 {
     if let Some(app_state) = app_state.as_ref() {
-        if let Some(glyph_data) = app_state.workspace.font.glyphs.get(glyph_name) {
+        if let Some(glyph_data) = app_state.workspace.font.editing().get_glyph(glyph_name) {
             return glyph_data.advance_width as f32;
         }
     }
@@ -790,6 +906,13 @@ fn insert_character_at_buffer_cursor(
         return false;
     };
 
+    // Typing replaces the selection
+    delete_selection(
+        text_editor_state,
+        text_buffer.id,
+        &mut buffer_cursor,
+        respawn_queue,
+    );
     let cursor_position = buffer_cursor.position;
     let buffer_id = text_buffer.id;
     let layout_mode = text_buffer.layout_mode.clone();
@@ -799,16 +922,12 @@ fn insert_character_at_buffer_cursor(
         character, cursor_position, buffer_id.0, layout_mode
     );
 
-    // DEBUG: Show buffer state before insertion
-    debug!("🔍 INSERT DEBUG: Buffer state before insertion:");
-    for (i, sort) in text_editor_state.buffer.iter().enumerate() {
-        if sort.buffer_id == Some(buffer_id) {
-            debug!(
-                "  [{}] glyph='{}', buffer_id={:?}",
-                i,
-                sort.kind.glyph_name(),
-                sort.buffer_id
-            );
+    // The buffer's sorts before insertion, only walked when traced
+    if tracing::enabled!(tracing::Level::TRACE) {
+        for (i, sort) in text_editor_state.buffer.iter().enumerate() {
+            if sort.buffer_id == Some(buffer_id) {
+                trace!(index = i, glyph = %sort.kind.glyph_name(), "Sort before insertion");
+            }
         }
     }
 
@@ -816,7 +935,7 @@ fn insert_character_at_buffer_cursor(
     use crate::core::state::text_editor::buffer::{SortData, SortKind};
 
     // Use the buffer's root position, not a calculated one
-    // The actual world position will be calculated by calculate_buffer_local_position
+    // The actual world position will be calculated by layout_text_buffers
     // based on the buffer's root position + accumulated advances
     let new_sort = SortData {
         kind: SortKind::Glyph {
@@ -827,6 +946,7 @@ fn insert_character_at_buffer_cursor(
         is_active: false, // Don't make new sorts active by default
         layout_mode: layout_mode.clone(),
         root_position: text_buffer.root_position, // Use buffer's root position for consistency
+        placement_offset: Vec2::ZERO,
         buffer_cursor_position: None,
         buffer_id: Some(buffer_id), // Inherit buffer ID from buffer entity
     };
@@ -872,13 +992,10 @@ fn insert_character_at_buffer_cursor(
     // CRITICAL FIX: Queue respawn for all buffer indices that shifted due to insertion
     // When we insert at index N, all existing entities at indices N and above need respawning
     // because their buffer indices shifted by +1
-    for i in insert_buffer_index..text_editor_state.buffer.len() {
-        respawn_queue.indices.push(i);
-        debug!(
-            "🔄 RESPAWN QUEUE: Added buffer index {} to respawn queue due to insertion",
-            i
-        );
-    }
+    respawn_queue
+        .indices
+        .extend(insert_buffer_index..text_editor_state.buffer.len());
+    trace!(from = insert_buffer_index, "Queued shifted sorts for respawn");
 
     // DEBUG: Verify what actually got inserted
     if let Some(inserted_sort) = text_editor_state.buffer.get(insert_buffer_index) {
@@ -890,7 +1007,7 @@ fn insert_character_at_buffer_cursor(
     }
 
     // Update the cursor position in the buffer entity (advance by 1)
-    buffer_cursor.position = cursor_position + 1;
+    buffer_cursor.move_to(cursor_position + 1, false);
 
     debug!(
         "✅ INSERT: Successfully inserted '{}' as glyph '{}', cursor advanced to position {}",
@@ -905,16 +1022,7 @@ fn insert_character_at_buffer_cursor(
     true
 }
 
-/// Handle left arrow key press - move cursor left in the active buffer
-///
-/// IMPORTANT: Arrow keys work consistently across LTR and RTL modes
-/// - Left arrow always decreases buffer position (n → n-1)
-/// - The visual movement is handled by the cursor rendering system
-/// - In LTR: decreasing position moves cursor visually left (as expected)
-/// - In RTL: decreasing position ALSO moves cursor visually left because:
-///   * Position 0 is at the rightmost (start) position
-///   * Position N is at the leftmost (end) position
-///   * So decreasing position (N → 0) moves cursor from left to right visually
+/// Handle left arrow key press - move cursor left on screen
 fn handle_arrow_left(
     text_editor_state: &mut ResMut<TextEditorState>,
     active_buffer: &Option<Res<crate::core::state::text_editor::text_buffer::ActiveTextBuffer>>,
@@ -922,46 +1030,13 @@ fn handle_arrow_left(
         &crate::core::state::text_editor::text_buffer::TextBuffer,
         &mut crate::core::state::text_editor::text_buffer::BufferCursor,
     )>,
+    app_state: &Option<Res<AppState>>,
+    extend: bool,
 ) {
-    let Some(active_buffer) = active_buffer.as_ref() else {
-        debug!("No active buffer for arrow left");
-        return;
-    };
-
-    let Some(buffer_entity) = active_buffer.buffer_entity else {
-        debug!("No buffer entity for arrow left");
-        return;
-    };
-
-    let Ok((_text_buffer, mut buffer_cursor)) = buffer_query.get_mut(buffer_entity) else {
-        debug!("Buffer entity not found for arrow left");
-        return;
-    };
-
-    // Left arrow always decreases position regardless of text direction
-    // The visual effect (moving left on screen) is achieved through the cursor rendering logic
-    if buffer_cursor.position > 0 {
-        buffer_cursor.position -= 1;
-        debug!("⬅️ Left arrow moved to position {}", buffer_cursor.position);
-    } else {
-        debug!("Cursor already at position 0, cannot move left");
-    }
-
-    // Mark text editor state as changed to trigger cursor rendering update
-    use bevy::prelude::DetectChangesMut;
-    text_editor_state.set_changed();
+    move_cursor_visually(text_editor_state, active_buffer, buffer_query, app_state, false, extend);
 }
 
-/// Handle right arrow key press - move cursor right in the active buffer
-///
-/// IMPORTANT: Arrow keys work consistently across LTR and RTL modes
-/// - Right arrow always increases buffer position (n → n+1)
-/// - The visual movement is handled by the cursor rendering system
-/// - In LTR: increasing position moves cursor visually right (as expected)
-/// - In RTL: increasing position ALSO moves cursor visually right because:
-///   * Position 0 is at the rightmost (start) position
-///   * Position N is at the leftmost (end) position
-///   * So increasing position (0 → N) moves cursor from right to left visually
+/// Handle right arrow key press - move cursor right on screen
 fn handle_arrow_right(
     text_editor_state: &mut ResMut<TextEditorState>,
     active_buffer: &Option<Res<crate::core::state::text_editor::text_buffer::ActiveTextBuffer>>,
@@ -969,42 +1044,69 @@ fn handle_arrow_right(
         &crate::core::state::text_editor::text_buffer::TextBuffer,
         &mut crate::core::state::text_editor::text_buffer::BufferCursor,
     )>,
+    app_state: &Option<Res<AppState>>,
+    extend: bool,
 ) {
-    let Some(active_buffer) = active_buffer.as_ref() else {
-        debug!("No active buffer for arrow right");
-        return;
-    };
+    move_cursor_visually(text_editor_state, active_buffer, buffer_query, app_state, true, extend);
+}
 
-    let Some(buffer_entity) = active_buffer.buffer_entity else {
-        debug!("No buffer entity for arrow right");
+/// Move the active buffer's cursor one position left or right on screen
+///
+/// Left and right follow the displayed text rather than buffer order, so
+/// the cursor moves the way the arrow points through RTL buffers and
+/// through runs of the opposite direction (see `visual_cursor_step`).
+/// With `extend` (Shift held) the selection grows or shrinks with it.
+fn move_cursor_visually(
+    text_editor_state: &mut ResMut<TextEditorState>,
+    active_buffer: &Option<Res<crate::core::state::text_editor::text_buffer::ActiveTextBuffer>>,
+    buffer_query: &mut Query<(
+        &crate::core::state::text_editor::text_buffer::TextBuffer,
+        &mut crate::core::state::text_editor::text_buffer::BufferCursor,
+    )>,
+    app_state: &Option<Res<AppState>>,
+    rightwards: bool,
+    extend: bool,
+) {
+    let Some(buffer_entity) = active_buffer.as_ref().and_then(|active| active.buffer_entity)
+    else {
+        debug!("No active buffer for arrow key");
         return;
     };
-
     let Ok((text_buffer, mut buffer_cursor)) = buffer_query.get_mut(buffer_entity) else {
-        debug!("Buffer entity not found for arrow right");
+        debug!("Buffer entity not found for arrow key");
         return;
     };
 
-    // Right arrow always increases position regardless of text direction
-    // The visual effect (moving right on screen) is achieved through the cursor rendering logic
-    let buffer_sort_count = text_editor_state
+    let line_height = text_line_height(app_state.as_deref());
+    let buffer_sorts: Vec<&crate::core::state::text_editor::SortData> = text_editor_state
         .buffer
         .iter()
         .filter(|sort| sort.buffer_id == Some(text_buffer.id))
-        .count();
+        .collect();
+    let layout = crate::systems::sorts::text_flow_positioning::flow_layout(
+        &buffer_sorts,
+        line_height,
+        &text_buffer.layout_mode,
+        text_buffer.frame.as_ref(),
+    );
+    let base_rtl =
+        text_buffer.layout_mode == crate::core::state::text_editor::SortLayoutMode::RTLText;
 
-    if buffer_cursor.position < buffer_sort_count {
-        buffer_cursor.position += 1;
-        debug!(
-            "➡️ Right arrow moved to position {}",
-            buffer_cursor.position
-        );
-    } else {
-        debug!("Cursor already at end position, cannot move right");
+    let from = buffer_cursor.position.min(buffer_sorts.len());
+    match crate::systems::sorts::text_flow_positioning::visual_cursor_step(
+        &layout, from, rightwards, base_rtl,
+    ) {
+        Some(position) => {
+            buffer_cursor.move_to(position, extend);
+            debug!("Arrow moved cursor from {} to {}", from, position);
+        }
+        None => {
+            buffer_cursor.move_to(from, extend);
+            debug!("Cursor already at the end of the line");
+        }
     }
 
     // Mark text editor state as changed to trigger cursor rendering update
-    use bevy::prelude::DetectChangesMut;
     text_editor_state.set_changed();
 }
 
@@ -1017,6 +1119,7 @@ fn handle_arrow_up(
         &mut crate::core::state::text_editor::text_buffer::BufferCursor,
     )>,
     app_state: &Option<Res<AppState>>,
+    extend: bool,
 ) {
     let Some(active_buffer) = active_buffer.as_ref() else {
         debug!("No active buffer for arrow up");
@@ -1043,12 +1146,13 @@ fn handle_arrow_up(
         LineNavigation::Up,
         app_state,
     ) {
-        buffer_cursor.position = new_position;
+        buffer_cursor.move_to(new_position, extend);
         debug!(
             "⬆️ Moved cursor up from position {} to position {}",
             current_position, new_position
         );
     } else {
+        buffer_cursor.move_to(current_position, extend);
         debug!("Cursor already at top line, cannot move up");
     }
 
@@ -1066,6 +1170,7 @@ fn handle_arrow_down(
         &mut crate::core::state::text_editor::text_buffer::BufferCursor,
     )>,
     app_state: &Option<Res<AppState>>,
+    extend: bool,
 ) {
     let Some(active_buffer) = active_buffer.as_ref() else {
         debug!("No active buffer for arrow down");
@@ -1092,12 +1197,13 @@ fn handle_arrow_down(
         LineNavigation::Down,
         app_state,
     ) {
-        buffer_cursor.position = new_position;
+        buffer_cursor.move_to(new_position, extend);
         debug!(
             "⬇️ Moved cursor down from position {} to position {}",
             current_position, new_position
         );
     } else {
+        buffer_cursor.move_to(current_position, extend);
         debug!("Cursor already at bottom line, cannot move down");
     }
 
@@ -1159,8 +1265,8 @@ fn calculate_line_navigation_position(
                     app_state
                         .workspace
                         .font
-                        .glyphs
-                        .get(glyph_name)
+                        .editing()
+                        .get_glyph(glyph_name)
                         .map(|glyph_data| glyph_data.advance_width as f32)
                         .unwrap_or(*advance_width)
                 } else {
@@ -1237,3 +1343,129 @@ fn calculate_line_navigation_position(
 
     Some(best_position)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::state::text_editor::text_buffer::{ActiveTextBuffer, TextBuffer};
+    use crate::core::state::text_editor::{SortData, SortKind, SortLayoutMode};
+    use crate::systems::sorts::sort_clipboard::insert_sorts;
+    use bevy::ecs::system::RunSystemOnce;
+
+    /// A world with an active buffer holding "abcd", "bc" selected
+    fn world_with_selection() -> (World, Entity) {
+        let buffer = TextBuffer::new(BufferId(1), SortLayoutMode::LTRText, Vec2::ZERO);
+        let sorts: Vec<SortData> = "abcd"
+            .chars()
+            .map(|c| SortData {
+                kind: SortKind::Glyph {
+                    codepoint: Some(c),
+                    glyph_name: c.to_string(),
+                    advance_width: 100.0,
+                },
+                ..Default::default()
+            })
+            .collect();
+        let mut state = TextEditorState::default();
+        insert_sorts(&mut state, &buffer, 0, &sorts);
+        let mut cursor = BufferCursor::new(1);
+        cursor.move_to(3, true);
+
+        let mut world = World::new();
+        let entity = world.spawn((buffer, cursor)).id();
+        world.insert_resource(state);
+        world.insert_resource(ActiveTextBuffer {
+            buffer_entity: Some(entity),
+        });
+        world.init_resource::<BufferSortRespawnQueue>();
+        (world, entity)
+    }
+
+    fn text(world: &World) -> String {
+        world
+            .resource::<TextEditorState>()
+            .buffer
+            .iter()
+            .filter_map(|sort| sort.kind.codepoint())
+            .collect()
+    }
+
+    fn cursor(world: &World, entity: Entity) -> (usize, Option<std::ops::Range<usize>>) {
+        let cursor = world.get::<BufferCursor>(entity).unwrap();
+        (cursor.position, cursor.selection())
+    }
+
+    #[test]
+    fn test_typing_replaces_selection() {
+        let (mut world, entity) = world_with_selection();
+        world
+            .run_system_once(
+                |mut commands: Commands,
+                 mut state: ResMut<TextEditorState>,
+                 active: Option<Res<ActiveTextBuffer>>,
+                 mut query: Query<(&TextBuffer, &mut BufferCursor)>,
+                 mut respawn_queue: ResMut<BufferSortRespawnQueue>| {
+                    insert_character_at_buffer_cursor(
+                        'x',
+                        "x".to_string(),
+                        100.0,
+                        &mut commands,
+                        &mut state,
+                        &active,
+                        &mut query,
+                        &mut respawn_queue,
+                    );
+                },
+            )
+            .unwrap();
+        assert_eq!(text(&world), "axd");
+        assert_eq!(cursor(&world, entity), (2, None));
+    }
+
+    #[test]
+    fn test_backspace_deletes_selection() {
+        let (mut world, entity) = world_with_selection();
+        world
+            .run_system_once(
+                |mut state: ResMut<TextEditorState>,
+                 active: Option<Res<ActiveTextBuffer>>,
+                 mut query: Query<(&TextBuffer, &mut BufferCursor)>,
+                 mut respawn_queue: ResMut<BufferSortRespawnQueue>| {
+                    handle_backspace(
+                        &mut state,
+                        &TextPlacementMode::Insert,
+                        &active,
+                        &mut query,
+                        &mut respawn_queue,
+                    );
+                },
+            )
+            .unwrap();
+        assert_eq!(text(&world), "ad");
+        assert_eq!(cursor(&world, entity), (1, None));
+    }
+
+    #[test]
+    fn test_delete_deletes_selection() {
+        let (mut world, entity) = world_with_selection();
+        world
+            .run_system_once(
+                |mut state: ResMut<TextEditorState>,
+                 active: Option<Res<ActiveTextBuffer>>,
+                 mut query: Query<(&TextBuffer, &mut BufferCursor)>,
+                 mut respawn_queue: ResMut<BufferSortRespawnQueue>| {
+                    handle_delete(
+                        &mut state,
+                        &TextPlacementMode::Insert,
+                        &active,
+                        &mut query,
+                        &mut respawn_queue,
+                    );
+                },
+            )
+            .unwrap();
+        assert_eq!(text(&world), "ad");
+        assert_eq!(cursor(&world, entity), (1, None));
+        assert_eq!(world.resource::<BufferSortRespawnQueue>().indices, vec![1]);
+    }
+}
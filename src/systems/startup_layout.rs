@@ -126,6 +126,7 @@ fn create_default_sort_at_position(
         is_active: true,                      // Make it active and ready to edit
         layout_mode: SortLayoutMode::LTRText, // LTR text mode for typing
         root_position: position,
+        placement_offset: Vec2::ZERO,
         buffer_cursor_position: Some(1),
         buffer_id: Some(buffer_id), // Assign unique buffer ID for isolation
     };
@@ -0,0 +1,148 @@
+//! Substitution overlay for feature authors
+//!
+//! While the overlay is on, thin lines mark the edges of every shaped
+//! cluster in the text buffers, and clusters changed by the font's GSUB get
+//! a label under the descender: the characters that went in, the glyphs
+//! that came out and the features responsible. It is the buffer's answer to
+//! hb-view's debugging output.
+
+#![allow(clippy::too_many_arguments)]
+
+use crate::core::state::text_editor::SortKind;
+use crate::core::state::{AppState, TextEditorState};
+use crate::rendering::mesh_utils::create_line_mesh;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::systems::sorts::sort_entities::{BufferSortEntities, BufferSortIndex};
+use crate::systems::text_shaping::{ClusterTrace, SubstitutionTrace};
+use crate::ui::themes::CurrentTheme;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+/// Label text size relative to the font's units per em
+const LABEL_SIZE_EM: f32 = 0.08;
+/// The overlay draws just above the text frames
+const OVERLAY_Z: f32 = 5.0;
+
+/// Marker for cluster edge and label entities
+#[derive(Component)]
+struct SubstitutionOverlayVisual;
+
+pub struct SubstitutionOverlayPlugin;
+
+impl Plugin for SubstitutionOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, render_substitution_overlay);
+    }
+}
+
+/// Input characters, output glyphs and features, one per line
+fn cluster_label(cluster: &ClusterTrace) -> String {
+    let codepoints: Vec<String> = cluster
+        .codepoints
+        .iter()
+        .map(|c| format!("U+{:04X}", *c as u32))
+        .collect();
+    format!(
+        "{}\n{}\n{}",
+        codepoints.join(" "),
+        cluster.glyph_names.join(" "),
+        cluster.features.join(" ")
+    )
+}
+
+/// Draw cluster edges and substitution labels as meshes and text
+fn render_substitution_overlay(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    trace: Res<SubstitutionTrace>,
+    text_editor_state: Res<TextEditorState>,
+    buffer_entities: Res<BufferSortEntities>,
+    sort_query: Query<&Transform, With<BufferSortIndex>>,
+    moved_sorts: Query<(), (Changed<Transform>, With<BufferSortIndex>)>,
+    existing: Query<Entity, With<SubstitutionOverlayVisual>>,
+    camera_scale: Res<CameraResponsiveScale>,
+    theme: Res<CurrentTheme>,
+    asset_server: Res<AssetServer>,
+    app_state: Option<Res<AppState>>,
+) {
+    let needs_redraw = trace.is_changed()
+        || camera_scale.is_changed()
+        || theme.is_changed()
+        || !moved_sorts.is_empty();
+    if !needs_redraw {
+        return;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+    let Some(app_state) = app_state.as_ref() else {
+        return;
+    };
+    if trace.clusters.is_empty() {
+        return;
+    }
+
+    let metrics = &app_state.workspace.info.metrics;
+    let upm = metrics.units_per_em as f32;
+    let descender = metrics.descender.unwrap_or(-256.0) as f32;
+    let ascender = metrics.ascender.map_or(upm * 0.8, |a| a as f32);
+    let color = theme.theme().substitution_overlay_color();
+    let material = materials.add(ColorMaterial::from_color(color));
+    let line_width = camera_scale.adjusted_line_width();
+    let font = asset_server.load(theme.theme().mono_font_path());
+
+    for cluster in &trace.clusters {
+        // Edges of the cluster's advances, ignoring mark offsets
+        let mut left = f32::MAX;
+        let mut right = f32::MIN;
+        let mut baseline = None;
+        for index in &cluster.sorts {
+            let Some(sort) = text_editor_state.buffer.get(*index) else {
+                continue;
+            };
+            let SortKind::Glyph { advance_width, .. } = &sort.kind else {
+                continue;
+            };
+            let Some(transform) = buffer_entities
+                .entities
+                .get(index)
+                .and_then(|entity| sort_query.get(*entity).ok())
+            else {
+                continue;
+            };
+            let origin = transform.translation.truncate() - sort.placement_offset;
+            left = left.min(origin.x);
+            right = right.max(origin.x + advance_width);
+            baseline = Some(origin.y);
+        }
+        let Some(baseline) = baseline else {
+            continue;
+        };
+
+        for x in [left, right] {
+            let start = Vec2::new(x, baseline + descender);
+            let end = Vec2::new(x, baseline + ascender);
+            commands.spawn((
+                SubstitutionOverlayVisual,
+                Mesh2d(meshes.add(create_line_mesh(start, end, line_width))),
+                MeshMaterial2d(material.clone()),
+                Transform::from_translation(((start + end) * 0.5).extend(OVERLAY_Z)),
+            ));
+        }
+        if cluster.is_substituted() {
+            commands.spawn((
+                SubstitutionOverlayVisual,
+                Text2d::new(cluster_label(cluster)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: upm * LABEL_SIZE_EM,
+                    ..default()
+                },
+                TextColor(color),
+                Anchor::TopLeft,
+                Transform::from_xyz(left, baseline + descender, OVERLAY_Z),
+            ));
+        }
+    }
+}
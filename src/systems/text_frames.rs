@@ -0,0 +1,356 @@
+//! Text frames for proofing paragraphs
+//!
+//! A text frame gives a buffer a fixed width so its text wraps into lines.
+//! With the text tool active:
+//! - Cmd/Ctrl+Shift+F turns the active buffer into a frame (or back)
+//! - Cmd/Ctrl+Shift+L / C / R aligns its lines left, center or right
+//! - dragging a frame's left or right edge resizes it
+//!
+//! Layout itself lives in `sorts::text_flow_positioning`, so sorts, cursor
+//! and hit-testing all agree on where wrapped text goes.
+
+#![allow(clippy::too_many_arguments)]
+
+use crate::core::state::text_editor::text_buffer::{ActiveTextBuffer, TextBuffer};
+use crate::core::state::text_editor::{SortData, SortLayoutMode, TextAlignment, TextFrame};
+use crate::core::state::{AppState, TextEditorState};
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::cameras::DesignCamera;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::systems::sorts::handle_sort_placement_input;
+use crate::systems::sorts::text_flow_positioning::frame_line_count;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use crate::ui::themes::CurrentTheme;
+use bevy::prelude::*;
+
+/// Width given to a new frame, in ems
+const DEFAULT_FRAME_WIDTH_EM: f32 = 10.0;
+/// How close (in screen pixels) the pointer must be to grab a frame edge
+const EDGE_GRAB_TOLERANCE_PX: f32 = 8.0;
+/// Frames sit just behind the metrics lines
+const TEXT_FRAME_Z: f32 = 4.5;
+
+/// Turn the active buffer into a frame, or a frame back into a line
+#[derive(Event, Clone)]
+pub struct ToggleTextFrameEvent;
+
+/// Align the lines of the active buffer's frame
+#[derive(Event, Clone)]
+pub struct SetTextFrameAlignmentEvent(pub TextAlignment);
+
+/// Which frame edge is being dragged
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FrameEdge {
+    Left,
+    Right,
+}
+
+/// Frame edge drag in progress
+#[derive(Resource, Default)]
+struct TextFrameDrag {
+    target: Option<(Entity, FrameEdge)>,
+}
+
+/// Marker for frame outline and handle meshes
+#[derive(Component)]
+struct TextFrameVisual;
+
+pub struct TextFramesPlugin;
+
+impl Plugin for TextFramesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TextFrameDrag>()
+            .add_event::<ToggleTextFrameEvent>()
+            .add_event::<SetTextFrameAlignmentEvent>()
+            .add_systems(
+                Update,
+                (
+                    handle_text_frame_shortcuts,
+                    handle_text_frame_events,
+                    handle_text_frame_drag.before(handle_sort_placement_input),
+                )
+                    .chain(),
+            )
+            .add_systems(PostUpdate, render_text_frames);
+    }
+}
+
+/// Line metrics shared by the frame systems
+struct FrameMetrics {
+    line_height: f32,
+    ascender: f32,
+    descender: f32,
+    upm: f32,
+}
+
+impl FrameMetrics {
+    fn from_app_state(app_state: &AppState) -> Self {
+        let metrics = &app_state.workspace.info.metrics;
+        let upm = metrics.units_per_em as f32;
+        let descender = metrics.descender.unwrap_or(-256.0) as f32;
+        Self {
+            line_height: upm - descender,
+            ascender: metrics.ascender.map_or(upm * 0.8, |a| a as f32),
+            descender,
+            upm,
+        }
+    }
+
+    /// Top and bottom of a frame holding `lines` lines
+    fn vertical_extent(&self, root: Vec2, lines: usize) -> (f32, f32) {
+        let top = root.y + self.ascender;
+        let bottom = root.y - (lines.max(1) - 1) as f32 * self.line_height + self.descender;
+        (top, bottom)
+    }
+}
+
+fn buffer_sorts<'a>(
+    text_editor_state: &'a TextEditorState,
+    buffer: &TextBuffer,
+) -> Vec<&'a SortData> {
+    text_editor_state
+        .buffer
+        .iter()
+        .filter(|sort| sort.buffer_id == Some(buffer.id))
+        .collect()
+}
+
+fn handle_text_frame_shortcuts(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_tool: Res<CurrentTool>,
+    mut toggle_events: EventWriter<ToggleTextFrameEvent>,
+    mut align_events: EventWriter<SetTextFrameAlignmentEvent>,
+) {
+    if current_tool.get_current() != Some("text") {
+        return;
+    }
+    if Shortcut::TextFrame.just_pressed(&keyboard) {
+        toggle_events.write(ToggleTextFrameEvent);
+    }
+    let alignment = match Shortcut::AlignText.pressed_key(&keyboard) {
+        Some(KeyCode::KeyL) => TextAlignment::Left,
+        Some(KeyCode::KeyC) => TextAlignment::Center,
+        Some(KeyCode::KeyR) => TextAlignment::Right,
+        _ => return,
+    };
+    align_events.write(SetTextFrameAlignmentEvent(alignment));
+}
+
+fn handle_text_frame_events(
+    mut toggle_events: EventReader<ToggleTextFrameEvent>,
+    mut align_events: EventReader<SetTextFrameAlignmentEvent>,
+    active_buffer: Res<ActiveTextBuffer>,
+    mut buffer_query: Query<&mut TextBuffer>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    app_state: Option<Res<AppState>>,
+) {
+    let Some(mut buffer) = active_buffer
+        .buffer_entity
+        .and_then(|entity| buffer_query.get_mut(entity).ok())
+    else {
+        toggle_events.clear();
+        align_events.clear();
+        return;
+    };
+    let upm = app_state
+        .as_ref()
+        .map_or(1000.0, |state| state.workspace.info.metrics.units_per_em as f32);
+    let mut changed = false;
+
+    for _ in toggle_events.read() {
+        if buffer.layout_mode == SortLayoutMode::Freeform {
+            continue;
+        }
+        let is_rtl = buffer.layout_mode == SortLayoutMode::RTLText;
+        match buffer.frame.take() {
+            Some(frame) => {
+                // Unframed RTL text hangs left of its root, framed text
+                // starts at the frame's left edge
+                if is_rtl {
+                    buffer.root_position.x += frame.width;
+                }
+                info!("Removed text frame");
+            }
+            None => {
+                let frame = TextFrame::new(upm * DEFAULT_FRAME_WIDTH_EM, &buffer.layout_mode);
+                if is_rtl {
+                    buffer.root_position.x -= frame.width;
+                }
+                info!("Added {:.0} unit text frame", frame.width);
+                buffer.frame = Some(frame);
+            }
+        }
+        changed = true;
+    }
+
+    for SetTextFrameAlignmentEvent(alignment) in align_events.read() {
+        if let Some(frame) = buffer.frame.as_mut() {
+            frame.alignment = *alignment;
+            changed = true;
+        } else {
+            debug!("Alignment needs a text frame (Cmd/Ctrl+Shift+F)");
+        }
+    }
+
+    if changed {
+        // Sort positions are recomputed when the text state changes
+        text_editor_state.set_changed();
+    }
+}
+
+/// Resize frames by dragging their left or right edge
+fn handle_text_frame_drag(
+    mut mouse_button_input: ResMut<ButtonInput<MouseButton>>,
+    current_tool: Res<CurrentTool>,
+    pointer_info: Res<crate::io::pointer::PointerInfo>,
+    ui_hover_state: Res<crate::systems::ui_interaction::UiHoverState>,
+    camera_query: Query<&Projection, With<DesignCamera>>,
+    mut buffer_query: Query<(Entity, &mut TextBuffer)>,
+    mut drag: ResMut<TextFrameDrag>,
+    mut text_editor_state: ResMut<TextEditorState>,
+    app_state: Option<Res<AppState>>,
+) {
+    if !matches!(current_tool.get_current(), Some("text") | Some("select")) {
+        drag.target = None;
+        return;
+    }
+    let Some(app_state) = app_state.as_ref() else {
+        return;
+    };
+    let pointer = pointer_info.design.to_raw();
+
+    if mouse_button_input.just_pressed(MouseButton::Left) && !ui_hover_state.is_hovering_ui {
+        let zoom = match camera_query.single() {
+            Ok(Projection::Orthographic(ortho)) => ortho.scale,
+            _ => 1.0,
+        };
+        let tolerance = EDGE_GRAB_TOLERANCE_PX * zoom;
+        let metrics = FrameMetrics::from_app_state(app_state);
+
+        drag.target = buffer_query.iter().find_map(|(entity, buffer)| {
+            let frame = buffer.frame.as_ref()?;
+            let lines = frame_line_count(&buffer_sorts(&text_editor_state, buffer), frame);
+            let (top, bottom) = metrics.vertical_extent(buffer.root_position, lines);
+            if pointer.y > top + tolerance || pointer.y < bottom - tolerance {
+                return None;
+            }
+            let left = buffer.root_position.x;
+            let right = left + frame.width;
+            if (pointer.x - right).abs() <= tolerance {
+                Some((entity, FrameEdge::Right))
+            } else if (pointer.x - left).abs() <= tolerance {
+                Some((entity, FrameEdge::Left))
+            } else {
+                None
+            }
+        });
+        if drag.target.is_some() {
+            // The click belongs to the frame, not to text placement
+            mouse_button_input.clear_just_pressed(MouseButton::Left);
+        }
+    }
+
+    let Some((entity, edge)) = drag.target else {
+        return;
+    };
+    if !mouse_button_input.pressed(MouseButton::Left) {
+        drag.target = None;
+        return;
+    }
+    let Ok((_, mut buffer)) = buffer_query.get_mut(entity) else {
+        drag.target = None;
+        return;
+    };
+    let left = buffer.root_position.x;
+    let Some(frame) = buffer.frame.as_mut() else {
+        return;
+    };
+
+    let right = left + frame.width;
+    let new_left = match edge {
+        FrameEdge::Right => left,
+        FrameEdge::Left => pointer.x.min(right - TextFrame::MIN_WIDTH),
+    };
+    let new_width = match edge {
+        FrameEdge::Right => (pointer.x - left).max(TextFrame::MIN_WIDTH),
+        FrameEdge::Left => right - new_left,
+    };
+    if new_width != frame.width {
+        frame.width = new_width;
+        buffer.root_position.x = new_left;
+        text_editor_state.set_changed();
+    }
+}
+
+/// Draw each frame's outline and edge handles as meshes
+fn render_text_frames(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    buffer_query: Query<&TextBuffer>,
+    changed_buffers: Query<(), Changed<TextBuffer>>,
+    existing: Query<Entity, With<TextFrameVisual>>,
+    text_editor_state: Res<TextEditorState>,
+    camera_scale: Res<CameraResponsiveScale>,
+    theme: Res<CurrentTheme>,
+    app_state: Option<Res<AppState>>,
+) {
+    let needs_redraw = text_editor_state.is_changed()
+        || camera_scale.is_changed()
+        || theme.is_changed()
+        || !changed_buffers.is_empty();
+    if !needs_redraw {
+        return;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+    let Some(app_state) = app_state.as_ref() else {
+        return;
+    };
+
+    let metrics = FrameMetrics::from_app_state(app_state);
+    let color = theme.theme().text_frame_color();
+    let material = materials.add(ColorMaterial::from_color(color));
+    let line_width = camera_scale.adjusted_line_width();
+    let handle_size = camera_scale.adjusted_size(theme.theme().text_frame_handle_size());
+
+    for buffer in buffer_query.iter() {
+        let Some(frame) = buffer.frame.as_ref() else {
+            continue;
+        };
+        let lines = frame_line_count(&buffer_sorts(&text_editor_state, buffer), frame);
+        let (top, bottom) = metrics.vertical_extent(buffer.root_position, lines);
+        let left = buffer.root_position.x;
+        let right = left + frame.width;
+        let corners = [
+            Vec2::new(left, top),
+            Vec2::new(right, top),
+            Vec2::new(right, bottom),
+            Vec2::new(left, bottom),
+        ];
+
+        for i in 0..corners.len() {
+            let (start, end) = (corners[i], corners[(i + 1) % corners.len()]);
+            let mesh = crate::rendering::mesh_utils::create_line_mesh(start, end, line_width);
+            let midpoint = (start + end) * 0.5;
+            commands.spawn((
+                TextFrameVisual,
+                Mesh2d(meshes.add(mesh)),
+                MeshMaterial2d(material.clone()),
+                Transform::from_translation(midpoint.extend(TEXT_FRAME_Z)),
+            ));
+        }
+
+        // Edge handles at mid-height of the first line
+        let handle_y = buffer.root_position.y + metrics.upm * 0.25;
+        for x in [left, right] {
+            commands.spawn((
+                TextFrameVisual,
+                Mesh2d(meshes.add(Rectangle::new(handle_size, handle_size * 2.0))),
+                MeshMaterial2d(material.clone()),
+                Transform::from_xyz(x, handle_y, TEXT_FRAME_Z),
+            ));
+        }
+    }
+}
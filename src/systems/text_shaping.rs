@@ -1,533 +1,678 @@
 //! Unified Text Shaping System
 //!
-//! This module provides comprehensive text shaping for Arabic and other complex scripts
-//! using multiple approaches:
-//! - Simple Arabic contextual form mapping for basic support
-//! - HarfBuzz integration for advanced shaping (experimental)
-//! - Fallback character mapping for Latin and other scripts
+//! This module shapes text buffers with HarfBuzz (harfrust) against the
+//! compiled font, with OpenType features that can be switched on and off.
+//! Without a compiled font, or with shaping off, each character shows its
+//! cmap glyph.
 //!
-//! The shaping system integrates with the existing text editor to provide
-//! proper Arabic text rendering support while maintaining compatibility
-//! with Latin text editing.
-
+//! Shaping replaces the glyph of each sort in a text buffer with what the
+//! compiled font's substitutions produce, so ligatures, stylistic sets and
+//! contextual forms show up in the buffer the way they will in the font.
+//! Arabic joining comes from the font's own `init`/`medi`/`fina`/`rlig`
+//! lookups, whatever its glyphs are called.
+//! Marks are moved onto their bases by the GPOS offsets, or, for comparison,
+//! by lining up the glyphs' anchors the way the compiler is expected to.
+//! For feature authors, each cluster can also be traced back to its
+//! characters and the features that substituted it.
+
+#![allow(clippy::too_many_arguments)]
 
 use crate::core::state::text_editor::buffer::SortKind;
-use crate::core::state::{SortLayoutMode, TextEditorState};
+use crate::core::state::text_editor::text_buffer::TextBuffer;
+use crate::core::state::{AppState, FontData, SortLayoutMode, TextEditorState};
+use crate::editing::FontEditorSets;
+use crate::font_source::AnchorData;
+use crate::systems::compiled_font::{CompiledFont, CompiledFontData, CompiledFontPlugin};
+use crate::systems::sorts::input_utilities;
+use crate::systems::sorts::sort_entities::BufferSortRespawnQueue;
+use crate::systems::substitution_overlay::SubstitutionOverlayPlugin;
 use bevy::prelude::*;
-use std::collections::HashMap;
-
-// HarfBuzz imports (conditional compilation could be added later)
 use harfrust::{
-    Direction, FontRef, Language, Script, ShaperData, ShaperInstance, UnicodeBuffer,
+    BufferClusterLevel, Direction, Feature, FontRef, Shaper, ShaperData, ShaperInstance, Tag,
+    UnicodeBuffer,
 };
-use std::path::PathBuf;
-use std::str::FromStr;
-use tempfile::TempDir;
-
-/// Resource to cache text shaping information
-#[derive(Resource, Default)]
-pub struct TextShapingCache {
-    /// Cache of shaped text by input string
-    pub shaped_texts: HashMap<String, ShapedText>,
-    /// HarfBuzz-specific cache data
-    pub harfbuzz_cache: HarfBuzzShapingCache,
-    /// Arabic shaping cache
-    pub arabic_cache: HashMap<String, ShapedText>,
-}
-
-/// HarfBuzz-specific cache resource
-#[derive(Default)]
-pub struct HarfBuzzShapingCache {
-    /// Temporary directory for compiled fonts
-    _temp_dir: Option<TempDir>,
-    /// Path to last compiled font binary
-    _compiled_font_path: Option<PathBuf>,
-    /// Last compilation timestamp for cache invalidation
-    _last_compiled: Option<std::time::Instant>,
-    /// Whether HarfBuzz is available for use (future feature)
-    #[allow(dead_code)]
-    harfbuzz_available: bool,
-    /// Cache of shaped text results
-    shaped_cache: HashMap<String, ShapedText>,
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+use unicode_bidi::{bidi_class, BidiClass};
+
+// ===== HARFBUZZ SHAPING =====
+
+/// Features HarfBuzz applies to horizontal text on its own; every other
+/// feature starts switched off
+const DEFAULT_FEATURES: &[&str] = &[
+    "abvm", "blwm", "calt", "ccmp", "clig", "curs", "dist", "fina", "init", "isol", "kern",
+    "liga", "locl", "mark", "medi", "mkmk", "rclt", "rlig", "rvrn",
+];
+
+/// Whether a feature is on unless the user switches it off
+pub fn is_default_feature(tag: &str) -> bool {
+    DEFAULT_FEATURES.contains(&tag)
 }
 
-/// Component to mark text that has been shaped
-#[derive(Component, Debug, Clone)]
-pub struct ShapedText {
-    /// Original input text as Unicode codepoints
-    pub input_codepoints: Vec<char>,
-    /// Shaped glyph information from shaping engine
-    pub shaped_glyphs: Vec<ShapedGlyph>,
-    /// Layout direction used for shaping
-    pub direction: TextDirection,
-    /// Whether complex shaping was applied (vs simple character mapping)
-    pub is_complex_shaped: bool,
+/// How marks are placed on their bases in shaped text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarkPositioning {
+    /// Offsets from the compiled `mark`/`mkmk` GPOS lookups
+    #[default]
+    Gpos,
+    /// Offsets predicted by lining up the glyphs' anchors
+    Anchors,
 }
 
-/// Information about a single shaped glyph
-#[derive(Debug, Clone)]
-pub struct ShapedGlyph {
-    /// Glyph ID from the font
-    pub glyph_id: u32,
-    /// Original Unicode codepoint (for fallback)
-    pub codepoint: char,
-    /// Glyph name (derived from font)
-    pub glyph_name: String,
-    /// Horizontal advance width
-    pub advance_width: f32,
-    /// X offset for positioning
-    pub x_offset: f32,
-    /// Y offset for positioning
-    pub y_offset: f32,
-    /// Cluster index (for cursor positioning)
-    pub cluster: u32,
+/// How text buffers are shaped
+#[derive(Resource, Debug)]
+pub struct ShapingSettings {
+    /// Shape buffers with the compiled font; when off, every character
+    /// shows its cmap glyph
+    pub enabled: bool,
+    /// Features switched away from their default state
+    pub overrides: BTreeMap<String, bool>,
+    /// Where mark offsets come from
+    pub mark_positioning: MarkPositioning,
+    /// Trace clusters and substitutions for the overlay
+    pub show_substitutions: bool,
 }
 
-/// Text direction for shaping
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum TextDirection {
-    LeftToRight,
-    RightToLeft,
-    TopToBottom,
-    BottomToTop,
-}
-
-/// Position of an Arabic letter in a word (for contextual forms)
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum ArabicPosition {
-    Isolated,
-    Initial,
-    Medial,
-    Final,
-}
-
-impl From<SortLayoutMode> for TextDirection {
-    fn from(mode: SortLayoutMode) -> Self {
-        match mode {
-            SortLayoutMode::LTRText => TextDirection::LeftToRight,
-            SortLayoutMode::RTLText => TextDirection::RightToLeft,
-            SortLayoutMode::Freeform => TextDirection::LeftToRight, // Default
+impl Default for ShapingSettings {
+    fn default() -> Self {
+        Self {
+            // The web build has no compiled font to shape with
+            enabled: !cfg!(target_arch = "wasm32"),
+            overrides: BTreeMap::new(),
+            mark_positioning: MarkPositioning::default(),
+            show_substitutions: false,
         }
     }
 }
 
-impl From<TextDirection> for Direction {
-    fn from(direction: TextDirection) -> Self {
-        match direction {
-            TextDirection::LeftToRight => Direction::LeftToRight,
-            TextDirection::RightToLeft => Direction::RightToLeft,
-            TextDirection::TopToBottom => Direction::TopToBottom,
-            TextDirection::BottomToTop => Direction::BottomToTop,
-        }
+impl ShapingSettings {
+    pub fn is_feature_on(&self, tag: &str) -> bool {
+        self.overrides
+            .get(tag)
+            .copied()
+            .unwrap_or_else(|| is_default_feature(tag))
     }
-}
 
-/// Detect if text contains Arabic or other complex script characters
-pub fn needs_complex_shaping(text: &str) -> bool {
-    text.chars().any(|ch| {
-        let code = ch as u32;
-        // Arabic block: U+0600-U+06FF
-        (0x0600..=0x06FF).contains(&code) ||
-        // Arabic Supplement: U+0750-U+077F  
-        (0x0750..=0x077F).contains(&code) ||
-        // Arabic Extended-A: U+08A0-U+08FF
-        (0x08A0..=0x08FF).contains(&code) ||
-        // Arabic Presentation Forms-A: U+FB50-U+FDFF
-        (0xFB50..=0xFDFF).contains(&code) ||
-        // Arabic Presentation Forms-B: U+FE70-U+FEFF
-        (0xFE70..=0xFEFF).contains(&code)
-    })
+    /// Overrides as shaper features covering the whole run
+    fn shaper_features(&self) -> Vec<Feature> {
+        self.overrides
+            .iter()
+            .filter_map(|(tag, &on)| {
+                let tag = Tag::new_checked(tag.as_bytes()).ok()?;
+                Some(Feature::new(tag, u32::from(on), ..))
+            })
+            .collect()
+    }
 }
 
-// ===== ARABIC CONTEXTUAL SHAPING =====
-
-/// Check if a character is an Arabic letter that needs shaping
-fn is_arabic_letter(ch: char) -> bool {
-    let code = ch as u32;
-    // Basic Arabic letters that connect
-    (0x0621..=0x064A).contains(&code)
+/// Switch buffer shaping on or off
+#[derive(Event, Clone)]
+pub struct ToggleShapingEvent;
+
+/// Switch one OpenType feature on or off
+#[derive(Event, Clone)]
+pub struct ToggleFeatureEvent(pub String);
+
+/// Switch marks between GPOS and anchor-predicted placement
+#[derive(Event, Clone)]
+pub struct ToggleMarkPositioningEvent;
+
+/// Show or hide the substitution overlay
+#[derive(Event, Clone)]
+pub struct ToggleSubstitutionOverlayEvent;
+
+/// One shaped cluster, as shown by the substitution overlay
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClusterTrace {
+    /// Buffer indices of the cluster's sorts, in logical order
+    pub sorts: Vec<usize>,
+    /// Characters that went into the cluster
+    pub codepoints: Vec<char>,
+    /// Glyphs that came out of it
+    pub glyph_names: Vec<String>,
+    /// GSUB features that changed its glyphs
+    pub features: Vec<String>,
 }
 
-/// Check if an Arabic letter can connect to the next letter
-fn can_connect_to_next(ch: char) -> bool {
-    // Non-connecting Arabic letters
-    match ch {
-        '\u{0621}' | // Hamza
-        '\u{0622}' | '\u{0623}' | '\u{0624}' | '\u{0625}' | // Alef variants
-        '\u{0627}' | // Alef
-        '\u{0629}' | // Teh Marbuta
-        '\u{062F}' | // Dal
-        '\u{0630}' | // Thal
-        '\u{0631}' | // Reh
-        '\u{0632}' | // Zain
-        '\u{0648}' | // Waw
-        '\u{0649}' => false, // Alef Maksura
-        _ => is_arabic_letter(ch),
+impl ClusterTrace {
+    pub fn is_substituted(&self) -> bool {
+        !self.features.is_empty()
     }
 }
 
-/// Check if an Arabic letter can connect to the previous letter
-fn can_connect_to_prev(ch: char) -> bool {
-    is_arabic_letter(ch)
+/// Clusters of every shaped run while the overlay is on
+#[derive(Resource, Default, Debug, PartialEq)]
+pub struct SubstitutionTrace {
+    pub clusters: Vec<ClusterTrace>,
 }
 
-/// Determine the position of an Arabic letter in a word
-pub fn get_arabic_position(text: &[char], index: usize) -> ArabicPosition {
-    let ch = text[index];
-
-    // Check previous character
-    let has_prev = index > 0 && {
-        let prev = text[index - 1];
-        is_arabic_letter(prev) && can_connect_to_next(prev)
-    };
-
-    // Check next character
-    let has_next = index + 1 < text.len() && {
-        let next = text[index + 1];
-        is_arabic_letter(next) && can_connect_to_prev(next)
-    };
+/// What shaping puts on one sort
+#[derive(Debug, Clone, Default, PartialEq)]
+struct SortGlyph {
+    glyph_name: String,
+    advance: f32,
+    /// Shift from the sort's place in the text flow
+    offset: Vec2,
+}
 
-    // Determine position based on connections
-    match (has_prev, can_connect_to_next(ch) && has_next) {
-        (false, false) => ArabicPosition::Isolated,
-        (false, true) => ArabicPosition::Initial,
-        (true, true) => ArabicPosition::Medial,
-        (true, false) => ArabicPosition::Final,
+/// A shaped glyph with its cluster as a character index within the run
+type ClusterGlyph = (usize, SortGlyph);
+
+/// Spread shaped glyphs back over the characters they came from
+///
+/// `glyphs` must be in logical order. Within a cluster, glyphs and
+/// characters pair up in order: characters left without a glyph (the tail
+/// of a ligature) get an empty name and no advance, and glyphs left without
+/// a character add their advance to the cluster's last character.
+fn distribute_clusters(char_count: usize, glyphs: &[ClusterGlyph]) -> Vec<SortGlyph> {
+    let mut result = vec![SortGlyph::default(); char_count];
+    let mut start = 0;
+    while start < glyphs.len() {
+        let cluster = glyphs[start].0;
+        let end = glyphs[start..]
+            .iter()
+            .position(|glyph| glyph.0 != cluster)
+            .map_or(glyphs.len(), |len| start + len);
+        let next_cluster = glyphs.get(end).map_or(char_count, |glyph| glyph.0);
+        let chars = next_cluster.min(char_count).saturating_sub(cluster);
+
+        for (k, (_, glyph)) in glyphs[start..end].iter().enumerate() {
+            if k < chars {
+                result[cluster + k] = glyph.clone();
+            } else if chars > 0 {
+                result[cluster + chars - 1].advance += glyph.advance;
+            }
+        }
+        start = end;
     }
+    result
 }
 
-/// Get the contextual glyph name for an Arabic letter
-fn get_contextual_glyph_name(
-    ch: char,
-    position: ArabicPosition,
-) -> Result<String, String> {
-    // TEMPORARILY DISABLED: FontIR removed
-    // Use Unicode naming as fallback
-    let _ = position;
-    Ok(format!("uni{:04X}", ch as u32))
+/// Mark offsets predicted from anchors, for one run in logical order
+///
+/// A glyph with a `_name` anchor matching an anchor of the glyph before it,
+/// or of a mark already attached there, is a mark: it moves so the two
+/// anchors meet, the way mark-to-base and mark-to-mark lookups built from
+/// those anchors would place it. Offsets are relative to where the text
+/// flow puts each mark.
+fn anchor_mark_offsets(font: &FontData, glyphs: &[SortGlyph], rtl: bool) -> Vec<Vec2> {
+    let mut offsets = vec![Vec2::ZERO; glyphs.len()];
+    // Attachment points, relative to the current base's origin
+    let mut anchors: HashMap<&str, Vec2> = HashMap::new();
+    // Flow origin of the next mark, relative to the base's origin
+    let mut pen = 0.0;
+
+    for (index, sort_glyph) in glyphs.iter().enumerate() {
+        let Some(glyph) = font.editing().get_glyph(&sort_glyph.glyph_name) else {
+            anchors.clear();
+            continue;
+        };
+        let point = |anchor: &AnchorData| Vec2::new(anchor.x as f32, anchor.y as f32);
+        let attachment = glyph.anchors.iter().find_map(|anchor| {
+            let base = anchors.get(anchor.name.strip_prefix('_')?)?;
+            Some(*base - point(anchor))
+        });
+        let own_anchors = glyph.anchors.iter().filter(|anchor| !anchor.name.starts_with('_'));
+
+        match attachment {
+            Some(position) => {
+                // Right-to-left marks step leftwards from the base
+                let origin = if rtl { pen - sort_glyph.advance } else { pen };
+                offsets[index] = position - Vec2::new(origin, 0.0);
+                pen = if rtl { origin } else { pen + sort_glyph.advance };
+                for anchor in own_anchors {
+                    anchors.insert(anchor.name.as_str(), position + point(anchor));
+                }
+            }
+            None => {
+                anchors = own_anchors
+                    .map(|anchor| (anchor.name.as_str(), point(anchor)))
+                    .collect();
+                pen = if rtl { 0.0 } else { sort_glyph.advance };
+            }
+        }
+    }
+    offsets
 }
 
-/// Get the base glyph name for an Arabic character
-fn get_arabic_base_name(ch: char) -> String {
-    // Using the naming convention from bezy-grotesk font: {letter}-ar
-    match ch as u32 {
-        0x0621 => "hamza-ar".to_string(),
-        0x0622 => "alefMadda-ar".to_string(),
-        0x0623 => "alefHamzaabove-ar".to_string(),
-        0x0624 => "wawHamza-ar".to_string(),
-        0x0625 => "alefHamzabelow-ar".to_string(),
-        0x0626 => "yehHamza-ar".to_string(),
-        0x0627 => "alef-ar".to_string(),
-        0x0628 => "beh-ar".to_string(),
-        0x0629 => "tehMarbuta-ar".to_string(),
-        0x062A => "teh-ar".to_string(),
-        0x062B => "theh-ar".to_string(),
-        0x062C => "jeem-ar".to_string(),
-        0x062D => "hah-ar".to_string(),
-        0x062E => "khah-ar".to_string(),
-        0x062F => "dal-ar".to_string(),
-        0x0630 => "thal-ar".to_string(),
-        0x0631 => "reh-ar".to_string(),
-        0x0632 => "zain-ar".to_string(),
-        0x0633 => "seen-ar".to_string(),
-        0x0634 => "sheen-ar".to_string(),
-        0x0635 => "sad-ar".to_string(),
-        0x0636 => "dad-ar".to_string(),
-        0x0637 => "tah-ar".to_string(),
-        0x0638 => "zah-ar".to_string(),
-        0x0639 => "ain-ar".to_string(),
-        0x063A => "ghain-ar".to_string(),
-        0x0641 => "feh-ar".to_string(),
-        0x0642 => "qaf-ar".to_string(),
-        0x0643 => "kaf-ar".to_string(),
-        0x0644 => "lam-ar".to_string(),
-        0x0645 => "meem-ar".to_string(),
-        0x0646 => "noon-ar".to_string(),
-        0x0647 => "heh-ar".to_string(),
-        0x0648 => "waw-ar".to_string(),
-        0x0649 => "alefMaksura-ar".to_string(),
-        0x064A => "yeh-ar".to_string(),
-        _ => format!("uni{:04X}", ch as u32),
+/// Strong direction of a character (`true` for right-to-left), if any
+fn strong_direction(c: char) -> Option<bool> {
+    match bidi_class(c) {
+        BidiClass::L => Some(false),
+        BidiClass::R | BidiClass::AL => Some(true),
+        _ => None,
     }
 }
 
-/// Shape Arabic text using contextual form mapping
-/// TEMPORARILY DISABLED: FontIR removed
-pub fn shape_arabic_text(
-    text: &str,
-    direction: TextDirection,
-) -> Result<ShapedText, String> {
-    let input_codepoints: Vec<char> = text.chars().collect();
-    let shaped_glyphs: Vec<ShapedGlyph> = input_codepoints.iter().enumerate().map(|(i, &ch)| {
-        let glyph_name = format!("uni{:04X}", ch as u32);
-        ShapedGlyph {
-            glyph_id: 0,
-            codepoint: ch,
-            glyph_name,
-            advance_width: 500.0, // Default width
-            x_offset: 0.0,
-            y_offset: 0.0,
-            cluster: i as u32,
+/// Right-to-left flag and the (buffer index, character) pairs of a run
+type ShapingRun = (bool, Vec<(usize, char)>);
+
+/// Split a buffer's sorts into single-direction runs for shaping
+///
+/// `sorts` pairs buffer indices with codepoints; sorts without one (line
+/// breaks, glyphs placed by name) end the current run. Neutral characters
+/// join the run they follow, or start in the buffer's base direction.
+fn shaping_runs(sorts: &[(usize, Option<char>)], base_rtl: bool) -> Vec<ShapingRun> {
+    let mut runs: Vec<ShapingRun> = Vec::new();
+    let mut current: Option<ShapingRun> = None;
+
+    for &(index, codepoint) in sorts {
+        let Some(c) = codepoint else {
+            runs.extend(current.take());
+            continue;
+        };
+        let direction = strong_direction(c);
+        match current.as_mut() {
+            Some((rtl, run)) if direction.is_none_or(|d| d == *rtl) => run.push((index, c)),
+            _ => {
+                runs.extend(current.take());
+                current = Some((direction.unwrap_or(base_rtl), vec![(index, c)]));
+            }
         }
-    }).collect();
-
-    Ok(ShapedText {
-        input_codepoints,
-        shaped_glyphs,
-        direction,
-        is_complex_shaped: false,
-    })
-}
-
-/// Helper function to map Unicode to glyph name
-fn unicode_to_glyph_name(ch: char) -> Option<String> {
-    use crate::systems::sorts::input_utilities::unicode_to_glyph_name_fontir;
-    unicode_to_glyph_name_fontir(ch)
+    }
+    runs.extend(current);
+    runs
 }
 
-// ===== HARFBUZZ INTEGRATION =====
-
-/// Get font bytes for HarfBuzz shaping (using existing TTF file for now)
-pub fn compile_font_for_shaping(
-    _cache: &mut HarfBuzzShapingCache,
-) -> Result<Vec<u8>, String> {
-    // HACK: For proof of concept, use the existing TTF file directly
-    // TODO: This should compile from FontIR using fontc, but fontc has issues with Arabic composite glyphs
-
-    debug!("🔤 HarfBuzz: Loading existing BezyGrotesk-Regular.ttf for shaping");
+/// Shape one single-direction run into glyphs tagged with the index of
+/// their cluster's first character, in logical order
+fn shape_clusters(
+    shaper: &Shaper,
+    data: &CompiledFontData,
+    font: &FontData,
+    chars: &[char],
+    rtl: bool,
+    features: &[Feature],
+) -> Vec<ClusterGlyph> {
+    let text: String = chars.iter().collect();
+    let mut char_at_byte = vec![0; text.len()];
+    for (index, (byte, _)) in text.char_indices().enumerate() {
+        char_at_byte[byte] = index;
+    }
 
-    let font_bytes = if std::path::Path::new("assets").exists() {
-        // Use file system if assets exist
-        std::fs::read("assets/fonts/BezyGrotesk-Regular.ttf")
-            .map_err(|e| format!("Failed to load BezyGrotesk-Regular.ttf: {e}"))?
+    let mut buffer = UnicodeBuffer::new();
+    buffer.push_str(&text);
+    buffer.set_direction(if rtl {
+        Direction::RightToLeft
     } else {
-        // Use embedded font data
-        debug!("🔤 Using embedded font for text shaping");
-        crate::utils::embedded_assets::BEZY_GROTESK_BYTES.to_vec()
-    };
+        Direction::LeftToRight
+    });
+    // Give marks clusters of their own so each stays on its sort
+    buffer.set_cluster_level(BufferClusterLevel::MonotoneCharacters);
+    buffer.guess_segment_properties();
+    let output = shaper.shape(buffer, features);
+
+    let mut glyphs: Vec<ClusterGlyph> = output
+        .glyph_infos()
+        .iter()
+        .zip(output.glyph_positions())
+        .map(|(info, position)| {
+            let cluster = char_at_byte.get(info.cluster as usize).copied().unwrap_or(0);
+            let gid = info.glyph_id as usize;
+            let glyph_name = data.glyph_names.get(gid).cloned().unwrap_or_default();
+            // Spacing edited since the last compile shows up straight away
+            let edited = font
+                .editing()
+                .get_glyph(&glyph_name)
+                .zip(data.advances.get(gid))
+                .map_or(0.0, |(glyph, &compiled)| glyph.advance_width as f32 - compiled as f32);
+            let glyph = SortGlyph {
+                glyph_name,
+                advance: position.x_advance as f32 + edited,
+                offset: Vec2::new(position.x_offset as f32, position.y_offset as f32),
+            };
+            (cluster, glyph)
+        })
+        .collect();
+    // Right-to-left output comes in visual order
+    if rtl {
+        glyphs.reverse();
+    }
+    glyphs.sort_by_key(|glyph| glyph.0);
+    glyphs
+}
 
-    debug!(
-        "🔤 HarfBuzz: Loaded {} bytes from TTF file",
-        font_bytes.len()
-    );
-    Ok(font_bytes)
+/// Character ranges of the clusters in `glyphs` (in logical order)
+fn cluster_spans(char_count: usize, glyphs: &[ClusterGlyph]) -> Vec<Range<usize>> {
+    let mut starts: Vec<usize> = glyphs.iter().map(|glyph| glyph.0).collect();
+    starts.dedup();
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| start..starts.get(i + 1).copied().unwrap_or(char_count))
+        .collect()
 }
 
-/// Shape text using HarfBuzz with compiled font
-/// TEMPORARILY DISABLED: FontIR removed
-#[allow(dead_code)]
-pub fn shape_text_with_harfbuzz(
-    text: &str,
-    direction: TextDirection,
-    _cache: &mut HarfBuzzShapingCache,
-) -> Result<ShapedText, String> {
-    // Fallback to simple mapping
-    let input_codepoints: Vec<char> = text.chars().collect();
-    let shaped_glyphs: Vec<ShapedGlyph> = input_codepoints.iter().enumerate().map(|(i, &ch)| {
-        ShapedGlyph {
-            glyph_id: 0,
-            codepoint: ch,
-            glyph_name: format!("uni{:04X}", ch as u32),
-            advance_width: 500.0,
-            x_offset: 0.0,
-            y_offset: 0.0,
-            cluster: i as u32,
-        }
-    }).collect();
+/// Describe each cluster of a shaped run for the substitution overlay
+///
+/// A GSUB feature is credited with a cluster when shaping the run again
+/// without it gives the cluster's characters other glyphs.
+fn trace_run(
+    shaper: &Shaper,
+    data: &CompiledFontData,
+    font: &FontData,
+    settings: &ShapingSettings,
+    run: &[(usize, char)],
+    rtl: bool,
+    glyphs: &[ClusterGlyph],
+) -> Vec<ClusterTrace> {
+    let chars: Vec<char> = run.iter().map(|(_, c)| *c).collect();
+    let shaped = distribute_clusters(chars.len(), glyphs);
+    let without_feature: Vec<(&String, Vec<SortGlyph>)> = data
+        .gsub_features
+        .iter()
+        .filter(|tag| settings.is_feature_on(tag))
+        .filter_map(|tag| {
+            let mut features = settings.shaper_features();
+            // Later features override earlier ones for the same tag
+            features.push(Feature::new(Tag::new_checked(tag.as_bytes()).ok()?, 0, ..));
+            let glyphs = shape_clusters(shaper, data, font, &chars, rtl, &features);
+            Some((tag, distribute_clusters(chars.len(), &glyphs)))
+        })
+        .collect();
+
+    cluster_spans(chars.len(), glyphs)
+        .into_iter()
+        .map(|span| {
+            let features = without_feature
+                .iter()
+                .filter(|(_, other)| {
+                    other[span.clone()]
+                        .iter()
+                        .zip(&shaped[span.clone()])
+                        .any(|(a, b)| a.glyph_name != b.glyph_name)
+                })
+                .map(|(tag, _)| (*tag).clone())
+                .collect();
+            ClusterTrace {
+                sorts: run[span.clone()].iter().map(|(index, _)| *index).collect(),
+                codepoints: chars[span.clone()].to_vec(),
+                glyph_names: glyphs
+                    .iter()
+                    .filter(|glyph| glyph.0 == span.start)
+                    .map(|(_, glyph)| glyph.glyph_name.clone())
+                    .collect(),
+                features,
+            }
+        })
+        .collect()
+}
 
-    Ok(ShapedText {
-        input_codepoints,
-        shaped_glyphs,
-        direction,
-        is_complex_shaped: false,
+/// The glyph a character maps to without shaping
+fn unshaped_glyph(c: char, app_state: &AppState) -> Option<SortGlyph> {
+    let glyph_name = input_utilities::unicode_to_glyph_name(c, app_state)?;
+    let advance = app_state.workspace.font.editing().get_glyph(&glyph_name)?.advance_width as f32;
+    Some(SortGlyph {
+        glyph_name,
+        advance,
+        offset: Vec2::ZERO,
     })
 }
 
-/// Perform actual HarfBuzz text shaping using harfrust
-fn perform_harfbuzz_shaping(
-    text: &str,
-    direction: TextDirection,
-    font_bytes: &[u8],
-) -> Result<ShapedText, String> {
-    // Create harfrust font from compiled font bytes
-    let font_ref = FontRef::from_index(font_bytes, 0)
-        .map_err(|e| format!("Failed to create harfrust FontRef: {e:?}"))?;
-
-    // Create shaper data and instance
-    let shaper_data = ShaperData::new(&font_ref);
-    let shaper_instance = ShaperInstance::from_variations(&font_ref, &[] as &[harfrust::Variation]);
-    let shaper = shaper_data
-        .shaper(&font_ref)
-        .instance(Some(&shaper_instance))
-        .build();
-
-    // Create buffer and add text
-    let mut buffer = UnicodeBuffer::new();
-    buffer.push_str(text);
-
-    // Set buffer properties
-    buffer.set_direction(direction.into());
-    buffer.set_script(get_script_for_text(text));
-    buffer.set_language(Language::from_str("ar").unwrap_or(Language::from_str("en").unwrap()));
-
-    // Guess remaining properties automatically
-    buffer.guess_segment_properties();
-
-    // Perform HarfBuzz shaping
-    let glyph_buffer = shaper.shape(buffer, &[]);
-
-    // Extract shaped glyph information
-    let input_codepoints: Vec<char> = text.chars().collect();
-    let mut shaped_glyphs = Vec::new();
-
-    let glyph_infos = glyph_buffer.glyph_infos();
-    let glyph_positions = glyph_buffer.glyph_positions();
-
-    // Debug output to understand what HarfBuzz returns
-    debug!(
-        "🔤 HarfBuzz: Shaped {} characters into {} glyphs",
-        input_codepoints.len(),
-        glyph_infos.len()
-    );
-
-    for (i, glyph_info) in glyph_infos.iter().enumerate() {
-        debug!(
-            "🔤 HarfBuzz: Glyph[{}] - ID: {}, cluster: {}",
-            i, glyph_info.glyph_id, glyph_info.cluster
-        );
-        // Get glyph name from glyph ID
-        let glyph_name = get_glyph_name_from_id(glyph_info.glyph_id);
+// ===== SYSTEM IMPLEMENTATIONS =====
 
-        // Get original codepoint from cluster index
-        let codepoint = if (glyph_info.cluster as usize) < input_codepoints.len() {
-            input_codepoints[glyph_info.cluster as usize]
+fn handle_shaping_toggles(
+    mut shaping_events: EventReader<ToggleShapingEvent>,
+    mut feature_events: EventReader<ToggleFeatureEvent>,
+    mut mark_events: EventReader<ToggleMarkPositioningEvent>,
+    mut overlay_events: EventReader<ToggleSubstitutionOverlayEvent>,
+    mut settings: ResMut<ShapingSettings>,
+) {
+    for _ in shaping_events.read() {
+        settings.enabled = !settings.enabled;
+        info!("Text shaping {}", if settings.enabled { "on" } else { "off" });
+    }
+    for ToggleFeatureEvent(tag) in feature_events.read() {
+        let on = !settings.is_feature_on(tag);
+        if on == is_default_feature(tag) {
+            settings.overrides.remove(tag);
         } else {
-            '\u{FFFD}' // Replacement character
+            settings.overrides.insert(tag.clone(), on);
+        }
+        info!("Feature '{}' {}", tag, if on { "on" } else { "off" });
+    }
+    for _ in mark_events.read() {
+        settings.mark_positioning = match settings.mark_positioning {
+            MarkPositioning::Gpos => MarkPositioning::Anchors,
+            MarkPositioning::Anchors => MarkPositioning::Gpos,
         };
+        info!("Mark positioning: {:?}", settings.mark_positioning);
+    }
+    for _ in overlay_events.read() {
+        settings.show_substitutions = !settings.show_substitutions;
+        info!(
+            "Substitution overlay {}",
+            if settings.show_substitutions { "on" } else { "off" }
+        );
+    }
+}
 
-        // Get glyph position info
-        let pos = glyph_positions.get(i).cloned().unwrap_or_default();
-
-        // harfrust uses units per em directly (no scaling needed)
-        let advance_width = pos.x_advance as f32;
+/// Reshape text buffers when their text, the compiled font, or the shaping
+/// settings change
+fn shape_text_buffers(
+    mut text_editor_state: ResMut<TextEditorState>,
+    settings: Res<ShapingSettings>,
+    compiled: Res<CompiledFont>,
+    app_state: Option<Res<AppState>>,
+    buffer_query: Query<&TextBuffer>,
+    mut respawn_queue: ResMut<BufferSortRespawnQueue>,
+    mut trace: ResMut<SubstitutionTrace>,
+) {
+    if !text_editor_state.is_changed() && !settings.is_changed() && !compiled.is_changed() {
+        return;
+    }
+    let Some(app_state) = app_state else {
+        return;
+    };
 
-        shaped_glyphs.push(ShapedGlyph {
-            glyph_id: glyph_info.glyph_id,
-            codepoint,
+    let mut glyphs: HashMap<usize, SortGlyph> = HashMap::new();
+    let mut clusters = Vec::new();
+    let buffer = &text_editor_state.buffer;
+    if settings.enabled {
+        let Some(data) = compiled.data.as_ref() else {
+            return;
+        };
+        let Ok(font_ref) = FontRef::from_index(&data.bytes, 0) else {
+            return;
+        };
+        let shaper_data = ShaperData::new(&font_ref);
+        let instance = ShaperInstance::from_variations(&font_ref, &[] as &[harfrust::Variation]);
+        let shaper = shaper_data
+            .shaper(&font_ref)
+            .instance(Some(&instance))
+            .build();
+        let features = settings.shaper_features();
+
+        for text_buffer in buffer_query.iter() {
+            if text_buffer.layout_mode == SortLayoutMode::Freeform {
+                continue;
+            }
+            let sorts: Vec<(usize, Option<char>)> = (0..buffer.len())
+                .filter_map(|index| {
+                    let sort = buffer.get(index)?;
+                    (sort.buffer_id == Some(text_buffer.id)).then(|| (index, sort.kind.codepoint()))
+                })
+                .collect();
+            let base_rtl = text_buffer.layout_mode == SortLayoutMode::RTLText;
+            for (rtl, run) in shaping_runs(&sorts, base_rtl) {
+                let chars: Vec<char> = run.iter().map(|(_, c)| *c).collect();
+                let font = &app_state.workspace.font;
+                let shaped_clusters = shape_clusters(&shaper, data, font, &chars, rtl, &features);
+                if settings.show_substitutions {
+                    clusters.extend(trace_run(
+                        &shaper,
+                        data,
+                        font,
+                        &settings,
+                        &run,
+                        rtl,
+                        &shaped_clusters,
+                    ));
+                }
+                let mut shaped = distribute_clusters(chars.len(), &shaped_clusters);
+                if settings.mark_positioning == MarkPositioning::Anchors {
+                    let offsets = anchor_mark_offsets(font, &shaped, rtl);
+                    for (glyph, offset) in shaped.iter_mut().zip(offsets) {
+                        glyph.offset = offset;
+                    }
+                }
+                glyphs.extend(run.iter().map(|(index, _)| *index).zip(shaped));
+            }
+        }
+    } else if settings.is_changed() {
+        // Shaping was just switched off: back to one glyph per character
+        for index in 0..buffer.len() {
+            let Some(sort) = buffer.get(index) else {
+                continue;
+            };
+            if sort.buffer_id.is_none() || sort.layout_mode == SortLayoutMode::Freeform {
+                continue;
+            }
+            if let Some(glyph) = sort.kind.codepoint().and_then(|c| unshaped_glyph(c, &app_state)) {
+                glyphs.insert(index, glyph);
+            }
+        }
+    } else {
+        return;
+    }
+    trace.set_if_neq(SubstitutionTrace { clusters });
+
+    // Only touch sorts whose glyph actually changes, so reshaping settles
+    let editor = text_editor_state.bypass_change_detection();
+    let mut changed = false;
+    for (index, glyph) in glyphs {
+        let Some(sort) = editor.buffer.get_mut(index) else {
+            continue;
+        };
+        if sort.placement_offset != glyph.offset {
+            sort.placement_offset = glyph.offset;
+            changed = true;
+        }
+        let SortKind::Glyph {
             glyph_name,
             advance_width,
-            x_offset: pos.x_offset as f32,
-            y_offset: pos.y_offset as f32,
-            cluster: glyph_info.cluster,
-        });
-    }
-
-    debug!(
-        "HarfBuzz shaped '{}' into {} glyphs",
-        text,
-        shaped_glyphs.len()
-    );
-
-    Ok(ShapedText {
-        input_codepoints,
-        shaped_glyphs,
-        direction,
-        is_complex_shaped: true,
-    })
-}
-
-/// Get glyph name from glyph ID using FontIR
-fn get_glyph_name_from_id(glyph_id: u32) -> String {
-    // HACK: For proof of concept with "اشهد", let's create a manual mapping
-    // based on what we see in the debug output
-    // TODO: This needs proper font table parsing to get actual glyph names
-
-    debug!("🔤 HarfBuzz: Mapping glyph ID {} to name", glyph_id);
-
-    // HACK: Manual mapping based on actual HarfBuzz test output for "اشهد"
-    // From test_harfbuzz_arabic.rs output:
-    // Glyph[0]: ID 93 = dal-ar.fina (rightmost in RTL)
-    // Glyph[1]: ID 170 = heh-ar.medi
-    // Glyph[2]: ID 107 = sheen-ar.init
-    // Glyph[3]: ID 54 = alef-ar (leftmost in RTL)
-
-    match glyph_id {
-        // Confirmed glyph IDs from HarfBuzz shaping test for "اشهد"
-        54 => "alef-ar".to_string(),        // Alef isolated
-        93 => "dal-ar.fina".to_string(),    // Dal final form
-        107 => "sheen-ar.init".to_string(), // Sheen initial form
-        170 => "heh-ar.medi".to_string(),   // Heh medial form
-
-        _ => {
-            warn!(
-                "🔤 HarfBuzz: Unknown glyph ID {}, returning gid{}",
-                glyph_id, glyph_id
-            );
-            format!("gid{glyph_id}")
+            ..
+        } = &mut sort.kind
+        else {
+            continue;
+        };
+        if *glyph_name != glyph.glyph_name || *advance_width != glyph.advance {
+            *glyph_name = glyph.glyph_name;
+            *advance_width = glyph.advance;
+            respawn_queue.indices.push(index);
+            changed = true;
         }
     }
+    if changed {
+        text_editor_state.set_changed();
+    }
 }
 
-/// Get appropriate script for HarfBuzz based on text content
-fn get_script_for_text(text: &str) -> Script {
-    use harfrust::script;
+/// Unified plugin to register all text shaping systems
+pub struct TextShapingPlugin;
 
-    if text.chars().any(|ch| {
-        let code = ch as u32;
-        (0x0600..=0x06FF).contains(&code) // Arabic block
-    }) {
-        script::ARABIC
-    } else {
-        script::LATIN
+impl Plugin for TextShapingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShapingSettings>()
+            .init_resource::<SubstitutionTrace>()
+            .add_event::<ToggleShapingEvent>()
+            .add_event::<ToggleFeatureEvent>()
+            .add_event::<ToggleMarkPositioningEvent>()
+            .add_event::<ToggleSubstitutionOverlayEvent>()
+            .add_plugins((CompiledFontPlugin, SubstitutionOverlayPlugin))
+            .add_systems(
+                Update,
+                (handle_shaping_toggles, shape_text_buffers)
+                    .chain()
+                    .in_set(FontEditorSets::TextBuffer),
+            );
     }
 }
 
-// ===== SYSTEM IMPLEMENTATIONS =====
-
-/// System to perform basic text shaping for Arabic and complex scripts
-/// TEMPORARILY DISABLED: FontIR removed
-#[allow(dead_code)]
-pub fn shape_arabic_text_system(
-    _shaping_cache: ResMut<TextShapingCache>,
-    _text_editor_state: Res<TextEditorState>,
-) {
-    // FontIR removal: Shaping system temporarily disabled
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::state::GlyphData;
 
-/// System to shape Arabic text in the text buffer using contextual forms
-/// Only processes Arabic text - exits early for non-Arabic text
-/// TEMPORARILY DISABLED: FontIR removed
-#[allow(dead_code)]
-pub fn shape_arabic_buffer_system(
-    mut _text_editor_state: ResMut<TextEditorState>,
-) {
-    // FontIR removal: Shaping system temporarily disabled
-}
+    fn glyph(cluster: usize, name: &str, advance: f32) -> ClusterGlyph {
+        let glyph = SortGlyph {
+            glyph_name: name.to_string(),
+            advance,
+            offset: Vec2::ZERO,
+        };
+        (cluster, glyph)
+    }
 
+    #[test]
+    fn test_ligature_and_decomposition_clusters() {
+        // "ffi" ligated into one glyph, then "é" decomposed into two
+        let glyphs = [
+            glyph(0, "f_f_i", 900.0),
+            glyph(3, "e", 500.0),
+            glyph(3, "acutecomb", 0.0),
+        ];
+        let result = distribute_clusters(4, &glyphs);
+        assert_eq!(result[0], glyphs[0].1);
+        assert_eq!(result[1], SortGlyph::default());
+        assert_eq!(result[2], SortGlyph::default());
+        assert_eq!(result[3], glyphs[1].1);
+        assert_eq!(cluster_spans(4, &glyphs), vec![0..3, 3..4]);
+    }
 
-/// System for HarfBuzz text shaping with font compilation
-/// System for HarfBuzz text shaping with font compilation
-/// TEMPORARILY DISABLED: FontIR removed
-#[allow(dead_code)]
-pub fn harfbuzz_shaping_system(
-    mut _text_editor_state: ResMut<TextEditorState>,
-    mut _shaping_cache: ResMut<TextShapingCache>,
-) {
-    // FontIR removal: Shaping system temporarily disabled
-}
-/// Unified plugin to register all text shaping systems
-pub struct TextShapingPlugin;
+    #[test]
+    fn test_runs_split_on_direction_and_breaks() {
+        // "a ب " then a line break, then "c"
+        let sorts = [
+            (0, Some('a')),
+            (1, Some(' ')),
+            (2, Some('\u{0628}')),
+            (3, Some(' ')),
+            (4, None),
+            (5, Some('c')),
+        ];
+        let runs = shaping_runs(&sorts, false);
+        assert_eq!(runs.len(), 3);
+        assert_eq!(runs[0], (false, vec![(0, 'a'), (1, ' ')]));
+        assert_eq!(runs[1], (true, vec![(2, '\u{0628}'), (3, ' ')]));
+        assert_eq!(runs[2], (false, vec![(5, 'c')]));
+    }
 
-impl Plugin for TextShapingPlugin {
-    fn build(&self, app: &mut App) {
-        app.init_resource::<TextShapingCache>().add_systems(
-            Update,
-            (
-                shape_arabic_text_system,
-                shape_arabic_buffer_system,
-                harfbuzz_shaping_system,
-            )
-                .in_set(crate::editing::FontEditorSets::TextBuffer),
-        );
+    #[test]
+    fn test_anchor_offsets_stack_marks() {
+        let mut font = FontData::default();
+        for (name, anchors) in [
+            ("o", vec![("top", 250.0, 500.0)]),
+            ("acutecomb", vec![("_top", 100.0, 450.0), ("top", 100.0, 650.0)]),
+            ("gravecomb", vec![("_top", 50.0, 450.0)]),
+        ] {
+            let glyph = GlyphData {
+                anchors: anchors
+                    .into_iter()
+                    .map(|(name, x, y)| AnchorData {
+                        name: name.to_string(),
+                        x,
+                        y,
+                    })
+                    .collect(),
+                ..GlyphData::test(name, 0.0)
+            };
+            font.glyphs.insert(name.to_string(), glyph);
+        }
+        let run = [
+            glyph(0, "o", 500.0).1,
+            glyph(1, "acutecomb", 0.0).1,
+            glyph(2, "gravecomb", 0.0).1,
+        ];
+
+        // Marks flow in after the base's advance, so the offsets pull them
+        // back over it
+        let offsets = anchor_mark_offsets(&font, &run, false);
+        assert_eq!(offsets[0], Vec2::ZERO);
+        assert_eq!(offsets[1], Vec2::new(150.0 - 500.0, 50.0));
+        assert_eq!(offsets[2], Vec2::new(200.0 - 500.0, 250.0));
+
+        // Right to left, zero-width marks flow in at the base's origin
+        let offsets = anchor_mark_offsets(&font, &run, true);
+        assert_eq!(offsets[1], Vec2::new(150.0, 50.0));
     }
 }
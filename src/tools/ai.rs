@@ -140,10 +140,11 @@ fn spawn_ai_operation_button(
     embedded_fonts: &Res<EmbeddedFonts>,
     theme: &Res<crate::ui::themes::CurrentTheme>,
 ) {
-    // Use the unified toolbar button creation system for consistent styling
-    crate::ui::edit_mode_toolbar::create_toolbar_button(
+    // Use the unified toolbar button creation system for consistent styling with hover text
+    crate::ui::edit_mode_toolbar::create_toolbar_button_with_hover_text(
         parent,
         operation.get_icon(),
+        Some(operation.display_name()),
         (AiSubMenuButton, AiOperationButton { operation }),
         asset_server,
         embedded_fonts,
@@ -58,8 +58,6 @@ impl EditTool for PenTool {
 // CONSTANTS
 // ================================================================
 
-/// Distance threshold for closing a path by clicking near the start point
-const CLOSE_PATH_THRESHOLD: f32 = 16.0;
 /// Size of drawn points in the preview
 const POINT_PREVIEW_SIZE: f32 = 4.0;
 
@@ -213,6 +211,7 @@ pub fn handle_pen_mouse_events(
     pointer_info: Res<PointerInfo>,
     ui_hover_state: Res<UiHoverState>,
     settings: Res<crate::core::config::BezySettings>,
+    camera_scale: Res<CameraResponsiveScale>,
     // Query for active sort to get its position
     active_sort_query: Query<
         (Entity, &crate::editing::sort::Sort, &Transform),
@@ -282,7 +281,7 @@ pub fn handle_pen_mouse_events(
         if pen_state.current_path.len() > 2 {
             if let Some(first_point) = pen_state.current_path.first() {
                 let distance = final_dpoint.to_raw().distance(first_point.to_raw());
-                if distance < CLOSE_PATH_THRESHOLD {
+                if distance < camera_scale.hit_tolerance(&settings) {
                     pen_state.should_close_path = true;
                     debug!("Pen tool: Closing path - clicked near start point");
                     finalize_pen_path(
@@ -417,7 +416,7 @@ pub fn render_pen_preview(
             let distance = final_position_for_closure
                 .to_raw()
                 .distance(first_point.to_raw());
-            distance < CLOSE_PATH_THRESHOLD
+            distance < camera_scale.hit_tolerance(&settings)
         } else {
             false
         }
@@ -493,7 +492,7 @@ pub fn render_pen_preview(
                 &mut materials,
                 first_pos,
                 active_color,
-                CLOSE_PATH_THRESHOLD, // Use the threshold as radius
+                camera_scale.hit_tolerance(&settings), // Use the threshold as radius
                 camera_scale.adjusted_line_width(), // Use standard zoom-aware line thickness
             );
         }
@@ -828,7 +827,7 @@ fn calculate_final_position_dpoint(
     let final_pos = crate::geometry::utilities::calculate_final_position_with_constraints(
         cursor_pos.to_raw(),
         settings.grid.enabled,
-        settings.grid.unit_size,
+        settings.grid.snap_size(),
         axis_lock,
     );
 
@@ -80,6 +80,9 @@ pub enum ToolId {
     Select,
     Pen,
     Knife,
+    Eraser,
+    Corners,
+    Nib,
     Pan,
     Text,
     Shapes,
@@ -96,6 +99,9 @@ impl ToolId {
             ToolId::Select => "Select",
             ToolId::Pen => "Pen",
             ToolId::Knife => "Knife",
+            ToolId::Eraser => "Eraser",
+            ToolId::Corners => "Corners",
+            ToolId::Nib => "Nib",
             ToolId::Pan => "Pan",
             ToolId::Text => "Text",
             ToolId::Shapes => "Shapes",
@@ -112,6 +118,9 @@ impl ToolId {
             "select" => Some(ToolId::Select),
             "pen" => Some(ToolId::Pen),
             "knife" => Some(ToolId::Knife),
+            "eraser" => Some(ToolId::Eraser),
+            "corners" => Some(ToolId::Corners),
+            "nib" => Some(ToolId::Nib),
             "pan" => Some(ToolId::Pan),
             "text" => Some(ToolId::Text),
             "shapes" => Some(ToolId::Shapes),
@@ -129,6 +138,9 @@ impl ToolId {
             ToolId::Select => "select",
             ToolId::Pen => "pen",
             ToolId::Knife => "knife",
+            ToolId::Eraser => "eraser",
+            ToolId::Corners => "corners",
+            ToolId::Nib => "nib",
             ToolId::Pan => "pan",
             ToolId::Text => "text",
             ToolId::Shapes => "shapes",
@@ -167,6 +179,9 @@ pub fn handle_tool_switch(
             ToolId::Select => crate::io::input::InputMode::Select,
             ToolId::Pen => crate::io::input::InputMode::Pen,
             ToolId::Knife => crate::io::input::InputMode::Knife,
+            ToolId::Eraser => crate::io::input::InputMode::Eraser,
+            ToolId::Corners => crate::io::input::InputMode::Corners,
+            ToolId::Nib => crate::io::input::InputMode::Nib,
             ToolId::Pan => crate::io::input::InputMode::Pan,
             ToolId::Text => crate::io::input::InputMode::Text,
             ToolId::Shapes => crate::io::input::InputMode::Shape,
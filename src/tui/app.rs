@@ -33,6 +33,8 @@ impl App {
             Tab::new(TabType::Path),     // 7
             Tab::new(TabType::AI),       // 8
             Tab::new(TabType::Help),     // 9
+            Tab::new(TabType::Preferences), // 0
+            Tab::new(TabType::Build),       // Tab only
         ];
 
         Self {
@@ -61,6 +63,7 @@ impl App {
         // Request initial data
         let _ = self.app_tx.send(TuiMessage::RequestFontInfo);
         let _ = self.app_tx.send(TuiMessage::RequestGlyphList);
+        let _ = self.app_tx.send(TuiMessage::RequestPreferences);
 
         loop {
             terminal.draw(|f| ui::draw(f, self))?;
@@ -112,9 +115,10 @@ impl App {
             (KeyCode::BackTab, _) => {
                 self.previous_tab();
             }
-            (KeyCode::Char(c), _) if c.is_ascii_digit() => {
+            (KeyCode::Char(c), _) if c.is_ascii_digit() && !self.is_capturing_key() => {
                 if let Some(digit) = c.to_digit(10) {
-                    let tab_index = (digit as usize).saturating_sub(1);
+                    // 0 is the tenth tab, as on the number row
+                    let tab_index = (digit as usize + 9) % 10;
                     if tab_index < self.tabs.len() {
                         self.current_tab = tab_index;
                     }
@@ -154,6 +158,22 @@ impl App {
                         TabState::QA(state) => {
                             crate::tui::tabs::qa::handle_key_event(state, key, &app_tx).await?;
                         }
+                        TabState::Glyph(state) => {
+                            crate::tui::tabs::glyph::handle_key_event(
+                                state,
+                                key,
+                                &app_tx,
+                                &self.glyphs,
+                            )
+                            .await?;
+                        }
+                        TabState::Preferences(state) => {
+                            crate::tui::tabs::preferences::handle_key_event(state, key, &app_tx)
+                                .await?;
+                        }
+                        TabState::Build(state) => {
+                            crate::tui::tabs::build::handle_key_event(state, key, &app_tx).await?;
+                        }
                         _ => {}
                     }
                 }
@@ -208,6 +228,34 @@ impl App {
             AppMessage::Error(error) => {
                 self.logs.push(format!("Error: {}", error));
             }
+            AppMessage::Preferences(lines, message) => {
+                for tab in &mut self.tabs {
+                    if let TabState::Preferences(ref mut state) = tab.state {
+                        state.update(lines.clone(), message.clone());
+                    }
+                }
+            }
+            AppMessage::ColorblindSafe(colorblind_safe) => {
+                for tab in &mut self.tabs {
+                    if let TabState::QA(ref mut state) = tab.state {
+                        state.colorblind_safe = colorblind_safe;
+                    }
+                }
+            }
+            AppMessage::BuildInspections(fonts) => {
+                for tab in &mut self.tabs {
+                    if let TabState::Build(ref mut state) = tab.state {
+                        state.update(fonts.clone());
+                    }
+                }
+            }
+            AppMessage::CompileErrors(issues) => {
+                for tab in &mut self.tabs {
+                    if let TabState::QA(ref mut state) = tab.state {
+                        state.set_compile_issues(issues.clone());
+                    }
+                }
+            }
             AppMessage::FileAction(action) => {
                 for tab in &mut self.tabs {
                     if let TabState::File(ref mut state) = tab.state {
@@ -219,6 +267,18 @@ impl App {
         Ok(())
     }
 
+    /// Whether the Preferences tab is waiting for a key to bind, or the
+    /// Unicode tab for a search query or the Glyph tab for a glyph name, so
+    /// digits don't switch tabs
+    fn is_capturing_key(&self) -> bool {
+        match &self.get_current_tab().state {
+            TabState::Preferences(state) => state.capturing,
+            TabState::Unicode(state) => state.is_searching,
+            TabState::Glyph(state) => state.is_typing(),
+            _ => false,
+        }
+    }
+
     pub fn next_tab(&mut self) {
         self.current_tab = (self.current_tab + 1) % self.tabs.len();
     }
@@ -1,26 +1,49 @@
+use crate::data::font_inspector::FontInspection;
+use crate::data::glyph_sets::{Coverage, CoverageSummary, GlyphSet};
 use serde::{Deserialize, Serialize};
 
 /// Generate glyph list from AppState
+///
+/// With a target glyph set, each glyph of the set carries its coverage and
+/// the glyphs the font is missing are listed too.
 pub fn generate_glyph_list(
     app_state: Option<&crate::core::AppState>,
+    glyph_set: Option<&GlyphSet>,
 ) -> Vec<GlyphInfo> {
     let mut glyphs = Vec::new();
 
     // Extract glyph data from AppState
     if let Some(app_state) = app_state {
-        for (glyph_name, glyph) in &app_state.workspace.font.glyphs {
+        let font = &app_state.workspace.font;
+        let coverage = glyph_set.map(|set| set.coverage(font)).unwrap_or_default();
+        for (glyph_name, glyph) in &font.glyphs {
             let unicode_value = glyph.unicode_values.first().map(|c| *c as u32);
             let width = Some(glyph.advance_width as f32);
+            let in_set = coverage.iter().find(|(entry, _)| {
+                entry.codepoint.is_some_and(|c| glyph.unicode_values.contains(&c))
+                    || entry.name == *glyph_name
+            });
 
             let glyph_info = GlyphInfo {
                 codepoint: glyph_name.clone(),
                 name: Some(glyph_name.clone()),
                 unicode: unicode_value,
                 width,
+                coverage: in_set.map(|(_, coverage)| *coverage),
             };
 
             glyphs.push(glyph_info);
         }
+
+        for (entry, _) in coverage.iter().filter(|(_, c)| *c == Coverage::Missing) {
+            glyphs.push(GlyphInfo {
+                codepoint: entry.name.clone(),
+                name: Some(entry.name.clone()),
+                unicode: entry.codepoint.map(|c| c as u32),
+                width: None,
+                coverage: Some(Coverage::Missing),
+            });
+        }
     }
 
     // Sort glyphs by Unicode value, then by name
@@ -34,12 +57,34 @@ pub fn generate_glyph_list(
     glyphs
 }
 
+/// Generate the Font Info tab's data from AppState
+pub fn generate_font_info(
+    app_state: Option<&crate::core::AppState>,
+    glyph_set: Option<&GlyphSet>,
+) -> Option<FontInfo> {
+    let app_state = app_state?;
+    let info = &app_state.workspace.info;
+    Some(FontInfo {
+        family_name: Some(info.family_name.clone()).filter(|name| !name.is_empty()),
+        style_name: Some(info.style_name.clone()).filter(|name| !name.is_empty()),
+        version: None,
+        ascender: info.ascender.map(|v| v as f32),
+        descender: info.descender.map(|v| v as f32),
+        cap_height: info.cap_height.map(|v| v as f32),
+        x_height: info.x_height.map(|v| v as f32),
+        units_per_em: Some(info.units_per_em as f32),
+        glyph_set: glyph_set.map(|set| set.summary(&app_state.workspace.font)),
+    })
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GlyphInfo {
     pub codepoint: String,
     pub name: Option<String>,
     pub unicode: Option<u32>,
     pub width: Option<f32>,
+    /// How far along the glyph is, when it is in the target glyph set
+    pub coverage: Option<Coverage>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,6 +97,16 @@ pub struct FontInfo {
     pub cap_height: Option<f32>,
     pub x_height: Option<f32>,
     pub units_per_em: Option<f32>,
+    /// Coverage of the target glyph set, if there is one
+    pub glyph_set: Option<CoverageSummary>,
+}
+
+/// One preference as the Preferences tab lists it
+#[derive(Debug, Clone)]
+pub struct PreferenceLine {
+    pub preference: crate::core::config::Preference,
+    pub label: String,
+    pub value: String,
 }
 
 #[derive(Debug, Clone)]
@@ -61,6 +116,18 @@ pub enum TuiMessage {
     RequestFontInfo,
     ChangeZoom(f32),
     ForceRedraw, // Force immediate GUI redraw
+    BuildComposites, // Build missing accented glyphs from recipes
+    /// Make a built-in glyph set or glyph set file the target
+    LoadGlyphSet(String),
+    /// Add empty glyphs for the target glyph set's missing glyphs
+    AddGlyphSetPlaceholders,
+    RequestPreferences,
+    /// Step a preference to its next value, or its previous one with `false`
+    StepPreference(crate::core::config::Preference, bool),
+    /// Set the key switching to a tool, by tool id
+    SetToolShortcut(&'static str, char),
+    /// Show a glyph on the canvas, by name
+    JumpToGlyph(String),
     QAReportReady(crate::qa::QAReport),
     QAAnalysisFailed(String),
     Quit,
@@ -82,4 +149,12 @@ pub enum AppMessage {
     LogLine(String),
     Error(String),
     FileAction(FileAction),
+    /// The preferences, and why the last change was refused if it was
+    Preferences(Vec<PreferenceLine>, Option<String>),
+    /// Whether severities are colored with the colorblind-safe palette
+    ColorblindSafe(bool),
+    /// Tables, metrics and coverage of the fonts the last export wrote
+    BuildInspections(Vec<FontInspection>),
+    /// Errors of the last failed fontc build, empty once it succeeds
+    CompileErrors(Vec<crate::qa::QAIssue>),
 }
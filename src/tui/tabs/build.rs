@@ -0,0 +1,112 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use tokio::sync::mpsc;
+
+use crate::data::font_inspector::FontInspection;
+use crate::tui::communication::TuiMessage;
+
+#[derive(Debug, Clone)]
+pub struct BuildState {
+    /// Fonts written by the last export
+    pub fonts: Vec<FontInspection>,
+    pub selected: usize,
+    pub scroll_offset: u16,
+}
+
+impl Default for BuildState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BuildState {
+    pub fn new() -> Self {
+        Self {
+            fonts: Vec::new(),
+            selected: 0,
+            scroll_offset: 0,
+        }
+    }
+
+    pub fn update(&mut self, fonts: Vec<FontInspection>) {
+        self.fonts = fonts;
+        self.selected = 0;
+        self.scroll_offset = 0;
+    }
+}
+
+/// Handle key events for the Build tab
+///
+/// Left/Right pick a font of the build and Up/Down scroll its tables.
+pub async fn handle_key_event(
+    state: &mut BuildState,
+    key: KeyEvent,
+    _app_tx: &mpsc::UnboundedSender<TuiMessage>,
+) -> Result<()> {
+    match key.code {
+        KeyCode::Right | KeyCode::Char('l') => {
+            if state.selected + 1 < state.fonts.len() {
+                state.selected += 1;
+                state.scroll_offset = 0;
+            }
+        }
+        KeyCode::Left | KeyCode::Char('h') => {
+            state.selected = state.selected.saturating_sub(1);
+            state.scroll_offset = 0;
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            state.scroll_offset = state.scroll_offset.saturating_add(1);
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.scroll_offset = state.scroll_offset.saturating_sub(1);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Draw the Build tab UI
+pub fn draw(f: &mut Frame, state: &BuildState, area: Rect) {
+    let lines = match state.fonts.get(state.selected) {
+        Some(font) => {
+            let mut lines = vec![Line::from(format!(
+                "Font {} of {} (Left/Right for the others): {}",
+                state.selected + 1,
+                state.fonts.len(),
+                font.path.display()
+            ))];
+            for section in &font.sections {
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![Span::styled(
+                    section.title,
+                    Style::default().add_modifier(Modifier::BOLD),
+                )]));
+                for (name, value) in &section.rows {
+                    lines.push(Line::from(vec![
+                        Span::raw(format!("  {name:<32}")),
+                        Span::styled(value.clone(), Style::default().fg(Color::Cyan)),
+                    ]));
+                }
+            }
+            lines
+        }
+        None => vec![Line::from("No build yet: export the font to inspect it")],
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Span::styled("Build", Style::default().fg(Color::Green))),
+        )
+        .scroll((state.scroll_offset, 0));
+
+    f.render_widget(paragraph, area);
+}
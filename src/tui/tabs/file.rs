@@ -180,7 +180,7 @@ pub fn draw(f: &mut Frame, state: &mut FileState, area: Rect) {
     let file_location_lines = vec![
         Line::from(""),
         if let Some(path) = &state.current_file_path {
-            let display_path = if let Ok(stripped) = std::path::Path::new(path).strip_prefix(std::env::var("HOME").unwrap_or_default()) {
+            let display_path = if let Ok(stripped) = std::path::Path::new(path).strip_prefix(dirs::home_dir().unwrap_or_default()) {
                 format!("~/{}", stripped.display())
             } else {
                 path.clone()
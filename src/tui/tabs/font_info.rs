@@ -45,7 +45,7 @@ pub async fn handle_key_event(
 /// Draw the FontInfo tab UI
 pub fn draw(f: &mut Frame, state: &FontInfoState, area: Rect) {
     let info_text = if let Some(ref info) = state.font_info {
-        vec![
+        let mut lines = vec![
             Line::from(vec![Span::styled(
                 "Font Metadata",
                 Style::default().add_modifier(Modifier::BOLD),
@@ -90,7 +90,24 @@ pub fn draw(f: &mut Frame, state: &FontInfoState, area: Rect) {
                 "  X-Height:       {}",
                 info.x_height.map_or("N/A".to_string(), |v| v.to_string())
             )),
-        ]
+        ];
+        if let Some(coverage) = &info.glyph_set {
+            lines.extend([
+                Line::from(""),
+                Line::from(vec![Span::styled(
+                    "Glyph Set",
+                    Style::default().add_modifier(Modifier::BOLD),
+                )]),
+                Line::from(""),
+                Line::from(format!("  Name:           {}", coverage.set_name)),
+                Line::from(format!("  Coverage:       {:.1}%", coverage.percent())),
+                Line::from(format!(
+                    "  Done / Empty / Missing: {} / {} / {}",
+                    coverage.done, coverage.empty, coverage.missing
+                )),
+            ]);
+        }
+        lines
     } else {
         vec![Line::from("No font loaded")]
     };
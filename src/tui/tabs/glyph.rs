@@ -1,20 +1,30 @@
 use anyhow::Result;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
-    layout::{Alignment, Rect},
-    style::{Color, Style},
-    text::Span,
-    widgets::{Block, Borders, Paragraph},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
 use tokio::sync::mpsc;
 
-use crate::tui::communication::TuiMessage;
+use crate::data::glyph_completion::{complete, GlyphCompletion};
+use crate::data::glyph_sets::Coverage;
+use crate::tui::communication::{GlyphInfo, TuiMessage};
+
+/// Most glyphs listed for a query
+const MAX_COMPLETIONS: usize = 50;
 
 #[derive(Debug, Clone)]
 pub struct GlyphState {
     // TODO: Add glyph-specific state fields
     pub current_glyph: Option<String>,
+    /// Name typed to go to a glyph, while one is being typed
+    pub query: Option<String>,
+    pub completions: Vec<GlyphCompletion>,
+    /// Highlighted completion
+    pub selected: usize,
 }
 
 impl Default for GlyphState {
@@ -27,32 +37,211 @@ impl GlyphState {
     pub fn new() -> Self {
         Self {
             current_glyph: None,
+            query: None,
+            completions: Vec::new(),
+            selected: 0,
         }
     }
+
+    /// Whether keys go into a glyph name
+    pub fn is_typing(&self) -> bool {
+        self.query.is_some()
+    }
+
+    /// Complete the typed name to the font's glyphs, leaving out the glyph
+    /// set's missing glyphs
+    fn refresh(&mut self, glyphs: &[GlyphInfo]) {
+        let names = glyphs
+            .iter()
+            .filter(|glyph| glyph.coverage != Some(Coverage::Missing))
+            .filter_map(|glyph| {
+                let codepoint = glyph.unicode.and_then(char::from_u32);
+                glyph.name.as_deref().map(|name| (name, codepoint))
+            });
+        let query = self.query.as_deref().unwrap_or_default();
+        self.completions = complete(names, query, MAX_COMPLETIONS);
+        self.selected = 0;
+    }
 }
 
 /// Handle key events for the Glyph tab
 pub async fn handle_key_event(
-    _state: &mut GlyphState,
-    _key: KeyEvent,
-    _app_tx: &mpsc::UnboundedSender<TuiMessage>,
+    state: &mut GlyphState,
+    key: KeyEvent,
+    app_tx: &mpsc::UnboundedSender<TuiMessage>,
+    glyphs: &[GlyphInfo],
 ) -> Result<()> {
+    if let Some(query) = state.query.as_mut() {
+        match key.code {
+            KeyCode::Esc => state.query = None,
+            KeyCode::Enter => {
+                if let Some(completion) = state.completions.get(state.selected) {
+                    let _ = app_tx.send(TuiMessage::JumpToGlyph(completion.name.clone()));
+                }
+                state.query = None;
+            }
+            KeyCode::Down => {
+                state.selected =
+                    (state.selected + 1).min(state.completions.len().saturating_sub(1));
+            }
+            KeyCode::Up => state.selected = state.selected.saturating_sub(1),
+            KeyCode::Backspace => {
+                query.pop();
+                state.refresh(glyphs);
+            }
+            KeyCode::Char(c) => {
+                query.push(c);
+                state.refresh(glyphs);
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
     // TODO: Implement glyph-specific key handling
-    // - Navigate between glyphs
     // - Edit glyph properties
     // - View glyph metrics
+    match key.code {
+        KeyCode::Char('g') | KeyCode::Char('/') => {
+            state.query = Some(String::new());
+            state.refresh(glyphs);
+        }
+        KeyCode::Char('b') => {
+            let _ = app_tx.send(TuiMessage::BuildComposites);
+        }
+        KeyCode::Char('l') => {
+            let _ = app_tx.send(TuiMessage::LoadGlyphSet("latin-core".to_string()));
+        }
+        KeyCode::Char('p') => {
+            let _ = app_tx.send(TuiMessage::AddGlyphSetPlaceholders);
+        }
+        _ => {}
+    }
     Ok(())
 }
 
+/// A completion's name, then the character it encodes and its codepoint
+fn completion_line(completion: &GlyphCompletion) -> Line<'static> {
+    let mut spans = vec![Span::raw(format!("{:<24}", completion.name))];
+    if let Some(c) = completion.codepoint {
+        let shown = if c.is_control() { ' ' } else { c };
+        spans.push(Span::styled(
+            format!("{shown}  U+{:04X}", c as u32),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// The highlighted glyph's character, codepoint and advance width
+fn preview_lines(glyph: Option<&GlyphInfo>) -> Vec<Line<'static>> {
+    let Some(glyph) = glyph else {
+        return vec![Line::from("  No glyph highlighted")];
+    };
+    let character = glyph
+        .unicode
+        .and_then(char::from_u32)
+        .filter(|c| !c.is_control())
+        .map_or("  (no character)".to_string(), |c| format!("  {c}"));
+    let codepoint = glyph
+        .unicode
+        .map_or("none".to_string(), |u| format!("U+{u:04X}"));
+    let width = glyph
+        .width
+        .map_or("none".to_string(), |width| format!("{width}"));
+    vec![
+        Line::from(Span::styled(
+            character,
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(format!("  Codepoint: {codepoint}")),
+        Line::from(format!("  Advance width: {width}")),
+    ]
+}
+
 /// Draw the Glyph tab UI
-pub fn draw(f: &mut Frame, _state: &mut GlyphState, area: Rect) {
-    let paragraph = Paragraph::new("Glyph editing tools coming soon...")
+pub fn draw(f: &mut Frame, glyphs: &[GlyphInfo], state: &mut GlyphState, area: Rect) {
+    let Some(query) = state.query.as_deref() else {
+        let lines = vec![
+            Line::from(""),
+            Line::from("  g or /         - Go to a glyph, completing its name as you type"),
+            Line::from("  b              - Build missing accented glyphs from recipes"),
+            Line::from("  l              - Track coverage of the built-in Latin Core glyph set"),
+            Line::from(
+                "  p              - Add empty placeholders for the glyph set's missing glyphs",
+            ),
+        ];
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(Span::styled("Glyph", Style::default().fg(Color::Green))),
+            )
+            .alignment(Alignment::Left);
+
+        f.render_widget(paragraph, area);
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(5),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    let input = Paragraph::new(format!("{query}▏")).block(
+        Block::default().borders(Borders::ALL).title(Span::styled(
+            "Go to glyph (↑↓ move, Enter goes, Esc cancels)",
+            Style::default().fg(Color::Green),
+        )),
+    );
+    f.render_widget(input, chunks[0]);
+
+    let items: Vec<ListItem> = state
+        .completions
+        .iter()
+        .map(|completion| ListItem::new(completion_line(completion)))
+        .collect();
+    let title = if state.completions.is_empty() {
+        "No glyphs found".to_string()
+    } else {
+        format!("{} glyphs", state.completions.len())
+    };
+    let list = List::new(items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(Span::styled("Glyph", Style::default().fg(Color::Green))),
+                .title(Span::styled(title, Style::default().fg(Color::Green))),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::Yellow)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
         )
-        .alignment(Alignment::Center);
+        .highlight_symbol("> ");
+    let mut list_state = ListState::default();
+    list_state.select(Some(state.selected).filter(|_| !state.completions.is_empty()));
+    f.render_stateful_widget(list, chunks[1], &mut list_state);
 
-    f.render_widget(paragraph, area);
+    let highlighted = state
+        .completions
+        .get(state.selected)
+        .and_then(|completion| {
+            glyphs
+                .iter()
+                .find(|glyph| glyph.name.as_deref() == Some(completion.name.as_str()))
+        });
+    let preview = Paragraph::new(preview_lines(highlighted)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Span::styled("Preview", Style::default().fg(Color::Green))),
+    );
+    f.render_widget(preview, chunks[2]);
 }
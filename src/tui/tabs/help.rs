@@ -49,7 +49,21 @@ pub fn draw(f: &mut Frame, _state: &mut HelpState, area: Rect) {
         Line::from("  Ctrl+Q         - Quit application"),
         Line::from("  Tab            - Next tab"),
         Line::from("  Shift+Tab      - Previous tab"),
-        Line::from("  1-9            - Jump to tab by number"),
+        Line::from("  0-9            - Jump to tab by number"),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Editor Window:",
+            Style::default().add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+        Line::from("  Ctrl+C / Ctrl+V       - Copy/paste outlines (SVG on system clipboard)"),
+        Line::from("  Ctrl+E                - Export fonts (pick a profile)"),
+        Line::from("  Ctrl+Shift+E          - Export active glyph as SVG"),
+        Line::from("  Ctrl+Alt+E            - Export all glyphs as SVGs"),
+        Line::from("  Drop .svg on window   - Import SVG into active glyph"),
+        Line::from("  Ctrl+B                - Toggle editing the background layer"),
+        Line::from("  Ctrl+Shift+B          - Copy active glyph to background layer"),
+        Line::from("  Ctrl+,                - Preferences"),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Unicode Tab:",
@@ -58,9 +72,10 @@ pub fn draw(f: &mut Frame, _state: &mut HelpState, area: Rect) {
         Line::from(""),
         Line::from("  ↑/↓ or j/k     - Navigate codepoint list"),
         Line::from("  Page Up/Down   - Navigate by page"),
-        Line::from("  Enter          - Select codepoint in editor"),
-        Line::from("  /              - Search codepoints"),
-        Line::from("  Esc            - Exit search"),
+        Line::from("  Enter          - Select codepoint in editor, or open a block"),
+        Line::from("  /              - Search codepoints (character names outside the font)"),
+        Line::from("  b / f          - Browse Unicode blocks / list the font's glyphs"),
+        Line::from("  Esc            - Exit search, or go back to the blocks"),
         Line::from(""),
         Line::from(vec![Span::styled(
             "AI Tab:",
@@ -70,12 +85,29 @@ pub fn draw(f: &mut Frame, _state: &mut HelpState, area: Rect) {
         Line::from("  Space          - Pause/Resume Game of Life"),
         Line::from("  R              - Reset with new random state"),
         Line::from(""),
+        Line::from(vec![Span::styled(
+            "Preferences Tab:",
+            Style::default().add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+        Line::from("  ↑/↓ or j/k     - Pick a preference"),
+        Line::from("  ←/→ or h/l     - Change it (saved to settings.json)"),
+        Line::from("  Enter          - On a tool, press a letter to set its shortcut"),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Build Tab (after the Preferences tab):",
+            Style::default().add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+        Line::from("  ←/→ or h/l     - Pick a font of the last export"),
+        Line::from("  ↑/↓ or j/k     - Scroll its tables, metrics and coverage"),
+        Line::from(""),
         Line::from(vec![Span::styled(
             "Navigation:",
             Style::default().add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
-        Line::from("  Use number keys 1-9 to quickly jump between tabs"),
+        Line::from("  Use number keys 0-9 to quickly jump between tabs"),
         Line::from("  Tab/Shift+Tab to cycle through tabs"),
     ];
 
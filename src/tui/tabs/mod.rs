@@ -4,6 +4,7 @@ use crossterm::event::KeyEvent;
 use tokio::sync::mpsc;
 
 pub mod ai;
+pub mod build;
 pub mod edit;
 pub mod file;
 pub mod font_info;
@@ -12,6 +13,7 @@ pub mod glyph;
 pub mod help;
 pub mod logs;
 pub mod path;
+pub mod preferences;
 pub mod qa;
 pub mod unicode;
 
@@ -26,6 +28,8 @@ pub enum TabType {
     Path,
     AI,
     Help,
+    Preferences,
+    Build,
 }
 
 impl TabType {
@@ -40,6 +44,8 @@ impl TabType {
             TabType::Path => "Path",
             TabType::AI => "AI",
             TabType::Help => "Help",
+            TabType::Preferences => "Preferences",
+            TabType::Build => "Build",
         }
     }
 }
@@ -60,6 +66,8 @@ pub enum TabState {
     Path(path::PathState),
     AI(ai::AIState),
     Help(help::HelpState),
+    Preferences(preferences::PreferencesState),
+    Build(build::BuildState),
 }
 
 impl Tab {
@@ -74,6 +82,8 @@ impl Tab {
             TabType::Path => TabState::Path(path::PathState::new()),
             TabType::AI => TabState::AI(ai::AIState::new()),
             TabType::Help => TabState::Help(help::HelpState::new()),
+            TabType::Preferences => TabState::Preferences(preferences::PreferencesState::new()),
+            TabType::Build => TabState::Build(build::BuildState::new()),
         };
 
         Self { tab_type, state }
@@ -91,10 +101,16 @@ impl Tab {
             TabState::Edit(state) => edit::handle_key_event(state, key, app_tx).await,
             TabState::FontInfo(state) => font_info::handle_key_event(state, key, app_tx).await,
             TabState::QA(state) => qa::handle_key_event(state, key, app_tx).await,
-            TabState::Glyph(state) => glyph::handle_key_event(state, key, app_tx).await,
+            TabState::Glyph(state) => {
+                glyph::handle_key_event(state, key, app_tx, &app.glyphs).await
+            }
             TabState::Path(state) => path::handle_key_event(state, key, app_tx).await,
             TabState::AI(state) => ai::handle_key_event(state, key, app_tx).await,
             TabState::Help(state) => help::handle_key_event(state, key, app_tx).await,
+            TabState::Preferences(state) => {
+                preferences::handle_key_event(state, key, app_tx).await
+            }
+            TabState::Build(state) => build::handle_key_event(state, key, app_tx).await,
         }
     }
 }
@@ -0,0 +1,156 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+use tokio::sync::mpsc;
+
+use crate::core::config::Preference;
+use crate::tui::communication::{PreferenceLine, TuiMessage};
+
+#[derive(Debug, Clone)]
+pub struct PreferencesState {
+    pub lines: Vec<PreferenceLine>,
+    pub selected: usize,
+    /// Waiting for the key to switch to the selected tool
+    pub capturing: bool,
+    /// Why the last change was refused
+    pub message: Option<String>,
+}
+
+impl Default for PreferencesState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PreferencesState {
+    pub fn new() -> Self {
+        Self {
+            lines: Vec::new(),
+            selected: 0,
+            capturing: false,
+            message: None,
+        }
+    }
+
+    pub fn update(&mut self, lines: Vec<PreferenceLine>, message: Option<String>) {
+        self.lines = lines;
+        self.message = message;
+        self.selected = self.selected.min(self.lines.len().saturating_sub(1));
+    }
+
+    fn selected_preference(&self) -> Option<&Preference> {
+        self.lines.get(self.selected).map(|line| &line.preference)
+    }
+}
+
+/// Handle key events for the Preferences tab
+///
+/// Up/Down pick a preference, Left/Right change it, and Enter on a tool
+/// waits for the letter to switch to it.
+pub async fn handle_key_event(
+    state: &mut PreferencesState,
+    key: KeyEvent,
+    app_tx: &mpsc::UnboundedSender<TuiMessage>,
+) -> Result<()> {
+    if state.capturing {
+        state.capturing = false;
+        if let (KeyCode::Char(c), Some(Preference::ToolShortcut(tool_id))) =
+            (key.code, state.selected_preference())
+        {
+            let _ = app_tx.send(TuiMessage::SetToolShortcut(*tool_id, c));
+        }
+        return Ok(());
+    }
+
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => {
+            if state.selected + 1 < state.lines.len() {
+                state.selected += 1;
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.selected = state.selected.saturating_sub(1);
+        }
+        KeyCode::Left | KeyCode::Right | KeyCode::Char('h') | KeyCode::Char('l') => {
+            let forward = matches!(key.code, KeyCode::Right | KeyCode::Char('l'));
+            if let Some(preference) = state.selected_preference() {
+                let _ = app_tx.send(TuiMessage::StepPreference(preference.clone(), forward));
+            }
+        }
+        KeyCode::Enter => match state.selected_preference().cloned() {
+            Some(Preference::ToolShortcut(_)) => {
+                state.capturing = true;
+                state.message = None;
+            }
+            Some(preference) => {
+                let _ = app_tx.send(TuiMessage::StepPreference(preference, true));
+            }
+            None => {}
+        },
+        KeyCode::Char('r') => {
+            let _ = app_tx.send(TuiMessage::RequestPreferences);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Draw the Preferences tab UI
+pub fn draw(f: &mut Frame, state: &PreferencesState, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(area);
+
+    let items: Vec<ListItem> = state
+        .lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let value = if state.capturing && i == state.selected {
+                crate::i18n::tr("pref-press-a-letter")
+            } else {
+                line.value.clone()
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{:<16}", line.label)),
+                Span::styled(value, Style::default().fg(Color::Cyan)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(Span::styled(
+            "Preferences",
+            Style::default().fg(Color::Green),
+        )))
+        .highlight_style(
+            Style::default()
+                .bg(Color::Yellow)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+
+    let mut list_state = ratatui::widgets::ListState::default();
+    if !state.lines.is_empty() {
+        list_state.select(Some(state.selected));
+    }
+    f.render_stateful_widget(list, chunks[0], &mut list_state);
+
+    let status = match &state.message {
+        Some(message) => Line::from(Span::styled(
+            message.clone(),
+            Style::default().fg(Color::Red),
+        )),
+        None => Line::from("↑/↓ pick  ←/→ change  Enter set a tool's key  r refresh"),
+    };
+    let paragraph = Paragraph::new(status).block(Block::default().borders(Borders::ALL));
+    f.render_widget(paragraph, chunks[1]);
+}
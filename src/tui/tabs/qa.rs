@@ -10,6 +10,7 @@ use ratatui::{
 use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 
+use crate::i18n;
 use crate::qa::{Category, Location, QAIssue, QAReport, QASummary, Severity};
 use crate::tui::communication::TuiMessage;
 
@@ -24,6 +25,8 @@ pub struct QAState {
     pub progress: f32,
     pub scroll_offset: usize,
     pub view_mode: QAView,
+    /// Color severities with the colorblind-safe palette
+    pub colorblind_safe: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -52,6 +55,7 @@ impl QAState {
             progress: 0.0,
             scroll_offset: 0,
             view_mode: QAView::IssueList,
+            colorblind_safe: false,
         };
         // Load demo data for initial display
         state.load_demo_data();
@@ -148,6 +152,25 @@ impl QAState {
         self.issues = demo_issues;
     }
 
+    /// Replaces the compile errors listed, putting the new ones first
+    pub fn set_compile_issues(&mut self, compile_issues: Vec<QAIssue>) {
+        use crate::qa::compilation::CHECK_ID;
+        self.issues.retain(|issue| issue.check_id != CHECK_ID);
+        self.issues.splice(0..0, compile_issues);
+        self.selected_issue = 0;
+        self.scroll_offset = 0;
+    }
+
+    /// The glyph the selected issue is located at
+    pub fn selected_glyph(&self) -> Option<&str> {
+        self.issues
+            .get(self.selected_issue)?
+            .location
+            .as_ref()?
+            .glyph_name
+            .as_deref()
+    }
+
     pub fn select_next_issue(&mut self) {
         if !self.issues.is_empty() {
             self.selected_issue = (self.selected_issue + 1).min(self.issues.len() - 1);
@@ -217,12 +240,17 @@ impl QAState {
 pub async fn handle_key_event(
     state: &mut QAState,
     key: KeyEvent,
-    _app_tx: &mpsc::UnboundedSender<TuiMessage>,
+    app_tx: &mpsc::UnboundedSender<TuiMessage>,
 ) -> Result<()> {
     match key.code {
         KeyCode::Down | KeyCode::Char('j') => {
             state.select_next_issue();
         }
+        KeyCode::Char('g') => {
+            if let Some(glyph_name) = state.selected_glyph() {
+                let _ = app_tx.send(TuiMessage::JumpToGlyph(glyph_name.to_string()));
+            }
+        }
         KeyCode::Up | KeyCode::Char('k') => {
             state.select_previous_issue();
         }
@@ -396,12 +424,12 @@ fn draw_issue_list(f: &mut Frame, state: &mut QAState, area: Rect) {
         .take(visible_height)
         .map(|(_, issue)| {
             let severity_style = Style::default()
-                .fg(issue.severity.color())
+                .fg(issue.severity.color(state.colorblind_safe))
                 .add_modifier(Modifier::BOLD);
             let line = Line::from(vec![
-                Span::styled(format!("{:<6}", issue.severity.as_str()), severity_style),
+                Span::styled(format!("{:<6}", issue.severity.label()), severity_style),
                 Span::raw(" "),
-                Span::raw(&issue.check_id),
+                Span::raw(issue.check_summary().unwrap_or_else(|| issue.check_id.clone())),
             ]);
             ListItem::new(line)
         })
@@ -455,14 +483,14 @@ fn draw_filters(f: &mut Frame, state: &QAState, area: Rect) {
     let severity_filter = state
         .filter_severity
         .as_ref()
-        .map(|s| s.as_str())
-        .unwrap_or("All");
+        .map(|s| s.label())
+        .unwrap_or_else(|| "All".to_string());
 
     let category_filter = state
         .filter_category
         .as_ref()
-        .map(|c| c.as_str())
-        .unwrap_or("All");
+        .map(|c| c.label())
+        .unwrap_or_else(|| "All".to_string());
 
     let text = format!(
         "Severity: [{}]  Category: [{}]",
@@ -479,7 +507,8 @@ fn draw_filters(f: &mut Frame, state: &QAState, area: Rect) {
 
 fn draw_controls(f: &mut Frame, area: Rect) {
     let text =
-        "↑↓/j/k: Navigate | Enter: Details | S: Summary | F: Filter | R: Refresh | Esc: Back";
+        "↑↓/j/k: Navigate | Enter: Details | G: Go to glyph | S: Summary | F: Filter | \
+         R: Refresh | Esc: Back";
     let paragraph = Paragraph::new(text).block(
         Block::default()
             .borders(Borders::ALL)
@@ -496,21 +525,22 @@ fn draw_issue_detail(f: &mut Frame, state: &QAState, area: Rect) {
             .constraints([Constraint::Min(0), Constraint::Length(3)])
             .split(area);
 
+        let bold = Style::default().add_modifier(Modifier::BOLD);
         let mut lines = vec![
             Line::from(vec![
-                Span::styled("Check: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{} ", i18n::tr("qa-label-check")), bold),
                 Span::raw(&issue.check_id),
             ]),
             Line::from(vec![
-                Span::styled("Severity: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{} ", i18n::tr("qa-label-severity")), bold),
                 Span::styled(
-                    issue.severity.as_str(),
-                    Style::default().fg(issue.severity.color()),
+                    issue.severity.label(),
+                    Style::default().fg(issue.severity.color(state.colorblind_safe)),
                 ),
             ]),
             Line::from(vec![
-                Span::styled("Category: ", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(issue.category.as_str()),
+                Span::styled(format!("{} ", i18n::tr("qa-label-category")), bold),
+                Span::raw(issue.category.label()),
             ]),
             Line::from(""),
             Line::from(vec![Span::styled(
@@ -519,6 +549,10 @@ fn draw_issue_detail(f: &mut Frame, state: &QAState, area: Rect) {
             )]),
         ];
 
+        if let Some(summary) = issue.check_summary() {
+            lines.insert(1, Line::from(format!("  {summary}")));
+        }
+
         // Split message into multiple lines if needed
         for line in issue.message.lines() {
             lines.push(Line::from(format!("  {}", line)));
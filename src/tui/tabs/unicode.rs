@@ -1,3 +1,5 @@
+use crate::data::glyph_sets::Coverage;
+use crate::data::unicode_db::{self, CharInfo};
 use crate::tui::communication::{GlyphInfo, TuiMessage};
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent};
@@ -8,14 +10,38 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
+use std::collections::HashSet;
 use tokio::sync::mpsc;
 
+/// Most characters a name search lists
+const SEARCH_LIMIT: usize = 500;
+
+/// What the Unicode tab lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnicodeView {
+    /// The font's glyphs
+    Font,
+    /// Unicode blocks, with how much of each the font covers
+    Blocks,
+    /// The characters of a block, or those found by name
+    Characters,
+}
+
 #[derive(Debug, Clone)]
 pub struct GlyphsState {
     pub selected_index: usize,
     pub scroll_offset: usize,
     pub search_query: String,
     pub is_searching: bool,
+    pub view: UnicodeView,
+    /// Characters listed in the Characters view
+    pub chars: Vec<char>,
+    /// The block or search the characters come from
+    pub chars_title: String,
+    /// The block last opened, to go back to
+    pub block_index: usize,
+    /// Assigned characters per block, counted the first time blocks show
+    pub block_sizes: Vec<usize>,
 }
 
 impl Default for GlyphsState {
@@ -31,6 +57,57 @@ impl GlyphsState {
             scroll_offset: 0,
             search_query: String::new(),
             is_searching: false,
+            view: UnicodeView::Font,
+            chars: Vec::new(),
+            chars_title: String::new(),
+            block_index: 0,
+            block_sizes: Vec::new(),
+        }
+    }
+
+    /// List the blocks, with the last opened one selected
+    pub fn show_blocks(&mut self) {
+        if self.block_sizes.is_empty() {
+            self.block_sizes = unicode_db::blocks()
+                .iter()
+                .map(|block| block.chars().len())
+                .collect();
+        }
+        self.view = UnicodeView::Blocks;
+        self.selected_index = self.block_index;
+        self.scroll_offset = 0;
+    }
+
+    /// List the characters of a block
+    pub fn open_block(&mut self, index: usize) {
+        let Some(block) = unicode_db::blocks().get(index) else {
+            return;
+        };
+        self.block_index = index;
+        self.chars = block.chars();
+        self.chars_title = block.name.to_string();
+        self.show_chars();
+    }
+
+    /// List the characters whose names match the search query
+    pub fn search_names(&mut self) {
+        self.chars = unicode_db::search(&self.search_query, SEARCH_LIMIT);
+        self.chars_title = format!("Search: {}", self.search_query);
+        self.show_chars();
+    }
+
+    fn show_chars(&mut self) {
+        self.view = UnicodeView::Characters;
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+    }
+
+    /// How many rows the current view lists
+    pub fn list_len(&self, glyphs_len: usize) -> usize {
+        match self.view {
+            UnicodeView::Font => glyphs_len,
+            UnicodeView::Blocks => unicode_db::blocks().len(),
+            UnicodeView::Characters => self.chars.len(),
         }
     }
 
@@ -71,53 +148,7 @@ pub async fn handle_key_event(
     app_tx: &mpsc::UnboundedSender<TuiMessage>,
     app: &crate::tui::app::App,
 ) -> Result<()> {
-    if state.is_searching {
-        match key.code {
-            KeyCode::Esc => {
-                state.is_searching = false;
-                state.search_query.clear();
-            }
-            KeyCode::Enter => {
-                state.is_searching = false;
-            }
-            KeyCode::Backspace => {
-                state.search_query.pop();
-            }
-            KeyCode::Char(c) => {
-                state.search_query.push(c);
-            }
-            _ => {}
-        }
-    } else {
-        match key.code {
-            KeyCode::Down | KeyCode::Char('j') => {
-                state.select_next(app.glyphs.len());
-            }
-            KeyCode::Up | KeyCode::Char('k') => {
-                state.select_previous();
-            }
-            KeyCode::PageDown => {
-                state.page_down(app.glyphs.len(), 10);
-            }
-            KeyCode::PageUp => {
-                state.page_up(10);
-            }
-            KeyCode::Enter => {
-                // Send message to select current glyph using Unicode codepoint
-                if let Some(glyph) = app.glyphs.get(state.selected_index) {
-                    if let Some(unicode) = glyph.unicode {
-                        let _ = app_tx.send(TuiMessage::SelectGlyph(unicode));
-                    }
-                }
-            }
-            KeyCode::Char('/') => {
-                state.is_searching = true;
-                state.search_query.clear();
-            }
-            _ => {}
-        }
-    }
-    Ok(())
+    handle_key_event_simple(state, key, app_tx, app.glyphs.len(), &app.glyphs).await
 }
 
 pub async fn handle_key_event_simple(
@@ -127,6 +158,8 @@ pub async fn handle_key_event_simple(
     glyphs_len: usize,
     glyphs: &[crate::tui::communication::GlyphInfo],
 ) -> Result<()> {
+    // Outside the font's glyphs, searching looks up character names
+    let searches_names = state.view != UnicodeView::Font;
     if state.is_searching {
         match key.code {
             KeyCode::Esc => {
@@ -138,33 +171,59 @@ pub async fn handle_key_event_simple(
             }
             KeyCode::Backspace => {
                 state.search_query.pop();
+                if searches_names {
+                    state.search_names();
+                }
             }
             KeyCode::Char(c) => {
                 state.search_query.push(c);
+                if searches_names {
+                    state.search_names();
+                }
             }
             _ => {}
         }
     } else {
+        let list_len = state.list_len(glyphs_len);
         match key.code {
             KeyCode::Down | KeyCode::Char('j') => {
-                state.select_next(glyphs_len);
+                state.select_next(list_len);
             }
             KeyCode::Up | KeyCode::Char('k') => {
                 state.select_previous();
             }
             KeyCode::PageDown => {
-                state.page_down(glyphs_len, 10);
+                state.page_down(list_len, 10);
             }
             KeyCode::PageUp => {
                 state.page_up(10);
             }
-            KeyCode::Enter => {
-                // Send message to select current glyph using Unicode codepoint
-                if let Some(glyph) = glyphs.get(state.selected_index) {
-                    if let Some(unicode) = glyph.unicode {
-                        let _ = app_tx.send(TuiMessage::SelectGlyph(unicode));
+            KeyCode::Enter => match state.view {
+                UnicodeView::Font => {
+                    // Send message to select current glyph using Unicode codepoint
+                    if let Some(glyph) = glyphs.get(state.selected_index) {
+                        if let Some(unicode) = glyph.unicode {
+                            let _ = app_tx.send(TuiMessage::SelectGlyph(unicode));
+                        }
+                    }
+                }
+                UnicodeView::Blocks => state.open_block(state.selected_index),
+                UnicodeView::Characters => {
+                    if let Some(c) = state.chars.get(state.selected_index) {
+                        let _ = app_tx.send(TuiMessage::SelectGlyph(*c as u32));
                     }
                 }
+            },
+            KeyCode::Esc | KeyCode::Backspace if state.view == UnicodeView::Characters => {
+                state.show_blocks();
+            }
+            KeyCode::Char('b') => {
+                state.show_blocks();
+            }
+            KeyCode::Char('f') => {
+                state.view = UnicodeView::Font;
+                state.selected_index = 0;
+                state.scroll_offset = 0;
             }
             KeyCode::Char('/') => {
                 state.is_searching = true;
@@ -176,105 +235,189 @@ pub async fn handle_key_event_simple(
     Ok(())
 }
 
+/// A glyph's codepoint and name, then how far along it is when it belongs
+/// to the target glyph set
+fn glyph_line(glyph: &GlyphInfo) -> Line<'static> {
+    let name = glyph.name.as_deref().unwrap_or(&glyph.codepoint);
+    let unicode = glyph
+        .unicode
+        .map(|u| format!("U+{:04X}", u))
+        .unwrap_or_else(|| "U+0000".to_string());
+    let mut spans = vec![Span::raw(format!("{} {}", unicode, name))];
+    if let Some(coverage) = glyph.coverage {
+        let color = match coverage {
+            Coverage::Done => Color::Green,
+            Coverage::Empty => Color::Yellow,
+            Coverage::Missing => Color::Red,
+        };
+        let label = format!("  [{}]", coverage.label());
+        spans.push(Span::styled(label, Style::default().fg(color)));
+    }
+    Line::from(spans)
+}
+
+/// A character as it can be shown in a terminal: marks on a dotted
+/// circle, and nothing for controls, separators and unassigned codepoints
+fn printable(c: char) -> String {
+    match unicode_db::general_category(c) {
+        "Mn" | "Mc" | "Me" => format!("◌{c}"),
+        category if category.starts_with(['C', 'Z']) => String::new(),
+        _ => c.to_string(),
+    }
+}
+
+/// A character of the Characters view, marked by whether the font has it
+fn char_line(c: char, covered: &HashSet<u32>) -> Line<'static> {
+    let (mark, color) = if covered.contains(&(c as u32)) {
+        ("●", Color::Green)
+    } else {
+        ("○", Color::DarkGray)
+    };
+    let name = unicode_db::name(c).unwrap_or_default();
+    Line::from(vec![
+        Span::styled(mark, Style::default().fg(color)),
+        Span::raw(format!(" U+{:04X} {:<3} {}", c as u32, printable(c), name)),
+    ])
+}
+
+/// A block of the Blocks view, with how many of its characters the font has
+fn block_line(index: usize, state: &GlyphsState, covered: &HashSet<u32>) -> Line<'static> {
+    let Some(block) = unicode_db::blocks().get(index) else {
+        return Line::default();
+    };
+    let in_font = covered
+        .iter()
+        .filter(|code| (block.first..=block.last).contains(*code))
+        .count();
+    let size = state.block_sizes.get(index).copied().unwrap_or_default();
+    let color = if in_font == 0 {
+        Color::DarkGray
+    } else if in_font >= size {
+        Color::Green
+    } else {
+        Color::Yellow
+    };
+    Line::from(vec![
+        Span::raw(format!(
+            "{:04X}–{:04X} {:<44}",
+            block.first, block.last, block.name
+        )),
+        Span::styled(format!("{in_font}/{size}"), Style::default().fg(color)),
+    ])
+}
+
+/// Name, script, category and block of a character, and whether the font
+/// has it
+fn details_lines(c: char, covered: &HashSet<u32>) -> Vec<Line<'static>> {
+    let info = CharInfo::new(c);
+    let in_font = if covered.contains(&(c as u32)) {
+        Span::styled("in font", Style::default().fg(Color::Green))
+    } else {
+        Span::styled("not in font", Style::default().fg(Color::Red))
+    };
+    vec![
+        Line::from(format!(
+            "U+{:04X} {} {}",
+            c as u32,
+            printable(c),
+            info.name.unwrap_or_default()
+        )),
+        Line::from(vec![
+            Span::raw(format!(
+                "Script: {} | Category: {} ({}) | Block: {} | ",
+                info.script.replace('_', " "),
+                info.category,
+                unicode_db::category_name(info.category),
+                info.block.unwrap_or("none")
+            )),
+            in_font,
+        ]),
+    ]
+}
+
 /// Draw the Unicode tab UI
 pub fn draw(f: &mut Frame, glyphs: &[GlyphInfo], state: &mut GlyphsState, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(0), Constraint::Length(3)].as_ref())
+        .constraints(
+            [
+                Constraint::Min(0),
+                Constraint::Length(4),
+                Constraint::Length(3),
+            ]
+            .as_ref(),
+        )
         .split(area);
 
-    // Filter glyphs based on search query
-    let filtered_glyphs: Vec<(usize, &GlyphInfo)> =
-        if state.is_searching && !state.search_query.is_empty() {
-            glyphs
-                .iter()
-                .enumerate()
-                .filter(|(_, g)| {
-                    let name = g.name.as_deref().unwrap_or(&g.codepoint);
-                    let unicode_str = g
-                        .unicode
-                        .map(|u| format!("U+{:04X}", u))
-                        .unwrap_or_default();
-                    name.to_lowercase()
-                        .contains(&state.search_query.to_lowercase())
-                        || unicode_str
-                            .to_lowercase()
-                            .contains(&state.search_query.to_lowercase())
-                })
-                .collect()
-        } else {
-            glyphs.iter().enumerate().collect()
-        };
-
-    // Create list items
-    let items: Vec<ListItem> = filtered_glyphs
+    // Codepoints with a glyph in the font, leaving out the glyph set's
+    // missing glyphs
+    let covered: HashSet<u32> = glyphs
         .iter()
-        .map(|(_, glyph)| {
-            let name = glyph.name.as_deref().unwrap_or(&glyph.codepoint);
-            let unicode = glyph
-                .unicode
-                .map(|u| format!("U+{:04X}", u))
-                .unwrap_or_else(|| "U+0000".to_string());
-            let line = format!("{} {}", unicode, name);
-            ListItem::new(Line::from(line))
-        })
+        .filter(|g| g.coverage != Some(Coverage::Missing))
+        .filter_map(|g| g.unicode)
         .collect();
 
     // Update scroll based on selection
     let visible_height = chunks[0].height.saturating_sub(2) as usize;
     state.update_scroll(visible_height);
+    let scroll_offset = state.scroll_offset;
 
-    // Calculate the selected index in the filtered list
-    let filtered_selected = if state.is_searching {
-        // Find the index in the filtered list
-        filtered_glyphs
-            .iter()
-            .position(|(orig_idx, _)| *orig_idx == state.selected_index)
-            .unwrap_or(0)
-    } else {
-        state.selected_index
-    };
-
-    // Create the list widget
-    let _list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(Span::styled("Unicode", Style::default().fg(Color::Green))),
-        )
-        .highlight_style(
-            Style::default()
-                .bg(Color::Yellow)
-                .fg(Color::Black)
-                .add_modifier(Modifier::BOLD),
-        )
-        .highlight_symbol("> ");
+    let (title, lines, selected, selected_char): (String, Vec<Line>, usize, Option<char>) =
+        match state.view {
+            UnicodeView::Font => {
+                let (lines, selected) = glyph_list(glyphs, state);
+                let selected_char = lines
+                    .get(selected)
+                    .and_then(|(index, _)| glyphs.get(*index))
+                    .and_then(|g| g.unicode)
+                    .and_then(char::from_u32);
+                let lines = lines
+                    .into_iter()
+                    .skip(scroll_offset)
+                    .take(visible_height)
+                    .map(|(_, line)| line)
+                    .collect();
+                ("Unicode".to_string(), lines, selected, selected_char)
+            }
+            UnicodeView::Blocks => {
+                let lines = (0..unicode_db::blocks().len())
+                    .skip(scroll_offset)
+                    .take(visible_height)
+                    .map(|index| block_line(index, state, &covered))
+                    .collect();
+                (
+                    "Unicode Blocks".to_string(),
+                    lines,
+                    state.selected_index,
+                    None,
+                )
+            }
+            UnicodeView::Characters => {
+                let lines = state
+                    .chars
+                    .iter()
+                    .skip(scroll_offset)
+                    .take(visible_height)
+                    .map(|c| char_line(*c, &covered))
+                    .collect();
+                let selected_char = state.chars.get(state.selected_index).copied();
+                let title = format!("Unicode: {}", state.chars_title);
+                (title, lines, state.selected_index, selected_char)
+            }
+        };
 
     // Render the list with scroll offset
     let mut list_state = ratatui::widgets::ListState::default();
-    list_state.select(Some(filtered_selected.saturating_sub(state.scroll_offset)));
+    list_state.select(Some(selected.saturating_sub(scroll_offset)));
 
-    // Only show the visible portion
-    let visible_items: Vec<ListItem> = filtered_glyphs
-        .iter()
-        .skip(state.scroll_offset)
-        .take(visible_height)
-        .map(|(_, glyph)| {
-            let name = glyph.name.as_deref().unwrap_or(&glyph.codepoint);
-            let unicode = glyph
-                .unicode
-                .map(|u| format!("U+{:04X}", u))
-                .unwrap_or_else(|| "U+0000".to_string());
-            let line = format!("{} {}", unicode, name);
-            ListItem::new(Line::from(line))
-        })
-        .collect();
+    // Only the visible portion was built
+    let visible_items: Vec<ListItem> = lines.into_iter().map(ListItem::new).collect();
 
     let visible_list = List::new(visible_items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(Span::styled("Unicode", Style::default().fg(Color::Green))),
+                .title(Span::styled(title, Style::default().fg(Color::Green))),
         )
         .highlight_style(
             Style::default()
@@ -286,6 +429,17 @@ pub fn draw(f: &mut Frame, glyphs: &[GlyphInfo], state: &mut GlyphsState, area:
 
     f.render_stateful_widget(visible_list, chunks[0], &mut list_state);
 
+    // Properties of the selected character
+    let details = selected_char
+        .map(|c| details_lines(c, &covered))
+        .unwrap_or_default();
+    let details = Paragraph::new(details).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Span::styled("Character", Style::default().fg(Color::Green))),
+    );
+    f.render_widget(details, chunks[1]);
+
     // Controls/status area
     let controls_text = if state.is_searching {
         format!(
@@ -293,14 +447,17 @@ pub fn draw(f: &mut Frame, glyphs: &[GlyphInfo], state: &mut GlyphsState, area:
             state.search_query
         )
     } else {
-        let selected_glyph = filtered_glyphs
-            .get(filtered_selected)
-            .and_then(|(_, g)| g.name.as_deref())
-            .unwrap_or("None");
-        format!(
-            "Selected: {} | Use ↑↓ or j/k to navigate, Enter to select, / to search",
-            selected_glyph
-        )
+        match state.view {
+            UnicodeView::Font => {
+                "↑↓ or j/k navigate, Enter selects, / searches, b browses blocks".to_string()
+            }
+            UnicodeView::Blocks => {
+                "Enter opens a block, / searches names, f lists the font's glyphs".to_string()
+            }
+            UnicodeView::Characters => {
+                "Enter selects, Esc goes back to blocks, / searches names, f font".to_string()
+            }
+        }
     };
 
     let controls = Paragraph::new(controls_text).block(
@@ -309,5 +466,52 @@ pub fn draw(f: &mut Frame, glyphs: &[GlyphInfo], state: &mut GlyphsState, area:
             .title(Span::styled("Controls", Style::default().fg(Color::Green))),
     );
 
-    f.render_widget(controls, chunks[1]);
+    f.render_widget(controls, chunks[2]);
+}
+
+/// The font's glyphs, filtered by the search query, as their index in
+/// `glyphs` and their line, then the position of the selected one
+fn glyph_list(glyphs: &[GlyphInfo], state: &GlyphsState) -> (Vec<(usize, Line<'static>)>, usize) {
+    // Filter glyphs based on search query, also matching character names
+    let query = state.search_query.to_lowercase();
+    let filtered_glyphs: Vec<(usize, &GlyphInfo)> = if state.is_searching && !query.is_empty() {
+        glyphs
+            .iter()
+            .enumerate()
+            .filter(|(_, g)| {
+                let name = g.name.as_deref().unwrap_or(&g.codepoint);
+                let unicode_str = g
+                    .unicode
+                    .map(|u| format!("U+{:04X}", u))
+                    .unwrap_or_default();
+                let char_name = g
+                    .unicode
+                    .and_then(char::from_u32)
+                    .and_then(unicode_db::name)
+                    .unwrap_or_default();
+                name.to_lowercase().contains(&query)
+                    || unicode_str.to_lowercase().contains(&query)
+                    || char_name.to_lowercase().contains(&query)
+            })
+            .collect()
+    } else {
+        glyphs.iter().enumerate().collect()
+    };
+
+    // Calculate the selected index in the filtered list
+    let filtered_selected = if state.is_searching {
+        // Find the index in the filtered list
+        filtered_glyphs
+            .iter()
+            .position(|(orig_idx, _)| *orig_idx == state.selected_index)
+            .unwrap_or(0)
+    } else {
+        state.selected_index
+    };
+
+    let lines = filtered_glyphs
+        .into_iter()
+        .map(|(index, glyph)| (index, glyph_line(glyph)))
+        .collect();
+    (lines, filtered_selected)
 }
@@ -27,7 +27,12 @@ fn draw_tabs(f: &mut Frame, app: &App, area: Rect) {
         .iter()
         .enumerate()
         .map(|(i, tab)| {
-            let title = format!("{}.{}", i + 1, tab.tab_type.title());
+            // Only the first ten tabs have a number key
+            let title = if i < 10 {
+                format!("{}.{}", (i + 1) % 10, tab.tab_type.title())
+            } else {
+                tab.tab_type.title().to_string()
+            };
             Line::from(title)
         })
         .collect();
@@ -67,7 +72,7 @@ fn draw_tab_content(f: &mut Frame, app: &mut App, area: Rect) {
             crate::tui::tabs::qa::draw(f, state, area);
         }
         TabState::Glyph(state) => {
-            crate::tui::tabs::glyph::draw(f, state, area);
+            crate::tui::tabs::glyph::draw(f, &glyphs, state, area);
         }
         TabState::Path(state) => {
             crate::tui::tabs::path::draw(f, state, area);
@@ -78,5 +83,11 @@ fn draw_tab_content(f: &mut Frame, app: &mut App, area: Rect) {
         TabState::Help(state) => {
             crate::tui::tabs::help::draw(f, state, area);
         }
+        TabState::Preferences(state) => {
+            crate::tui::tabs::preferences::draw(f, state, area);
+        }
+        TabState::Build(state) => {
+            crate::tui::tabs::build::draw(f, state, area);
+        }
     }
 }
@@ -0,0 +1,226 @@
+//! Screen reader support and keyboard focus
+//!
+//! Bevy hands the UI tree to the platform's screen reader through AccessKit,
+//! and bevy_ui already names text buttons after their text. This module
+//! names what the screen can't say, such as toolbar icons and panes, marks
+//! the active tool as toggled and hidden widgets as hidden, and lets Tab and
+//! Shift+Tab move keyboard focus through every shown button. Enter or Space
+//! presses the focused button, Escape or a click drops the focus.
+
+use crate::ui::edit_mode_toolbar::ui::ToolButtonData;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use crate::ui::themes::CurrentTheme;
+use accesskit::{Node as AccessKitNode, Role, Toggled};
+use bevy::a11y::{AccessibilityNode, AccessibilitySystem};
+use bevy::input_focus::InputFocus;
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+
+/// What a screen reader calls a widget its text doesn't describe, such as
+/// an icon button
+#[derive(Component, Clone)]
+pub struct AccessibleName(pub String);
+
+/// Marks the widget drawn with the keyboard focus ring
+#[derive(Component)]
+struct FocusRing;
+
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputFocus>()
+            .add_systems(PreUpdate, press_focused_button.after(UiSystem::Focus))
+            .add_systems(
+                PostUpdate,
+                (
+                    (
+                        apply_accessible_names,
+                        mark_active_tool,
+                        mark_hidden_widgets,
+                    )
+                        .before(AccessibilitySystem::Update),
+                    (move_keyboard_focus, draw_focus_ring).chain(),
+                ),
+            );
+    }
+}
+
+/// An AccessKit node for a dialog
+pub fn dialog_node(label: &str) -> AccessibilityNode {
+    let mut node = AccessKitNode::new(Role::Dialog);
+    node.set_label(label);
+    AccessibilityNode::from(node)
+}
+
+/// Whether a widget is on screen: visible and laid out with some size
+fn is_shown(visibility: &InheritedVisibility, node: &ComputedNode) -> bool {
+    visibility.get() && !node.is_empty()
+}
+
+/// The entity after `current` in `order`, or before it going back, wrapping
+/// around; with nothing focused it's the first (or last) one
+fn next_in_order(order: &[Entity], current: Option<Entity>, forward: bool) -> Option<Entity> {
+    if order.is_empty() {
+        return None;
+    }
+    let position = current.and_then(|current| order.iter().position(|e| *e == current));
+    let next = match (position, forward) {
+        (Some(i), true) => (i + 1) % order.len(),
+        (Some(i), false) => (i + order.len() - 1) % order.len(),
+        (None, true) => 0,
+        (None, false) => order.len() - 1,
+    };
+    Some(order[next])
+}
+
+// SYSTEMS ---------------------------------------------------------------------
+
+/// Puts `AccessibleName`s on the nodes bevy_ui labels from their text
+fn apply_accessible_names(mut query: Query<(&AccessibleName, &mut AccessibilityNode)>) {
+    for (name, mut node) in &mut query {
+        if node.label() != Some(name.0.as_str()) {
+            node.set_label(name.0.as_str());
+        }
+    }
+}
+
+/// Marks the current tool's toolbar button as toggled on
+fn mark_active_tool(
+    current_tool: Res<CurrentTool>,
+    mut query: Query<(&ToolButtonData, &mut AccessibilityNode)>,
+) {
+    for (tool_button, mut node) in &mut query {
+        let toggled = if current_tool.get_current() == Some(tool_button.tool_id) {
+            Toggled::True
+        } else {
+            Toggled::False
+        };
+        if node.toggled() != Some(toggled) {
+            node.set_toggled(toggled);
+        }
+    }
+}
+
+/// Hides widgets that aren't on screen, like closed dialogs and submenus,
+/// from the screen reader
+fn mark_hidden_widgets(
+    mut query: Query<(&mut AccessibilityNode, &InheritedVisibility, &ComputedNode)>,
+) {
+    for (mut node, visibility, computed) in &mut query {
+        let hidden = !is_shown(visibility, computed);
+        if node.is_hidden() != hidden {
+            if hidden {
+                node.set_hidden();
+            } else {
+                node.clear_hidden();
+            }
+        }
+    }
+}
+
+/// Moves the focus through the shown buttons on Tab, in reading order
+///
+/// Runs after `Update` so tools that use Tab themselves (the alternates
+/// picker, the AI tool) can claim it first.
+fn move_keyboard_focus(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut focus: ResMut<InputFocus>,
+    buttons: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &InheritedVisibility,
+            &ComputedNode,
+        ),
+        With<Button>,
+    >,
+) {
+    if !keyboard.just_pressed(KeyCode::Tab) {
+        return;
+    }
+    let mut shown: Vec<(Entity, Vec2)> = buttons
+        .iter()
+        .filter(|(_, _, visibility, node)| is_shown(visibility, node))
+        .map(|(entity, transform, _, _)| (entity, transform.translation().truncate()))
+        .collect();
+    shown.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+    let order: Vec<Entity> = shown.into_iter().map(|(entity, _)| entity).collect();
+
+    let backward = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    focus.0 = next_in_order(&order, focus.0, !backward);
+}
+
+/// Outlines the focused widget in the theme's focus color
+fn draw_focus_ring(
+    mut commands: Commands,
+    focus: Res<InputFocus>,
+    theme: Res<CurrentTheme>,
+    rings: Query<Entity, With<FocusRing>>,
+) {
+    if !focus.is_changed() && !theme.is_changed() {
+        return;
+    }
+    for entity in &rings {
+        commands.entity(entity).remove::<(Outline, FocusRing)>();
+    }
+    if let Some(entity) = focus.0 {
+        let width = Val::Px(theme.theme().widget_border_width());
+        let color = theme.theme().focus_background_color();
+        if let Ok(mut focused) = commands.get_entity(entity) {
+            focused.insert((Outline::new(width, width, color), FocusRing));
+        }
+    }
+}
+
+/// Presses the focused button on Enter or Space, before the button handlers
+/// look at `Interaction`, and drops the focus on Escape, a click, or when
+/// the button goes away
+fn press_focused_button(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut focus: ResMut<InputFocus>,
+    mut buttons: Query<(&mut Interaction, &InheritedVisibility, &ComputedNode), With<Button>>,
+) {
+    let Some(entity) = focus.0 else {
+        return;
+    };
+    if keyboard.just_pressed(KeyCode::Escape) || mouse.just_pressed(MouseButton::Left) {
+        focus.0 = None;
+        return;
+    }
+    let Ok((mut interaction, visibility, node)) = buttons.get_mut(entity) else {
+        focus.0 = None;
+        return;
+    };
+    if !is_shown(visibility, node) {
+        focus.0 = None;
+        return;
+    }
+    for key in [KeyCode::Enter, KeyCode::NumpadEnter, KeyCode::Space] {
+        if keyboard.just_pressed(key) {
+            // Keep the key from also reaching the editor
+            keyboard.clear_just_pressed(key);
+            *interaction = Interaction::Pressed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_in_order_wraps() {
+        let order = [
+            Entity::from_raw(1),
+            Entity::from_raw(2),
+            Entity::from_raw(3),
+        ];
+        assert_eq!(next_in_order(&order, None, true), Some(order[0]));
+        assert_eq!(next_in_order(&order, None, false), Some(order[2]));
+        assert_eq!(next_in_order(&order, Some(order[2]), true), Some(order[0]));
+        assert_eq!(next_in_order(&order, Some(order[0]), false), Some(order[2]));
+        assert_eq!(next_in_order(&[], Some(order[0]), true), None);
+    }
+}
@@ -0,0 +1,222 @@
+//! Context menus
+//!
+//! A small floating menu of items opened at a point on the screen, used for
+//! the canvas's right-click menus. Menus are generic over the event their
+//! items send: with `ContextMenuPlugin::<A>` added, writing an
+//! `OpenContextMenu<A>` opens a menu, and choosing one of its items writes
+//! that item's `A`. Items without an action are plain text, for showing
+//! information. One menu is open at a time; it closes when an item is
+//! chosen, on Escape, or on a click anywhere else.
+
+use std::marker::PhantomData;
+
+use crate::ui::theme::*;
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::{AssetServerFontExt, EmbeddedFonts};
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+/// Padding inside an item
+const ITEM_PADDING: f32 = 4.0;
+
+/// Narrowest a menu gets, so short menus are still easy to hit
+const MIN_WIDTH: f32 = 120.0;
+
+/// An entry of a context menu
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextMenuItem<A> {
+    pub label: String,
+    /// Event sent when the item is chosen; `None` for information only
+    pub action: Option<A>,
+}
+
+impl<A> ContextMenuItem<A> {
+    pub fn new(label: impl Into<String>, action: A) -> Self {
+        Self {
+            label: label.into(),
+            action: Some(action),
+        }
+    }
+
+    /// An item that only shows its label
+    pub fn info(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            action: None,
+        }
+    }
+}
+
+/// Opens a menu with its top left corner at `position`, in window pixels,
+/// closing any menu already open
+#[derive(Event)]
+pub struct OpenContextMenu<A: Send + Sync + 'static> {
+    pub position: Vec2,
+    pub items: Vec<ContextMenuItem<A>>,
+}
+
+/// The open menu's container
+#[derive(Component)]
+struct ContextMenu;
+
+/// An item of the open menu, and the event it sends if it has one
+#[derive(Component)]
+struct ContextMenuEntry<A>(Option<A>);
+
+pub struct ContextMenuPlugin<A>(PhantomData<A>);
+
+impl<A> Default for ContextMenuPlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: Event + Clone> Plugin for ContextMenuPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.add_event::<A>()
+            .add_event::<OpenContextMenu<A>>()
+            .add_systems(
+                Update,
+                (
+                    close_context_menu::<A>,
+                    open_context_menu::<A>,
+                    handle_context_menu_items::<A>,
+                    highlight_context_menu_items::<A>,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn despawn_menus(commands: &mut Commands, menus: &Query<Entity, With<ContextMenu>>) {
+    for entity in menus.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Closes the menu on Escape or on a click outside it
+fn close_context_menu<A: Event + Clone>(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    menus: Query<Entity, With<ContextMenu>>,
+    hovered: Query<&Interaction, Or<(With<ContextMenu>, With<ContextMenuEntry<A>>)>>,
+) {
+    if menus.is_empty() {
+        return;
+    }
+    let over_menu = hovered
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+    let clicked_outside = mouse.get_just_pressed().next().is_some() && !over_menu;
+    if keyboard.just_pressed(KeyCode::Escape) || clicked_outside {
+        despawn_menus(&mut commands, &menus);
+    }
+}
+
+fn open_context_menu<A: Event + Clone>(
+    mut commands: Commands,
+    mut events: EventReader<OpenContextMenu<A>>,
+    menus: Query<Entity, With<ContextMenu>>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    let Some(event) = events.read().last() else {
+        return;
+    };
+    despawn_menus(&mut commands, &menus);
+    if event.items.is_empty() {
+        return;
+    }
+
+    let font =
+        asset_server.load_font_with_fallback(theme.theme().mono_font_path(), &embedded_fonts);
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(event.position.x),
+                top: Val::Px(event.position.y),
+                flex_direction: FlexDirection::Column,
+                min_width: Val::Px(MIN_WIDTH),
+                padding: UiRect::all(Val::Px(theme.theme().widget_padding() / 2.0)),
+                border: UiRect::all(Val::Px(theme.theme().widget_border_width())),
+                ..default()
+            },
+            BackgroundColor(theme.theme().widget_background_color()),
+            BorderColor(theme.theme().widget_border_color()),
+            BorderRadius::all(Val::Px(theme.theme().widget_border_radius())),
+            Interaction::default(),
+            FocusPolicy::Block,
+            GlobalZIndex(i32::MAX - 1),
+            ContextMenu,
+            Name::new("ContextMenu"),
+        ))
+        .with_children(|menu| {
+            for item in &event.items {
+                let text_color = if item.action.is_some() {
+                    theme.get_ui_text_primary()
+                } else {
+                    theme.get_ui_text_secondary()
+                };
+                let mut entry = menu.spawn((
+                    Node {
+                        padding: UiRect::all(Val::Px(ITEM_PADDING)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::NONE),
+                    Interaction::default(),
+                    ContextMenuEntry(item.action.clone()),
+                ));
+                if item.action.is_some() {
+                    entry.insert(Button);
+                }
+                entry.with_child((
+                    Text::new(item.label.clone()),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: WIDGET_TEXT_FONT_SIZE,
+                        ..default()
+                    },
+                    TextColor(text_color),
+                ));
+            }
+        });
+}
+
+/// Sends the chosen item's event and closes the menu
+fn handle_context_menu_items<A: Event + Clone>(
+    mut commands: Commands,
+    entries: Query<(&Interaction, &ContextMenuEntry<A>), Changed<Interaction>>,
+    menus: Query<Entity, With<ContextMenu>>,
+    mut actions: EventWriter<A>,
+) {
+    for (interaction, ContextMenuEntry(action)) in entries.iter() {
+        if let (Interaction::Pressed, Some(action)) = (interaction, action) {
+            actions.write(action.clone());
+            despawn_menus(&mut commands, &menus);
+            return;
+        }
+    }
+}
+
+fn highlight_context_menu_items<A: Event + Clone>(
+    theme: Res<CurrentTheme>,
+    mut entries: Query<
+        (&Interaction, &ContextMenuEntry<A>, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+) {
+    for (interaction, ContextMenuEntry(action), mut background) in entries.iter_mut() {
+        let color = match interaction {
+            Interaction::Hovered | Interaction::Pressed if action.is_some() => {
+                theme.theme().button_hovered()
+            }
+            _ => Color::NONE,
+        };
+        if background.0 != color {
+            background.0 = color;
+        }
+    }
+}
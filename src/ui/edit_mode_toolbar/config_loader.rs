@@ -3,6 +3,10 @@
 //! This module automatically creates toolbar tools from the centralized configuration
 //! in toolbar_config.rs. No need to manually register tools anywhere else!
 
+use super::tool_options::{
+    ToolOption, CORNERS_OPTIONS, KNIFE_OPTIONS, MEASURE_OPTIONS, NIB_OPTIONS, PEN_OPTIONS,
+    SHAPES_OPTIONS,
+};
 use super::toolbar_config::{ToolBehavior, ToolConfig};
 use super::{EditTool, ToolId, ToolRegistry};
 use bevy::prelude::*;
@@ -43,6 +47,18 @@ impl EditTool for ConfigurableTool {
         self.config.description
     }
 
+    fn options(&self) -> &'static [ToolOption] {
+        match self.config.behavior {
+            ToolBehavior::Pen => PEN_OPTIONS,
+            ToolBehavior::Shapes => SHAPES_OPTIONS,
+            ToolBehavior::Knife => KNIFE_OPTIONS,
+            ToolBehavior::Measure => MEASURE_OPTIONS,
+            ToolBehavior::Corners => CORNERS_OPTIONS,
+            ToolBehavior::Nib => NIB_OPTIONS,
+            _ => &[],
+        }
+    }
+
     fn update(&self, commands: &mut Commands) {
         use crate::io::input::InputMode;
 
@@ -86,6 +102,24 @@ impl EditTool for ConfigurableTool {
                 // Deactivate pen mode when switching to knife
                 commands.insert_resource(crate::tools::pen::PenModeActive(false));
             }
+            ToolBehavior::Eraser => {
+                // Set input mode for eraser tool
+                commands.insert_resource(InputMode::Eraser);
+                // Deactivate pen mode when switching to eraser
+                commands.insert_resource(crate::tools::pen::PenModeActive(false));
+            }
+            ToolBehavior::Corners => {
+                // Set input mode for corners tool
+                commands.insert_resource(InputMode::Corners);
+                // Deactivate pen mode when switching to corners
+                commands.insert_resource(crate::tools::pen::PenModeActive(false));
+            }
+            ToolBehavior::Nib => {
+                // Set input mode for nib tool
+                commands.insert_resource(InputMode::Nib);
+                // Deactivate pen mode when switching to nib
+                commands.insert_resource(crate::tools::pen::PenModeActive(false));
+            }
             ToolBehavior::Hyper => {
                 // Set input mode for hyper tool
                 commands.insert_resource(InputMode::Hyper);
@@ -153,6 +187,9 @@ pub fn register_tools_from_config(mut tool_registry: ResMut<ToolRegistry>) {
     for config in enabled_tools {
         let tool = ConfigurableTool::new(config);
         tool_registry.register_tool(Box::new(tool));
+        if let Some(host) = config.group {
+            tool_registry.register_group(host, config.id);
+        }
         debug!("✅ Registered tool: {} ({})", config.name, config.id);
     }
 
@@ -0,0 +1,578 @@
+//! Corners Tool - Rounds corner points
+//!
+//! Each corner picked with this tool is rounded into an arc: the corner point
+//! gives way to two on-curve points joined by a curve, and the sides on
+//! either side are cut back to meet them. Switching to the tool picks the
+//! selected corners; clicking a point adds or removes it. The radius comes
+//! from the options bar, and a corner gets one of its own by pressing [ or ]
+//! with the pointer over it, \ going back to the bar's. The rounded outline
+//! is drawn over the glyph until Enter applies it; Escape drops the corners.
+
+use super::eraser::{contour_segments, segment_path};
+use super::tool_options::FILLET_RADII;
+use crate::core::config::preferences::step_value;
+use crate::core::config::BezySettings;
+use crate::core::state::{AppState, ContourData, PointData, PointTypeData};
+use crate::editing::outline_locks::OutlineLocks;
+use crate::editing::selection::components::{GlyphPointReference, SelectionState};
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::sort::manager::SortPointEntity;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::io::pointer::PointerInfo;
+use crate::rendering::cameras::DesignCamera;
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use crate::rendering::mesh_utils::create_line_mesh;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::systems::ui_interaction::UiHoverState;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use crate::ui::themes::CurrentTheme;
+use bevy::prelude::*;
+use bevy::render::mesh::Mesh2d;
+use bevy::sprite::{ColorMaterial, MeshMaterial2d};
+use kurbo::{
+    ParamCurve, ParamCurveArclen, ParamCurveDeriv, PathEl, PathSeg, Point, Vec2 as KurboVec2,
+};
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+
+/// How close, in screen pixels, the pointer has to be to a point to pick it
+const HIT_DISTANCE_PX: f32 = 8.0;
+
+/// Size of the marks on the corners about to be rounded
+const CORNER_MARK_SIZE: f32 = 4.0;
+
+/// Tolerance of the polyline the rounded outline is drawn as
+const PREVIEW_FLATTEN_TOLERANCE: f64 = 0.5;
+
+/// Z-order of the rounded outline, above the glyph's
+const PREVIEW_Z: f32 = 18.0;
+
+/// Accuracy of lengths measured along the sides of a corner
+const ARCLEN_ACCURACY: f64 = 1e-3;
+
+/// Angles, in radians, this close to a straight line or a cusp aren't
+/// corners
+const ANGLE_EPSILON: f64 = 1e-3;
+
+/// Sides shorter than this after rounding are dropped
+const MIN_SIDE_LENGTH: f64 = 1e-3;
+
+/// Corners about to be rounded
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct CornersToolState {
+    /// Glyph the corners are on
+    pub glyph_name: Option<String>,
+    /// Corners by contour and point index, with the radius each has instead
+    /// of the options bar's
+    pub corners: BTreeMap<(usize, usize), Option<f32>>,
+}
+
+impl CornersToolState {
+    /// The radius of each corner by point index, by contour index
+    pub fn radii(&self, radius: f32) -> BTreeMap<usize, BTreeMap<usize, f64>> {
+        let mut radii: BTreeMap<usize, BTreeMap<usize, f64>> = BTreeMap::new();
+        for ((contour_index, point_index), own) in &self.corners {
+            radii
+                .entry(*contour_index)
+                .or_default()
+                .insert(*point_index, own.unwrap_or(radius) as f64);
+        }
+        radii
+    }
+}
+
+/// Plugin for the corners tool
+pub struct CornersToolPlugin;
+
+impl Plugin for CornersToolPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CornersToolState>().add_systems(
+            Update,
+            (
+                pick_selected_corners,
+                handle_corners_input,
+                render_corners_preview,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Picks the selected corners of the active glyph when the tool is chosen
+fn pick_selected_corners(
+    current_tool: Res<CurrentTool>,
+    selection_state: Res<SelectionState>,
+    point_query: Query<&GlyphPointReference>,
+    active_sort_query: Query<&Sort, With<ActiveSort>>,
+    app_state: Option<Res<AppState>>,
+    mut corners_state: ResMut<CornersToolState>,
+) {
+    if !current_tool.is_changed() || current_tool.get_current() != Some("corners") {
+        return;
+    }
+    let (Ok(sort), Some(state)) = (active_sort_query.single(), app_state.as_deref()) else {
+        return;
+    };
+    let Some(outline) = state
+        .workspace
+        .font
+        .editing()
+        .get_glyph(&sort.glyph_name)
+        .and_then(|glyph| glyph.outline.as_ref())
+    else {
+        return;
+    };
+    let corners = selection_state
+        .selected
+        .iter()
+        .filter_map(|entity| point_query.get(*entity).ok())
+        .filter(|reference| reference.glyph_name == sort.glyph_name)
+        .filter(|reference| {
+            outline
+                .contours
+                .get(reference.contour_index)
+                .is_some_and(|contour| is_corner(contour, reference.point_index))
+        })
+        .map(|reference| ((reference.contour_index, reference.point_index), None))
+        .collect();
+    *corners_state = CornersToolState {
+        glyph_name: Some(sort.glyph_name.clone()),
+        corners,
+    };
+}
+
+/// Adds and removes corners, sets their own radii, and rounds them on Enter
+#[allow(clippy::too_many_arguments)]
+fn handle_corners_input(
+    mouse: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_tool: Res<CurrentTool>,
+    ui_hover_state: Res<UiHoverState>,
+    pointer: Res<PointerInfo>,
+    settings: Res<BezySettings>,
+    locks: Res<OutlineLocks>,
+    camera_query: Query<&Projection, With<DesignCamera>>,
+    points: Query<(&Transform, &GlyphPointReference), With<SortPointEntity>>,
+    active_sort_query: Query<&Sort, With<ActiveSort>>,
+    mut corners_state: ResMut<CornersToolState>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    let active_glyph = active_sort_query
+        .single()
+        .ok()
+        .map(|sort| sort.glyph_name.clone());
+    let other_glyph =
+        corners_state.glyph_name.is_some() && corners_state.glyph_name != active_glyph;
+    if current_tool.get_current() != Some("corners")
+        || keyboard.just_pressed(KeyCode::Escape)
+        || other_glyph
+    {
+        if *corners_state != CornersToolState::default() {
+            *corners_state = CornersToolState::default();
+        }
+        return;
+    }
+    let Some(glyph_name) = active_glyph else {
+        return;
+    };
+    let Some(outline) = app_state
+        .as_deref()
+        .and_then(|state| state.workspace.font.editing().get_glyph(&glyph_name))
+        .and_then(|glyph| glyph.outline.as_ref())
+    else {
+        return;
+    };
+
+    let zoom_scale = match camera_query.single() {
+        Ok(Projection::Orthographic(ortho)) => ortho.scale,
+        _ => 1.0,
+    };
+    let reach = HIT_DISTANCE_PX * zoom_scale;
+    let glyph_locks = locks.glyph(&glyph_name);
+    let hovered = points
+        .iter()
+        .filter(|(_, reference)| {
+            reference.glyph_name == glyph_name && glyph_locks.is_editable(reference.contour_index)
+        })
+        .map(|(transform, reference)| {
+            let distance = transform.translation.truncate().distance(pointer.world);
+            (distance, (reference.contour_index, reference.point_index))
+        })
+        .filter(|(distance, _)| *distance <= reach)
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, corner)| corner)
+        .filter(|(contour_index, point_index)| {
+            outline
+                .contours
+                .get(*contour_index)
+                .is_some_and(|contour| is_corner(contour, *point_index))
+        });
+
+    if let Some(corner) = hovered {
+        if mouse.just_pressed(MouseButton::Left) && !ui_hover_state.is_hovering_ui {
+            corners_state.glyph_name = Some(glyph_name.clone());
+            if corners_state.corners.remove(&corner).is_none() {
+                corners_state.corners.insert(corner, None);
+            }
+        }
+        let step = [(KeyCode::BracketLeft, false), (KeyCode::BracketRight, true)]
+            .into_iter()
+            .find(|(key, _)| keyboard.just_pressed(*key));
+        let radius = settings.tool_options.fillet_radius;
+        if let (Some((_, forward)), Some(own)) = (step, corners_state.corners.get(&corner)) {
+            let own = step_value(&FILLET_RADII, own.unwrap_or(radius), forward);
+            corners_state.corners.insert(corner, Some(own));
+        }
+        if keyboard.just_pressed(KeyCode::Backslash) && corners_state.corners.contains_key(&corner)
+        {
+            corners_state.corners.insert(corner, None);
+        }
+    }
+
+    let apply = keyboard.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]);
+    if !apply || corners_state.corners.is_empty() {
+        return;
+    }
+    let radii = corners_state.radii(settings.tool_options.fillet_radius);
+    let Some(outline) = app_state
+        .as_deref_mut()
+        .and_then(|state| state.workspace.font.editing_glyph_mut(&glyph_name))
+        .and_then(|glyph| glyph.outline.as_mut())
+    else {
+        return;
+    };
+    for (contour_index, corners) in &radii {
+        if let Some(contour) = outline.contours.get_mut(*contour_index) {
+            *contour = round_corners(contour, corners);
+        }
+    }
+    let rounded = corners_state.corners.len();
+    *corners_state = CornersToolState::default();
+    debug!(glyph = %glyph_name, corners = rounded, "Rounded corners");
+
+    app_state_changed.write(AppStateChanged);
+    visual_update_tracker.needs_update = true;
+}
+
+/// Draws the rounded outline of the contours with corners about to be
+/// rounded, and marks the corners
+#[allow(clippy::too_many_arguments)]
+fn render_corners_preview(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut preview_entities: Local<Vec<Entity>>,
+    mut shown: Local<Option<(CornersToolState, f32, Vec2)>>,
+    mut app_state_changed: EventReader<AppStateChanged>,
+    corners_state: Res<CornersToolState>,
+    settings: Res<BezySettings>,
+    active_sort_query: Query<(&Sort, &Transform), With<ActiveSort>>,
+    app_state: Option<Res<AppState>>,
+    camera_scale: Res<CameraResponsiveScale>,
+    theme: Res<CurrentTheme>,
+) {
+    let active_sort = active_sort_query.single().ok();
+    let sort_position = active_sort.map_or(Vec2::ZERO, |(_, transform)| {
+        transform.translation.truncate()
+    });
+    let radius = settings.tool_options.fillet_radius;
+    let view = (corners_state.clone(), radius, sort_position);
+    let outline_changed = app_state_changed.read().count() > 0;
+    if !outline_changed && shown.as_ref() == Some(&view) {
+        return;
+    }
+    *shown = Some(view);
+
+    for entity in preview_entities.drain(..) {
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.despawn();
+        }
+    }
+    let (Some((sort, _)), Some(state)) = (active_sort, app_state.as_deref()) else {
+        return;
+    };
+    if corners_state.glyph_name.as_deref() != Some(sort.glyph_name.as_str()) {
+        return;
+    }
+    let Some(outline) = state
+        .workspace
+        .font
+        .editing()
+        .get_glyph(&sort.glyph_name)
+        .and_then(|glyph| glyph.outline.as_ref())
+    else {
+        return;
+    };
+
+    let width = camera_scale.adjusted_line_width();
+    let line_material = materials.add(ColorMaterial::from(theme.theme().hover_orange_color()));
+    let mark_material = materials.add(ColorMaterial::from(theme.theme().selected_color()));
+    let mark_mesh = meshes.add(Circle::new(camera_scale.adjusted_size(CORNER_MARK_SIZE)));
+    for (contour_index, corners) in corners_state.radii(radius) {
+        let Some(contour) = outline.contours.get(contour_index) else {
+            continue;
+        };
+        let mut polyline = Vec::new();
+        let rounded = round_corners(contour, &corners).to_bezpath();
+        kurbo::flatten(rounded.iter(), PREVIEW_FLATTEN_TOLERANCE, |element| {
+            if let PathEl::MoveTo(point) | PathEl::LineTo(point) = element {
+                polyline.push(Vec2::new(point.x as f32, point.y as f32) + sort_position);
+            }
+        });
+        for pair in polyline.windows(2) {
+            if pair[0] == pair[1] {
+                continue;
+            }
+            let entity = commands
+                .spawn((
+                    Mesh2d(meshes.add(create_line_mesh(pair[0], pair[1], width))),
+                    MeshMaterial2d(line_material.clone()),
+                    Transform::from_translation(((pair[0] + pair[1]) * 0.5).extend(PREVIEW_Z)),
+                ))
+                .id();
+            preview_entities.push(entity);
+        }
+        for point in corners
+            .keys()
+            .filter_map(|index| contour.points.get(*index))
+        {
+            let position = Vec2::new(point.x as f32, point.y as f32) + sort_position;
+            let entity = commands
+                .spawn((
+                    Mesh2d(mark_mesh.clone()),
+                    MeshMaterial2d(mark_material.clone()),
+                    Transform::from_translation(position.extend(PREVIEW_Z + 1.0)),
+                ))
+                .id();
+            preview_entities.push(entity);
+        }
+    }
+}
+
+/// Where rounding a corner cuts into its sides
+struct Cut {
+    /// Index of the segment ending at the corner
+    incoming: usize,
+    /// Index of the segment starting at the corner
+    outgoing: usize,
+    /// Length cut off each side
+    distance: f64,
+}
+
+/// A segment's only curve, None for chains of quadratic curves
+fn segment_curve(contour: &ContourData, indices: &[usize]) -> Option<PathSeg> {
+    let path = segment_path(contour, indices);
+    let mut curves = path.segments();
+    let curve = curves.next()?;
+    curves.next().is_none().then_some(curve)
+}
+
+/// Direction of travel along `curve` at `t`
+pub fn tangent(curve: PathSeg, t: f64) -> KurboVec2 {
+    let direction = match curve {
+        PathSeg::Line(line) => line.p1 - line.p0,
+        PathSeg::Quad(quad) => quad.deriv().eval(t).to_vec2(),
+        PathSeg::Cubic(cubic) => cubic.deriv().eval(t).to_vec2(),
+    };
+    if direction.hypot2() > 0.0 {
+        direction.normalize()
+    } else {
+        // A handle on its point; the chord gives the direction instead
+        (curve.end() - curve.start()).normalize()
+    }
+}
+
+/// How rounding the on-curve point `index` with `radius` cuts its sides,
+/// None when it isn't a corner. A side is cut back half its length at most
+fn corner_cut(
+    contour: &ContourData,
+    segments: &[Vec<usize>],
+    index: usize,
+    radius: f64,
+) -> Option<Cut> {
+    let incoming = segments
+        .iter()
+        .position(|segment| segment.last() == Some(&index))?;
+    let outgoing = segments
+        .iter()
+        .position(|segment| segment.first() == Some(&index))?;
+    if incoming == outgoing {
+        return None;
+    }
+    let incoming_curve = segment_curve(contour, &segments[incoming])?;
+    let outgoing_curve = segment_curve(contour, &segments[outgoing])?;
+    let back = -tangent(incoming_curve, 1.0);
+    let ahead = tangent(outgoing_curve, 0.0);
+    let angle = back.dot(ahead).clamp(-1.0, 1.0).acos();
+    if !(ANGLE_EPSILON..PI - ANGLE_EPSILON).contains(&angle) {
+        return None;
+    }
+    let shortest = incoming_curve
+        .arclen(ARCLEN_ACCURACY)
+        .min(outgoing_curve.arclen(ARCLEN_ACCURACY));
+    let distance = (radius / (angle / 2.0).tan()).min(shortest / 2.0);
+    (distance > 0.0).then_some(Cut {
+        incoming,
+        outgoing,
+        distance,
+    })
+}
+
+/// Whether the point at `index` is a corner that can be rounded
+pub fn is_corner(contour: &ContourData, index: usize) -> bool {
+    corner_cut(contour, &contour_segments(contour), index, 1.0).is_some()
+}
+
+/// The points of a curve after its start: its handles, then its end
+fn curve_points(curve: PathSeg) -> Vec<PointData> {
+    match curve {
+        PathSeg::Line(line) => vec![PointData::at(line.p1, PointTypeData::Line)],
+        PathSeg::Quad(quad) => vec![
+            PointData::at(quad.p1, PointTypeData::OffCurve),
+            PointData::at(quad.p2, PointTypeData::QCurve),
+        ],
+        PathSeg::Cubic(cubic) => vec![
+            PointData::at(cubic.p1, PointTypeData::OffCurve),
+            PointData::at(cubic.p2, PointTypeData::OffCurve),
+            PointData::at(cubic.p3, PointTypeData::Curve),
+        ],
+    }
+}
+
+/// Handles of a curve leaving `from` and arriving at `to` in the given
+/// directions, following a circular arc when the directions allow one
+fn arc_handles(from: Point, leaving: KurboVec2, to: Point, arriving: KurboVec2) -> (Point, Point) {
+    let chord = (to - from).hypot();
+    let turn = leaving.dot(arriving).clamp(-1.0, 1.0).acos();
+    let length = if turn < ANGLE_EPSILON {
+        chord / 3.0
+    } else {
+        let radius = chord / (2.0 * (turn / 2.0).sin());
+        4.0 / 3.0 * (turn / 4.0).tan() * radius
+    };
+    (from + leaving * length, to - arriving * length)
+}
+
+/// The contour with the corners at the point indices of `radii` rounded to
+/// their radii. Points that aren't corners are left as they are
+pub fn round_corners(contour: &ContourData, radii: &BTreeMap<usize, f64>) -> ContourData {
+    let segments = contour_segments(contour);
+    let cuts: Vec<Cut> = radii
+        .iter()
+        .filter_map(|(index, radius)| corner_cut(contour, &segments, *index, *radius))
+        .collect();
+    if cuts.is_empty() {
+        return contour.clone();
+    }
+    let mut start_cuts = vec![0.0; segments.len()];
+    let mut end_cuts: Vec<Option<&Cut>> = vec![None; segments.len()];
+    for cut in &cuts {
+        start_cuts[cut.outgoing] = cut.distance;
+        end_cuts[cut.incoming] = Some(cut);
+    }
+
+    let mut points = Vec::new();
+    for (segment, indices) in segments.iter().enumerate() {
+        let start_cut = start_cuts[segment];
+        let end_cut = end_cuts[segment].map_or(0.0, |cut| cut.distance);
+        if start_cut == 0.0 && end_cut == 0.0 {
+            points.extend(
+                indices[1..]
+                    .iter()
+                    .map(|index| contour.points[*index].clone()),
+            );
+        } else if let Some(curve) = segment_curve(contour, indices) {
+            // What's left of a side both its corners ate is dropped
+            let length = curve.arclen(ARCLEN_ACCURACY);
+            if length - start_cut - end_cut > MIN_SIDE_LENGTH {
+                let from = curve.inv_arclen(start_cut, ARCLEN_ACCURACY);
+                let to = curve.inv_arclen(length - end_cut, ARCLEN_ACCURACY);
+                points.extend(curve_points(curve.subsegment(from..to)));
+            }
+        }
+
+        let Some(cut) = end_cuts[segment] else {
+            continue;
+        };
+        let (Some(incoming), Some(outgoing)) = (
+            segment_curve(contour, indices),
+            segment_curve(contour, &segments[cut.outgoing]),
+        ) else {
+            continue;
+        };
+        let incoming_length = incoming.arclen(ARCLEN_ACCURACY);
+        let t_in = incoming.inv_arclen(incoming_length - cut.distance, ARCLEN_ACCURACY);
+        let t_out = outgoing.inv_arclen(cut.distance, ARCLEN_ACCURACY);
+        let (from, to) = (incoming.eval(t_in), outgoing.eval(t_out));
+        let (first, second) =
+            arc_handles(from, tangent(incoming, t_in), to, tangent(outgoing, t_out));
+        points.push(PointData::at(first, PointTypeData::OffCurve));
+        points.push(PointData::at(second, PointTypeData::OffCurve));
+        points.push(PointData::at(to, PointTypeData::Curve));
+    }
+
+    if contour.is_open() {
+        points.insert(0, contour.points[0].clone());
+    } else if let Some(last) = points.pop() {
+        // A closed contour starts on the point its last segment ends on
+        points.insert(0, last);
+    }
+    ContourData { points }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contour(points: &[(f64, f64)]) -> ContourData {
+        ContourData {
+            points: points
+                .iter()
+                .map(|(x, y)| PointData::at(Point::new(*x, *y), PointTypeData::Line))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_round_corners() {
+        let square = contour(&[(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)]);
+        let rounded = round_corners(&square, &BTreeMap::from([(0, 10.0)]));
+        let coordinates: Vec<(f64, f64, PointTypeData)> = rounded
+            .points
+            .iter()
+            .map(|point| (point.x.round(), point.y.round(), point.point_type))
+            .collect();
+        assert_eq!(
+            coordinates,
+            [
+                (10.0, 0.0, PointTypeData::Curve),
+                (100.0, 0.0, PointTypeData::Line),
+                (100.0, 100.0, PointTypeData::Line),
+                (0.0, 100.0, PointTypeData::Line),
+                (0.0, 10.0, PointTypeData::Line),
+                (0.0, 4.0, PointTypeData::OffCurve),
+                (4.0, 0.0, PointTypeData::OffCurve),
+            ]
+        );
+        // The handles follow a circular arc
+        assert!((rounded.points[5].y - 4.477).abs() < 1e-3);
+
+        // Radii too large for the sides leave only the arcs
+        let all = BTreeMap::from([(0, 80.0), (1, 80.0), (2, 80.0), (3, 80.0)]);
+        let pill = round_corners(&square, &all);
+        assert_eq!(pill.points.len(), 12);
+        assert_eq!((pill.points[0].x, pill.points[0].y), (50.0, 0.0));
+        assert!(pill
+            .points
+            .iter()
+            .all(|point| point.point_type != PointTypeData::Line));
+
+        // A point on a straight side isn't a corner
+        let side = contour(&[(0.0, 0.0), (50.0, 0.0), (100.0, 0.0), (50.0, 80.0)]);
+        assert!(!is_corner(&side, 1));
+        assert!(is_corner(&side, 2));
+        assert_eq!(round_corners(&side, &BTreeMap::from([(1, 10.0)])), side);
+    }
+}
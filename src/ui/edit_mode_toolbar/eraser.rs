@@ -0,0 +1,459 @@
+//! Eraser Tool - Removes path segments
+//!
+//! Clicking a segment removes it: a closed contour opens where the segment
+//! was, and an open contour splits in two or loses its end. Dragging marks
+//! every segment the pointer crosses and removes them all on release. The
+//! knife cuts contours apart; the eraser cleans up what's left.
+
+use crate::core::state::{AppState, ContourData, PointData, PointTypeData};
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use crate::rendering::mesh_utils::create_line_mesh;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::systems::ui_interaction::UiHoverState;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use crate::ui::themes::CurrentTheme;
+use bevy::prelude::*;
+use bevy::render::mesh::Mesh2d;
+use bevy::sprite::{ColorMaterial, MeshMaterial2d};
+use kurbo::{BezPath, Line, ParamCurveNearest, PathEl, Point};
+use std::collections::BTreeSet;
+
+/// Distance from a segment the pointer erases it at, in screen pixels
+const ERASER_HIT_DISTANCE: f32 = 8.0;
+
+/// Width of the highlight over segments about to be erased, in line widths
+const ERASER_HIGHLIGHT_WIDTH: f32 = 3.0;
+
+/// Tolerance of the polyline a highlighted segment is drawn as
+const ERASER_FLATTEN_TOLERANCE: f64 = 0.5;
+
+/// Z-order of the highlight, above the glyph's outline
+const ERASER_HIGHLIGHT_Z: f32 = 18.0;
+
+/// A segment, as the index of its contour and its index in the contour
+pub type SegmentId = (usize, usize);
+
+/// Resource tracking the eraser's hover and drag
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct EraserToolState {
+    /// The segment under the pointer
+    pub hovered: Option<SegmentId>,
+    /// Segments the current drag crossed, erased on release
+    pub marked: BTreeSet<SegmentId>,
+    /// Last pointer position of the drag, relative to the active sort
+    pub last_position: Option<Vec2>,
+}
+
+/// Plugin for the eraser tool
+pub struct EraserToolPlugin;
+
+impl Plugin for EraserToolPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EraserToolState>()
+            .add_systems(Update, (handle_eraser_input, render_eraser_preview).chain());
+    }
+}
+
+/// Tracks the segment under the pointer, marks the segments a drag crosses
+/// and erases them when the button is released
+#[allow(clippy::too_many_arguments)]
+pub fn handle_eraser_input(
+    mouse: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window, With<bevy::window::PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    active_sort_query: Query<(&Sort, &Transform), With<ActiveSort>>,
+    current_tool: Res<CurrentTool>,
+    ui_hover_state: Res<UiHoverState>,
+    camera_scale: Res<CameraResponsiveScale>,
+    mut eraser_state: ResMut<EraserToolState>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    let reset = |state: &mut ResMut<EraserToolState>| {
+        if **state != EraserToolState::default() {
+            **state = EraserToolState::default();
+        }
+    };
+    if current_tool.get_current() != Some("eraser") || keyboard.just_pressed(KeyCode::Escape) {
+        reset(&mut eraser_state);
+        return;
+    }
+    let Ok((sort, sort_transform)) = active_sort_query.single() else {
+        reset(&mut eraser_state);
+        return;
+    };
+    // Read through `as_deref` so AppState only changes when segments go
+    let Some(state) = app_state.as_deref() else {
+        return;
+    };
+
+    let cursor = windows
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.single().ok())
+        .and_then(|(cursor, (camera, transform))| {
+            camera.viewport_to_world_2d(transform, cursor).ok()
+        })
+        .map(|world| world - sort_transform.translation.truncate());
+    let contours = state
+        .workspace
+        .font
+        .editing()
+        .get_glyph(&sort.glyph_name)
+        .and_then(|glyph| glyph.outline.as_ref())
+        .map(|outline| outline.contours.as_slice())
+        .unwrap_or_default();
+    let max_distance = (ERASER_HIT_DISTANCE * camera_scale.scale_factor()) as f64;
+    let point_at = |position: Vec2| Point::new(position.x as f64, position.y as f64);
+
+    let hovered = cursor
+        .filter(|_| !ui_hover_state.is_hovering_ui)
+        .and_then(|position| segment_near(contours, point_at(position), max_distance));
+    if eraser_state.hovered != hovered {
+        eraser_state.hovered = hovered;
+    }
+
+    let dragging = eraser_state.last_position.is_some();
+    if mouse.just_pressed(MouseButton::Left) && !ui_hover_state.is_hovering_ui {
+        eraser_state.marked = hovered.into_iter().collect();
+        eraser_state.last_position = cursor;
+    } else if dragging && mouse.pressed(MouseButton::Left) {
+        if let (Some(last), Some(position)) = (eraser_state.last_position, cursor) {
+            if last != position {
+                let line = Line::new(point_at(last), point_at(position));
+                let crossed = segments_crossed(contours, line);
+                eraser_state
+                    .marked
+                    .extend(crossed.into_iter().chain(hovered));
+                eraser_state.last_position = Some(position);
+            }
+        }
+    }
+
+    if !(dragging && mouse.just_released(MouseButton::Left)) {
+        return;
+    }
+    let marked = std::mem::take(&mut eraser_state.marked);
+    eraser_state.last_position = None;
+    if marked.is_empty() {
+        return;
+    }
+    let Some(outline) = app_state
+        .as_deref_mut()
+        .and_then(|state| state.workspace.font.editing_glyph_mut(&sort.glyph_name))
+        .and_then(|glyph| glyph.outline.as_mut())
+    else {
+        return;
+    };
+    outline.contours = erase_segments(&outline.contours, &marked);
+    eraser_state.hovered = None;
+    debug!(glyph = %sort.glyph_name, segments = marked.len(), "Eraser removed segments");
+
+    app_state_changed.write(AppStateChanged);
+    visual_update_tracker.needs_update = true;
+}
+
+/// Highlights the segment under the pointer and the ones the drag marked
+#[allow(clippy::too_many_arguments)]
+pub fn render_eraser_preview(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut preview_entities: Local<Vec<Entity>>,
+    eraser_state: Res<EraserToolState>,
+    active_sort_query: Query<(&Sort, &Transform), With<ActiveSort>>,
+    app_state: Option<Res<AppState>>,
+    camera_scale: Res<CameraResponsiveScale>,
+    theme: Res<CurrentTheme>,
+) {
+    if !eraser_state.is_changed() {
+        return;
+    }
+    for entity in preview_entities.drain(..) {
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.despawn();
+        }
+    }
+    let (Ok((sort, sort_transform)), Some(app_state)) = (active_sort_query.single(), app_state)
+    else {
+        return;
+    };
+    let Some(contours) = app_state
+        .workspace
+        .font
+        .editing()
+        .get_glyph(&sort.glyph_name)
+        .and_then(|glyph| glyph.outline.as_ref())
+        .map(|outline| &outline.contours)
+    else {
+        return;
+    };
+
+    let sort_position = sort_transform.translation.truncate();
+    let width = camera_scale.adjusted_line_width() * ERASER_HIGHLIGHT_WIDTH;
+    let highlights = eraser_state
+        .marked
+        .iter()
+        .map(|segment| (*segment, theme.theme().knife_line_color()))
+        .chain(
+            eraser_state
+                .hovered
+                .filter(|segment| !eraser_state.marked.contains(segment))
+                .map(|segment| (segment, theme.theme().hover_orange_color())),
+        );
+    for ((contour_index, segment_index), color) in highlights {
+        let Some(contour) = contours.get(contour_index) else {
+            continue;
+        };
+        let Some(indices) = contour_segments(contour).into_iter().nth(segment_index) else {
+            continue;
+        };
+        let material = materials.add(ColorMaterial::from(color));
+        let mut polyline = Vec::new();
+        let segment = segment_path(contour, &indices);
+        kurbo::flatten(segment.iter(), ERASER_FLATTEN_TOLERANCE, |element| {
+            if let PathEl::MoveTo(point) | PathEl::LineTo(point) = element {
+                polyline.push(Vec2::new(point.x as f32, point.y as f32) + sort_position);
+            }
+        });
+        for pair in polyline.windows(2) {
+            if pair[0] == pair[1] {
+                continue;
+            }
+            let entity = commands
+                .spawn((
+                    Mesh2d(meshes.add(create_line_mesh(pair[0], pair[1], width))),
+                    MeshMaterial2d(material.clone()),
+                    Transform::from_translation(
+                        ((pair[0] + pair[1]) * 0.5).extend(ERASER_HIGHLIGHT_Z),
+                    ),
+                ))
+                .id();
+            preview_entities.push(entity);
+        }
+    }
+}
+
+/// The segments of a contour, each as the indices of its points from the
+/// on-curve point starting it to the one ending it
+pub fn contour_segments(contour: &ContourData) -> Vec<Vec<usize>> {
+    let count = contour.points.len();
+    let on_curve: Vec<usize> = (0..count)
+        .filter(|index| contour.points[*index].point_type != PointTypeData::OffCurve)
+        .collect();
+    let segment_count = if contour.is_open() {
+        on_curve.len().saturating_sub(1)
+    } else {
+        on_curve.len()
+    };
+    (0..segment_count)
+        .map(|segment| {
+            let start = on_curve[segment];
+            let end = on_curve[(segment + 1) % on_curve.len()];
+            let mut indices = vec![start];
+            let mut index = start;
+            loop {
+                index = (index + 1) % count;
+                indices.push(index);
+                if index == end {
+                    break indices;
+                }
+            }
+        })
+        .collect()
+}
+
+/// The curve of a segment given by `contour_segments`
+pub fn segment_path(contour: &ContourData, indices: &[usize]) -> BezPath {
+    let point = |index: &usize| {
+        let point = &contour.points[*index];
+        Point::new(point.x, point.y)
+    };
+    let (Some(first), Some(last)) = (indices.first(), indices.last()) else {
+        return BezPath::new();
+    };
+    let (start, end) = (point(first), point(last));
+    let off_curves: Vec<Point> = indices[1..indices.len() - 1].iter().map(point).collect();
+
+    let mut path = BezPath::new();
+    path.move_to(start);
+    let is_cubic = contour.points[*last].point_type == PointTypeData::Curve;
+    match off_curves.as_slice() {
+        [] => path.line_to(end),
+        [.., c1, c2] if is_cubic => path.curve_to(*c1, *c2, end),
+        _ => {
+            // Quadratic off-curves imply on-curve points halfway between them
+            for (index, control) in off_curves.iter().enumerate() {
+                let to = off_curves
+                    .get(index + 1)
+                    .map_or(end, |next| control.midpoint(*next));
+                path.quad_to(*control, to);
+            }
+        }
+    }
+    path
+}
+
+/// The segment nearest to `point`, if it's closer than `max_distance`
+pub fn segment_near(
+    contours: &[ContourData],
+    point: Point,
+    max_distance: f64,
+) -> Option<SegmentId> {
+    let mut nearest: Option<(f64, SegmentId)> = None;
+    for (contour_index, contour) in contours.iter().enumerate() {
+        for (segment_index, indices) in contour_segments(contour).iter().enumerate() {
+            let distance_sq = segment_path(contour, indices)
+                .segments()
+                .map(|segment| segment.nearest(point, 1e-3).distance_sq)
+                .fold(f64::INFINITY, f64::min);
+            if distance_sq <= max_distance * max_distance
+                && nearest.is_none_or(|(nearest_sq, _)| distance_sq < nearest_sq)
+            {
+                nearest = Some((distance_sq, (contour_index, segment_index)));
+            }
+        }
+    }
+    nearest.map(|(_, segment)| segment)
+}
+
+/// Every segment `line` crosses
+pub fn segments_crossed(contours: &[ContourData], line: Line) -> Vec<SegmentId> {
+    let mut crossed = Vec::new();
+    for (contour_index, contour) in contours.iter().enumerate() {
+        for (segment_index, indices) in contour_segments(contour).iter().enumerate() {
+            let crosses = segment_path(contour, indices)
+                .segments()
+                .any(|segment| !segment.intersect_line(line).is_empty());
+            if crosses {
+                crossed.push((contour_index, segment_index));
+            }
+        }
+    }
+    crossed
+}
+
+/// The contours left after removing `erased` segments. A closed contour
+/// opens where its first erased segment was; the segments kept between
+/// erased ones become open contours of their own
+pub fn erase_segments(contours: &[ContourData], erased: &BTreeSet<SegmentId>) -> Vec<ContourData> {
+    let mut result = Vec::new();
+    for (contour_index, contour) in contours.iter().enumerate() {
+        let segments = contour_segments(contour);
+        let removed: BTreeSet<usize> = erased
+            .range((contour_index, 0)..=(contour_index, usize::MAX))
+            .map(|(_, segment)| *segment)
+            .filter(|segment| *segment < segments.len())
+            .collect();
+        let Some(first_removed) = removed.first() else {
+            result.push(contour.clone());
+            continue;
+        };
+
+        // Walking a closed contour from the segment after an erased one
+        // keeps every run of kept segments in one piece
+        let first = if contour.is_open() {
+            0
+        } else {
+            first_removed + 1
+        };
+        let mut run: Vec<PointData> = Vec::new();
+        for step in 0..segments.len() {
+            let segment = (first + step) % segments.len();
+            if removed.contains(&segment) {
+                if !run.is_empty() {
+                    result.push(ContourData {
+                        points: std::mem::take(&mut run),
+                    });
+                }
+                continue;
+            }
+            let indices = &segments[segment];
+            if run.is_empty() {
+                run.push(PointData {
+                    point_type: PointTypeData::Move,
+                    ..contour.points[indices[0]].clone()
+                });
+            }
+            run.extend(
+                indices[1..]
+                    .iter()
+                    .map(|index| contour.points[*index].clone()),
+            );
+        }
+        if !run.is_empty() {
+            result.push(ContourData { points: run });
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contour(points: &[(f64, f64, PointTypeData)]) -> ContourData {
+        ContourData {
+            points: points
+                .iter()
+                .map(|(x, y, point_type)| PointData {
+                    x: *x,
+                    y: *y,
+                    point_type: *point_type,
+                })
+                .collect(),
+        }
+    }
+
+    fn coordinates(contour: &ContourData) -> Vec<(f64, f64)> {
+        contour
+            .points
+            .iter()
+            .map(|point| (point.x, point.y))
+            .collect()
+    }
+
+    #[test]
+    fn test_erase_segments() {
+        use PointTypeData::{Line, Move};
+        let square = contour(&[
+            (0.0, 0.0, Line),
+            (100.0, 0.0, Line),
+            (100.0, 100.0, Line),
+            (0.0, 100.0, Line),
+        ]);
+        assert_eq!(contour_segments(&square).len(), 4);
+        assert_eq!(
+            segment_near(std::slice::from_ref(&square), Point::new(50.0, 3.0), 5.0),
+            Some((0, 0))
+        );
+
+        // Erasing a closed contour's segment opens it there
+        let opened = erase_segments(std::slice::from_ref(&square), &BTreeSet::from([(0, 0)]));
+        assert_eq!(opened.len(), 1);
+        assert_eq!(opened[0].points[0].point_type, Move);
+        assert_eq!(
+            coordinates(&opened[0]),
+            [(100.0, 0.0), (100.0, 100.0), (0.0, 100.0), (0.0, 0.0)]
+        );
+
+        // Erasing an open contour's middle segment splits it in two
+        let split = erase_segments(&opened, &BTreeSet::from([(0, 1)]));
+        assert_eq!(split.len(), 2);
+        assert_eq!(coordinates(&split[0]), [(100.0, 0.0), (100.0, 100.0)]);
+        assert_eq!(coordinates(&split[1]), [(0.0, 100.0), (0.0, 0.0)]);
+        assert!(split.iter().all(ContourData::is_open));
+
+        // A stroke across the square crosses two of its segments
+        let line = kurbo::Line::new((50.0, -10.0), (50.0, 110.0));
+        let crossed = segments_crossed(std::slice::from_ref(&square), line);
+        assert_eq!(crossed, [(0, 0), (0, 2)]);
+        let halves = erase_segments(&[square], &crossed.into_iter().collect());
+        assert_eq!(halves.len(), 2);
+    }
+}
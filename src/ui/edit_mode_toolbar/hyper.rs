@@ -339,7 +339,7 @@ fn create_hyper_contour(
     }
 
     // Add the contour to the glyph
-    if let Some(glyph_data) = app_state.workspace.font.glyphs.get_mut(&glyph_name) {
+    if let Some(glyph_data) = app_state.workspace.font.editing_glyph_mut(&glyph_name) {
         if glyph_data.outline.is_none() {
             glyph_data.outline = Some(crate::core::state::OutlineData {
                 contours: Vec::new(),
@@ -2,9 +2,13 @@
 //!
 //! This module provides a single system that handles all keyboard shortcuts
 //! for tool switching, ensuring consistency and preventing conflicts.
+//! Shortcuts set in the preferences replace the toolbar's.
 
 use bevy::prelude::*;
+use crate::core::config::BezySettings;
+use crate::editing::text_field_focus::TextFieldFocus;
 use crate::tools::{SwitchToolEvent, ToolId, ToolState};
+use crate::ui::panes::preferences_pane::PreferencesDialogState;
 use super::toolbar_config::TOOLBAR_TOOLS;
 
 /// System to handle keyboard shortcuts for all tools
@@ -14,14 +18,27 @@ use super::toolbar_config::TOOLBAR_TOOLS;
 pub fn handle_tool_keyboard_shortcuts(
     keyboard: Res<ButtonInput<KeyCode>>,
     tool_state: Res<ToolState>,
+    settings: Res<BezySettings>,
     mut switch_events: EventWriter<SwitchToolEvent>,
     text_mode_active: Option<Res<super::text::TextModeActive>>,
+    preferences: Option<Res<PreferencesDialogState>>,
+    text_field_focus: Option<Res<TextFieldFocus>>,
 ) {
     // Skip if text mode is active (text tool needs raw keyboard input)
     if text_mode_active.map(|t| t.0).unwrap_or(false) {
         return;
     }
 
+    // Skip while the preferences are waiting for a new shortcut
+    if preferences.is_some_and(|p| p.capturing.is_some()) {
+        return;
+    }
+
+    // Skip while a prompt's text field is typed into
+    if text_field_focus.is_some_and(|focus| focus.is_focused()) {
+        return;
+    }
+
     // Skip if any modifier keys are held (Ctrl, Cmd, Alt, Shift)
     // This prevents tool shortcuts from triggering when using Ctrl+S to save, etc.
     let modifier_held = keyboard.pressed(KeyCode::ControlLeft)
@@ -39,7 +56,7 @@ pub fn handle_tool_keyboard_shortcuts(
 
     // Check each tool's shortcut from the config
     for tool_config in TOOLBAR_TOOLS {
-        if let Some(shortcut_char) = tool_config.shortcut {
+        if let Some(shortcut_char) = settings.tool_shortcut(tool_config.id, tool_config.shortcut) {
             // Convert char to KeyCode
             let keycode = char_to_keycode(shortcut_char);
 
@@ -68,7 +85,7 @@ pub fn handle_tool_keyboard_shortcuts(
 }
 
 /// Convert a char to its corresponding KeyCode
-fn char_to_keycode(c: char) -> Option<KeyCode> {
+pub(crate) fn char_to_keycode(c: char) -> Option<KeyCode> {
     match c.to_ascii_lowercase() {
         'a' => Some(KeyCode::KeyA),
         'b' => Some(KeyCode::KeyB),
@@ -112,6 +129,9 @@ fn behavior_to_tool_id(behavior: &super::toolbar_config::ToolBehavior) -> Option
         ToolBehavior::Text => Some(ToolId::Text),
         ToolBehavior::Shapes => Some(ToolId::Shapes),
         ToolBehavior::Knife => Some(ToolId::Knife),
+        ToolBehavior::Eraser => Some(ToolId::Eraser),
+        ToolBehavior::Corners => Some(ToolId::Corners),
+        ToolBehavior::Nib => Some(ToolId::Nib),
         ToolBehavior::Hyper => Some(ToolId::Hyper),
         ToolBehavior::Measure => Some(ToolId::Measure),
         ToolBehavior::Metaballs => Some(ToolId::Metaballs),
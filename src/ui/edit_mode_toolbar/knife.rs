@@ -4,6 +4,13 @@
 //! This tool allows users to cut paths by drawing a line across them.
 //! The tool shows a preview of the cutting line and intersection points.
 
+use crate::core::config::BezySettings;
+use crate::core::state::{AppState, ContourData, GlyphNavigation};
+use crate::editing::selection::events::AppStateChanged;
+use crate::geometry::utilities::{outline_point_positions, snap_to_nearest_point};
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::ui::edit_mode_toolbar::tool_options::{KnifeCut, ToolOption, KNIFE_OPTIONS};
 use crate::ui::edit_mode_toolbar::{EditTool, ToolRegistry};
 use crate::ui::theme::*;
 use bevy::prelude::*;
@@ -43,6 +50,10 @@ impl EditTool for KnifeTool {
         "Cut and slice paths"
     }
 
+    fn options(&self) -> &'static [ToolOption] {
+        KNIFE_OPTIONS
+    }
+
     fn update(&self, commands: &mut Commands) {
         debug!(
             "🔪 KNIFE_TOOL: update() called - setting knife mode active and input mode to Knife"
@@ -127,9 +138,10 @@ impl Plugin for KnifeToolPlugin {
                 Update,
                 (
                     manage_knife_mode_state,
+                    // Cuts with the line before the release resets the gesture
+                    handle_knife_cutting.before(handle_knife_mouse_events),
                     handle_knife_mouse_events.after(manage_knife_mode_state),
                     render_knife_preview.after(handle_knife_mouse_events),
-                    handle_fontir_knife_cutting,
                 ),
             );
     }
@@ -154,6 +166,9 @@ pub fn handle_knife_mouse_events(
         (Entity, &crate::editing::sort::Sort, &Transform),
         With<crate::editing::sort::ActiveSort>,
     >,
+    app_state: Option<Res<AppState>>,
+    settings: Res<BezySettings>,
+    camera_scale: Res<CameraResponsiveScale>,
 ) {
     // Check if knife mode is active
     let knife_is_active = if let Some(knife_mode) = knife_mode {
@@ -170,7 +185,7 @@ pub fn handle_knife_mouse_events(
     };
 
     // Early exit if knife tool is not active, no active sort, or other conditions
-    let Some((_sort_entity, _sort, sort_transform)) = active_sort else {
+    let Some((_sort_entity, sort, sort_transform)) = active_sort else {
         if knife_is_active {
             // Only show this message when knife tool is actually trying to be used
             if mouse_button_input.just_pressed(MouseButton::Left) {
@@ -199,8 +214,17 @@ pub fn handle_knife_mouse_events(
 
     // Convert cursor position to world coordinates, then to sort-relative coordinates
     if let Ok(world_position) = camera.viewport_to_world_2d(camera_transform, cursor_position) {
-        // Convert to sort-relative coordinates
-        let sort_relative_position = world_position - sort_position;
+        // Convert to sort-relative coordinates, onto the glyph's point under
+        // the cursor if there is one so cuts can go exactly through points
+        let outline = app_state.as_ref().and_then(|state| {
+            let glyph = state.workspace.font.editing().get_glyph(&sort.glyph_name)?;
+            glyph.outline.as_ref()
+        });
+        let sort_relative_position = snap_to_nearest_point(
+            world_position - sort_position,
+            outline.into_iter().flat_map(outline_point_positions),
+            camera_scale.hit_tolerance(&settings),
+        );
 
         // Update shift lock state
         knife_state.shift_locked =
@@ -214,8 +238,10 @@ pub fn handle_knife_mouse_events(
             };
             knife_state.intersections.clear();
             debug!(
-                "🔪 KNIFE_DEBUG: Started cutting at sort-relative {:?}, world {:?}, sort pos {:?}",
-                sort_relative_position, world_position, sort_position
+                start = ?sort_relative_position,
+                world = ?world_position,
+                sort = ?sort_position,
+                "Knife cut started"
             );
         }
 
@@ -227,17 +253,14 @@ pub fn handle_knife_mouse_events(
             };
 
             // Intersections will be calculated by the render system
-            debug!(
-                "🔪 KNIFE_DEBUG: Dragging to sort-relative {:?}",
-                sort_relative_position
-            );
+            trace!(current = ?sort_relative_position, "Knife cut dragged");
         }
 
         // Handle mouse button release
         if mouse_button_input.just_released(MouseButton::Left) {
             if let Some((_start, _end)) = knife_state.get_cutting_line() {
-                // The actual cutting is handled by handle_fontir_knife_cutting system
-                debug!("🔪 KNIFE_DEBUG: Mouse released - cutting will be handled by FontIR system");
+                // The actual cutting is handled by the handle_knife_cutting system
+                debug!("Knife cut released");
             }
 
             // Reset state
@@ -393,10 +416,7 @@ pub fn render_knife_preview(
         let world_start = start + sort_position;
         let world_end = end + sort_position;
 
-        debug!(
-            "🔪 RENDER_KNIFE_PREVIEW: Drawing cutting line from sort-relative {:?}-{:?} to world {:?}-{:?}",
-            start, end, world_start, world_end
-        );
+        trace!(?start, ?end, ?world_start, "Drawing knife line");
         let line_color = theme.theme().knife_line_color();
 
         // Create dashed line effect with a single batched mesh for performance
@@ -448,15 +468,7 @@ pub fn render_knife_preview(
         );
         knife_entities.push(end_entity);
 
-        debug!(
-            "🔪 RENDER_KNIFE_PREVIEW: Created {} visual entities for knife preview",
-            knife_entities.len()
-        );
-    } else {
-        // Log when we're not drawing
-        if matches!(knife_state.gesture, KnifeGestureState::Ready) {
-            debug!("🔪 RENDER_KNIFE_PREVIEW: No cutting line to draw (Ready state)");
-        }
+        trace!(entities = knife_entities.len(), "Knife preview drawn");
     }
 
     // TODO: Re-enable after FontIR removal - calculate and draw intersection points
@@ -483,28 +495,88 @@ fn calculate_real_intersections(
     intersections
 }
 
-/// System to handle actual path cutting with FontIR integration
+/// Cuts the current glyph's contours along the knife line when the button
+/// is released, keeping or deleting the smaller pieces as the knife's cut
+/// option says
 #[allow(clippy::too_many_arguments)]
-pub fn handle_fontir_knife_cutting(
-    mut knife_state: ResMut<KnifeToolState>, // Use main knife state instead of consumer
-    mut app_state_changed: EventWriter<crate::editing::selection::events::AppStateChanged>,
-    _keyboard: Res<ButtonInput<KeyCode>>,
+pub fn handle_knife_cutting(
+    mut knife_state: ResMut<KnifeToolState>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
     mouse_input: Res<ButtonInput<MouseButton>>,
+    knife_mode: Option<Res<KnifeModeActive>>,
+    settings: Res<BezySettings>,
+    glyph_navigation: Res<GlyphNavigation>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
 ) {
-    // TODO: Re-enable after FontIR removal - handle cutting gesture
-    // FontIR removed - cutting logic needs to be reimplemented
-    if mouse_input.just_released(MouseButton::Left) {
-        if let Some((_start, _end)) = knife_state.get_cutting_line() {
-            // Reset the knife gesture state
-            knife_state.gesture = KnifeGestureState::Ready;
-            knife_state.intersections.clear();
-            debug!("🔪 KNIFE CUTTING: Gesture state reset (cutting disabled during FontIR removal)");
-        }
+    if !mouse_input.just_released(MouseButton::Left) || !knife_mode.is_some_and(|mode| mode.0) {
+        return;
     }
+    let Some((start, end)) = knife_state.get_cutting_line() else {
+        return;
+    };
+    knife_state.gesture = KnifeGestureState::Ready;
+    knife_state.intersections.clear();
+
+    let Some(app_state) = app_state.as_deref_mut() else {
+        return;
+    };
+    let Some(glyph_name) = glyph_navigation.find_glyph(app_state) else {
+        return;
+    };
+    let Some(outline) = app_state
+        .workspace
+        .font
+        .editing_glyph_mut(&glyph_name)
+        .and_then(|glyph| glyph.outline.as_mut())
+    else {
+        return;
+    };
+
+    let line = kurbo::Line::new(
+        (start.x as f64, start.y as f64),
+        (end.x as f64, end.y as f64),
+    );
+    let cut = settings.tool_options.knife_cut;
+    let Some(pieces) = cut_contours(&outline.to_bezpaths(), &line, cut) else {
+        debug!("Knife line crosses no contour twice");
+        return;
+    };
+    outline.contours = pieces.iter().flat_map(ContourData::from_bezpath).collect();
+    debug!(glyph = %glyph_name, ?cut, "Knife cut applied");
+
+    app_state_changed.write(AppStateChanged);
+    visual_update_tracker.needs_update = true;
 }
 
-// TODO: Re-enable after FontIR removal - perform cutting with FontIR
-// FontIR removed - perform_fontir_cut function disabled
+/// The contours after cutting the ones `line` crosses at least twice; with
+/// `KnifeCut::DeleteSmaller`, each cut contour loses its piece with the
+/// smallest area. None when the line cuts nothing
+fn cut_contours(contours: &[BezPath], line: &kurbo::Line, cut: KnifeCut) -> Option<Vec<BezPath>> {
+    let mut result = Vec::new();
+    let mut cut_any = false;
+    for contour in contours {
+        let hits = find_path_intersections_with_parameters(contour, line);
+        let mut pieces = slice_path_at_hits(contour, &hits);
+        if pieces.len() < 2 {
+            result.push(contour.clone());
+            continue;
+        }
+        cut_any = true;
+        if cut == KnifeCut::DeleteSmaller {
+            let smallest = pieces
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.area().abs().total_cmp(&b.area().abs()))
+                .map(|(index, _)| index);
+            if let Some(smallest) = smallest {
+                pieces.remove(smallest);
+            }
+        }
+        result.extend(pieces);
+    }
+    cut_any.then_some(result)
+}
 
 /// Perform multi-contour cutting using Runebender's unified approach
 /// Treats all segments from all contours as one unified sequence
@@ -1285,86 +1357,56 @@ struct Hit {
 
 /// Find intersections with parameter information for accurate slicing
 fn find_path_intersections_with_parameters(path: &BezPath, cutting_line: &kurbo::Line) -> Vec<Hit> {
+    let _span = debug_span!(
+        "knife_intersections",
+        elements = path.elements().len(),
+        from = ?cutting_line.p0,
+        to = ?cutting_line.p1
+    )
+    .entered();
     let mut hits = Vec::new();
     let mut current_point = Point::ZERO;
     let mut segment_idx = 0;
 
-    debug!(
-        "🔍 INTERSECTION_SEARCH: Starting search through {} path elements",
-        path.elements().len()
-    );
-    debug!(
-        "🔍 CUTTING_LINE: from {:?} to {:?}",
-        cutting_line.p0, cutting_line.p1
-    );
-
     for (element_idx, element) in path.elements().iter().enumerate() {
         match element {
             PathEl::MoveTo(pt) => {
                 current_point = *pt;
-                debug!("🔍 ELEMENT_{}: MoveTo({:?})", element_idx, pt);
             }
             PathEl::LineTo(end) => {
                 let segment = kurbo::Line::new(current_point, *end);
-                debug!(
-                    "🔍 ELEMENT_{}: LineTo from {:?} to {:?}",
-                    element_idx, current_point, end
-                );
-
                 if let Some(intersection) =
                     line_line_intersection_with_parameter(&segment, cutting_line)
                 {
-                    debug!(
-                        "🔍 LINE_INTERSECTION: Found at {:?}, t={:.3}",
-                        intersection.0, intersection.1
-                    );
+                    let (point, t) = intersection;
+                    trace!(element = element_idx, ?point, t, "Line hit");
                     hits.push(Hit {
                         point: intersection.0,
                         t: intersection.1,
                         segment_idx,
                     });
-                } else {
-                    debug!("🔍 LINE_NO_INTERSECTION: Line segment does not intersect cutting line");
                 }
                 current_point = *end;
                 segment_idx += 1;
             }
             PathEl::CurveTo(c1, c2, end) => {
                 let curve = kurbo::CubicBez::new(current_point, *c1, *c2, *end);
-                debug!(
-                    "🔍 ELEMENT_{}: CurveTo from {:?} via {:?},{:?} to {:?}",
-                    element_idx, current_point, c1, c2, end
-                );
-
                 let curve_hits =
                     curve_line_intersections_with_parameters(&curve, cutting_line, segment_idx);
-                debug!(
-                    "🔍 CURVE_INTERSECTIONS: Found {} intersections",
-                    curve_hits.len()
-                );
-
-                for (i, hit) in curve_hits.iter().enumerate() {
-                    debug!("🔍 CURVE_HIT_{}: point={:?}, t={:.3}", i, hit.point, hit.t);
+                for hit in &curve_hits {
+                    trace!(element = element_idx, point = ?hit.point, t = hit.t, "Curve hit");
                 }
-
                 hits.extend(curve_hits);
                 current_point = *end;
                 segment_idx += 1;
             }
             PathEl::QuadTo(c, end) => {
                 let curve = kurbo::QuadBez::new(current_point, *c, *end);
-                debug!(
-                    "🔍 ELEMENT_{}: QuadTo from {:?} via {:?} to {:?}",
-                    element_idx, current_point, c, end
-                );
-
                 let curve_hits =
                     quad_line_intersections_with_parameters(&curve, cutting_line, segment_idx);
-                debug!(
-                    "🔍 QUAD_INTERSECTIONS: Found {} intersections",
-                    curve_hits.len()
-                );
-
+                for hit in &curve_hits {
+                    trace!(element = element_idx, point = ?hit.point, t = hit.t, "Quad hit");
+                }
                 hits.extend(curve_hits);
                 current_point = *end;
                 segment_idx += 1;
@@ -1372,25 +1414,16 @@ fn find_path_intersections_with_parameters(path: &BezPath, cutting_line: &kurbo:
             PathEl::ClosePath => {
                 if let Some(start_point) = get_path_start_point_inline(path) {
                     let segment = kurbo::Line::new(current_point, start_point);
-                    debug!(
-                        "🔍 ELEMENT_{}: ClosePath from {:?} to {:?}",
-                        element_idx, current_point, start_point
-                    );
-
                     if let Some(intersection) =
                         line_line_intersection_with_parameter(&segment, cutting_line)
                     {
-                        debug!(
-                            "🔍 CLOSE_INTERSECTION: Found at {:?}, t={:.3}",
-                            intersection.0, intersection.1
-                        );
+                        let (point, t) = intersection;
+                        trace!(element = element_idx, ?point, t, "Closing line hit");
                         hits.push(Hit {
                             point: intersection.0,
                             t: intersection.1,
                             segment_idx,
                         });
-                    } else {
-                        debug!("🔍 CLOSE_NO_INTERSECTION: ClosePath segment does not intersect cutting line");
                     }
                 }
                 segment_idx += 1;
@@ -1398,21 +1431,9 @@ fn find_path_intersections_with_parameters(path: &BezPath, cutting_line: &kurbo:
         }
     }
 
-    debug!(
-        "🔍 RAW_HITS: Found {} raw intersections before deduplication",
-        hits.len()
-    );
-
     // Remove duplicate hits with better tolerance
-    let original_count = hits.len();
+    let raw_hits = hits.len();
     hits.dedup_by(|a, b| a.point.distance(b.point) < 1.0);
-    if hits.len() < original_count {
-        debug!(
-            "🔍 DEDUPLICATION: Removed {} duplicates, {} remaining",
-            original_count - hits.len(),
-            hits.len()
-        );
-    }
 
     // Sort hits by their position along the knife cutting line for better ordering
     hits.sort_by(|a, b| {
@@ -1425,17 +1446,7 @@ fn find_path_intersections_with_parameters(path: &BezPath, cutting_line: &kurbo:
             .unwrap_or(std::cmp::Ordering::Equal)
     });
 
-    debug!(
-        "🔍 FINAL_HITS: Returning {} sorted intersections",
-        hits.len()
-    );
-    for (i, hit) in hits.iter().enumerate() {
-        debug!(
-            "🔍 FINAL_HIT_{}: point={:?}, t={:.3}, segment_idx={}",
-            i, hit.point, hit.t, hit.segment_idx
-        );
-    }
-
+    debug!(raw_hits, hits = hits.len(), "Knife intersections found");
     hits
 }
 
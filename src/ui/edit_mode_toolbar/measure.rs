@@ -1,4 +1,11 @@
 #![allow(unreachable_code, dead_code)]
+use crate::core::config::BezySettings;
+use crate::core::state::AppState;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::geometry::utilities::{outline_point_positions, snap_to_nearest_point};
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::systems::input_consumer::{process_input_events, MeasureGestureState};
+use crate::ui::edit_mode_toolbar::tool_options::{ToolOption, MEASURE_OPTIONS};
 use crate::ui::edit_mode_toolbar::{EditTool, ToolRegistry};
 use bevy::prelude::*;
 use kurbo::ParamCurve;
@@ -34,6 +41,10 @@ impl EditTool for MeasureTool {
         "Measure distances and dimensions"
     }
 
+    fn options(&self) -> &'static [ToolOption] {
+        MEASURE_OPTIONS
+    }
+
     fn update(&self, commands: &mut Commands) {
         debug!("📏 MEASURE_TOOL: update() called - setting measure mode active and input mode to Measure");
         commands.insert_resource(MeasureModeActive(true));
@@ -61,6 +72,9 @@ impl Plugin for MeasureToolPlugin {
                 (
                     manage_measure_mode_state,
                     update_measure_shift_state, // Add shift key detection
+                    snap_measure_to_points
+                        .after(process_input_events)
+                        .before(render_measure_preview),
                     render_measure_preview.after(manage_measure_mode_state),
                 ),
             );
@@ -91,6 +105,41 @@ pub fn manage_measure_mode_state(
     }
 }
 
+/// Moves the ends of the measure line onto the active glyph's points they
+/// are within the click tolerance of, to measure exactly between points
+pub fn snap_measure_to_points(
+    mut measure_consumer: ResMut<crate::systems::input_consumer::MeasureInputConsumer>,
+    active_sort_query: Query<(&Sort, &Transform), With<ActiveSort>>,
+    app_state: Option<Res<AppState>>,
+    settings: Res<BezySettings>,
+    camera_scale: Res<CameraResponsiveScale>,
+) {
+    let MeasureGestureState::Measuring { start, current } = measure_consumer.gesture else {
+        return;
+    };
+    let Ok((sort, sort_transform)) = active_sort_query.single() else {
+        return;
+    };
+    let Some(outline) = app_state.as_ref().and_then(|state| {
+        let glyph = state.workspace.font.editing().get_glyph(&sort.glyph_name)?;
+        glyph.outline.as_ref()
+    }) else {
+        return;
+    };
+    let sort_position = sort_transform.translation.truncate();
+    let points = || outline_point_positions(outline).map(|point| point + sort_position);
+    let tolerance = camera_scale.hit_tolerance(&settings);
+    let snapped = MeasureGestureState::Measuring {
+        start: snap_to_nearest_point(start, points(), tolerance),
+        current: snap_to_nearest_point(current, points(), tolerance),
+    };
+    // Only write when an end moves so the consumer isn't marked changed
+    // every frame
+    if snapped != measure_consumer.gesture {
+        measure_consumer.gesture = snapped;
+    }
+}
+
 /// System to update shift key state for axis-aligned measurements
 pub fn update_measure_shift_state(
     keyboard: Res<ButtonInput<KeyCode>>,
@@ -32,6 +32,7 @@ Status:
 use crate::core::config::BezySettings;
 use crate::core::state::{AppState, GlyphNavigation};
 use crate::editing::selection::systems::AppStateChanged;
+use crate::io::shortcuts::Shortcut;
 use crate::ui::edit_mode_toolbar::{EditTool, ToolId, ToolRegistry};
 use crate::ui::themes::CurrentTheme;
 use bevy::prelude::*;
@@ -252,8 +253,8 @@ pub fn handle_metaballs_keyboard_shortcuts(
         return;
     }
 
-    // Ctrl+C: Convert metaballs to curves
-    if keyboard_input.pressed(KeyCode::ControlLeft) && keyboard_input.just_pressed(KeyCode::KeyC) {
+    // Cmd/Ctrl+Shift+C: Convert metaballs to curves
+    if Shortcut::ConvertMetaballs.just_pressed(&keyboard_input) {
         debug!("METABALLS TOOL: Converting metaballs to curves");
         convert_metaballs_to_curves(
             &mut metaball_gizmos,
@@ -475,7 +476,7 @@ fn convert_metaballs_to_curves(
     let curves_count = curves.len();
 
     // Add curves to the glyph
-    if let Some(glyph_data) = app_state.workspace.font.glyphs.get_mut(&glyph_name) {
+    if let Some(glyph_data) = app_state.workspace.font.editing_glyph_mut(&glyph_name) {
         if glyph_data.outline.is_none() {
             glyph_data.outline = Some(crate::core::state::OutlineData {
                 contours: Vec::new(),
@@ -64,6 +64,8 @@
 //!
 //! - **Dynamic Registration**: Tools register themselves at runtime
 //! - **Configurable Ordering**: Control the order tools appear in the toolbar
+//! - **Tool Groups**: Tools can share a toolbar button, the others revealed by
+//!   a long press on it; users reorder, hide and group tools themselves
 //! - **Temporary Modes**: Support for temporary mode activation (e.g., holding
 //!   spacebar for pan)
 //! - **State Management**: Proper enter/exit lifecycle for each tool
@@ -73,22 +75,29 @@
 
 use bevy::prelude::*;
 use std::collections::HashMap;
+use toolbar_layout::{ToolbarLayout, ToolbarSlot};
 
 // NEW: Centralized configuration system
 pub mod config_loader;
 pub mod toolbar_config;
 
+pub mod corners;
+pub mod eraser;
 mod hyper;
 pub mod keyboard_shortcuts;
 pub mod keyboard_utils;
 pub mod knife;
 mod measure;
 mod metaballs;
+pub mod nib;
 mod pan;
 pub mod pen;
 pub mod select;
 mod shapes;
 pub mod text;
+pub mod tool_groups;
+pub mod tool_options;
+pub mod toolbar_layout;
 pub mod ui;
 
 // Add the spacebar toggle module
@@ -98,8 +107,8 @@ mod spacebar_toggle;
 pub use spacebar_toggle::{handle_spacebar_toggle, SpacebarToggleState};
 pub use ui::{
     create_toolbar_button, create_toolbar_button_with_hover_text, handle_toolbar_mode_selection,
-    spawn_edit_mode_toolbar, update_current_edit_mode, update_hover_text_visibility,
-    update_toolbar_button_appearances, update_toolbar_button_colors,
+    rebuild_edit_mode_toolbar, spawn_edit_mode_toolbar, update_current_edit_mode,
+    update_hover_text_visibility, update_toolbar_button_appearances, update_toolbar_button_colors,
     update_toolbar_button_text_colors,
 };
 
@@ -135,6 +144,7 @@ pub type ToolId = &'static str;
 /// - `on_enter()`: Setup when tool becomes active
 /// - `on_exit()`: Cleanup when switching away from tool
 /// - `supports_temporary_mode()`: Whether tool can be temporarily activated via spacebar
+/// - `options()`: Parameters shown in the options bar below the toolbar
 ///
 /// # Example Implementation
 ///
@@ -180,6 +190,11 @@ pub trait EditTool: Send + Sync + 'static {
     fn supports_temporary_mode(&self) -> bool {
         false
     }
+
+    /// Parameters shown in the options bar while this tool is active
+    fn options(&self) -> &'static [tool_options::ToolOption] {
+        &[]
+    }
 }
 
 /// Registry for all available edit tools.
@@ -199,6 +214,8 @@ pub struct ToolRegistry {
     tools: HashMap<ToolId, Box<dyn EditTool>>,
     ordered_tool_ids: Vec<ToolId>,
     ordering_dirty: bool,
+    /// Variants of each tool group by the id of the tool hosting it
+    groups: HashMap<ToolId, Vec<ToolId>>,
 }
 
 impl ToolRegistry {
@@ -211,7 +228,7 @@ impl ToolRegistry {
     }
 
     /// Get a tool by its ID
-    pub fn get_tool(&self, id: ToolId) -> Option<&dyn EditTool> {
+    pub fn get_tool(&self, id: &str) -> Option<&dyn EditTool> {
         self.tools.get(id).map(|t| t.as_ref())
     }
 
@@ -228,20 +245,44 @@ impl ToolRegistry {
         self.tools.keys().copied().collect()
     }
 
+    /// Put `variant` in the group hosted by `host`: the toolbar shows one
+    /// button for both, and a long press on it reveals the variants
+    pub fn register_group(&mut self, host: ToolId, variant: ToolId) {
+        let variants = self.groups.entry(host).or_default();
+        if !variants.contains(&variant) {
+            variants.push(variant);
+        }
+    }
+
+    /// The variants of the group each tool hosts, by host id
+    pub fn get_groups(&self) -> &HashMap<ToolId, Vec<ToolId>> {
+        &self.groups
+    }
+
+    /// The toolbar's slots once the user's `layout` is applied
+    pub fn get_toolbar_slots(&self, layout: &ToolbarLayout) -> Vec<ToolbarSlot> {
+        layout.slots(&self.sorted_tool_ids(), &self.groups)
+    }
+
     /// Rebuild the tool ordering based on default_order() values
     fn rebuild_ordering(&mut self) {
+        self.ordered_tool_ids = self.sorted_tool_ids();
+        self.ordering_dirty = false;
+    }
+
+    /// All tool IDs sorted by their default_order() values
+    pub fn sorted_tool_ids(&self) -> Vec<ToolId> {
         let mut tools_with_order: Vec<(ToolId, i32)> = self
             .tools
             .iter()
             .map(|(id, tool)| (*id, tool.default_order()))
             .collect();
 
-        // Sort by order value (lower numbers first)
-        tools_with_order.sort_by_key(|(_, order)| *order);
-
-        self.ordered_tool_ids = tools_with_order.into_iter().map(|(id, _)| id).collect();
+        // Sort by order value (lower numbers first), then by id so tools
+        // with the same order keep their place
+        tools_with_order.sort_by_key(|(id, order)| (*order, *id));
 
-        self.ordering_dirty = false;
+        tools_with_order.into_iter().map(|(id, _)| id).collect()
     }
 }
 
@@ -294,6 +335,9 @@ impl CurrentTool {
 /// Tool ordering can be changed at runtime based on user preferences
 /// or workflow requirements using the various set_*_order() methods.
 // New tool exports (using current available exports)
+pub use corners::CornersToolPlugin;
+pub use eraser::EraserToolPlugin;
+pub use nib::NibToolPlugin;
 pub use hyper::HyperToolPlugin;
 pub use knife::{KnifeModeActive, KnifeToolPlugin};
 pub use measure::MeasureToolPlugin;
@@ -378,7 +422,6 @@ impl Plugin for EditModeToolbarPlugin {
             // Legacy resources (will be removed after migration)
             // .init_resource::<CurrentPrimitiveType>()  // Will be added when shapes is ported
             // .init_resource::<ActivePrimitiveDrawing>()  // Will be added when shapes is ported
-            // .init_resource::<CurrentCornerRadius>()  // Will be added when shapes is ported
             // .init_resource::<UiInteractionState>()  // Will be added when shapes is ported
             .init_resource::<SpacebarToggleState>()
             // ✅ NEW SYSTEM: Centralized configuration system handles all tool registration
@@ -392,7 +435,12 @@ impl Plugin for EditModeToolbarPlugin {
             .add_plugins(TextToolPlugin) // Text tool with submenu functionality
             .add_plugins(ShapesToolPlugin) // Shapes tool with submenu functionality
             .add_plugins(KnifeToolPlugin) // Knife tool for cutting paths
+            .add_plugins(EraserToolPlugin) // Eraser tool for removing segments
+            .add_plugins(CornersToolPlugin) // Corners tool for rounding corners
+            .add_plugins(NibToolPlugin) // Nib tool for expanding skeletons
             .add_plugins(crate::tools::ai::AiToolPlugin) // AI tool with submenu functionality
+            .add_plugins(tool_options::ToolOptionsPlugin) // Options bar of the current tool
+            .add_plugins(tool_groups::ToolGroupsPlugin) // Long-press flyouts of tool groups
             // ✅ NOTE: Tool registration (toolbar buttons) is automatic via ConfigBasedToolbarPlugin
             // ✅ NOTE: Tool behavior (what tools do) still needs these individual behavior plugins
             .add_systems(
@@ -407,6 +455,7 @@ impl Plugin for EditModeToolbarPlugin {
             .add_systems(
                 Update,
                 (
+                    rebuild_edit_mode_toolbar,
                     handle_toolbar_mode_selection,
                     update_toolbar_button_appearances,
                     update_hover_text_visibility,
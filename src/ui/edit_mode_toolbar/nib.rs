@@ -0,0 +1,591 @@
+//! Nib Tool - Expands skeletons into contrasted outlines
+//!
+//! Open contours are skeletons: the tool draws each one with a nib held at a
+//! fixed angle, the way a broad-edged pen is, so strokes are thick where they
+//! run across the nib and thin where they run along it. The nib is an
+//! ellipse or a rectangle, sized and angled in the options bar; an ellipse as
+//! wide as it is high draws a constant width. The outlines are drawn over
+//! the glyph while the tool is active and Enter replaces the skeletons with
+//! them: the skeletons with selected points if there are any, or all of them.
+//! Glyph filters offset closed contours with the same sampling and fitting.
+
+use super::corners::tangent;
+use super::eraser::{contour_segments, segment_path};
+use super::tool_options::{NibShape, ToolOptions};
+use crate::core::config::BezySettings;
+use crate::core::state::{AppState, ContourData, GlyphData};
+use crate::editing::outline_locks::OutlineLocks;
+use crate::editing::selection::components::{GlyphPointReference, SelectionState};
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::rendering::glyph_renderer::SortVisualUpdateTracker;
+use crate::rendering::mesh_utils::create_line_mesh;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use crate::ui::themes::CurrentTheme;
+use bevy::prelude::*;
+use bevy::render::mesh::Mesh2d;
+use bevy::sprite::{ColorMaterial, MeshMaterial2d};
+use kurbo::simplify::{simplify_bezpath, SimplifyOptions};
+use kurbo::{
+    BezPath, Line, ParamCurve, ParamCurveArclen, ParamCurveNearest, PathEl, PathSeg, Point, Shape,
+    Vec2 as KurboVec2,
+};
+use std::collections::BTreeSet;
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
+
+/// Tolerance of the polyline the outlines are drawn as
+const PREVIEW_FLATTEN_TOLERANCE: f64 = 0.5;
+
+/// Z-order of the outlines, above the glyph's
+const PREVIEW_Z: f32 = 18.0;
+
+/// Length of skeleton between the places the nib is stamped, in font units
+const SAMPLE_STEP: f64 = 4.0;
+
+/// Largest turn of the nib between stamps, in radians; the nib turns in
+/// place at corners of the skeleton and around its ends
+const SWEEP_STEP: f64 = PI / 32.0;
+
+/// Edges of the swept outline turning more than this, in radians, meet at a
+/// corner instead of being fitted with a curve
+const CORNER_ANGLE: f64 = PI / 6.0;
+
+/// How far the curves of an outline may stray from the nib's sweep
+const FIT_ACCURACY: f64 = 0.5;
+
+/// Accuracy of lengths measured along the skeleton
+const ARCLEN_ACCURACY: f64 = 1e-3;
+
+/// Stamps closer than this, in font units, are merged
+const MIN_EDGE_LENGTH: f64 = 1e-3;
+
+/// Curves meeting at less than this angle, in radians, join smoothly when
+/// offset
+const SMOOTH_ANGLE: f64 = PI / 180.0;
+
+/// Longest miter at an offset corner, in offset distances; sharper corners
+/// are beveled
+const MITER_LIMIT: f64 = 4.0;
+
+/// The nib a skeleton is drawn with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Nib {
+    pub shape: NibShape,
+    pub width: f64,
+    pub height: f64,
+    /// Angle of the nib's width from the baseline, in degrees
+    pub angle: f64,
+}
+
+impl Nib {
+    pub fn from_options(options: &ToolOptions) -> Self {
+        Self {
+            shape: options.nib_shape,
+            width: options.nib_width as f64,
+            height: options.nib_height as f64,
+            angle: options.nib_angle as f64,
+        }
+    }
+
+    /// The point of the nib's edge furthest in `direction`, from its center
+    pub fn support(&self, direction: KurboVec2) -> KurboVec2 {
+        let across = KurboVec2::from_angle(self.angle.to_radians());
+        let along = KurboVec2::new(-across.y, across.x);
+        let (u, v) = (direction.dot(across), direction.dot(along));
+        let (a, b) = (self.width / 2.0, self.height / 2.0);
+        let (x, y) = match self.shape {
+            NibShape::Ellipse => {
+                let length = (a * a * u * u + b * b * v * v).sqrt();
+                if length == 0.0 {
+                    return KurboVec2::ZERO;
+                }
+                (a * a * u / length, b * b * v / length)
+            }
+            NibShape::Rectangle => (a.copysign(u), b.copysign(v)),
+        };
+        across * x + along * y
+    }
+}
+
+/// Plugin for the nib tool
+pub struct NibToolPlugin;
+
+impl Plugin for NibToolPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (handle_nib_input, render_nib_preview).chain());
+    }
+}
+
+/// Indices of the contours of `glyph` expanded: the open, editable ones,
+/// only those with selected points if any are selected
+fn skeleton_indices(
+    glyph: &GlyphData,
+    glyph_name: &str,
+    selection_state: &SelectionState,
+    point_query: &Query<&GlyphPointReference>,
+    locks: &OutlineLocks,
+) -> Vec<usize> {
+    let Some(outline) = glyph.outline.as_ref() else {
+        return Vec::new();
+    };
+    let glyph_locks = locks.glyph(glyph_name);
+    let skeletons: Vec<usize> = outline
+        .contours
+        .iter()
+        .enumerate()
+        .filter(|(index, contour)| contour.is_open() && glyph_locks.is_editable(*index))
+        .map(|(index, _)| index)
+        .collect();
+    let selected: BTreeSet<usize> = selection_state
+        .selected
+        .iter()
+        .filter_map(|entity| point_query.get(*entity).ok())
+        .filter(|reference| reference.glyph_name == glyph_name)
+        .map(|reference| reference.contour_index)
+        .filter(|index| skeletons.contains(index))
+        .collect();
+    if selected.is_empty() {
+        skeletons
+    } else {
+        selected.into_iter().collect()
+    }
+}
+
+/// Replaces the skeletons of the active glyph with their outlines on Enter
+#[allow(clippy::too_many_arguments)]
+fn handle_nib_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_tool: Res<CurrentTool>,
+    settings: Res<BezySettings>,
+    locks: Res<OutlineLocks>,
+    selection_state: Res<SelectionState>,
+    point_query: Query<&GlyphPointReference>,
+    active_sort_query: Query<&Sort, With<ActiveSort>>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+    mut visual_update_tracker: ResMut<SortVisualUpdateTracker>,
+) {
+    if current_tool.get_current() != Some("nib")
+        || !keyboard.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter])
+    {
+        return;
+    }
+    let (Ok(sort), Some(state)) = (active_sort_query.single(), app_state.as_deref_mut()) else {
+        return;
+    };
+    let Some(glyph) = state.workspace.font.editing_glyph_mut(&sort.glyph_name) else {
+        return;
+    };
+    let skeletons = skeleton_indices(
+        glyph,
+        &sort.glyph_name,
+        &selection_state,
+        &point_query,
+        &locks,
+    );
+    let Some(outline) = glyph.outline.as_mut() else {
+        return;
+    };
+    let nib = Nib::from_options(&settings.tool_options);
+    let mut expanded = 0;
+    for index in skeletons {
+        if let Some(contour) = expand_skeleton(&outline.contours[index], &nib) {
+            outline.contours[index] = contour;
+            expanded += 1;
+        }
+    }
+    if expanded == 0 {
+        return;
+    }
+    debug!(glyph = %sort.glyph_name, skeletons = expanded, "Expanded skeletons");
+
+    app_state_changed.write(AppStateChanged);
+    visual_update_tracker.needs_update = true;
+}
+
+/// Draws the outlines the skeletons of the active glyph expand to
+#[allow(clippy::too_many_arguments)]
+fn render_nib_preview(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut preview_entities: Local<Vec<Entity>>,
+    mut shown: Local<Option<(Nib, String, Vec<usize>, Vec2)>>,
+    mut app_state_changed: EventReader<AppStateChanged>,
+    current_tool: Res<CurrentTool>,
+    settings: Res<BezySettings>,
+    locks: Res<OutlineLocks>,
+    selection_state: Res<SelectionState>,
+    point_query: Query<&GlyphPointReference>,
+    active_sort_query: Query<(&Sort, &Transform), With<ActiveSort>>,
+    app_state: Option<Res<AppState>>,
+    camera_scale: Res<CameraResponsiveScale>,
+    theme: Res<CurrentTheme>,
+) {
+    let active = current_tool.get_current() == Some("nib");
+    let glyph = active_sort_query
+        .single()
+        .ok()
+        .filter(|_| active)
+        .and_then(|(sort, transform)| {
+            let glyph = app_state
+                .as_deref()?
+                .workspace
+                .font
+                .editing()
+                .get_glyph(&sort.glyph_name)?;
+            Some((sort, transform.translation.truncate(), glyph))
+        });
+    let view = glyph.map(|(sort, position, glyph)| {
+        let skeletons = skeleton_indices(
+            glyph,
+            &sort.glyph_name,
+            &selection_state,
+            &point_query,
+            &locks,
+        );
+        let nib = Nib::from_options(&settings.tool_options);
+        (nib, sort.glyph_name.clone(), skeletons, position)
+    });
+    let outline_changed = app_state_changed.read().count() > 0;
+    if !outline_changed && *shown == view {
+        return;
+    }
+    *shown = view;
+
+    for entity in preview_entities.drain(..) {
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.despawn();
+        }
+    }
+    let (Some((nib, _, skeletons, position)), Some((_, _, glyph))) = (shown.as_ref(), glyph) else {
+        return;
+    };
+    let Some(outline) = glyph.outline.as_ref() else {
+        return;
+    };
+
+    let width = camera_scale.adjusted_line_width();
+    let material = materials.add(ColorMaterial::from(theme.theme().hover_orange_color()));
+    for index in skeletons {
+        let Some(expanded) = expand_skeleton(&outline.contours[*index], nib) else {
+            continue;
+        };
+        let mut polyline = Vec::new();
+        let path = expanded.to_bezpath();
+        kurbo::flatten(path.iter(), PREVIEW_FLATTEN_TOLERANCE, |element| {
+            if let PathEl::MoveTo(point) | PathEl::LineTo(point) = element {
+                polyline.push(Vec2::new(point.x as f32, point.y as f32) + *position);
+            }
+        });
+        if let Some(first) = polyline.first().copied() {
+            polyline.push(first);
+        }
+        for pair in polyline.windows(2) {
+            if pair[0] == pair[1] {
+                continue;
+            }
+            let entity = commands
+                .spawn((
+                    Mesh2d(meshes.add(create_line_mesh(pair[0], pair[1], width))),
+                    MeshMaterial2d(material.clone()),
+                    Transform::from_translation(((pair[0] + pair[1]) * 0.5).extend(PREVIEW_Z)),
+                ))
+                .id();
+            preview_entities.push(entity);
+        }
+    }
+}
+
+/// `angle` brought into -π..π
+fn wrap_angle(angle: f64) -> f64 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+/// Angles strictly between `from` and `to`, at most `SWEEP_STEP` apart
+fn sweep(from: f64, to: f64) -> impl Iterator<Item = f64> {
+    let steps = ((to - from).abs() / SWEEP_STEP).ceil().max(1.0) as usize;
+    (1..steps).map(move |step| from + (to - from) * step as f64 / steps as f64)
+}
+
+/// Where the nib is stamped along the skeleton, with the direction of travel
+/// as an angle. At corners the nib turns in place
+fn skeleton_stamps(contour: &ContourData) -> Vec<(Point, f64)> {
+    let mut stamps: Vec<(Point, f64)> = Vec::new();
+    for indices in contour_segments(contour) {
+        for curve in segment_path(contour, &indices).segments() {
+            let length = curve.arclen(ARCLEN_ACCURACY);
+            if length < MIN_EDGE_LENGTH {
+                continue;
+            }
+            let steps = (length / SAMPLE_STEP).ceil().max(1.0) as usize;
+            for step in 0..=steps {
+                let t = step as f64 / steps as f64;
+                let (point, direction) = (curve.eval(t), tangent(curve, t));
+                let mut angle = direction.atan2();
+                if let Some(&(_, last)) = stamps.last() {
+                    angle = last + wrap_angle(angle - last);
+                    stamps.extend(sweep(last, angle).map(|turn| (point, turn)));
+                }
+                stamps.push((point, angle));
+            }
+        }
+    }
+    stamps
+}
+
+/// The curve through `points`, a line where they lie on one
+fn fit_run(points: &[Point], path: &mut BezPath) {
+    let [.., last] = points else {
+        return;
+    };
+    let chord = Line::new(points[0], *last);
+    let straight = points
+        .iter()
+        .all(|point| chord.nearest(*point, ARCLEN_ACCURACY).distance_sq <= FIT_ACCURACY.powi(2));
+    if straight {
+        path.line_to(*last);
+        return;
+    }
+    let polyline = std::iter::once(PathEl::MoveTo(points[0]))
+        .chain(points[1..].iter().map(|point| PathEl::LineTo(*point)));
+    let fitted = simplify_bezpath(polyline, FIT_ACCURACY, &SimplifyOptions::default());
+    for curve in fitted.segments() {
+        match curve {
+            PathSeg::Cubic(cubic) => path.curve_to(cubic.p1, cubic.p2, cubic.p3),
+            PathSeg::Quad(quad) => path.quad_to(quad.p1, quad.p2),
+            PathSeg::Line(line) => path.line_to(line.p1),
+        }
+    }
+}
+
+/// Curves fitted to the closed polygon `points`, meeting at its corners
+fn fit_outline(points: &[Point]) -> BezPath {
+    let count = points.len();
+    let turn = |index: usize| {
+        let before = points[index] - points[(index + count - 1) % count];
+        let after = points[(index + 1) % count] - points[index];
+        wrap_angle(after.atan2() - before.atan2()).abs()
+    };
+    let corners: Vec<usize> = (0..count)
+        .filter(|index| turn(*index) > CORNER_ANGLE)
+        .collect();
+    let start = corners.first().copied().unwrap_or(0);
+
+    let mut path = BezPath::new();
+    path.move_to(points[start]);
+    let mut run = vec![points[start]];
+    for offset in 1..=count {
+        let index = (start + offset) % count;
+        run.push(points[index]);
+        if offset == count || corners.contains(&index) {
+            fit_run(&run, &mut path);
+            run = vec![points[index]];
+        }
+    }
+    path.close_path();
+    path
+}
+
+/// Curves fitted to the closed polygon `points`, with repeated points
+/// dropped, or None when fewer than three are left
+fn fit_polygon(points: impl IntoIterator<Item = Point>) -> Option<BezPath> {
+    let mut polygon: Vec<Point> = Vec::new();
+    for point in points {
+        if polygon
+            .last()
+            .is_none_or(|last| last.distance(point) >= MIN_EDGE_LENGTH)
+        {
+            polygon.push(point);
+        }
+    }
+    while polygon.len() > 1 && polygon[0].distance(polygon[polygon.len() - 1]) < MIN_EDGE_LENGTH {
+        polygon.pop();
+    }
+    (polygon.len() >= 3).then(|| fit_outline(&polygon))
+}
+
+/// The outline drawn by sweeping `nib` along the open contour, or None for
+/// a closed contour or one too short to draw. The outline runs
+/// counter-clockwise, as an outer contour does
+pub fn expand_skeleton(contour: &ContourData, nib: &Nib) -> Option<ContourData> {
+    if !contour.is_open() {
+        return None;
+    }
+    let stamps = skeleton_stamps(contour);
+    let (&(start, start_angle), &(end, end_angle)) = (stamps.first()?, stamps.last()?);
+    let edge = |point: Point, angle: f64| point + nib.support(KurboVec2::from_angle(angle));
+
+    // Left side forward, around the end, right side back, around the start
+    let left = stamps
+        .iter()
+        .map(|(point, angle)| edge(*point, angle + FRAC_PI_2));
+    let end_cap = sweep(end_angle + FRAC_PI_2, end_angle - FRAC_PI_2).map(|angle| edge(end, angle));
+    let right = stamps
+        .iter()
+        .rev()
+        .map(|(point, angle)| edge(*point, angle - FRAC_PI_2));
+    let start_cap = sweep(start_angle - FRAC_PI_2, start_angle - 3.0 * FRAC_PI_2)
+        .map(|angle| edge(start, angle));
+    let path = fit_polygon(left.chain(end_cap).chain(right).chain(start_cap))?;
+
+    let area = path.area();
+    let contour = ContourData::from_bezpath(&path).into_iter().next()?;
+    Some(if area < 0.0 {
+        contour.reversed()
+    } else {
+        contour
+    })
+}
+
+/// The closed contour moved `distance` to the right of its direction of
+/// travel: outward for a counter-clockwise outer contour and for a clockwise
+/// counter, so positive distances make a glyph bolder. Corners stay sharp up
+/// to the miter limit. None for an open contour
+pub fn offset_contour(contour: &ContourData, distance: f64) -> Option<ContourData> {
+    if contour.is_open() {
+        return None;
+    }
+    let curves: Vec<PathSeg> = contour_segments(contour)
+        .iter()
+        .flat_map(|indices| segment_path(contour, indices).segments().collect::<Vec<_>>())
+        .filter(|curve| curve.arclen(ARCLEN_ACCURACY) >= MIN_EDGE_LENGTH)
+        .collect();
+    if curves.is_empty() {
+        return None;
+    }
+    let right = |direction: KurboVec2| KurboVec2::new(direction.y, -direction.x) * distance;
+    let mut sides: Vec<Vec<Point>> = curves
+        .iter()
+        .map(|curve| {
+            let steps = (curve.arclen(ARCLEN_ACCURACY) / SAMPLE_STEP).ceil().max(1.0) as usize;
+            (0..=steps)
+                .map(|step| {
+                    let t = step as f64 / steps as f64;
+                    curve.eval(t) + right(tangent(*curve, t))
+                })
+                .collect()
+        })
+        .collect();
+
+    // Where a curve meets the next at a corner, their sides are cut back or
+    // extended to meet at the miter
+    let mut miters: Vec<Option<Point>> = vec![None; curves.len()];
+    for (index, curve) in curves.iter().enumerate() {
+        let next = (index + 1) % curves.len();
+        let (incoming, outgoing) = (tangent(*curve, 1.0), tangent(curves[next], 0.0));
+        let cos = incoming.dot(outgoing);
+        if cos > SMOOTH_ANGLE.cos() || 1.0 + cos < 2.0 / MITER_LIMIT.powi(2) {
+            continue;
+        }
+        let miter = curve.end() + (right(incoming) + right(outgoing)) / (1.0 + cos);
+        while sides[index]
+            .last()
+            .is_some_and(|point| (*point - miter).dot(incoming) > 0.0)
+        {
+            sides[index].pop();
+        }
+        let behind = sides[next]
+            .iter()
+            .take_while(|point| (**point - miter).dot(outgoing) < 0.0)
+            .count();
+        sides[next].drain(..behind);
+        miters[index] = Some(miter);
+    }
+
+    let points = sides
+        .into_iter()
+        .zip(miters)
+        .flat_map(|(side, miter)| side.into_iter().chain(miter));
+    ContourData::from_bezpath(&fit_polygon(points)?)
+        .into_iter()
+        .next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::state::{PointData, PointTypeData};
+
+    fn skeleton(points: &[(f64, f64)]) -> ContourData {
+        ContourData {
+            points: points
+                .iter()
+                .enumerate()
+                .map(|(index, (x, y))| PointData {
+                    x: *x,
+                    y: *y,
+                    point_type: if index == 0 {
+                        PointTypeData::Move
+                    } else {
+                        PointTypeData::Line
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    fn bounds(contour: &ContourData) -> kurbo::Rect {
+        contour.to_bezpath().bounding_box()
+    }
+
+    #[test]
+    fn test_expand_skeleton() {
+        let stroke = skeleton(&[(0.0, 0.0), (100.0, 0.0)]);
+        let flat = Nib {
+            shape: NibShape::Rectangle,
+            width: 40.0,
+            height: 10.0,
+            angle: 0.0,
+        };
+        // A horizontal stroke runs along the nib's width, drawing its height
+        let outline = expand_skeleton(&stroke, &flat).unwrap();
+        let rect = bounds(&outline);
+        assert!((rect.height() - 10.0).abs() < 1e-6);
+        assert!((rect.width() - 140.0).abs() < 1e-6);
+        assert!(outline.to_bezpath().area() > 0.0);
+
+        // Turned upright, the same stroke draws the nib's width
+        let upright = Nib {
+            angle: 90.0,
+            ..flat
+        };
+        let rect = bounds(&expand_skeleton(&stroke, &upright).unwrap());
+        assert!((rect.height() - 40.0).abs() < 1e-6);
+
+        // A round nib draws a constant width with round ends
+        let round = Nib {
+            shape: NibShape::Ellipse,
+            width: 20.0,
+            height: 20.0,
+            angle: 0.0,
+        };
+        let corner = skeleton(&[(0.0, 0.0), (100.0, 0.0), (100.0, 100.0)]);
+        let rect = bounds(&expand_skeleton(&corner, &round).unwrap());
+        assert!((rect.min_x() + 10.0).abs() < 0.5);
+        assert!((rect.max_x() - 110.0).abs() < 0.5);
+        assert!((rect.max_y() - 110.0).abs() < 0.5);
+
+        let closed = ContourData {
+            points: corner.points[1..].to_vec(),
+        };
+        assert!(expand_skeleton(&closed, &round).is_none());
+    }
+
+    #[test]
+    fn test_offset_contour() {
+        let mut square = skeleton(&[(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)]);
+        square.points[0].point_type = PointTypeData::Line;
+        let corners = |contour: &ContourData| {
+            let rect = bounds(contour);
+            [rect.x0, rect.y0, rect.x1, rect.y1].map(f64::round)
+        };
+        let bolder = offset_contour(&square, 10.0).unwrap();
+        assert_eq!(bolder.points.len(), 4);
+        assert_eq!(corners(&bolder), [-10.0, -10.0, 110.0, 110.0]);
+        let lighter = offset_contour(&square, -10.0).unwrap();
+        assert_eq!(corners(&lighter), [10.0, 10.0, 90.0, 90.0]);
+        assert!(offset_contour(&skeleton(&[(0.0, 0.0), (100.0, 0.0)]), 10.0).is_none());
+    }
+}
@@ -15,6 +15,12 @@ use bevy_pancam::PanCam;
 #[derive(Resource, Default)]
 pub struct PresentationMode {
     pub active: bool,
+    /// Reading mode is on (see `ui::reading_mode`), which keeps presentation
+    /// mode on whatever the tool and hides the rest of the editor too
+    pub reading: bool,
+    /// Background reading mode switched to: `Some(true)` for dark,
+    /// `Some(false)` for light, `None` for the theme's own
+    pub dark_background: Option<bool>,
 }
 
 pub struct PanTool;
@@ -51,8 +57,7 @@ impl EditTool for PanTool {
     fn update(&self, commands: &mut Commands) {
         // Ensure select mode is disabled while in pan mode
         commands.insert_resource(SelectModeActive(false));
-        // Enable presentation mode
-        commands.insert_resource(PresentationMode { active: true });
+        // Presentation mode follows the tool in manage_presentation_mode
     }
 
     fn on_enter(&self) {
@@ -109,16 +114,15 @@ pub fn toggle_pancam_on_mode_change(
     }
 }
 
-// System to manage presentation mode based on current tool
+// System to manage presentation mode based on current tool and reading mode
 pub fn manage_presentation_mode(
-    mut commands: Commands,
     current_tool: Res<crate::ui::edit_mode_toolbar::CurrentTool>,
-    presentation_mode: Option<Res<PresentationMode>>,
+    mut presentation_mode: ResMut<PresentationMode>,
 ) {
-    // Only run this system when the current tool changes
-    if current_tool.is_changed() {
+    // Only run this system when the current tool or reading mode changes
+    if current_tool.is_changed() || presentation_mode.is_changed() {
         let is_pan_active = current_tool.get_current() == Some("pan");
-        let current_mode = presentation_mode.as_ref().is_some_and(|pm| pm.active);
+        let current_mode = presentation_mode.active;
 
         debug!(
             "🎭 TOOL CHANGED: current_tool={:?}, is_pan_active={}, current_presentation_mode={}",
@@ -127,13 +131,16 @@ pub fn manage_presentation_mode(
             current_mode
         );
 
-        if is_pan_active {
+        let active = is_pan_active || presentation_mode.reading;
+        if active == current_mode {
+            return;
+        }
+        if active {
             debug!("🎭 ACTIVATING PRESENTATION MODE - hiding grid, metrics, and editing helpers");
-            commands.insert_resource(PresentationMode { active: true });
         } else {
             debug!("🎭 DEACTIVATING PRESENTATION MODE - showing normal editing interface");
-            commands.insert_resource(PresentationMode { active: false });
         }
+        presentation_mode.active = active;
     }
 }
 
@@ -13,6 +13,7 @@ use crate::io::input::{helpers, InputEvent, InputMode, InputState};
 use crate::io::pointer::PointerInfo;
 use crate::systems::ui_interaction::UiHoverState;
 use crate::ui::edit_mode_toolbar::select::SelectModeActive;
+use crate::ui::edit_mode_toolbar::tool_options::{ToolOption, PEN_OPTIONS};
 use crate::ui::edit_mode_toolbar::EditTool;
 use crate::ui::theme::*;
 use crate::ui::themes::CurrentTheme;
@@ -50,6 +51,10 @@ impl EditTool for PenTool {
         "Draw paths and contours"
     }
 
+    fn options(&self) -> &'static [ToolOption] {
+        PEN_OPTIONS
+    }
+
     fn update(&self, commands: &mut Commands) {
         // Ensure pen mode is active
         commands.insert_resource(PenModeActive(true));
@@ -234,6 +239,20 @@ impl PenDrawingMode {
             PenDrawingMode::Hyperbezier => "Draw smooth hyperbezier curves",
         }
     }
+
+    /// Name of the mode in the config file
+    pub fn id(&self) -> &'static str {
+        match self {
+            PenDrawingMode::Regular => "regular",
+            PenDrawingMode::Hyperbezier => "hyperbezier",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        [PenDrawingMode::Regular, PenDrawingMode::Hyperbezier]
+            .into_iter()
+            .find(|mode| mode.id() == id)
+    }
 }
 
 /// Component to mark pen submenu buttons
@@ -435,7 +454,7 @@ fn calculate_final_position(
     crate::geometry::utilities::calculate_final_position_with_constraints(
         cursor_pos,
         settings.grid.enabled,
-        settings.grid.unit_size,
+        settings.grid.snap_size(),
         axis_lock,
     )
 }
@@ -582,7 +601,7 @@ fn add_contour_to_glyph(
     let contour_data = crate::core::state::font_data::ContourData::from_norad_contour(&contour);
 
     // Check if the glyph exists in our thread-safe data
-    if let Some(glyph_data) = app_state.workspace.font.glyphs.get_mut(&glyph_name) {
+    if let Some(glyph_data) = app_state.workspace.font.editing_glyph_mut(&glyph_name) {
         // Get or create the outline data
         let outline_data =
             glyph_data
@@ -888,16 +907,14 @@ pub fn handle_pen_submenu_selection(
     children_query: Query<&Children>,
     mut text_query: Query<&mut TextColor>,
     theme: Res<CurrentTheme>,
+    time: Res<Time>,
 ) {
     // Debug: Log if we find any submenu buttons
     let button_count = interaction_query.iter().len();
     if button_count > 0 {
         static mut LAST_LOG: f32 = 0.0;
         unsafe {
-            let current_time = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs_f32();
+            let current_time = time.elapsed_secs();
             if current_time - LAST_LOG > 2.0 {
                 LAST_LOG = current_time;
                 debug!(
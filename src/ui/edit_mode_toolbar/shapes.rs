@@ -10,6 +10,7 @@ use crate::core::config::BezySettings;
 use crate::core::state::{AppState, GlyphNavigation};
 use crate::editing::selection::events::AppStateChanged;
 use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::ui::edit_mode_toolbar::tool_options::{ToolOption, ToolOptions, SHAPES_OPTIONS};
 use crate::ui::edit_mode_toolbar::{EditTool, ToolRegistry};
 use crate::ui::theme::*;
 use crate::ui::themes::CurrentTheme;
@@ -54,6 +55,10 @@ impl EditTool for ShapesTool {
         "Draw geometric shapes"
     }
 
+    fn options(&self) -> &'static [ToolOption] {
+        SHAPES_OPTIONS
+    }
+
     fn update(&self, commands: &mut Commands) {
         // Activate shapes mode
         commands.insert_resource(ShapesModeActive(true));
@@ -141,16 +146,6 @@ impl ActiveShapeDrawing {
     }
 }
 
-/// Resource to store the current corner radius for rounded rectangles
-#[derive(Resource)]
-pub struct CurrentCornerRadius(pub f32);
-
-impl Default for CurrentCornerRadius {
-    fn default() -> Self {
-        Self(10.0)
-    }
-}
-
 /// Plugin for the shapes tool
 pub struct ShapesToolPlugin;
 
@@ -159,7 +154,6 @@ impl Plugin for ShapesToolPlugin {
         app.init_resource::<ShapesModeActive>()
             .init_resource::<CurrentShapeType>()
             .init_resource::<ActiveShapeDrawing>()
-            .init_resource::<CurrentCornerRadius>()
             .add_systems(Startup, register_shapes_tool)
             .add_systems(PostStartup, spawn_shapes_submenu)
             .add_systems(
@@ -200,7 +194,6 @@ pub fn handle_shape_mouse_events(
     mut app_state_changed: EventWriter<AppStateChanged>,
     mut app_state: Option<ResMut<AppState>>,
     glyph_navigation: Res<GlyphNavigation>,
-    corner_radius: Res<CurrentCornerRadius>,
     shapes_mode: Option<Res<ShapesModeActive>>,
     current_tool: Option<Res<crate::ui::edit_mode_toolbar::CurrentTool>>,
     settings: Res<BezySettings>,
@@ -310,7 +303,7 @@ pub fn handle_shape_mouse_events(
                     create_shape(
                         rect,
                         active_drawing.shape_type,
-                        corner_radius.0,
+                        &settings.tool_options,
                         &glyph_navigation,
                         state,
                         &mut app_state_changed,
@@ -352,6 +345,7 @@ pub fn render_active_shape_drawing_with_dimensions(
     theme: Res<CurrentTheme>,
     asset_server: Res<AssetServer>,
     embedded_fonts: Res<EmbeddedFonts>,
+    settings: Res<BezySettings>,
     // Query for active sort to get its position for preview rendering
     active_sort_query: Query<
         (Entity, &crate::editing::sort::Sort, &Transform),
@@ -436,6 +430,7 @@ pub fn render_active_shape_drawing_with_dimensions(
                     &mut meshes,
                     &mut materials,
                     world_rect, // Use world coordinates for rendering
+                    settings.tool_options.polygon_sides,
                     preview_color,
                     line_width,
                 );
@@ -500,7 +495,7 @@ pub fn reset_shapes_mode_when_inactive(
 fn create_shape(
     rect: Rect,
     shape_type: ShapeType,
-    corner_radius: f32,
+    options: &ToolOptions,
     glyph_navigation: &GlyphNavigation,
     app_state: &mut AppState,
     app_state_changed: &mut EventWriter<AppStateChanged>,
@@ -513,12 +508,15 @@ fn create_shape(
     // Create contour points based on shape type
     let points = match shape_type {
         ShapeType::Rectangle => create_rectangle_points(rect),
+        ShapeType::Oval if options.polygon_sides >= 3 => {
+            create_polygon_points(rect, options.polygon_sides)
+        }
         ShapeType::Oval => create_ellipse_points(rect),
-        ShapeType::RoundedRectangle => create_rounded_rectangle_points(rect, corner_radius),
+        ShapeType::RoundedRectangle => create_rounded_rectangle_points(rect, options.corner_radius),
     };
 
     // Add the contour to the glyph
-    if let Some(glyph_data) = app_state.workspace.font.glyphs.get_mut(&glyph_name) {
+    if let Some(glyph_data) = app_state.workspace.font.editing_glyph_mut(&glyph_name) {
         if glyph_data.outline.is_none() {
             glyph_data.outline = Some(crate::core::state::OutlineData {
                 contours: Vec::new(),
@@ -574,6 +572,38 @@ fn create_rectangle_points(rect: Rect) -> Vec<crate::core::state::PointData> {
     ]
 }
 
+/// Corners of a regular polygon inscribed in the oval of `rect`, the first
+/// at the top, counter-clockwise
+fn polygon_corners(rect: Rect, sides: u32) -> Vec<Vec2> {
+    use std::f32::consts::{FRAC_PI_2, TAU};
+
+    let center = rect.center();
+    let radii = rect.half_size();
+    (0..sides)
+        .map(|i| {
+            let angle = FRAC_PI_2 + TAU * i as f32 / sides as f32;
+            center + radii * Vec2::new(angle.cos(), angle.sin())
+        })
+        .collect()
+}
+
+/// Create points for a regular polygon drawn in place of an oval
+fn create_polygon_points(rect: Rect, sides: u32) -> Vec<crate::core::state::PointData> {
+    polygon_corners(rect, sides)
+        .into_iter()
+        .enumerate()
+        .map(|(i, corner)| crate::core::state::PointData {
+            x: corner.x as f64,
+            y: corner.y as f64,
+            point_type: if i == 0 {
+                crate::core::state::PointTypeData::Move
+            } else {
+                crate::core::state::PointTypeData::Line
+            },
+        })
+        .collect()
+}
+
 /// Create points for an ellipse using proper Kurbo curves
 fn create_ellipse_points(rect: Rect) -> Vec<crate::core::state::PointData> {
     let center_x = (rect.min.x + rect.max.x) / 2.0;
@@ -1011,33 +1041,29 @@ fn draw_mesh_dashed_rectangle(
     }
 }
 
-/// Draw a mesh-based dashed ellipse preview
+/// Draw a mesh-based dashed ellipse preview, or the polygon drawn in its
+/// place when `polygon_sides` is 3 or more
+#[allow(clippy::too_many_arguments)]
 fn draw_mesh_dashed_ellipse(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
     rect: Rect,
+    polygon_sides: u32,
     color: Color,
     width: f32,
 ) {
-    let center_x = (rect.min.x + rect.max.x) / 2.0;
-    let center_y = (rect.min.y + rect.max.y) / 2.0;
-    let radius_x = (rect.max.x - rect.min.x) / 2.0;
-    let radius_y = (rect.max.y - rect.min.y) / 2.0;
-
-    let mut points = Vec::new();
-
-    // Create 32 points for smooth ellipse preview (more than the 16 in old version)
-    for i in 0..32 {
-        let angle = (i as f32) * std::f32::consts::PI * 2.0 / 32.0;
-        let x = center_x + radius_x * angle.cos();
-        let y = center_y + radius_y * angle.sin();
-        points.push(Vec2::new(x, y));
-    }
+    // 32 corners make a smooth ellipse preview
+    let sides = if polygon_sides >= 3 {
+        polygon_sides
+    } else {
+        32
+    };
+    let points = polygon_corners(rect, sides);
 
-    for i in 0..32 {
+    for i in 0..points.len() {
         let start = points[i];
-        let end = points[(i + 1) % 32];
+        let end = points[(i + 1) % points.len()];
         spawn_shape_preview_dashed_line(commands, meshes, materials, start, end, color, width);
     }
 }
@@ -1172,16 +1198,14 @@ pub fn handle_shapes_submenu_selection(
     children_query: Query<&Children>,
     mut text_query: Query<&mut TextColor>,
     theme: Res<CurrentTheme>,
+    time: Res<Time>,
 ) {
     // Debug: Log if we find any submenu buttons
     let button_count = interaction_query.iter().len();
     if button_count > 0 {
         static mut LAST_LOG: f32 = 0.0;
         unsafe {
-            let current_time = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs_f32();
+            let current_time = time.elapsed_secs();
             if current_time - LAST_LOG > 2.0 {
                 LAST_LOG = current_time;
                 debug!(
@@ -14,6 +14,7 @@
 use crate::core::state::{
     AppState, GlyphNavigation, SortLayoutMode, TextEditorState, TextModeConfig,
 };
+use crate::io::shortcuts::Shortcut;
 use crate::utils::embedded_assets::EmbeddedFonts;
 use bevy::prelude::*;
 
@@ -128,7 +129,7 @@ impl EditTool for TextTool {
         debug!("Entered Text tool - Enhanced features:");
         debug!("• Click to place sorts, type letters to add glyphs");
         debug!("• 1-9 keys to switch glyphs, F1 for help");
-        debug!("• Arrow keys for navigation, Ctrl+S to show text mode");
+        debug!("• Arrow keys for navigation, Cmd/Ctrl+Alt+Shift+S to log the buffer");
     }
 
     fn on_exit(&self) {
@@ -333,10 +334,7 @@ pub fn handle_text_mode_cursor(
     text_mode_state.cursor_position = Some(raw_cursor_world_pos);
     text_mode_state.showing_preview = true;
     if cursor_moved || position_changed {
-        debug!(
-            "Text mode cursor updated: raw=({:.1}, {:.1})",
-            raw_cursor_world_pos.x, raw_cursor_world_pos.y
-        );
+        trace!(position = ?raw_cursor_world_pos, "Text mode cursor moved");
     }
 }
 
@@ -477,20 +475,11 @@ pub fn render_sort_preview(
     mut preview_metrics_state: ResMut<crate::rendering::metrics::PreviewMetricsState>,
     theme: Res<CurrentTheme>,
 ) {
-    debug!(
-        "[PREVIEW] Entered render_sort_preview - text_mode_active: {}, placement_mode: {:?}",
-        text_mode_active.0, *current_placement_mode
-    );
-    if !text_mode_active.0 {
+    if !text_mode_active.0 || *current_placement_mode == TextPlacementMode::Insert {
         preview_metrics_state.active = false;
-        debug!("[PREVIEW] Early return: text_mode_active is false - disabled metrics preview");
-        return;
-    }
-    if *current_placement_mode == TextPlacementMode::Insert {
-        preview_metrics_state.active = false;
-        debug!("[PREVIEW] DISABLING preview: placement mode is Insert");
         return;
     }
+    let _span = trace_span!("sort_preview", mode = ?*current_placement_mode).entered();
 
     let zoom_scale = camera_query
         .single()
@@ -504,10 +493,6 @@ pub fn render_sort_preview(
         .unwrap_or(1.0);
     let grid_size = calculate_dynamic_grid_size(zoom_scale, &theme);
     let snapped_position = (pointer_info.design.to_raw() / grid_size).round() * grid_size;
-    debug!(
-        "[PREVIEW] Placement mode: {:?}, snapped_position: ({:.1}, {:.1})",
-        *current_placement_mode, snapped_position.x, snapped_position.y
-    );
 
     let _preview_color = Color::srgb(1.0, 0.5, 0.0).with_alpha(0.8);
 
@@ -520,20 +505,9 @@ pub fn render_sort_preview(
             .unwrap_or_else(|| "a".to_string()), // Current glyph or 'a' for LTR
     };
 
-    debug!(
-        "[PREVIEW] Using preview glyph: {} (placement mode: {:?})",
-        preview_glyph_name, *current_placement_mode
-    );
-
     // TODO: Re-enable after FontIR removal - try FontIR first
     if let Some(app_state) = &app_state {
-        debug!("[PREVIEW] Using AppState for preview");
         if let Some(glyph_data) = app_state.workspace.font.glyphs.get(&preview_glyph_name) {
-            debug!(
-                "[PREVIEW] Drawing AppState preview for glyph '{}' at ({:.1}, {:.1})",
-                preview_glyph_name, snapped_position.x, snapped_position.y
-            );
-
             // TODO: Implement mesh-based glyph preview
             // For now, just show metrics without glyph outline
 
@@ -544,22 +518,15 @@ pub fn render_sort_preview(
             preview_metrics_state.glyph_name = preview_glyph_name.clone();
             preview_metrics_state.advance_width = advance_width;
             preview_metrics_state.color = theme.theme().button_pressed().with_alpha(0.8);
-            debug!(
-                "[PREVIEW] Updated mesh-based preview metrics for '{}' at ({:.1}, {:.1})",
-                preview_glyph_name, snapped_position.x, snapped_position.y
-            );
+            trace!(glyph = %preview_glyph_name, position = ?snapped_position, "Preview updated");
         } else {
             // No glyph data found - disable preview metrics
             preview_metrics_state.active = false;
-            debug!(
-                "[PREVIEW] No AppState glyph_data found for '{}', cannot draw preview - disabled metrics",
-                preview_glyph_name
-            );
+            trace!(glyph = %preview_glyph_name, "No glyph to preview");
         }
     } else {
         // No font state available - disable preview metrics
         preview_metrics_state.active = false;
-        debug!("[PREVIEW] Neither FontIR nor AppState available, cannot draw preview - disabled metrics preview");
     }
 }
 
@@ -589,9 +556,7 @@ pub fn handle_text_tool_shortcuts(
         keyboard_input.clear_just_pressed(KeyCode::KeyT);
     }
     if current_tool.get_current() == Some("text")
-        && keyboard_input.just_pressed(KeyCode::KeyS)
-        && (keyboard_input.pressed(KeyCode::ControlLeft)
-            || keyboard_input.pressed(KeyCode::ControlRight))
+        && Shortcut::LogTextBuffer.just_pressed(&keyboard_input)
     {
         if let Some(text_editor_state) = text_editor_state {
             let buffer_text: String = text_editor_state
@@ -611,6 +576,7 @@ pub fn handle_text_tool_shortcuts(
         debug!("T - Activate text tool");
         debug!("TEXT MODE:");
         debug!("  • Click to place glyphs");
+        debug!("  • Click inside existing text to move the cursor there");
         debug!("  • Type letters to create sorts");
         debug!("  • Arrow keys for navigation");
         debug!("INSERT MODE:");
@@ -623,8 +589,18 @@ pub fn handle_text_tool_shortcuts(
         debug!("  • Type letters to create sorts");
         debug!("1-9 - Switch to glyph by number");
         debug!("Home/End - Go to start/end (Insert mode)");
-        debug!("Ctrl+S - Show current text buffer");
+        debug!("Cmd/Ctrl+Alt+Shift+S - Log the current text buffer");
         debug!("Escape - Exit text tool");
+        debug!("Double-click a sort with Select to edit it, Escape to return here");
+        debug!("Cmd/Ctrl+Shift+F - Wrap the buffer in a text frame (drag its edges to resize)");
+        debug!("Cmd/Ctrl+Shift+L/C/R - Align frame text left/center/right");
+        debug!("IME input (Chinese/Japanese/Korean) composes at the cursor");
+        debug!("Tab - Pick an alternate (.ss01, .alt...) for the glyph before the cursor");
+        debug!("Cmd/Ctrl+Shift+M - Spacing mode: Up/Down pick a glyph, Left/Right its RSB");
+        debug!("  (Alt for LSB, Shift for steps of 10, Escape to return to typing)");
+        debug!("Cmd/Ctrl+Shift+P - Suggest sidebearings for the buffer's glyphs to review");
+        debug!("Cmd/Ctrl+Shift+K - Kerning proof of the glyph before the cursor, below the text");
+        debug!("Cmd/Ctrl+Shift+J - Suggest kerning for the buffer's glyph pairs to review");
         debug!("F1 - Show this help");
         debug!("====================");
     }
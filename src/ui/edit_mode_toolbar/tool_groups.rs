@@ -0,0 +1,196 @@
+//! Tool Groups
+//!
+//! A tool group shares one toolbar button, which shows the group's last used
+//! tool and a mark in its corner. Holding the button down opens a flyout
+//! below it with a button for every tool of the group; choosing one switches
+//! to it. The flyout closes when a tool is chosen, on Escape, or on a click
+//! anywhere else.
+
+use super::toolbar_layout::ToolbarSlot;
+use super::ui::{create_tool_button, handle_toolbar_mode_selection};
+use super::{CurrentTool, ToolId, ToolRegistry};
+use crate::core::config::BezySettings;
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use std::collections::BTreeMap;
+
+/// How long a group's button is held before its flyout opens, in seconds
+const LONG_PRESS_SECS: f32 = 0.4;
+
+/// Size of the mark in the corner of a group's button
+const GROUP_MARK_SIZE: f32 = 6.0;
+
+/// Distance of the mark from the button's corner
+const GROUP_MARK_INSET: f32 = 3.0;
+
+/// The tool each group's button shows, by the id of the tool hosting it
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolGroupChoices(pub BTreeMap<ToolId, ToolId>);
+
+impl ToolGroupChoices {
+    /// The tool the button of `slot` shows: the last one used, or the host
+    pub fn shown(&self, slot: &ToolbarSlot) -> ToolId {
+        self.0
+            .get(slot.host)
+            .copied()
+            .filter(|tool| slot.members().any(|member| member == *tool))
+            .unwrap_or(slot.host)
+    }
+}
+
+/// The group a toolbar button stands for
+#[derive(Component, Debug, Clone)]
+pub struct ToolGroupSlot(pub ToolbarSlot);
+
+/// The open flyout
+#[derive(Component)]
+struct ToolGroupFlyout;
+
+/// The group button being held down, and for how long
+#[derive(Default)]
+struct LongPress {
+    button: Option<Entity>,
+    held_secs: f32,
+    opened: bool,
+}
+
+pub struct ToolGroupsPlugin;
+
+impl Plugin for ToolGroupsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ToolGroupChoices>().add_systems(
+            Update,
+            (
+                remember_group_choices,
+                open_group_flyout,
+                close_group_flyout.after(handle_toolbar_mode_selection),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Spawns the mark telling a group's button from the others
+pub fn spawn_group_mark(button: &mut ChildSpawnerCommands, theme: &CurrentTheme) {
+    button.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(GROUP_MARK_INSET),
+            bottom: Val::Px(GROUP_MARK_INSET),
+            width: Val::Px(GROUP_MARK_SIZE),
+            height: Val::Px(GROUP_MARK_SIZE),
+            ..default()
+        },
+        BackgroundColor(theme.theme().button_regular_icon()),
+        FocusPolicy::Pass,
+    ));
+}
+
+/// Makes a group's button show the tool of the group switched to last
+fn remember_group_choices(
+    current_tool: Res<CurrentTool>,
+    settings: Res<BezySettings>,
+    tool_registry: Res<ToolRegistry>,
+    mut choices: ResMut<ToolGroupChoices>,
+) {
+    if !current_tool.is_changed() {
+        return;
+    }
+    let Some(tool) = current_tool.get_current() else {
+        return;
+    };
+    for slot in tool_registry.get_toolbar_slots(&settings.toolbar) {
+        let in_group = !slot.variants.is_empty() && slot.members().any(|member| member == tool);
+        if in_group && choices.0.get(slot.host) != Some(&tool) {
+            choices.0.insert(slot.host, tool);
+        }
+    }
+}
+
+/// Opens the flyout of a group's button held down long enough
+#[allow(clippy::too_many_arguments)]
+fn open_group_flyout(
+    mut commands: Commands,
+    mut press: Local<LongPress>,
+    time: Res<Time>,
+    slot_query: Query<(Entity, &Interaction, &ToolGroupSlot, &ChildOf)>,
+    flyout_query: Query<Entity, With<ToolGroupFlyout>>,
+    tool_registry: Res<ToolRegistry>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    let Some((button, _, ToolGroupSlot(slot), child_of)) = slot_query
+        .iter()
+        .find(|(_, interaction, ..)| **interaction == Interaction::Pressed)
+    else {
+        *press = LongPress::default();
+        return;
+    };
+    if press.button != Some(button) {
+        *press = LongPress {
+            button: Some(button),
+            ..default()
+        };
+    }
+    press.held_secs += time.delta_secs();
+    if press.opened || press.held_secs < LONG_PRESS_SECS {
+        return;
+    }
+    press.opened = true;
+
+    for flyout in flyout_query.iter() {
+        commands.entity(flyout).despawn();
+    }
+    // The flyout hangs below the button's container
+    let flyout = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(100.0),
+                left: Val::ZERO,
+                flex_direction: FlexDirection::Column,
+                border: UiRect::all(Val::Px(theme.theme().widget_border_width())),
+                ..default()
+            },
+            BackgroundColor(theme.theme().widget_background_color()),
+            BorderColor(theme.theme().widget_border_color()),
+            BorderRadius::all(Val::Px(theme.theme().widget_border_radius())),
+            Interaction::default(),
+            FocusPolicy::Block,
+            GlobalZIndex(i32::MAX - 1),
+            ToolGroupFlyout,
+            Name::new("ToolGroupFlyout"),
+            ChildOf(child_of.parent()),
+        ))
+        .id();
+    commands.entity(flyout).with_children(|flyout| {
+        for tool in slot.members().filter_map(|id| tool_registry.get_tool(id)) {
+            create_tool_button(flyout, tool, None, &asset_server, &embedded_fonts, &theme);
+        }
+    });
+}
+
+/// Closes the flyout once a tool is chosen from it, on Escape, or on a click
+/// outside it
+fn close_group_flyout(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    flyout_query: Query<(Entity, &Interaction), With<ToolGroupFlyout>>,
+    children_query: Query<&Children>,
+    interaction_query: Query<&Interaction, Changed<Interaction>>,
+) {
+    for (flyout, interaction) in flyout_query.iter() {
+        let chosen = children_query
+            .iter_descendants(flyout)
+            .any(|entity| interaction_query.get(entity) == Ok(&Interaction::Pressed));
+        let clicked_outside =
+            mouse.get_just_pressed().next().is_some() && *interaction == Interaction::None;
+        if chosen || clicked_outside || keyboard.just_pressed(KeyCode::Escape) {
+            commands.entity(flyout).despawn();
+        }
+    }
+}
@@ -0,0 +1,602 @@
+//! Tool Options Bar
+//!
+//! A row below the toolbar with the parameters of the current tool: the
+//! pen's curve mode, the shapes tool's corner radius and polygon sides, what
+//! the knife does with the pieces of a cut, whether measurements stay on
+//! the canvas, the radius the corners tool rounds corners to, and the shape,
+//! size and angle of the nib skeletons are expanded with. Tools list their
+//! options through `EditTool::options`. Clicking an option steps it to its
+//! next value, Shift+click to the previous one. Values live in
+//! `BezySettings`, kept per tool, so they're saved with the other
+//! preferences.
+
+use crate::core::config::preferences::step_value;
+use crate::core::config::BezySettings;
+use crate::systems::input_consumer::MeasureInputConsumer;
+use crate::ui::edit_mode_toolbar::pen::PenDrawingMode;
+use crate::ui::edit_mode_toolbar::{CurrentTool, ToolId, ToolRegistry};
+use crate::ui::theme::*;
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::{AssetServerFontExt, EmbeddedFonts};
+use bevy::prelude::*;
+
+/// Corner radii the shapes tool steps through, in font units
+const CORNER_RADII: [f32; 7] = [0.0, 2.0, 5.0, 10.0, 20.0, 40.0, 80.0];
+
+/// Radii the corners tool steps through, in font units
+pub(crate) const FILLET_RADII: [f32; 8] = [5.0, 10.0, 20.0, 30.0, 40.0, 60.0, 80.0, 120.0];
+
+/// Nib widths the nib tool steps through, in font units
+const NIB_WIDTHS: [f32; 8] = [10.0, 20.0, 40.0, 60.0, 80.0, 100.0, 140.0, 200.0];
+
+/// Nib heights the nib tool steps through, in font units
+const NIB_HEIGHTS: [f32; 8] = [2.0, 5.0, 10.0, 20.0, 30.0, 40.0, 60.0, 80.0];
+
+/// Nib angles the nib tool steps through, in degrees
+const NIB_ANGLES: [f32; 12] = [
+    0.0, 15.0, 30.0, 45.0, 60.0, 75.0, 90.0, 105.0, 120.0, 135.0, 150.0, 165.0,
+];
+
+/// Polygon sides the shapes tool steps through, 0 drawing an oval
+const POLYGON_SIDES: [u32; 7] = [0, 3, 4, 5, 6, 8, 12];
+
+/// Most sides a polygon can have in the config file
+const MAX_POLYGON_SIDES: u32 = 64;
+
+/// Gap between the options of the bar
+const OPTION_GAP: f32 = 4.0;
+
+/// Padding inside an option button
+const BUTTON_PADDING: f32 = 4.0;
+
+pub const PEN_OPTIONS: &[ToolOption] = &[ToolOption::CurveMode];
+pub const SHAPES_OPTIONS: &[ToolOption] = &[ToolOption::CornerRadius, ToolOption::PolygonSides];
+pub const KNIFE_OPTIONS: &[ToolOption] = &[ToolOption::KnifeCut];
+pub const MEASURE_OPTIONS: &[ToolOption] = &[ToolOption::PersistentMeasurement];
+pub const CORNERS_OPTIONS: &[ToolOption] = &[ToolOption::FilletRadius];
+pub const NIB_OPTIONS: &[ToolOption] = &[
+    ToolOption::NibShape,
+    ToolOption::NibWidth,
+    ToolOption::NibHeight,
+    ToolOption::NibAngle,
+];
+
+/// A parameter of a tool shown in the options bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolOption {
+    /// Whether the pen draws regular Béziers or hyperbeziers
+    CurveMode,
+    /// Corner radius of rounded rectangles
+    CornerRadius,
+    /// Sides of the polygon the oval shape draws instead, 0 for an oval
+    PolygonSides,
+    /// What the knife does with the pieces of a cut contour
+    KnifeCut,
+    /// Whether measurements stay on the canvas after the button is released
+    PersistentMeasurement,
+    /// Radius the corners tool rounds corners to
+    FilletRadius,
+    /// Shape of the nib skeletons are expanded with
+    NibShape,
+    /// Width of the nib, across its angle
+    NibWidth,
+    /// Height of the nib, the thinnest stroke it draws
+    NibHeight,
+    /// Angle the nib is held at, in degrees counter-clockwise
+    NibAngle,
+}
+
+impl ToolOption {
+    pub const ALL: [ToolOption; 10] = [
+        ToolOption::CurveMode,
+        ToolOption::CornerRadius,
+        ToolOption::PolygonSides,
+        ToolOption::KnifeCut,
+        ToolOption::PersistentMeasurement,
+        ToolOption::FilletRadius,
+        ToolOption::NibShape,
+        ToolOption::NibWidth,
+        ToolOption::NibHeight,
+        ToolOption::NibAngle,
+    ];
+
+    /// The tool the option belongs to
+    pub fn tool_id(&self) -> ToolId {
+        match self {
+            ToolOption::CurveMode => "pen",
+            ToolOption::CornerRadius | ToolOption::PolygonSides => "shapes",
+            ToolOption::KnifeCut => "knife",
+            ToolOption::PersistentMeasurement => "measure",
+            ToolOption::FilletRadius => "corners",
+            ToolOption::NibShape
+            | ToolOption::NibWidth
+            | ToolOption::NibHeight
+            | ToolOption::NibAngle => "nib",
+        }
+    }
+
+    /// Name of the option in the config file, under its tool
+    pub fn id(&self) -> &'static str {
+        match self {
+            ToolOption::CurveMode => "curves",
+            ToolOption::CornerRadius => "corner_radius",
+            ToolOption::PolygonSides => "sides",
+            ToolOption::KnifeCut => "cut",
+            ToolOption::PersistentMeasurement => "persistent",
+            ToolOption::FilletRadius => "radius",
+            ToolOption::NibShape => "shape",
+            ToolOption::NibWidth => "width",
+            ToolOption::NibHeight => "height",
+            ToolOption::NibAngle => "angle",
+        }
+    }
+
+    pub fn from_id(tool_id: &str, id: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|option| option.tool_id() == tool_id && option.id() == id)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ToolOption::CurveMode => "Curves",
+            ToolOption::CornerRadius => "Radius",
+            ToolOption::PolygonSides => "Sides",
+            ToolOption::KnifeCut => "Cut",
+            ToolOption::PersistentMeasurement => "Keep line",
+            ToolOption::FilletRadius => "Radius",
+            ToolOption::NibShape => "Nib",
+            ToolOption::NibWidth => "Width",
+            ToolOption::NibHeight => "Height",
+            ToolOption::NibAngle => "Angle",
+        }
+    }
+}
+
+/// What the knife does with the pieces of a cut contour
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KnifeCut {
+    #[default]
+    KeepBoth,
+    /// Delete the piece with the smallest area
+    DeleteSmaller,
+}
+
+impl KnifeCut {
+    pub fn id(&self) -> &'static str {
+        match self {
+            KnifeCut::KeepBoth => "keep_both",
+            KnifeCut::DeleteSmaller => "delete_smaller",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        [KnifeCut::KeepBoth, KnifeCut::DeleteSmaller]
+            .into_iter()
+            .find(|cut| cut.id() == id)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            KnifeCut::KeepBoth => "keep both",
+            KnifeCut::DeleteSmaller => "delete smaller",
+        }
+    }
+}
+
+/// Shape of the nib skeletons are expanded with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NibShape {
+    /// A round or oval nib, drawing smooth contrast
+    #[default]
+    Ellipse,
+    /// A broad nib, drawing sharp terminals
+    Rectangle,
+}
+
+impl NibShape {
+    pub fn id(&self) -> &'static str {
+        match self {
+            NibShape::Ellipse => "ellipse",
+            NibShape::Rectangle => "rectangle",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        [NibShape::Ellipse, NibShape::Rectangle]
+            .into_iter()
+            .find(|shape| shape.id() == id)
+    }
+}
+
+/// Values of the tool options
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToolOptions {
+    pub pen_mode: PenDrawingMode,
+    pub corner_radius: f32,
+    /// Sides of the polygon the oval shape draws instead, 0 for an oval
+    pub polygon_sides: u32,
+    pub knife_cut: KnifeCut,
+    pub persistent_measurement: bool,
+    /// Radius of the corners rounded by the corners tool
+    pub fillet_radius: f32,
+    pub nib_shape: NibShape,
+    pub nib_width: f32,
+    pub nib_height: f32,
+    /// Angle of the nib in degrees, 0 holding its width along the baseline
+    pub nib_angle: f32,
+}
+
+impl Default for ToolOptions {
+    fn default() -> Self {
+        Self {
+            pen_mode: PenDrawingMode::default(),
+            corner_radius: 10.0,
+            polygon_sides: 0,
+            knife_cut: KnifeCut::default(),
+            persistent_measurement: false,
+            fillet_radius: 20.0,
+            nib_shape: NibShape::default(),
+            nib_width: 80.0,
+            nib_height: 20.0,
+            nib_angle: 30.0,
+        }
+    }
+}
+
+impl ToolOptions {
+    /// The value of `option` as the config file has it
+    pub fn value(&self, option: ToolOption) -> String {
+        match option {
+            ToolOption::CurveMode => self.pen_mode.id().to_string(),
+            ToolOption::CornerRadius => self.corner_radius.to_string(),
+            ToolOption::PolygonSides => self.polygon_sides.to_string(),
+            ToolOption::KnifeCut => self.knife_cut.id().to_string(),
+            ToolOption::PersistentMeasurement => self.persistent_measurement.to_string(),
+            ToolOption::FilletRadius => self.fillet_radius.to_string(),
+            ToolOption::NibShape => self.nib_shape.id().to_string(),
+            ToolOption::NibWidth => self.nib_width.to_string(),
+            ToolOption::NibHeight => self.nib_height.to_string(),
+            ToolOption::NibAngle => self.nib_angle.to_string(),
+        }
+    }
+
+    /// Set `option` from a config file value; invalid values are ignored
+    pub fn set(&mut self, option: ToolOption, value: &str) {
+        match option {
+            ToolOption::CurveMode => {
+                if let Some(mode) = PenDrawingMode::from_id(value) {
+                    self.pen_mode = mode;
+                }
+            }
+            ToolOption::CornerRadius => {
+                let radius = value.parse::<f32>().ok();
+                if let Some(radius) = radius.filter(|radius| radius.is_finite() && *radius >= 0.0) {
+                    self.corner_radius = radius;
+                }
+            }
+            ToolOption::PolygonSides => {
+                let sides = value.parse::<u32>().ok();
+                if let Some(sides) =
+                    sides.filter(|sides| *sides == 0 || (3..=MAX_POLYGON_SIDES).contains(sides))
+                {
+                    self.polygon_sides = sides;
+                }
+            }
+            ToolOption::KnifeCut => {
+                if let Some(cut) = KnifeCut::from_id(value) {
+                    self.knife_cut = cut;
+                }
+            }
+            ToolOption::PersistentMeasurement => {
+                if let Ok(persistent) = value.parse() {
+                    self.persistent_measurement = persistent;
+                }
+            }
+            ToolOption::FilletRadius => {
+                let radius = value.parse::<f32>().ok();
+                if let Some(radius) = radius.filter(|radius| radius.is_finite() && *radius > 0.0) {
+                    self.fillet_radius = radius;
+                }
+            }
+            ToolOption::NibShape => {
+                if let Some(shape) = NibShape::from_id(value) {
+                    self.nib_shape = shape;
+                }
+            }
+            ToolOption::NibWidth => {
+                let width = value.parse::<f32>().ok();
+                if let Some(width) = width.filter(|width| width.is_finite() && *width > 0.0) {
+                    self.nib_width = width;
+                }
+            }
+            ToolOption::NibHeight => {
+                let height = value.parse::<f32>().ok();
+                if let Some(height) = height.filter(|height| height.is_finite() && *height > 0.0) {
+                    self.nib_height = height;
+                }
+            }
+            ToolOption::NibAngle => {
+                let angle = value.parse::<f32>().ok();
+                if let Some(angle) = angle.filter(|angle| angle.is_finite()) {
+                    self.nib_angle = angle.rem_euclid(180.0);
+                }
+            }
+        }
+    }
+
+    /// Step `option` to its next value, or the previous one going back
+    pub fn step(&mut self, option: ToolOption, forward: bool) {
+        match option {
+            ToolOption::CurveMode => {
+                self.pen_mode = match self.pen_mode {
+                    PenDrawingMode::Regular => PenDrawingMode::Hyperbezier,
+                    PenDrawingMode::Hyperbezier => PenDrawingMode::Regular,
+                };
+            }
+            ToolOption::CornerRadius => {
+                self.corner_radius = step_value(&CORNER_RADII, self.corner_radius, forward);
+            }
+            ToolOption::PolygonSides => {
+                self.polygon_sides = step_value(&POLYGON_SIDES, self.polygon_sides, forward);
+            }
+            ToolOption::KnifeCut => {
+                self.knife_cut = match self.knife_cut {
+                    KnifeCut::KeepBoth => KnifeCut::DeleteSmaller,
+                    KnifeCut::DeleteSmaller => KnifeCut::KeepBoth,
+                };
+            }
+            ToolOption::PersistentMeasurement => {
+                self.persistent_measurement = !self.persistent_measurement;
+            }
+            ToolOption::FilletRadius => {
+                self.fillet_radius = step_value(&FILLET_RADII, self.fillet_radius, forward);
+            }
+            ToolOption::NibShape => {
+                self.nib_shape = match self.nib_shape {
+                    NibShape::Ellipse => NibShape::Rectangle,
+                    NibShape::Rectangle => NibShape::Ellipse,
+                };
+            }
+            ToolOption::NibWidth => {
+                self.nib_width = step_value(&NIB_WIDTHS, self.nib_width, forward);
+            }
+            ToolOption::NibHeight => {
+                self.nib_height = step_value(&NIB_HEIGHTS, self.nib_height, forward);
+            }
+            ToolOption::NibAngle => {
+                self.nib_angle = step_value(&NIB_ANGLES, self.nib_angle, forward);
+            }
+        }
+    }
+
+    /// The value of `option` as the bar shows it
+    pub fn display(&self, option: ToolOption) -> String {
+        match option {
+            ToolOption::CurveMode => self.pen_mode.get_name().to_string(),
+            ToolOption::CornerRadius => self.corner_radius.to_string(),
+            ToolOption::PolygonSides if self.polygon_sides == 0 => "oval".to_string(),
+            ToolOption::PolygonSides => self.polygon_sides.to_string(),
+            ToolOption::KnifeCut => self.knife_cut.label().to_string(),
+            ToolOption::PersistentMeasurement if self.persistent_measurement => "on".to_string(),
+            ToolOption::PersistentMeasurement => "off".to_string(),
+            ToolOption::FilletRadius => self.fillet_radius.to_string(),
+            ToolOption::NibShape => self.nib_shape.id().to_string(),
+            ToolOption::NibWidth => self.nib_width.to_string(),
+            ToolOption::NibHeight => self.nib_height.to_string(),
+            ToolOption::NibAngle => format!("{}°", self.nib_angle),
+        }
+    }
+}
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Container of the options of the current tool
+#[derive(Component)]
+struct ToolOptionsBar;
+
+/// The option a button steps
+#[derive(Component, Clone, Copy)]
+struct ToolOptionButton(ToolOption);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct ToolOptionsPlugin;
+
+impl Plugin for ToolOptionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostStartup, spawn_tool_options_bar)
+            .add_systems(
+                Update,
+                (
+                    handle_tool_option_buttons,
+                    sync_pen_mode,
+                    sync_measure_persistence,
+                    rebuild_tool_options_bar,
+                )
+                    .chain(),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+fn spawn_tool_options_bar(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(tool_options_top_position()),
+            left: Val::Px(TOOLBAR_CONTAINER_MARGIN),
+            height: Val::Px(TOOL_OPTIONS_BAR_HEIGHT),
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(OPTION_GAP),
+            display: Display::None,
+            ..default()
+        },
+        ToolOptionsBar,
+        Name::new("ToolOptionsBar"),
+    ));
+}
+
+fn spawn_option_button(
+    parent: &mut ChildSpawnerCommands,
+    option: ToolOption,
+    options: &ToolOptions,
+    asset_server: &AssetServer,
+    embedded_fonts: &EmbeddedFonts,
+    theme: &CurrentTheme,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::all(Val::Px(BUTTON_PADDING)),
+                border: UiRect::all(Val::Px(TOOLBAR_BORDER_WIDTH)),
+                ..default()
+            },
+            BackgroundColor(theme.theme().button_regular()),
+            BorderColor(theme.theme().button_regular_outline()),
+            ToolOptionButton(option),
+        ))
+        .with_children(|button| {
+            button.spawn((
+                Text::new(format!("{}: {}", option.label(), options.display(option))),
+                TextFont {
+                    font: asset_server
+                        .load_font_with_fallback(theme.theme().mono_font_path(), embedded_fonts),
+                    font_size: WIDGET_TEXT_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(theme.get_ui_text_primary()),
+            ));
+        });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the bar when the tool or the option values change, hiding it
+/// for tools without options
+#[allow(clippy::too_many_arguments)]
+fn rebuild_tool_options_bar(
+    mut commands: Commands,
+    mut shown: Local<Option<(Option<ToolId>, ToolOptions)>>,
+    mut bar_query: Query<(Entity, &mut Node), With<ToolOptionsBar>>,
+    current_tool: Res<CurrentTool>,
+    tool_registry: Res<ToolRegistry>,
+    settings: Res<BezySettings>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    let state = (current_tool.get_current(), settings.tool_options);
+    if shown.as_ref() == Some(&state) {
+        return;
+    }
+    let Ok((bar, mut node)) = bar_query.single_mut() else {
+        return;
+    };
+    *shown = Some(state);
+
+    let options = current_tool
+        .get_current()
+        .and_then(|id| tool_registry.get_tool(id))
+        .map_or(&[][..], |tool| tool.options());
+    node.display = if options.is_empty() {
+        Display::None
+    } else {
+        Display::Flex
+    };
+
+    commands.entity(bar).despawn_related::<Children>();
+    commands.entity(bar).with_children(|parent| {
+        for option in options {
+            spawn_option_button(
+                parent,
+                *option,
+                &settings.tool_options,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        }
+    });
+}
+
+/// Steps the clicked option, backward with Shift held
+fn handle_tool_option_buttons(
+    interaction_query: Query<(&Interaction, &ToolOptionButton), Changed<Interaction>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<BezySettings>,
+) {
+    let backward = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    for (interaction, ToolOptionButton(option)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            settings.tool_options.step(*option, !backward);
+        }
+    }
+}
+
+/// Keeps the pen submenu's mode and the curve mode option in step, whichever
+/// of the two changed
+fn sync_pen_mode(mut settings: ResMut<BezySettings>, mut pen_mode: ResMut<PenDrawingMode>) {
+    if pen_mode.is_changed() && !pen_mode.is_added() {
+        if settings.tool_options.pen_mode != *pen_mode {
+            settings.tool_options.pen_mode = *pen_mode;
+        }
+    } else if settings.is_changed() {
+        pen_mode.set_if_neq(settings.tool_options.pen_mode);
+    }
+}
+
+fn sync_measure_persistence(
+    settings: Res<BezySettings>,
+    mut measure_consumer: ResMut<MeasureInputConsumer>,
+) {
+    if settings.is_changed() {
+        let persistent = settings.tool_options.persistent_measurement;
+        if measure_consumer.persistent != persistent {
+            measure_consumer.persistent = persistent;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_option_values() {
+        let mut options = ToolOptions::default();
+        for option in ToolOption::ALL {
+            options.step(option, true);
+        }
+        let mut loaded = ToolOptions::default();
+        for option in ToolOption::ALL {
+            loaded.set(option, &options.value(option));
+        }
+        assert_eq!(loaded, options);
+        assert_eq!(loaded.corner_radius, 20.0);
+        assert_eq!(loaded.polygon_sides, 3);
+
+        // Stepping back from the first choice stays on it
+        options.step(ToolOption::PolygonSides, false);
+        options.step(ToolOption::PolygonSides, false);
+        assert_eq!(options.polygon_sides, 0);
+
+        loaded.set(ToolOption::PolygonSides, "2");
+        loaded.set(ToolOption::CornerRadius, "-5");
+        loaded.set(ToolOption::KnifeCut, "delete_larger");
+        assert_eq!(loaded.polygon_sides, 3);
+        assert_eq!(loaded.corner_radius, 20.0);
+        assert_eq!(loaded.knife_cut, KnifeCut::DeleteSmaller);
+    }
+}
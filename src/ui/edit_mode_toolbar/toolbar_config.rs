@@ -6,6 +6,8 @@
 //! 2. Set enabled: false to hide a tool
 //! 3. Change icon to use different Unicode character
 //! 4. Modify shortcut key or set to None
+//! 5. Set group to another tool's id to reveal the tool on a long press of
+//!    that tool's button instead of giving it its own
 //!
 //! This is the SINGLE SOURCE OF TRUTH for the edit mode toolbar.
 
@@ -32,6 +34,9 @@ pub struct ToolConfig {
     /// Whether this tool appears in the toolbar
     pub enabled: bool,
 
+    /// Id of the tool hosting this one in its toolbar group, if any
+    pub group: Option<&'static str>,
+
     /// What the tool does when active
     pub behavior: ToolBehavior,
 
@@ -48,6 +53,9 @@ pub enum ToolBehavior {
     Text,
     Shapes,
     Knife,
+    Eraser,
+    Corners,
+    Nib,
     Hyper,
     Measure,
     Metaballs,
@@ -69,7 +77,7 @@ pub enum ToolBehavior {
 /// - 20-29: Drawing tools (Pen)
 /// - 30-39: Shape tools
 /// - 40-49: Text tools
-/// - 50-59: Editing tools (Knife)
+/// - 50-59: Editing tools (Knife, Eraser, Corners, Nib)
 /// - 60-69: Advanced tools (Hyper)
 /// - 70-79: Utility tools (Measure)
 /// - 80-89: Experimental tools (Metaballs)
@@ -81,6 +89,7 @@ pub const TOOLBAR_TOOLS: &[ToolConfig] = &[
         icon: "\u{E010}", // Arrow cursor icon (FIXED - was E001, now E010)
         shortcut: Some('v'),
         enabled: true,
+        group: None,
         behavior: ToolBehavior::Select,
         description: "Select and move points, handles, and components",
     },
@@ -91,6 +100,7 @@ pub const TOOLBAR_TOOLS: &[ToolConfig] = &[
         icon: "\u{E014}",    // Hand icon (FIXED - was E015, now E014)
         shortcut: Some(' '), // Spacebar
         enabled: true,
+        group: None,
         behavior: ToolBehavior::Pan,
         description: "Pan the view (hold spacebar for temporary mode)",
     },
@@ -101,6 +111,7 @@ pub const TOOLBAR_TOOLS: &[ToolConfig] = &[
         icon: "\u{E011}", // Pen nib icon (FIXED - was E002, now E011)
         shortcut: Some('p'),
         enabled: true,
+        group: None,
         behavior: ToolBehavior::Pen,
         description: "Draw and edit Bézier curves (Tab for modes)",
     },
@@ -111,6 +122,7 @@ pub const TOOLBAR_TOOLS: &[ToolConfig] = &[
         icon: "\u{E016}", // Square icon (CORRECT)
         shortcut: Some('s'),
         enabled: true, // ✅ This is the square button you want to keep
+        group: None,
         behavior: ToolBehavior::Shapes,
         description: "Create geometric shapes like rectangles and ellipses",
     },
@@ -121,6 +133,7 @@ pub const TOOLBAR_TOOLS: &[ToolConfig] = &[
         icon: "\u{E017}", // T icon (FIXED - was E003, now E017)
         shortcut: Some('t'),
         enabled: true,
+        group: None,
         behavior: ToolBehavior::Text,
         description: "Place text and create sorts (Tab for modes)",
     },
@@ -131,9 +144,43 @@ pub const TOOLBAR_TOOLS: &[ToolConfig] = &[
         icon: "\u{E013}", // Knife icon
         shortcut: Some('k'),
         enabled: true,
+        group: None,
         behavior: ToolBehavior::Knife,
         description: "Cut contours at specific points",
     },
+    ToolConfig {
+        order: 55,
+        id: "eraser",
+        name: "Eraser",
+        icon: "\u{E007}", // Eraser icon
+        shortcut: Some('e'),
+        enabled: true,
+        group: None,
+        behavior: ToolBehavior::Eraser,
+        description: "Remove path segments (drag to erase every segment crossed)",
+    },
+    ToolConfig {
+        order: 57,
+        id: "corners",
+        name: "Corners",
+        icon: "\u{E008}", // Rounded corner icon
+        shortcut: Some('r'),
+        enabled: true,
+        group: None,
+        behavior: ToolBehavior::Corners,
+        description: "Round corner points (Enter to apply, [ ] for one corner's radius)",
+    },
+    ToolConfig {
+        order: 58,
+        id: "nib",
+        name: "Nib",
+        icon: "\u{E009}", // Broad nib icon
+        shortcut: Some('n'),
+        enabled: true,
+        group: None,
+        behavior: ToolBehavior::Nib,
+        description: "Expand open contours with a calligraphic nib (Enter to apply)",
+    },
     ToolConfig {
         order: 60,
         id: "hyper",
@@ -141,6 +188,7 @@ pub const TOOLBAR_TOOLS: &[ToolConfig] = &[
         icon: "\u{E012}", // Spiral icon
         shortcut: Some('h'),
         enabled: false, // ❌ Moved to pen tool submenu
+        group: None,
         behavior: ToolBehavior::Hyper,
         description: "Draw smooth hyperbezier curves",
     },
@@ -151,6 +199,7 @@ pub const TOOLBAR_TOOLS: &[ToolConfig] = &[
         icon: "\u{E015}", // Ruler icon (FIXED - was E014, now E015)
         shortcut: Some('m'),
         enabled: true,
+        group: None,
         behavior: ToolBehavior::Measure,
         description: "Measure distances and show guides",
     },
@@ -161,6 +210,7 @@ pub const TOOLBAR_TOOLS: &[ToolConfig] = &[
         icon: "\u{E019}", // Circle icon
         shortcut: Some('b'),
         enabled: false, // ❌ This is the circle button you want to disable
+        group: None,
         behavior: ToolBehavior::Metaballs,
         description: "Create organic shapes with metaball effects",
     },
@@ -171,6 +221,7 @@ pub const TOOLBAR_TOOLS: &[ToolConfig] = &[
         icon: "\u{E012}", // Spiral icon (same as hyperbezier)
         shortcut: Some('a'),
         enabled: true,
+        group: None,
         behavior: ToolBehavior::Ai,
         description: "AI-powered font editing tools (Tab for submenu)",
     },
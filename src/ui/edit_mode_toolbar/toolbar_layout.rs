@@ -0,0 +1,259 @@
+//! Toolbar Layout
+//!
+//! The user's arrangement of the edit-mode toolbar: the order of the tools,
+//! the ones left off it, and tool groups. A group is a toolbar slot hosting
+//! several tools; the slot shows the last one used, and a long press on it
+//! reveals the others. Tools register default groups with
+//! `ToolRegistry::register_group`; the layout's groups replace them host by
+//! host. The layout lives in `BezySettings` and is saved in the config file.
+
+use super::ToolId;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// A toolbar slot: the tool hosting it and the variants revealed by a long
+/// press, in toolbar order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolbarSlot {
+    pub host: ToolId,
+    pub variants: Vec<ToolId>,
+}
+
+impl ToolbarSlot {
+    /// The host followed by its variants
+    pub fn members(&self) -> impl Iterator<Item = ToolId> + '_ {
+        std::iter::once(self.host).chain(self.variants.iter().copied())
+    }
+}
+
+/// How the user arranged the toolbar
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolbarLayout {
+    /// Tool ids in toolbar order; tools not listed follow in their default
+    /// order
+    pub order: Vec<String>,
+    /// Tools left off the toolbar; their shortcuts still work
+    pub hidden: BTreeSet<String>,
+    /// Variants of each group by the id of the tool hosting it, replacing the
+    /// group the tool registered
+    pub groups: BTreeMap<String, Vec<String>>,
+}
+
+impl ToolbarLayout {
+    /// Every tool of `default_order` in the layout's order
+    pub fn full_order(&self, default_order: &[ToolId]) -> Vec<ToolId> {
+        let mut order: Vec<ToolId> = self
+            .order
+            .iter()
+            .filter_map(|id| default_order.iter().find(|tool| **tool == id.as_str()))
+            .copied()
+            .collect();
+        order.dedup();
+        for tool in default_order {
+            if !order.contains(tool) {
+                order.push(*tool);
+            }
+        }
+        order
+    }
+
+    /// The groups in effect: the registered ones, replaced by the layout's
+    pub fn effective_groups(
+        &self,
+        registered: &HashMap<ToolId, Vec<ToolId>>,
+    ) -> BTreeMap<String, Vec<String>> {
+        let mut groups: BTreeMap<String, Vec<String>> = registered
+            .iter()
+            .map(|(host, variants)| {
+                let variants = variants.iter().map(|tool| tool.to_string()).collect();
+                (host.to_string(), variants)
+            })
+            .collect();
+        groups.extend(self.groups.clone());
+        groups
+    }
+
+    /// The toolbar's slots. Hidden tools are left out, a tool is in one
+    /// group at most, and a tool hosting a group can't be another's variant
+    pub fn slots(
+        &self,
+        default_order: &[ToolId],
+        registered: &HashMap<ToolId, Vec<ToolId>>,
+    ) -> Vec<ToolbarSlot> {
+        let visible: Vec<ToolId> = self
+            .full_order(default_order)
+            .into_iter()
+            .filter(|tool| !self.hidden.contains(*tool))
+            .collect();
+        let groups = self.effective_groups(registered);
+
+        let mut host_of: HashMap<ToolId, ToolId> = HashMap::new();
+        for host in &visible {
+            let Some(variants) = groups.get(*host) else {
+                continue;
+            };
+            if host_of.contains_key(host) {
+                continue;
+            }
+            for variant in variants {
+                let Some(variant) = visible.iter().find(|tool| **tool == variant.as_str()) else {
+                    continue;
+                };
+                let hosts_group = groups.get(*variant).is_some_and(|group| !group.is_empty());
+                if variant != host && !hosts_group && !host_of.contains_key(variant) {
+                    host_of.insert(*variant, *host);
+                }
+            }
+        }
+
+        visible
+            .iter()
+            .filter(|tool| !host_of.contains_key(*tool))
+            .map(|host| ToolbarSlot {
+                host: *host,
+                variants: visible
+                    .iter()
+                    .filter(|tool| host_of.get(*tool) == Some(host))
+                    .copied()
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Move `tool` one place earlier or later in the toolbar
+    pub fn move_tool(&mut self, tool: ToolId, default_order: &[ToolId], later: bool) {
+        let mut order = self.full_order(default_order);
+        let Some(index) = order.iter().position(|id| *id == tool) else {
+            return;
+        };
+        let other = if later {
+            index + 1
+        } else {
+            index.wrapping_sub(1)
+        };
+        if other < order.len() {
+            order.swap(index, other);
+        }
+        self.order = order.into_iter().map(str::to_string).collect();
+    }
+
+    /// Show or hide `tool`
+    pub fn toggle_hidden(&mut self, tool: ToolId) {
+        if !self.hidden.remove(tool) {
+            self.hidden.insert(tool.to_string());
+        }
+    }
+
+    /// The tool hosting the group `tool` is in, if any
+    pub fn host_of(
+        &self,
+        tool: ToolId,
+        registered: &HashMap<ToolId, Vec<ToolId>>,
+    ) -> Option<String> {
+        self.effective_groups(registered)
+            .into_iter()
+            .find(|(_, variants)| variants.iter().any(|variant| variant == tool))
+            .map(|(host, _)| host)
+    }
+
+    /// Put `tool` in the group of the next tool that can host it, or in no
+    /// group after the last one
+    pub fn cycle_group(
+        &mut self,
+        tool: ToolId,
+        default_order: &[ToolId],
+        registered: &HashMap<ToolId, Vec<ToolId>>,
+    ) {
+        let mut groups = self.effective_groups(registered);
+        if groups
+            .get(tool)
+            .is_some_and(|variants| !variants.is_empty())
+        {
+            // A tool hosting a group stays on the toolbar
+            return;
+        }
+        let is_variant = |id: &str| {
+            groups
+                .values()
+                .any(|variants| variants.iter().any(|variant| variant == id))
+        };
+        let hosts: Vec<ToolId> = self
+            .full_order(default_order)
+            .into_iter()
+            .filter(|id| *id != tool && !is_variant(*id))
+            .collect();
+        let current = self.host_of(tool, registered);
+        let next = match current.as_deref() {
+            None => hosts.first().copied(),
+            Some(host) => hosts.iter().skip_while(|id| **id != host).nth(1).copied(),
+        };
+
+        for variants in groups.values_mut() {
+            variants.retain(|variant| variant != tool);
+        }
+        // An empty group only needs keeping to override a registered one
+        groups.retain(|host, variants| {
+            !variants.is_empty() || registered.contains_key(host.as_str())
+        });
+        if let Some(host) = next {
+            groups
+                .entry(host.to_string())
+                .or_default()
+                .push(tool.to_string());
+        }
+        self.groups = groups;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOOLS: [ToolId; 5] = ["select", "pan", "pen", "shapes", "knife"];
+
+    #[test]
+    fn test_toolbar_slots() {
+        let registered = HashMap::from([("shapes", vec!["knife"])]);
+        let mut layout = ToolbarLayout {
+            order: vec!["pen".to_string(), "gone".to_string()],
+            ..Default::default()
+        };
+        layout.toggle_hidden("pan");
+        let slots = layout.slots(&TOOLS, &registered);
+        let hosts: Vec<ToolId> = slots.iter().map(|slot| slot.host).collect();
+        assert_eq!(hosts, ["pen", "select", "shapes"]);
+        assert_eq!(slots[2].variants, ["knife"]);
+
+        // The layout's groups replace the registered ones
+        layout.groups.insert("shapes".to_string(), Vec::new());
+        assert_eq!(layout.slots(&TOOLS, &registered).len(), 4);
+    }
+
+    #[test]
+    fn test_toolbar_layout_editing() {
+        let registered = HashMap::new();
+        let mut layout = ToolbarLayout::default();
+        layout.move_tool("pen", &TOOLS, false);
+        assert_eq!(
+            layout.full_order(&TOOLS),
+            ["select", "pen", "pan", "shapes", "knife"]
+        );
+        layout.move_tool("select", &TOOLS, false);
+        assert_eq!(layout.full_order(&TOOLS)[0], "select");
+
+        layout.cycle_group("knife", &TOOLS, &registered);
+        assert_eq!(
+            layout.host_of("knife", &registered).as_deref(),
+            Some("select")
+        );
+        layout.cycle_group("knife", &TOOLS, &registered);
+        assert_eq!(layout.host_of("knife", &registered).as_deref(), Some("pen"));
+        // A host can't join another group
+        layout.cycle_group("pen", &TOOLS, &registered);
+        assert_eq!(layout.host_of("pen", &registered), None);
+        for _ in 0..3 {
+            layout.cycle_group("knife", &TOOLS, &registered);
+        }
+        assert_eq!(layout.host_of("knife", &registered), None);
+        assert!(layout.groups.is_empty());
+    }
+}
@@ -5,6 +5,8 @@
 //! The system automatically discovers and displays all registered tools with
 //! proper ordering and visual feedback. To add a new tool, implement the
 //! `EditTool` trait and register it with `ToolRegistry::register_tool()`.
+//! The buttons follow the user's `ToolbarLayout` and are rebuilt when it
+//! changes; a tool group gets one button (see `tool_groups`).
 //!
 //! ## Consistent Button Rendering System
 //!
@@ -30,14 +32,24 @@
 //! This approach ensures perfect visual consistency between main toolbar and all submenus,
 //! making it easy to maintain a professional, unified interface.
 
+use crate::core::config::BezySettings;
+use crate::ui::accessibility::AccessibleName;
+use crate::ui::edit_mode_toolbar::tool_groups::{
+    spawn_group_mark, ToolGroupChoices, ToolGroupSlot,
+};
+use crate::ui::edit_mode_toolbar::toolbar_layout::{ToolbarLayout, ToolbarSlot};
 use crate::ui::edit_mode_toolbar::*;
-use crate::ui::theme::TOOLBAR_GRID_SPACING;
+use crate::ui::theme::{tool_options_top_position, TOOLBAR_GRID_SPACING, TOOL_OPTIONS_BAR_HEIGHT};
 use crate::ui::theme_system::ToolbarBorderRadius;
 use crate::ui::themes::CurrentTheme;
 use crate::utils::embedded_assets::{AssetServerFontExt, EmbeddedFonts};
 
 // COMPONENTS ------------------------------------------------------------------
 
+/// Component marker for the toolbar container holding the buttons
+#[derive(Component)]
+pub struct EditModeToolbar;
+
 /// Component marker for toolbar buttons - used for querying toolbar entities
 #[derive(Component)]
 pub struct EditModeToolbarButton;
@@ -54,30 +66,52 @@ pub struct ButtonHoverText;
 
 // TOOLBAR CREATION ------------------------------------------------------------
 
-/// Creates the main edit mode toolbar with all registered tools
-pub fn spawn_edit_mode_toolbar(
+/// Creates the main edit mode toolbar; `rebuild_edit_mode_toolbar` fills it
+pub fn spawn_edit_mode_toolbar(mut commands: Commands, theme: Res<CurrentTheme>) {
+    commands.spawn((
+        create_toolbar_container(&theme),
+        EditModeToolbar,
+        Name::new("EditModeToolbar"),
+    ));
+}
+
+/// Fills the toolbar with a button for each slot of the user's layout, again
+/// whenever the layout or the tool shown by a group changes
+#[allow(clippy::too_many_arguments)]
+pub fn rebuild_edit_mode_toolbar(
     mut commands: Commands,
+    mut shown: Local<Option<(ToolbarLayout, ToolGroupChoices)>>,
+    toolbar_query: Query<Entity, With<EditModeToolbar>>,
+    settings: Res<BezySettings>,
+    choices: Res<ToolGroupChoices>,
+    tool_registry: Res<ToolRegistry>,
     asset_server: Res<AssetServer>,
     embedded_fonts: Res<EmbeddedFonts>,
     theme: Res<CurrentTheme>,
-    mut tool_registry: ResMut<ToolRegistry>,
 ) {
-    let ordered_tool_ids = tool_registry.get_ordered_tools().to_vec();
-    debug!(
-        "Spawning edit-mode toolbar with {} tools",
-        ordered_tool_ids.len()
-    );
-    commands
-        .spawn(create_toolbar_container(&theme))
-        .with_children(|parent| {
-            for tool_id in ordered_tool_ids {
-                if let Some(tool) = tool_registry.get_tool(tool_id) {
-                    create_tool_button(parent, tool, &asset_server, &embedded_fonts, &theme);
-                } else {
-                    warn!("Tool '{}' not found in registry", tool_id);
-                }
+    let layout = (settings.toolbar.clone(), choices.clone());
+    if shown.as_ref() == Some(&layout) {
+        return;
+    }
+    let Ok(toolbar) = toolbar_query.single() else {
+        return;
+    };
+    *shown = Some(layout);
+
+    let slots = tool_registry.get_toolbar_slots(&settings.toolbar);
+    debug!("Building edit-mode toolbar with {} buttons", slots.len());
+    commands.entity(toolbar).despawn_related::<Children>();
+    commands.entity(toolbar).with_children(|parent| {
+        for slot in slots {
+            let tool_id = choices.shown(&slot);
+            if let Some(tool) = tool_registry.get_tool(tool_id) {
+                let slot = (!slot.variants.is_empty()).then_some(slot);
+                create_tool_button(parent, tool, slot, &asset_server, &embedded_fonts, &theme);
+            } else {
+                warn!("Tool '{}' not found in registry", tool_id);
             }
-        });
+        }
+    });
 }
 
 /// Creates the main toolbar container with proper positioning and styling
@@ -96,10 +130,12 @@ fn create_toolbar_container(theme: &CurrentTheme) -> impl Bundle {
 
 // BUTTON CREATION -------------------------------------------------------------
 
-/// Creates a single tool button with proper styling and components
-fn create_tool_button(
+/// Creates a single tool button with proper styling and components; the
+/// button of a tool group also gets the group and a mark in its corner
+pub(crate) fn create_tool_button(
     parent: &mut ChildSpawnerCommands,
     tool: &dyn EditTool,
+    slot: Option<ToolbarSlot>,
     asset_server: &AssetServer,
     embedded_fonts: &EmbeddedFonts,
     theme: &Res<CurrentTheme>,
@@ -110,7 +146,14 @@ fn create_tool_button(
             ..default()
         })
         .with_children(|button_container| {
-            create_button_entity(button_container, tool, asset_server, embedded_fonts, theme);
+            create_button_entity(
+                button_container,
+                tool,
+                slot,
+                asset_server,
+                embedded_fonts,
+                theme,
+            );
         });
 }
 
@@ -118,23 +161,32 @@ fn create_tool_button(
 fn create_button_entity(
     parent: &mut ChildSpawnerCommands,
     tool: &dyn EditTool,
+    slot: Option<ToolbarSlot>,
     asset_server: &AssetServer,
     embedded_fonts: &EmbeddedFonts,
     theme: &Res<CurrentTheme>,
 ) -> Entity {
-    parent
-        .spawn((
-            Button,
-            EditModeToolbarButton,
-            ToolButtonData { tool_id: tool.id() },
-            create_button_styling(theme),
-            BackgroundColor(theme.theme().button_regular()),
-            BorderColor(theme.theme().button_regular_outline()),
-            BorderRadius::all(Val::Px(theme.theme().toolbar_border_radius())),
-            ToolbarBorderRadius,
-        ))
+    let is_group = slot.is_some();
+    let mut button = parent.spawn((
+        Button,
+        EditModeToolbarButton,
+        ToolButtonData { tool_id: tool.id() },
+        AccessibleName(crate::i18n::tool_name(tool.id(), tool.name())),
+        create_button_styling(theme),
+        BackgroundColor(theme.theme().button_regular()),
+        BorderColor(theme.theme().button_regular_outline()),
+        BorderRadius::all(Val::Px(theme.theme().toolbar_border_radius())),
+        ToolbarBorderRadius,
+    ));
+    if let Some(slot) = slot {
+        button.insert(ToolGroupSlot(slot));
+    }
+    button
         .with_children(|button| {
             create_button_text(button, tool, asset_server, embedded_fonts, theme);
+            if is_group {
+                spawn_group_mark(button, theme);
+            }
         })
         .id()
 }
@@ -212,36 +264,38 @@ pub fn create_toolbar_button<T: Bundle>(
 }
 
 /// Creates a standard button with hover text support
-/// This version allows specifying the hover text to display when the button is hovered
+/// The hover text also names the button for screen readers
 pub fn create_toolbar_button_with_hover_text<T: Bundle>(
     parent: &mut ChildSpawnerCommands,
     icon: &str,
-    _hover_text: Option<&str>,
+    hover_text: Option<&str>,
     additional_components: T,
     asset_server: &AssetServer,
     embedded_fonts: &EmbeddedFonts,
     theme: &Res<CurrentTheme>,
 ) {
-    // Note: _hover_text parameter is now ignored since hover text is handled dynamically
+    // Note: the hover text itself is handled dynamically by update_hover_text_visibility
     parent
         .spawn(Node {
             margin: UiRect::all(Val::Px(TOOLBAR_GRID_SPACING)),
             ..default()
         })
         .with_children(|button_container| {
-            button_container
-                .spawn((
-                    Button,
-                    additional_components,
-                    create_button_styling(theme),
-                    BackgroundColor(theme.theme().button_regular()),
-                    BorderColor(theme.theme().button_regular_outline()),
-                    BorderRadius::all(Val::Px(theme.theme().toolbar_border_radius())),
-                    ToolbarBorderRadius,
-                ))
-                .with_children(|button| {
-                    create_button_icon_text(button, icon, asset_server, embedded_fonts, theme);
-                });
+            let mut button = button_container.spawn((
+                Button,
+                additional_components,
+                create_button_styling(theme),
+                BackgroundColor(theme.theme().button_regular()),
+                BorderColor(theme.theme().button_regular_outline()),
+                BorderRadius::all(Val::Px(theme.theme().toolbar_border_radius())),
+                ToolbarBorderRadius,
+            ));
+            if let Some(name) = hover_text {
+                button.insert(AccessibleName(name.to_string()));
+            }
+            button.with_children(|button| {
+                create_button_icon_text(button, icon, asset_server, embedded_fonts, theme);
+            });
         });
 }
 
@@ -503,7 +557,7 @@ pub fn update_hover_text_visibility(
     for (interaction, _button_entity, tool_data) in toolbar_button_query.iter() {
         if *interaction == Interaction::Hovered {
             if let Some(tool) = tool_registry.get_tool(tool_data.tool_id) {
-                hovered_text = Some(tool.name().to_string());
+                hovered_text = Some(crate::i18n::tool_name(tool.id(), tool.name()));
                 break;
             }
         }
@@ -568,9 +622,15 @@ pub fn update_hover_text_visibility(
             break;
         }
     }
+    let options_visible = submenu_query
+        .iter()
+        .any(|(node, name)| name.as_str() == "ToolOptionsBar" && node.display != Display::None);
 
     // Calculate position: if submenu visible, position below submenu; otherwise below main toolbar
-    let vertical_offset = if submenu_visible {
+    let vertical_offset = if options_visible {
+        // Below the tool options bar
+        tool_options_top_position() + TOOL_OPTIONS_BAR_HEIGHT + TOOLBAR_GRID_SPACING * 2.0
+    } else if submenu_visible {
         // Position below submenu: container margin + main toolbar + spacing + submenu + smaller spacing
         theme.theme().toolbar_container_margin()
             + theme.theme().toolbar_button_size()
@@ -596,7 +656,8 @@ pub fn update_hover_text_visibility(
             };
             // Invert the scale since smaller scale = zoomed in
             let zoom_percentage = ((1.0 / zoom_scale) * 100.0) as i32;
-            format!("Zoom: {zoom_percentage}%")
+            let percent = zoom_percentage.to_string();
+            crate::i18n::tr_args("hover-zoom", &[("percent", &percent)])
         } else {
             String::new()
         }
@@ -787,3 +848,41 @@ pub fn create_pane_button<T: Bundle>(
             create_button_icon_text(button, text, asset_server, embedded_fonts, theme);
         });
 }
+
+/// Creates a pane button labeled with text, sized to the label
+/// Like `create_pane_button`, the components given mark what the button does
+pub fn create_pane_text_button<'a, T: Bundle>(
+    parent: &'a mut ChildSpawnerCommands,
+    text: &str,
+    additional_components: T,
+    asset_server: &AssetServer,
+    embedded_fonts: &EmbeddedFonts,
+    theme: &CurrentTheme,
+) -> EntityCommands<'a> {
+    let mut button = parent.spawn((
+        Button,
+        Node {
+            padding: UiRect::all(Val::Px(4.0)),
+            border: UiRect::all(Val::Px(2.0)),
+            align_self: AlignSelf::FlexStart,
+            ..default()
+        },
+        BackgroundColor(theme.theme().button_regular()),
+        BorderColor(theme.theme().button_regular_outline()),
+        BorderRadius::all(Val::Px(theme.theme().ui_border_radius())),
+        additional_components,
+    ));
+    button.with_children(|button| {
+        button.spawn((
+            Text::new(text),
+            TextFont {
+                font: asset_server
+                    .load_font_with_fallback(theme.theme().mono_font_path(), embedded_fonts),
+                font_size: crate::ui::theme_system::layout_constants::WIDGET_TEXT_FONT_SIZE,
+                ..default()
+            },
+            TextColor(theme.get_ui_text_primary()),
+        ));
+    });
+    button
+}
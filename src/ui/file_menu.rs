@@ -2,16 +2,25 @@
 //! Cross-platform file menu implementation
 //!
 //! Provides keyboard-based file menu functionality that works reliably across
-//! all platforms without threading complexity.
+//! all platforms. Font exports run on the async compute pool so the editor
+//! stays responsive while fontc works.
 
 
+use crate::data::export::{export_project, ExportProgress, ExportReport};
+use crate::data::family::build_family;
+use crate::editing::build_inspector::BuildInspector;
+use crate::editing::compile_errors::{CompileBuild, CompileErrors};
+use crate::font_source::spacing_import::SpacingFormat;
+use crate::io::shortcuts::Shortcut;
 use crate::ui::panes::file_pane::FileInfo;
 use bevy::prelude::*;
-use bevy::window::{PrimaryWindow, Window};
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task};
+use bevy::window::{FileDragAndDrop, PrimaryWindow, Window};
 // Note: Removed unused imports - we now preserve original glyph data
 use kurbo::PathEl;
 use norad::{designspace::DesignSpaceDocument, Font as NoradFont};
 use std::path::PathBuf;
+use std::sync::Arc;
 
 // ============================================================================
 // DUMMY TYPES FOR DEAD CODE (FontIR Removal)
@@ -34,9 +43,33 @@ struct DummyWorkingCopy {
 #[derive(Event)]
 pub struct SaveFileEvent;
 
-/// Event fired when the user triggers an export to TTF action
+/// Event fired to export fonts with the named export profile, or the
+/// project's first profile with `None`
+#[derive(Event, Clone)]
+pub struct ExportFontEvent(pub Option<String>);
+
+/// Event fired to export every source of a family project into its
+/// release folder
+#[derive(Event, Clone)]
+pub struct BuildFamilyEvent(pub PathBuf);
+
+/// Event fired to export the active sort's glyph as an SVG file
+#[derive(Event)]
+pub struct ExportGlyphSvgEvent;
+
+/// Event fired to export every glyph in the font as individual SVG files
+#[derive(Event)]
+pub struct ExportAllGlyphsSvgEvent;
+
+/// Event fired to write the font's glyph metrics as CSV and JSON reports
+#[derive(Event)]
+pub struct ExportGlyphReportEvent;
+
+/// Event fired to import an SVG file into the active sort's glyph
 #[derive(Event)]
-pub struct ExportTTFEvent;
+pub struct ImportGlyphSvgEvent {
+    pub path: PathBuf,
+}
 
 /// Event fired when a file action completes (for screen flash feedback)
 #[derive(Event, Clone)]
@@ -106,6 +139,38 @@ pub struct FileMenuState {
     pub initialized: bool,
 }
 
+/// Whether the export dialog is open, and the profile picked in it
+#[derive(Resource, Default)]
+pub struct ExportDialogState {
+    pub open: bool,
+    pub selected_profile: Option<String>,
+}
+
+/// The export running in the background, if any
+#[derive(Resource, Default)]
+pub struct ExportJob {
+    task: Option<Task<anyhow::Result<ExportReport>>>,
+    progress: Option<Arc<ExportProgress>>,
+}
+
+impl ExportJob {
+    pub fn is_running(&self) -> bool {
+        self.task.is_some()
+    }
+
+    pub fn progress(&self) -> Option<&ExportProgress> {
+        self.progress.as_deref()
+    }
+
+    /// Stop the running export once the font being built is written
+    pub fn cancel(&self) {
+        if let Some(progress) = &self.progress {
+            info!("📦 Cancelling export");
+            progress.cancel();
+        }
+    }
+}
+
 // ============================================================================
 // PLUGIN
 // ============================================================================
@@ -115,16 +180,30 @@ pub struct FileMenuPlugin;
 impl Plugin for FileMenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<SaveFileEvent>()
-            .add_event::<ExportTTFEvent>()
+            .add_event::<ExportFontEvent>()
+            .add_event::<BuildFamilyEvent>()
+            .add_event::<ExportGlyphSvgEvent>()
+            .add_event::<ExportAllGlyphsSvgEvent>()
+            .add_event::<ExportGlyphReportEvent>()
+            .add_event::<ImportGlyphSvgEvent>()
+            .add_event::<FileActionCompleteEvent>()
             .init_resource::<FileInfo>()
+            .init_resource::<ExportDialogState>()
+            .init_resource::<ExportJob>()
             .insert_resource(FileMenuState { initialized: false })
             .add_systems(Startup, setup_file_menu)
-            .add_systems(PreUpdate, handle_keyboard_shortcuts)
+            .add_systems(PreUpdate, (handle_keyboard_shortcuts, handle_dropped_svg_files))
             .add_systems(
                 Update,
                 (
                     handle_save_file_events,
-                    handle_export_ttf_events,
+                    handle_export_font_events,
+                    handle_build_family_events,
+                    finish_export_job,
+                    handle_export_glyph_svg_events,
+                    handle_export_all_glyphs_svg_events,
+                    handle_export_glyph_report_events,
+                    handle_import_glyph_svg_events,
                     update_save_state,
                     trigger_screen_flash_on_file_action,
                 ),
@@ -148,7 +227,11 @@ fn setup_file_menu(
         // Initialize keyboard-based file menu
         debug!("✅ File menu initialized with cross-platform keyboard shortcuts:");
         debug!("   💾 Save: Cmd+S (macOS) or Ctrl+S (Windows/Linux)");
-        debug!("   📦 Export TTF: Cmd+E (macOS) or Ctrl+E (Windows/Linux)");
+        debug!("   📦 Export dialog: Cmd+E (macOS) or Ctrl+E (Windows/Linux)");
+        debug!("   🖼 Export glyph SVG: Cmd+Shift+E / Ctrl+Shift+E");
+        debug!("   🖼 Export all glyph SVGs: Cmd+Alt+E / Ctrl+Alt+E");
+        debug!("   📊 Export glyph metrics report: Cmd+Alt+Shift+E / Ctrl+Alt+Shift+E");
+        debug!("   📥 Import SVG: drop an .svg file onto the window");
         debug!("   ⚡ Reliable keyboard shortcuts work on all platforms");
 
         file_menu_state.initialized = true;
@@ -162,7 +245,11 @@ fn setup_file_menu(
 /// Handles keyboard shortcuts for file operations
 fn handle_keyboard_shortcuts(
     mut save_events: EventWriter<SaveFileEvent>,
-    mut export_events: EventWriter<ExportTTFEvent>,
+    mut export_events: EventWriter<ExportFontEvent>,
+    mut export_dialog: ResMut<ExportDialogState>,
+    mut svg_export_events: EventWriter<ExportGlyphSvgEvent>,
+    mut svg_batch_events: EventWriter<ExportAllGlyphsSvgEvent>,
+    mut report_events: EventWriter<ExportGlyphReportEvent>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     file_menu_state: Res<FileMenuState>,
 ) {
@@ -171,26 +258,33 @@ fn handle_keyboard_shortcuts(
     }
 
     // Handle Cmd+S (macOS) or Ctrl+S (Windows/Linux)
-    let cmd_or_ctrl = keyboard_input.pressed(KeyCode::SuperLeft)
-        || keyboard_input.pressed(KeyCode::SuperRight)
-        || keyboard_input.pressed(KeyCode::ControlLeft)
-        || keyboard_input.pressed(KeyCode::ControlRight);
-
-    if cmd_or_ctrl && keyboard_input.just_pressed(KeyCode::KeyS) {
+    if Shortcut::Save.just_pressed(&keyboard_input) {
         debug!("💾 Save shortcut triggered (Cmd+S/Ctrl+S)");
         save_events.write(SaveFileEvent);
     }
 
     // Handle Cmd+E (macOS) or Ctrl+E (Windows/Linux) for export
-    if cmd_or_ctrl && keyboard_input.just_pressed(KeyCode::KeyE) {
-        debug!("📦 Export TTF shortcut triggered (Cmd+E/Ctrl+E)");
-        export_events.write(ExportTTFEvent);
+    if Shortcut::ExportGlyphReport.just_pressed(&keyboard_input) {
+        debug!("📊 Export glyph metrics report triggered (Cmd+Alt+Shift+E/Ctrl+Alt+Shift+E)");
+        report_events.write(ExportGlyphReportEvent);
+    }
+    if Shortcut::ExportAllGlyphsSvg.just_pressed(&keyboard_input) {
+        debug!("🖼 Export all glyphs as SVG triggered (Cmd+Alt+E/Ctrl+Alt+E)");
+        svg_batch_events.write(ExportAllGlyphsSvgEvent);
+    }
+    if Shortcut::ExportGlyphSvg.just_pressed(&keyboard_input) {
+        debug!("🖼 Export glyph SVG triggered (Cmd+Shift+E/Ctrl+Shift+E)");
+        svg_export_events.write(ExportGlyphSvgEvent);
+    }
+    if Shortcut::ExportDialog.just_pressed(&keyboard_input) {
+        debug!("📦 Export dialog toggled (Cmd+E/Ctrl+E)");
+        export_dialog.open = !export_dialog.open;
     }
 
     // TEMPORARY: Also trigger export with F5 key for testing
     if keyboard_input.just_pressed(KeyCode::F5) {
-        debug!("📦 Export TTF triggered via F5 (temporary test)");
-        export_events.write(ExportTTFEvent);
+        debug!("📦 Export triggered via F5 (temporary test)");
+        export_events.write(ExportFontEvent(export_dialog.selected_profile.clone()));
     }
 }
 
@@ -701,218 +795,396 @@ fn rotate_points_to_start(
     result
 }
 
-/// Handles export to TTF events
-fn handle_export_ttf_events(
-    mut export_events: EventReader<ExportTTFEvent>,
-    mut file_info: ResMut<FileInfo>,
-    #[cfg(feature = "tui")] tui_comm: Option<Res<crate::core::tui_communication::TuiCommunication>>,
+/// Handles export events with the named export profile, or the first one,
+/// by starting the export in the background
+fn handle_export_font_events(
+    mut export_events: EventReader<ExportFontEvent>,
+    file_info: Res<FileInfo>,
+    mut job: ResMut<ExportJob>,
+    mut dialog: ResMut<ExportDialogState>,
+    app_state: Option<Res<crate::core::state::AppState>>,
 ) {
-    for _ in export_events.read() {
-        debug!("🚀🚀🚀 EXPORT EVENT RECEIVED! 🚀🚀🚀");
+    for ExportFontEvent(profile_name) in export_events.read() {
+        if job.is_running() {
+            warn!("Cannot export: an export is already running");
+            continue;
+        }
+        let Some(state) = app_state.as_deref() else {
+            warn!("Cannot export: no font loaded");
+            continue;
+        };
+        let workspace = &state.workspace;
+        let source = if file_info.designspace_path.is_empty() {
+            workspace.font.path.clone()
+        } else {
+            Some(PathBuf::from(&file_info.designspace_path))
+        };
+        let Some(source) = source else {
+            warn!("Cannot export: the font has not been saved yet");
+            continue;
+        };
+        let Some(profile) = workspace.font.export_profiles.get(profile_name.as_deref()) else {
+            warn!(
+                "Cannot export: no export profile named '{}'",
+                profile_name.as_deref().unwrap_or_default()
+            );
+            continue;
+        };
 
-        // Always update the export time to show the feature is working
-        file_info.last_exported = Some(std::time::SystemTime::now());
-        debug!("✅ Updated export timestamp in UI");
+        info!("📦 Exporting {} with profile '{}'", source.display(), profile.name);
+        let profile = profile.clone();
+        let font = workspace.font.clone();
+        let info = workspace.info.clone();
+        let progress = Arc::new(ExportProgress::default());
+        let task_progress = Arc::clone(&progress);
+        job.task = Some(AsyncComputeTaskPool::get().spawn(async move {
+            export_project(&source, &profile, &font, &info, &task_progress)
+        }));
+        job.progress = Some(progress);
+        dialog.set_changed();
+    }
+}
 
-        if file_info.designspace_path.is_empty() {
-            warn!("Cannot export: No designspace file loaded");
-            warn!("But the export system is working - timestamp updated!");
+/// Handles family build events by exporting the family in the background,
+/// as one export
+fn handle_build_family_events(
+    mut family_events: EventReader<BuildFamilyEvent>,
+    mut job: ResMut<ExportJob>,
+    mut dialog: ResMut<ExportDialogState>,
+) {
+    for BuildFamilyEvent(project) in family_events.read() {
+        if job.is_running() {
+            warn!("Cannot build the family: an export is already running");
             continue;
         }
+        info!("📦 Building family {}", project.display());
+        let project = project.clone();
+        let progress = Arc::new(ExportProgress::default());
+        let task_progress = Arc::clone(&progress);
+        job.task = Some(AsyncComputeTaskPool::get().spawn(async move {
+            build_family(&project, &task_progress)
+        }));
+        job.progress = Some(progress);
+        dialog.set_changed();
+    }
+}
 
-        debug!(
-            "🚀 Starting TTF export from designspace: {}",
-            file_info.designspace_path
-        );
-
-        // Get the directory of the designspace file
-        let designspace_path = PathBuf::from(&file_info.designspace_path);
-        let default_dir = PathBuf::from(".");
-        let output_dir = designspace_path.parent().unwrap_or(&default_dir);
-
-        // Create temporary build directory
-        let build_dir = output_dir.join(".fontc-build");
-        if !build_dir.exists() {
-            if let Err(e) = std::fs::create_dir(&build_dir) {
-                error!("Failed to create build directory: {}", e);
-                return;
-            }
+/// Picks up a finished background export and reports what it wrote
+#[allow(clippy::too_many_arguments)]
+fn finish_export_job(
+    mut job: ResMut<ExportJob>,
+    mut file_info: ResMut<FileInfo>,
+    mut dialog: ResMut<ExportDialogState>,
+    mut inspector: ResMut<BuildInspector>,
+    mut compile_errors: ResMut<CompileErrors>,
+    app_state: Option<Res<crate::core::state::AppState>>,
+    mut complete_events: EventWriter<FileActionCompleteEvent>,
+    #[cfg(feature = "tui")] tui_comm: Option<Res<crate::core::tui_communication::TuiCommunication>>,
+) {
+    let Some(task) = job.task.as_mut() else {
+        return;
+    };
+    let Some(result) = block_on(future::poll_once(task)) else {
+        return;
+    };
+    job.task = None;
+    job.progress = None;
+    dialog.set_changed();
+
+    let report = match result {
+        Ok(report) => report,
+        Err(e) => {
+            error!("❌ Export failed: {:#}", e);
+            compile_errors.report(CompileBuild::Export, &format!("{e:#}"), app_state.as_deref());
+            return;
         }
+    };
+    compile_errors.clear(CompileBuild::Export);
+    for build in &report.builds {
+        debug!("🧱 {}", build);
+    }
+    for warning in &report.warnings {
+        warn!("⚠️ {}", warning);
+    }
+    for check in &report.checks {
+        if check.problems.is_empty() {
+            info!("🔍 {}", check.summary());
+        } else {
+            warn!("⚠️ {}", check.summary());
+        }
+    }
+    if report.cancelled {
+        warn!("⚠️ Export cancelled after {} font file(s)", report.written.len());
+    }
+    if report.written.is_empty() {
+        warn!("⚠️ No font files were exported");
+        return;
+    }
 
-        // Load the designspace to understand what instances we expect
-        let ds = match DesignSpaceDocument::load(&designspace_path) {
-            Ok(ds) => ds,
-            Err(e) => {
-                error!("Failed to load designspace: {}", e);
-                return;
-            }
+    info!("📁 Exported {} font file(s)", report.written.len());
+    for path in &report.written {
+        debug!("   - {}", path.display());
+    }
+    file_info.last_exported = Some(std::time::SystemTime::now());
+    dialog.open = false;
+    inspector.inspect(report.inspections.clone());
+    complete_events.write(FileActionCompleteEvent {
+        action_type: FileActionType::Export,
+    });
+
+    #[cfg(feature = "tui")]
+    if let Some(tui) = &tui_comm {
+        let mut message = if report.written.len() == 1 {
+            "Exported 1 font".to_string()
+        } else {
+            format!("Exported {} fonts", report.written.len())
         };
-
-        debug!("📋 Found {} instances in designspace", ds.instances.len());
-        for instance in &ds.instances {
-            let style_name = instance.stylename.as_deref().unwrap_or("Regular");
-            debug!("   - {}", style_name);
+        let flagged = report
+            .checks
+            .iter()
+            .filter(|check| !check.problems.is_empty() || !check.changes.is_empty())
+            .count();
+        if flagged > 0 {
+            message.push_str(&format!(", {flagged} with findings (see log)"));
+        }
+        if report.cancelled {
+            message.push_str(" before it was cancelled");
         }
+        let dir = report.written[0].parent().map(|dir| dir.display().to_string());
+        tui.send_file_action(message, dir);
+        tui.send_build_inspections(report.inspections);
+    }
+}
 
-        // Create input from the designspace path
-        let input = match fontc::Input::new(&designspace_path) {
-            Ok(input) => input,
-            Err(e) => {
-                error!("Failed to create fontc input: {}", e);
-                return;
+// ============================================================================
+// SVG IMPORT / EXPORT
+// ============================================================================
+
+/// Directory that SVG exports are written to: an `svg` folder next to the
+/// loaded UFO, or the working directory if the font has no path yet
+fn svg_export_dir(app_state: &crate::core::state::AppState) -> PathBuf {
+    app_state
+        .workspace
+        .font
+        .path
+        .as_ref()
+        .and_then(|path| path.parent())
+        .unwrap_or_else(|| std::path::Path::new("."))
+        .join("svg")
+}
+
+/// Turns SVG files dropped onto the window into import events
+fn handle_dropped_svg_files(
+    mut drop_events: EventReader<FileDragAndDrop>,
+    mut import_events: EventWriter<ImportGlyphSvgEvent>,
+) {
+    for event in drop_events.read() {
+        if let FileDragAndDrop::DroppedFile { path_buf, .. } = event {
+            let is_svg = path_buf
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+            if is_svg {
+                import_events.write(ImportGlyphSvgEvent {
+                    path: path_buf.clone(),
+                });
+            } else if SpacingFormat::from_path(path_buf).is_none() {
+                debug!("Ignoring dropped file that is not an SVG: {}", path_buf.display());
             }
+        }
+    }
+}
+
+/// Exports the active sort's glyph to `<ufo dir>/svg/<glyph>.svg`
+fn handle_export_glyph_svg_events(
+    mut events: EventReader<ExportGlyphSvgEvent>,
+    app_state: Option<Res<crate::core::state::AppState>>,
+    active_sort_query: Query<&crate::editing::sort::Sort, With<crate::editing::sort::ActiveSort>>,
+    #[cfg(feature = "tui")] tui_comm: Option<Res<crate::core::tui_communication::TuiCommunication>>,
+) {
+    for _ in events.read() {
+        let Some(state) = app_state.as_ref() else {
+            warn!("Cannot export SVG: no font loaded");
+            continue;
+        };
+        let Ok(sort) = active_sort_query.single() else {
+            warn!("Cannot export SVG: no active sort");
+            continue;
+        };
+        let Some(glyph) = state.workspace.font.get_glyph(&sort.glyph_name) else {
+            warn!("Cannot export SVG: glyph '{}' not found", sort.glyph_name);
+            continue;
         };
 
-        // Extract family name from the first source or instance
-        let family_name = ds
-            .sources
-            .first()
-            .and_then(|s| s.familyname.as_ref())
-            .or_else(|| ds.instances.first().and_then(|i| i.familyname.as_ref()))
-            .cloned()
-            .unwrap_or_else(|| "Font".to_string());
-
-        let mut exported_files = Vec::new();
-
-        // First, compile the variable font
-        debug!("🔨 Compiling variable font with fontc...");
-        let flags = fontc::Flags::default();
-
-        match fontc::generate_font(&input, &build_dir, None, flags, false) {
-            Ok(font_bytes) => {
-                debug!("✅ Variable font compilation completed!");
-
-                let output_filename = format!("{}-Variable.ttf", family_name.replace(" ", ""));
-                let output_path = output_dir.join(&output_filename);
-
-                // Write the variable font bytes to file
-                match std::fs::write(&output_path, &font_bytes) {
-                    Ok(_) => {
-                        debug!("📁 Exported variable font: {}", output_filename);
-                        exported_files.push(output_filename);
-                    }
-                    Err(e) => {
-                        error!("Failed to write variable font file: {}", e);
-                    }
+        let dir = svg_export_dir(state);
+        match crate::data::svg::export_glyphs_to_dir([glyph], &state.workspace.info, &dir) {
+            Ok(_) => {
+                let path = dir.join(crate::data::svg::glyph_svg_file_name(&glyph.name));
+                info!("🖼 Exported glyph '{}' to {}", glyph.name, path.display());
+
+                #[cfg(feature = "tui")]
+                if let Some(tui) = &tui_comm {
+                    tui.send_file_action(
+                        format!("Exported {}.svg", glyph.name),
+                        Some(path.display().to_string()),
+                    );
                 }
             }
-            Err(e) => {
-                error!("❌ Variable font compilation failed: {}", e);
-            }
+            Err(e) => error!("Failed to export glyph '{}' as SVG: {}", glyph.name, e),
         }
+    }
+}
 
-        // Now generate static instances for Regular and Bold
-        debug!("🔨 Generating static font instances...");
-
-        // For each desired static instance (Regular and Bold)
-        for instance in &ds.instances {
-            let style_name = instance.stylename.as_deref().unwrap_or("Regular");
+/// Exports every glyph in the font as an SVG, for icon-font workflows
+fn handle_export_all_glyphs_svg_events(
+    mut events: EventReader<ExportAllGlyphsSvgEvent>,
+    app_state: Option<Res<crate::core::state::AppState>>,
+    #[cfg(feature = "tui")] tui_comm: Option<Res<crate::core::tui_communication::TuiCommunication>>,
+) {
+    for _ in events.read() {
+        let Some(state) = app_state.as_ref() else {
+            warn!("Cannot export SVGs: no font loaded");
+            continue;
+        };
 
-            // Only export Regular and Bold static instances
-            if style_name != "Regular" && style_name != "Bold" {
-                continue;
+        let dir = svg_export_dir(state);
+        let glyphs = state.workspace.font.glyphs.values();
+        match crate::data::svg::export_glyphs_to_dir(glyphs, &state.workspace.info, &dir) {
+            Ok(count) => {
+                info!("🖼 Exported {} glyph SVG(s) to {}", count, dir.display());
+
+                #[cfg(feature = "tui")]
+                if let Some(tui) = &tui_comm {
+                    tui.send_file_action(
+                        format!("Exported {count} glyph SVGs"),
+                        Some(dir.display().to_string()),
+                    );
+                }
             }
+            Err(e) => error!("Failed to export glyph SVGs: {}", e),
+        }
+    }
+}
 
-            debug!("   Generating static instance: {}", style_name);
-
-            // Find the appropriate source UFO based on the instance location
-            // For non-interpolated instances, find the matching source
-            let source_ufo_path = if let Some(source) = ds.sources.iter().find(|s| {
-                // Match by stylename if available, otherwise use the first source
-                s.stylename.as_deref() == Some(style_name)
-            }) {
-                output_dir.join(&source.filename)
-            } else if !ds.sources.is_empty() {
-                // Fallback to first source if no exact match
-                output_dir.join(&ds.sources[0].filename)
-            } else {
-                warn!("No source UFO found for instance: {}", style_name);
-                continue;
-            };
+/// Writes `<font>-glyphs.csv` and `<font>-glyphs.json` next to the font
+fn handle_export_glyph_report_events(
+    mut events: EventReader<ExportGlyphReportEvent>,
+    app_state: Option<Res<crate::core::state::AppState>>,
+    #[cfg(feature = "tui")]
+    tui_comm: Option<Res<crate::core::tui_communication::TuiCommunication>>,
+) {
+    use crate::data::glyph_report::{glyph_report, write_glyph_report};
 
-            // If the source UFO exists, compile it directly
-            if source_ufo_path.exists() {
-                match fontc::Input::new(&source_ufo_path) {
-                    Ok(static_input) => {
-                        match fontc::generate_font(&static_input, &build_dir, None, flags, false) {
-                            Ok(static_font_bytes) => {
-                                let instance_filename = format!(
-                                    "{}-{}.ttf",
-                                    family_name.replace(" ", ""),
-                                    style_name.replace(" ", "")
-                                );
-                                let instance_path = output_dir.join(&instance_filename);
-
-                                match std::fs::write(&instance_path, &static_font_bytes) {
-                                    Ok(_) => {
-                                        debug!(
-                                            "   ✅ Exported static instance: {}",
-                                            instance_filename
-                                        );
-                                        exported_files.push(instance_filename);
-                                    }
-                                    Err(e) => {
-                                        error!("   Failed to write static font: {}", e);
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                error!(
-                                    "   Failed to compile static instance {}: {}",
-                                    style_name, e
-                                );
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        error!("   Failed to create input for static instance: {}", e);
+    for _ in events.read() {
+        let Some(state) = app_state.as_ref() else {
+            warn!("Cannot export a glyph report: no font loaded");
+            continue;
+        };
+        let font = &state.workspace.font;
+        let font_path = font.path.clone().unwrap_or_else(|| PathBuf::from("font.ufo"));
+        let stem = font_path.file_stem().unwrap_or_default().to_string_lossy();
+        let source = font_path.file_name().unwrap_or_default().to_string_lossy();
+        let rows = glyph_report(font, &source);
+
+        for extension in ["csv", "json"] {
+            let path = font_path.with_file_name(format!("{stem}-glyphs.{extension}"));
+            match write_glyph_report(&rows, &path) {
+                Ok(()) => {
+                    info!("📊 Wrote {} glyph rows to {}", rows.len(), path.display());
+
+                    #[cfg(feature = "tui")]
+                    if let Some(tui) = &tui_comm {
+                        tui.send_file_action(
+                            "Exported glyph report".to_string(),
+                            Some(path.display().to_string()),
+                        );
                     }
                 }
-            } else {
-                debug!(
-                    "   Source UFO not found for {}, skipping static instance",
-                    style_name
-                );
+                Err(e) => error!("Failed to export the glyph report: {:#}", e),
             }
         }
+    }
+}
 
-        if !exported_files.is_empty() {
-            debug!(
-                "📁 Successfully exported {} font file(s) to: {}",
-                exported_files.len(),
-                output_dir.display()
-            );
-            for file in &exported_files {
-                debug!("   - {}", file);
-            }
+/// Imports an SVG file's outlines into the active sort's glyph
+fn handle_import_glyph_svg_events(
+    mut events: EventReader<ImportGlyphSvgEvent>,
+    mut app_state: Option<ResMut<crate::core::state::AppState>>,
+    active_sort_query: Query<&crate::editing::sort::Sort, With<crate::editing::sort::ActiveSort>>,
+    mut app_state_changed: EventWriter<crate::editing::selection::events::AppStateChanged>,
+    mut visual_update_tracker: ResMut<crate::rendering::glyph_renderer::SortVisualUpdateTracker>,
+) {
+    for event in events.read() {
+        let Some(state) = app_state.as_mut() else {
+            warn!("Cannot import SVG: no font loaded");
+            continue;
+        };
+        let Ok(sort) = active_sort_query.single() else {
+            warn!("Cannot import SVG: select a sort to import into first");
+            continue;
+        };
 
-            // Update the last exported time
-            file_info.last_exported = Some(std::time::SystemTime::now());
+        let svg = match std::fs::read_to_string(&event.path) {
+            Ok(svg) => svg,
+            Err(e) => {
+                error!("Failed to read {}: {}", event.path.display(), e);
+                continue;
+            }
+        };
+        let contours = match crate::data::svg::svg_to_glyph_contours(&svg, &state.workspace.info)
+        {
+            Ok(contours) => contours,
+            Err(e) => {
+                error!("Failed to import {}: {}", event.path.display(), e);
+                continue;
+            }
+        };
 
-            #[cfg(feature = "tui")]
-            if let Some(tui) = &tui_comm {
-                let message = if exported_files.len() == 1 {
-                    format!("Exported {} font", exported_files.len())
-                } else {
-                    format!("Exported {} fonts", exported_files.len())
-                };
-                tui.send_file_action(message, Some(output_dir.display().to_string()));
+        // Keep the original artwork so color exports can embed it verbatim
+        if let Some(ufo_path) = &state.workspace.font.path {
+            let artwork_path = crate::data::svg_table::artwork_path(ufo_path, &sort.glyph_name);
+            let saved = artwork_path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| std::fs::write(&artwork_path, &svg));
+            if let Err(e) = saved {
+                warn!("Could not keep SVG artwork for '{}': {}", sort.glyph_name, e);
             }
-        } else {
-            warn!("⚠️ No font files were exported");
         }
 
-        // Clean up build directory
-        let _ = std::fs::remove_dir_all(&build_dir);
+        let Some(glyph) = state.workspace.font.editing_glyph_mut(&sort.glyph_name) else {
+            warn!("Cannot import SVG: glyph '{}' not found", sort.glyph_name);
+            continue;
+        };
+
+        let count = contours.len();
+        glyph
+            .outline
+            .get_or_insert_with(|| crate::core::state::OutlineData {
+                contours: Vec::new(),
+            })
+            .contours
+            .extend(contours);
+
+        info!(
+            "📥 Imported {} contour(s) from {} into '{}'",
+            count,
+            event.path.display(),
+            sort.glyph_name
+        );
+        app_state_changed.write(crate::editing::selection::events::AppStateChanged);
+        visual_update_tracker.needs_update = true;
     }
 }
 
 fn trigger_screen_flash_on_file_action(
     mut save_events: EventReader<SaveFileEvent>,
-    mut export_events: EventReader<ExportTTFEvent>,
+    mut complete_events: EventReader<FileActionCompleteEvent>,
     mut screen_flash: ResMut<crate::ui::screen_flash::ScreenFlash>,
 ) {
-    if save_events.read().next().is_some() || export_events.read().next().is_some() {
+    let exported = complete_events
+        .read()
+        .any(|event| event.action_type == FileActionType::Export);
+    if save_events.read().next().is_some() || exported {
         screen_flash.trigger();
     }
 }
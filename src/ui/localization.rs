@@ -0,0 +1,97 @@
+//! Localized UI text and right-to-left layout
+//!
+//! Labels spawned with `LocalizedText` are looked up in the `i18n` catalogs
+//! and looked up again whenever the language changes, so panes built at
+//! startup don't have to be rebuilt. Nodes marked `MirrorForRtl` lay their
+//! rows out right to left, and hug the right edge, while the language is
+//! written that way (Arabic, Persian, Hebrew, Urdu).
+
+use crate::core::config::preferences::apply_preferences;
+use crate::core::config::BezySettings;
+use crate::i18n;
+use bevy::prelude::*;
+
+/// A label showing the catalog message with this id
+#[derive(Component, Clone, Copy)]
+pub struct LocalizedText(pub &'static str);
+
+impl LocalizedText {
+    /// The label's `Text` in the current language, to spawn along with it
+    pub fn text(&self) -> Text {
+        Text::new(i18n::tr(self.0))
+    }
+}
+
+/// A row or column whose direction follows the language's
+#[derive(Component, Default)]
+pub struct MirrorForRtl;
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                mirror_new_nodes,
+                relabel_on_language_change.after(apply_preferences),
+            ),
+        );
+    }
+}
+
+/// Lay a node out for the current language: rows run right to left and
+/// columns align to the right in right-to-left languages
+pub fn mirror_node(node: &mut Node, rtl: bool) {
+    node.flex_direction = match (node.flex_direction, rtl) {
+        (FlexDirection::Row, true) => FlexDirection::RowReverse,
+        (FlexDirection::RowReverse, false) => FlexDirection::Row,
+        (direction, _) => direction,
+    };
+    node.align_items = match (node.align_items, rtl) {
+        (AlignItems::FlexStart, true) => AlignItems::FlexEnd,
+        (AlignItems::FlexEnd, false) => AlignItems::FlexStart,
+        (align, _) => align,
+    };
+}
+
+/// Lays out nodes spawned while a right-to-left language is on
+fn mirror_new_nodes(mut nodes: Query<&mut Node, Added<MirrorForRtl>>) {
+    if nodes.is_empty() || !i18n::is_rtl() {
+        return;
+    }
+    for mut node in &mut nodes {
+        mirror_node(&mut node, true);
+    }
+}
+
+/// Relabels localized text and flips mirrored layouts when the language
+/// setting changes
+fn relabel_on_language_change(
+    settings: Res<BezySettings>,
+    mut shown_language: Local<Option<String>>,
+    mut texts: Query<(&LocalizedText, &mut Text)>,
+    mut nodes: Query<&mut Node, With<MirrorForRtl>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    let language = i18n::language();
+    if shown_language.as_deref() == Some(language.as_str()) {
+        return;
+    }
+    let first_run = shown_language.is_none();
+    *shown_language = Some(language);
+    if first_run {
+        // Everything spawned so far was spawned in this language
+        return;
+    }
+
+    for (localized, mut text) in &mut texts {
+        text.0 = i18n::tr(localized.0);
+    }
+    let rtl = i18n::is_rtl();
+    for mut node in &mut nodes {
+        mirror_node(&mut node, rtl);
+    }
+}
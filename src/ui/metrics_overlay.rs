@@ -0,0 +1,639 @@
+//! Vertical metrics across all text
+//!
+//! Each sort draws its own metrics lines, stopping at its advance. This
+//! overlay draws the baseline, descender, x-height, cap height and ascender
+//! straight across every line of text instead, so heights can be compared
+//! along the whole text. A tab left of the active sort's line of text (or
+//! the first line) names each height with its value. Dragging a tab moves
+//! that height, and on release a prompt asks before the new value is
+//! written to the font info, after which the sorts reflow to the new
+//! metrics. The baseline can't be dragged. Cmd/Ctrl+Shift+' shows or hides
+//! the overlay.
+
+use crate::core::state::AppState;
+use crate::editing::font_changes::MetricsChanged;
+use crate::editing::selection::events::AppStateChanged;
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::font_source::FontInfo;
+use crate::io::pointer::PointerInfo;
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::cameras::DesignCamera;
+use crate::rendering::mesh_utils::create_lines_mesh;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::ui::edit_mode_toolbar::PresentationMode;
+use crate::ui::theme::*;
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::{AssetServerFontExt, EmbeddedFonts};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// How far the lines reach past the first and last sort of a line, in em
+const OVERHANG: f32 = 0.25;
+
+/// Sorts whose baselines are this close, in design units, share a line
+const SAME_LINE_TOLERANCE: f32 = 0.5;
+
+/// Gap between a tab and the start of its line, in screen pixels
+const TAB_GAP_PX: f32 = 8.0;
+
+/// Height of a tab, in screen pixels
+const TAB_HEIGHT: f32 = 18.0;
+
+/// Padding inside tabs and prompt buttons
+const TAB_PADDING: f32 = 4.0;
+
+/// Border width of the prompt buttons
+const BUTTON_BORDER: f32 = 2.0;
+
+/// Above the sorts' metrics lines and below the guidelines
+const OVERLAY_Z: f32 = 5.5;
+
+/// A height from the font info that can be dragged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerticalMetric {
+    Descender,
+    XHeight,
+    CapHeight,
+    Ascender,
+}
+
+impl VerticalMetric {
+    pub const ALL: [Self; 4] = [
+        Self::Descender,
+        Self::XHeight,
+        Self::CapHeight,
+        Self::Ascender,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Descender => "descender",
+            Self::XHeight => "x-height",
+            Self::CapHeight => "cap height",
+            Self::Ascender => "ascender",
+        }
+    }
+
+    /// The font's value, or the one the metrics lines use while it's unset
+    pub fn value(self, info: &FontInfo) -> f64 {
+        let upm = info.units_per_em;
+        match self {
+            Self::Descender => info.descender.unwrap_or(upm * -0.2),
+            Self::XHeight => info.x_height.unwrap_or(upm * 0.5),
+            Self::CapHeight => info.cap_height.unwrap_or(upm * 0.7),
+            Self::Ascender => info.ascender.unwrap_or(upm * 0.8),
+        }
+    }
+
+    /// Writes the value to the font info and to its copy in the metrics
+    pub fn set(self, info: &mut FontInfo, value: f64) {
+        let (field, metrics_field) = match self {
+            Self::Descender => (&mut info.descender, &mut info.metrics.descender),
+            Self::XHeight => (&mut info.x_height, &mut info.metrics.x_height),
+            Self::CapHeight => (&mut info.cap_height, &mut info.metrics.cap_height),
+            Self::Ascender => (&mut info.ascender, &mut info.metrics.ascender),
+        };
+        *field = Some(value);
+        *metrics_field = Some(value);
+    }
+}
+
+/// A new value for one of the heights
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricChange {
+    pub metric: VerticalMetric,
+    pub from: f64,
+    pub to: f64,
+}
+
+/// Whether the overlay is shown
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct MetricsOverlay {
+    pub visible: bool,
+}
+
+/// The height being dragged, or dropped and waiting to be confirmed
+#[derive(Resource, Default)]
+pub struct MetricsOverlayEdit {
+    /// The change so far and the baseline it's dragged from
+    dragging: Option<(MetricChange, f32)>,
+    pub pending: Option<MetricChange>,
+}
+
+impl MetricsOverlayEdit {
+    /// The value shown for a metric while it's dragged or waiting
+    fn shown_value(&self, metric: VerticalMetric) -> Option<f64> {
+        self.dragging
+            .map(|(change, _)| change)
+            .or(self.pending)
+            .filter(|change| change.metric == metric)
+            .map(|change| change.to)
+    }
+}
+
+/// A line of text: its baseline and how far its sorts reach
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextLine {
+    pub baseline: f32,
+    pub left: f32,
+    pub right: f32,
+}
+
+/// The lines of text of the sorts, by position and advance, top to bottom
+pub fn text_lines(sorts: impl IntoIterator<Item = (Vec2, f32)>) -> Vec<TextLine> {
+    let mut sorts: Vec<(Vec2, f32)> = sorts.into_iter().collect();
+    sorts.sort_by(|a, b| b.0.y.total_cmp(&a.0.y));
+    let mut lines: Vec<TextLine> = Vec::new();
+    for (origin, advance) in sorts {
+        let (left, right) = (origin.x, origin.x + advance);
+        match lines.last_mut() {
+            Some(line) if (line.baseline - origin.y).abs() <= SAME_LINE_TOLERANCE => {
+                line.left = line.left.min(left);
+                line.right = line.right.max(right);
+            }
+            _ => lines.push(TextLine {
+                baseline: origin.y,
+                left,
+                right,
+            }),
+        }
+    }
+    lines
+}
+
+/// The lines of text in the font, and the one the tabs are on
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+struct OverlayLines {
+    lines: Vec<TextLine>,
+    anchor: usize,
+}
+
+impl OverlayLines {
+    fn anchor(&self) -> Option<&TextLine> {
+        self.lines.get(self.anchor)
+    }
+}
+
+/// What the overlay was last drawn for
+#[derive(Debug, Clone, PartialEq)]
+struct DrawnOverlay {
+    lines: Vec<TextLine>,
+    /// Heights above the baseline, and whether each is being edited
+    heights: Vec<(f32, bool)>,
+    overhang: f32,
+    line_width: f32,
+}
+
+/// Marker for the overlay meshes
+#[derive(Component)]
+struct MetricsOverlayMesh;
+
+/// The tab for dragging a height
+#[derive(Component)]
+struct MetricTab(VerticalMetric);
+
+/// The label in a tab
+#[derive(Component)]
+struct MetricTabLabel(VerticalMetric);
+
+/// The prompt confirming a dropped height
+#[derive(Component, Default)]
+struct MetricPrompt;
+
+#[derive(Component)]
+struct MetricPromptLabel;
+
+/// What a prompt button does
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum PromptButton {
+    Apply,
+    Cancel,
+}
+
+pub struct MetricsOverlayPlugin;
+
+impl Plugin for MetricsOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MetricsOverlay>()
+            .init_resource::<MetricsOverlayEdit>()
+            .init_resource::<OverlayLines>()
+            .add_systems(Startup, spawn_metric_tabs)
+            .add_systems(
+                Update,
+                (
+                    toggle_metrics_overlay,
+                    update_overlay_lines,
+                    drag_metric_tabs,
+                    handle_prompt_buttons,
+                    update_metric_tabs,
+                    update_metric_prompt,
+                    render_metrics_overlay,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn spawn_metric_tabs(
+    mut commands: Commands,
+    theme: Res<CurrentTheme>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+) {
+    let text_font = TextFont {
+        font: asset_server.load_font_with_fallback(theme.theme().mono_font_path(), &embedded_fonts),
+        font_size: WIDGET_TEXT_FONT_SIZE,
+        ..default()
+    };
+    let text_color = TextColor(theme.get_ui_text_primary());
+
+    for metric in VerticalMetric::ALL {
+        commands
+            .spawn((
+                Button,
+                Node {
+                    position_type: PositionType::Absolute,
+                    height: Val::Px(TAB_HEIGHT),
+                    padding: UiRect::horizontal(Val::Px(TAB_PADDING)),
+                    align_items: AlignItems::Center,
+                    display: Display::None,
+                    ..default()
+                },
+                BackgroundColor(theme.theme().widget_background_color()),
+                MetricTab(metric),
+                Name::new(format!("{metric:?}Tab")),
+            ))
+            .with_child((
+                Text::new(metric.label()),
+                text_font.clone(),
+                text_color,
+                MetricTabLabel(metric),
+            ));
+    }
+
+    let position = UiRect {
+        left: Val::Percent(40.0),
+        top: Val::Px(theme.theme().widget_margin()),
+        right: Val::Auto,
+        bottom: Val::Auto,
+    };
+    commands
+        .spawn(create_widget_style(
+            &asset_server,
+            &theme,
+            PositionType::Absolute,
+            position,
+            MetricPrompt,
+            "MetricPrompt",
+        ))
+        .insert(Visibility::Hidden)
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                text_font.clone(),
+                text_color,
+                MetricPromptLabel,
+            ));
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(TAB_PADDING),
+                    ..default()
+                })
+                .with_children(|row| {
+                    for (label, button) in [
+                        ("apply", PromptButton::Apply),
+                        ("cancel", PromptButton::Cancel),
+                    ] {
+                        row.spawn((
+                            Button,
+                            Node {
+                                padding: UiRect::all(Val::Px(TAB_PADDING)),
+                                border: UiRect::all(Val::Px(BUTTON_BORDER)),
+                                ..default()
+                            },
+                            BackgroundColor(theme.theme().button_regular()),
+                            BorderColor(theme.theme().button_regular_outline()),
+                            button,
+                        ))
+                        .with_child((
+                            Text::new(label),
+                            text_font.clone(),
+                            text_color,
+                        ));
+                    }
+                });
+        });
+}
+
+/// Cmd/Ctrl+Shift+' shows or hides the overlay
+fn toggle_metrics_overlay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<MetricsOverlay>,
+) {
+    if Shortcut::MetricsOverlay.just_pressed(&keyboard) {
+        overlay.visible = !overlay.visible;
+        debug!("Metrics overlay shown: {}", overlay.visible);
+    }
+}
+
+/// Groups the sorts into lines of text while the overlay is shown
+fn update_overlay_lines(
+    overlay: Res<MetricsOverlay>,
+    sorts: Query<(&Transform, &Sort)>,
+    active_sort: Query<&Transform, With<ActiveSort>>,
+    app_state: Option<Res<AppState>>,
+    mut overlay_lines: ResMut<OverlayLines>,
+) {
+    let Some(state) = app_state.filter(|_| overlay.visible) else {
+        overlay_lines.set_if_neq(OverlayLines::default());
+        return;
+    };
+    let font = &state.workspace.font;
+    let lines = text_lines(sorts.iter().map(|(transform, sort)| {
+        let advance = font
+            .editing()
+            .get_glyph(&sort.glyph_name)
+            .map_or(0.0, |glyph| glyph.advance_width as f32);
+        (transform.translation.truncate(), advance)
+    }));
+    let anchor = active_sort
+        .single()
+        .ok()
+        .and_then(|transform| {
+            let baseline = transform.translation.y;
+            lines
+                .iter()
+                .position(|line| (line.baseline - baseline).abs() <= SAME_LINE_TOLERANCE)
+        })
+        .unwrap_or(0);
+    overlay_lines.set_if_neq(OverlayLines { lines, anchor });
+}
+
+/// Moves a height while its tab is dragged and holds it for confirmation
+/// on release
+fn drag_metric_tabs(
+    mouse: Res<ButtonInput<MouseButton>>,
+    pointer: Res<PointerInfo>,
+    tabs: Query<(&MetricTab, &Interaction), Changed<Interaction>>,
+    overlay_lines: Res<OverlayLines>,
+    app_state: Option<Res<AppState>>,
+    mut edit: ResMut<MetricsOverlayEdit>,
+) {
+    if let Some((change, baseline)) = edit.dragging {
+        let to = (pointer.world.y - baseline).round() as f64;
+        if mouse.just_released(MouseButton::Left) {
+            edit.dragging = None;
+            edit.pending = (to != change.from).then_some(MetricChange { to, ..change });
+        } else if to != change.to {
+            edit.dragging = Some((MetricChange { to, ..change }, baseline));
+        }
+        return;
+    }
+
+    let (Some(state), Some(line)) = (app_state, overlay_lines.anchor()) else {
+        return;
+    };
+    for (MetricTab(metric), interaction) in tabs.iter() {
+        if *interaction == Interaction::Pressed {
+            let from = metric.value(&state.workspace.info);
+            edit.pending = None;
+            edit.dragging = Some((
+                MetricChange {
+                    metric: *metric,
+                    from,
+                    to: from,
+                },
+                line.baseline,
+            ));
+        }
+    }
+}
+
+/// Writes a confirmed height to the font info so the sorts reflow
+fn handle_prompt_buttons(
+    interaction_query: Query<(&Interaction, &PromptButton), Changed<Interaction>>,
+    mut edit: ResMut<MetricsOverlayEdit>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut metrics_changes: EventWriter<MetricsChanged>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(change) = edit.pending.take() else {
+            continue;
+        };
+        if *button == PromptButton::Cancel {
+            continue;
+        }
+        let Some(state) = app_state.as_mut() else {
+            continue;
+        };
+        change.metric.set(&mut state.workspace.info, change.to);
+        info!(
+            "Set the {} from {} to {}",
+            change.metric.label(),
+            change.from,
+            change.to
+        );
+        metrics_changes.write(MetricsChanged);
+        app_state_changed.write(AppStateChanged);
+    }
+}
+
+/// Places the tabs left of the anchor line at their heights
+#[allow(clippy::too_many_arguments)]
+fn update_metric_tabs(
+    overlay_lines: Res<OverlayLines>,
+    edit: Res<MetricsOverlayEdit>,
+    app_state: Option<Res<AppState>>,
+    presentation_mode: Option<Res<PresentationMode>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<DesignCamera>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut tabs: Query<(&MetricTab, &mut Node)>,
+    mut labels: Query<(&MetricTabLabel, &mut Text)>,
+) {
+    let presentation_active = presentation_mode.is_some_and(|pm| pm.active);
+    let view = camera_query.single().ok().zip(window_query.single().ok());
+    let (Some(state), Some(line), Some(((camera, camera_transform), window)), false) =
+        (app_state, overlay_lines.anchor(), view, presentation_active)
+    else {
+        for (_, mut node) in tabs.iter_mut() {
+            if node.display != Display::None {
+                node.display = Display::None;
+            }
+        }
+        return;
+    };
+
+    let info = &state.workspace.info;
+    let start = line.left - info.units_per_em as f32 * OVERHANG;
+    let value = |metric: VerticalMetric| edit.shown_value(metric).unwrap_or(metric.value(info));
+    for (MetricTab(metric), mut node) in tabs.iter_mut() {
+        let height = line.baseline + value(*metric) as f32;
+        let Ok(screen) = camera.world_to_viewport(camera_transform, Vec3::new(start, height, 0.0))
+        else {
+            continue;
+        };
+        let right = Val::Px(window.width() - screen.x + TAB_GAP_PX);
+        let top = Val::Px(screen.y - TAB_HEIGHT / 2.0);
+        if node.display != Display::Flex || node.right != right || node.top != top {
+            node.display = Display::Flex;
+            node.right = right;
+            node.top = top;
+        }
+    }
+    for (MetricTabLabel(metric), mut text) in labels.iter_mut() {
+        let label = format!("{} {}", metric.label(), value(*metric));
+        if text.0 != label {
+            text.0 = label;
+        }
+    }
+}
+
+/// Shows the prompt while a dropped height waits for confirmation
+fn update_metric_prompt(
+    edit: Res<MetricsOverlayEdit>,
+    mut prompt_query: Query<&mut Visibility, With<MetricPrompt>>,
+    mut label_query: Query<&mut Text, With<MetricPromptLabel>>,
+) {
+    if !edit.is_changed() {
+        return;
+    }
+    let target = if edit.pending.is_some() {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    for mut visibility in prompt_query.iter_mut() {
+        *visibility = target;
+    }
+    if let Some(change) = edit.pending {
+        for mut label in label_query.iter_mut() {
+            label.0 = format!(
+                "Set the {} from {} to {}?",
+                change.metric.label(),
+                change.from,
+                change.to
+            );
+        }
+    }
+}
+
+/// Redraws the lines when the text, the heights or the view change
+#[allow(clippy::too_many_arguments)]
+fn render_metrics_overlay(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut drawn: Local<Option<DrawnOverlay>>,
+    overlay_lines: Res<OverlayLines>,
+    edit: Res<MetricsOverlayEdit>,
+    app_state: Option<Res<AppState>>,
+    presentation_mode: Option<Res<PresentationMode>>,
+    camera_scale: Res<CameraResponsiveScale>,
+    theme: Res<CurrentTheme>,
+    existing: Query<Entity, With<MetricsOverlayMesh>>,
+) {
+    let presentation_active = presentation_mode.is_some_and(|pm| pm.active);
+    let Some(state) = app_state.filter(|_| !presentation_active && !overlay_lines.lines.is_empty())
+    else {
+        if drawn.take().is_some() {
+            for entity in existing.iter() {
+                commands.entity(entity).despawn();
+            }
+        }
+        return;
+    };
+
+    let info = &state.workspace.info;
+    let mut heights = vec![(0.0, false)];
+    heights.extend(VerticalMetric::ALL.into_iter().map(|metric| {
+        let shown = edit.shown_value(metric);
+        (shown.unwrap_or(metric.value(info)) as f32, shown.is_some())
+    }));
+    let overlay = DrawnOverlay {
+        lines: overlay_lines.lines.clone(),
+        heights,
+        overhang: info.units_per_em as f32 * OVERHANG,
+        line_width: camera_scale.adjusted_line_width(),
+    };
+    if drawn.as_ref() == Some(&overlay) && !theme.is_changed() {
+        return;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let mut lines: [Vec<(Vec2, Vec2)>; 2] = Default::default();
+    for line in &overlay.lines {
+        for &(height, edited) in &overlay.heights {
+            let y = line.baseline + height;
+            lines[edited as usize].push((
+                Vec2::new(line.left - overlay.overhang, y),
+                Vec2::new(line.right + overlay.overhang, y),
+            ));
+        }
+    }
+    let colors = [
+        theme.theme().metrics_overlay_color(),
+        theme.theme().metrics_overlay_drag_color(),
+    ];
+    for (lines, color) in lines.iter().zip(colors) {
+        if lines.is_empty() {
+            continue;
+        }
+        commands.spawn((
+            MetricsOverlayMesh,
+            Mesh2d(meshes.add(create_lines_mesh(lines, overlay.line_width))),
+            MeshMaterial2d(materials.add(ColorMaterial::from_color(color))),
+            Transform::from_xyz(0.0, 0.0, OVERLAY_Z),
+        ));
+    }
+    *drawn = Some(overlay);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_lines_group_sorts_by_baseline() {
+        let sorts = [
+            (Vec2::new(600.0, 0.0), 500.0),
+            (Vec2::new(0.0, -1200.0), 400.0),
+            (Vec2::new(0.0, 0.0), 600.0),
+            (Vec2::new(400.0, -1200.2), 300.0),
+        ];
+        let lines = text_lines(sorts);
+        assert_eq!(
+            lines,
+            vec![
+                TextLine {
+                    baseline: 0.0,
+                    left: 0.0,
+                    right: 1100.0,
+                },
+                TextLine {
+                    baseline: -1200.0,
+                    left: 0.0,
+                    right: 700.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_metric_updates_both_copies() {
+        let mut info = FontInfo {
+            units_per_em: 1000.0,
+            ..Default::default()
+        };
+        assert_eq!(VerticalMetric::XHeight.value(&info), 500.0);
+        VerticalMetric::XHeight.set(&mut info, 520.0);
+        assert_eq!(info.x_height, Some(520.0));
+        assert_eq!(info.metrics.x_height, Some(520.0));
+    }
+}
@@ -1,9 +1,17 @@
 //! User interface modules for the Bezy font editor
 
+pub mod accessibility;
+pub mod context_menu;
 pub mod edit_mode_toolbar;
 pub mod file_menu;
+pub mod localization;
+pub mod metrics_overlay;
 pub mod panes;
+pub mod performance_overlay;
+pub mod reading_mode;
+pub mod rulers;
 pub mod screen_flash;
+pub mod status_bar;
 pub mod theme;
 pub mod theme_system;
 pub mod themes;
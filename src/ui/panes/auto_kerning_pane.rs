@@ -0,0 +1,268 @@
+//! Auto-Kerning Pane Module
+//!
+//! The review table for kerning suggestions: each pair's current and suggested
+//! value, nudged with −/+ before it is accepted or rejected. Colliding pairs
+//! are marked "!" and can be set in a kerning proof.
+
+use crate::editing::auto_kerning::{AcceptKernEvent, AutoKerning, EditKernEvent, RejectKernEvent};
+use crate::font_source::kerning::{FIRST_GROUP_PREFIX, SECOND_GROUP_PREFIX};
+use crate::i18n;
+use crate::systems::sorts::KernProofRequest;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Most rows shown at once; the rest follow as earlier rows are reviewed
+const MAX_ROWS: usize = 20;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the auto-kerning pane
+#[derive(Component, Default)]
+pub struct AutoKerningPane;
+
+/// Container of the rows, rebuilt when the suggestions change
+#[derive(Component)]
+struct KernSuggestionTable;
+
+/// What a button in the pane does, for one pair of sides or with `None`
+/// for all
+#[derive(Component, Clone)]
+enum KernButton {
+    Accept(Option<(String, String)>),
+    Reject(Option<(String, String)>),
+    Nudge((String, String), f64),
+    /// Proof the glyph pairs of the colliding suggestions
+    ProofCollisions,
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct AutoKerningPanePlugin;
+
+impl Plugin for AutoKerningPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_auto_kerning_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_kern_buttons,
+                    rebuild_kern_rows,
+                    toggle_pane_visibility::<AutoKerningPane, _>(|kerning: Res<AutoKerning>| {
+                        !kerning.suggestions.is_empty()
+                    }),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the auto-kerning pane at its slot
+pub fn spawn_auto_kerning_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::AutoKerning,
+        AutoKerningPane,
+        "AutoKerningPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("auto-kerning-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(KernSuggestionTable));
+    });
+}
+
+/// A row of a label, nudge buttons when it is for one pair, and accept and
+/// reject buttons
+fn spawn_row(
+    parent: &mut ChildSpawnerCommands,
+    label: &str,
+    sides: Option<(String, String)>,
+    rounding: f64,
+    asset_server: &AssetServer,
+    embedded_fonts: &EmbeddedFonts,
+    theme: &CurrentTheme,
+) {
+    parent.spawn(pane_row_node()).with_children(|row| {
+        create_label_text(row, label, (), asset_server, embedded_fonts, theme);
+        if let Some(sides) = &sides {
+            for (text, delta) in [("−", -rounding), ("+", rounding)] {
+                create_pane_text_button(
+                    row,
+                    text,
+                    KernButton::Nudge(sides.clone(), delta),
+                    asset_server,
+                    embedded_fonts,
+                    theme,
+                );
+            }
+        }
+        create_pane_text_button(
+            row,
+            &i18n::tr("auto-kerning-accept"),
+            KernButton::Accept(sides.clone()),
+            asset_server,
+            embedded_fonts,
+            theme,
+        );
+        create_pane_text_button(
+            row,
+            &i18n::tr("auto-kerning-reject"),
+            KernButton::Reject(sides),
+            asset_server,
+            embedded_fonts,
+            theme,
+        );
+    });
+}
+
+/// A side as shown in the table: groups as "@name", glyphs by name
+fn side_label(side: &str) -> String {
+    side.strip_prefix(FIRST_GROUP_PREFIX)
+        .or_else(|| side.strip_prefix(SECOND_GROUP_PREFIX))
+        .map_or_else(|| side.to_string(), |group| format!("@{group}"))
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the table from the suggestions awaiting review
+fn rebuild_kern_rows(
+    mut commands: Commands,
+    table_query: Query<Entity, With<KernSuggestionTable>>,
+    kerning: Res<AutoKerning>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !kerning.is_changed() {
+        return;
+    }
+    let Ok(table) = table_query.single() else {
+        return;
+    };
+
+    rebuild_rows(&mut commands, table, |parent| {
+        if kerning.suggestions.is_empty() {
+            return;
+        }
+        let rounding = kerning.parameters.rounding;
+        create_label_text(
+            parent,
+            &format!(
+                "  {:<10} {:<10} {:>5} → {:>5}",
+                i18n::tr("auto-kerning-first"),
+                i18n::tr("auto-kerning-second"),
+                i18n::tr("auto-kerning-now"),
+                i18n::tr("auto-kerning-new")
+            ),
+            (),
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+        for suggestion in kerning.suggestions.iter().take(MAX_ROWS) {
+            let label = format!(
+                "{} {:<10} {:<10} {:>5} → {:>5}",
+                if suggestion.collides { "!" } else { " " },
+                side_label(&suggestion.first),
+                side_label(&suggestion.second),
+                suggestion.current.round(),
+                suggestion.suggested.round()
+            );
+            spawn_row(
+                parent,
+                &label,
+                Some((suggestion.first.clone(), suggestion.second.clone())),
+                rounding,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        }
+        let count = kerning.suggestions.len().to_string();
+        let label = i18n::tr_args("auto-kerning-all-pairs", &[("count", &count)]);
+        spawn_row(
+            parent,
+            &label,
+            None,
+            rounding,
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+        if kerning
+            .suggestions
+            .iter()
+            .any(|suggestion| suggestion.collides)
+        {
+            create_pane_text_button(
+                parent,
+                &i18n::tr("auto-kerning-proof-collisions"),
+                KernButton::ProofCollisions,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        }
+    });
+}
+
+/// Sends accept, reject, edit and proof events for clicked buttons
+fn handle_kern_buttons(
+    interaction_query: Query<(&Interaction, &KernButton), Changed<Interaction>>,
+    kerning: Res<AutoKerning>,
+    mut accept_events: EventWriter<AcceptKernEvent>,
+    mut reject_events: EventWriter<RejectKernEvent>,
+    mut edit_events: EventWriter<EditKernEvent>,
+    mut proof_requests: EventWriter<KernProofRequest>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            KernButton::Accept(sides) => {
+                accept_events.write(AcceptKernEvent(sides.clone()));
+            }
+            KernButton::Reject(sides) => {
+                reject_events.write(RejectKernEvent(sides.clone()));
+            }
+            KernButton::Nudge((first, second), delta) => {
+                edit_events.write(EditKernEvent {
+                    first: first.clone(),
+                    second: second.clone(),
+                    delta: *delta,
+                });
+            }
+            KernButton::ProofCollisions => {
+                proof_requests.write(KernProofRequest::Pairs(kerning.collision_pairs()));
+            }
+        }
+    }
+}
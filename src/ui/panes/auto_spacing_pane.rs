@@ -0,0 +1,182 @@
+//! Auto-Spacing Pane Module
+//!
+//! Suggested sidebearings as "current → suggested", accepted or discarded one
+//! glyph at a time or all at once. Hidden while there is nothing to review.
+
+use crate::editing::auto_spacing::{AcceptSpacingEvent, AutoSpacing, DiscardSpacingEvent};
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the auto-spacing pane
+#[derive(Component, Default)]
+pub struct AutoSpacingPane;
+
+/// Container of the rows, rebuilt when the suggestions change
+#[derive(Component)]
+struct SuggestionList;
+
+/// What a button in the pane does, for one glyph or with `None` for all
+#[derive(Component, Clone)]
+enum SuggestionButton {
+    Accept(Option<String>),
+    Discard(Option<String>),
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct AutoSpacingPanePlugin;
+
+impl Plugin for AutoSpacingPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_auto_spacing_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_suggestion_buttons,
+                    rebuild_suggestion_rows,
+                    toggle_pane_visibility::<AutoSpacingPane, _>(|spacing: Res<AutoSpacing>| {
+                        !spacing.preview.is_empty()
+                    }),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the auto-spacing pane at its slot
+pub fn spawn_auto_spacing_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::AutoSpacing,
+        AutoSpacingPane,
+        "AutoSpacingPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("auto-spacing-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(SuggestionList));
+    });
+}
+
+/// A row of a label followed by accept and discard buttons
+fn spawn_row(
+    parent: &mut ChildSpawnerCommands,
+    label: &str,
+    glyph_name: Option<String>,
+    asset_server: &AssetServer,
+    embedded_fonts: &EmbeddedFonts,
+    theme: &CurrentTheme,
+) {
+    parent.spawn(pane_row_node()).with_children(|row| {
+        create_label_text(row, label, (), asset_server, embedded_fonts, theme);
+        create_pane_text_button(
+            row,
+            &i18n::tr("auto-spacing-accept"),
+            SuggestionButton::Accept(glyph_name.clone()),
+            asset_server,
+            embedded_fonts,
+            theme,
+        );
+        create_pane_text_button(
+            row,
+            &i18n::tr("auto-spacing-discard"),
+            SuggestionButton::Discard(glyph_name),
+            asset_server,
+            embedded_fonts,
+            theme,
+        );
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows from the suggestions awaiting review
+fn rebuild_suggestion_rows(
+    mut commands: Commands,
+    list_query: Query<Entity, With<SuggestionList>>,
+    spacing: Res<AutoSpacing>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !spacing.is_changed() {
+        return;
+    }
+    let Ok(list) = list_query.single() else {
+        return;
+    };
+
+    rebuild_rows(&mut commands, list, |parent| {
+        if spacing.preview.is_empty() {
+            return;
+        }
+        for suggestion in &spacing.preview {
+            let label = format!(
+                "{:<12} {:>4}|{:<4} → {:>4}|{:<4}",
+                suggestion.glyph_name,
+                suggestion.current.0.round(),
+                suggestion.current.1.round(),
+                suggestion.suggested.0,
+                suggestion.suggested.1
+            );
+            spawn_row(
+                parent,
+                &label,
+                Some(suggestion.glyph_name.clone()),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        }
+        let count = spacing.preview.len().to_string();
+        let label = i18n::tr_args("auto-spacing-all-glyphs", &[("count", &count)]);
+        spawn_row(parent, &label, None, &asset_server, &embedded_fonts, &theme);
+    });
+}
+
+/// Sends accept and discard events for clicked buttons
+fn handle_suggestion_buttons(
+    interaction_query: Query<(&Interaction, &SuggestionButton), Changed<Interaction>>,
+    mut accept_events: EventWriter<AcceptSpacingEvent>,
+    mut discard_events: EventWriter<DiscardSpacingEvent>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            SuggestionButton::Accept(glyph_name) => {
+                accept_events.write(AcceptSpacingEvent(glyph_name.clone()));
+            }
+            SuggestionButton::Discard(glyph_name) => {
+                discard_events.write(DiscardSpacingEvent(glyph_name.clone()));
+            }
+        }
+    }
+}
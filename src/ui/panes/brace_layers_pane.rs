@@ -0,0 +1,251 @@
+//! Brace Layers Pane Module
+//!
+//! Steps the location of a new brace layer along each axis, and lists the
+//! active glyph's brace layers and every glyph that has any.
+
+use crate::core::state::AppState;
+use crate::editing::brace_layers::{BraceLayersEvent, BraceLayersState};
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::font_source::brace_layers::brace_layer_name;
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the brace layers pane
+#[derive(Component, Default)]
+pub struct BraceLayersPane;
+
+/// Container of the rows, rebuilt when what they show changes
+#[derive(Component)]
+struct BraceRows;
+
+/// The brace layers event a button sends
+#[derive(Component, Clone)]
+struct BraceButton(BraceLayersEvent);
+
+/// What the rows were last built from
+#[derive(Clone, PartialEq)]
+struct ShownBraceLayers {
+    /// Each axis and the location's value on it
+    axes: Vec<(String, f64)>,
+    new_layer: String,
+    /// The active glyph and its brace layers
+    glyph: Option<(String, Vec<String>)>,
+    editing_layer: String,
+    glyphs_with_layers: Vec<String>,
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct BraceLayersPanePlugin;
+
+impl Plugin for BraceLayersPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_brace_layers_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_brace_buttons,
+                    rebuild_brace_rows,
+                    toggle_pane_visibility::<BraceLayersPane, _>(
+                        |brace_state: Res<BraceLayersState>| brace_state.visible,
+                    ),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the brace layers pane at its slot
+pub fn spawn_brace_layers_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::BraceLayers,
+        BraceLayersPane,
+        "BraceLayersPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("brace-layers-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(BraceRows));
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the location, the active glyph or its layers
+/// change
+#[allow(clippy::too_many_arguments)]
+fn rebuild_brace_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<Option<ShownBraceLayers>>>,
+    rows_query: Query<Entity, With<BraceRows>>,
+    brace_state: Res<BraceLayersState>,
+    active_sorts: Query<&Sort, With<ActiveSort>>,
+    app_state: Option<Res<AppState>>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    let current = brace_state
+        .masters
+        .as_ref()
+        .zip(app_state.as_ref())
+        .map(|(masters, state)| {
+            let font = &state.workspace.font;
+            let axes = &masters.axes;
+            ShownBraceLayers {
+                axes: axes
+                    .iter()
+                    .map(|axis| {
+                        let value = brace_state.location.get(&axis.name).copied();
+                        (axis.name.clone(), value.unwrap_or(axis.default))
+                    })
+                    .collect(),
+                new_layer: brace_layer_name(&brace_state.location, axes),
+                glyph: active_sorts.single().ok().map(|sort| {
+                    let layers = font.brace_layer_names(&sort.glyph_name, axes);
+                    (sort.glyph_name.clone(), layers)
+                }),
+                editing_layer: font.editing_layer_name().to_string(),
+                glyphs_with_layers: font.glyphs_with_brace_layers(axes),
+            }
+        });
+    if shown.as_ref() == Some(&current) {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = Some(current.clone());
+
+    rebuild_rows(&mut commands, rows, |parent| {
+        let Some(current) = current else {
+            create_label_text(
+                parent,
+                &i18n::tr("pane-no-designspace"),
+                (),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            return;
+        };
+
+        for (index, (axis, value)) in current.axes.iter().enumerate() {
+            parent.spawn(pane_row_node()).with_children(|row| {
+                create_label_text(
+                    row,
+                    &format!("{axis} {value}"),
+                    (),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+                let buttons = [
+                    ("-", BraceLayersEvent::StepLocation(index, false)),
+                    ("+", BraceLayersEvent::StepLocation(index, true)),
+                ];
+                for (label, event) in buttons {
+                    create_pane_text_button(
+                        row,
+                        label,
+                        BraceButton(event),
+                        &asset_server,
+                        &embedded_fonts,
+                        &theme,
+                    );
+                }
+            });
+        }
+
+        if let Some((glyph_name, layers)) = &current.glyph {
+            create_pane_text_button(
+                parent,
+                &i18n::tr_args(
+                    "brace-layers-add",
+                    &[("layer", &current.new_layer), ("glyph", glyph_name)],
+                ),
+                BraceButton(BraceLayersEvent::Create),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            for layer in layers {
+                parent.spawn(pane_row_node()).with_children(|row| {
+                    create_label_text(row, layer, (), &asset_server, &embedded_fonts, &theme);
+                    if *layer != current.editing_layer {
+                        let event = BraceLayersEvent::Edit(Some(layer.clone()));
+                        create_pane_text_button(
+                            row,
+                            &i18n::tr("pane-edit"),
+                            BraceButton(event),
+                            &asset_server,
+                            &embedded_fonts,
+                            &theme,
+                        );
+                    }
+                });
+            }
+        }
+        if current
+            .glyph
+            .as_ref()
+            .is_some_and(|(_, layers)| layers.contains(&current.editing_layer))
+        {
+            create_pane_text_button(
+                parent,
+                &i18n::tr("pane-edit-foreground"),
+                BraceButton(BraceLayersEvent::Edit(None)),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        }
+
+        let glyphs = if current.glyphs_with_layers.is_empty() {
+            i18n::tr("brace-layers-none")
+        } else {
+            let glyphs = current.glyphs_with_layers.join(" ");
+            i18n::tr_args("brace-layers-glyphs", &[("glyphs", &glyphs)])
+        };
+        create_label_text(parent, &glyphs, (), &asset_server, &embedded_fonts, &theme);
+    });
+}
+
+/// Sends the brace layers events of clicked buttons
+fn handle_brace_buttons(
+    interaction_query: Query<(&Interaction, &BraceButton), Changed<Interaction>>,
+    mut brace_events: EventWriter<BraceLayersEvent>,
+) {
+    for (interaction, BraceButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            brace_events.write(event.clone());
+        }
+    }
+}
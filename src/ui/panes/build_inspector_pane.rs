@@ -0,0 +1,203 @@
+//! Build Inspector Pane Module
+//!
+//! Opens when an export finishes. Pick one of the fonts written to see its
+//! tables, `head`, `hhea` and `OS/2` values, `cmap` coverage and outline sizes,
+//! one column per section.
+
+use crate::editing::build_inspector::{BuildInspector, BuildInspectorEvent};
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_wrapping_row_node, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+    PANE_ROW_GAP,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Widest the pane gets before its sections wrap
+const PANE_MAX_WIDTH: Val = Val::Percent(70.0);
+
+/// Gap between the sections' columns
+const SECTION_GAP: f32 = 16.0;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the build inspector pane
+#[derive(Component, Default)]
+pub struct BuildInspectorPane;
+
+/// Container of the pane's rows, rebuilt when the inspector changes
+#[derive(Component)]
+struct BuildInspectorContent;
+
+/// The build inspector event a button sends
+#[derive(Component, Clone)]
+struct BuildInspectorButton(BuildInspectorEvent);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct BuildInspectorPanePlugin;
+
+impl Plugin for BuildInspectorPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_build_inspector_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_build_inspector_buttons,
+                    rebuild_build_inspector,
+                    toggle_pane_visibility::<BuildInspectorPane, _>(
+                        |inspector: Res<BuildInspector>| inspector.visible,
+                    ),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the build inspector pane, hidden until there is a build to show
+pub fn spawn_build_inspector_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::BuildInspector,
+        BuildInspectorPane,
+        "BuildInspectorPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("build-inspector-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(PANE_ROW_GAP),
+                max_width: PANE_MAX_WIDTH,
+                ..default()
+            },
+            BuildInspectorContent,
+        ));
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the font buttons and sections when the inspector changes
+fn rebuild_build_inspector(
+    mut commands: Commands,
+    content_query: Query<Entity, With<BuildInspectorContent>>,
+    inspector: Res<BuildInspector>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !inspector.is_changed() || !inspector.visible {
+        return;
+    }
+    let Ok(content) = content_query.single() else {
+        return;
+    };
+
+    rebuild_rows(&mut commands, content, |parent| {
+        parent.spawn(pane_wrapping_row_node()).with_children(|row| {
+            for (index, font) in inspector.fonts.iter().enumerate() {
+                let mark = if index == inspector.selected {
+                    "●"
+                } else {
+                    "○"
+                };
+                create_pane_text_button(
+                    row,
+                    &format!("{mark} {}", font.file_name()),
+                    BuildInspectorButton(BuildInspectorEvent::Select(index)),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+            create_pane_text_button(
+                row,
+                &i18n::tr("button-close"),
+                BuildInspectorButton(BuildInspectorEvent::Hide),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        });
+        let Some(font) = inspector.selected_font() else {
+            return;
+        };
+        parent
+            .spawn(Node {
+                flex_direction: FlexDirection::Row,
+                flex_wrap: FlexWrap::Wrap,
+                align_items: AlignItems::FlexStart,
+                column_gap: Val::Px(SECTION_GAP),
+                row_gap: Val::Px(SECTION_GAP),
+                ..default()
+            })
+            .with_children(|sections| {
+                for section in &font.sections {
+                    sections
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Column,
+                            row_gap: Val::Px(PANE_ROW_GAP),
+                            ..default()
+                        })
+                        .with_children(|column| {
+                            create_label_text(
+                                column,
+                                &format!("[{}]", section.title),
+                                (),
+                                &asset_server,
+                                &embedded_fonts,
+                                &theme,
+                            );
+                            for (name, value) in &section.rows {
+                                create_label_text(
+                                    column,
+                                    &format!("{name}: {value}"),
+                                    (),
+                                    &asset_server,
+                                    &embedded_fonts,
+                                    &theme,
+                                );
+                            }
+                        });
+                }
+            });
+    });
+}
+
+/// Sends the build inspector events of clicked buttons
+fn handle_build_inspector_buttons(
+    interaction_query: Query<(&Interaction, &BuildInspectorButton), Changed<Interaction>>,
+    mut inspector_events: EventWriter<BuildInspectorEvent>,
+) {
+    for (interaction, BuildInspectorButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            inspector_events.write(event.clone());
+        }
+    }
+}
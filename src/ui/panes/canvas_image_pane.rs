@@ -0,0 +1,218 @@
+//! Canvas Image Pane Module
+//!
+//! Format, region, resolution and background of the canvas image, and the
+//! button saving it next to the font.
+
+use crate::editing::canvas_image::{
+    CanvasImageEvent, CanvasImageFormat, CanvasImageRegion, CanvasImageState, CANVAS_IMAGE_SCALES,
+};
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the canvas image pane
+#[derive(Component, Default)]
+pub struct CanvasImagePane;
+
+/// Container of the rows, rebuilt when what they show changes
+#[derive(Component)]
+struct CanvasImageRows;
+
+/// The canvas image event a button sends
+#[derive(Component, Clone)]
+struct CanvasImageButton(CanvasImageEvent);
+
+/// What the rows were last built from
+#[derive(Clone, PartialEq)]
+struct ShownCanvasImage {
+    format: CanvasImageFormat,
+    region: CanvasImageRegion,
+    scale: f64,
+    transparent: bool,
+    saving: bool,
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct CanvasImagePanePlugin;
+
+impl Plugin for CanvasImagePanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_canvas_image_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_canvas_image_buttons,
+                    rebuild_canvas_image_rows,
+                    toggle_pane_visibility::<CanvasImagePane, _>(|image: Res<CanvasImageState>| {
+                        image.visible
+                    }),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the canvas image pane at its slot
+pub fn spawn_canvas_image_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::CanvasImage,
+        CanvasImagePane,
+        "CanvasImagePane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("canvas-image-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(CanvasImageRows));
+    });
+}
+
+/// A choice's label, marked when it's the one picked
+fn choice_label(label: &str, picked: bool) -> String {
+    let mark = if picked { "(o)" } else { "( )" };
+    format!("{mark} {label}")
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the choices change
+fn rebuild_canvas_image_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<ShownCanvasImage>>,
+    rows_query: Query<Entity, With<CanvasImageRows>>,
+    image: Res<CanvasImageState>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !image.visible {
+        return;
+    }
+    let current = ShownCanvasImage {
+        format: image.format,
+        region: image.region,
+        scale: image.scale,
+        transparent: image.transparent,
+        saving: image.is_saving(),
+    };
+    if shown.as_ref() == Some(&current) {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = Some(current.clone());
+
+    rebuild_rows(&mut commands, rows, |parent| {
+        parent.spawn(pane_row_node()).with_children(|row| {
+            for (label, format) in [
+                ("PNG", CanvasImageFormat::Png),
+                ("SVG", CanvasImageFormat::Svg),
+            ] {
+                create_pane_text_button(
+                    row,
+                    &choice_label(label, current.format == format),
+                    CanvasImageButton(CanvasImageEvent::SetFormat(format)),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+        });
+        parent.spawn(pane_row_node()).with_children(|row| {
+            for (label, region) in [
+                ("canvas-image-window", CanvasImageRegion::Viewport),
+                ("canvas-image-text", CanvasImageRegion::Text),
+                ("canvas-image-selection", CanvasImageRegion::Selection),
+            ] {
+                create_pane_text_button(
+                    row,
+                    &choice_label(&i18n::tr(label), current.region == region),
+                    CanvasImageButton(CanvasImageEvent::SetRegion(region)),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+        });
+        parent.spawn(pane_row_node()).with_children(|row| {
+            for scale in CANVAS_IMAGE_SCALES {
+                create_pane_text_button(
+                    row,
+                    &choice_label(&format!("{scale}x"), current.scale == scale),
+                    CanvasImageButton(CanvasImageEvent::SetScale(scale)),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+        });
+        let mark = if current.transparent { "[x]" } else { "[ ]" };
+        create_pane_text_button(
+            parent,
+            &format!("{mark} {}", i18n::tr("canvas-image-transparent")),
+            CanvasImageButton(CanvasImageEvent::ToggleTransparent),
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+
+        if current.saving {
+            create_label_text(
+                parent,
+                &i18n::tr("canvas-image-saving"),
+                (),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            return;
+        }
+        create_pane_text_button(
+            parent,
+            &i18n::tr("canvas-image-save"),
+            CanvasImageButton(CanvasImageEvent::Save),
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+    });
+}
+
+/// Sends the canvas image events of clicked buttons
+fn handle_canvas_image_buttons(
+    interaction_query: Query<(&Interaction, &CanvasImageButton), Changed<Interaction>>,
+    mut image_events: EventWriter<CanvasImageEvent>,
+) {
+    for (interaction, CanvasImageButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            image_events.write(event.clone());
+        }
+    }
+}
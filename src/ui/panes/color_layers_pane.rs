@@ -0,0 +1,382 @@
+//! Color Layers Pane Module
+//!
+//! The active glyph's color layers, top first, each with its palette entry and
+//! buttons to restack or remove it, followed by the palette's colors.
+
+use crate::core::state::AppState;
+use crate::editing::color_layers::{
+    hex_color, step_palette_index, AddColorLayerEvent, ColorLayersPanel, RemoveColorLayerEvent,
+    ReorderColorLayerEvent, SelectPaletteEvent, SetLayerPaletteIndexEvent,
+};
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::font_source::color::FOREGROUND_PALETTE_INDEX;
+use crate::font_source::{ColorLayer, PaletteColor};
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the color layers pane
+#[derive(Component, Default)]
+pub struct ColorLayersPane;
+
+/// Container of the rows, rebuilt when what they show changes
+#[derive(Component)]
+struct ColorLayerRows;
+
+/// What a button does
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ColorLayersAction {
+    /// Show the next (`true`) or previous palette
+    StepPalette(bool),
+    /// Add a layer drawn from a copy of the glyph
+    AddLayer,
+    /// Fill a layer with the next or previous palette entry
+    StepPaletteIndex(usize, bool),
+    /// Move a layer up (`true`) or down the stack
+    Move(usize, bool),
+    Remove(usize),
+    /// Type the color of a palette entry
+    EditColor(usize),
+}
+
+/// The action a button takes
+#[derive(Component, Clone)]
+struct ColorLayersButton(ColorLayersAction);
+
+/// What the rows were last built from
+#[derive(Clone, PartialEq)]
+struct ShownColorLayers {
+    glyph: Option<(String, Vec<ColorLayer>)>,
+    palettes: Vec<Vec<PaletteColor>>,
+    active_palette: usize,
+    editing: Option<(usize, String)>,
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct ColorLayersPanePlugin;
+
+impl Plugin for ColorLayersPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_color_layers_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_color_layers_buttons,
+                    rebuild_color_layer_rows,
+                    toggle_pane_visibility::<ColorLayersPane, _>(|panel: Res<ColorLayersPanel>| {
+                        panel.visible
+                    }),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the color layers pane at its slot
+pub fn spawn_color_layers_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::ColorLayers,
+        ColorLayersPane,
+        "ColorLayersPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("color-layers-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(ColorLayerRows));
+    });
+}
+
+/// A layer's fill as the pane shows it
+fn fill_label(palette_index: u16, palette: Option<&Vec<PaletteColor>>) -> String {
+    if palette_index == FOREGROUND_PALETTE_INDEX {
+        return i18n::tr("color-layers-foreground");
+    }
+    match palette.and_then(|palette| palette.get(palette_index as usize)) {
+        Some(color) => format!("{palette_index} {}", hex_color(*color)),
+        None => format!("{palette_index} {}", i18n::tr("color-layers-missing")),
+    }
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the active glyph, its layers or the palettes
+/// change
+#[allow(clippy::too_many_arguments)]
+fn rebuild_color_layer_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<ShownColorLayers>>,
+    rows_query: Query<Entity, With<ColorLayerRows>>,
+    panel: Res<ColorLayersPanel>,
+    active_sorts: Query<&Sort, With<ActiveSort>>,
+    app_state: Option<Res<AppState>>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !panel.visible {
+        return;
+    }
+    let Some(state) = app_state else {
+        return;
+    };
+    let color = &state.workspace.font.color;
+    let current = ShownColorLayers {
+        glyph: active_sorts.single().ok().map(|sort| {
+            let layers = color.layers_for(&sort.glyph_name).unwrap_or_default();
+            (sort.glyph_name.clone(), layers.to_vec())
+        }),
+        palettes: color.palettes.clone(),
+        active_palette: color.active_palette,
+        editing: panel.editing.clone(),
+    };
+    if shown.as_ref() == Some(&current) {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = Some(current.clone());
+
+    let palette = current.palettes.get(current.active_palette);
+    rebuild_rows(&mut commands, rows, |parent| {
+        match &current.glyph {
+            Some((glyph_name, layers)) => {
+                create_label_text(
+                    parent,
+                    &i18n::tr_args(
+                        "color-layers-count",
+                        &[("glyph", glyph_name), ("count", &layers.len().to_string())],
+                    ),
+                    (),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+                // The top layer is drawn last
+                for (index, layer) in layers.iter().enumerate().rev() {
+                    parent.spawn(pane_row_node()).with_children(|row| {
+                        let fill = fill_label(layer.palette_index, palette);
+                        let label = format!("{} {fill}", layer.glyph_name);
+                        create_label_text(row, &label, (), &asset_server, &embedded_fonts, &theme);
+                        let buttons = [
+                            (
+                                "color-layers-color-down",
+                                ColorLayersAction::StepPaletteIndex(index, false),
+                            ),
+                            (
+                                "color-layers-color-up",
+                                ColorLayersAction::StepPaletteIndex(index, true),
+                            ),
+                            ("color-layers-up", ColorLayersAction::Move(index, true)),
+                            ("color-layers-down", ColorLayersAction::Move(index, false)),
+                            ("pane-remove", ColorLayersAction::Remove(index)),
+                        ];
+                        for (label, action) in buttons {
+                            create_pane_text_button(
+                                row,
+                                &i18n::tr(label),
+                                ColorLayersButton(action),
+                                &asset_server,
+                                &embedded_fonts,
+                                &theme,
+                            );
+                        }
+                    });
+                }
+                create_pane_text_button(
+                    parent,
+                    &i18n::tr("color-layers-add-layer"),
+                    ColorLayersButton(ColorLayersAction::AddLayer),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+            None => create_label_text(
+                parent,
+                &i18n::tr("pane-no-active-glyph"),
+                (),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            ),
+        }
+
+        parent.spawn(pane_row_node()).with_children(|row| {
+            let label = i18n::tr_args(
+                "color-layers-palette",
+                &[
+                    ("palette", &(current.active_palette + 1).to_string()),
+                    ("count", &current.palettes.len().max(1).to_string()),
+                ],
+            );
+            create_label_text(row, &label, (), &asset_server, &embedded_fonts, &theme);
+            for (label, action) in [
+                ("-", ColorLayersAction::StepPalette(false)),
+                ("+", ColorLayersAction::StepPalette(true)),
+            ] {
+                create_pane_text_button(
+                    row,
+                    label,
+                    ColorLayersButton(action),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+        });
+        let entries = palette.map_or(0, |palette| palette.len());
+        // One more row adds an entry
+        for index in 0..=entries {
+            parent.spawn(pane_row_node()).with_children(|row| {
+                let label = match (&current.editing, palette.and_then(|p| p.get(index))) {
+                    (Some((editing, typed)), _) if *editing == index => {
+                        format!("{index} {typed}_")
+                    }
+                    (_, Some(color)) => format!("{index} {}", hex_color(*color)),
+                    (_, None) => format!("{index} {}", i18n::tr("color-layers-new-color")),
+                };
+                create_label_text(row, &label, (), &asset_server, &embedded_fonts, &theme);
+                let action = ColorLayersAction::EditColor(index);
+                create_pane_text_button(
+                    row,
+                    &i18n::tr("pane-edit"),
+                    ColorLayersButton(action),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            });
+        }
+        if current.editing.is_some() {
+            create_label_text(
+                parent,
+                &i18n::tr("color-layers-edit-hint"),
+                (),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        }
+    });
+}
+
+/// Turns clicked buttons into color layer events
+#[allow(clippy::too_many_arguments)]
+fn handle_color_layers_buttons(
+    interaction_query: Query<(&Interaction, &ColorLayersButton), Changed<Interaction>>,
+    mut panel: ResMut<ColorLayersPanel>,
+    app_state: Option<Res<AppState>>,
+    active_sorts: Query<&Sort, With<ActiveSort>>,
+    mut add_events: EventWriter<AddColorLayerEvent>,
+    mut remove_events: EventWriter<RemoveColorLayerEvent>,
+    mut index_events: EventWriter<SetLayerPaletteIndexEvent>,
+    mut reorder_events: EventWriter<ReorderColorLayerEvent>,
+    mut select_events: EventWriter<SelectPaletteEvent>,
+) {
+    let Some(state) = app_state else {
+        return;
+    };
+    let color = &state.workspace.font.color;
+    let glyph = active_sorts
+        .single()
+        .ok()
+        .map(|sort| sort.glyph_name.clone());
+    let layers = glyph
+        .as_deref()
+        .and_then(|glyph| color.layers_for(glyph))
+        .unwrap_or_default();
+
+    for (interaction, ColorLayersButton(action)) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *action {
+            ColorLayersAction::StepPalette(forward) => {
+                let count = color.palettes.len().max(1);
+                let palette = if forward {
+                    (color.active_palette + 1) % count
+                } else {
+                    (color.active_palette + count - 1) % count
+                };
+                select_events.write(SelectPaletteEvent(palette));
+            }
+            ColorLayersAction::EditColor(index) => {
+                let current = color
+                    .palettes
+                    .get(color.active_palette)
+                    .and_then(|palette| palette.get(index))
+                    .map(|color| hex_color(*color))
+                    .unwrap_or_else(|| "#".to_string());
+                panel.editing = Some((index, current));
+            }
+            _ => {}
+        }
+        let Some(base_glyph) = glyph.clone() else {
+            continue;
+        };
+        match *action {
+            ColorLayersAction::AddLayer => {
+                // Each new layer starts on an entry of its own
+                add_events.write(AddColorLayerEvent {
+                    base_glyph,
+                    layer_glyph: None,
+                    palette_index: layers.len() as u16,
+                });
+            }
+            ColorLayersAction::StepPaletteIndex(layer_index, forward) => {
+                let Some(layer) = layers.get(layer_index) else {
+                    continue;
+                };
+                let palette_index =
+                    step_palette_index(layer.palette_index, color.palette_size(), forward);
+                index_events.write(SetLayerPaletteIndexEvent {
+                    base_glyph,
+                    layer_index,
+                    palette_index,
+                });
+            }
+            ColorLayersAction::Move(layer_index, move_up) => {
+                reorder_events.write(ReorderColorLayerEvent {
+                    base_glyph,
+                    layer_index,
+                    move_up,
+                });
+            }
+            ColorLayersAction::Remove(layer_index) => {
+                remove_events.write(RemoveColorLayerEvent {
+                    base_glyph,
+                    layer_index,
+                });
+            }
+            ColorLayersAction::StepPalette(_) | ColorLayersAction::EditColor(_) => {}
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Compile Errors Pane Module
+//!
+//! Opens when fontc fails. Each error links to the glyph it came from.
+
+use crate::editing::compile_errors::{CompileBuild, CompileErrors, CompileErrorsEvent};
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_wrapping_row_node, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+    PANE_ROW_GAP,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Widest the pane gets before messages wrap
+const PANE_MAX_WIDTH: Val = Val::Percent(50.0);
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the compile errors pane
+#[derive(Component, Default)]
+pub struct CompileErrorsPane;
+
+/// Container of the pane's rows, rebuilt when the errors change
+#[derive(Component)]
+struct CompileErrorsContent;
+
+/// The compile errors event a button sends
+#[derive(Component, Clone)]
+struct CompileErrorsButton(CompileErrorsEvent);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct CompileErrorsPanePlugin;
+
+impl Plugin for CompileErrorsPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_compile_errors_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_compile_errors_buttons,
+                    rebuild_compile_errors,
+                    toggle_pane_visibility::<CompileErrorsPane, _>(|errors: Res<CompileErrors>| {
+                        errors.visible
+                    }),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the compile errors pane, hidden until a build fails
+pub fn spawn_compile_errors_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::CompileErrors,
+        CompileErrorsPane,
+        "CompileErrorsPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("compile-errors-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(PANE_ROW_GAP),
+                max_width: PANE_MAX_WIDTH,
+                ..default()
+            },
+            CompileErrorsContent,
+        ));
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the error rows when the errors change
+fn rebuild_compile_errors(
+    mut commands: Commands,
+    content_query: Query<Entity, With<CompileErrorsContent>>,
+    errors: Res<CompileErrors>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !errors.is_changed() || !errors.visible {
+        return;
+    }
+    let Ok(content) = content_query.single() else {
+        return;
+    };
+
+    let build = i18n::tr(match errors.build {
+        Some(CompileBuild::Shaping) => "compile-errors-shaping",
+        Some(CompileBuild::Export) => "compile-errors-export",
+        None => "compile-errors-build",
+    });
+    let count = errors.diagnostics.len().to_string();
+    rebuild_rows(&mut commands, content, |parent| {
+        parent.spawn(pane_wrapping_row_node()).with_children(|row| {
+            create_label_text(
+                row,
+                &i18n::tr_args(
+                    "compile-errors-failed",
+                    &[("build", &build), ("count", &count)],
+                ),
+                (),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            create_pane_text_button(
+                row,
+                &i18n::tr("button-close"),
+                CompileErrorsButton(CompileErrorsEvent::Hide),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        });
+        for diagnostic in &errors.diagnostics {
+            parent.spawn(pane_wrapping_row_node()).with_children(|row| {
+                if let Some(glyph) = &diagnostic.glyph {
+                    create_pane_text_button(
+                        row,
+                        glyph,
+                        CompileErrorsButton(CompileErrorsEvent::JumpToGlyph(glyph.clone())),
+                        &asset_server,
+                        &embedded_fonts,
+                        &theme,
+                    );
+                }
+                create_label_text(
+                    row,
+                    &diagnostic.message,
+                    (),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            });
+        }
+    });
+}
+
+/// Sends the compile errors events of clicked buttons
+fn handle_compile_errors_buttons(
+    interaction_query: Query<(&Interaction, &CompileErrorsButton), Changed<Interaction>>,
+    mut errors_events: EventWriter<CompileErrorsEvent>,
+) {
+    for (interaction, CompileErrorsButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            errors_events.write(event.clone());
+        }
+    }
+}
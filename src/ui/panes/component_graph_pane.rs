@@ -0,0 +1,321 @@
+//! Component Graph Pane Module
+//!
+//! What the active glyph is built from and what is built from it, by depth,
+//! plus any component loops in the font. Every glyph name jumps to the
+//! glyph.
+
+use crate::editing::anchor_sync::AnchorSyncReport;
+use crate::editing::component_graph::{ComponentGraphEvent, ComponentGraphPaneState, MANY_USERS};
+use crate::font_source::components::ComponentNode;
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_wrapping_row_node, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+    PANE_ROW_GAP,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Widest the pane gets before rows wrap
+const PANE_MAX_WIDTH: Val = Val::Percent(35.0);
+
+/// Indent of each level of a tree
+const DEPTH_INDENT: f32 = 16.0;
+
+/// Most rows listed for each tree
+const MAX_TREE_ROWS: usize = 30;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the component graph pane
+#[derive(Component, Default)]
+pub struct ComponentGraphPane;
+
+/// Container of the pane's rows, rebuilt when the graph changes
+#[derive(Component)]
+struct ComponentGraphContent;
+
+/// The component graph event a button sends
+#[derive(Component, Clone)]
+struct ComponentGraphButton(ComponentGraphEvent);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct ComponentGraphPanePlugin;
+
+impl Plugin for ComponentGraphPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_component_graph_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_component_graph_buttons,
+                    rebuild_component_graph,
+                    toggle_pane_visibility::<ComponentGraphPane, _>(
+                        |pane: Res<ComponentGraphPaneState>| pane.visible,
+                    ),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the component graph pane, hidden until it is toggled
+pub fn spawn_component_graph_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::ComponentGraph,
+        ComponentGraphPane,
+        "ComponentGraphPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("component-graph-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(PANE_ROW_GAP),
+                max_width: PANE_MAX_WIDTH,
+                ..default()
+            },
+            ComponentGraphContent,
+        ));
+    });
+}
+
+/// A tree's rows under its title, each glyph indented by its depth
+fn spawn_tree(
+    parent: &mut ChildSpawnerCommands,
+    title: &str,
+    nodes: &[ComponentNode],
+    asset_server: &AssetServer,
+    embedded_fonts: &EmbeddedFonts,
+    theme: &CurrentTheme,
+) {
+    create_label_text(parent, title, (), asset_server, embedded_fonts, theme);
+    if nodes.is_empty() {
+        let none = format!("  {}", i18n::tr("pref-none"));
+        create_label_text(parent, &none, (), asset_server, embedded_fonts, theme);
+    }
+    for node in nodes.iter().take(MAX_TREE_ROWS) {
+        let mut row = pane_wrapping_row_node();
+        row.padding.left = Val::Px(DEPTH_INDENT * node.depth as f32);
+        parent.spawn(row).with_children(|row| {
+            if node.missing {
+                create_label_text(row, &node.glyph, (), asset_server, embedded_fonts, theme);
+                create_label_text(
+                    row,
+                    &i18n::tr("component-graph-not-in-font"),
+                    (),
+                    asset_server,
+                    embedded_fonts,
+                    theme,
+                );
+                return;
+            }
+            create_pane_text_button(
+                row,
+                &node.glyph,
+                ComponentGraphButton(ComponentGraphEvent::JumpToGlyph(node.glyph.clone())),
+                asset_server,
+                embedded_fonts,
+                theme,
+            );
+            if node.cycle {
+                let loops = i18n::tr("component-graph-loops-back");
+                create_label_text(row, &loops, (), asset_server, embedded_fonts, theme);
+            }
+        });
+    }
+    if nodes.len() > MAX_TREE_ROWS {
+        create_label_text(
+            parent,
+            &format!(
+                "  {}",
+                i18n::tr_args(
+                    "component-graph-more",
+                    &[("count", &(nodes.len() - MAX_TREE_ROWS).to_string())],
+                )
+            ),
+            (),
+            asset_server,
+            embedded_fonts,
+            theme,
+        );
+    }
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the trees and loops when the graph changes
+fn rebuild_component_graph(
+    mut commands: Commands,
+    content_query: Query<Entity, With<ComponentGraphContent>>,
+    pane: Res<ComponentGraphPaneState>,
+    anchor_sync: Res<AnchorSyncReport>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !(pane.is_changed() || anchor_sync.is_changed()) || !pane.visible {
+        return;
+    }
+    let Ok(content) = content_query.single() else {
+        return;
+    };
+
+    rebuild_rows(&mut commands, content, |parent| {
+        parent.spawn(pane_wrapping_row_node()).with_children(|row| {
+            let heading = match &pane.glyph {
+                Some(glyph) => format!("'{glyph}'"),
+                None => i18n::tr("pane-no-active-glyph"),
+            };
+            create_label_text(row, &heading, (), &asset_server, &embedded_fonts, &theme);
+            if let Some(glyph) = &pane.glyph {
+                create_pane_text_button(
+                    row,
+                    &i18n::tr("component-graph-add-component"),
+                    ComponentGraphButton(ComponentGraphEvent::AddComponent(glyph.clone())),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+            create_pane_text_button(
+                row,
+                &i18n::tr("button-close"),
+                ComponentGraphButton(ComponentGraphEvent::Hide),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        });
+        if let Some(glyph) = &pane.glyph {
+            spawn_tree(
+                parent,
+                &i18n::tr("component-graph-built-from"),
+                &pane.components,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            if !pane.components.is_empty() {
+                let follows = i18n::tr(if pane.follows_anchors {
+                    "pref-on"
+                } else {
+                    "pref-off"
+                });
+                parent.spawn(pane_wrapping_row_node()).with_children(|row| {
+                    create_pane_text_button(
+                        row,
+                        &i18n::tr_args("component-graph-follow-anchors", &[("state", &follows)]),
+                        ComponentGraphButton(ComponentGraphEvent::ToggleFollowAnchors(
+                            glyph.clone(),
+                        )),
+                        &asset_server,
+                        &embedded_fonts,
+                        &theme,
+                    );
+                });
+            }
+            spawn_tree(
+                parent,
+                &i18n::tr_args(
+                    "component-graph-used-by",
+                    &[("count", &pane.all_users.to_string())],
+                ),
+                &pane.users,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            if pane.all_users >= MANY_USERS {
+                create_label_text(
+                    parent,
+                    &i18n::tr_args(
+                        "component-graph-many-users",
+                        &[("count", &pane.all_users.to_string())],
+                    ),
+                    (),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+        }
+
+        if let Some(moved) = &anchor_sync.glyph_name {
+            create_label_text(
+                parent,
+                &i18n::tr_args(
+                    "component-graph-moved",
+                    &[
+                        ("glyph", moved),
+                        ("composites", &anchor_sync.composites.join(", ")),
+                    ],
+                ),
+                (),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        }
+
+        let loops = if pane.cycles.is_empty() {
+            i18n::tr("component-graph-no-loops")
+        } else {
+            let count = pane.cycles.len().to_string();
+            i18n::tr_args("component-graph-loops", &[("count", &count)])
+        };
+        create_label_text(parent, &loops, (), &asset_server, &embedded_fonts, &theme);
+        for cycle in &pane.cycles {
+            parent.spawn(pane_wrapping_row_node()).with_children(|row| {
+                for glyph in cycle {
+                    create_pane_text_button(
+                        row,
+                        glyph,
+                        ComponentGraphButton(ComponentGraphEvent::JumpToGlyph(glyph.clone())),
+                        &asset_server,
+                        &embedded_fonts,
+                        &theme,
+                    );
+                }
+            });
+        }
+    });
+}
+
+/// Sends the component graph events of clicked buttons
+fn handle_component_graph_buttons(
+    interaction_query: Query<(&Interaction, &ComponentGraphButton), Changed<Interaction>>,
+    mut graph_events: EventWriter<ComponentGraphEvent>,
+) {
+    for (interaction, ComponentGraphButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            graph_events.write(event.clone());
+        }
+    }
+}
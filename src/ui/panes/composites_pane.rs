@@ -0,0 +1,293 @@
+//! Composites Pane Module
+//!
+//! Recipe glyphs the font lacks, a page at a time, with buttons building the
+//! chosen ones or all of them, and what the last build made or skipped.
+
+use crate::core::state::AppState;
+use crate::data::glyph_recipes::{builtin_recipes, CompositeBuildReport, GlyphRecipe};
+use crate::editing::composites::{missing_recipes, CompositesPanel};
+use crate::i18n;
+use crate::systems::commands::{BuildCompositesEvent, LastCompositeBuild};
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Missing glyphs listed per page
+const PAGE_SIZE: usize = 12;
+
+/// Glyphs of the last build's report listed before the rest are counted
+const MAX_REPORT_ROWS: usize = 8;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the composites pane
+#[derive(Component, Default)]
+pub struct CompositesPane;
+
+/// Container of the rows, rebuilt when what they show changes
+#[derive(Component)]
+struct CompositeRows;
+
+/// What a button does
+#[derive(Debug, Clone, PartialEq)]
+enum CompositesAction {
+    /// Choose a glyph, or drop it if it was chosen
+    Choose(String),
+    /// Show a page of the missing glyphs
+    Page(usize),
+    BuildChosen,
+    BuildAll,
+}
+
+/// The action a button takes
+#[derive(Component, Clone)]
+struct CompositesButton(CompositesAction);
+
+/// What the rows were last built from
+#[derive(Clone, PartialEq)]
+struct ShownComposites {
+    missing: Vec<String>,
+    panel: CompositesPanel,
+    report: Option<CompositeBuildReport>,
+}
+
+/// The built-in recipes, parsed once
+#[derive(Resource)]
+struct Recipes(Vec<GlyphRecipe>);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct CompositesPanePlugin;
+
+impl Plugin for CompositesPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Recipes(builtin_recipes()))
+            .add_systems(Startup, spawn_composites_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_composites_buttons,
+                    rebuild_composite_rows,
+                    toggle_pane_visibility::<CompositesPane, _>(|panel: Res<CompositesPanel>| {
+                        panel.visible
+                    }),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the composites pane at its slot
+pub fn spawn_composites_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::Composites,
+        CompositesPane,
+        "CompositesPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("composites-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(CompositeRows));
+    });
+}
+
+/// Number of pages `count` missing glyphs fill
+fn page_count(count: usize) -> usize {
+    count.div_ceil(PAGE_SIZE).max(1)
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the missing glyphs, the choices or the last
+/// build's report change
+#[allow(clippy::too_many_arguments)]
+fn rebuild_composite_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<ShownComposites>>,
+    rows_query: Query<Entity, With<CompositeRows>>,
+    panel: Res<CompositesPanel>,
+    recipes: Res<Recipes>,
+    last_build: Res<LastCompositeBuild>,
+    app_state: Option<Res<AppState>>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !panel.visible {
+        return;
+    }
+    let Some(state) = app_state else {
+        return;
+    };
+    let current = ShownComposites {
+        missing: missing_recipes(&state.workspace.font, &recipes.0),
+        panel: panel.clone(),
+        report: last_build.0.clone(),
+    };
+    if shown.as_ref() == Some(&current) {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = Some(current.clone());
+
+    let (asset_server, embedded_fonts, theme) = (&*asset_server, &*embedded_fonts, &*theme);
+    rebuild_rows(&mut commands, rows, |parent| {
+        let missing = &current.missing;
+        let pages = page_count(missing.len());
+        let page = current.panel.page.min(pages - 1);
+        let label = i18n::tr_args(
+            "composites-missing",
+            &[
+                ("missing", &missing.len().to_string()),
+                ("chosen", &current.panel.chosen.len().to_string()),
+            ],
+        );
+        create_label_text(parent, &label, (), asset_server, embedded_fonts, theme);
+
+        for glyph_name in missing.iter().skip(page * PAGE_SIZE).take(PAGE_SIZE) {
+            let mark = if current.panel.chosen.contains(glyph_name) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            let label = format!("{mark} {glyph_name}");
+            let action = CompositesAction::Choose(glyph_name.clone());
+            create_pane_text_button(
+                parent,
+                &label,
+                CompositesButton(action),
+                asset_server,
+                embedded_fonts,
+                theme,
+            );
+        }
+        if pages > 1 {
+            parent.spawn(pane_row_node()).with_children(|row| {
+                let previous = (page + pages - 1) % pages;
+                let action = CompositesAction::Page(previous);
+                create_pane_text_button(
+                    row,
+                    "<",
+                    CompositesButton(action),
+                    asset_server,
+                    embedded_fonts,
+                    theme,
+                );
+                let label = i18n::tr_args(
+                    "pane-page",
+                    &[
+                        ("page", &(page + 1).to_string()),
+                        ("count", &pages.to_string()),
+                    ],
+                );
+                create_label_text(row, &label, (), asset_server, embedded_fonts, theme);
+                let action = CompositesAction::Page((page + 1) % pages);
+                create_pane_text_button(
+                    row,
+                    ">",
+                    CompositesButton(action),
+                    asset_server,
+                    embedded_fonts,
+                    theme,
+                );
+            });
+        }
+
+        parent.spawn(pane_row_node()).with_children(|row| {
+            let mut buttons = Vec::new();
+            if !current.panel.chosen.is_empty() {
+                buttons.push(("composites-build-chosen", CompositesAction::BuildChosen));
+            }
+            if !missing.is_empty() {
+                buttons.push(("composites-build-all", CompositesAction::BuildAll));
+            }
+            for (label, action) in buttons {
+                create_pane_text_button(
+                    row,
+                    &i18n::tr(label),
+                    CompositesButton(action),
+                    asset_server,
+                    embedded_fonts,
+                    theme,
+                );
+            }
+        });
+
+        let Some(report) = &current.report else {
+            return;
+        };
+        let label = i18n::tr_args(
+            "composites-last-build",
+            &[
+                ("built", &report.built.len().to_string()),
+                ("skipped", &report.skipped.len().to_string()),
+            ],
+        );
+        create_label_text(parent, &label, (), asset_server, embedded_fonts, theme);
+        for (glyph_name, reason) in report.skipped.iter().take(MAX_REPORT_ROWS) {
+            let label = format!("{glyph_name}: {reason}");
+            create_label_text(parent, &label, (), asset_server, embedded_fonts, theme);
+        }
+        if report.skipped.len() > MAX_REPORT_ROWS {
+            let count = (report.skipped.len() - MAX_REPORT_ROWS).to_string();
+            let label = i18n::tr_args("composites-more", &[("count", &count)]);
+            create_label_text(parent, &label, (), asset_server, embedded_fonts, theme);
+        }
+    });
+}
+
+/// Applies clicked buttons to the choices or sends build events
+fn handle_composites_buttons(
+    interaction_query: Query<(&Interaction, &CompositesButton), Changed<Interaction>>,
+    mut panel: ResMut<CompositesPanel>,
+    mut build_events: EventWriter<BuildCompositesEvent>,
+) {
+    for (interaction, CompositesButton(action)) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            CompositesAction::Choose(glyph_name) => panel.toggle(glyph_name),
+            CompositesAction::Page(page) => panel.page = *page,
+            CompositesAction::BuildChosen => {
+                let glyph_names = std::mem::take(&mut panel.chosen).into_iter().collect();
+                build_events.write(BuildCompositesEvent {
+                    glyph_names: Some(glyph_names),
+                });
+            }
+            CompositesAction::BuildAll => {
+                panel.chosen.clear();
+                build_events.write(BuildCompositesEvent::default());
+            }
+        }
+    }
+}
@@ -9,6 +9,7 @@
 use crate::editing::selection::components::Selected;
 use crate::geometry::quadrant::Quadrant;
 use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_value_text};
+use crate::ui::panes::pane::pane_node;
 use crate::ui::theme::*;
 use crate::ui::theme_system::UiBorderRadius;
 use crate::ui::themes::CurrentTheme;
@@ -142,6 +143,7 @@ pub fn spawn_coordinate_pane(
             CoordinatePane,
             "CoordinatePane",
         ))
+        .insert(pane_node("CoordinatePane"))
         .with_children(|parent| {
             // ============ COORDINATE ROWS ============
 
@@ -0,0 +1,237 @@
+//! Curve Flavor Pane Module
+//!
+//! Which curves the active glyph has, what converting it or the font would
+//! add, and buttons converting between cubic and quadratic within the
+//! maximum error.
+
+use crate::core::state::{AppState, OutlineData};
+use crate::editing::curve_flavor::{CurveFlavorEvent, CurveFlavorPanel};
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::font_source::curve_flavor::{added_points, to_cubic, to_quadratic, CurveFlavor};
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{spawn_pane, toggle_pane_visibility, PaneSlot, PANE_ROW_GAP};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// The pane's button rows, each label by its catalog id
+const BUTTON_ROWS: [[(&str, CurveFlavorEvent); 2]; 3] = [
+    [
+        (
+            "curve-flavor-error-down",
+            CurveFlavorEvent::StepMaxError(false),
+        ),
+        (
+            "curve-flavor-error-up",
+            CurveFlavorEvent::StepMaxError(true),
+        ),
+    ],
+    [
+        (
+            "curve-flavor-glyph-to-quadratic",
+            CurveFlavorEvent::ToQuadratic { whole_font: false },
+        ),
+        (
+            "curve-flavor-glyph-to-cubic",
+            CurveFlavorEvent::ToCubic { whole_font: false },
+        ),
+    ],
+    [
+        (
+            "curve-flavor-font-to-quadratic",
+            CurveFlavorEvent::ToQuadratic { whole_font: true },
+        ),
+        (
+            "curve-flavor-font-to-cubic",
+            CurveFlavorEvent::ToCubic { whole_font: true },
+        ),
+    ],
+];
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the curve flavor pane
+#[derive(Component, Default)]
+pub struct CurveFlavorPane;
+
+/// The flavor, maximum error and point counts
+#[derive(Component)]
+struct CurveFlavorText;
+
+/// The curve flavor event a button sends
+#[derive(Component, Clone)]
+struct CurveFlavorButton(CurveFlavorEvent);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct CurveFlavorPanePlugin;
+
+impl Plugin for CurveFlavorPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_curve_flavor_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_curve_flavor_buttons,
+                    update_curve_flavor_text,
+                    toggle_pane_visibility::<CurveFlavorPane, _>(|panel: Res<CurveFlavorPanel>| {
+                        panel.visible
+                    }),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the curve flavor pane at its slot
+pub fn spawn_curve_flavor_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::CurveFlavor,
+        CurveFlavorPane,
+        "CurveFlavorPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("curve-flavor-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        create_label_text(
+            parent,
+            "",
+            CurveFlavorText,
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+        for buttons in BUTTON_ROWS {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(PANE_ROW_GAP),
+                    margin: UiRect::top(Val::Px(PANE_ROW_GAP)),
+                    ..default()
+                })
+                .with_children(|row| {
+                    for (label, event) in buttons {
+                        create_pane_text_button(
+                            row,
+                            &i18n::tr(label),
+                            CurveFlavorButton(event),
+                            &asset_server,
+                            &embedded_fonts,
+                            &theme,
+                        );
+                    }
+                });
+        }
+    });
+}
+
+/// A point count with its sign, e.g. "+24 points"
+fn points_label(added: isize) -> String {
+    i18n::tr_args("curve-flavor-points", &[("count", &format!("{added:+}"))])
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Keeps the text in step with the active glyph and the font
+fn update_curve_flavor_text(
+    mut shown: Local<Option<(CurveFlavorPanel, Option<OutlineData>)>>,
+    panel: Res<CurveFlavorPanel>,
+    app_state: Option<Res<AppState>>,
+    active_sorts: Query<&Sort, With<ActiveSort>>,
+    mut text_query: Query<&mut Text, With<CurveFlavorText>>,
+) {
+    if !panel.visible {
+        return;
+    }
+    let outline = active_sorts.single().ok().and_then(|sort| {
+        app_state
+            .as_deref()?
+            .workspace
+            .font
+            .editing()
+            .get_glyph(&sort.glyph_name)?
+            .outline
+            .clone()
+    });
+    // Converting takes a while, so only when something changed
+    let current = Some((panel.clone(), outline.clone()));
+    if *shown == current {
+        return;
+    }
+    *shown = current;
+
+    let error = panel.max_error.to_string();
+    let mut lines = vec![i18n::tr_args(
+        "curve-flavor-max-error",
+        &[("error", &error)],
+    )];
+    match outline {
+        Some(outline) => {
+            let contours = &outline.contours;
+            let quadratic = match to_quadratic(contours, panel.max_error) {
+                Ok(converted) => points_label(added_points(contours, &converted)),
+                Err(_) => i18n::tr("curve-flavor-not-within"),
+            };
+            let cubic = added_points(contours, &to_cubic(contours));
+            let flavor = CurveFlavor::of(contours).label();
+            lines.push(i18n::tr_args("curve-flavor-glyph", &[("flavor", &flavor)]));
+            let added = i18n::tr_args(
+                "curve-flavor-added",
+                &[("quadratic", &quadratic), ("cubic", &points_label(cubic))],
+            );
+            lines.push(format!("  {added}"));
+        }
+        None => lines.push(i18n::tr("curve-flavor-no-glyph")),
+    }
+    if let Some((quadratic, cubic)) = panel.font_added {
+        lines.push(i18n::tr_args(
+            "curve-flavor-font",
+            &[
+                ("quadratic", &points_label(quadratic)),
+                ("cubic", &points_label(cubic)),
+            ],
+        ));
+    }
+    let label = lines.join("\n");
+    for mut text in text_query.iter_mut() {
+        if text.0 != label {
+            text.0 = label.clone();
+        }
+    }
+}
+
+/// Sends the curve flavor events of clicked buttons
+fn handle_curve_flavor_buttons(
+    interaction_query: Query<(&Interaction, &CurveFlavorButton), Changed<Interaction>>,
+    mut events: EventWriter<CurveFlavorEvent>,
+) {
+    for (interaction, CurveFlavorButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            events.write(*event);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! Design Frames Pane Module
+//!
+//! Switches the CJK center lines and third grid, and resizes, adds or removes
+//! inner frames.
+
+use crate::core::state::AppState;
+use crate::editing::design_frames::{DesignFramesEvent, DesignFramesOverlay};
+use crate::font_source::DesignFrames;
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// How much a button grows or shrinks an inner frame, in percent of the em
+const RESIZE_STEP: f64 = 1.0;
+
+/// Size of a newly added inner frame, in percent of the em
+const NEW_FRAME_PERCENT: f64 = 80.0;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the design frames pane
+#[derive(Component, Default)]
+pub struct DesignFramesPane;
+
+/// Container of the rows, rebuilt when the frames change
+#[derive(Component)]
+struct FrameRows;
+
+/// The design frames event a button sends
+#[derive(Component, Clone)]
+struct FrameButton(DesignFramesEvent);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct DesignFramesPanePlugin;
+
+impl Plugin for DesignFramesPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_design_frames_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_frame_buttons,
+                    rebuild_frame_rows,
+                    toggle_pane_visibility::<DesignFramesPane, _>(
+                        |overlay: Res<DesignFramesOverlay>| overlay.visible,
+                    ),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the design frames pane at its slot
+pub fn spawn_design_frames_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::DesignFrames,
+        DesignFramesPane,
+        "DesignFramesPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("design-frames-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(FrameRows));
+    });
+}
+
+fn on_off(on: bool) -> String {
+    i18n::tr(if on { "pref-on" } else { "pref-off" })
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the project's frames change
+fn rebuild_frame_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<DesignFrames>>,
+    rows_query: Query<Entity, With<FrameRows>>,
+    app_state: Option<Res<AppState>>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    let frames = app_state.map(|state| state.workspace.font.design_frames.clone());
+    if *shown == frames {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = frames.clone();
+
+    rebuild_rows(&mut commands, rows, |parent| {
+        let Some(frames) = frames else {
+            return;
+        };
+        let toggles = [
+            (
+                i18n::tr_args(
+                    "design-frames-center-lines",
+                    &[("state", &on_off(frames.center_lines))],
+                ),
+                DesignFramesEvent::ToggleCenterLines,
+            ),
+            (
+                i18n::tr_args(
+                    "design-frames-thirds",
+                    &[("state", &on_off(frames.third_grid))],
+                ),
+                DesignFramesEvent::ToggleThirdGrid,
+            ),
+        ];
+        for (label, event) in toggles {
+            create_pane_text_button(
+                parent,
+                &label,
+                FrameButton(event),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        }
+        for (index, percent) in frames.inner_frames.iter().enumerate() {
+            parent.spawn(pane_row_node()).with_children(|row| {
+                let percent = format!("{percent:>5.1}");
+                let label = i18n::tr_args("design-frames-frame", &[("percent", &percent)]);
+                create_label_text(row, &label, (), &asset_server, &embedded_fonts, &theme);
+                let remove = i18n::tr("pane-remove");
+                let buttons = [
+                    (
+                        "-",
+                        DesignFramesEvent::ResizeInnerFrame(index, -RESIZE_STEP),
+                    ),
+                    ("+", DesignFramesEvent::ResizeInnerFrame(index, RESIZE_STEP)),
+                    (remove.as_str(), DesignFramesEvent::RemoveInnerFrame(index)),
+                ];
+                for (label, event) in buttons {
+                    create_pane_text_button(
+                        row,
+                        label,
+                        FrameButton(event),
+                        &asset_server,
+                        &embedded_fonts,
+                        &theme,
+                    );
+                }
+            });
+        }
+        create_pane_text_button(
+            parent,
+            &i18n::tr("design-frames-add"),
+            FrameButton(DesignFramesEvent::AddInnerFrame(NEW_FRAME_PERCENT)),
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+    });
+}
+
+/// Sends the design frames events of clicked buttons
+fn handle_frame_buttons(
+    interaction_query: Query<(&Interaction, &FrameButton), Changed<Interaction>>,
+    mut frame_events: EventWriter<DesignFramesEvent>,
+) {
+    for (interaction, FrameButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            frame_events.write(event.clone());
+        }
+    }
+}
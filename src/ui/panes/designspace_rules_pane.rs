@@ -0,0 +1,292 @@
+//! Designspace Rules Pane Module
+//!
+//! The designspace's substitution rules at a previewed location: what each
+//! replaces, its conditions with steppable bounds, and what's wrong with it.
+
+use crate::core::state::AppState;
+use crate::editing::designspace_rules::{DesignspaceRulesEvent, DesignspaceRulesState};
+use crate::font_source::designspace_rules::{DesignspaceRules, Location};
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the designspace rules pane
+#[derive(Component, Default)]
+pub struct DesignspaceRulesPane;
+
+/// Container of the rows, rebuilt when the rules or the location change
+#[derive(Component)]
+struct RuleRows;
+
+/// The designspace rules event a button sends
+#[derive(Component, Clone)]
+struct RuleButton(DesignspaceRulesEvent);
+
+/// What the rows were last built from
+type ShownRules = (DesignspaceRules, Location, Vec<Vec<String>>);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct DesignspaceRulesPanePlugin;
+
+impl Plugin for DesignspaceRulesPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_designspace_rules_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_rule_buttons,
+                    rebuild_rule_rows,
+                    toggle_pane_visibility::<DesignspaceRulesPane, _>(
+                        |rules_state: Res<DesignspaceRulesState>| rules_state.visible,
+                    ),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the designspace rules pane at its slot
+pub fn spawn_designspace_rules_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::DesignspaceRules,
+        DesignspaceRulesPane,
+        "DesignspaceRulesPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("designspace-rules-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(RuleRows));
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the rules, the location or the problems change
+#[allow(clippy::too_many_arguments)]
+fn rebuild_rule_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<ShownRules>>,
+    rows_query: Query<Entity, With<RuleRows>>,
+    rules_state: Res<DesignspaceRulesState>,
+    app_state: Option<Res<AppState>>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    let current = rules_state.rules.as_ref().map(|rules| {
+        let problems = app_state
+            .as_ref()
+            .map(|state| {
+                let font = &state.workspace.font;
+                rules
+                    .rules
+                    .iter()
+                    .map(|rule| rules.rule_problems(rule, font))
+                    .collect()
+            })
+            .unwrap_or_default();
+        (rules.clone(), rules_state.location.clone(), problems)
+    });
+    if *shown == current {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = current.clone();
+
+    rebuild_rows(&mut commands, rows, |parent| {
+        let Some((rules, location, problems)) = current else {
+            create_label_text(
+                parent,
+                &i18n::tr("pane-no-designspace"),
+                (),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            return;
+        };
+
+        for (index, axis) in rules.axes.iter().enumerate() {
+            let value = location.get(&axis.name).copied().unwrap_or(axis.default);
+            parent.spawn(pane_row_node()).with_children(|row| {
+                create_label_text(
+                    row,
+                    &format!("{} {value}", axis.name),
+                    (),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+                let buttons = [
+                    ("-", DesignspaceRulesEvent::StepLocation(index, false)),
+                    ("+", DesignspaceRulesEvent::StepLocation(index, true)),
+                ];
+                for (label, event) in buttons {
+                    create_pane_text_button(
+                        row,
+                        label,
+                        RuleButton(event),
+                        &asset_server,
+                        &embedded_fonts,
+                        &theme,
+                    );
+                }
+            });
+        }
+
+        for (index, rule) in rules.rules.iter().enumerate() {
+            let state = i18n::tr(if rule.applies_at(&location) {
+                "pref-on"
+            } else {
+                "pref-off"
+            });
+            let substitutions: Vec<String> = rule
+                .substitutions
+                .iter()
+                .map(|(from, to)| format!("{from} → {to}"))
+                .collect();
+            parent.spawn(pane_row_node()).with_children(|row| {
+                create_label_text(
+                    row,
+                    &format!("{} ({state}): {}", rule.name, substitutions.join(", ")),
+                    (),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+                let buttons = [
+                    (
+                        "designspace-rules-add-condition",
+                        DesignspaceRulesEvent::AddCondition(index),
+                    ),
+                    ("pane-remove", DesignspaceRulesEvent::RemoveRule(index)),
+                ];
+                for (label, event) in buttons {
+                    create_pane_text_button(
+                        row,
+                        &i18n::tr(label),
+                        RuleButton(event),
+                        &asset_server,
+                        &embedded_fonts,
+                        &theme,
+                    );
+                }
+            });
+
+            let conditions = rule.condition_sets.first().into_iter().flatten();
+            for (condition_index, condition) in conditions.enumerate() {
+                let step = |maximum, forward| DesignspaceRulesEvent::StepCondition {
+                    rule: index,
+                    condition: condition_index,
+                    maximum,
+                    forward,
+                };
+                parent.spawn(pane_row_node()).with_children(|row| {
+                    create_label_text(
+                        row,
+                        &condition.label(),
+                        (),
+                        &asset_server,
+                        &embedded_fonts,
+                        &theme,
+                    );
+                    let buttons = [
+                        ("designspace-rules-min-down", step(false, false)),
+                        ("designspace-rules-min-up", step(false, true)),
+                        ("designspace-rules-max-down", step(true, false)),
+                        ("designspace-rules-max-up", step(true, true)),
+                        (
+                            "pane-remove",
+                            DesignspaceRulesEvent::RemoveCondition(index, condition_index),
+                        ),
+                    ];
+                    for (label, event) in buttons {
+                        create_pane_text_button(
+                            row,
+                            &i18n::tr(label),
+                            RuleButton(event),
+                            &asset_server,
+                            &embedded_fonts,
+                            &theme,
+                        );
+                    }
+                });
+            }
+            // Sets past the first are kept but only listed
+            for set in rule.condition_sets.iter().skip(1) {
+                let labels: Vec<String> = set.iter().map(|condition| condition.label()).collect();
+                create_label_text(
+                    parent,
+                    &i18n::tr_args(
+                        "designspace-rules-or",
+                        &[("conditions", &labels.join(", "))],
+                    ),
+                    (),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+            for problem in problems.get(index).into_iter().flatten() {
+                create_label_text(
+                    parent,
+                    &format!("! {problem}"),
+                    (),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+        }
+
+        create_pane_text_button(
+            parent,
+            &i18n::tr("designspace-rules-add-rule"),
+            RuleButton(DesignspaceRulesEvent::AddFromSelection),
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+    });
+}
+
+/// Sends the designspace rules events of clicked buttons
+fn handle_rule_buttons(
+    interaction_query: Query<(&Interaction, &RuleButton), Changed<Interaction>>,
+    mut rule_events: EventWriter<DesignspaceRulesEvent>,
+) {
+    for (interaction, RuleButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            rule_events.write(event.clone());
+        }
+    }
+}
@@ -0,0 +1,473 @@
+//! Export Pane Module
+//!
+//! The export dialog (Cmd/Ctrl+E). Picks an export profile and edits its
+//! formats, subset, styles and output path; changes are saved in the profile.
+//! Also builds the whole family when a family project lists the font (see
+//! `data::family`) and reopens the build inspector afterwards.
+//!
+//! While an export runs in the background the pane shows its progress and a
+//! cancel button instead, and stays up even if the dialog is closed.
+
+use crate::core::config::BezySettings;
+use crate::core::state::AppState;
+use crate::data::family::find_family_project;
+use crate::editing::build_inspector::{BuildInspector, BuildInspectorEvent};
+use crate::editing::selection::events::AppStateChanged;
+use crate::font_source::curve_flavor::{step_max_error, MAX_ERROR_STEPS};
+use crate::font_source::export_profiles::{ExportFormat, ExportProfile, FormatSettings};
+use crate::i18n;
+use crate::ui::accessibility::dialog_node;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::file_menu::{BuildFamilyEvent, ExportDialogState, ExportFontEvent, ExportJob};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::file_pane::FileInfo;
+use crate::ui::panes::pane::{
+    pane_mirrored_row, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the export pane
+#[derive(Component, Default)]
+pub struct ExportPane;
+
+/// Container of the dialog's rows, rebuilt when the dialog changes
+#[derive(Component)]
+struct ExportDialogContent;
+
+/// Text showing the progress of the running export
+#[derive(Component)]
+struct ExportProgressText;
+
+/// What a button in the dialog does; format settings are by index
+#[derive(Component, Clone)]
+enum ExportDialogButton {
+    Profile(String),
+    /// Step through the maximum errors of quadratic outlines
+    CurveError(usize),
+    Autohint(usize),
+    RemoveOverlaps(usize),
+    SvgVariant(usize),
+    Export,
+    BuildFamily(PathBuf),
+    /// Open the build inspector on the last build
+    Inspect,
+    Cancel,
+    Close,
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct ExportPanePlugin;
+
+impl Plugin for ExportPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_export_pane).add_systems(
+            Update,
+            (
+                handle_export_dialog_buttons,
+                select_default_profile.before(rebuild_export_dialog),
+                rebuild_export_dialog,
+                update_export_progress,
+                toggle_pane_visibility::<ExportPane, _>(
+                    |dialog: Res<ExportDialogState>, job: Res<ExportJob>| {
+                        dialog.open || job.is_running()
+                    },
+                ),
+            ),
+        );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the export pane, hidden until the dialog is opened
+pub fn spawn_export_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::Export,
+        ExportPane,
+        "ExportPane",
+        &asset_server,
+        &theme,
+    )
+    .insert(dialog_node(&i18n::tr("export-title")))
+    .with_children(|parent| {
+        let title = LocalizedText("export-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(ExportDialogContent));
+    });
+}
+
+/// A row naming a format, with a toggle for each of its settings
+fn spawn_format_row(
+    parent: &mut ChildSpawnerCommands,
+    index: usize,
+    settings: &FormatSettings,
+    asset_server: &AssetServer,
+    embedded_fonts: &EmbeddedFonts,
+    theme: &CurrentTheme,
+) {
+    let on_off = |on: bool| i18n::tr(if on { "pref-on" } else { "pref-off" });
+    // Variable fonts keep their overlaps, flagged
+    let overlaps = match settings.format {
+        ExportFormat::Variable => "export-flag-overlaps",
+        ExportFormat::Static => "export-remove-overlaps",
+    };
+    let error = settings.quadratic_max_error.to_string();
+    let toggles = [
+        (
+            i18n::tr_args("export-curve-error", &[("error", &error)]),
+            ExportDialogButton::CurveError(index),
+        ),
+        (
+            i18n::tr_args("export-autohint", &[("state", &on_off(settings.autohint))]),
+            ExportDialogButton::Autohint(index),
+        ),
+        (
+            i18n::tr_args(overlaps, &[("state", &on_off(settings.remove_overlaps))]),
+            ExportDialogButton::RemoveOverlaps(index),
+        ),
+        (
+            i18n::tr_args("export-svg", &[("state", &on_off(settings.svg_variant))]),
+            ExportDialogButton::SvgVariant(index),
+        ),
+    ];
+    parent.spawn(pane_mirrored_row()).with_children(|row| {
+        let label = format!("{:<9}", settings.format.label());
+        create_label_text(row, &label, (), asset_server, embedded_fonts, theme);
+        for (label, button) in toggles {
+            create_pane_text_button(row, &label, button, asset_server, embedded_fonts, theme);
+        }
+    });
+}
+
+/// What the running export is doing, e.g. "Exporting 2/5: Font-Bold.ttf"
+fn progress_label(job: &ExportJob) -> String {
+    let Some(progress) = job.progress() else {
+        return String::new();
+    };
+    let (done, total) = progress.counts();
+    let file = progress.current();
+    if progress.is_cancelled() {
+        return i18n::tr_args("export-cancelling", &[("file", &file)]);
+    }
+    if total == 0 {
+        return i18n::tr("export-preparing");
+    }
+    let (done, total) = ((done + 1).min(total).to_string(), total.to_string());
+    i18n::tr_args(
+        "export-progress",
+        &[("done", &done), ("total", &total), ("file", &file)],
+    )
+}
+
+/// Subset, styles and output path of a profile, one line each
+fn profile_summary(profile: &ExportProfile) -> Vec<String> {
+    let subsets: Vec<String> = profile
+        .formats
+        .iter()
+        .filter(|settings| !settings.subset.is_empty())
+        .map(|settings| format!("{}: {}", settings.format.label(), settings.subset.join(" ")))
+        .collect();
+    let output_dir = if profile.output_dir.is_empty() {
+        "."
+    } else {
+        profile.output_dir.as_str()
+    };
+    let subset = if subsets.is_empty() {
+        i18n::tr("export-whole-font")
+    } else {
+        subsets.join(", ")
+    };
+    let styles = if profile.styles.is_empty() {
+        i18n::tr("export-all-sources")
+    } else {
+        profile.styles.join(", ")
+    };
+    let path = format!("{}/{}.ttf", output_dir, profile.filename_pattern);
+    vec![
+        i18n::tr_args("export-subset", &[("subset", &subset)]),
+        i18n::tr_args("export-styles", &[("styles", &styles)]),
+        i18n::tr_args("export-output", &[("path", &path)]),
+    ]
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Picks the default export profile from the preferences when the dialog
+/// opens without a profile picked, if the project has it
+fn select_default_profile(
+    mut dialog: ResMut<ExportDialogState>,
+    settings: Res<BezySettings>,
+    app_state: Option<Res<AppState>>,
+) {
+    if !dialog.is_changed() || !dialog.open || dialog.selected_profile.is_some() {
+        return;
+    }
+    let Some(default) = settings.default_export_profile.as_deref() else {
+        return;
+    };
+    let profiles = app_state
+        .as_deref()
+        .map(|state| &state.workspace.font.export_profiles);
+    if profiles.is_some_and(|profiles| profiles.get(Some(default)).is_some()) {
+        dialog.selected_profile = Some(default.to_string());
+    }
+}
+
+/// Rebuilds the dialog from the project's profiles when it changes
+#[allow(clippy::too_many_arguments)]
+fn rebuild_export_dialog(
+    mut commands: Commands,
+    content_query: Query<Entity, With<ExportDialogContent>>,
+    dialog: Res<ExportDialogState>,
+    job: Res<ExportJob>,
+    app_state: Option<Res<AppState>>,
+    file_info: Res<FileInfo>,
+    inspector: Res<BuildInspector>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !dialog.is_changed() {
+        return;
+    }
+    let Ok(content) = content_query.single() else {
+        return;
+    };
+
+    rebuild_rows(&mut commands, content, |parent| {
+        if job.is_running() {
+            create_label_text(
+                parent,
+                &progress_label(&job),
+                ExportProgressText,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            parent.spawn(pane_mirrored_row()).with_children(|row| {
+                create_pane_text_button(
+                    row,
+                    &i18n::tr("export-cancel"),
+                    ExportDialogButton::Cancel,
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            });
+            return;
+        }
+        if !dialog.open {
+            return;
+        }
+        let Some(state) = app_state.as_deref() else {
+            create_label_text(
+                parent,
+                &i18n::tr("export-no-font"),
+                (),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            return;
+        };
+        let profiles = &state.workspace.font.export_profiles;
+        let selected = profiles.get(dialog.selected_profile.as_deref());
+        let source = if file_info.designspace_path.is_empty() {
+            state.workspace.font.path.clone()
+        } else {
+            Some(PathBuf::from(&file_info.designspace_path))
+        };
+        let family_project = source.as_deref().and_then(find_family_project);
+
+        parent.spawn(pane_mirrored_row()).with_children(|row| {
+            for name in profiles.names() {
+                let is_selected = selected.is_some_and(|profile| profile.name == name);
+                let label = format!("{} {}", if is_selected { "●" } else { "○" }, name);
+                create_pane_text_button(
+                    row,
+                    &label,
+                    ExportDialogButton::Profile(name.to_string()),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+        });
+        let Some(profile) = selected else {
+            return;
+        };
+        for (index, settings) in profile.formats.iter().enumerate() {
+            spawn_format_row(
+                parent,
+                index,
+                settings,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        }
+        for line in profile_summary(profile) {
+            create_label_text(parent, &line, (), &asset_server, &embedded_fonts, &theme);
+        }
+        parent.spawn(pane_mirrored_row()).with_children(|row| {
+            create_pane_text_button(
+                row,
+                &i18n::tr("export-export"),
+                ExportDialogButton::Export,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            if let Some(project) = family_project {
+                let name = project.file_stem().unwrap_or_default().to_string_lossy();
+                create_pane_text_button(
+                    row,
+                    &i18n::tr_args("export-build-family", &[("family", &name)]),
+                    ExportDialogButton::BuildFamily(project.clone()),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+            if !inspector.fonts.is_empty() {
+                create_pane_text_button(
+                    row,
+                    &i18n::tr("export-inspect"),
+                    ExportDialogButton::Inspect,
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+            create_pane_text_button(
+                row,
+                &i18n::tr("button-close"),
+                ExportDialogButton::Close,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        });
+    });
+}
+
+/// Picks profiles, switches format settings and starts exports
+#[allow(clippy::too_many_arguments)]
+fn handle_export_dialog_buttons(
+    interaction_query: Query<(&Interaction, &ExportDialogButton), Changed<Interaction>>,
+    mut dialog: ResMut<ExportDialogState>,
+    job: Res<ExportJob>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut export_events: EventWriter<ExportFontEvent>,
+    mut family_events: EventWriter<BuildFamilyEvent>,
+    mut inspector_events: EventWriter<BuildInspectorEvent>,
+    mut app_state_changed: EventWriter<AppStateChanged>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let index = match button {
+            ExportDialogButton::Profile(name) => {
+                dialog.selected_profile = Some(name.clone());
+                continue;
+            }
+            ExportDialogButton::Export => {
+                export_events.write(ExportFontEvent(dialog.selected_profile.clone()));
+                continue;
+            }
+            ExportDialogButton::BuildFamily(project) => {
+                family_events.write(BuildFamilyEvent(project.clone()));
+                continue;
+            }
+            ExportDialogButton::Inspect => {
+                dialog.open = false;
+                inspector_events.write(BuildInspectorEvent::Show);
+                continue;
+            }
+            ExportDialogButton::Cancel => {
+                job.cancel();
+                continue;
+            }
+            ExportDialogButton::Close => {
+                dialog.open = false;
+                continue;
+            }
+            ExportDialogButton::CurveError(index)
+            | ExportDialogButton::Autohint(index)
+            | ExportDialogButton::RemoveOverlaps(index)
+            | ExportDialogButton::SvgVariant(index) => *index,
+        };
+
+        let Some(state) = app_state.as_mut() else {
+            continue;
+        };
+        let profiles = &mut state.workspace.font.export_profiles.profiles;
+        let selected = dialog.selected_profile.as_deref();
+        let Some(settings) = profiles
+            .iter_mut()
+            .enumerate()
+            .find(|(i, profile)| selected.map_or(*i == 0, |name| profile.name == name))
+            .and_then(|(_, profile)| profile.formats.get_mut(index))
+        else {
+            continue;
+        };
+        match button {
+            ExportDialogButton::CurveError(_) => {
+                // Past the largest error, back to the smallest
+                let current = settings.quadratic_max_error;
+                let next = step_max_error(current, true);
+                settings.quadratic_max_error = if next == current {
+                    MAX_ERROR_STEPS[0]
+                } else {
+                    next
+                };
+            }
+            ExportDialogButton::Autohint(_) => settings.autohint = !settings.autohint,
+            ExportDialogButton::RemoveOverlaps(_) => {
+                settings.remove_overlaps = !settings.remove_overlaps
+            }
+            ExportDialogButton::SvgVariant(_) => settings.svg_variant = !settings.svg_variant,
+            _ => {}
+        }
+        app_state_changed.write(AppStateChanged);
+        dialog.set_changed();
+    }
+}
+
+/// Keeps the progress text in step with the running export
+fn update_export_progress(
+    job: Res<ExportJob>,
+    mut text_query: Query<&mut Text, With<ExportProgressText>>,
+) {
+    if !job.is_running() {
+        return;
+    }
+    let label = progress_label(&job);
+    for mut text in text_query.iter_mut() {
+        if text.0 != label {
+            text.0 = label.clone();
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! Features Pane Module
+//!
+//! Shown with the text tool: the compiled font's OpenType features, switched
+//! on and off for the text buffers, after switches for shaping, mark placement
+//! and the substitution overlay.
+
+use crate::i18n;
+use crate::systems::compiled_font::CompiledFont;
+use crate::systems::text_shaping::{
+    MarkPositioning, ShapingSettings, ToggleFeatureEvent, ToggleMarkPositioningEvent,
+    ToggleShapingEvent, ToggleSubstitutionOverlayEvent,
+};
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::edit_mode_toolbar::CurrentTool;
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Gap between feature buttons
+const BUTTON_GAP: f32 = 4.0;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the features pane
+#[derive(Component, Default)]
+pub struct FeaturesPane;
+
+/// Container of the buttons, rebuilt when features or settings change
+#[derive(Component)]
+struct FeatureButtonList;
+
+/// What a button in the pane switches
+#[derive(Component, Clone)]
+enum FeatureButton {
+    Shaping,
+    MarkPositioning,
+    SubstitutionOverlay,
+    Feature(String),
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct FeaturesPanePlugin;
+
+impl Plugin for FeaturesPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_features_pane).add_systems(
+            Update,
+            (
+                handle_feature_buttons,
+                rebuild_feature_buttons,
+                toggle_pane_visibility::<FeaturesPane, _>(|current_tool: Res<CurrentTool>| {
+                    current_tool.get_current() == Some("text")
+                }),
+            ),
+        );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the features pane at its slot
+pub fn spawn_features_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::Features,
+        FeaturesPane,
+        "FeaturesPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("features-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                flex_wrap: FlexWrap::Wrap,
+                column_gap: Val::Px(BUTTON_GAP),
+                row_gap: Val::Px(BUTTON_GAP),
+                ..default()
+            },
+            FeatureButtonList,
+        ));
+    });
+}
+
+/// A button drawn pressed while what it toggles is on
+fn spawn_feature_button(
+    parent: &mut ChildSpawnerCommands,
+    label: &str,
+    is_on: bool,
+    button: FeatureButton,
+    asset_server: &AssetServer,
+    embedded_fonts: &EmbeddedFonts,
+    theme: &CurrentTheme,
+) {
+    let mut button =
+        create_pane_text_button(parent, label, button, asset_server, embedded_fonts, theme);
+    if is_on {
+        button.insert((
+            BackgroundColor(theme.theme().button_pressed()),
+            BorderColor(theme.theme().button_pressed_outline()),
+        ));
+    }
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the buttons from the compiled font's features
+#[allow(clippy::too_many_arguments)]
+fn rebuild_feature_buttons(
+    mut commands: Commands,
+    list_query: Query<Entity, With<FeatureButtonList>>,
+    compiled: Res<CompiledFont>,
+    settings: Res<ShapingSettings>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+    mut built: Local<bool>,
+) {
+    if *built && !compiled.is_changed() && !settings.is_changed() {
+        return;
+    }
+    let Ok(list) = list_query.single() else {
+        return;
+    };
+    *built = true;
+
+    rebuild_rows(&mut commands, list, |parent| {
+        spawn_feature_button(
+            parent,
+            &i18n::tr("features-shaping"),
+            settings.enabled,
+            FeatureButton::Shaping,
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+        if !settings.enabled {
+            return;
+        }
+        let anchors = settings.mark_positioning == MarkPositioning::Anchors;
+        spawn_feature_button(
+            parent,
+            &i18n::tr(if anchors {
+                "features-marks-anchors"
+            } else {
+                "features-marks-gpos"
+            }),
+            anchors,
+            FeatureButton::MarkPositioning,
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+        spawn_feature_button(
+            parent,
+            &i18n::tr("features-substitutions"),
+            settings.show_substitutions,
+            FeatureButton::SubstitutionOverlay,
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+
+        let Some(data) = compiled.data.as_ref() else {
+            let status = i18n::tr(if compiled.error.is_some() {
+                "features-compile-failed"
+            } else {
+                "features-compiling"
+            });
+            create_label_text(parent, &status, (), &asset_server, &embedded_fonts, &theme);
+            return;
+        };
+        for tag in data.features() {
+            spawn_feature_button(
+                parent,
+                &tag,
+                settings.is_feature_on(&tag),
+                FeatureButton::Feature(tag.clone()),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        }
+    });
+}
+
+/// Sends toggle events for clicked buttons
+fn handle_feature_buttons(
+    interaction_query: Query<(&Interaction, &FeatureButton), Changed<Interaction>>,
+    mut shaping_events: EventWriter<ToggleShapingEvent>,
+    mut feature_events: EventWriter<ToggleFeatureEvent>,
+    mut mark_events: EventWriter<ToggleMarkPositioningEvent>,
+    mut overlay_events: EventWriter<ToggleSubstitutionOverlayEvent>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            FeatureButton::Shaping => {
+                shaping_events.write(ToggleShapingEvent);
+            }
+            FeatureButton::MarkPositioning => {
+                mark_events.write(ToggleMarkPositioningEvent);
+            }
+            FeatureButton::SubstitutionOverlay => {
+                overlay_events.write(ToggleSubstitutionOverlayEvent);
+            }
+            FeatureButton::Feature(tag) => {
+                feature_events.write(ToggleFeatureEvent(tag.clone()));
+            }
+        }
+    }
+}
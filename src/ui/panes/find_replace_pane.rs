@@ -0,0 +1,314 @@
+//! Find/Replace Pane Module
+//!
+//! Find and replace fields for the whole font, the glyphs the query would
+//! change, and buttons replacing them or undoing the last replacement.
+
+use crate::editing::find_replace::{
+    ApplyReplaceEvent, FindReplaceField, FindReplaceKind, FindReplacePanel,
+    FindReplacePreviewEvent, FindReplaceState, UndoReplaceEvent,
+};
+use crate::font_source::find_replace::{Axis, ReplaceMatch, ReplaceQuery};
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Change of the coordinate tolerance per click, in font units
+const TOLERANCE_STEP: f64 = 0.5;
+
+/// Glyphs of a preview listed before the rest are counted
+const MAX_PREVIEW_ROWS: usize = 12;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the find/replace pane
+#[derive(Component, Default)]
+pub struct FindReplacePane;
+
+/// Container of the rows, rebuilt when what they show changes
+#[derive(Component)]
+struct FindReplaceRows;
+
+/// What a button does
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FindReplaceAction {
+    /// Look for the next kind of thing
+    NextKind,
+    /// Type into a field
+    Type(FindReplaceField),
+    ToggleAxis,
+    /// Step the coordinate tolerance up (`true`) or down
+    StepTolerance(bool),
+    Preview,
+    Apply,
+    Undo,
+}
+
+/// The action a button takes
+#[derive(Component, Clone)]
+struct FindReplaceButton(FindReplaceAction);
+
+/// What the rows were last built from
+#[derive(Clone, PartialEq)]
+struct ShownFindReplace {
+    panel: FindReplacePanel,
+    preview: Option<(ReplaceQuery, Vec<ReplaceMatch>)>,
+    can_undo: bool,
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct FindReplacePanePlugin;
+
+impl Plugin for FindReplacePanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_find_replace_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_find_replace_buttons,
+                    rebuild_find_replace_rows,
+                    toggle_pane_visibility::<FindReplacePane, _>(|panel: Res<FindReplacePanel>| {
+                        panel.visible
+                    }),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the find/replace pane at its slot
+pub fn spawn_find_replace_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::FindReplace,
+        FindReplacePane,
+        "FindReplacePane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("find-replace-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(FindReplaceRows));
+    });
+}
+
+/// A field's button label, with a cursor while it's typed into
+fn field_label(name: &str, text: &str, typing: bool) -> String {
+    let cursor = if typing { "_" } else { "" };
+    format!("{name}: {text}{cursor}")
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the fields, the preview or the undo history
+/// change
+#[allow(clippy::too_many_arguments)]
+fn rebuild_find_replace_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<ShownFindReplace>>,
+    rows_query: Query<Entity, With<FindReplaceRows>>,
+    panel: Res<FindReplacePanel>,
+    find_state: Res<FindReplaceState>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !panel.visible {
+        return;
+    }
+    let current = ShownFindReplace {
+        panel: panel.clone(),
+        preview: find_state.preview.clone(),
+        can_undo: find_state.can_undo(),
+    };
+    if shown.as_ref() == Some(&current) {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = Some(current.clone());
+
+    let (asset_server, embedded_fonts, theme) = (&*asset_server, &*embedded_fonts, &*theme);
+    let panel = &current.panel;
+    rebuild_rows(&mut commands, rows, |parent| {
+        let label = i18n::tr_args("find-replace-kind", &[("kind", &panel.kind.label())]);
+        let action = FindReplaceAction::NextKind;
+        create_pane_text_button(
+            parent,
+            &label,
+            FindReplaceButton(action),
+            asset_server,
+            embedded_fonts,
+            theme,
+        );
+
+        let fields = [
+            ("find-replace-find", &panel.find, FindReplaceField::Find),
+            (
+                "find-replace-replace",
+                &panel.replace,
+                FindReplaceField::Replace,
+            ),
+        ];
+        for (name, text, field) in fields {
+            let label = field_label(&i18n::tr(name), text, panel.typing == Some(field));
+            let action = FindReplaceAction::Type(field);
+            create_pane_text_button(
+                parent,
+                &label,
+                FindReplaceButton(action),
+                asset_server,
+                embedded_fonts,
+                theme,
+            );
+        }
+        if panel.kind == FindReplaceKind::Coordinate {
+            parent.spawn(pane_row_node()).with_children(|row| {
+                let axis = if panel.axis == Axis::X { "x" } else { "y" };
+                let label = i18n::tr_args("find-replace-axis", &[("axis", axis)]);
+                let action = FindReplaceAction::ToggleAxis;
+                create_pane_text_button(
+                    row,
+                    &label,
+                    FindReplaceButton(action),
+                    asset_server,
+                    embedded_fonts,
+                    theme,
+                );
+                let tolerance = panel.tolerance.to_string();
+                let label = i18n::tr_args("find-replace-tolerance", &[("tolerance", &tolerance)]);
+                create_label_text(row, &label, (), asset_server, embedded_fonts, theme);
+                for (label, up) in [("-", false), ("+", true)] {
+                    let action = FindReplaceAction::StepTolerance(up);
+                    create_pane_text_button(
+                        row,
+                        label,
+                        FindReplaceButton(action),
+                        asset_server,
+                        embedded_fonts,
+                        theme,
+                    );
+                }
+            });
+        }
+        if panel.typing.is_some() {
+            create_label_text(
+                parent,
+                &i18n::tr("find-replace-typing-hint"),
+                (),
+                asset_server,
+                embedded_fonts,
+                theme,
+            );
+        }
+
+        parent.spawn(pane_row_node()).with_children(|row| {
+            let mut buttons = vec![("find-replace-preview", FindReplaceAction::Preview)];
+            if current
+                .preview
+                .as_ref()
+                .is_some_and(|(_, matches)| !matches.is_empty())
+            {
+                buttons.push(("find-replace-replace-all", FindReplaceAction::Apply));
+            }
+            if current.can_undo {
+                buttons.push(("find-replace-undo", FindReplaceAction::Undo));
+            }
+            for (label, action) in buttons {
+                create_pane_text_button(
+                    row,
+                    &i18n::tr(label),
+                    FindReplaceButton(action),
+                    asset_server,
+                    embedded_fonts,
+                    theme,
+                );
+            }
+        });
+
+        let Some((_, matches)) = &current.preview else {
+            return;
+        };
+        let count = matches.len().to_string();
+        let label = i18n::tr_args("find-replace-would-change", &[("count", &count)]);
+        create_label_text(parent, &label, (), asset_server, embedded_fonts, theme);
+        for found in matches.iter().take(MAX_PREVIEW_ROWS) {
+            let label = format!("{}: {}", found.glyph_name, found.detail);
+            create_label_text(parent, &label, (), asset_server, embedded_fonts, theme);
+        }
+        if matches.len() > MAX_PREVIEW_ROWS {
+            let count = (matches.len() - MAX_PREVIEW_ROWS).to_string();
+            let label = i18n::tr_args("find-replace-more", &[("count", &count)]);
+            create_label_text(parent, &label, (), asset_server, embedded_fonts, theme);
+        }
+    });
+}
+
+/// Applies clicked buttons to the fields or sends find/replace events
+fn handle_find_replace_buttons(
+    interaction_query: Query<(&Interaction, &FindReplaceButton), Changed<Interaction>>,
+    mut panel: ResMut<FindReplacePanel>,
+    mut preview_events: EventWriter<FindReplacePreviewEvent>,
+    mut apply_events: EventWriter<ApplyReplaceEvent>,
+    mut undo_events: EventWriter<UndoReplaceEvent>,
+) {
+    for (interaction, FindReplaceButton(action)) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *action {
+            FindReplaceAction::NextKind => panel.kind = panel.kind.next(),
+            FindReplaceAction::Type(field) => panel.typing = Some(field),
+            FindReplaceAction::ToggleAxis => {
+                panel.axis = if panel.axis == Axis::X {
+                    Axis::Y
+                } else {
+                    Axis::X
+                };
+            }
+            FindReplaceAction::StepTolerance(up) => {
+                let step = if up { TOLERANCE_STEP } else { -TOLERANCE_STEP };
+                panel.tolerance = (panel.tolerance + step).max(0.0);
+            }
+            FindReplaceAction::Preview => match panel.query() {
+                Some(query) => {
+                    preview_events.write(FindReplacePreviewEvent(query));
+                }
+                None => warn!("Coordinates to find and replace have to be numbers"),
+            },
+            FindReplaceAction::Apply => {
+                apply_events.write(ApplyReplaceEvent);
+            }
+            FindReplaceAction::Undo => {
+                undo_events.write(UndoReplaceEvent);
+            }
+        }
+    }
+}
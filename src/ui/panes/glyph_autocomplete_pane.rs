@@ -0,0 +1,255 @@
+//! Glyph Autocomplete Pane Module
+//!
+//! This module draws the glyph name popup: what the name is for, the query,
+//! the completions with the character each glyph encodes, and a preview of
+//! the highlighted glyph filled in, drawn at the monitor's resolution.
+//! Clicking a completion picks it.
+
+use crate::core::state::{AppState, FontData};
+use crate::data::canvas_image::{composite, rasterize};
+use crate::editing::glyph_autocomplete::{GlyphAutocomplete, GlyphNamePicked, VISIBLE_ROWS};
+use crate::font_source::FontMetrics;
+use crate::i18n;
+use crate::ui::theme::*;
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::{AssetServerFontExt, EmbeddedFonts};
+use bevy::color::ColorToPacked;
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::window::PrimaryWindow;
+use kurbo::Shape;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Distance of the popup from the left and top of the window
+const PANEL_LEFT: Val = Val::Percent(35.0);
+const PANEL_TOP: Val = Val::Percent(15.0);
+
+/// Padding inside each completion row
+const OPTION_PADDING: f32 = 4.0;
+
+/// Height of the glyph preview, descender to ascender, in logical pixels
+const PREVIEW_HEIGHT: f64 = 96.0;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the glyph autocomplete popup
+#[derive(Component, Default)]
+pub struct GlyphAutocompletePanel;
+
+/// One completion row, by index in the popup's list
+#[derive(Component)]
+struct GlyphCompletionOption(usize);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct GlyphAutocompletePanePlugin;
+
+impl Plugin for GlyphAutocompletePanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (handle_completion_clicks, render_glyph_autocomplete),
+        );
+    }
+}
+
+// ============================================================================
+// PREVIEW
+// ============================================================================
+
+/// A glyph filled in over its advance, from the descender to the ascender,
+/// `PREVIEW_HEIGHT` logical pixels high in device pixels `window_scale`
+/// times as many
+fn preview_image(
+    font: &FontData,
+    metrics: &FontMetrics,
+    glyph_name: &str,
+    fill: [u8; 4],
+    window_scale: f64,
+) -> Option<Image> {
+    let glyph = font.glyphs.get(glyph_name)?;
+    let upm = metrics.units_per_em;
+    let descender = metrics.descender.unwrap_or(-0.2 * upm);
+    let ascender = metrics.ascender.unwrap_or(0.8 * upm);
+    let paths = font.resolved_bezpaths(glyph_name);
+
+    // Marks with no advance are shown with the room they draw in
+    let mut region = kurbo::Rect::new(0.0, descender, glyph.advance_width, ascender);
+    for path in &paths {
+        let bounds = path.bounding_box();
+        region.x0 = region.x0.min(bounds.x0);
+        region.x1 = region.x1.max(bounds.x1);
+    }
+    if region.width() < 1.0 {
+        region.x1 = region.x0 + upm / 2.0;
+    }
+
+    let pixels_per_unit = PREVIEW_HEIGHT * window_scale / (ascender - descender);
+    let (width, height, coverage) = rasterize(&paths, region, pixels_per_unit).ok()?;
+    Some(Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        composite(&coverage, fill, None),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    ))
+}
+
+/// A row's text: the glyph's name, then the character it encodes and its
+/// codepoint
+fn option_label(name: &str, codepoint: Option<char>) -> String {
+    match codepoint {
+        Some(c) if !c.is_control() => format!("{name}  {c} U+{:04X}", c as u32),
+        Some(c) => format!("{name}  U+{:04X}", c as u32),
+        None => name.to_string(),
+    }
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the popup when its query or highlight changes, or the window
+/// moves to a monitor of another DPI
+#[allow(clippy::too_many_arguments)]
+fn render_glyph_autocomplete(
+    mut commands: Commands,
+    autocomplete: Res<GlyphAutocomplete>,
+    panel_query: Query<Entity, With<GlyphAutocompletePanel>>,
+    app_state: Option<Res<AppState>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut last_window_scale: Local<f32>,
+    mut images: ResMut<Assets<Image>>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    let window_scale = window_query
+        .single()
+        .map_or(1.0, |window| window.scale_factor());
+    let rescaled = *last_window_scale != window_scale;
+    *last_window_scale = window_scale;
+    if !(autocomplete.is_changed() || (rescaled && autocomplete.is_open())) {
+        return;
+    }
+    for entity in panel_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    let Some(target) = autocomplete.target.as_ref() else {
+        return;
+    };
+
+    let fill = theme.theme().filled_glyph_color().to_srgba().to_u8_array();
+    let preview = app_state.as_ref().and_then(|state| {
+        let glyph_name = autocomplete.selected_glyph()?;
+        preview_image(
+            &state.workspace.font,
+            &state.workspace.info.metrics,
+            glyph_name,
+            fill,
+            window_scale as f64,
+        )
+    });
+
+    let position = UiRect {
+        left: PANEL_LEFT,
+        top: PANEL_TOP,
+        right: Val::Auto,
+        bottom: Val::Auto,
+    };
+    let text_font = TextFont {
+        font: asset_server.load_font_with_fallback(theme.theme().mono_font_path(), &embedded_fonts),
+        font_size: WIDGET_TEXT_FONT_SIZE,
+        ..default()
+    };
+    commands
+        .spawn(create_widget_style(
+            &asset_server,
+            &theme,
+            PositionType::Absolute,
+            position,
+            GlyphAutocompletePanel,
+            "GlyphAutocompletePanel",
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("{}: {}▏", target.prompt(), autocomplete.query)),
+                text_font.clone(),
+                TextColor(theme.get_ui_text_primary()),
+            ));
+            let first = autocomplete.first_visible();
+            for (index, completion) in autocomplete
+                .completions
+                .iter()
+                .enumerate()
+                .skip(first)
+                .take(VISIBLE_ROWS)
+            {
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            padding: UiRect::all(Val::Px(OPTION_PADDING)),
+                            ..default()
+                        },
+                        BackgroundColor(if index == autocomplete.selected {
+                            theme.theme().button_pressed()
+                        } else {
+                            theme.theme().button_regular()
+                        }),
+                        GlyphCompletionOption(index),
+                    ))
+                    .with_children(|row| {
+                        row.spawn((
+                            Text::new(option_label(&completion.name, completion.codepoint)),
+                            text_font.clone(),
+                            TextColor(theme.get_ui_text_primary()),
+                        ));
+                    });
+            }
+            if autocomplete.completions.is_empty() {
+                parent.spawn((
+                    Text::new(i18n::tr("autocomplete-no-glyphs")),
+                    text_font.clone(),
+                    TextColor(theme.get_ui_text_secondary()),
+                ));
+            }
+            if let Some(image) = preview {
+                let size = image.size().as_vec2() / window_scale;
+                parent.spawn((
+                    ImageNode::new(images.add(image)),
+                    Node {
+                        width: Val::Px(size.x),
+                        height: Val::Px(size.y),
+                        ..default()
+                    },
+                ));
+            }
+        });
+}
+
+/// Picks a completion when its row is clicked
+fn handle_completion_clicks(
+    interaction_query: Query<(&Interaction, &GlyphCompletionOption), Changed<Interaction>>,
+    mut autocomplete: ResMut<GlyphAutocomplete>,
+    mut picked_events: EventWriter<GlyphNamePicked>,
+) {
+    for (interaction, GlyphCompletionOption(index)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            picked_events.write_batch(autocomplete.pick(*index));
+            return;
+        }
+    }
+}
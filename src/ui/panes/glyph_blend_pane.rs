@@ -0,0 +1,342 @@
+//! Glyph Blend Pane Module
+//!
+//! Sliders for the blend between two glyphs being previewed (a second one for
+//! y when the axes are apart), with buttons to add the blend, play it back,
+//! save it as a GIF or cancel. Hidden while nothing is being blended.
+
+use crate::editing::glyph_blend::{
+    CancelGlyphBlendEvent, CommitGlyphBlendEvent, GlyphBlend, SaveBlendAnimationEvent,
+    SetBlendAmountEvent, SetVerticalBlendAmountEvent, ToggleBlendPlaybackEvent,
+};
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::panes::pane::{
+    pane_row_node, spawn_pane, toggle_pane_visibility, PaneSlot, PANE_ROW_GAP,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Border width of the sliders
+const SLIDER_BORDER: f32 = 2.0;
+
+/// Size of the slider
+const SLIDER_WIDTH: f32 = 200.0;
+const SLIDER_HEIGHT: f32 = 16.0;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the glyph blend pane
+#[derive(Component, Default)]
+pub struct GlyphBlendPane;
+
+/// Names of the glyphs being blended
+#[derive(Component)]
+struct BlendTitle;
+
+/// Which coordinates a slider blends: all of them, or x ones when y ones
+/// are blended apart
+#[derive(Clone, Copy, PartialEq)]
+enum BlendAxis {
+    X,
+    Y,
+}
+
+/// Slider track, dragged along to set the amount
+#[derive(Component)]
+struct BlendSlider(BlendAxis);
+
+/// Part of the slider filled up to the amount
+#[derive(Component)]
+struct BlendSliderFill(BlendAxis);
+
+/// Amount as a percentage
+#[derive(Component)]
+struct BlendAmountLabel(BlendAxis);
+
+/// Row of the y slider, shown only while y is blended apart
+#[derive(Component)]
+struct VerticalBlendRow;
+
+/// What a button in the pane does
+#[derive(Component, Clone, Copy)]
+enum BlendButton {
+    Commit,
+    Cancel,
+    /// Blend y coordinates apart from x ones, or stop
+    Anisotropic,
+    /// Sweep the blend back and forth, or stop
+    Play,
+    /// Save the sweep as a GIF
+    SaveAnimation,
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct GlyphBlendPanePlugin;
+
+impl Plugin for GlyphBlendPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_glyph_blend_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_blend_buttons,
+                    drag_blend_slider,
+                    update_blend_pane,
+                    toggle_pane_visibility::<GlyphBlendPane, _>(|blend: Res<GlyphBlend>| {
+                        blend.session.is_some()
+                    }),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the glyph blend pane at its slot
+pub fn spawn_glyph_blend_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::GlyphBlend,
+        GlyphBlendPane,
+        "GlyphBlendPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        create_label_text(
+            parent,
+            &i18n::tr("glyph-blend-title"),
+            BlendTitle,
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+        for axis in [BlendAxis::X, BlendAxis::Y] {
+            let mut row = parent.spawn(Node {
+                display: match axis {
+                    BlendAxis::X => Display::Flex,
+                    BlendAxis::Y => Display::None,
+                },
+                ..pane_row_node()
+            });
+            if axis == BlendAxis::Y {
+                row.insert(VerticalBlendRow);
+            }
+            row.with_children(|row| {
+                row.spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(SLIDER_WIDTH),
+                        height: Val::Px(SLIDER_HEIGHT),
+                        border: UiRect::all(Val::Px(SLIDER_BORDER)),
+                        ..default()
+                    },
+                    BackgroundColor(theme.theme().button_regular()),
+                    BorderColor(theme.theme().button_regular_outline()),
+                    BlendSlider(axis),
+                ))
+                .with_children(|slider| {
+                    slider.spawn((
+                        Node {
+                            width: Val::Percent(50.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(theme.theme().action_color()),
+                        BlendSliderFill(axis),
+                    ));
+                });
+                create_label_text(
+                    row,
+                    "50%",
+                    BlendAmountLabel(axis),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            });
+        }
+        for buttons in [
+            &[
+                ("glyph-blend-add-glyph", BlendButton::Commit),
+                ("glyph-blend-cancel", BlendButton::Cancel),
+                ("glyph-blend-apart", BlendButton::Anisotropic),
+            ][..],
+            &[
+                ("glyph-blend-play", BlendButton::Play),
+                ("glyph-blend-save-gif", BlendButton::SaveAnimation),
+            ],
+        ] {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(PANE_ROW_GAP),
+                    ..default()
+                })
+                .with_children(|row| {
+                    for &(label, button) in buttons {
+                        create_pane_text_button(
+                            row,
+                            &i18n::tr(label),
+                            button,
+                            &asset_server,
+                            &embedded_fonts,
+                            &theme,
+                        );
+                    }
+                });
+        }
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Moves the blend by how far the cursor moves along the pressed slider
+fn drag_blend_slider(
+    slider_query: Query<(&Interaction, &ComputedNode, &BlendSlider)>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    blend: Res<GlyphBlend>,
+    mut last_cursor_x: Local<Option<f32>>,
+    mut amount_events: EventWriter<SetBlendAmountEvent>,
+    mut vertical_amount_events: EventWriter<SetVerticalBlendAmountEvent>,
+) {
+    let Some(session) = blend.session.as_ref() else {
+        *last_cursor_x = None;
+        return;
+    };
+    let cursor_x = window_query
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .map(|position| position.x);
+    let pressed = slider_query.iter().find_map(|(interaction, node, slider)| {
+        let width = node.size().x * node.inverse_scale_factor();
+        (*interaction == Interaction::Pressed).then_some((width, slider.0))
+    });
+    let (Some((width, axis)), Some(cursor_x)) = (pressed, cursor_x) else {
+        *last_cursor_x = None;
+        return;
+    };
+
+    if let Some(last_x) = last_cursor_x.replace(cursor_x) {
+        let delta = ((cursor_x - last_x) / width.max(1.0)) as f64;
+        match (axis, session.amount_y) {
+            _ if delta == 0.0 => {}
+            (BlendAxis::Y, Some(amount_y)) => {
+                vertical_amount_events.write(SetVerticalBlendAmountEvent(Some(amount_y + delta)));
+            }
+            _ => {
+                amount_events.write(SetBlendAmountEvent(session.amount + delta));
+            }
+        }
+    }
+}
+
+/// Shows the glyphs and amounts of the blend being previewed
+fn update_blend_pane(
+    blend: Res<GlyphBlend>,
+    mut title_query: Query<&mut Text, (With<BlendTitle>, Without<BlendAmountLabel>)>,
+    mut amount_query: Query<(&mut Text, &BlendAmountLabel), Without<BlendTitle>>,
+    mut fill_query: Query<(&mut Node, &BlendSliderFill), Without<VerticalBlendRow>>,
+    mut row_query: Query<&mut Node, With<VerticalBlendRow>>,
+) {
+    if !blend.is_changed() {
+        return;
+    }
+    let Some(session) = blend.session.as_ref() else {
+        return;
+    };
+    let percent = |axis: BlendAxis| {
+        let amount = match axis {
+            BlendAxis::X => session.amount,
+            BlendAxis::Y => session.amount_y.unwrap_or(session.amount),
+        };
+        (amount * 100.0) as f32
+    };
+    for mut title in title_query.iter_mut() {
+        title.0 = i18n::tr_args(
+            "glyph-blend-session",
+            &[("first", &session.first), ("second", &session.second)],
+        );
+    }
+    for (mut amount, BlendAmountLabel(axis)) in amount_query.iter_mut() {
+        let prefix = match (axis, session.amount_y) {
+            (_, None) => "",
+            (BlendAxis::X, Some(_)) => "x ",
+            (BlendAxis::Y, Some(_)) => "y ",
+        };
+        amount.0 = format!("{prefix}{:.0}%", percent(*axis));
+    }
+    for (mut fill, BlendSliderFill(axis)) in fill_query.iter_mut() {
+        fill.width = Val::Percent(percent(*axis));
+    }
+    for mut row in row_query.iter_mut() {
+        row.display = if session.amount_y.is_some() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+/// Sends the events of clicked buttons
+fn handle_blend_buttons(
+    interaction_query: Query<(&Interaction, &BlendButton), Changed<Interaction>>,
+    blend: Res<GlyphBlend>,
+    mut commit_events: EventWriter<CommitGlyphBlendEvent>,
+    mut cancel_events: EventWriter<CancelGlyphBlendEvent>,
+    mut vertical_amount_events: EventWriter<SetVerticalBlendAmountEvent>,
+    mut playback_events: EventWriter<ToggleBlendPlaybackEvent>,
+    mut save_animation_events: EventWriter<SaveBlendAnimationEvent>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            BlendButton::Commit => {
+                commit_events.write(CommitGlyphBlendEvent);
+            }
+            BlendButton::Cancel => {
+                cancel_events.write(CancelGlyphBlendEvent);
+            }
+            BlendButton::Anisotropic => {
+                let Some(session) = blend.session.as_ref() else {
+                    continue;
+                };
+                // The y slider starts where the shared one was
+                let amount_y = match session.amount_y {
+                    Some(_) => None,
+                    None => Some(session.amount),
+                };
+                vertical_amount_events.write(SetVerticalBlendAmountEvent(amount_y));
+            }
+            BlendButton::Play => {
+                playback_events.write(ToggleBlendPlaybackEvent);
+            }
+            BlendButton::SaveAnimation => {
+                save_animation_events.write(SaveBlendAnimationEvent);
+            }
+        }
+    }
+}
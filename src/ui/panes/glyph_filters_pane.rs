@@ -0,0 +1,220 @@
+//! Glyph Filters Pane Module
+//!
+//! The active glyph's filter recipe, each filter's parameter stepped or
+//! removed, and buttons to add filters, redraw from the source or bake.
+
+use crate::core::state::AppState;
+use crate::editing::glyph_filters::{GlyphFiltersEvent, GlyphFiltersPanel};
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::font_source::GlyphFilter;
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Filters added by the pane, by the catalog id of their button, with
+/// their starting parameters
+const NEW_FILTERS: [(&str, GlyphFilter); 3] = [
+    (
+        "glyph-filters-add-round",
+        GlyphFilter::RoundCorners { radius: 20.0 },
+    ),
+    (
+        "glyph-filters-add-offset",
+        GlyphFilter::Offset { distance: 10.0 },
+    ),
+    (
+        "glyph-filters-add-slant",
+        GlyphFilter::Slant { angle: 12.0 },
+    ),
+];
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the glyph filters pane
+#[derive(Component, Default)]
+pub struct GlyphFiltersPane;
+
+/// Container of the rows, rebuilt when the recipe changes
+#[derive(Component)]
+struct FilterRows;
+
+/// The glyph filters event a button sends
+#[derive(Component, Clone)]
+struct FilterButton(GlyphFiltersEvent);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct GlyphFiltersPanePlugin;
+
+impl Plugin for GlyphFiltersPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_glyph_filters_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_filter_buttons,
+                    rebuild_filter_rows,
+                    toggle_pane_visibility::<GlyphFiltersPane, _>(
+                        |panel: Res<GlyphFiltersPanel>| panel.visible,
+                    ),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the glyph filters pane at its slot
+pub fn spawn_glyph_filters_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::GlyphFilters,
+        GlyphFiltersPane,
+        "GlyphFiltersPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("glyph-filters-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(FilterRows));
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the active glyph or its recipe changes
+#[allow(clippy::too_many_arguments)]
+fn rebuild_filter_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<(String, Vec<GlyphFilter>)>>,
+    rows_query: Query<Entity, With<FilterRows>>,
+    active_sorts: Query<&Sort, With<ActiveSort>>,
+    app_state: Option<Res<AppState>>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    let recipe = app_state
+        .zip(active_sorts.single().ok())
+        .map(|(state, sort)| {
+            let filters = state.workspace.font.glyph_filters(&sort.glyph_name);
+            (sort.glyph_name.clone(), filters.to_vec())
+        });
+    if *shown == recipe {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = recipe.clone();
+
+    rebuild_rows(&mut commands, rows, |parent| {
+        let Some((glyph_name, filters)) = recipe else {
+            return;
+        };
+        create_label_text(
+            parent,
+            &glyph_name,
+            (),
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+        for (index, filter) in filters.iter().enumerate() {
+            parent.spawn(pane_row_node()).with_children(|row| {
+                create_label_text(
+                    row,
+                    &filter.label(),
+                    (),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+                let remove = i18n::tr("pane-remove");
+                let buttons = [
+                    ("-", GlyphFiltersEvent::Step(index, false)),
+                    ("+", GlyphFiltersEvent::Step(index, true)),
+                    (remove.as_str(), GlyphFiltersEvent::Remove(index)),
+                ];
+                for (label, event) in buttons {
+                    create_pane_text_button(
+                        row,
+                        label,
+                        FilterButton(event),
+                        &asset_server,
+                        &embedded_fonts,
+                        &theme,
+                    );
+                }
+            });
+        }
+        parent.spawn(pane_row_node()).with_children(|row| {
+            for (label, filter) in NEW_FILTERS {
+                let event = GlyphFiltersEvent::Add(filter);
+                create_pane_text_button(
+                    row,
+                    &i18n::tr(label),
+                    FilterButton(event),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+        });
+        if !filters.is_empty() {
+            parent.spawn(pane_row_node()).with_children(|row| {
+                let buttons = [
+                    ("glyph-filters-apply", GlyphFiltersEvent::Apply),
+                    ("glyph-filters-bake", GlyphFiltersEvent::Bake),
+                ];
+                for (label, event) in buttons {
+                    create_pane_text_button(
+                        row,
+                        &i18n::tr(label),
+                        FilterButton(event),
+                        &asset_server,
+                        &embedded_fonts,
+                        &theme,
+                    );
+                }
+            });
+        }
+    });
+}
+
+/// Sends the glyph filters events of clicked buttons
+fn handle_filter_buttons(
+    interaction_query: Query<(&Interaction, &FilterButton), Changed<Interaction>>,
+    mut filter_events: EventWriter<GlyphFiltersEvent>,
+) {
+    for (interaction, FilterButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            filter_events.write(event.clone());
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Glyph Ghosts Pane Module
+//!
+//! The glyphs ghosted behind the active sort, each in its ghost color, moved,
+//! recolored or removed. Hidden while nothing is ghosted.
+
+use crate::editing::glyph_ghosts::{GlyphGhostEvent, GlyphGhosts};
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Size of the swatch showing a ghost's color
+const SWATCH_SIZE: f32 = 12.0;
+
+/// How far an arrow button moves a ghost, in font units
+const MOVE_STEP: f32 = 10.0;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the glyph ghosts pane
+#[derive(Component, Default)]
+pub struct GlyphGhostsPane;
+
+/// Container of the rows, rebuilt when the ghosts change
+#[derive(Component)]
+struct GhostList;
+
+/// The ghost event a button sends
+#[derive(Component, Clone)]
+struct GhostButton(GlyphGhostEvent);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct GlyphGhostsPanePlugin;
+
+impl Plugin for GlyphGhostsPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_glyph_ghosts_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_ghost_buttons,
+                    rebuild_ghost_rows,
+                    toggle_pane_visibility::<GlyphGhostsPane, _>(
+                        |glyph_ghosts: Res<GlyphGhosts>| !glyph_ghosts.ghosts.is_empty(),
+                    ),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the glyph ghosts pane at its slot
+pub fn spawn_glyph_ghosts_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::GlyphGhosts,
+        GlyphGhostsPane,
+        "GlyphGhostsPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("ghosts-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(GhostList));
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows from the ghosts
+fn rebuild_ghost_rows(
+    mut commands: Commands,
+    list_query: Query<Entity, With<GhostList>>,
+    glyph_ghosts: Res<GlyphGhosts>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !glyph_ghosts.is_changed() {
+        return;
+    }
+    let Ok(list) = list_query.single() else {
+        return;
+    };
+
+    rebuild_rows(&mut commands, list, |parent| {
+        if glyph_ghosts.ghosts.is_empty() {
+            return;
+        }
+        let colors = theme.theme().glyph_ghost_colors();
+        for (index, ghost) in glyph_ghosts.ghosts.iter().enumerate() {
+            parent.spawn(pane_row_node()).with_children(|row| {
+                row.spawn((
+                    Node {
+                        width: Val::Px(SWATCH_SIZE),
+                        height: Val::Px(SWATCH_SIZE),
+                        ..default()
+                    },
+                    BackgroundColor(colors[ghost.color_index % colors.len()].with_alpha(1.0)),
+                ));
+                let label = format!(
+                    "{:<12} {:>4},{:<4}",
+                    ghost.label(),
+                    ghost.offset.x,
+                    ghost.offset.y
+                );
+                create_label_text(row, &label, (), &asset_server, &embedded_fonts, &theme);
+                let move_by =
+                    |x: f32, y: f32| GlyphGhostEvent::Move(index, Vec2::new(x, y) * MOVE_STEP);
+                let buttons = [
+                    ("←".to_string(), move_by(-1.0, 0.0)),
+                    ("→".to_string(), move_by(1.0, 0.0)),
+                    ("↑".to_string(), move_by(0.0, 1.0)),
+                    ("↓".to_string(), move_by(0.0, -1.0)),
+                    (i18n::tr("ghosts-color"), GlyphGhostEvent::NextColor(index)),
+                    (i18n::tr("ghosts-remove"), GlyphGhostEvent::Remove(index)),
+                ];
+                for (label, event) in buttons {
+                    create_pane_text_button(
+                        row,
+                        &label,
+                        GhostButton(event),
+                        &asset_server,
+                        &embedded_fonts,
+                        &theme,
+                    );
+                }
+            });
+        }
+        create_pane_text_button(
+            parent,
+            &i18n::tr("ghosts-remove-all"),
+            GhostButton(GlyphGhostEvent::Clear),
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+    });
+}
+
+/// Sends the ghost events of clicked buttons
+fn handle_ghost_buttons(
+    interaction_query: Query<(&Interaction, &GhostButton), Changed<Interaction>>,
+    mut ghost_events: EventWriter<GlyphGhostEvent>,
+) {
+    for (interaction, GhostButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            ghost_events.write(event.clone());
+        }
+    }
+}
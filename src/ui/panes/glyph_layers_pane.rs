@@ -0,0 +1,155 @@
+//! Glyph Layers Pane Module
+//!
+//! The font's layers, foreground first, with the one being edited marked;
+//! clicking a layer edits it.
+
+use crate::core::state::AppState;
+use crate::editing::glyph_layers::{GlyphLayersPanel, SwitchEditingLayerEvent};
+use crate::font_source::DEFAULT_LAYER_NAME;
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the layers pane
+#[derive(Component, Default)]
+pub struct GlyphLayersPane;
+
+/// Container of the rows, rebuilt when what they show changes
+#[derive(Component)]
+struct GlyphLayerRows;
+
+/// The layer a button switches to, `None` for the foreground
+#[derive(Component, Clone)]
+struct GlyphLayerButton(Option<String>);
+
+/// What the rows were last built from
+#[derive(Clone, PartialEq)]
+struct ShownGlyphLayers {
+    layers: Vec<String>,
+    editing: String,
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct GlyphLayersPanePlugin;
+
+impl Plugin for GlyphLayersPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_glyph_layers_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_glyph_layers_buttons,
+                    rebuild_glyph_layer_rows,
+                    toggle_pane_visibility::<GlyphLayersPane, _>(|panel: Res<GlyphLayersPanel>| {
+                        panel.visible
+                    }),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the layers pane at its slot
+pub fn spawn_glyph_layers_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::GlyphLayers,
+        GlyphLayersPane,
+        "GlyphLayersPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("glyph-layers-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(GlyphLayerRows));
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the font's layers or the layer being edited
+/// change
+#[allow(clippy::too_many_arguments)]
+fn rebuild_glyph_layer_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<ShownGlyphLayers>>,
+    rows_query: Query<Entity, With<GlyphLayerRows>>,
+    panel: Res<GlyphLayersPanel>,
+    app_state: Option<Res<AppState>>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !panel.visible {
+        return;
+    }
+    let Some(state) = app_state else {
+        return;
+    };
+    let font = &state.workspace.font;
+    let current = ShownGlyphLayers {
+        layers: font.layer_names(),
+        editing: font.editing_layer_name().to_string(),
+    };
+    if shown.as_ref() == Some(&current) {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = Some(current.clone());
+
+    let (asset_server, embedded_fonts, theme) = (&*asset_server, &*embedded_fonts, &*theme);
+    rebuild_rows(&mut commands, rows, |parent| {
+        for layer in &current.layers {
+            let mark = if *layer == current.editing { ">" } else { " " };
+            let label = format!("{mark} {layer}");
+            let target = Some(layer.clone()).filter(|layer| layer != DEFAULT_LAYER_NAME);
+            create_pane_text_button(
+                parent,
+                &label,
+                GlyphLayerButton(target),
+                asset_server,
+                embedded_fonts,
+                theme,
+            );
+        }
+    });
+}
+
+/// Switches to the clicked layer
+fn handle_glyph_layers_buttons(
+    interaction_query: Query<(&Interaction, &GlyphLayerButton), Changed<Interaction>>,
+    mut switch_events: EventWriter<SwitchEditingLayerEvent>,
+) {
+    for (interaction, GlyphLayerButton(layer)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            switch_events.write(SwitchEditingLayerEvent(layer.clone()));
+        }
+    }
+}
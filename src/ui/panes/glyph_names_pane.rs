@@ -0,0 +1,174 @@
+//! Glyph Names Pane Module
+//!
+//! Glyph name and Unicode problems in the font, with "fix" on the ones that
+//! can be fixed safely. Only the first issues are listed.
+
+use crate::data::glyph_names::GlyphNameIssue;
+use crate::editing::glyph_names::{GlyphNameEvent, GlyphNamesPaneState};
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Most issues listed at once
+const MAX_ROWS: usize = 20;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the glyph names pane
+#[derive(Component, Default)]
+pub struct GlyphNamesPane;
+
+/// Container of the rows, rebuilt when the issues change
+#[derive(Component)]
+struct GlyphNameRows;
+
+/// The fix a button sends
+#[derive(Component, Clone)]
+struct GlyphNameButton(GlyphNameEvent);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct GlyphNamesPanePlugin;
+
+impl Plugin for GlyphNamesPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_glyph_names_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_glyph_name_buttons,
+                    rebuild_glyph_name_rows,
+                    toggle_pane_visibility::<GlyphNamesPane, _>(
+                        |pane: Res<GlyphNamesPaneState>| pane.visible,
+                    ),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the glyph names pane at its slot
+pub fn spawn_glyph_names_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::GlyphNames,
+        GlyphNamesPane,
+        "GlyphNamesPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("glyph-names-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(GlyphNameRows));
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the issues change
+fn rebuild_glyph_name_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<Vec<GlyphNameIssue>>>,
+    rows_query: Query<Entity, With<GlyphNameRows>>,
+    pane: Res<GlyphNamesPaneState>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if shown.as_ref() == Some(&pane.issues) {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = Some(pane.issues.clone());
+
+    rebuild_rows(&mut commands, rows, |parent| {
+        if pane.issues.is_empty() {
+            create_label_text(
+                parent,
+                &i18n::tr("glyph-names-no-issues"),
+                (),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            return;
+        }
+        for (index, issue) in pane.issues.iter().enumerate().take(MAX_ROWS) {
+            parent.spawn(pane_row_node()).with_children(|row| {
+                let message = issue.message();
+                create_label_text(row, &message, (), &asset_server, &embedded_fonts, &theme);
+                if issue.is_fixable() {
+                    let event = GlyphNameEvent::Fix(index);
+                    create_pane_text_button(
+                        row,
+                        &i18n::tr("glyph-names-fix"),
+                        GlyphNameButton(event),
+                        &asset_server,
+                        &embedded_fonts,
+                        &theme,
+                    );
+                }
+            });
+        }
+        if pane.issues.len() > MAX_ROWS {
+            let count = (pane.issues.len() - MAX_ROWS).to_string();
+            let more = i18n::tr_args("glyph-names-more", &[("count", &count)]);
+            create_label_text(parent, &more, (), &asset_server, &embedded_fonts, &theme);
+        }
+        let fixable = pane
+            .issues
+            .iter()
+            .filter(|issue| issue.is_fixable())
+            .count();
+        if fixable > 0 {
+            create_pane_text_button(
+                parent,
+                &i18n::tr_args("glyph-names-fix-all", &[("count", &fixable.to_string())]),
+                GlyphNameButton(GlyphNameEvent::FixAll),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        }
+    });
+}
+
+/// Sends the fixes of clicked buttons
+fn handle_glyph_name_buttons(
+    interaction_query: Query<(&Interaction, &GlyphNameButton), Changed<Interaction>>,
+    mut glyph_name_events: EventWriter<GlyphNameEvent>,
+) {
+    for (interaction, GlyphNameButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            glyph_name_events.write(event.clone());
+        }
+    }
+}
@@ -5,6 +5,9 @@
 
 
 use crate::core::state::AppState;
+use crate::i18n;
+use crate::ui::localization::{LocalizedText, MirrorForRtl};
+use crate::ui::panes::pane::pane_node;
 use crate::ui::theme::*;
 use crate::ui::themes::CurrentTheme;
 use crate::utils::embedded_assets::{AssetServerFontExt, EmbeddedFonts};
@@ -105,13 +108,13 @@ fn update_glyph_pane(world: &mut World) {
 
     // Format the values for display
     let glyph_name = if metrics.glyph_name.is_empty() {
-        "None".to_string()
+        i18n::tr("glyph-pane-none")
     } else {
         metrics.glyph_name.clone()
     };
 
     let unicode = if metrics.unicode.is_empty() {
-        "None".to_string()
+        i18n::tr("glyph-pane-none")
     } else {
         metrics.unicode.to_uppercase()
     };
@@ -135,13 +138,13 @@ fn update_glyph_pane(world: &mut World) {
     };
 
     let left_group = if metrics.left_group.is_empty() {
-        "None".to_string()
+        i18n::tr("glyph-pane-none")
     } else {
         metrics.left_group.clone()
     };
 
     let right_group = if metrics.right_group.is_empty() {
-        "None".to_string()
+        i18n::tr("glyph-pane-none")
     } else {
         metrics.right_group.clone()
     };
@@ -240,10 +243,11 @@ pub fn spawn_glyph_pane(
             GlyphPane,
             "GlyphPane",
         ))
+        .insert(pane_node("GlyphPane"))
         .with_children(|parent| {
             // Glyph name row
             parent
-                .spawn((Node {
+                .spawn((MirrorForRtl, Node {
                     flex_direction: FlexDirection::Row,
                     align_items: AlignItems::Center,
                     width: Val::Auto,
@@ -259,7 +263,8 @@ pub fn spawn_glyph_pane(
                             width: Val::Auto,
                             ..default()
                         },
-                        Text::new("Glyph:"),
+                        LocalizedText("glyph-pane-glyph").text(),
+                        LocalizedText("glyph-pane-glyph"),
                         TextFont {
                             font: asset_server.load_font_with_fallback(
                                 theme.theme().mono_font_path(),
@@ -273,7 +278,7 @@ pub fn spawn_glyph_pane(
 
                     // Value
                     row.spawn((
-                        Text::new("Loading..."),
+                        Text::new(i18n::tr("pane-loading")),
                         TextFont {
                             font: asset_server.load_font_with_fallback(
                                 theme.theme().mono_font_path(),
@@ -289,7 +294,7 @@ pub fn spawn_glyph_pane(
 
             // Unicode value row
             parent
-                .spawn((Node {
+                .spawn((MirrorForRtl, Node {
                     flex_direction: FlexDirection::Row,
                     align_items: AlignItems::Center,
                     width: Val::Auto,
@@ -305,7 +310,8 @@ pub fn spawn_glyph_pane(
                             width: Val::Auto,
                             ..default()
                         },
-                        Text::new("Unicode:"),
+                        LocalizedText("glyph-pane-unicode").text(),
+                        LocalizedText("glyph-pane-unicode"),
                         TextFont {
                             font: asset_server.load_font_with_fallback(
                                 theme.theme().mono_font_path(),
@@ -319,7 +325,7 @@ pub fn spawn_glyph_pane(
 
                     // Value
                     row.spawn((
-                        Text::new("Loading..."),
+                        Text::new(i18n::tr("pane-loading")),
                         TextFont {
                             font: asset_server.load_font_with_fallback(
                                 theme.theme().mono_font_path(),
@@ -335,7 +341,7 @@ pub fn spawn_glyph_pane(
 
             // Advance width row
             parent
-                .spawn((Node {
+                .spawn((MirrorForRtl, Node {
                     flex_direction: FlexDirection::Row,
                     align_items: AlignItems::Center,
                     margin: UiRect::bottom(Val::Px(WIDGET_ROW_LEADING)),
@@ -351,7 +357,8 @@ pub fn spawn_glyph_pane(
                             width: Val::Auto,
                             ..default()
                         },
-                        Text::new("Advance:"),
+                        LocalizedText("glyph-pane-advance").text(),
+                        LocalizedText("glyph-pane-advance"),
                         TextFont {
                             font: asset_server.load_font_with_fallback(
                                 theme.theme().mono_font_path(),
@@ -365,7 +372,7 @@ pub fn spawn_glyph_pane(
 
                     // Value
                     row.spawn((
-                        Text::new("Loading..."),
+                        Text::new(i18n::tr("pane-loading")),
                         TextFont {
                             font: asset_server.load_font_with_fallback(
                                 theme.theme().mono_font_path(),
@@ -381,7 +388,7 @@ pub fn spawn_glyph_pane(
 
             // Left side bearing row
             parent
-                .spawn((Node {
+                .spawn((MirrorForRtl, Node {
                     flex_direction: FlexDirection::Row,
                     align_items: AlignItems::Center,
                     margin: UiRect::bottom(Val::Px(WIDGET_ROW_LEADING)),
@@ -397,7 +404,8 @@ pub fn spawn_glyph_pane(
                             width: Val::Auto,
                             ..default()
                         },
-                        Text::new("LSB:"),
+                        LocalizedText("glyph-pane-lsb").text(),
+                        LocalizedText("glyph-pane-lsb"),
                         TextFont {
                             font: asset_server.load_font_with_fallback(
                                 theme.theme().mono_font_path(),
@@ -411,7 +419,7 @@ pub fn spawn_glyph_pane(
 
                     // Value
                     row.spawn((
-                        Text::new("Loading..."),
+                        Text::new(i18n::tr("pane-loading")),
                         TextFont {
                             font: asset_server.load_font_with_fallback(
                                 theme.theme().mono_font_path(),
@@ -427,7 +435,7 @@ pub fn spawn_glyph_pane(
 
             // Right side bearing row
             parent
-                .spawn((Node {
+                .spawn((MirrorForRtl, Node {
                     flex_direction: FlexDirection::Row,
                     align_items: AlignItems::Center,
                     margin: UiRect::bottom(Val::Px(WIDGET_ROW_LEADING)),
@@ -443,7 +451,8 @@ pub fn spawn_glyph_pane(
                             width: Val::Auto,
                             ..default()
                         },
-                        Text::new("RSB:"),
+                        LocalizedText("glyph-pane-rsb").text(),
+                        LocalizedText("glyph-pane-rsb"),
                         TextFont {
                             font: asset_server.load_font_with_fallback(
                                 theme.theme().mono_font_path(),
@@ -457,7 +466,7 @@ pub fn spawn_glyph_pane(
 
                     // Value
                     row.spawn((
-                        Text::new("Loading..."),
+                        Text::new(i18n::tr("pane-loading")),
                         TextFont {
                             font: asset_server.load_font_with_fallback(
                                 theme.theme().mono_font_path(),
@@ -473,7 +482,7 @@ pub fn spawn_glyph_pane(
 
             // Left kerning group row
             parent
-                .spawn((Node {
+                .spawn((MirrorForRtl, Node {
                     flex_direction: FlexDirection::Row,
                     align_items: AlignItems::Center,
                     margin: UiRect::bottom(Val::Px(WIDGET_ROW_LEADING)),
@@ -489,7 +498,8 @@ pub fn spawn_glyph_pane(
                             width: Val::Auto,
                             ..default()
                         },
-                        Text::new("Left Group:"),
+                        LocalizedText("glyph-pane-left-group").text(),
+                        LocalizedText("glyph-pane-left-group"),
                         TextFont {
                             font: asset_server.load_font_with_fallback(
                                 theme.theme().mono_font_path(),
@@ -503,7 +513,7 @@ pub fn spawn_glyph_pane(
 
                     // Value
                     row.spawn((
-                        Text::new("Loading..."),
+                        Text::new(i18n::tr("pane-loading")),
                         TextFont {
                             font: asset_server.load_font_with_fallback(
                                 theme.theme().mono_font_path(),
@@ -519,7 +529,7 @@ pub fn spawn_glyph_pane(
 
             // Right kerning group row (no bottom margin on last row)
             parent
-                .spawn((Node {
+                .spawn((MirrorForRtl, Node {
                     flex_direction: FlexDirection::Row,
                     align_items: AlignItems::Center,
                     width: Val::Auto,
@@ -534,7 +544,8 @@ pub fn spawn_glyph_pane(
                             width: Val::Auto,
                             ..default()
                         },
-                        Text::new("Right Group:"),
+                        LocalizedText("glyph-pane-right-group").text(),
+                        LocalizedText("glyph-pane-right-group"),
                         TextFont {
                             font: asset_server.load_font_with_fallback(
                                 theme.theme().mono_font_path(),
@@ -548,7 +559,7 @@ pub fn spawn_glyph_pane(
 
                     // Value
                     row.spawn((
-                        Text::new("Loading..."),
+                        Text::new(i18n::tr("pane-loading")),
                         TextFont {
                             font: asset_server.load_font_with_fallback(
                                 theme.theme().mono_font_path(),
@@ -596,7 +607,7 @@ pub fn update_glyph_metrics(
                 "Glyph pane: FALLBACK BRANCH - Using AppState for glyph '{}'",
                 glyph_name
             );
-            if let Some(glyph_data) = state.workspace.font.get_glyph(&glyph_name) {
+            if let Some(glyph_data) = state.workspace.font.editing().get_glyph(&glyph_name) {
                 // Find the first Unicode codepoint for this glyph
                 if let Some(first_codepoint) = glyph_data.unicode_values.first() {
                     metrics.unicode = format!("{:04X}", *first_codepoint as u32);
@@ -0,0 +1,93 @@
+//! Handle Entry Pane Module
+//!
+//! The handle length or angle being typed, while the prompt is open.
+
+use crate::editing::handle_entry::HandleEntry;
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::create_label_text;
+use crate::ui::panes::pane::{set_pane_visibility, spawn_pane, PaneSlot};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the handle entry pane
+#[derive(Component, Default)]
+pub struct HandleEntryPane;
+
+/// The prompt with the typed value
+#[derive(Component)]
+struct HandleEntryText;
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct HandleEntryPanePlugin;
+
+impl Plugin for HandleEntryPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_handle_entry_pane)
+            .add_systems(Update, update_handle_entry_pane);
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the handle entry pane at its slot
+pub fn spawn_handle_entry_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::HandleEntry,
+        HandleEntryPane,
+        "HandleEntryPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        create_label_text(
+            parent,
+            "",
+            HandleEntryText,
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Shows the prompt while a value is typed
+fn update_handle_entry_pane(
+    entry: Res<HandleEntry>,
+    mut text_query: Query<&mut Text, With<HandleEntryText>>,
+    pane_query: Query<&mut Visibility, With<HandleEntryPane>>,
+) {
+    if !entry.is_changed() {
+        return;
+    }
+    if let Some((_, value)) = &entry.target {
+        for mut text in text_query.iter_mut() {
+            text.0 = format!(
+                "{}: {}▏ {}",
+                value.label(),
+                entry.typed,
+                i18n::tr("handle-entry-hint")
+            );
+        }
+    }
+    set_pane_visibility(pane_query, entry.target.is_some());
+}
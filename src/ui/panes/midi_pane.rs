@@ -0,0 +1,168 @@
+//! MIDI Pane Module
+//!
+//! Each parameter's bound MIDI control, with buttons to learn or clear it.
+//! Hidden while no controller is connected.
+
+use crate::core::config::BezySettings;
+use crate::i18n;
+use crate::io::midi::{MidiBindingEvent, MidiBindings, MidiDevice, MidiLearn, MidiParameter};
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the MIDI pane
+#[derive(Component, Default)]
+pub struct MidiPane;
+
+/// Name of the connected input
+#[derive(Component)]
+struct MidiTitle;
+
+/// Container of the rows, rebuilt when the bindings change
+#[derive(Component)]
+struct BindingRows;
+
+/// The binding event a button sends
+#[derive(Component, Clone, Copy)]
+struct BindingButton(MidiBindingEvent);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct MidiPanePlugin;
+
+impl Plugin for MidiPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_midi_pane).add_systems(
+            Update,
+            (
+                handle_binding_buttons,
+                rebuild_binding_rows,
+                toggle_pane_visibility::<MidiPane, _>(|device: Res<MidiDevice>| {
+                    device.port_name.is_some()
+                }),
+            ),
+        );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the MIDI pane at its slot
+pub fn spawn_midi_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::Midi,
+        MidiPane,
+        "MidiPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        create_label_text(
+            parent,
+            "MIDI",
+            MidiTitle,
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+        parent.spawn(pane_rows(BindingRows));
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the bindings or the parameter being learned change
+#[allow(clippy::too_many_arguments)]
+fn rebuild_binding_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<(MidiBindings, Option<MidiParameter>)>>,
+    rows_query: Query<Entity, With<BindingRows>>,
+    mut title_query: Query<&mut Text, With<MidiTitle>>,
+    settings: Res<BezySettings>,
+    learn: Res<MidiLearn>,
+    device: Res<MidiDevice>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if device.is_changed() {
+        let name = device
+            .port_name
+            .clone()
+            .unwrap_or_else(|| i18n::tr("midi-no-input"));
+        for mut title in title_query.iter_mut() {
+            title.0 = i18n::tr_args("midi-title", &[("device", &name)]);
+        }
+    }
+    let bindings = (settings.midi_bindings.clone(), learn.parameter);
+    if shown.as_ref() == Some(&bindings) {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = Some(bindings);
+
+    rebuild_rows(&mut commands, rows, |parent| {
+        for parameter in MidiParameter::ALL {
+            let control = if learn.parameter == Some(parameter) {
+                i18n::tr("midi-move-a-control")
+            } else {
+                settings
+                    .midi_bindings
+                    .control(parameter)
+                    .map_or("-".to_string(), |control| control.to_string())
+            };
+            parent.spawn(pane_row_node()).with_children(|row| {
+                let label = format!("{:<8}{control:<14}", parameter.label());
+                create_label_text(row, &label, (), &asset_server, &embedded_fonts, &theme);
+                let buttons = [
+                    ("midi-learn", MidiBindingEvent::Learn(parameter)),
+                    ("midi-clear", MidiBindingEvent::Clear(parameter)),
+                ];
+                for (label, event) in buttons {
+                    create_pane_text_button(
+                        row,
+                        &i18n::tr(label),
+                        BindingButton(event),
+                        &asset_server,
+                        &embedded_fonts,
+                        &theme,
+                    );
+                }
+            });
+        }
+    });
+}
+
+/// Sends the binding events of clicked buttons
+fn handle_binding_buttons(
+    interaction_query: Query<(&Interaction, &BindingButton), Changed<Interaction>>,
+    mut binding_events: EventWriter<MidiBindingEvent>,
+) {
+    for (interaction, BindingButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            binding_events.write(*event);
+        }
+    }
+}
@@ -1,6 +1,38 @@
-
+pub mod auto_kerning_pane;
+pub mod auto_spacing_pane;
+pub mod brace_layers_pane;
+pub mod build_inspector_pane;
+pub mod canvas_image_pane;
+pub mod color_layers_pane;
+pub mod compile_errors_pane;
+pub mod component_graph_pane;
+pub mod composites_pane;
 pub mod coordinate_pane;
+pub mod curve_flavor_pane;
+pub mod design_frames_pane;
+pub mod designspace_rules_pane;
+pub mod export_pane;
+pub mod features_pane;
 pub mod file_pane;
+pub mod find_replace_pane;
+pub mod glyph_autocomplete_pane;
+pub mod glyph_blend_pane;
+pub mod glyph_filters_pane;
+pub mod glyph_ghosts_pane;
+pub mod glyph_layers_pane;
+pub mod glyph_names_pane;
 pub mod glyph_pane;
+pub mod handle_entry_pane;
+pub mod midi_pane;
+pub mod os2_pane;
+pub mod pane;
+pub mod preferences_pane;
+pub mod smart_pieces_pane;
+pub mod spacing_import_pane;
+pub mod stat_pane;
+pub mod stem_report_pane;
+pub mod subset_pane;
+pub mod text_buffers_pane;
+pub mod toolbar_pane;
 
 pub use file_pane::FilePanePlugin;
@@ -0,0 +1,167 @@
+//! OS/2 Pane Module
+//!
+//! OS/2 classification values that differ from what the glyph set and
+//! metrics suggest, applied one at a time or all together.
+
+use crate::editing::os2_classification::{Os2Event, Os2PaneState};
+use crate::font_source::os2::Os2Mismatch;
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the OS/2 pane
+#[derive(Component, Default)]
+pub struct Os2Pane;
+
+/// Container of the rows, rebuilt when the mismatches change
+#[derive(Component)]
+struct Os2Rows;
+
+/// The suggestions a button applies
+#[derive(Component, Clone)]
+struct Os2Button(Os2Event);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct Os2PanePlugin;
+
+impl Plugin for Os2PanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_os2_pane).add_systems(
+            Update,
+            (
+                handle_os2_buttons,
+                rebuild_os2_rows,
+                toggle_pane_visibility::<Os2Pane, _>(|pane: Res<Os2PaneState>| pane.visible),
+            ),
+        );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the OS/2 pane at its slot
+pub fn spawn_os2_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::Os2,
+        Os2Pane,
+        "Os2Pane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("os2-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(Os2Rows));
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the mismatches change
+fn rebuild_os2_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<(bool, Vec<Os2Mismatch>)>>,
+    rows_query: Query<Entity, With<Os2Rows>>,
+    pane: Res<Os2PaneState>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    let current = (pane.suggestion.is_some(), pane.mismatches.clone());
+    if shown.as_ref() == Some(&current) {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = Some(current);
+
+    rebuild_rows(&mut commands, rows, |parent| {
+        if pane.suggestion.is_none() {
+            create_label_text(
+                parent,
+                &i18n::tr("pane-no-font"),
+                (),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            return;
+        }
+        if pane.mismatches.is_empty() {
+            create_label_text(
+                parent,
+                &i18n::tr("os2-matches"),
+                (),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            return;
+        }
+        for mismatch in &pane.mismatches {
+            parent.spawn(pane_row_node()).with_children(|row| {
+                let label = format!(
+                    "{}: {} -> {}",
+                    mismatch.field.label(),
+                    mismatch.current,
+                    mismatch.suggested
+                );
+                create_label_text(row, &label, (), &asset_server, &embedded_fonts, &theme);
+                let event = Os2Event::Apply(mismatch.field);
+                create_pane_text_button(
+                    row,
+                    &i18n::tr("os2-apply"),
+                    Os2Button(event),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            });
+        }
+        create_pane_text_button(
+            parent,
+            &i18n::tr("os2-apply-all"),
+            Os2Button(Os2Event::ApplyAll),
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+    });
+}
+
+/// Sends the applies of clicked buttons
+fn handle_os2_buttons(
+    interaction_query: Query<(&Interaction, &Os2Button), Changed<Interaction>>,
+    mut os2_events: EventWriter<Os2Event>,
+) {
+    for (interaction, Os2Button(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            os2_events.write(event.clone());
+        }
+    }
+}
@@ -0,0 +1,415 @@
+//! Floating Pane Module
+//!
+//! The parts every floating pane is built from. A pane is a widget hidden
+//! until its toggle is on, holding a title and a column of rows rebuilt
+//! when what they show changes. Each pane has its own `PaneSlot`, cells of a
+//! grid over the window that no other pane uses, so panes shown together
+//! never cover each other.
+//!
+//! Panes spawn with `spawn_pane`, fill their rows container from
+//! `pane_rows` with `rebuild_rows`, lay out each row with `pane_row_node`,
+//! show and hide with `toggle_pane_visibility`, and make their buttons with
+//! `create_pane_text_button`.
+
+use crate::ui::localization::MirrorForRtl;
+use crate::ui::theme::*;
+use crate::ui::themes::CurrentTheme;
+use accesskit::{Node as AccessKitNode, Role};
+use bevy::a11y::AccessibilityNode;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Gap between the rows of a pane and between the buttons of a row
+pub const PANE_ROW_GAP: f32 = 4.0;
+
+// ============================================================================
+// POSITIONS
+// ============================================================================
+
+/// Columns of the grid panes are placed on, each this share of the window
+/// width
+const GRID_COLUMNS: u8 = 8;
+
+/// Rows of the grid, between the toolbars at the top and the glyph and
+/// coordinate panes at the bottom
+const GRID_ROWS: u8 = 7;
+
+/// Top and bottom of the grid, in percent of the window height
+const GRID_TOP: f32 = 8.0;
+const GRID_BOTTOM: f32 = 92.0;
+
+/// Space left between neighboring panes, in percent of the window
+const CELL_GAP: f32 = 0.5;
+
+/// The cells of the grid a pane may fill: its first column and row and how
+/// many of each it spans
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PaneCells {
+    column: u8,
+    row: u8,
+    columns: u8,
+    rows: u8,
+}
+
+const fn cells(column: u8, row: u8, columns: u8, rows: u8) -> PaneCells {
+    PaneCells {
+        column,
+        row,
+        columns,
+        rows,
+    }
+}
+
+/// A pane's rectangle, in percent of the window
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PaneRect {
+    left: f32,
+    top: f32,
+    width: f32,
+    height: f32,
+}
+
+impl PaneRect {
+    fn intersects(&self, other: &PaneRect) -> bool {
+        self.left < other.left + other.width
+            && other.left < self.left + self.width
+            && self.top < other.top + other.height
+            && other.top < self.top + self.height
+    }
+}
+
+/// The floating panes, each with its own place in the window
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PaneSlot {
+    AutoKerning,
+    AutoSpacing,
+    BraceLayers,
+    BuildInspector,
+    CanvasImage,
+    ColorLayers,
+    CompileErrors,
+    ComponentGraph,
+    Composites,
+    CurveFlavor,
+    DesignFrames,
+    DesignspaceRules,
+    Export,
+    Features,
+    FindReplace,
+    GlyphBlend,
+    GlyphFilters,
+    GlyphGhosts,
+    GlyphLayers,
+    GlyphNames,
+    HandleEntry,
+    Midi,
+    Os2,
+    Preferences,
+    SmartPieces,
+    SpacingImport,
+    Stat,
+    StemReport,
+    Subset,
+    TextBuffers,
+    Toolbar,
+}
+
+impl PaneSlot {
+    /// The cells of the grid the pane fills. No two panes share a cell, so
+    /// panes shown together never cover each other
+    fn cells(self) -> PaneCells {
+        match self {
+            // Assistants on the left edge, two columns wide
+            Self::AutoSpacing => cells(0, 0, 2, 1),
+            Self::DesignspaceRules => cells(0, 1, 2, 1),
+            Self::Subset => cells(0, 2, 2, 1),
+            Self::AutoKerning => cells(0, 3, 2, 1),
+            Self::GlyphBlend => cells(0, 4, 2, 1),
+            Self::DesignFrames => cells(0, 5, 2, 1),
+            // Dialogs toward the middle
+            Self::Toolbar => cells(2, 0, 3, 1),
+            Self::Preferences => cells(2, 1, 3, 2),
+            Self::Export => cells(2, 3, 3, 2),
+            Self::BuildInspector => cells(2, 5, 2, 2),
+            Self::CompileErrors => cells(4, 5, 1, 1),
+            // Inspectors on the right edge, in columns of one cell each
+            Self::Features => cells(7, 0, 1, 1),
+            Self::Composites => cells(7, 1, 1, 1),
+            Self::FindReplace => cells(7, 2, 1, 1),
+            Self::GlyphLayers => cells(7, 3, 1, 1),
+            Self::Stat => cells(7, 4, 1, 1),
+            Self::TextBuffers => cells(7, 5, 1, 1),
+            Self::ComponentGraph => cells(6, 0, 1, 1),
+            Self::ColorLayers => cells(6, 1, 1, 1),
+            Self::GlyphNames => cells(6, 2, 1, 1),
+            Self::BraceLayers => cells(6, 3, 1, 1),
+            Self::Os2 => cells(6, 4, 1, 1),
+            Self::GlyphGhosts => cells(6, 5, 1, 1),
+            Self::HandleEntry => cells(5, 0, 1, 1),
+            Self::SmartPieces => cells(5, 1, 1, 1),
+            Self::SpacingImport => cells(5, 2, 1, 1),
+            Self::GlyphFilters => cells(5, 3, 1, 1),
+            Self::CanvasImage => cells(5, 4, 1, 1),
+            Self::StemReport => cells(5, 5, 1, 1),
+            Self::CurveFlavor => cells(5, 6, 1, 1),
+            Self::Midi => cells(4, 6, 1, 1),
+        }
+    }
+
+    /// The rectangle the pane may fill, its cells less the gap around them
+    fn rect(self) -> PaneRect {
+        let cells = self.cells();
+        let column_width = 100.0 / GRID_COLUMNS as f32;
+        let row_height = (GRID_BOTTOM - GRID_TOP) / GRID_ROWS as f32;
+        PaneRect {
+            left: cells.column as f32 * column_width + CELL_GAP / 2.0,
+            top: GRID_TOP + cells.row as f32 * row_height + CELL_GAP / 2.0,
+            width: cells.columns as f32 * column_width - CELL_GAP,
+            height: cells.rows as f32 * row_height - CELL_GAP,
+        }
+    }
+
+    /// Where the pane sits in the window. Panes in the right half hang from
+    /// the right edge of their cells so they line up with the window edge
+    pub fn position(self) -> UiRect {
+        let rect = self.rect();
+        let (left, right) = if self.cells().column >= GRID_COLUMNS / 2 {
+            (Val::Auto, Val::Percent(100.0 - rect.left - rect.width))
+        } else {
+            (Val::Percent(rect.left), Val::Auto)
+        };
+        UiRect {
+            left,
+            right,
+            top: Val::Percent(rect.top),
+            bottom: Val::Auto,
+        }
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns a pane hidden at its slot, named for screen readers; its title
+/// and rows are added as its children
+pub fn spawn_pane<'a, T: Component + Default>(
+    commands: &'a mut Commands,
+    slot: PaneSlot,
+    marker: T,
+    name: &str,
+    asset_server: &Res<AssetServer>,
+    theme: &Res<CurrentTheme>,
+) -> EntityCommands<'a> {
+    let mut pane = commands.spawn(create_widget_style(
+        asset_server,
+        theme,
+        PositionType::Absolute,
+        slot.position(),
+        marker,
+        name,
+    ));
+    // Keep the pane within its rectangle, clipping rows that don't fit
+    let rect = slot.rect();
+    pane.entry::<Node>().and_modify(move |mut node| {
+        node.max_width = Val::Percent(rect.width);
+        node.max_height = Val::Percent(rect.height);
+        node.overflow = Overflow::clip();
+    });
+    pane.insert((Visibility::Hidden, pane_node(name)));
+    pane
+}
+
+/// An AccessKit node for a pane, labeled with the words of its `Name`
+/// ("ExportPane" reads as "Export Pane")
+pub fn pane_node(name: &str) -> AccessibilityNode {
+    let mut node = AccessKitNode::new(Role::Pane);
+    node.set_label(spaced_words(name));
+    AccessibilityNode::from(node)
+}
+
+/// "AutoSpacingPane" as "Auto Spacing Pane"
+fn spaced_words(name: &str) -> String {
+    let mut words = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if i > 0 && c.is_uppercase() {
+            words.push(' ');
+        }
+        words.push(c);
+    }
+    words
+}
+
+/// The container of a pane's rows, marked so `rebuild_rows` can find it
+pub fn pane_rows<R: Component>(marker: R) -> impl Bundle {
+    (
+        Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(PANE_ROW_GAP),
+            ..default()
+        },
+        marker,
+    )
+}
+
+/// A row of a pane: its labels and buttons side by side
+pub fn pane_row_node() -> Node {
+    Node {
+        flex_direction: FlexDirection::Row,
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(PANE_ROW_GAP),
+        ..default()
+    }
+}
+
+/// A row of a pane that lays its items out right to left in right-to-left
+/// languages
+pub fn pane_mirrored_row() -> impl Bundle {
+    (pane_row_node(), MirrorForRtl)
+}
+
+/// A row of a pane that wraps onto more lines when it doesn't fit
+pub fn pane_wrapping_row_node() -> Node {
+    Node {
+        flex_wrap: FlexWrap::Wrap,
+        row_gap: Val::Px(PANE_ROW_GAP),
+        ..pane_row_node()
+    }
+}
+
+// ============================================================================
+// SYSTEM HELPERS
+// ============================================================================
+
+/// Replaces the rows in a pane's rows container with the ones `build`
+/// spawns
+pub fn rebuild_rows(
+    commands: &mut Commands,
+    rows: Entity,
+    build: impl FnOnce(&mut ChildSpawnerCommands),
+) {
+    commands.entity(rows).despawn_related::<Children>();
+    commands.entity(rows).with_children(build);
+}
+
+/// A system showing the panes marked `P` while `shown` returns true and
+/// hiding them otherwise, such as
+/// `toggle_pane_visibility::<StatPane, _>(|pane: Res<StatPaneState>| pane.visible)`
+pub fn toggle_pane_visibility<P: Component, M>(
+    shown: impl IntoSystem<(), bool, M>,
+) -> impl System<In = (), Out = ()> {
+    IntoSystem::into_system(shown.pipe(show_panes_while::<P>))
+}
+
+fn show_panes_while<P: Component>(
+    In(shown): In<bool>,
+    pane_query: Query<&mut Visibility, With<P>>,
+) {
+    set_pane_visibility(pane_query, shown);
+}
+
+/// Shows the panes of the query while `shown`, hides them otherwise
+pub fn set_pane_visibility<T: Component>(
+    mut pane_query: Query<&mut Visibility, With<T>>,
+    shown: bool,
+) {
+    let target = if shown {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    for mut visibility in pane_query.iter_mut() {
+        visibility.set_if_neq(target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spaced_words() {
+        assert_eq!(spaced_words("AutoSpacingPane"), "Auto Spacing Pane");
+        assert_eq!(spaced_words("Glyph"), "Glyph");
+    }
+
+    #[test]
+    fn test_panes_have_their_own_places() {
+        use PaneSlot::*;
+        let slots = [
+            AutoKerning,
+            AutoSpacing,
+            BraceLayers,
+            BuildInspector,
+            CanvasImage,
+            ColorLayers,
+            CompileErrors,
+            ComponentGraph,
+            Composites,
+            CurveFlavor,
+            DesignFrames,
+            DesignspaceRules,
+            Export,
+            Features,
+            FindReplace,
+            GlyphBlend,
+            GlyphFilters,
+            GlyphGhosts,
+            GlyphLayers,
+            GlyphNames,
+            HandleEntry,
+            Midi,
+            Os2,
+            Preferences,
+            SmartPieces,
+            SpacingImport,
+            Stat,
+            StemReport,
+            Subset,
+            TextBuffers,
+            Toolbar,
+        ];
+        for (i, slot) in slots.iter().enumerate() {
+            for other in &slots[i + 1..] {
+                assert!(
+                    !slot.rect().intersects(&other.rect()),
+                    "{slot:?} and {other:?} overlap"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_panes_stay_on_the_grid() {
+        use PaneSlot::*;
+        for slot in [AutoSpacing, Toolbar, BuildInspector, Features, Midi] {
+            let cells = slot.cells();
+            assert!(cells.column + cells.columns <= GRID_COLUMNS, "{slot:?}");
+            assert!(cells.row + cells.rows <= GRID_ROWS, "{slot:?}");
+            let rect = slot.rect();
+            assert!(rect.left >= 0.0 && rect.left + rect.width <= 100.0);
+            assert!(rect.top >= GRID_TOP && rect.top + rect.height <= GRID_BOTTOM);
+        }
+    }
+
+    #[test]
+    fn test_rects_that_touch_do_not_intersect() {
+        let a = PaneRect {
+            left: 0.0,
+            top: 0.0,
+            width: 10.0,
+            height: 10.0,
+        };
+        let b = PaneRect { left: 10.0, ..a };
+        let c = PaneRect {
+            left: 5.0,
+            top: 5.0,
+            ..a
+        };
+        assert!(!a.intersects(&b));
+        assert!(a.intersects(&c));
+    }
+}
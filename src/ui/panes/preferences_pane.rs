@@ -0,0 +1,290 @@
+//! Preferences Pane Module
+//!
+//! The preferences dialog (Cmd/Ctrl+,): every preference from
+//! `core::config::preferences` with buttons stepping it. Click a tool's
+//! shortcut and press a letter to change it. Changes apply at once and are
+//! saved to the user config file.
+
+use crate::core::config::preferences::set_tool_shortcut;
+use crate::core::config::{BezySettings, Preference};
+use crate::core::state::AppState;
+use crate::i18n;
+use crate::io::shortcuts::Shortcut;
+use crate::ui::accessibility::dialog_node;
+use crate::ui::edit_mode_toolbar::keyboard_shortcuts::char_to_keycode;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_mirrored_row, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::panes::toolbar_pane::ToolbarDialogState;
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Width the preference names are padded to, in characters
+const LABEL_WIDTH: usize = 16;
+
+// ============================================================================
+// RESOURCES & COMPONENTS
+// ============================================================================
+
+/// Whether the dialog is open, and the tool waiting for a new shortcut
+#[derive(Resource, Default)]
+pub struct PreferencesDialogState {
+    pub open: bool,
+    /// Tool id whose shortcut is set by the next letter pressed
+    pub capturing: Option<&'static str>,
+    /// Why the last shortcut was refused
+    message: Option<String>,
+}
+
+/// Component marker for the preferences pane
+#[derive(Component, Default)]
+pub struct PreferencesPane;
+
+/// Container of the dialog's rows, rebuilt when a preference changes
+#[derive(Component)]
+struct PreferencesDialogContent;
+
+/// What a button in the dialog does
+#[derive(Component, Clone)]
+enum PreferencesButton {
+    Back(Preference),
+    Forward(Preference),
+    Capture(&'static str),
+    ResetShortcuts,
+    CustomizeToolbar,
+    Close,
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct PreferencesPanePlugin;
+
+impl Plugin for PreferencesPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PreferencesDialogState>()
+            .add_systems(Startup, spawn_preferences_pane)
+            .add_systems(
+                Update,
+                (
+                    toggle_preferences_dialog,
+                    handle_preferences_buttons,
+                    capture_tool_shortcut,
+                    rebuild_preferences_dialog,
+                    toggle_pane_visibility::<PreferencesPane, _>(
+                        |dialog: Res<PreferencesDialogState>| dialog.open,
+                    ),
+                )
+                    .chain(),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the preferences pane, hidden until the dialog is opened
+pub fn spawn_preferences_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::Preferences,
+        PreferencesPane,
+        "PreferencesPane",
+        &asset_server,
+        &theme,
+    )
+    .insert(dialog_node(&i18n::tr("preferences-title")))
+    .with_children(|parent| {
+        let title = LocalizedText("preferences-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(PreferencesDialogContent));
+    });
+}
+
+/// A row naming a preference and its value, with buttons to change it
+fn spawn_preference_row(
+    parent: &mut ChildSpawnerCommands,
+    preference: Preference,
+    settings: &BezySettings,
+    capturing: Option<&str>,
+    asset_server: &AssetServer,
+    embedded_fonts: &EmbeddedFonts,
+    theme: &CurrentTheme,
+) {
+    parent.spawn(pane_mirrored_row()).with_children(|row| {
+        let label = format!("{:<LABEL_WIDTH$}", preference.label());
+        create_label_text(row, &label, (), asset_server, embedded_fonts, theme);
+        let value = preference.value(settings);
+        if let Preference::ToolShortcut(tool_id) = preference {
+            let label = if capturing == Some(tool_id) {
+                i18n::tr("pref-press-a-letter")
+            } else {
+                value
+            };
+            let button = PreferencesButton::Capture(tool_id);
+            create_pane_text_button(row, &label, button, asset_server, embedded_fonts, theme);
+            return;
+        }
+        let back = PreferencesButton::Back(preference.clone());
+        create_pane_text_button(row, "‹", back, asset_server, embedded_fonts, theme);
+        create_label_text(row, &value, (), asset_server, embedded_fonts, theme);
+        let forward = PreferencesButton::Forward(preference);
+        create_pane_text_button(row, "›", forward, asset_server, embedded_fonts, theme);
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Opens and closes the dialog on Cmd/Ctrl+,
+fn toggle_preferences_dialog(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut dialog: ResMut<PreferencesDialogState>,
+) {
+    if Shortcut::Preferences.just_pressed(&keyboard) {
+        dialog.open = !dialog.open;
+        dialog.capturing = None;
+        dialog.message = None;
+    }
+}
+
+/// Steps preferences, starts waiting for shortcuts and closes the dialog
+fn handle_preferences_buttons(
+    interaction_query: Query<(&Interaction, &PreferencesButton), Changed<Interaction>>,
+    mut dialog: ResMut<PreferencesDialogState>,
+    mut toolbar_dialog: ResMut<ToolbarDialogState>,
+    mut settings: ResMut<BezySettings>,
+    app_state: Option<Res<AppState>>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        dialog.message = None;
+        match button {
+            PreferencesButton::Back(preference) | PreferencesButton::Forward(preference) => {
+                let forward = matches!(button, PreferencesButton::Forward(_));
+                let profiles = app_state
+                    .as_deref()
+                    .map(|state| state.workspace.font.export_profiles.names())
+                    .unwrap_or_default();
+                preference.step(&mut settings, forward, &profiles);
+            }
+            PreferencesButton::Capture(tool_id) => {
+                dialog.capturing = match dialog.capturing {
+                    Some(capturing) if capturing == *tool_id => None,
+                    _ => Some(tool_id),
+                };
+            }
+            PreferencesButton::ResetShortcuts => settings.tool_shortcuts.clear(),
+            PreferencesButton::CustomizeToolbar => toolbar_dialog.open = true,
+            PreferencesButton::Close => {
+                dialog.open = false;
+                dialog.capturing = None;
+            }
+        }
+    }
+}
+
+/// Sets the shortcut of the tool waiting for one to the letter pressed
+fn capture_tool_shortcut(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut dialog: ResMut<PreferencesDialogState>,
+    mut settings: ResMut<BezySettings>,
+) {
+    let Some(tool_id) = dialog.capturing else {
+        return;
+    };
+    let Some(key) = ('a'..='z')
+        .find(|c| char_to_keycode(*c).is_some_and(|keycode| keyboard.just_pressed(keycode)))
+    else {
+        return;
+    };
+    dialog.capturing = None;
+    if let Err(message) = set_tool_shortcut(settings.bypass_change_detection(), tool_id, key) {
+        warn!("{}", message);
+        dialog.message = Some(message);
+        return;
+    }
+    settings.set_changed();
+}
+
+/// Rebuilds the dialog when it opens or a preference changes
+fn rebuild_preferences_dialog(
+    mut commands: Commands,
+    content_query: Query<Entity, With<PreferencesDialogContent>>,
+    dialog: Res<PreferencesDialogState>,
+    settings: Res<BezySettings>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !dialog.is_changed() && !settings.is_changed() {
+        return;
+    }
+    let Ok(content) = content_query.single() else {
+        return;
+    };
+
+    rebuild_rows(&mut commands, content, |parent| {
+        if !dialog.open {
+            return;
+        }
+        for preference in Preference::all() {
+            spawn_preference_row(
+                parent,
+                preference,
+                &settings,
+                dialog.capturing,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        }
+        if let Some(message) = &dialog.message {
+            create_label_text(parent, message, (), &asset_server, &embedded_fonts, &theme);
+        }
+        parent.spawn(pane_mirrored_row()).with_children(|row| {
+            create_pane_text_button(
+                row,
+                &i18n::tr("pref-reset-shortcuts"),
+                PreferencesButton::ResetShortcuts,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            create_pane_text_button(
+                row,
+                &i18n::tr("pref-customize-toolbar"),
+                PreferencesButton::CustomizeToolbar,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            create_pane_text_button(
+                row,
+                &i18n::tr("button-close"),
+                PreferencesButton::Close,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        });
+    });
+}
@@ -0,0 +1,389 @@
+//! Smart Pieces Pane Module
+//!
+//! Defines pieces from the selected contours, sets how the next stamp is
+//! transformed, and stamps or unlinks the font's pieces.
+
+use crate::core::state::AppState;
+use crate::editing::smart_pieces::{
+    DetachSmartPiecesEvent, SmartPiecesPanel, StampSmartPieceEvent,
+};
+use crate::editing::sort::{ActiveSort, Sort};
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Change of a stamp's scale per click
+const SCALE_STEP: f32 = 0.1;
+
+/// Change of a stamp's offset per click, in font units
+const OFFSET_STEP: f32 = 10.0;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the smart pieces pane
+#[derive(Component, Default)]
+pub struct SmartPiecesPane;
+
+/// Container of the rows, rebuilt when what they show changes
+#[derive(Component)]
+struct SmartPieceRows;
+
+/// What a button does
+#[derive(Debug, Clone, PartialEq)]
+enum SmartPiecesAction {
+    /// Start typing the name of a piece made from the selected contours
+    Define,
+    /// Step the horizontal (`false`) or vertical scale up or down
+    StepScale(bool, bool),
+    /// Flip horizontally (`false`) or vertically
+    Flip(bool),
+    /// Step the horizontal (`false`) or vertical offset up or down
+    StepOffset(bool, bool),
+    Stamp(String),
+    Detach,
+}
+
+/// The action a button takes
+#[derive(Component, Clone)]
+struct SmartPiecesButton(SmartPiecesAction);
+
+/// What the rows were last built from
+#[derive(Clone, PartialEq)]
+struct ShownSmartPieces {
+    /// The active glyph and how many pieces are stamped into it
+    glyph: Option<(String, usize)>,
+    pieces: Vec<String>,
+    panel: SmartPiecesPanel,
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct SmartPiecesPanePlugin;
+
+impl Plugin for SmartPiecesPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_smart_pieces_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_smart_pieces_buttons,
+                    rebuild_smart_piece_rows,
+                    toggle_pane_visibility::<SmartPiecesPane, _>(|panel: Res<SmartPiecesPanel>| {
+                        panel.visible
+                    }),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the smart pieces pane at its slot
+pub fn spawn_smart_pieces_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::SmartPieces,
+        SmartPiecesPane,
+        "SmartPiecesPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("smart-pieces-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(SmartPieceRows));
+    });
+}
+
+/// A row with a label and a button to step its value down and up
+fn spawn_stepper(
+    parent: &mut ChildSpawnerCommands,
+    label: &str,
+    actions: [SmartPiecesAction; 2],
+    asset_server: &AssetServer,
+    embedded_fonts: &EmbeddedFonts,
+    theme: &CurrentTheme,
+) {
+    parent.spawn(pane_row_node()).with_children(|row| {
+        create_label_text(row, label, (), asset_server, embedded_fonts, theme);
+        for (label, action) in ["-", "+"].into_iter().zip(actions) {
+            create_pane_text_button(
+                row,
+                label,
+                SmartPiecesButton(action),
+                asset_server,
+                embedded_fonts,
+                theme,
+            );
+        }
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the pieces, the active glyph or the stamp
+/// settings change
+#[allow(clippy::too_many_arguments)]
+fn rebuild_smart_piece_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<ShownSmartPieces>>,
+    rows_query: Query<Entity, With<SmartPieceRows>>,
+    panel: Res<SmartPiecesPanel>,
+    active_sorts: Query<&Sort, With<ActiveSort>>,
+    app_state: Option<Res<AppState>>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !panel.visible {
+        return;
+    }
+    let Some(state) = app_state else {
+        return;
+    };
+    let font = &state.workspace.font;
+    let current = ShownSmartPieces {
+        glyph: active_sorts.single().ok().map(|sort| {
+            let stamped = font
+                .smart_pieces
+                .instances
+                .get(&sort.glyph_name)
+                .map_or(0, Vec::len);
+            (sort.glyph_name.clone(), stamped)
+        }),
+        pieces: font.smart_piece_names(),
+        panel: panel.clone(),
+    };
+    if shown.as_ref() == Some(&current) {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = Some(current.clone());
+
+    let (asset_server, embedded_fonts, theme) = (&*asset_server, &*embedded_fonts, &*theme);
+    rebuild_rows(&mut commands, rows, |parent| {
+        let Some((glyph_name, stamped)) = &current.glyph else {
+            create_label_text(
+                parent,
+                &i18n::tr("pane-no-active-glyph"),
+                (),
+                asset_server,
+                embedded_fonts,
+                theme,
+            );
+            return;
+        };
+        let label = i18n::tr_args(
+            "smart-pieces-stamped",
+            &[("glyph", glyph_name), ("count", &stamped.to_string())],
+        );
+        create_label_text(parent, &label, (), asset_server, embedded_fonts, theme);
+
+        match &current.panel.naming {
+            Some(typed) => {
+                let label = i18n::tr_args("smart-pieces-new-piece", &[("name", typed)]);
+                create_label_text(parent, &label, (), asset_server, embedded_fonts, theme);
+                create_label_text(
+                    parent,
+                    &i18n::tr("smart-pieces-naming-hint"),
+                    (),
+                    asset_server,
+                    embedded_fonts,
+                    theme,
+                );
+            }
+            None => {
+                create_pane_text_button(
+                    parent,
+                    &i18n::tr("smart-pieces-define"),
+                    SmartPiecesButton(SmartPiecesAction::Define),
+                    asset_server,
+                    embedded_fonts,
+                    theme,
+                );
+            }
+        }
+
+        let settings = &current.panel;
+        let steppers = [
+            (
+                "smart-pieces-scale-x",
+                format!("{:.2}", settings.scale.x),
+                false,
+                true,
+            ),
+            (
+                "smart-pieces-scale-y",
+                format!("{:.2}", settings.scale.y),
+                true,
+                true,
+            ),
+            (
+                "smart-pieces-offset-x",
+                settings.offset.x.to_string(),
+                false,
+                false,
+            ),
+            (
+                "smart-pieces-offset-y",
+                settings.offset.y.to_string(),
+                true,
+                false,
+            ),
+        ];
+        for (id, value, vertical, scale) in steppers {
+            let label = i18n::tr_args(id, &[("value", &value)]);
+            let actions = if scale {
+                [
+                    SmartPiecesAction::StepScale(vertical, false),
+                    SmartPiecesAction::StepScale(vertical, true),
+                ]
+            } else {
+                [
+                    SmartPiecesAction::StepOffset(vertical, false),
+                    SmartPiecesAction::StepOffset(vertical, true),
+                ]
+            };
+            spawn_stepper(parent, &label, actions, asset_server, embedded_fonts, theme);
+        }
+        parent.spawn(pane_row_node()).with_children(|row| {
+            let on_off = |flipped: bool| i18n::tr(if flipped { "pref-on" } else { "pref-off" });
+            let flips = [
+                (
+                    i18n::tr_args(
+                        "smart-pieces-flip-x",
+                        &[("state", &on_off(settings.flip_x))],
+                    ),
+                    false,
+                ),
+                (
+                    i18n::tr_args(
+                        "smart-pieces-flip-y",
+                        &[("state", &on_off(settings.flip_y))],
+                    ),
+                    true,
+                ),
+            ];
+            for (label, vertical) in flips {
+                let action = SmartPiecesAction::Flip(vertical);
+                create_pane_text_button(
+                    row,
+                    &label,
+                    SmartPiecesButton(action),
+                    asset_server,
+                    embedded_fonts,
+                    theme,
+                );
+            }
+        });
+
+        if current.pieces.is_empty() {
+            create_label_text(
+                parent,
+                &i18n::tr("smart-pieces-none"),
+                (),
+                asset_server,
+                embedded_fonts,
+                theme,
+            );
+        }
+        for piece in &current.pieces {
+            parent.spawn(pane_row_node()).with_children(|row| {
+                create_label_text(row, piece, (), asset_server, embedded_fonts, theme);
+                let action = SmartPiecesAction::Stamp(piece.clone());
+                create_pane_text_button(
+                    row,
+                    &i18n::tr("smart-pieces-stamp"),
+                    SmartPiecesButton(action),
+                    asset_server,
+                    embedded_fonts,
+                    theme,
+                );
+            });
+        }
+        if *stamped > 0 {
+            create_pane_text_button(
+                parent,
+                &i18n::tr("smart-pieces-detach"),
+                SmartPiecesButton(SmartPiecesAction::Detach),
+                asset_server,
+                embedded_fonts,
+                theme,
+            );
+        }
+    });
+}
+
+/// Applies clicked buttons to the stamp settings or sends piece events
+fn handle_smart_pieces_buttons(
+    interaction_query: Query<(&Interaction, &SmartPiecesButton), Changed<Interaction>>,
+    mut panel: ResMut<SmartPiecesPanel>,
+    mut stamp_events: EventWriter<StampSmartPieceEvent>,
+    mut detach_events: EventWriter<DetachSmartPiecesEvent>,
+) {
+    for (interaction, SmartPiecesButton(action)) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            SmartPiecesAction::Define => panel.naming = Some(String::new()),
+            SmartPiecesAction::StepScale(vertical, up) => {
+                let step = if *up { SCALE_STEP } else { -SCALE_STEP };
+                let scale = if *vertical {
+                    &mut panel.scale.y
+                } else {
+                    &mut panel.scale.x
+                };
+                // A zero scale would flatten the piece; flips reverse it
+                *scale = ((*scale + step) * 100.0).round().max(SCALE_STEP * 100.0) / 100.0;
+            }
+            SmartPiecesAction::Flip(vertical) => {
+                if *vertical {
+                    panel.flip_y = !panel.flip_y;
+                } else {
+                    panel.flip_x = !panel.flip_x;
+                }
+            }
+            SmartPiecesAction::StepOffset(vertical, up) => {
+                let step = if *up { OFFSET_STEP } else { -OFFSET_STEP };
+                if *vertical {
+                    panel.offset.y += step;
+                } else {
+                    panel.offset.x += step;
+                }
+            }
+            SmartPiecesAction::Stamp(piece) => {
+                stamp_events.write(panel.stamp(piece));
+            }
+            SmartPiecesAction::Detach => {
+                detach_events.write(DetachSmartPiecesEvent);
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Spacing Import Pane Module
+//!
+//! Asks how to merge a dropped metrics or kerning file: replace, merge, skip
+//! existing values or cancel.
+
+use crate::core::state::AppState;
+use crate::editing::spacing_import::{SpacingImport, SpacingImportEvent};
+use crate::font_source::spacing_import::MergeMode;
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the spacing import pane
+#[derive(Component, Default)]
+pub struct SpacingImportPane;
+
+/// Container of the rows, rebuilt for each import
+#[derive(Component)]
+struct ImportRows;
+
+/// The spacing import event a button sends
+#[derive(Component, Clone)]
+struct ImportButton(SpacingImportEvent);
+
+/// What the rows were last built from
+#[derive(Clone, PartialEq)]
+struct ShownImport {
+    file_name: String,
+    advances: usize,
+    pairs: usize,
+    existing_pairs: usize,
+    groups: usize,
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct SpacingImportPanePlugin;
+
+impl Plugin for SpacingImportPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_spacing_import_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_import_buttons,
+                    rebuild_import_rows,
+                    toggle_pane_visibility::<SpacingImportPane, _>(|import: Res<SpacingImport>| {
+                        import.pending.is_some()
+                    }),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the spacing import pane at its slot
+pub fn spawn_spacing_import_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::SpacingImport,
+        SpacingImportPane,
+        "SpacingImportPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("spacing-import-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(ImportRows));
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when another file is imported
+#[allow(clippy::too_many_arguments)]
+fn rebuild_import_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<Option<ShownImport>>>,
+    rows_query: Query<Entity, With<ImportRows>>,
+    import: Res<SpacingImport>,
+    app_state: Option<Res<AppState>>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    let current = import.pending.as_ref().map(|pending| {
+        let kerning = &pending.spacing.kerning;
+        ShownImport {
+            file_name: pending
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            advances: pending.spacing.advances.len(),
+            pairs: kerning.pairs.values().map(|seconds| seconds.len()).sum(),
+            existing_pairs: app_state.as_ref().map_or(0, |state| {
+                pending
+                    .spacing
+                    .existing_pairs(&state.workspace.font.kerning)
+            }),
+            groups: kerning.groups.len(),
+        }
+    });
+    if shown.as_ref() == Some(&current) {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = Some(current.clone());
+
+    rebuild_rows(&mut commands, rows, |parent| {
+        let Some(current) = current else {
+            return;
+        };
+        let lines = [
+            current.file_name.clone(),
+            i18n::tr_args(
+                "spacing-import-advances",
+                &[("count", &current.advances.to_string())],
+            ),
+            i18n::tr_args(
+                "spacing-import-pairs",
+                &[
+                    ("count", &current.pairs.to_string()),
+                    ("existing", &current.existing_pairs.to_string()),
+                ],
+            ),
+            i18n::tr_args(
+                "spacing-import-groups",
+                &[("count", &current.groups.to_string())],
+            ),
+        ];
+        for line in &lines {
+            create_label_text(parent, line, (), &asset_server, &embedded_fonts, &theme);
+        }
+        parent.spawn(pane_row_node()).with_children(|row| {
+            let buttons = [
+                (
+                    "spacing-import-replace",
+                    SpacingImportEvent::Apply(MergeMode::Replace),
+                ),
+                (
+                    "spacing-import-merge",
+                    SpacingImportEvent::Apply(MergeMode::Merge),
+                ),
+                (
+                    "spacing-import-skip-existing",
+                    SpacingImportEvent::Apply(MergeMode::SkipExisting),
+                ),
+                ("spacing-import-cancel", SpacingImportEvent::Cancel),
+            ];
+            for (label, event) in buttons {
+                create_pane_text_button(
+                    row,
+                    &i18n::tr(label),
+                    ImportButton(event),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+        });
+    });
+}
+
+/// Sends the spacing import events of clicked buttons
+fn handle_import_buttons(
+    interaction_query: Query<(&Interaction, &ImportButton), Changed<Interaction>>,
+    mut import_events: EventWriter<SpacingImportEvent>,
+) {
+    for (interaction, ImportButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            import_events.write(event.clone());
+        }
+    }
+}
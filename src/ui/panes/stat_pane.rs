@@ -0,0 +1,186 @@
+//! STAT Pane Module
+//!
+//! The family's STAT axis values, each stepped, renamed, linked, made elidable
+//! or removed, with buttons adding a value per registered axis.
+
+use crate::core::state::AppState;
+use crate::editing::stat::{StatEvent, StatPaneState};
+use crate::font_source::stat::{StatConfig, STAT_AXES};
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the STAT pane
+#[derive(Component, Default)]
+pub struct StatPane;
+
+/// Container of the rows, rebuilt when the axis values change
+#[derive(Component)]
+struct StatRows;
+
+/// The STAT event a button sends
+#[derive(Component, Clone)]
+struct StatButton(StatEvent);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct StatPanePlugin;
+
+impl Plugin for StatPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_stat_pane).add_systems(
+            Update,
+            (
+                handle_stat_buttons,
+                rebuild_stat_rows,
+                toggle_pane_visibility::<StatPane, _>(|pane: Res<StatPaneState>| pane.visible),
+            ),
+        );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the STAT pane at its slot
+pub fn spawn_stat_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::Stat,
+        StatPane,
+        "StatPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("stat-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(StatRows));
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the axis values change
+fn rebuild_stat_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<Option<StatConfig>>>,
+    rows_query: Query<Entity, With<StatRows>>,
+    app_state: Option<Res<AppState>>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    let stat = app_state.map(|state| state.workspace.font.stat.clone());
+    if shown.as_ref() == Some(&stat) {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = Some(stat.clone());
+
+    rebuild_rows(&mut commands, rows, |parent| {
+        let Some(stat) = stat else {
+            create_label_text(
+                parent,
+                &i18n::tr("pane-no-font"),
+                (),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            return;
+        };
+        for (index, value) in stat.values.iter().enumerate() {
+            parent.spawn(pane_row_node()).with_children(|row| {
+                let label = format!("{} {:>6} {}", value.axis, value.value, value.name);
+                create_label_text(row, &label, (), &asset_server, &embedded_fonts, &theme);
+                let link = match value.linked_value {
+                    Some(linked) => {
+                        i18n::tr_args("stat-link-to", &[("value", &linked.to_string())])
+                    }
+                    None => i18n::tr("stat-link"),
+                };
+                let mark = if value.elidable { "[x]" } else { "[ ]" };
+                let elide = format!("{mark} {}", i18n::tr("stat-elide"));
+                let buttons = [
+                    ("-".to_string(), StatEvent::StepValue(index, false)),
+                    ("+".to_string(), StatEvent::StepValue(index, true)),
+                    (i18n::tr("stat-name"), StatEvent::CycleName(index)),
+                    (link, StatEvent::ToggleLink(index)),
+                    (elide, StatEvent::ToggleElidable(index)),
+                    (i18n::tr("pane-remove"), StatEvent::RemoveValue(index)),
+                ];
+                for (label, event) in buttons {
+                    create_pane_text_button(
+                        row,
+                        &label,
+                        StatButton(event),
+                        &asset_server,
+                        &embedded_fonts,
+                        &theme,
+                    );
+                }
+            });
+        }
+        parent.spawn(pane_row_node()).with_children(|row| {
+            for (tag, _) in STAT_AXES {
+                let label = i18n::tr_args("stat-add", &[("tag", tag)]);
+                let event = StatEvent::AddValue(tag);
+                create_pane_text_button(
+                    row,
+                    &label,
+                    StatButton(event),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+        });
+        create_pane_text_button(
+            parent,
+            &i18n::tr_args(
+                "stat-elided-fallback",
+                &[("name", &stat.elided_fallback_name)],
+            ),
+            StatButton(StatEvent::CycleElidedFallbackName),
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+    });
+}
+
+/// Sends the STAT events of clicked buttons
+fn handle_stat_buttons(
+    interaction_query: Query<(&Interaction, &StatButton), Changed<Interaction>>,
+    mut stat_events: EventWriter<StatEvent>,
+) {
+    for (interaction, StatButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            stat_events.write(event.clone());
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Stem Report Pane Module
+//!
+//! The stem widths measured in a glyph, with how many rays crossed each and
+//! the nearest standard stem; rows off their standard are marked "!".
+
+use crate::editing::stem_report::{CloseStemReportEvent, StemReport};
+use crate::font_source::stems::{StemDirection, StemWidth};
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::panes::pane::{
+    pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the stem report pane
+#[derive(Component, Default)]
+pub struct StemReportPane;
+
+/// Name of the measured glyph
+#[derive(Component)]
+struct StemReportTitle;
+
+/// Container of the table rows, rebuilt when the report changes
+#[derive(Component)]
+struct StemRows;
+
+/// Button that closes the report
+#[derive(Component)]
+struct CloseButton;
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct StemReportPanePlugin;
+
+impl Plugin for StemReportPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_stem_report_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_close_button,
+                    rebuild_stem_rows,
+                    toggle_pane_visibility::<StemReportPane, _>(|stem_report: Res<StemReport>| {
+                        stem_report.report.is_some()
+                    }),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the stem report pane at its slot
+pub fn spawn_stem_report_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::StemReport,
+        StemReportPane,
+        "StemReportPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        create_label_text(
+            parent,
+            &i18n::tr("stem-report-title"),
+            StemReportTitle,
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+        parent.spawn(pane_rows(StemRows));
+        create_pane_text_button(
+            parent,
+            &i18n::tr("button-close"),
+            CloseButton,
+            &asset_server,
+            &embedded_fonts,
+            &theme,
+        );
+    });
+}
+
+/// One table row: kind, width, rays and the standard with the difference
+fn row_text(stem: &StemWidth) -> String {
+    let kind = i18n::tr(match (stem.direction, stem.thin) {
+        (StemDirection::Vertical, false) => "stem-report-stem",
+        (StemDirection::Horizontal, false) => "stem-report-bar",
+        (StemDirection::Vertical, true) => "stem-report-thin-stem",
+        (StemDirection::Horizontal, true) => "stem-report-thin-bar",
+    });
+    let standard = match stem.standard {
+        Some(standard) => format!("{:>4.0} {:+4.0}", standard, stem.width - standard),
+        None => "   -".to_string(),
+    };
+    let flag = if stem.deviates { "!" } else { " " };
+    format!(
+        "{flag} {kind:<9} {:>6.1} {:>4} {standard}",
+        stem.width, stem.rays
+    )
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the title and rows from the report
+fn rebuild_stem_rows(
+    mut commands: Commands,
+    rows_query: Query<Entity, With<StemRows>>,
+    mut title_query: Query<&mut Text, With<StemReportTitle>>,
+    stem_report: Res<StemReport>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !stem_report.is_changed() {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+
+    let report = stem_report.report.as_ref();
+    if let Some(report) = report {
+        for mut title in title_query.iter_mut() {
+            title.0 = i18n::tr_args("stem-report-glyph", &[("glyph", &report.glyph_name)]);
+        }
+    }
+    rebuild_rows(&mut commands, rows, |parent| {
+        let Some(report) = report else {
+            return;
+        };
+        if report.stems.is_empty() {
+            create_label_text(
+                parent,
+                &i18n::tr("stem-report-none"),
+                (),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            return;
+        }
+        let header = format!(
+            "  {:<9} {:>6} {:>4} {}",
+            i18n::tr("stem-report-kind"),
+            i18n::tr("stem-report-width"),
+            i18n::tr("stem-report-rays"),
+            i18n::tr("stem-report-standard")
+        );
+        create_label_text(parent, &header, (), &asset_server, &embedded_fonts, &theme);
+        for stem in &report.stems {
+            let text = row_text(stem);
+            create_label_text(parent, &text, (), &asset_server, &embedded_fonts, &theme);
+        }
+    });
+}
+
+/// Closes the report when the close button is clicked
+fn handle_close_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<CloseButton>)>,
+    mut close_events: EventWriter<CloseStemReportEvent>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            close_events.write(CloseStemReportEvent);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! Subset Pane Module
+//!
+//! Picks the glyph sources of a subset, counts what it keeps, and writes it
+//! as a UFO, a TTF or a trial font.
+
+use crate::core::state::{AppState, TextEditorState};
+use crate::editing::subset::{SubsetEvent, SubsetOutput, SubsetSource, SubsetState, SUBSET_BLOCKS};
+use crate::font_source::subset::SubsetSpec;
+use crate::i18n;
+use crate::systems::commands::TargetGlyphSet;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+use std::collections::BTreeSet;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the subset pane
+#[derive(Component, Default)]
+pub struct SubsetPane;
+
+/// Container of the rows, rebuilt when what they show changes
+#[derive(Component)]
+struct SubsetRows;
+
+/// The subset event a button sends
+#[derive(Component, Clone)]
+struct SubsetButton(SubsetEvent);
+
+/// What the rows were last built from
+#[derive(Clone, PartialEq)]
+struct ShownSubset {
+    sources: BTreeSet<SubsetSource>,
+    glyph_set: Option<String>,
+    /// Glyphs kept and how many of them only for components
+    kept: Option<(usize, usize)>,
+    writing: bool,
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct SubsetPanePlugin;
+
+impl Plugin for SubsetPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_subset_pane).add_systems(
+            Update,
+            (
+                handle_subset_buttons,
+                rebuild_subset_rows,
+                toggle_pane_visibility::<SubsetPane, _>(|subset: Res<SubsetState>| subset.visible),
+            ),
+        );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the subset pane at its slot
+pub fn spawn_subset_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::Subset,
+        SubsetPane,
+        "SubsetPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("subset-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(SubsetRows));
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the sources or what they keep change
+#[allow(clippy::too_many_arguments)]
+fn rebuild_subset_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<ShownSubset>>,
+    rows_query: Query<Entity, With<SubsetRows>>,
+    subset: Res<SubsetState>,
+    app_state: Option<Res<AppState>>,
+    text_editor_state: Option<Res<TextEditorState>>,
+    target: Res<TargetGlyphSet>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    if !subset.visible {
+        return;
+    }
+    let kept = match (app_state.as_ref(), text_editor_state.as_ref()) {
+        (Some(state), Some(text_editor)) => {
+            let spec = SubsetSpec::parse(&subset.entries(text_editor, &target));
+            spec.ok().map(|spec| {
+                let resolved = state.workspace.font.resolve_subset(&spec);
+                (resolved.glyphs.len(), resolved.dependencies.len())
+            })
+        }
+        _ => None,
+    };
+    let current = ShownSubset {
+        sources: subset.sources.clone(),
+        glyph_set: target.0.as_ref().map(|set| set.name.clone()),
+        kept,
+        writing: subset.is_writing(),
+    };
+    if shown.as_ref() == Some(&current) {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = Some(current.clone());
+
+    rebuild_rows(&mut commands, rows, |parent| {
+        let mut sources = vec![(SubsetSource::Text, i18n::tr("subset-text-buffers"))];
+        if let Some(name) = &current.glyph_set {
+            let label = i18n::tr_args("subset-glyph-set", &[("name", name)]);
+            sources.push((SubsetSource::GlyphSet, label));
+        }
+        for (index, (name, range)) in SUBSET_BLOCKS.iter().enumerate() {
+            sources.push((SubsetSource::Block(index), format!("{name} {range}")));
+        }
+        for (source, label) in sources {
+            let mark = if current.sources.contains(&source) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            create_pane_text_button(
+                parent,
+                &format!("{mark} {label}"),
+                SubsetButton(SubsetEvent::Toggle(source)),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        }
+
+        let summary = match current.kept {
+            Some((glyphs, dependencies)) => i18n::tr_args(
+                "subset-keeps",
+                &[
+                    ("glyphs", &glyphs.to_string()),
+                    ("dependencies", &dependencies.to_string()),
+                ],
+            ),
+            None => i18n::tr("pane-no-font"),
+        };
+        create_label_text(parent, &summary, (), &asset_server, &embedded_fonts, &theme);
+        if current.writing {
+            create_label_text(
+                parent,
+                &i18n::tr("subset-writing"),
+                (),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            return;
+        }
+        parent.spawn(pane_row_node()).with_children(|row| {
+            let buttons = [
+                ("subset-write-ufo", SubsetOutput::Ufo, false),
+                ("subset-write-ttf", SubsetOutput::Ttf, false),
+                ("subset-trial-ufo", SubsetOutput::Ufo, true),
+                ("subset-trial-ttf", SubsetOutput::Ttf, true),
+            ];
+            for (label, output, trial) in buttons {
+                let event = SubsetEvent::Write { output, trial };
+                create_pane_text_button(
+                    row,
+                    &i18n::tr(label),
+                    SubsetButton(event),
+                    &asset_server,
+                    &embedded_fonts,
+                    &theme,
+                );
+            }
+        });
+    });
+}
+
+/// Sends the subset events of clicked buttons
+fn handle_subset_buttons(
+    interaction_query: Query<(&Interaction, &SubsetButton), Changed<Interaction>>,
+    mut subset_events: EventWriter<SubsetEvent>,
+) {
+    for (interaction, SubsetButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            subset_events.write(event.clone());
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Text Buffers Pane Module
+//!
+//! The scene's text buffers with the one being typed into marked, and buttons
+//! to type into, find, rename, duplicate or delete each.
+
+use crate::editing::text_buffers::{TextBufferEvent, TextBufferListing, TextBuffersPaneState};
+use crate::i18n;
+use crate::ui::edit_mode_toolbar::ui::{create_label_text, create_pane_text_button};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_row_node, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Component marker for the text buffers pane
+#[derive(Component, Default)]
+pub struct TextBuffersPane;
+
+/// Container of the rows, rebuilt when the buffers change
+#[derive(Component)]
+struct TextBufferRows;
+
+/// The operation a button performs
+#[derive(Component, Clone)]
+struct TextBufferButton(TextBufferEvent);
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct TextBuffersPanePlugin;
+
+impl Plugin for TextBuffersPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_text_buffers_pane)
+            .add_systems(
+                Update,
+                (
+                    handle_text_buffer_buttons,
+                    rebuild_text_buffer_rows,
+                    toggle_pane_visibility::<TextBuffersPane, _>(
+                        |pane: Res<TextBuffersPaneState>| pane.visible,
+                    ),
+                ),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the text buffers pane at its slot
+pub fn spawn_text_buffers_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::TextBuffers,
+        TextBuffersPane,
+        "TextBuffersPane",
+        &asset_server,
+        &theme,
+    )
+    .with_children(|parent| {
+        let title = LocalizedText("text-buffers-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(TextBufferRows));
+    });
+}
+
+/// A buffer's line: a mark on the one being typed into, its name (or the
+/// name being typed) and the start of its text
+fn listing_label(listing: &TextBufferListing, renaming: Option<&str>) -> String {
+    let mark = if listing.active { "▸" } else { " " };
+    match renaming {
+        Some(name) => format!("{mark} {name}▏ {}", i18n::tr("text-buffers-renaming-hint")),
+        None => format!(
+            "{mark} {} ({}): {}",
+            listing.name, listing.sort_count, listing.preview
+        ),
+    }
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Rebuilds the rows when the buffers or the name being typed change
+fn rebuild_text_buffer_rows(
+    mut commands: Commands,
+    mut shown: Local<Option<(Vec<TextBufferListing>, Option<(Entity, String)>)>>,
+    rows_query: Query<Entity, With<TextBufferRows>>,
+    pane: Res<TextBuffersPaneState>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    let current = (pane.buffers.clone(), pane.renaming.clone());
+    if shown.as_ref() == Some(&current) {
+        return;
+    }
+    let Ok(rows) = rows_query.single() else {
+        return;
+    };
+    *shown = Some(current);
+
+    rebuild_rows(&mut commands, rows, |parent| {
+        if pane.buffers.is_empty() {
+            create_label_text(
+                parent,
+                &i18n::tr("text-buffers-none"),
+                (),
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            return;
+        }
+        for listing in &pane.buffers {
+            let renaming = pane
+                .renaming
+                .as_ref()
+                .filter(|(entity, _)| *entity == listing.entity)
+                .map(|(_, name)| name.as_str());
+            let label = listing_label(listing, renaming);
+            create_label_text(parent, &label, (), &asset_server, &embedded_fonts, &theme);
+            parent.spawn(pane_row_node()).with_children(|row| {
+                let entity = listing.entity;
+                for (label, event) in [
+                    ("text-buffers-type", TextBufferEvent::Activate(entity)),
+                    ("text-buffers-go-to", TextBufferEvent::Focus(entity)),
+                    ("text-buffers-rename", TextBufferEvent::Rename(entity)),
+                    ("text-buffers-duplicate", TextBufferEvent::Duplicate(entity)),
+                    ("text-buffers-delete", TextBufferEvent::Delete(entity)),
+                ] {
+                    create_pane_text_button(
+                        row,
+                        &i18n::tr(label),
+                        TextBufferButton(event),
+                        &asset_server,
+                        &embedded_fonts,
+                        &theme,
+                    );
+                }
+            });
+        }
+    });
+}
+
+/// Sends the operations of clicked buttons
+fn handle_text_buffer_buttons(
+    interaction_query: Query<(&Interaction, &TextBufferButton), Changed<Interaction>>,
+    mut buffer_events: EventWriter<TextBufferEvent>,
+) {
+    for (interaction, TextBufferButton(event)) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            buffer_events.write(*event);
+        }
+    }
+}
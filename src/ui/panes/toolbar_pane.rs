@@ -0,0 +1,303 @@
+//! Toolbar Pane Module
+//!
+//! The toolbar customization dialog, opened from the preferences or a toolbar
+//! button's context menu: reorder, hide and regroup tools. Changes are saved
+//! to the user config file.
+
+use crate::core::config::BezySettings;
+use crate::i18n;
+use crate::io::pointer::PointerInfo;
+use crate::ui::accessibility::dialog_node;
+use crate::ui::context_menu::{ContextMenuItem, ContextMenuPlugin, OpenContextMenu};
+use crate::ui::edit_mode_toolbar::toolbar_layout::ToolbarLayout;
+use crate::ui::edit_mode_toolbar::ui::{
+    create_label_text, create_pane_text_button, ToolButtonData,
+};
+use crate::ui::edit_mode_toolbar::{ToolId, ToolRegistry};
+use crate::ui::localization::LocalizedText;
+use crate::ui::panes::pane::{
+    pane_mirrored_row, pane_rows, rebuild_rows, spawn_pane, toggle_pane_visibility, PaneSlot,
+};
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::EmbeddedFonts;
+use bevy::prelude::*;
+
+// ============================================================================
+// DESIGN CONSTANTS
+// ============================================================================
+
+/// Width the tool names are padded to, in characters
+const LABEL_WIDTH: usize = 10;
+
+// ============================================================================
+// RESOURCES & COMPONENTS
+// ============================================================================
+
+/// Whether the dialog is open
+#[derive(Resource, Default)]
+pub struct ToolbarDialogState {
+    pub open: bool,
+}
+
+/// What the menu of a right click on a toolbar button does
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum ToolbarAction {
+    Hide(ToolId),
+    Customize,
+}
+
+/// Component marker for the toolbar pane
+#[derive(Component, Default)]
+pub struct ToolbarPane;
+
+/// Container of the dialog's rows, rebuilt when the layout changes
+#[derive(Component)]
+struct ToolbarDialogContent;
+
+/// What a button in the dialog does
+#[derive(Component, Clone, Copy)]
+enum ToolbarButton {
+    Earlier(ToolId),
+    Later(ToolId),
+    ToggleHidden(ToolId),
+    CycleGroup(ToolId),
+    Reset,
+    Close,
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct ToolbarPanePlugin;
+
+impl Plugin for ToolbarPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ToolbarDialogState>()
+            .add_plugins(ContextMenuPlugin::<ToolbarAction>::default())
+            .add_systems(Startup, spawn_toolbar_pane)
+            .add_systems(
+                Update,
+                (
+                    open_toolbar_menu,
+                    handle_toolbar_actions,
+                    handle_toolbar_buttons,
+                    rebuild_toolbar_dialog,
+                    toggle_pane_visibility::<ToolbarPane, _>(|dialog: Res<ToolbarDialogState>| {
+                        dialog.open
+                    }),
+                )
+                    .chain(),
+            );
+    }
+}
+
+// ============================================================================
+// UI CREATION
+// ============================================================================
+
+/// Spawns the toolbar pane, hidden until the dialog is opened
+pub fn spawn_toolbar_pane(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    spawn_pane(
+        &mut commands,
+        PaneSlot::Toolbar,
+        ToolbarPane,
+        "ToolbarPane",
+        &asset_server,
+        &theme,
+    )
+    .insert(dialog_node(&i18n::tr("toolbar-title")))
+    .with_children(|parent| {
+        let title = LocalizedText("toolbar-title");
+        let text = i18n::tr(title.0);
+        create_label_text(parent, &text, title, &asset_server, &embedded_fonts, &theme);
+        parent.spawn(pane_rows(ToolbarDialogContent));
+    });
+}
+
+/// The name of a tool in the interface language
+fn tool_name(tool_registry: &ToolRegistry, tool_id: &str) -> String {
+    let name = tool_registry
+        .get_tool(tool_id)
+        .map_or(tool_id, |tool| tool.name());
+    i18n::tool_name(tool_id, name)
+}
+
+/// A row naming a tool, with buttons to move, hide and group it
+fn spawn_tool_row(
+    parent: &mut ChildSpawnerCommands,
+    tool_id: ToolId,
+    layout: &ToolbarLayout,
+    tool_registry: &ToolRegistry,
+    asset_server: &AssetServer,
+    embedded_fonts: &EmbeddedFonts,
+    theme: &CurrentTheme,
+) {
+    let shown = if layout.hidden.contains(tool_id) {
+        i18n::tr("toolbar-hidden")
+    } else {
+        i18n::tr("toolbar-shown")
+    };
+    let group = match layout.host_of(tool_id, tool_registry.get_groups()) {
+        Some(host) => {
+            let host = tool_name(tool_registry, &host);
+            i18n::tr_args("toolbar-in-group", &[("tool", &host)])
+        }
+        None => i18n::tr("toolbar-no-group"),
+    };
+    parent.spawn(pane_mirrored_row()).with_children(|row| {
+        let label = format!("{:<LABEL_WIDTH$}", tool_name(tool_registry, tool_id));
+        create_label_text(row, &label, (), asset_server, embedded_fonts, theme);
+        let buttons = [
+            ("‹".to_string(), ToolbarButton::Earlier(tool_id)),
+            ("›".to_string(), ToolbarButton::Later(tool_id)),
+            (shown, ToolbarButton::ToggleHidden(tool_id)),
+            (group, ToolbarButton::CycleGroup(tool_id)),
+        ];
+        for (label, button) in buttons {
+            create_pane_text_button(row, &label, button, asset_server, embedded_fonts, theme);
+        }
+    });
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Opens a menu for the toolbar button right-clicked
+fn open_toolbar_menu(
+    mouse: Res<ButtonInput<MouseButton>>,
+    pointer: Res<PointerInfo>,
+    button_query: Query<(&Interaction, &ToolButtonData)>,
+    tool_registry: Res<ToolRegistry>,
+    mut menus: EventWriter<OpenContextMenu<ToolbarAction>>,
+) {
+    if !mouse.just_pressed(MouseButton::Right) {
+        return;
+    }
+    let Some((_, button)) = button_query
+        .iter()
+        .find(|(interaction, _)| **interaction != Interaction::None)
+    else {
+        return;
+    };
+    let name = tool_name(&tool_registry, button.tool_id);
+    menus.write(OpenContextMenu {
+        position: pointer.screen,
+        items: vec![
+            ContextMenuItem::new(
+                i18n::tr_args("toolbar-hide-tool", &[("tool", &name)]),
+                ToolbarAction::Hide(button.tool_id),
+            ),
+            ContextMenuItem::new(i18n::tr("toolbar-customize"), ToolbarAction::Customize),
+        ],
+    });
+}
+
+/// Hides tools and opens the dialog from the toolbar buttons' menu
+fn handle_toolbar_actions(
+    mut actions: EventReader<ToolbarAction>,
+    mut dialog: ResMut<ToolbarDialogState>,
+    mut settings: ResMut<BezySettings>,
+) {
+    for action in actions.read() {
+        match action {
+            ToolbarAction::Hide(tool_id) => {
+                settings.toolbar.hidden.insert(tool_id.to_string());
+            }
+            ToolbarAction::Customize => dialog.open = true,
+        }
+    }
+}
+
+/// Moves, hides and groups tools, resets the layout and closes the dialog
+fn handle_toolbar_buttons(
+    interaction_query: Query<(&Interaction, &ToolbarButton), Changed<Interaction>>,
+    mut dialog: ResMut<ToolbarDialogState>,
+    mut settings: ResMut<BezySettings>,
+    tool_registry: Res<ToolRegistry>,
+) {
+    let order = tool_registry.sorted_tool_ids();
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let layout = &mut settings.toolbar;
+        match *button {
+            ToolbarButton::Earlier(tool_id) => layout.move_tool(tool_id, &order, false),
+            ToolbarButton::Later(tool_id) => layout.move_tool(tool_id, &order, true),
+            ToolbarButton::ToggleHidden(tool_id) => layout.toggle_hidden(tool_id),
+            ToolbarButton::CycleGroup(tool_id) => {
+                layout.cycle_group(tool_id, &order, tool_registry.get_groups());
+            }
+            ToolbarButton::Reset => *layout = ToolbarLayout::default(),
+            ToolbarButton::Close => dialog.open = false,
+        }
+    }
+}
+
+/// Rebuilds the dialog when it opens or the layout changes
+#[allow(clippy::too_many_arguments)]
+fn rebuild_toolbar_dialog(
+    mut commands: Commands,
+    mut shown: Local<Option<ToolbarLayout>>,
+    content_query: Query<Entity, With<ToolbarDialogContent>>,
+    dialog: Res<ToolbarDialogState>,
+    settings: Res<BezySettings>,
+    tool_registry: Res<ToolRegistry>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    theme: Res<CurrentTheme>,
+) {
+    let layout = dialog.open.then(|| settings.toolbar.clone());
+    if !dialog.is_changed() && *shown == layout {
+        return;
+    }
+    let Ok(content) = content_query.single() else {
+        return;
+    };
+    *shown = layout;
+
+    rebuild_rows(&mut commands, content, |parent| {
+        if !dialog.open {
+            return;
+        }
+        let order = settings
+            .toolbar
+            .full_order(&tool_registry.sorted_tool_ids());
+        for tool_id in order {
+            spawn_tool_row(
+                parent,
+                tool_id,
+                &settings.toolbar,
+                &tool_registry,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        }
+        parent.spawn(pane_mirrored_row()).with_children(|row| {
+            create_pane_text_button(
+                row,
+                &i18n::tr("toolbar-reset"),
+                ToolbarButton::Reset,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+            create_pane_text_button(
+                row,
+                &i18n::tr("button-close"),
+                ToolbarButton::Close,
+                &asset_server,
+                &embedded_fonts,
+                &theme,
+            );
+        });
+    });
+}
@@ -0,0 +1,390 @@
+//! Performance overlay and budget warnings
+//!
+//! F3 shows an overlay in the top-right corner with the frame time, the
+//! slowest of the editor's system stages, entity counts and the glyph mesh
+//! cache hit rates. Each stage is timed from a marker system ordered before
+//! its set to one ordered after it, so systems running alongside the stage
+//! count towards it too.
+//!
+//! Whether or not the overlay is shown, a warning is logged when a stage or
+//! the whole frame goes over its budget, at most once a second for each.
+
+use crate::editing::sort::manager::SortPointEntity;
+use crate::editing::sort::Sort;
+use crate::editing::system_sets::FontEditorSets;
+use crate::rendering::glyph_renderer::GlyphRenderElement;
+use crate::rendering::mesh_cache::GlyphMeshCache;
+use crate::rendering::points::PointInstances;
+use crate::rendering::PostEditingRenderingSet;
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::{AssetServerFontExt, EmbeddedFonts};
+use bevy::ecs::entity::Entities;
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::time::{Duration, Instant};
+
+/// Time a frame can take and still keep up with a 60 Hz display
+const FRAME_BUDGET: Duration = Duration::from_micros(16_667);
+
+/// Time a single stage can take before it's reported
+const STAGE_BUDGET: Duration = Duration::from_millis(4);
+
+/// Shortest gap between two warnings about the same stage
+const WARNING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How much of each new measurement goes into the smoothed timings
+const SMOOTHING: f32 = 0.1;
+
+/// How many of the slowest stages the overlay lists
+const LISTED_STAGES: usize = 4;
+
+/// How often the overlay's text is rewritten
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Name frame-time warnings are filed under
+const FRAME: &str = "Frame";
+
+/// Whether the performance overlay is shown
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct PerformanceOverlay {
+    pub visible: bool,
+}
+
+/// How long one stage took
+#[derive(Debug, Clone)]
+pub struct StageTiming {
+    pub name: &'static str,
+    pub last: Duration,
+    pub smoothed: Duration,
+    started: Option<Instant>,
+}
+
+/// Timings of the frame and each timed stage
+#[derive(Resource, Debug, Default)]
+pub struct PerformanceTimings {
+    pub stages: Vec<StageTiming>,
+    pub frame: Duration,
+    /// Longest frame since the overlay last refreshed
+    pub worst_frame: Duration,
+    last_warnings: Vec<(&'static str, Instant)>,
+}
+
+impl PerformanceTimings {
+    fn stage_mut(&mut self, name: &'static str) -> &mut StageTiming {
+        let index = match self.stages.iter().position(|stage| stage.name == name) {
+            Some(index) => index,
+            None => {
+                self.stages.push(StageTiming {
+                    name,
+                    last: Duration::ZERO,
+                    smoothed: Duration::ZERO,
+                    started: None,
+                });
+                self.stages.len() - 1
+            }
+        };
+        &mut self.stages[index]
+    }
+
+    fn begin(&mut self, name: &'static str, now: Instant) {
+        self.stage_mut(name).started = Some(now);
+    }
+
+    /// Record the end of a stage and return how long it took
+    fn end(&mut self, name: &'static str, now: Instant) -> Option<Duration> {
+        let stage = self.stage_mut(name);
+        let elapsed = now.saturating_duration_since(stage.started.take()?);
+        stage.last = elapsed;
+        stage.smoothed = smooth(stage.smoothed, elapsed);
+        Some(elapsed)
+    }
+
+    fn record_frame(&mut self, frame: Duration) {
+        self.frame = smooth(self.frame, frame);
+        self.worst_frame = self.worst_frame.max(frame);
+    }
+
+    /// The stages that took longest lately, slowest first
+    pub fn slowest(&self, count: usize) -> Vec<&StageTiming> {
+        let mut stages: Vec<&StageTiming> = self.stages.iter().collect();
+        stages.sort_by(|a, b| b.smoothed.cmp(&a.smoothed));
+        stages.truncate(count);
+        stages
+    }
+
+    /// Whether `elapsed` is over `budget` and `name` hasn't been warned
+    /// about in the last [`WARNING_INTERVAL`]; if so, note the warning
+    fn should_warn(
+        &mut self,
+        name: &'static str,
+        elapsed: Duration,
+        budget: Duration,
+        now: Instant,
+    ) -> bool {
+        if elapsed <= budget {
+            return false;
+        }
+        match self
+            .last_warnings
+            .iter_mut()
+            .find(|(warned, _)| *warned == name)
+        {
+            Some((_, last)) if now.saturating_duration_since(*last) < WARNING_INTERVAL => false,
+            Some((_, last)) => {
+                *last = now;
+                true
+            }
+            None => {
+                self.last_warnings.push((name, now));
+                true
+            }
+        }
+    }
+}
+
+/// Exponential moving average of durations
+pub fn smooth(previous: Duration, latest: Duration) -> Duration {
+    if previous.is_zero() {
+        return latest;
+    }
+    previous.mul_f32(1.0 - SMOOTHING) + latest.mul_f32(SMOOTHING)
+}
+
+fn millis(duration: Duration) -> f32 {
+    duration.as_secs_f32() * 1000.0
+}
+
+/// Marks the overlay's text
+#[derive(Component)]
+struct PerformanceOverlayText;
+
+/// Marks the overlay's panel
+#[derive(Component)]
+struct PerformanceOverlayPanel;
+
+pub struct PerformanceOverlayPlugin;
+
+impl Plugin for PerformanceOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PerformanceOverlay>()
+            .init_resource::<PerformanceTimings>()
+            .add_systems(Startup, spawn_overlay)
+            .add_systems(
+                Update,
+                (
+                    toggle_overlay,
+                    (record_frame_time, update_overlay_visibility),
+                    update_overlay_text.run_if(on_timer(REFRESH_INTERVAL)),
+                )
+                    .chain(),
+            );
+        time_stage(app, "Input", FontEditorSets::Input);
+        time_stage(app, "Text buffer", FontEditorSets::TextBuffer);
+        time_stage(app, "Entity sync", FontEditorSets::EntitySync);
+        time_stage(app, "Rendering", FontEditorSets::Rendering);
+        time_stage(app, "Cleanup", FontEditorSets::Cleanup);
+        time_stage(app, "Glyph rendering", PostEditingRenderingSet);
+    }
+}
+
+/// Add marker systems around `set` that time it as the stage `name`
+fn time_stage(app: &mut App, name: &'static str, set: impl SystemSet + Clone) {
+    let begin = move |mut timings: ResMut<PerformanceTimings>| {
+        timings.begin(name, Instant::now());
+    };
+    let end = move |mut timings: ResMut<PerformanceTimings>| {
+        let now = Instant::now();
+        let Some(elapsed) = timings.end(name, now) else {
+            return;
+        };
+        if timings.should_warn(name, elapsed, STAGE_BUDGET, now) {
+            warn!(
+                "{} took {:.1} ms, over its {:.1} ms budget",
+                name,
+                millis(elapsed),
+                millis(STAGE_BUDGET)
+            );
+        }
+    };
+    app.add_systems(Update, (begin.before(set.clone()), end.after(set)));
+}
+
+fn toggle_overlay(keyboard: Res<ButtonInput<KeyCode>>, mut overlay: ResMut<PerformanceOverlay>) {
+    if keyboard.just_pressed(KeyCode::F3) {
+        overlay.visible = !overlay.visible;
+        debug!(
+            "Performance overlay {}",
+            if overlay.visible { "shown" } else { "hidden" }
+        );
+    }
+}
+
+fn record_frame_time(time: Res<Time<Real>>, mut timings: ResMut<PerformanceTimings>) {
+    let frame = time.delta();
+    if frame.is_zero() {
+        return;
+    }
+    timings.record_frame(frame);
+    let now = Instant::now();
+    if timings.should_warn(FRAME, frame, FRAME_BUDGET, now) {
+        warn!(
+            "Frame took {:.1} ms, over its {:.1} ms budget",
+            millis(frame),
+            millis(FRAME_BUDGET)
+        );
+    }
+}
+
+fn spawn_overlay(
+    mut commands: Commands,
+    theme: Res<CurrentTheme>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+) {
+    let theme = theme.theme();
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(theme.ruler_size() + theme.widget_margin()),
+                right: Val::Px(theme.widget_margin()),
+                padding: UiRect::all(Val::Px(theme.widget_padding())),
+                border: UiRect::all(Val::Px(1.0)),
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(theme.widget_background_color()),
+            BorderColor(theme.widget_border_color()),
+            GlobalZIndex(1000),
+            PerformanceOverlayPanel,
+            Name::new("PerformanceOverlay"),
+        ))
+        .with_child((
+            Text::new(""),
+            TextFont {
+                font: asset_server.load_font_with_fallback(theme.mono_font_path(), &embedded_fonts),
+                font_size: theme.small_font_size(),
+                ..default()
+            },
+            TextColor(theme.ui_text_secondary()),
+            PerformanceOverlayText,
+        ));
+}
+
+fn update_overlay_visibility(
+    overlay: Res<PerformanceOverlay>,
+    mut panels: Query<&mut Node, With<PerformanceOverlayPanel>>,
+) {
+    if !overlay.is_changed() {
+        return;
+    }
+    for mut node in panels.iter_mut() {
+        node.display = if overlay.visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_overlay_text(
+    overlay: Res<PerformanceOverlay>,
+    mut timings: ResMut<PerformanceTimings>,
+    theme: Res<CurrentTheme>,
+    mesh_cache: Res<GlyphMeshCache>,
+    point_instances: Res<PointInstances>,
+    entities: &Entities,
+    counts: (
+        Query<(), With<Sort>>,
+        Query<(), With<SortPointEntity>>,
+        Query<(), With<GlyphRenderElement>>,
+    ),
+    mut texts: Query<(&mut Text, &mut TextColor), With<PerformanceOverlayText>>,
+) {
+    let worst_frame = std::mem::take(&mut timings.worst_frame);
+    if !overlay.visible {
+        return;
+    }
+    let (sorts, points, elements) = counts;
+    let mut lines = vec![format!(
+        "frame    {:5.1} ms  worst {:5.1} ms",
+        millis(timings.frame),
+        millis(worst_frame)
+    )];
+    for stage in timings.slowest(LISTED_STAGES) {
+        let flag = if stage.smoothed > STAGE_BUDGET {
+            "!"
+        } else {
+            ""
+        };
+        lines.push(format!(
+            "{:<16}{:5.1} ms{}",
+            stage.name,
+            millis(stage.smoothed),
+            flag
+        ));
+    }
+    lines.push(format!(
+        "entities {}  sorts {}  points {}",
+        entities.len(),
+        sorts.iter().count(),
+        points.iter().count()
+    ));
+    lines.push(format!(
+        "elements {}  instances {}",
+        elements.iter().count(),
+        point_instances.len()
+    ));
+    lines.push(format!(
+        "mesh hits  filled {:.0}%  outline {:.0}%  metrics {:.0}%",
+        mesh_cache.filled_hit_rate() * 100.0,
+        mesh_cache.outline_hit_rate() * 100.0,
+        mesh_cache.metrics_hit_rate() * 100.0
+    ));
+
+    let over_budget = timings.frame > FRAME_BUDGET
+        || timings
+            .stages
+            .iter()
+            .any(|stage| stage.smoothed > STAGE_BUDGET);
+    let color = if over_budget {
+        theme.theme().error_color()
+    } else {
+        theme.theme().ui_text_secondary()
+    };
+    for (mut text, mut text_color) in texts.iter_mut() {
+        text.0 = lines.join("\n");
+        text_color.0 = color;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_timing_and_warnings() {
+        let mut timings = PerformanceTimings::default();
+        let start = Instant::now();
+        timings.begin("Slow", start);
+        let elapsed = timings.end("Slow", start + Duration::from_millis(6));
+        assert_eq!(elapsed, Some(Duration::from_millis(6)));
+        assert_eq!(timings.end("Slow", start), None);
+        timings.begin("Fast", start);
+        timings.end("Fast", start + Duration::from_millis(1));
+        let slowest: Vec<&str> = timings.slowest(1).iter().map(|stage| stage.name).collect();
+        assert_eq!(slowest, ["Slow"]);
+
+        let slow = Duration::from_millis(6);
+        assert!(timings.should_warn("Slow", slow, STAGE_BUDGET, start));
+        let soon = start + Duration::from_millis(500);
+        assert!(!timings.should_warn("Slow", slow, STAGE_BUDGET, soon));
+        let later = start + Duration::from_secs(2);
+        assert!(timings.should_warn("Slow", slow, STAGE_BUDGET, later));
+        assert!(!timings.should_warn("Fast", Duration::from_millis(1), STAGE_BUDGET, later));
+
+        let smoothed = smooth(Duration::from_millis(10), Duration::from_millis(20));
+        assert!((millis(smoothed) - 11.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,156 @@
+//! Reading mode
+//!
+//! Cmd/Ctrl+Alt+Shift+N shows the text as it would print, for screenshots
+//! and design reviews: presentation mode stays on whatever the tool, so
+//! points, handles, metrics, guides and the checkerboard go away and every
+//! sort is drawn filled, and the toolbars, panes, rulers and overlays are
+//! hidden as well. Pressing the shortcut again swaps the background between
+//! dark and light; Escape returns to editing with the theme in the
+//! preferences.
+
+use crate::core::config::BezySettings;
+use crate::io::shortcuts::Shortcut;
+use crate::ui::edit_mode_toolbar::PresentationMode;
+use crate::ui::themes::{CurrentTheme, ThemeVariant};
+use bevy::color::Luminance;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+
+/// Top-level UI nodes hidden for the frame being drawn, with the display
+/// their own systems gave them
+#[derive(Resource, Default)]
+struct HiddenChrome(Vec<(Entity, Display)>);
+
+pub struct ReadingModePlugin;
+
+impl Plugin for ReadingModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HiddenChrome>()
+            .add_systems(
+                PreUpdate,
+                (
+                    restore_editing_chrome,
+                    handle_reading_mode_keys,
+                    apply_reading_background,
+                )
+                    .chain()
+                    .after(InputSystem),
+            )
+            .add_systems(PostUpdate, hide_editing_chrome.before(UiSystem::Layout));
+    }
+}
+
+impl PresentationMode {
+    /// The reading mode shortcut: turns reading mode on, then swaps the
+    /// background each time after, starting from the editing theme's
+    fn press_reading_shortcut(&mut self, theme_is_dark: bool) {
+        if !self.reading {
+            self.reading = true;
+            return;
+        }
+        let dark = self.dark_background.unwrap_or(theme_is_dark);
+        self.dark_background = Some(!dark);
+    }
+
+    fn leave_reading(&mut self) {
+        self.reading = false;
+        self.dark_background = None;
+    }
+}
+
+/// Cmd/Ctrl+Alt+Shift+N enters reading mode or swaps its background, and
+/// Escape leaves it without reaching the tools
+fn handle_reading_mode_keys(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut presentation_mode: ResMut<PresentationMode>,
+    theme: Res<CurrentTheme>,
+) {
+    if Shortcut::ReadingMode.just_pressed(&keyboard) {
+        let theme_is_dark = theme.theme().background_color().luminance() < 0.5;
+        presentation_mode.press_reading_shortcut(theme_is_dark);
+        match presentation_mode.dark_background {
+            Some(dark) => info!(
+                "Reading mode on a {} background",
+                if dark { "dark" } else { "light" }
+            ),
+            None => info!("Reading mode on; Escape returns to editing"),
+        }
+    } else if presentation_mode.reading && keyboard.just_pressed(KeyCode::Escape) {
+        keyboard.clear_just_pressed(KeyCode::Escape);
+        presentation_mode.leave_reading();
+        info!("Reading mode off");
+    }
+}
+
+/// Switches to the dark or light theme while reading mode asks for one, and
+/// back to the preferred theme afterwards
+fn apply_reading_background(
+    presentation_mode: Res<PresentationMode>,
+    settings: Res<BezySettings>,
+    mut theme: ResMut<CurrentTheme>,
+    mut clear_color: ResMut<ClearColor>,
+) {
+    if !presentation_mode.is_changed() {
+        return;
+    }
+    let variant = match presentation_mode.dark_background {
+        Some(dark) if presentation_mode.reading => {
+            ThemeVariant::parse(if dark { "dark" } else { "light" })
+        }
+        _ => Some(settings.theme.clone()),
+    };
+    let Some(variant) = variant.filter(|variant| *variant != theme.variant) else {
+        return;
+    };
+    theme.switch_to(variant);
+    clear_color.0 = theme.theme().background_color();
+}
+
+/// Hides every top-level UI node in reading mode, just for drawing, so the
+/// systems that show and hide them go on as before
+fn hide_editing_chrome(
+    presentation_mode: Res<PresentationMode>,
+    mut hidden: ResMut<HiddenChrome>,
+    mut root_nodes: Query<(Entity, &mut Node), Without<ChildOf>>,
+) {
+    if !presentation_mode.reading {
+        return;
+    }
+    for (entity, mut node) in root_nodes.iter_mut() {
+        if node.display != Display::None {
+            hidden.0.push((entity, node.display));
+            node.display = Display::None;
+        }
+    }
+}
+
+/// Gives the nodes hidden for the last frame their display back
+fn restore_editing_chrome(mut hidden: ResMut<HiddenChrome>, mut nodes: Query<&mut Node>) {
+    for (entity, display) in hidden.0.drain(..) {
+        if let Ok(mut node) = nodes.get_mut(entity) {
+            node.display = display;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortcut_enters_then_swaps_background() {
+        let mut mode = PresentationMode::default();
+        mode.press_reading_shortcut(true);
+        assert!(mode.reading);
+        assert_eq!(mode.dark_background, None);
+        mode.press_reading_shortcut(true);
+        assert_eq!(mode.dark_background, Some(false));
+        mode.press_reading_shortcut(true);
+        assert_eq!(mode.dark_background, Some(true));
+
+        mode.leave_reading();
+        assert!(!mode.reading);
+        assert_eq!(mode.dark_background, None);
+    }
+}
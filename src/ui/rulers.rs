@@ -0,0 +1,562 @@
+//! Canvas rulers and guidelines
+//!
+//! Rulers along the top and left edges of the window show design units for
+//! the part of the canvas in view. Dragging out of the top ruler pulls a
+//! horizontal guideline onto the canvas and dragging out of the left ruler a
+//! vertical one. Each guideline has a marker on the ruler it crosses; press
+//! the marker to move the guideline, and drop a guideline back onto a ruler
+//! to remove it. Cmd/Ctrl+R shows or hides the rulers, along with the
+//! status bar (see `ui::status_bar`).
+//!
+//! Guidelines last for the session and are not saved to the font.
+
+use crate::io::pointer::PointerInfo;
+use crate::io::shortcuts::Shortcut;
+use crate::rendering::cameras::DesignCamera;
+use crate::rendering::mesh_utils::create_line_mesh;
+use crate::rendering::zoom_aware_scaling::CameraResponsiveScale;
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::{AssetServerFontExt, EmbeddedFonts};
+use bevy::prelude::*;
+
+/// Labelled ticks are at least this many screen pixels apart
+const MIN_LABEL_SPACING_PX: f32 = 60.0;
+
+/// How close, in screen pixels, a press has to be to a guideline's marker
+const GRAB_DISTANCE_PX: f32 = 4.0;
+
+/// Guidelines reach this far, in design units, each way from the origin
+const GUIDELINE_EXTENT: f32 = 100_000.0;
+
+/// Guidelines draw above the metrics lines and below the glyph handles
+const GUIDELINE_Z: f32 = 6.0;
+
+/// Upper bound on the ticks drawn along one ruler
+const MAX_TICKS: usize = 2000;
+
+/// Whether the rulers and the status bar are shown
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Rulers {
+    pub visible: bool,
+}
+
+impl Default for Rulers {
+    fn default() -> Self {
+        Self { visible: true }
+    }
+}
+
+/// Guidelines on the canvas, in design units: the heights of horizontal
+/// guidelines and the positions of vertical ones
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct CanvasGuidelines {
+    pub horizontal: Vec<f32>,
+    pub vertical: Vec<f32>,
+}
+
+/// Which way a guideline runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuidelineAxis {
+    Horizontal,
+    Vertical,
+}
+
+impl CanvasGuidelines {
+    fn positions_mut(&mut self, axis: GuidelineAxis) -> &mut Vec<f32> {
+        match axis {
+            GuidelineAxis::Horizontal => &mut self.horizontal,
+            GuidelineAxis::Vertical => &mut self.vertical,
+        }
+    }
+}
+
+/// The guideline being dragged, by axis and index
+#[derive(Resource, Default)]
+struct GuidelineDrag(Option<(GuidelineAxis, usize)>);
+
+/// How the window maps onto the canvas: the design position at the window's
+/// top-left corner and the design units per screen pixel along each axis
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
+struct RulerView {
+    window: Vec2,
+    origin: Vec2,
+    units_per_px: Vec2,
+}
+
+impl RulerView {
+    fn to_screen(&self, design: Vec2) -> Vec2 {
+        (design - self.origin) / self.units_per_px
+    }
+
+    fn to_design(&self, screen: Vec2) -> Vec2 {
+        self.origin + screen * self.units_per_px
+    }
+}
+
+/// Which edge of the window a ruler runs along
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum RulerEdge {
+    Top,
+    Left,
+}
+
+/// The square where the two rulers meet
+#[derive(Component)]
+struct RulerCorner;
+
+/// Ticks, labels and guideline markers, rebuilt when the view changes
+#[derive(Component)]
+struct RulerMark;
+
+/// Marker for guideline meshes
+#[derive(Component)]
+struct GuidelineLine;
+
+pub struct RulersPlugin;
+
+impl Plugin for RulersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Rulers>()
+            .init_resource::<CanvasGuidelines>()
+            .init_resource::<GuidelineDrag>()
+            .init_resource::<RulerView>()
+            .add_systems(Startup, spawn_rulers)
+            .add_systems(
+                Update,
+                (
+                    toggle_rulers,
+                    update_ruler_view,
+                    drag_guidelines,
+                    update_ruler_visibility,
+                    update_ruler_marks,
+                    render_guidelines,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// The step between labelled ticks, in design units, and how many ticks
+/// each step is divided into, for a zoom of `px_per_unit`
+pub fn ruler_step(px_per_unit: f32) -> (f32, u32) {
+    let min_units = MIN_LABEL_SPACING_PX / px_per_unit;
+    if min_units <= 1.0 {
+        return (1.0, 1);
+    }
+    let magnitude = 10f32.powi(min_units.log10().floor() as i32);
+    for (multiple, divisions) in [(1.0, 5), (2.0, 4), (5.0, 5)] {
+        if multiple * magnitude >= min_units {
+            return (multiple * magnitude, divisions);
+        }
+    }
+    (10.0 * magnitude, 5)
+}
+
+/// The ticks of a ruler spanning `from` to `to` in design units, as their
+/// position and whether they are labelled
+pub fn ruler_ticks(from: f32, to: f32, px_per_unit: f32) -> Vec<(f32, bool)> {
+    if !px_per_unit.is_finite() || px_per_unit <= 0.0 {
+        return Vec::new();
+    }
+    let (step, divisions) = ruler_step(px_per_unit);
+    let tick = step / divisions as f32;
+    let (low, high) = (from.min(to), from.max(to));
+    let first = (low / tick).ceil() as i64;
+    let last = (high / tick).floor() as i64;
+    (first..=last)
+        .take(MAX_TICKS)
+        .map(|k| (k as f32 * tick, k.rem_euclid(divisions as i64) == 0))
+        .collect()
+}
+
+fn spawn_rulers(mut commands: Commands, theme: Res<CurrentTheme>) {
+    let size = theme.theme().ruler_size();
+    let background = BackgroundColor(theme.theme().ruler_background_color());
+    let bars = [
+        (
+            RulerEdge::Top,
+            Node {
+                top: Val::Px(0.0),
+                left: Val::Px(size),
+                right: Val::Px(0.0),
+                height: Val::Px(size),
+                ..default()
+            },
+        ),
+        (
+            RulerEdge::Left,
+            Node {
+                left: Val::Px(0.0),
+                top: Val::Px(size),
+                bottom: Val::Px(0.0),
+                width: Val::Px(size),
+                ..default()
+            },
+        ),
+    ];
+    for (edge, node) in bars {
+        commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                overflow: Overflow::clip(),
+                ..node
+            },
+            background,
+            Interaction::default(),
+            ZIndex(-1),
+            edge,
+            Name::new(format!("{edge:?}Ruler")),
+        ));
+    }
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(0.0),
+            top: Val::Px(0.0),
+            width: Val::Px(size),
+            height: Val::Px(size),
+            ..default()
+        },
+        background,
+        Interaction::default(),
+        ZIndex(-1),
+        RulerCorner,
+        Name::new("RulerCorner"),
+    ));
+}
+
+/// Cmd/Ctrl+R shows or hides the rulers
+fn toggle_rulers(keyboard: Res<ButtonInput<KeyCode>>, mut rulers: ResMut<Rulers>) {
+    if Shortcut::Rulers.just_pressed(&keyboard) {
+        rulers.visible = !rulers.visible;
+        debug!("Rulers {}", if rulers.visible { "shown" } else { "hidden" });
+    }
+}
+
+fn update_ruler_view(
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<DesignCamera>>,
+    mut view: ResMut<RulerView>,
+) {
+    let (Ok(window), Ok((camera, camera_transform))) = (windows.single(), camera_query.single())
+    else {
+        return;
+    };
+    let sample = Vec2::splat(100.0);
+    let (Ok(origin), Ok(far)) = (
+        camera.viewport_to_world_2d(camera_transform, Vec2::ZERO),
+        camera.viewport_to_world_2d(camera_transform, sample),
+    ) else {
+        return;
+    };
+    let units_per_px = (far - origin) / sample;
+    if units_per_px.x == 0.0 || units_per_px.y == 0.0 {
+        return;
+    }
+    view.set_if_neq(RulerView {
+        window: window.size(),
+        origin,
+        units_per_px,
+    });
+}
+
+/// Pulls guidelines out of the rulers, moves them and drops them back
+#[allow(clippy::too_many_arguments)]
+fn drag_guidelines(
+    mouse: Res<ButtonInput<MouseButton>>,
+    pointer: Res<PointerInfo>,
+    rulers: Res<Rulers>,
+    view: Res<RulerView>,
+    bars: Query<(&RulerEdge, &Interaction)>,
+    theme: Res<CurrentTheme>,
+    mut guidelines: ResMut<CanvasGuidelines>,
+    mut drag: ResMut<GuidelineDrag>,
+) {
+    let design = pointer.world.round();
+    if mouse.just_pressed(MouseButton::Left) && rulers.visible {
+        let Some((edge, _)) = bars
+            .iter()
+            .find(|(_, interaction)| **interaction == Interaction::Pressed)
+        else {
+            return;
+        };
+        // A marker on this ruler belongs to a guideline running across it;
+        // anywhere else starts a guideline running along it
+        let (crossing, along, position, new_position) = match edge {
+            RulerEdge::Top => (
+                GuidelineAxis::Vertical,
+                GuidelineAxis::Horizontal,
+                pointer.world.x,
+                design.y,
+            ),
+            RulerEdge::Left => (
+                GuidelineAxis::Horizontal,
+                GuidelineAxis::Vertical,
+                pointer.world.y,
+                design.x,
+            ),
+        };
+        let reach = match edge {
+            RulerEdge::Top => view.units_per_px.x.abs(),
+            RulerEdge::Left => view.units_per_px.y.abs(),
+        } * GRAB_DISTANCE_PX;
+        let grabbed = guidelines
+            .positions_mut(crossing)
+            .iter()
+            .position(|existing| (existing - position).abs() <= reach);
+        drag.0 = match grabbed {
+            Some(index) => Some((crossing, index)),
+            None => {
+                let positions = guidelines.positions_mut(along);
+                positions.push(new_position);
+                Some((along, positions.len() - 1))
+            }
+        };
+        return;
+    }
+
+    let Some((axis, index)) = drag.0 else {
+        return;
+    };
+    if mouse.just_released(MouseButton::Left) {
+        drag.0 = None;
+        let size = theme.theme().ruler_size();
+        let over_ruler = pointer.screen.x < size || pointer.screen.y < size;
+        if over_ruler && index < guidelines.positions_mut(axis).len() {
+            guidelines.positions_mut(axis).remove(index);
+        }
+        return;
+    }
+    let position = match axis {
+        GuidelineAxis::Horizontal => design.y,
+        GuidelineAxis::Vertical => design.x,
+    };
+    let current = guidelines
+        .bypass_change_detection()
+        .positions_mut(axis)
+        .get(index)
+        .copied();
+    if current.is_some_and(|current| current != position) {
+        guidelines.positions_mut(axis)[index] = position;
+    }
+}
+
+fn update_ruler_visibility(
+    rulers: Res<Rulers>,
+    mut nodes: Query<&mut Node, Or<(With<RulerEdge>, With<RulerCorner>)>>,
+) {
+    if !rulers.is_changed() {
+        return;
+    }
+    let display = if rulers.visible {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    for mut node in nodes.iter_mut() {
+        node.display = display;
+    }
+}
+
+/// Rebuilds the ticks, labels and guideline markers when the view changes
+#[allow(clippy::too_many_arguments)]
+fn update_ruler_marks(
+    mut commands: Commands,
+    rulers: Res<Rulers>,
+    view: Res<RulerView>,
+    guidelines: Res<CanvasGuidelines>,
+    theme: Res<CurrentTheme>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+    bars: Query<(Entity, &RulerEdge)>,
+    marks: Query<Entity, With<RulerMark>>,
+) {
+    let changed = view.is_changed() || guidelines.is_changed() || theme.is_changed();
+    if !rulers.visible || !(changed || rulers.is_changed()) || view.window == Vec2::ZERO {
+        return;
+    }
+    for entity in marks.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let size = theme.theme().ruler_size();
+    let tick_color = BackgroundColor(theme.theme().ruler_tick_color());
+    let guideline_color = BackgroundColor(theme.theme().guideline_color());
+    let text_font = TextFont {
+        font: asset_server.load_font_with_fallback(theme.theme().mono_font_path(), &embedded_fonts),
+        font_size: theme.theme().ruler_font_size(),
+        ..default()
+    };
+    let text_color = TextColor(theme.theme().ruler_tick_color());
+    let start = view.to_design(Vec2::splat(size));
+    let end = view.to_design(view.window);
+
+    for (bar, edge) in bars.iter() {
+        commands.entity(bar).with_children(|parent| {
+            let (ticks, px_per_unit, markers) = match edge {
+                RulerEdge::Top => (
+                    ruler_ticks(start.x, end.x, 1.0 / view.units_per_px.x.abs()),
+                    Vec2::X,
+                    &guidelines.vertical,
+                ),
+                RulerEdge::Left => (
+                    ruler_ticks(start.y, end.y, 1.0 / view.units_per_px.y.abs()),
+                    Vec2::Y,
+                    &guidelines.horizontal,
+                ),
+            };
+            // Position along the ruler, in pixels from its start
+            let along = |design: f32| {
+                let screen = view.to_screen(Vec2::splat(design)).dot(px_per_unit);
+                screen - size
+            };
+            for (position, labelled) in ticks {
+                let offset = along(position);
+                let length = if labelled { size * 0.5 } else { size * 0.25 };
+                let tick = match edge {
+                    RulerEdge::Top => Node {
+                        left: Val::Px(offset),
+                        bottom: Val::Px(0.0),
+                        width: Val::Px(1.0),
+                        height: Val::Px(length),
+                        ..default()
+                    },
+                    RulerEdge::Left => Node {
+                        top: Val::Px(offset),
+                        right: Val::Px(0.0),
+                        width: Val::Px(length),
+                        height: Val::Px(1.0),
+                        ..default()
+                    },
+                };
+                parent.spawn((absolute(tick), tick_color, RulerMark));
+                if !labelled {
+                    continue;
+                }
+                let value = format!("{}", position.round() as i64);
+                let (label, text) = match edge {
+                    RulerEdge::Top => (
+                        Node {
+                            left: Val::Px(offset + 2.0),
+                            top: Val::Px(0.0),
+                            ..default()
+                        },
+                        value,
+                    ),
+                    // Digits stack down the narrow left ruler
+                    RulerEdge::Left => (
+                        Node {
+                            left: Val::Px(1.0),
+                            top: Val::Px(offset + 2.0),
+                            ..default()
+                        },
+                        value
+                            .chars()
+                            .map(String::from)
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    ),
+                };
+                parent.spawn((
+                    absolute(label),
+                    Text::new(text),
+                    text_font.clone(),
+                    text_color,
+                    RulerMark,
+                ));
+            }
+            for &position in markers {
+                let offset = along(position) - GRAB_DISTANCE_PX;
+                let marker = match edge {
+                    RulerEdge::Top => Node {
+                        left: Val::Px(offset),
+                        bottom: Val::Px(0.0),
+                        width: Val::Px(GRAB_DISTANCE_PX * 2.0),
+                        height: Val::Px(size * 0.5),
+                        ..default()
+                    },
+                    RulerEdge::Left => Node {
+                        top: Val::Px(offset),
+                        right: Val::Px(0.0),
+                        width: Val::Px(size * 0.5),
+                        height: Val::Px(GRAB_DISTANCE_PX * 2.0),
+                        ..default()
+                    },
+                };
+                parent.spawn((absolute(marker), guideline_color, RulerMark));
+            }
+        });
+    }
+}
+
+fn absolute(node: Node) -> Node {
+    Node {
+        position_type: PositionType::Absolute,
+        ..node
+    }
+}
+
+/// Redraws the guidelines when they change
+fn render_guidelines(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    guidelines: Res<CanvasGuidelines>,
+    existing: Query<Entity, With<GuidelineLine>>,
+    camera_scale: Res<CameraResponsiveScale>,
+    theme: Res<CurrentTheme>,
+) {
+    if !guidelines.is_changed() && !camera_scale.is_changed() && !theme.is_changed() {
+        return;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let material = materials.add(ColorMaterial::from_color(theme.theme().guideline_color()));
+    let line_width = camera_scale.adjusted_line_width();
+    let horizontal = guidelines.horizontal.iter().map(|&y| {
+        (
+            Vec2::new(-GUIDELINE_EXTENT, y),
+            Vec2::new(GUIDELINE_EXTENT, y),
+        )
+    });
+    let vertical = guidelines.vertical.iter().map(|&x| {
+        (
+            Vec2::new(x, -GUIDELINE_EXTENT),
+            Vec2::new(x, GUIDELINE_EXTENT),
+        )
+    });
+    for (start, end) in horizontal.chain(vertical) {
+        commands.spawn((
+            GuidelineLine,
+            Mesh2d(meshes.add(create_line_mesh(start, end, line_width))),
+            MeshMaterial2d(material.clone()),
+            Transform::from_translation(((start + end) * 0.5).extend(GUIDELINE_Z)),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ruler_step_keeps_labels_apart() {
+        assert_eq!(ruler_step(1.0), (100.0, 5));
+        assert_eq!(ruler_step(0.1), (1000.0, 5));
+        assert_eq!(ruler_step(0.5), (200.0, 4));
+        assert_eq!(ruler_step(100.0), (1.0, 1));
+
+        let ticks = ruler_ticks(-30.0, 210.0, 1.0);
+        assert_eq!(ticks.first(), Some(&(-20.0, false)));
+        assert_eq!(ticks.last(), Some(&(200.0, true)));
+        let labelled: Vec<f32> = ticks
+            .iter()
+            .filter(|(_, labelled)| *labelled)
+            .map(|(position, _)| *position)
+            .collect();
+        assert_eq!(labelled, vec![0.0, 100.0, 200.0]);
+        assert_eq!(ruler_ticks(210.0, -30.0, 1.0), ticks);
+    }
+}
@@ -0,0 +1,283 @@
+//! Status bar
+//!
+//! A line along the bottom of the window describing what's under the
+//! pointer: the glyph of the sort it's over with the glyph's first code
+//! point, the point it's hovering (contour and point index, in font units),
+//! then the current tool, the zoom and the pointer's design coordinates.
+//! It's shown and hidden with the rulers (Cmd/Ctrl+R).
+
+use crate::core::state::AppState;
+use crate::editing::selection::components::GlyphPointReference;
+use crate::editing::sort::manager::SortPointEntity;
+use crate::editing::sort::Sort;
+use crate::io::pointer::PointerInfo;
+use crate::rendering::cameras::DesignCamera;
+use crate::ui::edit_mode_toolbar::{CurrentTool, ToolConfig};
+use crate::ui::metrics_overlay::VerticalMetric;
+use crate::ui::rulers::Rulers;
+use crate::ui::themes::CurrentTheme;
+use crate::utils::embedded_assets::{AssetServerFontExt, EmbeddedFonts};
+use bevy::prelude::*;
+
+/// How close, in screen pixels, the pointer has to be to hover a point
+const HOVER_DISTANCE_PX: f32 = 8.0;
+
+/// Gap between the parts of the status line
+const SEPARATOR: &str = "  ·  ";
+
+/// A point under the pointer
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoveredPoint {
+    pub contour_index: usize,
+    pub point_index: usize,
+    /// Position in the glyph, in font units
+    pub position: (f64, f64),
+}
+
+/// What the status bar describes
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusLine {
+    /// Glyph of the sort under the pointer and its first code point
+    pub glyph: Option<(String, Option<char>)>,
+    pub point: Option<HoveredPoint>,
+    pub tool: Option<String>,
+    pub zoom_percent: f32,
+    /// The pointer in design coordinates
+    pub pointer: Vec2,
+}
+
+impl StatusLine {
+    pub fn text(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some((glyph_name, codepoint)) = &self.glyph {
+            parts.push(match codepoint {
+                Some(ch) => format!("{glyph_name} U+{:04X}", *ch as u32),
+                None => glyph_name.clone(),
+            });
+        }
+        if let Some(point) = &self.point {
+            parts.push(format!(
+                "point {}:{} ({}, {})",
+                point.contour_index,
+                point.point_index,
+                point.position.0.round(),
+                point.position.1.round()
+            ));
+        }
+        parts.extend(self.tool.clone());
+        parts.push(format!("{:.0}%", self.zoom_percent));
+        parts.push(format!(
+            "x {}  y {}",
+            self.pointer.x.round(),
+            self.pointer.y.round()
+        ));
+        parts.join(SEPARATOR)
+    }
+}
+
+/// The status line text
+#[derive(Component)]
+struct StatusBarText;
+
+/// The status bar's container, hidden with the rulers
+#[derive(Component)]
+struct StatusBar;
+
+pub struct StatusBarPlugin;
+
+impl Plugin for StatusBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_status_bar)
+            .add_systems(Update, (update_status_bar_visibility, update_status_bar));
+    }
+}
+
+fn spawn_status_bar(
+    mut commands: Commands,
+    theme: Res<CurrentTheme>,
+    asset_server: Res<AssetServer>,
+    embedded_fonts: Res<EmbeddedFonts>,
+) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                right: Val::Px(0.0),
+                bottom: Val::Px(theme.theme().widget_margin()),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ZIndex(-1),
+            StatusBar,
+            Name::new("StatusBar"),
+        ))
+        .with_child((
+            Text::new(""),
+            TextFont {
+                font: asset_server
+                    .load_font_with_fallback(theme.theme().mono_font_path(), &embedded_fonts),
+                font_size: theme.theme().ruler_font_size(),
+                ..default()
+            },
+            TextColor(theme.theme().ruler_tick_color()),
+            StatusBarText,
+        ));
+}
+
+fn update_status_bar_visibility(
+    rulers: Res<Rulers>,
+    mut status_bar: Query<&mut Visibility, With<StatusBar>>,
+) {
+    if !rulers.is_changed() {
+        return;
+    }
+    for mut visibility in status_bar.iter_mut() {
+        *visibility = if rulers.visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// The sort whose em box, from descender to ascender, holds `position`;
+/// sorts are given by origin, advance width and whatever identifies them
+pub(crate) fn sort_under<T>(
+    position: Vec2,
+    sorts: impl IntoIterator<Item = (Vec2, f32, T)>,
+    descender: f32,
+    ascender: f32,
+) -> Option<T> {
+    sorts.into_iter().find_map(|(origin, advance, sort)| {
+        let local = position - origin;
+        let inside =
+            (0.0..=advance).contains(&local.x) && (descender..=ascender).contains(&local.y);
+        inside.then_some(sort)
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_status_bar(
+    rulers: Res<Rulers>,
+    pointer: Res<PointerInfo>,
+    current_tool: Res<CurrentTool>,
+    app_state: Option<Res<AppState>>,
+    camera_query: Query<&Projection, With<DesignCamera>>,
+    sorts: Query<(&Transform, &Sort)>,
+    points: Query<(&Transform, &GlyphPointReference), With<SortPointEntity>>,
+    mut status_text: Query<&mut Text, With<StatusBarText>>,
+) {
+    if !rulers.visible {
+        return;
+    }
+    let zoom_scale = match camera_query.single() {
+        Ok(Projection::Orthographic(ortho)) => ortho.scale,
+        _ => 1.0,
+    };
+
+    let mut glyph = None;
+    let mut point = None;
+    if let Some(state) = app_state.as_deref() {
+        let font = &state.workspace.font;
+        let info = &state.workspace.info;
+        let placed = sorts.iter().filter_map(|(transform, sort)| {
+            let advance = font.editing().get_glyph(&sort.glyph_name)?.advance_width as f32;
+            Some((
+                transform.translation.truncate(),
+                advance,
+                sort.glyph_name.as_str(),
+            ))
+        });
+        let descender = VerticalMetric::Descender.value(info) as f32;
+        let ascender = VerticalMetric::Ascender.value(info) as f32;
+        glyph = sort_under(pointer.world, placed, descender, ascender).map(|glyph_name| {
+            let codepoint = font
+                .get_glyph(glyph_name)
+                .and_then(|glyph| glyph.unicode_values.first().copied());
+            (glyph_name.to_string(), codepoint)
+        });
+
+        let reach = HOVER_DISTANCE_PX * zoom_scale;
+        point = points
+            .iter()
+            .map(|(transform, reference)| {
+                let distance = transform.translation.truncate().distance(pointer.world);
+                (distance, reference)
+            })
+            .filter(|(distance, _)| *distance <= reach)
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .and_then(|(_, reference)| {
+                let outline = font
+                    .editing()
+                    .get_glyph(&reference.glyph_name)?
+                    .outline
+                    .as_ref()?;
+                let data = outline
+                    .contours
+                    .get(reference.contour_index)?
+                    .points
+                    .get(reference.point_index)?;
+                Some(HoveredPoint {
+                    contour_index: reference.contour_index,
+                    point_index: reference.point_index,
+                    position: (data.x, data.y),
+                })
+            });
+    }
+
+    let tool = current_tool.get_current().map(|id| {
+        let name = ToolConfig::get_tool(id).map_or(id, |tool| tool.name);
+        crate::i18n::tool_name(id, name)
+    });
+    let line = StatusLine {
+        glyph,
+        point,
+        tool,
+        zoom_percent: 100.0 / zoom_scale,
+        pointer: pointer.world,
+    };
+    let text = line.text();
+    for mut status in status_text.iter_mut() {
+        if status.0 != text {
+            status.0 = text.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_line_text() {
+        let line = StatusLine {
+            glyph: Some(("A".to_string(), Some('A'))),
+            point: Some(HoveredPoint {
+                contour_index: 0,
+                point_index: 3,
+                position: (120.4, 700.0),
+            }),
+            tool: Some("Select".to_string()),
+            zoom_percent: 150.0,
+            pointer: Vec2::new(121.2, 698.7),
+        };
+        assert_eq!(
+            line.text(),
+            "A U+0041  ·  point 0:3 (120, 700)  ·  Select  ·  150%  ·  x 121  y 699"
+        );
+
+        let sorts = [
+            (Vec2::ZERO, 500.0, "a"),
+            (Vec2::new(500.0, 0.0), 600.0, "b"),
+        ];
+        assert_eq!(
+            sort_under(Vec2::new(620.0, 300.0), sorts, -200.0, 800.0),
+            Some("b")
+        );
+        assert_eq!(
+            sort_under(Vec2::new(620.0, 900.0), sorts, -200.0, 800.0),
+            None
+        );
+    }
+}
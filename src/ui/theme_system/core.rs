@@ -31,6 +31,7 @@ impl ThemeRegistry {
         themes.insert("strawberry".to_string(), Box::new(StrawberryTheme));
         themes.insert("campfire".to_string(), Box::new(CampfireTheme));
         themes.insert("forest".to_string(), Box::new(ForestTheme));
+        themes.insert("high-contrast".to_string(), Box::new(HighContrastTheme));
 
         Self { themes }
     }
@@ -57,6 +58,7 @@ impl ThemeRegistry {
             "strawberry" => Some(Box::new(StrawberryTheme)),
             "campfire" => Some(Box::new(CampfireTheme)),
             "forest" => Some(Box::new(ForestTheme)),
+            "high-contrast" => Some(Box::new(HighContrastTheme)),
             _ => None,
         }
     }
@@ -340,6 +342,22 @@ pub trait BezyTheme: Send + Sync + 'static {
     /// Handle lines
     fn handle_line_color(&self) -> Color;
 
+    /// Outlines of locked contours, which are drawn without their points
+    fn locked_outline_color(&self) -> Color {
+        self.sort_inactive_outline_color()
+    }
+
+    /// Glyphs ghosted behind the active sort, one color after another
+    fn glyph_ghost_colors(&self) -> [Color; 4] {
+        [
+            self.action_color(),
+            self.selected_color(),
+            self.active_color(),
+            self.special_color(),
+        ]
+        .map(|color| color.with_alpha(0.3))
+    }
+
     // =================================================================
     // INFO COLORS - SEMANTIC COLORS
     // =================================================================
@@ -391,6 +409,13 @@ pub trait BezyTheme: Send + Sync + 'static {
     fn text_cursor_radius(&self) -> f32 {
         12.0
     }
+    fn text_cursor_color(&self) -> Color {
+        self.hover_orange_color()
+    }
+    /// Highlight behind selected text in a text buffer
+    fn text_selection_color(&self) -> Color {
+        self.selected_color().with_alpha(0.3)
+    }
 
     // =================================================================
     // EDITING TOOLS
@@ -468,6 +493,111 @@ pub trait BezyTheme: Send + Sync + 'static {
     /// Filled glyph color for inactive sorts
     fn filled_glyph_color(&self) -> Color;
 
+    /// Text frame outline and resize handles
+    fn text_frame_color(&self) -> Color {
+        self.sort_inactive_metrics_color()
+    }
+    fn text_frame_handle_size(&self) -> f32 {
+        8.0
+    }
+
+    /// Uncommitted input method text shown at the cursor
+    fn ime_preedit_color(&self) -> Color {
+        self.sort_active_metrics_color()
+    }
+
+    /// Selected sort and sidebearing label in spacing mode
+    fn spacing_mode_color(&self) -> Color {
+        self.sort_active_metrics_color()
+    }
+
+    /// Cluster edges and labels of the substitution overlay
+    fn substitution_overlay_color(&self) -> Color {
+        self.sort_active_metrics_color()
+    }
+
+    /// Alignment lines shown while sorts snap to each other
+    fn smart_guide_color(&self) -> Color {
+        self.action_color()
+    }
+
+    /// Canvas rulers, their ticks and labels, and the guidelines pulled
+    /// out of them
+    fn ruler_size(&self) -> f32 {
+        20.0
+    }
+    fn ruler_font_size(&self) -> f32 {
+        10.0
+    }
+    fn ruler_background_color(&self) -> Color {
+        self.widget_background_color()
+    }
+    fn ruler_tick_color(&self) -> Color {
+        self.ui_text_secondary()
+    }
+    fn guideline_color(&self) -> Color {
+        self.selected_color()
+    }
+
+    /// Measurement grid lines, faintest to strongest, and the metrics
+    /// heights drawn over them
+    fn measurement_grid_colors(&self) -> [Color; 3] {
+        let color = self.ui_text_secondary();
+        [
+            color.with_alpha(0.08),
+            color.with_alpha(0.16),
+            color.with_alpha(0.32),
+        ]
+    }
+    fn measurement_grid_metrics_color(&self) -> Color {
+        self.metrics_guide_color()
+    }
+
+    /// Metrics heights drawn across all text, and the one being dragged
+    fn metrics_overlay_color(&self) -> Color {
+        self.metrics_guide_color()
+    }
+    fn metrics_overlay_drag_color(&self) -> Color {
+        self.action_color()
+    }
+
+    /// Alignment zone bands, and the rings around extremes inside a zone
+    /// but off its edges
+    fn blue_zone_color(&self) -> Color {
+        self.selected_color().with_alpha(0.12)
+    }
+    fn blue_zone_stray_extreme_color(&self) -> Color {
+        self.error_color()
+    }
+
+    /// CJK design frames inside each sort's em box
+    fn design_frame_color(&self) -> Color {
+        self.metrics_guide_color().with_alpha(0.4)
+    }
+
+    /// Template outlines shown in glyphs that aren't drawn yet
+    fn template_glyph_color(&self) -> Color {
+        self.ui_text_secondary().with_alpha(0.35)
+    }
+
+    /// The small loops open corners make
+    fn open_corner_color(&self) -> Color {
+        self.selected_color().with_alpha(0.2)
+    }
+
+    /// Point numbers, and the arrows at contour start points
+    fn point_number_color(&self) -> Color {
+        self.ui_text_secondary()
+    }
+    fn start_point_color(&self) -> Color {
+        self.action_color()
+    }
+
+    /// The points converting a glyph's curves would add
+    fn curve_preview_color(&self) -> Color {
+        self.special_color()
+    }
+
     /// Sort spacing
     fn sort_horizontal_padding(&self) -> f32 {
         256.0
@@ -553,6 +683,8 @@ pub trait BezyTheme: Send + Sync + 'static {
 pub struct CurrentTheme {
     pub variant: ThemeVariant,
     theme: Box<dyn BezyTheme>,
+    /// Draw points in the Okabe-Ito palette instead of the theme's colors
+    pub colorblind_safe: bool,
 }
 
 impl CurrentTheme {
@@ -568,7 +700,11 @@ impl CurrentTheme {
             Box::new(DarkTheme)
         });
 
-        Self { variant, theme }
+        Self {
+            variant,
+            theme,
+            colorblind_safe: false,
+        }
     }
 
     /// Get the current theme implementation
@@ -657,4 +793,48 @@ impl CurrentTheme {
     pub fn special_color(&self) -> Color {
         self.theme().special_color()
     }
+
+    // =================================================================
+    // POINT COLORS - COLORBLIND-SAFE ALTERNATIVES
+    // =================================================================
+    //
+    // With `colorblind_safe` set, points use the Okabe-Ito palette: blue
+    // on-curve, orange off-curve and yellow when selected stay apart for
+    // every common kind of color blindness.
+
+    /// Primary and secondary colors of on-curve points
+    pub fn on_curve_point_colors(&self) -> (Color, Color) {
+        if self.colorblind_safe {
+            (Color::srgb(0.34, 0.71, 0.91), Color::srgb(0.0, 0.45, 0.70))
+        } else {
+            (
+                self.theme().on_curve_primary_color(),
+                self.theme().on_curve_secondary_color(),
+            )
+        }
+    }
+
+    /// Primary and secondary colors of off-curve points
+    pub fn off_curve_point_colors(&self) -> (Color, Color) {
+        if self.colorblind_safe {
+            (Color::srgb(0.90, 0.62, 0.0), Color::srgb(0.84, 0.37, 0.0))
+        } else {
+            (
+                self.theme().off_curve_primary_color(),
+                self.theme().off_curve_secondary_color(),
+            )
+        }
+    }
+
+    /// Primary and secondary colors of selected points
+    pub fn selected_point_colors(&self) -> (Color, Color) {
+        if self.colorblind_safe {
+            (Color::srgb(0.94, 0.89, 0.26), Color::srgb(0.47, 0.44, 0.13))
+        } else {
+            (
+                self.theme().selected_primary_color(),
+                self.theme().selected_secondary_color(),
+            )
+        }
+    }
 }
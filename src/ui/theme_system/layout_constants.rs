@@ -65,6 +65,14 @@ pub fn toolbar_submenu_top_position() -> f32 {
     TOOLBAR_CONTAINER_MARGIN + 64.0 + TOOLBAR_GRID_SPACING * 2.0
 }
 
+/// Height of the options bar of the current tool
+pub const TOOL_OPTIONS_BAR_HEIGHT: f32 = 32.0;
+
+/// Helper function to calculate the tool options bar position, below the submenu row
+pub fn tool_options_top_position() -> f32 {
+    toolbar_submenu_top_position() + 64.0 + TOOLBAR_GRID_SPACING * 2.0
+}
+
 // =================================================================
 // WIDGET AND PANE CONSTANTS
 // =================================================================
@@ -143,5 +151,6 @@ pub fn create_widget_style<T: Component + Default>(
         WidgetBorderRadius,
         marker,
         Name::new(name.to_string()),
+        crate::ui::localization::MirrorForRtl,
     )
 }
@@ -21,8 +21,8 @@ pub use layout_constants::{
     GRID_SIZE_CHANGE_THRESHOLD, INITIAL_ZOOM_SCALE, LINE_LEADING, MAX_ALLOWED_ZOOM_SCALE,
     MAX_SQUARES_PER_FRAME, MIN_ALLOWED_ZOOM_SCALE, MIN_VISIBILITY_ZOOM, SELECTION_MARGIN,
     SELECTION_Z_DEPTH_OFFSET, TOOLBAR_BORDER_RADIUS, TOOLBAR_BORDER_WIDTH,
-    TOOLBAR_CONTAINER_MARGIN, TOOLBAR_GRID_SPACING, TOOLBAR_PADDING,
+    TOOLBAR_CONTAINER_MARGIN, TOOLBAR_GRID_SPACING, TOOLBAR_PADDING, TOOL_OPTIONS_BAR_HEIGHT,
     VISIBLE_AREA_COVERAGE_MULTIPLIER, WIDGET_ROW_LEADING, WIDGET_TEXT_FONT_SIZE,
     WIDGET_TITLE_FONT_SIZE, ToolbarBorderRadius, UiBorderRadius, WidgetBorderRadius,
-    create_widget_style, toolbar_submenu_top_position,
+    create_widget_style, tool_options_top_position, toolbar_submenu_top_position,
 };
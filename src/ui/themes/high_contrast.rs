@@ -0,0 +1,76 @@
+use crate::ui::theme_system::BezyTheme;
+use bevy::prelude::*;
+
+/// Pure black and white with saturated accents, thicker lines and bigger
+/// points, for low vision and bright rooms
+pub struct HighContrastTheme;
+
+impl BezyTheme for HighContrastTheme {
+    fn name(&self) -> &'static str { "High Contrast" }
+    fn ui_text_primary(&self) -> Color { Color::srgb(1.0, 1.0, 1.0) }
+    fn ui_text_secondary(&self) -> Color { Color::srgb(1.0, 1.0, 1.0) }
+    fn ui_text_tertiary(&self) -> Color { Color::srgb(0.9, 0.9, 0.9) }
+    fn ui_text_quaternary(&self) -> Color { Color::srgb(0.8, 0.8, 0.8) }
+    fn background_color(&self) -> Color { Color::srgb(0.0, 0.0, 0.0) }
+    fn widget_background_color(&self) -> Color { Color::srgba(0.0, 0.0, 0.0, 1.0) }
+    fn widget_border_color(&self) -> Color { Color::srgb(1.0, 1.0, 1.0) }
+    fn widget_border_width(&self) -> f32 { 3.0 }
+    fn toolbar_border_width(&self) -> f32 { 3.0 }
+    fn button_regular(&self) -> Color { Color::srgb(0.0, 0.0, 0.0) }
+    fn button_hovered(&self) -> Color { Color::srgb(0.2, 0.2, 0.2) }
+    fn button_pressed(&self) -> Color { Color::srgb(1.0, 1.0, 0.0) }
+    fn button_regular_outline(&self) -> Color { Color::srgb(1.0, 1.0, 1.0) }
+    fn button_hovered_outline(&self) -> Color { Color::srgb(1.0, 1.0, 0.0) }
+    fn button_pressed_outline(&self) -> Color { Color::srgb(1.0, 1.0, 0.0) }
+    fn button_regular_icon(&self) -> Color { Color::srgb(1.0, 1.0, 1.0) }
+    fn button_hovered_icon(&self) -> Color { Color::srgb(1.0, 1.0, 0.0) }
+    fn button_pressed_icon(&self) -> Color { Color::srgb(0.0, 0.0, 0.0) }
+    fn focus_background_color(&self) -> Color { Color::srgb(1.0, 1.0, 0.0) }
+    fn on_curve_point_radius(&self) -> f32 { 4.0 }
+    fn off_curve_point_radius(&self) -> f32 { 4.0 }
+    fn on_curve_primary_color(&self) -> Color { Color::srgb(0.0, 1.0, 1.0) }
+    fn on_curve_secondary_color(&self) -> Color { Color::srgb(0.0, 0.4, 0.4) }
+    fn off_curve_primary_color(&self) -> Color { Color::srgb(1.0, 0.0, 1.0) }
+    fn off_curve_secondary_color(&self) -> Color { Color::srgb(0.4, 0.0, 0.4) }
+    fn path_line_color(&self) -> Color { Color::srgba(1.0, 1.0, 1.0, 1.0) }
+    fn path_line_width(&self) -> f32 { 2.0 }
+    fn path_stroke_color(&self) -> Color { Color::srgb(1.0, 1.0, 1.0) }
+    fn point_stroke_color(&self) -> Color { Color::srgba(0.0, 0.0, 0.0, 1.0) }
+    fn handle_line_color(&self) -> Color { Color::srgba(1.0, 1.0, 1.0, 0.8) }
+    fn error_color(&self) -> Color { Color::srgb(1.0, 0.2, 0.2) }
+    fn action_color(&self) -> Color { Color::srgb(1.0, 1.0, 0.0) }
+    fn selected_color(&self) -> Color { Color::srgb(1.0, 1.0, 0.0) }
+    fn active_color(&self) -> Color { Color::srgb(0.0, 1.0, 0.0) }
+    fn helper_color(&self) -> Color { Color::srgb(0.0, 1.0, 1.0) }
+    fn special_color(&self) -> Color { Color::srgb(1.0, 0.0, 1.0) }
+    fn selected_primary_color(&self) -> Color { Color::srgba(1.0, 1.0, 0.0, 1.0) }
+    fn selected_secondary_color(&self) -> Color { Color::srgba(0.4, 0.4, 0.0, 1.0) }
+    fn hover_point_color(&self) -> Color { Color::srgba(1.0, 1.0, 0.0, 1.0) }
+    fn hover_orange_color(&self) -> Color { Color::srgb(1.0, 0.6, 0.0) }
+    fn knife_line_color(&self) -> Color { Color::srgba(1.0, 0.2, 0.2, 1.0) }
+    fn knife_intersection_color(&self) -> Color { Color::srgba(1.0, 1.0, 0.0, 1.0) }
+    fn knife_start_point_color(&self) -> Color { Color::srgba(0.0, 1.0, 0.0, 1.0) }
+    fn pen_point_color(&self) -> Color { Color::srgb(1.0, 1.0, 0.0) }
+    fn pen_start_point_color(&self) -> Color { Color::srgb(0.0, 1.0, 0.0) }
+    fn pen_line_color(&self) -> Color { Color::srgba(1.0, 1.0, 1.0, 1.0) }
+    fn hyper_point_color(&self) -> Color { Color::srgba(0.0, 1.0, 0.0, 1.0) }
+    fn hyper_line_color(&self) -> Color { Color::srgba(0.0, 1.0, 1.0, 1.0) }
+    fn hyper_close_indicator_color(&self) -> Color { Color::srgba(1.0, 1.0, 0.0, 1.0) }
+    fn shape_preview_color(&self) -> Color { Color::srgba(1.0, 1.0, 1.0, 0.8) }
+    fn metaball_gizmo_color(&self) -> Color { Color::srgba(1.0, 0.0, 1.0, 0.8) }
+    fn metaball_outline_color(&self) -> Color { Color::srgba(1.0, 1.0, 1.0, 1.0) }
+    fn metaball_selected_color(&self) -> Color { Color::srgba(1.0, 1.0, 0.0, 1.0) }
+    fn filled_glyph_color(&self) -> Color { Color::srgb(1.0, 1.0, 1.0) }
+    fn checkerboard_enabled_by_default(&self) -> bool { false }
+    fn checkerboard_color(&self) -> Color { Color::srgba(0.15, 0.15, 0.15, 0.5) }
+    fn checkerboard_color_1(&self) -> Color { Color::srgb(0.0, 0.0, 0.0) }
+    fn checkerboard_color_2(&self) -> Color { Color::srgb(0.12, 0.12, 0.12) }
+    fn metrics_guide_color(&self) -> Color { Color::srgba(0.0, 1.0, 0.0, 0.8) }
+    fn sort_active_metrics_color(&self) -> Color { self.active_color() }
+    fn sort_inactive_metrics_color(&self) -> Color { Color::srgba(0.7, 0.7, 0.7, 0.8) }
+    fn sort_active_outline_color(&self) -> Color { Color::srgb(1.0, 1.0, 0.0) }
+    fn sort_inactive_outline_color(&self) -> Color { Color::srgb(1.0, 1.0, 1.0) }
+    fn widget_border_radius(&self) -> f32 { 0.0 }
+    fn toolbar_border_radius(&self) -> f32 { 0.0 }
+    fn ui_border_radius(&self) -> f32 { 0.0 }
+}
@@ -8,12 +8,14 @@ pub mod light;
 pub mod strawberry;
 pub mod campfire;
 pub mod forest;
+pub mod high_contrast;
 
 pub use dark::DarkTheme;
 pub use light::LightTheme;
 pub use strawberry::StrawberryTheme;
 pub use campfire::CampfireTheme;
 pub use forest::ForestTheme;
+pub use high_contrast::HighContrastTheme;
 
 pub use crate::ui::theme_system::{
     get_theme_registry, BezyTheme, CurrentTheme, ThemeRegistry, ThemeVariant,
@@ -1 +1,4 @@
 pub mod embedded_assets;
+pub mod system_clipboard;
+#[cfg(target_arch = "wasm32")]
+pub mod web_file_access;
@@ -0,0 +1,103 @@
+//! System clipboard access
+//!
+//! Bezy talks to the OS clipboard through the platform's own command line
+//! tools instead of linking a windowing clipboard library. This keeps the
+//! dependency list short and works the same from the GUI and the TUI.
+//!
+//! Running a tool takes far longer than a frame, so the clipboard is only
+//! read and written on the async compute pool. Both hand back a task to
+//! poll each frame; a write's task can be detached when nothing reads the
+//! clipboard after it.
+
+use anyhow::{anyhow, Result};
+use bevy::log::warn;
+use bevy::tasks::{AsyncComputeTaskPool, Task};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Candidate (program, args) pairs for writing to the clipboard
+fn copy_commands() -> &'static [(&'static str, &'static [&'static str])] {
+    if cfg!(target_os = "macos") {
+        &[("pbcopy", &[])]
+    } else if cfg!(target_os = "windows") {
+        &[("clip", &[])]
+    } else {
+        &[
+            ("wl-copy", &[]),
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+        ]
+    }
+}
+
+/// Candidate (program, args) pairs for reading from the clipboard
+fn paste_commands() -> &'static [(&'static str, &'static [&'static str])] {
+    if cfg!(target_os = "macos") {
+        &[("pbpaste", &[])]
+    } else if cfg!(target_os = "windows") {
+        &[("powershell", &["-NoProfile", "-Command", "Get-Clipboard"])]
+    } else {
+        &[
+            ("wl-paste", &["--no-newline"]),
+            ("xclip", &["-selection", "clipboard", "-o"]),
+            ("xsel", &["--clipboard", "--output"]),
+        ]
+    }
+}
+
+/// Write text to the system clipboard in the background, logging failures
+pub fn write_text_in_background(text: String) -> Task<()> {
+    AsyncComputeTaskPool::get().spawn(async move {
+        if let Err(e) = write_text(&text) {
+            warn!("Could not write to the system clipboard: {}", e);
+        }
+    })
+}
+
+/// Read text from the system clipboard in the background
+pub fn read_text_in_background() -> Task<Result<String>> {
+    AsyncComputeTaskPool::get().spawn(async { read_text() })
+}
+
+/// Write text to the system clipboard, waiting for the tool
+fn write_text(text: &str) -> Result<()> {
+    for (program, args) in copy_commands() {
+        let Ok(mut child) = Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+
+        if let Some(stdin) = child.stdin.as_mut() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+
+    Err(anyhow!("No working clipboard command found"))
+}
+
+/// Read text from the system clipboard, waiting for the tool
+fn read_text() -> Result<String> {
+    for (program, args) in paste_commands() {
+        let Ok(output) = Command::new(program)
+            .args(*args)
+            .stderr(Stdio::null())
+            .output()
+        else {
+            continue;
+        };
+
+        if output.status.success() {
+            return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+        }
+    }
+
+    Err(anyhow!("No working clipboard command found"))
+}
@@ -0,0 +1,134 @@
+//! Browser file access for the web build
+//!
+//! A page has no file system, so the web build opens UFOs through the
+//! browser's directory picker: Cmd/Ctrl+O opens it, the picked folder's
+//! files are read into memory, and the UFO among them is loaded with
+//! `data::ufo::load_ufo_from_files`. Fonts opened this way have no path,
+//! so they can be edited but not saved back to disk.
+
+use crate::core::state::{AppState, FontData, FontInfo};
+use crate::data::ufo::load_ufo_from_files;
+use crate::io::shortcuts::Shortcut;
+use bevy::prelude::*;
+use std::sync::{Arc, Mutex};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+/// Files of the UFO folder picked in the browser, path and contents
+type PickedFiles = Vec<(String, Vec<u8>)>;
+
+/// Files read by the browser, waiting to be loaded on the next frame
+#[derive(Resource, Default, Clone)]
+struct PickedFolder(Arc<Mutex<Option<PickedFiles>>>);
+
+pub struct WebFileAccessPlugin;
+
+impl Plugin for WebFileAccessPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PickedFolder>()
+            .add_systems(Update, (open_picker_on_shortcut, load_picked_ufo));
+    }
+}
+
+/// Opens the directory picker on Cmd/Ctrl+O
+fn open_picker_on_shortcut(keyboard: Res<ButtonInput<KeyCode>>, picked: Res<PickedFolder>) {
+    if !Shortcut::OpenFolder.just_pressed(&keyboard) {
+        return;
+    }
+    if let Err(e) = open_directory_picker(picked.clone()) {
+        warn!("Could not open the folder picker: {:?}", e);
+    }
+}
+
+/// Loads the UFO once the browser has read the picked folder
+fn load_picked_ufo(mut commands: Commands, picked: Res<PickedFolder>) {
+    let Some(files) = picked.0.lock().ok().and_then(|mut slot| slot.take()) else {
+        return;
+    };
+    let Some((name, files)) = ufo_files(files) else {
+        warn!("The picked folder doesn't contain a UFO");
+        return;
+    };
+    match load_ufo_from_files(&files) {
+        Ok(font) => {
+            let mut app_state = AppState::default();
+            app_state.workspace.font = FontData::from_norad_font(&font, None);
+            app_state.workspace.info = FontInfo::from_norad_font(&font);
+            info!(
+                "Loaded {} with {} glyphs",
+                name,
+                app_state.workspace.font.glyphs.len()
+            );
+            commands.insert_resource(app_state);
+        }
+        Err(e) => error!("Failed to load {}: {:#}", name, e),
+    }
+}
+
+/// Ask the browser for a folder and read its files into `picked`
+fn open_directory_picker(picked: PickedFolder) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    let input: web_sys::HtmlInputElement = document.create_element("input")?.dyn_into()?;
+    input.set_type("file");
+    input.set_webkitdirectory(true);
+
+    let picker = input.clone();
+    let on_change = Closure::once(move || {
+        let Some(files) = picker.files() else {
+            return;
+        };
+        wasm_bindgen_futures::spawn_local(async move {
+            match read_files(&files).await {
+                Ok(read) => {
+                    if let Ok(mut slot) = picked.0.lock() {
+                        *slot = Some(read);
+                    }
+                }
+                Err(e) => error!("Failed to read the picked folder: {:?}", e),
+            }
+        });
+    });
+    input.set_onchange(Some(on_change.as_ref().unchecked_ref()));
+    on_change.forget();
+    input.click();
+    Ok(())
+}
+
+/// Contents of every file in a picked folder, by path relative to it
+async fn read_files(files: &web_sys::FileList) -> Result<PickedFiles, JsValue> {
+    let mut read = Vec::new();
+    for index in 0..files.length() {
+        let Some(file) = files.get(index) else {
+            continue;
+        };
+        let buffer = JsFuture::from(file.array_buffer()).await?;
+        let bytes = js_sys::Uint8Array::new(&buffer).to_vec();
+        read.push((file.webkit_relative_path(), bytes));
+    }
+    Ok(read)
+}
+
+/// The UFO in a picked folder (the folder itself or the outermost UFO in
+/// it): its name and its files by path inside the UFO
+fn ufo_files(files: PickedFiles) -> Option<(String, PickedFiles)> {
+    let root = files
+        .iter()
+        .filter_map(|(path, _)| path.strip_suffix("metainfo.plist"))
+        .filter(|root| root.is_empty() || root.ends_with('/'))
+        .min_by_key(|root| root.len())?
+        .to_string();
+    let name = root
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let files = files
+        .into_iter()
+        .filter_map(|(path, bytes)| Some((path.strip_prefix(&root)?.to_string(), bytes)))
+        .collect();
+    Some((name, files))
+}